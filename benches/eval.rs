@@ -0,0 +1,45 @@
+//! Evaluation workloads,  timed by running the interpreter on each with -e
+//!
+//! Each run includes starting the interpreter up;  startup is timed on its own,  as
+//! (+ 1 2),  to be taken off the rest.  Run with cargo bench
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::process::Command;
+
+// What's timed;  each builds,  calls and binds enough for it to outweigh startup
+const WORKLOADS: &[(&str, &str)] = &[
+    ("startup", "(+ 1 2)"),
+    ("vector", "(count (reduce conj [] (range 100000)))"),
+    (
+        "map",
+        "(count (reduce (fn [m n] (assoc m (mod n 1000) n)) {} (range 100000)))",
+    ),
+    (
+        "fn-let",
+        "(loop [n 0 acc 0] (if (< n 100000) (let [x (* n 2) y (inc x)] (recur (inc n) (+ acc y))) acc))",
+    ),
+    (
+        "seq",
+        "(count (filter even? (map inc (range 100000))))",
+    ),
+];
+
+fn eval(expr: &str) {
+    let status = Command::new(env!("CARGO_BIN_EXE_rust_clojure"))
+        .args(["-e", expr])
+        .output()
+        .expect("the interpreter runs")
+        .status;
+    assert!(status.success(), "{} failed", expr);
+}
+
+fn workloads(c: &mut Criterion) {
+    let mut group = c.benchmark_group("eval");
+    group.sample_size(10);
+    for (name, expr) in WORKLOADS {
+        group.bench_function(*name, |b| b.iter(|| eval(expr)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, workloads);
+criterion_main!(benches);
@@ -0,0 +1,159 @@
+;   Copyright (c) Rich Hickey. All rights reserved.
+;   The use and distribution terms for this software are covered by the
+;   Eclipse Public License 1.0 (http://opensource.org/licenses/eclipse-1.0.php)
+;   which can be found in the file epl-v10.html at the root of this distribution.
+;   By using this software in any fashion, you are agreeing to be bound by
+;   the terms of this license.
+;   You must not remove this notice, or any other, from this software.
+
+;; Ported from Clojure's test/clojure/test_clojure/control.clj;  a subset
+
+(ns clojure.test-clojure.control
+  (:use clojure.test))
+
+(defn maintains-identity [f]
+  (are [x] (= (f x) x)
+      nil
+      false true
+      0 42
+      0.0 3.14
+      \c
+      "" "abc"
+      'sym
+      :kw
+      () '(1 2)
+      [] [1 2]
+      {} {:a 1 :b 2}
+      #{} #{1 2}))
+
+(deftest test-do
+  (are [x y] (= x y)
+      (do) nil
+      (do nil) nil
+      (do (do (do))) nil
+      (do 1 2 3) 3
+      (do (do 1 2) 3) 3)
+  (maintains-identity (fn [_] (do _))))
+
+(deftest test-loop
+  (are [x y] (= x y)
+       1 (loop []
+           1)
+       3 (loop [a 1]
+           (if (< a 3)
+             (recur (inc a))
+             a))
+       [2 4 6] (loop [a []
+                      b [1 2 3]]
+                 (if (seq b)
+                   (recur (conj a (* 2 (first b)))
+                          (rest b))
+                   a))
+       [6 4 2] (loop [a ()
+                      b [1 2 3]]
+                 (if (seq b)
+                   (recur (conj a (* 2 (first b)))
+                          (rest b))
+                   a))))
+
+(deftest test-when
+  (are [x y] (= x y)
+       1 (when true 1)
+       nil (when true)
+       nil (when false)
+       nil (when false (throw (ex-info "Never" {})))))
+
+(deftest test-if-let
+  (are [x y] (= x y)
+       1 (if-let [a 1]
+           a)
+       nil (if-let [a false]
+             (throw (ex-info "Never" {})))
+       1 (if-let [a false]
+           a 1)
+       1 (if-let [a false]
+           (throw (ex-info "Never" {}))
+           1)))
+
+(deftest test-when-let
+  (are [x y] (= x y)
+       1 (when-let [a 1]
+           a)
+       nil (when-let [a false]
+             (throw (ex-info "Never" {})))))
+
+(deftest test-if-some
+  (are [x y] (= x y)
+       1 (if-some [a 1] a)
+       false (if-some [a false] a)
+       nil (if-some [a nil] (throw (ex-info "Never" {})))
+       1 (if-some [a nil] (throw (ex-info "Never" {})) 1)))
+
+(deftest test-when-some
+  (are [x y] (= x y)
+       1 (when-some [a 1] a)
+       false (when-some [a false] a)
+       nil (when-some [a nil] (throw (ex-info "Never" {})))))
+
+(deftest test-cond
+  (are [x y] (= x y)
+      (cond) nil
+
+      (cond nil true) nil
+      (cond false true) nil
+
+      (cond true 1 true (throw (ex-info "Never" {}))) 1
+      (cond nil 1 false 2 true 3 true 4) 3
+      (cond nil 1 false 2 true 3 true (throw (ex-info "Never" {}))) 3)
+
+  ; false
+  (are [x]  (= (cond x :a true :b) :b)
+      nil false)
+
+  ; true
+  (are [x]  (= (cond x :a true :b) :a)
+      true
+      0 42
+      0.0 3.14
+      \c
+      "" "abc"
+      'sym
+      :kw
+      () '(1 2)
+      [] [1 2]
+      {} {:a 1 :b 2}
+      #{} #{1 2})
+
+  ; evaluation
+  (are [x y] (= x y)
+      (cond (> 3 2) (+ 1 2) true :result true (throw (ex-info "Never" {}))) 3
+      (cond (< 3 2) (+ 1 2) true :result true (throw (ex-info "Never" {}))) :result)
+
+  ; identity (= (cond true x) x)
+  (maintains-identity (fn [_] (cond true _))))
+
+(deftest test-dotimes
+  ;; dotimes always returns nil
+  (is (= nil (dotimes [n 1] n)))
+  ;; test using an atom since dotimes is for modifying
+  ;; test executes n times
+  (is (= 3
+         (let [a (atom 0)]
+           (do (dotimes [n 3]
+                 (swap! a inc))
+               @a))))
+  (is (= [0 1 2]
+         (let [a (atom [])]
+           (do (dotimes [n 3]
+                 (swap! a conj n))
+               @a)))))
+
+(deftest test-while
+  (is (= nil (while nil (throw (ex-info "never" {})))))
+  (is (= [0 nil]
+         ;; a will dec to 0
+         ;; while always returns nil
+         (let [a (atom 3)
+               w (while (pos? @a)
+                   (swap! a dec))]
+           [@a w]))))
@@ -0,0 +1,157 @@
+;   Copyright (c) Rich Hickey. All rights reserved.
+;   The use and distribution terms for this software are covered by the
+;   Eclipse Public License 1.0 (http://opensource.org/licenses/eclipse-1.0.php)
+;   which can be found in the file epl-v10.html at the root of this distribution.
+;   By using this software in any fashion, you are agreeing to be bound by
+;   the terms of this license.
+;   You must not remove this notice, or any other, from this software.
+
+;; Ported from Clojure's test/clojure/test_clojure/logic.clj;  a subset
+
+(ns clojure.test-clojure.logic
+  (:use clojure.test))
+
+(deftest test-if
+  ; true/false/nil
+  (are [x y] (= x y)
+      (if true :t) :t
+      (if true :t :f) :t
+      (if true :t (throw (ex-info "Never" {}))) :t
+
+      (if false :t) nil
+      (if false :t :f) :f
+      (if false (throw (ex-info "Never" {})) :f) :f
+
+      (if nil :t) nil
+      (if nil :t :f) :f
+      (if nil (throw (ex-info "Never" {})) :f) :f)
+
+  ; zero/empty is true
+  (are [x] (= (if x :t :f) :t)
+      0
+      0.0
+      \space
+      ""
+      ()
+      []
+      {}
+      #{}
+      (lazy-seq nil))
+
+  ; anything except nil/false is true
+  (are [x] (= (if x :t :f) :t)
+      2
+      -2
+      1.2
+      \a
+      "abc"
+      'abc
+      :kw
+      '(1 2)
+      [1 2]
+      {:a 1 :b 2}
+      #{1 2}))
+
+(deftest test-nil-punning
+  (are [x y] (= (if x :no :yes) y)
+    (first []) :yes
+    (rest [1]) :no
+
+    (seq nil) :yes
+    (seq []) :yes
+
+    (concat) :no
+    (concat []) :no
+
+    (map inc []) :no
+    (filter even? []) :no
+    (remove even? []) :no
+
+    (reverse nil) :no
+    (sort nil) :no))
+
+(deftest test-and
+  (are [x y] (= x y)
+      (and) true
+      (and true) true
+      (and nil) nil
+      (and false) false
+
+      (and true nil) nil
+      (and true false) false
+
+      (and 1 true :kw 'abc "abc") "abc"
+
+      (and 1 true :kw nil 'abc "abc") nil
+      (and 1 true :kw nil (throw (ex-info "Never" {})) 'abc "abc") nil
+
+      (and 1 true :kw 'abc "abc" false) false
+      (and 1 true :kw 'abc "abc" false (throw (ex-info "Never" {}))) false))
+
+(deftest test-or
+  (are [x y] (= x y)
+      (or) nil
+      (or true) true
+      (or nil) nil
+      (or false) false
+
+      (or nil false true) true
+      (or nil false 1 2) 1
+      (or nil false "abc" :kw) "abc"
+
+      (or false nil) nil
+      (or nil false) false
+      (or nil nil nil false) false
+
+      (or nil true false) true
+      (or nil true (throw (ex-info "Never" {})) false) true
+      (or nil false "abc" (throw (ex-info "Never" {}))) "abc"))
+
+(deftest test-not
+  (are [x] (= (not x) true)
+      nil
+      false)
+  (are [x] (= (not x) false)
+      true
+
+      ; numbers
+      0
+      0.0
+      42
+      1.2
+      -1
+      -2.5
+
+      ; characters
+      \space
+      \tab
+      \a
+
+      ; strings
+      ""
+      "abc"
+
+      ; keywords
+      :kw
+
+      ; collections/sequences
+      ()
+      '(1 2)
+      []
+      [1 2]
+      {}
+      {:a 1 :b 2}
+      #{}
+      #{1 2}
+
+      ; functions
+      inc
+      println))
+
+(deftest test-some?
+  (are [expected x] (= expected (some? x))
+       false nil
+       true false
+       true 0
+       true "abc"
+       true []))
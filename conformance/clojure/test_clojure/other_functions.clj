@@ -0,0 +1,55 @@
+;   Copyright (c) Rich Hickey. All rights reserved.
+;   The use and distribution terms for this software are covered by the
+;   Eclipse Public License 1.0 (http://opensource.org/licenses/eclipse-1.0.php)
+;   which can be found in the file epl-v10.html at the root of this distribution.
+;   By using this software in any fashion, you are agreeing to be bound by
+;   the terms of this license.
+;   You must not remove this notice, or any other, from this software.
+
+;; Ported from Clojure's test/clojure/test_clojure/other_functions.clj;  a subset
+
+(ns clojure.test-clojure.other-functions
+  (:use clojure.test))
+
+(deftest test-comp
+  (let [c0 (comp)]
+    (are [x] (= (c0 x) x)
+         nil
+         42
+         [1 2 3]
+         #{}
+         :foo))
+  (are [x y] (= x y)
+       ((comp inc inc) 1) 3
+       ((comp str inc) 1) "2"
+       ((comp - *) 2 3) -6))
+
+(deftest test-complement
+  (let [not-contains? (complement contains?)]
+    (are [x y] (= x y)
+         true (not-contains? [2 3 4] 5)
+         false (not-contains? [2 3 4] 2)))
+  (are [x y] (= x y)
+       ((complement even?) 2) false
+       ((complement even?) 3) true))
+
+(deftest test-juxt
+  ;; juxt for colls
+  (let [m0 {:a 1 :b 2}
+        a [1 2]]
+    (are [x y] (= x y)
+         [1 2] ((juxt :a :b) m0)
+         [2 1] ((juxt peek first) a)))
+  ;; juxt for fns
+  (let [a1 (fn [a] (+ 2 a))
+        b1 (fn [b] (* 2 b))]
+    (is (= [5 6] ((juxt a1 b1) 3)))))
+
+(deftest test-partial
+  (let [p0 (partial inc)
+        p1 (partial + 20)
+        p2 (partial conj [1 2])]
+    (are [x y] (= x y)
+         41 (p0 40)
+         40 (p1 20)
+         [1 2 3] (p2 3))))
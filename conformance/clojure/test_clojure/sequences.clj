@@ -0,0 +1,225 @@
+;   Copyright (c) Rich Hickey. All rights reserved.
+;   The use and distribution terms for this software are covered by the
+;   Eclipse Public License 1.0 (http://opensource.org/licenses/eclipse-1.0.php)
+;   which can be found in the file epl-v10.html at the root of this distribution.
+;   By using this software in any fashion, you are agreeing to be bound by
+;   the terms of this license.
+;   You must not remove this notice, or any other, from this software.
+
+;; Ported from Clojure's test/clojure/test_clojure/sequences.clj;  a subset
+
+(ns clojure.test-clojure.sequences
+  (:use clojure.test))
+
+(deftest test-equality
+  ; lazy sequences
+  (are [x y] (= x y)
+      (map inc [1 2 3]) '(2 3 4)
+      (filter even? [1 2 3 4]) [2 4]
+      (range 3) [0 1 2]))
+
+(deftest test-cons
+  (are [x y] (= x y)
+      (cons 1 nil) '(1)
+      (cons nil nil) '(nil)
+
+      (cons \a nil) '(\a)
+      (cons \a "") '(\a)
+      (cons \a "bc") '(\a \b \c)
+
+      (cons 1 ()) '(1)
+      (cons 1 '(2 3)) '(1 2 3)
+
+      (cons 1 []) [1]
+      (cons 1 [2 3]) [1 2 3]
+
+      (cons 1 #{}) '(1)))
+
+(deftest test-first
+  (are [x y] (= x y)
+    (first nil) nil
+
+    ; string
+    (first "") nil
+    (first "a") \a
+    (first "abc") \a
+
+    ; list
+    (first ()) nil
+    (first '(1)) 1
+    (first '(1 2 3)) 1
+
+    (first '(nil)) nil
+    (first '(1 nil)) 1
+    (first '(nil 2)) nil
+    (first '(())) ()
+    (first '(() nil)) ()
+    (first '(() 2 nil)) ()
+
+    ; vector
+    (first []) nil
+    (first [1]) 1
+    (first [1 2 3]) 1
+
+    (first [nil]) nil
+    (first [1 nil]) 1
+    (first [nil 2]) nil
+    (first [[]]) []
+    (first [[] nil]) []
+    (first [[] 2 nil]) []
+
+    ; set
+    (first #{}) nil
+    (first #{1}) 1))
+
+(deftest test-rest
+  (are [x y] (= x y)
+    (rest nil) ()
+
+    (rest "") ()
+    (rest "a") ()
+    (rest "abc") '(\b \c)
+
+    (rest ()) ()
+    (rest '(1)) ()
+    (rest '(1 2 3)) '(2 3)
+
+    (rest '(nil)) ()
+    (rest '(1 nil)) '(nil)
+    (rest '(nil 2)) '(2)
+    (rest '(())) ()
+    (rest '(() nil)) '(nil)
+    (rest '(() 2 nil)) '(2 nil)
+
+    (rest []) ()
+    (rest [1]) ()
+    (rest [1 2 3]) [2 3]
+
+    (rest [nil]) ()
+    (rest [1 nil]) [nil]
+    (rest [nil 2]) [2]
+    (rest [[]]) ()
+    (rest [[] nil]) [nil]
+    (rest [[] 2 nil]) [2 nil]))
+
+(deftest test-take
+  (are [x y] (= x y)
+    (take 1 [1 2 3 4 5]) '(1)
+    (take 3 [1 2 3 4 5]) '(1 2 3)
+    (take 5 [1 2 3 4 5]) '(1 2 3 4 5)
+    (take 9 [1 2 3 4 5]) '(1 2 3 4 5)
+
+    (take 0 [1 2 3 4 5]) ()
+    (take -1 [1 2 3 4 5]) ()
+    (take -2 [1 2 3 4 5]) ()))
+
+(deftest test-drop
+  (are [x y] (= x y)
+    (drop 1 [1 2 3 4 5]) '(2 3 4 5)
+    (drop 3 [1 2 3 4 5]) '(4 5)
+    (drop 5 [1 2 3 4 5]) ()
+    (drop 9 [1 2 3 4 5]) ()
+
+    (drop 0 [1 2 3 4 5]) '(1 2 3 4 5)
+    (drop -1 [1 2 3 4 5]) '(1 2 3 4 5)
+    (drop -2 [1 2 3 4 5]) '(1 2 3 4 5)))
+
+(deftest test-take-while
+  (are [x y] (= x y)
+    (take-while pos? []) ()
+    (take-while pos? [1 2 3 4]) '(1 2 3 4)
+    (take-while pos? [1 2 3 -1]) '(1 2 3)
+    (take-while pos? [1 -1 2 3]) '(1)
+    (take-while pos? [-1 1 2 3]) ()
+    (take-while pos? [-1 -2 -3]) ()))
+
+(deftest test-drop-while
+  (are [x y] (= x y)
+    (drop-while pos? []) ()
+    (drop-while pos? [1 2 3 4]) ()
+    (drop-while pos? [1 2 3 -1]) '(-1)
+    (drop-while pos? [1 -1 2 3]) '(-1 2 3)
+    (drop-while pos? [-1 1 2 3]) '(-1 1 2 3)
+    (drop-while pos? [-1 -2 -3]) '(-1 -2 -3)))
+
+(deftest test-repeat
+  (are [x y] (= x y)
+    (take 0 (repeat 7)) ()
+    (take 1 (repeat 7)) '(7)
+    (take 2 (repeat 7)) '(7 7)
+    (take 5 (repeat 7)) '(7 7 7 7 7))
+
+  (are [x y] (= x y)
+    (repeat 0 7) ()
+    (repeat 1 7) '(7)
+    (repeat 2 7) '(7 7)
+    (repeat 5 7) '(7 7 7 7 7)
+
+    (repeat -1 7) ()))
+
+(deftest test-range
+  (are [x y] (= x y)
+      (take 100 (range)) (range 100)
+
+      (range 0) ()
+      (range 1) '(0)
+      (range 5) '(0 1 2 3 4)
+
+      (range -1) ()
+      (range -3) ()
+
+      (range 0 3) '(0 1 2)
+      (range 0 1) '(0)
+      (range 0 0) ()
+      (range 0 -3) ()
+
+      (range 3 6) '(3 4 5)
+      (range 3 4) '(3)
+      (range 3 3) ()
+      (range 3 1) ()
+
+      (range 3 9 1) '(3 4 5 6 7 8)
+      (range 3 9 2) '(3 5 7)
+      (range 3 9 3) '(3 6)
+      (range 3 9 10) '(3)
+      (range 3 9 -1) ()
+
+      (range 10 9 -1) '(10)
+      (range 10 8 -1) '(10 9)))
+
+(deftest test-reverse
+  (are [x y] (= x y)
+      (reverse nil) ()
+      (reverse []) ()
+      (reverse [1]) '(1)
+      (reverse [1 2 3]) '(3 2 1)))
+
+(deftest test-partition
+  (are [x y] (= x y)
+    (partition 2 [1 2 3]) '((1 2))
+    (partition 2 [1 2 3 4]) '((1 2) (3 4))
+    (partition 2 []) ()
+
+    (partition 2 3 [1 2 3 4 5 6 7]) '((1 2) (4 5))
+    (partition 2 3 [1 2 3 4 5 6 7 8]) '((1 2) (4 5) (7 8))
+    (partition 2 3 []) ()
+
+    (partition 1 []) ()
+    (partition 1 [1 2 3]) '((1) (2) (3))
+
+    (partition 5 [1 2 3]) ()))
+
+(deftest test-some
+  ;; always nil for nil or empty coll/seq
+  (are [x] (= (some pos? x) nil)
+       nil
+       () [] {} #{})
+
+  (are [x y] (= x y)
+       true (some pos? [1])
+       true (some pos? [1 2])
+
+       nil (some pos? [-1])
+       nil (some pos? [-1 -2])
+       true (some pos? [-1 2])
+       true (some pos? [1 -2])))
@@ -0,0 +1,112 @@
+;   Copyright (c) Rich Hickey. All rights reserved.
+;   The use and distribution terms for this software are covered by the
+;   Eclipse Public License 1.0 (http://opensource.org/licenses/eclipse-1.0.php)
+;   which can be found in the file epl-v10.html at the root of this distribution.
+;   By using this software in any fashion, you are agreeing to be bound by
+;   the terms of this license.
+;   You must not remove this notice, or any other, from this software.
+
+;; Ported from Clojure's test/clojure/test_clojure/string.clj;  a subset
+
+(ns clojure.test-clojure.string
+  (:require [clojure.string :as s])
+  (:use clojure.test))
+
+(deftest t-split
+  (is (= ["a" "b"] (s/split "a-b" #"-")))
+  (is (= ["a" "b-c"] (s/split "a-b-c" #"-" 2)))
+  (is (vector? (s/split "abc" #"-"))))
+
+(deftest t-reverse
+  (is (= "tab" (s/reverse "bat"))))
+
+(deftest t-replace
+  (is (= "faabar" (s/replace "foobar" \o \a)))
+  (is (= "foobar" (s/replace "foobar" \z \a)))
+  (is (= "barbarbar" (s/replace "foobarfoo" "foo" "bar")))
+  (is (= "foobarfoo" (s/replace "foobarfoo" "baz" "bar")))
+  (is (= "f$$d" (s/replace "food" "o" "$")))
+  (is (= "barbarbar" (s/replace "foobarfoo" #"foo" "bar")))
+  (is (= "FOObarFOO" (s/replace "foobarfoo" #"foo" s/upper-case))))
+
+(deftest t-replace-first
+  (is (= "barbarfoo" (s/replace-first "foobarfoo" "foo" "bar")))
+  (is (= "foobarfoo" (s/replace-first "foobarfoo" "baz" "bar")))
+  (is (= "barbarfoo" (s/replace-first "foobarfoo" #"foo" "bar")))
+  (is (= "foobarfoo" (s/replace-first "foobarfoo" #"baz" "bar"))))
+
+(deftest t-join
+  (are [x coll] (= x (s/join coll))
+       "" nil
+       "" []
+       "1" [1]
+       "12" [1 2])
+  (are [x sep coll] (= x (s/join sep coll))
+       "1,2,3" \, [1 2 3]
+       "" \, []
+       "1" \, [1]
+       "1 and-a 2 and-a 3" " and-a " [1 2 3]))
+
+(deftest t-trim-newline
+  (is (= "foo" (s/trim-newline "foo")))
+  (is (= "" (s/trim-newline ""))))
+
+(deftest t-capitalize
+  (is (= "Foobar" (s/capitalize "foobar")))
+  (is (= "Foobar" (s/capitalize "FOOBAR"))))
+
+(deftest t-triml
+  (is (= "foo " (s/triml " foo ")))
+  (is (= "" (s/triml "   "))))
+
+(deftest t-trimr
+  (is (= " foo" (s/trimr " foo ")))
+  (is (= "" (s/trimr "   "))))
+
+(deftest t-trim
+  (is (= "foo" (s/trim "  foo  "))))
+
+(deftest t-upper-case
+  (is (= "FOOBAR" (s/upper-case "Foobar"))))
+
+(deftest t-lower-case
+  (is (= "foobar" (s/lower-case "FooBar"))))
+
+(deftest t-escape
+  (is (= "&lt;foo&amp;bar&gt;"
+         (s/escape "<foo&bar>" {\& "&amp;" \< "&lt;" \> "&gt;"})))
+  (is (= "faabor"
+         (s/escape "foobar" {\a \o, \o \a}))))
+
+(deftest t-blank
+  (is (s/blank? nil))
+  (is (s/blank? ""))
+  (is (s/blank? " "))
+  (is (not (s/blank? "  foo  "))))
+
+(deftest t-index-of
+  (are [x y] (= x y)
+       2 (s/index-of "tacos" "c")
+       2 (s/index-of "tacos" \c)
+       1 (s/index-of "tacos" "ac")
+       3 (s/index-of "tacos" "o" 2)
+       nil (s/index-of "tacos" "z")
+       nil (s/index-of "tacos" "c" 3)))
+
+(deftest t-last-index-of
+  (are [x y] (= x y)
+       4 (s/last-index-of "banana" "n")
+       3 (s/last-index-of "banana" "an")
+       nil (s/last-index-of "banana" "z")))
+
+(deftest t-starts-with?
+  (is (s/starts-with? "clojure west" "clojure"))
+  (is (not (s/starts-with? "conj" "clojure"))))
+
+(deftest t-ends-with?
+  (is (s/ends-with? "Clojure West" "West"))
+  (is (not (s/ends-with? "Conj" "West"))))
+
+(deftest t-includes?
+  (is (s/includes? "Clojure Applied Book" "Applied"))
+  (is (not (s/includes? "Clojure Applied Book" "Living"))))
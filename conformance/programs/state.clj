@@ -0,0 +1,27 @@
+;; Atoms,  volatile state and the order side effects happen in;  checked with plain asserts,
+;; so any one failing stops the program
+
+(def counter (atom 0))
+(assert (= 1 (swap! counter inc)))
+(assert (= 11 (swap! counter + 10)))
+(assert (= 0 (reset! counter 0)))
+(assert (compare-and-set! counter 0 5))
+(assert (not (compare-and-set! counter 0 6)))
+(assert (= 5 @counter) "a failed compare-and-set! leaves the atom be")
+
+(def seen (atom []))
+(run! #(swap! seen conj %) [1 2 3])
+(doseq [x [4 5] :when (odd? x)] (swap! seen conj x))
+(dotimes [n 2] (swap! seen conj (* 10 n)))
+(assert (= [1 2 3 5 0 10] @seen))
+
+(def watched (atom []))
+(def a (atom 1))
+(add-watch a :log (fn [k r old new] (swap! watched conj [k old new])))
+(swap! a inc)
+(reset! a 10)
+(assert (= [[:log 1 2] [:log 2 10]] @watched))
+
+(assert (= 7 (reduce (fn [acc x] (if (> x 3) (reduced acc) (+ acc x))) 1 [1 2 3 4 5]))
+        "reduce stops at reduced")
+(assert (= {:a 1 :b 2} (persistent! (assoc! (assoc! (transient {}) :a 1) :b 2))))
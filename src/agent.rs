@@ -0,0 +1,221 @@
+//! (agent x), (send a f) and friends
+//!
+//! An agent holds a value that's changed by actions sent to it,  one action at a time and
+//! in the order they were sent.  In Clojure the actions run on a pool of worker threads;
+//! ours run on the thread that sent them,  as soon as it's done with whatever action it's
+//! in the middle of.  So sending from the top level runs the action right away,  while an action
+//! sending another only has it run once it's finished itself,  as in Clojure.  Either way
+//! it runs with the dynamic bindings in place when it was sent.
+//!
+//! An action that fails (or whose result the validator rejects) fails its agent;  the
+//! agent keeps the value it had,  holds on to its pending actions,  and refuses new ones
+//! until it's restarted with restart-agent
+use crate::atom;
+use crate::dynamic::{self, Frame};
+use crate::ifn::IFn;
+use crate::value::Value;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+// f,  its args after the state,  and the bindings conveyed to it
+type Action = (Arc<dyn IFn>, Vec<Arc<Value>>, Frame);
+
+#[derive(Debug)]
+pub struct Agent {
+    state: RwLock<Arc<Value>>,
+    error: RwLock<Option<Arc<Value>>>,
+    validator: Option<Arc<dyn IFn>>,
+    pending: RwLock<VecDeque<Action>>,
+}
+
+thread_local! {
+    // Agents with actions waiting to run,  in the order they're to get a turn
+    static READY: RefCell<VecDeque<Arc<Agent>>> = const { RefCell::new(VecDeque::new()) };
+    static RUNNING: Cell<bool> = const { Cell::new(false) };
+}
+
+impl Agent {
+    /// Fails if validator rejects val
+    pub fn new(val: Arc<Value>, validator: Option<Arc<dyn IFn>>) -> Result<Agent, Value> {
+        atom::validate(&validator, &val)?;
+        Ok(Agent {
+            state: RwLock::new(val),
+            error: RwLock::new(None),
+            validator,
+            pending: RwLock::new(VecDeque::new()),
+        })
+    }
+    pub fn deref(&self) -> Arc<Value> {
+        Arc::clone(&self.state.read().unwrap())
+    }
+    /// The condition that failed this agent,  if it has failed
+    pub fn error(&self) -> Option<Arc<Value>> {
+        self.error.read().unwrap().clone()
+    }
+    /// What sending to (or awaiting) a failed agent gives
+    pub fn failed() -> Value {
+        Value::Condition("Agent is failed, needs restart".into())
+    }
+}
+
+/// Queues (apply f state args) to run on agent
+pub fn send(agent: &Arc<Agent>, f: Arc<dyn IFn>, args: Vec<Arc<Value>>) -> Result<(), Value> {
+    if agent.error.read().unwrap().is_some() {
+        return Err(Agent::failed());
+    }
+    let first = {
+        let mut pending = agent.pending.write().unwrap();
+        pending.push_back((f, args, dynamic::conveyed()));
+        pending.len() == 1
+    };
+    // An agent already waiting for its turn will get to this action in time
+    if first {
+        READY.with(|ready| ready.borrow_mut().push_back(Arc::clone(agent)));
+    }
+    run_pending();
+    Ok(())
+}
+
+/// Whether we're in the middle of an action
+pub fn is_running() -> bool {
+    RUNNING.with(|running| running.get())
+}
+
+/// Runs every action sent so far,  and any they send in turn;  unless we're already in
+/// the middle of an action,  which will run the rest once it's done
+pub fn run_pending() {
+    if RUNNING.with(|running| running.replace(true)) {
+        return;
+    }
+    while let Some(agent) = READY.with(|ready| ready.borrow_mut().pop_front()) {
+        if run_next_action(&agent) {
+            READY.with(|ready| ready.borrow_mut().push_back(agent));
+        }
+    }
+    RUNNING.with(|running| running.set(false));
+}
+
+// Whether the agent has more actions to run after this one.  Decided as the action's taken
+// off pending,  so an agent another thread sends to is only ever in one READY
+fn run_next_action(agent: &Arc<Agent>) -> bool {
+    // Stays in pending while it runs,  so sends it makes don't put the agent in READY twice
+    let (f, args, bindings) = match agent.pending.read().unwrap().front() {
+        Some(action) => action.clone(),
+        None => return false,
+    };
+    let mut f_args = vec![agent.deref()];
+    f_args.extend(args);
+    let result = match dynamic::with_conveyed(bindings, || f.invoke(f_args)) {
+        Value::Condition(condition) => Err(Value::Condition(condition)),
+        new => {
+            let new = Arc::new(new);
+            atom::validate(&agent.validator, &new).map(|_| new)
+        }
+    };
+    let mut pending = agent.pending.write().unwrap();
+    pending.pop_front();
+    match result {
+        Ok(new) => {
+            *agent.state.write().unwrap() = new;
+            !pending.is_empty()
+        }
+        // The failed action is dropped,  the ones after it held until the restart
+        Err(condition) => {
+            *agent.error.write().unwrap() = Some(Arc::new(condition));
+            false
+        }
+    }
+}
+
+/// Clears a failed agent's error and sets its value to val;  then runs its held actions,
+/// unless clear_actions says to throw them away
+pub fn restart(agent: &Arc<Agent>, val: Arc<Value>, clear_actions: bool) -> Result<(), Value> {
+    if agent.error.read().unwrap().is_none() {
+        return Err(Value::Condition("Agent does not need a restart".into()));
+    }
+    atom::validate(&agent.validator, &val)?;
+    let mut pending = agent.pending.write().unwrap();
+    *agent.state.write().unwrap() = val;
+    *agent.error.write().unwrap() = None;
+    if clear_actions {
+        pending.clear();
+    } else if !pending.is_empty() {
+        drop(pending);
+        READY.with(|ready| ready.borrow_mut().push_back(Arc::clone(agent)));
+        run_pending();
+    }
+    Ok(())
+}
+
+impl fmt::Display for Agent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = match &*self.error.read().unwrap() {
+            Some(_) => ":failed",
+            None => ":ready",
+        };
+        write!(
+            f,
+            "#agent[{{:status {}, :val {}}}]",
+            status,
+            self.state.read().unwrap().to_string_explicit()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    #[test]
+    fn actions_run_in_the_order_sent() {
+        let result = Repl::default().eval_all(&[
+            "(def a (agent []))",
+            "(send a conj 1)",
+            "(send-off a conj 2)",
+            "(await a)",
+            "@a",
+        ]);
+        assert_eq!("[1 2]", result.to_string());
+    }
+
+    #[test]
+    fn an_action_finishes_before_what_it_sends() {
+        let result = Repl::default().eval_all(&[
+            "(def order (atom []))",
+            "(def a (agent 0))",
+            "(send a (fn [x] (send a (fn [y] (swap! order conj :sent) y)) (swap! order conj :sender) x))",
+            "@order",
+        ]);
+        assert_eq!("[:sender :sent]", result.to_string());
+    }
+
+    #[test]
+    fn a_failed_agent_holds_its_actions_until_restarted() {
+        let repl = Repl::default();
+        repl.eval_all(&[
+            "(def a (agent 1))",
+            "(def b (agent 1))",
+            "(send a (fn [x] (send b + \"oops\") (send b inc) x))",
+        ]);
+        assert!(matches!(
+            repl.eval_all(&["(agent-error b)"]),
+            Value::Exception(_)
+        ));
+        let result = repl.eval_all(&["(when-let [e (agent-error b)] (string? (ex-message e)))"]);
+        assert_eq!(Value::Boolean(true), result);
+        assert!(matches!(
+            repl.eval_all(&["(send b inc)"]),
+            Value::Condition(_)
+        ));
+        assert_eq!(Value::Nil, repl.eval_all(&["(agent-error a)"]));
+        let result = repl.eval_all(&["(restart-agent b 10)", "@b"]);
+        assert_eq!(Value::I32(11), result);
+        assert!(matches!(
+            repl.eval_all(&["(restart-agent b 10)"]),
+            Value::Condition(_)
+        ));
+    }
+}
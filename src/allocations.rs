@@ -0,0 +1,110 @@
+//! Counting the values evaluation makes,  by type;  for the REPL to say what an expression
+//! cost,  as it's what takes this interpreter's time more often than not
+//!
+//! What's counted is what comes out of each call of a fn,  each collection a literal
+//! evaluates to,  and each fn made;  each one put in an allocation of its own.  So it's
+//! approximate:  a call that gives back what it was given is counted,  and what a fn makes
+//! along the way but doesn't give back isn't.  Only what's made on the thread counting is
+//! counted
+use crate::type_tag::TypeTag;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+// How many threads are counting;  so no thread looks for counts of its own when none is
+static COUNTING: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static COUNTS: RefCell<Option<HashMap<TypeTag, u64>>> = const { RefCell::new(None) };
+}
+
+/// What evaluating something made,  and how long it took
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub elapsed: Duration,
+    pub made: HashMap<TypeTag, u64>,
+}
+impl Stats {
+    pub fn total(&self) -> u64 {
+        self.made.values().sum()
+    }
+}
+/// As ;; 3 values made (2 rust.std.i32, 1 clojure.lang.PersistentVector) in 0.052 ms;  most
+/// made first
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut made = self
+            .made
+            .iter()
+            .map(|(type_tag, count)| (*count, type_tag.to_string()))
+            .collect::<Vec<(u64, String)>>();
+        made.sort_by(|(a_count, a), (b_count, b)| b_count.cmp(a_count).then(a.cmp(b)));
+        let made = made
+            .iter()
+            .map(|(count, type_tag)| format!("{} {}", count, type_tag))
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            ";; {} value{} made ({}) in {:.3} ms",
+            self.total(),
+            if self.total() == 1 { "" } else { "s" },
+            made.join(", "),
+            self.elapsed.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+/// What f gives,  and what it made on this thread while it ran
+pub fn counting<T>(f: impl FnOnce() -> T) -> (T, Stats) {
+    COUNTING.fetch_add(1, Ordering::SeqCst);
+    let outer = COUNTS.with(|counts| counts.replace(Some(HashMap::new())));
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    let made = COUNTS
+        .with(|counts| counts.replace(outer))
+        .unwrap_or_default();
+    COUNTING.fetch_sub(1, Ordering::SeqCst);
+    // What's counted here is counted as made by whatever's counting around us too
+    COUNTS.with(|counts| {
+        if let Some(counts) = counts.borrow_mut().as_mut() {
+            for (type_tag, count) in &made {
+                *counts.entry(*type_tag).or_default() += count;
+            }
+        }
+    });
+    (result, Stats { elapsed, made })
+}
+
+/// Counts val as made,  if this thread's counting
+pub fn made(val: &Value) {
+    if COUNTING.load(Ordering::Relaxed) == 0 {
+        return;
+    }
+    COUNTS.with(|counts| {
+        if let Some(counts) = counts.borrow_mut().as_mut() {
+            *counts.entry(val.type_tag()).or_default() += 1;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocations;
+    use crate::repl::Repl;
+    use crate::type_tag::TypeTag;
+
+    #[test]
+    fn counts_what_calls_and_literals_make() {
+        let repl = Repl::default();
+        let (_, stats) = allocations::counting(|| repl.eval_all(&["[1 (+ 1 2) {:a (str 1)}]"]));
+        assert_eq!(Some(&1), stats.made.get(&TypeTag::I32));
+        assert_eq!(Some(&1), stats.made.get(&TypeTag::String));
+        assert_eq!(Some(&1), stats.made.get(&TypeTag::PersistentListMap));
+        assert_eq!(Some(&1), stats.made.get(&TypeTag::PersistentVector));
+        assert_eq!(4, stats.total());
+    }
+}
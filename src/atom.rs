@@ -0,0 +1,180 @@
+//! (atom x), (swap! a f), (reset! a x) and @a
+//!
+//! An atom is a mutable reference to a value.  Each change is checked by the atom's
+//! validator, if it has one,  before it's made;  and each change that's made is handed to
+//! the atom's watches, as (watch key atom old new)
+use crate::ifn::IFn;
+use crate::value::Value;
+use std::fmt;
+use std::mem;
+use std::sync::{Arc, RwLock};
+
+/// A watch's key, and the fn it calls
+type Watch = (Arc<Value>, Arc<dyn IFn>);
+
+#[derive(Debug)]
+pub struct Atom {
+    val: RwLock<Arc<Value>>,
+    validator: Option<Arc<dyn IFn>>,
+    watches: RwLock<Vec<Watch>>,
+}
+impl Atom {
+    /// Fails if validator rejects val
+    pub fn new(val: Arc<Value>, validator: Option<Arc<dyn IFn>>) -> Result<Atom, Value> {
+        let atom = Atom {
+            val: RwLock::new(Arc::clone(&val)),
+            validator,
+            watches: RwLock::new(vec![]),
+        };
+        atom.validate(&val)?;
+        Ok(atom)
+    }
+    pub fn deref(&self) -> Arc<Value> {
+        Arc::clone(&self.val.read().unwrap())
+    }
+    fn validate(&self, val: &Arc<Value>) -> Result<(), Value> {
+        validate(&self.validator, val)
+    }
+    /// Sets the atom to new if it's still exactly old,  the same value and not just an equal
+    /// one;  returns whether it was.  atom is this atom,  as the watches are given it
+    pub fn compare_and_set(
+        &self,
+        atom: &Arc<Value>,
+        old: &Arc<Value>,
+        new: Arc<Value>,
+    ) -> Result<bool, Value> {
+        if !Arc::ptr_eq(&self.val.read().unwrap(), old) {
+            return Ok(false);
+        }
+        self.validate(&new)?;
+        {
+            // The validator may have changed the atom itself,  as may another thread
+            let mut val = self.val.write().unwrap();
+            if !Arc::ptr_eq(&val, old) {
+                return Ok(false);
+            }
+            *val = Arc::clone(&new);
+        }
+        self.notify_watches(atom, old, &new)?;
+        Ok(true)
+    }
+    /// Sets the atom to new whatever it was;  returns what it was
+    pub fn reset(&self, atom: &Arc<Value>, new: Arc<Value>) -> Result<Arc<Value>, Value> {
+        self.validate(&new)?;
+        let old = mem::replace(&mut *self.val.write().unwrap(), Arc::clone(&new));
+        self.notify_watches(atom, &old, &new)?;
+        Ok(old)
+    }
+    /// Replaces any watch already under key
+    pub fn add_watch(&self, key: Arc<Value>, watch: Arc<dyn IFn>) {
+        let mut watches = self.watches.write().unwrap();
+        match watches.iter_mut().find(|(watch_key, _)| *watch_key == key) {
+            Some(entry) => entry.1 = watch,
+            None => watches.push((key, watch)),
+        }
+    }
+    pub fn remove_watch(&self, key: &Arc<Value>) {
+        self.watches
+            .write()
+            .unwrap()
+            .retain(|(watch_key, _)| watch_key != key);
+    }
+    // Called after the change is made;  so a watch that fails doesn't undo it,  though the
+    // failure is still passed on
+    fn notify_watches(
+        &self,
+        atom: &Arc<Value>,
+        old: &Arc<Value>,
+        new: &Arc<Value>,
+    ) -> Result<(), Value> {
+        // Copied out,  so a watch can add or remove watches
+        let watches = self.watches.read().unwrap().clone();
+        for (key, watch) in watches {
+            let args = vec![key, Arc::clone(atom), Arc::clone(old), Arc::clone(new)];
+            if let Value::Condition(condition) = watch.invoke(args) {
+                return Err(Value::Condition(condition));
+            }
+        }
+        Ok(())
+    }
+}
+/// Whether validator, if there is one, accepts val;  shared with refs
+pub(crate) fn validate(validator: &Option<Arc<dyn IFn>>, val: &Arc<Value>) -> Result<(), Value> {
+    let validator = match validator {
+        Some(validator) => validator,
+        None => return Ok(()),
+    };
+    match validator.invoke(vec![Arc::clone(val)]) {
+        Value::Condition(condition) => Err(Value::Condition(condition)),
+        valid if valid.is_truthy() => Ok(()),
+        _ => Err(Value::Condition("Invalid reference state".into())),
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#atom[{{:status :ready, :val {}}}]",
+            self.val.read().unwrap().to_string_explicit()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    fn eval_all(sources: &[&str]) -> Value {
+        Repl::default().eval_all(sources)
+    }
+
+    #[test]
+    fn swap_applies_f_to_the_current_value() {
+        let result = eval_all(&["(def a (atom 1))", "(swap! a + 10 100)", "@a"]);
+        assert_eq!(Value::I32(111), result);
+    }
+
+    #[test]
+    fn swap_goes_again_if_f_changed_the_atom() {
+        let result = eval_all(&[
+            "(def a (atom 0))",
+            "(def calls (atom 0))",
+            "(swap! a (fn [x] (if (= 1 (swap! calls inc)) (reset! a 10) nil) (+ x 1)))",
+            "[@a @calls]",
+        ]);
+        assert_eq!("[11 2]", result.to_string());
+    }
+
+    #[test]
+    fn a_rejected_value_leaves_the_atom_alone() {
+        let sources = [
+            "(def a (atom 1 :validator (fn [x] (< x 10))))",
+            "(reset! a 20)",
+        ];
+        assert!(matches!(eval_all(&sources), Value::Condition(_)));
+        let result = eval_all(&[sources[0], sources[1], "@a"]);
+        assert_eq!(Value::I32(1), result);
+        assert!(matches!(
+            eval_all(&["(atom 20 :validator (fn [x] (< x 10)))"]),
+            Value::Condition(_)
+        ));
+    }
+
+    #[test]
+    fn watches_see_every_change() {
+        let result = eval_all(&[
+            "(def a (atom 1))",
+            "(def log (atom []))",
+            "(add-watch a :log (fn [k r old new] (swap! log conj [k old new])))",
+            "(swap! a inc)",
+            "(compare-and-set! a 2 5)",
+            "(compare-and-set! a 2 6)",
+            "(remove-watch a :log)",
+            "(reset! a 0)",
+            "@log",
+        ]);
+        assert_eq!("[[:log 1 2] [:log 2 5]]", result.to_string());
+    }
+}
@@ -0,0 +1,247 @@
+//! Channels,  as made by clojure.core.async/chan;  queues that threads hand values over
+//!
+//! A channel holds as many values as its buffer does;  an unbuffered one takes a put only
+//! when someone's waiting to take it.  Once closed,  it takes no more puts,  but what it
+//! holds can still be taken;  after that,  takes give nil.
+//!
+//! Every wait,  on one channel or on any of several (alts!!),  is on one condition variable
+//! that's signalled whenever any channel changes;  so a thread can wait on several channels
+//! at once.  Woken,  it just tries again
+use crate::interrupt;
+use crate::value::Value;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+// How long a wait goes before looking again at whether it's been interrupted
+const INTERRUPT_CHECK: Duration = Duration::from_millis(50);
+
+// Counts changes to any channel;  a waiter that saw one count waits for another
+static GENERATION: Mutex<u64> = Mutex::new(0);
+static CHANGED: Condvar = Condvar::new();
+
+fn changed() {
+    *GENERATION.lock().unwrap() += 1;
+    CHANGED.notify_all();
+}
+
+#[derive(Debug, Default)]
+struct State {
+    buffer: VecDeque<Arc<Value>>,
+    closed: bool,
+    // How many are waiting to take;  what lets an unbuffered channel take a put
+    takers: usize,
+}
+
+#[derive(Debug)]
+pub struct Channel {
+    state: Mutex<State>,
+    capacity: usize,
+    // When a timeout channel closes by itself
+    closes_at: Option<Instant>,
+}
+impl Channel {
+    /// A channel buffering up to capacity values;  0 for an unbuffered one
+    pub fn new(capacity: usize) -> Channel {
+        Channel {
+            state: Mutex::new(State::default()),
+            capacity,
+            closes_at: None,
+        }
+    }
+    /// A channel that closes by itself after after
+    pub fn timeout(after: Duration) -> Channel {
+        Channel {
+            closes_at: Some(Instant::now() + after),
+            ..Channel::new(0)
+        }
+    }
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        changed();
+    }
+    fn is_closed(&self, state: &State) -> bool {
+        state.closed || self.closes_at.is_some_and(|at| Instant::now() >= at)
+    }
+    // Some(val) if there was one to take,  Some(Nil) if there never will be;  None if
+    // there's nothing yet.  A taker that was waiting stops as it takes
+    fn try_take(&self, waiting: bool) -> Option<Value> {
+        let mut state = self.state.lock().unwrap();
+        let taken = match state.buffer.pop_front() {
+            Some(val) => (*val).clone(),
+            None if self.is_closed(&state) => Value::Nil,
+            None => return None,
+        };
+        if waiting {
+            state.takers -= 1;
+        }
+        drop(state);
+        changed();
+        Some(taken)
+    }
+    // Some(whether val was put),  or None if there's no room for it yet
+    fn try_put(&self, val: &Arc<Value>) -> Option<bool> {
+        let mut state = self.state.lock().unwrap();
+        if self.is_closed(&state) {
+            return Some(false);
+        }
+        let room = match self.capacity {
+            0 => state.buffer.is_empty() && state.takers > 0,
+            capacity => state.buffer.len() < capacity,
+        };
+        if !room {
+            return None;
+        }
+        state.buffer.push_back(Arc::clone(val));
+        drop(state);
+        changed();
+        Some(true)
+    }
+    fn wait_to_take(&self, by: isize) {
+        let mut state = self.state.lock().unwrap();
+        state.takers = (state.takers as isize + by) as usize;
+        drop(state);
+        changed();
+    }
+}
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        write!(
+            f,
+            "#channel[{{:count {}, :closed {}}}]",
+            state.buffer.len(),
+            self.is_closed(&state)
+        )
+    }
+}
+
+/// Something alts!! is asked to do with a channel
+pub enum Op {
+    Take(Arc<Channel>),
+    Put(Arc<Channel>, Arc<Value>),
+}
+impl Op {
+    fn channel(&self) -> &Channel {
+        match self {
+            Op::Take(channel) | Op::Put(channel, _) => channel,
+        }
+    }
+}
+
+/// Does the first of ops that can be done,  waiting till one can if wait;  which one it
+/// did,  and what it gave:  the value taken (nil once the channel's closed),  or whether the
+/// value was put.  They're tried in order if priority,  otherwise starting at random;  so
+/// no one channel is always taken from first.  A condition if the wait was interrupted
+pub fn alts(ops: &[Op], priority: bool, wait: bool) -> Result<Option<(usize, Value)>, Value> {
+    let start = match priority || ops.is_empty() {
+        true => 0,
+        false => rand::random::<usize>() % ops.len(),
+    };
+    let takes = || {
+        ops.iter().filter_map(|op| match op {
+            Op::Take(channel) => Some(channel),
+            Op::Put(..) => None,
+        })
+    };
+    let mut waiting = false;
+    let done = loop {
+        let generation = *GENERATION.lock().unwrap();
+        let done = (0..ops.len())
+            .map(|i| (start + i) % ops.len())
+            .find_map(|i| {
+                let done = match &ops[i] {
+                    Op::Take(channel) => channel.try_take(waiting),
+                    Op::Put(channel, val) => channel.try_put(val).map(Value::Boolean),
+                };
+                done.map(|done| (i, done))
+            });
+        if done.is_some() || !wait {
+            break Ok(done);
+        }
+        // Waiting to take is what lets an unbuffered channel take a put;  so try again once
+        // we are
+        if !waiting {
+            takes().for_each(|channel| channel.wait_to_take(1));
+            waiting = true;
+            continue;
+        }
+        if interrupt::is_interrupted() {
+            break Err(Value::Condition(interrupt::INTERRUPTED_MSG.into()));
+        }
+        let now = Instant::now();
+        let wait = ops
+            .iter()
+            .filter_map(|op| op.channel().closes_at)
+            .map(|at| at.saturating_duration_since(now))
+            .fold(INTERRUPT_CHECK, Duration::min);
+        let current = GENERATION.lock().unwrap();
+        if *current == generation {
+            let _ = CHANGED.wait_timeout(current, wait).unwrap();
+        }
+    };
+    if waiting {
+        // The take that was done has stopped waiting already
+        for (i, op) in ops.iter().enumerate() {
+            let taken = matches!(&done, Ok(Some((done, _))) if *done == i);
+            if let (Op::Take(channel), false) = (op, taken) {
+                channel.wait_to_take(-1);
+            }
+        }
+    }
+    done
+}
+
+/// Takes from channel,  waiting till there's something to take or it's closed
+pub fn take(channel: &Arc<Channel>) -> Value {
+    match alts(&[Op::Take(Arc::clone(channel))], true, true) {
+        Ok(Some((_, taken))) => taken,
+        Ok(None) => Value::Nil,
+        Err(condition) => condition,
+    }
+}
+
+/// Puts val on channel,  waiting till there's room;  whether it could be,  as it can't once
+/// the channel's closed
+pub fn put(channel: &Arc<Channel>, val: Arc<Value>) -> Value {
+    match alts(&[Op::Put(Arc::clone(channel), val)], true, true) {
+        Ok(Some((_, put))) => put,
+        Ok(None) => Value::Boolean(false),
+        Err(condition) => condition,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::channel::{self, Channel};
+    use crate::value::Value;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn an_unbuffered_put_waits_for_a_taker() {
+        let channel = Arc::new(Channel::new(0));
+        let putter = Arc::clone(&channel);
+        let put = thread::spawn(move || channel::put(&putter, Arc::new(Value::I32(1))));
+        thread::sleep(Duration::from_millis(20));
+        assert!(!put.is_finished());
+        assert_eq!(Value::I32(1), channel::take(&channel));
+        assert_eq!(Value::Boolean(true), put.join().unwrap());
+        channel.close();
+        assert_eq!(Value::Nil, channel::take(&channel));
+        assert_eq!(
+            Value::Boolean(false),
+            channel::put(&channel, Arc::new(Value::I32(2)))
+        );
+    }
+
+    #[test]
+    fn a_timeout_closes_by_itself() {
+        let started = Instant::now();
+        let timeout = Arc::new(Channel::timeout(Duration::from_millis(30)));
+        assert_eq!(Value::Nil, channel::take(&timeout));
+        assert!(started.elapsed() >= Duration::from_millis(30));
+    }
+}
@@ -0,0 +1,644 @@
+//! The command line;  `rust_clojure COMMAND [FLAGS]`,  where each command has flags of
+//! its own
+use crate::clojure_test::Selector;
+use crate::nrepl;
+use crate::nrepl::tls::TlsFiles;
+use crate::nrepl::Auth;
+use crate::socket_repl;
+use std::fs;
+use std::io;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "\
+Usage: rust_clojure [COMMAND]
+       rust_clojure FILE.clj [ARG..]
+       rust_clojure (-e EXPR | -m NS) [ARG..]
+
+Commands:
+  repl [--socket-repl PORT | --prepl PORT]   Start a REPL (the default),  or serve one on PORT
+  nrepl [--port PORT] [--access-log FILE]    Start an nREPL server,  logging each request to FILE
+  connect [[HOST:]PORT]                      Evaluate what's typed on the nREPL server at PORT
+  run FILE [ARG..]                           Run FILE as a script
+  -e, --eval EXPR [ARG..]                    Evaluate EXPR,  printing what each form gives but nil
+  -m, --main NS [ARG..]                      Require the namespace NS and call its -main with ARGs
+  replay FILE                                Evaluate each form recorded in the transcript FILE again
+  fmt [--check] PATH..                       Re-indent the .clj files at each PATH
+  lint PATH..                                Report likely mistakes in the .clj files at each PATH
+  test [DIR]                                 Run the *_test.clj files under DIR (./test by default)
+  conformance [DIR]                          Run every .clj program under DIR (./conformance by
+                                             default),  reporting which pass
+  help                                       Print this message
+
+A script,  -e or -m is given the ARGs after it as *command-line-args*,  flags or not;  and
+FILE.clj alone is run as by run
+
+repl and nrepl also take --record FILE,  to add every form evaluated and what it printed
+to the transcript FILE
+
+nrepl also takes --tls-cert FILE and --tls-key FILE,  the PEM certificate chain and key to
+accept only TLS connections with;  and with them --tls-client-ca FILE,  to accept only
+clients with a certificate signed by one in FILE
+
+nrepl also takes --auth-token TOKEN,  to answer only describe until a client has sent TOKEN
+as the auth-token of a request;  or --auth,  to make one up and write it to
+.nrepl-auth-token
+
+test also takes --namespaces REGEX,  to run only the clojure.test tests of the namespaces
+REGEX matches;  and --include TAG and --exclude TAG,  each as many times as wanted,  to run
+only those tagged (as by (deftest ^:integration t ..)) with one of the included tags,  and
+none of the excluded ones
+
+nrepl,  and repl with --socket-repl or --prepl,  also take --host HOST,  the address to
+listen on;  127.0.0.1,  this machine only,  by default.  nrepl only listens where others can
+reach it with an auth token or --tls-client-ca,  and the socket REPL,  which has neither,
+never does
+
+connect also takes --auth-token TOKEN,  to send with each request;  without it,  what's
+in .nrepl-auth-token,  if anything.  Without a PORT,  it connects to the one in .nrepl-port;
+and it takes nrepl://HOST:PORT,  as nrepl prints it
+
+Every command takes --path DIRS,  the directories (separated as in PATH) load and require
+look for files in;  my.app.core in my/app/core.clj under one of them.  Without it,  they're
+those of CLOJURE_RS_PATH,  or src and .
+
+Every command also takes --profile,  to time each var's fn as it's called and print how
+long they took to stderr on exit;  as (clojure.profile/start!) and (clojure.profile/report)
+would
+
+Set CLOJURE_RS_PRINT_SORTED (to anything but 0 or false) to have every command print sets
+and maps sorted,  as binding *print-sorted* true does;  so golden files of what's printed
+stay the same
+
+Set CLOJURE_RS_COMPILE (likewise) to have each fn compiled to bytecode the first time it's
+called,  rather than its forms evaluated over again each time
+
+Set CLOJURE_RS_EVAL_TIMEOUT to a number of milliseconds to have each form the REPL,  a
+script,  -e or -m evaluates interrupted once it's taken that long;  as Ctrl-C interrupts
+it,  with any finally blocks still run
+
+Set CLOJURE_RS_MAX_READ_DEPTH and CLOJURE_RS_MAX_READ_ELEMENTS to limit how deeply forms
+read may nest (256 by default),  and how many forms one may be made of (1000000);  past
+either,  the form is read as a condition saying so";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// With record,  the transcript to record the session to
+    Repl {
+        record: Option<String>,
+    },
+    /// The mode,  and the host and port to listen on
+    SocketRepl(socket_repl::Mode, String, u16),
+    /// With access_log,  the file to log each request to;  with tls,  the files to set up
+    /// TLS with;  and with auth,  what clients must authenticate with
+    Nrepl {
+        host: String,
+        port: u16,
+        record: Option<String>,
+        access_log: Option<String>,
+        tls: Option<TlsFiles>,
+        auth: Option<Auth>,
+    },
+    /// With addr,  the server to connect to;  with auth_token,  what to authenticate with
+    Connect {
+        addr: Option<String>,
+        auth_token: Option<String>,
+    },
+    /// A script,  -e expression or -m namespace;  with the arguments after it
+    Run(String, Vec<String>),
+    Eval(String, Vec<String>),
+    Main(String, Vec<String>),
+    Replay(String),
+    /// With check,  only reports the files that need formatting
+    Fmt {
+        paths: Vec<String>,
+        check: bool,
+    },
+    Lint(Vec<String>),
+    /// With selector,  which of the clojure.test tests to run
+    Test {
+        dir: String,
+        selector: Selector,
+    },
+    /// The directory of the conformance corpus
+    Conformance(String),
+    Help,
+}
+
+/// Parses the arguments following the program's name
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let (command, flags) = match args.split_first() {
+        Some((command, flags)) => (command.as_str(), flags),
+        None => return Ok(Command::Repl { record: None }),
+    };
+    // What follows is the script's,  flags or not
+    match (command, flags) {
+        ("run", [file, args @ ..]) => return Ok(Command::Run(file.clone(), args.to_vec())),
+        (file, args) if file.ends_with(".clj") => {
+            return Ok(Command::Run(file.to_string(), args.to_vec()))
+        }
+        ("-e", [expr, args @ ..]) | ("--eval", [expr, args @ ..]) => {
+            return Ok(Command::Eval(expr.clone(), args.to_vec()))
+        }
+        ("-m", [ns, args @ ..]) | ("--main", [ns, args @ ..]) => {
+            return Ok(Command::Main(ns.clone(), args.to_vec()))
+        }
+        _ => {}
+    }
+    let (record, flags) = take_flag("--record", flags)?;
+    if record.is_some() && command != "repl" && command != "nrepl" {
+        return Err(USAGE.to_string());
+    }
+    let (access_log, flags) = take_flag("--access-log", &flags)?;
+    if access_log.is_some() && command != "nrepl" {
+        return Err(USAGE.to_string());
+    }
+    let (cert, flags) = take_flag("--tls-cert", &flags)?;
+    let (key, flags) = take_flag("--tls-key", &flags)?;
+    let (client_ca, flags) = take_flag("--tls-client-ca", &flags)?;
+    let tls = match (cert, key, client_ca) {
+        (Some(cert), Some(key), client_ca) if command == "nrepl" => Some(TlsFiles {
+            cert,
+            key,
+            client_ca,
+        }),
+        (None, None, None) => None,
+        _ => return Err(USAGE.to_string()),
+    };
+    let (token, flags) = take_flag("--auth-token", &flags)?;
+    let (generate, flags) = take_switch("--auth", &flags);
+    let auth = match (token, generate) {
+        (Some(token), false) if command == "nrepl" || command == "connect" => {
+            Some(Auth::Token(token))
+        }
+        (None, true) if command == "nrepl" => Some(Auth::Generate),
+        (None, false) => None,
+        _ => return Err(USAGE.to_string()),
+    };
+    let (namespaces, flags) = take_flag("--namespaces", &flags)?;
+    if let Some(re) = &namespaces {
+        regex::Regex::new(re).map_err(|e| format!("Invalid regex {}: {}", re, e))?;
+    }
+    let (include, flags) = take_flags("--include", &flags)?;
+    let (exclude, flags) = take_flags("--exclude", &flags)?;
+    let tag = |tag: String| tag.trim_start_matches(':').to_string();
+    let selector = Selector {
+        namespaces,
+        include: include.into_iter().map(tag).collect(),
+        exclude: exclude.into_iter().map(tag).collect(),
+    };
+    if selector != Selector::default() && command != "test" {
+        return Err(USAGE.to_string());
+    }
+    let (host, flags) = take_flag("--host", &flags)?;
+    let host = match host {
+        Some(host) if command == "nrepl" || (command == "repl" && flags.len() == 2) => host,
+        Some(_) => return Err(USAGE.to_string()),
+        None => String::from(nrepl::DEFAULT_HOST),
+    };
+    // Anyone who can reach a server can run code on this machine;  TLS alone only encrypts
+    // what they send,  unless it's mutual,  checking clients' certificates too
+    let client_checked = tls.as_ref().is_some_and(|tls| tls.client_ca.is_some());
+    if !is_loopback(&host) && (command == "repl" || (!client_checked && auth.is_none())) {
+        return Err(format!(
+            "Won't listen on {} without an auth token or --tls-client-ca;  anyone who could reach it could run code here",
+            host
+        ));
+    }
+    match (command, &flags[..]) {
+        ("repl", []) => Ok(Command::Repl { record }),
+        ("repl", [flag, port]) if record.is_none() => {
+            let mode = match flag.as_str() {
+                "--socket-repl" => socket_repl::Mode::Repl,
+                "--prepl" => socket_repl::Mode::Prepl,
+                _ => return Err(USAGE.to_string()),
+            };
+            match port.parse::<u16>() {
+                Ok(port) => Ok(Command::SocketRepl(mode, host, port)),
+                Err(_) => Err(format!("Invalid port: {}", port)),
+            }
+        }
+        ("nrepl", flags) => nrepl::parse_port(flags).map(|port| Command::Nrepl {
+            host,
+            port,
+            record,
+            access_log,
+            tls,
+            auth,
+        }),
+        ("connect", flags) if flags.len() <= 1 && !flags.iter().any(|f| f.starts_with("--")) => {
+            Ok(Command::Connect {
+                addr: flags.first().cloned(),
+                auth_token: match auth {
+                    Some(Auth::Token(token)) => Some(token),
+                    _ => None,
+                },
+            })
+        }
+        ("replay", [file]) => Ok(Command::Replay(file.clone())),
+        ("fmt", flags) => {
+            let check = flags.iter().any(|flag| flag == "--check");
+            let paths = flags
+                .iter()
+                .filter(|flag| *flag != "--check")
+                .cloned()
+                .collect::<Vec<String>>();
+            if paths.is_empty() || paths.iter().any(|path| path.starts_with("--")) {
+                return Err(USAGE.to_string());
+            }
+            Ok(Command::Fmt { paths, check })
+        }
+        ("lint", paths)
+            if !paths.is_empty() && !paths.iter().any(|path| path.starts_with("--")) =>
+        {
+            Ok(Command::Lint(paths.to_vec()))
+        }
+        ("test", []) => Ok(Command::Test {
+            dir: String::from("test"),
+            selector,
+        }),
+        ("test", [dir]) if !dir.starts_with("--") => Ok(Command::Test {
+            dir: dir.clone(),
+            selector,
+        }),
+        ("conformance", []) => Ok(Command::Conformance(String::from("conformance"))),
+        ("conformance", [dir]) if !dir.starts_with("--") => Ok(Command::Conformance(dir.clone())),
+        ("help", []) | ("--help", []) | ("-h", []) => Ok(Command::Help),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// Takes `--path DIRS` out of args,  wherever it is among them;  as every command takes it,
+/// before the rest are parsed
+pub fn load_path_flag(args: &[String]) -> Result<(Option<String>, Vec<String>), String> {
+    take_flag("--path", args)
+}
+
+/// Takes `--profile` out of args,  wherever it is among them;  whether it was there
+pub fn profile_flag(args: &[String]) -> (bool, Vec<String>) {
+    take_switch("--profile", args)
+}
+
+// Takes `name VALUE` out of flags,  wherever it is among them
+fn take_flag(name: &str, flags: &[String]) -> Result<(Option<String>, Vec<String>), String> {
+    match flags.iter().position(|flag| flag == name) {
+        Some(at) => match flags.get(at + 1) {
+            Some(val) if !val.starts_with("--") => {
+                let rest = [&flags[..at], &flags[at + 2..]].concat();
+                Ok((Some(val.clone()), rest))
+            }
+            _ => Err(USAGE.to_string()),
+        },
+        None => Ok((None, flags.to_vec())),
+    }
+}
+
+// Takes each `name VALUE` out of flags,  wherever they are among them
+fn take_flags(name: &str, flags: &[String]) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut vals = vec![];
+    let mut flags = flags.to_vec();
+    while let (Some(val), rest) = take_flag(name, &flags)? {
+        vals.push(val);
+        flags = rest;
+    }
+    Ok((vals, flags))
+}
+
+// Takes `name` out of flags,  wherever it is among them;  whether it was there
+fn take_switch(name: &str, flags: &[String]) -> (bool, Vec<String>) {
+    let rest = flags
+        .iter()
+        .filter(|flag| *flag != name)
+        .cloned()
+        .collect::<Vec<String>>();
+    (rest.len() < flags.len(), rest)
+}
+
+// Whether host is this machine's own,  which no other can reach
+fn is_loopback(host: &str) -> bool {
+    host == "localhost" || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// The .clj files at path;  path itself if it's a file,  or every one under it,  sorted,
+/// if it's a directory
+pub fn clojure_files(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = vec![];
+    for entry in fs::read_dir(path)? {
+        let entry = entry?.path();
+        if entry.is_dir() {
+            files.extend(clojure_files(&entry)?);
+        } else if entry.extension().is_some_and(|ext| ext == "clj") {
+            files.push(entry);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{load_path_flag, parse, Command};
+    use crate::clojure_test::Selector;
+    use crate::nrepl::tls::TlsFiles;
+    use crate::nrepl::Auth;
+    use crate::socket_repl;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| String::from(*arg)).collect()
+    }
+
+    #[test]
+    fn each_command_takes_its_own_flags() {
+        assert_eq!(Ok(Command::Repl { record: None }), parse(&args(&[])));
+        assert_eq!(
+            Ok(Command::Repl {
+                record: Some(String::from("session.edn"))
+            }),
+            parse(&args(&["repl", "--record", "session.edn"]))
+        );
+        assert_eq!(
+            Ok(Command::SocketRepl(
+                socket_repl::Mode::Prepl,
+                String::from("127.0.0.1"),
+                5555
+            )),
+            parse(&args(&["repl", "--prepl", "5555"]))
+        );
+        assert_eq!(
+            Ok(Command::Nrepl {
+                host: String::from("127.0.0.1"),
+                port: 0,
+                record: None,
+                access_log: None,
+                tls: None,
+                auth: None
+            }),
+            parse(&args(&["nrepl", "-p", "0"]))
+        );
+        assert_eq!(
+            Ok(Command::Nrepl {
+                host: String::from("127.0.0.1"),
+                port: 5555,
+                record: Some(String::from("s.edn")),
+                access_log: None,
+                tls: None,
+                auth: None
+            }),
+            parse(&args(&["nrepl", "--record", "s.edn", "--port", "5555"]))
+        );
+        assert_eq!(
+            Ok(Command::Nrepl {
+                host: String::from("127.0.0.1"),
+                port: 7888,
+                record: None,
+                access_log: Some(String::from("access.log")),
+                tls: None,
+                auth: None
+            }),
+            parse(&args(&["nrepl", "--access-log", "access.log"]))
+        );
+        assert_eq!(
+            Ok(Command::Nrepl {
+                host: String::from("127.0.0.1"),
+                port: 7888,
+                record: None,
+                access_log: None,
+                tls: Some(TlsFiles {
+                    cert: String::from("cert.pem"),
+                    key: String::from("key.pem"),
+                    client_ca: Some(String::from("ca.pem"))
+                }),
+                auth: None
+            }),
+            parse(&args(&[
+                "nrepl",
+                "--tls-key",
+                "key.pem",
+                "--tls-client-ca",
+                "ca.pem",
+                "--tls-cert",
+                "cert.pem"
+            ]))
+        );
+        assert_eq!(
+            Ok(Command::Nrepl {
+                host: String::from("127.0.0.1"),
+                port: 7888,
+                record: None,
+                access_log: None,
+                tls: None,
+                auth: Some(Auth::Token(String::from("s3cret")))
+            }),
+            parse(&args(&["nrepl", "--auth-token", "s3cret"]))
+        );
+        assert_eq!(
+            Ok(Command::Nrepl {
+                host: String::from("127.0.0.1"),
+                port: 5555,
+                record: None,
+                access_log: None,
+                tls: None,
+                auth: Some(Auth::Generate)
+            }),
+            parse(&args(&["nrepl", "--auth", "-p", "5555"]))
+        );
+        assert_eq!(
+            Ok(Command::Connect {
+                addr: None,
+                auth_token: None
+            }),
+            parse(&args(&["connect"]))
+        );
+        assert_eq!(
+            Ok(Command::Connect {
+                addr: Some(String::from("localhost:5555")),
+                auth_token: Some(String::from("s3cret"))
+            }),
+            parse(&args(&[
+                "connect",
+                "--auth-token",
+                "s3cret",
+                "localhost:5555"
+            ]))
+        );
+        assert_eq!(
+            Ok(Command::Replay(String::from("s.edn"))),
+            parse(&args(&["replay", "s.edn"]))
+        );
+        assert_eq!(
+            Ok(Command::Run(String::from("a.clj"), vec![])),
+            parse(&args(&["run", "a.clj"]))
+        );
+        assert_eq!(
+            Ok(Command::Fmt {
+                paths: args(&["src", "b.clj"]),
+                check: true
+            }),
+            parse(&args(&["fmt", "src", "--check", "b.clj"]))
+        );
+        assert_eq!(
+            Ok(Command::Lint(args(&["src"]))),
+            parse(&args(&["lint", "src"]))
+        );
+        assert_eq!(
+            Ok(Command::Conformance(String::from("conformance"))),
+            parse(&args(&["conformance"]))
+        );
+        assert_eq!(
+            Ok(Command::Conformance(String::from("corpus"))),
+            parse(&args(&["conformance", "corpus"]))
+        );
+        assert_eq!(
+            Ok(Command::Test {
+                dir: String::from("test"),
+                selector: Selector::default()
+            }),
+            parse(&args(&["test"]))
+        );
+        assert_eq!(
+            Ok(Command::Test {
+                dir: String::from("spec"),
+                selector: Selector {
+                    namespaces: Some(String::from("app\\..*")),
+                    include: args(&["integration", "db"]),
+                    exclude: args(&["slow"]),
+                }
+            }),
+            parse(&args(&[
+                "test",
+                "--include",
+                ":integration",
+                "spec",
+                "--namespaces",
+                "app\\..*",
+                "--include",
+                "db",
+                "--exclude",
+                "slow"
+            ]))
+        );
+    }
+
+    #[test]
+    fn scripts_expressions_and_mains_take_the_args_after_them() {
+        assert_eq!(
+            Ok(Command::Run(
+                String::from("a.clj"),
+                args(&["x", "--record", "y"])
+            )),
+            parse(&args(&["a.clj", "x", "--record", "y"]))
+        );
+        assert_eq!(
+            Ok(Command::Run(String::from("a"), args(&["x"]))),
+            parse(&args(&["run", "a", "x"]))
+        );
+        assert_eq!(
+            Ok(Command::Eval(String::from("(+ 1 2)"), vec![])),
+            parse(&args(&["-e", "(+ 1 2)"]))
+        );
+        assert_eq!(
+            Ok(Command::Main(
+                String::from("my.app"),
+                args(&["-v", "in.txt"])
+            )),
+            parse(&args(&["--main", "my.app", "-v", "in.txt"]))
+        );
+        assert!(parse(&args(&["-m"])).is_err());
+    }
+
+    #[test]
+    fn anything_else_is_a_usage_error() {
+        assert!(parse(&args(&["run"])).is_err());
+        assert!(parse(&args(&["fmt", "--check"])).is_err());
+        assert!(parse(&args(&["lint"])).is_err());
+        assert!(parse(&args(&["repl", "--port", "1"])).is_err());
+        assert!(parse(&args(&["-e"])).is_err());
+        assert!(parse(&args(&["repl", "--record"])).is_err());
+        assert!(parse(&args(&["repl", "--prepl", "1", "--record", "s.edn"])).is_err());
+        assert!(parse(&args(&["replay", "a.edn", "--record", "s.edn"])).is_err());
+        assert!(parse(&args(&["repl", "--access-log", "access.log"])).is_err());
+        assert!(parse(&args(&["nrepl", "--access-log"])).is_err());
+        assert!(parse(&args(&["nrepl", "--tls-cert", "cert.pem"])).is_err());
+        assert!(parse(&args(&["repl", "--tls-cert", "c", "--tls-key", "k"])).is_err());
+        assert!(parse(&args(&["nrepl", "--auth", "--auth-token", "s3cret"])).is_err());
+        assert!(parse(&args(&["replay", "a.edn", "--auth"])).is_err());
+        assert!(parse(&args(&["replay", "a.edn", "--include", "integration"])).is_err());
+        assert!(parse(&args(&["test", "--namespaces", "app("])).is_err());
+        assert!(parse(&args(&["test", "--exclude"])).is_err());
+        assert!(parse(&args(&["connect", "--auth"])).is_err());
+        assert!(parse(&args(&["connect", "1", "2"])).is_err());
+        assert!(parse(&args(&["conformance", "a", "b"])).is_err());
+        assert!(parse(&args(&["conformance", "--include", "slow"])).is_err());
+    }
+
+    #[test]
+    fn servers_listen_where_others_can_reach_them_only_with_mutual_tls_or_a_token() {
+        assert_eq!(
+            Ok(Command::SocketRepl(
+                socket_repl::Mode::Repl,
+                String::from("::1"),
+                5555
+            )),
+            parse(&args(&["repl", "--host", "::1", "--socket-repl", "5555"]))
+        );
+        assert_eq!(
+            Ok(Command::Nrepl {
+                host: String::from("0.0.0.0"),
+                port: 7888,
+                record: None,
+                access_log: None,
+                tls: None,
+                auth: Some(Auth::Generate)
+            }),
+            parse(&args(&["nrepl", "--host", "0.0.0.0", "--auth"]))
+        );
+        assert!(parse(&args(&["nrepl", "--host", "localhost"])).is_ok());
+        assert!(parse(&args(&["nrepl", "--host", "0.0.0.0"])).is_err());
+        assert!(parse(&args(&[
+            "nrepl",
+            "--host",
+            "0.0.0.0",
+            "--tls-cert",
+            "c",
+            "--tls-key",
+            "k"
+        ]))
+        .is_err());
+        assert!(parse(&args(&[
+            "nrepl",
+            "--host",
+            "0.0.0.0",
+            "--tls-cert",
+            "c",
+            "--tls-key",
+            "k",
+            "--tls-client-ca",
+            "ca"
+        ]))
+        .is_ok());
+        assert!(parse(&args(&["nrepl", "--host", "example.com", "-p", "1"])).is_err());
+        assert!(parse(&args(&["repl", "--prepl", "1", "--host", "10.0.0.2"])).is_err());
+        assert!(parse(&args(&["repl", "--host", "127.0.0.1"])).is_err());
+        assert!(parse(&args(&["connect", "--host", "127.0.0.1"])).is_err());
+    }
+
+    #[test]
+    fn any_command_takes_a_load_path() {
+        assert_eq!(
+            Ok((Some(String::from("lib:src")), args(&["run", "a.clj"]))),
+            load_path_flag(&args(&["--path", "lib:src", "run", "a.clj"]))
+        );
+        assert_eq!(
+            Ok((Some(String::from("lib")), args(&["test", "spec"]))),
+            load_path_flag(&args(&["test", "spec", "--path", "lib"]))
+        );
+        assert_eq!(
+            Ok((None, args(&["repl"]))),
+            load_path_flag(&args(&["repl"]))
+        );
+        assert!(load_path_flag(&args(&["run", "a.clj", "--path"])).is_err());
+    }
+}
@@ -1,15 +1,13 @@
-(def *flush-on-newline* true)
-(def *print-readably* true)
+(def ^:dynamic *flush-on-newline* true)
+(def ^:dynamic *print-readably* true)
+(def ^:dynamic *data-readers* {})
 
 (def list (fn [& ls] ls))
 
-(defmacro defn [name args & body]
-  (list (quote def) name 
-        (list (quote fn) args 
-              (concat (list (quote do)) body))))
-
-(defn apply [f args]
-  (lexical-eval (concat (list f) args)))
+(defmacro defn [name & fdecl]
+  (if (string? (first fdecl))
+    (list (quote def) name (first fdecl) (apply list (quote fn) (rest fdecl)))
+    (list (quote def) name (apply list (quote fn) fdecl))))
 
 (defn newline
   []
@@ -20,7 +18,7 @@
   (flush-stdout))
 
 (defn pr [& more]
-  (print-string (apply str more)))
+  (print-string (apply str (map (fn [x] (if (nil? x) "nil" x)) more))))
 
 (defn prn [& more]
   (apply pr more)
@@ -33,6 +31,18 @@
 (defn println [& more]
   (apply prn more))
 
+(defmacro with-out-str [& body]
+  (list (quote with-out-str*) (concat (list (quote fn) []) body)))
+
+(defmacro with-in-str [s & body]
+  (list (quote with-in-str*) s (concat (list (quote fn) []) body)))
+
+(defn clojure-version []
+  (let [version *clojure-version*
+        qualifier (:qualifier version)]
+    (str (:major version) "." (:minor version) "." (:incremental version)
+         (if qualifier (str "-" qualifier) ""))))
+
 (defn inc [x]
   (+ x 1))
 
@@ -45,5 +55,15 @@
         (println (str "Elapsed time: " (_slash_ (- (System/nanoTime) start) 1000000.0) " msecs"))
         ret))))
 
-(defn slurp [f & opts]
-  (rust-slurp f opts))
+(defmacro future [& body]
+  (list (quote future-call) (concat (list (quote fn) []) body)))
+
+(defn pcalls [& fns]
+  (pmap (fn [f] (f)) fns))
+
+(defmacro pvalues [& exprs]
+  (concat (list (quote pcalls))
+          (map (fn [expr] (list (quote fn) [] expr)) exprs)))
+
+(defn var-get [x]
+  (deref x))
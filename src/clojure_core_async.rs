@@ -0,0 +1,6 @@
+pub(crate) mod alts;
+pub(crate) mod chan;
+pub(crate) mod close_bang_;
+pub(crate) mod go;
+pub(crate) mod put;
+pub(crate) mod take;
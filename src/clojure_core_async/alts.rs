@@ -0,0 +1,125 @@
+use crate::channel::{self, Op};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_vector::PersistentVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.core.async/alts!! ; (alts!! ports & {:keys [default priority]})
+///
+/// Does whichever one of ports can be done first,  waiting till one can;  each is a channel
+/// to take from,  or a [channel val] to put val on.  Gives [val port],  where val is what
+/// was taken,  or whether val was put.  With :default,  gives [default :default] rather
+/// than wait;  with :priority true,  ports are tried in order rather than at random
+#[derive(Debug, Clone)]
+pub struct AltsFn {}
+impl ToValue for AltsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AltsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len().is_multiple_of(2) {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to alts!! (Given: {}, Expected: ports and options in pairs)",
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let ports = match &*args[0] {
+            Value::PersistentVector(ports) if !ports.is_empty() => ports,
+            Value::PersistentVector(_) => {
+                return Value::Condition("alts!! needs at least one port".into())
+            }
+            _ => return error_message::type_mismatch(TypeTag::PersistentVector, &args[0]),
+        };
+        let mut ops = Vec::with_capacity(ports.len());
+        for port in ports.iter() {
+            match op(port) {
+                Ok(op) => ops.push(op),
+                Err(condition) => return condition,
+            }
+        }
+        let mut default = None;
+        let mut priority = false;
+        for option in args[1..].chunks(2) {
+            match &*option[0] {
+                Value::Keyword(kw) if kw.sym.name == "default" => {
+                    default = Some(Arc::clone(&option[1]))
+                }
+                Value::Keyword(kw) if kw.sym.name == "priority" => {
+                    priority = !matches!(&*option[1], Value::Nil | Value::Boolean(false))
+                }
+                _ => {
+                    return Value::Condition(format!("Unknown alts!! option: {}", option[0]).into())
+                }
+            }
+        }
+        match channel::alts(&ops, priority, default.is_none()) {
+            Ok(Some((i, val))) => vec![Arc::new(val), channel_of(ports.nth(i).unwrap())],
+            Ok(None) => vec![
+                default.unwrap_or_else(|| Arc::new(Value::Nil)),
+                Keyword::intern("default").to_rc_value(),
+            ],
+            Err(condition) => return condition,
+        }
+        .into_iter()
+        .collect::<PersistentVector>()
+        .to_value()
+    }
+}
+
+fn op(port: &Arc<Value>) -> Result<Op, Value> {
+    match &**port {
+        Value::Channel(ch) => Ok(Op::Take(Arc::clone(ch))),
+        Value::PersistentVector(put) if put.len() == 2 => {
+            match (&**put.nth(0).unwrap(), put.nth(1).unwrap()) {
+                (Value::Channel(_), val) if **val == Value::Nil => {
+                    Err(Value::Condition("Can't put nil on a channel".into()))
+                }
+                (Value::Channel(ch), val) => Ok(Op::Put(Arc::clone(ch), Arc::clone(val))),
+                (_, _) => Err(error_message::type_mismatch(
+                    TypeTag::Channel,
+                    put.nth(0).unwrap(),
+                )),
+            }
+        }
+        _ => Err(Value::Condition(
+            format!(
+                "A port should be a channel,  or a [channel val] to put val on;  not {}",
+                port
+            )
+            .into(),
+        )),
+    }
+}
+
+// The channel a port's done with;  the port itself if it's a take
+fn channel_of(port: &Arc<Value>) -> Arc<Value> {
+    match &**port {
+        Value::PersistentVector(put) => Arc::clone(put.nth(0).unwrap()),
+        _ => Arc::clone(port),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn alts_gives_the_value_and_the_port() {
+        let result = Repl::default().eval_all(&[
+            "(def c (clojure.core.async/chan 1))",
+            "(def t (clojure.core.async/timeout 20))",
+            "(def full (clojure.core.async/chan 1))",
+            "(clojure.core.async/>!! full :x)",
+            "[(= [true c] (clojure.core.async/alts!! [[c :a] t])) (= [:a c] (clojure.core.async/alts!! [t c])) (= [nil t] (clojure.core.async/alts!! [t c])) (clojure.core.async/alts!! [[full :y]] :default :none) (= [:x full] (clojure.core.async/alts!! [full c] :priority true))]",
+        ]);
+        assert_eq!("[true true true [:none :default] true]", result.to_string());
+    }
+}
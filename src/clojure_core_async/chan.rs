@@ -0,0 +1,59 @@
+use crate::channel::Channel;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// clojure.core.async/chan ; (chan) (chan n)
+///
+/// A channel buffering up to n values;  without n,  an unbuffered one,  that takes a put
+/// only once someone's waiting to take it
+#[derive(Debug, Clone)]
+pub struct ChanFn {}
+impl ToValue for ChanFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ChanFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() > 1 {
+            return error_message::wrong_varg_count(&[0, 1], args.len());
+        }
+        match args.first().map(|arg| &**arg) {
+            None | Some(Value::Nil) => Value::Channel(Arc::new(Channel::new(0))),
+            Some(Value::I32(n)) if *n >= 0 => Value::Channel(Arc::new(Channel::new(*n as usize))),
+            Some(Value::I32(n)) => {
+                Value::Condition(format!("A channel's buffer can't hold {} values", n).into())
+            }
+            Some(_) => error_message::type_mismatch(TypeTag::Integer, &args[0]),
+        }
+    }
+}
+
+/// clojure.core.async/timeout ; (timeout msecs)
+///
+/// A channel that closes by itself after msecs;  so taking from it waits that long
+#[derive(Debug, Clone)]
+pub struct TimeoutFn {}
+impl ToValue for TimeoutFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TimeoutFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::I32(msecs) => {
+                let after = Duration::from_millis((*msecs).max(0) as u64);
+                Value::Channel(Arc::new(Channel::timeout(after)))
+            }
+            _ => error_message::type_mismatch(TypeTag::Integer, &args[0]),
+        }
+    }
+}
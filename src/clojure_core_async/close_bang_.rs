@@ -0,0 +1,31 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.core.async/close! ; (close! ch)
+///
+/// Closes ch;  it takes no more puts,  and once what it holds has been taken,  takes give
+/// nil
+#[derive(Debug, Clone)]
+pub struct CloseFn {}
+impl ToValue for CloseFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CloseFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Channel(ch) => {
+                ch.close();
+                Value::Nil
+            }
+            _ => error_message::type_mismatch(TypeTag::Channel, &args[0]),
+        }
+    }
+}
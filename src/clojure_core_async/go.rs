@@ -0,0 +1,108 @@
+use crate::channel::{self, Channel};
+use crate::error_message;
+use crate::future::Future;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.core.async/go ; (go body)
+///
+/// Evaluates body on a thread of its own;  gives a channel that what body gives is put on,
+/// and that's then closed.  Unlike core.async's go,  body isn't rewritten to park;  what
+/// it waits on,  it waits on with the thread.  clojure.core.async/thread is the same
+///
+/// (go body) expands to
+///
+/// (clojure.core.async/thread-call (fn [] body))
+#[derive(Debug, Clone)]
+pub struct GoMacro {}
+impl ToValue for GoMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for GoMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let mut fn_expr = vec![
+            Symbol::intern("fn").to_rc_value(),
+            PersistentVector::empty().to_rc_value(),
+        ];
+        fn_expr.extend(args);
+        vec![
+            Symbol::intern("clojure.core.async/thread-call").to_rc_value(),
+            fn_expr.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+/// clojure.core.async/thread-call ; (thread-call f)
+///
+/// Calls f (with no arguments) on a thread of its own;  (go body) wraps this
+#[derive(Debug, Clone)]
+pub struct ThreadCallFn {}
+impl ToValue for ThreadCallFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ThreadCallFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let f = match &*args[0] {
+            Value::IFn(ifn) => Arc::clone(ifn),
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let result = Arc::new(Channel::new(1));
+        let ch = Arc::clone(&result);
+        // A future,  for the thread it runs on and what it conveys there;  its own result's
+        // never looked at
+        let started = Future::run(move || {
+            match f.invoke(vec![]) {
+                Value::Nil => (),
+                val => {
+                    channel::put(&ch, Arc::new(val));
+                }
+            }
+            ch.close();
+            Value::Nil
+        });
+        match started {
+            Ok(_) => Value::Channel(result),
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn go_puts_what_body_gives_then_closes() {
+        let result = Repl::default().eval_all(&[
+            "(def c (clojure.core.async/go (+ 1 2)))",
+            "[(clojure.core.async/<!! c) (clojure.core.async/<!! c) (clojure.core.async/<!! (clojure.core.async/thread nil))]",
+        ]);
+        assert_eq!("[3 nil nil]", result.to_string());
+    }
+
+    #[test]
+    fn channels_hand_values_between_go_blocks() {
+        let result = Repl::default().eval_all(&[
+            "(def in (clojure.core.async/chan))",
+            "(def out (clojure.core.async/chan 10))",
+            "(clojure.core.async/go (loop [] (let [x (clojure.core.async/<!! in)] (if x (do (clojure.core.async/>!! out (* x x)) (recur)) (clojure.core.async/close! out)))))",
+            "(clojure.core.async/go (loop [x 1] (when (<= x 3) (clojure.core.async/>!! in x) (recur (inc x)))) (clojure.core.async/close! in))",
+            "[(clojure.core.async/<!! out) (clojure.core.async/<!! out) (clojure.core.async/<!! out) (clojure.core.async/<!! out)]",
+        ]);
+        assert_eq!("[1 4 9 nil]", result.to_string());
+    }
+}
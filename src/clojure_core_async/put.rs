@@ -0,0 +1,32 @@
+use crate::channel;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.core.async/>!! ; (>!! ch val)
+///
+/// Puts val on ch,  waiting till there's room for it;  true,  or false if ch is closed.
+/// nil can't be put,  as taking nil is how a closed channel's told apart
+#[derive(Debug, Clone)]
+pub struct PutFn {}
+impl ToValue for PutFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PutFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match (&*args[0], &*args[1]) {
+            (Value::Channel(_), Value::Nil) => {
+                Value::Condition("Can't put nil on a channel".into())
+            }
+            (Value::Channel(ch), _) => channel::put(ch, Arc::clone(&args[1])),
+            _ => error_message::type_mismatch(TypeTag::Channel, &args[0]),
+        }
+    }
+}
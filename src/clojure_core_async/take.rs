@@ -0,0 +1,28 @@
+use crate::channel;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.core.async/<!! ; (<!! ch)
+///
+/// Takes a value from ch,  waiting till there's one;  nil once ch is closed and empty
+#[derive(Debug, Clone)]
+pub struct TakeFn {}
+impl ToValue for TakeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TakeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Channel(ch) => channel::take(ch),
+            _ => error_message::type_mismatch(TypeTag::Channel, &args[0]),
+        }
+    }
+}
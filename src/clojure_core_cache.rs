@@ -0,0 +1,216 @@
+//! clojure.core.cache,  and clojure.core.cache.wrapped;  caches that forget what they were
+//! given,  as a policy says
+//!
+//! A cache is a value,  as a map is;  hit,  miss and evict give a new one,  and leave the one
+//! they were given as it was.  Each copies its entries,  so these are for the tens or
+//! thousands of entries a script keeps,  not millions.  The wrapped namespace's fns take an
+//! atom holding a cache instead,  and swap! it;  its lookup-or-miss is what most scripts
+//! want,  the value for a key,  worked out and cached if it's not already there
+pub(crate) mod factory;
+pub(crate) mod lookup_or_miss;
+pub(crate) mod ops;
+
+use crate::clojure_java_io;
+use crate::host_object::HostObject;
+use crate::lazy_seq;
+use crate::value::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// What a cache forgets,  and when
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Policy {
+    /// Nothing,  unless it's evicted
+    Basic,
+    /// Whatever came first,  once there are more than threshold entries
+    Fifo { threshold: usize },
+    /// Whatever was used least recently,  once there are more than threshold entries
+    Lru { threshold: usize },
+    /// Whatever came more than ttl ago
+    Ttl { ttl: Duration },
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    val: Arc<Value>,
+    // When it came,  or for an LRU cache,  was last used;  by the cache's clock
+    stamp: u64,
+    added: Instant,
+}
+
+/// A cache;  what the factories give
+#[derive(Debug, Clone)]
+pub struct Cache {
+    policy: Policy,
+    entries: HashMap<Arc<Value>, Entry>,
+    // Ticks with each entry added,  or used
+    clock: u64,
+}
+impl Cache {
+    /// A cache with policy,  seeded with the entries of base,  a map
+    pub fn new(policy: Policy, base: &Arc<Value>) -> Result<Cache, Value> {
+        Cache {
+            policy,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+        .seed(base)
+    }
+    fn is_live(&self, entry: &Entry) -> bool {
+        match self.policy {
+            Policy::Ttl { ttl } => entry.added.elapsed() < ttl,
+            _ => true,
+        }
+    }
+    pub fn has(&self, key: &Arc<Value>) -> bool {
+        self.lookup(key).is_some()
+    }
+    pub fn lookup(&self, key: &Arc<Value>) -> Option<Arc<Value>> {
+        self.entries
+            .get(key)
+            .filter(|entry| self.is_live(entry))
+            .map(|entry| Arc::clone(&entry.val))
+    }
+    /// This cache,  with key noted as just used
+    pub fn hit(&self, key: &Arc<Value>) -> Cache {
+        let mut cache = self.clone();
+        if let Policy::Lru { .. } = self.policy {
+            cache.clock += 1;
+            let clock = cache.clock;
+            if let Some(entry) = cache.entries.get_mut(key) {
+                entry.stamp = clock;
+            }
+        }
+        cache
+    }
+    /// This cache,  with val under key,  and without what the policy has it forget for it
+    pub fn miss(&self, key: &Arc<Value>, val: &Arc<Value>) -> Cache {
+        let mut cache = self.clone();
+        cache.add(key, val);
+        cache.forget();
+        cache
+    }
+    pub fn evict(&self, key: &Arc<Value>) -> Cache {
+        let mut cache = self.clone();
+        cache.entries.remove(key);
+        cache
+    }
+    /// A cache with this one's policy,  and just the entries of base
+    pub fn seed(&self, base: &Arc<Value>) -> Result<Cache, Value> {
+        let mut cache = Cache {
+            policy: self.policy,
+            entries: HashMap::new(),
+            clock: self.clock,
+        };
+        lazy_seq::try_for_each(base, |entry| match &*entry {
+            Value::PersistentVector(pair) if pair.len() == 2 => {
+                cache.add(pair.nth(0).unwrap(), pair.nth(1).unwrap());
+                Ok(())
+            }
+            _ => Err(Value::Condition(
+                format!(
+                    "Cannot seed a cache with {};  give a map",
+                    base.to_string_explicit()
+                )
+                .into(),
+            )),
+        })?;
+        Ok(cache)
+    }
+    fn add(&mut self, key: &Arc<Value>, val: &Arc<Value>) {
+        self.clock += 1;
+        let entry = Entry {
+            val: Arc::clone(val),
+            stamp: self.clock,
+            added: Instant::now(),
+        };
+        self.entries.insert(Arc::clone(key), entry);
+    }
+    fn forget(&mut self) {
+        let threshold = match self.policy {
+            Policy::Basic => return,
+            Policy::Fifo { threshold } | Policy::Lru { threshold } => threshold,
+            Policy::Ttl { ttl } => {
+                self.entries.retain(|_, entry| entry.added.elapsed() < ttl);
+                return;
+            }
+        };
+        while self.entries.len() > threshold {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stamp)
+                .map(|(key, _)| Arc::clone(key))
+                .unwrap();
+            self.entries.remove(&oldest);
+        }
+    }
+}
+impl HostObject for Cache {
+    fn type_name(&self) -> &str {
+        match self.policy {
+            Policy::Basic => "clojure.core.cache.BasicCache",
+            Policy::Fifo { .. } => "clojure.core.cache.FIFOCache",
+            Policy::Lru { .. } => "clojure.core.cache.LRUCache",
+            Policy::Ttl { .. } => "clojure.core.cache.TTLCacheQ",
+        }
+    }
+    fn call(&self, _method: &str, _args: Vec<Arc<Value>>) -> Option<Value> {
+        None
+    }
+    /// As a map of what it still has;  oldest first
+    fn display(&self) -> String {
+        let mut live = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| self.is_live(entry))
+            .collect::<Vec<_>>();
+        live.sort_by_key(|(_, entry)| entry.stamp);
+        let entries = live
+            .iter()
+            .map(|(key, entry)| {
+                format!(
+                    "{} {}",
+                    key.to_string_explicit(),
+                    entry.val.to_string_explicit()
+                )
+            })
+            .collect::<Vec<String>>();
+        format!("{{{}}}", entries.join(", "))
+    }
+}
+
+/// The cache x is
+pub fn as_cache(x: &Value) -> Result<&Cache, Value> {
+    clojure_java_io::downcast::<Cache>(x).ok_or_else(|| {
+        Value::Condition(
+            format!(
+                "Expected a cache,  not {};  make one with a cache factory",
+                x.to_string_explicit()
+            )
+            .into(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn each_policy_forgets_what_it_says_it_will() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.core.cache :as c])",
+            "(def fifo (-> (c/fifo-cache-factory {:a 1} :threshold 2) (c/miss :b 2) (c/hit :a) (c/miss :c 3)))",
+            "(def lru (-> (c/lru-cache-factory {:a 1} :threshold 2) (c/miss :b 2) (c/hit :a) (c/miss :c 3)))",
+            "(def ttl (c/ttl-cache-factory {:a 1} :ttl 20))",
+            "(sleep 40)",
+            "[fifo lru (c/has? ttl :a) (c/lookup (c/miss ttl :b 2) :b) (c/lookup lru :b :gone) (c/evict lru :a) (c/seed lru {:z 26}) (c/through-cache (c/basic-cache-factory {}) 2 inc)]",
+        ]);
+        assert_eq!(
+            "[{:b 2, :c 3} {:a 1, :c 3} false 2 :gone {:c 3} {:z 26} {2 3}]",
+            result.to_string()
+        );
+    }
+}
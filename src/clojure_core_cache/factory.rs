@@ -0,0 +1,99 @@
+use crate::atom::Atom;
+use crate::clojure_core_cache::{Cache, Policy};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::options::Opts;
+use crate::timer;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+const THRESHOLD: usize = 32;
+const TTL: Duration = Duration::from_secs(2);
+
+/// Each cache factory,  and the policy of the caches it makes;  with the options' defaults
+pub const FACTORIES: &[(&str, Policy)] = &[
+    ("basic-cache-factory", Policy::Basic),
+    (
+        "fifo-cache-factory",
+        Policy::Fifo {
+            threshold: THRESHOLD,
+        },
+    ),
+    (
+        "lru-cache-factory",
+        Policy::Lru {
+            threshold: THRESHOLD,
+        },
+    ),
+    ("ttl-cache-factory", Policy::Ttl { ttl: TTL }),
+];
+
+/// (lru-cache-factory base & options) and friends
+///
+/// A cache seeded with base,  a map.  FIFO and LRU caches take :threshold,  the most entries
+/// they keep (32,  unless it's given);  TTL caches :ttl,  the milliseconds each entry's kept
+/// (2000).  In clojure.core.cache.wrapped,  an atom holding the cache
+#[derive(Debug, Clone)]
+pub struct CacheFactoryFn {
+    name: &'static str,
+    policy: Policy,
+    wrapped: bool,
+}
+impl CacheFactoryFn {
+    pub fn new(name: &'static str, policy: Policy, wrapped: bool) -> CacheFactoryFn {
+        CacheFactoryFn {
+            name,
+            policy,
+            wrapped,
+        }
+    }
+}
+impl ToValue for CacheFactoryFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CacheFactoryFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let cache = match policy(self.name, self.policy, &args[1..])
+            .and_then(|policy| Cache::new(policy, &args[0]))
+        {
+            Ok(cache) => Value::host_object(cache),
+            Err(condition) => return condition,
+        };
+        if !self.wrapped {
+            return cache;
+        }
+        match Atom::new(cache.to_rc_value(), None) {
+            Ok(atom) => Value::Atom(Arc::new(atom)),
+            Err(condition) => condition,
+        }
+    }
+}
+
+// policy,  as the options in args,  given to name,  have it
+fn policy(name: &str, mut policy: Policy, args: &[Arc<Value>]) -> Result<Policy, Value> {
+    let known: &[&str] = match policy {
+        Policy::Basic => &[],
+        Policy::Fifo { .. } | Policy::Lru { .. } => &["threshold"],
+        Policy::Ttl { .. } => &["ttl"],
+    };
+    for (_, val) in Opts::from_pairs(name, known, args)?.iter() {
+        match &mut policy {
+            Policy::Fifo { threshold } | Policy::Lru { threshold } => {
+                *threshold = match &**val {
+                    Value::I32(n) if *n > 0 => *n as usize,
+                    _ => return Err(error_message::type_mismatch(TypeTag::I32, val)),
+                }
+            }
+            Policy::Ttl { ttl } => *ttl = timer::millis(val)?,
+            Policy::Basic => {}
+        }
+    }
+    Ok(policy)
+}
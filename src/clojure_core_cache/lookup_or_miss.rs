@@ -0,0 +1,77 @@
+use crate::clojure_core_cache;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (lookup-or-miss cache-atom key value-fn)
+///
+/// What the cache in cache-atom has under key;  or if it hasn't,  (value-fn key),  put in
+/// the cache for next time.  value-fn's called at most once,  however many other threads
+/// change the cache meanwhile;  though two threads missing the same key at once each call
+/// their own
+#[derive(Debug, Clone)]
+pub struct LookupOrMissFn {}
+impl ToValue for LookupOrMissFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for LookupOrMissFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let (key, value_fn) = (&args[1], &args[2]);
+        let atom = match &*args[0] {
+            Value::Atom(atom) => atom,
+            _ => return error_message::type_mismatch(TypeTag::Atom, &args[0]),
+        };
+        let mut computed: Option<Arc<Value>> = None;
+        loop {
+            let old = atom.deref();
+            let cache = match clojure_core_cache::as_cache(&old) {
+                Ok(cache) => cache,
+                Err(condition) => return condition,
+            };
+            let (val, new) = match cache.lookup(key) {
+                Some(val) => (val, cache.hit(key)),
+                None => {
+                    let val = match &computed {
+                        Some(val) => Arc::clone(val),
+                        None => match ifn::call(value_fn, vec![Arc::clone(key)]) {
+                            Value::Condition(condition) => return Value::Condition(condition),
+                            val => Arc::clone(computed.insert(val.to_rc_value())),
+                        },
+                    };
+                    let new = cache.miss(key, &val);
+                    (val, new)
+                }
+            };
+            let new = Value::host_object(new).to_rc_value();
+            match atom.compare_and_set(&args[0], &old, new) {
+                Ok(true) => return val.to_value(),
+                Ok(false) => continue,
+                Err(condition) => return condition,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn value_fn_is_called_only_for_what_the_cache_has_not_got() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.core.cache.wrapped :as w])",
+            "(def calls (atom 0))",
+            "(def c (w/lru-cache-factory {} :threshold 2))",
+            "(defn slow-square [n] (swap! calls inc) (* n n))",
+            "[(w/lookup-or-miss c 3 slow-square) (w/lookup-or-miss c 3 slow-square) (w/lookup-or-miss c 4 slow-square) (w/lookup-or-miss c 3 slow-square) (w/lookup-or-miss c 5 slow-square) @calls (w/has? c 4) (w/has? c 3) @c]",
+        ]);
+        assert_eq!("[9 9 16 9 25 3 false true {3 9, 5 25}]", result.to_string());
+    }
+}
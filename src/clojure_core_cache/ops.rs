@@ -0,0 +1,128 @@
+use crate::clojure_core_cache::{self, Cache};
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// What a cache fn does with the cache it's given
+#[derive(Debug, Clone, Copy)]
+pub enum Op {
+    /// Asks it something
+    Query(fn(&Cache, &[Arc<Value>]) -> Value),
+    /// Gives the cache it becomes
+    Update(fn(&Cache, &[Arc<Value>]) -> Result<Cache, Value>),
+}
+
+/// Each fn both cache namespaces have,  the numbers of args it takes after the cache,  and
+/// what it does
+pub const OPS: &[(&str, &[usize], Op)] = &[
+    (
+        "has?",
+        &[1],
+        Op::Query(|cache, args| Value::Boolean(cache.has(&args[0]))),
+    ),
+    ("lookup", &[1, 2], Op::Query(lookup)),
+    (
+        "hit",
+        &[1],
+        Op::Update(|cache, args| Ok(cache.hit(&args[0]))),
+    ),
+    (
+        "miss",
+        &[2],
+        Op::Update(|cache, args| Ok(cache.miss(&args[0], &args[1]))),
+    ),
+    (
+        "evict",
+        &[1],
+        Op::Update(|cache, args| Ok(cache.evict(&args[0]))),
+    ),
+    ("seed", &[1], Op::Update(|cache, args| cache.seed(&args[0]))),
+    ("through-cache", &[2], Op::Update(through_cache)),
+];
+
+// (lookup cache key not-found?);  what's under key,  or not-found (nil) if nothing is
+fn lookup(cache: &Cache, args: &[Arc<Value>]) -> Value {
+    match cache.lookup(&args[0]).or_else(|| args.get(1).cloned()) {
+        Some(val) => val.to_value(),
+        None => Value::Nil,
+    }
+}
+
+// (through-cache cache key value-fn);  the cache with key hit,  if it has it;  with
+// (value-fn key) under it otherwise
+fn through_cache(cache: &Cache, args: &[Arc<Value>]) -> Result<Cache, Value> {
+    if cache.has(&args[0]) {
+        return Ok(cache.hit(&args[0]));
+    }
+    match ifn::call(&args[1], vec![Arc::clone(&args[0])]) {
+        Value::Condition(condition) => Err(Value::Condition(condition)),
+        val => Ok(cache.miss(&args[0], &val.to_rc_value())),
+    }
+}
+
+/// (has? cache key),  (miss cache key val) and the rest;  in clojure.core.cache.wrapped,
+/// (has? cache-atom key) and so on,  with each that gives a new cache swap!ing it into the
+/// atom,  and returning it
+#[derive(Debug, Clone)]
+pub struct CacheOpFn {
+    counts: &'static [usize],
+    op: Op,
+    wrapped: bool,
+}
+impl CacheOpFn {
+    pub fn new(counts: &'static [usize], op: Op, wrapped: bool) -> CacheOpFn {
+        CacheOpFn {
+            counts,
+            op,
+            wrapped,
+        }
+    }
+}
+impl ToValue for CacheOpFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CacheOpFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || !self.counts.contains(&(args.len() - 1)) {
+            let counts = self.counts.iter().map(|n| n + 1).collect::<Vec<usize>>();
+            return error_message::wrong_varg_count(&counts, args.len());
+        }
+        if !self.wrapped {
+            return match apply(self.op, &args[0], &args[1..]) {
+                Ok(val) => val,
+                Err(condition) => condition,
+            };
+        }
+        let atom = match &*args[0] {
+            Value::Atom(atom) => atom,
+            _ => return error_message::type_mismatch(TypeTag::Atom, &args[0]),
+        };
+        loop {
+            let old = atom.deref();
+            let new = match apply(self.op, &old, &args[1..]) {
+                Ok(new) => Arc::new(new),
+                Err(condition) => return condition,
+            };
+            if let Op::Query(_) = self.op {
+                return new.to_value();
+            }
+            match atom.compare_and_set(&args[0], &old, Arc::clone(&new)) {
+                Ok(true) => return new.to_value(),
+                Ok(false) => continue,
+                Err(condition) => return condition,
+            }
+        }
+    }
+}
+
+fn apply(op: Op, cache: &Value, args: &[Arc<Value>]) -> Result<Value, Value> {
+    let cache = clojure_core_cache::as_cache(cache)?;
+    match op {
+        Op::Query(query) => Ok(query(cache, args)),
+        Op::Update(update) => Ok(Value::host_object(update(cache, args)?)),
+    }
+}
@@ -0,0 +1,2 @@
+//! clojure.data;  diff,  for what two values have in common and where they differ
+pub(crate) mod diff;
@@ -0,0 +1,230 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq;
+use crate::maps::MapEntry;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list_map::{PersistentListMap, ToPersistentListMapIter};
+use crate::persistent_vector::ToPersistentVector;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.data/diff ; [things-only-in-a things-only-in-b things-in-both]
+/// (diff a b)
+///
+/// Maps are compared key by key,  and sequential colls index by index,  each down into
+/// what's under it;  so only what differs is in the first two.  A vector's indices that
+/// don't differ are nil in them.  Sets are compared element by element.  Anything else,  or
+/// two values of different kinds,  are the same or they aren't;  and nothing's nil
+#[derive(Debug, Clone)]
+pub struct DiffFn {}
+impl ToValue for DiffFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DiffFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match diff(&args[0], &args[1]) {
+            Ok(diffed) => diffed
+                .iter()
+                .map(|part| part.clone().unwrap_or_else(|| Arc::new(Value::Nil)))
+                .collect::<Vec<Arc<Value>>>()
+                .into_vector()
+                .to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// What's only in a,  what's only in b,  and what's in both;  None for nothing
+pub(crate) type Diff = [Option<Arc<Value>>; 3];
+
+// The kinds of value diff compares in their own way;  values of different kinds aren't
+// compared any further than that they differ
+enum Partition {
+    Atom,
+    Set(Vec<Arc<Value>>),
+    Sequential(Vec<Arc<Value>>),
+    Map(Vec<(Arc<Value>, Arc<Value>)>),
+}
+
+fn partition(x: &Arc<Value>) -> Result<Partition, Value> {
+    let elements = || {
+        lazy_seq::iter(Arc::clone(x))
+            .map(|element| match &*element {
+                Value::Condition(_) => Err((*element).clone()),
+                _ => Ok(element),
+            })
+            .collect::<Result<Vec<Arc<Value>>, Value>>()
+    };
+    Ok(match &**x {
+        Value::PersistentListMap(map) => Partition::Map(
+            map.iter()
+                .map(|entry| (Arc::clone(&entry.key), Arc::clone(&entry.val)))
+                .collect(),
+        ),
+        Value::PersistentTreeMap(map) => Partition::Map(
+            map.iter()
+                .map(|entry| (Arc::clone(&entry.key), Arc::clone(&entry.val)))
+                .collect(),
+        ),
+        Value::PersistentHashSet(_) | Value::PersistentTreeSet(_) => Partition::Set(elements()?),
+        Value::PersistentList(_) | Value::PersistentVector(_) | Value::LazySeq(_) => {
+            Partition::Sequential(elements()?)
+        }
+        _ => Partition::Atom,
+    })
+}
+
+/// What diff gives for a and b
+pub(crate) fn diff(a: &Arc<Value>, b: &Arc<Value>) -> Result<Diff, Value> {
+    if a == b {
+        return Ok([None, None, Some(Arc::clone(a))]);
+    }
+    Ok(match (partition(a)?, partition(b)?) {
+        (Partition::Map(a), Partition::Map(b)) => {
+            let keys = a
+                .iter()
+                .map(|(key, _)| key)
+                .chain(b.iter().map(|(key, _)| key).filter(|key| !has_key(&a, key)))
+                .cloned()
+                .collect::<Vec<Arc<Value>>>();
+            let [a, b, both] = diff_by_key(&keys, |key| get(&a, key), |key| get(&b, key))?;
+            [as_map(a), as_map(b), as_map(both)]
+        }
+        (Partition::Sequential(a), Partition::Sequential(b)) => {
+            let indices = (0..a.len().max(b.len()))
+                .map(|i| Arc::new(Value::I32(i as i32)))
+                .collect::<Vec<Arc<Value>>>();
+            let nth = |coll: &[Arc<Value>], i: &Arc<Value>| match **i {
+                Value::I32(i) => coll.get(i as usize).cloned(),
+                _ => None,
+            };
+            let [a, b, both] = diff_by_key(&indices, |i| nth(&a, i), |i| nth(&b, i))?;
+            [as_vector(a), as_vector(b), as_vector(both)]
+        }
+        (Partition::Set(a), Partition::Set(b)) => {
+            let only = |these: &[Arc<Value>], those: &[Arc<Value>]| {
+                as_set(these.iter().filter(|x| !those.contains(x)).cloned())
+            };
+            let both = as_set(a.iter().filter(|x| b.contains(x)).cloned());
+            [only(&a, &b), only(&b, &a), both]
+        }
+        _ => [Some(Arc::clone(a)), Some(Arc::clone(b)), None],
+    })
+}
+
+type Entries = Vec<(Arc<Value>, Arc<Value>)>;
+
+// As clojure.data's diff-associative;  each key's vals diffed,  and what's only in a,  only
+// in b,  or in both,  kept under the key
+fn diff_by_key(
+    keys: &[Arc<Value>],
+    get_a: impl Fn(&Arc<Value>) -> Option<Arc<Value>>,
+    get_b: impl Fn(&Arc<Value>) -> Option<Arc<Value>>,
+) -> Result<[Entries; 3], Value> {
+    let mut diffed: [Entries; 3] = Default::default();
+    let nil = || Arc::new(Value::Nil);
+    for key in keys.iter() {
+        let (in_a, in_b) = (get_a(key), get_b(key));
+        let va = in_a.clone().unwrap_or_else(nil);
+        let vb = in_b.clone().unwrap_or_else(nil);
+        let [only_a, only_b, both] = diff(&va, &vb)?;
+        let same = in_a.is_some()
+            && in_b.is_some()
+            && (both.is_some() || (*va == Value::Nil && *vb == Value::Nil));
+        if in_a.is_some() && (only_a.is_some() || !same) {
+            diffed[0].push((Arc::clone(key), only_a.unwrap_or_else(nil)));
+        }
+        if in_b.is_some() && (only_b.is_some() || !same) {
+            diffed[1].push((Arc::clone(key), only_b.unwrap_or_else(nil)));
+        }
+        if same {
+            diffed[2].push((Arc::clone(key), both.unwrap_or_else(nil)));
+        }
+    }
+    Ok(diffed)
+}
+
+fn has_key(entries: &[(Arc<Value>, Arc<Value>)], key: &Arc<Value>) -> bool {
+    entries.iter().any(|(k, _)| k == key)
+}
+
+fn get(entries: &[(Arc<Value>, Arc<Value>)], key: &Arc<Value>) -> Option<Arc<Value>> {
+    entries
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, val)| Arc::clone(val))
+}
+
+fn as_map(entries: Entries) -> Option<Arc<Value>> {
+    if entries.is_empty() {
+        return None;
+    }
+    let map = entries
+        .into_iter()
+        .map(|(key, val)| MapEntry { key, val })
+        .collect::<PersistentListMap>();
+    Some(map.to_rc_value())
+}
+
+// A vector of each index's val,  nil at those missing;  as long as the last index needs
+fn as_vector(entries: Entries) -> Option<Arc<Value>> {
+    let len = entries
+        .iter()
+        .filter_map(|(i, _)| match **i {
+            Value::I32(i) => Some(i as usize + 1),
+            _ => None,
+        })
+        .max()?;
+    let mut vals = (0..len)
+        .map(|_| Arc::new(Value::Nil))
+        .collect::<Vec<Arc<Value>>>();
+    for (i, val) in entries.into_iter() {
+        if let Value::I32(i) = *i {
+            vals[i as usize] = val;
+        }
+    }
+    Some(vals.into_vector().to_rc_value())
+}
+
+fn as_set(elements: impl Iterator<Item = Arc<Value>>) -> Option<Arc<Value>> {
+    let set = elements.fold(PersistentHashSet::empty(), |set, x| set.conj(x));
+    match set.is_empty() {
+        true => None,
+        false => Some(set.to_rc_value()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    fn assert_diff(a: &str, b: &str, expected: &str) {
+        let diffed = format!("(clojure.data/diff {} {})", a, b);
+        assert_eq!(
+            Value::Boolean(true),
+            Repl::default().eval_all(&[&format!("(= {} {})", expected, diffed)]),
+            "{}",
+            diffed
+        );
+    }
+
+    #[test]
+    fn only_what_differs_is_in_the_first_two() {
+        assert_diff("{:a 1}", "{:a 1}", "[nil nil {:a 1}]");
+        assert_diff(
+            "{:a 1 :b {:c 2 :e 5}}",
+            "{:a 1 :b {:c 3 :e 5} :d 4}",
+            "[{:b {:c 2}} {:b {:c 3} :d 4} {:a 1 :b {:e 5}}]",
+        );
+        assert_diff("[1 2 3]", "[1 5 3 4]", "[[nil 2] [nil 5 nil 4] [1 nil 3]]");
+        assert_diff("#{1 2}", "#{2 4}", "[#{1} #{4} #{2}]");
+        assert_diff("1", "[1]", "[1 [1] nil]");
+        assert_diff("{:a nil}", "{:a 1}", "[{:a nil} {:a 1} nil]");
+    }
+}
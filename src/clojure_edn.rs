@@ -0,0 +1,24 @@
+pub(crate) mod pr_str;
+pub(crate) mod read;
+pub(crate) mod read_string;
+
+use crate::edn::Options;
+use crate::value::Value;
+use std::sync::Arc;
+
+/// The options given name before the rest of args,  if there are any;  and that rest
+pub(crate) fn options(name: &str, args: &[Arc<Value>], rest: usize) -> Result<Options, Value> {
+    match args.len().checked_sub(rest) {
+        Some(0) => Ok(Options::default()),
+        Some(1) => Options::from_map(name, &args[0]),
+        _ => Err(Value::Condition(
+            format!(
+                "Wrong number of arguments given to function (Given: {}, Expected: {} | {})",
+                args.len(),
+                rest,
+                rest + 1
+            )
+            .into(),
+        )),
+    }
+}
@@ -0,0 +1,45 @@
+use crate::edn;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.edn/pr-str ; x as EDN,  that clojure.edn/read-string reads back as x;  unlike
+/// pr-str,  it fails on anything that isn't data,  such as a fn or an atom
+/// (pr-str x)
+#[derive(Debug, Clone)]
+pub struct PrStrFn {}
+impl ToValue for PrStrFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PrStrFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match edn::print_str(&args[0]) {
+            Ok(s) => Value::String(s.into()),
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn only_data_is_printed_as_edn() {
+        let result = Repl::default().eval_all(&[
+            "(def data [[1 1.5 2.0 1/2 3N] '(x \"y\") #{:d} {:e nil}])",
+            "(def failed (ex-message (try (clojure.edn/pr-str [1 (atom 2)]) (catch Exception e e))))",
+            "[(clojure.edn/pr-str data) (= data (clojure.edn/read-string (clojure.edn/pr-str data))) failed]",
+        ]);
+        assert_eq!(
+            "[\"[[1 1.5 2.0 1/2 3N] (x \\\"y\\\") #{:d} {:e nil}]\" true \"Can't print clojure.lang.Atom as EDN\"]",
+            result.to_string()
+        );
+    }
+}
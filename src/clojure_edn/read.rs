@@ -0,0 +1,26 @@
+use crate::clojure_edn::options;
+use crate::edn;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::io;
+use std::sync::Arc;
+
+/// clojure.edn/read ; the next form from stdin,  read as EDN;  nil (or opts' :eof) at its
+/// end  TODO: should be aware of *in*
+/// (read) (read opts)
+#[derive(Debug, Clone)]
+pub struct ReadFn {}
+impl ToValue for ReadFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReadFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let options = match options("clojure.edn/read", &args, 0) {
+            Ok(options) => options,
+            Err(condition) => return condition,
+        };
+        edn::read(&mut io::stdin().lock(), options).unwrap_or_else(|condition| condition)
+    }
+}
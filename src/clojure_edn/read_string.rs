@@ -0,0 +1,64 @@
+use crate::clojure_edn::options;
+use crate::edn;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.edn/read-string ; the first form in s,  read as EDN;  nil (or opts' :eof) if
+/// there isn't one,  or s is nil.  opts' :max-depth and :max-elements limit how deep and
+/// how big that form may be
+/// (read-string s) (read-string opts s)
+#[derive(Debug, Clone)]
+pub struct ReadStringFn {}
+impl ToValue for ReadStringFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReadStringFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let options = match options("clojure.edn/read-string", &args, 1) {
+            Ok(options) => options,
+            Err(condition) => return condition,
+        };
+        let read = match &**args.last().unwrap() {
+            Value::String(s) => edn::read_str_with(s, options),
+            Value::Nil => edn::read_str_with("", options),
+            s => return error_message::type_mismatch(TypeTag::String, s),
+        };
+        read.unwrap_or_else(|condition| condition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn edn_is_read_with_readers_and_a_default() {
+        let result = Repl::default().eval_all(&[
+            "(defrecord Point [x y])",
+            "(def opts {:readers {'point (fn [v] (apply ->Point v))} :default (fn [tag form] [tag form]) :eof :none})",
+            "(def uuid (str \"#uuid \" \\\" \"0b1e7f3a-2c64-4e1f-9d38-5b1b0c6a7d42\" \\\"))",
+            "[(clojure.edn/read-string opts \"#point [1 2]\") (clojure.edn/read-string opts \"#my/tag {:a 1}\") (clojure.edn/read-string opts \"\") (clojure.edn/read-string nil) (clojure.edn/read-string uuid)]",
+        ]);
+        assert_eq!(
+            "[#user.Point{:x 1, :y 2} [my/tag {:a 1}] :none nil #uuid \"0b1e7f3a-2c64-4e1f-9d38-5b1b0c6a7d42\"]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn code_isnt_read_as_edn() {
+        let result = Repl::default().eval_all(&[
+            "(defn message [s] (ex-message (try (clojure.edn/read-string s) (catch Exception e e))))",
+            "[(message \"#(+ 1 2)\") (message \"#point [1 2]\")]",
+        ]);
+        assert_eq!(
+            "[\"Reader Error: could not read the form at NO_SOURCE_FILE:1:1\" \"No reader function for tag point\"]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,457 @@
+//! clojure.java.io;  files,  and readers and writers on them,  backed by std::fs
+//!
+//! Files,  readers and writers are host objects (see host_object.rs) named for the Java
+//! classes they stand in for;  so scripts can call (.readLine rdr),  (.write w s) and the like
+//! on them,  as in Clojure,  and with-open closes them.  *in*,  *out* and *err* are a reader
+//! and writers too.  Files are read and written as UTF-8,  unless :encoding says otherwise;
+//! see encoding.rs
+pub(crate) mod copy;
+pub(crate) mod delete_file;
+pub(crate) mod file;
+pub(crate) mod glob;
+pub(crate) mod reader;
+pub(crate) mod temp_file;
+pub(crate) mod walk;
+pub(crate) mod with_temp_dir;
+pub(crate) mod writer;
+
+use crate::dynamic;
+use crate::encoding::Encoding;
+use crate::error_message;
+use crate::host_object::HostObject;
+use crate::options::Opts;
+use crate::output::{self, Stream};
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::any::Any;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// A path;  what (file "a" "b") gives
+#[derive(Debug)]
+pub struct File {
+    pub path: PathBuf,
+}
+impl HostObject for File {
+    fn type_name(&self) -> &str {
+        "java.io.File"
+    }
+    fn call(&self, method: &str, args: Vec<Arc<Value>>) -> Option<Value> {
+        let path = &self.path;
+        let text = |path: &Path| path.display().to_string().to_value();
+        let val = match method {
+            "getPath" | "toString" => text(path),
+            "getName" => path.file_name().map_or(Value::from(""), |name| {
+                Value::from(name.to_string_lossy().into_owned())
+            }),
+            "getParent" => path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .map_or(Value::Nil, text),
+            "getAbsolutePath" => {
+                std::path::absolute(path).map_or_else(|_| text(path), |p| text(&p))
+            }
+            "exists" => Value::Boolean(path.exists()),
+            "isFile" => Value::Boolean(path.is_file()),
+            "isDirectory" => Value::Boolean(path.is_dir()),
+            "length" => Value::from(fs::metadata(path).map_or(0, |metadata| metadata.len() as i64)),
+            "lastModified" => Value::from(walk::modified(path)),
+            "delete" => Value::Boolean(delete(path).is_ok()),
+            "mkdirs" => Value::Boolean(!path.is_dir() && fs::create_dir_all(path).is_ok()),
+            _ => return None,
+        };
+        Some(match args.len() {
+            0 => val,
+            argc => error_message::wrong_arg_count(1, argc + 1),
+        })
+    }
+    fn display(&self) -> String {
+        self.path.display().to_string()
+    }
+}
+
+/// Something open for reading;  a file,  as (reader f) gives,  stdin,  as *in* starts as,
+/// or a string,  as with-in-str reads from
+#[derive(Debug)]
+pub struct Reader {
+    name: String,
+    type_name: &'static str,
+    input: Mutex<Input>,
+}
+#[derive(Debug)]
+enum Input {
+    File(BufReader<fs::File>),
+    // Read through std's own buffer,  shared with whatever else reads stdin;  so a script's
+    // read-line gets the line after the form the REPL read,  and no further
+    Stdin,
+    Text(io::Cursor<String>),
+    Closed,
+}
+impl Reader {
+    /// A reader on path,  its bytes read as encoding has them.  Anything but UTF-8 is
+    /// decoded all at once,  as it's opened
+    pub fn open(path: &Path, encoding: Encoding) -> Result<Reader, Value> {
+        let input = match encoding {
+            Encoding::Utf8 => fs::File::open(path).map(|file| Input::File(BufReader::new(file))),
+            encoding => {
+                fs::read(path).map(|bytes| Input::Text(io::Cursor::new(encoding.decode(&bytes))))
+            }
+        };
+        match input {
+            Ok(input) => Ok(Reader {
+                name: path.display().to_string(),
+                type_name: "java.io.BufferedReader",
+                input: Mutex::new(input),
+            }),
+            Err(e) => Err(io_error("open", path.display(), e)),
+        }
+    }
+    /// A reader on stdin;  what *in* is,  unless it's bound
+    pub fn stdin() -> Reader {
+        Reader {
+            name: String::from("*in*"),
+            type_name: "clojure.lang.LineNumberingPushbackReader",
+            input: Mutex::new(Input::Stdin),
+        }
+    }
+    /// A reader on text
+    pub fn on_string(text: &str) -> Reader {
+        Reader {
+            name: String::from("a string"),
+            type_name: "java.io.StringReader",
+            input: Mutex::new(Input::Text(io::Cursor::new(text.to_string()))),
+        }
+    }
+    /// The next line,  without its line ending;  None once there are no more
+    pub fn read_line(&self) -> Result<Option<String>, Value> {
+        let mut line = String::new();
+        let read = match &mut *self.input.lock().unwrap() {
+            Input::File(input) => input.read_line(&mut line),
+            Input::Stdin => io::stdin().read_line(&mut line),
+            Input::Text(input) => input.read_line(&mut line),
+            Input::Closed => return Err(closed(&self.name)),
+        };
+        match read {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                let len = line.trim_end_matches(['\n', '\r']).len();
+                line.truncate(len);
+                Ok(Some(line))
+            }
+            Err(e) => Err(io_error("read", &self.name, e)),
+        }
+    }
+    /// Whatever's left to read
+    pub fn read_to_end(&self) -> Result<String, Value> {
+        let mut text = String::new();
+        let read = match &mut *self.input.lock().unwrap() {
+            Input::File(input) => input.read_to_string(&mut text),
+            Input::Stdin => io::stdin().read_to_string(&mut text),
+            Input::Text(input) => input.read_to_string(&mut text),
+            Input::Closed => return Err(closed(&self.name)),
+        };
+        read.map(|_| text)
+            .map_err(|e| io_error("read", &self.name, e))
+    }
+    pub fn close(&self) {
+        *self.input.lock().unwrap() = Input::Closed;
+    }
+}
+impl HostObject for Reader {
+    fn type_name(&self) -> &str {
+        self.type_name
+    }
+    fn call(&self, method: &str, args: Vec<Arc<Value>>) -> Option<Value> {
+        let read = match (method, &args[..]) {
+            ("readLine", []) => self
+                .read_line()
+                .map(|line| line.map_or(Value::Nil, Value::from)),
+            ("close", []) => {
+                self.close();
+                Ok(Value::Nil)
+            }
+            ("readLine", _) | ("close", _) => Ok(error_message::wrong_arg_count(1, args.len() + 1)),
+            _ => return None,
+        };
+        Some(read.unwrap_or_else(|condition| condition))
+    }
+}
+
+/// Something open for writing;  a file,  as (writer f) gives,  stdout or stderr,  as *out*
+/// and *err* start as,  or a string,  as with-out-str writes to
+#[derive(Debug)]
+pub struct Writer {
+    name: String,
+    type_name: &'static str,
+    // What a file's written as;  strings and the standard streams are always UTF-8
+    encoding: Encoding,
+    output: Mutex<Output>,
+}
+#[derive(Debug)]
+enum Output {
+    File(BufWriter<fs::File>),
+    // Through crate::output,  so an nREPL session's output goes back to its client
+    Stream(Stream),
+    Text(String),
+    Closed,
+}
+impl Writer {
+    /// A writer on path,  writing text as encoding has it;  adding to what's there if append,
+    /// rather than replacing it.  UTF-16 starts with its byte order mark,  as in Java
+    pub fn open(path: &Path, append: bool, encoding: Encoding) -> Result<Writer, Value> {
+        let opened = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .append(append)
+            .truncate(!append)
+            .open(path);
+        let opened = opened.and_then(|file| {
+            let mut file = BufWriter::new(file);
+            match encoding {
+                Encoding::Utf16 => file
+                    .write_all(&[0xFE, 0xFF])
+                    .map(|()| (file, Encoding::Utf16Be)),
+                encoding => Ok((file, encoding)),
+            }
+        });
+        match opened {
+            Ok((file, encoding)) => Ok(Writer {
+                name: path.display().to_string(),
+                type_name: "java.io.BufferedWriter",
+                encoding,
+                output: Mutex::new(Output::File(file)),
+            }),
+            Err(e) => Err(io_error("open", path.display(), e)),
+        }
+    }
+    /// A writer on stdout or stderr;  what *out* and *err* are,  unless they're bound
+    pub fn on_stream(stream: Stream) -> Writer {
+        Writer {
+            name: String::from(match stream {
+                Stream::Out => "*out*",
+                Stream::Err => "*err*",
+            }),
+            type_name: "java.io.PrintWriter",
+            encoding: Encoding::Utf8,
+            output: Mutex::new(Output::Stream(stream)),
+        }
+    }
+    /// A writer on a string;  str gives what's been written to it
+    pub fn on_string() -> Writer {
+        Writer {
+            name: String::from("a string"),
+            type_name: "java.io.StringWriter",
+            encoding: Encoding::Utf8,
+            output: Mutex::new(Output::Text(String::new())),
+        }
+    }
+    pub fn write(&self, text: &str) -> Result<(), Value> {
+        let written = match &mut *self.output.lock().unwrap() {
+            Output::File(output) => match self.encoding {
+                Encoding::Utf8 => output.write_all(text.as_bytes()),
+                encoding => output.write_all(&encoding.encode(text)),
+            },
+            Output::Stream(stream) => {
+                output::write(*stream, text);
+                Ok(())
+            }
+            Output::Text(output) => {
+                output.push_str(text);
+                Ok(())
+            }
+            Output::Closed => return Err(closed(&self.name)),
+        };
+        written.map_err(|e| io_error("write", &self.name, e))
+    }
+    pub fn flush(&self) -> Result<(), Value> {
+        let flushed = match &mut *self.output.lock().unwrap() {
+            Output::File(output) => output.flush(),
+            Output::Stream(stream) => {
+                output::flush(*stream);
+                Ok(())
+            }
+            Output::Text(_) => Ok(()),
+            Output::Closed => return Err(closed(&self.name)),
+        };
+        flushed.map_err(|e| io_error("write", &self.name, e))
+    }
+    /// Flushes what's still buffered,  and closes the file;  closing it again does nothing.
+    /// stdout and stderr are only flushed,  and a string's left as it is
+    pub fn close(&self) -> Result<(), Value> {
+        let mut output = self.output.lock().unwrap();
+        match &mut *output {
+            Output::File(file) => {
+                let flushed = file.flush();
+                *output = Output::Closed;
+                flushed.map_err(|e| io_error("write", &self.name, e))
+            }
+            Output::Stream(stream) => {
+                output::flush(*stream);
+                Ok(())
+            }
+            Output::Text(_) | Output::Closed => Ok(()),
+        }
+    }
+}
+impl HostObject for Writer {
+    fn type_name(&self) -> &str {
+        self.type_name
+    }
+    fn call(&self, method: &str, args: Vec<Arc<Value>>) -> Option<Value> {
+        let written = match (method, &args[..]) {
+            ("write", [x]) | ("append", [x]) => self.write(&x.to_string()),
+            ("newLine", []) => self.write("\n"),
+            ("flush", []) => self.flush(),
+            ("close", []) => self.close(),
+            ("write" | "append" | "newLine" | "flush" | "close", _) => {
+                return Some(error_message::wrong_arg_count(1, args.len() + 1))
+            }
+            _ => return None,
+        };
+        Some(written.map_or_else(|condition| condition, |()| Value::Nil))
+    }
+    fn display(&self) -> String {
+        match &*self.output.lock().unwrap() {
+            Output::Text(text) => text.clone(),
+            _ => format!("#object[{}]", self.type_name),
+        }
+    }
+}
+
+/// What's said to do with a file;  reader,  writer,  slurp and spit take these
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Whether to add to what a file holds,  rather than replacing it
+    pub append: bool,
+    /// What a file's text is read or written as
+    pub encoding: Encoding,
+}
+
+/// The options in args,  given to name as :key val pairs;  :append,  and :encoding,  the
+/// name of one of encoding.rs's
+pub fn options(name: &str, args: &[Arc<Value>]) -> Result<Options, Value> {
+    let mut options = Options::default();
+    for (option, val) in Opts::from_pairs(name, &["append", "encoding"], args)?.iter() {
+        match (option, &**val) {
+            ("append", val) => options.append = val.is_truthy(),
+            (_, Value::String(encoding)) => options.encoding = Encoding::named(encoding)?,
+            (_, val) => return Err(error_message::type_mismatch(TypeTag::String, val)),
+        }
+    }
+    Ok(options)
+}
+
+/// What x is,  if it's one of this namespace's
+pub fn downcast<T: HostObject>(x: &Value) -> Option<&T> {
+    match x {
+        Value::HostObject(object) => (&***object as &dyn Any).downcast_ref::<T>(),
+        _ => None,
+    }
+}
+
+/// The path x names;  as a string,  or a file
+pub fn as_path(x: &Value) -> Result<PathBuf, Value> {
+    match x {
+        Value::String(path) => Ok(PathBuf::from(&**path)),
+        _ => match downcast::<File>(x) {
+            Some(file) => Ok(file.path.clone()),
+            None => Err(Value::Condition(
+                format!("Cannot open {} as a file", x.to_string_explicit()).into(),
+            )),
+        },
+    }
+}
+
+/// All there is to read from x;  a reader (what's left of it),  or the file a path names,
+/// read as encoding has it
+pub fn read_all(x: &Value, encoding: Encoding) -> Result<String, Value> {
+    match downcast::<Reader>(x) {
+        Some(reader) => reader.read_to_end(),
+        None => read_bytes(x).map(|bytes| encoding.decode(&bytes)),
+    }
+}
+
+/// The bytes of the file x names;  a string,  or a file
+pub fn read_bytes(x: &Value) -> Result<Vec<u8>, Value> {
+    let path = as_path(x)?;
+    fs::read(&path).map_err(|e| io_error("read", path.display(), e))
+}
+
+/// Writes text to *out*,  as print does;  to whatever it's bound to,  or stdout
+pub fn print(text: &str) -> Result<(), Value> {
+    match bound("*out*") {
+        Some(out) => as_writer(&out)?.write(text),
+        None => {
+            output::write(Stream::Out, text);
+            Ok(())
+        }
+    }
+}
+
+/// Writes text to *err*;  to whatever it's bound to,  or stderr
+pub fn print_err(text: &str) -> Result<(), Value> {
+    match bound("*err*") {
+        Some(err) => as_writer(&err)?.write(text),
+        None => {
+            output::write(Stream::Err, text);
+            Ok(())
+        }
+    }
+}
+
+/// Flushes *out*
+pub fn flush_out() -> Result<(), Value> {
+    match bound("*out*") {
+        Some(out) => as_writer(&out)?.flush(),
+        None => {
+            output::flush(Stream::Out);
+            Ok(())
+        }
+    }
+}
+
+/// The next line of *in*,  as read-line reads it;  from whatever it's bound to,  or stdin
+pub fn read_line() -> Result<Option<String>, Value> {
+    match bound("*in*") {
+        Some(input) => match downcast::<Reader>(&input) {
+            Some(reader) => reader.read_line(),
+            None => Err(Value::Condition(
+                format!("Cannot read from {}", input.to_string_explicit()).into(),
+            )),
+        },
+        None => Reader::stdin().read_line(),
+    }
+}
+
+// What the standard stream name,  *out* say,  is bound to on this thread;  None if it isn't
+fn bound(name: &str) -> Option<Arc<Value>> {
+    dynamic::bound(&Symbol::intern("clojure.core"), &Symbol::intern(name))
+}
+
+/// x,  if it's a writer
+pub fn as_writer(x: &Value) -> Result<&Writer, Value> {
+    downcast::<Writer>(x).ok_or_else(|| {
+        Value::Condition(format!("Cannot write to {}", x.to_string_explicit()).into())
+    })
+}
+
+/// Removes the file,  or empty directory,  at path
+pub fn delete(path: &Path) -> io::Result<()> {
+    match path.is_dir() {
+        true => fs::remove_dir(path),
+        false => fs::remove_file(path),
+    }
+}
+
+/// Couldn't do what was being done to name (a path,  say);  as a condition
+pub fn io_error(doing: &str, name: impl fmt::Display, e: io::Error) -> Value {
+    Value::Condition(format!("Could not {} {}: {}", doing, name, e).into())
+}
+
+fn closed(name: &str) -> Value {
+    Value::Condition(format!("{} is closed", name).into())
+}
@@ -0,0 +1,51 @@
+use crate::clojure_java_io::{self, Writer};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::fs;
+use std::sync::Arc;
+
+/// (copy input output & opts)
+///
+/// Copies input to output.  input is a string (copied as it is),  a file,  or a reader
+/// (all that's left of it);  output is a file (replacing what it holds) or a writer.  Files
+/// are read and written as UTF-8,  or as :encoding says
+#[derive(Debug, Clone)]
+pub struct CopyFn {}
+impl ToValue for CopyFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CopyFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let options = match clojure_java_io::options("clojure.java.io/copy", &args[2..]) {
+            Ok(options) => options,
+            Err(condition) => return condition,
+        };
+        let text = match &*args[0] {
+            Value::String(text) => Ok(text.to_string()),
+            input => clojure_java_io::read_all(input, options.encoding),
+        };
+        let copied = text.and_then(|text| match clojure_java_io::downcast::<Writer>(&args[1]) {
+            Some(writer) => writer.write(&text),
+            None => match &*args[1] {
+                // As in Clojure;  a string's the input's content,  not a path,  so it's not one here
+                Value::String(_) => Err(Value::Condition(
+                    "Cannot copy to a string;  give a file or a writer".into(),
+                )),
+                output => clojure_java_io::as_path(output).and_then(|path| {
+                    fs::write(&path, options.encoding.encode(&text))
+                        .map_err(|e| clojure_java_io::io_error("write", path.display(), e))
+                }),
+            },
+        });
+        match copied {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
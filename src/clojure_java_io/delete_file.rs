@@ -0,0 +1,33 @@
+use crate::clojure_java_io;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (delete-file f & [silently])
+///
+/// Deletes the file,  or empty directory,  f names (a string,  or a file);  true.  If it
+/// can't be,  that's a condition;  unless silently is given,  when it's silently
+#[derive(Debug, Clone)]
+pub struct DeleteFileFn {}
+impl ToValue for DeleteFileFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DeleteFileFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let path = match clojure_java_io::as_path(&args[0]) {
+            Ok(path) => path,
+            Err(condition) => return condition,
+        };
+        match (clojure_java_io::delete(&path), args.get(1)) {
+            (Ok(()), _) => Value::Boolean(true),
+            (Err(_), Some(silently)) => silently.to_value(),
+            (Err(e), None) => clojure_java_io::io_error("delete", path.display(), e),
+        }
+    }
+}
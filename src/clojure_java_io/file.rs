@@ -0,0 +1,54 @@
+use crate::clojure_java_io::{self, File};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (file x & more)
+///
+/// The file at the path x names (a string,  or a file),  with each of more added on;  so
+/// (file "src" "core.clj") is src/core.clj
+#[derive(Debug, Clone)]
+pub struct FileFn {}
+impl ToValue for FileFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FileFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let mut path = match clojure_java_io::as_path(&args[0]) {
+            Ok(path) => path,
+            Err(condition) => return condition,
+        };
+        for more in args[1..].iter() {
+            match &**more {
+                Value::String(more) => path.push(&**more),
+                _ => return error_message::type_mismatch(TypeTag::String, more),
+            }
+        }
+        Value::host_object(File { path })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn a_file_is_its_path_joined() {
+        assert_eq!(
+            "[\"src/clojure/core.clj\" \"core.clj\" \"src/clojure\" true java.io.File]",
+            Repl::default()
+                .eval_all(&[
+                    "(def f (clojure.java.io/file (clojure.java.io/file \"src\") \"clojure\" \"core.clj\"))",
+                    "[(str f) (.getName f) (.getParent f) (.exists f) (type f)]",
+                ])
+                .to_string_explicit()
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::clojure_java_io::{self, walk};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+/// (glob pattern)
+/// (glob root pattern)
+///
+/// A lazy seq of the paths,  as strings,  of the files and directories that match pattern;
+/// under root (a string,  or a file),  if it's given.  In pattern,  a / separates the
+/// names of directories,  and in each name
+///
+/// ```text
+///  *        matches any run of characters
+///  ?        any one character
+///  [abc]    any of those;  [a-z] any in that range,  and [!a-z] any not
+///  {a,b}    either a or b (each a pattern itself)
+/// ```
+///
+/// while ** on its own matches any number of directories,  none included.  So
+/// (glob "src/**/*.{clj,cljc}") is the Clojure files under src,  however deep
+#[derive(Debug, Clone)]
+pub struct GlobFn {}
+impl ToValue for GlobFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for GlobFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (root, pattern) = match &args[..] {
+            [pattern] => (None, pattern),
+            [root, pattern] => match clojure_java_io::as_path(root) {
+                Ok(root) => (Some(root), pattern),
+                Err(condition) => return condition,
+            },
+            _ => return error_message::wrong_varg_count(&[1, 2], args.len()),
+        };
+        let pattern = match &**pattern {
+            Value::String(pattern) => pattern,
+            _ => return error_message::type_mismatch(TypeTag::String, pattern),
+        };
+        // Where the walk starts;  root,  and as much of the pattern as has no wildcards
+        let mut base = root.unwrap_or_default();
+        let mut parts = vec![];
+        for part in pattern.split('/') {
+            match part {
+                "" if base.as_os_str().is_empty() && parts.is_empty() => base.push("/"),
+                "" => {}
+                part if parts.is_empty() && !is_wild(part) => base.push(part),
+                part => parts.push(part.to_string()),
+            }
+        }
+        if parts.is_empty() {
+            // Just the one path,  if it's there
+            let keep: walk::Keep = Arc::new(|path| {
+                Ok(path
+                    .exists()
+                    .then(|| Value::from(path.display().to_string())))
+            });
+            return walk::tree(vec![base], Arc::new(|_| false), keep).into_value();
+        }
+        let (parts, start) = (Arc::new(parts), base.clone());
+        let relative = move |path: &Path| -> Vec<String> {
+            let relative = path.strip_prefix(&start).unwrap_or(path);
+            relative
+                .components()
+                .filter_map(|component| match component {
+                    Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                    _ => None,
+                })
+                .collect()
+        };
+        let (descend_parts, descend_relative) = (Arc::clone(&parts), relative.clone());
+        let descend: walk::Descend =
+            Arc::new(move |path| matches(&descend_parts, &descend_relative(path), true));
+        // Paths under the current directory are given as they are in the pattern,  without a ./
+        let here = base.as_os_str().is_empty();
+        let keep: walk::Keep = Arc::new(move |path| {
+            let names = relative(path);
+            Ok(match !names.is_empty() && matches(&parts, &names, false) {
+                true if here => Some(Value::from(names.join("/"))),
+                true => Some(Value::from(path.display().to_string())),
+                false => None,
+            })
+        });
+        let walked = match here {
+            true => PathBuf::from("."),
+            false => base,
+        };
+        walk::tree(vec![walked], descend, keep).into_value()
+    }
+}
+
+fn is_wild(part: &str) -> bool {
+    part.contains(['*', '?', '[', '{'])
+}
+
+/// Whether names,  the names in a path,  match the pattern's parts;  or,  if partly,
+/// whether more names after them could
+pub fn matches(parts: &[String], names: &[String], partly: bool) -> bool {
+    match (parts.split_first(), names.split_first()) {
+        (_, None) => partly || parts.iter().all(|part| part == "**"),
+        (None, Some(_)) => false,
+        (Some((part, rest)), Some((_, rest_names))) if part == "**" => {
+            matches(rest, names, partly) || matches(parts, rest_names, partly)
+        }
+        (Some((part, rest)), Some((name, rest_names))) => {
+            let (part, name) = (
+                part.chars().collect::<Vec<char>>(),
+                name.chars().collect::<Vec<char>>(),
+            );
+            matches_name(&part, &name) && matches(rest, rest_names, partly)
+        }
+    }
+}
+
+// Whether name matches the pattern,  one of the names in a path
+fn matches_name(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| matches_name(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && matches_name(rest, &name[1..]),
+        Some(('[', rest)) => match (rest.iter().position(|c| *c == ']'), name.first()) {
+            (Some(end), Some(c)) => {
+                in_class(&rest[..end], *c) && matches_name(&rest[end + 1..], &name[1..])
+            }
+            (Some(_), None) => false,
+            (None, _) => name.first() == Some(&'[') && matches_name(rest, &name[1..]),
+        },
+        Some(('{', rest)) => match rest.iter().position(|c| *c == '}') {
+            Some(end) => rest[..end].split(|c| *c == ',').any(|alternative| {
+                let mut pattern = alternative.to_vec();
+                pattern.extend_from_slice(&rest[end + 1..]);
+                matches_name(&pattern, name)
+            }),
+            None => name.first() == Some(&'{') && matches_name(rest, &name[1..]),
+        },
+        Some((c, rest)) => name.first() == Some(c) && matches_name(rest, &name[1..]),
+    }
+}
+
+// Whether c is in the class;  what's between a [ and its ]
+fn in_class(class: &[char], c: char) -> bool {
+    let (negated, class) = match class.split_first() {
+        Some(('!', class)) | Some(('^', class)) => (true, class),
+        _ => (false, class),
+    };
+    let mut found = false;
+    let mut ind = 0;
+    while ind < class.len() {
+        if ind + 2 < class.len() && class[ind + 1] == '-' {
+            found |= class[ind] <= c && c <= class[ind + 2];
+            ind += 3;
+        } else {
+            found |= class[ind] == c;
+            ind += 1;
+        }
+    }
+    found != negated
+}
+
+#[cfg(test)]
+mod tests {
+    mod glob_tests {
+        use crate::clojure_java_io::glob::matches;
+        use crate::repl::Repl;
+
+        fn strings(s: &str) -> Vec<String> {
+            s.split('/').map(String::from).collect()
+        }
+
+        #[test]
+        fn patterns_match_names_part_by_part() {
+            let glob =
+                |pattern: &str, path: &str| matches(&strings(pattern), &strings(path), false);
+            assert!(glob("**/*.clj", "core.clj"));
+            assert!(glob("src/**/*.clj", "src/a/b/core.clj"));
+            assert!(!glob("src/*.clj", "src/a/core.clj"));
+            assert!(glob("*.{clj,cljc}", "core.cljc"));
+            assert!(glob("?[a-c][!x].txt", "zby.txt"));
+            assert!(!glob("?[a-c][!x].txt", "zbx.txt"));
+            assert!(matches(&strings("src/*/x.clj"), &strings("src/a"), true));
+            assert!(!matches(&strings("src/*/x.clj"), &strings("test"), true));
+        }
+
+        #[test]
+        fn glob_gives_the_paths_that_match() {
+            let result = Repl::default().eval_all(&[
+                "(require '[clojure.java.io :as io])",
+                "(io/with-temp-dir [d] (.mkdirs (io/file d \"src\" \"app\")) (spit (io/file d \"src\" \"app\" \"core.clj\") \"\") (spit (io/file d \"src\" \"main.cljc\") \"\") (spit (io/file d \"README.md\") \"\")
+                   (let [root (.getPath d) names (fn [paths] (mapv #(subs % (inc (count root))) paths))]
+                     [(names (io/glob d \"**/*.{clj,cljc}\")) (names (io/glob d \"src/*\")) (names (io/glob (str root \"/*.md\"))) (names (io/glob d \"README.md\")) (into [] (io/glob d \"nothing/here\"))]))",
+            ]);
+            assert_eq!(
+                "[[\"src/app/core.clj\" \"src/main.cljc\"] [\"src/app\" \"src/main.cljc\"] [\"README.md\"] [\"README.md\"] []]",
+                result.to_string()
+            );
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::clojure_java_io::{self, Reader};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (reader x & opts)
+///
+/// A reader on the file x names (a string,  or a file);  or x,  if it's a reader already.
+/// The file's read as UTF-8,  or as :encoding says;  ie :encoding "ISO-8859-1"
+#[derive(Debug, Clone)]
+pub struct ReaderFn {}
+impl ToValue for ReaderFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReaderFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let options = match clojure_java_io::options("clojure.java.io/reader", &args[1..]) {
+            Ok(options) => options,
+            Err(condition) => return condition,
+        };
+        if clojure_java_io::downcast::<Reader>(&args[0]).is_some() {
+            return args[0].to_value();
+        }
+        match clojure_java_io::as_path(&args[0])
+            .and_then(|path| Reader::open(&path, options.encoding))
+        {
+            Ok(reader) => Value::host_object(reader),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::clojure_java_io::{self, File};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// (temp-file prefix suffix & [dir])
+///
+/// A new,  empty file,  named prefix,  something unique,  then suffix (.tmp if it's nil);  in
+/// dir,  or the system's temp directory.  It's deleted as we exit,  if it's still there;  or
+/// made in a with-temp-dir's dir,  it goes when that does
+#[derive(Debug, Clone)]
+pub struct TempFileFn {}
+impl ToValue for TempFileFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TempFileFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 || args.len() > 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
+        }
+        let prefix = match &*args[0] {
+            Value::String(prefix) => prefix.to_string(),
+            _ => return error_message::type_mismatch(TypeTag::String, &args[0]),
+        };
+        let suffix = match &*args[1] {
+            Value::String(suffix) => suffix.to_string(),
+            Value::Nil => String::from(".tmp"),
+            _ => return error_message::type_mismatch(TypeTag::String, &args[1]),
+        };
+        let dir = match args.get(2).map(|dir| clojure_java_io::as_path(dir)) {
+            Some(Ok(dir)) => dir,
+            Some(Err(condition)) => return condition,
+            None => std::env::temp_dir(),
+        };
+        let create = |path: &Path| {
+            fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)
+                .map(|_| ())
+        };
+        match create_unique(&dir, &prefix, &suffix, create) {
+            Ok(path) => Value::host_object(File { path }),
+            Err(condition) => condition,
+        }
+    }
+}
+
+fn made() -> &'static Mutex<Vec<PathBuf>> {
+    static MADE: Mutex<Vec<PathBuf>> = Mutex::new(vec![]);
+    &MADE
+}
+
+/// A path in dir no file has,  named prefix,  something unique,  then suffix;  that create
+/// has made something at,  and that's deleted as we exit
+pub fn create_unique(
+    dir: &Path,
+    prefix: &str,
+    suffix: &str,
+    create: impl Fn(&Path) -> io::Result<()>,
+) -> Result<PathBuf, Value> {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.subsec_nanos());
+        let unique = format!(
+            "{}-{}-{}",
+            process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed),
+            nanos
+        );
+        let path = dir.join(format!("{}{}{}", prefix, unique, suffix));
+        match create(&path) {
+            Ok(()) => {
+                made().lock().unwrap().push(path.clone());
+                return Ok(path);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(clojure_java_io::io_error("create", path.display(), e)),
+        }
+    }
+}
+
+/// Removes path,  and,  if it's a directory,  everything in it
+pub fn delete_tree(path: &Path) -> io::Result<()> {
+    match path.is_dir() {
+        true => fs::remove_dir_all(path),
+        false => fs::remove_file(path),
+    }
+}
+
+/// Deletes each temp file and directory made,  that's still there;  as we exit
+pub fn delete_all() {
+    for path in made().lock().unwrap().drain(..).rev() {
+        let _ = delete_tree(&path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn temp_files_are_new_and_empty() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.io :as io] '[clojure.string :as str])",
+            "(def a (io/temp-file \"crate-\" \".txt\"))",
+            "(def b (io/temp-file \"crate-\" nil))",
+            "(def found [(.exists a) (.length a) (= (.getPath a) (.getPath b)) (str/ends-with? (.getName b) \".tmp\") (str/starts-with? (.getName a) \"crate-\")])",
+            "(io/delete-file a)",
+            "(io/delete-file b)",
+            "found",
+        ]);
+        assert_eq!("[true 0 false true true]", result.to_string());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::clojure_java_io;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq::{self, LazySeq, Step};
+use crate::numbers;
+use crate::options::Opts;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+/// What a walk yields for a path;  None to skip it
+pub type Keep = Arc<dyn Fn(&Path) -> Result<Option<Value>, Value> + Send + Sync>;
+/// Whether a walk looks into a directory
+pub type Descend = Arc<dyn Fn(&Path) -> bool + Send + Sync>;
+
+/// A lazy seq of what keep yields for each path in pending,  and each path in those that
+/// are directories descend looks into,  and so on;  depth first,  a directory before what's
+/// in it,  and what's in it in order of name.  Directories that can't be read look empty,
+/// as they do to file-seq.  Links are followed,  but a directory already looked into isn't
+/// looked into again,  so a link to one of its own parents doesn't go round forever
+pub fn tree(pending: Vec<PathBuf>, descend: Descend, keep: Keep) -> LazySeq {
+    walk(pending, HashSet::new(), descend, keep)
+}
+
+// visited holds each directory looked into so far,  as its canonical path
+fn walk(
+    mut pending: Vec<PathBuf>,
+    mut visited: HashSet<PathBuf>,
+    descend: Descend,
+    keep: Keep,
+) -> LazySeq {
+    LazySeq::new(move || {
+        while let Some(path) = pending.pop() {
+            if path.is_dir() && descend(&path) && first_visit(&path, &mut visited) {
+                if let Ok(entries) = fs::read_dir(&path) {
+                    let mut children = entries
+                        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                        .collect::<Vec<PathBuf>>();
+                    children.sort_by(|a, b| b.cmp(a));
+                    pending.extend(children);
+                }
+            }
+            if let Some(val) = keep(&path)? {
+                let rest = walk(pending, visited, descend, keep);
+                return Ok(Step::Next(Arc::new(val), Arc::new(rest.into_value())));
+            }
+        }
+        Ok(Step::Done)
+    })
+}
+
+fn first_visit(dir: &Path, visited: &mut HashSet<PathBuf>) -> bool {
+    match fs::canonicalize(dir) {
+        Ok(canonical) => visited.insert(canonical),
+        Err(_) => false,
+    }
+}
+
+/// When the file at path was last modified,  in milliseconds since the epoch;  0 if that
+/// can't be told
+pub fn modified(path: &Path) -> i64 {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |since| since.as_millis() as i64)
+}
+
+/// (walk dir & options)
+///
+/// A lazy seq of the paths,  as strings,  of the files under dir (a string,  or a file),
+/// however deep;  those that pass each of the options given
+///
+///  :ext "clj",  or ["clj" "cljc"]    ends in that extension,  or one of them
+///  :min-size n,  :max-size n        is at least,  or at most,  n bytes long
+///  :modified-after t,  :modified-before t
+///                                   was last modified after,  or before,  t;  an #inst,
+///                                   or milliseconds since the epoch
+///  :pred f                          (f path) is truthy
+#[derive(Debug, Clone)]
+pub struct WalkFn {}
+impl ToValue for WalkFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for WalkFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let dir = match clojure_java_io::as_path(&args[0]) {
+            Ok(dir) => dir,
+            Err(condition) => return condition,
+        };
+        let filters = match filters(&args[1..]) {
+            Ok(filters) => Arc::new(filters),
+            Err(condition) => return condition,
+        };
+        let keep: Keep = Arc::new(move |path| {
+            Ok(match path.is_file() && filters.pass(path)? {
+                true => Some(Value::from(path.display().to_string())),
+                false => None,
+            })
+        });
+        tree(vec![dir], Arc::new(|_| true), keep).into_value()
+    }
+}
+
+#[derive(Default)]
+struct Filters {
+    exts: Option<Vec<String>>,
+    min_size: Option<f64>,
+    max_size: Option<f64>,
+    after: Option<f64>,
+    before: Option<f64>,
+    pred: Option<Arc<Value>>,
+}
+impl Filters {
+    fn pass(&self, path: &Path) -> Result<bool, Value> {
+        if let Some(exts) = &self.exts {
+            let ext = path.extension().map(|ext| ext.to_string_lossy());
+            if !exts.iter().any(|wanted| ext.as_deref() == Some(wanted)) {
+                return Ok(false);
+            }
+        }
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(_) => return Ok(false),
+        };
+        let (size, modified) = (metadata.len() as f64, modified(path) as f64);
+        let within = |bound: Option<f64>, at_most: bool, n: f64| match bound {
+            Some(bound) if at_most => n <= bound,
+            Some(bound) => n >= bound,
+            None => true,
+        };
+        if !within(self.min_size, false, size)
+            || !within(self.max_size, true, size)
+            || self.after.is_some_and(|after| modified <= after)
+            || self.before.is_some_and(|before| modified >= before)
+        {
+            return Ok(false);
+        }
+        match &self.pred {
+            Some(pred) => {
+                let path = Value::from(path.display().to_string()).to_rc_value();
+                match ifn::call(pred, vec![path]) {
+                    Value::Condition(condition) => Err(Value::Condition(condition)),
+                    passed => Ok(passed.is_truthy()),
+                }
+            }
+            None => Ok(true),
+        }
+    }
+}
+
+fn filters(args: &[Arc<Value>]) -> Result<Filters, Value> {
+    let known = &[
+        "ext",
+        "min-size",
+        "max-size",
+        "modified-after",
+        "modified-before",
+        "pred",
+    ];
+    let mut filters = Filters::default();
+    for (option, val) in Opts::from_pairs("clojure.java.io/walk", known, args)?.iter() {
+        let number = || match &**val {
+            Value::Inst(millis) => Ok(*millis as f64),
+            val => {
+                numbers::to_f64(val).ok_or_else(|| error_message::type_mismatch(TypeTag::I32, val))
+            }
+        };
+        match option {
+            "ext" => filters.exts = Some(extensions(val)?),
+            "min-size" => filters.min_size = Some(number()?),
+            "max-size" => filters.max_size = Some(number()?),
+            "modified-after" => filters.after = Some(number()?),
+            "modified-before" => filters.before = Some(number()?),
+            _ if ifn::is_callable(val) => filters.pred = Some(Arc::clone(val)),
+            _ => return Err(error_message::type_mismatch(TypeTag::IFn, val)),
+        }
+    }
+    Ok(filters)
+}
+
+// The extensions val names,  without their dots;  one,  or a seq of them
+fn extensions(val: &Arc<Value>) -> Result<Vec<String>, Value> {
+    let mut exts = vec![];
+    let mut push = |ext: &Arc<Value>| match &**ext {
+        Value::String(ext) => {
+            exts.push(ext.trim_start_matches('.').to_string());
+            Ok(())
+        }
+        _ => Err(error_message::type_mismatch(TypeTag::String, ext)),
+    };
+    match &**val {
+        Value::String(_) => push(val)?,
+        _ => lazy_seq::try_for_each(val, |ext| push(&ext))?,
+    }
+    Ok(exts)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn walk_gives_the_files_that_pass_each_filter() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.io :as io])",
+            "(io/with-temp-dir [d] (spit (io/file d \"b.clj\") \"(ns b)\") (spit (io/file d \"a.txt\") \"\") (.mkdirs (io/file d \"src\" \"x\")) (spit (io/file d \"src\" \"x\" \"c.cljc\") \"(ns c)\") (spit (io/file d \"src\" \"d.clj\") \"\")
+               (let [names (fn [paths] (mapv #(subs % (inc (count (.getPath d)))) paths))]
+                 [(names (io/walk d)) (names (io/walk d :ext [\"clj\" \".cljc\"] :min-size 1)) (names (io/walk (.getPath d) :pred #(= \"a.txt\" (.getName (io/file %))) :modified-after 0))]))",
+        ]);
+        assert_eq!(
+            "[[\"a.txt\" \"b.clj\" \"src/d.clj\" \"src/x/c.cljc\"] [\"b.clj\" \"src/x/c.cljc\"] [\"a.txt\"]]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn a_link_to_a_parent_is_not_walked_round_forever() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.io :as io] '[clojure.java.shell :refer [sh]])",
+            "(io/with-temp-dir [d] (.mkdirs (io/file d \"src\")) (spit (io/file d \"src\" \"a.clj\") \"\") (sh \"ln\" \"-s\" \"..\" (.getPath (io/file d \"src\" \"loop\")))
+               (let [names (fn [paths] (mapv #(subs % (inc (count (.getPath d)))) paths))]
+                 [(names (io/walk d)) (names (io/glob d \"**/*.clj\")) (count (file-seq d))]))",
+        ]);
+        assert_eq!("[[\"src/a.clj\"] [\"src/a.clj\"] 4]", result.to_string());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::clojure_java_io::{self, temp_file, File};
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::interrupt;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::ToPersistentVector;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::fs;
+use std::sync::Arc;
+
+/// (with-temp-dir [d] body)
+/// (with-temp-dir [d prefix] body)
+///
+/// Evaluates body with d bound to a new,  empty directory in the system's temp directory
+/// (its name starting with prefix,  if given);  then deletes it,  and all that's in it,
+/// whether body returned or threw
+///
+/// (with-temp-dir [d "app-"] x y) expands to
+///
+/// (clojure.java.io/with-temp-dir* "app-" (fn [d] x y))
+#[derive(Debug, Clone)]
+pub struct WithTempDirMacro {}
+impl ToValue for WithTempDirMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for WithTempDirMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (binding, body) = match args.split_first() {
+            Some((binding, body)) => (binding, body),
+            None => return error_message::wrong_varg_count(&[1], 0),
+        };
+        let (name, prefix) = match &**binding {
+            Value::PersistentVector(pvector) if pvector.len() == 1 || pvector.len() == 2 => (
+                Arc::clone(pvector.nth(0).unwrap()),
+                pvector
+                    .nth(1)
+                    .cloned()
+                    .unwrap_or_else(|| Arc::new(Value::Nil)),
+            ),
+            _ => {
+                return Value::Condition(
+                    "with-temp-dir binds a name,  as in [d] or [d \"app-\"]".into(),
+                )
+            }
+        };
+        let mut f = vec![
+            Symbol::intern("fn").to_rc_value(),
+            vec![name].into_vector().to_rc_value(),
+        ];
+        f.extend_from_slice(body);
+        vec![
+            Symbol::intern_with_ns("clojure.java.io", "with-temp-dir*").to_rc_value(),
+            prefix,
+            f.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+/// (with-temp-dir* prefix f) ; what with-temp-dir expands to
+///
+/// Calls f with a new temp directory,  then deletes it
+#[derive(Debug, Clone)]
+pub struct WithTempDirFn {}
+impl ToValue for WithTempDirFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for WithTempDirFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let prefix = match &*args[0] {
+            Value::String(prefix) => prefix.to_string(),
+            Value::Nil => String::new(),
+            _ => return error_message::type_mismatch(TypeTag::String, &args[0]),
+        };
+        let dir = match temp_file::create_unique(&std::env::temp_dir(), &prefix, "", |path| {
+            fs::create_dir(path)
+        }) {
+            Ok(dir) => dir,
+            Err(condition) => return condition,
+        };
+        let file = Value::host_object(File { path: dir.clone() });
+        let result = ifn::call(&args[1], vec![file.to_rc_value()]);
+        // Deleted as it would be were we exiting,  interrupted or not;  a condition thrown by
+        // the body is what we throw,  over one deleting it
+        interrupt::uninterruptibly(|| match (temp_file::delete_tree(&dir), result) {
+            (Err(e), result) if !matches!(result, Value::Condition(_)) => {
+                clojure_java_io::io_error("delete", dir.display(), e)
+            }
+            (_, result) => result,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn the_dir_and_all_in_it_is_gone_after_the_body() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.io :as io])",
+            "(def inside (atom []))",
+            "(def made (io/with-temp-dir [d] (spit (io/file d \"a.txt\") \"a\") (io/temp-file \"b\" nil d) (swap! inside conj (.isDirectory d) (slurp (io/file d \"a.txt\"))) d))",
+            "(def thrown (try (io/with-temp-dir [d \"crate-\"] (reset! inside d) (throw (ex-info \"body failed\" {}))) (catch Exception e (ex-message e))))",
+            "[(.exists made) (.exists @inside) thrown]",
+        ]);
+        assert_eq!("[false false \"body failed\"]", result.to_string());
+    }
+}
@@ -0,0 +1,59 @@
+use crate::clojure_java_io::{self, Writer};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (writer x & opts)
+///
+/// A writer on the file x names (a string,  or a file);  or x,  if it's a writer already.
+/// With :append true,  what's written is added to what the file holds,  rather than
+/// replacing it.  Text's written as UTF-8,  or as :encoding says;  ie :encoding "UTF-16"
+#[derive(Debug, Clone)]
+pub struct WriterFn {}
+impl ToValue for WriterFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for WriterFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let options = match clojure_java_io::options("clojure.java.io/writer", &args[1..]) {
+            Ok(options) => options,
+            Err(condition) => return condition,
+        };
+        if clojure_java_io::downcast::<Writer>(&args[0]).is_some() {
+            return args[0].to_value();
+        }
+        match clojure_java_io::as_path(&args[0])
+            .and_then(|path| Writer::open(&path, options.append, options.encoding))
+        {
+            Ok(writer) => Value::host_object(writer),
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn what_a_writer_writes_a_reader_reads() {
+        let path = env::temp_dir().join(format!("writer_test_{}.txt", std::process::id()));
+        let path = path.display().to_string();
+        let result = Repl::default().eval_all(&[
+            &format!("(def path \"{}\")", path),
+            "(with-open [w (clojure.java.io/writer path)] (.write w \"one\") (.newLine w))",
+            "(with-open [w (clojure.java.io/writer path :append true)] (.write w 2))",
+            "(with-open [r (clojure.java.io/reader path)] (into [] (line-seq r)))",
+        ]);
+        let _ = fs::remove_file(&path);
+        assert_eq!("[\"one\" \"2\"]", result.to_string_explicit());
+    }
+}
@@ -0,0 +1,114 @@
+//! clojure.java.shell;  running other programs,  backed by std::process
+//!
+//! sh runs one to the end,  and gives back all it wrote;  sh-pipe runs one or more,  each
+//! reading what the one before it writes,  and gives the lines the last writes as they
+//! come,  so none of it has to be held at once
+pub(crate) mod sh;
+pub(crate) mod sh_pipe;
+
+use crate::clojure_java_io;
+use crate::error_message;
+use crate::lazy_seq;
+use crate::options::Opts;
+use crate::type_tag::TypeTag;
+use crate::value::Value;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Command};
+use std::sync::Arc;
+use std::thread;
+
+/// What's said about how to run a program;  sh and sh-pipe take these
+#[derive(Debug, Default)]
+pub struct Options {
+    /// What it's given to read;  nothing,  if this is None
+    pub input: Option<String>,
+    /// The directory it's run in;  ours,  if this is None
+    pub dir: Option<PathBuf>,
+    /// All the environment variables it has;  ours,  if this is None
+    pub env: Option<Vec<(String, String)>>,
+}
+impl Options {
+    /// A command to run argv,  as these options say
+    pub fn command(&self, argv: &[String]) -> Command {
+        let mut command = Command::new(&argv[0]);
+        command.args(&argv[1..]);
+        if let Some(dir) = &self.dir {
+            command.current_dir(dir);
+        }
+        if let Some(env) = &self.env {
+            command.env_clear();
+            command.envs(env.iter().map(|(name, val)| (name, val)));
+        }
+        command
+    }
+}
+
+/// The options in args,  given to name as :key val pairs;  :in,  a string;  :dir,  a string
+/// or a file;  and :env,  a map of names to values
+pub fn options(name: &str, args: &[Arc<Value>]) -> Result<Options, Value> {
+    let mut options = Options::default();
+    for (option, val) in Opts::from_pairs(name, &["in", "dir", "env"], args)?.iter() {
+        match option {
+            "in" => match &**val {
+                Value::String(input) => options.input = Some(input.to_string()),
+                _ => return Err(error_message::type_mismatch(TypeTag::String, val)),
+            },
+            "dir" => options.dir = Some(clojure_java_io::as_path(val)?),
+            _ => {
+                let mut env = vec![];
+                lazy_seq::try_for_each(val, |entry| match &*entry {
+                    Value::PersistentVector(pair) if pair.len() == 2 => {
+                        env.push((name_of(pair.nth(0).unwrap()), name_of(pair.nth(1).unwrap())));
+                        Ok(())
+                    }
+                    _ => Err(error_message::type_mismatch(
+                        TypeTag::PersistentListMap,
+                        val,
+                    )),
+                })?;
+                options.env = Some(env);
+            }
+        }
+    }
+    Ok(options)
+}
+
+// An env var's name or value;  a string as it is,  a keyword by its name
+fn name_of(x: &Value) -> String {
+    match x {
+        Value::String(s) => s.to_string(),
+        Value::Keyword(key) => key.sym.name.to_string(),
+        x => x.to_string(),
+    }
+}
+
+/// The program and args vals name;  each a string
+pub fn argv(vals: &[Arc<Value>]) -> Result<Vec<String>, Value> {
+    if vals.is_empty() {
+        return Err(Value::Condition("No program given to run".into()));
+    }
+    vals.iter()
+        .map(|val| match &**val {
+            Value::String(arg) => Ok(arg.to_string()),
+            _ => Err(error_message::type_mismatch(TypeTag::String, val)),
+        })
+        .collect()
+}
+
+/// Starts command;  or says why it couldn't be
+pub fn spawn(command: &mut Command, argv: &[String]) -> Result<Child, Value> {
+    command
+        .spawn()
+        .map_err(|e| Value::Condition(format!("Cannot run program \"{}\": {}", argv[0], e).into()))
+}
+
+/// Writes input to stdin,  then closes it;  on a thread of its own,  so a program that
+/// writes as it reads doesn't wait on us,  while we wait on it
+pub fn feed(stdin: Option<ChildStdin>, input: String) {
+    if let Some(mut stdin) = stdin {
+        thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+}
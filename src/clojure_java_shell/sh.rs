@@ -0,0 +1,100 @@
+use crate::clojure_java_shell;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::maps::MapEntry;
+use crate::persistent_list_map::PersistentListMap;
+use crate::value::{ToValue, Value};
+use std::process::Stdio;
+use std::sync::Arc;
+
+/// (sh program & args-and-options)
+///
+/// Runs program with the args (strings) before the first option,  and waits for it to
+/// finish;  {:exit code,  :out what it wrote to stdout,  :err what it wrote to stderr}.
+/// The options are :in (what it reads;  nothing otherwise),  :dir and :env
+#[derive(Debug, Clone)]
+pub struct ShFn {}
+impl ToValue for ShFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ShFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let split = args
+            .iter()
+            .position(|arg| matches!(&**arg, Value::Keyword(_)))
+            .unwrap_or(args.len());
+        match run(&args[..split], &args[split..]) {
+            Ok(result) => result,
+            Err(condition) => condition,
+        }
+    }
+}
+
+fn run(argv: &[Arc<Value>], options: &[Arc<Value>]) -> Result<Value, Value> {
+    let argv = clojure_java_shell::argv(argv)?;
+    let options = clojure_java_shell::options("clojure.java.shell/sh", options)?;
+    let mut command = options.command(&argv);
+    command
+        .stdin(match options.input {
+            Some(_) => Stdio::piped(),
+            None => Stdio::null(),
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = clojure_java_shell::spawn(&mut command, &argv)?;
+    if let Some(input) = options.input {
+        clojure_java_shell::feed(child.stdin.take(), input);
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| Value::Condition(format!("Could not run {}: {}", argv[0], e).into()))?;
+    let entry = |key, val: Value| MapEntry {
+        key: Keyword::intern(key).to_rc_value(),
+        val: val.to_rc_value(),
+    };
+    let text = |bytes: &[u8]| Value::from(String::from_utf8_lossy(bytes).into_owned());
+    // Killed by a signal,  it has no code;  -1,  as a JVM would say in its place
+    let exit = output.status.code().unwrap_or(-1);
+    Ok(vec![
+        entry("exit", Value::I32(exit)),
+        entry("out", text(&output.stdout)),
+        entry("err", text(&output.stderr)),
+    ]
+    .into_iter()
+    .collect::<PersistentListMap>()
+    .to_value())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn sh_gives_the_exit_code_and_all_the_program_wrote() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.shell :refer [sh]])",
+            "[(sh \"tr\" \"a-z\" \"A-Z\" :in \"shout\") (:exit (sh \"sh\" \"-c\" \"echo oops >&2; exit 3\")) (:out (sh \"sh\" \"-c\" \"echo $GREETING\" :env {\"GREETING\" \"hi\"})) (:out (sh \"pwd\" :dir \"/\"))]",
+        ]);
+        assert_eq!(
+            "[{:exit 0, :out \"SHOUT\", :err \"\"} 3 \"hi\\n\" \"/\\n\"]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn a_program_that_isnt_there_cannot_be_run() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.shell :refer [sh]])",
+            "(ex-message (try (sh \"no-such-program-here\") (catch Exception e e)))",
+        ]);
+        assert!(result
+            .to_string()
+            .starts_with("Cannot run program \"no-such-program-here\""));
+    }
+}
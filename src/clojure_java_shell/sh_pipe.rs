@@ -0,0 +1,174 @@
+use crate::clojure_java_shell::{self, Options};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{LazySeq, Step};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::io::{BufRead, BufReader};
+use std::process::{Child, ChildStdout, Stdio};
+use std::sync::Arc;
+
+/// (sh-pipe [program & args] .. & options)
+///
+/// Runs each program,  with its args,  all at once;  each reading what the one before it
+/// writes,  as a shell's | has them.  A lazy seq of the lines the last one writes,  without
+/// their line endings;  read only as the seq's walked to them.  So
+///
+///   (sh-pipe ["cat" "big.log"] ["grep" "ERROR"])
+///
+/// never holds more of big.log than a line.  What they write to stderr goes to ours,  and
+/// as in a shell,  how they exit isn't looked at.  The options are
+///
+///  :in s      what the first one reads;  nothing otherwise
+///  :dir d     the directory they're run in
+///  :env m     the environment variables they have,  as a map of names to values
+#[derive(Debug, Clone)]
+pub struct ShPipeFn {}
+impl ToValue for ShPipeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ShPipeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let split = args
+            .iter()
+            .position(|arg| !matches!(&**arg, Value::PersistentVector(_)))
+            .unwrap_or(args.len());
+        if split == 0 {
+            return match args.first() {
+                Some(arg) => error_message::type_mismatch(TypeTag::PersistentVector, arg),
+                None => error_message::wrong_varg_count(&[1], 0),
+            };
+        }
+        match start(&args[..split], &args[split..]) {
+            Ok(pipeline) => lines(pipeline).into_value(),
+            Err(condition) => condition,
+        }
+    }
+}
+
+// Programs started,  and the end of the pipe they write to
+struct Pipeline {
+    children: Vec<Child>,
+    out: BufReader<ChildStdout>,
+}
+
+fn start(commands: &[Arc<Value>], options: &[Arc<Value>]) -> Result<Pipeline, Value> {
+    let options = clojure_java_shell::options("clojure.java.shell/sh-pipe", options)?;
+    let mut children: Vec<Child> = vec![];
+    // Those started before one that can't be would otherwise be left running
+    if let Err(condition) = spawn_each(commands, &options, &mut children) {
+        stop(&mut children);
+        return Err(condition);
+    }
+    let out = children
+        .last_mut()
+        .and_then(|last| last.stdout.take())
+        .map(BufReader::new)
+        .expect("the last program in a pipe writes to it");
+    Ok(Pipeline { children, out })
+}
+
+// Starts each program,  piped into the one after it,  adding it to children
+fn spawn_each(
+    commands: &[Arc<Value>],
+    options: &Options,
+    children: &mut Vec<Child>,
+) -> Result<(), Value> {
+    for (ind, argv) in commands.iter().enumerate() {
+        let argv = match &**argv {
+            Value::PersistentVector(argv) => {
+                clojure_java_shell::argv(&argv.iter().cloned().collect::<Vec<Arc<Value>>>())?
+            }
+            _ => {
+                return Err(error_message::type_mismatch(
+                    TypeTag::PersistentVector,
+                    argv,
+                ))
+            }
+        };
+        let mut command = options.command(&argv);
+        let stdin = match children.last_mut().and_then(|before| before.stdout.take()) {
+            Some(stdout) => Stdio::from(stdout),
+            None if ind == 0 && options.input.is_some() => Stdio::piped(),
+            None => Stdio::null(),
+        };
+        command.stdin(stdin).stdout(Stdio::piped());
+        let mut child = clojure_java_shell::spawn(&mut command, &argv)?;
+        if let (0, Some(input)) = (ind, &options.input) {
+            clojure_java_shell::feed(child.stdin.take(), input.clone());
+        }
+        children.push(child);
+    }
+    Ok(())
+}
+
+fn stop(children: &mut [Child]) {
+    for child in children {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+// A seq let go of before it's read to the end leaves programs that may never finish,  as
+// (first (sh-pipe ["yes"])) does;  they're stopped,  not left running unreaped
+impl Drop for Pipeline {
+    fn drop(&mut self) {
+        stop(&mut self.children);
+    }
+}
+
+fn lines(mut pipeline: Pipeline) -> LazySeq {
+    LazySeq::new(move || {
+        let mut line = vec![];
+        match pipeline.out.read_until(b'\n', &mut line) {
+            Ok(0) => {
+                // All written;  so each is done,  or soon will be,  and can be waited on
+                for child in pipeline.children.iter_mut() {
+                    let _ = child.wait();
+                }
+                Ok(Step::Done)
+            }
+            Ok(_) => {
+                let mut line = String::from_utf8_lossy(&line).into_owned();
+                let len = line.trim_end_matches(['\n', '\r']).len();
+                line.truncate(len);
+                Ok(Step::Next(
+                    Arc::new(Value::from(line)),
+                    Arc::new(lines(pipeline).into_value()),
+                ))
+            }
+            Err(e) => Err(Value::Condition(
+                format!("Could not read from a pipe: {}", e).into(),
+            )),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn each_program_reads_what_the_one_before_it_wrote() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.shell :refer [sh-pipe]])",
+            "[(sh-pipe [\"printf\" \"b\\nab\\nc\\na\\n\"] [\"grep\" \"a\"] [\"sort\"]) (sh-pipe [\"printf\" \"x\\r\\n\"] [\"cat\"]) (sh-pipe [\"cat\"] [\"rev\"] :in \"ab\ncd\") (first (sh-pipe [\"yes\"])) (sh-pipe [\"true\"])]",
+        ]);
+        assert_eq!(
+            "[(\"a\" \"ab\") (\"x\") (\"ba\" \"dc\") \"y\" ()]",
+            result.to_string()
+        );
+    }
+
+    // kill -0 finds a program that's exited but not been waited on;  not one that has been
+    #[test]
+    fn programs_are_stopped_and_waited_on_when_their_seq_is_let_go_of() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.shell :refer [sh sh-pipe]])",
+            "(let [pid (first (sh-pipe [\"sh\" \"-c\" \"echo $$; exec yes\"]))] (:exit (sh \"kill\" \"-0\" pid)))",
+        ]);
+        assert_eq!("1", result.to_string());
+    }
+}
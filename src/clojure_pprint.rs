@@ -0,0 +1,231 @@
+//! clojure.pprint;  pprint,  pp and print-table
+//!
+//! pprint writes a collection that doesn't fit in what's left of the line over several;
+//! its items one under the other,  each just inside its opening bracket.  A map entry that
+//! doesn't fit has its value under its key.  How much is printed is up to *print-length*
+//! and *print-level*,  as in Clojure,  and how wide it is up to *print-right-margin*.  Sets
+//! and maps are in the order they'd be printed in otherwise;  sorted,  with *print-sorted*
+pub(crate) mod pp;
+pub(crate) mod pprint;
+pub(crate) mod print_table;
+
+use crate::environment::Environment;
+use crate::error_message;
+use crate::iterable::Iterable;
+use crate::persistent_vector::PersistentVector;
+use crate::print_order;
+use crate::protocol::ProtocolCastable;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// How pprint prints;  what *print-right-margin*,  *print-length* and *print-level* are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub right_margin: usize,
+    pub length: Option<usize>,
+    pub level: Option<usize>,
+}
+impl Settings {
+    /// What the vars are in environment;  bound,  or not
+    pub fn of(environment: &Environment) -> Result<Settings, Value> {
+        let var = |ns: &str, name: &str| {
+            let val = environment.get(&Symbol::intern_with_ns(ns, name));
+            match &*val {
+                Value::Nil => Ok(None),
+                Value::I32(n) => Ok(Some((*n).max(0) as usize)),
+                _ => Err(error_message::type_mismatch(TypeTag::I32, &val)),
+            }
+        };
+        Ok(Settings {
+            right_margin: var("clojure.pprint", "*print-right-margin*")?.unwrap_or(72),
+            length: var("clojure.core", "*print-length*")?,
+            level: var("clojure.core", "*print-level*")?,
+        })
+    }
+}
+
+// What's printed;  an atom,  printed as it is,  or a collection's items between brackets
+enum Doc {
+    Atom(String),
+    Coll {
+        open: &'static str,
+        items: Vec<Doc>,
+        close: &'static str,
+        // What goes after each item but the last;  "," in a map
+        separator: &'static str,
+    },
+    Entry(Box<Doc>, Box<Doc>),
+}
+
+// x as it's printed,  depth collections deep
+fn doc(x: &Value, settings: &Settings, depth: usize) -> Doc {
+    let (open, close, separator) = match x {
+        Value::PersistentVector(_) => ("[", "]", ""),
+        Value::PersistentQueue(_) => ("#queue [", "]", ""),
+        Value::PersistentList(_) | Value::LazySeq(_) => ("(", ")", ""),
+        Value::PersistentHashSet(_) | Value::PersistentTreeSet(_) => ("#{", "}", ""),
+        Value::PersistentListMap(_) | Value::PersistentTreeMap(_) => ("{", "}", ","),
+        _ => return Doc::Atom(x.to_string_explicit()),
+    };
+    if settings.level.is_some_and(|level| depth >= level) {
+        return Doc::Atom("#".to_string());
+    }
+    let mut items = vec![];
+    let coll = match x.to_rc_value().try_as_protocol::<Iterable>() {
+        Some(coll) => coll,
+        None => return Doc::Atom(x.to_string_explicit()),
+    };
+    // Unordered ones in the order they'd be printed in otherwise;  see print_order
+    let vals: Box<dyn Iterator<Item = Arc<Value>>> = match x {
+        Value::PersistentHashSet(_) | Value::PersistentListMap(_) => {
+            let mut vals = coll.iter().collect::<Vec<Arc<Value>>>();
+            print_order::sort(&mut vals, |val| match &**val {
+                Value::PersistentVector(entry) if separator == "," => {
+                    entry.nth(0).map_or(&**val, |key| &**key)
+                }
+                _ => val,
+            });
+            Box::new(vals.into_iter())
+        }
+        _ => Box::new(coll.iter()),
+    };
+    for (i, item) in vals.enumerate() {
+        if settings.length == Some(i) {
+            items.push(Doc::Atom("...".to_string()));
+            break;
+        }
+        items.push(match (separator, &*item) {
+            (",", Value::PersistentVector(entry)) => entry_doc(entry, settings, depth + 1),
+            _ => doc(&item, settings, depth + 1),
+        });
+    }
+    Doc::Coll {
+        open,
+        items,
+        close,
+        separator,
+    }
+}
+
+// A map entry,  [key val],  as it's printed
+fn entry_doc(entry: &PersistentVector, settings: &Settings, depth: usize) -> Doc {
+    let part = |i| match entry.nth(i) {
+        Some(part) => doc(part, settings, depth),
+        None => Doc::Atom(Value::Nil.to_string()),
+    };
+    Doc::Entry(Box::new(part(0)), Box::new(part(1)))
+}
+
+// doc on one line
+fn flat(doc: &Doc) -> String {
+    match doc {
+        Doc::Atom(atom) => atom.clone(),
+        Doc::Coll {
+            open,
+            items,
+            close,
+            separator,
+        } => format!(
+            "{}{}{}",
+            open,
+            items
+                .iter()
+                .map(flat)
+                .collect::<Vec<String>>()
+                .join(&format!("{} ", separator)),
+            close
+        ),
+        Doc::Entry(key, val) => format!("{} {}", flat(key), flat(val)),
+    }
+}
+
+// Writes doc to out,  starting at column;  on one line if it fits there,  with trailing
+// more to go after it,  and over several if not
+fn write(doc: &Doc, column: usize, trailing: usize, margin: usize, out: &mut String) {
+    let one_line = flat(doc);
+    if column + one_line.chars().count() + trailing <= margin {
+        out.push_str(&one_line);
+        return;
+    }
+    let newline = |out: &mut String, column: usize| {
+        out.push('\n');
+        out.push_str(&" ".repeat(column));
+    };
+    match doc {
+        Doc::Atom(atom) => out.push_str(atom),
+        Doc::Coll {
+            open,
+            items,
+            close,
+            separator,
+        } => {
+            out.push_str(open);
+            let inner = column + open.chars().count();
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(separator);
+                    newline(out, inner);
+                }
+                let after = match i + 1 == items.len() {
+                    true => trailing + close.len(),
+                    false => separator.len(),
+                };
+                write(item, inner, after, margin, out);
+            }
+            out.push_str(close);
+        }
+        Doc::Entry(key, val) => {
+            write(key, column, 0, margin, out);
+            newline(out, column);
+            write(val, column, trailing, margin, out);
+        }
+    }
+}
+
+/// x as pprint prints it;  without a newline at the end
+pub fn pretty(x: &Value, settings: &Settings) -> String {
+    let mut out = String::new();
+    write(&doc(x, settings, 0), 0, 0, settings.right_margin, &mut out);
+    out
+}
+
+/// clojure.pprint/name,  for the forms our macros expand to
+pub(crate) fn qualified(name: &str) -> Arc<Value> {
+    Symbol::intern_with_ns("clojure.pprint", name).to_rc_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clojure_pprint::{pretty, Settings};
+    use crate::repl::Repl;
+
+    fn settings(right_margin: usize) -> Settings {
+        Settings {
+            right_margin,
+            length: None,
+            level: None,
+        }
+    }
+
+    #[test]
+    fn what_fits_stays_on_one_line() {
+        let x = Repl::default().eval_all(&["{:a [1 2 3] :b #{:c}}"]);
+        assert_eq!(x.to_string(), pretty(&x, &settings(72)));
+    }
+
+    #[test]
+    fn what_doesnt_fit_is_put_one_item_under_the_other() {
+        let repl = Repl::default();
+        let x = repl.eval_all(&["[{:name \"alpha\" :tags [:a :b]} (list 1 2 3)]"]);
+        assert_eq!(
+            "[{:name \"alpha\",\n  :tags [:a :b]}\n (1 2 3)]",
+            pretty(&x, &settings(28))
+        );
+        assert_eq!(
+            "[{:name\n  \"alpha\",\n  :tags\n  [:a\n   :b]}\n (1\n  2\n  3)]",
+            pretty(&x, &settings(8))
+        );
+    }
+}
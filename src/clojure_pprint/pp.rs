@@ -0,0 +1,34 @@
+use crate::clojure_pprint;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.pprint/pp ; pprints the last value the REPL printed
+/// (pp)
+///
+/// (pp) expands to
+///
+/// (clojure.pprint/pprint clojure.core/*1)
+#[derive(Debug, Clone)]
+pub struct PpMacro {}
+impl ToValue for PpMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for PpMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.is_empty() {
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        vec![
+            clojure_pprint::qualified("pprint"),
+            Symbol::intern_with_ns("clojure.core", "*1").to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
@@ -0,0 +1,61 @@
+use crate::clojure_java_io;
+use crate::clojure_pprint::{self, Settings};
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.pprint/pprint ; prints x,  and a newline,  over as many lines as it takes to fit
+/// in *print-right-margin*;  to *out*,  or writer
+/// (pprint x)
+/// (pprint x writer)
+#[derive(Debug, Clone)]
+pub struct PprintFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl PprintFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> PprintFn {
+        PprintFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for PprintFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PprintFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let settings = match Settings::of(&self.enclosing_environment) {
+            Ok(settings) => settings,
+            Err(condition) => return condition,
+        };
+        let text = format!("{}\n", clojure_pprint::pretty(&args[0], &settings));
+        let printed = match args.get(1) {
+            Some(writer) => clojure_java_io::as_writer(writer).and_then(|w| w.write(&text)),
+            None => clojure_java_io::print(&text),
+        };
+        match printed {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn pprint_is_bounded_by_the_print_vars() {
+        let result = Repl::default().eval_all(&[
+            "(binding [clojure.pprint/*print-right-margin* 15 *print-length* 3 *print-level* 2] (with-out-str (clojure.pprint/pprint [[1 [2]] (range 10)])))",
+        ]);
+        assert_eq!("\"[[1 #]\\n (0 1 2 ...)]\\n\"", result.to_string_explicit());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::clojure_java_io;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::iterable::Iterable;
+use crate::protocol::ProtocolCastable;
+use crate::rust_core::GetFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.pprint/print-table ; prints rows,  maps,  as a table;  a column for each of ks
+/// (the keys of the first row,  if they're not given),  headed by the key,  with each cell
+/// right justified
+/// (print-table rows)
+/// (print-table ks rows)
+#[derive(Debug, Clone)]
+pub struct PrintTableFn {}
+impl ToValue for PrintTableFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PrintTableFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (ks, rows) = match args.as_slice() {
+            [rows] => (None, rows),
+            [ks, rows] => (Some(ks), rows),
+            _ => return error_message::wrong_varg_count(&[1, 2], args.len()),
+        };
+        let rows = match items(rows) {
+            Ok(rows) => rows,
+            Err(condition) => return condition,
+        };
+        let ks = match (ks, rows.first()) {
+            (Some(ks), _) => items(ks),
+            // The keys of a map's entries,  [key val]
+            (None, Some(row)) => items(row).map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| match &**entry {
+                        Value::PersistentVector(entry) => entry.nth(0).cloned(),
+                        _ => None,
+                    })
+                    .collect()
+            }),
+            (None, None) => Ok(vec![]),
+        };
+        let ks = match ks {
+            Ok(ks) => ks,
+            Err(condition) => return condition,
+        };
+        if rows.is_empty() {
+            return Value::Nil;
+        }
+        match clojure_java_io::print(&table(&ks, &rows)) {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
+
+// The items of coll
+fn items(coll: &Arc<Value>) -> Result<Vec<Arc<Value>>, Value> {
+    match coll.try_as_protocol::<Iterable>() {
+        Some(coll) => Ok(coll.iter().collect()),
+        None if *coll.as_ref() == Value::Nil => Ok(vec![]),
+        None => Err(error_message::type_mismatch(TypeTag::ISeq, coll)),
+    }
+}
+
+// What (get row k) gives
+fn cell(row: &Arc<Value>, k: &Arc<Value>) -> Arc<Value> {
+    GetFn {}
+        .invoke(vec![Arc::clone(row), Arc::clone(k)])
+        .to_rc_value()
+}
+
+// What str makes of x
+fn text(x: &Value) -> String {
+    match x {
+        Value::Nil => String::new(),
+        _ => x.to_string(),
+    }
+}
+
+// The table,  a line at a time;  after an empty line,  as Clojure has it
+fn table(ks: &[Arc<Value>], rows: &[Arc<Value>]) -> String {
+    let cells = rows
+        .iter()
+        .map(|row| ks.iter().map(|k| text(&cell(row, k))).collect())
+        .collect::<Vec<Vec<String>>>();
+    let headers = ks.iter().map(|k| text(k)).collect::<Vec<String>>();
+    let widths = headers
+        .iter()
+        .enumerate()
+        .map(|(i, header)| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .fold(header.chars().count(), usize::max)
+        })
+        .collect::<Vec<usize>>();
+    let line = |leader: &str, divider: &str, trailer: &str, row: &[String]| {
+        let row = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:>1$}", cell, width))
+            .collect::<Vec<String>>()
+            .join(divider);
+        format!("{}{}{}\n", leader, row, trailer)
+    };
+    let spacers = widths
+        .iter()
+        .map(|width| "-".repeat(*width))
+        .collect::<Vec<String>>();
+    let mut table = String::from("\n");
+    table.push_str(&line("| ", " | ", " |", &headers));
+    table.push_str(&line("|-", "-+-", "-|", &spacers));
+    for row in cells.iter() {
+        table.push_str(&line("| ", " | ", " |", row));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn print_table_right_justifies_each_column() {
+        let result = Repl::default().eval_all(&[
+            "(with-out-str (clojure.pprint/print-table [:a :b] [{:a 1 :b \"xyz\"} {:a 10}]))",
+        ]);
+        assert_eq!(
+            "\"\\n| :a |  :b |\\n|----+-----|\\n|  1 | xyz |\\n| 10 |     |\\n\"",
+            result.to_string_explicit()
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! clojure.profile;  timing each var's fn as it's called,  to find where a program spends
+//! its time in terms of its own fns,  rather than the interpreter's
+//!
+//! From (clojure.profile/start!),  or --profile,  each call of a var's fn is counted and
+//! timed;  inclusive of the fns it calls,  and exclusive of them.  A recursive call's time
+//! is only counted once inclusively,  by the outermost call.  Calls are timed on whatever
+//! thread they're made on,  each with its own stack of calls
+pub(crate) mod report;
+pub(crate) mod start;
+
+use crate::environment::Environment;
+use crate::persistent_list::PersistentList;
+use crate::symbol::Symbol;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+static PROFILING: AtomicBool = AtomicBool::new(false);
+
+/// What's been timed of a var's fn
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stat {
+    pub calls: u64,
+    pub inclusive: Duration,
+    pub exclusive: Duration,
+}
+
+// A call being timed;  children is how long the calls it's made so far took
+struct Frame {
+    var: Symbol,
+    start: Instant,
+    children: Duration,
+}
+
+thread_local! {
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(vec![]) };
+}
+
+fn stats() -> MutexGuard<'static, HashMap<Symbol, Stat>> {
+    static STATS: OnceLock<Mutex<HashMap<Symbol, Stat>>> = OnceLock::new();
+    STATS.get_or_init(Default::default).lock().unwrap()
+}
+
+/// Starts profiling over;  forgetting what was timed before
+pub fn start() {
+    stats().clear();
+    PROFILING.store(true, Ordering::SeqCst);
+}
+
+pub fn stop() {
+    PROFILING.store(false, Ordering::SeqCst);
+}
+
+/// What f gives;  timed as a call of the var var names,  if we're profiling and it names
+/// one
+pub fn call<T>(var: impl FnOnce() -> Option<Symbol>, f: impl FnOnce() -> T) -> T {
+    if !PROFILING.load(Ordering::Relaxed) {
+        return f();
+    }
+    let var = match var() {
+        Some(var) => var,
+        None => return f(),
+    };
+    FRAMES.with(|frames| {
+        frames.borrow_mut().push(Frame {
+            var,
+            start: Instant::now(),
+            children: Duration::ZERO,
+        })
+    });
+    let result = f();
+    let (frame, recursive) = FRAMES.with(|frames| {
+        let mut frames = frames.borrow_mut();
+        let frame = frames.pop().unwrap();
+        if let Some(caller) = frames.last_mut() {
+            caller.children += frame.start.elapsed();
+        }
+        let recursive = frames.iter().any(|outer| outer.var == frame.var);
+        (frame, recursive)
+    });
+    let elapsed = frame.start.elapsed();
+    let mut stats = stats();
+    let stat = stats.entry(frame.var).or_default();
+    stat.calls += 1;
+    stat.exclusive += elapsed.saturating_sub(frame.children);
+    if !recursive {
+        stat.inclusive += elapsed;
+    }
+    result
+}
+
+/// The var call calls,  qualified;  if its head is a symbol naming one here
+pub fn called_var(environment: &Environment, call: &Arc<PersistentList>) -> Option<Symbol> {
+    match &**call {
+        PersistentList::Cons(head, _, _) => match &**head {
+            Value::Symbol(sym) if !environment.is_local(sym) => {
+                environment.resolve_var(sym).map(|var| var.qualified())
+            }
+            _ => None,
+        },
+        PersistentList::Empty => None,
+    }
+}
+
+/// What's been timed of each var's fn,  most exclusive time first
+pub fn timed() -> Vec<(Symbol, Stat)> {
+    let mut timed = stats()
+        .iter()
+        .map(|(var, stat)| (Symbol::clone(var), *stat))
+        .collect::<Vec<(Symbol, Stat)>>();
+    timed.sort_by(|(a_var, a), (b_var, b)| {
+        b.exclusive
+            .cmp(&a.exclusive)
+            .then_with(|| a_var.to_string().cmp(&b_var.to_string()))
+    });
+    timed
+}
+
+/// What's been timed,  as a table
+pub fn report() -> String {
+    let mut report = format!(
+        "{:>10} {:>14} {:>14}  {}\n",
+        "calls", "inclusive ms", "exclusive ms", "var"
+    );
+    for (var, stat) in timed() {
+        report.push_str(&format!(
+            "{:>10} {:>14.3} {:>14.3}  {}\n",
+            stat.calls,
+            millis(stat.inclusive),
+            millis(stat.exclusive),
+            var
+        ));
+    }
+    report
+}
+
+pub fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clojure_profile;
+    use crate::repl::Repl;
+    use crate::symbol::Symbol;
+    use std::thread;
+    use std::time::Duration;
+
+    // Profiling is on for every thread at once;  so it's all tested here,  rather than by
+    // tests that could start it over under each other
+    #[test]
+    fn calls_are_counted_and_timed_exclusive_of_what_they_call() {
+        let (outer, inner) = (Symbol::intern("t/outer"), Symbol::intern("t/inner"));
+        clojure_profile::start();
+        clojure_profile::call(
+            || Some(outer.clone()),
+            || {
+                for _ in 0..2 {
+                    clojure_profile::call(
+                        || Some(inner.clone()),
+                        || thread::sleep(Duration::from_millis(10)),
+                    );
+                }
+            },
+        );
+        let timed = clojure_profile::timed();
+        let stat = |var: &Symbol| timed.iter().find(|(timed, _)| timed == var).unwrap().1;
+        assert_eq!((1, 2), (stat(&outer).calls, stat(&inner).calls));
+        assert!(stat(&inner).exclusive >= Duration::from_millis(20));
+        assert!(stat(&outer).inclusive >= Duration::from_millis(20));
+        assert!(stat(&outer).exclusive < Duration::from_millis(10));
+
+        let result = Repl::default().eval_all(&[
+            "(ns profiled)",
+            "(defn fact [n] (if (< n 2) 1 (* n (fact (dec n)))))",
+            "(defn facts [] (let [f (fn [n] (fact n))] [(f 5) (f 3)]))",
+            "(clojure.profile/start!)",
+            "(facts)",
+            "(clojure.profile/stop!)",
+            "(facts)",
+            "(let [stats (clojure.profile/stats)
+                   calls (fn [var] (:calls (first (filter #(= var (:var %)) stats))))]
+               [(calls 'profiled/facts) (calls 'profiled/fact) (calls 'profiled/f)])",
+        ]);
+        assert_eq!("[1 8 nil]", result.to_string());
+    }
+}
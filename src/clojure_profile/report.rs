@@ -0,0 +1,75 @@
+use crate::clojure_profile;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::output::{self, Stream};
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::persistent_vector::PersistentVector;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.profile/report ; prints what's been timed,  most exclusive time first
+/// (report)
+///
+/// A line for each var;  how many times its fn was called,  and how many milliseconds
+/// those calls took in all,  inclusive and exclusive of the fns they called
+#[derive(Debug, Clone)]
+pub struct ReportFn {
+    pub stream: Stream,
+}
+impl ToValue for ReportFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReportFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.is_empty() {
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        output::write(self.stream, &clojure_profile::report());
+        Value::Nil
+    }
+}
+
+/// clojure.profile/stats ; what's been timed,  most exclusive time first;  each a map of
+/// :var,  :calls,  :inclusive-ms and :exclusive-ms
+/// (stats)
+#[derive(Debug, Clone)]
+pub struct StatsFn {}
+impl ToValue for StatsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for StatsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.is_empty() {
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        clojure_profile::timed()
+            .into_iter()
+            .map(|(var, stat)| {
+                let entries = vec![
+                    ("var", var.to_value()),
+                    ("calls", Value::from(stat.calls as usize)),
+                    (
+                        "inclusive-ms",
+                        Value::F64(clojure_profile::millis(stat.inclusive)),
+                    ),
+                    (
+                        "exclusive-ms",
+                        Value::F64(clojure_profile::millis(stat.exclusive)),
+                    ),
+                ];
+                entries
+                    .into_iter()
+                    .fold(PersistentListMap::Empty, |map, (key, val)| {
+                        map.assoc(Keyword::intern(key).to_rc_value(), Arc::new(val))
+                    })
+                    .to_rc_value()
+            })
+            .collect::<PersistentVector>()
+            .to_value()
+    }
+}
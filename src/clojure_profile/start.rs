@@ -0,0 +1,43 @@
+use crate::clojure_profile;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.profile/start! ; starts timing each var's fn as it's called,  from scratch
+/// (start!)
+#[derive(Debug, Clone)]
+pub struct StartFn {}
+impl ToValue for StartFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for StartFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.is_empty() {
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        clojure_profile::start();
+        Value::Nil
+    }
+}
+
+/// clojure.profile/stop! ; stops timing calls;  keeping what's been timed,  for report
+/// (stop!)
+#[derive(Debug, Clone)]
+pub struct StopFn {}
+impl ToValue for StopFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for StopFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.is_empty() {
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        clojure_profile::stop();
+        Value::Nil
+    }
+}
@@ -0,0 +1,231 @@
+//! Protocols,  as made by defprotocol
+//!
+//! (Not to be confused with protocol.rs,  which is how our Rust code asks a value what it
+//! can do.)
+//!
+//! A protocol is a named set of methods,  each a fn that picks what to run by the type of
+//! its first argument.  A type gets its implementations either when it's made with deftype
+//! or defrecord,  or later,  by extend-type,  extend-protocol and extend;  which is also
+//! how the built-in types get theirs.  A value is first looked up by its own type, then by
+//! each type it belongs to (see TypeTag::supers),  nearest first, and last of all as Object.
+//! What reify makes carries its implementations with its type,  so they come before all that
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+/// What extending a protocol to Object extends it to;  everything but nil
+pub const OBJECT: &str = "Object";
+
+/// The names of the types to look val's implementations up under,  in the order to try them
+pub fn type_names(val: &Value) -> Vec<String> {
+    let mut names = vec![];
+    let tag = match val {
+        Value::Instance(instance) => {
+            names.push(instance.type_().name.to_string());
+            instance.type_tag()
+        }
+        Value::HostObject(object) => {
+            names.push(object.type_name().to_string());
+            val.type_tag()
+        }
+        _ => val.type_tag(),
+    };
+    names.push(tag.to_string());
+    names.extend(tag.supers().iter().map(|tag| tag.to_string()));
+    // As in Clojure,  nil isn't an Object
+    if *val != Value::Nil {
+        names.push(String::from(OBJECT));
+    }
+    names
+}
+
+/// A type's implementations of a protocol's methods;  one fn for each arity given
+pub type Impls = HashMap<Symbol, Vec<Arc<dyn IFn>>>;
+
+/// Adds f to the implementations of method;  replacing the one for the arity f takes,  if
+/// there is one
+pub fn add_impl(impls: &mut Impls, method: &Symbol, f: Arc<dyn IFn>) {
+    let fns = impls.entry(method.clone()).or_default();
+    // Fns that don't say what they take can't be told apart,  so the newest wins
+    let same_arity = |other: &Arc<dyn IFn>| match (f.arglists(), other.arglists()) {
+        (Some(arglists), Some(other_arglists)) => arglists == other_arglists,
+        _ => true,
+    };
+    fns.retain(|other| !same_arity(other));
+    fns.push(f);
+}
+
+// The implementation of method that takes as many args as there are
+fn find_impl(impls: &Impls, method: &Symbol, args: &[Arc<Value>]) -> Option<Arc<dyn IFn>> {
+    impls
+        .get(method)?
+        .iter()
+        .find(|f| match f.arglists() {
+            Some(arglists) => arglists
+                .iter()
+                .any(|arglist| ifn::accepts(arglist, args.len())),
+            None => true,
+        })
+        .cloned()
+}
+
+// The implementations of protocol that val's own type carries,  if it was made by reify
+fn reified_impls<'a>(protocol: &Symbol, val: &'a Value) -> Option<&'a Impls> {
+    match val {
+        Value::Instance(instance) => instance.type_().impls.get(protocol),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub struct Protocol {
+    pub name: Symbol,
+    // Each method's name,  and the arglists it was declared with
+    methods: Vec<(Symbol, Vec<Vec<Symbol>>)>,
+    // By type name
+    impls: RwLock<HashMap<String, Impls>>,
+}
+impl Protocol {
+    pub fn new(name: Symbol, methods: Vec<(Symbol, Vec<Vec<Symbol>>)>) -> Protocol {
+        Protocol {
+            name,
+            methods,
+            impls: RwLock::new(HashMap::new()),
+        }
+    }
+    pub fn method_names(&self) -> Vec<Symbol> {
+        self.methods.iter().map(|(name, _)| name.clone()).collect()
+    }
+    /// The arglists method was declared with
+    pub fn arglists(&self, method: &Symbol) -> Option<Vec<Vec<Symbol>>> {
+        self.methods
+            .iter()
+            .find(|(name, _)| name == method)
+            .map(|(_, arglists)| arglists.clone())
+    }
+    /// Implements method for the type named type_name with f;  replacing the
+    /// implementation it already has for the arity f takes,  if there is one
+    pub fn extend(&self, type_name: &str, method: &Symbol, f: Arc<dyn IFn>) -> Result<(), Value> {
+        if self.arglists(method).is_none() {
+            return Err(Value::Condition(
+                format!("No method {} in protocol {}", method, self.name).into(),
+            ));
+        }
+        let mut impls = self.impls.write().unwrap();
+        add_impl(impls.entry(String::from(type_name)).or_default(), method, f);
+        Ok(())
+    }
+    /// The fn that implements method for args;  looked up by the type of the first one
+    pub fn find(&self, method: &Symbol, args: &[Arc<Value>]) -> Option<Arc<dyn IFn>> {
+        if let Some(f) =
+            reified_impls(&self.name, &args[0]).and_then(|impls| find_impl(impls, method, args))
+        {
+            return Some(f);
+        }
+        let impls = self.impls.read().unwrap();
+        type_names(&args[0])
+            .iter()
+            .find_map(|type_name| find_impl(impls.get(type_name)?, method, args))
+    }
+    /// Whether val's type,  or one it belongs to,  implements this protocol
+    pub fn is_satisfied_by(&self, val: &Value) -> bool {
+        if reified_impls(&self.name, val).is_some() {
+            return true;
+        }
+        let impls = self.impls.read().unwrap();
+        type_names(val)
+            .iter()
+            .any(|type_name| impls.contains_key(type_name))
+    }
+}
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#protocol[{}]", self.name)
+    }
+}
+
+/// One of a protocol's methods;  calls whichever implementation its first argument's type
+/// has
+#[derive(Debug, Clone)]
+pub struct MethodFn {
+    pub protocol: Arc<Protocol>,
+    pub method: Symbol,
+}
+impl ToValue for MethodFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MethodFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        match self.protocol.find(&self.method, &args) {
+            Some(f) => f.invoke(args),
+            None => Value::Condition(
+                format!(
+                    "No implementation of method: :{} of protocol: {} found for class: {} with {} args",
+                    self.method,
+                    self.protocol.name,
+                    type_names(&args[0])[0],
+                    args.len()
+                )
+                .into(),
+            ),
+        }
+    }
+    fn arglists(&self) -> Option<Vec<Vec<Symbol>>> {
+        self.protocol.arglists(&self.method)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    #[test]
+    fn methods_dispatch_on_the_type_of_their_first_arg() {
+        let result = Repl::default().eval_all(&[
+            "(defprotocol Shape (area [this]) (describe [this] [this prefix]))",
+            "(defrecord Square [side] Shape (area [this] (* side side)) (describe [this] :square))",
+            "(deftype Circle [r] Shape (area [_] (* 3 r r)))",
+            "(extend-type Circle Shape (describe [this prefix] [prefix :circle]))",
+            "[(area (->Square 2)) (area (->Circle 1)) (describe (->Square 1)) (describe (->Circle 1) :a)]",
+        ]);
+        assert_eq!("[4 3 :square [:a :circle]]", result.to_string());
+    }
+
+    #[test]
+    fn built_in_types_are_extended_through_what_they_belong_to() {
+        let repl = Repl::default();
+        let result = repl.eval_all(&[
+            "(defprotocol Describe (describe [this]))",
+            "(extend-protocol Describe
+               clojure.lang.Number (describe [n] :number)
+               nil (describe [_] :nothing)
+               Object (describe [_] :something))",
+            "[(describe 1) (describe 1.5) (describe nil) (describe \"s\") (satisfies? Describe 1)]",
+        ]);
+        assert_eq!(
+            "[:number :number :nothing :something true]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn a_missing_implementation_is_a_condition() {
+        let repl = Repl::default();
+        let result = repl.eval_all(&["(defprotocol Describe (describe [this]))", "(describe 1)"]);
+        assert!(matches!(result, Value::Condition(_)));
+        assert_eq!(
+            Value::Boolean(false),
+            repl.eval_all(&["(satisfies? Describe 1)"])
+        );
+    }
+}
@@ -0,0 +1,73 @@
+pub(crate) mod doc;
+pub(crate) mod source;
+
+use crate::environment::{CORE_FILE, CORE_SOURCE};
+use crate::keyword::Keyword;
+use crate::persistent_list_map::IPersistentMap;
+use crate::reader;
+use crate::value::{ToValue, Value};
+use crate::var::Var;
+use std::fs;
+use std::sync::Arc;
+
+/// What (doc name) prints for var;  its name,  arglists and docstring
+pub(crate) fn doc(var: &Var) -> String {
+    let mut doc = format!("-------------------------\n{}\n", var.qualified());
+    if let Some(arglists) = arglists(var) {
+        doc.push_str(&arglists);
+        doc.push('\n');
+    }
+    if let Value::Macro(_) = &*var.root() {
+        doc.push_str("Macro\n");
+    }
+    doc.push_str("  ");
+    doc.push_str(docstring(var).as_deref().unwrap_or("nil"));
+    doc
+}
+
+/// var's :doc,  if it has one
+pub(crate) fn docstring(var: &Var) -> Option<String> {
+    match &*meta(var, "doc") {
+        Value::String(doc) => Some(doc.to_string()),
+        _ => None,
+    }
+}
+
+/// The parameter lists var's fn takes,  as ([x] [x & more]);  its :arglists,  if it
+/// was given them,  or else what the fn says
+pub(crate) fn arglists(var: &Var) -> Option<String> {
+    let arglists = meta(var, "arglists");
+    if *arglists != Value::Nil {
+        return Some(arglists.to_string());
+    }
+    let arglists = match &*var.root() {
+        Value::IFn(ifn) | Value::Macro(ifn) => ifn.arglists()?,
+        _ => return None,
+    };
+    let arglists = arglists
+        .iter()
+        .map(|arglist| {
+            let params = arglist.iter().map(|param| param.to_string());
+            format!("[{}]", params.collect::<Vec<String>>().join(" "))
+        })
+        .collect::<Vec<String>>();
+    Some(format!("({})", arglists.join(" ")))
+}
+
+/// The text var was defined with,  read back from the file it was defined in;  None if we
+/// don't know where that is,  or can't read it
+pub(crate) fn source(var: &Var) -> Option<String> {
+    let location = var.namespace.location(&var.sym)?;
+    let text = match location.file.as_str() {
+        CORE_FILE => CORE_SOURCE.to_string(),
+        file => fs::read_to_string(file).ok()?,
+    };
+    reader::form_at_line(&text, location.line).map(String::from)
+}
+
+fn meta(var: &Var, key: &str) -> Arc<Value> {
+    match var.meta() {
+        Value::PersistentListMap(meta) => meta.get(&Keyword::intern(key).to_rc_value()),
+        _ => Value::Nil.to_rc_value(),
+    }
+}
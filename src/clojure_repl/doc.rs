@@ -0,0 +1,105 @@
+use crate::clojure_repl;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.repl/print-doc ; prints the doc of the var sym names,  if it names one
+/// (print-doc sym)
+#[derive(Debug, Clone)]
+pub struct PrintDocFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl PrintDocFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> PrintDocFn {
+        PrintDocFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for PrintDocFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PrintDocFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let sym = match &*args[0] {
+            Value::Symbol(sym) => sym,
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        if let Some(var) = self.enclosing_environment.resolve_var(sym) {
+            println!("{}", clojure_repl::doc(&var));
+        }
+        Value::Nil
+    }
+}
+
+/// clojure.repl/doc ; prints the name,  arglists and docstring of the var name names
+/// (doc name)
+///
+/// (doc map) expands to (clojure.repl/print-doc (quote map))
+#[derive(Debug, Clone)]
+pub struct DocMacro {}
+impl ToValue for DocMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DocMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        vec![
+            Symbol::intern_with_ns("clojure.repl", "print-doc").to_rc_value(),
+            vec![Symbol::intern("quote").to_rc_value(), Arc::clone(&args[0])]
+                .into_list()
+                .to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clojure_repl;
+    use crate::environment::Environment;
+    use crate::repl::Repl;
+    use crate::symbol::Symbol;
+    use std::sync::Arc;
+
+    #[test]
+    fn doc_gives_the_name_arglists_and_docstring() {
+        let environment = Environment::clojure_core_environment();
+        Repl::new(Arc::clone(&environment)).eval_all(&[
+            "(defn twice \"Doubles x\" [x] (* 2 x))",
+            "(defn bare [] 1)",
+            "(defmacro unless [test then] (list (quote if) test nil then))",
+        ]);
+        let doc = |name: &str| {
+            let var = environment.resolve_var(&Symbol::intern(name)).unwrap();
+            clojure_repl::doc(&var)
+        };
+        assert_eq!(
+            "-------------------------\nuser/twice\n([x])\n  Doubles x",
+            doc("twice")
+        );
+        assert_eq!(
+            "-------------------------\nuser/bare\n([])\n  nil",
+            doc("bare")
+        );
+        assert_eq!(
+            "-------------------------\nuser/unless\n([test then])\nMacro\n  nil",
+            doc("unless")
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::clojure_repl;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.repl/source-fn ; the text the var sym names was defined with,  or nil if it
+/// names none or we can't find it
+/// (source-fn sym)
+#[derive(Debug, Clone)]
+pub struct SourceFnFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl SourceFnFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> SourceFnFn {
+        SourceFnFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for SourceFnFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SourceFnFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let sym = match &*args[0] {
+            Value::Symbol(sym) => sym,
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        self.enclosing_environment
+            .resolve_var(sym)
+            .and_then(|var| clojure_repl::source(&var))
+            .map_or(Value::Nil, |source| Value::String(source.into()))
+    }
+}
+
+/// clojure.repl/source ; prints the text the var name names was defined with
+/// (source name)
+///
+/// (source f) expands to
+///
+/// (println (or (clojure.repl/source-fn (quote f)) "Source not found"))
+#[derive(Debug, Clone)]
+pub struct SourceMacro {}
+impl ToValue for SourceMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for SourceMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let source = vec![
+            Symbol::intern_with_ns("clojure.repl", "source-fn").to_rc_value(),
+            vec![Symbol::intern("quote").to_rc_value(), Arc::clone(&args[0])]
+                .into_list()
+                .to_rc_value(),
+        ];
+        vec![
+            Symbol::intern("println").to_rc_value(),
+            vec![
+                Symbol::intern("or").to_rc_value(),
+                source.into_list().to_rc_value(),
+                Value::String("Source not found".into()).to_rc_value(),
+            ]
+            .into_list()
+            .to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use std::fs;
+
+    #[test]
+    fn source_is_read_back_from_where_it_was_defined() {
+        let path = std::env::temp_dir().join("clojure_rs_source_test.clj");
+        fs::write(
+            &path,
+            "(def greeting \"hi\")\n\n;; Loud\n(defn shout\n  \"Shouts s\"\n  [s] ; the text\n  (str s \"!\"))\n",
+        )
+        .unwrap();
+        let result = Repl::default().eval_all(&[
+            &format!("(load-file \"{}\")", path.to_string_lossy()),
+            "[(clojure.repl/source-fn 'shout) (clojure.repl/source-fn 'inc) (clojure.repl/source-fn 'nope) (clojure.repl/source-fn 'first)]",
+        ]);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            "[\"(defn shout\\n  \\\"Shouts s\\\"\\n  [s] ; the text\\n  (str s \\\"!\\\"))\" \"(defn inc [x]\\n  (+ x 1))\" nil nil]",
+            result.to_string()
+        );
+    }
+}
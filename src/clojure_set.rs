@@ -0,0 +1,23 @@
+pub(crate) mod difference;
+pub(crate) mod intersection;
+pub(crate) mod rename_keys;
+pub(crate) mod select;
+pub(crate) mod subset_qmark_;
+pub(crate) mod union;
+
+use crate::error_message;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::type_tag::TypeTag;
+use crate::value::Value;
+
+/// The set fns treat nil as the empty set, like Clojure's do
+pub(crate) fn as_set(val: &Value) -> Result<PersistentHashSet, Value> {
+    match val {
+        Value::PersistentHashSet(pset) => Ok((**pset).clone()),
+        Value::Nil => Ok(PersistentHashSet::empty()),
+        _ => Err(error_message::type_mismatch(
+            TypeTag::PersistentHashSet,
+            val,
+        )),
+    }
+}
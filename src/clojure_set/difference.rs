@@ -0,0 +1,63 @@
+use crate::clojure_set::as_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.set/difference ; returns the first set without the elements of the others
+/// (difference set & sets)
+#[derive(Debug, Clone)]
+pub struct DifferenceFn {}
+impl ToValue for DifferenceFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DifferenceFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        let mut difference = match as_set(&args[0]) {
+            Ok(pset) => pset,
+            Err(condition) => return condition,
+        };
+        for arg in args[1..].iter() {
+            match as_set(arg) {
+                Ok(pset) => {
+                    for val in pset.iter() {
+                        difference = difference.disj(&val);
+                    }
+                }
+                Err(condition) => return condition,
+            }
+        }
+        difference.to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod difference_tests {
+        use crate::clojure_set::difference::DifferenceFn;
+        use crate::ifn::IFn;
+        use crate::persistent_hash_set::PersistentHashSet;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn set(vals: &[i32]) -> Arc<Value> {
+            vals.iter()
+                .map(|i| i.to_rc_value())
+                .collect::<PersistentHashSet>()
+                .to_rc_value()
+        }
+
+        #[test]
+        fn difference_of_sets() {
+            assert_eq!(
+                *set(&[1]),
+                DifferenceFn {}.invoke(vec![set(&[1, 2, 3]), set(&[2]), set(&[3, 4])])
+            );
+        }
+    }
+}
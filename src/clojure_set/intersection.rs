@@ -0,0 +1,61 @@
+use crate::clojure_set::as_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.set/intersection ; returns a set of the elements in all of the given sets
+/// (intersection set & sets)
+#[derive(Debug, Clone)]
+pub struct IntersectionFn {}
+impl ToValue for IntersectionFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for IntersectionFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        let mut sets = vec![];
+        for arg in args.iter() {
+            match as_set(arg) {
+                Ok(pset) => sets.push(pset),
+                Err(condition) => return condition,
+            }
+        }
+        sets[0]
+            .iter()
+            .filter(|val| sets[1..].iter().all(|pset| pset.contains(val)))
+            .collect::<PersistentHashSet>()
+            .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod intersection_tests {
+        use crate::clojure_set::intersection::IntersectionFn;
+        use crate::ifn::IFn;
+        use crate::persistent_hash_set::PersistentHashSet;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn set(vals: &[i32]) -> Arc<Value> {
+            vals.iter()
+                .map(|i| i.to_rc_value())
+                .collect::<PersistentHashSet>()
+                .to_rc_value()
+        }
+
+        #[test]
+        fn intersection_of_sets() {
+            assert_eq!(
+                *set(&[2]),
+                IntersectionFn {}.invoke(vec![set(&[1, 2, 3]), set(&[2, 3]), set(&[2, 5])])
+            );
+        }
+    }
+}
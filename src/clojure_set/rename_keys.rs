@@ -0,0 +1,88 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::maps::MapEntry;
+use crate::persistent_list_map::{PersistentListMap, ToPersistentListMapIter};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.set/rename-keys ; returns map with each key of kmap renamed to its value in kmap
+/// (rename-keys map kmap)
+#[derive(Debug, Clone)]
+pub struct RenameKeysFn {}
+impl ToValue for RenameKeysFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RenameKeysFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (map, kmap) = match (&*args[0], &*args[1]) {
+            (Value::PersistentListMap(map), Value::PersistentListMap(kmap)) => (map, kmap),
+            (Value::PersistentListMap(_), _) => {
+                return error_message::type_mismatch(TypeTag::PersistentListMap, &args[1])
+            }
+            _ => return error_message::type_mismatch(TypeTag::PersistentListMap, &args[0]),
+        };
+        let renames = kmap.iter().collect::<Vec<MapEntry>>();
+        let renamed = |key: &Arc<Value>| renames.iter().find(|rename| rename.key == *key);
+        // Like Clojure's,  every old key is removed before any new one is added,  so
+        // swapping two keys works
+        let entries = map.iter().collect::<Vec<MapEntry>>();
+        let kept = entries
+            .iter()
+            .filter(|entry| renamed(&entry.key).is_none())
+            .cloned();
+        let moved = entries.iter().filter_map(|entry| {
+            renamed(&entry.key).map(|rename| MapEntry {
+                key: Arc::clone(&rename.val),
+                val: Arc::clone(&entry.val),
+            })
+        });
+        kept.chain(moved).collect::<PersistentListMap>().to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod rename_keys_tests {
+        use crate::clojure_set::rename_keys::RenameKeysFn;
+        use crate::ifn::IFn;
+        use crate::keyword::Keyword;
+        use crate::maps::MapEntry;
+        use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn kw(name: &str) -> Arc<Value> {
+            Keyword::intern(name).to_rc_value()
+        }
+        fn map(entries: &[(Arc<Value>, Arc<Value>)]) -> PersistentListMap {
+            entries
+                .iter()
+                .map(|(key, val)| MapEntry {
+                    key: Arc::clone(key),
+                    val: Arc::clone(val),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn swaps_keys() {
+            let renamed = RenameKeysFn {}.invoke(vec![
+                map(&[(kw("a"), 1.to_rc_value()), (kw("b"), 2.to_rc_value())]).to_rc_value(),
+                map(&[(kw("a"), kw("b")), (kw("b"), kw("a"))]).to_rc_value(),
+            ]);
+            match renamed {
+                Value::PersistentListMap(renamed) => {
+                    assert_eq!(Value::I32(2), *renamed.get(&kw("a")));
+                    assert_eq!(Value::I32(1), *renamed.get(&kw("b")));
+                }
+                other => panic!("Expected a map, got {}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::clojure_set::as_set;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.set/select ; returns the elements of xset for which pred is truthy
+/// (select pred xset)
+#[derive(Debug, Clone)]
+pub struct SelectFn {}
+impl ToValue for SelectFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SelectFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let pred = match ifn::as_ifn(&args[0]) {
+            Some(ifn) => ifn,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let xset = match as_set(&args[1]) {
+            Ok(pset) => pset,
+            Err(condition) => return condition,
+        };
+        let mut selected = xset.clone();
+        for val in xset.iter() {
+            match pred.invoke(vec![Arc::clone(&val)]) {
+                Value::Condition(condition) => return Value::Condition(condition),
+                result if !result.is_truthy() => selected = selected.disj(&val),
+                _ => {}
+            }
+        }
+        selected.to_value()
+    }
+}
@@ -0,0 +1,60 @@
+use crate::clojure_set::as_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.set/subset? ; whether every element of set1 is also in set2
+/// (subset? set1 set2)
+#[derive(Debug, Clone)]
+pub struct SubsetFn {}
+impl ToValue for SubsetFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SubsetFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match (as_set(&args[0]), as_set(&args[1])) {
+            (Ok(set1), Ok(set2)) => Value::Boolean(set1.is_subset(&set2)),
+            (Err(condition), _) | (_, Err(condition)) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod subset_tests {
+        use crate::clojure_set::subset_qmark_::SubsetFn;
+        use crate::ifn::IFn;
+        use crate::persistent_hash_set::PersistentHashSet;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn set(vals: &[i32]) -> Arc<Value> {
+            vals.iter()
+                .map(|i| i.to_rc_value())
+                .collect::<PersistentHashSet>()
+                .to_rc_value()
+        }
+
+        #[test]
+        fn subset() {
+            assert_eq!(
+                Value::Boolean(true),
+                SubsetFn {}.invoke(vec![set(&[1]), set(&[1, 2])])
+            );
+            assert_eq!(
+                Value::Boolean(true),
+                SubsetFn {}.invoke(vec![set(&[]), set(&[])])
+            );
+            assert_eq!(
+                Value::Boolean(false),
+                SubsetFn {}.invoke(vec![set(&[1, 3]), set(&[1, 2])])
+            );
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::clojure_set::as_set;
+use crate::ifn::IFn;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.set/union ; returns a set of every element of every given set
+/// (union & sets)
+#[derive(Debug, Clone)]
+pub struct UnionFn {}
+impl ToValue for UnionFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for UnionFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let mut union = PersistentHashSet::empty();
+        for arg in args.iter() {
+            match as_set(arg) {
+                Ok(pset) => {
+                    for val in pset.iter() {
+                        union = union.conj(val);
+                    }
+                }
+                Err(condition) => return condition,
+            }
+        }
+        union.to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod union_tests {
+        use crate::clojure_set::union::UnionFn;
+        use crate::ifn::IFn;
+        use crate::persistent_hash_set::PersistentHashSet;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn set(vals: &[i32]) -> Arc<Value> {
+            vals.iter()
+                .map(|i| i.to_rc_value())
+                .collect::<PersistentHashSet>()
+                .to_rc_value()
+        }
+
+        #[test]
+        fn union_of_sets() {
+            assert_eq!(
+                *set(&[1, 2, 3, 4]),
+                UnionFn {}.invoke(vec![set(&[1, 2]), set(&[2, 3]), set(&[4])])
+            );
+        }
+
+        #[test]
+        fn union_of_nothing_is_empty() {
+            assert_eq!(*set(&[]), UnionFn {}.invoke(vec![]));
+            assert_eq!(
+                *set(&[1]),
+                UnionFn {}.invoke(vec![set(&[1]), Arc::new(Value::Nil)])
+            );
+        }
+    }
+}
@@ -0,0 +1,644 @@
+//! clojure.spec.alpha;  specs of what data should look like,  and valid?,  conform and
+//! explain to check it against them.  A pragmatic subset:  predicates (any fn,  or a set of
+//! the values allowed),  and,  or,  nilable,  keys,  coll-of and tuple;  and fdef,  with
+//! clojure.spec.test.alpha/instrument checking a fn's args each time it's called
+//!
+//! Specs are registered under qualified keywords with def,  in a registry every thread
+//! shares.  There's no ::kw here,  so their names are written out;  :my.app/age,  say.  The
+//! macros that make specs keep the forms they were made from,  so explain can say which
+//! predicate failed
+pub(crate) mod conform;
+pub(crate) mod def;
+pub(crate) mod explain;
+pub(crate) mod fdef;
+pub(crate) mod instrument;
+pub(crate) mod spec;
+pub(crate) mod valid;
+
+use crate::clojure_java_io;
+use crate::host_object::HostObject;
+use crate::ifn::{self, IFn};
+use crate::keyword::Keyword;
+use crate::maps::MapEntry;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_list_map::PersistentListMap;
+use crate::persistent_vector::ToPersistentVector;
+use crate::rust_core::thread_macros::{list, sym};
+use crate::rust_core::{AssocFn, ContainsFn, GetFn};
+use crate::seqable;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+/// A spec;  what the macros of clojure.spec.alpha make
+#[derive(Debug)]
+pub struct Spec {
+    // What it was made from;  (clojure.spec.alpha/coll-of int?),  say
+    form: Arc<Value>,
+    kind: Kind,
+}
+#[derive(Debug)]
+enum Kind {
+    // A fn,  or a set of the values allowed
+    Pred(Arc<Value>),
+    // Each of the rest holds specs;  Spec host objects,  or the keywords they're registered
+    // under
+    And(Vec<Arc<Value>>),
+    // Each spec tagged with the keyword conform gives with what it conforms to
+    Or(Vec<(Arc<Value>, Arc<Value>)>),
+    Nilable(Arc<Value>),
+    Keys(Keys),
+    CollOf(CollOf),
+    Tuple(Vec<Arc<Value>>),
+}
+// The keys a map must have;  the -un ones unqualified in the map.  Whatever keys it has,
+// their values must conform to their specs;  :opt ones only say which there are,  as
+// documentation,  but :opt-un ones say which spec an unqualified key's value has
+#[derive(Debug, Default)]
+struct Keys {
+    req: Vec<Keyword>,
+    req_un: Vec<Keyword>,
+    opt_un: Vec<Keyword>,
+}
+#[derive(Debug)]
+struct CollOf {
+    spec: Arc<Value>,
+    // A pred the coll itself must satisfy,  with its form;  vector?,  say
+    kind: Option<(Arc<Value>, Arc<Value>)>,
+    count: Option<usize>,
+    min_count: Option<usize>,
+    max_count: Option<usize>,
+    distinct: bool,
+}
+impl HostObject for Spec {
+    fn type_name(&self) -> &str {
+        "clojure.spec.alpha.Spec"
+    }
+    fn call(&self, _method: &str, _args: Vec<Arc<Value>>) -> Option<Value> {
+        None
+    }
+    fn display(&self) -> String {
+        format!("#spec {}", self.form.to_string_explicit())
+    }
+}
+impl Spec {
+    fn new_value(form: Arc<Value>, kind: Kind) -> Arc<Value> {
+        Arc::new(Value::host_object(Spec { form, kind }))
+    }
+}
+
+// Shared by every thread,  as a namespace's vars are;  spec names,  and fdef'd fns'
+// qualified symbols,  to their specs
+fn registry() -> MutexGuard<'static, HashMap<Value, Arc<Value>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<Value, Arc<Value>>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default).lock().unwrap()
+}
+
+/// Registers spec under name;  a qualified keyword,  or an fdef'd fn's qualified symbol.
+/// A nil spec takes name out of the registry
+pub(crate) fn register(name: Value, spec: Arc<Value>) {
+    match &*spec {
+        Value::Nil => registry().remove(&name),
+        _ => registry().insert(name, spec),
+    };
+}
+
+/// The spec registered under name;  if there is one
+pub(crate) fn registered(name: &Value) -> Option<Arc<Value>> {
+    registry().get(name).cloned()
+}
+
+/// The qualified symbols of the fns fdef has specs for
+pub(crate) fn fdefs() -> Vec<Symbol> {
+    registry()
+        .keys()
+        .filter_map(|name| match name {
+            Value::Symbol(sym) => Some(Symbol::clone(sym)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// What conform gives for what doesn't conform;  :clojure.spec.alpha/invalid
+pub(crate) fn invalid() -> Value {
+    Keyword::intern_with_ns("clojure.spec.alpha", "invalid").to_value()
+}
+
+/// val as a spec,  made from form;  a spec,  or a spec's name,  as it is,  and a fn or a set
+/// as a predicate
+pub(crate) fn spec_of(form: &Arc<Value>, val: &Arc<Value>) -> Result<Arc<Value>, Value> {
+    match &**val {
+        Value::Keyword(_) => Ok(Arc::clone(val)),
+        _ if as_spec(val).is_some() => Ok(Arc::clone(val)),
+        Value::PersistentHashSet(_) | Value::PersistentTreeSet(_) => Ok(pred(form, val)),
+        _ if ifn::is_callable(val) => Ok(pred(form, val)),
+        _ => Err(Value::Condition(
+            format!("{} is not a spec", val.to_string_explicit()).into(),
+        )),
+    }
+}
+
+fn pred(form: &Arc<Value>, pred: &Arc<Value>) -> Arc<Value> {
+    Spec::new_value(Arc::clone(form), Kind::Pred(Arc::clone(pred)))
+}
+
+fn as_spec(val: &Value) -> Option<&Spec> {
+    clojure_java_io::downcast::<Spec>(val)
+}
+
+// The spec val is,  or names;  following a name through the registry to the spec it's for
+fn resolve(val: &Arc<Value>) -> Result<&Spec, Value> {
+    match as_spec(val) {
+        Some(spec) => Ok(spec),
+        None => Err(Value::Condition(
+            format!("Unable to resolve spec: {}", val.to_string_explicit()).into(),
+        )),
+    }
+}
+
+// A spec's name's spec;  or,  given anything else,  itself
+fn lookup(val: &Arc<Value>) -> Result<Arc<Value>, Value> {
+    let mut val = Arc::clone(val);
+    while let Value::Keyword(_) = &*val {
+        val = registered(&val)
+            .ok_or_else(|| Value::Condition(format!("Unable to resolve spec: {}", val).into()))?;
+    }
+    Ok(val)
+}
+
+/// What x conforms to under spec;  None if it doesn't.  A condition a predicate throws is
+/// thrown on
+pub(crate) fn conform(spec: &Arc<Value>, x: &Arc<Value>) -> Result<Option<Arc<Value>>, Value> {
+    let spec = lookup(spec)?;
+    let spec = resolve(&spec)?;
+    Ok(match &spec.kind {
+        Kind::Pred(pred) => match satisfies(pred, x)? {
+            true => Some(Arc::clone(x)),
+            false => None,
+        },
+        Kind::And(specs) => {
+            let mut conformed = Arc::clone(x);
+            for spec in specs {
+                match conform(spec, &conformed)? {
+                    Some(val) => conformed = val,
+                    None => return Ok(None),
+                }
+            }
+            Some(conformed)
+        }
+        Kind::Or(branches) => {
+            for (tag, spec) in branches {
+                if let Some(val) = conform(spec, x)? {
+                    return Ok(Some(vec![Arc::clone(tag), val].into_vector().to_rc_value()));
+                }
+            }
+            None
+        }
+        Kind::Nilable(spec) => match &**x {
+            Value::Nil => Some(Arc::clone(x)),
+            _ => conform(spec, x)?,
+        },
+        Kind::Keys(keys) => conform_keys(keys, x)?,
+        Kind::CollOf(coll_of) => conform_coll(coll_of, x)?,
+        Kind::Tuple(specs) => match &**x {
+            Value::PersistentVector(pvector) if pvector.len() == specs.len() => {
+                let mut conformed = Vec::with_capacity(specs.len());
+                for (spec, val) in specs.iter().zip(pvector.iter()) {
+                    match conform(spec, val)? {
+                        Some(val) => conformed.push(val),
+                        None => return Ok(None),
+                    }
+                }
+                Some(conformed.into_vector().to_rc_value())
+            }
+            _ => None,
+        },
+    })
+}
+
+// Whether x satisfies pred;  is one of the set's members,  or what the fn gives is truthy
+fn satisfies(pred: &Arc<Value>, x: &Arc<Value>) -> Result<bool, Value> {
+    let satisfied = match &**pred {
+        Value::PersistentHashSet(_) | Value::PersistentTreeSet(_) => {
+            ContainsFn {}.invoke(vec![Arc::clone(pred), Arc::clone(x)])
+        }
+        _ => ifn::call(pred, vec![Arc::clone(x)]),
+    };
+    match satisfied {
+        Value::Condition(_) => Err(satisfied),
+        satisfied => Ok(satisfied.is_truthy()),
+    }
+}
+
+// The name of the spec the value of key in a map has;  the key,  or the qualified key an
+// unqualified one was listed as with :req-un or :opt-un.  None if there's no such spec
+fn key_spec(keys: &Keys, key: &Value) -> Option<Arc<Value>> {
+    let key = match key {
+        Value::Keyword(key) if !key.sym.has_ns() => keys
+            .req_un
+            .iter()
+            .chain(keys.opt_un.iter())
+            .find(|qualified| qualified.sym.name == key.sym.name)?
+            .to_value(),
+        Value::Keyword(_) => key.clone(),
+        _ => return None,
+    };
+    registered(&key).map(|_| key.to_rc_value())
+}
+
+// Whether map has key;  listed as qualified,  or,  if un,  unqualified
+fn has_key(map: &Arc<Value>, key: &Keyword, un: bool) -> Result<bool, Value> {
+    let key = match un {
+        true => Keyword::intern(&key.sym.name),
+        false => key.clone(),
+    };
+    let contains = ContainsFn {}.invoke(vec![Arc::clone(map), key.to_rc_value()]);
+    match contains {
+        Value::Condition(_) => Err(contains),
+        contains => Ok(contains.is_truthy()),
+    }
+}
+
+// The [key val] entries of a map
+fn entries(map: &Arc<Value>) -> Vec<(Arc<Value>, Arc<Value>)> {
+    seqable::seqable(map)
+        .map(|map| {
+            map.seq_iter()
+                .filter_map(|entry| match &*entry {
+                    Value::PersistentVector(pair) if pair.len() == 2 => {
+                        Some((Arc::clone(pair.nth(0)?), Arc::clone(pair.nth(1)?)))
+                    }
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn conform_keys(keys: &Keys, x: &Arc<Value>) -> Result<Option<Arc<Value>>, Value> {
+    if !x.is_map() {
+        return Ok(None);
+    }
+    for key in &keys.req {
+        if !has_key(x, key, false)? {
+            return Ok(None);
+        }
+    }
+    for key in &keys.req_un {
+        if !has_key(x, key, true)? {
+            return Ok(None);
+        }
+    }
+    let mut conformed = Arc::clone(x);
+    for (key, val) in entries(x) {
+        if let Some(spec) = key_spec(keys, &key) {
+            match conform(&spec, &val)? {
+                Some(new_val) if !Arc::ptr_eq(&new_val, &val) => {
+                    conformed = AssocFn {}
+                        .invoke(vec![conformed, key, new_val])
+                        .to_rc_value();
+                }
+                Some(_) => {}
+                None => return Ok(None),
+            }
+        }
+    }
+    Ok(Some(conformed))
+}
+
+// The elements of a coll;  None if x isn't one.  nil is an empty one
+fn elements(x: &Arc<Value>) -> Option<Vec<Arc<Value>>> {
+    match &**x {
+        Value::Nil => Some(vec![]),
+        _ if x.is_sequential() || x.is_map() || x.is_set() => {
+            seqable::seqable(x).map(|coll| coll.seq_iter().collect())
+        }
+        _ => None,
+    }
+}
+
+// Why coll isn't what coll_of says a coll should be;  as the form of the pred it fails
+fn coll_problem(coll_of: &CollOf, x: &Arc<Value>) -> Result<Option<Arc<Value>>, Value> {
+    let elements = match elements(x) {
+        Some(elements) => elements,
+        None => return Ok(Some(sym("coll?"))),
+    };
+    if let Some((kind, form)) = &coll_of.kind {
+        if !satisfies(kind, x)? {
+            return Ok(Some(Arc::clone(form)));
+        }
+    }
+    let count = || list(vec![sym("count"), sym("%")]);
+    let len = elements.len();
+    if let Some(n) = coll_of.count.filter(|&n| n != len) {
+        return Ok(Some(list(vec![sym("="), count(), usize_value(n)])));
+    }
+    match (coll_of.min_count, coll_of.max_count) {
+        (Some(min), Some(max)) if len < min || len > max => {
+            let bounds = vec![sym("<="), usize_value(min), count(), usize_value(max)];
+            return Ok(Some(list(bounds)));
+        }
+        (Some(min), None) if len < min => {
+            return Ok(Some(list(vec![sym("<="), usize_value(min), count()])));
+        }
+        (None, Some(max)) if len > max => {
+            return Ok(Some(list(vec![sym("<="), count(), usize_value(max)])));
+        }
+        _ => {}
+    }
+    if coll_of.distinct
+        && elements
+            .iter()
+            .cloned()
+            .collect::<PersistentHashSet>()
+            .len()
+            != len
+    {
+        return Ok(Some(sym("distinct?")));
+    }
+    Ok(None)
+}
+
+fn conform_coll(coll_of: &CollOf, x: &Arc<Value>) -> Result<Option<Arc<Value>>, Value> {
+    if coll_problem(coll_of, x)?.is_some() {
+        return Ok(None);
+    }
+    let mut changed = false;
+    let mut conformed = vec![];
+    for val in elements(x).unwrap_or_default() {
+        match conform(&coll_of.spec, &val)? {
+            Some(new_val) => {
+                changed = changed || !Arc::ptr_eq(&new_val, &val);
+                conformed.push(new_val);
+            }
+            None => return Ok(None),
+        }
+    }
+    if !changed {
+        return Ok(Some(Arc::clone(x)));
+    }
+    Ok(Some(match &**x {
+        Value::PersistentVector(_) => conformed.into_vector().to_rc_value(),
+        Value::PersistentHashSet(_) | Value::PersistentTreeSet(_) => conformed
+            .into_iter()
+            .collect::<PersistentHashSet>()
+            .to_rc_value(),
+        _ => conformed.into_list().to_rc_value(),
+    }))
+}
+
+/// Where explain is;  the path through the spec,  the specs' names gone through,  and the
+/// keys and indexes into the value
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Trail {
+    path: Vec<Arc<Value>>,
+    via: Vec<Arc<Value>>,
+    at: Vec<Arc<Value>>,
+}
+impl Trail {
+    fn down(&self, path: Option<&Arc<Value>>, at: Option<&Arc<Value>>) -> Trail {
+        let mut trail = self.clone();
+        trail.path.extend(path.cloned());
+        trail.at.extend(at.cloned());
+        trail
+    }
+}
+
+/// Why x doesn't conform to spec;  a problem for each pred it fails,  as
+/// {:path [..] :pred form :val val :via [..] :in [..]}.  None if it does
+pub(crate) fn explain(spec: &Arc<Value>, x: &Arc<Value>) -> Result<Vec<Value>, Value> {
+    let mut problems = vec![];
+    explain_into(spec, x, Trail::default(), &mut problems)?;
+    Ok(problems)
+}
+
+fn explain_into(
+    spec: &Arc<Value>,
+    x: &Arc<Value>,
+    mut trail: Trail,
+    problems: &mut Vec<Value>,
+) -> Result<(), Value> {
+    if let Value::Keyword(_) = &**spec {
+        trail.via.push(Arc::clone(spec));
+        let registered = registered(spec)
+            .ok_or_else(|| Value::Condition(format!("Unable to resolve spec: {}", spec).into()))?;
+        return explain_into(&registered, x, trail, problems);
+    }
+    let spec = resolve(spec)?;
+    match &spec.kind {
+        Kind::Pred(pred) => {
+            if !satisfies(pred, x)? {
+                problems.push(problem(Arc::clone(&spec.form), x, &trail));
+            }
+        }
+        Kind::And(specs) => {
+            let mut conformed = Arc::clone(x);
+            for spec in specs {
+                match conform(spec, &conformed)? {
+                    Some(val) => conformed = val,
+                    None => return explain_into(spec, &conformed, trail, problems),
+                }
+            }
+        }
+        Kind::Or(branches) => {
+            if conform_or(branches, x)? {
+                return Ok(());
+            }
+            for (tag, spec) in branches {
+                explain_into(spec, x, trail.down(Some(tag), None), problems)?;
+            }
+        }
+        Kind::Nilable(spec) => {
+            if let Value::Nil = &**x {
+                return Ok(());
+            }
+            if conform(spec, x)?.is_none() {
+                let pred = keyword("pred");
+                explain_into(spec, x, trail.down(Some(&pred), None), problems)?;
+                let trail = trail.down(Some(&keyword("nil")), None);
+                problems.push(problem(sym("nil?"), x, &trail));
+            }
+        }
+        Kind::Keys(keys) => explain_keys(keys, x, trail, problems)?,
+        Kind::CollOf(coll_of) => {
+            if let Some(pred) = coll_problem(coll_of, x)? {
+                problems.push(problem(pred, x, &trail));
+                return Ok(());
+            }
+            for (ind, val) in elements(x).unwrap_or_default().iter().enumerate() {
+                let at = match x.is_map() || x.is_set() {
+                    true => None,
+                    false => Some(usize_value(ind)),
+                };
+                explain_into(&coll_of.spec, val, trail.down(None, at.as_ref()), problems)?;
+            }
+        }
+        Kind::Tuple(specs) => match &**x {
+            Value::PersistentVector(pvector) if pvector.len() == specs.len() => {
+                for (ind, (spec, val)) in specs.iter().zip(pvector.iter()).enumerate() {
+                    let ind = usize_value(ind);
+                    explain_into(spec, val, trail.down(Some(&ind), Some(&ind)), problems)?;
+                }
+            }
+            Value::PersistentVector(_) => {
+                let count = list(vec![sym("count"), sym("%")]);
+                let pred = list(vec![sym("="), count, usize_value(specs.len())]);
+                problems.push(problem(pred, x, &trail));
+            }
+            _ => problems.push(problem(sym("vector?"), x, &trail)),
+        },
+    }
+    Ok(())
+}
+
+fn conform_or(branches: &[(Arc<Value>, Arc<Value>)], x: &Arc<Value>) -> Result<bool, Value> {
+    for (_, spec) in branches {
+        if conform(spec, x)?.is_some() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn explain_keys(
+    keys: &Keys,
+    x: &Arc<Value>,
+    trail: Trail,
+    problems: &mut Vec<Value>,
+) -> Result<(), Value> {
+    if !x.is_map() {
+        problems.push(problem(sym("map?"), x, &trail));
+        return Ok(());
+    }
+    let required = keys.req.iter().map(|key| (key, false));
+    let required_un = keys.req_un.iter().map(|key| (key, true));
+    for (key, un) in required.chain(required_un) {
+        if !has_key(x, key, un)? {
+            let key = match un {
+                true => Keyword::intern(&key.sym.name),
+                false => key.clone(),
+            };
+            let pred = list(vec![sym("contains?"), sym("%"), key.to_rc_value()]);
+            problems.push(problem(pred, x, &trail));
+        }
+    }
+    for (key, val) in entries(x) {
+        if let Some(spec) = key_spec(keys, &key) {
+            explain_into(&spec, &val, trail.down(Some(&key), Some(&key)), problems)?;
+        }
+    }
+    Ok(())
+}
+
+// {:path [..] :pred pred :val x :via [..] :in [..]}
+fn problem(pred: Arc<Value>, x: &Arc<Value>, trail: &Trail) -> Value {
+    let entry = |key, val: Arc<Value>| MapEntry {
+        key: Keyword::intern(key).to_rc_value(),
+        val,
+    };
+    let vector = |vals: &[Arc<Value>]| vals.to_vec().into_vector().to_rc_value();
+    vec![
+        entry("path", vector(&trail.path)),
+        entry("pred", pred),
+        entry("val", Arc::clone(x)),
+        entry("via", vector(&trail.via)),
+        entry("in", vector(&trail.at)),
+    ]
+    .into_iter()
+    .collect::<PersistentListMap>()
+    .to_value()
+}
+
+/// problems,  as explain-str gives them;  a line for each,  as
+///
+///   -1 - failed: pos? in: [0] at: [:age] spec: :app/age
+pub(crate) fn explain_str(problems: &[Value]) -> String {
+    if problems.is_empty() {
+        return String::from("Success!\n");
+    }
+    let mut out = String::new();
+    for problem in problems {
+        let get = |key| GetFn {}.invoke(vec![problem.to_rc_value(), keyword_unqualified(key)]);
+        out.push_str(&format!(
+            "{} - failed: {}",
+            get("val").to_string_explicit(),
+            get("pred").to_string_explicit()
+        ));
+        for (key, label) in [("in", "in"), ("path", "at")] {
+            let vals = get(key);
+            if !matches!(&vals, Value::PersistentVector(pvector) if pvector.is_empty()) {
+                out.push_str(&format!(" {}: {}", label, vals.to_string_explicit()));
+            }
+        }
+        if let Value::PersistentVector(via) = get("via") {
+            if let Some(last) = via.peek() {
+                out.push_str(&format!(" spec: {}", last.to_string_explicit()));
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// (clojure.spec.alpha/name ..),  as a symbol
+pub(crate) fn qualified(name: &str) -> Arc<Value> {
+    Symbol::intern_with_ns("clojure.spec.alpha", name).to_rc_value()
+}
+
+fn keyword(name: &str) -> Arc<Value> {
+    Keyword::intern_with_ns("clojure.spec.alpha", name).to_rc_value()
+}
+
+fn keyword_unqualified(name: &str) -> Arc<Value> {
+    Keyword::intern(name).to_rc_value()
+}
+
+fn usize_value(n: usize) -> Arc<Value> {
+    Value::I32(n as i32).to_rc_value()
+}
+
+#[cfg(test)]
+mod tests {
+    mod clojure_spec_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn specs_validate_and_conform() {
+            let result = Repl::default().eval_all(&[
+                "(require '[clojure.spec.alpha :as s])",
+                "(s/def :spec-test/age (s/and int? pos?))",
+                "[(s/valid? :spec-test/age 3) (s/valid? :spec-test/age -3) (s/conform (s/or :i int? :s string?) \"x\") (s/conform (s/coll-of (s/or :n int? :k keyword?) :kind vector?) [1 :a]) (s/invalid? (s/conform #{:a} :b))]",
+            ]);
+            assert_eq!(
+                "[true false [:s \"x\"] [[:n 1] [:k :a]] true]",
+                result.to_string()
+            );
+        }
+
+        #[test]
+        fn keys_specs_check_required_keys_and_their_specs() {
+            let result = Repl::default().eval_all(&[
+                "(require '[clojure.spec.alpha :as s])",
+                "(s/def :spec-test/name string?)",
+                "(s/def :spec-test/person (s/keys :req [:spec-test/name] :opt-un [:spec-test/name]))",
+                "[(s/valid? :spec-test/person {:spec-test/name \"x\" :name \"y\"}) (s/valid? :spec-test/person {:name \"y\"}) (s/valid? :spec-test/person {:spec-test/name \"x\" :name 1})]",
+            ]);
+            assert_eq!("[true false false]", result.to_string());
+        }
+
+        #[test]
+        fn explain_says_where_and_why_values_fail() {
+            let result = Repl::default().eval_all(&[
+                "(require '[clojure.spec.alpha :as s])",
+                "(s/def :spec-test/pos pos?)",
+                "[(s/explain-str :spec-test/pos -1) (s/explain-str (s/tuple int? string?) [1 2]) (s/explain-str (s/coll-of int?) [1]) (s/explain-data :spec-test/pos 1)]",
+            ]);
+            assert_eq!(
+                "[\"-1 - failed: pos? spec: :spec-test/pos\\n\" \"2 - failed: string? in: [1] at: [1]\\n\" \"Success!\\n\" nil]",
+                result.to_string()
+            );
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::clojure_spec;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (clojure.spec.alpha/conform spec x)
+///
+/// What x conforms to under spec;  x,  but with each value an or spec matched tagged with
+/// its branch,  as [:tag val].  :clojure.spec.alpha/invalid if x doesn't conform
+#[derive(Debug, Clone)]
+pub struct ConformFn {}
+impl ToValue for ConformFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ConformFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match clojure_spec::spec_of(&args[0], &args[0])
+            .and_then(|spec| clojure_spec::conform(&spec, &args[1]))
+        {
+            Ok(Some(conformed)) => (*conformed).clone(),
+            Ok(None) => clojure_spec::invalid(),
+            Err(condition) => condition,
+        }
+    }
+}
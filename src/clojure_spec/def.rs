@@ -0,0 +1,96 @@
+use crate::clojure_spec;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::rust_core::defprotocol_macro::quote;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.spec.alpha/def ; registers a spec under a name
+/// (def k spec)
+///
+/// Registers spec (a pred,  a spec,  or another spec's name) under k,  a qualified keyword;
+/// or,  if spec is nil,  takes k out of the registry.  Returns k
+///
+/// (def :app/age pos?) expands to
+///
+/// (clojure.spec.alpha/def* ':app/age 'pos? pos?)
+#[derive(Debug, Clone)]
+pub struct DefMacro {}
+impl ToValue for DefMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DefMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        vec![
+            clojure_spec::qualified("def*"),
+            quote(Arc::clone(&args[0])),
+            quote(Arc::clone(&args[1])),
+            Arc::clone(&args[1]),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+/// (clojure.spec.alpha/def* k form spec) ; what def expands to
+#[derive(Debug, Clone)]
+pub struct DefFn {}
+impl ToValue for DefFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DefFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        match &*args[0] {
+            Value::Keyword(k) if k.sym.has_ns() => {}
+            k => {
+                return Value::Condition(
+                    format!(
+                        "A spec is registered under a qualified keyword;  not {}",
+                        k.to_string_explicit()
+                    )
+                    .into(),
+                )
+            }
+        }
+        let spec = match &*args[2] {
+            Value::Nil => Arc::clone(&args[2]),
+            _ => match clojure_spec::spec_of(&args[1], &args[2]) {
+                Ok(spec) => spec,
+                Err(condition) => return condition,
+            },
+        };
+        clojure_spec::register((*args[0]).clone(), spec);
+        (*args[0]).clone()
+    }
+}
+
+/// (clojure.spec.alpha/get-spec k)
+///
+/// The spec registered under k;  a spec's name,  or an fdef'd fn's qualified symbol.  nil if
+/// there's none
+#[derive(Debug, Clone)]
+pub struct GetSpecFn {}
+impl ToValue for GetSpecFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for GetSpecFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        clojure_spec::registered(&args[0]).map_or(Value::Nil, |spec| (*spec).clone())
+    }
+}
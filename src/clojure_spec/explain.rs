@@ -0,0 +1,104 @@
+use crate::clojure_java_io;
+use crate::clojure_spec;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::maps::MapEntry;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_list_map::PersistentListMap;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+// Why (args) x doesn't conform to spec;  none if it does
+fn problems(args: &[Arc<Value>]) -> Result<Vec<Value>, Value> {
+    if args.len() != 2 {
+        return Err(error_message::wrong_arg_count(2, args.len()));
+    }
+    let spec = clojure_spec::spec_of(&args[0], &args[0])?;
+    clojure_spec::explain(&spec, &args[1])
+}
+
+/// (clojure.spec.alpha/explain spec x)
+///
+/// Prints why x doesn't conform to spec,  as explain-str gives it;  or Success!
+#[derive(Debug, Clone)]
+pub struct ExplainFn {}
+impl ToValue for ExplainFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExplainFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match problems(&args)
+            .and_then(|problems| clojure_java_io::print(&clojure_spec::explain_str(&problems)))
+        {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// (clojure.spec.alpha/explain-str spec x)
+///
+/// Why x doesn't conform to spec;  a line for each pred it fails,  as
+///
+///   -1 - failed: pos? in: [:age] at: [:age] spec: :app/age
+///
+/// saying what failed it,  where in x it is,  where in spec the pred is,  and the name of
+/// the spec it's in.  Success! if x conforms
+#[derive(Debug, Clone)]
+pub struct ExplainStrFn {}
+impl ToValue for ExplainStrFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExplainStrFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match problems(&args) {
+            Ok(problems) => Value::String(clojure_spec::explain_str(&problems).into()),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// (clojure.spec.alpha/explain-data spec x)
+///
+/// Why x doesn't conform to spec,  as data;  nil if it does
+///
+///   {:clojure.spec.alpha/problems ({:path [] :pred pos? :val -1 :via [] :in []})
+///    :clojure.spec.alpha/spec pos?
+///    :clojure.spec.alpha/value -1}
+#[derive(Debug, Clone)]
+pub struct ExplainDataFn {}
+impl ToValue for ExplainDataFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExplainDataFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let problems = match problems(&args) {
+            Ok(problems) if problems.is_empty() => return Value::Nil,
+            Ok(problems) => problems,
+            Err(condition) => return condition,
+        };
+        let entry = |key, val| MapEntry {
+            key: Keyword::intern_with_ns("clojure.spec.alpha", key).to_rc_value(),
+            val,
+        };
+        let problems = problems
+            .into_iter()
+            .map(Arc::new)
+            .collect::<Vec<Arc<Value>>>();
+        vec![
+            entry("problems", problems.into_list().to_rc_value()),
+            entry("spec", Arc::clone(&args[0])),
+            entry("value", Arc::clone(&args[1])),
+        ]
+        .into_iter()
+        .collect::<PersistentListMap>()
+        .to_value()
+    }
+}
@@ -0,0 +1,112 @@
+use crate::clojure_spec;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::maps::MapEntry;
+use crate::options;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_list_map::PersistentListMap;
+use crate::rust_core::defprotocol_macro::quote;
+use crate::rust_core::thread_macros::list;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.spec.alpha/fdef ; specs a fn's args,  and what it returns
+/// (fdef f :args spec :ret spec)
+///
+/// Registers the specs under f's qualified symbol;  so that once
+/// clojure.spec.test.alpha/instrument instruments f,  each call of it checks its args,  as a
+/// vector,  against the :args spec.  (fdef add :args (s/tuple int? int?)),  say
+///
+/// (fdef f :args (tuple int?)) expands to
+///
+/// (clojure.spec.alpha/fdef* 'user/f :args (clojure.spec.alpha/spec (tuple int?)))
+#[derive(Debug, Clone)]
+pub struct FdefMacro {
+    enclosing_environment: Arc<Environment>,
+}
+impl FdefMacro {
+    pub fn new(enclosing_environment: Arc<Environment>) -> FdefMacro {
+        FdefMacro {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for FdefMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for FdefMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let sym = match &*args[0] {
+            Value::Symbol(sym) => sym,
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        let qualified = match self.enclosing_environment.resolve_var(sym) {
+            Some(var) => var.qualified(),
+            None if sym.has_ns() => Symbol::clone(sym),
+            None => Symbol::intern_with_ns(
+                &self.enclosing_environment.get_current_namespace_name(),
+                &sym.name,
+            ),
+        };
+        let mut expansion = vec![
+            clojure_spec::qualified("fdef*"),
+            quote(qualified.to_rc_value()),
+        ];
+        for (ind, form) in args.iter().enumerate().skip(1) {
+            expansion.push(match ind % 2 {
+                // Keys,  as they are;  and specs,  made into specs that keep their forms
+                1 => Arc::clone(form),
+                _ => list(vec![clojure_spec::qualified("spec"), Arc::clone(form)]),
+            });
+        }
+        expansion.into_list().to_value()
+    }
+}
+
+/// (clojure.spec.alpha/fdef* sym & specs) ; what fdef expands to
+#[derive(Debug, Clone)]
+pub struct FdefFn {}
+impl ToValue for FdefFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FdefFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        if args.len().is_multiple_of(2) {
+            return Value::Condition(
+                "fdef takes :args,  :ret and :fn,  each followed by a spec".into(),
+            );
+        }
+        let mut specs = vec![];
+        for pair in args[1..].chunks(2) {
+            match &*pair[0] {
+                Value::Keyword(key) if ["args", "ret", "fn"].contains(&key.sym.name.as_str()) => {
+                    specs.push(MapEntry {
+                        key: Keyword::clone(key).to_rc_value(),
+                        val: Arc::clone(&pair[1]),
+                    })
+                }
+                key => return options::unknown("fdef", &["args", "ret", "fn"], key),
+            }
+        }
+        let specs = specs
+            .into_iter()
+            .collect::<PersistentListMap>()
+            .to_rc_value();
+        clojure_spec::register((*args[0]).clone(), specs);
+        (*args[0]).clone()
+    }
+}
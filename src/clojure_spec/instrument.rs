@@ -0,0 +1,221 @@
+use crate::clojure_spec;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::keyword::Keyword;
+use crate::lazy_seq;
+use crate::persistent_vector::ToPersistentVector;
+use crate::rust_core::GetFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+// What each instrumented fn's var held before;  for unstrument to give back
+fn originals() -> MutexGuard<'static, HashMap<Symbol, Arc<Value>>> {
+    static ORIGINALS: OnceLock<Mutex<HashMap<Symbol, Arc<Value>>>> = OnceLock::new();
+    ORIGINALS.get_or_init(Default::default).lock().unwrap()
+}
+
+// The fns (args) names;  a symbol,  or a coll of them.  Every fdef'd fn,  if there's none
+fn named(
+    environment: &Environment,
+    args: &[Arc<Value>],
+    every: impl FnOnce() -> Vec<Symbol>,
+) -> Result<Vec<Symbol>, Value> {
+    let mut syms = vec![];
+    match args {
+        [] => syms = every(),
+        [arg] => match &**arg {
+            Value::Symbol(sym) => syms.push(Symbol::clone(sym)),
+            _ => lazy_seq::try_for_each(arg, |sym| match &*sym {
+                Value::Symbol(sym) => {
+                    syms.push(Symbol::clone(sym));
+                    Ok(())
+                }
+                _ => Err(error_message::type_mismatch(TypeTag::Symbol, &sym)),
+            })?,
+        },
+        _ => return Err(error_message::wrong_varg_count(&[0, 1], args.len())),
+    }
+    Ok(syms
+        .iter()
+        .map(|sym| match environment.resolve_var(sym) {
+            Some(var) => var.qualified(),
+            None => Symbol::clone(sym),
+        })
+        .collect())
+}
+
+fn syms_value(syms: Vec<Symbol>) -> Value {
+    syms.into_iter()
+        .map(|sym| sym.to_rc_value())
+        .collect::<Vec<Arc<Value>>>()
+        .into_vector()
+        .to_value()
+}
+
+/// (clojure.spec.test.alpha/instrument)
+/// (clojure.spec.test.alpha/instrument sym-or-syms)
+///
+/// Has each fn named (each fdef'd one,  if none are) check its args against its fdef's :args
+/// spec on every call;  throwing why they don't conform,  if they don't.  Returns the
+/// qualified symbols of those it instrumented;  a fn without an :args spec isn't
+#[derive(Debug, Clone)]
+pub struct InstrumentFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl InstrumentFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> InstrumentFn {
+        InstrumentFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for InstrumentFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for InstrumentFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let syms = match named(&self.enclosing_environment, &args, clojure_spec::fdefs) {
+            Ok(syms) => syms,
+            Err(condition) => return condition,
+        };
+        let mut instrumented = vec![];
+        for sym in syms {
+            let args_spec = clojure_spec::registered(&sym.to_value())
+                .map(|specs| GetFn {}.invoke(vec![specs, Keyword::intern("args").to_rc_value()]));
+            let args_spec = match args_spec {
+                Some(Value::Nil) | None => continue,
+                Some(args_spec) => args_spec.to_rc_value(),
+            };
+            let var = match self.enclosing_environment.resolve_var(&sym) {
+                Some(var) => var,
+                None => continue,
+            };
+            let mut originals = originals();
+            let f = Arc::clone(
+                originals
+                    .entry(Symbol::clone(&sym))
+                    .or_insert_with(|| var.root()),
+            );
+            let checked = Instrumented {
+                name: Symbol::clone(&sym),
+                f,
+                args_spec,
+            };
+            var.set_root(checked.to_rc_value());
+            instrumented.push(sym);
+        }
+        syms_value(instrumented)
+    }
+}
+
+/// (clojure.spec.test.alpha/unstrument)
+/// (clojure.spec.test.alpha/unstrument sym-or-syms)
+///
+/// Undoes instrument;  for each fn named,  or each instrumented one if none are.  Returns
+/// the qualified symbols of those it undid it for
+#[derive(Debug, Clone)]
+pub struct UnstrumentFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl UnstrumentFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> UnstrumentFn {
+        UnstrumentFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for UnstrumentFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for UnstrumentFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let every = || originals().keys().cloned().collect();
+        let syms = match named(&self.enclosing_environment, &args, every) {
+            Ok(syms) => syms,
+            Err(condition) => return condition,
+        };
+        let mut unstrumented = vec![];
+        for sym in syms {
+            let original = originals().remove(&sym);
+            if let (Some(original), Some(var)) =
+                (original, self.enclosing_environment.resolve_var(&sym))
+            {
+                var.set_root(original);
+                unstrumented.push(sym);
+            }
+        }
+        syms_value(unstrumented)
+    }
+}
+
+// An instrumented fn;  checking its args before it's called
+#[derive(Debug, Clone)]
+struct Instrumented {
+    name: Symbol,
+    f: Arc<Value>,
+    args_spec: Arc<Value>,
+}
+impl ToValue for Instrumented {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for Instrumented {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let arg_vector = args.clone().into_vector().to_rc_value();
+        let problems = match clojure_spec::conform(&self.args_spec, &arg_vector) {
+            Ok(Some(_)) => return ifn::call(&self.f, args),
+            Ok(None) => clojure_spec::explain(&self.args_spec, &arg_vector),
+            Err(condition) => return condition,
+        };
+        match problems {
+            Ok(problems) => Value::Condition(
+                format!(
+                    "Call to #'{} did not conform to spec:  {}",
+                    self.name,
+                    clojure_spec::explain_str(&problems).trim_end()
+                )
+                .into(),
+            ),
+            Err(condition) => condition,
+        }
+    }
+    fn arglists(&self) -> Option<Vec<Vec<Symbol>>> {
+        match &*self.f {
+            Value::IFn(f) => f.arglists(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod instrument_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn instrumented_fns_check_their_args_until_unstrumented() {
+            let result = Repl::default().eval_all(&[
+                "(require '[clojure.spec.alpha :as s] '[clojure.spec.test.alpha :as stest])",
+                "(defn spec-test-add [a b] (+ a b))",
+                "(s/fdef spec-test-add :args (s/tuple int? int?))",
+                "(stest/instrument 'spec-test-add)",
+                "(def checked (ex-message (try (spec-test-add 1 :b) (catch Exception e e))))",
+                "(stest/unstrument 'spec-test-add)",
+                "[(spec-test-add 1 2) checked]",
+            ]);
+            assert_eq!(
+                "[3 \"Call to #'user/spec-test-add did not conform to spec:  :b - failed: int? in: [1] at: [1]\"]",
+                result.to_string()
+            );
+        }
+    }
+}
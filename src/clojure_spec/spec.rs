@@ -0,0 +1,245 @@
+use crate::clojure_spec::{self, CollOf, Keys, Kind, Spec};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_list::{PersistentList, ToPersistentList};
+use crate::rust_core::defprotocol_macro::quote;
+use crate::rust_core::thread_macros::list;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.spec.alpha/spec,  and,  or,  nilable,  keys,  coll-of and tuple ; specs
+/// (spec pred)
+/// (and spec ..)
+/// (or :tag spec ..)
+/// (nilable spec)
+/// (keys :req [:ns/k ..] :opt [..] :req-un [..] :opt-un [..])
+/// (coll-of spec :kind pred :count n :min-count n :max-count n :distinct bool)
+/// (tuple spec ..)
+///
+/// A spec is a pred (a fn,  or a set of the values allowed),  a spec one of these makes,  or
+/// the name a spec was registered under with def.  (or :i int? :s string?) conforms 1 to
+/// [:i 1].  (keys ..) is a map with the :req keys (and :req-un ones,  unqualified);  any
+/// of its keys that names a spec,  or that is the name of an :opt-un or :req-un key,  has
+/// a value that conforms to it
+///
+/// (and int? pos?) expands to
+///
+/// (clojure.spec.alpha/spec* '(clojure.spec.alpha/and int? pos?) int? pos?)
+#[derive(Debug, Clone)]
+pub struct SpecMacro {
+    pub name: &'static str,
+}
+impl ToValue for SpecMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for SpecMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let mut form = vec![clojure_spec::qualified(self.name)];
+        form.extend(args.iter().cloned());
+        let mut expansion = vec![clojure_spec::qualified("spec*"), quote(list(form))];
+        expansion.extend(args);
+        expansion.into_list().to_value()
+    }
+}
+
+/// (clojure.spec.alpha/spec* form & args) ; what the spec macros expand to
+///
+/// The spec form says to make,  of args;  the values of form's args
+#[derive(Debug, Clone)]
+pub struct SpecFn {}
+impl ToValue for SpecFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SpecFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let form = &args[0];
+        let items = match &**form {
+            Value::PersistentList(plist) => {
+                PersistentList::iter(plist).collect::<Vec<Arc<Value>>>()
+            }
+            _ => return error_message::type_mismatch(TypeTag::PersistentList, form),
+        };
+        let name = match items.first().map(|head| &**head) {
+            Some(Value::Symbol(head)) => head.name.to_string(),
+            _ => return Value::Condition(format!("Unknown spec {}", form).into()),
+        };
+        let (forms, vals) = (&items[1..], &args[1..]);
+        if forms.len() != vals.len() {
+            return error_message::wrong_varg_count(&[forms.len() + 1], args.len());
+        }
+        if name == "spec" {
+            return match vals.len() {
+                1 => clojure_spec::spec_of(&forms[0], &vals[0])
+                    .map_or_else(|condition| condition, |spec| (*spec).clone()),
+                argc => error_message::wrong_arg_count(1, argc),
+            };
+        }
+        match kind(&name, forms, vals) {
+            Ok(kind) => (*Spec::new_value(Arc::clone(form), kind)).clone(),
+            Err(condition) => condition,
+        }
+    }
+}
+
+fn kind(name: &str, forms: &[Arc<Value>], vals: &[Arc<Value>]) -> Result<Kind, Value> {
+    let specs = |forms: &[Arc<Value>], vals: &[Arc<Value>]| {
+        forms
+            .iter()
+            .zip(vals.iter())
+            .map(|(form, val)| clojure_spec::spec_of(form, val))
+            .collect::<Result<Vec<Arc<Value>>, Value>>()
+    };
+    Ok(match name {
+        "and" => Kind::And(specs(forms, vals)?),
+        "tuple" => Kind::Tuple(specs(forms, vals)?),
+        "nilable" if vals.len() == 1 => Kind::Nilable(clojure_spec::spec_of(&forms[0], &vals[0])?),
+        "nilable" => return Err(error_message::wrong_arg_count(1, vals.len())),
+        "or" => {
+            if !vals.len().is_multiple_of(2) {
+                return Err(Value::Condition(
+                    "or takes keyword tags each followed by a spec".into(),
+                ));
+            }
+            let mut branches = vec![];
+            for (forms, vals) in forms.chunks(2).zip(vals.chunks(2)) {
+                if !matches!(&*vals[0], Value::Keyword(_)) {
+                    return Err(error_message::type_mismatch(TypeTag::Keyword, &vals[0]));
+                }
+                let spec = clojure_spec::spec_of(&forms[1], &vals[1])?;
+                branches.push((Arc::clone(&vals[0]), spec));
+            }
+            Kind::Or(branches)
+        }
+        "keys" => Kind::Keys(keys(vals)?),
+        "coll-of" if !vals.is_empty() => Kind::CollOf(coll_of(forms, vals)?),
+        "coll-of" => return Err(error_message::wrong_varg_count(&[1], 0)),
+        _ => {
+            return Err(Value::Condition(
+                format!("Unknown spec clojure.spec.alpha/{}", name).into(),
+            ))
+        }
+    })
+}
+
+// The options in vals,  as :key val pairs;  each key the name of one given
+fn options(
+    name: &str,
+    vals: &[Arc<Value>],
+    known: &[&'static str],
+) -> Result<Vec<(&'static str, usize)>, Value> {
+    if !vals.len().is_multiple_of(2) {
+        return Err(Value::Condition(
+            format!("{} takes options;  keys each followed by a value", name).into(),
+        ));
+    }
+    let mut options = vec![];
+    for (ind, key) in vals.iter().enumerate().step_by(2) {
+        let option = match &**key {
+            Value::Keyword(key) => known
+                .iter()
+                .find(|option| **option == key.sym.name.as_str()),
+            _ => None,
+        };
+        match option {
+            Some(option) => options.push((*option, ind + 1)),
+            None => return Err(crate::options::unknown(name, known, key)),
+        }
+    }
+    Ok(options)
+}
+
+fn keys(vals: &[Arc<Value>]) -> Result<Keys, Value> {
+    let mut keys = Keys::default();
+    for (option, ind) in options("keys", vals, &["req", "opt", "req-un", "opt-un"])? {
+        let listed = qualified_keywords(&vals[ind])?;
+        match option {
+            "req" => keys.req = listed,
+            "req-un" => keys.req_un = listed,
+            "opt-un" => keys.opt_un = listed,
+            _ => {}
+        }
+    }
+    Ok(keys)
+}
+
+// The keywords in a vector;  each qualified,  as a spec's name is
+fn qualified_keywords(val: &Value) -> Result<Vec<Keyword>, Value> {
+    let pvector = match val {
+        Value::PersistentVector(pvector) => pvector,
+        _ => return Err(error_message::type_mismatch(TypeTag::PersistentVector, val)),
+    };
+    pvector
+        .iter()
+        .map(|key| match &**key {
+            Value::Keyword(key) if key.sym.has_ns() => Ok(Keyword::clone(key)),
+            _ => Err(Value::Condition(
+                format!(
+                    "keys lists qualified keywords;  not {}",
+                    key.to_string_explicit()
+                )
+                .into(),
+            )),
+        })
+        .collect()
+}
+
+fn coll_of(forms: &[Arc<Value>], vals: &[Arc<Value>]) -> Result<CollOf, Value> {
+    let mut coll_of = CollOf {
+        spec: clojure_spec::spec_of(&forms[0], &vals[0])?,
+        kind: None,
+        count: None,
+        min_count: None,
+        max_count: None,
+        distinct: false,
+    };
+    let known = ["kind", "count", "min-count", "max-count", "distinct"];
+    for (option, ind) in options("coll-of", &vals[1..], &known)? {
+        let (form, val) = (&forms[ind + 1], &vals[ind + 1]);
+        let count = || match &**val {
+            Value::I32(n) if *n >= 0 => Ok(*n as usize),
+            _ => Err(error_message::type_mismatch(TypeTag::I32, val)),
+        };
+        match option {
+            "kind" => coll_of.kind = Some((Arc::clone(val), Arc::clone(form))),
+            "count" => coll_of.count = Some(count()?),
+            "min-count" => coll_of.min_count = Some(count()?),
+            "max-count" => coll_of.max_count = Some(count()?),
+            _ => coll_of.distinct = val.is_truthy(),
+        }
+    }
+    Ok(coll_of)
+}
+
+/// (clojure.spec.alpha/form spec)
+///
+/// What spec was made from;  (clojure.spec.alpha/coll-of int?),  say.  Given a spec's
+/// name,  that of the spec it names
+#[derive(Debug, Clone)]
+pub struct FormFn {}
+impl ToValue for FormFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FormFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match clojure_spec::lookup(&args[0])
+            .and_then(|spec| clojure_spec::resolve(&spec).map(|spec| Arc::clone(&spec.form)))
+        {
+            Ok(form) => (*form).clone(),
+            Err(condition) => condition,
+        }
+    }
+}
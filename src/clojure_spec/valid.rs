@@ -0,0 +1,48 @@
+use crate::clojure_spec;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (clojure.spec.alpha/valid? spec x)
+///
+/// Whether x conforms to spec;  a spec,  a spec's name,  or a pred
+#[derive(Debug, Clone)]
+pub struct ValidFn {}
+impl ToValue for ValidFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ValidFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match clojure_spec::spec_of(&args[0], &args[0])
+            .and_then(|spec| clojure_spec::conform(&spec, &args[1]))
+        {
+            Ok(conformed) => Value::Boolean(conformed.is_some()),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// (clojure.spec.alpha/invalid? x)
+///
+/// Whether x is :clojure.spec.alpha/invalid;  what conform gives for what doesn't conform
+#[derive(Debug, Clone)]
+pub struct InvalidFn {}
+impl ToValue for InvalidFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for InvalidFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Boolean(*args[0] == clojure_spec::invalid())
+    }
+}
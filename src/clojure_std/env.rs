@@ -1,7 +1,7 @@
 use crate::error_message;
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use std::env;
 use crate::type_tag::TypeTag;
@@ -12,17 +12,17 @@ use crate::type_tag::TypeTag;
 pub struct GetEnvFn {}
 impl ToValue for GetEnvFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 
 impl IFn for GetEnvFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() == 1 {
             match args.get(0).unwrap().to_value() {
                 Value::String(key) => {
-                    match env::var(key) {
-                        Ok(val) => Value::String(val),
+                    match env::var(&*key) {
+                        Ok(val) => Value::String(val.into()),
                         Err(_) => Value::Nil
                     }
                 }
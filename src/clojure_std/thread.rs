@@ -2,7 +2,7 @@ use crate::error_message;
 use crate::ifn::IFn;
 use crate::type_tag::TypeTag;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use std::time;
 
@@ -11,12 +11,12 @@ use std::time;
 pub struct SleepFn {}
 impl ToValue for SleepFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 
 impl IFn for SleepFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() == 1 {
             let arg = &**args.get(0).unwrap();
             match arg {
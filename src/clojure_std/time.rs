@@ -1,7 +1,7 @@
 use crate::error_message;
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -10,12 +10,12 @@ use std::time::{SystemTime, UNIX_EPOCH};
 pub struct NanoTimeFn {}
 impl ToValue for NanoTimeFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 
 impl IFn for NanoTimeFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.is_empty() {
             let ns = SystemTime::now()
                 .duration_since(UNIX_EPOCH)
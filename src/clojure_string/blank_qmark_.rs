@@ -1,6 +1,6 @@
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error_message;
 use crate::type_tag::TypeTag;
@@ -10,11 +10,11 @@ use crate::type_tag::TypeTag;
 pub struct BlankFn {}
 impl ToValue for BlankFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for BlankFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 1 {
             return error_message::wrong_arg_count(1, args.len());
         } else {
@@ -33,7 +33,7 @@ impl IFn for BlankFn {
                         );
                     }
                 }
-                Value::String(s) => Value::String(s.chars().rev().collect()),
+                Value::String(s) => Value::String(s.chars().rev().collect::<String>().into()),
                 _a => error_message::type_mismatch(TypeTag::String, &_a.to_value()),
             }
         }
@@ -46,13 +46,13 @@ mod tests {
         use crate::clojure_string::blank_qmark_::BlankFn;
         use crate::ifn::IFn;
         use crate::value::Value;
-        use std::rc::Rc;
+        use std::sync::Arc;
 
         #[test]
         fn is_non_empty_string_blank() {
             let blank = BlankFn {};
             let s = "hello";
-            let args = vec![Rc::new(Value::String(String::from(s)))];
+            let args = vec![Arc::new(Value::String(s.into()))];
             assert_eq!(Value::Boolean(false), blank.invoke(args));
         }
 
@@ -60,7 +60,7 @@ mod tests {
         fn is_empty_string_blank() {
             let blank = BlankFn {};
             let s = "";
-            let args = vec![Rc::new(Value::String(String::from(s)))];
+            let args = vec![Arc::new(Value::String(s.into()))];
             assert_eq!(Value::Boolean(true), blank.invoke(args));
         }
 
@@ -68,7 +68,7 @@ mod tests {
         fn is_string_with_whitespace_only_blank() {
             let blank = BlankFn {};
             let s = " \t \n   \r ";
-            let args = vec![Rc::new(Value::String(String::from(s)))];
+            let args = vec![Arc::new(Value::String(s.into()))];
             assert_eq!(Value::Boolean(true), blank.invoke(args));
         }
 
@@ -76,14 +76,14 @@ mod tests {
         fn is_string_with_whitespace_and_text_blank() {
             let blank = BlankFn {};
             let s = " \thello \n   \r ";
-            let args = vec![Rc::new(Value::String(String::from(s)))];
+            let args = vec![Arc::new(Value::String(s.into()))];
             assert_eq!(Value::Boolean(false), blank.invoke(args));
         }
 
         #[test]
         fn is_nil_blank() {
             let blank = BlankFn {};
-            let args = vec![Rc::new(Value::Nil)];
+            let args = vec![Arc::new(Value::Nil)];
             assert_eq!(Value::Boolean(true), blank.invoke(args));
         }
     }
@@ -0,0 +1,49 @@
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/capitalize ; upper cases the first char of s,  and lower cases the rest
+#[derive(Debug, Clone)]
+pub struct CapitalizeFn {}
+impl ToValue for CapitalizeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CapitalizeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::String(s) => {
+                let mut chars = s.chars();
+                let capitalized = match chars.next() {
+                    Some(first) => first
+                        .to_uppercase()
+                        .chain(chars.as_str().to_lowercase().chars())
+                        .collect(),
+                    None => String::new(),
+                };
+                Value::String(capitalized.into())
+            }
+            _ => error_message::type_mismatch(TypeTag::String, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn capitalize_upper_cases_only_the_first_char() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/capitalize \"hELLO wORLD\") (clojure.string/capitalize \"éCOLE\") (clojure.string/capitalize \"\")]",
+        ]);
+        assert_eq!("[\"Hello world\" \"École\" \"\"]", result.to_string());
+    }
+}
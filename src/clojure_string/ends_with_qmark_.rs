@@ -1,6 +1,6 @@
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error_message;
 use crate::type_tag::TypeTag;
@@ -10,11 +10,11 @@ use crate::type_tag::TypeTag;
 pub struct EndsWithFn {}
 impl ToValue for EndsWithFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for EndsWithFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 2 {
             return error_message::wrong_arg_count(2, args.len());
         } else {
@@ -23,7 +23,7 @@ impl IFn for EndsWithFn {
                 args.get(1).unwrap().to_value(),
             ) {
                 (Value::String(s), Value::String(substring)) => {
-                    Value::Boolean(s.ends_with(&substring))
+                    Value::Boolean(s.ends_with(&*substring))
                 }
                 _a => error_message::type_mismatch(TypeTag::String, &_a.1.to_value()),
             }
@@ -37,7 +37,7 @@ mod tests {
         use crate::clojure_string::ends_with_qmark_::EndsWithFn;
         use crate::ifn::IFn;
         use crate::value::Value;
-        use std::rc::Rc;
+        use std::sync::Arc;
 
         #[test]
         fn hello_ends_with_lo() {
@@ -45,8 +45,8 @@ mod tests {
             let s = "hello";
             let substring = "lo";
             let args = vec![
-                Rc::new(Value::String(String::from(s))),
-                Rc::new(Value::String(String::from(substring))),
+                Arc::new(Value::String(s.into())),
+                Arc::new(Value::String(substring.into())),
             ];
             assert_eq!(Value::Boolean(true), ends_with.invoke(args));
         }
@@ -57,8 +57,8 @@ mod tests {
             let s = "hello";
             let substring = "klo";
             let args = vec![
-                Rc::new(Value::String(String::from(s))),
-                Rc::new(Value::String(String::from(substring))),
+                Arc::new(Value::String(s.into())),
+                Arc::new(Value::String(substring.into())),
             ];
             assert_eq!(Value::Boolean(false), ends_with.invoke(args));
         }
@@ -69,8 +69,8 @@ mod tests {
             let s = "hello";
             let substring = "";
             let args = vec![
-                Rc::new(Value::String(String::from(s))),
-                Rc::new(Value::String(String::from(substring))),
+                Arc::new(Value::String(s.into())),
+                Arc::new(Value::String(substring.into())),
             ];
             assert_eq!(Value::Boolean(true), ends_with.invoke(args));
         }
@@ -0,0 +1,51 @@
+use crate::ifn::{self, IFn};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/escape ; (escape s cmap) replaces each char c of s with (cmap c),  unless
+/// that's nil.  cmap is a fn,  or a map of chars to what to replace them with
+#[derive(Debug, Clone)]
+pub struct EscapeFn {}
+impl ToValue for EscapeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for EscapeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let s = match &*args[0] {
+            Value::String(s) => s,
+            _ => return error_message::type_mismatch(TypeTag::String, &args[0]),
+        };
+        let mut escaped = String::with_capacity(s.len());
+        for c in s.chars() {
+            let c_value = Value::Char(c).to_rc_value();
+            let replacement = ifn::call(&args[1], vec![c_value]);
+            match replacement {
+                Value::Nil => escaped.push(c),
+                condition @ Value::Condition(_) => return condition,
+                replacement => escaped.push_str(&replacement.to_string()),
+            }
+        }
+        Value::String(escaped.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn chars_are_escaped_with_maps_or_fns() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/escape \"<a & b>\" {\\< \"&lt;\" \\> \"&gt;\" \\& \"&amp;\"}) (clojure.string/escape \"a-b\" (fn [c] (if (= c \\-) \\_)))]",
+        ]);
+        assert_eq!("[\"&lt;a &amp; b&gt;\" \"a_b\"]", result.to_string());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/grapheme-count ; how many grapheme clusters s has;  what a reader
+/// would count as its characters,  where count counts its chars
+#[derive(Debug, Clone)]
+pub struct GraphemeCountFn {}
+impl ToValue for GraphemeCountFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for GraphemeCountFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::String(s) => Value::I32(s.graphemes(true).count() as i32),
+            _ => error_message::type_mismatch(TypeTag::String, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn graphemes_are_counted() {
+        let result = Repl::default().eval_all(&[
+            "(def flag \"\u{1F1EB}\u{1F1F7}\")",
+            "[(count flag) (clojure.string/grapheme-count flag) (clojure.string/grapheme-count \"ne\u{301}e\")]",
+        ]);
+        assert_eq!("[2 1 3]", result.to_string());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/grapheme-reverse ; reverses s a grapheme cluster at a time,  so
+/// accents stay on the letters they are on
+#[derive(Debug, Clone)]
+pub struct GraphemeReverseFn {}
+impl ToValue for GraphemeReverseFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for GraphemeReverseFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::String(s) => {
+                let graphemes = s.graphemes(true).collect::<Vec<&str>>();
+                Value::String(graphemes.into_iter().rev().collect::<String>().into())
+            }
+            _ => error_message::type_mismatch(TypeTag::String, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn accents_stay_on_their_letters() {
+        let result =
+            Repl::default().eval_all(&["(clojure.string/grapheme-reverse \"ne\u{301}e\")"]);
+        assert_eq!("ee\u{301}n", result.to_string());
+    }
+}
@@ -0,0 +1,46 @@
+use crate::ifn::IFn;
+use crate::rust_core::subs;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// clojure.string/grapheme-subs ; (grapheme-subs s start) (grapheme-subs s start end) is
+/// like subs,  but counting grapheme clusters rather than chars
+#[derive(Debug, Clone)]
+pub struct GraphemeSubsFn {}
+impl ToValue for GraphemeSubsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for GraphemeSubsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (s, start, end) = match subs::subs_args(&args) {
+            Ok(subs_args) => subs_args,
+            Err(condition) => return condition,
+        };
+        let graphemes = s.graphemes(true).collect::<Vec<&str>>();
+        match subs::bounds(start, end, graphemes.len()) {
+            Ok((start, end)) => Value::String(graphemes[start..end].concat().into()),
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn graphemes_are_never_split() {
+        let result = Repl::default().eval_all(&[
+            "[(subs \"ne\u{301}e\" 0 2) (clojure.string/grapheme-subs \"ne\u{301}e\" 0 2)]",
+        ]);
+        assert_eq!("[\"ne\" \"ne\u{301}\"]", result.to_string());
+        let result = Repl::default().eval_all(&["(clojure.string/grapheme-subs \"ne\u{301}e\" 4)"]);
+        assert_eq!(
+            "#Condition[\"String index out of range: 4\"]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::ifn::IFn;
+use crate::persistent_vector::ToPersistentVector;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/graphemes ; the grapheme clusters of s,  what a reader takes
+/// as its characters,  as a vector of strings
+#[derive(Debug, Clone)]
+pub struct GraphemesFn {}
+impl ToValue for GraphemesFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for GraphemesFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::String(s) => s
+                .graphemes(true)
+                .map(|grapheme| Value::String(grapheme.into()).to_rc_value())
+                .collect::<Vec<Arc<Value>>>()
+                .into_vector()
+                .to_value(),
+            _ => error_message::type_mismatch(TypeTag::String, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn strings_are_split_into_graphemes() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/graphemes \"ne\u{301}e\") (clojure.string/graphemes \"\")]",
+        ]);
+        assert_eq!("[[\"n\" \"e\u{301}\" \"e\"] []]", result.to_string());
+    }
+
+    #[test]
+    fn emoji_flags_and_hangul_are_one_grapheme_each() {
+        let result = Repl::default().eval_all(&[
+            "(clojure.string/graphemes \"\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{1F44D}\u{1F3FD}\u{1F1EB}\u{1F1F7}\u{1100}\u{1161}\u{11A8}\r\n\")",
+        ]);
+        assert_eq!(
+            "[\"\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\" \"\u{1F44D}\u{1F3FD}\" \"\u{1F1EB}\u{1F1F7}\" \"\u{1100}\u{1161}\u{11A8}\" \"\\r\\n\"]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn spacing_marks_join_what_they_follow() {
+        // Thai sara am,  and Devanagari vowel signs and viramas
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/graphemes \"\u{E33}\u{E01}\u{E33}\") (clojure.string/graphemes \"\u{928}\u{92E}\u{938}\u{94D}\u{924}\u{947}\")]",
+        ]);
+        assert_eq!(
+            "[[\"\u{E33}\" \"\u{E01}\u{E33}\"] [\"\u{928}\" \"\u{92E}\" \"\u{938}\u{94D}\u{924}\u{947}\"]]",
+            result.to_string()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error_message;
 use crate::type_tag::TypeTag;
@@ -10,11 +10,11 @@ use crate::type_tag::TypeTag;
 pub struct IncludesFn {}
 impl ToValue for IncludesFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for IncludesFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 2 {
             return error_message::wrong_arg_count(2, args.len());
         } else {
@@ -23,7 +23,7 @@ impl IFn for IncludesFn {
                 args.get(1).unwrap().to_value(),
             ) {
                 (Value::String(s), Value::String(substring)) => {
-                    Value::Boolean(s.contains(&substring))
+                    Value::Boolean(s.contains(&*substring))
                 }
                 _a => error_message::type_mismatch(TypeTag::String, &_a.1.to_value()),
             }
@@ -37,7 +37,7 @@ mod tests {
         use crate::clojure_string::includes_qmark_::IncludesFn;
         use crate::ifn::IFn;
         use crate::value::Value;
-        use std::rc::Rc;
+        use std::sync::Arc;
 
         #[test]
         fn hello_includes_ell() {
@@ -45,8 +45,8 @@ mod tests {
             let s = "hello";
             let substring = "ell";
             let args = vec![
-                Rc::new(Value::String(String::from(s))),
-                Rc::new(Value::String(String::from(substring))),
+                Arc::new(Value::String(s.into())),
+                Arc::new(Value::String(substring.into())),
             ];
             assert_eq!(Value::Boolean(true), includes.invoke(args));
         }
@@ -57,8 +57,8 @@ mod tests {
             let s = "hello";
             let substring = "leh";
             let args = vec![
-                Rc::new(Value::String(String::from(s))),
-                Rc::new(Value::String(String::from(substring))),
+                Arc::new(Value::String(s.into())),
+                Arc::new(Value::String(substring.into())),
             ];
             assert_eq!(Value::Boolean(false), includes.invoke(args));
         }
@@ -69,8 +69,8 @@ mod tests {
             let s = "hello";
             let substring = "";
             let args = vec![
-                Rc::new(Value::String(String::from(s))),
-                Rc::new(Value::String(String::from(substring))),
+                Arc::new(Value::String(s.into())),
+                Arc::new(Value::String(substring.into())),
             ];
             assert_eq!(Value::Boolean(true), includes.invoke(args));
         }
@@ -0,0 +1,76 @@
+use crate::ifn::IFn;
+use crate::shared_str::SharedStr;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/index-of ; (index-of s value) (index-of s value from-index) is the index
+/// of the first value (a string or char) in s,  at or after from-index;  or nil
+#[derive(Debug, Clone)]
+pub struct IndexOfFn {}
+impl ToValue for IndexOfFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for IndexOfFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (s, value, from) = match index_args(&args) {
+            Ok(index_args) => index_args,
+            Err(condition) => return condition,
+        };
+        let start = byte_index(&s, from.unwrap_or(0).max(0) as usize);
+        match s[start..].find(&value) {
+            Some(found) => Value::I32(char_index(&s, start + found)),
+            None => Value::Nil,
+        }
+    }
+}
+
+/// Shared with last-index-of;  s,  the value looked for in it,  and where from
+pub(crate) fn index_args(args: &[Arc<Value>]) -> Result<(SharedStr, String, Option<i32>), Value> {
+    if args.len() != 2 && args.len() != 3 {
+        return Err(error_message::wrong_varg_count(&[2, 3], args.len()));
+    }
+    let s = match &*args[0] {
+        Value::String(s) => SharedStr::clone(s),
+        _ => return Err(error_message::type_mismatch(TypeTag::String, &args[0])),
+    };
+    let value = match &*args[1] {
+        Value::String(value) => value.to_string(),
+        Value::Char(value) => value.to_string(),
+        _ => return Err(error_message::type_mismatch(TypeTag::String, &args[1])),
+    };
+    let from = match args.get(2).map(|from| &**from) {
+        None => None,
+        Some(Value::I32(from)) => Some(*from),
+        Some(from) => return Err(error_message::type_mismatch(TypeTag::Integer, from)),
+    };
+    Ok((s, value, from))
+}
+
+/// Shared with last-index-of;  where the char at index starts in s,  or its end if
+/// there's no such char
+pub(crate) fn byte_index(s: &str, index: usize) -> usize {
+    s.char_indices().nth(index).map_or(s.len(), |(i, _)| i)
+}
+
+/// Shared with last-index-of;  which char starts at byte_index in s
+pub(crate) fn char_index(s: &str, byte_index: usize) -> i32 {
+    s[..byte_index].chars().count() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn index_of_finds_strings_and_chars() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/index-of \"héllo, hello\" \"o\") (clojure.string/index-of \"héllo, hello\" \\o 5) (clojure.string/index-of \"abc\" \"z\") (clojure.string/index-of \"abc\" \"\" 7) (clojure.string/index-of \"abc\" \"a\" -3)]",
+        ]);
+        assert_eq!("[4 11 nil 3 0]", result.to_string());
+    }
+}
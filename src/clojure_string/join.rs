@@ -1,7 +1,7 @@
-use crate::iterable::Iterable;
 use crate::ifn::IFn;
+use crate::iterable::Iterable;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error_message;
 use crate::protocol::ProtocolCastable;
@@ -14,15 +14,15 @@ use crate::protocol::ProtocolCastable;
 pub struct JoinFn {}
 impl ToValue for JoinFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for JoinFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 1 && args.len() != 2 {
             return error_message::wrong_varg_count(&[1, 2], args.len());
         }
-        
+
         let separator = if args.len() == 1 {
             String::from("")
         } else {
@@ -41,12 +41,11 @@ impl IFn for JoinFn {
                     .map(|x| x.to_string())
                     .collect::<Vec<std::string::String>>()
                     .join(&separator)
+                    .into(),
             )
+        } else {
+            Value::String("".into())
         }
-        else {
-            Value::String(String::from(""))
-        }
-        
     }
 }
 
@@ -58,46 +57,43 @@ mod tests {
         use crate::persistent_list::PersistentList;
         use crate::persistent_vector::PersistentVector;
         use crate::value::Value;
-        use std::rc::Rc;
+        use std::sync::Arc;
 
         #[test]
         fn join_empty_collection_to_empty_string() {
             let join = JoinFn {};
-            let args = vec![Rc::new(Value::PersistentList(
+            let args = vec![Arc::new(Value::PersistentList(Arc::new(
                 vec![].into_iter().collect::<PersistentList>(),
-            ))];
-            assert_eq!(Value::String(String::from("")), join.invoke(args));
+            )))];
+            assert_eq!(Value::String("".into()), join.invoke(args));
         }
 
         #[test]
         fn join_one_item_collection_to_string() {
             let join = JoinFn {};
             let s = "hello";
-            let args = vec![Rc::new(Value::PersistentList(
-                vec![Rc::new(Value::String(String::from(s)))]
+            let args = vec![Arc::new(Value::PersistentList(Arc::new(
+                vec![Arc::new(Value::String(s.into()))]
                     .into_iter()
                     .collect::<PersistentList>(),
-            ))];
-            assert_eq!(Value::String(String::from("hello")), join.invoke(args));
+            )))];
+            assert_eq!(Value::String("hello".into()), join.invoke(args));
         }
 
         #[test]
         fn join_multiple_items_in_collection_to_string() {
             let join = JoinFn {};
             let s = "hello";
-            let args = vec![Rc::new(Value::PersistentList(
+            let args = vec![Arc::new(Value::PersistentList(Arc::new(
                 vec![
-                    Rc::new(Value::String(String::from(s))),
-                    Rc::new(Value::I32(5)),
-                    Rc::new(Value::String(String::from(s))),
+                    Arc::new(Value::String(s.into())),
+                    Arc::new(Value::I32(5)),
+                    Arc::new(Value::String(s.into())),
                 ]
                 .into_iter()
                 .collect::<PersistentList>(),
-            ))];
-            assert_eq!(
-                Value::String(String::from("hello5hello")),
-                join.invoke(args)
-            );
+            )))];
+            assert_eq!(Value::String("hello5hello".into()), join.invoke(args));
         }
 
         #[test]
@@ -105,21 +101,18 @@ mod tests {
             let join = JoinFn {};
             let s = "hello";
             let args = vec![
-                Rc::new(Value::String(String::from(", "))),
-                Rc::new(Value::PersistentList(
+                Arc::new(Value::String(", ".into())),
+                Arc::new(Value::PersistentList(Arc::new(
                     vec![
-                        Rc::new(Value::String(String::from(s))),
-                        Rc::new(Value::I32(5)),
-                        Rc::new(Value::String(String::from(s))),
+                        Arc::new(Value::String(s.into())),
+                        Arc::new(Value::I32(5)),
+                        Arc::new(Value::String(s.into())),
                     ]
                     .into_iter()
                     .collect::<PersistentList>(),
-                )),
+                ))),
             ];
-            assert_eq!(
-                Value::String(String::from("hello, 5, hello")),
-                join.invoke(args)
-            );
+            assert_eq!(Value::String("hello, 5, hello".into()), join.invoke(args));
         }
 
         #[test]
@@ -127,21 +120,18 @@ mod tests {
             let join = JoinFn {};
             let s = "hello";
             let args = vec![
-                Rc::new(Value::String(String::from(", "))),
-                Rc::new(Value::PersistentVector(
+                Arc::new(Value::String(", ".into())),
+                Arc::new(Value::PersistentVector(Arc::new(
                     vec![
-                        Rc::new(Value::String(String::from(s))),
-                        Rc::new(Value::I32(5)),
-                        Rc::new(Value::String(String::from(s))),
+                        Arc::new(Value::String(s.into())),
+                        Arc::new(Value::I32(5)),
+                        Arc::new(Value::String(s.into())),
                     ]
                     .into_iter()
                     .collect::<PersistentVector>(),
-                )),
+                ))),
             ];
-            assert_eq!(
-                Value::String(String::from("hello, 5, hello")),
-                join.invoke(args)
-            );
+            assert_eq!(Value::String("hello, 5, hello".into()), join.invoke(args));
         }
     }
 }
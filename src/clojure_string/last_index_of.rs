@@ -0,0 +1,48 @@
+use crate::clojure_string::index_of::{byte_index, char_index, index_args};
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.string/last-index-of ; (last-index-of s value) (last-index-of s value from-index)
+/// is the index of the last value (a string or char) in s,  at or before from-index;  or nil
+#[derive(Debug, Clone)]
+pub struct LastIndexOfFn {}
+impl ToValue for LastIndexOfFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for LastIndexOfFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (s, value, from) = match index_args(&args) {
+            Ok(index_args) => index_args,
+            Err(condition) => return condition,
+        };
+        let mut end = match from {
+            Some(from) if from < 0 => return Value::Nil,
+            Some(from) => (byte_index(&s, from as usize) + value.len()).min(s.len()),
+            None => s.len(),
+        };
+        // Nothing can be found ending in the middle of a char
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        match s[..end].rfind(&value) {
+            Some(found) => Value::I32(char_index(&s, found)),
+            None => Value::Nil,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn last_index_of_finds_strings_and_chars() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/last-index-of \"héllo, hello\" \"o\") (clojure.string/last-index-of \"héllo, hello\" \\o 10) (clojure.string/last-index-of \"abcabc\" \"bc\" 4) (clojure.string/last-index-of \"abc\" \"z\") (clojure.string/last-index-of \"abc\" \"a\" -1) (clojure.string/last-index-of \"abc\" \"\")]",
+        ]);
+        assert_eq!("[11 4 4 nil nil 3]", result.to_string());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::ifn::IFn;
+use crate::locale::Locale;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/locale-compare ; (locale-compare locale a b) is how a sorts against b in
+/// locale (ie "sv" or :es),  as compare would say;  letters first,  then accents,  then case.
+/// (sort (partial clojure.string/locale-compare "sv") words) sorts words as Swedes would
+#[derive(Debug, Clone)]
+pub struct LocaleCompareFn {}
+impl ToValue for LocaleCompareFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for LocaleCompareFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let locale = match Locale::of(&args[0]) {
+            Ok(locale) => locale,
+            Err(condition) => return condition,
+        };
+        match (&*args[1], &*args[2]) {
+            (Value::String(a), Value::String(b)) => Value::I32(locale.compare(a, b) as i32),
+            (Value::String(_), b) => error_message::type_mismatch(TypeTag::String, b),
+            (a, _) => error_message::type_mismatch(TypeTag::String, a),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn strings_are_sorted_as_the_locale_does() {
+        let result = Repl::default().eval_all(&[
+            "(def words [\"öl\" \"zebra\" \"Apa\" \"äpple\"])",
+            "[(sort words) (sort (partial clojure.string/locale-compare :de) words) (sort (partial clojure.string/locale-compare \"sv-SE\") words)]",
+        ]);
+        assert_eq!(
+            "[(\"Apa\" \"zebra\" \"äpple\" \"öl\") (\"Apa\" \"äpple\" \"öl\" \"zebra\") (\"Apa\" \"zebra\" \"äpple\" \"öl\")]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn case_follows_the_locale() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/upper-case \"istanbul\" \"tr\") (clojure.string/lower-case \"ISTANBUL\" :tr) (clojure.string/upper-case \"istanbul\")]",
+        ]);
+        assert_eq!(
+            "[\"İSTANBUL\" \"ıstanbul\" \"ISTANBUL\"]",
+            result.to_string()
+        );
+    }
+}
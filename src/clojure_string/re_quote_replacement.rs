@@ -0,0 +1,49 @@
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/re-quote-replacement ; escapes the \ and $ in replacement,  so replace
+/// puts it in as is,  rather than reading $1 as group 1
+#[derive(Debug, Clone)]
+pub struct ReQuoteReplacementFn {}
+impl ToValue for ReQuoteReplacementFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReQuoteReplacementFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::String(replacement) => {
+                let mut quoted = String::with_capacity(replacement.len());
+                for c in replacement.chars() {
+                    if c == '\\' || c == '$' {
+                        quoted.push('\\');
+                    }
+                    quoted.push(c);
+                }
+                Value::String(quoted.into())
+            }
+            _ => error_message::type_mismatch(TypeTag::String, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn quoted_replacements_are_put_in_as_is() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/re-quote-replacement \"$1 x\") (clojure.string/replace \"a1\" #\"(\\d)\" (clojure.string/re-quote-replacement \"$1\"))]",
+        ]);
+        assert_eq!("[\"\\\\$1 x\" \"a$1\"]", result.to_string());
+    }
+}
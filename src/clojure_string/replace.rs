@@ -0,0 +1,145 @@
+use crate::ifn::{self, IFn};
+use crate::pattern;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/replace ; (replace s match replacement) replaces every match in s with
+/// replacement.  match is a string or char,  replaced as is with one of the same,  or a
+/// regex;  whose replacement is a string,  where $1 stands for what group 1 matched (see
+/// re-quote-replacement),  or a fn,  given each match as re-find gives it and returning
+/// the string to replace it with
+#[derive(Debug, Clone)]
+pub struct ReplaceFn {}
+impl ToValue for ReplaceFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReplaceFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        replace(&args, 0)
+    }
+}
+
+/// Shared with replace-first;  (replace s match replacement),  replacing the first limit
+/// matches,  or all of them if limit is 0
+pub(crate) fn replace(args: &[Arc<Value>], limit: usize) -> Value {
+    let s = match &*args[0] {
+        Value::String(s) => s,
+        _ => return error_message::type_mismatch(TypeTag::String, &args[0]),
+    };
+    match (&*args[1], &*args[2]) {
+        (Value::String(from), Value::String(to)) if limit == 0 => {
+            Value::String(s.replace(&**from, to).into())
+        }
+        (Value::String(from), Value::String(to)) => {
+            Value::String(s.replacen(&**from, to, limit).into())
+        }
+        (Value::String(_), _) => error_message::type_mismatch(TypeTag::String, &args[2]),
+        (Value::Char(from), Value::Char(to)) => {
+            let limit = if limit == 0 { usize::MAX } else { limit };
+            let mut replaced = 0;
+            let replaced = s
+                .chars()
+                .map(|c| {
+                    if c == *from && replaced < limit {
+                        replaced += 1;
+                        *to
+                    } else {
+                        c
+                    }
+                })
+                .collect::<String>();
+            Value::String(replaced.into())
+        }
+        (Value::Char(_), _) => error_message::type_mismatch(TypeTag::Char, &args[2]),
+        (Value::Regex(regex), Value::String(to)) => {
+            Value::String(regex.replacen(s, limit, replacement(to).as_str()).into())
+        }
+        (Value::Regex(regex), f) if ifn::is_callable(f) => {
+            let mut replaced = String::with_capacity(s.len());
+            let mut last_end = 0;
+            let limit = if limit == 0 { usize::MAX } else { limit };
+            for captures in regex.captures_iter(s).take(limit) {
+                let whole = captures.get(0).unwrap();
+                let to = match ifn::call(
+                    &args[2],
+                    vec![pattern::match_value(&captures).to_rc_value()],
+                ) {
+                    Value::String(to) => to,
+                    condition @ Value::Condition(_) => return condition,
+                    to => return error_message::type_mismatch(TypeTag::String, &to),
+                };
+                replaced.push_str(&s[last_end..whole.start()]);
+                replaced.push_str(&to);
+                last_end = whole.end();
+            }
+            replaced.push_str(&s[last_end..]);
+            Value::String(replaced.into())
+        }
+        (Value::Regex(_), _) => error_message::type_mismatch(TypeTag::IFn, &args[2]),
+        _ => error_message::type_mismatch(TypeTag::Regex, &args[1]),
+    }
+}
+
+// A replacement as Clojure writes it,  $1 for group 1 and \$ for a $,  as the regex crate
+// writes it;  where $1a would be the group named 1a
+fn replacement(to: &str) -> String {
+    let mut replacement = String::with_capacity(to.len());
+    let mut chars = to.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some('$') => replacement.push_str("$$"),
+                Some(ch) => replacement.push(ch),
+                None => {}
+            },
+            '$' if chars.peek().is_some_and(char::is_ascii_digit) => {
+                replacement.push_str("${");
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    replacement.push(digit);
+                }
+                replacement.push('}');
+            }
+            '$' => replacement.push_str("$$"),
+            ch => replacement.push(ch),
+        }
+    }
+    replacement
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn replacements_are_strings_groups_or_fns() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/replace \"a.b.c\" \".\" \"-\") (clojure.string/replace \"x1y22\" #\"\\d+\" \"#\") (clojure.string/replace \"ab-cd\" #\"(\\w)(\\w)\" \"$2$1a\") (clojure.string/replace \"a1b2\" #\"\\d\" (fn [d] (str \"<\" d \">\"))) (clojure.string/replace \"k=v\" #\"(\\w)=(\\w)\" (fn [m] (str (nth m 2) \"=\" (nth m 1))))]",
+        ]);
+        assert_eq!(
+            "[\"a-b-c\" \"x#y#\" \"baa-dca\" \"a<1>b<2>\" \"v=k\"]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn replacements_match_what_they_replace() {
+        let result = Repl::default().eval_all(&[
+            "(defn swap [m] (str (nth m 2) \"=\" (nth m 1)))",
+            "[(clojure.string/replace \"a.b.c\" \\. \\-) (clojure.string/replace-first \"a.b.c\" \\. \\-) (clojure.string/replace \"k=v\" #\"(\\w)=(\\w)\" #'swap) (clojure.string/replace \"a1\" #\"\\d\" (fn [d] \"$0\"))]",
+        ]);
+        assert_eq!("[\"a-b-c\" \"a-b.c\" \"v=k\" \"a$0\"]", result.to_string());
+        let result = Repl::default().eval_all(&["(clojure.string/replace \"a.b\" \\. \"-\")"]);
+        assert!(result.to_string().starts_with("#Condition[\"Type mismatch"));
+        let result =
+            Repl::default().eval_all(&["(clojure.string/replace \"a1\" #\"\\d\" (fn [d] 1))"]);
+        assert!(result.to_string().starts_with("#Condition[\"Type mismatch"));
+    }
+}
@@ -0,0 +1,40 @@
+use crate::clojure_string::replace::replace;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+use crate::error_message;
+
+/// clojure.string/replace-first ; (replace-first s match replacement) as replace,  but
+/// only replacing the first match
+#[derive(Debug, Clone)]
+pub struct ReplaceFirstFn {}
+impl ToValue for ReplaceFirstFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReplaceFirstFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        replace(&args, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn only_the_first_match_is_replaced() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/replace-first \"a.b.c\" \".\" \"-\") (clojure.string/replace-first \"x1y22\" #\"\\d+\" \"<$0>\") (clojure.string/replace-first \"a1b2\" #\"\\d\" (fn [d] (str \"<\" d \">\"))) (clojure.string/replace-first \"abc\" \"z\" \"y\")]",
+        ]);
+        assert_eq!(
+            "[\"a-b.c\" \"x<1>y22\" \"a<1>b2\" \"abc\"]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::ifn::IFn;
+use crate::options::Opts;
+use crate::persistent_vector::PersistentVector;
+use crate::value::{ToValue, Value};
+use regex::Regex;
+use std::sync::Arc;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/split ; (split s re) (split s re limit) splits s on the matches of re,
+/// into a vector of at most limit strings;  trailing empty strings are dropped,  unless
+/// there's a limit.  The limit can also be given as opts' :limit,  ie {:limit 2}
+#[derive(Debug, Clone)]
+pub struct SplitFn {}
+impl ToValue for SplitFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SplitFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
+        }
+        let s = match &*args[0] {
+            Value::String(s) => s,
+            _ => return error_message::type_mismatch(TypeTag::String, &args[0]),
+        };
+        let regex = match &*args[1] {
+            Value::Regex(regex) => regex,
+            _ => return error_message::type_mismatch(TypeTag::Regex, &args[1]),
+        };
+        let limit = match args.get(2) {
+            Some(opts) if matches!(**opts, Value::PersistentListMap(_)) => {
+                match Opts::from_map("clojure.string/split", &["limit"], opts) {
+                    Ok(opts) => opts.get("limit").cloned(),
+                    Err(condition) => return condition,
+                }
+            }
+            limit => limit.cloned(),
+        };
+        let limit = match limit.as_deref() {
+            None => 0,
+            Some(Value::I32(limit)) => *limit,
+            Some(limit) => return error_message::type_mismatch(TypeTag::Integer, limit),
+        };
+        split(s, regex, limit)
+    }
+}
+
+/// Shared with split-lines;  s split on regex,  as java.lang.String's split does.  A limit
+/// over 0 is the most strings there can be,  and one under keeps trailing empty strings too
+pub(crate) fn split(s: &str, regex: &Regex, limit: i32) -> Value {
+    let mut parts = vec![];
+    let mut last_end = 0;
+    let mut matched = false;
+    for found in regex.find_iter(s) {
+        if limit > 0 && parts.len() + 1 == limit as usize {
+            break;
+        }
+        matched = true;
+        // A match of nothing at the very start doesn't make an empty string before it
+        if found.end() == 0 {
+            continue;
+        }
+        parts.push(&s[last_end..found.start()]);
+        last_end = found.end();
+    }
+    parts.push(&s[last_end..]);
+    if limit == 0 && matched {
+        while parts.last() == Some(&"") {
+            parts.pop();
+        }
+    }
+    parts
+        .into_iter()
+        .map(|part| Value::String(part.into()).to_rc_value())
+        .collect::<PersistentVector>()
+        .to_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn strings_are_split_on_regexes() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/split \"a,b,,c,,\" #\",\") (clojure.string/split \"a,b,,c,,\" #\",\" -1) (clojure.string/split \"a1b22c\" #\"\\d+\" 2) (clojure.string/split \"abc\" #\"\") (clojure.string/split \"\" #\",\") (clojure.string/split \",,\" #\",\")]",
+        ]);
+        assert_eq!(
+            "[[\"a\" \"b\" \"\" \"c\"] [\"a\" \"b\" \"\" \"c\" \"\" \"\"] [\"a\" \"b22c\"] [\"a\" \"b\" \"c\"] [\"\"] []]",
+            result.to_string()
+        );
+    }
+}
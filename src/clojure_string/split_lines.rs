@@ -0,0 +1,44 @@
+use crate::clojure_string::split::split;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use regex::Regex;
+use std::sync::Arc;
+
+use crate::error_message;
+use crate::type_tag::TypeTag;
+
+/// clojure.string/split-lines ; splits s on \n or \r\n,  into a vector of its lines
+#[derive(Debug, Clone)]
+pub struct SplitLinesFn {}
+impl ToValue for SplitLinesFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SplitLinesFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::String(s) => split(s, &Regex::new("\r?\n").unwrap(), 0),
+            _ => error_message::type_mismatch(TypeTag::String, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn strings_are_split_into_lines() {
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/split-lines \"one\ntwo\r\n\nfour\n\n\") (clojure.string/split-lines \"\")]",
+        ]);
+        assert_eq!(
+            "[[\"one\" \"two\" \"\" \"four\"] [\"\"]]",
+            result.to_string()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error_message;
 use crate::type_tag::TypeTag;
@@ -10,18 +10,20 @@ use crate::type_tag::TypeTag;
 pub struct TrimNewlineFn {}
 impl ToValue for TrimNewlineFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for TrimNewlineFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 1 {
             return error_message::wrong_arg_count(1, args.len());
         } else {
             match args.get(0).unwrap().to_value() {
-                Value::String(s) => {
-                    Value::String(s.trim_end_matches(|c| c == '\n' || c == '\r').to_string())
-                }
+                Value::String(s) => Value::String(
+                    s.trim_end_matches(|c| c == '\n' || c == '\r')
+                        .to_string()
+                        .into(),
+                ),
                 _a => error_message::type_mismatch(TypeTag::String, &_a.to_value()),
             }
         }
@@ -34,15 +36,15 @@ mod tests {
         use crate::clojure_string::trim_newline::TrimNewlineFn;
         use crate::ifn::IFn;
         use crate::value::Value;
-        use std::rc::Rc;
+        use std::sync::Arc;
 
         #[test]
         fn trim_newline() {
             let trim_newline = TrimNewlineFn {};
             let s = " \r \t  hello   \n\r";
-            let args = vec![Rc::new(Value::String(String::from(s)))];
+            let args = vec![Arc::new(Value::String(s.into()))];
             assert_eq!(
-                Value::String(String::from(" \r \t  hello   ")),
+                Value::String(" \r \t  hello   ".into()),
                 trim_newline.invoke(args)
             );
         }
@@ -50,9 +52,9 @@ mod tests {
         fn trim_newline_does_nothing() {
             let trim_newline = TrimNewlineFn {};
             let s = " \r \t  hello   . ";
-            let args = vec![Rc::new(Value::String(String::from(s)))];
+            let args = vec![Arc::new(Value::String(s.into()))];
             assert_eq!(
-                Value::String(String::from(" \r \t  hello   . ")),
+                Value::String(" \r \t  hello   . ".into()),
                 trim_newline.invoke(args)
             );
         }
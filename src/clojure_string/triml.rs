@@ -1,6 +1,6 @@
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error_message;
 use crate::type_tag::TypeTag;
@@ -10,16 +10,16 @@ use crate::type_tag::TypeTag;
 pub struct TrimLFn {}
 impl ToValue for TrimLFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for TrimLFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 1 {
             return error_message::wrong_arg_count(1, args.len());
         } else {
             match args.get(0).unwrap().to_value() {
-                Value::String(s) => Value::String(s.trim_start().to_string()),
+                Value::String(s) => Value::String(s.trim_start().to_string().into()),
                 _a => error_message::type_mismatch(TypeTag::String, &_a.to_value()),
             }
         }
@@ -32,17 +32,14 @@ mod tests {
         use crate::clojure_string::triml::TrimLFn;
         use crate::ifn::IFn;
         use crate::value::Value;
-        use std::rc::Rc;
+        use std::sync::Arc;
 
         #[test]
         fn triml() {
             let triml = TrimLFn {};
             let s = " \r \t  hello   \n";
-            let args = vec![Rc::new(Value::String(String::from(s)))];
-            assert_eq!(
-                Value::String(String::from("hello   \n")),
-                triml.invoke(args)
-            );
+            let args = vec![Arc::new(Value::String(s.into()))];
+            assert_eq!(Value::String("hello   \n".into()), triml.invoke(args));
         }
     }
 }
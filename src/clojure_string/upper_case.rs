@@ -1,27 +1,33 @@
 use crate::ifn::IFn;
+use crate::locale::Locale;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error_message;
 use crate::type_tag::TypeTag;
 
-/// clojure.string/upper-case ; converts characters to upper case
+/// clojure.string/upper-case ; converts characters to upper case;  as locale (ie "tr" or
+/// :el) does,  if it's given
 #[derive(Debug, Clone)]
 pub struct UpperCaseFn {}
 impl ToValue for UpperCaseFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for UpperCaseFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        if args.len() != 1 {
-            return error_message::wrong_arg_count(1, args.len());
-        } else {
-            match args.get(0).unwrap().to_value() {
-                Value::String(s) => Value::String(s.to_uppercase()),
-                _a => error_message::type_mismatch(TypeTag::String, &_a.to_value()),
-            }
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 && args.len() != 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let locale = match args.get(1).map(|locale| Locale::of(locale)).transpose() {
+            Ok(locale) => locale,
+            Err(condition) => return condition,
+        };
+        match (&*args[0], locale) {
+            (Value::String(s), None) => Value::String(s.to_uppercase().into()),
+            (Value::String(s), Some(locale)) => Value::String(locale.upper_case(s).into()),
+            _ => error_message::type_mismatch(TypeTag::String, &args[0]),
         }
     }
 }
@@ -32,17 +38,14 @@ mod tests {
         use crate::clojure_string::upper_case::UpperCaseFn;
         use crate::ifn::IFn;
         use crate::value::Value;
-        use std::rc::Rc;
+        use std::sync::Arc;
 
         #[test]
         fn upper_case_string() {
             let upper_case = UpperCaseFn {};
             let s = "1.2.3 hello";
-            let args = vec![Rc::new(Value::String(String::from(s)))];
-            assert_eq!(
-                Value::String(String::from("1.2.3 HELLO")),
-                upper_case.invoke(args)
-            );
+            let args = vec![Arc::new(Value::String(s.into()))];
+            assert_eq!(Value::String("1.2.3 HELLO".into()), upper_case.invoke(args));
         }
     }
 }
@@ -0,0 +1,92 @@
+use crate::ifn::IFn;
+use crate::output::{self, Stream};
+use crate::rust_core::StrFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+pub const RED: u8 = 31;
+pub const GREEN: u8 = 32;
+
+/// Each fn clojure.term.colors has,  and the ANSI code it sets text in
+pub const CODES: &[(&str, u8)] = &[
+    ("grey", 30),
+    ("red", RED),
+    ("green", GREEN),
+    ("yellow", 33),
+    ("blue", 34),
+    ("magenta", 35),
+    ("cyan", 36),
+    ("white", 37),
+    ("on-grey", 40),
+    ("on-red", 41),
+    ("on-green", 42),
+    ("on-yellow", 43),
+    ("on-blue", 44),
+    ("on-magenta", 45),
+    ("on-cyan", 46),
+    ("on-white", 47),
+    ("bold", 1),
+    ("dark", 2),
+    ("underline", 4),
+    ("blink", 5),
+    ("reverse-color", 7),
+    ("concealed", 8),
+];
+
+/// text,  set in the color (or style) code when written to a terminal
+pub fn colorize(code: u8, text: &str, colored: bool) -> String {
+    if colored {
+        format!("\u{1b}[{}m{}\u{1b}[0m", code, text)
+    } else {
+        String::from(text)
+    }
+}
+
+/// text,  colored if stream is a terminal;  left plain when it's piped somewhere,  or sent
+/// back to an nREPL client
+pub fn paint(code: u8, text: &str, stream: Stream) -> String {
+    colorize(code, text, output::is_terminal(stream))
+}
+
+/// clojure.term.colors/red and friends ; (red & strs)
+///
+/// Returns (str strs..),  wrapped in the ANSI codes that show it in red;  or as it is, if
+/// *out* isn't a terminal
+#[derive(Debug, Clone)]
+pub struct ColorFn {
+    code: u8,
+}
+impl ColorFn {
+    pub fn new(code: u8) -> ColorFn {
+        ColorFn { code }
+    }
+}
+impl ToValue for ColorFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ColorFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let text = StrFn {}.invoke(args);
+        match text {
+            Value::String(text) => Value::String(paint(self.code, &text, Stream::Out).into()),
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clojure_term_colors::colors::{colorize, RED};
+    use crate::repl::Repl;
+
+    #[test]
+    fn colors_only_terminals() {
+        assert_eq!("\u{1b}[31mhi\u{1b}[0m", colorize(RED, "hi", true));
+        assert_eq!("hi", colorize(RED, "hi", false));
+        // Test output is captured,  so never a terminal
+        let result = Repl::default().eval_all(&["(clojure.term.colors/bold \"a\" 1)"]);
+        assert_eq!("a1", result.to_string());
+    }
+}
@@ -0,0 +1,2 @@
+pub(crate) mod progress_bar;
+pub(crate) mod with_progress;
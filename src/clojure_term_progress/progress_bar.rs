@@ -0,0 +1,197 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::output::{self, Stream};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const WIDTH: usize = 30;
+const SPINNER: &[char] = &['|', '/', '-', '\\'];
+// How often the bar is redrawn at most;  a tight loop ticking it shouldn't spend its time
+// printing
+const REDRAW_EVERY: Duration = Duration::from_millis(100);
+
+/// A progress bar,  as made by progress-bar;  or a spinner,  if there's no telling how much
+/// there is to do
+#[derive(Debug)]
+pub struct Progress {
+    label: Option<String>,
+    total: Option<usize>,
+    done: AtomicUsize,
+    started: Instant,
+    last_drawn: Mutex<Option<Instant>>,
+    finished: AtomicBool,
+    // Whether stderr is a terminal;  if not,  there's only a line at the end to say how long
+    // it took
+    redrawn: bool,
+}
+impl Progress {
+    pub fn new(label: Option<String>, total: Option<usize>) -> Progress {
+        Progress {
+            label,
+            total,
+            done: AtomicUsize::new(0),
+            started: Instant::now(),
+            last_drawn: Mutex::new(None),
+            finished: AtomicBool::new(false),
+            redrawn: output::is_terminal(Stream::Err),
+        }
+    }
+    fn advance(&self, by: usize) {
+        if self.finished.load(Ordering::SeqCst) {
+            return;
+        }
+        let done = self.done.fetch_add(by, Ordering::SeqCst) + by;
+        if self.total.is_some_and(|total| done >= total) {
+            return self.finish();
+        }
+        let now = Instant::now();
+        let due = {
+            let mut last_drawn = self.last_drawn.lock().unwrap();
+            let due = last_drawn.is_none_or(|last_drawn| now - last_drawn >= REDRAW_EVERY);
+            if due {
+                *last_drawn = Some(now);
+            }
+            due
+        };
+        if self.redrawn && due {
+            output::set_status(Some(&self.line(now - self.started)));
+        }
+    }
+    fn finish(&self) {
+        if self.finished.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let line = self.line(self.started.elapsed());
+        if self.redrawn {
+            output::set_status(None);
+        }
+        output::write(Stream::Err, &format!("{}\n", line));
+    }
+    /// How the bar looks elapsed after it was started
+    pub fn line(&self, elapsed: Duration) -> String {
+        let done = self.done.load(Ordering::SeqCst);
+        let secs = elapsed.as_secs_f64();
+        let rate = if secs > 0.0 { done as f64 / secs } else { 0.0 };
+        let mut parts = vec![];
+        if let Some(label) = &self.label {
+            parts.push(label.clone());
+        }
+        match self.total {
+            Some(total) => {
+                let fraction = if total == 0 {
+                    1.0
+                } else {
+                    (done as f64 / total as f64).min(1.0)
+                };
+                let filled = (fraction * WIDTH as f64) as usize;
+                parts.push(format!(
+                    "[{}{}]",
+                    "=".repeat(filled),
+                    " ".repeat(WIDTH - filled)
+                ));
+                parts.push(format!("{:.0}%", fraction * 100.0));
+                parts.push(format!("{}/{}", done, total));
+            }
+            None => {
+                if !self.finished.load(Ordering::SeqCst) {
+                    parts.push(SPINNER[(secs * 10.0) as usize % SPINNER.len()].to_string());
+                }
+                parts.push(done.to_string());
+            }
+        }
+        parts.push(format!("{:.1}/s", rate));
+        match self.total {
+            _ if self.finished.load(Ordering::SeqCst) => {
+                parts.push(format!("in {}", duration(secs)))
+            }
+            Some(total) if rate > 0.0 => parts.push(format!(
+                "ETA {}",
+                duration(total.saturating_sub(done) as f64 / rate)
+            )),
+            _ => {}
+        }
+        parts.join(" ")
+    }
+}
+impl IFn for Arc<Progress> {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match args.as_slice() {
+            [] => self.advance(1),
+            [by] => match &**by {
+                Value::I32(by) if *by >= 0 => self.advance(*by as usize),
+                Value::Keyword(kw) if kw.sym.name == "done" => self.finish(),
+                _ => return error_message::type_mismatch(TypeTag::Integer, by),
+            },
+            _ => return error_message::wrong_varg_count(&[0, 1], args.len()),
+        }
+        Value::I32(self.done.load(Ordering::SeqCst) as i32)
+    }
+}
+
+// 75.2 => 1m15s
+fn duration(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format!("{}m{}s", secs / 60, secs % 60)
+    }
+}
+
+/// clojure.term.progress/progress-bar ; (progress-bar total) or (progress-bar total label)
+///
+/// Returns a progress bar,  drawn on *err*;  a fn,  that's called as (bar) when one more of
+/// the total is done,  (bar n) when n more are,  and (bar :done) when it's all over.  Each
+/// returns how many are done.  Redrawn in place,  with its rate and ETA,  if *err* is a
+/// terminal;  otherwise only the last line is printed,  when it's done.  With a nil total,
+/// it's a spinner
+#[derive(Debug, Clone)]
+pub struct ProgressBarFn {}
+impl ToValue for ProgressBarFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ProgressBarFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let total = match &*args[0] {
+            Value::I32(total) if *total >= 0 => Some(*total as usize),
+            Value::Nil => None,
+            _ => return error_message::type_mismatch(TypeTag::Integer, &args[0]),
+        };
+        let label = match args.get(1).map(|label| &**label) {
+            Some(Value::String(label)) => Some(label.to_string()),
+            Some(Value::Nil) | None => None,
+            Some(_) => return error_message::type_mismatch(TypeTag::String, &args[1]),
+        };
+        Value::ifn(Arc::new(Progress::new(label, total)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clojure_term_progress::progress_bar::Progress;
+    use crate::ifn::IFn;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn shows_how_far_along_it_is() {
+        let bar = Arc::new(Progress::new(Some(String::from("files")), Some(10)));
+        bar.invoke(vec![]);
+        bar.invoke(vec![]);
+        assert_eq!(
+            "files [======                        ] 20% 2/10 1.0/s ETA 8s",
+            bar.line(Duration::from_secs(2))
+        );
+        let spinner = Arc::new(Progress::new(None, None));
+        spinner.invoke(vec![]);
+        assert_eq!("| 1 0.5/s", spinner.line(Duration::from_secs(2)));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.term.progress/with-progress ; (with-progress [bar total label?] body)
+///
+/// Binds bar to a progress bar (see progress-bar),  evaluates body,  and then finishes the
+/// bar;  in a finally,  so that it's left behind cleanly even if body fails
+///
+/// (with-progress [bar 10] body) expands to
+///
+/// (let [bar (clojure.term.progress/progress-bar 10)] (try (do body) (finally (bar :done))))
+#[derive(Debug, Clone)]
+pub struct WithProgressMacro {}
+impl ToValue for WithProgressMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for WithProgressMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return Value::Condition(
+                "Wrong number of arguments given to with-progress (Given: 0, Expected: >=1)".into(),
+            );
+        }
+        let binding = match &*args[0] {
+            Value::PersistentVector(pvector) if pvector.len() == 2 || pvector.len() == 3 => {
+                pvector.iter().cloned().collect::<Vec<Arc<Value>>>()
+            }
+            _ => return Value::Condition(
+                "Binding to with-progress should be a vector of a name,  a total and maybe a label"
+                    .into(),
+            ),
+        };
+        let name = Arc::clone(&binding[0]);
+        let mut progress_bar =
+            vec![Symbol::intern("clojure.term.progress/progress-bar").to_rc_value()];
+        progress_bar.extend_from_slice(&binding[1..]);
+
+        let mut do_body = vec![Symbol::intern("do").to_rc_value()];
+        do_body.extend_from_slice(&args[1..]);
+        let done = vec![Arc::clone(&name), Keyword::intern("done").to_rc_value()].into_list();
+        let finally = vec![Symbol::intern("finally").to_rc_value(), done.to_rc_value()].into_list();
+        let try_expr = vec![
+            Symbol::intern("try").to_rc_value(),
+            do_body.into_list().to_rc_value(),
+            finally.to_rc_value(),
+        ]
+        .into_list();
+        let let_bindings = vec![name, progress_bar.into_list().to_rc_value()]
+            .into_iter()
+            .collect::<PersistentVector>();
+        vec![
+            Symbol::intern("let").to_rc_value(),
+            let_bindings.to_rc_value(),
+            try_expr.to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn finishes_the_bar_after_the_body() {
+        let result = Repl::default().eval_all(&[
+            "(def bar* (atom nil))",
+            "(clojure.term.progress/with-progress [bar 3 \"steps\"] (do (reset! bar* bar) (bar 2) :result))",
+            "[(deref bar*) ((deref bar*))]",
+        ]);
+        assert_eq!("[#function[] 2]", result.to_string());
+    }
+}
@@ -0,0 +1,491 @@
+//! clojure.test;  deftest,  is,  are,  testing,  fixtures and run-tests
+//!
+//! As in Clojure,  a test is the :test metadata of its var,  and a namespace's fixtures are
+//! kept in its own metadata;  so whatever thread runs the tests finds them.  What a run has
+//! counted,  and the tests and testings it's within,  are what the dynamic vars
+//! *report-counters*,  *testing-vars* and *testing-contexts* are bound to while it runs;
+//! conveyed to the futures it makes,  as other bindings are.  Reports go to *out*
+pub(crate) mod are;
+pub(crate) mod deftest;
+pub(crate) mod is;
+pub(crate) mod run_tests;
+pub(crate) mod testing;
+pub(crate) mod use_fixtures;
+
+use crate::clojure_java_io;
+use crate::dynamic;
+use crate::environment::Environment;
+use crate::host_object::HostObject;
+use crate::ifn::{self, IFn};
+use crate::iterable::Iterable;
+use crate::keyword::Keyword;
+use crate::maps::MapEntry;
+use crate::namespace::Namespace;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::persistent_vector::ToPersistentVector;
+use crate::protocol::ProtocolCastable;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use crate::var::Var;
+use crate::{error_message, pattern};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// How many tests and assertions have been run,  and how they went
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub test: usize,
+    pub pass: usize,
+    pub fail: usize,
+    pub error: usize,
+}
+impl Summary {
+    fn add(&mut self, other: &Summary) {
+        self.test += other.test;
+        self.pass += other.pass;
+        self.fail += other.fail;
+        self.error += other.error;
+    }
+}
+impl ToValue for Summary {
+    fn to_value(&self) -> Value {
+        let entry = |key, val: usize| MapEntry {
+            key: Keyword::intern(key).to_rc_value(),
+            val: (val as i32).to_rc_value(),
+        };
+        vec![
+            entry("test", self.test),
+            entry("pass", self.pass),
+            entry("fail", self.fail),
+            entry("error", self.error),
+            MapEntry {
+                key: Keyword::intern("type").to_rc_value(),
+                val: Keyword::intern("summary").to_rc_value(),
+            },
+        ]
+        .into_iter()
+        .collect::<PersistentListMap>()
+        .to_value()
+    }
+}
+
+/// Which tests to run;  those of the namespaces namespaces matches (every one,  if it's
+/// None),  tagged with any of include (or not,  if it's empty) and none of exclude.  A
+/// test's tags are the keys of its metadata;  (deftest ^:integration t ..) is tagged
+/// integration
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selector {
+    pub namespaces: Option<String>,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+impl Selector {
+    /// The selector run-tests is given as options;  {:namespaces #"app.*" :include
+    /// [:integration] :exclude :slow},  say,  each of them optional
+    pub(crate) fn from_options(options: &Value) -> Result<Selector, Value> {
+        let options = match options {
+            Value::PersistentListMap(options) => options,
+            _ => {
+                return Err(error_message::type_mismatch(
+                    TypeTag::PersistentListMap,
+                    options,
+                ))
+            }
+        };
+        let option = |key: &str| options.get(&Keyword::intern(key).to_rc_value());
+        let namespaces = match &*option("namespaces") {
+            Value::Nil => None,
+            Value::Regex(re) => Some(re.as_str().to_string()),
+            Value::String(re) => Some(re.to_string()),
+            other => return Err(error_message::type_mismatch(TypeTag::Regex, other)),
+        };
+        Ok(Selector {
+            namespaces,
+            include: tags(&option("include"))?,
+            exclude: tags(&option("exclude"))?,
+        })
+    }
+    fn selects_namespace(&self, ns: &str) -> Result<bool, Value> {
+        match &self.namespaces {
+            None => Ok(true),
+            Some(re) => match pattern::compile(re) {
+                Value::Regex(re) => Ok(re.is_match(ns)),
+                condition => Err(condition),
+            },
+        }
+    }
+    // Whether it selects a test with meta
+    fn selects(&self, meta: &Value) -> bool {
+        let tagged = |tag: &String| match meta {
+            Value::PersistentListMap(meta) => {
+                meta.get(&Keyword::intern(tag).to_rc_value()).is_truthy()
+            }
+            _ => false,
+        };
+        (self.include.is_empty() || self.include.iter().any(tagged))
+            && !self.exclude.iter().any(tagged)
+    }
+}
+
+// The names of the tags in tags;  a keyword,  or a collection of them
+fn tags(tags: &Value) -> Result<Vec<String>, Value> {
+    let tag = |tag: &Value| match tag {
+        Value::Keyword(tag) => Ok(tag.sym.name.to_string()),
+        _ => Err(error_message::type_mismatch(TypeTag::Keyword, tag)),
+    };
+    match tags {
+        Value::Nil => Ok(vec![]),
+        Value::Keyword(_) => Ok(vec![tag(tags)?]),
+        _ => match tags.to_rc_value().try_as_protocol::<Iterable>() {
+            Some(tags) => tags.iter().map(|t| tag(&t)).collect(),
+            None => Err(error_message::type_mismatch(TypeTag::Keyword, tags)),
+        },
+    }
+}
+
+/// What *report-counters* is bound to while tests are run;  the counts so far
+#[derive(Debug, Default)]
+pub struct Counters(Mutex<Summary>);
+impl HostObject for Counters {
+    fn type_name(&self) -> &str {
+        "clojure.test.Counters"
+    }
+    fn call(&self, _method: &str, _args: Vec<Arc<Value>>) -> Option<Value> {
+        None
+    }
+    fn display(&self) -> String {
+        self.0.lock().unwrap().to_value().to_string()
+    }
+}
+
+// What the test command selects;  run-tests runs only these,  unless given options
+static SELECTED: RwLock<Option<Selector>> = RwLock::new(None);
+
+const REPORT_COUNTERS: &str = "*report-counters*";
+// The vars of the tests being run,  outermost first;  a test can call another
+const TESTING_VARS: &str = "*testing-vars*";
+// What each testing being run is about,  outermost first
+const TESTING_CONTEXTS: &str = "*testing-contexts*";
+
+/// clojure.test/name,  for the forms our macros expand to
+pub(crate) fn qualified(name: &str) -> Arc<Value> {
+    Symbol::intern_with_ns("clojure.test", name).to_rc_value()
+}
+
+// What clojure.test's dynamic var name is bound to,  if it's bound
+fn bound(name: &str) -> Option<Arc<Value>> {
+    dynamic::bound(&Symbol::intern("clojure.test"), &Symbol::intern(name))
+}
+
+// Runs f with clojure.test's dynamic var name bound to val
+fn binding<R>(name: &str, val: Arc<Value>, f: impl FnOnce() -> R) -> R {
+    let frame = std::iter::once((Symbol::intern_with_ns("clojure.test", name), val)).collect();
+    dynamic::with_bindings(frame, f)
+}
+
+/// Runs f,  counting what's run while it does afresh;  the counts,  and what f gives.
+/// They're added to those of the run f is within,  if it's within one
+pub fn counting<R>(f: impl FnOnce() -> R) -> (Summary, R) {
+    let counters = Value::host_object(Counters::default()).to_rc_value();
+    let result = binding(REPORT_COUNTERS, Arc::clone(&counters), f);
+    let counted = match clojure_java_io::downcast::<Counters>(&counters) {
+        Some(counters) => counters.0.lock().unwrap().clone(),
+        None => Summary::default(),
+    };
+    count(|summary| summary.add(&counted));
+    (counted, result)
+}
+
+/// The counts of the run we're within;  nothing's counted outside of one
+pub fn counts() -> Summary {
+    let mut counts = Summary::default();
+    count(|summary| counts = summary.clone());
+    counts
+}
+
+fn count(f: impl FnOnce(&mut Summary)) {
+    if let Some(counters) = bound(REPORT_COUNTERS) {
+        if let Some(counters) = clojure_java_io::downcast::<Counters>(&counters) {
+            f(&mut counters.0.lock().unwrap());
+        }
+    }
+}
+
+/// Runs only the tests selector selects from now on,  unless run-tests is given options
+pub fn select(selector: Selector) {
+    *SELECTED.write().unwrap() = Some(selector);
+}
+
+fn selected() -> Selector {
+    SELECTED.read().unwrap().clone().unwrap_or_default()
+}
+
+/// Counts an assertion that passed
+pub(crate) fn pass() {
+    count(|summary| summary.pass += 1);
+}
+
+// Prints text to *out*;  a report that can't be printed is still counted
+fn out(text: &str) {
+    let _ = clojure_java_io::print(text);
+}
+
+/// Counts,  and prints,  an assertion that failed (kind "FAIL") or threw (kind "ERROR");
+/// what was expected,  then each of details under its label,  "actual" say.  A detail
+/// labelled "" goes on under the one before
+pub(crate) fn report(
+    kind: &str,
+    position: &str,
+    message: &Value,
+    expected: &str,
+    details: &[(&str, String)],
+) {
+    count(|summary| match kind {
+        "FAIL" => summary.fail += 1,
+        _ => summary.error += 1,
+    });
+    let running = stack(TESTING_VARS)
+        .iter()
+        .map(|var| match &**var {
+            Value::Var(var) => var.sym.name.to_string(),
+            var => var.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+    let mut text = match position {
+        "" => format!("\n{} in ({})\n", kind, running),
+        _ => format!("\n{} in ({}) ({})\n", kind, running, position),
+    };
+    let contexts = stack(TESTING_CONTEXTS)
+        .iter()
+        .map(|context| match &**context {
+            Value::String(context) => context.to_string(),
+            context => context.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+    if !contexts.is_empty() {
+        text.push_str(&format!("{}\n", contexts));
+    }
+    if let Value::String(message) = message {
+        text.push_str(&format!("{}\n", message));
+    }
+    text.push_str(&format!("expected: {}\n", expected));
+    for (label, detail) in details.iter() {
+        match *label {
+            "" => text.push_str(&format!("          {}\n", detail)),
+            _ => text.push_str(&format!("{:>8}: {}\n", label, detail)),
+        }
+    }
+    out(&text);
+}
+
+// What clojure.test's dynamic var name has been given by within,  outermost first
+fn stack(name: &str) -> Vec<Arc<Value>> {
+    match bound(name).as_deref() {
+        Some(Value::PersistentVector(items)) => items.iter().cloned().collect(),
+        _ => vec![],
+    }
+}
+
+// Runs f with item added to the end of what clojure.test's dynamic var name is bound to
+fn within<R>(name: &str, item: Arc<Value>, f: impl FnOnce() -> R) -> R {
+    let items = match bound(name).as_deref() {
+        Some(Value::PersistentVector(items)) => items.conj(item),
+        _ => vec![item].into_vector(),
+    };
+    binding(name, items.to_rc_value(), f)
+}
+
+/// Runs f as the test of var
+pub(crate) fn running<R>(var: Arc<Value>, f: impl FnOnce() -> R) -> R {
+    count(|summary| summary.test += 1);
+    within(TESTING_VARS, var, f)
+}
+
+/// Runs f within (testing context ..)
+pub(crate) fn testing<R>(context: String, f: impl FnOnce() -> R) -> R {
+    within(
+        TESTING_CONTEXTS,
+        Value::String(context.into()).to_rc_value(),
+        f,
+    )
+}
+
+// The metadata map meta is;  or an empty one,  if there's none
+fn meta_map(meta: Option<Arc<Value>>) -> PersistentListMap {
+    match meta.as_deref() {
+        Some(Value::PersistentListMap(meta)) => (**meta).clone(),
+        _ => PersistentListMap::Empty,
+    }
+}
+
+// The key of a namespace's metadata its :once or :each fixtures are kept under
+fn fixtures_key(once: bool) -> Arc<Value> {
+    let name = match once {
+        true => "once-fixtures",
+        false => "each-fixtures",
+    };
+    Keyword::intern_with_ns("clojure.test", name).to_rc_value()
+}
+
+/// Adds fixture to those of environment's current namespace;  once or each
+pub(crate) fn add_fixture(environment: &Environment, once: bool, fixture: Arc<Value>) {
+    let namespace = environment.find_or_create_namespace(&environment.get_current_namespace());
+    let meta = meta_map(namespace.ns_meta());
+    let key = fixtures_key(once);
+    let fixtures = match &*meta.get(&key) {
+        Value::PersistentVector(fixtures) => fixtures.conj(fixture),
+        _ => vec![fixture].into_vector(),
+    };
+    namespace.set_ns_meta(meta.assoc(key, fixtures.to_rc_value()).to_rc_value());
+}
+
+// namespace's once or each fixtures
+fn fixtures(namespace: &Namespace, once: bool) -> Vec<Arc<Value>> {
+    match &*meta_map(namespace.ns_meta()).get(&fixtures_key(once)) {
+        Value::PersistentVector(fixtures) => fixtures.iter().cloned().collect(),
+        _ => vec![],
+    }
+}
+
+/// Makes test,  a fn of no args,  var's test;  its :test metadata
+pub(crate) fn set_test(var: &Var, test: Arc<Value>) {
+    let meta = meta_map(var.namespace.meta(&var.sym));
+    let meta = meta.assoc(Keyword::intern("test").to_rc_value(), test);
+    var.namespace.set_meta(&var.sym, meta.to_rc_value());
+}
+
+/// var's test,  if it's one
+pub(crate) fn test_of(var: &Var) -> Option<Arc<Value>> {
+    let test = meta_map(var.namespace.meta(&var.sym)).get(&Keyword::intern("test").to_rc_value());
+    match &*test {
+        Value::Nil => None,
+        _ => Some(test),
+    }
+}
+
+// The vars of namespace that are tests;  those defined in files in the order they were,
+// after those that weren't,  by name
+fn tests(namespace: &Arc<Namespace>) -> Vec<Var> {
+    let mut tests = namespace
+        .mappings()
+        .into_iter()
+        .map(|(sym, _)| Var::new(Arc::clone(namespace), &sym))
+        .filter(|var| test_of(var).is_some())
+        .collect::<Vec<Var>>();
+    tests.sort_by_key(|var| {
+        let location = namespace.location(&var.sym);
+        (
+            location.map(|at| (at.file, at.line)),
+            var.sym.name.to_string(),
+        )
+    });
+    tests
+}
+
+/// The namespaces of environment that have tests,  by name
+pub(crate) fn tested_namespaces(environment: &Environment) -> Vec<String> {
+    let mut namespaces = environment
+        .all_namespaces()
+        .iter()
+        .filter(|namespace| !tests(namespace).is_empty())
+        .map(|namespace| namespace.name.name.to_string())
+        .collect::<Vec<String>>();
+    namespaces.sort();
+    namespaces
+}
+
+/// Runs each test of ns selector selects (or the test command does,  without one),
+/// within its fixtures;  printing what fails.  Nothing,  if it doesn't select ns
+pub(crate) fn run_namespace(
+    environment: &Environment,
+    ns: &str,
+    selector: Option<&Selector>,
+) -> Result<(), Value> {
+    let selector = selector.cloned().unwrap_or_else(selected);
+    if !selector.selects_namespace(ns)? {
+        return Ok(());
+    }
+    out(&format!("\nTesting {}\n", ns));
+    let namespace = match environment.find_namespace(&Symbol::intern(ns)) {
+        Some(namespace) => namespace,
+        None => return Ok(()),
+    };
+    let tests = tests(&namespace)
+        .into_iter()
+        .filter(|var| selector.selects(&var.meta()))
+        .map(|var| var.deref())
+        .collect::<Vec<Arc<Value>>>();
+    let all = RunEach {
+        tests,
+        each: fixtures(&namespace, false),
+    };
+    match fixtured(&fixtures(&namespace, true), all.to_rc_value()).invoke(vec![]) {
+        Value::Condition(message) => Err(Value::Condition(message)),
+        _ => Ok(()),
+    }
+}
+
+/// Prints the counts,  as run-tests does when it's done
+pub(crate) fn print_summary(summary: &Summary) {
+    let assertions = summary.pass + summary.fail + summary.error;
+    out(&format!(
+        "\nRan {} tests containing {} assertions.\n{} failures, {} errors.\n",
+        summary.test, assertions, summary.fail, summary.error
+    ));
+}
+
+// Calls each test in turn,  each within the fixtures
+#[derive(Debug, Clone)]
+struct RunEach {
+    tests: Vec<Arc<Value>>,
+    each: Vec<Arc<Value>>,
+}
+impl ToValue for RunEach {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RunEach {
+    fn invoke(&self, _args: Vec<Arc<Value>>) -> Value {
+        for test in self.tests.iter() {
+            if let Value::Condition(message) = fixtured(&self.each, Arc::clone(test)).invoke(vec![])
+            {
+                return Value::Condition(message);
+            }
+        }
+        Value::Nil
+    }
+}
+
+// A fn of no args that calls the first fixture with a fn that calls the next,  and so on;
+// the last one is given test itself
+fn fixtured(fixtures: &[Arc<Value>], test: Arc<Value>) -> Fixtured {
+    Fixtured {
+        fixtures: fixtures.to_vec(),
+        test,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Fixtured {
+    fixtures: Vec<Arc<Value>>,
+    test: Arc<Value>,
+}
+impl ToValue for Fixtured {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for Fixtured {
+    fn invoke(&self, _args: Vec<Arc<Value>>) -> Value {
+        match self.fixtures.split_first() {
+            None => ifn::call(&self.test, vec![]),
+            Some((fixture, rest)) => {
+                let inner = fixtured(rest, Arc::clone(&self.test));
+                ifn::call(fixture, vec![inner.to_rc_value()])
+            }
+        }
+    }
+}
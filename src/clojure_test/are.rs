@@ -0,0 +1,91 @@
+use crate::clojure_test;
+use crate::clojure_walk;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::symbol::Symbol;
+use crate::trace;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.test/are ; asserts template,  with each group of args in place of argv's symbols
+/// (are argv template args)
+///
+/// (are [x y] (= x y) 1 1 2 2) expands to
+///
+/// (do (clojure.test/is (= 1 1)) (clojure.test/is (= 2 2)))
+#[derive(Debug, Clone)]
+pub struct AreMacro {}
+impl ToValue for AreMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for AreMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let argv = match &*args[0] {
+            Value::PersistentVector(argv) => argv.iter().cloned().collect::<Vec<Arc<Value>>>(),
+            _ => return error_message::type_mismatch(TypeTag::PersistentVector, &args[0]),
+        };
+        if let Some(arg) = argv.iter().find(|arg| !matches!(***arg, Value::Symbol(_))) {
+            return error_message::type_mismatch(TypeTag::Symbol, arg);
+        }
+        let template = &args[1];
+        let vals = &args[2..];
+        if argv.is_empty() || !vals.len().is_multiple_of(argv.len()) {
+            return Value::Condition(
+                format!(
+                    "are takes a multiple of {} args,  one for each of {}",
+                    argv.len(),
+                    args[0]
+                )
+                .into(),
+            );
+        }
+        // Each is reported where the template is written
+        let position = match &**template {
+            Value::PersistentList(list) => trace::position(list),
+            _ => None,
+        };
+        let mut assertions = vec![Symbol::intern("do").to_rc_value()];
+        for group in vals.chunks(argv.len()) {
+            let form = clojure_walk::postwalk(
+                &|form| match argv.iter().position(|arg| *arg == form) {
+                    Some(ind) => (*group[ind]).clone(),
+                    None => (*form).clone(),
+                },
+                template,
+            );
+            if let (Value::PersistentList(list), Some(position)) = (&form, &position) {
+                trace::set_position(list, position.clone());
+            }
+            assertions.push(
+                vec![clojure_test::qualified("is"), form.to_rc_value()]
+                    .into_list()
+                    .to_rc_value(),
+            );
+        }
+        assertions.into_list().to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn are_asserts_the_template_for_each_group() {
+        let result = Repl::default().eval_all(&[
+            "(clojure.test/deftest sums (clojure.test/are [x y z] (= z (+ x y)) 1 1 2 2 2 4 1 2 4))",
+            "(clojure.test/run-tests)",
+        ]);
+        assert_eq!(
+            "{:test 1, :pass 2, :fail 1, :error 0, :type :summary}",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use crate::clojure_test;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.test/deftest ; defines name as a fn of no args that runs body as a test;  which
+/// is kept as the :test metadata of name's var,  where run-tests finds it
+/// (deftest name body)
+///
+/// (deftest t a b) expands to
+///
+/// (do (def t (fn [] (clojure.test/test-var* (var t))))
+///     (clojure.test/register-test* (var t) (fn [] a b)))
+#[derive(Debug, Clone)]
+pub struct DeftestMacro {}
+impl ToValue for DeftestMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DeftestMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (name, body) = match args.split_first() {
+            Some((name, body)) if matches!(**name, Value::Symbol(_)) => (name, body),
+            Some((name, _)) => return error_message::type_mismatch(TypeTag::Symbol, name),
+            None => return error_message::zero_arg_count(0),
+        };
+        let fn_of = |body: Vec<Arc<Value>>| {
+            let mut f = vec![
+                Symbol::intern("fn").to_rc_value(),
+                PersistentVector::empty().to_rc_value(),
+            ];
+            f.extend(body);
+            f.into_list().to_rc_value()
+        };
+        let var = vec![Symbol::intern("var").to_rc_value(), Arc::clone(name)]
+            .into_list()
+            .to_rc_value();
+        let run = vec![clojure_test::qualified("test-var*"), Arc::clone(&var)]
+            .into_list()
+            .to_rc_value();
+        vec![
+            Symbol::intern("do").to_rc_value(),
+            vec![
+                Symbol::intern("def").to_rc_value(),
+                Arc::clone(name),
+                fn_of(vec![run]),
+            ]
+            .into_list()
+            .to_rc_value(),
+            vec![
+                clojure_test::qualified("register-test*"),
+                var,
+                fn_of(body.to_vec()),
+            ]
+            .into_list()
+            .to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+/// (clojure.test/register-test* var test)
+///
+/// Makes test,  a fn of no args,  var's test;  gives var
+#[derive(Debug, Clone)]
+pub struct RegisterTestFn {}
+impl ToValue for RegisterTestFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RegisterTestFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match &*args[0] {
+            Value::Var(var) => {
+                clojure_test::set_test(var, Arc::clone(&args[1]));
+                Value::Var(Arc::clone(var))
+            }
+            _ => error_message::type_mismatch(TypeTag::Var, &args[0]),
+        }
+    }
+}
+
+/// (clojure.test/test-var* var)
+///
+/// Calls var's test;  reporting,  as an error,  anything it throws.  Nothing,  if var isn't
+/// a test
+#[derive(Debug, Clone)]
+pub struct TestVarFn {}
+impl ToValue for TestVarFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TestVarFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let test = match &*args[0] {
+            Value::Var(var) => clojure_test::test_of(var),
+            _ => return error_message::type_mismatch(TypeTag::Var, &args[0]),
+        };
+        if let Some(test) = test {
+            clojure_test::running(Arc::clone(&args[0]), || {
+                let result = ifn::call(&test, vec![]);
+                if let Value::Condition(_) = result {
+                    clojure_test::report(
+                        "ERROR",
+                        "",
+                        &"Uncaught exception, not in assertion.".to_value(),
+                        "nil",
+                        &[("actual", result.to_string())],
+                    );
+                }
+            });
+        }
+        Value::Nil
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn tests_are_fns_that_count_what_they_assert() {
+        let result = Repl::default().eval_all(&[
+            "(clojure.test/deftest arithmetic (clojure.test/is (= 2 (+ 1 1))) (clojure.test/is (= 3 (+ 1 1))))",
+            "(arithmetic)",
+            "(clojure.test/deftest throws (clojure.test/is true) (throw (ex-info \"boom\" {})))",
+            "(throws)",
+            "(clojure.test/run-tests)",
+        ]);
+        assert_eq!(
+            "{:test 2, :pass 2, :fail 1, :error 1, :type :summary}",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use crate::clojure_data::diff;
+use crate::clojure_test;
+use crate::condition;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::pattern;
+use crate::persistent_list::{PersistentList, ToPersistentList};
+use crate::persistent_vector::PersistentVector;
+use crate::rust_core::defprotocol_macro;
+use crate::shared_str::SharedStr;
+use crate::symbol::Symbol;
+use crate::trace;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.test/is ; asserts form is truthy,  reporting where,  and what it was,  if it isn't
+/// (is form)
+/// (is form message)
+///
+/// A call of a fn is reported with the values it was given,  as in (not (= 1 2));  but
+/// (= expected actual) on colls by what only each has (see clojure.data/diff),  and
+/// (is (thrown? C body)) asserts body throws what (catch C e ..) would catch,  as
+/// (is (thrown-with-msg? C re body)) does,  when its message also matches re
+///
+/// (is (= a b)) expands to
+///
+/// (clojure.test/assert-predicate* "file:1:5" nil '(= a b) (fn [] [= a b]))
+#[derive(Debug, Clone)]
+pub struct IsMacro {
+    enclosing_environment: Arc<Environment>,
+}
+impl IsMacro {
+    pub fn new(enclosing_environment: Arc<Environment>) -> IsMacro {
+        IsMacro {
+            enclosing_environment,
+        }
+    }
+    // Whether sym names a fn,  rather than a macro or a special form
+    fn is_fn(&self, sym: &Symbol) -> bool {
+        matches!(*self.enclosing_environment.get(sym), Value::IFn(_))
+    }
+}
+impl ToValue for IsMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for IsMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let form = &args[0];
+        let message = args.get(1).cloned().unwrap_or_else(|| Arc::new(Value::Nil));
+        let list = match &**form {
+            Value::PersistentList(list) => Some(list),
+            _ => None,
+        };
+        let position = list
+            .and_then(trace::position)
+            .map_or(String::new(), |position| position.to_string());
+        let items = list.map_or(vec![], |list| PersistentList::iter(list).collect());
+        let thunk = |body: Arc<Value>| {
+            vec![
+                Symbol::intern("fn").to_rc_value(),
+                PersistentVector::empty().to_rc_value(),
+                body,
+            ]
+            .into_list()
+            .to_rc_value()
+        };
+        let mut expansion = vec![
+            Arc::new(Value::Nil),
+            position.to_rc_value(),
+            message,
+            defprotocol_macro::quote(Arc::clone(form)),
+        ];
+        let head = match items.first().map(|head| &**head) {
+            Some(Value::Symbol(head)) => Some(head),
+            _ => None,
+        };
+        match head.map(|head| head.name.as_str()) {
+            Some(thrown @ "thrown?") | Some(thrown @ "thrown-with-msg?") => {
+                let with_msg = thrown == "thrown-with-msg?";
+                let body_start = if with_msg { 3 } else { 2 };
+                if items.len() < body_start {
+                    return Value::Condition(
+                        format!(
+                            "{} takes what's thrown,  as in ({} Exception ..)",
+                            thrown, thrown
+                        )
+                        .into(),
+                    );
+                }
+                let mut body = vec![Symbol::intern("do").to_rc_value()];
+                body.extend_from_slice(&items[body_start..]);
+                expansion[0] = clojure_test::qualified("assert-thrown*");
+                expansion.push(defprotocol_macro::quote(Arc::clone(&items[1])));
+                expansion.push(match with_msg {
+                    true => Arc::clone(&items[2]),
+                    false => Arc::new(Value::Nil),
+                });
+                expansion.push(thunk(body.into_list().to_rc_value()));
+            }
+            _ if head.is_some_and(|head| self.is_fn(head)) => {
+                expansion[0] = clojure_test::qualified("assert-predicate*");
+                expansion.push(thunk(
+                    items
+                        .into_iter()
+                        .collect::<PersistentVector>()
+                        .to_rc_value(),
+                ));
+            }
+            _ => {
+                expansion[0] = clojure_test::qualified("assert-expr*");
+                expansion.push(thunk(Arc::clone(form)));
+            }
+        }
+        expansion.into_list().to_value()
+    }
+}
+
+// The position,  message and form every assertion is given first
+struct Assertion<'a> {
+    position: &'a str,
+    message: &'a Value,
+    form: &'a Value,
+}
+impl<'a> Assertion<'a> {
+    fn from_args(args: &'a [Arc<Value>], len: usize) -> Result<Assertion<'a>, Value> {
+        if args.len() != len {
+            return Err(error_message::wrong_arg_count(len, args.len()));
+        }
+        let position = match &*args[0] {
+            Value::String(position) => &**position,
+            _ => "",
+        };
+        Ok(Assertion {
+            position,
+            message: &args[1],
+            form: &args[2],
+        })
+    }
+    fn pass(&self, result: Value) -> Value {
+        clojure_test::pass();
+        result
+    }
+    fn fail(&self, actual: &str) -> Value {
+        self.fail_with(&[("actual", actual.to_string())])
+    }
+    fn fail_with(&self, details: &[(&str, String)]) -> Value {
+        let expected = self.form.to_string_explicit();
+        clojure_test::report("FAIL", self.position, self.message, &expected, details);
+        Value::Boolean(false)
+    }
+    fn error(&self, condition: &Value) -> Value {
+        let expected = self.form.to_string_explicit();
+        let actual = condition.to_string();
+        clojure_test::report(
+            "ERROR",
+            self.position,
+            self.message,
+            &expected,
+            &[("actual", actual)],
+        );
+        Value::Nil
+    }
+}
+
+/// (clojure.test/assert-expr* position message form f)
+///
+/// Asserts what f gives is truthy
+#[derive(Debug, Clone)]
+pub struct AssertExprFn {}
+impl ToValue for AssertExprFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AssertExprFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let assertion = match Assertion::from_args(&args, 4) {
+            Ok(assertion) => assertion,
+            Err(condition) => return condition,
+        };
+        match ifn::call(&args[3], vec![]) {
+            result @ Value::Condition(_) => assertion.error(&result),
+            result if result.is_truthy() => assertion.pass(result),
+            result => {
+                assertion.fail(&result.to_string_explicit());
+                result
+            }
+        }
+    }
+}
+
+/// (clojure.test/assert-predicate* position message form f)
+///
+/// Asserts calling the fn first in what f gives,  with the rest,  gives something truthy
+#[derive(Debug, Clone)]
+pub struct AssertPredicateFn {}
+impl ToValue for AssertPredicateFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AssertPredicateFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let assertion = match Assertion::from_args(&args, 4) {
+            Ok(assertion) => assertion,
+            Err(condition) => return condition,
+        };
+        let call = match ifn::call(&args[3], vec![]) {
+            Value::PersistentVector(call) => call.iter().cloned().collect::<Vec<Arc<Value>>>(),
+            result => return assertion.error(&result),
+        };
+        let (pred, vals) = match call.split_first() {
+            Some(split) => split,
+            None => return assertion.error(&Value::Nil),
+        };
+        match ifn::call(pred, vals.to_vec()) {
+            result @ Value::Condition(_) => assertion.error(&result),
+            result if result.is_truthy() => assertion.pass(result),
+            result => {
+                // Two colls that aren't equal are reported by how they differ;  rather than
+                // in full,  as they can be big
+                if let Some(differ) = differences(assertion.form, vals) {
+                    assertion.fail_with(&differ);
+                    return result;
+                }
+                // As it was called;  (= a b) is reported as (not (= 1 2))
+                let mut called = vec![match assertion.form {
+                    Value::PersistentList(form) => PersistentList::iter(form)
+                        .next()
+                        .unwrap_or_else(|| Arc::clone(pred)),
+                    _ => Arc::clone(pred),
+                }];
+                called.extend_from_slice(vals);
+                let actual = vec![
+                    Symbol::intern("not").to_rc_value(),
+                    called.into_list().to_rc_value(),
+                ]
+                .into_list();
+                assertion.fail(&actual.to_value().to_string_explicit());
+                result
+            }
+        }
+    }
+}
+
+// What's only in expected,  and only in actual,  when form's (= expected actual) on colls;
+// as diff: lines.  None for any other form
+fn differences(form: &Value, vals: &[Arc<Value>]) -> Option<Vec<(&'static str, String)>> {
+    let is_equals = match form {
+        Value::PersistentList(form) => matches!(
+            PersistentList::iter(form).next().as_deref(),
+            Some(Value::Symbol(head)) if head.name == "=" && (!head.has_ns() || head.ns == "clojure.core")
+        ),
+        _ => false,
+    };
+    let is_coll = |x: &Value| {
+        matches!(
+            x,
+            Value::PersistentList(_)
+                | Value::PersistentVector(_)
+                | Value::LazySeq(_)
+                | Value::PersistentListMap(_)
+                | Value::PersistentTreeMap(_)
+                | Value::PersistentHashSet(_)
+                | Value::PersistentTreeSet(_)
+        )
+    };
+    let (expected, actual) = match vals {
+        [expected, actual] if is_equals && is_coll(expected) && is_coll(actual) => {
+            (expected, actual)
+        }
+        _ => return None,
+    };
+    let [only_expected, only_actual, _] = diff::diff(expected, actual).ok()?;
+    let shown =
+        |only: Option<Arc<Value>>| only.map_or(String::from("nil"), |x| x.to_string_explicit());
+    Some(vec![
+        ("diff", format!("- {}", shown(only_expected))),
+        ("", format!("+ {}", shown(only_actual))),
+    ])
+}
+
+/// (clojure.test/assert-thrown* position message form selector re f)
+///
+/// Asserts calling f throws what (catch selector e ..) catches;  with a message re matches,
+/// unless re is nil
+#[derive(Debug, Clone)]
+pub struct AssertThrownFn {}
+impl ToValue for AssertThrownFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AssertThrownFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let assertion = match Assertion::from_args(&args, 6) {
+            Ok(assertion) => assertion,
+            Err(condition) => return condition,
+        };
+        let result = ifn::call(&args[5], vec![]);
+        let message = match &result {
+            Value::Condition(message) => SharedStr::clone(message),
+            _ => return assertion.fail(&result.to_string_explicit()),
+        };
+        match condition::catches(&args[3], &result) {
+            Ok(true) => {}
+            Ok(false) => return assertion.error(&result),
+            Err(condition) => return condition,
+        }
+        if let Value::Nil = &*args[4] {
+            return assertion.pass(Value::Exception(message));
+        }
+        match pattern::regex_and_string(&args[4], &Value::String(SharedStr::clone(&message))) {
+            Ok((re, s)) if re.is_match(&s) => assertion.pass(Value::Exception(message)),
+            Ok(_) => assertion.fail(&result.to_string()),
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clojure_test::is::differences;
+    use crate::repl::Repl;
+    use std::sync::Arc;
+
+    #[test]
+    fn is_gives_back_what_it_asserted() {
+        let result = Repl::default().eval_all(&[
+            "(def passed (clojure.test/is (= 1 1)))",
+            "(def failed (clojure.test/is (= 1 2) \"one isn't two\"))",
+            "(def truthy (clojure.test/is (get {:a 1} :a)))",
+            "(def errored (clojure.test/is (nth [] 1)))",
+            "[passed failed truthy errored]",
+        ]);
+        assert_eq!("[true false 1 nil]", result.to_string());
+    }
+
+    #[test]
+    fn unequal_colls_are_reported_by_how_they_differ() {
+        let repl = Repl::default();
+        let eval = |expr| Arc::new(repl.eval_all(&[expr]));
+        let form = eval("'(= expected actual)");
+        let vals = [eval("{:a 1 :b [1 2]}"), eval("{:a 1 :b [1 3] :c 4}")];
+        assert_eq!(
+            Some(vec![
+                ("diff", String::from("- {:b [nil 2]}")),
+                ("", String::from("+ {:b [nil 3], :c 4}"))
+            ]),
+            differences(&form, &vals)
+        );
+        assert_eq!(None, differences(&form, &[eval("1"), eval("2")]));
+        assert_eq!(None, differences(&eval("'(not= a b)"), &vals));
+    }
+
+    #[test]
+    fn thrown_is_asserted_by_what_would_catch_it() {
+        let result = Repl::default().eval_all(&[
+            "(def caught (clojure.test/is (thrown? Exception (throw (ex-info \"boom\" {})))))",
+            "(def not-thrown (clojure.test/is (thrown? Exception 1)))",
+            "(def matched (clojure.test/is (thrown-with-msg? ExceptionInfo #\"bo+m\" (throw (ex-info \"boom\" {})))))",
+            "(def not-matched (clojure.test/is (thrown-with-msg? Exception #\"bang\" (throw (ex-info \"boom\" {})))))",
+            "[(ex-message caught) not-thrown (ex-message matched) not-matched]",
+        ]);
+        assert_eq!(
+            "[\"boom\" false \"boom\" false]",
+            result.to_string_explicit()
+        );
+    }
+}
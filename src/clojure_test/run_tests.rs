@@ -0,0 +1,170 @@
+use crate::clojure_test::{self, Selector};
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::pattern;
+use crate::rust_core::the_ns::the_ns;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.test/run-tests ; runs the tests of each namespace (the current one,  if none are
+/// given),  prints how they went and returns a summary;  {:test 1 :pass 2 :fail 0 :error 0}.
+/// Given options first,  only those they select;  see clojure_test::Selector
+/// (run-tests)
+/// (run-tests namespaces)
+/// (run-tests {:include [:integration] :exclude [:slow]} namespaces)
+#[derive(Debug, Clone)]
+pub struct RunTestsFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl RunTestsFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> RunTestsFn {
+        RunTestsFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for RunTestsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RunTestsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let environment = &self.enclosing_environment;
+        let (selector, args) = match selector(&args) {
+            Ok(selected) => selected,
+            Err(condition) => return condition,
+        };
+        let namespaces = match args.is_empty() {
+            true => vec![environment.get_current_namespace_name()],
+            false => {
+                let namespaces: Result<Vec<String>, Value> = args
+                    .iter()
+                    .map(|ns| the_ns(environment, ns).map(|ns| ns.name.name.to_string()))
+                    .collect();
+                match namespaces {
+                    Ok(namespaces) => namespaces,
+                    Err(condition) => return condition,
+                }
+            }
+        };
+        run(environment, &namespaces, selector)
+    }
+}
+
+/// clojure.test/run-all-tests ; run-tests,  of every namespace with tests;  or only those
+/// whose names re matches
+/// (run-all-tests)
+/// (run-all-tests re)
+/// (run-all-tests {:namespaces re :include [:integration]})
+#[derive(Debug, Clone)]
+pub struct RunAllTestsFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl RunAllTestsFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> RunAllTestsFn {
+        RunAllTestsFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for RunAllTestsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RunAllTestsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (selector, args) = match selector(&args) {
+            Ok(selected) => selected,
+            Err(condition) => return condition,
+        };
+        let mut namespaces = clojure_test::tested_namespaces(&self.enclosing_environment);
+        match args {
+            [] => {}
+            [re] => {
+                let mut matching = vec![];
+                for ns in namespaces {
+                    match pattern::regex_and_string(re, &Value::String(ns.as_str().into())) {
+                        Ok((re, _)) if re.is_match(&ns) => matching.push(ns),
+                        Ok(_) => {}
+                        Err(condition) => return condition,
+                    }
+                }
+                namespaces = matching;
+            }
+            _ => return error_message::wrong_varg_count(&[0, 1], args.len()),
+        }
+        run(&self.enclosing_environment, &namespaces, selector)
+    }
+}
+
+// The selector args start with,  if they start with options;  and the rest of them
+fn selector(args: &[Arc<Value>]) -> Result<(Option<Selector>, &[Arc<Value>]), Value> {
+    match args.split_first() {
+        Some((options, rest)) if matches!(**options, Value::PersistentListMap(_)) => {
+            Ok((Some(Selector::from_options(options)?), rest))
+        }
+        _ => Ok((None, args)),
+    }
+}
+
+fn run(environment: &Environment, namespaces: &[String], selector: Option<Selector>) -> Value {
+    let (summary, ran) = clojure_test::counting(|| {
+        namespaces
+            .iter()
+            .try_for_each(|ns| clojure_test::run_namespace(environment, ns, selector.as_ref()))
+    });
+    match ran {
+        Ok(()) => {
+            clojure_test::print_summary(&summary);
+            summary.to_value()
+        }
+        Err(condition) => condition,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn all_tests_are_those_of_every_namespace() {
+        let result = Repl::default().eval_all(&[
+            "(clojure.test/deftest a (clojure.test/is true))",
+            "(clojure.test/deftest b (clojure.test/is false))",
+            "[(clojure.test/run-all-tests) (clojure.test/run-all-tests #\"app.*\") (clojure.test/run-tests 'user)]",
+        ]);
+        assert_eq!(
+            "[{:test 2, :pass 1, :fail 1, :error 0, :type :summary} {:test 0, :pass 0, :fail 0, :error 0, :type :summary} {:test 2, :pass 1, :fail 1, :error 0, :type :summary}]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn options_select_tests_by_namespace_and_tag() {
+        let result = Repl::default().eval_all(&[
+            "(clojure.test/deftest ^:integration a (clojure.test/is true))",
+            "(clojure.test/deftest ^:integration ^:slow b (clojure.test/is true))",
+            "(clojure.test/deftest c (clojure.test/is true))",
+            "[(:test (clojure.test/run-tests {:include :integration})) (:test (clojure.test/run-tests {:exclude [:slow]} 'user)) (:test (clojure.test/run-all-tests {:include [:integration] :exclude [:slow]})) (:test (clojure.test/run-all-tests {:namespaces #\"app.*\"}))]",
+        ]);
+        assert_eq!("[2 2 1 0]", result.to_string());
+    }
+
+    #[test]
+    fn tests_are_found_and_reported_from_any_thread() {
+        let result = Repl::default().eval_all(&[
+            "(ns app.core-test (:use clojure.test))",
+            "(deftest a (is (= 1 1)) @(future (is (= 1 2))))",
+            "(def summary (atom nil))",
+            "(def report (with-out-str (reset! summary @(future (run-tests 'app.core-test)))))",
+            "[@summary (fn? (:test (meta #'a))) (re-find #\"FAIL in \\(a\\)\" report)]",
+        ]);
+        assert_eq!(
+            "[{:test 1, :pass 1, :fail 1, :error 0, :type :summary} true \"FAIL in (a)\"]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::clojure_test;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.test/testing ; runs body with what it's testing added to any assertion's report
+/// (testing string body)
+///
+/// (testing "s" a b) expands to
+///
+/// (clojure.test/testing* "s" (fn [] a b))
+#[derive(Debug, Clone)]
+pub struct TestingMacro {}
+impl ToValue for TestingMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for TestingMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (context, body) = match args.split_first() {
+            Some(split) => split,
+            None => return error_message::zero_arg_count(0),
+        };
+        let mut f = vec![
+            Symbol::intern("fn").to_rc_value(),
+            PersistentVector::empty().to_rc_value(),
+        ];
+        f.extend_from_slice(body);
+        vec![
+            clojure_test::qualified("testing*"),
+            Arc::clone(context),
+            f.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+/// (clojure.test/testing* context f)
+///
+/// Calls f within context
+#[derive(Debug, Clone)]
+pub struct TestingFn {}
+impl ToValue for TestingFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TestingFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let context = match &*args[0] {
+            Value::String(s) => s.to_string(),
+            context => context.to_string_explicit(),
+        };
+        clojure_test::testing(context, || ifn::call(&args[1], vec![]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn testing_gives_back_what_its_body_does() {
+        let result = Repl::default().eval_all(&[
+            "(clojure.test/testing \"outer\" (clojure.test/testing \"inner\" (clojure.test/is (= 1 1))))",
+        ]);
+        assert_eq!("true", result.to_string());
+    }
+}
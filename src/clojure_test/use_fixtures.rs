@@ -0,0 +1,72 @@
+use crate::clojure_test;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.test/use-fixtures ; has the current namespace's tests run within each fixture,  a
+/// fn given a fn of no args that runs them.  :once fixtures are called around all of them,
+/// and :each fixtures around each one
+/// (use-fixtures :once fixtures)
+/// (use-fixtures :each fixtures)
+#[derive(Debug, Clone)]
+pub struct UseFixturesFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl UseFixturesFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> UseFixturesFn {
+        UseFixturesFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for UseFixturesFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for UseFixturesFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (kind, fixtures) = match args.split_first() {
+            Some(split) => split,
+            None => return error_message::zero_arg_count(0),
+        };
+        let once = match &**kind {
+            Value::Keyword(kind) if **kind == Keyword::intern("once") => true,
+            Value::Keyword(kind) if **kind == Keyword::intern("each") => false,
+            _ => {
+                return Value::Condition(
+                    format!("use-fixtures takes :once or :each,  not {}", kind).into(),
+                )
+            }
+        };
+        for fixture in fixtures {
+            clojure_test::add_fixture(&self.enclosing_environment, once, Arc::clone(fixture));
+        }
+        Value::Nil
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn fixtures_are_run_around_the_tests() {
+        let result = Repl::default().eval_all(&[
+            "(def log (atom []))",
+            "(clojure.test/use-fixtures :once (fn [f] (swap! log conj :setup) (f) (swap! log conj :teardown)))",
+            "(clojure.test/use-fixtures :each (fn [f] (swap! log conj :before) (f)))",
+            "(clojure.test/deftest a (swap! log conj :a))",
+            "(clojure.test/deftest b (swap! log conj :b))",
+            "(clojure.test/run-tests)",
+            "@log",
+        ]);
+        assert_eq!(
+            "[:setup :before :a :before :b :teardown]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,451 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::iterable::Iterable;
+use crate::keyword::Keyword;
+use crate::maps::MapEntry;
+use crate::persistent_list_map::PersistentListMap;
+use crate::persistent_vector::PersistentVector;
+use crate::protocol::ProtocolCastable;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.tools.cli/parse-opts ; parses command line arguments against option specs
+/// (parse-opts args option-specs)
+///
+/// Each option spec is a vector like
+///
+///   ["-p" "--port PORT" "Port number"
+///    :default 80 :parse-fn parse-int :validate [valid-port? "Must be a valid port"]]
+///
+/// where the short flag (or nil), the long flag and the description are positional, and
+/// the rest are optional keyword arguments.  A long flag naming an argument (PORT) takes a
+/// value;  one that doesn't is a boolean flag.  The option's key is the long flag's name,
+/// unless given with :id.  Returns
+///
+///   {:options {:port 80 ..} :arguments [..] :summary "  -p, --port PORT  80  Port number" :errors nil}
+#[derive(Debug, Clone)]
+pub struct ParseOptsFn {}
+impl ToValue for ParseOptsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OptSpec {
+    short: Option<String>,
+    long: Option<String>,
+    // The name of the option's argument (PORT), if it takes one
+    required: Option<String>,
+    desc: String,
+    id: Arc<Value>,
+    default: Option<Arc<Value>>,
+    parse_fn: Option<Arc<Value>>,
+    // Pairs of predicate and error message
+    validate: Vec<(Arc<Value>, Arc<Value>)>,
+}
+impl OptSpec {
+    fn parse(spec: &Arc<Value>) -> Result<OptSpec, String> {
+        let parts = match spec.try_as_protocol::<Iterable>() {
+            Some(iterable) => iterable.iter().collect::<Vec<Arc<Value>>>(),
+            None => return Err(format!("Option spec must be a vector, got {}", spec)),
+        };
+        // Everything before the first keyword is positional
+        let kwarg_start = parts
+            .iter()
+            .position(|part| matches!(**part, Value::Keyword(_)))
+            .unwrap_or(parts.len());
+        let mut positional = vec![];
+        for part in parts[..kwarg_start].iter() {
+            match &**part {
+                Value::String(s) => positional.push(Some(s.to_string())),
+                Value::Nil => positional.push(None),
+                _ => return Err(format!("Invalid option spec part {} in {}", part, spec)),
+            }
+        }
+
+        // Positionals are [short] long [desc];  a lone leading "--.." is the long flag
+        let (short, long, desc) = match positional.as_slice() {
+            [long] => (None, long.clone(), None),
+            [first, second] => match first {
+                Some(flag) if flag.starts_with("--") => (None, Some(flag.clone()), second.clone()),
+                _ => (first.clone(), second.clone(), None),
+            },
+            [short, long, desc] => (short.clone(), long.clone(), desc.clone()),
+            _ => return Err(format!("Invalid option spec {}", spec)),
+        };
+
+        let (long, required) = match long {
+            Some(long) => {
+                let mut words = long.split_whitespace();
+                let flag = words.next().map(String::from);
+                (flag, words.next().map(String::from))
+            }
+            None => (None, None),
+        };
+        // A short flag can name the argument too, as in "-p PORT"
+        let (short, required) = match short {
+            Some(short) => {
+                let mut words = short.split_whitespace();
+                let flag = words.next().map(String::from);
+                let short_required = words.next().map(String::from);
+                (flag, required.or(short_required))
+            }
+            None => (None, required),
+        };
+
+        let name = long
+            .as_ref()
+            .or(short.as_ref())
+            .map(|flag| flag.trim_start_matches('-').to_string())
+            .ok_or_else(|| format!("Option spec {} has no flag", spec))?;
+        let mut opt = OptSpec {
+            short,
+            long,
+            required,
+            desc: desc.unwrap_or_default(),
+            id: Keyword::intern(&name).to_rc_value(),
+            default: None,
+            parse_fn: None,
+            validate: vec![],
+        };
+
+        let kwargs = &parts[kwarg_start..];
+        if kwargs.len() % 2 != 0 {
+            return Err(format!(
+                "Option spec {} has an odd number of keyword arguments",
+                spec
+            ));
+        }
+        for pair in kwargs.chunks(2) {
+            let (key, val) = (&pair[0], &pair[1]);
+            let key_name = match &**key {
+                Value::Keyword(kw) => kw.sym.name.clone(),
+                _ => {
+                    return Err(format!(
+                        "Expected a keyword in option spec {}, got {}",
+                        spec, key
+                    ))
+                }
+            };
+            match key_name.as_str() {
+                "id" => opt.id = Arc::clone(val),
+                "default" => opt.default = Some(Arc::clone(val)),
+                "parse-fn" => opt.parse_fn = Some(Arc::clone(val)),
+                "validate" => {
+                    let validations = match val.try_as_protocol::<Iterable>() {
+                        Some(iterable) => iterable.iter().collect::<Vec<Arc<Value>>>(),
+                        None => return Err(format!(":validate must be a vector, got {}", val)),
+                    };
+                    for validation in validations.chunks(2) {
+                        let msg = validation
+                            .get(1)
+                            .cloned()
+                            .unwrap_or_else(|| Arc::new(Value::Nil));
+                        opt.validate.push((Arc::clone(&validation[0]), msg));
+                    }
+                }
+                _ => return Err(format!("Unknown option spec key :{} in {}", key_name, spec)),
+            }
+        }
+        Ok(opt)
+    }
+    // How the option is shown in the summary and errors, ie  "-p, --port PORT"
+    fn usage(&self) -> String {
+        let flags = match (&self.short, &self.long) {
+            (Some(short), Some(long)) => format!("{}, {}", short, long),
+            (Some(flag), None) | (None, Some(flag)) => flag.clone(),
+            (None, None) => String::new(),
+        };
+        match &self.required {
+            Some(required) => format!("{} {}", flags, required),
+            None => flags,
+        }
+    }
+    fn matches(&self, flag: &str) -> bool {
+        self.short.as_deref() == Some(flag) || self.long.as_deref() == Some(flag)
+    }
+}
+
+fn summary(specs: &[OptSpec]) -> String {
+    let show_defaults = specs.iter().any(|spec| spec.default.is_some());
+    let rows = specs
+        .iter()
+        .map(|spec| {
+            let default = match (&spec.default, &spec.required) {
+                (Some(default), Some(_)) => default.to_string_explicit(),
+                _ => String::new(),
+            };
+            vec![spec.usage(), default, spec.desc.clone()]
+        })
+        .collect::<Vec<Vec<String>>>();
+    let width = |col: usize| rows.iter().map(|row| row[col].len()).max().unwrap_or(0);
+    let (usage_width, default_width) = (width(0), width(1));
+    rows.iter()
+        .map(|row| {
+            let line = if show_defaults {
+                format!(
+                    "  {:uw$}  {:dw$}  {}",
+                    row[0],
+                    row[1],
+                    row[2],
+                    uw = usage_width,
+                    dw = default_width
+                )
+            } else {
+                format!("  {:uw$}  {}", row[0], row[2], uw = usage_width)
+            };
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Runs the option's parse-fn and validations over a raw argument, returning the value to
+// store or an error message
+fn parse_value(spec: &OptSpec, flag: &str, raw: Arc<Value>) -> Result<Arc<Value>, String> {
+    let parsed = match &spec.parse_fn {
+        Some(parse_fn) if ifn::is_callable(parse_fn) => {
+            match ifn::call(parse_fn, vec![Arc::clone(&raw)]) {
+                Value::Condition(msg) => {
+                    return Err(format!(
+                        "Error while parsing option \"{} {}\": {}",
+                        flag, raw, msg
+                    ))
+                }
+                parsed => Arc::new(parsed),
+            }
+        }
+        Some(_) => return Err(format!(":parse-fn for {} is not a function", flag)),
+        None => raw,
+    };
+    for (pred, msg) in spec.validate.iter() {
+        if !ifn::is_callable(pred) {
+            return Err(format!(":validate for {} is not a function", flag));
+        }
+        let valid = !matches!(
+            ifn::call(pred, vec![Arc::clone(&parsed)]),
+            Value::Nil | Value::Boolean(false) | Value::Condition(_)
+        );
+        if !valid {
+            let mut err = format!("Failed to validate \"{} {}\"", flag, parsed);
+            if let Value::String(msg) = &**msg {
+                err.push_str(&format!(": {}", msg));
+            }
+            return Err(err);
+        }
+    }
+    Ok(parsed)
+}
+
+fn to_map(entries: Vec<(Arc<Value>, Arc<Value>)>) -> Arc<Value> {
+    entries
+        .into_iter()
+        .map(|(key, val)| MapEntry { key, val })
+        .collect::<PersistentListMap>()
+        .to_rc_value()
+}
+
+impl IFn for ParseOptsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let cli_args = match args[0].try_as_protocol::<Iterable>() {
+            Some(iterable) => iterable
+                .iter()
+                .map(|arg| arg.to_string())
+                .collect::<Vec<String>>(),
+            None if args[0] == Arc::new(Value::Nil) => vec![],
+            None => return error_message::type_mismatch(TypeTag::ISeq, &args[0]),
+        };
+        let specs = match args[1].try_as_protocol::<Iterable>() {
+            Some(iterable) => iterable
+                .iter()
+                .map(|spec| OptSpec::parse(&spec))
+                .collect::<Result<Vec<OptSpec>, String>>(),
+            None => return error_message::type_mismatch(TypeTag::PersistentVector, &args[1]),
+        };
+        let specs = match specs {
+            Ok(specs) => specs,
+            Err(msg) => return Value::Condition(msg.into()),
+        };
+
+        let mut options: Vec<(Arc<Value>, Arc<Value>)> = vec![];
+        let mut arguments = vec![];
+        let mut errors = vec![];
+        let mut cli_args = cli_args.into_iter();
+        while let Some(arg) = cli_args.next() {
+            if arg == "--" {
+                arguments.extend(cli_args.by_ref().map(|arg| arg.to_rc_value()));
+                break;
+            }
+            if !arg.starts_with('-') || arg == "-" {
+                arguments.push(arg.to_rc_value());
+                continue;
+            }
+            // --port=80
+            let (flag, inline_value) = match arg.find('=') {
+                Some(ind) if arg.starts_with("--") => {
+                    (arg[..ind].to_string(), Some(arg[ind + 1..].to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let spec = match specs.iter().find(|spec| spec.matches(&flag)) {
+                Some(spec) => spec,
+                None => {
+                    errors.push(format!("Unknown option: \"{}\"", flag).to_rc_value());
+                    continue;
+                }
+            };
+            let value = match &spec.required {
+                Some(_) => match inline_value.or_else(|| cli_args.next()) {
+                    Some(raw) => parse_value(spec, &flag, raw.to_rc_value()),
+                    None => Err(format!(
+                        "Missing required argument for \"{}\"",
+                        spec.usage()
+                    )),
+                },
+                None => Ok(Arc::new(Value::Boolean(true))),
+            };
+            match value {
+                Ok(value) => {
+                    options.retain(|(id, _)| *id != spec.id);
+                    options.push((Arc::clone(&spec.id), value));
+                }
+                Err(msg) => errors.push(msg.to_rc_value()),
+            }
+        }
+        for spec in specs.iter() {
+            if let Some(default) = &spec.default {
+                if !options.iter().any(|(id, _)| *id == spec.id) {
+                    options.push((Arc::clone(&spec.id), Arc::clone(default)));
+                }
+            }
+        }
+
+        let errors = if errors.is_empty() {
+            Arc::new(Value::Nil)
+        } else {
+            PersistentVector::from(errors).to_rc_value()
+        };
+        to_map(vec![
+            (Keyword::intern("options").to_rc_value(), to_map(options)),
+            (
+                Keyword::intern("arguments").to_rc_value(),
+                PersistentVector::from(arguments).to_rc_value(),
+            ),
+            (
+                Keyword::intern("summary").to_rc_value(),
+                summary(&specs).to_rc_value(),
+            ),
+            (Keyword::intern("errors").to_rc_value(), errors),
+        ])
+        .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod parse_opts_tests {
+        use crate::clojure_tools_cli::parse_opts::ParseOptsFn;
+        use crate::ifn::IFn;
+        use crate::keyword::Keyword;
+        use crate::persistent_list_map::IPersistentMap;
+        use crate::persistent_vector::PersistentVector;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn strs(strs: &[&str]) -> Arc<Value> {
+            strs.iter()
+                .map(|s| s.to_rc_value())
+                .collect::<PersistentVector>()
+                .to_rc_value()
+        }
+
+        fn kw(name: &str) -> Arc<Value> {
+            Keyword::intern(name).to_rc_value()
+        }
+
+        fn specs() -> Arc<Value> {
+            PersistentVector::from(vec![
+                PersistentVector::from(vec![
+                    "-p".to_rc_value(),
+                    "--port PORT".to_rc_value(),
+                    "Port number".to_rc_value(),
+                    kw("default"),
+                    Arc::new(Value::I32(80)),
+                ])
+                .to_rc_value(),
+                strs(&["-v", "--verbose", "Print more"]),
+                strs(&["-h", "--help"]),
+            ])
+            .to_rc_value()
+        }
+
+        fn get(map: &Value, key: &str) -> Arc<Value> {
+            match map {
+                Value::PersistentListMap(map) => map.get(&kw(key)),
+                _ => panic!("Expected a map, got {}", map),
+            }
+        }
+
+        fn parse(args: &[&str]) -> Value {
+            ParseOptsFn {}.invoke(vec![strs(args), specs()])
+        }
+
+        #[test]
+        fn parse_opts_long_short_and_arguments() {
+            let result = parse(&["--port", "8080", "-v", "file.clj"]);
+            let options = get(&result, "options");
+            assert_eq!(Value::String("8080".into()), *get(&options, "port"));
+            assert_eq!(Value::Boolean(true), *get(&options, "verbose"));
+            assert_eq!(Value::Nil, *get(&options, "help"));
+            assert_eq!(*strs(&["file.clj"]), *get(&result, "arguments"));
+            assert_eq!(Value::Nil, *get(&result, "errors"));
+        }
+
+        #[test]
+        fn parse_opts_uses_defaults() {
+            let result = parse(&["--port=9000"]);
+            assert_eq!(
+                Value::String("9000".into()),
+                *get(&get(&result, "options"), "port")
+            );
+            let result = parse(&[]);
+            assert_eq!(Value::I32(80), *get(&get(&result, "options"), "port"));
+        }
+
+        #[test]
+        fn parse_opts_double_dash_ends_options() {
+            let result = parse(&["--", "-v"]);
+            assert_eq!(*strs(&["-v"]), *get(&result, "arguments"));
+            assert_eq!(Value::Nil, *get(&get(&result, "options"), "verbose"));
+        }
+
+        #[test]
+        fn parse_opts_collects_errors() {
+            let result = parse(&["-x", "--port"]);
+            assert_eq!(
+                *strs(&[
+                    "Unknown option: \"-x\"",
+                    "Missing required argument for \"-p, --port PORT\""
+                ]),
+                *get(&result, "errors")
+            );
+        }
+
+        #[test]
+        fn parse_opts_summary() {
+            let result = parse(&[]);
+            assert_eq!(
+                Value::String(
+                    "  -p, --port PORT  80  Port number\n  -v, --verbose        Print more\n  -h, --help"
+                        .into()
+                ),
+                *get(&result, "summary")
+            );
+        }
+    }
+}
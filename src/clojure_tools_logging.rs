@@ -0,0 +1,118 @@
+//! clojure.tools.logging;  log lines from scripts,  at levels that can be turned down
+//!
+//! (log/info "Fetched" n "pages") writes
+//!
+//!   2026-10-16T09:30:00.123-00:00 INFO  user - Fetched 3 pages
+//!
+//! to *err*;  or,  while *log-file* is bound to one,  to a file or writer.  Lines below
+//! *level* aren't written,  and their args aren't even evaluated;  *level* is :info,  unless
+//! CLOJURE_RS_LOG_LEVEL names another.  Each line written is also a tracing event,  with
+//! the target "clojure",  so whatever embeds us sees them in its own subscriber
+pub(crate) mod enabled_qmark_;
+pub(crate) mod log;
+
+use crate::clojure_java_io::{self, Writer};
+use crate::dynamic;
+use crate::inst;
+use crate::symbol::Symbol;
+use crate::value::Value;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::sync::{Arc, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const LOG_LEVEL_VAR: &str = "CLOJURE_RS_LOG_LEVEL";
+
+/// The levels,  from the lowest
+pub const LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error", "fatal"];
+
+/// Where level is among the LEVELS;  for a keyword naming one
+pub fn rank(level: &Value) -> Result<usize, Value> {
+    let name = match level {
+        Value::Keyword(level) if !level.sym.has_ns() => level.sym.name.as_str(),
+        _ => "",
+    };
+    LEVELS.iter().position(|known| *known == name).ok_or_else(|| {
+        Value::Condition(
+            format!(
+                "Unknown log level {};  use one of :trace,  :debug,  :info,  :warn,  :error or :fatal",
+                level.to_string_explicit()
+            )
+            .into(),
+        )
+    })
+}
+
+/// What *level* starts out as;  what CLOJURE_RS_LOG_LEVEL says,  or info
+pub fn level_by_default() -> &'static str {
+    static LEVEL: OnceLock<&str> = OnceLock::new();
+    LEVEL.get_or_init(|| {
+        let named = env::var(LOG_LEVEL_VAR).unwrap_or_default().to_lowercase();
+        LEVELS
+            .iter()
+            .find(|level| **level == named)
+            .unwrap_or(&"info")
+    })
+}
+
+// The var named name in this namespace,  as it's bound on this thread;  None,  if it isn't
+fn bound(name: &str) -> Option<Arc<Value>> {
+    dynamic::bound(
+        &Symbol::intern("clojure.tools.logging"),
+        &Symbol::intern(name),
+    )
+}
+
+/// Whether lines at level are written now
+pub fn is_enabled(level: usize) -> Result<bool, Value> {
+    let least = match bound("*level*") {
+        Some(least) => rank(&least)?,
+        None => LEVELS
+            .iter()
+            .position(|known| *known == level_by_default())
+            .unwrap(),
+    };
+    Ok(level >= least)
+}
+
+/// Writes message as a line logged from ns,  at level;  and hands it to tracing
+pub fn log(level: usize, ns: &str, message: &str) -> Result<(), Value> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as i64);
+    let line = format!(
+        "{} {:5} {} - {}\n",
+        inst::format(millis),
+        LEVELS[level].to_uppercase(),
+        ns,
+        message
+    );
+    trace(level, ns, message);
+    match bound("*log-file*").as_deref() {
+        None | Some(Value::Nil) => clojure_java_io::print_err(&line),
+        Some(out) => match clojure_java_io::downcast::<Writer>(out) {
+            Some(writer) => writer.write(&line).and_then(|()| writer.flush()),
+            None => {
+                let path = clojure_java_io::as_path(out)?;
+                fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .and_then(|mut file| file.write_all(line.as_bytes()))
+                    .map_err(|e| clojure_java_io::io_error("write", path.display(), e))
+            }
+        },
+    }
+}
+
+// Fatal is tracing's error;  it has nothing higher
+fn trace(level: usize, ns: &str, message: &str) {
+    match LEVELS[level] {
+        "trace" => tracing::trace!(target: "clojure", ns, "{}", message),
+        "debug" => tracing::debug!(target: "clojure", ns, "{}", message),
+        "info" => tracing::info!(target: "clojure", ns, "{}", message),
+        "warn" => tracing::warn!(target: "clojure", ns, "{}", message),
+        _ => tracing::error!(target: "clojure", ns, "{}", message),
+    }
+}
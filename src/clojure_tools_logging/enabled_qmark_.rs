@@ -0,0 +1,28 @@
+use crate::clojure_tools_logging;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (enabled? level)
+///
+/// Whether lines logged at level are written now;  as *level* is bound,  or
+/// CLOJURE_RS_LOG_LEVEL set
+#[derive(Debug, Clone)]
+pub struct EnabledQmarkFn {}
+impl ToValue for EnabledQmarkFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for EnabledQmarkFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match clojure_tools_logging::rank(&args[0]).and_then(clojure_tools_logging::is_enabled) {
+            Ok(enabled) => Value::Boolean(enabled),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::clojure_tools_logging::{self, LEVELS};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_list::ToPersistentList;
+use crate::reader;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (info & args),  (warn & args) and the rest;  one for each level
+///
+/// Logs args,  joined by spaces as print joins them,  if lines at this level are written
+/// now;  and doesn't evaluate them if they aren't
+///
+/// (info "Fetched" n) in the namespace user expands to
+///
+/// (when (clojure.tools.logging/enabled? :info)
+///   (clojure.tools.logging/log* :info "user" "Fetched" n))
+#[derive(Debug, Clone)]
+pub struct LogMacro {
+    level: usize,
+}
+impl LogMacro {
+    pub fn new(level: usize) -> LogMacro {
+        LogMacro { level }
+    }
+}
+impl ToValue for LogMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for LogMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let level = Keyword::intern(LEVELS[self.level]).to_rc_value();
+        let enabled = vec![
+            Symbol::intern_with_ns("clojure.tools.logging", "enabled?").to_rc_value(),
+            Arc::clone(&level),
+        ];
+        let mut log = vec![
+            Symbol::intern_with_ns("clojure.tools.logging", "log*").to_rc_value(),
+            level,
+            Value::from(reader::current_namespace()).to_rc_value(),
+        ];
+        log.extend(args);
+        vec![
+            Symbol::intern("when").to_rc_value(),
+            enabled.into_list().to_rc_value(),
+            log.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+/// (log* level ns & args) ; what info and the rest expand to
+///
+/// Logs args from the namespace named ns,  at level;  whether or not lines at that level
+/// are written now.  Returns nil
+#[derive(Debug, Clone)]
+pub struct LogFn {}
+impl ToValue for LogFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for LogFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let level = match clojure_tools_logging::rank(&args[0]) {
+            Ok(level) => level,
+            Err(condition) => return condition,
+        };
+        let message = args[2..]
+            .iter()
+            .map(|arg| match &**arg {
+                Value::String(s) => s.to_string(),
+                arg => arg.to_string_explicit(),
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        match clojure_tools_logging::log(level, &args[1].to_string(), &message) {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn lines_below_the_level_are_not_written() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.tools.logging :as log])",
+            "(def evaluated (atom []))",
+            "(defn note [x] (swap! evaluated conj x) x)",
+            "(def logged (with-out-str (binding [*err* *out*] (log/debug \"hidden\" (note 1)) (log/info \"Fetched\" (note 2) :pages) (binding [log/*level* :error] (log/warn (note 3)) (log/fatal \"down\")))))",
+            "[(re-seq #\"[A-Z]+ +user - .*\" logged) @evaluated]",
+        ]);
+        assert_eq!(
+            "[(\"INFO  user - Fetched 2 :pages\" \"FATAL user - down\") [2]]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn lines_go_to_the_log_file_while_it_is_bound() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.tools.logging :as log] '[clojure.java.io :as io])",
+            "(io/with-temp-dir [dir] (let [path (str dir \"/app.log\")] (do (binding [log/*log-file* path] (log/error \"one\") (log/warn \"two\")) (map (fn [line] (subs line 30)) (clojure.string/split-lines (slurp path))))))",
+        ]);
+        assert_eq!(
+            "(\"ERROR user - one\" \"WARN  user - two\")",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,120 @@
+pub(crate) mod keywordize_keys;
+pub(crate) mod macroexpand_all;
+pub(crate) mod postwalk;
+pub(crate) mod prewalk;
+pub(crate) mod stringify_keys;
+pub(crate) mod walk;
+
+use crate::keyword::Keyword;
+use crate::lazy_seq;
+use crate::meta;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_list_map::PersistentListMap;
+use crate::persistent_queue::PersistentQueue;
+use crate::persistent_tree_map::PersistentTreeMap;
+use crate::persistent_tree_set::PersistentTreeSet;
+use crate::persistent_vector::ToPersistentVector;
+use crate::record::Instance;
+use crate::rust_core::conj::as_pair;
+use crate::rust_core::into::into;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// form with f called on each of its elements;  a map's as [key val] vectors,  and a
+/// record's fields' too.  The same kind of collection,  with the same metadata;  anything
+/// that isn't one is its own
+pub(crate) fn map_children(form: &Arc<Value>, mut f: impl FnMut(Arc<Value>) -> Value) -> Value {
+    let empty = match &**form {
+        Value::PersistentList(_) | Value::LazySeq(_) | Value::PersistentVector(_) => None,
+        Value::PersistentListMap(_) => Some(PersistentListMap::Empty.to_value()),
+        Value::PersistentHashSet(_) => Some(PersistentHashSet::empty().to_value()),
+        Value::PersistentTreeMap(ptmap) => {
+            Some(PersistentTreeMap::new(ptmap.comparator().clone()).to_value())
+        }
+        Value::PersistentTreeSet(ptset) => {
+            Some(PersistentTreeSet::new(ptset.comparator().clone()).to_value())
+        }
+        Value::PersistentQueue(_) => Some(PersistentQueue::empty().to_value()),
+        Value::Instance(instance) if instance.type_().is_record => return map_fields(instance, f),
+        _ => return (**form).clone(),
+    };
+    let mut children = vec![];
+    for child in lazy_seq::iter(Arc::clone(form)) {
+        let child = match &*child {
+            Value::Condition(_) => (*child).clone(),
+            _ => f(child),
+        };
+        if let Value::Condition(_) = child {
+            return child;
+        }
+        children.push(child.to_rc_value());
+    }
+    let walked = match (&**form, empty) {
+        (Value::PersistentVector(_), _) => children.into_vector().to_value(),
+        (_, None) => children.into_list().to_value(),
+        (_, Some(empty)) => into(&empty.to_rc_value(), &children.into_vector().to_rc_value()),
+    };
+    if let Some(meta) = meta::meta(form) {
+        meta::set_meta(&walked, Some(meta));
+    }
+    walked
+}
+
+// Each [:field val] must come back as a [:field val] for the same field;  a record can't
+// gain or lose any
+fn map_fields(instance: &Instance, mut f: impl FnMut(Arc<Value>) -> Value) -> Value {
+    let type_ = instance.type_();
+    let mut vals = Vec::with_capacity(type_.fields.len());
+    for field in type_.fields.iter() {
+        let key = Keyword { sym: field.clone() }.to_rc_value();
+        let val = instance.field(field).unwrap();
+        let walked = f(vec![Arc::clone(&key), val].into_vector().to_rc_value());
+        match as_pair(&walked) {
+            Some((walked_key, val)) if walked_key == key => vals.push(val),
+            _ if matches!(walked, Value::Condition(_)) => return walked,
+            _ => {
+                return Value::Condition(
+                    format!("Can't walk {} of {} to {}", key, type_.name, walked).into(),
+                )
+            }
+        }
+    }
+    Value::Instance(Arc::new(Instance::new(Arc::clone(type_), vals)))
+}
+
+/// f called on each form inside form,  inner ones first;  and then on form itself
+pub(crate) fn postwalk(f: &dyn Fn(Arc<Value>) -> Value, form: &Arc<Value>) -> Value {
+    match map_children(form, |child| postwalk(f, &child)) {
+        Value::Condition(msg) => Value::Condition(msg),
+        walked => f(walked.to_rc_value()),
+    }
+}
+
+/// f called on form;  and then on each form inside what it gives,  outer ones first
+pub(crate) fn prewalk(f: &dyn Fn(Arc<Value>) -> Value, form: &Arc<Value>) -> Value {
+    match f(Arc::clone(form)) {
+        Value::Condition(msg) => Value::Condition(msg),
+        replaced => map_children(&replaced.to_rc_value(), |child| prewalk(f, &child)),
+    }
+}
+
+/// Every map in form with its keys replaced by what key gives for them;  those it gives
+/// None for are left as they are
+pub(crate) fn replace_keys(form: &Arc<Value>, key: impl Fn(&Value) -> Option<Value>) -> Value {
+    postwalk(
+        &|walked| match &*walked {
+            Value::PersistentListMap(_) | Value::PersistentTreeMap(_) => {
+                map_children(&walked, |entry| match as_pair(&entry) {
+                    Some((k, v)) => match key(&k) {
+                        Some(k) => vec![k.to_rc_value(), v].into_vector().to_value(),
+                        None => (*entry).clone(),
+                    },
+                    None => (*entry).clone(),
+                })
+            }
+            _ => (*walked).clone(),
+        },
+        form,
+    )
+}
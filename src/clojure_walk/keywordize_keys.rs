@@ -0,0 +1,40 @@
+use crate::clojure_walk::replace_keys;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.walk/keywordize-keys ; every map in m,  however deep,  with its string keys
+/// made keywords
+/// (keywordize-keys m)
+#[derive(Debug, Clone)]
+pub struct KeywordizeKeysFn {}
+impl ToValue for KeywordizeKeysFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for KeywordizeKeysFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        replace_keys(&args[0], |key| match key {
+            Value::String(s) => Some(Keyword::intern(s).to_value()),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn string_keys_become_keywords_however_deep() {
+        let result = Repl::default()
+            .eval_all(&["(clojure.walk/keywordize-keys [{\"a\" {\"b\" 1}} {1 \"c\"} \"d\"])"]);
+        assert_eq!("[{:a {:b 1}} {1 \"c\"} \"d\"]", result.to_string());
+    }
+}
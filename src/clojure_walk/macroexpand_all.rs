@@ -0,0 +1,295 @@
+use crate::clojure_walk::map_children;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq;
+use crate::persistent_list::{ToPersistentList, ToPersistentListIter};
+use crate::persistent_vector::ToPersistentVector;
+use crate::symbol::Symbol;
+use crate::trace;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+// How deep macros may expand into other macros before we assume they never stop;  for all
+// that expands code,  as the linter,  the compiler and tail_position do it through here
+const MAX_EXPANSION_DEPTH: usize = 64;
+
+/// clojure.walk/macroexpand-all ; form with each macro call in it expanded,  all the way
+/// down.  What's quoted is left as it is,  as are the names let,  loop,  fn and catch
+/// bind;  and within them,  those names are locals,  not the macros they might shadow
+/// (macroexpand-all form)
+#[derive(Debug, Clone)]
+pub struct MacroexpandAllFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl MacroexpandAllFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> MacroexpandAllFn {
+        MacroexpandAllFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for MacroexpandAllFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MacroexpandAllFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        macroexpand_all(&self.enclosing_environment, &args[0])
+    }
+}
+
+/// form with each macro call in it expanded,  as it would be were it evaluated in
+/// environment;  a condition,  if a macro gives one
+pub(crate) fn macroexpand_all(environment: &Arc<Environment>, form: &Arc<Value>) -> Value {
+    macroexpand_all_in(environment, &[], form)
+}
+
+/// form expanded as macroexpand_all does,  but within the scope of locals;  as a fn's body
+/// is,  in that of its params
+pub(crate) fn macroexpand_all_in(
+    environment: &Arc<Environment>,
+    locals: &[Symbol],
+    form: &Arc<Value>,
+) -> Value {
+    Expansion {
+        environment,
+        locals: locals.to_vec(),
+    }
+    .form(form, 0)
+}
+
+/// (sym args..),  a call of macro_ made by expanding depth macros,  expanded the once;  a
+/// condition if the macro gives one,  or if it's expanded too deep to ever stop
+pub(crate) fn expand_call(
+    macro_: &Arc<dyn IFn>,
+    sym: &Symbol,
+    args: &[Arc<Value>],
+    depth: usize,
+) -> Value {
+    if depth >= MAX_EXPANSION_DEPTH {
+        return Value::Condition(format!("Expanding {} never stops", sym).into());
+    }
+    macro_.invoke(args.to_vec())
+}
+
+struct Expansion<'a> {
+    environment: &'a Arc<Environment>,
+    // The names bound around the form being expanded
+    locals: Vec<Symbol>,
+}
+impl Expansion<'_> {
+    fn form(&mut self, form: &Arc<Value>, depth: usize) -> Value {
+        match &**form {
+            Value::PersistentList(list) => {
+                let items = list.iter().collect::<Vec<Arc<Value>>>();
+                let expanded = self.list(&items, depth);
+                // So what it expands to is still reported where the call was written
+                if let (Value::PersistentList(expanded), Some(position)) =
+                    (&expanded, trace::position(list))
+                {
+                    if trace::position(expanded).is_none() {
+                        trace::set_position(expanded, position);
+                    }
+                }
+                expanded
+            }
+            // As macros build code with concat,  it may come as a seq
+            Value::LazySeq(_) => {
+                let items = lazy_seq::iter(Arc::clone(form)).collect::<Vec<Arc<Value>>>();
+                self.list(&items, depth)
+            }
+            _ => map_children(form, |child| self.form(&child, depth)),
+        }
+    }
+
+    // Each of forms expanded,  or the first condition one gives
+    fn forms(&mut self, forms: &[Arc<Value>], depth: usize) -> Result<Vec<Arc<Value>>, Value> {
+        forms
+            .iter()
+            .map(|form| match self.form(form, depth) {
+                Value::Condition(message) => Err(Value::Condition(message)),
+                expanded => Ok(expanded.to_rc_value()),
+            })
+            .collect()
+    }
+
+    fn list(&mut self, items: &[Arc<Value>], depth: usize) -> Value {
+        match items.first().map(|head| &**head) {
+            Some(Value::Symbol(sym)) if !self.locals.contains(sym) => self.call(sym, items, depth),
+            _ => listed(self.forms(items, depth)),
+        }
+    }
+
+    // (sym args..);  a special form,  a macro call or a call
+    fn call(&mut self, sym: &Symbol, items: &[Arc<Value>], depth: usize) -> Value {
+        if sym.name == "do" && (!sym.has_ns() || sym.ns == "clojure.core") {
+            // do's a macro,  expanding to a fn of its body;  but it's meant to be a
+            // special form
+            return listed(self.forms(items, depth));
+        }
+        let expanded = match &*self.environment.get(sym) {
+            Value::QuoteMacro => Ok(items.to_vec()),
+            // (fn [params] body..)
+            Value::FnMacro => self.fn_form(items, 1, depth),
+            // (defmacro name [params] body..)
+            Value::DefmacroMacro => self.fn_form(items, 2, depth),
+            Value::LetMacro | Value::LoopMacro => self.let_form(items, depth),
+            // (def name val);  name isn't evaluated
+            Value::DefMacro => {
+                let name = items.len().min(2);
+                self.forms(&items[name..], depth)
+                    .map(|val| [items[..name].to_vec(), val].concat())
+            }
+            Value::TryMacro => self.try_form(items, depth),
+            Value::Macro(macro_) => {
+                return match expand_call(macro_, sym, &items[1..], depth) {
+                    Value::Condition(message) => Value::Condition(message),
+                    expansion => self.form(&expansion.to_rc_value(), depth + 1),
+                };
+            }
+            _ => self.forms(items, depth),
+        };
+        listed(expanded)
+    }
+
+    // items[..=params] as they are,  and the body after them expanded with params bound
+    fn fn_form(
+        &mut self,
+        items: &[Arc<Value>],
+        params: usize,
+        depth: usize,
+    ) -> Result<Vec<Arc<Value>>, Value> {
+        let outer = self.locals.len();
+        let body = items.len().min(params + 1);
+        if let Some(params) = items.get(params) {
+            self.bind(params);
+        }
+        let mut expanded = items[..body].to_vec();
+        let body = self.forms(&items[body..], depth);
+        self.locals.truncate(outer);
+        expanded.extend(body?);
+        Ok(expanded)
+    }
+
+    // (let [name val ..] body..);  each val sees the names bound before it
+    fn let_form(&mut self, items: &[Arc<Value>], depth: usize) -> Result<Vec<Arc<Value>>, Value> {
+        let outer = self.locals.len();
+        let expanded = self.let_bindings(items, depth);
+        self.locals.truncate(outer);
+        expanded
+    }
+
+    fn let_bindings(
+        &mut self,
+        items: &[Arc<Value>],
+        depth: usize,
+    ) -> Result<Vec<Arc<Value>>, Value> {
+        let mut expanded = items[..1].to_vec();
+        let bindings = match items.get(1).map(|bindings| &**bindings) {
+            Some(Value::PersistentVector(bindings)) => {
+                bindings.iter().cloned().collect::<Vec<Arc<Value>>>()
+            }
+            _ => return Ok([expanded, self.forms(&items[1..], depth)?].concat()),
+        };
+        let mut expanded_bindings = vec![];
+        for pair in bindings.chunks(2) {
+            expanded_bindings.push(Arc::clone(&pair[0]));
+            if let Some(val) = pair.get(1) {
+                expanded_bindings.extend(self.forms(std::slice::from_ref(val), depth)?);
+            }
+            self.bind(&pair[0]);
+        }
+        expanded.push(expanded_bindings.into_vector().to_rc_value());
+        expanded.extend(self.forms(&items[2..], depth)?);
+        Ok(expanded)
+    }
+
+    // (try body.. (catch Class e handler..) (finally cleanup..))
+    fn try_form(&mut self, items: &[Arc<Value>], depth: usize) -> Result<Vec<Arc<Value>>, Value> {
+        let mut expanded = items[..1].to_vec();
+        for item in items[1..].iter() {
+            let clause = match &**item {
+                Value::PersistentList(clause)
+                    if item.is_catch_clause() || item.is_finally_clause() =>
+                {
+                    clause.iter().collect::<Vec<Arc<Value>>>()
+                }
+                _ => {
+                    expanded.extend(self.forms(std::slice::from_ref(item), depth)?);
+                    continue;
+                }
+            };
+            // What's caught,  and the name it's bound to,  aren't evaluated
+            let handler = match item.is_catch_clause() {
+                true => clause.len().min(3),
+                false => 1,
+            };
+            let outer = self.locals.len();
+            if let Some(sym) = clause.get(2).filter(|_| item.is_catch_clause()) {
+                self.bind(sym);
+            }
+            let body = self.forms(&clause[handler..], depth);
+            self.locals.truncate(outer);
+            let clause = [clause[..handler].to_vec(), body?].concat();
+            expanded.push(clause.into_list().to_rc_value());
+        }
+        Ok(expanded)
+    }
+
+    // Makes each name in binding,  a symbol or a vector or map of them,  a local
+    fn bind(&mut self, binding: &Arc<Value>) {
+        match &**binding {
+            Value::Symbol(sym) => self.locals.push(Symbol::clone(sym)),
+            Value::PersistentVector(_) | Value::PersistentListMap(_) => {
+                map_children(binding, |child| {
+                    self.bind(&child);
+                    (*child).clone()
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+// The list of what's expanded,  or the condition expanding it gave
+fn listed(expanded: Result<Vec<Arc<Value>>, Value>) -> Value {
+    match expanded {
+        Ok(expanded) => expanded.into_list().to_value(),
+        Err(condition) => condition,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn expands_all_the_way_down_but_not_quoted_or_bound_names() {
+        let result = Repl::default().eval_all(&[
+            "(defmacro twice [x] (list (quote +) x x))",
+            "(defmacro unless [test then] (list (quote if) test nil then))",
+            "(clojure.walk/macroexpand-all (quote [(unless a (twice 1)) (quote (twice 2)) (let [twice inc x (twice 3)] (twice x)) (fn [unless] (unless 4)) (try (twice 5) (catch Exception twice (twice 6)))]))",
+        ]);
+        assert_eq!(
+            "[(if a nil (+ 1 1)) (quote (twice 2)) (let [twice inc x (twice 3)] (twice x)) (fn [unless] (unless 4)) (try (+ 5 5) (catch Exception twice (twice 6)))]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn binding_values_are_expanded_before_their_names_are_bound() {
+        let result = Repl::default().eval_all(&[
+            "(defmacro twice [x] (list (quote +) x x))",
+            "(clojure.walk/macroexpand-all (quote (loop [twice (twice 1) n (twice 2)] (twice n))))",
+        ]);
+        assert_eq!(
+            "(loop [twice (+ 1 1) n (twice 2)] (twice n))",
+            result.to_string()
+        );
+    }
+}
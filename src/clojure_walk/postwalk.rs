@@ -0,0 +1,43 @@
+use crate::clojure_walk;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.walk/postwalk ; f called on each form inside form,  depth first,  and then on
+/// form;  each with what f gave for the forms inside it
+/// (postwalk f form)
+#[derive(Debug, Clone)]
+pub struct PostwalkFn {}
+impl ToValue for PostwalkFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PostwalkFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let f = &args[0];
+        clojure_walk::postwalk(&|form| ifn::call(f, vec![form]), &args[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn inner_forms_are_walked_first() {
+        let result = Repl::default().eval_all(&[
+            "(def seen (atom []))",
+            "(def walked (clojure.walk/postwalk (fn [x] (swap! seen conj x) (if (contains? #{1 2} x) (inc x) x)) [1 {:a 2}]))",
+            "[walked @seen]",
+        ]);
+        assert_eq!(
+            "[[2 {:a 3}] [1 :a 2 [:a 3] {:a 3} [2 {:a 3}]]]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::clojure_walk;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.walk/prewalk ; f called on form,  and then on each form inside what it gave;
+/// outer forms first
+/// (prewalk f form)
+#[derive(Debug, Clone)]
+pub struct PrewalkFn {}
+impl ToValue for PrewalkFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PrewalkFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let f = &args[0];
+        clojure_walk::prewalk(&|form| ifn::call(f, vec![form]), &args[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn outer_forms_are_walked_first() {
+        let result = Repl::default().eval_all(&[
+            "(def seen (atom []))",
+            "(def walked (clojure.walk/prewalk (fn [x] (swap! seen conj x) (if (= x :leaf) [:branch 1] x)) [:leaf]))",
+            "[walked @seen]",
+        ]);
+        assert_eq!(
+            "[[[:branch 1]] [[:leaf] :leaf :branch 1]]",
+            result.to_string()
+        );
+    }
+}
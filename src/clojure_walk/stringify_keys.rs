@@ -0,0 +1,39 @@
+use crate::clojure_walk::replace_keys;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.walk/stringify-keys ; every map in m,  however deep,  with its keyword keys
+/// made strings;  their names,  without any namespace
+/// (stringify-keys m)
+#[derive(Debug, Clone)]
+pub struct StringifyKeysFn {}
+impl ToValue for StringifyKeysFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for StringifyKeysFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        replace_keys(&args[0], |key| match key {
+            Value::Keyword(kw) => Some(Value::String(kw.sym.name.as_str().into())),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn keyword_keys_become_strings_however_deep() {
+        let result = Repl::default()
+            .eval_all(&["(clojure.walk/stringify-keys {:a (list {:b/c 1}) 2 (sorted-map :d 3)})"]);
+        assert_eq!("{\"a\" ({\"c\" 1}), 2 {\"d\" 3}}", result.to_string());
+    }
+}
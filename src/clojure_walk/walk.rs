@@ -0,0 +1,47 @@
+use crate::clojure_walk::map_children;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// clojure.walk/walk ; inner called on each element of form,  and outer on the same kind
+/// of collection they're put back together in.  A map's elements are its [key val]
+/// vectors;  anything that isn't a collection is given to outer as it is
+/// (walk inner outer form)
+#[derive(Debug, Clone)]
+pub struct WalkFn {}
+impl ToValue for WalkFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for WalkFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let (inner, outer) = (&args[0], &args[1]);
+        match map_children(&args[2], |child| ifn::call(inner, vec![child])) {
+            Value::Condition(msg) => Value::Condition(msg),
+            walked => ifn::call(outer, vec![walked.to_rc_value()]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn each_kind_of_coll_is_walked_into_its_own_kind() {
+        let result = Repl::default().eval_all(&[
+            "(defn twice [x] (if (contains? #{1 2 3} x) (* 2 x) x))",
+            "(defn walked [form] (clojure.walk/walk twice (fn [x] x) form))",
+            "[(walked '(1 2)) (walked [1 [2]]) (walked #{1}) (walked (sorted-set 3 1 2)) (walked (map inc [1 2])) (walked 4) (clojure.walk/walk (fn [e] [(nth e 0) (twice (nth e 1))]) (fn [m] (get m :b)) {:a 1 :b 2})]",
+        ]);
+        assert_eq!(
+            "[(2 4) [2 [2]] #{2} #{2 4 6} (4 6) 4 4]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,441 @@
+//! Carrying out the command the command line gives;  see cli for reading it
+use crate::clojure_term_colors::colors::{paint, GREEN, RED};
+use crate::output::Stream;
+use crate::{
+    clojure_profile, clojure_test, cli, conformance, environment, formatter, interrupt, linter,
+    nrepl, nrepl_client, reader, repl, rust_core, shutdown, socket_repl, transcript,
+};
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Runs the command this process was started with,  as the rust_clojure binary does
+pub fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let parsed = cli::load_path_flag(&args[1..]).and_then(|(load_path, args)| {
+        if let Some(load_path) = load_path {
+            rust_core::load::set_load_path(&load_path);
+        }
+        let (profile, args) = cli::profile_flag(&args);
+        if profile {
+            clojure_profile::start();
+            shutdown::add_hook(Arc::new(clojure_profile::report::ReportFn {
+                stream: Stream::Err,
+            }));
+        }
+        cli::parse(&args)
+    });
+    let command = match parsed {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    match command {
+        cli::Command::Repl { record } => {
+            interrupt::on_ctrl_c();
+            let mut repl = repl::Repl::default();
+            if let Some(transcript) = record.as_deref().map(open_transcript) {
+                repl = repl.recording(transcript);
+            }
+            shutdown::exit(repl.run());
+        }
+        cli::Command::SocketRepl(mode, host, port) => serve_socket_repl(mode, &host, port),
+        cli::Command::Nrepl {
+            host,
+            port,
+            record,
+            access_log,
+            tls,
+            auth,
+        } => serve_nrepl(
+            &host,
+            port,
+            record.as_deref().map(open_transcript),
+            access_log.as_deref().map(open_access_log),
+            tls.as_ref().map(load_tls),
+            auth,
+        ),
+        cli::Command::Connect { addr, auth_token } => {
+            std::process::exit(connect(addr.as_deref(), auth_token))
+        }
+        cli::Command::Run(filepath, args) => {
+            shutdown::exit(run(&args, |repl| repl.run_script(&filepath)))
+        }
+        cli::Command::Eval(expr, args) => shutdown::exit(run(&args, |repl| repl.run_expr(&expr))),
+        cli::Command::Main(ns, args) => shutdown::exit(run(&args, |repl| repl.run_main(&ns))),
+        cli::Command::Replay(filepath) => shutdown::exit(replay(&filepath)),
+        cli::Command::Fmt { paths, check } => std::process::exit(format_files(&paths, check)),
+        cli::Command::Lint(paths) => std::process::exit(lint_files(&paths)),
+        cli::Command::Test { dir, selector } => shutdown::exit(run_tests(&dir, selector)),
+        cli::Command::Conformance(dir) => shutdown::exit(run_conformance(&dir)),
+        cli::Command::Help => println!("{}", cli::USAGE),
+    }
+}
+
+// A session can't be recorded to a transcript that can't be opened;  so it doesn't start
+fn open_transcript(path: &str) -> transcript::Transcript {
+    match transcript::Transcript::open(path) {
+        Ok(transcript) => transcript,
+        Err(e) => {
+            eprintln!("Could not open transcript {}: {}", path, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Nor is a server started whose requests can't be logged
+fn open_access_log(path: &str) -> nrepl::metrics::AccessLog {
+    match nrepl::metrics::AccessLog::open(path) {
+        Ok(access_log) => access_log,
+        Err(e) => {
+            eprintln!("Could not open access log {}: {}", path, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Nor one that's been asked for TLS it can't set up
+fn load_tls(files: &nrepl::tls::TlsFiles) -> nrepl::tls::Tls {
+    match nrepl::tls::Tls::load(files) {
+        Ok(tls) => tls,
+        Err(e) => {
+            eprintln!("Could not set up TLS: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn serve_nrepl(
+    host: &str,
+    port: u16,
+    transcript: Option<transcript::Transcript>,
+    access_log: Option<nrepl::metrics::AccessLog>,
+    tls: Option<nrepl::tls::Tls>,
+    auth: Option<nrepl::Auth>,
+) {
+    let (auth_token, generated) = match auth {
+        Some(nrepl::Auth::Token(token)) => (Some(token), false),
+        Some(nrepl::Auth::Generate) => (Some(nrepl::generate_auth_token()), true),
+        None => (None, false),
+    };
+    let server = match nrepl::Server::bind((host, port)) {
+        Ok(server) => server
+            .recording(transcript)
+            .logging(access_log)
+            .encrypting(tls)
+            .authenticating(auth_token.clone()),
+        Err(e) => {
+            eprintln!("Could not start nREPL server: {}", e);
+            std::process::exit(1);
+        }
+    };
+    // With port 0, only the bound socket knows which port we actually got
+    let addr = match server.local_addr() {
+        Ok(addr) => addr,
+        Err(e) => {
+            eprintln!("Could not start nREPL server: {}", e);
+            std::process::exit(1);
+        }
+    };
+    // A token no client can find out is no use
+    let _auth_token_file = match &auth_token {
+        Some(token) if generated => match nrepl::AuthTokenFile::create(token) {
+            Ok(auth_token_file) => Some(auth_token_file),
+            Err(e) => {
+                eprintln!("Could not write .nrepl-auth-token: {}", e);
+                std::process::exit(1);
+            }
+        },
+        _ => None,
+    };
+    let _port_file = match nrepl::PortFile::create(addr.port()) {
+        Ok(port_file) => Some(port_file),
+        Err(e) => {
+            eprintln!("Could not write .nrepl-port: {}", e);
+            None
+        }
+    };
+    println!(
+        "nREPL server started on port {} on host {} - nrepl://{}",
+        addr.port(),
+        addr.ip(),
+        addr
+    );
+    if let Err(e) = server.run() {
+        eprintln!("nREPL server stopped: {}", e);
+    }
+}
+
+fn serve_socket_repl(mode: socket_repl::Mode, host: &str, port: u16) {
+    let server = match socket_repl::SocketServer::bind((host, port), mode) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Could not start socket REPL server: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let addr = match server.local_addr() {
+        Ok(addr) => addr,
+        Err(e) => {
+            eprintln!("Could not start socket REPL server: {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("Socket REPL server started on {}", addr);
+    if let Err(e) = server.run() {
+        eprintln!("Socket REPL server stopped: {}", e);
+    }
+}
+
+/// A REPL on the nREPL server at addr,  in a session of its own;  forms read from stdin are
+/// evaluated there.  A port alone is one on this machine,  and without addr,  it's the one in
+/// .nrepl-port.  The exit code is 1 if any form ended in a condition
+fn connect(addr: Option<&str>, auth_token: Option<String>) -> i32 {
+    // As the server prints it when it starts
+    let addr = match addr.map(|addr| addr.trim_start_matches("nrepl://")) {
+        Some(port) if port.parse::<u16>().is_ok() => format!("127.0.0.1:{}", port),
+        Some(addr) => addr.to_string(),
+        None => {
+            let port = fs::read_to_string(".nrepl-port")
+                .ok()
+                .and_then(|port| port.trim().parse::<u16>().ok())
+                .unwrap_or(nrepl::DEFAULT_PORT);
+            format!("127.0.0.1:{}", port)
+        }
+    };
+    let auth_token = auth_token.or_else(|| {
+        fs::read_to_string(".nrepl-auth-token")
+            .ok()
+            .map(|token| token.trim().to_string())
+    });
+    let connected = nrepl_client::connect(&addr).and_then(|client| {
+        let mut client = client.authenticating(auth_token);
+        client.clone_session()?;
+        Ok(client)
+    });
+    let mut client = match connected {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Could not connect to {}: {}", addr, e);
+            return 1;
+        }
+    };
+    let interactive = io::stdout().is_terminal();
+    let ran = nrepl_client::run_on(
+        &mut client,
+        io::stdin().lock(),
+        &mut io::stdout().lock(),
+        &mut io::stderr().lock(),
+        interactive,
+    );
+    let _ = client.close_session();
+    match ran {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            eprintln!("Lost the connection to {}: {}", addr, e);
+            1
+        }
+    }
+}
+
+/// Runs a script,  -e expression or -m namespace with *command-line-args* args;  an
+/// uncaught condition ends it with exit code 1, after printing a trace to stderr
+fn run(args: &[String], script: impl FnOnce(&repl::Repl) -> Result<(), String>) -> i32 {
+    // Ctrl-C stops what's running as a condition would,  so its finally blocks still run
+    interrupt::on_ctrl_c();
+    let repl = repl::Repl::default().with_command_line_args(args);
+    match script(&repl) {
+        Ok(()) => 0,
+        Err(trace) => {
+            eprintln!("{}", trace);
+            1
+        }
+    }
+}
+
+/// Replays a transcript recorded with --record
+fn replay(filepath: &str) -> i32 {
+    let repl = repl::Repl::default();
+    match repl.replay(filepath) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+/// Re-indents (or with check, only lists) the files that need it;  the exit code is 1 if,
+/// checking,  any did
+fn format_files(paths: &[String], check: bool) -> i32 {
+    let mut code = 0;
+    for path in paths {
+        let files = match cli::clojure_files(Path::new(path)) {
+            Ok(files) => files,
+            Err(e) => {
+                eprintln!("Could not read {}: {}", path, e);
+                return 1;
+            }
+        };
+        for file in files {
+            let source = match fs::read_to_string(&file) {
+                Ok(source) => source,
+                Err(e) => {
+                    eprintln!("Could not read {}: {}", file.display(), e);
+                    return 1;
+                }
+            };
+            // Code that's still being written is formatted all the same;  its syntax
+            // errors are only pointed out
+            for error in reader::read_lenient(&source).errors {
+                eprintln!(
+                    "{}:{}:{}: warning: {}",
+                    file.display(),
+                    error.position.line,
+                    error.position.col,
+                    error.message
+                );
+            }
+            let formatted = formatter::format(&source);
+            if formatted == source {
+                continue;
+            }
+            if check {
+                println!("Needs formatting: {}", file.display());
+                code = 1;
+            } else if let Err(e) = fs::write(&file, formatted) {
+                eprintln!("Could not write {}: {}", file.display(), e);
+                return 1;
+            } else {
+                println!("Formatted {}", file.display());
+            }
+        }
+    }
+    code
+}
+
+/// Prints what the linter finds in each file,  as path:line:col: kind: message;  the exit
+/// code is 1 if it found anything
+fn lint_files(paths: &[String]) -> i32 {
+    let environment = environment::Environment::clojure_core_environment();
+    let mut code = 0;
+    for path in paths {
+        let files = match cli::clojure_files(Path::new(path)) {
+            Ok(files) => files,
+            Err(e) => {
+                eprintln!("Could not read {}: {}", path, e);
+                return 1;
+            }
+        };
+        for file in files {
+            let source = match fs::read_to_string(&file) {
+                Ok(source) => source,
+                Err(e) => {
+                    eprintln!("Could not read {}: {}", file.display(), e);
+                    return 1;
+                }
+            };
+            for diagnostic in linter::lint(&environment, &source) {
+                println!("{}:{}", file.display(), diagnostic);
+                code = 1;
+            }
+        }
+    }
+    code
+}
+
+/// Runs each *_test.clj file under dir as its own script;  a file passes if nothing in it
+/// ends in an uncaught condition.  Only the clojure.test tests selector selects are run
+fn run_tests(dir: &str, selector: clojure_test::Selector) -> i32 {
+    clojure_test::select(selector);
+    let files = match cli::clojure_files(Path::new(dir)) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", dir, e);
+            return 1;
+        }
+    };
+    let test_files = files
+        .iter()
+        .filter(|file| {
+            file.file_stem()
+                .is_some_and(|stem| stem.to_string_lossy().ends_with("_test"))
+        })
+        .collect::<Vec<_>>();
+    let mut failed = 0;
+    for file in test_files.iter() {
+        let outcome = conformance::run_program(file);
+        print_outcome(&outcome, false);
+        if !outcome.passed() {
+            failed += 1;
+        }
+    }
+    println!("\nRan {} test files, {} failed", test_files.len(), failed);
+    if failed > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+/// Runs each program of the conformance corpus under dir;  the exit code is 1 if any failed
+fn run_conformance(dir: &str) -> i32 {
+    let outcomes = match conformance::run(Path::new(dir)) {
+        Ok(outcomes) => outcomes,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", dir, e);
+            return 1;
+        }
+    };
+    for outcome in outcomes.iter() {
+        print_outcome(outcome, true);
+    }
+    let failed = outcomes.iter().filter(|outcome| !outcome.passed()).count();
+    let assertions = outcomes
+        .iter()
+        .map(|outcome| outcome.summary.pass + outcome.summary.fail + outcome.summary.error)
+        .sum::<usize>();
+    println!(
+        "\nRan {} programs ({} assertions), {} passed, {} failed",
+        outcomes.len(),
+        assertions,
+        outcomes.len() - failed,
+        failed
+    );
+    if failed > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+// PASS or FAIL and the file;  with counts,  how many tests and assertions it ran
+fn print_outcome(outcome: &conformance::Outcome, counts: bool) {
+    let counted = match &outcome.summary {
+        summary if counts && summary.test > 0 => format!(
+            " ({} tests, {} assertions)",
+            summary.test,
+            summary.pass + summary.fail + summary.error
+        ),
+        _ => String::new(),
+    };
+    match &outcome.failure {
+        None => println!(
+            "{} {}{}",
+            paint(GREEN, "PASS", Stream::Out),
+            outcome.file.display(),
+            counted
+        ),
+        Some(trace) => println!(
+            "{} {}{}\n{}",
+            paint(RED, "FAIL", Stream::Out),
+            outcome.file.display(),
+            counted,
+            trace
+        ),
+    }
+}
@@ -0,0 +1,800 @@
+//! Compiling a fn's body to bytecode,  for a small stack machine to run;  rather than
+//! walking its forms over again each time it's called
+//!
+//! With CLOJURE_RS_COMPILE set (to anything but "" or 0 or false),  a fn is compiled the
+//! first time it's called.  Its macros are expanded once and for all,  its locals are given
+//! slots in a frame,  and the vars it names are linked,  so that calling it looks nothing up
+//! by name;  what wasn't defined yet when it was compiled,  and the locals it closes over,
+//! still are.  if,  let,  loop,  recur,  do,  quote,  calls and collection literals are
+//! compiled;  any other form is evaluated as it would be uncompiled,  in an environment with
+//! the locals in scope bound,  so it means just what it did.  A fn that defs isn't compiled
+//! at all,  as def in a fn defines a local of it
+//!
+//! (:k x) is compiled to a field read,  for when x is a record;  the first record it reads
+//! from has its type and the field's index kept,  so that reading from another of that type
+//! goes straight to the field.  One of any other type is looked up in as uncompiled
+use crate::allocations;
+use crate::clojure_profile;
+use crate::clojure_walk::macroexpand_all::macroexpand_all_in;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::host_object;
+use crate::ifn::{self, IFn};
+use crate::interrupt;
+use crate::maps::MapEntry;
+use crate::meta;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list::{PersistentList, ToPersistentList};
+use crate::persistent_list_map::{PersistentListMap, ToPersistentListMapIter};
+use crate::persistent_vector::PersistentVector;
+use crate::record::Type;
+use crate::rust_core;
+use crate::symbol::Symbol;
+use crate::tail_position;
+use crate::trace;
+use crate::value::{Evaluable, ToValue, Value};
+use crate::var::Var;
+use std::env;
+use std::sync::{Arc, OnceLock};
+
+pub const COMPILE_VAR: &str = "CLOJURE_RS_COMPILE";
+
+/// Whether fns are compiled;  whether CLOJURE_RS_COMPILE says to
+pub fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| match env::var(COMPILE_VAR) {
+        Ok(enabled) => !["", "0", "false"].contains(&enabled.to_lowercase().as_str()),
+        Err(_) => false,
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    /// Pushes constant n
+    Const(usize),
+    /// Pushes local n
+    Local(usize),
+    /// Pushes what var n holds now
+    Var(usize),
+    /// Pushes what the symbol that's constant n is bound to around the fn
+    Lookup(usize),
+    /// Pops into local n
+    Store(usize),
+    /// Returns what's on top,  if it's a condition
+    Check,
+    Pop,
+    Jump(usize),
+    /// Pops,  and jumps if it was false or nil
+    JumpIfFalse(usize),
+    /// With what call n calls on top;  goes on to push its args,  if it's a fn,  or a
+    /// keyword,  map or set.  Otherwise replaces it with what applying it to the args as
+    /// written gives,  with scope's locals bound,  and jumps to after
+    Callee {
+        call: usize,
+        scope: usize,
+        after: usize,
+    },
+    /// Pops argc args and what call n calls,  and pushes what calling it on them gives
+    Invoke {
+        call: usize,
+        argc: usize,
+    },
+    /// Pops the args of a recur into target n's locals,  and goes round it again
+    Recur(usize),
+    /// If what's on top is a recur,  as a form that wasn't compiled can give,  pops it into
+    /// target n's locals and goes round it again
+    Recurred(usize),
+    /// Pushes what constant n evaluates to uncompiled,  with scope's locals bound
+    Eval {
+        form: usize,
+        scope: usize,
+    },
+    /// Pops n values,  and pushes them as a vector
+    Vector(usize),
+    /// Pops n keys and vals,  in turn,  and pushes them as a map
+    Map(usize),
+    /// Pops n values,  and pushes them as a set
+    Set(usize),
+    /// Replaces what's on top with what the keyword that's constant n gives for it;  by
+    /// field cache n,  if it's a record
+    Field {
+        key: usize,
+        cache: usize,
+    },
+}
+
+// What recur goes round again;  a fn's body,  or a loop's
+#[derive(Debug)]
+struct Target {
+    start: usize,
+    locals: Vec<usize>,
+}
+
+/// A fn's body,  compiled
+#[derive(Debug, Default)]
+pub struct Code {
+    ops: Vec<Op>,
+    constants: Vec<Arc<Value>>,
+    vars: Vec<Var>,
+    calls: Vec<Arc<PersistentList>>,
+    // The var each call calls,  if what it calls is a var's;  for profiling
+    callees: Vec<Option<usize>>,
+    // The locals in scope where a form's evaluated uncompiled;  each a symbol and its slot
+    scopes: Vec<Vec<(Symbol, usize)>>,
+    targets: Vec<Target>,
+    // For each (:k x),  the first record type x was,  and where that type holds k
+    fields: Vec<OnceLock<(Arc<Type>, Option<usize>)>>,
+    slots: usize,
+    params: usize,
+    variadic: bool,
+}
+
+/// body,  the body of a fn taking arg_syms and closing over environment,  compiled;  None if
+/// it can't be
+pub fn compile(
+    arg_syms: &[Symbol],
+    body: &Arc<Value>,
+    environment: &Arc<Environment>,
+) -> Option<Code> {
+    let params = tail_position::recur_count(arg_syms);
+    let variadic = params < arg_syms.len();
+    // Only as the one before the last;  otherwise calling the fn is an error,  given as
+    // uncompiled
+    let ampersand = arg_syms
+        .len()
+        .checked_sub(2)
+        .map(|ind| arg_syms[ind].name == "&");
+    if variadic && (params + 1 != arg_syms.len() || ampersand != Some(true)) {
+        return None;
+    }
+    let mut compiler = Compiler {
+        environment,
+        code: Code {
+            params,
+            variadic,
+            ..Code::default()
+        },
+        scope: vec![],
+    };
+    for sym in arg_syms.iter().filter(|sym| sym.name != "&") {
+        let slot = compiler.slot();
+        compiler.scope.push((Symbol::clone(sym), slot));
+    }
+    compiler.code.targets.push(Target {
+        start: 0,
+        locals: (0..params).collect(),
+    });
+    // Expanded the once,  with the params as locals;  if a macro gives a condition,  it's
+    // given as it would be uncompiled
+    let body = match macroexpand_all_in(environment, arg_syms, body) {
+        Value::Condition(_) => return None,
+        expanded => Arc::new(expanded),
+    };
+    compiler.form(&body, Some(0))?;
+    compiler.emit(Op::Recurred(0));
+    Some(compiler.code)
+}
+
+struct Compiler<'a> {
+    environment: &'a Arc<Environment>,
+    code: Code,
+    scope: Vec<(Symbol, usize)>,
+}
+impl Compiler<'_> {
+    fn emit(&mut self, op: Op) -> usize {
+        self.code.ops.push(op);
+        self.code.ops.len() - 1
+    }
+    fn constant(&mut self, val: Arc<Value>) -> usize {
+        self.code.constants.push(val);
+        self.code.constants.len() - 1
+    }
+    fn slot(&mut self) -> usize {
+        self.code.slots += 1;
+        self.code.slots - 1
+    }
+    fn scope(&mut self) -> usize {
+        self.code.scopes.push(self.scope.clone());
+        self.code.scopes.len() - 1
+    }
+    // Points the jump at op to where the next op will go
+    fn patch(&mut self, op: usize) {
+        let here = self.code.ops.len();
+        match &mut self.code.ops[op] {
+            Op::Jump(to) | Op::JumpIfFalse(to) | Op::Callee { after: to, .. } => *to = here,
+            _ => {}
+        }
+    }
+    fn local(&self, sym: &Symbol) -> Option<usize> {
+        if sym.has_ns() {
+            return None;
+        }
+        self.scope
+            .iter()
+            .rev()
+            .find(|(local, _)| local == sym)
+            .map(|(_, slot)| *slot)
+    }
+    // The var sym names,  if it's not a local and names one
+    fn var(&self, sym: &Symbol) -> Option<Var> {
+        if self.local(sym).is_some() || self.environment.is_local(sym) {
+            return None;
+        }
+        self.environment.resolve_var(sym)
+    }
+    // form,  evaluated as uncompiled,  in its place
+    fn eval(&mut self, form: &Arc<Value>) {
+        let form = self.constant(Arc::clone(form));
+        let scope = self.scope();
+        self.emit(Op::Eval { form, scope });
+    }
+
+    // Compiles form;  tail is the target of a recur in tail position in it
+    fn form(&mut self, form: &Arc<Value>, tail: Option<usize>) -> Option<()> {
+        if is_constant(form) {
+            let val = self.constant(Arc::clone(form));
+            self.emit(Op::Const(val));
+            return Some(());
+        }
+        match &**form {
+            Value::Symbol(sym) => {
+                match (self.local(sym), self.var(sym)) {
+                    (Some(slot), _) => self.emit(Op::Local(slot)),
+                    (None, Some(var)) => {
+                        self.code.vars.push(var);
+                        self.emit(Op::Var(self.code.vars.len() - 1))
+                    }
+                    (None, None) => {
+                        let sym = self.constant(Arc::clone(form));
+                        self.emit(Op::Lookup(sym))
+                    }
+                };
+            }
+            Value::PersistentList(list) => return self.call(list, tail),
+            // As macros build code with concat,  it may come as a seq
+            Value::LazySeq(lseq) => {
+                let list = Arc::new(lseq.iter().collect::<PersistentList>());
+                return self.call(&list, tail);
+            }
+            // With metadata,  which is evaluated too
+            _ if meta::meta(form).is_some() => self.eval(form),
+            Value::PersistentVector(vector) => {
+                for val in vector.iter() {
+                    self.checked(val)?;
+                }
+                self.emit(Op::Vector(vector.len()));
+            }
+            Value::PersistentListMap(map) => {
+                let mut len = 0;
+                for entry in map.iter() {
+                    self.checked(&entry.key)?;
+                    self.checked(&entry.val)?;
+                    len += 1;
+                }
+                self.emit(Op::Map(len));
+            }
+            Value::PersistentHashSet(set) => {
+                for val in set.iter() {
+                    self.checked(&val)?;
+                }
+                self.emit(Op::Set(set.len()));
+            }
+            _ => self.eval(form),
+        }
+        Some(())
+    }
+
+    // Compiles form,  not in tail position;  throwing what it gives if that's a condition
+    fn checked(&mut self, form: &Arc<Value>) -> Option<()> {
+        self.form(form, None)?;
+        if !matches!(
+            self.code.ops.last(),
+            Some(Op::Const(_)) | Some(Op::Local(_))
+        ) {
+            self.emit(Op::Check);
+        }
+        Some(())
+    }
+
+    // forms,  evaluated in turn for the last one's value;  as a fn's,  loop's or do's
+    fn body(&mut self, forms: &[Arc<Value>], tail: Option<usize>) -> Option<()> {
+        match forms.split_last() {
+            Some((last, init)) => {
+                for form in init {
+                    self.checked(form)?;
+                    self.emit(Op::Pop);
+                }
+                self.form(last, tail)
+            }
+            None => self.form(&Arc::new(Value::Nil), tail),
+        }
+    }
+
+    fn call(&mut self, list: &Arc<PersistentList>, tail: Option<usize>) -> Option<()> {
+        let (head, args) = match &**list {
+            PersistentList::Cons(head, args, _) => (
+                head,
+                PersistentList::iter(args).collect::<Vec<Arc<Value>>>(),
+            ),
+            PersistentList::Empty => return self.form(&list.to_rc_value(), tail),
+        };
+        let form = Value::PersistentList(Arc::clone(list));
+        let var = match &**head {
+            Value::Keyword(kw) if args.len() == 1 && !kw.sym.has_ns() => {
+                self.checked(&args[0])?;
+                let key = self.constant(Arc::clone(head));
+                self.code.fields.push(OnceLock::new());
+                self.emit(Op::Field {
+                    key,
+                    cache: self.code.fields.len() - 1,
+                });
+                return Some(());
+            }
+            // (.method obj ..) and (.-field obj) aren't calls of what they name
+            Value::Symbol(sym) if host_object::is_member(sym) => {
+                self.eval(&Arc::new(form));
+                return Some(());
+            }
+            Value::Symbol(sym) => self.var(sym),
+            _ => None,
+        };
+        let val = var.as_ref().map(Var::deref);
+        match val.as_deref() {
+            Some(Value::IfMacro) if args.len() == 2 || args.len() == 3 => {
+                self.checked(&args[0])?;
+                let otherwise = self.emit(Op::JumpIfFalse(0));
+                self.form(&args[1], tail)?;
+                let end = self.emit(Op::Jump(0));
+                self.patch(otherwise);
+                let nil = Arc::new(Value::Nil);
+                self.form(args.get(2).unwrap_or(&nil), tail)?;
+                self.patch(end);
+            }
+            Some(Value::LetMacro) => match bindings(&args) {
+                Some(bindings) if args.len() <= 2 => {
+                    let outer = self.scope.len();
+                    self.bind(&bindings)?;
+                    self.form(args.get(1).unwrap_or(&Arc::new(Value::Nil)), tail)?;
+                    self.scope.truncate(outer);
+                }
+                _ => self.eval(&Arc::new(form)),
+            },
+            Some(Value::LoopMacro) => match bindings(&args) {
+                Some(bindings)
+                    if tail_position::check_expanded(&args[1..], bindings.len()).is_ok() =>
+                {
+                    let outer = self.scope.len();
+                    let locals = self.bind(&bindings)?;
+                    let target = self.code.targets.len();
+                    self.code.targets.push(Target {
+                        start: self.code.ops.len(),
+                        locals,
+                    });
+                    self.body(&args[1..], Some(target))?;
+                    self.emit(Op::Recurred(target));
+                    self.scope.truncate(outer);
+                }
+                _ => self.eval(&Arc::new(form)),
+            },
+            Some(Value::QuoteMacro) if args.len() == 1 => {
+                let val = self.constant(Arc::clone(&args[0]));
+                self.emit(Op::Const(val));
+            }
+            Some(Value::DefMacro) | Some(Value::DefmacroMacro) => return None,
+            // macroexpand-all leaves do as it is;  what else is left didn't expand
+            Some(Value::Macro(_)) if is_core(&var, "do") => self.body(&args, tail)?,
+            Some(Value::Macro(_)) => self.eval(&Arc::new(form)),
+            Some(Value::IFn(_)) if is_core(&var, "do-fn*") => self.body(&args, tail)?,
+            Some(Value::IFn(_))
+                if is_core(&var, "recur")
+                    && tail.map(|target| self.code.targets[target].locals.len())
+                        == Some(args.len()) =>
+            {
+                for arg in &args {
+                    self.checked(arg)?;
+                }
+                self.emit(Op::Recur(tail.unwrap()));
+            }
+            Some(Value::IFn(_)) | Some(Value::Keyword(_)) | None => {
+                self.form(head, None)?;
+                let call = self.code.calls.len();
+                self.code.calls.push(Arc::clone(list));
+                self.code.callees.push(match self.code.ops.last() {
+                    Some(Op::Var(var)) => Some(*var),
+                    _ => None,
+                });
+                let scope = self.scope();
+                let callee = self.emit(Op::Callee {
+                    call,
+                    scope,
+                    after: 0,
+                });
+                for arg in &args {
+                    self.checked(arg)?;
+                }
+                self.emit(Op::Invoke {
+                    call,
+                    argc: args.len(),
+                });
+                self.patch(callee);
+            }
+            Some(_) => self.eval(&Arc::new(form)),
+        }
+        Some(())
+    }
+
+    // Binds each symbol to what its form gives,  in turn,  as let and loop do;  giving the
+    // slots they're bound in
+    fn bind(&mut self, bindings: &[(Symbol, Arc<Value>)]) -> Option<Vec<usize>> {
+        let mut slots = vec![];
+        for (sym, form) in bindings {
+            self.checked(form)?;
+            let slot = self.slot();
+            self.emit(Op::Store(slot));
+            self.scope.push((Symbol::clone(sym), slot));
+            slots.push(slot);
+        }
+        Some(slots)
+    }
+}
+
+// The bindings of a let or loop,  if they're pairs of a symbol and a form
+fn bindings(args: &[Arc<Value>]) -> Option<Vec<(Symbol, Arc<Value>)>> {
+    let vector = match args.first().map(|bindings| &**bindings) {
+        Some(Value::PersistentVector(vector)) => vector,
+        _ => return None,
+    };
+    let forms = vector.iter().cloned().collect::<Vec<Arc<Value>>>();
+    forms
+        .chunks(2)
+        .map(|pair| match (&*pair[0], pair.get(1)) {
+            (Value::Symbol(sym), Some(form)) => Some((Symbol::clone(sym), Arc::clone(form))),
+            _ => None,
+        })
+        .collect()
+}
+
+// Whether var is clojure.core's var name
+fn is_core(var: &Option<Var>, name: &str) -> bool {
+    var.as_ref()
+        .is_some_and(|var| var.namespace.name.name == "clojure.core" && var.sym.name == name)
+}
+
+// Whether form evaluates to itself,  so it can be a constant
+fn is_constant(form: &Arc<Value>) -> bool {
+    if meta::meta(form).is_some() {
+        return false;
+    }
+    match &**form {
+        Value::Symbol(_) | Value::LazySeq(_) => false,
+        Value::PersistentList(list) => matches!(&**list, PersistentList::Empty),
+        Value::PersistentVector(vector) => vector.iter().all(is_constant),
+        Value::PersistentListMap(map) => map
+            .iter()
+            .all(|entry| is_constant(&entry.key) && is_constant(&entry.val)),
+        Value::PersistentHashSet(set) => set.iter().all(|val| is_constant(&val)),
+        _ => true,
+    }
+}
+
+impl Code {
+    /// Runs the fn on args,  enough for it;  environment being what it closes over
+    pub fn run(&self, environment: &Arc<Environment>, args: Vec<Arc<Value>>) -> Value {
+        let nil = Arc::new(Value::Nil);
+        let mut frame = (0..self.slots)
+            .map(|_| Arc::clone(&nil))
+            .collect::<Vec<Arc<Value>>>();
+        let fixed = if self.variadic {
+            self.params - 1
+        } else {
+            self.params
+        };
+        for (slot, arg) in args.iter().take(fixed).enumerate() {
+            frame[slot] = Arc::clone(arg);
+        }
+        if self.variadic && args.len() > fixed {
+            frame[fixed] = args[fixed..].to_vec().into_list().to_rc_value();
+        }
+        let mut stack: Vec<Arc<Value>> = vec![];
+        let mut pc = 0;
+        while let Some(op) = self.ops.get(pc) {
+            pc += 1;
+            match *op {
+                Op::Const(val) => stack.push(Arc::clone(&self.constants[val])),
+                Op::Local(slot) => stack.push(Arc::clone(&frame[slot])),
+                Op::Var(var) => stack.push(self.vars[var].deref()),
+                Op::Lookup(sym) => match &*self.constants[sym] {
+                    Value::Symbol(sym) => stack.push(environment.get(sym)),
+                    _ => unreachable!(),
+                },
+                Op::Store(slot) => frame[slot] = stack.pop().unwrap(),
+                Op::Check => {
+                    if let Value::Condition(_) = &**stack.last().unwrap() {
+                        return stack.pop().unwrap().to_value();
+                    }
+                }
+                Op::Pop => {
+                    stack.pop();
+                }
+                Op::Jump(to) => pc = to,
+                Op::JumpIfFalse(to) => {
+                    if !stack.pop().unwrap().is_truthy() {
+                        pc = to;
+                    }
+                }
+                Op::Callee { call, scope, after } => {
+                    // Checked once per call,  so that even a runaway loop can be stopped
+                    if interrupt::is_interrupted() {
+                        return Value::Condition(interrupt::INTERRUPTED_MSG.into());
+                    }
+                    let mut callee = stack.pop().unwrap();
+                    // (#'f a b) calls what f is now
+                    while let Value::Var(var) = &*callee {
+                        callee = var.deref();
+                    }
+                    match &*callee {
+                        // A keyword,  map or set looks up what it's called on,  as a fn would
+                        _ if ifn::is_callable(&callee) => stack.push(callee),
+                        // Thrown while finding what to call;  what the call gives,  as it is
+                        Value::Condition(_) => {
+                            stack.push(callee);
+                            pc = after;
+                        }
+                        _ => {
+                            let environment = self.environment(environment, &frame, scope);
+                            stack.push(callee.apply_to_call(&environment, &self.calls[call]));
+                            pc = after;
+                        }
+                    }
+                }
+                Op::Invoke { call, argc } => {
+                    let args = stack.split_off(stack.len() - argc);
+                    let callee = stack.pop().unwrap();
+                    let result = match &*callee {
+                        Value::IFn(ifn) => clojure_profile::call(
+                            || self.callees[call].map(|var| self.vars[var].qualified()),
+                            || ifn.invoke(args),
+                        ),
+                        // (:k coll),  ({:k 1} :k) and (#{1} 1)
+                        _ => ifn::call(&callee, args),
+                    };
+                    if let Value::Condition(_) = result {
+                        trace::called(&result, &self.calls[call]);
+                    }
+                    allocations::made(&result);
+                    stack.push(Arc::new(result));
+                }
+                Op::Recur(target) => {
+                    if interrupt::is_interrupted() {
+                        return Value::Condition(interrupt::INTERRUPTED_MSG.into());
+                    }
+                    let target = &self.targets[target];
+                    let vals = stack.split_off(stack.len() - target.locals.len());
+                    for (slot, val) in target.locals.iter().zip(vals) {
+                        frame[*slot] = val;
+                    }
+                    pc = target.start;
+                }
+                Op::Recurred(target) => {
+                    let target = &self.targets[target];
+                    if let Value::Recur(vals) = &**stack.last().unwrap() {
+                        if vals.len() != target.locals.len() {
+                            return error_message::mismatched_recur_count(
+                                target.locals.len(),
+                                vals.len(),
+                            );
+                        }
+                        for (slot, val) in target.locals.iter().zip(vals.iter()) {
+                            frame[*slot] = Arc::clone(val);
+                        }
+                        stack.pop();
+                        pc = target.start;
+                    }
+                }
+                Op::Eval { form, scope } => {
+                    let environment = self.environment(environment, &frame, scope);
+                    stack.push(self.constants[form].eval_to_rc(environment));
+                }
+                Op::Vector(len) => {
+                    let vals = stack.split_off(stack.len() - len);
+                    let vector = vals.into_iter().collect::<PersistentVector>().to_rc_value();
+                    allocations::made(&vector);
+                    stack.push(vector);
+                }
+                Op::Map(len) => {
+                    let vals = stack.split_off(stack.len() - 2 * len);
+                    let map = vals
+                        .chunks(2)
+                        .map(|entry| MapEntry {
+                            key: Arc::clone(&entry[0]),
+                            val: Arc::clone(&entry[1]),
+                        })
+                        .collect::<PersistentListMap>();
+                    let map = map.to_rc_value();
+                    allocations::made(&map);
+                    stack.push(map);
+                }
+                Op::Set(len) => {
+                    let vals = stack.split_off(stack.len() - len);
+                    let set = vals
+                        .into_iter()
+                        .collect::<PersistentHashSet>()
+                        .to_rc_value();
+                    allocations::made(&set);
+                    stack.push(set);
+                }
+                Op::Field { key, cache } => {
+                    let coll = stack.pop().unwrap();
+                    let key = &self.constants[key];
+                    let val = match &*coll {
+                        Value::Instance(instance) => {
+                            let type_ = instance.type_();
+                            let (cached, ind) = self.fields[cache]
+                                .get_or_init(|| (Arc::clone(type_), type_.field_index(key)));
+                            let ind = if Arc::ptr_eq(cached, type_) {
+                                *ind
+                            } else {
+                                type_.field_index(key)
+                            };
+                            ind.map_or_else(|| Arc::new(Value::Nil), |ind| instance.field_at(ind))
+                        }
+                        _ => Arc::new(rust_core::GetFn {}.invoke(vec![coll, Arc::clone(key)])),
+                    };
+                    allocations::made(&val);
+                    stack.push(val);
+                }
+            }
+        }
+        stack.pop().unwrap().to_value()
+    }
+
+    // An environment binding scope's locals as they are in frame;  for a form to be
+    // evaluated uncompiled in
+    fn environment(
+        &self,
+        environment: &Arc<Environment>,
+        frame: &[Arc<Value>],
+        scope: usize,
+    ) -> Arc<Environment> {
+        let local_environment = Environment::new_local_environment(Arc::clone(environment));
+        for (sym, slot) in &self.scopes[scope] {
+            local_environment.insert(Symbol::clone(sym), Arc::clone(&frame[*slot]));
+        }
+        Arc::new(local_environment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiler;
+    use crate::environment::Environment;
+    use crate::reader;
+    use crate::repl::Repl;
+    use crate::symbol::Symbol;
+    use crate::value::{ToValue, Value};
+    use std::sync::Arc;
+
+    // What calling (fn params body) on args gives,  compiled and not;  with defs defined
+    // first
+    fn run(defs: &[&str], params: &str, body: &str, args: &[&str]) -> (String, String) {
+        let environment = Environment::clojure_core_environment();
+        let repl = Repl::new(Arc::clone(&environment));
+        repl.eval_all(defs);
+        let read = |source: &str| reader::try_read(source).unwrap().1.to_rc_value();
+        let arg_syms = match &*read(params) {
+            Value::PersistentVector(params) => params
+                .iter()
+                .map(|param| Symbol::intern(&param.to_string()))
+                .collect::<Vec<Symbol>>(),
+            _ => panic!("params should be a vector"),
+        };
+        let args = args
+            .iter()
+            .map(|arg| repl.eval(&read(arg)))
+            .collect::<Vec<Value>>();
+        let code = compiler::compile(
+            &arg_syms,
+            &read(body),
+            &Arc::new(Environment::new_fn_environment(Arc::clone(&environment))),
+        )
+        .unwrap();
+        let compiled = code.run(
+            &Arc::new(Environment::new_fn_environment(Arc::clone(&environment))),
+            args.iter().map(|arg| arg.to_rc_value()).collect(),
+        );
+        let call = format!("((fn {} {}) {})", params, body, args_source(args));
+        let uncompiled = repl.eval_all(&[&call]);
+        (compiled.to_string(), uncompiled.to_string())
+    }
+
+    fn args_source(args: Vec<Value>) -> String {
+        args.iter()
+            .map(|arg| format!("(quote {})", arg.to_string_explicit()))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn compiled_fns_give_what_they_would_uncompiled() {
+        let cases: &[(&[&str], &str, &str, &[&str])] = &[
+            (
+                &[],
+                "[n]",
+                "(loop [i 0 acc []] (if (< i n) (recur (inc i) (conj acc i)) acc))",
+                &["5"],
+            ),
+            (
+                &[],
+                "[n acc]",
+                "(if (zero? n) acc (recur (dec n) (* acc n)))",
+                &["10", "1"],
+            ),
+            (
+                &["(defn fib [n] (if (< n 2) n (+ (fib (- n 1)) (fib (- n 2)))))"],
+                "[n]",
+                "(fib n)",
+                &["15"],
+            ),
+            (&[], "[a & more]", "[a more {:a a} #{a}]", &["1", "2", "3"]),
+            (
+                &[],
+                "[m]",
+                "(let [k :b] [(:a m) (k m) (when (:a m) (str \"a\"))])",
+                &["{:a 1 :b 2}"],
+            ),
+            (&[], "[xs]", "(map (fn [x] (* x x)) xs)", &["[1 2 3]"]),
+            (
+                &[],
+                "[x]",
+                "(try (/ x 0) (catch Exception e (ex-message e)))",
+                &["1"],
+            ),
+            (&[], "[x]", "(do (undefined-thing x) 2)", &["1"]),
+            (&[], "[x]", "(let [y (/ x 0)] y)", &["1"]),
+            (
+                &[],
+                "[n]",
+                "(if (< n 3) (let [[_] [0]] (recur (inc n))) n)",
+                &["0"],
+            ),
+        ];
+        for (defs, params, body, args) in cases {
+            let (compiled, uncompiled) = run(defs, params, body, args);
+            assert_eq!(uncompiled, compiled, "{} {}", params, body);
+        }
+    }
+
+    #[test]
+    fn keywords_read_record_fields_whatever_the_record() {
+        let environment = Environment::clojure_core_environment();
+        let repl = Repl::new(Arc::clone(&environment));
+        repl.eval_all(&["(defrecord Point [x y])", "(defrecord Pair [y x])"]);
+        let body = reader::try_read("[(:x p) (:y p) (:z p) (:user/x p)]")
+            .unwrap()
+            .1
+            .to_rc_value();
+        let code = compiler::compile(&[Symbol::intern("p")], &body, &environment).unwrap();
+        let cases = [
+            ("(->Point 1 2)", "[1 2 nil nil]"),
+            ("(->Point 3 4)", "[3 4 nil nil]"),
+            ("(->Pair 5 6)", "[6 5 nil nil]"),
+            ("{:x 7 :z 8}", "[7 nil 8 nil]"),
+            ("(do (deftype Box [x]) (->Box 9))", "[nil nil nil nil]"),
+            ("nil", "[nil nil nil nil]"),
+        ];
+        for (arg, read) in cases {
+            let arg = repl.eval_all(&[arg]).to_rc_value();
+            assert_eq!(read, code.run(&environment, vec![arg]).to_string());
+        }
+    }
+
+    #[test]
+    fn fns_that_def_are_left_uncompiled() {
+        let environment = Environment::clojure_core_environment();
+        let body = reader::try_read("(do (def x 1) x)")
+            .unwrap()
+            .1
+            .to_rc_value();
+        assert!(compiler::compile(&[], &body, &environment).is_none());
+    }
+}
@@ -0,0 +1,159 @@
+//! Completing a name that's been partly typed;  for tab at the REPL,  and nREPL's
+//! completions op
+//!
+//! A name's completed with what it could be in the namespace it's typed in:  what's defined
+//! or referred there,  clojure.core's,  the special forms,  and the namespaces loaded and
+//! their aliases there.  A qualified one,  alias/na or some.ns/na,  with the public vars of
+//! the namespace it names
+use crate::environment::Environment;
+use crate::namespace::Namespace;
+use crate::reader;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// A name that completes what's been typed;  with the namespace it's from,  if it's a
+/// var's,  and what kind of thing it names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub candidate: String,
+    pub ns: Option<String>,
+    pub kind: &'static str,
+}
+
+// What kind of thing val is,  as CIDER knows them
+fn kind(val: &Value) -> &'static str {
+    match val.type_tag() {
+        TypeTag::Macro => "macro",
+        TypeTag::IFn | TypeTag::MultiFn => "function",
+        _ => "var",
+    }
+}
+
+/// Every name prefix could be the start of,  typed in namespace ns;  sorted
+pub fn completions(environment: &Environment, ns: &Symbol, prefix: &str) -> Vec<Candidate> {
+    let namespaces = environment.all_namespaces();
+    let find = |name: &str| {
+        namespaces
+            .iter()
+            .find(|namespace| namespace.name.name == name)
+            .cloned()
+    };
+    let here = match find(&ns.name) {
+        Some(here) => here,
+        None => return vec![],
+    };
+    // Keyed by name,  so each one's given once,  and they're sorted
+    let mut candidates = BTreeMap::new();
+    let mut add = |candidate: &str, ns: Option<&str>, kind: &'static str| {
+        if candidate.starts_with(prefix) {
+            candidates
+                .entry(candidate.to_string())
+                .or_insert(Candidate {
+                    candidate: candidate.to_string(),
+                    ns: ns.map(String::from),
+                    kind,
+                });
+        }
+    };
+    if let Some((qualifier, _)) = prefix.rsplit_once('/').filter(|(q, _)| !q.is_empty()) {
+        let named = here
+            .alias(&Symbol::intern(qualifier))
+            .map(|ns| ns.name.to_string())
+            .unwrap_or_else(|| qualifier.to_string());
+        if let Some(namespace) = find(&named) {
+            for (sym, val) in public(&namespace, &here) {
+                let candidate = format!("{}/{}", qualifier, sym.name);
+                add(&candidate, Some(&namespace.name.name), kind(&val));
+            }
+        }
+        return candidates.into_values().collect();
+    }
+    for (sym, val) in here.mappings() {
+        add(&sym.name, Some(&here.name.name), kind(&val));
+    }
+    for (sym, var) in here.refers() {
+        let val = environment.get(&var);
+        add(&sym.name, Some(&var.ns), kind(&val));
+    }
+    if let Some(core) = find("clojure.core") {
+        for (sym, val) in public(&core, &here) {
+            if here.refers_core(&sym) {
+                add(&sym.name, Some("clojure.core"), kind(&val));
+            }
+        }
+    }
+    for special_form in reader::SPECIAL_FORMS {
+        add(special_form, None, "special-form");
+    }
+    for namespace in &namespaces {
+        add(&namespace.name.name, None, "namespace");
+    }
+    for (alias, _) in here.aliases() {
+        add(&alias.name, None, "namespace");
+    }
+    candidates.into_values().collect()
+}
+
+// What of namespace's can be used from here;  everything,  if it's here,  and otherwise
+// what's not private
+fn public(namespace: &Arc<Namespace>, here: &Namespace) -> Vec<(Symbol, Arc<Value>)> {
+    namespace
+        .mappings()
+        .into_iter()
+        .filter(|(sym, _)| namespace.name == here.name || !namespace.is_private(sym))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::completion::{completions, Candidate};
+    use crate::environment::Environment;
+    use crate::repl::Repl;
+    use crate::symbol::Symbol;
+    use std::sync::Arc;
+
+    #[test]
+    fn names_are_completed_with_what_they_could_be_there() {
+        let environment = Environment::clojure_core_environment();
+        Repl::new(Arc::clone(&environment)).eval_all(&[
+            "(ns zz.lib)",
+            "(defn zz-open [x] x)",
+            "(defn- zz-hidden [] 1)",
+            "(defmacro zz-with [x] x)",
+            "(ns user (:require [zz.lib :as zl]))",
+            "(def zz-local 1)",
+        ]);
+        let complete = |prefix: &str| {
+            completions(&environment, &Symbol::intern("user"), prefix)
+                .into_iter()
+                .map(|candidate| candidate.candidate)
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(vec!["zero?", "zl", "zz-local", "zz.lib"], complete("z"));
+        assert_eq!(vec!["zl/zz-open", "zl/zz-with"], complete("zl/"));
+        assert_eq!(vec!["zz.lib/zz-open"], complete("zz.lib/zz-o"));
+        assert_eq!(vec!["recur"], complete("recu"));
+        assert_eq!(
+            vec![
+                Candidate {
+                    candidate: String::from("zl/zz-with"),
+                    ns: Some(String::from("zz.lib")),
+                    kind: "macro",
+                },
+                Candidate {
+                    candidate: String::from("loop*"),
+                    ns: None,
+                    kind: "special-form",
+                },
+            ],
+            [
+                completions(&environment, &Symbol::intern("user"), "zl/zz-w"),
+                completions(&environment, &Symbol::intern("user"), "loop*"),
+            ]
+            .concat()
+        );
+    }
+}
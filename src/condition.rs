@@ -0,0 +1,245 @@
+//! What a condition carries besides its message;  the data and cause given to ex-info,
+//! and which catch clauses it's caught by
+//!
+//! A condition that's evaluated to is thrown;  each call,  binding and collection it's in
+//! gives it in turn,  until a try catches it.  Caught,  or made by ex-info,  it's a
+//! Value::Exception instead,  which can be passed around like any other value;  throw
+//! turns it back into a condition.  Both are only a message,  so the rest is kept beside
+//! it here,  keyed by the message's allocation,  as metadata is in meta.rs;  along with the
+//! trace of calls it came out of
+use crate::error_message;
+use crate::interrupt;
+use crate::keyword::Keyword;
+use crate::persistent_list_map::IPersistentMap;
+use crate::shared_str::SharedStr;
+use crate::trace::Frame;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, Weak};
+
+struct Slot {
+    // Keeps the message from being reused while its data is here
+    owner: Weak<String>,
+    // None unless it was made by ex-info
+    data: Option<Arc<Value>>,
+    cause: Option<Arc<Value>>,
+    trace: Vec<Frame>,
+}
+impl Slot {
+    fn new(message: &SharedStr) -> Slot {
+        Slot {
+            owner: SharedStr::downgrade(message),
+            data: None,
+            cause: None,
+            trace: vec![],
+        }
+    }
+}
+
+// How many frames a trace keeps;  the innermost,  as that's where the condition was made
+const MAX_FRAMES: usize = 64;
+
+// Shared by every thread;  a condition a future gives is thrown again by whoever derefs it
+#[derive(Default)]
+struct Slots {
+    slots: HashMap<usize, Slot>,
+    // How many slots there can be before we next drop those of conditions that are gone
+    prune_at: usize,
+}
+
+fn slots() -> MutexGuard<'static, Slots> {
+    static SLOTS: OnceLock<Mutex<Slots>> = OnceLock::new();
+    SLOTS.get_or_init(Default::default).lock().unwrap()
+}
+
+fn key(message: &SharedStr) -> usize {
+    SharedStr::as_ptr(message)
+}
+
+// Runs f on the slot of message,  made for it if it has none yet
+fn update(message: &SharedStr, f: impl FnOnce(&mut Slot)) {
+    let mut slots = slots();
+    f(slots
+        .slots
+        .entry(key(message))
+        .or_insert_with(|| Slot::new(message)));
+    if slots.slots.len() >= slots.prune_at {
+        slots.slots.retain(|_, slot| slot.owner.strong_count() > 0);
+        slots.prune_at = 64.max(slots.slots.len() * 2);
+    }
+}
+
+/// A new exception with message,  carrying data (a map) and maybe the exception that
+/// caused it;  as made by ex-info
+pub fn ex_info(message: &str, data: Arc<Value>, cause: Option<Arc<Value>>) -> Value {
+    let message: SharedStr = message.into();
+    update(&message, |slot| {
+        slot.data = Some(data);
+        slot.cause = cause;
+    });
+    Value::Exception(message)
+}
+
+fn slot<T>(ex: &Value, f: impl FnOnce(&Slot) -> T) -> Option<T> {
+    let message = match ex {
+        Value::Condition(message) | Value::Exception(message) => message,
+        _ => return None,
+    };
+    slots().slots.get(&key(message)).map(f)
+}
+
+/// Adds frame to the trace of condition,  as the call it's just come out of
+pub fn push_frame(condition: &Value, frame: Frame) {
+    if let Value::Condition(message) = condition {
+        update(message, |slot| {
+            if slot.trace.len() < MAX_FRAMES {
+                slot.trace.push(frame);
+            }
+        });
+    }
+}
+
+/// The calls ex came out of,  innermost first
+pub fn frames(ex: &Value) -> Vec<Frame> {
+    slot(ex, |slot| slot.trace.clone()).unwrap_or_default()
+}
+
+/// The data ex (a condition or exception) was made with by ex-info;  None if it wasn't
+pub fn ex_data(ex: &Value) -> Option<Arc<Value>> {
+    slot(ex, |slot| slot.data.clone()).flatten()
+}
+
+/// What caused ex,  if ex-info was given it
+pub fn ex_cause(ex: &Value) -> Option<Arc<Value>> {
+    slot(ex, |slot| slot.cause.clone()).flatten()
+}
+
+/// Whether (catch selector e ..) catches condition.  selector is a type;  one of
+/// Exception,  Throwable and the like for any condition,  ExceptionInfo for those made by
+/// ex-info,  or one of the JVM's for what it'd throw,  as ArithmeticException is for
+/// dividing by zero.  Or it's :default,  for any condition,  or another keyword,  for those
+/// whose ex-data has it as :type.  An interrupt is never caught,  so it always gets back out
+pub fn catches(selector: &Value, condition: &Value) -> Result<bool, Value> {
+    if let Value::Condition(message) = condition {
+        if &**message == interrupt::INTERRUPTED_MSG {
+            return Ok(false);
+        }
+    }
+    match selector {
+        Value::Keyword(kw) if **kw == Keyword::intern("default") => Ok(true),
+        Value::Keyword(_) => Ok(match ex_data(condition).as_deref() {
+            Some(Value::PersistentListMap(data)) => {
+                *data.get(&Keyword::intern("type").to_rc_value()) == *selector
+            }
+            _ => false,
+        }),
+        Value::Symbol(sym) => {
+            let name = sym.name.trim_start_matches("java.lang.");
+            match name {
+                "Exception" | "Throwable" | "Error" | "RuntimeException" => Ok(true),
+                "ExceptionInfo" | "clojure.lang.ExceptionInfo" => Ok(ex_data(condition).is_some()),
+                _ if TypeTag::from_name(&sym.name) == Some(TypeTag::Condition) => Ok(true),
+                _ if JVM_EXCEPTIONS.contains(&name) => {
+                    Ok(ex_data(condition).is_none() && jvm_exceptions(condition).contains(&name))
+                }
+                _ => Err(Value::Condition(
+                    format!("Unable to resolve classname: {}", sym).into(),
+                )),
+            }
+        }
+        _ => Err(error_message::type_mismatch(TypeTag::Symbol, selector)),
+    }
+}
+
+// The JVM's exceptions a condition of our own can be caught as
+const JVM_EXCEPTIONS: &[&str] = &[
+    "ArithmeticException",
+    "AssertionError",
+    "ClassCastException",
+    "IllegalArgumentException",
+    "IllegalStateException",
+    "IndexOutOfBoundsException",
+    "ArityException",
+    "clojure.lang.ArityException",
+    "NullPointerException",
+    "UnsupportedOperationException",
+];
+
+// What the JVM would have thrown instead of condition,  and what that is too;  going by the
+// message,  as that's all a condition has
+fn jvm_exceptions(condition: &Value) -> &'static [&'static str] {
+    let message = match condition {
+        Value::Condition(message) | Value::Exception(message) => &**message,
+        _ => return &[],
+    };
+    if message.starts_with("Divide by zero") {
+        &["ArithmeticException"]
+    } else if message.starts_with("Assert failed") {
+        &["AssertionError"]
+    } else if message.starts_with("Index out of bounds")
+        || message.starts_with("Index cannot be negative")
+    {
+        &["IndexOutOfBoundsException"]
+    } else if message.starts_with("Type mismatch") || message.contains("cannot be cast to") {
+        &["ClassCastException"]
+    } else if message.starts_with("Wrong number of arguments")
+        || message.starts_with("Mismatched argument count")
+    {
+        &[
+            "ArityException",
+            "clojure.lang.ArityException",
+            "IllegalArgumentException",
+        ]
+    } else {
+        &[]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn thrown_conditions_are_caught_by_the_first_matching_clause() {
+        let result = Repl::default().eval_all(&[
+            "(def log (atom []))",
+            "(defn risky [x] (if (< x 0) (throw (ex-info \"negative\" {:type :bad-input :x x})) (* x 2)))",
+            "(defn safe [x] (try (risky x) (catch :other e :wrong) (catch :bad-input e (:x (ex-data e))) (finally (swap! log conj x))))",
+            "[(safe 2) (safe -3) @log]",
+        ]);
+        assert_eq!("[4 -3 [2 -3]]", result.to_string());
+    }
+
+    #[test]
+    fn conditions_stop_evaluation_until_caught() {
+        let result = Repl::default().eval_all(&[
+            "(def reached (atom false))",
+            "(def caught (try (let [x (+ 1 :a)] (do (reset! reached true) x)) (catch Exception e (ex-message e))))",
+            "[caught @reached (ex-data (ex-info \"m\" {})) (ex-data (try (nth [] 1) (catch :default e e)))]",
+        ]);
+        assert_eq!(
+            "[\"Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: clojure.lang.Keyword\" false {} nil]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn conditions_are_caught_as_what_the_jvm_would_throw() {
+        let result = Repl::default().eval_all(&[
+            "(defn caught [f] (try (f) (catch ArithmeticException e :arithmetic) (catch IndexOutOfBoundsException e :index) (catch IllegalArgumentException e :argument) (catch Exception e :other)))",
+            "[(caught (fn [] (/ 1 0))) (caught (fn [] (nth [] 1))) (caught (fn [] (inc 1 2))) (caught (fn [] (throw (ex-info \"Divide by zero\" {}))))]",
+        ]);
+        assert_eq!("[:arithmetic :index :argument :other]", result.to_string());
+    }
+
+    #[test]
+    fn uncaught_conditions_keep_going_out() {
+        let result = Repl::default().eval_all(&[
+            "(def cause (ex-info \"low\" {:level 1}))",
+            "(def e (try (try (throw (ex-info \"high\" {:level 2} cause)) (catch :no-such-type e 1)) (catch ExceptionInfo e e)))",
+            "[(ex-message e) (ex-data (ex-cause e)) (ex-message (ex-cause e))]",
+        ]);
+        assert_eq!("[\"high\" {:level 1} \"low\"]", result.to_string());
+    }
+}
@@ -0,0 +1,117 @@
+//! The conformance corpus;  .clj programs that check what the interpreter does against what
+//! Clojure does,  each with clojure.test tests or plain asserts.  conformance/clojure has
+//! the parts of Clojure's own test suite that we pass,  ported;  conformance/programs,
+//! programs of our own
+//!
+//! Each program is run as a script of its own,  as by `rust_clojure FILE.clj`;  then its
+//! clojure.test tests,  unless it ran them itself.  It passes if nothing in it ended in an
+//! uncaught condition (as a failed assert does),  and none of its tests failed
+use crate::cli;
+use crate::clojure_test::{self, Summary};
+use crate::reader;
+use crate::repl::Repl;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// How running a program went;  with summary,  what its clojure.test tests counted
+#[derive(Debug)]
+pub struct Outcome {
+    pub file: PathBuf,
+    pub summary: Summary,
+    /// The trace of what it failed with,  if it did
+    pub failure: Option<String>,
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Runs the program in file,  in an environment of its own
+pub fn run_program(file: &Path) -> Outcome {
+    let repl = Repl::default();
+    let (summary, ran) = clojure_test::counting(|| {
+        repl.run_script(&file.to_string_lossy())
+            .and_then(|()| run_deftests(&repl))
+    });
+    Outcome {
+        file: file.to_path_buf(),
+        summary,
+        failure: ran.err(),
+    }
+}
+
+/// Runs every program under dir,  in order
+pub fn run(dir: &Path) -> io::Result<Vec<Outcome>> {
+    Ok(cli::clojure_files(dir)?
+        .iter()
+        .map(|file| run_program(file))
+        .collect())
+}
+
+// Runs the clojure.test tests a program defined,  unless it ran them itself;  and fails it
+// if any of them did
+fn run_deftests(repl: &Repl) -> Result<(), String> {
+    let tested = clojure_test::tested_namespaces(repl.environment());
+    if clojure_test::counts().test == 0 && !tested.is_empty() {
+        repl.eval_text(reader::NO_SOURCE_FILE, "(clojure.test/run-all-tests)");
+    }
+    let summary = clojure_test::counts();
+    match summary.fail + summary.error {
+        0 => Ok(()),
+        _ => Err(format!(
+            "{} failures, {} errors",
+            summary.fail, summary.error
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conformance;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn the_corpus_passes() {
+        let outcomes =
+            conformance::run(&Path::new(env!("CARGO_MANIFEST_DIR")).join("conformance")).unwrap();
+        assert!(outcomes.iter().any(|outcome| outcome.summary.pass > 0));
+        for outcome in outcomes {
+            assert_eq!(None, outcome.failure, "{} failed", outcome.file.display());
+        }
+    }
+
+    #[test]
+    fn failed_tests_and_asserts_fail_a_program() {
+        let dir = std::env::temp_dir().join("clojure_rs_conformance_test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("a_tests.clj"),
+            "(ns a-tests (:use clojure.test)) (deftest t (is (= 1 1)) (is (= 1 2)))",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("b_asserts.clj"),
+            "(assert (= 1 1)) (assert (= 1 2))",
+        )
+        .unwrap();
+        fs::write(dir.join("c_passes.clj"), "(assert (= 2 2))").unwrap();
+        let outcomes = conformance::run(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let failures = outcomes
+            .iter()
+            .map(|outcome| (outcome.summary.pass, outcome.failure.is_some()))
+            .collect::<Vec<(usize, bool)>>();
+        assert_eq!(vec![(1, true), (0, true), (0, false)], failures);
+        assert_eq!(
+            Some(String::from("1 failures, 0 errors")),
+            outcomes[0].failure
+        );
+        assert!(outcomes[1]
+            .failure
+            .as_ref()
+            .is_some_and(|trace| trace.contains("Assert failed: (= 1 2)")));
+    }
+}
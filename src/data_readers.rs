@@ -0,0 +1,192 @@
+//! Tagged literals;  #inst "..",  #uuid "..",  #queue [..],  #bytes [..],  and #my/tag form,
+//! read as what the fn *data-readers* has for my/tag makes of form
+//!
+//! *data-readers* is a map of tags to fns,  or to the symbols of fns;  looked up as each
+//! tagged literal is read,  so a fn can be defined after its tag is registered.  It starts
+//! out with what data_readers.clj,  in the directory we're run in,  has;  a map like
+//!
+//!   {my/point my.geometry/read-point}
+//!
+//! inst, uuid, queue and bytes are read as instants, UUIDs, queues and byte arrays,  unless
+//! *data-readers* has them too
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::inst;
+use crate::persistent_list_map::ToPersistentListMapIter;
+use crate::persistent_queue::PersistentQueue;
+use crate::reader;
+use crate::rust_core::{AssocFn, ByteArrayFn, GetFn};
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::uuid;
+use crate::value::{ToValue, Value};
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::sync::{Arc, Weak};
+
+/// Where the tags a program registers for itself are read from
+pub const DATA_READERS_FILE: &str = "data_readers.clj";
+
+thread_local! {
+    // The environment *data-readers* is looked up in;  the last one made,  as the last
+    // one whose namespace changed is the one syntax-quote qualifies symbols with
+    static ENVIRONMENT: RefCell<Weak<Environment>> = const { RefCell::new(Weak::new()) };
+}
+
+/// Has tagged literals read with environment's *data-readers*,  from now on
+pub fn read_with(environment: &Arc<Environment>) {
+    ENVIRONMENT.with(|current| *current.borrow_mut() = Arc::downgrade(environment));
+}
+
+/// Has tagged literals read with only the readers built in,  from now on
+pub fn read_with_none() {
+    ENVIRONMENT.with(|current| *current.borrow_mut() = Weak::new());
+}
+
+/// The environment tagged literals are read with,  if it's still around
+pub fn reading_with() -> Option<Arc<Environment>> {
+    ENVIRONMENT.with(|current| current.borrow().upgrade())
+}
+
+fn data_readers_sym() -> Symbol {
+    Symbol::intern_with_ns("clojure.core", "*data-readers*")
+}
+
+/// What #tag form is read as
+pub fn read_tagged(tag: &Symbol, form: Value) -> Value {
+    if let Some(reader_fn) = reader_fn(tag) {
+        if let Value::Condition(_) = &*reader_fn {
+            return (*reader_fn).clone();
+        }
+        return ifn::call(&reader_fn, vec![form.to_rc_value()]);
+    }
+    read_builtin_tagged(tag, &form)
+        .unwrap_or_else(|| Value::Condition(format!("No reader function for tag {}", tag).into()))
+}
+
+/// What #inst,  #uuid,  #queue or #bytes form is read as,  if that's tag;  shared with the
+/// EDN reader
+pub fn read_builtin_tagged(tag: &Symbol, form: &Value) -> Option<Value> {
+    if !tag.has_ns() && tag.name == "queue" {
+        return Some(read_queue(form));
+    }
+    if !tag.has_ns() && tag.name == "bytes" {
+        return Some(read_bytes(form));
+    }
+    let builtin: fn(&str) -> Result<Value, String> = match (tag.has_ns(), tag.name.as_str()) {
+        (false, "inst") => |s| inst::parse(s).map(Value::Inst),
+        (false, "uuid") => |s| uuid::parse(s).map(|bytes| Value::Uuid(Arc::new(bytes))),
+        _ => return None,
+    };
+    Some(match form {
+        Value::String(s) => builtin(s).unwrap_or_else(|e| Value::Condition(e.into())),
+        _ => error_message::type_mismatch(TypeTag::String, form),
+    })
+}
+
+// #queue [1 2 3];  a queue of the elements of a vector,  front first.  As with any
+// tagged literal,  they're read but not evaluated
+fn read_queue(form: &Value) -> Value {
+    match form {
+        Value::PersistentVector(pvector) => pvector
+            .iter()
+            .fold(PersistentQueue::empty(), |pqueue, val| {
+                pqueue.conj(Arc::clone(val))
+            })
+            .to_value(),
+        _ => error_message::type_mismatch(TypeTag::PersistentVector, form),
+    }
+}
+
+// #bytes [1 -1];  a byte array of the numbers in a vector,  as byte arrays print
+fn read_bytes(form: &Value) -> Value {
+    match form {
+        Value::PersistentVector(_) => ByteArrayFn {}.invoke(vec![form.to_rc_value()]),
+        _ => error_message::type_mismatch(TypeTag::PersistentVector, form),
+    }
+}
+
+// The fn *data-readers* has for tag;  or the condition its symbol resolves to,  if it
+// isn't defined
+fn reader_fn(tag: &Symbol) -> Option<Arc<Value>> {
+    let environment = ENVIRONMENT.with(|current| current.borrow().upgrade())?;
+    let data_readers = environment.get(&data_readers_sym());
+    let reader_fn = GetFn {}.invoke(vec![data_readers, tag.to_rc_value()]);
+    match reader_fn {
+        Value::Nil | Value::Condition(_) => None,
+        Value::Symbol(sym) => Some(environment.get(&sym)),
+        reader_fn => Some(Arc::new(reader_fn)),
+    }
+}
+
+/// Adds the tags in the file at path to environment's *data-readers*;  a file that isn't
+/// there has none
+pub fn load(environment: &Environment, path: &str) -> Result<(), String> {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(format!("Could not read {}: {}", path, e)),
+    };
+    let not_a_map = || format!("{} should be a map of tags to fns' symbols", path);
+    let entries = match reader::SourceReader::new(path, &source).next() {
+        Some(Value::PersistentListMap(entries)) => entries,
+        Some(Value::Condition(message)) => return Err(message.to_string()),
+        _ => return Err(not_a_map()),
+    };
+    let mut data_readers = environment.get(&data_readers_sym());
+    for entry in entries.iter() {
+        match (&*entry.key, &*entry.val) {
+            (Value::Symbol(_), Value::Symbol(_)) => {
+                data_readers = AssocFn {}
+                    .invoke(vec![data_readers, entry.key, entry.val])
+                    .to_rc_value();
+            }
+            _ => return Err(not_a_map()),
+        }
+    }
+    environment
+        .find_or_create_namespace(&Symbol::intern("clojure.core"))
+        .insert(&data_readers_sym(), data_readers);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_readers;
+    use crate::environment::Environment;
+    use crate::repl::Repl;
+    use std::fs;
+    use std::sync::Arc;
+
+    #[test]
+    fn built_in_tags_are_read_as_instants_and_uuids() {
+        let result = Repl::default().eval_all(&[
+            "[#inst \"2026-10-16T11:30:00.5+02:00\" (class #inst \"1970\") (= #uuid \"5D1E5A3C-8F6E-4B4F-9D5E-0A6F3C2B1A09\" #uuid \"5d1e5a3c-8f6e-4b4f-9d5e-0a6f3c2b1a09\") '#uuid \"5d1e5a3c-8f6e-4b4f-9d5e-0a6f3c2b1a09\"]",
+        ]);
+        assert_eq!(
+            "[#inst \"2026-10-16T09:30:00.500-00:00\" clojure.lang.Inst true #uuid \"5d1e5a3c-8f6e-4b4f-9d5e-0a6f3c2b1a09\"]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn other_tags_are_read_with_data_readers() {
+        let path = std::env::temp_dir().join("clojure_rs_data_readers_test.clj");
+        fs::write(&path, "{geo/point user/read-point}").unwrap();
+        let environment = Environment::clojure_core_environment();
+        let loaded = data_readers::load(&environment, &path.to_string_lossy());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(Ok(()), loaded);
+        let result = Repl::new(Arc::clone(&environment)).eval_all(&[
+            "(defn read-point [v] [:point (nth v 0) (nth v 1)])",
+            "(def clojure.core/*data-readers* (assoc *data-readers* 'geo/double (fn [n] (* 2 n))))",
+            "[#geo/point [1 2] #geo/double 21 (ex-message (try #nope/tag 1 (catch Exception e e)))]",
+        ]);
+        assert_eq!(
+            "[[:point 1 2] 42 \"No reader function for tag nope/tag\"]",
+            result.to_string()
+        );
+    }
+}
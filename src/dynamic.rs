@@ -0,0 +1,91 @@
+//! Dynamic vars;  those defined ^:dynamic,  that (binding [var val] body) gives a value
+//! of their own for as long as body runs
+//!
+//! Each binding pushes a frame of what it binds,  by the vars' qualified names,  and pops
+//! it when its body's done;  a var is what the innermost frame binding it says,  or its
+//! root if none does.  set! changes a var in that innermost frame,  never its root.
+//!
+//! Frames are kept per thread,  as in Clojure.  What a future or an agent action runs sees
+//! the frames in place when it was made,  or sent;  they're conveyed to it
+use crate::symbol::Symbol;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// What a binding binds;  each var's value,  by its qualified name
+pub type Frame = HashMap<Symbol, Arc<Value>>;
+
+thread_local! {
+    // The frames of the bindings being run,  outermost first
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+/// What sym in namespace is bound to,  if it's bound
+pub fn bound(namespace: &Symbol, sym: &Symbol) -> Option<Arc<Value>> {
+    FRAMES.with(|frames| {
+        let frames = frames.borrow();
+        // Most of the time nothing's bound;  so don't make the var's name to look for
+        if frames.is_empty() {
+            return None;
+        }
+        let var = Symbol::intern_with_ns(&namespace.name, &sym.name);
+        frames
+            .iter()
+            .rev()
+            .find_map(|frame| frame.get(&var).cloned())
+    })
+}
+
+/// Runs f with the vars in frame bound to their values in it
+pub fn with_bindings<R>(frame: Frame, f: impl FnOnce() -> R) -> R {
+    FRAMES.with(|frames| frames.borrow_mut().push(frame));
+    let result = f();
+    FRAMES.with(|frames| frames.borrow_mut().pop());
+    result
+}
+
+/// Sets var,  a qualified symbol,  to val in the innermost binding of it;  a condition if
+/// it isn't bound,  as set! can't change a root
+pub fn set(var: &Symbol, val: Arc<Value>) -> Result<(), Value> {
+    FRAMES.with(|frames| {
+        match frames
+            .borrow_mut()
+            .iter_mut()
+            .rev()
+            .find(|frame| frame.contains_key(var))
+        {
+            Some(frame) => {
+                frame.insert(var.clone(), val);
+                Ok(())
+            }
+            None => Err(Value::Condition(
+                format!(
+                    "Can't change/establish root binding of: {} with set",
+                    var.name
+                )
+                .into(),
+            )),
+        }
+    })
+}
+
+/// Every binding in place,  flattened into one frame;  to convey to what runs later
+pub fn conveyed() -> Frame {
+    FRAMES.with(|frames| {
+        frames
+            .borrow()
+            .iter()
+            .flat_map(|frame| frame.iter())
+            .map(|(var, val)| (var.clone(), Arc::clone(val)))
+            .collect()
+    })
+}
+
+/// Runs f with only frame's bindings in place;  those conveyed to it
+pub fn with_conveyed<R>(frame: Frame, f: impl FnOnce() -> R) -> R {
+    let outer = FRAMES.with(|frames| frames.replace(vec![frame]));
+    let result = f();
+    FRAMES.with(|frames| *frames.borrow_mut() = outer);
+    result
+}
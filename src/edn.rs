@@ -0,0 +1,334 @@
+//! EDN;  the subset of Clojure's syntax that's only data,  for reading and writing values
+//! that aren't code.  Nothing read as EDN is evaluated,  so it's safe to read from anyone
+//!
+//!   edn::read_str("{:a [1 2] :b #{\"c\"}}")
+//!
+//! reads the first form in a string,  and edn::print_str writes a value back out;  failing,
+//! rather than printing something that won't read back,  on fns and the like.  These are
+//! also clojure.edn/read-string,  read and pr-str
+//!
+//! What's read is held to the reader's limits,  so that a form nested too deep or made of
+//! too many forms is read as a condition;  clojure.edn's opts can set them as :max-depth
+//! and :max-elements
+
+use crate::data_readers;
+use crate::error_message;
+use crate::ifn;
+use crate::lazy_seq;
+use crate::options::Opts;
+use crate::persistent_list::PersistentList;
+use crate::persistent_list_map::ToPersistentListMapIter;
+use crate::reader::{self, Limits, SourceReader, NO_SOURCE_FILE};
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::sync::Arc;
+
+/// How EDN is read;  as clojure.edn's opts map
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The fn each tag's form is read with;  before the built in #inst and #uuid
+    pub readers: HashMap<Symbol, Arc<Value>>,
+    /// What any other tag's form is read with;  it's given the tag and the form
+    pub default: Option<Arc<Value>>,
+    /// What's read when there's nothing left to read;  nil,  if it isn't given
+    pub eof: Option<Arc<Value>>,
+    /// How deep and how big what's read may be;  unless given,  those code is read with
+    pub limits: Limits,
+}
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            readers: HashMap::new(),
+            default: None,
+            eof: None,
+            limits: Limits::configured(),
+        }
+    }
+}
+impl Options {
+    /// The options an opts map given to name,  ie {:readers {'point ->Point} :eof ::done},
+    /// gives;  and :max-depth and :max-elements,  the limits
+    pub fn from_map(name: &str, opts: &Arc<Value>) -> Result<Options, Value> {
+        let known = &["readers", "default", "eof", "max-depth", "max-elements"];
+        let opts = Opts::from_map(name, known, opts)?;
+        let opt = |name| match opts.get(name) {
+            Some(val) if **val != Value::Nil => Some(Arc::clone(val)),
+            _ => None,
+        };
+        let mut readers = HashMap::new();
+        if let Some(reader_map) = opt("readers") {
+            let entries: Vec<(Arc<Value>, Arc<Value>)> = match &*reader_map {
+                Value::PersistentListMap(map) => {
+                    map.iter().map(|entry| (entry.key, entry.val)).collect()
+                }
+                Value::PersistentTreeMap(map) => {
+                    map.iter().map(|entry| (entry.key, entry.val)).collect()
+                }
+                _ => {
+                    return Err(error_message::type_mismatch(
+                        TypeTag::IPersistentMap,
+                        &reader_map,
+                    ))
+                }
+            };
+            for (tag, f) in entries {
+                match &*tag {
+                    Value::Symbol(tag) => readers.insert((**tag).clone(), f),
+                    _ => return Err(error_message::type_mismatch(TypeTag::Symbol, &tag)),
+                };
+            }
+        }
+        let limit = |name, default| match opt(name).as_deref() {
+            None => Ok(default),
+            Some(Value::I32(limit)) if *limit > 0 => Ok(*limit as usize),
+            Some(limit) => Err(Value::Condition(
+                format!(":{} should be a positive number, not {}", name, limit).into(),
+            )),
+        };
+        let configured = Limits::configured();
+        Ok(Options {
+            readers,
+            default: opt("default"),
+            eof: opt("eof"),
+            limits: Limits {
+                max_depth: limit("max-depth", configured.max_depth)?,
+                max_elements: limit("max-elements", configured.max_elements)?,
+            },
+        })
+    }
+    /// What #tag form is read as
+    pub fn read_tagged(&self, tag: &Symbol, form: Value) -> Value {
+        // A tag's fn is ordinary code;  if it reads anything itself,  that's read as code
+        let call = |f, args| reader::with_edn(None, || ifn::call(f, args));
+        if let Some(f) = self.readers.get(tag) {
+            return call(f, vec![form.to_rc_value()]);
+        }
+        if let Some(read) = data_readers::read_builtin_tagged(tag, &form) {
+            return read;
+        }
+        match &self.default {
+            Some(f) => call(f, vec![tag.to_rc_value(), form.to_rc_value()]),
+            None => Value::Condition(format!("No reader function for tag {}", tag).into()),
+        }
+    }
+    fn eof(&self) -> Value {
+        self.eof.as_ref().map_or(Value::Nil, |eof| (**eof).clone())
+    }
+}
+
+/// The first form in s;  nil,  if there isn't one
+pub fn read_str(s: &str) -> Result<Value, Value> {
+    read_str_with(s, Options::default())
+}
+
+/// The first form in s,  read with options;  their eof,  if there isn't one
+pub fn read_str_with(s: &str, options: Options) -> Result<Value, Value> {
+    let options = Arc::new(options);
+    let read = reader::with_edn(Some(Arc::clone(&options)), || {
+        SourceReader::new(NO_SOURCE_FILE, s).next()
+    });
+    match read {
+        None => Ok(options.eof()),
+        Some(form) => checked(form),
+    }
+}
+
+/// The next form from reader,  read with options;  their eof,  if it's already at its end
+pub fn read<R: BufRead>(reader: &mut R, options: Options) -> Result<Value, Value> {
+    match reader.fill_buf() {
+        Ok([]) => return Ok(options.eof()),
+        Ok(_) => {}
+        Err(e) => return Err(Value::Condition(format!("Reader error: {}", e).into())),
+    }
+    let options = Arc::new(options);
+    checked(reader::with_edn(Some(options), || reader::read(reader)))
+}
+
+// form,  unless reading it failed;  somewhere inside it,  if a tag's fn did
+fn checked(form: Value) -> Result<Value, Value> {
+    match failure(&form) {
+        Some(condition) => Err(condition),
+        None => Ok(form),
+    }
+}
+
+fn failure(form: &Value) -> Option<Value> {
+    let vals: Vec<Arc<Value>> = match form {
+        Value::Condition(_) => return Some(form.clone()),
+        Value::PersistentList(plist) => PersistentList::iter(plist).collect(),
+        Value::PersistentVector(pvector) => pvector.iter().cloned().collect(),
+        Value::PersistentHashSet(pset) => pset.iter().collect(),
+        Value::PersistentListMap(map) => map
+            .iter()
+            .flat_map(|entry| vec![entry.key, entry.val])
+            .collect(),
+        _ => return None,
+    };
+    vals.iter().find_map(|val| failure(val))
+}
+
+/// val as EDN;  a condition,  if any of it can't be
+pub fn print_str(val: &Value) -> Result<String, Value> {
+    let mut out = String::new();
+    print(val, &mut out)?;
+    Ok(out)
+}
+
+fn print(val: &Value, out: &mut String) -> Result<(), Value> {
+    match val {
+        Value::Nil
+        | Value::Boolean(_)
+        | Value::I32(_)
+        | Value::BigInt(_)
+        | Value::Ratio(_)
+        | Value::BigDecimal(_)
+        | Value::Char(_)
+        | Value::String(_)
+        | Value::Symbol(_)
+        | Value::Keyword(_)
+        | Value::Inst(_)
+        | Value::Uuid(_) => out.push_str(&val.to_string_explicit()),
+        // Debug,  unlike Display,  keeps the .0 that makes 1.0 a float
+        Value::F64(f) if f.is_finite() => out.push_str(&format!("{:?}", f)),
+        Value::PersistentList(plist) => print_all("(", PersistentList::iter(plist), ")", out)?,
+        Value::LazySeq(_) => {
+            print_all("(", lazy_seq::iter(val.to_rc_value()), ")", out)?;
+        }
+        Value::PersistentVector(pvector) => print_all("[", pvector.iter().cloned(), "]", out)?,
+        Value::PersistentHashSet(pset) => print_all("#{", pset.iter(), "}", out)?,
+        Value::PersistentTreeSet(ptset) => print_all("#{", ptset.iter(), "}", out)?,
+        Value::PersistentListMap(map) => {
+            print_map(map.iter().map(|entry| (entry.key, entry.val)), out)?;
+        }
+        Value::PersistentTreeMap(map) => {
+            print_map(map.iter().map(|entry| (entry.key, entry.val)), out)?;
+        }
+        Value::Condition(_) => return Err(val.clone()),
+        _ => {
+            return Err(Value::Condition(
+                format!("Can't print {} as EDN", val.type_tag()).into(),
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn print_all(
+    open: &str,
+    vals: impl Iterator<Item = Arc<Value>>,
+    close: &str,
+    out: &mut String,
+) -> Result<(), Value> {
+    out.push_str(open);
+    for (i, val) in vals.enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        print(&val, out)?;
+    }
+    out.push_str(close);
+    Ok(())
+}
+
+fn print_map(
+    entries: impl Iterator<Item = (Arc<Value>, Arc<Value>)>,
+    out: &mut String,
+) -> Result<(), Value> {
+    out.push('{');
+    for (i, (key, val)) in entries.enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        print(&key, out)?;
+        out.push(' ');
+        print(&val, out)?;
+    }
+    out.push('}');
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::edn;
+    use crate::reader::Limits;
+    use crate::value::Value;
+
+    #[test]
+    fn edn_reads_data_and_prints_it_back() {
+        let read = edn::read_str(
+            "[1 2.0 \"three\" \\c {:a #{nil}} (x/y #inst \"2020-01-01T00:00:00.000-00:00\")] :ignored",
+        )
+        .unwrap();
+        assert_eq!(
+            "[1 2.0 \"three\" \\c {:a #{nil}} (x/y #inst \"2020-01-01T00:00:00.000-00:00\")]",
+            edn::print_str(&read).unwrap()
+        );
+        assert_eq!(Value::Nil, edn::read_str("  ; nothing\n").unwrap());
+    }
+
+    #[test]
+    fn strings_with_escapes_round_trip() {
+        let s = Value::String("say \"hi\"\n\tto C:\\ \u{e9}".into());
+        let printed = edn::print_str(&s).unwrap();
+        assert_eq!("\"say \\\"hi\\\"\\n\\tto C:\\\\ \u{e9}\"", printed);
+        assert_eq!(s, edn::read_str(&printed).unwrap());
+        assert_eq!(
+            Value::String("\u{e9}!".into()),
+            edn::read_str("\"\\u00e9!\"").unwrap()
+        );
+        assert_eq!(
+            "#Condition[\"Unsupported escape character: \\q\"]",
+            edn::read_str("[\"\\q\" 1]").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn edn_is_only_data() {
+        for code in &["'x", "@x", "#(inc %)", "^:m [1]", "#\"re\"", "#?(:clj 1)"] {
+            assert!(edn::read_str(code).is_err(), "{} read as EDN", code);
+        }
+        assert_eq!(
+            "#Condition[\"No reader function for tag point\"]",
+            edn::read_str("[#point [1 2]]").unwrap_err().to_string()
+        );
+        let f = crate::repl::Repl::default().eval_all(&["inc"]);
+        assert_eq!(
+            "#Condition[\"Can't print clojure.lang.Function as EDN\"]",
+            edn::print_str(&f).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn edn_is_read_within_limits() {
+        let options = |max_depth, max_elements| edn::Options {
+            limits: Limits {
+                max_depth,
+                max_elements,
+            },
+            ..edn::Options::default()
+        };
+        let read = edn::read_str_with("[[1] {:a [2]}]", options(4, 7)).unwrap();
+        assert_eq!("[[1] {:a [2]}]", read.to_string());
+        assert_eq!(
+            "#Condition[\"Reader Error: limits exceeded;  forms nest more than 3 deep\"]",
+            edn::read_str_with("[[1] {:a [2]}]", options(3, 7))
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "#Condition[\"Reader Error: limits exceeded;  more than 6 forms\"]",
+            edn::read_str_with("[[1] {:a [2]}]", options(4, 6))
+                .unwrap_err()
+                .to_string()
+        );
+        let deep = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        assert!(edn::read_str(&deep).is_err());
+        assert_eq!(
+            Value::I32(1),
+            edn::read_str_with("1", options(1, 1)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Character encodings;  what slurp, spit, reader and writer take as :encoding.  UTF-8,
+//! UTF-16 (big or little endian,  or either with a byte order mark),  ISO-8859-1 and
+//! US-ASCII;  written out by hand,  as there are only a few.  As in Java,  bytes that don't
+//! decode read as U+FFFD,  and chars an encoding has no bytes for are written as ?
+use crate::value::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    // Big endian,  unless a byte order mark says otherwise;  written with one
+    Utf16,
+    Utf16Be,
+    Utf16Le,
+    Latin1,
+    Ascii,
+}
+impl Encoding {
+    /// The encoding named name,  ie "UTF-8",  "utf16le" or "latin1";  whatever its case
+    pub fn named(name: &str) -> Result<Encoding, Value> {
+        let encoding = match &*name.to_lowercase().replace('_', "-") {
+            "utf-8" | "utf8" => Encoding::Utf8,
+            "utf-16" | "utf16" => Encoding::Utf16,
+            "utf-16be" | "utf16be" | "unicodebigunmarked" => Encoding::Utf16Be,
+            "utf-16le" | "utf16le" | "unicodelittleunmarked" => Encoding::Utf16Le,
+            "iso-8859-1" | "iso8859-1" | "latin1" | "iso-latin-1" => Encoding::Latin1,
+            "us-ascii" | "ascii" => Encoding::Ascii,
+            _ => {
+                return Err(Value::Condition(
+                    format!(
+                        "Unsupported encoding {};  UTF-8, UTF-16, UTF-16BE, UTF-16LE, \
+                         ISO-8859-1 and US-ASCII are",
+                        name
+                    )
+                    .into(),
+                ))
+            }
+        };
+        Ok(encoding)
+    }
+
+    /// The text bytes hold
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            Encoding::Utf16 => match bytes {
+                [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+                [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+                _ => decode_utf16(bytes, u16::from_be_bytes),
+            },
+            Encoding::Utf16Be => decode_utf16(bytes, u16::from_be_bytes),
+            Encoding::Utf16Le => decode_utf16(bytes, u16::from_le_bytes),
+            Encoding::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+            Encoding::Ascii => bytes
+                .iter()
+                .map(|&b| match b.is_ascii() {
+                    true => b as char,
+                    false => char::REPLACEMENT_CHARACTER,
+                })
+                .collect(),
+        }
+    }
+
+    /// The bytes text is written as
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            Encoding::Utf8 => text.as_bytes().to_vec(),
+            Encoding::Utf16 => {
+                let mut bytes = vec![0xFE, 0xFF];
+                bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+                bytes
+            }
+            Encoding::Utf16Be => text.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+            Encoding::Utf16Le => text.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            Encoding::Latin1 => encode_below(text, 0x100),
+            Encoding::Ascii => encode_below(text, 0x80),
+        }
+    }
+}
+
+// The UTF-16 code units of bytes,  two by two,  as text;  an odd byte left at the end
+// doesn't decode
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let units = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    let mut text: String = char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+    if !bytes.len().is_multiple_of(2) {
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+    text
+}
+
+// text,  a byte a char;  for encodings whose chars are the code points below limit
+fn encode_below(text: &str, limit: u32) -> Vec<u8> {
+    text.chars()
+        .map(|c| match (c as u32) < limit {
+            true => c as u8,
+            false => b'?',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    mod encoding_tests {
+        use crate::encoding::Encoding;
+
+        #[test]
+        fn text_comes_back_as_it_went_in() {
+            for name in &["UTF-8", "utf-16", "UTF-16BE", "utf16le", "ISO-8859-1"] {
+                let encoding = Encoding::named(name).unwrap();
+                let text = "naïve café";
+                assert_eq!(text, encoding.decode(&encoding.encode(text)));
+            }
+        }
+
+        #[test]
+        fn encodings_write_their_own_bytes() {
+            assert_eq!(vec![0x63, 0xE9], Encoding::Latin1.encode("cé"));
+            assert_eq!(vec![0x63, b'?'], Encoding::Ascii.encode("cé"));
+            assert_eq!(vec![0xFE, 0xFF, 0, 0x63], Encoding::Utf16.encode("c"));
+            assert_eq!("c", Encoding::Utf16.decode(&[0xFF, 0xFE, 0x63, 0]));
+            assert_eq!("c\u{FFFD}", Encoding::Utf8.decode(&[0x63, 0xE9]));
+            assert!(Encoding::named("EBCDIC").is_err());
+        }
+    }
+}
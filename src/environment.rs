@@ -1,70 +1,154 @@
+use crate::clojure_core_async;
+use crate::clojure_core_cache;
+use crate::clojure_data;
+use crate::clojure_edn;
+use crate::clojure_java_io;
+use crate::clojure_java_shell;
+use crate::clojure_pprint;
+use crate::clojure_profile;
+use crate::clojure_repl;
+use crate::clojure_set;
+use crate::clojure_spec;
 use crate::clojure_std;
 use crate::clojure_string;
-use crate::namespace::{Namespace, Namespaces};
+use crate::clojure_term_colors;
+use crate::clojure_term_progress;
+use crate::clojure_test;
+use crate::clojure_tools_cli;
+use crate::clojure_tools_logging;
+use crate::clojure_walk;
+use crate::data_readers;
+use crate::keyword::Keyword;
+use crate::maps::MapEntry;
+use crate::namespace::{Builtin, Namespace, Namespaces};
+use crate::output::Stream;
+use crate::persistent_list_map::PersistentListMap;
+use crate::persistent_queue::PersistentQueue;
+use crate::persistent_vector::PersistentVector;
+use crate::print_order;
+use crate::reader;
 use crate::repl::Repl;
 use crate::rust_core;
 use crate::symbol::Symbol;
 use crate::value::{ToValue, Value};
+use crate::var::Var;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+
+/// Where clojure.core's own definitions are said to be;  and their text,  built into the
+/// binary so it runs from any directory
+pub const CORE_FILE: &str = "clojure/core.clj";
+pub const CORE_SOURCE: &str = include_str!("clojure/core.clj");
 
 // @TODO lookup naming convention
 /// Inner value of our environment
 /// See Environment for overall purpose
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct EnvironmentVal {
     //@TODO is it worth just making this a mutable reference (to an
     // immutable value), and referencing the current symbol at any
     // point in time?  Is implementing that sort of speedup in general
     // significant
-    curr_ns_sym: RefCell<Symbol>,
-    namespaces: Namespaces,
+    curr_ns_sym: RwLock<Symbol>,
+    namespaces: Arc<Namespaces>,
 }
 impl EnvironmentVal {
+    // Runs f on the fork standing in for this on this thread,  if there is one;  or on this
+    fn with<T>(&self, f: impl FnOnce(&EnvironmentVal) -> T) -> T {
+        let fork = FORK.with(|fork| match &*fork.borrow() {
+            Some((of, fork)) if *of == self as *const EnvironmentVal as usize => {
+                Some(Arc::clone(&fork.0))
+            }
+            _ => None,
+        });
+        match fork {
+            Some(fork) => f(&fork),
+            None => f(self),
+        }
+    }
     fn change_namespace(&self, name: Symbol) {
-        self.curr_ns_sym.replace(name);
+        self.with(|val| *val.curr_ns_sym.write().unwrap() = name);
     }
-    fn insert_into_namespace(&self, namespace: &Symbol, sym: Symbol, val: Rc<Value>) {
-        self.namespaces.insert_into_namespace(namespace, &sym, val);
+    fn insert_into_namespace(&self, namespace: &Symbol, sym: Symbol, val: Arc<Value>) {
+        self.namespaces()
+            .insert_into_namespace(namespace, &sym, val);
     }
-    fn insert_into_current_namespace(&self, sym: Symbol, val: Rc<Value>) {
-        self.namespaces
-            .insert_into_namespace(&*self.curr_ns_sym.borrow(), &sym, val);
+    fn insert_into_current_namespace(&self, sym: Symbol, val: Arc<Value>) {
+        self.namespaces()
+            .insert_into_namespace(&self.get_current_namespace(), &sym, val);
     }
-    fn get_from_namespace(&self, namespace: &Symbol, sym: &Symbol) -> Rc<Value> {
-        self.namespaces.get(namespace, sym)
+    fn get_from_namespace(&self, namespace: &Symbol, sym: &Symbol) -> Arc<Value> {
+        self.namespaces().get(namespace, sym)
     }
     fn get_current_namespace(&self) -> Symbol {
-        self.curr_ns_sym.borrow().clone()
+        self.with(|val| val.curr_ns_sym.read().unwrap().clone())
+    }
+    fn namespaces(&self) -> Arc<Namespaces> {
+        self.with(|val| Arc::clone(&val.namespaces))
     }
     // @TODO as mentioned, we've been working with a memory model where values exist
-    //       in our system once-ish and we reference them all over with Rc<..>
+    //       in our system once-ish and we reference them all over with Arc<..>
     //       Look into possibly working this into that (if its even significant);
     /// Default main environment
     fn new_main_val() -> EnvironmentVal {
         let curr_ns_sym = Symbol::intern("user");
         let namespaces = Namespaces::new();
         namespaces.create_namespace(&curr_ns_sym);
+        reader::set_current_namespace(&curr_ns_sym.name);
         EnvironmentVal {
-            curr_ns_sym: RefCell::new(curr_ns_sym),
-            namespaces,
+            curr_ns_sym: RwLock::new(curr_ns_sym),
+            namespaces: Arc::new(namespaces),
         }
     }
 }
+
+thread_local! {
+    // The fork standing in for a main environment on this thread,  if one is;  with the
+    // address of the EnvironmentVal it stands in for.  See Environment::in_fork
+    static FORK: RefCell<Forked> = const { RefCell::new(None) };
+}
+
+/// A copy of a main environment's namespaces,  and of which is current,  that goes its own
+/// way from when it's made;  see Environment::fork
+#[derive(Debug, Clone)]
+pub struct Fork(Arc<EnvironmentVal>);
+
+/// The fork standing in for a main environment on a thread,  if one is;  for a future or
+/// the like to carry on in,  as it does the thread's bindings
+pub(crate) type Forked = Option<(usize, Fork)>;
+
+/// The fork standing in for a main environment on this thread,  if one is
+pub(crate) fn forked() -> Forked {
+    FORK.with(|fork| fork.borrow().clone())
+}
+
+/// Has forked stand in on this thread from now on;  see forked
+pub(crate) fn carry_on_in(forked: Forked) {
+    FORK.with(|fork| *fork.borrow_mut() = forked);
+}
 /// Our environment keeps track of the meaning of things 'right here', relative to where
 /// something is at (meaning, a form inside of a let might have a different meaning for
 /// the symbol x than a form outside of it, with a let introducing an additional local environment
 ///
 /// Stores our namespaces and our current namespace, which themselves personally store our symbols
 /// mapped to values
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub enum Environment {
     MainEnvironment(EnvironmentVal),
     /// Points to parent environment
     /// Introduced by Closures, and by let
-    LocalEnvironment(Rc<Environment>, RefCell<HashMap<Symbol, Rc<Value>>>),
+    ///
+    /// A fn's also has the namespace it was made in;  what isn't bound locally is looked up
+    /// there when it's called,  rather than in whatever namespace is current by then.  As
+    /// it's looked up on every call,  a fn always calls what its names are defined as now,
+    /// as if it called through vars
+    LocalEnvironment(
+        Arc<Environment>,
+        RwLock<HashMap<Symbol, Arc<Value>>>,
+        Option<Symbol>,
+    ),
 }
 use Environment::*;
 impl Environment {
@@ -72,8 +156,11 @@ impl Environment {
         let symbol = symbol.unqualified();
 
         match self.get_main_environment() {
-            MainEnvironment(EnvironmentVal { curr_ns_sym, .. }) => {
-                curr_ns_sym.replace(symbol);
+            MainEnvironment(env_val) => {
+                // So that even a namespace with nothing defined in it yet can be found
+                env_val.namespaces().find_or_create_namespace(&symbol);
+                reader::set_current_namespace(&symbol.name);
+                env_val.change_namespace(symbol);
             }
             LocalEnvironment(..) => panic!(
                 "get_main_environment() returns LocalEnvironment,\
@@ -85,7 +172,7 @@ impl Environment {
     // @TODO consider 'current_namespace_sym'? after all, its not the namespace itself
     pub fn get_current_namespace(&self) -> Symbol {
         match self.get_main_environment() {
-            MainEnvironment(EnvironmentVal { curr_ns_sym, .. }) => curr_ns_sym.borrow().clone(),
+            MainEnvironment(env_val) => env_val.get_current_namespace(),
             LocalEnvironment(..) => panic!(
                 "In get_current_namespace_name(): get_main_environment() returns LocalEnvironment,\
 		                 but by definition should only return MainEnvironment"
@@ -96,17 +183,47 @@ impl Environment {
     // returning a String instead of a &str, as I suspect a &str could
     // risk becoming invalid as curr_ns changes
     pub fn get_current_namespace_name(&self) -> String {
-        self.get_current_namespace().name.clone()
+        self.get_current_namespace().name.to_string()
+    }
+
+    fn get_namespaces(&self) -> Arc<Namespaces> {
+        match self.get_main_environment() {
+            MainEnvironment(env_val) => env_val.namespaces(),
+            LocalEnvironment(..) => panic!(
+                "get_main_environment() returns LocalEnvironment,\
+		                 but by definition should only return MainEnvironment"
+            ),
+        }
+    }
+    /// Returns the namespace named sym, if there is one
+    pub fn find_namespace(&self, sym: &Symbol) -> Option<Arc<Namespace>> {
+        self.get_namespaces().get_namespace(sym)
+    }
+    /// Returns the namespace named sym, creating it if it doesn't exist yet
+    pub fn find_or_create_namespace(&self, sym: &Symbol) -> Arc<Namespace> {
+        self.get_namespaces().find_or_create_namespace(sym)
+    }
+    pub fn all_namespaces(&self) -> Vec<Arc<Namespace>> {
+        self.get_namespaces().all_namespaces()
     }
 
     pub fn new_main_environment() -> Environment {
         MainEnvironment(EnvironmentVal::new_main_val())
     }
-    pub fn new_local_environment(outer_environment: Rc<Environment>) -> Environment {
-        LocalEnvironment(outer_environment, RefCell::new(HashMap::new()))
+    pub fn new_local_environment(outer_environment: Arc<Environment>) -> Environment {
+        LocalEnvironment(outer_environment, RwLock::new(HashMap::new()), None)
+    }
+    /// The environment a fn made in outer_environment closes over;  see LocalEnvironment
+    pub fn new_fn_environment(outer_environment: Arc<Environment>) -> Environment {
+        let namespace = outer_environment.get_current_namespace();
+        LocalEnvironment(
+            outer_environment,
+            RwLock::new(HashMap::new()),
+            Some(namespace),
+        )
     }
     /// Insert a binding into an arbitrary namespace
-    fn insert_into_namespace(&self, namespace: &Symbol, sym: Symbol, val: Rc<Value>) {
+    fn insert_into_namespace(&self, namespace: &Symbol, sym: Symbol, val: Arc<Value>) {
         match self.get_main_environment() {
             MainEnvironment(env_val) => env_val.insert_into_namespace(namespace, sym, val),
             LocalEnvironment(..) => panic!(
@@ -115,7 +232,7 @@ impl Environment {
             ),
         }
     }
-    pub fn insert_into_current_namespace(&self, sym: Symbol, val: Rc<Value>) {
+    pub fn insert_into_current_namespace(&self, sym: Symbol, val: Arc<Value>) {
         match self.get_main_environment() {
             MainEnvironment(env_val) => env_val.insert_into_current_namespace(sym, val),
             LocalEnvironment(..) => panic!(
@@ -130,16 +247,104 @@ impl Environment {
     /// ```clojure
     ///   (def a 1)      ;; => main_environment.insert(a,1)
     ///   (let [a 1] ..) ;; => local_environment.insert(a,1)  
-    pub fn insert(&self, sym: Symbol, val: Rc<Value>) {
+    pub fn insert(&self, sym: Symbol, val: Arc<Value>) {
         match self {
             MainEnvironment(_) => {
                 self.insert_into_current_namespace(sym, val);
             }
-            LocalEnvironment(_, mappings) => {
-                mappings.borrow_mut().insert(sym, val);
+            LocalEnvironment(_, mappings, _) => {
+                mappings.write().unwrap().insert(sym, val);
+            }
+        }
+    }
+    /// Defines name (in the current namespace,  unless it's qualified) as what make makes;
+    /// but only makes it the first time it's looked up,  so builtins cost nothing at startup
+    /// until they're used
+    pub fn insert_builtin(&self, name: &'static str, make: Builtin) {
+        let (namespace, name) = self.split_name(name);
+        self.get_namespaces()
+            .find_or_create_namespace(&namespace)
+            .insert_builtin(name, make);
+    }
+    /// Defines name (in the current namespace,  unless it's qualified) as val;  creating
+    /// its namespace if there's none yet
+    pub fn define(&self, name: &str, val: Arc<Value>) {
+        let (namespace, name) = self.split_name(name);
+        self.get_namespaces()
+            .find_or_create_namespace(&namespace)
+            .insert(&Symbol::intern(name), val);
+    }
+    // The namespace name is in,  and its name there
+    fn split_name<'a>(&self, name: &'a str) -> (Symbol, &'a str) {
+        match name.find('/').filter(|_| name != "/") {
+            Some(ind) => (Symbol::intern(&name[..ind]), &name[ind + 1..]),
+            None => (self.get_current_namespace(), name),
+        }
+    }
+    /// insert,  with meta as the metadata of what's defined;  as by (def ^:private a 1).
+    /// Only a definition in a namespace keeps it
+    pub fn insert_with_meta(&self, sym: Symbol, val: Arc<Value>, meta: Option<Arc<Value>>) {
+        self.insert(Symbol::clone(&sym), val);
+        if let (MainEnvironment(_), Some(meta)) = (self, meta) {
+            if let Some(namespace) = self.find_namespace(&self.get_current_namespace()) {
+                namespace.set_meta(&sym, meta);
+            }
+        }
+    }
+    /// The var sym names here,  if it names one;  resolved as get resolves it,  in the
+    /// innermost fn's namespace if we're in one
+    pub fn resolve_var(&self, sym: &Symbol) -> Option<Var> {
+        let namespace = self
+            .fn_namespace()
+            .unwrap_or_else(|| self.get_current_namespace());
+        self.resolve_var_in(&namespace, sym)
+    }
+    /// The var sym names in namespace,  if it names one
+    pub fn resolve_var_in(&self, namespace: &Symbol, sym: &Symbol) -> Option<Var> {
+        self.get_namespaces().resolve_var(namespace, sym)
+    }
+    /// Whether sym is bound by a let,  loop or fn around us;  rather than looked up in a
+    /// namespace
+    pub fn is_local(&self, sym: &Symbol) -> bool {
+        match self {
+            MainEnvironment(_) => false,
+            LocalEnvironment(parent_env, mappings, _) => {
+                !sym.has_ns()
+                    && (mappings.read().unwrap().contains_key(sym) || parent_env.is_local(sym))
+            }
+        }
+    }
+    // The namespace of the innermost fn we're in,  if we're in one
+    fn fn_namespace(&self) -> Option<Symbol> {
+        match self {
+            MainEnvironment(_) => None,
+            LocalEnvironment(parent_env, _, fn_namespace) => {
+                fn_namespace.clone().or_else(|| parent_env.fn_namespace())
             }
         }
     }
+    // Makes name,  a builtin (of the current namespace,  unless it's qualified),  ^:dynamic;
+    // as if it was defined so
+    fn set_dynamic(&self, name: &str) {
+        let (namespace, name) = self.split_name(name);
+        let meta = vec![MapEntry {
+            key: Keyword::intern("dynamic").to_rc_value(),
+            val: Value::Boolean(true).to_rc_value(),
+        }]
+        .into_iter()
+        .collect::<PersistentListMap>();
+        self.find_or_create_namespace(&namespace)
+            .set_meta(&Symbol::intern(name), meta.to_rc_value());
+    }
+    fn get_main_val(&self) -> &EnvironmentVal {
+        match self.get_main_environment() {
+            MainEnvironment(env_val) => env_val,
+            LocalEnvironment(..) => panic!(
+                "get_main_environment() returns LocalEnvironment,\
+		                 but by definition should only return MainEnvironment"
+            ),
+        }
+    }
     fn get_main_environment(&self) -> &Self {
         match self {
             MainEnvironment(_) => self,
@@ -150,264 +355,1084 @@ impl Environment {
     // @TODO figure out convention for 'ns' vs 'namespace'
     /// Get closest value "around" us;  try our local environment, then
     /// try our main environment (unless its namespace qualified)
-    pub fn get(&self, sym: &Symbol) -> Rc<Value> {
+    pub fn get(&self, sym: &Symbol) -> Arc<Value> {
+        self.get_resolving_in(sym, None)
+    }
+    // get,  with unqualified symbols that aren't bound locally resolved in namespace;  the
+    // innermost fn's,  if we're in one
+    fn get_resolving_in(&self, sym: &Symbol, namespace: Option<&Symbol>) -> Arc<Value> {
         match self {
             MainEnvironment(env_val) => {
-                // If we've recieved a qualified symbol like
-                // clojure.core/+
-                if sym.ns != "" {
-                    // Use that namespace
-                    env_val.get_from_namespace(&Symbol::intern(&sym.ns), sym)
-                } else {
-                    env_val.get_from_namespace(
-                        &env_val.get_current_namespace(),
-                        &Symbol::intern(&sym.name),
-                    )
-                }
+                let namespace = match namespace {
+                    Some(namespace) => Symbol::clone(namespace),
+                    None => env_val.get_current_namespace(),
+                };
+                // A qualified symbol,  like clojure.core/+,  is looked up in the namespace
+                // it names;  or the one that's an alias for,  in namespace
+                env_val.get_from_namespace(&namespace, sym)
             }
-            LocalEnvironment(parent_env, mappings) => {
+            LocalEnvironment(parent_env, mappings, fn_namespace) => {
+                // Qualified,  it's resolved in the namespace the innermost fn was defined in;
+                // whose environment may be any of those around us
                 if sym.ns != "" {
-                    return self.get_main_environment().get(sym);
+                    let namespace = namespace.cloned().or_else(|| self.fn_namespace());
+                    return self
+                        .get_main_environment()
+                        .get_resolving_in(sym, namespace.as_ref());
                 }
-                match mappings.borrow().get(sym) {
-                    Some(val) => Rc::clone(val),
-                    None => parent_env.get(sym),
+                match mappings.read().unwrap().get(sym) {
+                    Some(val) => Arc::clone(val),
+                    None => parent_env.get_resolving_in(sym, namespace.or(fn_namespace.as_ref())),
                 }
             }
         }
     }
-    pub fn clojure_core_environment() -> Rc<Environment> {
-        // Register our macros / functions ahead of time
-        let add_fn = rust_core::AddFn {};
-        let subtract_fn = rust_core::SubtractFn {};
-        let multiply_fn = rust_core::MultiplyFn {};
-        let divide_fn = rust_core::DivideFn {};
-        let rand_fn = rust_core::RandFn {};
-        let rand_int_fn = rust_core::RandIntFn {};
-        let str_fn = rust_core::StrFn {};
-        let do_fn = rust_core::DoFn {};
-        let nth_fn = rust_core::NthFn {};
-        let do_macro = rust_core::DoMacro {};
-        let concat_fn = rust_core::ConcatFn {};
-        let flush_stdout_fn = rust_core::FlushStdoutFn {};
-        let system_newline_fn = rust_core::SystemNewlineFn {};
-        let print_string_fn = rust_core::PrintStringFn {};
-        let read_line_fn = rust_core::ReadLineFn {};
-        let assoc_fn = rust_core::AssocFn {};
-
-        // rust implementations of core functions
-        let slurp_fn = rust_core::slurp::SlurpFn {};
-
-        // clojure.std functions
-        let thread_sleep_fn = clojure_std::thread::SleepFn {};
-        let nanotime_fn = clojure_std::time::NanoTimeFn {};
-        let get_env_fn = clojure_std::env::GetEnvFn {};
-
-        let get_fn = rust_core::GetFn {};
-        let map_fn = rust_core::MapFn {};
+    pub fn clojure_core_environment() -> Arc<Environment> {
+        let environment = Arc::new(Environment::new_main_environment());
+        Environment::load_core(&environment);
+        environment
+    }
+    /// A copy of all that's defined here,  and of which namespace is current,  as they are
+    /// now;  cheap,  as what's defined is shared rather than copied.  See in_fork
+    pub fn fork(&self) -> Fork {
+        self.get_main_val().with(|val| {
+            Fork(Arc::new(EnvironmentVal {
+                curr_ns_sym: RwLock::new(val.curr_ns_sym.read().unwrap().clone()),
+                namespaces: Arc::new(val.namespaces.fork()),
+            }))
+        })
+    }
+    /// Runs f with fork standing in for this environment on this thread;  so what f
+    /// defines,  requires,  looks up and so on is in fork,  even through the fns and
+    /// builtins made here before,  which hold on to this environment
+    pub fn in_fork<T>(&self, fork: &Fork, f: impl FnOnce() -> T) -> T {
+        let of = self.get_main_val() as *const EnvironmentVal as usize;
+        let outer = FORK.with(|current| current.replace(Some((of, fork.clone()))));
+        let reading_in = reader::current_namespace();
+        reader::set_current_namespace(&fork.0.get_current_namespace().name);
+        let result = f();
+        reader::set_current_namespace(&reading_in);
+        FORK.with(|current| *current.borrow_mut() = outer);
+        result
+    }
+    /// Takes on all that's defined in fork,  and its current namespace,  in place of what's
+    /// defined here;  what already holds on to this environment sees them
+    pub fn merge(&self, fork: &Fork) {
+        self.get_namespaces().adopt(&fork.0.namespaces);
+        self.change_namespace(fork.0.get_current_namespace());
+    }
+    /// Starts over;  forgets every namespace and all that's defined in them,  and loads
+    /// clojure.core again,  as if this were a new clojure_core_environment.  What already
+    /// holds on to this environment sees the fresh one
+    pub fn reset(self: &Arc<Environment>) {
+        let namespaces = self.get_namespaces();
+        namespaces.clear();
+        namespaces.create_namespace(&Symbol::intern("user"));
+        Environment::load_core(self);
+    }
+    // Defines clojure.core,  and the libs built in alongside it,  in environment;  leaving
+    // user the current namespace
+    fn load_core(environment: &Arc<Environment>) {
+        let environment = Arc::clone(environment);
+        data_readers::read_with(&environment);
 
-        // clojure.string
-        let reverse_fn = clojure_string::reverse::ReverseFn {};
-        let join_fn = clojure_string::join::JoinFn {};
-        let blank_fn = clojure_string::blank_qmark_::BlankFn {};
-        let upper_case_fn = clojure_string::upper_case::UpperCaseFn {};
-        let lower_case_fn = clojure_string::lower_case::LowerCaseFn {};
-        let starts_with_fn = clojure_string::starts_with_qmark_::StartsWithFn {};
-        let ends_with_fn = clojure_string::ends_with_qmark_::EndsWithFn {};
-        let includes_fn = clojure_string::includes_qmark_::IncludesFn {};
-        let trim_fn = clojure_string::trim::TrimFn {};
-        let triml_fn = clojure_string::triml::TrimLFn {};
-        let trimr_fn = clojure_string::trimr::TrimRFn {};
-        let trim_newline_fn = clojure_string::trim_newline::TrimNewlineFn {};
-
-        // Hardcoded fns
-        let lexical_eval_fn = Value::LexicalEvalFn {};
-        // Hardcoded macros
-        let let_macro = Value::LetMacro {};
-        let quote_macro = Value::QuoteMacro {};
-        let def_macro = Value::DefMacro {};
-        let fn_macro = Value::FnMacro {};
-        let defmacro_macro = Value::DefmacroMacro {};
-        let if_macro = Value::IfMacro {};
-        let environment = Rc::new(Environment::new_main_environment());
-
-        let eval_fn = rust_core::EvalFn::new(Rc::clone(&environment));
-        let ns_macro = rust_core::NsMacro::new(Rc::clone(&environment));
-        let load_file_fn = rust_core::LoadFileFn::new(Rc::clone(&environment));
+        let eval_fn = rust_core::EvalFn::new(Arc::clone(&environment));
+        let defprotocol_fn = rust_core::DefprotocolFn::new(Arc::clone(&environment));
+        let deftype_fn = rust_core::DeftypeFn::new(Arc::clone(&environment));
+        let reify_fn = rust_core::ReifyFn::new(Arc::clone(&environment));
+        let defmulti_fn = rust_core::DefmultiFn::new(Arc::clone(&environment));
+        let in_ns_fn = rust_core::InNsFn::new(Arc::clone(&environment));
+        let load_file_fn = rust_core::LoadFileFn::new(Arc::clone(&environment));
+        let load_fn = rust_core::LoadFn::new(Arc::clone(&environment));
+        let require_fn = rust_core::RequireFn::new(Arc::clone(&environment));
+        let use_fn = rust_core::UseFn::new(require_fn.clone());
+        let refer_fn = rust_core::ReferFn::new(Arc::clone(&environment));
+        let alias_fn = rust_core::AliasFn::new(Arc::clone(&environment));
+        let find_ns_fn = rust_core::FindNsFn::new(Arc::clone(&environment));
+        let create_ns_fn = rust_core::CreateNsFn::new(Arc::clone(&environment));
+        let the_ns_fn = rust_core::TheNsFn::new(Arc::clone(&environment));
+        let ns_name_fn = rust_core::NsNameFn::new(Arc::clone(&environment));
+        let ns_publics_fn = rust_core::NsPublicsFn::new(Arc::clone(&environment));
+        let ns_interns_fn = rust_core::NsInternsFn::new(Arc::clone(&environment));
+        let all_ns_fn = rust_core::AllNsFn::new(Arc::clone(&environment));
+        let ns_map_fn = rust_core::NsMapFn::new(Arc::clone(&environment));
+        let ns_refers_fn = rust_core::NsRefersFn::new(Arc::clone(&environment));
+        let ns_aliases_fn = rust_core::NsAliasesFn::new(Arc::clone(&environment));
+        let resolve_fn = rust_core::ResolveFn::new(Arc::clone(&environment));
+        let ns_resolve_fn = rust_core::NsResolveFn::new(Arc::clone(&environment));
         // @TODO after we merge this with all the other commits we have,
         //       just change all the `insert`s here to use insert_in_namespace
         //       I prefer explicity and the non-dependence-on-environmental-factors
         environment.change_namespace(Symbol::intern("clojure.core"));
 
-        environment.insert(Symbol::intern("+"), add_fn.to_rc_value());
-        environment.insert(Symbol::intern("-"), subtract_fn.to_rc_value());
-        environment.insert(Symbol::intern("*"), multiply_fn.to_rc_value());
-        environment.insert(Symbol::intern("_slash_"), divide_fn.to_rc_value());
-        environment.insert(Symbol::intern("rand"), rand_fn.to_rc_value());
-        environment.insert(Symbol::intern("rand-int"), rand_int_fn.to_rc_value());
-        environment.insert(Symbol::intern("let"), let_macro.to_rc_value());
-        environment.insert(Symbol::intern("str"), str_fn.to_rc_value());
-        environment.insert(Symbol::intern("quote"), quote_macro.to_rc_value());
-        environment.insert(Symbol::intern("def"), def_macro.to_rc_value());
-        environment.insert(Symbol::intern("fn"), fn_macro.to_rc_value());
-        environment.insert(Symbol::intern("defmacro"), defmacro_macro.to_rc_value());
+        // Each builtin is only made when it's first looked up,  so starting up doesn't
+        // wait on the hundreds a script never uses
+        environment.insert_builtin("+", || rust_core::AddFn {}.to_value());
+        environment.insert_builtin("-", || rust_core::SubtractFn {}.to_value());
+        environment.insert_builtin("*", || rust_core::MultiplyFn {}.to_value());
+        environment.insert_builtin("/", || rust_core::DivideFn {}.to_value());
+        environment.insert_builtin("_slash_", || rust_core::DivideFn {}.to_value());
+        environment.insert_builtin("quot", || rust_core::QuotFn {}.to_value());
+        environment.insert_builtin("rem", || rust_core::RemFn {}.to_value());
+        environment.insert_builtin("mod", || rust_core::ModFn {}.to_value());
+        environment.insert_builtin("abs", || rust_core::AbsFn {}.to_value());
+        environment.insert_builtin("signum", || rust_core::SignumFn {}.to_value());
+        environment.insert_builtin("rand", || rust_core::RandFn {}.to_value());
+        environment.insert_builtin("rand-int", || rust_core::RandIntFn {}.to_value());
+        environment.insert_builtin("set-random-seed!", || {
+            rust_core::SetRandomSeedFn {}.to_value()
+        });
+        environment.insert_builtin("random-uuid", || rust_core::RandomUuidFn {}.to_value());
+        environment.insert_builtin("shuffle", || rust_core::ShuffleFn {}.to_value());
+        environment.insert_builtin("random-sample", || rust_core::RandomSampleFn {}.to_value());
+        environment.insert_builtin("promise", || rust_core::PromiseFn {}.to_value());
+        environment.insert_builtin("deliver", || rust_core::DeliverFn {}.to_value());
+        environment.insert_builtin("future-call", || rust_core::FutureCallFn {}.to_value());
+        environment.insert_builtin("pmap", || rust_core::PmapFn {}.to_value());
+        environment.insert_builtin("deref", || rust_core::DerefFn {}.to_value());
+        environment.insert_builtin("atom", || rust_core::AtomFn {}.to_value());
+        environment.insert_builtin("durable-atom", || rust_core::DurableAtomFn {}.to_value());
+        environment.insert_builtin("swap!", || rust_core::SwapBangFn {}.to_value());
+        environment.insert_builtin("reset!", || rust_core::ResetBangFn {}.to_value());
+        environment.insert_builtin("compare-and-set!", || {
+            rust_core::CompareAndSetBangFn {}.to_value()
+        });
+        environment.insert_builtin("add-watch", || rust_core::AddWatchFn {}.to_value());
+        environment.insert_builtin("remove-watch", || rust_core::RemoveWatchFn {}.to_value());
+        environment.insert_builtin("ref", || rust_core::RefFn {}.to_value());
+        environment.insert_builtin("dosync*", || rust_core::DosyncFn {}.to_value());
+        environment.insert_builtin("dosync", || rust_core::DosyncMacro {}.to_value());
+        environment.insert_builtin("with-precision*", || {
+            rust_core::WithPrecisionFn {}.to_value()
+        });
+        environment.insert_builtin("with-precision", || {
+            rust_core::WithPrecisionMacro {}.to_value()
+        });
+        environment.insert_builtin("binding*", || rust_core::BindingFn {}.to_value());
+        environment.insert_builtin("binding", || rust_core::BindingMacro {}.to_value());
+        environment.insert_builtin("set!*", || rust_core::SetBangFn {}.to_value());
+        environment.insert_builtin("set!", || rust_core::SetBangMacro {}.to_value());
+        environment.insert_builtin("alter-var-root", || rust_core::AlterVarRootFn {}.to_value());
+        environment.insert_builtin("alter", || rust_core::AlterFn {}.to_value());
+        environment.insert_builtin("ref-set", || rust_core::RefSetFn {}.to_value());
+        environment.insert_builtin("commute", || rust_core::CommuteFn {}.to_value());
+        environment.insert_builtin("agent", || rust_core::AgentFn {}.to_value());
+        environment.insert_builtin("send", || rust_core::SendFn {}.to_value());
+        environment.insert_builtin("send-off", || rust_core::SendOffFn {}.to_value());
+        environment.insert_builtin("await", || rust_core::AwaitFn {}.to_value());
+        environment.insert_builtin("agent-error", || rust_core::AgentErrorFn {}.to_value());
+        environment.insert_builtin("restart-agent", || rust_core::RestartAgentFn {}.to_value());
+        environment.insert_builtin("realized?", || rust_core::RealizedFn {}.to_value());
+        environment.insert_builtin("close", || rust_core::CloseFn {}.to_value());
+        environment.insert_builtin("with-open", || rust_core::WithOpenMacro {}.to_value());
+        environment.insert(Symbol::intern("find-ns"), find_ns_fn.to_rc_value());
+        environment.insert(Symbol::intern("create-ns"), create_ns_fn.to_rc_value());
+        environment.insert(Symbol::intern("the-ns"), the_ns_fn.to_rc_value());
+        environment.insert(Symbol::intern("ns-name"), ns_name_fn.to_rc_value());
+        environment.insert(Symbol::intern("ns-publics"), ns_publics_fn.to_rc_value());
+        environment.insert(Symbol::intern("ns-interns"), ns_interns_fn.to_rc_value());
+        environment.insert(Symbol::intern("all-ns"), all_ns_fn.to_rc_value());
+        environment.insert(Symbol::intern("ns-map"), ns_map_fn.to_rc_value());
+        environment.insert(Symbol::intern("ns-refers"), ns_refers_fn.to_rc_value());
+        environment.insert(Symbol::intern("ns-aliases"), ns_aliases_fn.to_rc_value());
+        environment.insert(Symbol::intern("resolve"), resolve_fn.to_rc_value());
+        environment.insert(Symbol::intern("ns-resolve"), ns_resolve_fn.to_rc_value());
+        environment.insert_builtin("conj", || rust_core::ConjFn {}.to_value());
+        environment.insert_builtin("disj", || rust_core::DisjFn {}.to_value());
+        environment.insert_builtin("contains?", || rust_core::ContainsFn {}.to_value());
+        environment.insert_builtin("byte-array", || rust_core::ByteArrayFn {}.to_value());
+        environment.insert_builtin("compare", || rust_core::CompareFn {}.to_value());
+        environment.insert_builtin("<", || rust_core::LtFn {}.to_value());
+        environment.insert_builtin("<=", || rust_core::LteFn {}.to_value());
+        environment.insert_builtin(">", || rust_core::GtFn {}.to_value());
+        environment.insert_builtin(">=", || rust_core::GteFn {}.to_value());
+        environment.insert_builtin("=", || rust_core::EqualsFn {}.to_value());
+        environment.insert_builtin("not=", || rust_core::NotEqualsFn {}.to_value());
+        environment.insert_builtin("==", || rust_core::EquivFn {}.to_value());
+        environment.insert_builtin("hash", || rust_core::HashFn {}.to_value());
+        environment.insert_builtin("min", || rust_core::MinFn {}.to_value());
+        environment.insert_builtin("max", || rust_core::MaxFn {}.to_value());
+        environment.insert_builtin("set", || rust_core::SetFn {}.to_value());
+        environment.insert_builtin("sorted-map", || rust_core::SortedMapFn {}.to_value());
+        environment.insert_builtin("sorted-map-by", || rust_core::SortedMapByFn {}.to_value());
+        environment.insert_builtin("sorted-set", || rust_core::SortedSetFn {}.to_value());
+        environment.insert_builtin("sorted-set-by", || rust_core::SortedSetByFn {}.to_value());
+        environment.insert_builtin("subseq", || rust_core::SubseqFn {}.to_value());
+        environment.insert_builtin("rsubseq", || rust_core::RsubseqFn {}.to_value());
+        environment.insert_builtin("make-hierarchy", || {
+            rust_core::MakeHierarchyFn {}.to_value()
+        });
+        environment.insert_builtin("derive", || rust_core::DeriveFn {}.to_value());
+        environment.insert_builtin("underive", || rust_core::UnderiveFn {}.to_value());
+        environment.insert_builtin("parents", || rust_core::ParentsFn {}.to_value());
+        environment.insert_builtin("ancestors", || rust_core::AncestorsFn {}.to_value());
+        environment.insert_builtin("descendants", || rust_core::DescendantsFn {}.to_value());
+        environment.insert_builtin("isa?", || rust_core::IsaFn {}.to_value());
+        environment.insert_builtin("class", || rust_core::ClassFn {}.to_value());
+        environment.insert_builtin("type", || rust_core::ClassFn {}.to_value());
+        environment.insert_builtin("bases", || rust_core::BasesFn {}.to_value());
+        environment.insert_builtin("supers", || rust_core::SupersFn {}.to_value());
+        environment.insert(Symbol::intern("defprotocol*"), defprotocol_fn.to_rc_value());
+        environment.insert_builtin("defprotocol", || rust_core::DefprotocolMacro {}.to_value());
+        environment.insert(Symbol::intern("deftype*"), deftype_fn.to_rc_value());
+        environment.insert_builtin("deftype", || rust_core::DeftypeMacro {}.to_value());
+        environment.insert_builtin("defrecord", || rust_core::DefrecordMacro {}.to_value());
+        environment.insert_builtin("field*", || rust_core::FieldFn {}.to_value());
+        environment.insert_builtin("extend*", || rust_core::ExtendMethodFn {}.to_value());
+        environment.insert_builtin("extend", || rust_core::ExtendFn {}.to_value());
+        environment.insert_builtin("extend-type", || rust_core::ExtendTypeMacro {}.to_value());
+        environment.insert_builtin("extend-protocol", || {
+            rust_core::ExtendProtocolMacro {}.to_value()
+        });
+        environment.insert(Symbol::intern("reify*"), reify_fn.to_rc_value());
+        environment.insert_builtin("reify", || rust_core::ReifyMacro {}.to_value());
+        environment.insert_builtin("satisfies?", || rust_core::SatisfiesFn {}.to_value());
+        environment.insert(Symbol::intern("defmulti*"), defmulti_fn.to_rc_value());
+        environment.insert_builtin("defmulti", || rust_core::DefmultiMacro {}.to_value());
+        environment.insert_builtin("defmethod*", || rust_core::DefmethodFn {}.to_value());
+        environment.insert_builtin("defmethod", || rust_core::DefmethodMacro {}.to_value());
+        environment.insert_builtin("remove-method", || rust_core::RemoveMethodFn {}.to_value());
+        environment.insert_builtin("remove-all-methods", || {
+            rust_core::RemoveAllMethodsFn {}.to_value()
+        });
+        environment.insert_builtin("prefer-method", || rust_core::PreferMethodFn {}.to_value());
+        environment.insert_builtin("methods", || rust_core::MethodsFn {}.to_value());
+        environment.insert_builtin("get-method", || rust_core::GetMethodFn {}.to_value());
+        environment.insert_builtin("prefers", || rust_core::PrefersFn {}.to_value());
+
+        // sorting
+        environment.insert_builtin("sort", || rust_core::SortFn {}.to_value());
+        environment.insert_builtin("sort-by", || rust_core::SortByFn {}.to_value());
+        environment.insert_builtin("juxt", || rust_core::JuxtFn {}.to_value());
+
+        // higher order fns
+        environment.insert_builtin("apply", || rust_core::ApplyFn {}.to_value());
+        environment.insert_builtin("partial", || rust_core::PartialFn {}.to_value());
+        environment.insert_builtin("complement", || rust_core::ComplementFn {}.to_value());
+        environment.insert_builtin("fnil", || rust_core::FnilFn {}.to_value());
+        environment.insert_builtin("memoize", || rust_core::MemoizeFn {}.to_value());
+        environment.insert_builtin("trampoline", || rust_core::TrampolineFn {}.to_value());
+
+        // exceptions
+        environment.insert_builtin("throw", || rust_core::ThrowFn {}.to_value());
+        environment.insert_builtin("ex-info", || rust_core::ExInfoFn {}.to_value());
+        environment.insert_builtin("ex-data", || rust_core::ExDataFn {}.to_value());
+        environment.insert_builtin("ex-message", || rust_core::ExMessageFn {}.to_value());
+        environment.insert_builtin("ex-cause", || rust_core::ExCauseFn {}.to_value());
+
+        // metadata
+        environment.insert_builtin("meta", || rust_core::MetaFn {}.to_value());
+        environment.insert_builtin("with-meta", || rust_core::WithMetaFn {}.to_value());
+        environment.insert_builtin("vary-meta", || rust_core::VaryMetaFn {}.to_value());
+
+        // regexes
+        environment.insert_builtin("re-pattern", || rust_core::RePatternFn {}.to_value());
+        environment.insert_builtin("re-find", || rust_core::ReFindFn {}.to_value());
+        environment.insert_builtin("re-matches", || rust_core::ReMatchesFn {}.to_value());
+        environment.insert_builtin("re-seq", || rust_core::ReSeqFn {}.to_value());
+
+        // characters
+        environment.insert_builtin("char", || rust_core::CharFn {}.to_value());
+        environment.insert_builtin("int", || rust_core::IntFn {}.to_value());
+
+        environment.insert_builtin("let", || Value::LetMacro {}.to_value());
+        environment.insert_builtin("str", || rust_core::StrFn {}.to_value());
+        environment.insert_builtin("pr-str", || rust_core::PrStrFn {}.to_value());
+        environment.insert_builtin("format", || rust_core::FormatFn {}.to_value());
+        environment.insert_builtin("subs", || rust_core::SubsFn {}.to_value());
+        environment.insert_builtin("printf", || rust_core::PrintfFn {}.to_value());
+        environment.insert_builtin("sb", || rust_core::SbFn {}.to_value());
+        environment.insert_builtin("sb-append!", || rust_core::SbAppendBangFn {}.to_value());
+        environment.insert_builtin("sb-str", || rust_core::SbStrFn {}.to_value());
+        environment.insert_builtin("keyword", || rust_core::KeywordFn {}.to_value());
+        environment.insert_builtin("symbol", || rust_core::SymbolFn {}.to_value());
+        environment.insert_builtin("name", || rust_core::NameFn {}.to_value());
+        environment.insert_builtin("namespace", || rust_core::NamespaceFn {}.to_value());
+        environment.insert_builtin("quote", || Value::QuoteMacro {}.to_value());
+        environment.insert_builtin("def", || Value::DefMacro {}.to_value());
+        environment.insert_builtin("fn", || Value::FnMacro {}.to_value());
+        environment.insert_builtin("defmacro", || Value::DefmacroMacro {}.to_value());
         environment.insert(Symbol::intern("eval"), eval_fn.to_rc_value());
 
         // Thread namespace
-		environment.insert_into_namespace(
-			&Symbol::intern("Thread"),
-			Symbol::intern("sleep"),
-			thread_sleep_fn.to_rc_value()
-		);
-
-		// System namespace
-		environment.insert_into_namespace(
-			&Symbol::intern("System"),
-			Symbol::intern("nanoTime"),
-			nanotime_fn.to_rc_value()
-		);
-		environment.insert_into_namespace(
-			&Symbol::intern("System"),
-			Symbol::intern("getenv"),
-			get_env_fn.to_rc_value()
-		);
-
-        // core.clj wraps calls to the rust implementations
-        // @TODO add this to clojure.rs.core namespace as clojure.rs.core/slurp
-        environment.insert(Symbol::intern("rust-slurp"), slurp_fn.to_rc_value());
+        environment.insert_builtin("Thread/sleep", || {
+            clojure_std::thread::SleepFn {}.to_value()
+        });
+
+        // System namespace
+        environment.insert_builtin("System/nanoTime", || {
+            clojure_std::time::NanoTimeFn {}.to_value()
+        });
+        environment.insert_builtin("System/getenv", || clojure_std::env::GetEnvFn {}.to_value());
+
+        environment.insert_builtin("slurp", || rust_core::SlurpFn {}.to_value());
+        environment.insert_builtin("spit", || rust_core::SpitFn {}.to_value());
+        environment.insert_builtin("slurp-bytes", || rust_core::SlurpBytesFn {}.to_value());
+        environment.insert_builtin("spit-bytes", || rust_core::SpitBytesFn {}.to_value());
+        environment.insert_builtin("line-seq", || rust_core::LineSeqFn {}.to_value());
+        environment.insert_builtin("file-seq", || rust_core::FileSeqFn {}.to_value());
+
+        // clojure.java.io
+        environment.insert_builtin("clojure.java.io/file", || {
+            clojure_java_io::file::FileFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.io/reader", || {
+            clojure_java_io::reader::ReaderFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.io/writer", || {
+            clojure_java_io::writer::WriterFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.io/copy", || {
+            clojure_java_io::copy::CopyFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.io/delete-file", || {
+            clojure_java_io::delete_file::DeleteFileFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.io/glob", || {
+            clojure_java_io::glob::GlobFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.io/walk", || {
+            clojure_java_io::walk::WalkFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.io/temp-file", || {
+            clojure_java_io::temp_file::TempFileFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.io/with-temp-dir", || {
+            clojure_java_io::with_temp_dir::WithTempDirMacro {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.io/with-temp-dir*", || {
+            clojure_java_io::with_temp_dir::WithTempDirFn {}.to_value()
+        });
+
+        // clojure.java.shell
+        environment.insert_builtin("clojure.java.shell/sh", || {
+            clojure_java_shell::sh::ShFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.java.shell/sh-pipe", || {
+            clojure_java_shell::sh_pipe::ShPipeFn {}.to_value()
+        });
 
         // clojure.string
-        environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("reverse"),
-            reverse_fn.to_rc_value(),
-        );
+        environment.insert_builtin("clojure.string/reverse", || {
+            clojure_string::reverse::ReverseFn {}.to_value()
+        });
 
-        environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("join"),
-            join_fn.to_rc_value(),
-        );
+        environment.insert_builtin("clojure.string/join", || {
+            clojure_string::join::JoinFn {}.to_value()
+        });
 
-        environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("blank?"),
-            blank_fn.to_rc_value(),
-        );
+        environment.insert_builtin("clojure.string/blank?", || {
+            clojure_string::blank_qmark_::BlankFn {}.to_value()
+        });
 
-        environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("upper-case"),
-            upper_case_fn.to_rc_value(),
-        );
+        environment.insert_builtin("clojure.string/upper-case", || {
+            clojure_string::upper_case::UpperCaseFn {}.to_value()
+        });
 
-        environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("lower-case"),
-            lower_case_fn.to_rc_value(),
-        );
+        environment.insert_builtin("clojure.string/lower-case", || {
+            clojure_string::lower_case::LowerCaseFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/starts-with?", || {
+            clojure_string::starts_with_qmark_::StartsWithFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/ends-with?", || {
+            clojure_string::ends_with_qmark_::EndsWithFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/includes?", || {
+            clojure_string::includes_qmark_::IncludesFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/trim", || {
+            clojure_string::trim::TrimFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/triml", || {
+            clojure_string::triml::TrimLFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/trimr", || {
+            clojure_string::trimr::TrimRFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/trim-newline", || {
+            clojure_string::trim_newline::TrimNewlineFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/replace", || {
+            clojure_string::replace::ReplaceFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/replace-first", || {
+            clojure_string::replace_first::ReplaceFirstFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/split", || {
+            clojure_string::split::SplitFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/split-lines", || {
+            clojure_string::split_lines::SplitLinesFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/index-of", || {
+            clojure_string::index_of::IndexOfFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/last-index-of", || {
+            clojure_string::last_index_of::LastIndexOfFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/capitalize", || {
+            clojure_string::capitalize::CapitalizeFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/escape", || {
+            clojure_string::escape::EscapeFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/re-quote-replacement", || {
+            clojure_string::re_quote_replacement::ReQuoteReplacementFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/graphemes", || {
+            clojure_string::graphemes::GraphemesFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/grapheme-count", || {
+            clojure_string::grapheme_count::GraphemeCountFn {}.to_value()
+        });
 
+        environment.insert_builtin("clojure.string/grapheme-subs", || {
+            clojure_string::grapheme_subs::GraphemeSubsFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/grapheme-reverse", || {
+            clojure_string::grapheme_reverse::GraphemeReverseFn {}.to_value()
+        });
+
+        environment.insert_builtin("clojure.string/locale-compare", || {
+            clojure_string::locale_compare::LocaleCompareFn {}.to_value()
+        });
+
+        // clojure.set
+        environment.insert_builtin("clojure.set/union", || {
+            clojure_set::union::UnionFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.set/intersection", || {
+            clojure_set::intersection::IntersectionFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.set/difference", || {
+            clojure_set::difference::DifferenceFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.set/subset?", || {
+            clojure_set::subset_qmark_::SubsetFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.set/select", || {
+            clojure_set::select::SelectFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.set/rename-keys", || {
+            clojure_set::rename_keys::RenameKeysFn {}.to_value()
+        });
+
+        // clojure.data
+        environment.insert_builtin("clojure.data/diff", || {
+            clojure_data::diff::DiffFn {}.to_value()
+        });
+
+        // clojure.pprint
         environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("starts-with?"),
-            starts_with_fn.to_rc_value(),
+            &Symbol::intern("clojure.pprint"),
+            Symbol::intern("pprint"),
+            clojure_pprint::pprint::PprintFn::new(Arc::clone(&environment)).to_rc_value(),
         );
+        environment.insert_builtin("clojure.pprint/pp", || {
+            clojure_pprint::pp::PpMacro {}.to_value()
+        });
+        environment.insert_builtin("clojure.pprint/print-table", || {
+            clojure_pprint::print_table::PrintTableFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.pprint/*print-right-margin*", || Value::I32(72));
+        environment.set_dynamic("clojure.pprint/*print-right-margin*");
 
+        // clojure.edn
+        environment.insert_builtin("clojure.edn/read-string", || {
+            clojure_edn::read_string::ReadStringFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.edn/read", || {
+            clojure_edn::read::ReadFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.edn/pr-str", || {
+            clojure_edn::pr_str::PrStrFn {}.to_value()
+        });
+
+        // clojure.walk
+        environment.insert_builtin("clojure.walk/walk", || {
+            clojure_walk::walk::WalkFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.walk/postwalk", || {
+            clojure_walk::postwalk::PostwalkFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.walk/prewalk", || {
+            clojure_walk::prewalk::PrewalkFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.walk/keywordize-keys", || {
+            clojure_walk::keywordize_keys::KeywordizeKeysFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.walk/stringify-keys", || {
+            clojure_walk::stringify_keys::StringifyKeysFn {}.to_value()
+        });
         environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("ends-with?"),
-            ends_with_fn.to_rc_value(),
+            &Symbol::intern("clojure.walk"),
+            Symbol::intern("macroexpand-all"),
+            clojure_walk::macroexpand_all::MacroexpandAllFn::new(Arc::clone(&environment))
+                .to_rc_value(),
         );
 
+        // clojure.profile
+        environment.insert_builtin("clojure.profile/start!", || {
+            clojure_profile::start::StartFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.profile/stop!", || {
+            clojure_profile::start::StopFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.profile/report", || {
+            clojure_profile::report::ReportFn {
+                stream: Stream::Out,
+            }
+            .to_value()
+        });
+        environment.insert_builtin("clojure.profile/stats", || {
+            clojure_profile::report::StatsFn {}.to_value()
+        });
+
+        // clojure.repl
+        environment.insert_builtin("clojure.repl/doc", || {
+            clojure_repl::doc::DocMacro {}.to_value()
+        });
         environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("includes?"),
-            includes_fn.to_rc_value(),
+            &Symbol::intern("clojure.repl"),
+            Symbol::intern("print-doc"),
+            clojure_repl::doc::PrintDocFn::new(Arc::clone(&environment)).to_rc_value(),
         );
-
+        environment.insert_builtin("clojure.repl/source", || {
+            clojure_repl::source::SourceMacro {}.to_value()
+        });
         environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("trim"),
-            trim_fn.to_rc_value(),
+            &Symbol::intern("clojure.repl"),
+            Symbol::intern("source-fn"),
+            clojure_repl::source::SourceFnFn::new(Arc::clone(&environment)).to_rc_value(),
         );
 
+        // clojure.test
+        environment.insert_builtin("clojure.test/deftest", || {
+            clojure_test::deftest::DeftestMacro {}.to_value()
+        });
+        environment.insert_builtin("clojure.test/test-var*", || {
+            clojure_test::deftest::TestVarFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.test/register-test*", || {
+            clojure_test::deftest::RegisterTestFn {}.to_value()
+        });
+        // What a run of tests is bound to have counted,  and to be within;  see clojure_test
+        environment.insert_builtin("clojure.test/*report-counters*", || Value::Nil);
+        environment.insert_builtin("clojure.test/*testing-vars*", || {
+            PersistentVector::empty().to_value()
+        });
+        environment.insert_builtin("clojure.test/*testing-contexts*", || {
+            PersistentVector::empty().to_value()
+        });
+        for var in [
+            "clojure.test/*report-counters*",
+            "clojure.test/*testing-vars*",
+            "clojure.test/*testing-contexts*",
+        ] {
+            environment.set_dynamic(var);
+        }
+        environment.insert_builtin("clojure.test/testing", || {
+            clojure_test::testing::TestingMacro {}.to_value()
+        });
+        environment.insert_builtin("clojure.test/testing*", || {
+            clojure_test::testing::TestingFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.test/are", || {
+            clojure_test::are::AreMacro {}.to_value()
+        });
+        environment.insert_builtin("clojure.test/assert-expr*", || {
+            clojure_test::is::AssertExprFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.test/assert-predicate*", || {
+            clojure_test::is::AssertPredicateFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.test/assert-thrown*", || {
+            clojure_test::is::AssertThrownFn {}.to_value()
+        });
+        let clojure_test_ns = Symbol::intern("clojure.test");
+        for (name, val) in [
+            (
+                "is",
+                clojure_test::is::IsMacro::new(Arc::clone(&environment)).to_rc_value(),
+            ),
+            (
+                "use-fixtures",
+                clojure_test::use_fixtures::UseFixturesFn::new(Arc::clone(&environment))
+                    .to_rc_value(),
+            ),
+            (
+                "run-tests",
+                clojure_test::run_tests::RunTestsFn::new(Arc::clone(&environment)).to_rc_value(),
+            ),
+            (
+                "run-all-tests",
+                clojure_test::run_tests::RunAllTestsFn::new(Arc::clone(&environment)).to_rc_value(),
+            ),
+        ] {
+            environment.insert_into_namespace(&clojure_test_ns, Symbol::intern(name), val);
+        }
+
+        // clojure.spec.alpha
+        environment.insert_builtin("clojure.spec.alpha/def", || {
+            clojure_spec::def::DefMacro {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/def*", || {
+            clojure_spec::def::DefFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/get-spec", || {
+            clojure_spec::def::GetSpecFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/spec", || {
+            clojure_spec::spec::SpecMacro { name: "spec" }.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/and", || {
+            clojure_spec::spec::SpecMacro { name: "and" }.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/or", || {
+            clojure_spec::spec::SpecMacro { name: "or" }.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/nilable", || {
+            clojure_spec::spec::SpecMacro { name: "nilable" }.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/keys", || {
+            clojure_spec::spec::SpecMacro { name: "keys" }.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/coll-of", || {
+            clojure_spec::spec::SpecMacro { name: "coll-of" }.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/tuple", || {
+            clojure_spec::spec::SpecMacro { name: "tuple" }.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/spec*", || {
+            clojure_spec::spec::SpecFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/form", || {
+            clojure_spec::spec::FormFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/valid?", || {
+            clojure_spec::valid::ValidFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/invalid?", || {
+            clojure_spec::valid::InvalidFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/conform", || {
+            clojure_spec::conform::ConformFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/explain", || {
+            clojure_spec::explain::ExplainFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/explain-str", || {
+            clojure_spec::explain::ExplainStrFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/explain-data", || {
+            clojure_spec::explain::ExplainDataFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.spec.alpha/fdef*", || {
+            clojure_spec::fdef::FdefFn {}.to_value()
+        });
         environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("triml"),
-            triml_fn.to_rc_value(),
+            &Symbol::intern("clojure.spec.alpha"),
+            Symbol::intern("fdef"),
+            clojure_spec::fdef::FdefMacro::new(Arc::clone(&environment)).to_rc_value(),
         );
-
+        let clojure_spec_test_ns = Symbol::intern("clojure.spec.test.alpha");
         environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("trimr"),
-            trimr_fn.to_rc_value(),
+            &clojure_spec_test_ns,
+            Symbol::intern("instrument"),
+            clojure_spec::instrument::InstrumentFn::new(Arc::clone(&environment)).to_rc_value(),
         );
-
         environment.insert_into_namespace(
-            &Symbol::intern("clojure.string"),
-            Symbol::intern("trim-newline"),
-            trim_newline_fn.to_rc_value(),
+            &clojure_spec_test_ns,
+            Symbol::intern("unstrument"),
+            clojure_spec::instrument::UnstrumentFn::new(Arc::clone(&environment)).to_rc_value(),
         );
 
-        environment.insert(Symbol::intern("+"), add_fn.to_rc_value());
-        environment.insert(Symbol::intern("let"), let_macro.to_rc_value());
-        environment.insert(Symbol::intern("str"), str_fn.to_rc_value());
-        environment.insert(Symbol::intern("map"), map_fn.to_rc_value());
-
-        environment.insert(Symbol::intern("quote"), quote_macro.to_rc_value());
-        environment.insert(Symbol::intern("do-fn*"), do_fn.to_rc_value());
-        environment.insert(Symbol::intern("do"), do_macro.to_rc_value());
-        environment.insert(Symbol::intern("def"), def_macro.to_rc_value());
-        environment.insert(Symbol::intern("fn"), fn_macro.to_rc_value());
-        environment.insert(Symbol::intern("if"), if_macro.to_rc_value());
-        environment.insert(Symbol::intern("defmacro"), defmacro_macro.to_rc_value());
-        environment.insert(Symbol::intern("ns"), ns_macro.to_rc_value());
+        // clojure.tools.cli
+        environment.insert_builtin("clojure.tools.cli/parse-opts", || {
+            clojure_tools_cli::parse_opts::ParseOptsFn {}.to_value()
+        });
+
+        // clojure.tools.logging
+        for (level, name) in clojure_tools_logging::LEVELS.iter().enumerate() {
+            environment.insert_into_namespace(
+                &Symbol::intern("clojure.tools.logging"),
+                Symbol::intern(name),
+                clojure_tools_logging::log::LogMacro::new(level).to_rc_value(),
+            );
+        }
+        environment.insert_builtin("clojure.tools.logging/log*", || {
+            clojure_tools_logging::log::LogFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.tools.logging/enabled?", || {
+            clojure_tools_logging::enabled_qmark_::EnabledQmarkFn {}.to_value()
+        });
+        // The least level lines are written at,  and where they're written;  *err*,  if nil
+        environment.insert_builtin("clojure.tools.logging/*level*", || {
+            Keyword::intern(clojure_tools_logging::level_by_default()).to_value()
+        });
+        environment.insert_builtin("clojure.tools.logging/*log-file*", || Value::Nil);
+        environment.set_dynamic("clojure.tools.logging/*level*");
+        environment.set_dynamic("clojure.tools.logging/*log-file*");
+
+        // clojure.term.colors
+        for (name, code) in clojure_term_colors::colors::CODES {
+            environment.insert_into_namespace(
+                &Symbol::intern("clojure.term.colors"),
+                Symbol::intern(name),
+                clojure_term_colors::colors::ColorFn::new(*code).to_rc_value(),
+            );
+        }
+
+        // clojure.term.progress
+        environment.insert_builtin("clojure.term.progress/progress-bar", || {
+            clojure_term_progress::progress_bar::ProgressBarFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.term.progress/with-progress", || {
+            clojure_term_progress::with_progress::WithProgressMacro {}.to_value()
+        });
+
+        // clojure.core.async
+        environment.insert_builtin("clojure.core.async/chan", || {
+            clojure_core_async::chan::ChanFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/timeout", || {
+            clojure_core_async::chan::TimeoutFn {}.to_value()
+        });
+        // Puts and takes always block;  there's no parking,  so >! and <! are the same
+        environment.insert_builtin("clojure.core.async/>!!", || {
+            clojure_core_async::put::PutFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/>!", || {
+            clojure_core_async::put::PutFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/<!!", || {
+            clojure_core_async::take::TakeFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/<!", || {
+            clojure_core_async::take::TakeFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/alts!!", || {
+            clojure_core_async::alts::AltsFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/alts!", || {
+            clojure_core_async::alts::AltsFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/close!", || {
+            clojure_core_async::close_bang_::CloseFn {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/go", || {
+            clojure_core_async::go::GoMacro {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/thread", || {
+            clojure_core_async::go::GoMacro {}.to_value()
+        });
+        environment.insert_builtin("clojure.core.async/thread-call", || {
+            clojure_core_async::go::ThreadCallFn {}.to_value()
+        });
+
+        // clojure.core.cache,  and clojure.core.cache.wrapped
+        for (ns, wrapped) in &[
+            ("clojure.core.cache", false),
+            ("clojure.core.cache.wrapped", true),
+        ] {
+            let ns = Symbol::intern(ns);
+            for (name, policy) in clojure_core_cache::factory::FACTORIES {
+                environment.insert_into_namespace(
+                    &ns,
+                    Symbol::intern(name),
+                    clojure_core_cache::factory::CacheFactoryFn::new(name, *policy, *wrapped)
+                        .to_rc_value(),
+                );
+            }
+            for (name, counts, op) in clojure_core_cache::ops::OPS {
+                environment.insert_into_namespace(
+                    &ns,
+                    Symbol::intern(name),
+                    clojure_core_cache::ops::CacheOpFn::new(counts, *op, *wrapped).to_rc_value(),
+                );
+            }
+        }
+        environment.insert_builtin("clojure.core.cache.wrapped/lookup-or-miss", || {
+            clojure_core_cache::lookup_or_miss::LookupOrMissFn {}.to_value()
+        });
+
+        environment.insert_builtin("+", || rust_core::AddFn {}.to_value());
+        environment.insert_builtin("let", || Value::LetMacro {}.to_value());
+        environment.insert_builtin("str", || rust_core::StrFn {}.to_value());
+        environment.insert_builtin("map", || rust_core::MapFn {}.to_value());
+
+        environment.insert_builtin("quote", || Value::QuoteMacro {}.to_value());
+        environment.insert_builtin("do-fn*", || rust_core::DoFn {}.to_value());
+        environment.insert_builtin("do", || rust_core::DoMacro {}.to_value());
+        environment.insert_builtin("when", || rust_core::WhenMacro {}.to_value());
+        environment.insert_builtin("loop", || Value::LoopMacro {}.to_value());
+        environment.insert_builtin("recur", || rust_core::RecurFn {}.to_value());
+        environment.insert_builtin("while", || rust_core::WhileMacro {}.to_value());
+        environment.insert_builtin("dotimes", || rust_core::DotimesMacro {}.to_value());
+        environment.insert_builtin("doseq", || rust_core::DoseqMacro {}.to_value());
+        environment.insert_builtin("for", || rust_core::ForMacro {}.to_value());
+        environment.insert_builtin("lazy-seq*", || rust_core::LazySeqFn {}.to_value());
+        environment.insert_builtin("lazy-seq", || rust_core::LazySeqMacro {}.to_value());
+        environment.insert_builtin("first", || rust_core::FirstFn {}.to_value());
+        environment.insert_builtin("rest", || rust_core::RestFn {}.to_value());
+        environment.insert_builtin("seq", || rust_core::SeqFn {}.to_value());
+        environment.insert_builtin("cons", || rust_core::ConsFn {}.to_value());
+        environment.insert_builtin("filter", || rust_core::FilterFn {}.to_value());
+        environment.insert_builtin("take", || rust_core::TakeFn {}.to_value());
+        environment.insert_builtin("doall", || rust_core::DoallFn {}.to_value());
+        environment.insert_builtin("dorun", || rust_core::DorunFn {}.to_value());
+        environment.insert_builtin("iterate", || rust_core::IterateFn {}.to_value());
+        environment.insert_builtin("repeat", || rust_core::RepeatFn {}.to_value());
+        environment.insert_builtin("repeatedly", || rust_core::RepeatedlyFn {}.to_value());
+        environment.insert_builtin("range", || rust_core::RangeFn {}.to_value());
+        environment.insert_builtin("partition", || rust_core::PartitionFn {}.to_value());
+        environment.insert_builtin("drop", || rust_core::DropFn {}.to_value());
+        environment.insert_builtin("remove", || rust_core::RemoveFn {}.to_value());
+        environment.insert_builtin("keep", || rust_core::KeepFn {}.to_value());
+        environment.insert_builtin("some", || rust_core::SomeFn {}.to_value());
+        environment.insert_builtin("run!", || rust_core::RunBangFn {}.to_value());
+        environment.insert_builtin("take-while", || rust_core::TakeWhileFn {}.to_value());
+        environment.insert_builtin("drop-while", || rust_core::DropWhileFn {}.to_value());
+        environment.insert_builtin("partition-all", || rust_core::PartitionAllFn {}.to_value());
+        environment.insert_builtin("partition-by", || rust_core::PartitionByFn {}.to_value());
+        environment.insert_builtin("split-with", || rust_core::SplitWithFn {}.to_value());
+        environment.insert_builtin("split-at", || rust_core::SplitAtFn {}.to_value());
+        environment.insert_builtin("once", || rust_core::OnceFn {}.to_value());
+        environment.insert_builtin("cat", || rust_core::cat().to_value());
+        environment.insert_builtin("mapcat", || rust_core::MapcatFn {}.to_value());
+        environment.insert_builtin("dedupe", || rust_core::DedupeFn {}.to_value());
+        environment.insert_builtin("halt-when", || rust_core::HaltWhenFn {}.to_value());
+        environment.insert_builtin("reduce", || rust_core::ReduceFn {}.to_value());
+        environment.insert_builtin("reduced", || rust_core::ReducedFn {}.to_value());
+        environment.insert_builtin("reduced?", || rust_core::ReducedQmarkFn {}.to_value());
+        environment.insert_builtin("ensure-reduced", || {
+            rust_core::EnsureReducedFn {}.to_value()
+        });
+        environment.insert_builtin("unreduced", || rust_core::UnreducedFn {}.to_value());
+        environment.insert_builtin("comp", || rust_core::CompFn {}.to_value());
+        environment.insert_builtin("completing", || rust_core::CompletingFn {}.to_value());
+        environment.insert_builtin("transduce", || rust_core::TransduceFn {}.to_value());
+        environment.insert_builtin("sequence", || rust_core::SequenceFn {}.to_value());
+        environment.insert_builtin("eduction", || rust_core::EductionFn {}.to_value());
+        environment.insert_builtin("transient", || rust_core::TransientFn {}.to_value());
+        environment.insert_builtin("conj!", || rust_core::ConjBangFn {}.to_value());
+        environment.insert_builtin("assoc!", || rust_core::AssocBangFn {}.to_value());
+        environment.insert_builtin("dissoc!", || rust_core::DissocBangFn {}.to_value());
+        environment.insert_builtin("persistent!", || rust_core::PersistentBangFn {}.to_value());
+        environment.insert_builtin("into", || rust_core::IntoFn {}.to_value());
+        environment.insert_builtin("mapv", || rust_core::MapvFn {}.to_value());
+        environment.insert_builtin("and", || rust_core::AndMacro {}.to_value());
+        environment.insert_builtin("or", || rust_core::OrMacro {}.to_value());
+        environment.insert_builtin("cond", || rust_core::CondMacro {}.to_value());
+        environment.insert_builtin("->", || rust_core::ThreadMacro { last: false }.to_value());
+        environment.insert_builtin("->>", || rust_core::ThreadMacro { last: true }.to_value());
+        environment.insert_builtin("as->", || rust_core::AsThreadMacro {}.to_value());
+        environment.insert_builtin("some->", || {
+            rust_core::SomeThreadMacro { last: false }.to_value()
+        });
+        environment.insert_builtin("some->>", || {
+            rust_core::SomeThreadMacro { last: true }.to_value()
+        });
+        environment.insert_builtin("cond->", || {
+            rust_core::CondThreadMacro { last: false }.to_value()
+        });
+        environment.insert_builtin("cond->>", || {
+            rust_core::CondThreadMacro { last: true }.to_value()
+        });
+        environment.insert_builtin("assert", || rust_core::AssertMacro {}.to_value());
+        environment.insert_builtin("assert-failed*", || rust_core::AssertFailedFn {}.to_value());
+        environment.insert_builtin("doto", || rust_core::DotoMacro {}.to_value());
+        environment.insert_builtin(".", || rust_core::DotMacro {}.to_value());
+        environment.insert_builtin("..", || rust_core::DotDotMacro {}.to_value());
+        environment.insert_builtin("if-let", || {
+            rust_core::IfLetMacro { some: false }.to_value()
+        });
+        environment.insert_builtin("if-some", || {
+            rust_core::IfLetMacro { some: true }.to_value()
+        });
+        environment.insert_builtin("when-let", || {
+            rust_core::WhenLetMacro { some: false }.to_value()
+        });
+        environment.insert_builtin("when-some", || {
+            rust_core::WhenLetMacro { some: true }.to_value()
+        });
+        environment.insert_builtin("boolean", || rust_core::BooleanFn {}.to_value());
+        environment.insert_builtin("not", || rust_core::NotFn {}.to_value());
+        environment.insert_builtin("true?", || rust_core::TrueFn {}.to_value());
+        environment.insert_builtin("false?", || rust_core::FalseFn {}.to_value());
+        environment.insert_builtin("string?", || rust_core::StringQmarkFn {}.to_value());
+        environment.insert_builtin("bytes?", || rust_core::BytesQmarkFn {}.to_value());
+        for (name, test) in rust_core::PREDICATES {
+            environment.insert_into_namespace(
+                &Symbol::intern("clojure.core"),
+                Symbol::intern(name),
+                rust_core::PredicateFn::new(*test).to_rc_value(),
+            );
+        }
+        environment.insert_builtin("var?", || rust_core::VarQmarkFn {}.to_value());
+        environment.insert_builtin("def", || Value::DefMacro {}.to_value());
+        environment.insert_builtin("fn", || Value::FnMacro {}.to_value());
+        environment.insert_builtin("if", || Value::IfMacro {}.to_value());
+        environment.insert_builtin("try", || Value::TryMacro {}.to_value());
+        environment.insert_builtin("var", || Value::VarMacro {}.to_value());
+        environment.insert_builtin("defmacro", || Value::DefmacroMacro {}.to_value());
+        environment.insert_builtin("ns", || rust_core::NsMacro {}.to_value());
+        environment.insert(Symbol::intern("in-ns"), in_ns_fn.to_rc_value());
         environment.insert(Symbol::intern("eval"), eval_fn.to_rc_value());
-        environment.insert(
-            Symbol::intern("lexical-eval"),
-            lexical_eval_fn.to_rc_value(),
-        );
+        environment.insert_builtin("lexical-eval", || Value::LexicalEvalFn {}.to_value());
         environment.insert(Symbol::intern("load-file"), load_file_fn.to_rc_value());
-        environment.insert(Symbol::intern("nth"), nth_fn.to_rc_value());
-        environment.insert(Symbol::intern("assoc"), assoc_fn.to_rc_value());
-        environment.insert(Symbol::intern("get"), get_fn.to_rc_value());
-        environment.insert(Symbol::intern("concat"), concat_fn.to_rc_value());
+        environment.insert(Symbol::intern("load"), load_fn.to_rc_value());
+        environment.insert_builtin("*load-path*", rust_core::load_path);
+        environment.set_dynamic("*load-path*");
+        environment.insert(Symbol::intern("require"), require_fn.to_rc_value());
+        environment.insert(Symbol::intern("use"), use_fn.to_rc_value());
+        environment.insert(Symbol::intern("refer"), refer_fn.to_rc_value());
+        environment.insert_builtin("refer-clojure", || {
+            rust_core::ReferClojureMacro {}.to_value()
+        });
+        environment.insert(Symbol::intern("alias"), alias_fn.to_rc_value());
+        environment.insert_builtin("nth", || rust_core::NthFn {}.to_value());
+        environment.insert_builtin("assoc", || rust_core::AssocFn {}.to_value());
+        environment.insert_builtin("get", || rust_core::GetFn {}.to_value());
+        environment.insert_builtin("key", || rust_core::KeyFn {}.to_value());
+        environment.insert_builtin("val", || rust_core::ValFn {}.to_value());
+        environment.insert_builtin("concat", || rust_core::ConcatFn {}.to_value());
+        environment.insert_builtin("subvec", || rust_core::SubvecFn {}.to_value());
+        environment.insert_builtin("peek", || rust_core::PeekFn {}.to_value());
+        environment.insert_builtin("pop", || rust_core::PopFn {}.to_value());
+        environment.insert_builtin("rseq", || rust_core::RseqFn {}.to_value());
+        environment.insert_builtin("clojure.lang.PersistentQueue/EMPTY", || {
+            PersistentQueue::empty().to_value()
+        });
+        environment.insert_builtin("count", || rust_core::CountFn {}.to_value());
+        environment.insert_builtin("reverse", || rust_core::ReverseFn {}.to_value());
 
         // input and output
-        environment.insert(
-            Symbol::intern("system-newline"),
-            system_newline_fn.to_rc_value(),
-        );
-        environment.insert(
-            Symbol::intern("flush-stdout"),
-            flush_stdout_fn.to_rc_value(),
-        );
-        environment.insert(
-            Symbol::intern("print-string"),
-            print_string_fn.to_rc_value(),
-        );
-        environment.insert(Symbol::intern("read-line"), read_line_fn.to_rc_value());
+        environment.insert_builtin("system-newline", || {
+            rust_core::SystemNewlineFn {}.to_value()
+        });
+        environment.insert_builtin("flush-stdout", || rust_core::FlushStdoutFn {}.to_value());
+        environment.insert_builtin("print-string", || rust_core::PrintStringFn {}.to_value());
+        environment.insert_builtin("read-line", || rust_core::ReadLineFn {}.to_value());
+        environment.insert_builtin("with-out-str*", || rust_core::WithOutStrFn {}.to_value());
+        environment.insert_builtin("with-in-str*", || rust_core::WithInStrFn {}.to_value());
+        // What's printed,  and read,  unless they're bound otherwise
+        environment.insert_builtin("*in*", || {
+            Value::host_object(clojure_java_io::Reader::stdin())
+        });
+        environment.insert_builtin("*out*", || {
+            Value::host_object(clojure_java_io::Writer::on_stream(Stream::Out))
+        });
+        environment.insert_builtin("*err*", || {
+            Value::host_object(clojure_java_io::Writer::on_stream(Stream::Err))
+        });
+        for stream in ["*in*", "*out*", "*err*"] {
+            environment.set_dynamic(stream);
+        }
+        // How much pprint prints;  all of it,  unless they're bound
+        environment.insert_builtin("*print-length*", || Value::Nil);
+        environment.insert_builtin("*print-level*", || Value::Nil);
+        environment.set_dynamic("*print-length*");
+        environment.set_dynamic("*print-level*");
+        // Whether sets and maps are printed sorted;  see print_order
+        environment.insert_builtin("*print-sorted*", || {
+            Value::Boolean(print_order::sorted_by_default())
+        });
+        environment.set_dynamic("*print-sorted*");
+        // The last three values the REPL printed
+        environment.insert_builtin("*1", || Value::Nil);
+        environment.insert_builtin("*2", || Value::Nil);
+        environment.insert_builtin("*3", || Value::Nil);
+
+        // process
+        // The arguments after the script,  -e expression or -m namespace;  nil if there are none
+        environment.insert_builtin("*command-line-args*", || Value::Nil);
+        environment.set_dynamic("*command-line-args*");
+        environment.insert_builtin("exit", || rust_core::ExitFn {}.to_value());
+        environment.insert_builtin("add-shutdown-hook", || {
+            rust_core::AddShutdownHookFn {}.to_value()
+        });
+        environment.insert_builtin("on-signal", || rust_core::OnSignalFn {}.to_value());
+        environment.insert_builtin("schedule!", || rust_core::ScheduleBangFn {}.to_value());
+        environment.insert_builtin("every!", || rust_core::EveryBangFn {}.to_value());
+        environment.insert_builtin("cancel!", || rust_core::CancelBangFn {}.to_value());
+        environment.insert_builtin("sleep", || rust_core::SleepFn {}.to_value());
+
+        // the interpreter itself;  for code to check what it's running on
+        environment.insert_builtin("*clojure-version*", clojure_version);
+        environment.insert_builtin("*features*", reader::features);
 
         //
         // Read in clojure.core
         //
         // @TODO its time for a RT (runtime), which environment seems to be becoming
-        let _ = Repl::new(Rc::clone(&environment)).try_eval_file("./src/clojure/core.clj");
-
-        // We can add this back once we have requires
-        // environment.change_namespace(Symbol::intern("user"));
+        //
+        Repl::new(Arc::clone(&environment)).eval_text(CORE_FILE, CORE_SOURCE);
+        if let Err(e) = data_readers::load(&environment, data_readers::DATA_READERS_FILE) {
+            eprintln!("{}", e);
+        }
 
-        environment
+        environment.change_namespace(Symbol::intern("user"));
+        // As at Clojure's REPL,  doc and source are at hand in user
+        let user = environment.find_or_create_namespace(&Symbol::intern("user"));
+        for name in ["doc", "source"] {
+            user.refer(
+                &Symbol::intern(name),
+                Symbol::intern_with_ns("clojure.repl", name),
+            );
+        }
     }
 }
 
+// *clojure-version*;  ClojureRS's own,  as {:major 0 :minor 1 :incremental 0 :qualifier nil}
+fn clojure_version() -> Value {
+    let number = |part: &str| part.parse().map_or(Value::Nil, Value::I32);
+    let qualifier = match env!("CARGO_PKG_VERSION_PRE") {
+        "" => Value::Nil,
+        pre => Value::String(pre.into()),
+    };
+    vec![
+        ("major", number(env!("CARGO_PKG_VERSION_MAJOR"))),
+        ("minor", number(env!("CARGO_PKG_VERSION_MINOR"))),
+        ("incremental", number(env!("CARGO_PKG_VERSION_PATCH"))),
+        ("qualifier", qualifier),
+    ]
+    .into_iter()
+    .map(|(key, val)| MapEntry {
+        key: Keyword::intern(key).to_rc_value(),
+        val: val.to_rc_value(),
+    })
+    .collect::<PersistentListMap>()
+    .to_value()
+}
+
 #[cfg(test)]
 mod tests {
     mod environment_val {
         use crate::environment::EnvironmentVal;
         use crate::symbol::Symbol;
         use crate::value::Value;
-        use std::rc::Rc;
+        use std::sync::Arc;
 
         ////////////////////////////////////////////////////////////////////////////////
         //
@@ -433,7 +1458,7 @@ mod tests {
 
         /////////////////////////////////////////////////////////////////////////////
         //
-        //  fn get_from_namespace(&self,namespace: &Symbol,sym: &Symbol) -> Rc<Value>
+        //  fn get_from_namespace(&self,namespace: &Symbol,sym: &Symbol) -> Arc<Value>
         //
         //////////////////////////////////////////////////////////////////////////////
 
@@ -444,31 +1469,31 @@ mod tests {
             env_val.insert_into_namespace(
                 &Symbol::intern("core"),
                 Symbol::intern("+"),
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
             );
             env_val.insert_into_namespace(
                 &Symbol::intern_with_ns("dragon", "core"),
                 Symbol::intern("+2"),
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
             );
             env_val.insert_into_namespace(
                 &Symbol::intern_with_ns("dragon", "core"),
                 Symbol::intern_with_ns("override", "+3"),
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
             );
 
             assert_eq!(
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
                 env_val.get_from_namespace(&Symbol::intern("core"), &Symbol::intern("+"))
             );
 
             assert_eq!(
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
                 env_val.get_from_namespace(&Symbol::intern("core"), &Symbol::intern("+2"))
             );
 
             assert_eq!(
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
                 env_val.get_from_namespace(&Symbol::intern("override"), &Symbol::intern("+3"))
             );
         }
@@ -481,17 +1506,17 @@ mod tests {
         use crate::rust_core;
         use crate::symbol::Symbol;
         use crate::value::{ToValue, Value};
-        use std::rc::Rc;
+        use std::sync::Arc;
         ////////////////////////////////////////////////////////////////////////
         //
-        // pub fn get(&self, sym: &Symbol) -> Rc<Value> {
+        // pub fn get(&self, sym: &Symbol) -> Arc<Value> {
         //
         ////////////////////////////////////////////////////////////////////////
         #[test]
         fn get_plus() {
             let add_fn = rust_core::AddFn {};
 
-            let environment = Rc::new(Environment::new_main_environment());
+            let environment = Arc::new(Environment::new_main_environment());
             environment.insert(Symbol::intern("+"), add_fn.to_rc_value());
 
             let plus = environment.get(&Symbol::intern("+"));
@@ -512,22 +1537,21 @@ mod tests {
         }
         /////////////////////////////////////////////////////////////////////////
         //
-        // pub fn insert(&self, sym: Symbol, val: Rc<Value>) {
+        // pub fn insert(&self, sym: Symbol, val: Arc<Value>) {
         //
         /////////////////////////////////////////////////////////////////////////
         #[test]
         fn insert_plus() {
             let add_fn = rust_core::AddFn {};
 
-            let environment = Rc::new(Environment::new_main_environment());
+            let environment = Arc::new(Environment::new_main_environment());
             environment.insert(Symbol::intern("+"), add_fn.to_rc_value());
 
-            let plus: Rc<Value> = match &*environment {
+            let plus: Arc<Value> = match &*environment {
                 MainEnvironment(EnvironmentVal {
                     curr_ns_sym: _,
                     namespaces,
-                }) => namespaces
-                    .get(&Symbol::intern("user"),&Symbol::intern("+")),
+                }) => namespaces.get(&Symbol::intern("user"), &Symbol::intern("+")),
                 _ => panic!("new_main_environment() should return Main"),
             };
 
@@ -545,5 +1569,46 @@ mod tests {
             }
             panic!("plus should be IFn, is: {:#?}", plus);
         }
+
+        #[test]
+        fn fns_call_what_their_names_are_defined_as_now() {
+            let result = crate::repl::Repl::default().eval_all(&[
+                "(defn helper [] 1)",
+                "(defn caller [] (helper))",
+                "(def callers {:f (fn [] (helper))})",
+                "(defn helper [] 2)",
+                "(ns other)",
+                "[(user/caller) ((clojure.core/get user/callers :f))]",
+            ]);
+            assert_eq!("[2 2]", result.to_string());
+        }
+
+        #[test]
+        fn aliases_resolve_in_the_namespace_a_fn_was_defined_in() {
+            let result = crate::repl::Repl::default().eval_all(&[
+                "(ns lib.x)",
+                "(defn f [] :f)",
+                "(ns lib.a (:require [lib.x :as u]))",
+                "(defn r [] (u/f))",
+                "(defn r-in-let [] (let [y 1] [y (u/f)]))",
+                "(ns other)",
+                "[(lib.a/r) (lib.a/r-in-let) ((fn [] (lib.a/r)))]",
+            ]);
+            assert_eq!("[:f [1 :f] :f]", result.to_string());
+        }
+
+        #[test]
+        fn code_can_check_what_its_running_on() {
+            let result = crate::repl::Repl::default().eval_all(&[
+                "[(:major *clojure-version*) (:minor *clojure-version*) (clojure-version) (contains? *features* :cljrs)]",
+            ]);
+            let version = format!(
+                "[{} {} \"{}\" true]",
+                env!("CARGO_PKG_VERSION_MAJOR"),
+                env!("CARGO_PKG_VERSION_MINOR"),
+                env!("CARGO_PKG_VERSION")
+            );
+            assert_eq!(version, result.to_string());
+        }
     }
 }
@@ -3,44 +3,68 @@ use crate::value::Value;
 use std::error::Error;
 
 pub fn type_mismatch(expected: TypeTag, got: &Value) -> Value {
-    Value::Condition(format!(
-        "Type mismatch; Expected instance of {},  Recieved type {}",
-        expected, got
-    ))
+    Value::Condition(
+        format!(
+            "Type mismatch; Expected instance of {},  Recieved type {}",
+            expected, got
+        )
+        .into(),
+    )
+}
+
+/// What seq, and everything built on it, says of got when it's not a coll,  string or nil
+pub fn not_seqable(got: &Value) -> Value {
+    Value::Condition(format!("Don't know how to create ISeq from: {}", got.type_tag()).into())
 }
 
 pub fn wrong_arg_count(expected: usize, got: usize) -> Value {
-    Value::Condition(format!(
-        "Wrong number of arguments given to function (Given: {}, Expected: {})",
-        got, expected
-    ))
+    Value::Condition(
+        format!(
+            "Wrong number of arguments given to function (Given: {}, Expected: {})",
+            got, expected
+        )
+        .into(),
+    )
 }
 
 pub fn wrong_varg_count(expected: &[usize], got: usize) -> Value {
-    Value::Condition(format!(
-        "Wrong number of arguments given to function (Given: {}, Expected: {:?})",
-        got, expected
-    ))
+    Value::Condition(
+        format!(
+            "Wrong number of arguments given to function (Given: {}, Expected: {:?})",
+            got, expected
+        )
+        .into(),
+    )
 }
 
 pub fn zero_arg_count(got: usize) -> Value {
-    Value::Condition(format!(
-        "Wrong number of arguments given to function (Given: {})",
-        got
-    ))
+    Value::Condition(
+        format!(
+            "Wrong number of arguments given to function (Given: {})",
+            got
+        )
+        .into(),
+    )
+}
+
+pub fn mismatched_recur_count(expected: usize, got: usize) -> Value {
+    Value::Condition(
+        format!(
+            "Mismatched argument count to recur (Given: {}, Expected: {})",
+            got, expected
+        )
+        .into(),
+    )
 }
 
 pub fn index_out_of_bounds(ind: usize, count: usize) -> Value {
-    Value::Condition(format!(
-        "Index out of bounds: Index ({}), Length: ({})",
-        ind, count
-    ))
+    Value::Condition(format!("Index out of bounds: Index ({}), Length: ({})", ind, count).into())
 }
 
 pub fn index_cannot_be_negative(ind: usize) -> Value {
-    Value::Condition(format!("Index cannot be negative; Index ({})", ind))
+    Value::Condition(format!("Index cannot be negative; Index ({})", ind).into())
 }
 
 pub fn generic_err(error: Box<dyn Error>) -> Value {
-    Value::Condition(error.to_string())
+    Value::Condition(error.to_string().into())
 }
@@ -0,0 +1,102 @@
+//! `rust_clojure fmt`;  re-indents Clojure code
+//!
+//! Only the whitespace at the start and end of each line is touched;  everything else,
+//! comments included,  is kept as written.  A line is indented to line up one past the
+//! bracket it's in,  or two past it if that's a paren;  so
+//!
+//! (defn f [a
+//!          b]
+//!   (+ a b))
+//!
+//! Lines inside a multi-line string are part of the string,  and left alone
+use std::iter;
+
+pub fn format(source: &str) -> String {
+    let mut formatted = String::new();
+    // The column of each bracket still open,  and which bracket it is
+    let mut open: Vec<(usize, char)> = vec![];
+    let mut in_string = false;
+    for line in source.lines() {
+        let starts_in_string = in_string;
+        let (indent, content) = if starts_in_string {
+            (0, line)
+        } else {
+            let indent = match open.last() {
+                Some((col, '(')) => col + 2,
+                Some((col, _)) => col + 1,
+                None => 0,
+            };
+            (indent, line.trim_start())
+        };
+
+        let mut chars = content.chars().enumerate().map(|(i, ch)| (indent + i, ch));
+        while let Some((col, ch)) = chars.next() {
+            if in_string {
+                match ch {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match ch {
+                '"' => in_string = true,
+                // A character literal, like \( ;  whatever follows isn't code
+                '\\' => {
+                    chars.next();
+                }
+                ';' => break,
+                '(' | '[' | '{' => open.push((col, ch)),
+                ')' | ']' | '}' => {
+                    open.pop();
+                }
+                _ => {}
+            }
+        }
+
+        // Trailing whitespace in a string that carries on to the next line is part of it
+        let content = if in_string {
+            content
+        } else {
+            content.trim_end()
+        };
+        if !content.is_empty() {
+            formatted.extend(iter::repeat_n(' ', indent));
+            formatted.push_str(content);
+        }
+        formatted.push('\n');
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::formatter::format;
+
+    #[test]
+    fn lines_up_with_the_enclosing_bracket() {
+        let source = "(defn f [a\nb]\n    (let [x 1\n  y 2]\n(+ a b x y)))   \n";
+        assert_eq!(
+            "(defn f [a\n         b]\n  (let [x 1\n        y 2]\n    (+ a b x y)))\n",
+            format(source)
+        );
+    }
+
+    #[test]
+    fn leaves_strings_comments_and_characters_alone() {
+        let source = "(def s \"a (\n   b  \")\n(def c \\() ; (\n  (def d 1)\n";
+        assert_eq!(
+            "(def s \"a (\n   b  \")\n(def c \\() ; (\n(def d 1)\n",
+            format(source)
+        );
+    }
+
+    #[test]
+    fn formatting_twice_changes_nothing() {
+        let source = "(ns a)\n\n(defn g [x]\n  {:a x\n   :b [1\n       2]})\n";
+        assert_eq!(source, format(source));
+        assert_eq!(format(source), format(&format(source)));
+    }
+}
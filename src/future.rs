@@ -0,0 +1,134 @@
+//! (future body) and (future-call f)
+//!
+//! A future runs its body on a thread of its own;  derefing it waits for the body to finish.
+//! The body sees what the thread that made it had in place when it did:  its dynamic
+//! bindings,  where its output goes,  and the like (see Conveyed).  A set! there doesn't
+//! change them for the code that made it
+use crate::data_readers;
+use crate::dynamic::{self, Frame};
+use crate::environment::{self, Environment};
+use crate::hierarchy::{self, Hierarchy};
+use crate::ideref::IBlockingDeref;
+use crate::numbers::{self, MathContext};
+use crate::output;
+use crate::promise::Promise;
+use crate::reader;
+use crate::value::Value;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// As much as the main thread gets;  evaluating takes a lot of stack
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug)]
+pub struct Future {
+    result: Promise,
+}
+impl Future {
+    /// Starts body running;  a condition if there's no thread to run it on
+    pub fn run(body: impl FnOnce() -> Value + Send + 'static) -> Result<Arc<Future>, Value> {
+        let future = Arc::new(Future {
+            result: Promise::new(),
+        });
+        let conveyed = Conveyed::capture();
+        let finished = Arc::clone(&future);
+        thread::Builder::new()
+            .name(String::from("future"))
+            .stack_size(STACK_SIZE)
+            .spawn(move || {
+                let result = conveyed.run(body);
+                finished.result.deliver(Arc::new(result));
+            })
+            .map_err(|e| Value::Condition(format!("Could not start future: {}", e).into()))?;
+        Ok(future)
+    }
+}
+impl IBlockingDeref for Future {
+    fn deref_blocking(&self, timeout: Option<Duration>) -> Option<Arc<Value>> {
+        self.result.deref_blocking(timeout)
+    }
+    fn is_realized(&self) -> bool {
+        self.result.is_realized()
+    }
+}
+impl fmt::Display for Future {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.result.deref_blocking(Some(Duration::from_secs(0))) {
+            Some(val) => write!(
+                f,
+                "#future[{{:status :ready, :val {}}}]",
+                val.to_string_explicit()
+            ),
+            None => write!(f, "#future[{{:status :pending, :val nil}}]"),
+        }
+    }
+}
+
+/// What a future's body,  or a pool worker's job,  sees of the thread that made it
+#[derive(Clone)]
+pub(crate) struct Conveyed {
+    bindings: Frame,
+    sink: Option<output::Sink>,
+    math_context: Option<MathContext>,
+    hierarchy: Arc<Mutex<Hierarchy>>,
+    data_readers: Option<Arc<Environment>>,
+    namespace: String,
+    fork: environment::Forked,
+}
+impl Conveyed {
+    pub(crate) fn capture() -> Conveyed {
+        Conveyed {
+            bindings: dynamic::conveyed(),
+            sink: output::installed(),
+            math_context: numbers::math_context(),
+            hierarchy: hierarchy::shared_global(),
+            data_readers: data_readers::reading_with(),
+            namespace: reader::current_namespace(),
+            fork: environment::forked(),
+        }
+    }
+    /// Runs body with all of it in place,  whatever was before;  on a thread of the future's
+    /// or the pool's own,  so there's nothing to restore after
+    pub(crate) fn run(self, body: impl FnOnce() -> Value) -> Value {
+        output::install(self.sink);
+        hierarchy::share_global(self.hierarchy);
+        match &self.data_readers {
+            Some(environment) => data_readers::read_with(environment),
+            None => data_readers::read_with_none(),
+        }
+        reader::set_current_namespace(&self.namespace);
+        environment::carry_on_in(self.fork);
+        let bindings = self.bindings;
+        let body = || dynamic::with_conveyed(bindings, body);
+        match self.math_context {
+            Some(math_context) => numbers::with_math_context(math_context, body),
+            None => body(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output;
+    use crate::repl::Repl;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn a_future_prints_where_its_maker_does_and_shares_its_hierarchy() {
+        let captured = Arc::new(Mutex::new(String::new()));
+        let sink_captured = Arc::clone(&captured);
+        let previous = output::install(Some(Arc::new(move |_, text: &str| {
+            sink_captured.lock().unwrap().push_str(text)
+        })));
+        let result = Repl::default().eval_all(&[
+            "(def f (future (println \"from the future\") (derive :shapes/square :shapes/shape)))",
+            "@f",
+            "(isa? :shapes/square :shapes/shape)",
+        ]);
+        output::install(previous);
+        assert_eq!("true", result.to_string());
+        assert_eq!("from the future\n", *captured.lock().unwrap());
+    }
+}
@@ -0,0 +1,436 @@
+//! A hash array mapped trie;  what sets,  and maps past 8 entries,  keep their members in
+//!
+//! Each level of the trie takes the next 5 bits of a key's hash to pick which of its 32
+//! children the key is under,  only the children there are being stored.  So finding,
+//! adding or removing a key looks at a handful of nodes however big the trie gets,  and a
+//! trie changed shares every node with the one it was changed from,  but those on the path
+//! to what changed.  Keys with the very same hash share a collision node,  searched through
+//!
+//! Keys are hashed with SipHash keyed with a seed picked once per run,  so keys read off the
+//! network can't be chosen to all collide and make each lookup a scan.  So the order a trie
+//! iterates in changes from run to run
+//!
+//! A node no other trie has hold of is changed in place;  so building a trie up with insert,
+//! as from_iter does,  copies nothing,  as with a transient
+use crate::value::Value;
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::hash::BuildHasher;
+use std::iter::FromIterator;
+use std::sync::{Arc, OnceLock};
+
+/// Makes the hashers keys are hashed with;  all keyed with this run's seed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SeededState;
+impl BuildHasher for SeededState {
+    type Hasher = DefaultHasher;
+    fn build_hasher(&self) -> DefaultHasher {
+        static SEED: OnceLock<RandomState> = OnceLock::new();
+        SEED.get_or_init(RandomState::new).build_hasher()
+    }
+}
+
+const BITS: u32 = 5;
+// A branch at each level a hash has bits for,  and a collision node below the last
+const MAX_NESTING: usize = (u64::BITS / BITS) as usize + 2;
+
+#[derive(Debug, Clone)]
+enum Child<V> {
+    Leaf(u64, Arc<Value>, V),
+    Node(Arc<Node<V>>),
+}
+
+#[derive(Debug, Clone)]
+enum Node<V> {
+    /// Which of the 32 slots are taken,  as bits;  and what's in them,  in slot order
+    Branch(u32, Vec<Child<V>>),
+    /// The leaves of keys with the one hash
+    Collision(u64, Vec<Child<V>>),
+}
+impl<V> Node<V> {
+    fn children(&self) -> &[Child<V>] {
+        match self {
+            Node::Branch(_, children) | Node::Collision(_, children) => children,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Hamt<V> {
+    root: Arc<Node<V>>,
+    len: usize,
+}
+impl<V> Default for Hamt<V> {
+    fn default() -> Hamt<V> {
+        Hamt {
+            root: Arc::new(Node::Branch(0, vec![])),
+            len: 0,
+        }
+    }
+}
+impl<V: Clone> Hamt<V> {
+    pub fn new() -> Hamt<V> {
+        Hamt::default()
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// The key in the trie equal to key,  and its val
+    pub fn get_entry(&self, key: &Value) -> Option<(&Arc<Value>, &V)> {
+        let hash = hash(key);
+        let mut node = &*self.root;
+        let mut depth = 0;
+        loop {
+            let child = match node {
+                Node::Branch(bitmap, children) => {
+                    let bit = bit(hash, depth);
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+                    &children[index(*bitmap, bit)]
+                }
+                Node::Collision(_, leaves) => {
+                    return leaves.iter().find_map(|leaf| match leaf {
+                        Child::Leaf(_, leaf_key, val) if **leaf_key == *key => {
+                            Some((leaf_key, val))
+                        }
+                        _ => None,
+                    });
+                }
+            };
+            match child {
+                Child::Leaf(leaf_hash, leaf_key, val) => {
+                    return match *leaf_hash == hash && **leaf_key == *key {
+                        true => Some((leaf_key, val)),
+                        false => None,
+                    };
+                }
+                Child::Node(child) => {
+                    node = child;
+                    depth += 1;
+                }
+            }
+        }
+    }
+    pub fn get(&self, key: &Value) -> Option<&V> {
+        self.get_entry(key).map(|(_, val)| val)
+    }
+    pub fn contains_key(&self, key: &Value) -> bool {
+        self.get_entry(key).is_some()
+    }
+    /// Has key map to val;  the key already in the trie,  if there's one equal to it,  stays.
+    /// Whether key is new
+    pub fn insert(&mut self, key: Arc<Value>, val: V) -> bool {
+        let hash = hash(&key);
+        let added = insert(&mut self.root, 0, hash, key, val);
+        if added {
+            self.len += 1;
+        }
+        added
+    }
+    /// Whether key was there to remove
+    pub fn remove(&mut self, key: &Value) -> bool {
+        // So a trie that hasn't got key isn't copied
+        if !self.contains_key(key) {
+            return false;
+        }
+        remove(&mut self.root, 0, hash(key), key);
+        self.len -= 1;
+        true
+    }
+    pub fn iter(&self) -> Iter<'_, V> {
+        let mut nodes = [None; MAX_NESTING];
+        nodes[0] = Some(&*self.root);
+        Iter {
+            nodes,
+            positions: [0; MAX_NESTING],
+            depth: 0,
+            left: self.len,
+        }
+    }
+}
+impl<V: Clone> FromIterator<(Arc<Value>, V)> for Hamt<V> {
+    fn from_iter<I: IntoIterator<Item = (Arc<Value>, V)>>(iter: I) -> Self {
+        let mut hamt = Hamt::new();
+        for (key, val) in iter {
+            hamt.insert(key, val);
+        }
+        hamt
+    }
+}
+
+fn hash(key: &Value) -> u64 {
+    SeededState.hash_one(key)
+}
+
+// The slot hash is in at depth,  as a bit of a branch's bitmap
+fn bit(hash: u64, depth: u32) -> u32 {
+    1 << ((hash >> (depth * BITS)) & 31)
+}
+
+// Where in a branch's children the one in bit's slot is;  after one for each slot before it
+fn index(bitmap: u32, bit: u32) -> usize {
+    (bitmap & (bit - 1)).count_ones() as usize
+}
+
+fn insert<V: Clone>(
+    node: &mut Arc<Node<V>>,
+    depth: u32,
+    hash: u64,
+    key: Arc<Value>,
+    val: V,
+) -> bool {
+    // A key with another hash goes beside the collision node,  in a branch of their own
+    if let Node::Collision(collision_hash, _) = &**node {
+        if *collision_hash != hash {
+            let collision = (*collision_hash, Child::Node(Arc::clone(node)));
+            *node = Arc::new(branch(
+                depth,
+                collision,
+                (hash, Child::Leaf(hash, key, val)),
+            ));
+            return true;
+        }
+    }
+    match Arc::make_mut(node) {
+        Node::Branch(bitmap, children) => {
+            let bit = bit(hash, depth);
+            let at = index(*bitmap, bit);
+            if *bitmap & bit == 0 {
+                *bitmap |= bit;
+                children.insert(at, Child::Leaf(hash, key, val));
+                return true;
+            }
+            match &mut children[at] {
+                Child::Node(child) => insert(child, depth + 1, hash, key, val),
+                Child::Leaf(leaf_hash, leaf_key, leaf_val) => {
+                    if *leaf_hash == hash && **leaf_key == *key {
+                        *leaf_val = val;
+                        return false;
+                    }
+                    // The two go in a node of their own,  in the leaf's place
+                    let leaf = Child::Leaf(*leaf_hash, Arc::clone(leaf_key), leaf_val.clone());
+                    let both = branch(
+                        depth + 1,
+                        (*leaf_hash, leaf),
+                        (hash, Child::Leaf(hash, key, val)),
+                    );
+                    children[at] = Child::Node(Arc::new(both));
+                    true
+                }
+            }
+        }
+        Node::Collision(_, leaves) => {
+            for leaf in leaves.iter_mut() {
+                if let Child::Leaf(_, leaf_key, leaf_val) = leaf {
+                    if **leaf_key == *key {
+                        *leaf_val = val;
+                        return false;
+                    }
+                }
+            }
+            leaves.push(Child::Leaf(hash, key, val));
+            true
+        }
+    }
+}
+
+// A node at depth holding both of a and b,  given with their hashes;  a collision node,  if
+// those are the same
+fn branch<V>(depth: u32, a: (u64, Child<V>), b: (u64, Child<V>)) -> Node<V> {
+    let ((a_hash, a), (b_hash, b)) = (a, b);
+    if a_hash == b_hash {
+        return Node::Collision(a_hash, vec![a, b]);
+    }
+    let (a_bit, b_bit) = (bit(a_hash, depth), bit(b_hash, depth));
+    match a_bit.cmp(&b_bit) {
+        std::cmp::Ordering::Less => Node::Branch(a_bit | b_bit, vec![a, b]),
+        std::cmp::Ordering::Greater => Node::Branch(a_bit | b_bit, vec![b, a]),
+        std::cmp::Ordering::Equal => Node::Branch(
+            a_bit,
+            vec![Child::Node(Arc::new(branch(
+                depth + 1,
+                (a_hash, a),
+                (b_hash, b),
+            )))],
+        ),
+    }
+}
+
+// Removes key,  which is under node
+fn remove<V: Clone>(node: &mut Arc<Node<V>>, depth: u32, hash: u64, key: &Value) {
+    match Arc::make_mut(node) {
+        Node::Branch(bitmap, children) => {
+            let bit = bit(hash, depth);
+            let at = index(*bitmap, bit);
+            let lone_leaf = match &mut children[at] {
+                Child::Leaf(..) => {
+                    *bitmap &= !bit;
+                    children.remove(at);
+                    return;
+                }
+                Child::Node(child) => {
+                    remove(child, depth + 1, hash, key);
+                    match child.children() {
+                        [leaf @ Child::Leaf(..)] => Some(leaf.clone()),
+                        _ => None,
+                    }
+                }
+            };
+            // A node down to one leaf is only that leaf,  in its place
+            if let Some(leaf) = lone_leaf {
+                children[at] = leaf;
+            }
+        }
+        Node::Collision(_, leaves) => {
+            leaves.retain(|leaf| !matches!(leaf, Child::Leaf(_, leaf_key, _) if **leaf_key == *key))
+        }
+    }
+}
+
+/// The keys of a trie,  and their vals;  found without allocating,  as each level's
+/// children are gone through in turn
+pub struct Iter<'a, V> {
+    // Each node on the way down to the one we're in,  and how far through its children
+    // we've gone;  kept as references and counts,  so the iterator stays small
+    nodes: [Option<&'a Node<V>>; MAX_NESTING],
+    positions: [u32; MAX_NESTING],
+    depth: usize,
+    left: usize,
+}
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (&'a Arc<Value>, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let children = self.nodes[self.depth]?.children();
+            let position = self.positions[self.depth] as usize;
+            match children.get(position) {
+                Some(child) => {
+                    self.positions[self.depth] += 1;
+                    match child {
+                        Child::Leaf(_, key, val) => {
+                            self.left -= 1;
+                            return Some((key, val));
+                        }
+                        Child::Node(node) => {
+                            self.depth += 1;
+                            self.nodes[self.depth] = Some(node);
+                            self.positions[self.depth] = 0;
+                        }
+                    }
+                }
+                None if self.depth == 0 => return None,
+                None => self.depth -= 1,
+            }
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+impl<V> ExactSizeIterator for Iter<'_, V> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::hamt::{insert, remove, Child, Hamt, Node, SeededState};
+    use crate::value::{ToValue, Value};
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{BuildHasher, BuildHasherDefault};
+    use std::sync::Arc;
+
+    fn nodes<V>(node: &Arc<Node<V>>, found: &mut HashSet<*const Node<V>>) {
+        found.insert(Arc::as_ptr(node));
+        for child in node.children() {
+            if let Child::Node(child) = child {
+                nodes(child, found);
+            }
+        }
+    }
+
+    // How many of node's nodes aren't among old_nodes;  what changing it copied
+    fn copied<V>(node: &Arc<Node<V>>, old_nodes: &HashSet<*const Node<V>>) -> usize {
+        if old_nodes.contains(&Arc::as_ptr(node)) {
+            return 0;
+        }
+        let children = node.children().iter().map(|child| match child {
+            Child::Node(child) => copied(child, old_nodes),
+            Child::Leaf(..) => 0,
+        });
+        1 + children.sum::<usize>()
+    }
+
+    #[test]
+    fn finds_what_was_inserted_and_not_what_was_removed() {
+        let mut hamt = Hamt::new();
+        for i in 0..10_000 {
+            assert!(hamt.insert(i.to_rc_value(), i * 2));
+        }
+        assert!(!hamt.insert(5.to_rc_value(), 0));
+        for i in (0..10_000).step_by(2) {
+            assert!(hamt.remove(&Value::I32(i)));
+        }
+        assert!(!hamt.remove(&Value::I32(0)));
+        assert_eq!(5_000, hamt.len());
+        assert_eq!(Some(&0), hamt.get(&Value::I32(5)));
+        assert_eq!(Some(&14), hamt.get(&Value::I32(7)));
+        assert_eq!(None, hamt.get(&Value::I32(8)));
+        let mut keys = hamt
+            .iter()
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<String>>();
+        keys.sort_by_key(|key| key.parse::<i32>().unwrap());
+        let odds = (1..10_000).step_by(2).map(|i: i32| i.to_string());
+        assert_eq!(odds.collect::<Vec<String>>(), keys);
+        assert_eq!(5_000, hamt.iter().len());
+    }
+
+    #[test]
+    fn changing_a_trie_leaves_the_original_alone_and_copies_only_a_path() {
+        let original = (0..100_000)
+            .map(|i| (i.to_rc_value(), ()))
+            .collect::<Hamt<()>>();
+        let mut changed = original.clone();
+        changed.insert((-1).to_rc_value(), ());
+        let mut old_nodes = HashSet::new();
+        nodes(&original.root, &mut old_nodes);
+        // A node for each level of the trie,  of which there are 4 or so;  not the lot
+        assert!(copied(&changed.root, &old_nodes) <= 5);
+        changed.remove(&Value::I32(7));
+        assert!(copied(&changed.root, &old_nodes) <= 10);
+        assert_eq!(100_000, original.len());
+        assert!(original.contains_key(&Value::I32(7)));
+        assert!(!original.contains_key(&Value::I32(-1)));
+        assert_eq!(100_000, changed.len());
+    }
+
+    #[test]
+    fn keys_with_the_same_hash_are_told_apart() {
+        // As if they'd been picked to collide
+        let mut root = Arc::new(Node::Branch(0, vec![]));
+        let keys = ["a", "b", "c"].map(|key| key.to_rc_value());
+        for key in &keys {
+            assert!(insert(&mut root, 0, 42, Arc::clone(key), ()));
+        }
+        assert!(!insert(&mut root, 0, 42, "b".to_rc_value(), ()));
+        assert!(insert(&mut root, 0, 42 + (1 << 20), 1.to_rc_value(), ()));
+        remove(&mut root, 0, 42, &keys[1]);
+        let hamt = Hamt { root, len: 3 };
+        let mut found = hamt
+            .iter()
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<String>>();
+        found.sort();
+        assert_eq!(vec!["1", "a", "c"], found);
+    }
+
+    #[test]
+    fn keys_are_hashed_with_this_runs_seed() {
+        let key = "key".to_value();
+        let unseeded = BuildHasherDefault::<DefaultHasher>::default();
+        assert_eq!(SeededState.hash_one(&key), SeededState.hash_one(&key));
+        assert_ne!(unseeded.hash_one(&key), SeededState.hash_one(&key));
+    }
+}
@@ -0,0 +1,345 @@
+//! Tag hierarchies,  as built with derive and queried with isa?
+//!
+//! Like Clojure's, a hierarchy is plain data as far as the language is concerned;  it
+//! crosses into ClojureRS as the map
+//!
+//! {:parents {tag #{parent ..}}, :ancestors {tag #{ancestor ..}}, :descendants {tag #{descendant ..}}}
+//!
+//! (where only :parents is read back;  the others are derived from it).  The 1 and 2 argument
+//! versions of derive, isa? etc use a global default hierarchy instead of taking one
+//!
+//! Every hierarchy also knows about the built-in types (see TypeTag::bases),  so
+//! (isa? 'rust.std.i32 'clojure.lang.Number) holds without anything being derived
+use crate::error_message;
+use crate::keyword::Keyword;
+use crate::maps::MapEntry;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap, ToPersistentListMapIter};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Hierarchy {
+    // tag -> its direct parents,  in the order they were derived
+    parents: Vec<(Arc<Value>, Vec<Arc<Value>>)>,
+}
+
+thread_local! {
+    // Clojure keeps this in an atom;  each thread has its own,  so sessions (and tests)
+    // running alongside each other don't see each other's.  A future shares its maker's
+    static GLOBAL_HIERARCHY: RefCell<Arc<Mutex<Hierarchy>>> = RefCell::default();
+}
+
+/// A copy of the global hierarchy, as it is right now
+pub fn global() -> Hierarchy {
+    GLOBAL_HIERARCHY.with(|global| global.borrow().lock().unwrap().clone())
+}
+
+pub fn set_global(hierarchy: Hierarchy) {
+    GLOBAL_HIERARCHY.with(|global| *global.borrow().lock().unwrap() = hierarchy);
+}
+
+/// This thread's global hierarchy itself,  for another thread to share
+pub fn shared_global() -> Arc<Mutex<Hierarchy>> {
+    GLOBAL_HIERARCHY.with(|global| Arc::clone(&global.borrow()))
+}
+
+/// Has this thread share global,  another's global hierarchy,  from now on
+pub fn share_global(global: Arc<Mutex<Hierarchy>>) {
+    GLOBAL_HIERARCHY.with(|installed| *installed.borrow_mut() = global);
+}
+
+fn keyword(name: &str) -> Arc<Value> {
+    Keyword::intern(name).to_rc_value()
+}
+
+fn set(vals: Vec<Arc<Value>>) -> Arc<Value> {
+    vals.into_iter()
+        .collect::<PersistentHashSet>()
+        .to_rc_value()
+}
+
+impl Hierarchy {
+    /// The tags tag was derived from,  then (if tag names a type) its built-in bases
+    pub fn parents(&self, tag: &Value) -> Vec<Arc<Value>> {
+        let mut parents = self
+            .parents
+            .iter()
+            .find(|(child, _)| **child == *tag)
+            .map(|(_, parents)| parents.clone())
+            .unwrap_or_default();
+        if let Some(type_tag) = TypeTag::from_value(tag) {
+            for base in type_tag.bases() {
+                let base = base.to_symbol().to_rc_value();
+                if !parents.contains(&base) {
+                    parents.push(base);
+                }
+            }
+        }
+        parents
+    }
+    /// Parents, their parents, and so on;  nearest first, each only once
+    pub fn ancestors(&self, tag: &Value) -> Vec<Arc<Value>> {
+        let mut ancestors: Vec<Arc<Value>> = vec![];
+        let mut pending = self.parents(tag);
+        while !pending.is_empty() {
+            let parent = pending.remove(0);
+            if !ancestors.contains(&parent) {
+                pending.extend(self.parents(&parent));
+                ancestors.push(parent);
+            }
+        }
+        ancestors
+    }
+    pub fn descendants(&self, tag: &Value) -> Vec<Arc<Value>> {
+        self.parents
+            .iter()
+            .map(|(child, _)| child)
+            .filter(|child| {
+                self.ancestors(child)
+                    .iter()
+                    .any(|ancestor| **ancestor == *tag)
+            })
+            .cloned()
+            .collect()
+    }
+    /// Whether child is parent, or derives from it (directly or not).  Vectors are
+    /// compared element by element,  which is what lets multimethods dispatch on
+    /// several values at once
+    pub fn isa(&self, child: &Value, parent: &Value) -> bool {
+        if child == parent {
+            return true;
+        }
+        match (child, parent) {
+            (Value::PersistentVector(children), Value::PersistentVector(parents)) => {
+                children.len() == parents.len()
+                    && children
+                        .iter()
+                        .zip(parents.iter())
+                        .all(|(child, parent)| self.isa(child, parent))
+            }
+            _ => self
+                .ancestors(child)
+                .iter()
+                .any(|ancestor| **ancestor == *parent),
+        }
+    }
+    /// Returns a new hierarchy where tag derives from parent
+    pub fn derive(&self, tag: Arc<Value>, parent: Arc<Value>) -> Result<Hierarchy, Value> {
+        if tag == parent {
+            return Err(Value::Condition(
+                format!("Cannot derive {} from itself", tag).into(),
+            ));
+        }
+        if self.isa(&parent, &tag) {
+            return Err(Value::Condition(
+                format!("Cyclic derivation: {} has {} as an ancestor", parent, tag).into(),
+            ));
+        }
+        let mut derived = self.clone();
+        match derived.parents.iter_mut().find(|(child, _)| *child == tag) {
+            Some((_, parents)) => {
+                if !parents.contains(&parent) {
+                    parents.push(parent);
+                }
+            }
+            None => derived.parents.push((tag, vec![parent])),
+        }
+        Ok(derived)
+    }
+    /// Returns a new hierarchy where tag no longer derives (directly) from parent
+    pub fn underive(&self, tag: &Value, parent: &Value) -> Hierarchy {
+        let mut underived = self.clone();
+        for (_, parents) in underived
+            .parents
+            .iter_mut()
+            .filter(|(child, _)| **child == *tag)
+        {
+            parents.retain(|existing| **existing != *parent);
+        }
+        underived.parents.retain(|(_, parents)| !parents.is_empty());
+        underived
+    }
+
+    pub fn from_value(val: &Value) -> Result<Hierarchy, Value> {
+        let pmap = match val {
+            Value::PersistentListMap(pmap) => pmap,
+            _ => {
+                return Err(error_message::type_mismatch(
+                    TypeTag::PersistentListMap,
+                    val,
+                ))
+            }
+        };
+        let mut hierarchy = Hierarchy::default();
+        match &*pmap.get(&keyword("parents")) {
+            Value::PersistentListMap(parents) => {
+                for MapEntry { key, val } in parents.iter() {
+                    let tag_parents = match &*val {
+                        Value::PersistentHashSet(tag_parents) => tag_parents,
+                        _ => {
+                            return Err(error_message::type_mismatch(
+                                TypeTag::PersistentHashSet,
+                                &val,
+                            ))
+                        }
+                    };
+                    for parent in tag_parents.iter() {
+                        hierarchy = hierarchy.derive(Arc::clone(&key), parent)?;
+                    }
+                }
+                Ok(hierarchy)
+            }
+            Value::Nil => Ok(hierarchy),
+            other => Err(error_message::type_mismatch(
+                TypeTag::PersistentListMap,
+                other,
+            )),
+        }
+    }
+}
+impl ToValue for Hierarchy {
+    fn to_value(&self) -> Value {
+        let tags = self
+            .parents
+            .iter()
+            .map(|(tag, _)| tag)
+            .cloned()
+            .collect::<Vec<_>>();
+        // Descendants are keyed by parent, which may not be a child of anything itself
+        let mut roots = vec![];
+        for (_, parents) in self.parents.iter() {
+            for parent in parents.iter() {
+                if !roots.contains(parent) {
+                    roots.push(Arc::clone(parent));
+                }
+            }
+        }
+        let relation = |tags: &[Arc<Value>], related: &dyn Fn(&Value) -> Vec<Arc<Value>>| {
+            tags.iter()
+                .map(|tag| MapEntry {
+                    key: Arc::clone(tag),
+                    val: set(related(tag)),
+                })
+                .collect::<PersistentListMap>()
+                .to_rc_value()
+        };
+        vec![
+            MapEntry {
+                key: keyword("parents"),
+                val: relation(&tags, &|tag| self.parents(tag)),
+            },
+            MapEntry {
+                key: keyword("ancestors"),
+                val: relation(&tags, &|tag| self.ancestors(tag)),
+            },
+            MapEntry {
+                key: keyword("descendants"),
+                val: relation(&roots, &|tag| self.descendants(tag)),
+            },
+        ]
+        .into_iter()
+        .collect::<PersistentListMap>()
+        .to_value()
+    }
+}
+
+/// Splits the arguments of a builtin that takes an optional hierarchy first (ie,
+/// (isa? child parent) or (isa? h child parent)) into that hierarchy (or the global one)
+/// and the rest
+pub(crate) fn hierarchy_and_args(
+    args: &[Arc<Value>],
+    arg_count: usize,
+) -> Result<(Hierarchy, &[Arc<Value>]), Value> {
+    if args.len() == arg_count {
+        Ok((global(), args))
+    } else if args.len() == arg_count + 1 {
+        Ok((Hierarchy::from_value(&args[0])?, &args[1..]))
+    } else {
+        Err(error_message::wrong_varg_count(
+            &[arg_count, arg_count + 1],
+            args.len(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod hierarchy_tests {
+        use crate::hierarchy::Hierarchy;
+        use crate::keyword::Keyword;
+        use crate::type_tag::TypeTag;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn kw(name: &str) -> Arc<Value> {
+            Keyword::intern(name).to_rc_value()
+        }
+
+        fn animals() -> Hierarchy {
+            Hierarchy::default()
+                .derive(kw("dog"), kw("mammal"))
+                .unwrap()
+                .derive(kw("cat"), kw("mammal"))
+                .unwrap()
+                .derive(kw("mammal"), kw("animal"))
+                .unwrap()
+        }
+
+        #[test]
+        fn isa_is_transitive() {
+            let h = animals();
+            assert!(h.isa(&kw("dog"), &kw("animal")));
+            assert!(h.isa(&kw("dog"), &kw("dog")));
+            assert!(!h.isa(&kw("animal"), &kw("dog")));
+            assert!(!h.isa(&kw("dog"), &kw("cat")));
+        }
+
+        #[test]
+        fn ancestors_and_descendants() {
+            let h = animals();
+            assert_eq!(vec![kw("mammal"), kw("animal")], h.ancestors(&kw("dog")));
+            assert_eq!(
+                vec![kw("dog"), kw("cat"), kw("mammal")],
+                h.descendants(&kw("animal"))
+            );
+        }
+
+        #[test]
+        fn cyclic_derivation_is_an_error() {
+            assert!(animals().derive(kw("animal"), kw("dog")).is_err());
+            assert!(animals().derive(kw("dog"), kw("dog")).is_err());
+        }
+
+        #[test]
+        fn types_belong_to_their_bases() {
+            let ty = |tag: TypeTag| tag.to_symbol().to_rc_value();
+            let h = Hierarchy::default()
+                .derive(ty(TypeTag::Keyword), kw("named"))
+                .unwrap();
+            assert!(h.isa(&ty(TypeTag::I32), &ty(TypeTag::Number)));
+            assert!(h.isa(&ty(TypeTag::PersistentVector), &ty(TypeTag::Collection)));
+            assert!(!h.isa(&ty(TypeTag::Number), &ty(TypeTag::I32)));
+            assert!(h.isa(&ty(TypeTag::Keyword), &kw("named")));
+            assert!(h.derive(ty(TypeTag::Number), ty(TypeTag::F64)).is_err());
+        }
+
+        #[test]
+        fn underive_removes_only_that_edge() {
+            let h = animals().underive(&kw("dog"), &kw("mammal"));
+            assert!(!h.isa(&kw("dog"), &kw("animal")));
+            assert!(h.isa(&kw("cat"), &kw("animal")));
+        }
+
+        #[test]
+        fn round_trips_through_values() {
+            let h = Hierarchy::from_value(&animals().to_value()).unwrap();
+            assert!(h.isa(&kw("dog"), &kw("animal")));
+            assert!(h.isa(&kw("cat"), &kw("mammal")));
+            assert!(!h.isa(&kw("animal"), &kw("dog")));
+            assert_eq!(animals().to_value(), h.to_value());
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Rust values that scripts can drive with Clojure's interop syntax;  so whatever embeds us
+//! can hand scripts its own objects,  as Value::HostObject
+//!
+//!   (.method obj args)     calls obj's method with args
+//!   (.-field obj)          is obj's field
+//!   (. obj method args)    and (. obj (method args)) are (.method obj args) written out;
+//!                          and (.. obj (a x) b) is (.b (.a obj x))
+//!   (Type/static-fn args)  calls one of Type's static fns;  Repl::register_type defines
+//!                          them in a namespace named for Type,  as any qualified fn is
+//!
+//! Methods and fields are asked for by the name they're written with;  (.get-count c) asks
+//! c for get-count
+use crate::clojure_protocol::type_names;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::native_fn::NativeFn;
+use crate::persistent_list::{PersistentList, ToPersistentListIter};
+use crate::symbol::Symbol;
+use crate::value::{eval_or_throw, Value};
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+/// What a Rust type implements for scripts to use its values;  Any,  so code that knows
+/// the type can downcast to it
+pub trait HostObject: Any + fmt::Debug + Send + Sync {
+    /// Its type's name,  as (type obj) gives it;  my.app.Counter,  say
+    fn type_name(&self) -> &str;
+    /// Calls method with args;  None if it has no such method
+    fn call(&self, method: &str, args: Vec<Arc<Value>>) -> Option<Value>;
+    /// Its field;  None if it has no such field
+    fn field(&self, _field: &str) -> Option<Value> {
+        None
+    }
+    /// What str makes of it;  #object[ and its type's name ],  unless it says otherwise
+    fn display(&self) -> String {
+        format!("#object[{}]", self.type_name())
+    }
+}
+
+/// A host type,  and its static fns;  what Repl::register_type defines
+pub struct HostType {
+    pub(crate) name: String,
+    pub(crate) static_fns: Vec<(String, NativeFn)>,
+}
+impl HostType {
+    pub fn new(name: &str) -> HostType {
+        HostType {
+            name: name.to_string(),
+            static_fns: vec![],
+        }
+    }
+    /// This type,  with (Type/name args) calling f with args
+    pub fn static_fn(
+        mut self,
+        name: &str,
+        f: impl Fn(Vec<Arc<Value>>) -> Value + Send + Sync + 'static,
+    ) -> HostType {
+        let qualified = format!("{}/{}", self.name, name);
+        self.static_fns
+            .push((qualified.clone(), NativeFn::new(&qualified, f)));
+        self
+    }
+}
+
+enum Member<'a> {
+    Method(&'a str),
+    Field(&'a str),
+}
+
+// What sym asks for,  if it's .method or .-field
+fn member(sym: &Symbol) -> Option<Member<'_>> {
+    let name = sym.name.strip_prefix('.').filter(|_| !sym.has_ns())?;
+    match name.strip_prefix('-') {
+        Some(field) if !field.is_empty() => Some(Member::Field(field)),
+        _ if name.is_empty() || name.starts_with('.') => None,
+        _ => Some(Member::Method(name)),
+    }
+}
+
+/// Whether sym,  at the head of a list,  asks for a method or field;  rather than naming
+/// something
+pub fn is_member(sym: &Symbol) -> bool {
+    member(sym).is_some()
+}
+
+/// What (head ..args) gives,  if head is .method or .-field;  None if it's any other symbol
+pub fn eval_member(
+    head: &Symbol,
+    args: &Arc<PersistentList>,
+    environment: &Arc<Environment>,
+) -> Option<Value> {
+    let member = member(head)?;
+    let mut args = match args
+        .iter()
+        .map(|arg| eval_or_throw(&arg, environment))
+        .collect::<Result<Vec<Arc<Value>>, Arc<Value>>>()
+    {
+        Ok(args) => args,
+        Err(condition) => return Some((*condition).clone()),
+    };
+    if args.is_empty() {
+        return Some(Value::Condition(
+            format!(
+                "Malformed member expression, expecting ({} target ...)",
+                head
+            )
+            .into(),
+        ));
+    }
+    let target = args.remove(0);
+    let object = match &*target {
+        Value::HostObject(object) => object,
+        other => {
+            return Some(Value::Condition(
+                format!("No member {} on {}", head, type_names(other)[0]).into(),
+            ))
+        }
+    };
+    Some(match member {
+        Member::Method(method) => object.call(method, args).unwrap_or_else(|| {
+            Value::Condition(format!("No method {} on {}", method, object.type_name()).into())
+        }),
+        Member::Field(field) if args.is_empty() => object.field(field).unwrap_or_else(|| {
+            Value::Condition(format!("No field {} on {}", field, object.type_name()).into())
+        }),
+        Member::Field(_) => error_message::wrong_arg_count(1, args.len() + 1),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::host_object::{HostObject, HostType};
+    use crate::repl::Repl;
+    use crate::value::Value;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct Counter {
+        count: AtomicI32,
+    }
+    impl HostObject for Counter {
+        fn type_name(&self) -> &str {
+            "my.app.Counter"
+        }
+        fn call(&self, method: &str, args: Vec<Arc<Value>>) -> Option<Value> {
+            match (method, &args[..]) {
+                ("add", [n]) => match **n {
+                    Value::I32(n) => {
+                        Some(Value::I32(self.count.fetch_add(n, Ordering::SeqCst) + n))
+                    }
+                    _ => Some(Value::Condition("Not an int".into())),
+                },
+                _ => None,
+            }
+        }
+        fn field(&self, field: &str) -> Option<Value> {
+            match field {
+                "count" => Some(Value::I32(self.count.load(Ordering::SeqCst))),
+                _ => None,
+            }
+        }
+    }
+
+    fn repl() -> Repl {
+        let repl = Repl::default();
+        repl.register_type(
+            HostType::new("my.app.Counter").static_fn("starting-at", |args| match *args[0] {
+                Value::I32(n) => Value::host_object(Counter {
+                    count: AtomicI32::new(n),
+                }),
+                _ => Value::Condition("Not an int".into()),
+            }),
+        );
+        repl
+    }
+
+    #[test]
+    fn scripts_call_methods_fields_and_static_fns() {
+        let result = repl().eval_all(&[
+            "(def c (my.app.Counter/starting-at 1))",
+            "(.add c 2)",
+            "[(.add c 3) (.-count c) (type c)]",
+        ]);
+        assert_eq!("[6 6 my.app.Counter]", result.to_string());
+    }
+
+    #[test]
+    fn a_missing_member_is_a_condition() {
+        let repl = repl();
+        repl.eval_all(&["(def c (my.app.Counter/starting-at 1))"]);
+        assert_eq!(
+            Value::Condition("No method sub on my.app.Counter".into()),
+            repl.eval_all(&["(.sub c 1)"])
+        );
+        assert_eq!(
+            Value::Condition("No field total on my.app.Counter".into()),
+            repl.eval_all(&["(.-total c)"])
+        );
+        assert_eq!(
+            Value::Condition("No member .add on rust.std.i32".into()),
+            repl.eval_all(&["(.add 1 2)"])
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Rust traits that can be implemented from Clojure;  so host code can be given objects
+//! whose methods are ClojureRS fns,  and call them as it would any other trait object
+//!
+//!   host_trait! {
+//!       pub trait Greeter {
+//!           fn greet(&self, name: Arc<Value>) -> Value;
+//!       }
+//!   }
+//!
+//! declares the trait Greeter.  repl.register_trait::<dyn Greeter>() (or register,  given an
+//! environment) then defines a protocol Greeter there,  with a method for each of the
+//! trait's (greet_all would be greet-all);  and implement makes anything that satisfies it,
+//! say
+//!
+//!   (reify Greeter (greet [this name] (str "Hello, " name)))
+//!
+//! a Box<dyn Greeter>,  whose greet calls that fn with it and name
+use crate::clojure_protocol::{type_names, MethodFn, Protocol};
+use crate::environment::Environment;
+use crate::ifn::IFn;
+use crate::rust_core::defprotocol_macro;
+use crate::symbol::Symbol;
+use crate::value::Value;
+use std::sync::Arc;
+
+/// What host_trait! implements for its trait's trait objects
+pub trait HostTrait {
+    /// The trait's name;  and so its protocol's
+    const NAME: &'static str;
+    /// Each method's name,  and the names of what it takes after self
+    const METHODS: &'static [(&'static str, &'static [&'static str])];
+    fn from_host_object(object: HostObject) -> Box<Self>;
+}
+
+/// A value that satisfies a host trait's protocol;  the trait's methods call the protocol's
+#[derive(Debug, Clone)]
+pub struct HostObject {
+    protocol: Arc<Protocol>,
+    val: Arc<Value>,
+}
+impl HostObject {
+    /// Calls method (a Rust name,  as in greet_all) with the value and args
+    pub fn call(&self, method: &str, args: Vec<Arc<Value>>) -> Value {
+        let method_fn = MethodFn {
+            protocol: Arc::clone(&self.protocol),
+            method: Symbol::intern(&clojure_name(method)),
+        };
+        let mut args = args;
+        args.insert(0, Arc::clone(&self.val));
+        method_fn.invoke(args)
+    }
+}
+
+// greet_all => greet-all
+fn clojure_name(name: &str) -> String {
+    name.replace('_', "-")
+}
+
+/// Defines T's protocol,  and its methods,  in environment's current namespace
+pub fn register<T: HostTrait + ?Sized>(environment: &Environment) -> Arc<Protocol> {
+    let methods = T::METHODS
+        .iter()
+        .map(|(name, args)| {
+            let mut arglist = vec![Symbol::intern("this")];
+            arglist.extend(args.iter().map(|arg| Symbol::intern(&clojure_name(arg))));
+            (Symbol::intern(&clojure_name(name)), vec![arglist])
+        })
+        .collect();
+    defprotocol_macro::define(environment, &Symbol::intern(T::NAME), methods)
+}
+
+/// val as a T,  if it satisfies protocol;  T's protocol,  as register gave it
+pub fn implement<T: HostTrait + ?Sized>(
+    protocol: &Arc<Protocol>,
+    val: Arc<Value>,
+) -> Result<Box<T>, Value> {
+    if protocol.name.name != T::NAME || !protocol.is_satisfied_by(&val) {
+        return Err(Value::Condition(
+            format!("{} does not implement {}", type_names(&val)[0], T::NAME).into(),
+        ));
+    }
+    Ok(T::from_host_object(HostObject {
+        protocol: Arc::clone(protocol),
+        val,
+    }))
+}
+
+/// Declares a trait that can be implemented from Clojure;  its methods each take &self,
+/// and any number of Arc<Value>s,  and give a Value
+#[macro_export]
+macro_rules! host_trait {
+    (
+        $(#[$meta:meta])*
+        $vis:vis trait $name:ident {
+            $(
+                $(#[$method_meta:meta])*
+                fn $method:ident(&self $(, $arg:ident: Arc<Value>)* $(,)?) -> Value;
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis trait $name {
+            $(
+                $(#[$method_meta])*
+                fn $method(
+                    &self $(, $arg: std::sync::Arc<$crate::value::Value>)*
+                ) -> $crate::value::Value;
+            )*
+        }
+        impl $name for $crate::host_trait::HostObject {
+            $(
+                fn $method(
+                    &self $(, $arg: std::sync::Arc<$crate::value::Value>)*
+                ) -> $crate::value::Value {
+                    self.call(stringify!($method), vec![$($arg),*])
+                }
+            )*
+        }
+        impl $crate::host_trait::HostTrait for dyn $name {
+            const NAME: &'static str = stringify!($name);
+            const METHODS: &'static [(&'static str, &'static [&'static str])] =
+                &[$((stringify!($method), &[$(stringify!($arg)),*])),*];
+            fn from_host_object(object: $crate::host_trait::HostObject) -> Box<Self> {
+                Box::new(object)
+            }
+        }
+    };
+}
+#[cfg(test)]
+mod tests {
+    use crate::environment::Environment;
+    use crate::host_trait;
+    use crate::repl::Repl;
+    use crate::value::ToValue;
+    use std::sync::Arc;
+
+    host_trait! {
+        trait Greeter {
+            fn greet(&self, name: Arc<Value>) -> Value;
+            fn greet_all(&self, first: Arc<Value>, second: Arc<Value>) -> Value;
+        }
+    }
+
+    #[test]
+    fn host_traits_are_implemented_with_protocols() {
+        let environment = Environment::clojure_core_environment();
+        let protocol = host_trait::register::<dyn Greeter>(&environment);
+        let repl = Repl::new(Arc::clone(&environment));
+        let greeter = repl.eval_all(&[
+            "(def greeting \"Hello\")",
+            "(reify Greeter (greet [_ name] (str greeting \", \" name)) (greet-all [this a b] [(greet this a) (greet this b)]))",
+        ]);
+        let greeter =
+            host_trait::implement::<dyn Greeter>(&protocol, greeter.to_rc_value()).unwrap();
+        assert_eq!(
+            "Hello, Rust",
+            greeter.greet("Rust".to_rc_value()).to_string()
+        );
+        assert_eq!(
+            "[\"Hello, a\" \"Hello, b\"]",
+            greeter
+                .greet_all("a".to_rc_value(), "b".to_rc_value())
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn only_what_satisfies_a_host_trait_implements_it() {
+        let environment = Environment::clojure_core_environment();
+        let protocol = host_trait::register::<dyn Greeter>(&environment);
+        let not_a_greeter = host_trait::implement::<dyn Greeter>(&protocol, 1.to_rc_value());
+        assert_eq!(
+            Some("#Condition[\"rust.std.i32 does not implement Greeter\"]".to_string()),
+            not_a_greeter.err().map(|condition| condition.to_string())
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! The trait shared by reference types you can wait on with `deref`
+//!
+//! Based on clojure.lang.IBlockingDeref
+use crate::value::Value;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A reference whose value may not have arrived yet (promises, futures, ..)
+///
+/// All of these share one contract, so (deref x timeout-ms timeout-val) means the same
+/// thing whichever of them x is
+pub trait IBlockingDeref {
+    /// Waits up to timeout (or, with None, for as long as it takes) for the value,
+    /// returning None if it didn't arrive in time.  A None with no timeout means the
+    /// wait was interrupted
+    fn deref_blocking(&self, timeout: Option<Duration>) -> Option<Arc<Value>>;
+    fn is_realized(&self) -> bool;
+}
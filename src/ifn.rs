@@ -10,12 +10,17 @@
 //! a map
 //!    ({:name "Blah" :age 20} :name)
 //! As well as a few more types.
+use crate::error_message;
+use crate::multi_fn::MultiFn;
+use crate::rust_core::GetFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
 use crate::value::Value;
 
 use dyn_clone::DynClone;
 
 use std::fmt::Debug;
-use std::rc::Rc;
+use std::sync::Arc;
 
 //
 // Based on: clojure.lang.IFn
@@ -36,7 +41,93 @@ use std::rc::Rc;
 // trait, rather than saying 'this is an interface called Fn'
 //
 
-pub trait IFn: Debug + DynClone {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value;
+// Values are shared between threads,  so whatever can be called must be too
+pub trait IFn: Debug + DynClone + Send + Sync {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value;
+    /// The parameter lists it can be called with,  as in (fn [x & more] ..),  for tools to
+    /// show;  None if it doesn't say
+    fn arglists(&self) -> Option<Vec<Vec<Symbol>>> {
+        None
+    }
+    /// The multimethod it is,  if it's one
+    fn as_multi_fn(&self) -> Option<&Arc<MultiFn>> {
+        None
+    }
+    /// The name it prints with,  as in #function[user/f];  that of the var it was first
+    /// defined as,  if it's been
+    fn name(&self) -> Option<&Symbol> {
+        None
+    }
+    /// It,  named name,  if it's a fn that can be;  as def names the fns it defines
+    fn named(&self, _name: Symbol) -> Option<Arc<dyn IFn>> {
+        None
+    }
 }
 dyn_clone::clone_trait_object!(IFn);
+
+/// Whether a fn with this arglist can be called with argc arguments
+pub fn accepts(arglist: &[Symbol], argc: usize) -> bool {
+    let rest = Symbol::intern("&");
+    match arglist.iter().position(|arg| *arg == rest) {
+        Some(ind) => argc >= ind,
+        None => argc == arglist.len(),
+    }
+}
+
+/// Whether f can be called;  is a fn,  or a var holding one,  or a keyword,  map or set,
+/// which look things up
+pub fn is_callable(f: &Value) -> bool {
+    matches!(
+        f,
+        Value::IFn(_)
+            | Value::Var(_)
+            | Value::Keyword(_)
+            | Value::PersistentListMap(_)
+            | Value::PersistentTreeMap(_)
+            | Value::PersistentHashSet(_)
+            | Value::PersistentTreeSet(_)
+    )
+}
+
+/// f called on args;  what everything that calls a fn it's given calls it with.  A keyword
+/// looks itself up in its arg,  as (:k m) does,  and a map or set looks its arg up in
+/// itself,  as ({:k 1} :k) and (#{1} 1) do;  either with what to give if it's not there,
+/// as get takes.  A var calls what it holds
+pub fn call(f: &Arc<Value>, args: Vec<Arc<Value>>) -> Value {
+    match &**f {
+        Value::IFn(ifn) => ifn.invoke(args),
+        Value::Var(var) => call(&var.deref(), args),
+        _ if !is_callable(f) => error_message::type_mismatch(TypeTag::IFn, f),
+        _ if args.is_empty() || args.len() > 2 => {
+            error_message::wrong_varg_count(&[1, 2], args.len())
+        }
+        Value::Keyword(_) => {
+            let mut args = args;
+            args.insert(1, Arc::clone(f));
+            GetFn {}.invoke(args)
+        }
+        _ => {
+            let mut args = args;
+            args.insert(0, Arc::clone(f));
+            GetFn {}.invoke(args)
+        }
+    }
+}
+
+/// f as an IFn,  if it can be called;  for what wants to keep hold of a fn to call later
+pub fn as_ifn(f: &Arc<Value>) -> Option<Arc<dyn IFn>> {
+    match &**f {
+        Value::IFn(ifn) => Some(Arc::clone(ifn)),
+        _ if is_callable(f) => Some(Arc::new(Callable(Arc::clone(f)))),
+        _ => None,
+    }
+}
+
+// A keyword,  map,  set or var,  called as call calls it
+#[derive(Debug, Clone)]
+struct Callable(Arc<Value>);
+impl IFn for Callable {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        call(&self.0, args)
+    }
+}
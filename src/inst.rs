@@ -0,0 +1,172 @@
+//! Instants;  as read from #inst "2026-10-16T09:30:00.123-00:00",  kept as the milliseconds
+//! since the epoch (1970-01-01T00:00:00Z),  and printed back out in UTC
+//!
+//! An instant is read as Clojure reads one;  as much of
+//!
+//!   yyyy-mm-ddThh:mm:ss.fff+hh:mm
+//!
+//! as is there,  from the year on.  What's left out is the start of its unit,  and an
+//! instant without an offset is in UTC;  digits past the milliseconds are dropped
+
+/// The milliseconds since the epoch of the instant s,  ie 2026-10-16T09:30:00Z;  or what's
+/// wrong with it
+pub fn parse(s: &str) -> Result<i64, String> {
+    let unrecognized = || format!("Unrecognized date/time syntax: {}", s);
+    let mut rest = s;
+    // The next n digits of rest,  if prefix (a separator) comes before them
+    let mut field = |prefix: &str, n: usize| -> Result<Option<i64>, String> {
+        let after = match rest.strip_prefix(prefix) {
+            Some(after) if !rest.is_empty() => after,
+            _ => return Ok(None),
+        };
+        let digits = after
+            .get(..n)
+            .filter(|d| d.bytes().all(|b| b.is_ascii_digit()));
+        match digits {
+            Some(digits) => {
+                rest = &after[n..];
+                Ok(Some(digits.parse().unwrap()))
+            }
+            None => Err(unrecognized()),
+        }
+    };
+    let year = field("", 4)?.ok_or_else(unrecognized)?;
+    let month = field("-", 2)?.unwrap_or(1);
+    let day = field("-", 2)?.unwrap_or(1);
+    let hour = field("T", 2)?.unwrap_or(0);
+    let minute = field(":", 2)?.unwrap_or(0);
+    let second = field(":", 2)?.unwrap_or(0);
+    let mut millis = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            return Err(unrecognized());
+        }
+        let padded = format!("{:0<3}", &fraction[..len.min(3)]);
+        millis = padded.parse().unwrap();
+        rest = &fraction[len..];
+    }
+    let offset_minutes = match rest.chars().next() {
+        None | Some('Z') if rest.len() <= 1 => 0,
+        Some(sign @ '+') | Some(sign @ '-') => {
+            let is_two_digits = |d: &str| d.len() == 2 && d.bytes().all(|b| b.is_ascii_digit());
+            let digits = rest[1..]
+                .split_once(':')
+                .filter(|(h, m)| is_two_digits(h) && is_two_digits(m));
+            let (hours, minutes) = digits.ok_or_else(unrecognized)?;
+            let (hours, minutes): (i64, i64) = (hours.parse().unwrap(), minutes.parse().unwrap());
+            if hours > 23 || minutes > 59 {
+                return Err(unrecognized());
+            }
+            let offset = hours * 60 + minutes;
+            if sign == '-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return Err(unrecognized()),
+    };
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(unrecognized());
+    }
+    let seconds = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second
+        - offset_minutes * 60;
+    Ok(seconds * 1000 + millis)
+}
+
+/// The instant millis milliseconds after the epoch,  in UTC;  as it's printed,  ie
+/// 2026-10-16T09:30:00.123-00:00
+pub fn format(millis: i64) -> String {
+    let (secs, millis) = (millis.div_euclid(1000), millis.rem_euclid(1000));
+    let (days, secs_of_day) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}-00:00",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        millis
+    )
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since the epoch to a date in the proleptic Gregorian calendar,  and back;  see
+// http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * mp + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inst::{format, parse};
+
+    #[test]
+    fn instants_are_read_from_as_much_as_is_there() {
+        assert_eq!(Ok(0), parse("1970"));
+        assert_eq!(Ok(1_709_251_199_250), parse("2024-02-29T23:59:59.25Z"));
+        assert_eq!(
+            Ok(1_709_251_199_250),
+            parse("2024-03-01T01:59:59.250789+02:00")
+        );
+        assert_eq!(Ok(-86_400_000), parse("1969-12-31"));
+        for bad in &[
+            "",
+            "70",
+            "2023-02-29",
+            "2024-13",
+            "2024-01-01T24",
+            "2024-01-01X",
+            "2024.",
+            "2024-01-01T00:00+-1:00",
+        ] {
+            assert_eq!(
+                Err(format!("Unrecognized date/time syntax: {}", bad)),
+                parse(bad)
+            );
+        }
+    }
+
+    #[test]
+    fn instants_are_printed_in_utc() {
+        assert_eq!("1970-01-01T00:00:00.000-00:00", format(0));
+        assert_eq!("2024-02-29T23:59:59.250-00:00", format(1_709_251_199_250));
+        assert_eq!("1969-12-31T23:59:59.999-00:00", format(-1));
+    }
+}
@@ -0,0 +1,169 @@
+//! Cooperative interruption of a running evaluation
+//!
+//! Whoever drives evaluation on a thread (ie, an nREPL session) can install a flag here;
+//! setting that flag from any other thread makes every form evaluated afterwards on this
+//! thread return an "Interrupted" Condition instead of running, so the evaluation unwinds
+//! back out to whoever started it
+//!
+//! At the REPL,  and running a script,  -e or -m,  it's Ctrl-C that sets it;  or,  with
+//! CLOJURE_RS_EVAL_TIMEOUT set,  an evaluation taking longer than that
+//!
+//! Cleanup code (ie, `finally`) runs `uninterruptibly`, so an interruption can't also
+//! stop the code that's meant to clean up after it
+//!
+//! Checking is also when the fns given to on-signal are called (see signal.rs);  masked,
+//! like cleanup,  so one isn't cut short,  or called again from inside itself
+use crate::signal;
+use std::cell::{Cell, RefCell};
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+thread_local! {
+    static INTERRUPT_FLAG: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+    // How many uninterruptible sections we're currently nested in
+    static MASK_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+pub const INTERRUPTED_MSG: &str = "Evaluation interrupted";
+
+pub const TIMEOUT_VAR: &str = "CLOJURE_RS_EVAL_TIMEOUT";
+
+/// Installs the flag checked by evaluation on the current thread
+pub fn install(flag: Arc<AtomicBool>) {
+    INTERRUPT_FLAG.with(|installed| *installed.borrow_mut() = Some(flag));
+}
+
+/// Has Ctrl-C interrupt what's evaluated on this thread,  rather than end us
+pub fn on_ctrl_c() {
+    install(signal::interrupting_on_int());
+}
+
+/// How long an evaluation may take before it's interrupted;  as many milliseconds as
+/// CLOJURE_RS_EVAL_TIMEOUT says,  if it's set
+pub fn timeout() -> Option<Duration> {
+    static TIMEOUT: OnceLock<Option<Duration>> = OnceLock::new();
+    *TIMEOUT.get_or_init(|| {
+        env::var(TIMEOUT_VAR)
+            .ok()
+            .and_then(|millis| millis.parse().ok())
+            .map(Duration::from_millis)
+    })
+}
+
+/// Runs f,  one whole evaluation,  with the flag cleared before and after;  so neither an
+/// interrupt that came while nothing was evaluated,  nor one that came too late,  stops the
+/// next.  With a timeout,  the flag's set once it's passed;  one's installed,  if need be
+pub fn evaluating<T>(timeout: Option<Duration>, f: impl FnOnce() -> T) -> T {
+    let installed = INTERRUPT_FLAG.with(|installed| installed.borrow().clone());
+    let flag = match (installed, timeout) {
+        (Some(flag), _) => flag,
+        (None, Some(_)) => {
+            let flag = Arc::new(AtomicBool::new(false));
+            install(Arc::clone(&flag));
+            flag
+        }
+        (None, None) => return f(),
+    };
+    flag.store(false, Ordering::SeqCst);
+    let result = match timeout {
+        Some(timeout) => {
+            let (done, finished) = mpsc::channel::<()>();
+            let watchdog = {
+                let flag = Arc::clone(&flag);
+                thread::spawn(move || {
+                    if let Err(RecvTimeoutError::Timeout) = finished.recv_timeout(timeout) {
+                        flag.store(true, Ordering::SeqCst);
+                    }
+                })
+            };
+            let result = f();
+            drop(done);
+            let _ = watchdog.join();
+            result
+        }
+        None => f(),
+    };
+    flag.store(false, Ordering::SeqCst);
+    result
+}
+
+pub fn is_interrupted() -> bool {
+    if MASK_DEPTH.with(|depth| depth.get()) > 0 {
+        return false;
+    }
+    if signal::is_pending() {
+        uninterruptibly(signal::deliver);
+    }
+    INTERRUPT_FLAG.with(|installed| match &*installed.borrow() {
+        Some(flag) => flag.load(Ordering::SeqCst),
+        None => false,
+    })
+}
+
+// Unmasks again when dropped,  so a panicking cleanup can't leave us masked forever
+struct Mask;
+impl Mask {
+    fn new() -> Mask {
+        MASK_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        Mask
+    }
+}
+impl Drop for Mask {
+    fn drop(&mut self) {
+        MASK_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Runs f with interrupts masked;  a flag set meanwhile is still seen once f returns
+pub fn uninterruptibly<T>(f: impl FnOnce() -> T) -> T {
+    let _mask = Mask::new();
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interrupt;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn not_interrupted_without_a_flag() {
+        thread::spawn(|| assert!(!interrupt::is_interrupted()))
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn flag_set_from_another_thread_is_seen() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let installed = Arc::clone(&flag);
+        let evaluator = thread::spawn(move || {
+            interrupt::install(installed);
+            while !interrupt::is_interrupted() {
+                thread::yield_now();
+            }
+        });
+        flag.store(true, Ordering::SeqCst);
+        evaluator.join().unwrap();
+    }
+
+    #[test]
+    fn masked_sections_ignore_the_flag_until_they_end() {
+        thread::spawn(|| {
+            interrupt::install(Arc::new(AtomicBool::new(true)));
+            interrupt::uninterruptibly(|| {
+                assert!(!interrupt::is_interrupted());
+                interrupt::uninterruptibly(|| assert!(!interrupt::is_interrupted()));
+                assert!(!interrupt::is_interrupted());
+            });
+            assert!(interrupt::is_interrupted());
+        })
+        .join()
+        .unwrap();
+    }
+}
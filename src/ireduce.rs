@@ -0,0 +1,110 @@
+//! What reduce walks directly;  the collections that can hand over their elements from their
+//! own storage,  with no seq made of them along the way.  Vectors,  maps,  sets and queues
+//! do,  as do strings and ranges of i32s.  Anything else is reduced by walking its seq
+use crate::lazy_seq::Counted;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list_map::PersistentListMap;
+use crate::persistent_queue::PersistentQueue;
+use crate::persistent_tree_map::PersistentTreeMap;
+use crate::persistent_tree_set::PersistentTreeSet;
+use crate::persistent_vector::PersistentVector;
+use crate::seqable::Seqable;
+use crate::shared_str::SharedStr;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+pub trait IReduce {
+    /// Calls f on each element,  in seq order,  until it errs
+    fn reduce_each(&self, f: &mut dyn FnMut(Arc<Value>) -> Result<(), Value>) -> Result<(), Value>;
+}
+
+/// val,  as something reduce can walk without seqing it;  if it is one
+pub fn ireduce(val: &Value) -> Option<&dyn IReduce> {
+    Some(match val {
+        Value::PersistentVector(pvector) => &**pvector,
+        Value::PersistentListMap(plistmap) => &**plistmap,
+        Value::PersistentHashSet(pset) => &**pset,
+        Value::PersistentTreeMap(ptmap) => &**ptmap,
+        Value::PersistentTreeSet(ptset) => &**ptset,
+        Value::PersistentQueue(pqueue) => &**pqueue,
+        Value::LazySeq(lseq) => lseq.counted()?,
+        Value::String(string) => string,
+        Value::Bytes(bytes) => bytes,
+        _ => return None,
+    })
+}
+
+impl IReduce for PersistentVector {
+    fn reduce_each(&self, f: &mut dyn FnMut(Arc<Value>) -> Result<(), Value>) -> Result<(), Value> {
+        self.iter().try_for_each(|val| f(Arc::clone(val)))
+    }
+}
+
+impl IReduce for Counted {
+    fn reduce_each(&self, f: &mut dyn FnMut(Arc<Value>) -> Result<(), Value>) -> Result<(), Value> {
+        let mut counted = *self;
+        counted.try_for_each(|i| f(Value::I32(i).to_rc_value()))
+    }
+}
+
+// The rest already walk their storage to seq it
+macro_rules! reduce_through_seq_iter {
+    ($($seqable:ty),*) => {
+        $(impl IReduce for $seqable {
+            fn reduce_each(
+                &self,
+                f: &mut dyn FnMut(Arc<Value>) -> Result<(), Value>,
+            ) -> Result<(), Value> {
+                self.seq_iter().try_for_each(f)
+            }
+        })*
+    };
+}
+reduce_through_seq_iter!(
+    PersistentListMap,
+    PersistentHashSet,
+    PersistentTreeMap,
+    PersistentTreeSet,
+    PersistentQueue,
+    SharedStr,
+    Arc<Vec<u8>>
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::ireduce;
+    use crate::repl::Repl;
+    use crate::value::Value;
+    use std::sync::Arc;
+
+    #[test]
+    fn lists_and_unbounded_seqs_are_not_reduced_directly() {
+        let result = Repl::default().eval_all(&["'(1 2)"]);
+        assert!(ireduce::ireduce(&result).is_none());
+        let result = Repl::default().eval_all(&["(range)"]);
+        assert!(ireduce::ireduce(&result).is_none());
+        let result = Repl::default().eval_all(&["(range 3)"]);
+        let mut seen = vec![];
+        let reduced = ireduce::ireduce(&result).unwrap().reduce_each(&mut |val| {
+            seen.push(val);
+            Ok(())
+        });
+        assert!(reduced.is_ok());
+        assert_eq!(
+            vec![
+                Arc::new(Value::I32(0)),
+                Arc::new(Value::I32(1)),
+                Arc::new(Value::I32(2))
+            ],
+            seen
+        );
+    }
+
+    #[test]
+    fn reduce_walks_maps_sets_and_strings() {
+        let result = Repl::default().eval_all(&[
+            "[(reduce (fn [acc e] (+ acc (val e))) 0 {:a 1 :b 2}) (reduce + (sorted-set 1 2 3)) (reduce str \"\" \"abc\") (reduce + (range 1000))]",
+        ]);
+        assert_eq!("[3 6 \"abc\" 499500]", result.to_string());
+    }
+}
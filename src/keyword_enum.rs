@@ -0,0 +1,180 @@
+//! Rust enums that scripts see as keywords;  so native fns can take and give them without
+//! matching on keywords themselves
+//!
+//!   keyword_enum! {
+//!       pub enum Status {
+//!           Ok,
+//!           NotFound,
+//!       }
+//!   }
+//!
+//! declares the enum Status;  Status::NotFound.into() is then :not-found (each variant's
+//! name,  lower-cased and split on its capitals with -),  and Status::try_from(&*args[0])
+//! is Status::NotFound for :not-found,  or the condition to give for anything else
+use crate::error_message;
+use crate::keyword::Keyword;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+
+/// What keyword_enum! implements for its enum
+pub trait KeywordEnum: Copy + PartialEq + 'static {
+    /// The enum's name,  as the condition for a keyword it has no variant for gives it
+    const NAME: &'static str;
+    /// Each variant,  with its name
+    const VARIANTS: &'static [(&'static str, Self)];
+}
+
+// NotFound => not-found
+fn keyword_name(variant: &str) -> String {
+    let mut name = String::new();
+    let mut after_lower = false;
+    for c in variant.chars() {
+        if c.is_uppercase() && after_lower {
+            name.push('-');
+        }
+        after_lower = c.is_lowercase() || c.is_ascii_digit();
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+/// val's keyword
+pub fn to_keyword<T: KeywordEnum>(val: T) -> Value {
+    let (variant, _) = T::VARIANTS
+        .iter()
+        .find(|(_, variant)| *variant == val)
+        .expect("every variant is listed");
+    Keyword::intern(&keyword_name(variant)).to_value()
+}
+
+/// The variant val's the keyword of;  or a condition,  if it's none of T's
+pub fn from_keyword<T: KeywordEnum>(val: &Value) -> Result<T, Value> {
+    let keyword = match val {
+        Value::Keyword(keyword) => keyword,
+        _ => return Err(error_message::type_mismatch(TypeTag::Keyword, val)),
+    };
+    if !keyword.sym.has_ns() {
+        if let Some((_, variant)) = T::VARIANTS
+            .iter()
+            .find(|(variant, _)| keyword_name(variant) == keyword.sym.name.as_str())
+        {
+            return Ok(*variant);
+        }
+    }
+    let names = T::VARIANTS
+        .iter()
+        .map(|(variant, _)| format!(":{}", keyword_name(variant)))
+        .collect::<Vec<String>>();
+    let expected = match &names[..] {
+        [] => "it has none".to_string(),
+        [name] => format!("expected {}", name),
+        [names @ .., last] => format!("expected {} or {}", names.join(",  "), last),
+    };
+    Err(Value::Condition(
+        format!("No {} for {};  {}", T::NAME, keyword, expected).into(),
+    ))
+}
+
+/// Declares an enum of unit variants,  each one a keyword to scripts
+#[macro_export]
+macro_rules! keyword_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant
+            ),*
+        }
+        impl $crate::keyword_enum::KeywordEnum for $name {
+            const NAME: &'static str = stringify!($name);
+            const VARIANTS: &'static [(&'static str, Self)] =
+                &[$((stringify!($variant), $name::$variant)),*];
+        }
+        impl From<$name> for $crate::value::Value {
+            fn from(val: $name) -> $crate::value::Value {
+                $crate::keyword_enum::to_keyword(val)
+            }
+        }
+        impl $crate::value::ToValue for $name {
+            fn to_value(&self) -> $crate::value::Value {
+                $crate::keyword_enum::to_keyword(*self)
+            }
+        }
+        impl std::convert::TryFrom<&$crate::value::Value> for $name {
+            type Error = $crate::value::Value;
+            fn try_from(val: &$crate::value::Value) -> Result<$name, $crate::value::Value> {
+                $crate::keyword_enum::from_keyword(val)
+            }
+        }
+    };
+}
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+    use std::convert::TryFrom;
+
+    keyword_enum! {
+        enum Status {
+            Ok,
+            NotFound,
+            Http2Only,
+        }
+    }
+
+    fn repl() -> Repl {
+        let repl = Repl::default();
+        repl.register_fn("lookup", |args| match args[0].to_string().as_str() {
+            "home" => Status::Ok.into(),
+            _ => Status::NotFound.into(),
+        });
+        repl.register_fn("found?", |args| match Status::try_from(&*args[0]) {
+            Ok(status) => (status == Status::Ok).into(),
+            Err(condition) => condition,
+        });
+        repl
+    }
+
+    #[test]
+    fn variants_are_keywords_to_scripts() {
+        let result = repl().eval_all(&[
+            "[(lookup \"home\") (lookup \"away\") (found? :ok) (found? (lookup \"away\"))]",
+        ]);
+        assert_eq!("[:ok :not-found true false]", result.to_string());
+        assert_eq!(
+            "[:ok :not-found :http2-only]",
+            Value::from(vec![Status::Ok, Status::NotFound, Status::Http2Only]).to_string()
+        );
+    }
+
+    #[test]
+    fn anything_else_is_a_condition() {
+        let repl = repl();
+        assert_eq!(
+            Value::Condition(
+                "No Status for :fine;  expected :ok,  :not-found or :http2-only".into()
+            ),
+            repl.eval_all(&["(found? :fine)"])
+        );
+        assert_eq!(
+            Value::Condition(
+                "No Status for :my/ok;  expected :ok,  :not-found or :http2-only".into()
+            ),
+            repl.eval_all(&["(found? :my/ok)"])
+        );
+        assert!(matches!(
+            repl.eval_all(&["(found? \"ok\")"]),
+            Value::Condition(_)
+        ));
+    }
+}
@@ -1,25 +1,90 @@
+use crate::compiler::{self, Code};
 use crate::environment::Environment;
+use crate::error_message;
 use crate::ifn::IFn;
-use crate::persistent_list::ToPersistentList;
+use crate::persistent_list::{PersistentList, ToPersistentList, ToPersistentListIter};
 use crate::symbol::Symbol;
+use crate::tail_position;
 use crate::value::{Evaluable, ToValue, Value};
-use std::rc::Rc;
+use std::sync::{Arc, OnceLock};
 
 #[derive(Debug, Clone)]
 pub struct Fn {
-    pub body: Rc<Value>,
+    pub body: Arc<Value>,
     // Closed over variables
-    pub enclosing_environment: Rc<Environment>,
+    pub enclosing_environment: Arc<Environment>,
     pub arg_syms: Vec<Symbol>,
+    // Its body compiled,  once it's been called;  None if it can't be.  See compiler
+    pub code: Arc<OnceLock<Option<Code>>>,
+    // The var it was first defined as
+    pub name: Option<Symbol>,
+}
+impl Fn {
+    /// What (fn params & body) makes,  closing over environment;  given arity,  the list
+    /// of params and body.  params are symbols,  the last of which takes the rest of the
+    /// args if it follows &
+    pub fn new(environment: &Arc<Environment>, arity: &Arc<PersistentList>) -> Result<Fn, Value> {
+        let items = arity.iter().collect::<Vec<Arc<Value>>>();
+        let (params, body) = match items.split_first() {
+            Some(split) => split,
+            None => return Err(Value::Condition("Parameter declaration missing".into())),
+        };
+        let arg_syms = match &**params {
+            Value::PersistentVector(pvector) => pvector
+                .iter()
+                .filter_map(|val| match &**val {
+                    Value::Symbol(sym) => Some(Symbol::clone(sym)),
+                    _ => None,
+                })
+                .collect::<Vec<Symbol>>(),
+            _ => {
+                return Err(Value::Condition(
+                    format!("Parameter declaration should be a vector, not {}", params).into(),
+                ))
+            }
+        };
+        tail_position::check_fn(environment, arity, &arg_syms)?;
+        let body = match body {
+            // (fn [x y] ) -> nil
+            [] => Arc::new(Value::Nil),
+            // (fn [x y] expr) -> expr
+            [expr] => Arc::clone(expr),
+            // (fn [x y] expr1 expr2 expr3) -> (do expr1 expr2 expr3)
+            exprs => {
+                let mut do_body = vec![Symbol::intern("do").to_rc_value()];
+                do_body.extend_from_slice(exprs);
+                do_body.into_list().to_rc_value()
+            }
+        };
+        Ok(Fn {
+            body,
+            enclosing_environment: Arc::new(Environment::new_fn_environment(Arc::clone(
+                environment,
+            ))),
+            arg_syms,
+            code: Arc::default(),
+            name: None,
+        })
+    }
+    // How many args it takes before any rest args
+    fn fixed_arity(&self) -> usize {
+        self.arg_syms
+            .iter()
+            .position(|sym| sym.name == "&")
+            .unwrap_or(self.arg_syms.len())
+    }
+    fn is_variadic(&self) -> bool {
+        self.arg_syms.iter().any(|sym| sym.name == "&")
+    }
 }
 impl ToValue for Fn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for Fn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        let local_environment = Rc::new(Environment::new_local_environment(Rc::clone(
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let mut local_environment = Arc::new(Environment::new_local_environment(Arc::clone(
             &self.enclosing_environment,
         )));
 
@@ -31,17 +96,30 @@ impl IFn for Fn {
                 if sym.to_string() == "&" {
                     var_args = true;
                     let last_sym = self.arg_syms.get(argc - 1).unwrap();
-                    local_environment.insert(last_sym.clone(), Rc::new(Value::Nil));
+                    local_environment.insert(last_sym.clone(), Arc::new(Value::Nil));
                 }
             }
         }
 
         if !var_args && args.len() != argc {
-            return Value::Condition(format!(
-                "Wrong number of arguments given to function (Given: {}, Expected: {})",
-                args.len(),
-                argc
-            ));
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to function (Given: {}, Expected: {})",
+                    args.len(),
+                    argc
+                )
+                .into(),
+            );
+        }
+        // Given fewer args than it has before &,  the ones left out are looked up around it;
+        // as only the uncompiled body does
+        if compiler::enabled() && args.len() + 2 >= argc {
+            let code = self.code.get_or_init(|| {
+                compiler::compile(&self.arg_syms, &self.body, &self.enclosing_environment)
+            });
+            if let Some(code) = code {
+                return code.run(&self.enclosing_environment, args);
+            }
         }
 
         for (i, arg) in args.iter().enumerate() {
@@ -49,7 +127,7 @@ impl IFn for Fn {
             // We can bind the rest of the arguments, then, to the next variable and blow this popsicle stand
             if curr_sym.to_string() == "&" {
                 if !var_args {
-                    return Value::Condition(String::from("Invalid function argument '&' in non-variable-argument function definition"));
+                    return Value::Condition("Invalid function argument '&' in non-variable-argument function definition".into());
                 }
                 let last_sym = self.arg_syms.get(i + 1).unwrap();
                 let rest_args = args.get(i..).unwrap().to_vec().into_list().to_rc_value();
@@ -58,6 +136,206 @@ impl IFn for Fn {
             }
             local_environment.insert(curr_sym.clone(), arg.to_rc_value());
         }
-        self.body.eval(local_environment)
+        loop {
+            let vals = match self.body.eval(Arc::clone(&local_environment)) {
+                Value::Recur(vals) => vals,
+                result => return result,
+            };
+            // As in Clojure,  recur gives the rest args of a variadic fn as one seq;  so it
+            // takes exactly one value per symbol,  with & not counting
+            let syms = self
+                .arg_syms
+                .iter()
+                .filter(|sym| sym.to_string() != "&")
+                .collect::<Vec<&Symbol>>();
+            if vals.len() != syms.len() {
+                return error_message::mismatched_recur_count(syms.len(), vals.len());
+            }
+            local_environment = Arc::new(Environment::new_local_environment(Arc::clone(
+                &self.enclosing_environment,
+            )));
+            for (sym, val) in syms.into_iter().zip(vals.iter()) {
+                local_environment.insert(sym.clone(), Arc::clone(val));
+            }
+        }
+    }
+    fn arglists(&self) -> Option<Vec<Vec<Symbol>>> {
+        Some(vec![self.arg_syms.clone()])
+    }
+    fn name(&self) -> Option<&Symbol> {
+        self.name.as_ref()
+    }
+    fn named(&self, name: Symbol) -> Option<Arc<dyn IFn>> {
+        Some(Arc::new(Fn {
+            name: Some(name),
+            ..self.clone()
+        }))
+    }
+}
+
+/// A fn with more than one arity,  as (fn ([x] ..) ([x y] ..) ([x y & more] ..)) makes.  A
+/// call goes to the arity taking exactly as many args as it's given,  or else to the
+/// variadic one;  and a recur in an arity goes round that arity again
+#[derive(Debug, Clone)]
+pub struct MultiArityFn {
+    arities: Vec<Fn>,
+    name: Option<Symbol>,
+}
+impl MultiArityFn {
+    /// A fn of arities;  so long as no two take the same number of args,  only one takes
+    /// rest args,  and none takes more fixed args than that one
+    pub fn new(arities: Vec<Fn>) -> Result<MultiArityFn, Value> {
+        let variadic = arities
+            .iter()
+            .filter(|arity| arity.is_variadic())
+            .collect::<Vec<&Fn>>();
+        if variadic.len() > 1 {
+            return Err(Value::Condition(
+                "Can't have more than 1 variadic overload".into(),
+            ));
+        }
+        let fixed = arities
+            .iter()
+            .filter(|arity| !arity.is_variadic())
+            .map(Fn::fixed_arity)
+            .collect::<Vec<usize>>();
+        if (1..fixed.len()).any(|ind| fixed[ind..].contains(&fixed[ind - 1])) {
+            return Err(Value::Condition(
+                "Can't have 2 overloads with same arity".into(),
+            ));
+        }
+        if let Some(variadic) = variadic.first() {
+            if fixed.iter().any(|argc| *argc > variadic.fixed_arity()) {
+                return Err(Value::Condition(
+                    "Can't have fixed arity function with more params than variadic function"
+                        .into(),
+                ));
+            }
+        }
+        Ok(MultiArityFn {
+            arities,
+            name: None,
+        })
+    }
+}
+impl ToValue for MultiArityFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MultiArityFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let argc = args.len();
+        let arity = self
+            .arities
+            .iter()
+            .find(|arity| !arity.is_variadic() && arity.fixed_arity() == argc)
+            .or_else(|| {
+                self.arities
+                    .iter()
+                    .find(|arity| arity.is_variadic() && arity.fixed_arity() <= argc)
+            });
+        match arity {
+            Some(arity) => arity.invoke(args),
+            None => {
+                let arglists = self
+                    .arities
+                    .iter()
+                    .map(|arity| {
+                        let params = arity.arg_syms.iter().map(Symbol::to_string);
+                        format!("[{}]", params.collect::<Vec<String>>().join(" "))
+                    })
+                    .collect::<Vec<String>>();
+                Value::Condition(
+                    format!(
+                        "Wrong number of arguments given to function (Given: {}, Expected: {})",
+                        argc,
+                        arglists.join(" ")
+                    )
+                    .into(),
+                )
+            }
+        }
+    }
+    fn arglists(&self) -> Option<Vec<Vec<Symbol>>> {
+        Some(
+            self.arities
+                .iter()
+                .map(|arity| arity.arg_syms.clone())
+                .collect(),
+        )
+    }
+    fn name(&self) -> Option<&Symbol> {
+        self.name.as_ref()
+    }
+    fn named(&self, name: Symbol) -> Option<Arc<dyn IFn>> {
+        Some(Arc::new(MultiArityFn {
+            name: Some(name),
+            ..self.clone()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn calls_go_to_the_arity_taking_that_many_args() {
+        let result = Repl::default().eval_all(&[
+            "(defn f ([] :none) ([x] [x]) ([x y & more] [x y more]))",
+            "[(f) (f 1) (f 1 2) (f 1 2 3 4)]",
+        ]);
+        assert_eq!("[:none [1] [1 2 nil] [1 2 (3 4)]]", result.to_string());
+    }
+
+    #[test]
+    fn recur_goes_round_its_own_arity() {
+        let result = Repl::default().eval_all(&[
+            "(defn fact ([n] (fact n 1)) ([n acc] (if (< n 2) acc (recur (dec n) (* acc n)))))",
+            "(fact 5)",
+        ]);
+        assert_eq!("120", result.to_string());
+    }
+
+    #[test]
+    fn arities_must_be_told_apart() {
+        for (source, condition) in [
+            (
+                "(fn ([x] 1) ([y] 2))",
+                "Can't have 2 overloads with same arity",
+            ),
+            (
+                "(fn ([x & xs] 1) ([x & ys] 2))",
+                "Can't have more than 1 variadic overload",
+            ),
+            (
+                "(fn ([x & xs] 1) ([x y z] 2))",
+                "Can't have fixed arity function with more params than variadic function",
+            ),
+            (
+                "((fn ([x] 1) ([x y] 2)))",
+                "Wrong number of arguments given to function (Given: 0, Expected: [x] [x y])",
+            ),
+        ] {
+            let result = Repl::default().eval_all(&[source]);
+            assert_eq!(format!("#Condition[\"{}\"]", condition), result.to_string());
+        }
+    }
+
+    #[test]
+    fn fns_print_as_the_var_they_were_first_defined_as() {
+        let result = Repl::default().eval_all(&[
+            "(defn f [x] x)",
+            "(defn g ([] 1) ([x] x))",
+            "(def h f)",
+            "(ns other)",
+            "(def k (fn [] 1))",
+            "[user/f user/g user/h k (fn [] 1) (str user/f) (ex-message (try (nth user/g 0) (catch Exception e e)))]",
+        ]);
+        assert_eq!(
+            "[#function[user/f] #function[user/g] #function[user/f] #function[other/k] #function[] \"#function[user/f]\" \"Type mismatch; Expected instance of clojure.lang.ISeq,  Recieved type #function[user/g]\"]",
+            result.to_string()
+        );
     }
 }
@@ -0,0 +1,432 @@
+//! A lazy sequence,  as made by lazy-seq and the sequence fns (map, filter, range ..)
+//!
+//! A lazy seq holds a thunk that isn't called until something first looks into the seq.
+//! What it gives back is then kept as a step;  the first element, and the rest of the seq,
+//! which is usually lazy itself.  So a lazy seq is realized one element at a time, and only
+//! as far as it's walked,  which is what lets (take 5 (range)) finish
+use crate::error_message;
+use crate::interrupt;
+use crate::ireduce;
+use crate::persistent_list::PersistentList;
+use crate::persistent_list::PersistentList::{Cons, Empty};
+use crate::persistent_vector::PersistentVector;
+use crate::seqable::{self, Seqable};
+use crate::value::{self, ToValue, Value};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, ThreadId};
+
+/// One realized step of a seq
+#[derive(Debug, Clone)]
+pub enum Step {
+    Done,
+    /// The first element,  and the rest of the seq
+    Next(Arc<Value>, Arc<Value>),
+}
+
+type Thunk = Box<dyn FnOnce() -> Result<Step, Value> + Send>;
+type Guard = Box<dyn Fn() -> Result<(), Value> + Send + Sync>;
+
+enum State {
+    Unrealized(Thunk),
+    // Set while the thunk runs,  by the thread running it;  so a seq that needs itself to
+    // realize fails instead of waiting on itself forever.  Any other thread waits
+    Realizing(ThreadId),
+    Realized(Result<Step, Value>),
+}
+
+/// The i32s from start,  step apart,  up to but not including end;  as a range walks them
+#[derive(Debug, Clone, Copy)]
+pub struct Counted {
+    pub start: i32,
+    pub end: i32,
+    pub step: i32,
+}
+impl Iterator for Counted {
+    type Item = i32;
+    fn next(&mut self) -> Option<i32> {
+        let in_range = match self.step {
+            step if step > 0 => self.start < self.end,
+            step if step < 0 => self.start > self.end,
+            _ => false,
+        };
+        if !in_range {
+            return None;
+        }
+        let current = self.start;
+        // Past the end once it can't be represented
+        self.start = current.checked_add(self.step).unwrap_or(self.end);
+        Some(current)
+    }
+}
+
+pub struct LazySeq {
+    state: Mutex<State>,
+    // Signalled when the thunk's done,  for the threads waiting on it
+    realized: Condvar,
+    // What the seq counts through,  if it's a range that can be walked without realizing it
+    counted: Option<Counted>,
+    // Asked each time the seq's stepped,  realized or not;  see once.rs
+    guard: Option<Guard>,
+}
+impl LazySeq {
+    /// A seq whose first step is whatever thunk returns,  once something asks for it
+    pub fn new(thunk: impl FnOnce() -> Result<Step, Value> + Send + 'static) -> LazySeq {
+        LazySeq {
+            state: Mutex::new(State::Unrealized(Box::new(thunk))),
+            realized: Condvar::new(),
+            counted: None,
+            guard: None,
+        }
+    }
+    /// This seq,  known to be the numbers counted gives;  so reduce and the like can count
+    /// them out directly,  rather than step through the seq
+    pub fn with_counted(mut self, counted: Counted) -> LazySeq {
+        self.counted = Some(counted);
+        self
+    }
+    /// This seq,  stepped only if guard says it may be;  each time,  not just the first
+    pub fn with_guard(
+        mut self,
+        guard: impl Fn() -> Result<(), Value> + Send + Sync + 'static,
+    ) -> LazySeq {
+        self.guard = Some(Box::new(guard));
+        self
+    }
+    pub fn counted(&self) -> Option<&Counted> {
+        self.counted.as_ref()
+    }
+    /// An already realized seq of first followed by rest;  that is, a cons cell onto any seq
+    pub fn cons(first: Arc<Value>, rest: Arc<Value>) -> LazySeq {
+        LazySeq {
+            state: Mutex::new(State::Realized(Ok(Step::Next(first, rest)))),
+            realized: Condvar::new(),
+            counted: None,
+            guard: None,
+        }
+    }
+    // Not ToValue,  as a LazySeq can't be cloned without losing what it has realized
+    pub fn into_value(self) -> Value {
+        Value::LazySeq(Arc::new(self))
+    }
+    pub fn is_realized(&self) -> bool {
+        matches!(&*self.state.lock().unwrap(), State::Realized(_))
+    }
+    /// The first step of this seq,  realizing it if it hasn't been yet.  If the thunk fails,
+    /// its condition is kept and given back every time
+    pub fn step(&self) -> Result<Step, Value> {
+        if let Some(guard) = &self.guard {
+            guard()?;
+        }
+        let realizer = thread::current().id();
+        let mut state = self.state.lock().unwrap();
+        loop {
+            match &*state {
+                State::Realized(result) => return result.clone(),
+                State::Realizing(thread) if *thread == realizer => {
+                    return Err(Value::Condition(
+                        "Lazy seq needed its own value while being realized".into(),
+                    ))
+                }
+                State::Realizing(_) => state = self.realized.wait(state).unwrap(),
+                State::Unrealized(_) => break,
+            }
+        }
+        let thunk = match mem::replace(&mut *state, State::Realizing(realizer)) {
+            State::Unrealized(thunk) => thunk,
+            _ => unreachable!(),
+        };
+        // Not held while the thunk runs,  as it may well look at other seqs
+        drop(state);
+        let result = thunk();
+        *self.state.lock().unwrap() = State::Realized(result.clone());
+        self.realized.notify_all();
+        result
+    }
+    pub fn iter(&self) -> SeqIter {
+        SeqIter(Walk::Stepped(self.step()))
+    }
+}
+
+/// The first step of any seqable value;  nil and empty collections are Done
+pub fn step(val: &Arc<Value>) -> Result<Step, Value> {
+    match &**val {
+        Value::Nil => Ok(Step::Done),
+        Value::LazySeq(lseq) => lseq.step(),
+        Value::PersistentList(plist) => match &**plist {
+            Cons(head, tail, _) => Ok(Step::Next(
+                Arc::clone(head),
+                Value::PersistentList(Arc::clone(tail)).to_rc_value(),
+            )),
+            Empty => Ok(Step::Done),
+        },
+        Value::Condition(_) => Err((**val).clone()),
+        _ => match seqable::seqable(val) {
+            // Copied out into a list once, so walking the rest of it is cheap
+            Some(seqable) => step(&seqable.seq_iter().collect::<PersistentList>().to_rc_value()),
+            None => Err(error_message::not_seqable(val)),
+        },
+    }
+}
+
+/// Calls f on each element of any seqable value,  stopping at the first error;  whether
+/// that's f's, or the seq's own in realizing itself.  Whatever's IReduce is walked
+/// directly,  with no seq made of it along the way
+pub fn try_for_each(
+    coll: &Arc<Value>,
+    mut f: impl FnMut(Arc<Value>) -> Result<(), Value>,
+) -> Result<(), Value> {
+    let interrupted = || {
+        if interrupt::is_interrupted() {
+            return Err(Value::Condition(interrupt::INTERRUPTED_MSG.into()));
+        }
+        Ok(())
+    };
+    if let Some(ireduce) = ireduce::ireduce(coll) {
+        return ireduce.reduce_each(&mut |val| {
+            interrupted()?;
+            f(val)
+        });
+    }
+    let mut coll = Arc::clone(coll);
+    while let Step::Next(first, rest) = step(&coll)? {
+        interrupted()?;
+        f(first)?;
+        coll = rest;
+    }
+    Ok(())
+}
+
+/// How many elements a chunk is;  what map and filter realize at once from a chunked source
+pub const CHUNK_SIZE: usize = 32;
+
+/// A source that can be realized a chunk at a time,  rather than an element at a time;  a
+/// vector from some index,  or a range of i32s
+pub enum Chunks {
+    Vector(Arc<PersistentVector>, usize),
+    Counted(Counted),
+}
+impl Chunks {
+    /// The chunks of val,  if it's chunked
+    pub fn of(val: &Value) -> Option<Chunks> {
+        match val {
+            Value::PersistentVector(pvector) => Some(Chunks::Vector(Arc::clone(pvector), 0)),
+            Value::LazySeq(lseq) => lseq.counted().copied().map(Chunks::Counted),
+            _ => None,
+        }
+    }
+    /// The next up to CHUNK_SIZE elements,  and the chunks after them;  None once there are
+    /// no more
+    pub fn next_chunk(self) -> Option<(Vec<Arc<Value>>, Chunks)> {
+        let (chunk, rest) = match self {
+            Chunks::Vector(pvector, start) => {
+                let end = pvector.len().min(start + CHUNK_SIZE);
+                let chunk = (start..end)
+                    .filter_map(|i| pvector.nth(i).cloned())
+                    .collect::<Vec<Arc<Value>>>();
+                (chunk, Chunks::Vector(pvector, end))
+            }
+            Chunks::Counted(mut counted) => {
+                let chunk = counted
+                    .by_ref()
+                    .take(CHUNK_SIZE)
+                    .map(|i| Value::I32(i).to_rc_value())
+                    .collect::<Vec<Arc<Value>>>();
+                (chunk, Chunks::Counted(counted))
+            }
+        };
+        if chunk.is_empty() {
+            return None;
+        }
+        Some((chunk, rest))
+    }
+}
+
+/// The first step of a seq of chunk's elements followed by rest's;  realized already,  but
+/// for rest
+pub fn chunk_step(mut chunk: Vec<Arc<Value>>, mut rest: Arc<Value>) -> Result<Step, Value> {
+    if chunk.is_empty() {
+        return step(&rest);
+    }
+    while chunk.len() > 1 {
+        let last = chunk.pop().unwrap();
+        rest = Arc::new(LazySeq::cons(last, rest).into_value());
+    }
+    Ok(Step::Next(chunk.pop().unwrap(), rest))
+}
+
+/// Walks any seqable value,  realizing it as it goes
+pub fn iter(val: Arc<Value>) -> SeqIter {
+    SeqIter(Walk::Rest(val))
+}
+
+/// If realizing the seq fails part way,  the walk ends by giving the condition it failed with
+pub struct SeqIter(Walk);
+enum Walk {
+    Stepped(Result<Step, Value>),
+    Rest(Arc<Value>),
+    Ended,
+}
+impl Iterator for SeqIter {
+    type Item = Arc<Value>;
+    fn next(&mut self) -> Option<Arc<Value>> {
+        // Checked once per element, as the seq being walked may never end
+        if interrupt::is_interrupted() {
+            self.0 = Walk::Ended;
+            return Some(Arc::new(Value::Condition(
+                interrupt::INTERRUPTED_MSG.into(),
+            )));
+        }
+        let stepped = match mem::replace(&mut self.0, Walk::Ended) {
+            Walk::Stepped(stepped) => stepped,
+            Walk::Rest(rest) => step(&rest),
+            Walk::Ended => return None,
+        };
+        match stepped {
+            Ok(Step::Next(first, rest)) => {
+                self.0 = Walk::Rest(rest);
+                Some(first)
+            }
+            Ok(Step::Done) => None,
+            Err(condition) => Some(Arc::new(condition)),
+        }
+    }
+}
+
+// A long realized seq is a long chain of Arcs;  dropping it the default way recurses once per
+// element, and can overflow the stack.  So we unlink the chain here,  one element at a time
+impl Drop for LazySeq {
+    fn drop(&mut self) {
+        let mut rest = match self.state.get_mut().unwrap() {
+            State::Realized(Ok(Step::Next(_, rest))) => mem::replace(rest, Arc::new(Value::Nil)),
+            _ => return,
+        };
+        while let Ok(Value::LazySeq(lseq)) = Arc::try_unwrap(rest) {
+            match Arc::try_unwrap(lseq) {
+                Ok(mut lseq) => match lseq.state.get_mut().unwrap() {
+                    State::Realized(Ok(Step::Next(_, next))) => {
+                        rest = mem::replace(next, Arc::new(Value::Nil))
+                    }
+                    _ => return,
+                },
+                Err(_) => return,
+            }
+        }
+    }
+}
+impl fmt::Debug for LazySeq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazySeq")
+            .field("realized", &self.is_realized())
+            .finish()
+    }
+}
+impl Seqable for LazySeq {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(self.iter())
+    }
+}
+impl PartialEq for LazySeq {
+    fn eq(&self, other: &LazySeq) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+impl Hash for LazySeq {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        value::hash_ordered(self.iter(), state)
+    }
+}
+impl fmt::Display for LazySeq {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let str = self
+            .iter()
+            .map(|rc_arg| rc_arg.to_string_explicit())
+            .collect::<Vec<std::string::String>>()
+            .join(" ");
+        write!(f, "({})", str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lazy_seq::{self, Chunks, Counted, LazySeq, Step};
+    use crate::persistent_vector::PersistentVector;
+    use crate::repl::Repl;
+    use crate::value::{ToValue, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn thunk_runs_once_and_only_when_asked() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&calls);
+        let lseq = LazySeq::new(move || {
+            counted.fetch_add(1, Ordering::SeqCst);
+            Ok(Step::Next(Arc::new(Value::I32(1)), Arc::new(Value::Nil)))
+        });
+        assert_eq!(0, calls.load(Ordering::SeqCst));
+        assert!(!lseq.is_realized());
+        assert_eq!("(1)", lseq.to_string());
+        assert_eq!("(1)", lseq.to_string());
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+        assert!(lseq.is_realized());
+    }
+
+    #[test]
+    fn dropping_a_long_seq_does_not_overflow() {
+        let mut lseq = LazySeq::cons(Arc::new(Value::I32(0)), Arc::new(Value::Nil));
+        for i in 1..1_000_000 {
+            lseq = LazySeq::cons(Arc::new(Value::I32(i)), Arc::new(lseq.into_value()));
+        }
+        drop(lseq);
+    }
+
+    #[test]
+    fn chunks_come_32_at_a_time() {
+        let counted = Counted {
+            start: 0,
+            end: 40,
+            step: 1,
+        };
+        let (chunk, rest) = Chunks::Counted(counted).next_chunk().unwrap();
+        assert_eq!(32, chunk.len());
+        let (chunk, rest) = rest.next_chunk().unwrap();
+        assert_eq!(vec![Value::I32(32).to_rc_value()], chunk[..1].to_vec());
+        assert_eq!(8, chunk.len());
+        assert!(rest.next_chunk().is_none());
+        let pvector = (0..3)
+            .map(|i| Value::I32(i).to_rc_value())
+            .collect::<PersistentVector>();
+        let (chunk, rest) = Chunks::of(&pvector.to_value())
+            .unwrap()
+            .next_chunk()
+            .unwrap();
+        assert_eq!(3, chunk.len());
+        assert!(rest.next_chunk().is_none());
+        assert!(Chunks::of(&Value::Nil).is_none());
+    }
+
+    #[test]
+    fn a_chunk_steps_on_into_the_rest() {
+        let chunk = vec![Value::I32(1).to_rc_value(), Value::I32(2).to_rc_value()];
+        let rest = LazySeq::cons(Value::I32(3).to_rc_value(), Arc::new(Value::Nil));
+        let seq = match lazy_seq::chunk_step(chunk, Arc::new(rest.into_value())) {
+            Ok(Step::Next(first, rest)) => LazySeq::cons(first, rest),
+            _ => panic!("expected a step"),
+        };
+        assert_eq!("(1 2 3)", seq.to_string());
+    }
+
+    #[test]
+    fn map_and_filter_realize_a_chunk_at_a_time() {
+        let result = Repl::default().eval_all(&[
+            "(def calls (atom 0))",
+            "(def xs (map (fn [x] (swap! calls inc) x) (range 100)))",
+            "(first xs)",
+            "[@calls (first (filter (fn [x] (swap! calls inc) (> x 40)) (into [] (range 100)))) @calls]",
+        ]);
+        assert_eq!("[32 41 96]", result.to_string());
+    }
+}
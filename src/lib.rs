@@ -0,0 +1,111 @@
+//! ClojureRS,  a Clojure interpreter;  run as the rust_clojure binary,  see cli for its
+//! commands,  or embedded in a Rust program.  Embedded,  a Repl evaluates code,  with what
+//! the host registers with it (see Repl::register_fn) defined for that code to use
+#[macro_use]
+extern crate nom;
+extern crate itertools;
+
+mod agent;
+mod allocations;
+mod atom;
+mod channel;
+mod cli;
+mod clojure_core_async;
+mod clojure_core_cache;
+mod clojure_data;
+mod clojure_edn;
+mod clojure_java_io;
+mod clojure_java_shell;
+mod clojure_pprint;
+mod clojure_profile;
+mod clojure_protocol;
+mod clojure_repl;
+mod clojure_set;
+mod clojure_spec;
+mod clojure_std;
+mod clojure_string;
+mod clojure_term_colors;
+mod clojure_term_progress;
+mod clojure_test;
+mod clojure_tools_cli;
+mod clojure_tools_logging;
+mod clojure_walk;
+mod command;
+mod compiler;
+mod completion;
+mod condition;
+mod conformance;
+mod data_readers;
+mod dynamic;
+mod edn;
+mod encoding;
+mod environment;
+mod error_message;
+mod formatter;
+mod future;
+mod hamt;
+mod hierarchy;
+pub mod host_object;
+pub mod host_trait;
+mod ideref;
+pub mod ifn;
+mod inst;
+mod interrupt;
+mod ireduce;
+mod iterable;
+mod keyword;
+pub mod keyword_enum;
+mod lambda;
+mod lazy_seq;
+mod line_editor;
+mod linter;
+mod locale;
+mod maps;
+mod meta;
+#[cfg(test)]
+mod model_tests;
+mod multi_fn;
+mod namespace;
+mod native_fn;
+mod nrepl;
+pub mod nrepl_client;
+mod numbers;
+mod options;
+mod output;
+mod pattern;
+mod persistent_hash_set;
+mod persistent_list;
+mod persistent_list_map;
+mod persistent_queue;
+mod persistent_tree_map;
+mod persistent_tree_set;
+mod persistent_vector;
+mod print_order;
+mod promise;
+mod protocol;
+mod reader;
+mod record;
+pub mod repl;
+mod rust_core;
+mod seqable;
+mod shared_str;
+mod shutdown;
+mod signal;
+mod socket_repl;
+mod stm;
+mod string_builder;
+mod symbol;
+mod tail_position;
+mod timer;
+mod trace;
+mod transcript;
+mod transducer;
+mod transient;
+mod type_tag;
+mod util;
+mod uuid;
+pub mod value;
+mod var;
+mod worker_pool;
+
+pub use command::main;
@@ -0,0 +1,518 @@
+//! Line editing for the REPL on a terminal
+//!
+//! The line being typed can be moved about in and edited;  up and down step through the
+//! lines entered before,  this session's and those kept in ~/.clojure_rs_history,  and
+//! Ctrl-C gives up on the line (and on the form it was part of) without leaving the REPL.
+//! A form still open at the end of a line carries on on the next,  after a continuation
+//! prompt.  The keys are Emacs' and readline's:
+//!
+//!   left right,  Ctrl-B Ctrl-F    move back and forward a character
+//!   Home End,  Ctrl-A Ctrl-E      move to the start and end of the line
+//!   up down,  Ctrl-P Ctrl-N       step back and forward through the history
+//!   Backspace,  Delete            delete the character before and under the cursor
+//!   Ctrl-U Ctrl-K                 delete up to and from the cursor
+//!   Tab                           complete the name before the cursor;  or if it could be
+//!                                 several,  as far as they agree,  and list them
+//!   Ctrl-D                        delete the character under the cursor;  or on an empty
+//!                                 line,  end the REPL
+//!
+//! The terminal's raw only while a line's being read;  so while a form's evaluated,  Ctrl-C
+//! stops us as it always has
+use crate::reader::StreamReader;
+use crate::value::Value;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::path::PathBuf;
+
+// How many of the lines entered before are kept
+const HISTORY_SIZE: usize = 1000;
+
+/// What reading a line came to
+#[derive(Debug, PartialEq)]
+pub enum Line {
+    Entered(String),
+    Interrupted,
+    Eof,
+}
+
+#[derive(Debug, PartialEq)]
+enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,
+    End,
+    Up,
+    Down,
+    KillToStart,
+    KillToEnd,
+    Complete,
+    Interrupt,
+    EndOfInput,
+    Ignored,
+}
+
+// The next key pressed;  None if there are no more
+fn key(input: &mut impl Iterator<Item = io::Result<u8>>) -> io::Result<Option<Key>> {
+    let byte = match input.next() {
+        Some(byte) => byte?,
+        None => return Ok(None),
+    };
+    Ok(Some(match byte {
+        b'\r' | b'\n' => Key::Enter,
+        1 => Key::Home,
+        2 => Key::Left,
+        3 => Key::Interrupt,
+        4 => Key::EndOfInput,
+        5 => Key::End,
+        6 => Key::Right,
+        8 | 127 => Key::Backspace,
+        9 => Key::Complete,
+        11 => Key::KillToEnd,
+        14 => Key::Down,
+        16 => Key::Up,
+        21 => Key::KillToStart,
+        // ESC [ or ESC O,  and what says which key it is
+        27 => match input.next().transpose()? {
+            Some(b'[') | Some(b'O') => match input.next().transpose()? {
+                Some(b'A') => Key::Up,
+                Some(b'B') => Key::Down,
+                Some(b'C') => Key::Right,
+                Some(b'D') => Key::Left,
+                Some(b'H') => Key::Home,
+                Some(b'F') => Key::End,
+                // ESC [ n ~
+                Some(n @ b'0'..=b'9') => match (n, input.next().transpose()?) {
+                    (b'1', Some(b'~')) | (b'7', Some(b'~')) => Key::Home,
+                    (b'4', Some(b'~')) | (b'8', Some(b'~')) => Key::End,
+                    (b'3', Some(b'~')) => Key::Delete,
+                    _ => Key::Ignored,
+                },
+                _ => Key::Ignored,
+            },
+            _ => Key::Ignored,
+        },
+        byte if byte < 32 => Key::Ignored,
+        byte if byte < 128 => Key::Char(byte as char),
+        // The first byte of a UTF-8 encoded character;  the rest follow
+        byte => {
+            let len = match byte {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                _ => 4,
+            };
+            let mut bytes = vec![byte];
+            for _ in 1..len {
+                match input.next() {
+                    Some(byte) => bytes.push(byte?),
+                    None => break,
+                }
+            }
+            match std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|c| c.chars().next())
+            {
+                Some(c) => Key::Char(c),
+                None => Key::Ignored,
+            }
+        }
+    }))
+}
+
+// What a name could be completed with
+type Completer = Box<dyn Fn(&str) -> Vec<String>>;
+
+// Whether c ends a name;  whitespace,  or what the reader reads on its own
+fn ends_name(c: char) -> bool {
+    c.is_whitespace() || "()[]{}\"',;@^`~".contains(c)
+}
+
+/// Reads lines from a terminal,  letting them be edited as they're typed;  and remembers
+/// them,  in history_file if there is one
+pub struct LineEditor {
+    history: Vec<String>,
+    history_file: Option<PathBuf>,
+    complete: Option<Completer>,
+}
+impl LineEditor {
+    /// An editor with the lines kept in history_file,  if it has any,  to step back through
+    pub fn new(history_file: Option<PathBuf>) -> LineEditor {
+        let mut history = history_file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .map(|text| text.lines().map(String::from).collect::<Vec<String>>())
+            .unwrap_or_default();
+        history.drain(..history.len().saturating_sub(HISTORY_SIZE));
+        LineEditor {
+            history,
+            history_file,
+            complete: None,
+        }
+    }
+    /// This editor,  completing a name at Tab with what complete gives for it
+    pub fn completing(self, complete: impl Fn(&str) -> Vec<String> + 'static) -> LineEditor {
+        LineEditor {
+            complete: Some(Box::new(complete)),
+            ..self
+        }
+    }
+    /// Reads a line from the terminal,  after prompt
+    pub fn read_line(&mut self, prompt: &str) -> io::Result<Line> {
+        let _raw = raw::RawMode::enable()?;
+        let stdin = io::stdin();
+        let stdout = io::stdout();
+        let line = self.edit(prompt, &mut stdin.lock().bytes(), &mut stdout.lock())?;
+        if let Line::Entered(line) = &line {
+            self.remember(line);
+        }
+        Ok(line)
+    }
+    // Adds line to the history;  unless it's blank,  or the same as the line before
+    fn remember(&mut self, line: &str) {
+        if line.trim().is_empty() || self.history.last().is_some_and(|last| last == line) {
+            return;
+        }
+        self.history.push(line.to_string());
+        if let Some(file) = &self.history_file {
+            // Not being able to keep the history doesn't stop anyone typing
+            let _ = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(file)
+                .and_then(|mut file| writeln!(file, "{}", line));
+        }
+    }
+    // Completes the name line has before cursor;  as far as what it could be agree,  listing
+    // them on output if that's not all the way
+    fn complete(
+        &self,
+        line: &mut Vec<char>,
+        cursor: &mut usize,
+        output: &mut impl Write,
+    ) -> io::Result<()> {
+        let complete = match &self.complete {
+            Some(complete) => complete,
+            None => return Ok(()),
+        };
+        let start = line[..*cursor]
+            .iter()
+            .rposition(|c| ends_name(*c))
+            .map_or(0, |i| i + 1);
+        let name = line[start..*cursor].iter().collect::<String>();
+        if name.is_empty() {
+            return Ok(());
+        }
+        let candidates = complete(&name);
+        let mut agreed: Vec<char> = match candidates.first() {
+            Some(first) => first.chars().collect(),
+            None => return Ok(()),
+        };
+        for candidate in &candidates[1..] {
+            let common = agreed
+                .iter()
+                .zip(candidate.chars())
+                .take_while(|(a, b)| **a == *b)
+                .count();
+            agreed.truncate(common);
+        }
+        if agreed.len() > *cursor - start {
+            line.splice(start..*cursor, agreed.iter().copied());
+            *cursor = start + agreed.len();
+        } else if candidates.len() > 1 {
+            write!(output, "\r\n{}\r\n", candidates.join("  "))?;
+        }
+        Ok(())
+    }
+    // The line typed as input,  after prompt;  echoed to output as it's edited
+    fn edit(
+        &self,
+        prompt: &str,
+        input: &mut impl Iterator<Item = io::Result<u8>>,
+        output: &mut impl Write,
+    ) -> io::Result<Line> {
+        let mut line: Vec<char> = vec![];
+        let mut cursor = 0;
+        // Where in the history we are,  and the line being typed before we went back in it
+        let mut recalled = self.history.len();
+        let mut typed: Vec<char> = vec![];
+        let mut recall =
+            |line: &mut Vec<char>, cursor: &mut usize, to: usize, recalled: &mut usize| {
+                if *recalled == self.history.len() {
+                    typed = line.clone();
+                }
+                *line = match self.history.get(to) {
+                    Some(entered) => entered.chars().collect(),
+                    None => typed.clone(),
+                };
+                *cursor = line.len();
+                *recalled = to;
+            };
+        loop {
+            let width = prompt.chars().count() + cursor;
+            write!(
+                output,
+                "\r{}{}\x1b[K\r",
+                prompt,
+                line.iter().collect::<String>()
+            )?;
+            if width > 0 {
+                write!(output, "\x1b[{}C", width)?;
+            }
+            output.flush()?;
+            let key = match key(input)? {
+                Some(key) => key,
+                None => return Ok(Line::Eof),
+            };
+            match key {
+                Key::Char(c) => {
+                    line.insert(cursor, c);
+                    cursor += 1;
+                }
+                Key::Enter => {
+                    write!(output, "\r\n")?;
+                    return Ok(Line::Entered(line.into_iter().collect()));
+                }
+                Key::Interrupt => {
+                    write!(output, "^C\r\n")?;
+                    return Ok(Line::Interrupted);
+                }
+                Key::EndOfInput if line.is_empty() => {
+                    write!(output, "\r\n")?;
+                    return Ok(Line::Eof);
+                }
+                Key::EndOfInput | Key::Delete if cursor < line.len() => {
+                    line.remove(cursor);
+                }
+                Key::Backspace if cursor > 0 => {
+                    cursor -= 1;
+                    line.remove(cursor);
+                }
+                Key::Left if cursor > 0 => cursor -= 1,
+                Key::Right if cursor < line.len() => cursor += 1,
+                Key::Home => cursor = 0,
+                Key::End => cursor = line.len(),
+                Key::KillToStart => {
+                    line.drain(..cursor);
+                    cursor = 0;
+                }
+                Key::KillToEnd => line.truncate(cursor),
+                Key::Complete => self.complete(&mut line, &mut cursor, output)?,
+                Key::Up if recalled > 0 => {
+                    recall(&mut line, &mut cursor, recalled - 1, &mut recalled)
+                }
+                Key::Down if recalled < self.history.len() => {
+                    recall(&mut line, &mut cursor, recalled + 1, &mut recalled)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// ~/.clojure_rs_history,  where the lines entered at the REPL are kept;  if there's a home
+/// directory to keep them in
+pub fn history_file() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".clojure_rs_history"))
+}
+
+/// What's typed at a LineEditor,  read as the reader reads anything else;  each line after
+/// the prompt,  or if it carries on a form,  the continuation prompt
+pub struct Typed {
+    editor: LineEditor,
+    prompt: String,
+    // Whether a line of the form being read has been given to the reader already
+    in_form: bool,
+    pending: io::Cursor<Vec<u8>>,
+    interrupted: bool,
+    // What's been typed past the last form read
+    forms: StreamReader,
+}
+impl Typed {
+    pub fn new(editor: LineEditor) -> Typed {
+        Typed {
+            editor,
+            prompt: String::new(),
+            in_form: false,
+            pending: io::Cursor::new(vec![]),
+            interrupted: false,
+            forms: StreamReader::default(),
+        }
+    }
+    /// The next form typed,  its first line after prompt;  starting over with the next one
+    /// if it's given up on with Ctrl-C
+    pub fn read_form(&mut self, prompt: &str) -> Value {
+        self.prompt = prompt.to_string();
+        loop {
+            self.in_form = !self.forms.is_between_forms();
+            self.interrupted = false;
+            let mut forms = std::mem::take(&mut self.forms);
+            let form = forms.read(self);
+            self.forms = forms;
+            if !self.interrupted {
+                return form;
+            }
+        }
+    }
+    // "#_=> ",  under the end of prompt
+    fn continuation_prompt(&self) -> String {
+        format!("{:>1$}", "#_=> ", self.prompt.chars().count())
+    }
+}
+impl Read for Typed {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+impl BufRead for Typed {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pending.position() as usize >= self.pending.get_ref().len() {
+            let prompt = match self.in_form {
+                true => self.continuation_prompt(),
+                false => self.prompt.clone(),
+            };
+            match self.editor.read_line(&prompt)? {
+                Line::Entered(line) => {
+                    self.pending = io::Cursor::new(format!("{}\n", line).into_bytes());
+                    self.in_form = true;
+                }
+                // Not an ErrorKind::Interrupted;  reading would just try again
+                Line::Interrupted => {
+                    self.interrupted = true;
+                    return Err(io::Error::other("Interrupted"));
+                }
+                Line::Eof => return Ok(&[]),
+            }
+        }
+        self.pending.fill_buf()
+    }
+    fn consume(&mut self, amt: usize) {
+        self.pending.consume(amt)
+    }
+}
+
+#[cfg(unix)]
+mod raw {
+    use std::io;
+
+    /// The terminal,  raw until this is dropped;  so each key pressed is read as it's
+    /// pressed,  and not echoed,  and Ctrl-C is read rather than interrupting us
+    pub struct RawMode {
+        original: libc::termios,
+    }
+    impl RawMode {
+        pub fn enable() -> io::Result<RawMode> {
+            // termios is plain data;  tcgetattr fills it in
+            let mut original: libc::termios = unsafe { std::mem::zeroed() };
+            if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let mut raw = original;
+            raw.c_iflag &= !(libc::BRKINT | libc::ICRNL | libc::INPCK | libc::ISTRIP | libc::IXON);
+            raw.c_lflag &= !(libc::ECHO | libc::ICANON | libc::IEXTEN | libc::ISIG);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSADRAIN, &raw) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(RawMode { original })
+        }
+    }
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSADRAIN, &self.original) };
+        }
+    }
+}
+
+// Elsewhere the terminal's left as it is;  lines can't be edited,  but they're still read
+#[cfg(not(unix))]
+mod raw {
+    use std::io;
+
+    pub struct RawMode;
+    impl RawMode {
+        pub fn enable() -> io::Result<RawMode> {
+            Ok(RawMode)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::line_editor::{Line, LineEditor};
+    use std::io;
+
+    // What editing gives,  typing keys;  with entered typed before
+    fn typing(keys: &str, entered: &[&str]) -> Line {
+        let editor = LineEditor {
+            history: entered.iter().map(|line| line.to_string()).collect(),
+            history_file: None,
+            complete: None,
+        }
+        .completing(|name| {
+            ["map", "mapcat", "mapv", "max"]
+                .iter()
+                .filter(|candidate| candidate.starts_with(name))
+                .map(|candidate| candidate.to_string())
+                .collect()
+        });
+        let mut input = keys.bytes().map(Ok::<u8, io::Error>);
+        editor.edit("user=> ", &mut input, &mut vec![]).unwrap()
+    }
+
+    #[test]
+    fn lines_are_edited_as_theyre_typed() {
+        assert_eq!(
+            Line::Entered(String::from("(+ 1 2)")),
+            typing("(+ 2\x1b[D1 \x1b[C)\r", &[])
+        );
+        assert_eq!(
+            Line::Entered(String::from("(é)")),
+            typing("x\x7f(\u{e9}\x01\x05)\r", &[])
+        );
+        assert_eq!(
+            Line::Entered(String::from("ab")),
+            typing("xab\x1b[H\x1b[3~\r", &[])
+        );
+        assert_eq!(Line::Interrupted, typing("(foo\x03", &[]));
+        assert_eq!(Line::Eof, typing("\x04", &[]));
+    }
+
+    #[test]
+    fn up_and_down_step_through_the_history() {
+        let entered = ["(def a 1)", "(inc a)"];
+        assert_eq!(
+            Line::Entered(String::from("(def a 1)")),
+            typing("\x1b[A\x1b[A\x1b[A\r", &entered)
+        );
+        assert_eq!(
+            Line::Entered(String::from("(inc a) 2")),
+            typing("\x1b[A\x1b[A\x1b[B 2\r", &entered)
+        );
+        assert_eq!(
+            Line::Entered(String::from("(dec")),
+            typing("(dec\x1b[A\x1b[B\r", &entered)
+        );
+    }
+
+    #[test]
+    fn tab_completes_the_name_before_the_cursor() {
+        assert_eq!(
+            Line::Entered(String::from("(mapcat f)")),
+            typing("(mapc\t f)\r", &[])
+        );
+        assert_eq!(Line::Entered(String::from("(map")), typing("(map\t\r", &[]));
+        assert_eq!(
+            Line::Entered(String::from("(mapv x)")),
+            typing("(m x)\x1b[D\x1b[D\x1b[D\tpv\r", &[])
+        );
+    }
+}
@@ -0,0 +1,817 @@
+//! `rust_clojure lint`;  finds likely mistakes in Clojure code,  without running it
+//!
+//! Each file is read with reader::read_lenient,  so a file with syntax errors is still
+//! checked as far as it can be read.  Forms are macroexpanded with the macros we know of,
+//! and then checked for
+//!
+//! - symbols that resolve to nothing;  neither a local,  nor something defined in the file
+//!   or in a namespace we have loaded
+//! - calls with a number of args none of the called fn's arglists take
+//! - let and loop bindings that are never used (unless named _something)
+//! - locals named after something in clojure.core,  which they hide
+//!
+//! Forms are only ever read;  the one thing evaluated is the macros' own code.  Where the
+//! problem is a symbol written out in the file,  it's reported at that symbol;  otherwise
+//! (say,  for a symbol a macro made up) at the top level form it's in
+use crate::clojure_walk::macroexpand_all::expand_call;
+use crate::environment::Environment;
+use crate::host_object;
+use crate::ifn;
+use crate::maps::MapEntry;
+use crate::meta;
+use crate::persistent_list::{PersistentList, ToPersistentListIter};
+use crate::persistent_list_map::ToPersistentListMapIter;
+use crate::reader::{self, Position};
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+
+pub const SYNTAX_ERROR: &str = "syntax-error";
+pub const UNRESOLVED_SYMBOL: &str = "unresolved-symbol";
+pub const ARITY_MISMATCH: &str = "arity-mismatch";
+pub const UNUSED_LOCAL: &str = "unused-local";
+pub const SHADOWED_CORE_NAME: &str = "shadowed-core-name";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub position: Position,
+    /// One of the kinds above
+    pub kind: &'static str,
+    pub message: String,
+}
+/// line:col: kind: message
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.position.line, self.position.col, self.kind, self.message
+        )
+    }
+}
+
+/// Everything wrong with source we can find,  in the order it appears.  environment is
+/// what symbols not defined in source are resolved in
+pub fn lint(environment: &Arc<Environment>, source: &str) -> Vec<Diagnostic> {
+    let read = reader::read_lenient(source);
+    let tokens = symbol_tokens(source);
+    let mut linter = Linter {
+        environment,
+        positions: HashMap::new(),
+        form_position: Position { line: 1, col: 1 },
+        defined: HashMap::new(),
+        macros: HashSet::new(),
+        ns: None,
+        refers_all: false,
+        scopes: vec![],
+        diagnostics: read
+            .errors
+            .into_iter()
+            .map(|error| Diagnostic {
+                position: error.position,
+                kind: SYNTAX_ERROR,
+                message: error.message,
+            })
+            .collect(),
+    };
+    for (ind, (position, form)) in read.forms.iter().enumerate() {
+        let form = Arc::new(form.clone());
+        // The tokens written out in this form;  those from where it starts to where the
+        // next one does
+        let next = read.forms.get(ind + 1).map(|(next, _)| *next);
+        let mut written = HashMap::<&str, VecDeque<Position>>::new();
+        for (token_position, name) in tokens.iter().filter(|(token_position, _)| {
+            *token_position >= *position && next.is_none_or(|next| *token_position < next)
+        }) {
+            written
+                .entry(name.as_str())
+                .or_default()
+                .push_back(*token_position);
+        }
+        let mut nodes = vec![];
+        symbol_nodes(&form, &mut nodes);
+        linter.positions = nodes
+            .iter()
+            .filter_map(|node| {
+                let position = match &**node {
+                    Value::Symbol(sym) => written.get_mut(sym.to_string().as_str())?.pop_front()?,
+                    _ => return None,
+                };
+                Some((Arc::as_ptr(node), position))
+            })
+            .collect();
+        linter.form_position = *position;
+        linter.form(&form, 0);
+    }
+    let mut diagnostics = linter.diagnostics;
+    diagnostics.sort_by_key(|diagnostic| diagnostic.position);
+    diagnostics
+}
+
+// Every symbol written out in source,  and where;  as it was written,  ie clojure.core/map
+fn symbol_tokens(source: &str) -> Vec<(Position, String)> {
+    let mut tokens = vec![];
+    let mut token: Option<(Position, String)> = None;
+    let mut in_string = false;
+    let mut in_comment = false;
+    let (mut line, mut col) = (1, 1);
+    for ch in source.chars().chain(std::iter::once('\n')) {
+        let ends_token = in_string || in_comment || ch.is_whitespace() || "()[]{}\",;".contains(ch);
+        if ends_token {
+            tokens.extend(token.take().filter(|(_, text)| is_symbol_token(text)));
+        }
+        match ch {
+            '\n' => in_comment = false,
+            _ if in_comment => {}
+            '"' => in_string = !in_string,
+            ';' if !in_string => in_comment = true,
+            _ if ends_token => {}
+            // Reader macro characters,  as in 'a or @a ,  aren't part of the symbol
+            '\'' | '@' | '^' | '#' | '`' | '~' if token.is_none() => {}
+            _ => match &mut token {
+                Some((_, text)) => text.push(ch),
+                None => token = Some((Position { line, col }, ch.to_string())),
+            },
+        }
+        if ch == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+    tokens
+}
+
+fn is_symbol_token(text: &str) -> bool {
+    let mut chars = text.chars();
+    let starts_number = match chars.next() {
+        Some('+') | Some('-') => chars.next().is_some_and(|ch| ch.is_ascii_digit()),
+        Some(ch) => ch.is_ascii_digit() || ch == ':',
+        None => true,
+    };
+    !starts_number && !matches!(text, "nil" | "true" | "false")
+}
+
+// Each symbol in form,  in the order they're written;  save for in sets,  whose order is
+// lost on reading
+fn symbol_nodes(form: &Arc<Value>, nodes: &mut Vec<Arc<Value>>) {
+    // Written before the form,  as in ^String s
+    if let Some(meta) = meta::meta(form) {
+        symbol_nodes(&meta, nodes);
+    }
+    match &**form {
+        Value::Symbol(_) => nodes.push(Arc::clone(form)),
+        Value::PersistentList(list) => {
+            let mut list: &PersistentList = list;
+            while let PersistentList::Cons(head, tail, _) = list {
+                symbol_nodes(head, nodes);
+                list = tail;
+            }
+        }
+        Value::PersistentVector(vector) => {
+            for val in vector.iter() {
+                symbol_nodes(val, nodes);
+            }
+        }
+        Value::PersistentListMap(map) => {
+            for MapEntry { key, val } in map.iter() {
+                symbol_nodes(&key, nodes);
+                symbol_nodes(&val, nodes);
+            }
+        }
+        Value::PersistentHashSet(set) => {
+            for val in set.iter() {
+                symbol_nodes(&val, nodes);
+            }
+        }
+        _ => {}
+    }
+}
+
+struct Local {
+    sym: Symbol,
+    node: Arc<Value>,
+    used: bool,
+    // Whether it's worth reporting,  should it go unused;  fn params often aren't
+    check_unused: bool,
+}
+
+struct Linter<'a> {
+    environment: &'a Arc<Environment>,
+    // Where each symbol written out in the form being linted is,  by address
+    positions: HashMap<*const Value, Position>,
+    form_position: Position,
+    // What the file has defined so far;  with its arglists,  if it's a fn that says
+    defined: HashMap<Symbol, Option<Vec<Vec<Symbol>>>>,
+    // The macros the file has defined;  which we can't expand,  as we don't run the file
+    macros: HashSet<Symbol>,
+    // The namespace the file declares
+    ns: Option<String>,
+    // Whether it refers all of some namespace,  as (:use x) does;  then any name might be
+    // one of those
+    refers_all: bool,
+    scopes: Vec<Vec<Local>>,
+    diagnostics: Vec<Diagnostic>,
+}
+impl Linter<'_> {
+    fn report(&mut self, node: &Arc<Value>, kind: &'static str, message: String) {
+        let position = self
+            .positions
+            .get(&Arc::as_ptr(node))
+            .copied()
+            .unwrap_or(self.form_position);
+        self.diagnostics.push(Diagnostic {
+            position,
+            kind,
+            message,
+        });
+    }
+
+    fn is_local(&self, sym: &Symbol) -> bool {
+        self.scopes.iter().flatten().any(|local| local.sym == *sym)
+    }
+
+    fn is_written(&self, node: &Arc<Value>) -> bool {
+        self.positions.contains_key(&Arc::as_ptr(node))
+    }
+
+    fn resolves(&self, sym: &Symbol) -> bool {
+        if sym.has_ns() {
+            if self.ns.as_deref() == Some(sym.ns.as_str()) {
+                return self.defined.contains_key(&sym.unqualified());
+            }
+            return match self.environment.find_namespace(&Symbol::intern(&sym.ns)) {
+                Some(ns) => !matches!(*ns.get(sym), Value::Condition(_)),
+                // An alias,  or a namespace we haven't loaded;  there's nothing to check
+                // it against
+                None => true,
+            };
+        }
+        self.defined.contains_key(sym)
+            || host_object::is_member(sym)
+            || self.refers_all
+            || TypeTag::from_name(&sym.name).is_some()
+            || !matches!(*self.environment.get(sym), Value::Condition(_))
+    }
+
+    fn symbol(&mut self, node: &Arc<Value>, sym: &Symbol) {
+        let local = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .flat_map(|scope| scope.iter_mut().rev())
+            .find(|local| local.sym == *sym);
+        if let Some(local) = local {
+            local.used = true;
+            return;
+        }
+        if !self.resolves(sym) {
+            self.report(
+                node,
+                UNRESOLVED_SYMBOL,
+                format!("Unable to resolve symbol: {}", sym),
+            );
+        }
+    }
+
+    fn form(&mut self, form: &Arc<Value>, depth: usize) {
+        match &**form {
+            Value::Symbol(sym) => self.symbol(form, sym),
+            Value::PersistentList(list) => {
+                let items = list.iter().collect::<Vec<Arc<Value>>>();
+                self.list(&items, depth);
+            }
+            // As macros build code with concat,  it may come as a seq;  which runs like the
+            // list it would print as
+            Value::LazySeq(lseq) => {
+                let items = lseq.iter().collect::<Vec<Arc<Value>>>();
+                self.list(&items, depth);
+            }
+            Value::PersistentVector(vector) => {
+                for val in vector.iter() {
+                    self.form(val, depth);
+                }
+            }
+            Value::PersistentListMap(map) => {
+                for MapEntry { key, val } in map.iter() {
+                    self.form(&key, depth);
+                    self.form(&val, depth);
+                }
+            }
+            Value::PersistentHashSet(set) => {
+                for val in set.iter() {
+                    self.form(&val, depth);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn forms(&mut self, forms: &[Arc<Value>], depth: usize) {
+        for form in forms {
+            self.form(form, depth);
+        }
+    }
+
+    fn list(&mut self, items: &[Arc<Value>], depth: usize) {
+        let head = match items.first() {
+            Some(head) => head,
+            None => return,
+        };
+        let sym = match &**head {
+            Value::Symbol(sym) if !self.is_local(sym) => sym,
+            _ => return self.forms(items, depth),
+        };
+        let args = &items[1..];
+        if !sym.has_ns() || sym.ns == "clojure.core" {
+            match sym.name.as_str() {
+                "quote" => return,
+                // do expands to a copy of its body,  which we'd lose the positions of
+                "do" => return self.forms(args, depth),
+                "fn" => return self.fn_form(args, depth),
+                "let" | "loop" => return self.let_form(args, depth),
+                "def" => return self.def_form(args, depth),
+                "defmacro" => {
+                    if let Some(Value::Symbol(name)) = args.first().map(|name| &**name) {
+                        self.defined.insert(Symbol::clone(name), None);
+                        self.macros.insert(Symbol::clone(name));
+                    }
+                    return self.fn_form(args.get(1..).unwrap_or_default(), depth);
+                }
+                "ns" => {
+                    if let Some(Value::Symbol(name)) = args.first().map(|name| &**name) {
+                        self.ns = Some(name.to_string());
+                    }
+                    for reference in args.iter().skip(1) {
+                        self.ns_reference(reference);
+                    }
+                    return;
+                }
+                "defprotocol*" | "deftype*" | "defmulti*" => {
+                    self.define_from(&sym.name, args);
+                    if sym.name == "defmulti*" {
+                        self.forms(args.get(1..).unwrap_or_default(), depth);
+                    }
+                    return;
+                }
+                "try" => {
+                    for arg in args {
+                        match &**arg {
+                            Value::PersistentList(clause) if arg.is_finally_clause() => {
+                                let clause = clause.iter().collect::<Vec<Arc<Value>>>();
+                                self.forms(&clause[1..], depth);
+                            }
+                            // (catch Exception e ..);  what's caught isn't evaluated,  and
+                            // e needn't be used
+                            Value::PersistentList(clause) if arg.is_catch_clause() => {
+                                let clause = clause.iter().collect::<Vec<Arc<Value>>>();
+                                self.scopes.push(vec![]);
+                                if let Some(sym) = clause.get(2) {
+                                    self.bind(sym, false);
+                                }
+                                self.forms(clause.get(3..).unwrap_or_default(), depth);
+                                self.pop_scope();
+                            }
+                            _ => self.form(arg, depth),
+                        }
+                    }
+                    return;
+                }
+                _ => {}
+            }
+        }
+        if self.macros.contains(sym) {
+            return;
+        }
+        if !self.defined.contains_key(sym) {
+            if let Value::Macro(macro_) = &*self.environment.get(sym) {
+                match expand_call(macro_, sym, args, depth) {
+                    Value::Condition(message) => {
+                        if message.starts_with("Wrong number of arguments") {
+                            self.report(head, ARITY_MISMATCH, message.to_string());
+                        }
+                    }
+                    expansion => self.form(&Arc::new(expansion), depth + 1),
+                }
+                return;
+            }
+        }
+        self.symbol(head, sym);
+        if let Some(arglists) = self.arglists(sym) {
+            if !arglists
+                .iter()
+                .any(|arglist| ifn::accepts(arglist, args.len()))
+            {
+                let takes = arglists
+                    .iter()
+                    .map(|arglist| format!("{}", SymbolVector(arglist)))
+                    .collect::<Vec<String>>()
+                    .join(" or ");
+                self.report(
+                    head,
+                    ARITY_MISMATCH,
+                    format!(
+                        "{} is called with {} args,  but takes {}",
+                        sym,
+                        args.len(),
+                        takes
+                    ),
+                );
+            }
+        }
+        self.forms(args, depth);
+    }
+
+    // The arglists of what sym names,  if it's a fn that says
+    fn arglists(&self, sym: &Symbol) -> Option<Vec<Vec<Symbol>>> {
+        let unqualified = sym.unqualified();
+        if !sym.has_ns() || self.ns.as_deref() == Some(sym.ns.as_str()) {
+            if let Some(arglists) = self.defined.get(&unqualified) {
+                return arglists.clone();
+            }
+        }
+        match &*self.environment.get(sym) {
+            Value::IFn(ifn) => ifn.arglists(),
+            _ => None,
+        }
+    }
+
+    // Binds a local,  and checks its name
+    fn bind(&mut self, node: &Arc<Value>, check_unused: bool) {
+        let sym = match &**node {
+            Value::Symbol(sym) if sym.name != "&" => Symbol::clone(sym),
+            Value::PersistentVector(vector) => {
+                for val in vector.iter() {
+                    self.bind(val, check_unused);
+                }
+                return;
+            }
+            _ => return,
+        };
+        let written = self.is_written(node);
+        if written && !sym.has_ns() && !sym.name.starts_with('_') {
+            let core = self
+                .environment
+                .find_namespace(&Symbol::intern("clojure.core"));
+            if core.is_some_and(|core| !matches!(*core.get(&sym), Value::Condition(_))) {
+                self.report(
+                    node,
+                    SHADOWED_CORE_NAME,
+                    format!("{} shadows clojure.core/{}", sym, sym),
+                );
+            }
+        }
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(Local {
+                sym,
+                node: Arc::clone(node),
+                used: false,
+                // A binding a macro made up is its business
+                check_unused: check_unused && written,
+            });
+        }
+    }
+
+    fn pop_scope(&mut self) {
+        let scope = self.scopes.pop().unwrap_or_default();
+        for local in scope {
+            if local.check_unused && !local.used && !local.sym.name.starts_with('_') {
+                self.report(
+                    &local.node,
+                    UNUSED_LOCAL,
+                    format!("{} is never used", local.sym),
+                );
+            }
+        }
+    }
+
+    // (fn [params] body)
+    fn fn_form(&mut self, args: &[Arc<Value>], depth: usize) {
+        // (fn ([x] ..) ([x y] ..)) ,  each arity on its own
+        if let Some(Value::PersistentList(_)) = args.first().map(|arity| &**arity) {
+            for arity in args {
+                match &**arity {
+                    Value::PersistentList(arity) => {
+                        let arity = arity.iter().collect::<Vec<Arc<Value>>>();
+                        self.fn_form(&arity, depth);
+                    }
+                    _ => self.form(arity, depth),
+                }
+            }
+            return;
+        }
+        self.scopes.push(vec![]);
+        if let Some(params) = args.first() {
+            self.bind(params, false);
+        }
+        self.forms(args.get(1..).unwrap_or_default(), depth);
+        self.pop_scope();
+    }
+
+    // (let [name val ..] body)
+    fn let_form(&mut self, args: &[Arc<Value>], depth: usize) {
+        self.scopes.push(vec![]);
+        match args.first().map(|bindings| &**bindings) {
+            Some(Value::PersistentVector(bindings)) => {
+                let bindings = bindings.iter().cloned().collect::<Vec<Arc<Value>>>();
+                for pair in bindings.chunks(2) {
+                    // Each val sees the bindings before it
+                    if let Some(val) = pair.get(1) {
+                        self.form(val, depth);
+                    }
+                    self.bind(&pair[0], true);
+                }
+            }
+            Some(_) => self.form(&args[0], depth),
+            None => {}
+        }
+        self.forms(args.get(1..).unwrap_or_default(), depth);
+        self.pop_scope();
+    }
+
+    // (def name val)
+    fn def_form(&mut self, args: &[Arc<Value>], depth: usize) {
+        if let Some(Value::Symbol(name)) = args.first().map(|name| &**name) {
+            // Defined before its value is looked at,  so a fn can call itself
+            let arglists = match args.get(1).map(|val| &**val) {
+                Some(Value::PersistentList(val)) => {
+                    let val = val.iter().collect::<Vec<Arc<Value>>>();
+                    match (val.first().map(|head| &**head), val.get(1)) {
+                        (Some(Value::Symbol(head)), Some(params)) if head.name == "fn" => {
+                            match &**params {
+                                Value::PersistentList(_) => Some(
+                                    val[1..]
+                                        .iter()
+                                        .map(|arity| match &**arity {
+                                            Value::PersistentList(arity) => arity
+                                                .iter()
+                                                .next()
+                                                .map_or_else(Vec::new, |params| symbols(&params)),
+                                            _ => vec![],
+                                        })
+                                        .collect(),
+                                ),
+                                _ => Some(vec![symbols(params)]),
+                            }
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            self.defined.insert(Symbol::clone(name), arglists);
+        }
+        self.forms(args.get(1..).unwrap_or_default(), depth);
+    }
+
+    // What (defprotocol* ..),  (deftype* ..) and (defmulti* ..) define;  their names come
+    // quoted,  as in (deftype* (quote Point) (quote [x y]) true)
+    fn define_from(&mut self, definer: &str, args: &[Arc<Value>]) {
+        let quoted = args.iter().map(unquote).collect::<Vec<Arc<Value>>>();
+        let name = match quoted.first().map(|name| &**name) {
+            Some(Value::Symbol(name)) => Symbol::clone(name),
+            _ => return,
+        };
+        match definer {
+            "defprotocol*" => {
+                if let Some(Value::PersistentList(specs)) = quoted.get(1).map(|specs| &**specs) {
+                    for spec in specs.iter() {
+                        let spec = match &*spec {
+                            Value::PersistentList(spec) => spec.iter().collect::<Vec<_>>(),
+                            _ => continue,
+                        };
+                        if let Some(Value::Symbol(method)) = spec.first().map(|method| &**method) {
+                            let arglists = spec[1..]
+                                .iter()
+                                .filter(|arglist| matches!(&***arglist, Value::PersistentVector(_)))
+                                .map(symbols)
+                                .collect();
+                            self.defined.insert(Symbol::clone(method), Some(arglists));
+                        }
+                    }
+                }
+            }
+            "deftype*" => {
+                let fields = quoted.get(1).map(symbols).unwrap_or_default();
+                self.defined.insert(
+                    Symbol::intern(&format!("->{}", name.name)),
+                    Some(vec![fields]),
+                );
+                if let Some(Value::Boolean(true)) = args.get(2).map(|record| &**record) {
+                    self.defined.insert(
+                        Symbol::intern(&format!("map->{}", name.name)),
+                        Some(vec![vec![Symbol::intern("m")]]),
+                    );
+                }
+            }
+            _ => {}
+        }
+        self.defined.insert(name, None);
+    }
+
+    // What (:require ..) or (:use ..),  in an ns form,  refers
+    fn ns_reference(&mut self, reference: &Arc<Value>) {
+        let items = match &**reference {
+            Value::PersistentList(list) => list.iter().collect::<Vec<Arc<Value>>>(),
+            _ => return,
+        };
+        let using = match items.first().map(|head| &**head) {
+            Some(Value::Keyword(kind)) if kind.sym.name == "require" => false,
+            Some(Value::Keyword(kind)) if kind.sym.name == "use" => true,
+            _ => return,
+        };
+        for libspec in items[1..].iter() {
+            self.libspec(libspec, using);
+        }
+    }
+
+    // [lib :refer [a b]],  or (prefix lib ..)
+    fn libspec(&mut self, libspec: &Arc<Value>, using: bool) {
+        let options: Vec<Arc<Value>> = match &**libspec {
+            Value::PersistentVector(vector) => vector.iter().skip(1).cloned().collect(),
+            Value::PersistentList(list) => {
+                for libspec in list.iter().skip(1) {
+                    self.libspec(&libspec, using);
+                }
+                return;
+            }
+            _ => vec![],
+        };
+        let mut only = false;
+        for pair in options.chunks(2) {
+            let (key, val) = match pair {
+                [key, val] => (key, val),
+                _ => continue,
+            };
+            match (&**key, &**val) {
+                (Value::Keyword(key), Value::Keyword(all))
+                    if key.sym.name == "refer" && all.sym.name == "all" =>
+                {
+                    self.refers_all = true;
+                }
+                (Value::Keyword(key), Value::PersistentVector(_))
+                    if key.sym.name == "refer" || key.sym.name == "only" =>
+                {
+                    only = true;
+                    for name in symbols(val) {
+                        self.defined.insert(name, None);
+                    }
+                }
+                (Value::Keyword(key), Value::PersistentListMap(renames))
+                    if key.sym.name == "rename" =>
+                {
+                    for entry in renames.iter() {
+                        if let Value::Symbol(name) = &*entry.val {
+                            self.defined.insert(Symbol::clone(name), None);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if using && !only {
+            self.refers_all = true;
+        }
+    }
+}
+
+// x,  from (quote x)
+fn unquote(form: &Arc<Value>) -> Arc<Value> {
+    if let Value::PersistentList(list) = &**form {
+        let items = list.iter().collect::<Vec<Arc<Value>>>();
+        if let [head, quoted] = items.as_slice() {
+            if matches!(&**head, Value::Symbol(sym) if sym.name == "quote") {
+                return Arc::clone(quoted);
+            }
+        }
+    }
+    Arc::clone(form)
+}
+
+// The symbols in a vector,  like an arglist
+fn symbols(vector: &Arc<Value>) -> Vec<Symbol> {
+    match &**vector {
+        Value::PersistentVector(vector) => vector
+            .iter()
+            .filter_map(|val| match &**val {
+                Value::Symbol(sym) => Some(Symbol::clone(sym)),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+// [a b] ,  for arglists in messages
+struct SymbolVector<'a>(&'a [Symbol]);
+impl fmt::Display for SymbolVector<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let syms = self
+            .0
+            .iter()
+            .map(|sym| sym.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "[{}]", syms.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::environment::Environment;
+    use crate::linter::lint;
+
+    fn diagnostics(source: &str) -> Vec<String> {
+        lint(&Environment::clojure_core_environment(), source)
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn finds_unresolved_symbols_and_unused_locals() {
+        assert_eq!(
+            vec![
+                "2:9: unused-local: y is never used",
+                "4:10: unresolved-symbol: Unable to resolve symbol: z",
+            ],
+            diagnostics("(defn f [x]\n  (let [y 1\n        _w 2]\n    (+ x z)))\n")
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn checks_calls_against_arglists() {
+        assert_eq!(
+            vec![
+                "3:2: arity-mismatch: add is called with 1 args,  but takes [a b]",
+                "4:2: arity-mismatch: Wrong number of arguments given to with-open (Given: 0, Expected: >=1)",
+            ],
+            diagnostics("(defn add [a b] (+ a b))\n(add 1 2)\n(add 1)\n(with-open)\n")
+        );
+        assert_eq!(
+            vec!["2:2: arity-mismatch: f is called with 0 args,  but takes [x] or [x y & more]"],
+            diagnostics("(defn f ([x] x) ([x y & more] (f y)))\n(f)\n(f 1 2 3)\n")
+        );
+    }
+
+    #[test]
+    fn knows_what_the_file_defines() {
+        let source = "(ns app.core)
+(defprotocol Shape (area [this]))
+(defrecord Square [side] Shape (area [_] (* side side)))
+(defmulti describe :kind)
+(defn total [shapes] (app.core/area (->Square 1)))
+(defn g [map] (describe map))
+";
+        assert_eq!(
+            vec!["6:10: shadowed-core-name: map shadows clojure.core/map"],
+            diagnostics(source)
+        );
+    }
+
+    #[test]
+    fn knows_what_ns_refers() {
+        let source = "(ns app.core
+  (:require [app.util :as u :refer [parse]]
+            (app [db :refer [query] :rename {query q}])))
+(defn f [s] (u/g (parse s) (q s) (missing s)))
+";
+        assert_eq!(
+            vec!["4:35: unresolved-symbol: Unable to resolve symbol: missing"],
+            diagnostics(source)
+        );
+        assert!(diagnostics(
+            "(ns app.core (:use app.util))
+(defn f [s] (parse s))
+"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn catch_binds_what_it_caught() {
+        assert_eq!(
+            vec!["3:34: unresolved-symbol: Unable to resolve symbol: oops"],
+            diagnostics(
+                "(defn g [x] x)\n(defn f [x]\n  (try (g x) (catch Exception e (oops e)) (finally (g x))))\n"
+            )
+        );
+    }
+
+    #[test]
+    fn carries_on_past_syntax_errors() {
+        assert_eq!(
+            vec![
+                "1:1: syntax-error: Could not read this form",
+                "3:2: unresolved-symbol: Unable to resolve symbol: nope",
+            ],
+            diagnostics("(defn f [x]\n  (g x]\n(nope 1)\n")
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Locales;  for the case mappings and collation that differ from language to language,
+//! ie Turkish's dotted and dotless i,  or Swedish sorting å, ä and ö after z.  There's no
+//! ICU here,  so these are approximations written out by hand:  the languages whose rules
+//! most often bite,  and a collation that folds the Latin accents,  not the full CLDR
+//! tables
+use crate::error_message;
+use crate::type_tag::TypeTag;
+use crate::value::Value;
+use std::cmp::Ordering;
+
+/// A locale,  as far as case and collation go;  just its language
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    language: String,
+}
+impl Locale {
+    /// The locale of a tag like tr,  tr-TR or sv_SE
+    pub fn new(tag: &str) -> Locale {
+        let language = tag.split(['-', '_']).next().unwrap_or("");
+        Locale {
+            language: language.to_lowercase(),
+        }
+    }
+
+    /// The locale named by val,  a string or keyword;  ie "tr" or :sv-SE
+    pub fn of(val: &Value) -> Result<Locale, Value> {
+        match val {
+            Value::String(tag) => Ok(Locale::new(tag)),
+            Value::Keyword(tag) => Ok(Locale::new(&tag.sym.name)),
+            _ => Err(error_message::type_mismatch(TypeTag::String, val)),
+        }
+    }
+
+    fn is(&self, languages: &[&str]) -> bool {
+        languages.contains(&&*self.language)
+    }
+
+    /// s in upper case,  as this locale writes it
+    pub fn upper_case(&self, s: &str) -> String {
+        if self.is(&["tr", "az"]) {
+            return s.replace('i', "İ").to_uppercase();
+        }
+        let upper = s.to_uppercase();
+        if self.is(&["el"]) {
+            // Greek drops its accents in capitals
+            return upper
+                .chars()
+                .filter(|&c| c != '\u{301}' && c != '\u{344}')
+                .map(|c| match c {
+                    'Ά' => 'Α',
+                    'Έ' => 'Ε',
+                    'Ή' => 'Η',
+                    'Ί' => 'Ι',
+                    'Ό' => 'Ο',
+                    'Ύ' => 'Υ',
+                    'Ώ' => 'Ω',
+                    c => c,
+                })
+                .collect();
+        }
+        upper
+    }
+
+    /// s in lower case,  as this locale writes it
+    pub fn lower_case(&self, s: &str) -> String {
+        if self.is(&["tr", "az"]) {
+            return s.replace('I', "ı").replace('İ', "i").to_lowercase();
+        }
+        s.to_lowercase()
+    }
+
+    /// How a sorts against b in this locale.  First by letter,  ignoring case and accents;
+    /// then by accents,  then by case (lower first);  and last by code point,  so that only
+    /// equal strings are equal
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        let (primary_a, secondary_a) = self.collation_keys(a);
+        let (primary_b, secondary_b) = self.collation_keys(b);
+        let tertiary = |s: &str| s.chars().map(char::is_uppercase).collect::<Vec<bool>>();
+        primary_a
+            .cmp(&primary_b)
+            .then_with(|| secondary_a.cmp(&secondary_b))
+            .then_with(|| tertiary(a).cmp(&tertiary(b)))
+            .then_with(|| a.cmp(b))
+    }
+
+    // The letters of s as this locale sorts them;  each its base letter and,  for the
+    // letters the locale sorts apart from their base,  where it comes after it.  And the
+    // accents of s,  in order
+    fn collation_keys(&self, s: &str) -> (Vec<(char, usize)>, Vec<char>) {
+        let mut primary = vec![];
+        let mut secondary = vec![];
+        for c in self.lower_case(s).chars() {
+            if let Some(ind) = self.tailoring().iter().position(|&t| t == c) {
+                primary.push(('z', ind + 1));
+                continue;
+            }
+            if let Some(ind) = self.after_base().iter().position(|&t| t == c) {
+                primary.push((
+                    fold(c).map_or(c, |base| base.chars().next().unwrap()),
+                    ind + 1,
+                ));
+                continue;
+            }
+            if ('\u{300}'..='\u{36F}').contains(&c) {
+                secondary.push(c);
+                continue;
+            }
+            match fold(c) {
+                Some(base) => {
+                    primary.extend(base.chars().map(|base| (base, 0)));
+                    secondary.push(c);
+                }
+                None => primary.push((c, 0)),
+            }
+        }
+        (primary, secondary)
+    }
+
+    // The letters this locale sorts after z,  in order
+    fn tailoring(&self) -> &'static [char] {
+        match &*self.language {
+            "sv" | "fi" => &['å', 'ä', 'ö'],
+            "da" | "nb" | "nn" | "no" => &['æ', 'ø', 'å'],
+            _ => &[],
+        }
+    }
+
+    // The letters this locale sorts apart from,  and right after,  their base letter
+    fn after_base(&self) -> &'static [char] {
+        match &*self.language {
+            "es" => &['ñ'],
+            "pl" => &['ą', 'ć', 'ę', 'ł', 'ń', 'ó', 'ś', 'ź', 'ż'],
+            "tr" | "az" => &['ç', 'ğ', 'ı', 'ö', 'ş', 'ü'],
+            _ => &[],
+        }
+    }
+}
+
+// The base letters of c,  a lower case letter,  with its accents taken off;  if it has any
+fn fold(c: char) -> Option<&'static str> {
+    let base = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(base)
+}
+
+#[cfg(test)]
+mod tests {
+    mod locale_tests {
+        use crate::locale::Locale;
+        use std::cmp::Ordering;
+
+        fn sorted(locale: &str, words: &[&'static str]) -> Vec<&'static str> {
+            let locale = Locale::new(locale);
+            let mut words = words.to_vec();
+            words.sort_by(|a, b| locale.compare(a, b));
+            words
+        }
+
+        #[test]
+        fn turkish_has_dotted_and_dotless_is() {
+            let tr = Locale::new("tr-TR");
+            assert_eq!("İSTANBUL I", tr.upper_case("istanbul ı"));
+            assert_eq!("istanbul ı", tr.lower_case("İSTANBUL I"));
+            assert_eq!("ISTANBUL", Locale::new("en").upper_case("istanbul"));
+        }
+
+        #[test]
+        fn greek_capitals_drop_their_accents() {
+            assert_eq!("ΑΘΗΝΑ", Locale::new("el").upper_case("Αθήνα"));
+            assert_eq!("ΑΘΉΝΑ", Locale::new("en").upper_case("Αθήνα"));
+        }
+
+        #[test]
+        fn accents_and_case_sort_after_letters() {
+            let words = ["peach", "Péché", "péché", "pêche", "apple", "Zebra"];
+            assert_eq!(
+                vec!["apple", "peach", "péché", "Péché", "pêche", "Zebra"],
+                sorted("en", &words)
+            );
+            assert_eq!(Ordering::Equal, Locale::new("en").compare("é", "é"));
+        }
+
+        #[test]
+        fn locales_sort_their_own_letters() {
+            let words = ["öl", "zebra", "apa", "åska", "äpple"];
+            assert_eq!(
+                vec!["apa", "äpple", "åska", "öl", "zebra"],
+                sorted("de", &words)
+            );
+            assert_eq!(
+                vec!["apa", "zebra", "åska", "äpple", "öl"],
+                sorted("sv", &words)
+            );
+            let words = ["nube", "ñu", "nz", "oso"];
+            assert_eq!(vec!["nube", "nz", "ñu", "oso"], sorted("es", &words));
+        }
+    }
+}
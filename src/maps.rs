@@ -1,12 +1,22 @@
 //! General map utilities
-use crate::value::Value;
-use std::rc::Rc;
+use crate::persistent_vector::PersistentVector;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub struct MapEntry {
     // We need to box key to avoid an infinite cycle of
     // Value::Persistent*Map { Persistent*Map { MapEntry { Value <--- cycle restarts , val }}}
-    // Implemented with an Rc because inevitably,  our system tends to live in Rcs
-    pub key: Rc<Value>,
-    pub val: Rc<Value>,
+    // Implemented with an Arc because inevitably,  our system tends to live in Arcs
+    pub key: Arc<Value>,
+    pub val: Arc<Value>,
+}
+impl MapEntry {
+    /// As seq gives it;  a vector of key and val,  as key and val take apart
+    pub fn to_vector_value(&self) -> Arc<Value> {
+        PersistentVector::empty()
+            .conj(Arc::clone(&self.key))
+            .conj(Arc::clone(&self.val))
+            .to_rc_value()
+    }
 }
@@ -0,0 +1,188 @@
+//! Metadata;  a map hung on a symbol or collection,  that doesn't change what it's equal
+//! to,  or how it hashes
+//!
+//! Each of these values keeps what it holds behind an Arc,  so its metadata is kept beside
+//! it here,  keyed by that Arc's allocation;  and is shared by every clone of the value.
+//! with-meta gives a copy in an allocation of its own,  for other metadata to go on
+use crate::error_message;
+use crate::keyword::Keyword;
+use crate::maps::MapEntry;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap, ToPersistentListMapIter};
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, Weak};
+
+type Allocation = Arc<dyn Any + Send + Sync>;
+
+struct Slot {
+    // Keeps the allocation from being reused while its metadata is here;  once nothing else
+    // holds it,  the slot can go
+    owner: Weak<dyn Any + Send + Sync>,
+    meta: Arc<Value>,
+}
+
+// Shared by every thread,  as the values are
+#[derive(Default)]
+struct Slots {
+    slots: HashMap<usize, Slot>,
+    // How many slots there can be before we next drop those of values that are gone
+    prune_at: usize,
+}
+
+fn slots() -> MutexGuard<'static, Slots> {
+    static SLOTS: OnceLock<Mutex<Slots>> = OnceLock::new();
+    SLOTS.get_or_init(Default::default).lock().unwrap()
+}
+
+// The allocation val's metadata is kept by;  None if val can't have any
+fn allocation(val: &Value) -> Option<Allocation> {
+    let allocation: Allocation = match val {
+        Value::Symbol(sym) => Arc::clone(sym) as Allocation,
+        Value::PersistentList(plist) => Arc::clone(plist) as Allocation,
+        Value::PersistentVector(pvector) => Arc::clone(pvector) as Allocation,
+        Value::PersistentListMap(plistmap) => Arc::clone(plistmap) as Allocation,
+        Value::PersistentHashSet(pset) => Arc::clone(pset) as Allocation,
+        Value::PersistentTreeMap(ptmap) => Arc::clone(ptmap) as Allocation,
+        Value::PersistentTreeSet(ptset) => Arc::clone(ptset) as Allocation,
+        _ => return None,
+    };
+    Some(allocation)
+}
+
+fn key(allocation: &Allocation) -> usize {
+    Arc::as_ptr(allocation) as *const () as usize
+}
+
+/// Whether val can have metadata;  ie is a clojure.lang.IObj
+pub fn supports_meta(val: &Value) -> bool {
+    allocation(val).is_some()
+}
+
+/// val's metadata,  if it has any
+pub fn meta(val: &Value) -> Option<Arc<Value>> {
+    let allocation = allocation(val)?;
+    slots()
+        .slots
+        .get(&key(&allocation))
+        .map(|slot| Arc::clone(&slot.meta))
+}
+
+/// Hangs meta on val itself,  in place of any it had;  only for a value just made,  that
+/// nothing else has seen yet.  Anything else wants with_meta
+pub fn set_meta(val: &Value, meta: Option<Arc<Value>>) {
+    let allocation = match allocation(val) {
+        Some(allocation) => allocation,
+        None => return,
+    };
+    let mut slots = slots();
+    match meta {
+        Some(meta) => {
+            let owner = Arc::downgrade(&allocation);
+            slots.slots.insert(key(&allocation), Slot { owner, meta });
+        }
+        None => {
+            slots.slots.remove(&key(&allocation));
+        }
+    }
+    if slots.slots.len() >= slots.prune_at {
+        slots.slots.retain(|_, slot| slot.owner.strong_count() > 0);
+        slots.prune_at = 64.max(slots.slots.len() * 2);
+    }
+}
+
+/// A copy of val with meta (a map,  or nil for none) as its metadata
+pub fn with_meta(val: &Value, meta: &Arc<Value>) -> Result<Value, Value> {
+    let meta = match &**meta {
+        Value::Nil => None,
+        Value::PersistentListMap(_) => Some(Arc::clone(meta)),
+        _ => return Err(error_message::type_mismatch(TypeTag::IPersistentMap, meta)),
+    };
+    let copy = match val {
+        Value::Symbol(sym) => Value::Symbol(Arc::new(Symbol::clone(sym))),
+        Value::PersistentList(plist) => Value::PersistentList(Arc::new((**plist).clone())),
+        Value::PersistentVector(pvector) => Value::PersistentVector(Arc::new((**pvector).clone())),
+        Value::PersistentListMap(plistmap) => {
+            Value::PersistentListMap(Arc::new((**plistmap).clone()))
+        }
+        Value::PersistentHashSet(pset) => Value::PersistentHashSet(Arc::new((**pset).clone())),
+        Value::PersistentTreeMap(ptmap) => Value::PersistentTreeMap(Arc::new((**ptmap).clone())),
+        Value::PersistentTreeSet(ptset) => Value::PersistentTreeSet(Arc::new((**ptset).clone())),
+        _ => return Err(error_message::type_mismatch(TypeTag::IObj, val)),
+    };
+    set_meta(&copy, meta);
+    Ok(copy)
+}
+
+/// The metadata ^meta means, as in ^:private or ^String;  a keyword is short for
+/// {keyword true},  and a symbol or string for {:tag it}
+pub fn normalize(meta: &Arc<Value>) -> Result<Arc<Value>, Value> {
+    match &**meta {
+        Value::PersistentListMap(_) => Ok(Arc::clone(meta)),
+        Value::Keyword(_) => Ok(PersistentListMap::Empty
+            .assoc(Arc::clone(meta), Arc::new(Value::Boolean(true)))
+            .to_rc_value()),
+        Value::Symbol(_) | Value::String(_) => Ok(PersistentListMap::Empty
+            .assoc(Keyword::intern("tag").to_rc_value(), Arc::clone(meta))
+            .to_rc_value()),
+        _ => Err(Value::Condition(
+            "Metadata must be Symbol,Keyword,String or Map".into(),
+        )),
+    }
+}
+
+/// The entries of meta over those of val's metadata,  if it has any;  as when reading
+/// ^:a ^:b x
+pub fn merged(val: &Value, meta: &Arc<Value>) -> Arc<Value> {
+    let (outer, existing) = match (&**meta, self::meta(val).as_deref()) {
+        (Value::PersistentListMap(outer), Some(Value::PersistentListMap(existing))) => {
+            (outer, existing.clone())
+        }
+        _ => return Arc::clone(meta),
+    };
+    outer
+        .iter()
+        .fold(existing, |map, MapEntry { key, val }| map.assoc(key, val))
+        .to_rc_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn metadata_is_kept_beside_the_value() {
+        let result = Repl::default().eval_all(&[
+            "(def v (with-meta [1 2] {:a 1}))",
+            "[(meta v) (meta [1 2]) (= v [1 2]) (meta (vary-meta v assoc :b 2))]",
+        ]);
+        assert_eq!("[{:a 1} nil true {:a 1, :b 2}]", result.to_string());
+    }
+
+    #[test]
+    fn the_reader_attaches_metadata() {
+        let result = Repl::default().eval_all(&[
+            "[(meta '^:private x) (meta '^String y) (meta '^{:k 1} ^:b (z)) (meta ^{:n (+ 1 2)} [])]",
+        ]);
+        assert_eq!(
+            "[{:private true} {:tag String} {:b true, :k 1} {:n 3}]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn defs_keep_the_metadata_of_their_name() {
+        let result = Repl::default().eval_all(&[
+            "(ns app.core)",
+            "(clojure.core/def ^:private secret 1)",
+            "(clojure.core/def shown 2)",
+            "(clojure.core/ns clojure.core)",
+            "(defn ^:private helper [] 3)",
+            "(def publics (ns-publics 'app.core))",
+            "[(contains? publics 'secret) (contains? publics 'shown) (contains? (ns-interns 'app.core) 'secret) (contains? (ns-publics 'clojure.core) 'helper)]",
+        ]);
+        assert_eq!("[false true true false]", result.to_string());
+    }
+}
@@ -0,0 +1,421 @@
+//! Property tests for our persistent collections
+//!
+//! Each test generates a random sequence of operations and runs it against both one
+//! of our persistent structures and a plain `std` collection acting as a reference
+//! model.  Every intermediate version is kept around and rechecked at the end,  so an
+//! operation that accidentally mutates shared structure (instead of copying the path
+//! it changes) shows up as an older version drifting from its model
+
+use crate::maps::MapEntry;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list::{cons_rc, PersistentList, ToPersistentListIter};
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap, ToPersistentListMapIter};
+use crate::persistent_vector::PersistentVector;
+use crate::transient::TransientMap;
+use crate::value::{ToValue, Value};
+use proptest::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+use std::sync::Arc;
+
+// The most keys a map or set test draws from.  Each case draws from its own number of them,
+// so some keep overwriting a few keys and go back and forth past the 8 an array-map holds,
+// and others grow a hash trie a few levels deep
+const KEYS: i32 = 96;
+
+fn keys_strategy() -> impl Strategy<Value = i32> {
+    4..KEYS
+}
+
+// Key k is the int k / 2,  or,  if k is odd,  the char with that code;  a char hashes as
+// the int of its code does,  so the two are unequal keys with the very same hash,  and
+// share a collision node
+fn key(k: i32) -> Arc<Value> {
+    match k % 2 {
+        0 => Value::I32(k / 2).to_rc_value(),
+        _ => Value::Char(char::from_u32((k / 2) as u32).unwrap()).to_rc_value(),
+    }
+}
+fn key_index(key: &Value) -> i32 {
+    match key {
+        Value::I32(i) => i * 2,
+        Value::Char(c) => *c as i32 * 2 + 1,
+        other => panic!("Expected an i32 or char key, got {}", other),
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ListOp {
+    Cons(i32),
+    Rest,
+}
+fn list_op_strategy() -> impl Strategy<Value = ListOp> {
+    prop_oneof![
+        3 => any::<i32>().prop_map(ListOp::Cons),
+        1 => Just(ListOp::Rest),
+    ]
+}
+
+#[derive(Debug, Clone)]
+enum MapOp {
+    Assoc(i32, i32),
+    Dissoc(i32),
+    // Lookups leave the map alone,  but should still agree with the model
+    Get(i32),
+}
+fn map_op_strategy(keys: i32) -> impl Strategy<Value = MapOp> {
+    prop_oneof![
+        3 => (0..keys, any::<i32>()).prop_map(|(k, v)| MapOp::Assoc(k, v)),
+        2 => (0..keys).prop_map(MapOp::Dissoc),
+        1 => (0..keys).prop_map(MapOp::Get),
+    ]
+}
+fn map_ops_strategy() -> impl Strategy<Value = Vec<MapOp>> {
+    keys_strategy().prop_flat_map(|keys| prop::collection::vec(map_op_strategy(keys), 0..256))
+}
+
+#[derive(Debug, Clone)]
+enum SetOp {
+    Conj(i32),
+    Disj(i32),
+    Contains(i32),
+}
+fn set_op_strategy(keys: i32) -> impl Strategy<Value = SetOp> {
+    prop_oneof![
+        3 => (0..keys).prop_map(SetOp::Conj),
+        2 => (0..keys).prop_map(SetOp::Disj),
+        1 => (0..keys).prop_map(SetOp::Contains),
+    ]
+}
+fn set_ops_strategy() -> impl Strategy<Value = Vec<SetOp>> {
+    keys_strategy().prop_flat_map(|keys| prop::collection::vec(set_op_strategy(keys), 0..256))
+}
+
+#[derive(Debug, Clone)]
+enum VectorOp {
+    Conj(i32),
+    // Indices are taken modulo the length (plus one, for assoc),  so most of them land
+    Assoc(usize, i32),
+    Pop,
+    Subvec(usize, usize),
+    // The in place edits transients use;  made on a copy of the last version, so each
+    // version that shares with it is checked to be left alone
+    ConjInPlace(i32),
+    AssocInPlace(usize, i32),
+}
+fn vector_op_strategy() -> impl Strategy<Value = VectorOp> {
+    prop_oneof![
+        6 => any::<i32>().prop_map(VectorOp::Conj),
+        2 => (any::<usize>(), any::<i32>()).prop_map(|(ind, i)| VectorOp::Assoc(ind, i)),
+        2 => Just(VectorOp::Pop),
+        1 => (any::<usize>(), any::<usize>()).prop_map(|(a, b)| VectorOp::Subvec(a, b)),
+        3 => any::<i32>().prop_map(VectorOp::ConjInPlace),
+        2 => (any::<usize>(), any::<i32>()).prop_map(|(ind, i)| VectorOp::AssocInPlace(ind, i)),
+    ]
+}
+
+fn list_to_vec(plist: &Arc<PersistentList>) -> Vec<Value> {
+    plist.iter().map(|val| (*val).clone()).collect()
+}
+fn to_values(model: &[i32]) -> Vec<Value> {
+    model.iter().map(|i| i.to_value()).collect()
+}
+
+fn check_list(plist: &Arc<PersistentList>, model: &[i32]) {
+    assert_eq!(model.len() as i32, plist.len());
+    assert_eq!(to_values(model), list_to_vec(plist));
+    for (ind, expected) in model.iter().enumerate() {
+        assert_eq!(
+            *expected,
+            match &*plist.nth(ind) {
+                Value::I32(i) => *i,
+                other => panic!("Expected an i32, got {}", other),
+            }
+        );
+    }
+}
+
+fn check_map(pmap: &PersistentListMap, model: &HashMap<i32, i32>) {
+    for k in 0..KEYS {
+        let actual = pmap.get(&key(k));
+        match model.get(&k) {
+            Some(expected) => assert_eq!(Value::I32(*expected), *actual),
+            None => assert_eq!(Value::Nil, *actual),
+        }
+    }
+    // Iterating only sees the newest entry for each key
+    let mut seen = HashMap::new();
+    for MapEntry { key, val } in pmap.iter() {
+        let k = key_index(&key);
+        match &*val {
+            Value::I32(v) => assert!(seen.insert(k, *v).is_none(), "key {} iterated twice", key),
+            _ => panic!("Expected an i32 val, got {}", val),
+        }
+    }
+    assert_eq!(*model, seen);
+    assert_eq!(model.len(), pmap.len());
+    // And it's only hashed when an array-map couldn't hold it
+    assert_eq!(
+        model.len() > 8,
+        matches!(pmap, PersistentListMap::Hashed(_)),
+        "{} entries, kept as {:?}",
+        model.len(),
+        pmap
+    );
+}
+
+fn check_set(pset: &PersistentHashSet, model: &HashSet<i32>) {
+    for k in 0..KEYS {
+        assert_eq!(model.contains(&k), pset.contains(&key(k)));
+    }
+    let mut seen = HashSet::new();
+    for val in pset.iter() {
+        assert!(seen.insert(key_index(&val)), "{} iterated twice", val);
+    }
+    assert_eq!(*model, seen);
+    assert_eq!(model.len(), pset.len());
+}
+
+fn check_vector(pvector: &PersistentVector, model: &[i32]) {
+    assert_eq!(model.len(), pvector.len());
+    assert_eq!(
+        to_values(model),
+        pvector
+            .iter()
+            .map(|val| (**val).clone())
+            .collect::<Vec<Value>>()
+    );
+    let mut reversed = to_values(model);
+    reversed.reverse();
+    assert_eq!(
+        reversed,
+        pvector
+            .iter()
+            .rev()
+            .map(|val| (**val).clone())
+            .collect::<Vec<Value>>()
+    );
+    for (ind, expected) in model.iter().enumerate() {
+        assert_eq!(Some(&Arc::new(Value::I32(*expected))), pvector.nth(ind));
+    }
+    assert_eq!(None, pvector.nth(model.len()));
+}
+
+proptest! {
+    #[test]
+    fn persistent_list_matches_vec_model(ops in prop::collection::vec(list_op_strategy(), 0..64)) {
+        let mut versions = vec![(Arc::new(PersistentList::Empty), vec![])];
+        for op in ops {
+            let (plist, model) = versions.last().unwrap().clone();
+            let next = match op {
+                ListOp::Cons(i) => {
+                    let mut model = model.clone();
+                    model.insert(0, i);
+                    (Arc::new(cons_rc(i.to_rc_value(), plist)), model)
+                }
+                ListOp::Rest => match &*plist {
+                    PersistentList::Cons(_, rest, _) => (Arc::clone(rest), model[1..].to_vec()),
+                    PersistentList::Empty => (plist, model),
+                },
+            };
+            check_list(&next.0, &next.1);
+            versions.push(next);
+        }
+        for (plist, model) in versions.iter() {
+            check_list(plist, model);
+        }
+    }
+
+    #[test]
+    fn persistent_list_map_matches_hash_map_model(ops in map_ops_strategy()) {
+        let mut versions = vec![(Arc::new(PersistentListMap::Empty), HashMap::new())];
+        for op in ops {
+            let (pmap, model) = versions.last().unwrap().clone();
+            let mut model = model.clone();
+            let pmap = match op {
+                MapOp::Assoc(k, v) => {
+                    model.insert(k, v);
+                    pmap.assoc(key(k), v.to_rc_value())
+                }
+                // Made on a copy of the last version,  as a transient's dissoc! would be
+                MapOp::Dissoc(k) => {
+                    model.remove(&k);
+                    let mut pmap = (*pmap).clone();
+                    pmap.dissoc_in_place(&key(k));
+                    Arc::new(pmap)
+                }
+                MapOp::Get(k) => {
+                    let expected = model.get(&k).map(|v| Value::I32(*v)).unwrap_or(Value::Nil);
+                    prop_assert_eq!(expected, (*pmap.get(&key(k))).clone());
+                    continue;
+                }
+            };
+            check_map(&pmap, &model);
+            versions.push((pmap, model));
+        }
+        for (pmap, model) in versions.iter() {
+            check_map(pmap, model);
+        }
+    }
+
+    #[test]
+    fn persistent_hash_set_matches_hash_set_model(ops in set_ops_strategy()) {
+        let mut versions = vec![(PersistentHashSet::empty(), HashSet::new())];
+        for op in ops {
+            let (pset, model) = versions.last().unwrap().clone();
+            let mut model = model.clone();
+            let pset = match op {
+                SetOp::Conj(k) => {
+                    model.insert(k);
+                    pset.conj(key(k))
+                }
+                SetOp::Disj(k) => {
+                    model.remove(&k);
+                    pset.disj(&key(k))
+                }
+                SetOp::Contains(k) => {
+                    prop_assert_eq!(model.contains(&k), pset.contains(&key(k)));
+                    continue;
+                }
+            };
+            check_set(&pset, &model);
+            versions.push((pset, model));
+        }
+        for (pset, model) in versions.iter() {
+            check_set(pset, model);
+        }
+    }
+
+    // A transient's edits,  from a map built up beforehand;  which has to be left as it was
+    #[test]
+    fn transient_map_matches_hash_map_model(
+        start in map_ops_strategy(),
+        ops in map_ops_strategy()
+    ) {
+        let mut model = HashMap::new();
+        let mut pmap = PersistentListMap::Empty;
+        for op in start {
+            match op {
+                MapOp::Assoc(k, v) => {
+                    model.insert(k, v);
+                    pmap = pmap.assoc(key(k), v.to_rc_value());
+                }
+                MapOp::Dissoc(k) => {
+                    model.remove(&k);
+                    pmap.dissoc_in_place(&key(k));
+                }
+                MapOp::Get(_) => {}
+            }
+        }
+        let original = (pmap.clone(), model.clone());
+        let transient = TransientMap::new(&pmap);
+        for op in ops {
+            match op {
+                MapOp::Assoc(k, v) => {
+                    model.insert(k, v);
+                    transient.assoc(key(k), v.to_rc_value()).unwrap();
+                }
+                MapOp::Dissoc(k) => {
+                    model.remove(&k);
+                    transient.dissoc(&key(k)).unwrap();
+                }
+                MapOp::Get(k) => {
+                    let expected = model.get(&k).map(|v| v.to_rc_value());
+                    prop_assert_eq!(expected, transient.get(&key(k)).unwrap());
+                }
+            }
+        }
+        check_map(&transient.persistent().unwrap(), &model);
+        check_map(&pmap, &original.1);
+        check_map(&original.0, &original.1);
+    }
+
+    #[test]
+    fn persistent_vector_matches_vec_model(model in prop::collection::vec(any::<i32>(), 0..64)) {
+        let pvector = Arc::new(
+            model
+                .iter()
+                .map(|i| i.to_rc_value())
+                .collect::<PersistentVector>(),
+        );
+        let vals = pvector.iter().map(|val| (**val).clone()).collect::<Vec<Value>>();
+        prop_assert_eq!(to_values(&model), vals);
+        check_vector(&pvector, &model);
+    }
+
+    // Long enough sequences to grow the trie past its first level and back
+    #[test]
+    fn persistent_vector_ops_match_vec_model(
+        start in prop::collection::vec(any::<i32>(), 0..1100),
+        ops in prop::collection::vec(vector_op_strategy(), 0..128)
+    ) {
+        let pvector = start.iter().map(|i| i.to_rc_value()).collect::<PersistentVector>();
+        let mut versions = vec![(Arc::new(pvector), start)];
+        for op in ops {
+            let (pvector, model) = versions.last().unwrap().clone();
+            let mut model = model.clone();
+            let pvector = match op {
+                VectorOp::Conj(i) => {
+                    model.push(i);
+                    pvector.conj(i.to_rc_value())
+                }
+                VectorOp::Assoc(ind, i) => {
+                    let ind = ind % (model.len() + 1);
+                    if ind == model.len() {
+                        model.push(i);
+                    } else {
+                        model[ind] = i;
+                    }
+                    pvector.assoc(ind, i.to_rc_value()).unwrap()
+                }
+                VectorOp::Pop => match pvector.pop() {
+                    Some(popped) => {
+                        model.pop();
+                        popped
+                    }
+                    None => {
+                        prop_assert!(model.is_empty());
+                        continue;
+                    }
+                },
+                VectorOp::Subvec(a, b) => {
+                    let (a, b) = (a % (model.len() + 1), b % (model.len() + 1));
+                    let (start, end) = (a.min(b), a.max(b));
+                    model = model[start..end].to_vec();
+                    pvector.subvec(start, end).unwrap()
+                }
+                VectorOp::ConjInPlace(i) => {
+                    model.push(i);
+                    let mut pvector = (*pvector).clone();
+                    pvector.conj_in_place(i.to_rc_value());
+                    pvector
+                }
+                VectorOp::AssocInPlace(ind, i) => {
+                    let ind = ind % (model.len() + 1);
+                    if ind == model.len() {
+                        model.push(i);
+                    } else {
+                        model[ind] = i;
+                    }
+                    let mut pvector = (*pvector).clone();
+                    prop_assert!(pvector.assoc_in_place(ind, i.to_rc_value()));
+                    pvector
+                }
+            };
+            let pvector = Arc::new(pvector);
+            check_vector(&pvector, &model);
+            versions.push((pvector, model));
+        }
+        for (pvector, model) in versions.iter() {
+            check_vector(pvector, model);
+        }
+    }
+}
+
+// Else the collision nodes above would go untested
+#[test]
+fn a_char_hashes_as_the_int_of_its_code() {
+    let state = crate::hamt::SeededState;
+    assert_ne!(*key(84), *key(85));
+    assert_eq!(state.hash_one(&*key(84)), state.hash_one(&*key(85)));
+}
@@ -0,0 +1,277 @@
+//! Multimethods,  as made by defmulti
+//!
+//! A multimethod calls its dispatch fn on its arguments,  and then whichever of its methods
+//! was defined for the value that gives back.  Dispatch values are compared with isa?,  so a
+//! method defined for :shape also handles anything derived from :shape;  when several
+//! methods match,  the one whose dispatch value derives from (or is preferred over) all the
+//! others wins.  If none match,  the method defined for the default dispatch value (:default
+//! unless given) is called instead
+use crate::error_message;
+use crate::hierarchy::{self, Hierarchy};
+use crate::ifn::{self, IFn};
+use crate::maps::MapEntry;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list_map::PersistentListMap;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::{Arc, RwLock};
+
+// Each dispatch value,  and its method;  in the order they were defined
+type Methods = Vec<(Arc<Value>, Arc<dyn IFn>)>;
+// Each dispatch value,  and those it's preferred over
+type Prefers = Vec<(Arc<Value>, Vec<Arc<Value>>)>;
+
+#[derive(Debug)]
+pub struct MultiFn {
+    pub name: Symbol,
+    // A fn,  or a keyword to look up in the first argument
+    dispatch_fn: Arc<Value>,
+    default_dispatch_val: Arc<Value>,
+    // The hierarchy to dispatch with,  if not the global one;  either a hierarchy,  or an
+    // atom holding one,  so that later derives are seen
+    hierarchy: Option<Arc<Value>>,
+    methods: RwLock<Methods>,
+    prefers: RwLock<Prefers>,
+}
+impl MultiFn {
+    pub fn new(
+        name: Symbol,
+        dispatch_fn: Arc<Value>,
+        default_dispatch_val: Arc<Value>,
+        hierarchy: Option<Arc<Value>>,
+    ) -> MultiFn {
+        MultiFn {
+            name,
+            dispatch_fn,
+            default_dispatch_val,
+            hierarchy,
+            methods: RwLock::new(vec![]),
+            prefers: RwLock::new(vec![]),
+        }
+    }
+    fn dispatch_val(&self, args: &[Arc<Value>]) -> Value {
+        ifn::call(&self.dispatch_fn, args.to_vec())
+    }
+    fn hierarchy(&self) -> Result<Hierarchy, Value> {
+        match self.hierarchy.as_deref() {
+            None => Ok(hierarchy::global()),
+            Some(Value::Atom(atom)) => Hierarchy::from_value(&atom.deref()),
+            Some(hierarchy) => Hierarchy::from_value(hierarchy),
+        }
+    }
+    /// Defines the method for dispatch_val as f,  replacing any it already had
+    pub fn add_method(&self, dispatch_val: Arc<Value>, f: Arc<dyn IFn>) {
+        let mut methods = self.methods.write().unwrap();
+        match methods.iter_mut().find(|(val, _)| *val == dispatch_val) {
+            Some((_, method)) => *method = f,
+            None => methods.push((dispatch_val, f)),
+        }
+    }
+    pub fn remove_method(&self, dispatch_val: &Value) {
+        self.methods
+            .write()
+            .unwrap()
+            .retain(|(val, _)| **val != *dispatch_val);
+    }
+    pub fn remove_all_methods(&self) {
+        self.methods.write().unwrap().clear();
+    }
+    /// Makes x win over y when a dispatch value isa? both;  unless y is already
+    /// preferred over x
+    pub fn prefer_method(&self, x: Arc<Value>, y: Arc<Value>) -> Result<(), Value> {
+        let hierarchy = self.hierarchy()?;
+        if self.prefers(&hierarchy, &y, &x) {
+            return Err(Value::Condition(
+                format!(
+                    "Preference conflict in multimethod '{}': {} is already preferred to {}",
+                    self.name, y, x
+                )
+                .into(),
+            ));
+        }
+        let mut prefers = self.prefers.write().unwrap();
+        match prefers.iter_mut().find(|(val, _)| *val == x) {
+            Some((_, over)) => {
+                if !over.contains(&y) {
+                    over.push(y)
+                }
+            }
+            None => prefers.push((x, vec![y])),
+        }
+        Ok(())
+    }
+    // Whether x is preferred over y;  directly,  or through what either derives from
+    fn prefers(&self, hierarchy: &Hierarchy, x: &Value, y: &Value) -> bool {
+        let directly = self
+            .prefers
+            .read()
+            .unwrap()
+            .iter()
+            .any(|(val, over)| **val == *x && over.iter().any(|over| **over == *y));
+        directly
+            || hierarchy
+                .parents(y)
+                .iter()
+                .any(|parent| self.prefers(hierarchy, x, parent))
+            || hierarchy
+                .parents(x)
+                .iter()
+                .any(|parent| self.prefers(hierarchy, parent, y))
+    }
+    fn dominates(&self, hierarchy: &Hierarchy, x: &Value, y: &Value) -> bool {
+        self.prefers(hierarchy, x, y) || hierarchy.isa(x, y)
+    }
+    /// The method dispatch_val calls;  the best of those whose dispatch value it isa?,  or
+    /// failing that the default method.  Ok(None) if there's neither
+    pub fn get_method(&self, dispatch_val: &Value) -> Result<Option<Arc<dyn IFn>>, Value> {
+        let hierarchy = self.hierarchy()?;
+        let methods = self.methods.read().unwrap();
+        let mut best: Option<&(Arc<Value>, Arc<dyn IFn>)> = None;
+        for entry in methods.iter() {
+            if !hierarchy.isa(dispatch_val, &entry.0) {
+                continue;
+            }
+            let best_val = match best {
+                None => {
+                    best = Some(entry);
+                    continue;
+                }
+                Some((best_val, _)) => Arc::clone(best_val),
+            };
+            if self.dominates(&hierarchy, &entry.0, &best_val) {
+                best = Some(entry);
+            } else if !self.dominates(&hierarchy, &best_val, &entry.0) {
+                return Err(Value::Condition(
+                    format!(
+                        "Multiple methods in multimethod '{}' match dispatch value: {} -> {} and {}, and neither is preferred",
+                        self.name, dispatch_val, entry.0, best_val
+                    )
+                    .into(),
+                ));
+            }
+        }
+        if let Some((_, method)) = best {
+            return Ok(Some(Arc::clone(method)));
+        }
+        Ok(methods
+            .iter()
+            .find(|(val, _)| *val == self.default_dispatch_val)
+            .map(|(_, method)| Arc::clone(method)))
+    }
+    /// {dispatch-val method ..}
+    pub fn methods_value(&self) -> Value {
+        self.methods
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(val, method)| MapEntry {
+                key: Arc::clone(val),
+                val: Arc::new(Value::IFn(Arc::new(Arc::clone(method)))),
+            })
+            .collect::<PersistentListMap>()
+            .to_value()
+    }
+    /// {dispatch-val #{dispatch-val it's preferred over ..} ..}
+    pub fn prefers_value(&self) -> Value {
+        self.prefers
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(val, over)| MapEntry {
+                key: Arc::clone(val),
+                val: over
+                    .iter()
+                    .cloned()
+                    .collect::<PersistentHashSet>()
+                    .to_rc_value(),
+            })
+            .collect::<PersistentListMap>()
+            .to_value()
+    }
+}
+impl IFn for Arc<MultiFn> {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let dispatch_val = self.dispatch_val(&args);
+        if let Value::Condition(_) = dispatch_val {
+            return dispatch_val;
+        }
+        match self.get_method(&dispatch_val) {
+            Ok(Some(method)) => method.invoke(args),
+            Ok(None) => Value::Condition(
+                format!(
+                    "No method in multimethod '{}' for dispatch value: {}",
+                    self.name, dispatch_val
+                )
+                .into(),
+            ),
+            Err(condition) => condition,
+        }
+    }
+    fn as_multi_fn(&self) -> Option<&Arc<MultiFn>> {
+        Some(self)
+    }
+}
+
+/// The multimethod val is,  or a condition saying it isn't one
+pub fn from_value(val: &Value) -> Result<&Arc<MultiFn>, Value> {
+    match val {
+        Value::IFn(ifn) => ifn.as_multi_fn(),
+        _ => None,
+    }
+    .ok_or_else(|| error_message::type_mismatch(TypeTag::MultiFn, val))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    #[test]
+    fn dispatches_through_the_hierarchy() {
+        let result = Repl::default().eval_all(&[
+            "(derive :rect :shape)",
+            "(derive :square :rect)",
+            "(defmulti area :kind)",
+            "(defmethod area :rect [r] (* (:w r) (:h r)))",
+            "(defmethod area :shape [_] :unknown)",
+            "(defmethod area :default [_] :not-a-shape)",
+            "[(area {:kind :square :w 2 :h 2}) (area {:kind :shape}) (area {:kind :dog})]",
+        ]);
+        assert_eq!("[4 :unknown :not-a-shape]", result.to_string());
+    }
+
+    #[test]
+    fn ambiguity_is_settled_by_prefer_method() {
+        let repl = Repl::default();
+        let result = repl.eval_all(&[
+            "(def h (derive (derive (make-hierarchy) :tabby :cat) :tabby :pet))",
+            "(defmulti greet (fn [x] x) :hierarchy h)",
+            "(defmethod greet :cat [_] :meow)",
+            "(defmethod greet :pet [_] :hello)",
+            "(greet :tabby)",
+        ]);
+        assert!(matches!(result, Value::Condition(_)));
+        let result = repl.eval_all(&["(prefer-method greet :pet :cat)", "(greet :tabby)"]);
+        assert_eq!(":hello", result.to_string());
+        assert!(matches!(
+            repl.eval_all(&["(prefer-method greet :cat :pet)"]),
+            Value::Condition(_)
+        ));
+    }
+
+    #[test]
+    fn methods_can_be_removed() {
+        let repl = Repl::default();
+        let result = repl.eval_all(&[
+            "(defmulti size first :default :none)",
+            "(defmethod size nil [_] :empty)",
+            "(defmethod size :none [_] :some)",
+            "(remove-method size nil)",
+            "[(size []) (contains? (methods size) nil) (= nil (get-method size nil))]",
+        ]);
+        assert_eq!("[:some false false]", result.to_string());
+        let result = repl.eval_all(&["(remove-all-methods size)", "(size [])"]);
+        assert!(matches!(result, Value::Condition(_)));
+    }
+}
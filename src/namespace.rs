@@ -1,58 +1,337 @@
+use crate::dynamic;
+use crate::keyword::Keyword;
+use crate::persistent_list_map::IPersistentMap;
 use crate::symbol::Symbol;
-use crate::value::Value;
+use crate::value::{ToValue, Value};
+use crate::var::Var;
 use std::cell::RefCell;
-use std::collections::HashMap;
-use std::rc::Rc;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, RwLock};
 
-#[derive(Debug, Clone)]
+/// Where a top-level form was read from;  so where a def in it was made
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    pub file: String,
+    pub line: usize,
+}
+
+thread_local! {
+    // Where the top-level form being evaluated came from,  if we know
+    static DEFINING_AT: RefCell<Option<Location>> = const { RefCell::new(None) };
+}
+
+/// Runs f,  with every def it makes recorded as made at location
+pub fn defining_at<T>(location: Location, f: impl FnOnce() -> T) -> T {
+    let outer = DEFINING_AT.with(|defining_at| defining_at.replace(Some(location)));
+    let result = f();
+    DEFINING_AT.with(|defining_at| *defining_at.borrow_mut() = outer);
+    result
+}
+
+/// Makes a builtin;  see Namespace::insert_builtin
+pub type Builtin = fn() -> Value;
+
+#[derive(Debug)]
 pub struct Namespace {
     pub name: Symbol,
-    mappings: RefCell<HashMap<Symbol, Rc<Value>>>,
+    mappings: RwLock<HashMap<Symbol, Arc<Value>>>,
+    locations: RwLock<HashMap<Symbol, Location>>,
+    // The metadata each symbol was defined with,  if any;  as (def ^:private a 1) gives
+    metas: RwLock<HashMap<Symbol, Arc<Value>>>,
+    // The namespace's own;  where clojure.test keeps its fixtures,  say
+    meta: RwLock<Option<Arc<Value>>>,
+    // The builtins that haven't been looked up yet;  each made,  and moved to mappings,
+    // the first time it is
+    builtins: RwLock<HashMap<&'static str, Builtin>>,
+    // The namespaces known here by another name;  as (require '[clojure.string :as str])
+    // makes str one for clojure.string
+    aliases: RwLock<HashMap<Symbol, Symbol>>,
+    // The names here that are another namespace's;  each by the qualified symbol it
+    // resolves to,  so a redefinition there is seen here too
+    refers: RwLock<HashMap<Symbol, Symbol>>,
+    // The clojure.core names that aren't referred here;  every other one is
+    core_excludes: RwLock<HashSet<Symbol>>,
 }
 impl Namespace {
-    pub fn new(name: &Symbol, mappings: RefCell<HashMap<Symbol, Rc<Value>>>) -> Namespace {
+    pub fn new(name: &Symbol, mappings: RwLock<HashMap<Symbol, Arc<Value>>>) -> Namespace {
         Namespace {
             name: name.unqualified(),
             mappings,
+            locations: RwLock::new(HashMap::new()),
+            metas: RwLock::new(HashMap::new()),
+            meta: RwLock::new(None),
+            builtins: RwLock::new(HashMap::new()),
+            aliases: RwLock::new(HashMap::new()),
+            refers: RwLock::new(HashMap::new()),
+            core_excludes: RwLock::new(HashSet::new()),
         }
     }
     pub fn from_sym(name: &Symbol) -> Namespace {
-        Namespace::new(name, RefCell::new(HashMap::new()))
+        Namespace::new(name, RwLock::new(HashMap::new()))
+    }
+    /// A copy of this namespace as it is now,  that goes its own way from here;  what's
+    /// defined in it is shared,  not copied,  as values can't change
+    pub fn fork(&self) -> Namespace {
+        Namespace {
+            name: self.name.clone(),
+            mappings: RwLock::new(self.mappings.read().unwrap().clone()),
+            locations: RwLock::new(self.locations.read().unwrap().clone()),
+            metas: RwLock::new(self.metas.read().unwrap().clone()),
+            meta: RwLock::new(self.meta.read().unwrap().clone()),
+            builtins: RwLock::new(self.builtins.read().unwrap().clone()),
+            aliases: RwLock::new(self.aliases.read().unwrap().clone()),
+            refers: RwLock::new(self.refers.read().unwrap().clone()),
+            core_excludes: RwLock::new(self.core_excludes.read().unwrap().clone()),
+        }
+    }
+    pub fn insert(&self, sym: &Symbol, val: Arc<Value>) {
+        let sym = sym.unqualified();
+        // A redefinition we can't place shouldn't keep pointing at the old one
+        match DEFINING_AT.with(|defining_at| defining_at.borrow().clone()) {
+            Some(location) => self
+                .locations
+                .write()
+                .unwrap()
+                .insert(sym.clone(), location),
+            None => self.locations.write().unwrap().remove(&sym),
+        };
+        // Nor should it keep the old one's metadata
+        self.metas.write().unwrap().remove(&sym);
+        self.builtins.write().unwrap().remove(sym.name.as_str());
+        self.refers.write().unwrap().remove(&sym);
+        self.mappings.write().unwrap().insert(sym, val);
     }
-    pub fn insert(&self, sym: &Symbol, val: Rc<Value>) {
-        self.mappings.borrow_mut().insert(sym.unqualified(), val);
+    /// Defines name as what make makes,  when it's first looked up
+    pub fn insert_builtin(&self, name: &'static str, make: Builtin) {
+        self.mappings.write().unwrap().remove(&Symbol::intern(name));
+        self.builtins.write().unwrap().insert(name, make);
     }
-    pub fn get(&self, sym: &Symbol) -> Rc<Value> {
-        match self.mappings.borrow_mut().get(&sym.unqualified()) {
-            Some(val) => Rc::clone(val),
-            None => Rc::new(Value::Condition(format!("1 Undefined symbol {}", sym.name))),
+    /// What sym is defined as in this namespace itself
+    pub fn get(&self, sym: &Symbol) -> Arc<Value> {
+        self.interned(sym).unwrap_or_else(|| {
+            Arc::new(Value::Condition(
+                format!("1 Undefined symbol {}", sym.name).into(),
+            ))
+        })
+    }
+    /// What sym is defined as here,  outside of any binding of it
+    pub fn root(&self, sym: &Symbol) -> Arc<Value> {
+        self.defined(&sym.unqualified()).unwrap_or_else(|| {
+            Arc::new(Value::Condition(
+                format!("Var {}/{} is unbound.", self.name, sym.name).into(),
+            ))
+        })
+    }
+    /// Redefines sym as val,  keeping what it was defined with;  its metadata,  and where
+    pub fn set_root(&self, sym: &Symbol, val: Arc<Value>) {
+        let sym = sym.unqualified();
+        self.builtins.write().unwrap().remove(sym.name.as_str());
+        self.mappings.write().unwrap().insert(sym, val);
+    }
+    fn interned(&self, sym: &Symbol) -> Option<Arc<Value>> {
+        let sym = sym.unqualified();
+        // A dynamic var's binding,  while it's bound,  is what it is
+        if let Some(val) = dynamic::bound(&self.name, &sym) {
+            return Some(val);
         }
+        self.defined(&sym)
+    }
+    // What sym,  unqualified,  is defined as here;  making it,  if it's a builtin
+    fn defined(&self, sym: &Symbol) -> Option<Arc<Value>> {
+        if let Some(val) = self.mappings.read().unwrap().get(sym) {
+            return Some(Arc::clone(val));
+        }
+        let make = self.builtins.write().unwrap().remove(sym.name.as_str())?;
+        let val = Arc::new(make());
+        self.mappings
+            .write()
+            .unwrap()
+            .insert(Symbol::clone(sym), Arc::clone(&val));
+        Some(val)
+    }
+    /// Makes alias another name here for the namespace named namespace
+    pub fn add_alias(&self, alias: &Symbol, namespace: &Symbol) {
+        self.aliases
+            .write()
+            .unwrap()
+            .insert(alias.unqualified(), namespace.unqualified());
+    }
+    /// The name of the namespace alias is another name for here,  if it is one
+    pub fn alias(&self, alias: &Symbol) -> Option<Symbol> {
+        self.aliases
+            .read()
+            .unwrap()
+            .get(&alias.unqualified())
+            .cloned()
+    }
+    /// Every alias here,  with the name of its namespace
+    pub fn aliases(&self) -> Vec<(Symbol, Symbol)> {
+        self.aliases
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(alias, namespace)| (alias.clone(), namespace.clone()))
+            .collect()
+    }
+    /// Makes sym here resolve to var,  a qualified symbol;  unless sym is defined here
+    /// already,  as a referred name can't replace what a namespace defines itself
+    pub fn refer(&self, sym: &Symbol, var: Symbol) {
+        let sym = sym.unqualified();
+        if !self.mappings.read().unwrap().contains_key(&sym)
+            && !self
+                .builtins
+                .read()
+                .unwrap()
+                .contains_key(sym.name.as_str())
+        {
+            self.refers.write().unwrap().insert(sym, var);
+        }
+    }
+    /// The qualified symbol sym is referred here as,  if it is
+    pub fn referred(&self, sym: &Symbol) -> Option<Symbol> {
+        self.refers.read().unwrap().get(&sym.unqualified()).cloned()
+    }
+    /// Every name referred here,  with the qualified symbol it resolves to
+    pub fn refers(&self) -> Vec<(Symbol, Symbol)> {
+        self.refers
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(sym, var)| (sym.clone(), var.clone()))
+            .collect()
+    }
+    /// Stops each of syms resolving to clojure.core's here
+    pub fn exclude_core(&self, syms: impl Iterator<Item = Symbol>) {
+        self.core_excludes
+            .write()
+            .unwrap()
+            .extend(syms.map(|sym| sym.unqualified()));
+    }
+    /// Whether sym resolves to clojure.core's here,  when it isn't defined or referred
+    pub fn refers_core(&self, sym: &Symbol) -> bool {
+        self.name.name != "clojure.core" && !self.core_excludes.read().unwrap().contains(sym)
+    }
+    /// Every symbol defined in this namespace, along with its value
+    pub fn mappings(&self) -> Vec<(Symbol, Arc<Value>)> {
+        let builtins: Vec<&str> = self.builtins.read().unwrap().keys().copied().collect();
+        for name in builtins {
+            self.get(&Symbol::intern(name));
+        }
+        self.mappings
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(sym, val)| (sym.clone(), Arc::clone(val)))
+            .collect()
+    }
+    pub fn set_meta(&self, sym: &Symbol, meta: Arc<Value>) {
+        self.metas.write().unwrap().insert(sym.unqualified(), meta);
+    }
+    /// The metadata sym was defined with,  if any
+    pub fn meta(&self, sym: &Symbol) -> Option<Arc<Value>> {
+        self.metas.read().unwrap().get(&sym.unqualified()).cloned()
+    }
+    /// The namespace's own metadata,  if it has any
+    pub fn ns_meta(&self) -> Option<Arc<Value>> {
+        self.meta.read().unwrap().clone()
+    }
+    pub fn set_ns_meta(&self, meta: Arc<Value>) {
+        *self.meta.write().unwrap() = Some(meta);
+    }
+    /// Whether sym was defined ^:private
+    pub fn is_private(&self, sym: &Symbol) -> bool {
+        self.has_flag(sym, "private")
+    }
+    /// Whether sym was defined ^:dynamic;  so binding can rebind it
+    pub fn is_dynamic(&self, sym: &Symbol) -> bool {
+        self.has_flag(sym, "dynamic")
+    }
+    // Whether sym's metadata has flag true
+    fn has_flag(&self, sym: &Symbol, flag: &str) -> bool {
+        self.meta(sym).is_some_and(|meta| match &*meta {
+            Value::PersistentListMap(meta) => {
+                *meta.get(&Keyword::intern(flag).to_rc_value()) == Value::Boolean(true)
+            }
+            _ => false,
+        })
+    }
+    /// Where sym was defined,  if it was defined somewhere we know of
+    pub fn location(&self, sym: &Symbol) -> Option<Location> {
+        self.locations
+            .read()
+            .unwrap()
+            .get(&sym.unqualified())
+            .cloned()
     }
 }
-#[derive(Debug, Clone)]
-pub struct Namespaces(RefCell<HashMap<Symbol, Namespace>>);
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#namespace[{}]", self.name)
+    }
+}
+#[derive(Debug)]
+pub struct Namespaces(RwLock<HashMap<Symbol, Arc<Namespace>>>);
 
 impl Namespaces {
     pub fn new() -> Namespaces {
-        Namespaces(RefCell::new(HashMap::new()))
+        Namespaces(RwLock::new(HashMap::new()))
     }
     fn insert(&self, namespace: Namespace) {
         // When storing / retrieving from namespaces, we want
         // namespace unqualified keys
         self.0
-            .borrow_mut()
-            .insert(namespace.name.unqualified(), namespace);
+            .write()
+            .unwrap()
+            .insert(namespace.name.unqualified(), Arc::new(namespace));
     }
     /// Adds a new namespace to internal HashMap (but does
     /// *not* return a Namespace or reference to one)
     pub fn create_namespace(&self, sym: &Symbol) {
         self.insert(Namespace::from_sym(sym));
     }
+    /// Returns the namespace named sym,  if there is one
+    pub fn get_namespace(&self, sym: &Symbol) -> Option<Arc<Namespace>> {
+        self.0.read().unwrap().get(&sym.unqualified()).cloned()
+    }
+    /// Returns the namespace named sym,  creating it first if it doesn't exist yet
+    pub fn find_or_create_namespace(&self, sym: &Symbol) -> Arc<Namespace> {
+        if let Some(namespace) = self.get_namespace(sym) {
+            return namespace;
+        }
+        self.create_namespace(sym);
+        self.get_namespace(sym).unwrap()
+    }
+    /// A copy of every namespace as it is now;  see Namespace::fork
+    pub fn fork(&self) -> Namespaces {
+        let forked = self
+            .0
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, namespace)| (name.clone(), Arc::new(namespace.fork())))
+            .collect();
+        Namespaces(RwLock::new(forked))
+    }
+    /// Has every namespace be other's;  the very same ones,  so what's defined in either
+    /// from now on is seen by both
+    pub fn adopt(&self, other: &Namespaces) {
+        let adopted = other.0.read().unwrap().clone();
+        *self.0.write().unwrap() = adopted;
+    }
+    /// Forgets every namespace
+    pub fn clear(&self) {
+        self.0.write().unwrap().clear();
+    }
+    /// Every namespace there is, in no particular order
+    pub fn all_namespaces(&self) -> Vec<Arc<Namespace>> {
+        self.0.read().unwrap().values().cloned().collect()
+    }
     /// Insert a new namespace of name (sym)
     pub fn has_namespace(&self, namespace_sym: &Symbol) -> bool {
         let namespace_sym = namespace_sym.unqualified();
 
-        let namespaces = self.0.borrow();
+        let namespaces = self.0.read().unwrap();
         let namespace = namespaces.get(&namespace_sym);
         match namespace {
             Some(_) => true,
@@ -61,7 +340,7 @@ impl Namespaces {
     }
     /// Insert a binding (sym = val) *into* namespace (namespace)
     /// If namespace doesn't exist, create it
-    pub fn insert_into_namespace(&self, namespace_sym: &Symbol, sym: &Symbol, val: Rc<Value>) {
+    pub fn insert_into_namespace(&self, namespace_sym: &Symbol, sym: &Symbol, val: Arc<Value>) {
         let mut namespace_sym = &namespace_sym.unqualified();
         // We will only use this if ns isn't ""
         let symbol_namespace_sym = Symbol::intern(&sym.ns);
@@ -70,7 +349,7 @@ impl Namespaces {
             namespace_sym = &symbol_namespace_sym;
         }
 
-        let namespaces = self.0.borrow();
+        let namespaces = self.0.read().unwrap();
         let namespace = namespaces.get(namespace_sym);
         match namespace {
             Some(namespace) => {
@@ -84,27 +363,72 @@ impl Namespaces {
             }
         }
     }
-    /// Get value of sym at namespace
-    pub fn get(&self, namespace_sym: &Symbol, sym: &Symbol) -> Rc<Value> {
+    /// Get value of sym at namespace;  what it's defined as there,  or refers to.  A
+    /// qualified sym is looked up in the namespace it names,  or that's known by that alias
+    /// at namespace
+    pub fn get(&self, namespace_sym: &Symbol, sym: &Symbol) -> Arc<Value> {
+        let namespace_sym = self.namespace_for(namespace_sym, sym);
+        let sym = sym.unqualified();
+        match self
+            .get_namespace(&namespace_sym)
+            .and_then(|namespace| self.resolve(&namespace, &sym))
+        {
+            Some(val) => val,
+            // @TODO should this be a condition or nil?
+            _ => Arc::new(Value::Condition(
+                format!("Undefined symbol {}", sym.name).into(),
+            )),
+        }
+    }
+    /// The var sym is at namespace,  if it's one;  found as get finds its value
+    pub fn resolve_var(&self, namespace_sym: &Symbol, sym: &Symbol) -> Option<Var> {
+        let namespace = self.get_namespace(&self.namespace_for(namespace_sym, sym))?;
+        let sym = sym.unqualified();
+        if namespace.defined(&sym).is_some() {
+            return Some(Var::new(namespace, &sym));
+        }
+        if let Some(var) = namespace.referred(&sym) {
+            let namespace = self.get_namespace(&Symbol::intern(&var.ns))?;
+            return Some(Var::new(namespace, &var));
+        }
+        let core = self.get_namespace(&Symbol::intern("clojure.core"))?;
+        if namespace.refers_core(&sym) && core.defined(&sym).is_some() {
+            return Some(Var::new(core, &sym));
+        }
+        None
+    }
+    // The name of the namespace sym is looked up in from namespace_sym;  the one a
+    // qualified sym names,  or that's known by that alias there
+    fn namespace_for(&self, namespace_sym: &Symbol, sym: &Symbol) -> Symbol {
         // When storing / retrieving from namespaces, we want
         // namespace_sym unqualified keys
-        let mut namespace_sym = namespace_sym.unqualified();
-
+        let namespace_sym = namespace_sym.unqualified();
         // @TODO just make it an Optional<String>
-        // If our sym is namespace qualified,  use that as our namespace
-        if sym.has_ns() {
-            namespace_sym = Symbol::intern(&sym.ns);
+        if !sym.has_ns() {
+            return namespace_sym;
         }
-
-        let sym = sym.unqualified();
-        let namespaces = self.0.borrow();
-        let namespace = namespaces.get(&namespace_sym);
-
-        match namespace {
-            Some(namespace) => Rc::clone(&namespace.get(&sym)),
-            // @TODO should this be a condition or nil?
-            _ => Rc::new(Value::Condition(format!("Undefined symbol {}", sym.name))),
+        let qualifier = Symbol::intern(&sym.ns);
+        self.get_namespace(&namespace_sym)
+            .and_then(|namespace| namespace.alias(&qualifier))
+            .unwrap_or(qualifier)
+    }
+    // What sym is at namespace;  defined there,  referred from another namespace or,  failing
+    // both,  clojure.core's
+    fn resolve(&self, namespace: &Namespace, sym: &Symbol) -> Option<Arc<Value>> {
+        if let Some(val) = namespace.interned(sym) {
+            return Some(val);
         }
+        if let Some(var) = namespace.referred(sym) {
+            return self
+                .get_namespace(&Symbol::intern(&var.ns))?
+                .interned(&var.unqualified());
+        }
+        if namespace.refers_core(sym) {
+            return self
+                .get_namespace(&Symbol::intern("clojure.core"))?
+                .interned(sym);
+        }
+        None
     }
 }
 
@@ -114,39 +438,38 @@ mod tests {
     // why the word 'namespace' is repeated and that this is actually specifically
     // a struct
     mod namespace_struct {
-        use crate::namespace::Namespace;
+        use crate::namespace::{self, Location, Namespace};
         use crate::symbol::Symbol;
         use crate::value::Value;
-        use std::cell::RefCell;
         use std::collections::HashMap;
-        use std::rc::Rc;
+        use std::sync::{Arc, RwLock};
 
         #[test]
         fn new() {
-            let namespace = Namespace::new(&Symbol::intern("a"), RefCell::new(HashMap::new()));
+            let namespace = Namespace::new(&Symbol::intern("a"), RwLock::new(HashMap::new()));
             assert_eq!(namespace.name, Symbol::intern("a"));
-            assert!(namespace.mappings.borrow().is_empty());
+            assert!(namespace.mappings.read().unwrap().is_empty());
         }
 
         #[test]
         fn new_removes_namespace_from_qualified_symbol() {
             let namespace = Namespace::new(
                 &Symbol::intern_with_ns("ns", "a"),
-                RefCell::new(HashMap::new()),
+                RwLock::new(HashMap::new()),
             );
             assert_eq!(namespace.name, Symbol::intern("a"));
             assert!(namespace.name != Symbol::intern_with_ns("ns", "a"));
-            assert!(namespace.mappings.borrow().is_empty());
+            assert!(namespace.mappings.read().unwrap().is_empty());
         }
         #[test]
         fn new_namespace_starts_empty() {
-            let namespace = Namespace::new(&Symbol::intern("a"), RefCell::new(HashMap::new()));
+            let namespace = Namespace::new(&Symbol::intern("a"), RwLock::new(HashMap::new()));
             let namespace2 = Namespace::new(
                 &Symbol::intern_with_ns("ns", "b"),
-                RefCell::new(HashMap::new()),
+                RwLock::new(HashMap::new()),
             );
-            assert!(namespace.mappings.borrow().is_empty());
-            assert!(namespace2.mappings.borrow().is_empty());
+            assert!(namespace.mappings.read().unwrap().is_empty());
+            assert!(namespace2.mappings.read().unwrap().is_empty());
         }
 
         #[test]
@@ -154,18 +477,18 @@ mod tests {
             let namespace = Namespace::from_sym(&Symbol::intern_with_ns("ns", "name"));
             assert_eq!(namespace.name, Symbol::intern("name"));
             assert!(namespace.name != Symbol::intern_with_ns("ns", "name"));
-            assert!(namespace.mappings.borrow().is_empty());
+            assert!(namespace.mappings.read().unwrap().is_empty());
         }
         #[test]
         fn insert() {
             let namespace = Namespace::from_sym(&Symbol::intern("name"));
-            namespace.insert(&Symbol::intern("a"), Rc::new(Value::Nil));
-            namespace.insert(&Symbol::intern_with_ns("ns", "b"), Rc::new(Value::Nil));
+            namespace.insert(&Symbol::intern("a"), Arc::new(Value::Nil));
+            namespace.insert(&Symbol::intern_with_ns("ns", "b"), Arc::new(Value::Nil));
             assert_eq!(namespace.name, Symbol::intern("name"));
             assert!(namespace.name != Symbol::intern("ns"));
             assert!(namespace.name != Symbol::intern_with_ns("ns", "name"));
 
-            namespace.insert(&Symbol::intern("c"), Rc::new(Value::Nil));
+            namespace.insert(&Symbol::intern("c"), Arc::new(Value::Nil));
             match &*namespace.get(&Symbol::intern("c")) {
                 Value::Condition(_) => panic!("We are unable to get a symbol we've put into our namespace created with from_sym()"),
                 _ => {}
@@ -175,8 +498,8 @@ mod tests {
         #[test]
         fn get() {
             let namespace = Namespace::from_sym(&Symbol::intern("name"));
-            namespace.insert(&Symbol::intern("a"), Rc::new(Value::Nil));
-            namespace.insert(&Symbol::intern_with_ns("ns", "b"), Rc::new(Value::Nil));
+            namespace.insert(&Symbol::intern("a"), Arc::new(Value::Nil));
+            namespace.insert(&Symbol::intern_with_ns("ns", "b"), Arc::new(Value::Nil));
             match &*namespace.get(&Symbol::intern("a")) {
                 Value::Condition(_) => panic!("We are unable to get a symbol we've put into our namespace created with from_sym()"),
                 _ => {}
@@ -209,26 +532,69 @@ mod tests {
                 _ => {}
             }
         }
+
+        #[test]
+        fn insert_records_where_it_was_defined() {
+            let namespace = Namespace::from_sym(&Symbol::intern("name"));
+            let location = Location {
+                file: String::from("a.clj"),
+                line: 3,
+            };
+            namespace::defining_at(location.clone(), || {
+                namespace.insert(&Symbol::intern("a"), Arc::new(Value::Nil))
+            });
+            assert_eq!(Some(location), namespace.location(&Symbol::intern("a")));
+
+            namespace.insert(&Symbol::intern("a"), Arc::new(Value::Nil));
+            assert_eq!(None, namespace.location(&Symbol::intern("a")));
+        }
+
+        #[test]
+        fn builtins_are_made_once_when_first_looked_up() {
+            let namespace = Namespace::from_sym(&Symbol::intern("name"));
+            namespace.insert_builtin("a", || Value::I32(1));
+            namespace.insert_builtin("b", || Value::I32(2));
+            namespace.insert_builtin("c", || Value::I32(3));
+            namespace.insert(&Symbol::intern("b"), Arc::new(Value::Nil));
+
+            let a = namespace.get(&Symbol::intern("a"));
+            assert_eq!(Value::I32(1), *a);
+            assert!(Arc::ptr_eq(&a, &namespace.get(&Symbol::intern("a"))));
+            assert_eq!(Value::Nil, *namespace.get(&Symbol::intern("b")));
+
+            let mut mappings: Vec<(String, String)> = namespace
+                .mappings()
+                .iter()
+                .map(|(sym, val)| (sym.to_string(), val.to_string()))
+                .collect();
+            mappings.sort();
+            let expected = [("a", "1"), ("b", "nil"), ("c", "3")];
+            let expected: Vec<(String, String)> = expected
+                .iter()
+                .map(|(sym, val)| (sym.to_string(), val.to_string()))
+                .collect();
+            assert_eq!(expected, mappings);
+        }
     }
     mod namespaces_newtype {
         use crate::namespace::Namespace;
         use crate::namespace::Namespaces;
         use crate::symbol::Symbol;
         use crate::value::Value;
-        use std::rc::Rc;
+        use std::sync::Arc;
         fn new() {
             let namespaces = Namespaces::new();
-            assert!(namespaces.0.borrow().is_empty());
+            assert!(namespaces.0.read().unwrap().is_empty());
         }
         fn insert() {
             let namespaces = Namespaces::new();
             let namespace = Namespace::from_sym(&Symbol::intern("clojure.core"));
-            namespace.insert(&Symbol::intern("+"), Rc::new(Value::Nil));
+            namespace.insert(&Symbol::intern("+"), Arc::new(Value::Nil));
             // Namespace should be dropped; doesn't matter when inserting into
             // a namespace
             namespace.insert(
                 &Symbol::intern_with_ns("clojure.math", "+"),
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
             );
             /////////////////////////////////////////////////////////////
             namespaces.insert(namespace);
@@ -260,7 +626,7 @@ mod tests {
         fn has_namespace() {
             let namespaces = Namespaces::new();
             let namespace = Namespace::from_sym(&Symbol::intern("clojure.core"));
-            namespace.insert(&Symbol::intern("+"), Rc::new(Value::Nil));
+            namespace.insert(&Symbol::intern("+"), Arc::new(Value::Nil));
 
             assert!(namespaces.has_namespace(&Symbol::intern("clojure.core")));
             assert!(namespaces
@@ -275,7 +641,7 @@ mod tests {
             namespaces.insert_into_namespace(
                 &Symbol::intern("clojure.core"),
                 &Symbol::intern("+"),
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
             );
             assert!(!namespaces.has_namespace(&Symbol::intern("random_ns")));
             assert!(namespaces.has_namespace(&Symbol::intern("clojure.core")));
@@ -295,9 +661,28 @@ mod tests {
                 Value::Nil != *namespaces.get(&Symbol::intern("other-ns"), &Symbol::intern("+"))
             );
         }
+        #[test]
+        fn find_or_create_namespace_keeps_existing() {
+            let namespaces = Namespaces::new();
+            namespaces.insert_into_namespace(
+                &Symbol::intern("core2"),
+                &Symbol::intern("+2"),
+                Arc::new(Value::Nil),
+            );
+            let namespace = namespaces.find_or_create_namespace(&Symbol::intern("core2"));
+            assert_eq!(Value::Nil, *namespace.get(&Symbol::intern("+2")));
+
+            assert!(namespaces.get_namespace(&Symbol::intern("core3")).is_none());
+            let created = namespaces.find_or_create_namespace(&Symbol::intern("core3"));
+            assert!(Arc::ptr_eq(
+                &created,
+                &namespaces.get_namespace(&Symbol::intern("core3")).unwrap()
+            ));
+            assert_eq!(2, namespaces.all_namespaces().len());
+        }
         ////////////////////////////////////////////////////////////////////////////////////////////////////
         //
-        //  pub fn get(&self,namespace_sym: &Symbol,sym: &Symbol) -> Rc<Value>
+        //  pub fn get(&self,namespace_sym: &Symbol,sym: &Symbol) -> Arc<Value>
         //
         ////////////////////////////////////////////////////////////////////////////////////////////////////
         #[test]
@@ -323,7 +708,7 @@ mod tests {
             namespaces.insert_into_namespace(
                 &Symbol::intern("clojure.core1"),
                 &Symbol::intern("+1"),
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
             );
             match &*namespaces.get(&Symbol::intern("clojure.your"), &clojure_core1_plus_1) {
                 Value::Condition(_) => {
@@ -346,7 +731,7 @@ mod tests {
             namespaces.insert_into_namespace(
                 &Symbol::intern("clojure.core"),
                 &Symbol::intern("+"),
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
             );
             // Really means get +/+,  but is overwritten to mean get clojure.core/+
             match &*namespaces.get(&Symbol::intern("clojure.core/+"), &clojure_core_plus) {
@@ -369,7 +754,7 @@ mod tests {
             namespaces.insert_into_namespace(
                 &Symbol::intern("core2"),
                 &Symbol::intern("+2"),
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
             );
             // Get intern("core2/+2")
             // ----------------------
@@ -392,7 +777,7 @@ mod tests {
             namespaces.insert_into_namespace(
                 &Symbol::intern("core2"),
                 &Symbol::intern("+2"),
-                Rc::new(Value::Nil),
+                Arc::new(Value::Nil),
             );
 
             let plus_2 = Symbol::intern("+2");
@@ -423,6 +808,6 @@ mod tests {
         //
         ////////////////////////////////////////////////////////////////////////////////////////////////////
 
-        //let namespaces.insert_into_namespace(&Symbol::intern("clojure.core/+"), , ${3:val: Rc<Value>})
+        //let namespaces.insert_into_namespace(&Symbol::intern("clojure.core/+"), , ${3:val: Arc<Value>})
     }
 }
@@ -0,0 +1,40 @@
+//! Fns written in Rust by whatever embeds us,  rather than by us;  so it needn't write an
+//! IFn of its own to give scripts one (see Repl::register_fn)
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::fmt;
+use std::sync::Arc;
+
+type Body = dyn Fn(Vec<Arc<Value>>) -> Value + Send + Sync;
+
+#[derive(Clone)]
+pub struct NativeFn {
+    name: String,
+    body: Arc<Body>,
+}
+impl NativeFn {
+    pub fn new(
+        name: &str,
+        body: impl Fn(Vec<Arc<Value>>) -> Value + Send + Sync + 'static,
+    ) -> NativeFn {
+        NativeFn {
+            name: name.to_string(),
+            body: Arc::new(body),
+        }
+    }
+}
+impl fmt::Debug for NativeFn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NativeFn({})", self.name)
+    }
+}
+impl ToValue for NativeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NativeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        (self.body)(args)
+    }
+}
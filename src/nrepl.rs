@@ -0,0 +1,121 @@
+//! An nREPL server,  so editors can connect to a running ClojureRS and evaluate code in it
+//!
+//! See https://nrepl.org/nrepl/design/overview.html for the protocol
+pub(crate) mod bencode;
+pub(crate) mod metrics;
+#[cfg(test)]
+pub(crate) mod script;
+pub(crate) mod server;
+pub(crate) mod tls;
+pub use self::server::*;
+
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+
+pub const DEFAULT_PORT: u16 = 7888;
+/// What servers listen on unless told otherwise;  this machine only
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+
+/// The `.nrepl-port` file editors (CIDER, Calva, ..) look for in a project's directory
+/// to find a running server.  It is removed again when this is dropped
+pub struct PortFile {
+    path: PathBuf,
+}
+impl PortFile {
+    pub fn create(port: u16) -> io::Result<PortFile> {
+        let path = PathBuf::from(".nrepl-port");
+        fs::write(&path, port.to_string())?;
+        Ok(PortFile { path })
+    }
+}
+impl Drop for PortFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// What clients must send before the server answers anything but describe;  a token we're
+/// given,  or one to make up and write to `.nrepl-auth-token`
+#[derive(Debug, PartialEq)]
+pub enum Auth {
+    Token(String),
+    Generate,
+}
+
+/// A token no one could guess
+pub fn generate_auth_token() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// `.nrepl-auth-token`,  next to `.nrepl-port`;  holding a token we made up,  for editors
+/// started by the same user to send.  Only that user can read it,  and it is removed again
+/// when this is dropped
+pub struct AuthTokenFile {
+    path: PathBuf,
+}
+impl AuthTokenFile {
+    pub fn create(token: &str) -> io::Result<AuthTokenFile> {
+        let path = PathBuf::from(".nrepl-auth-token");
+        // Or an old one,  readable by anyone,  would keep its permissions
+        let _ = fs::remove_file(&path);
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(&path)?.write_all(token.as_bytes())?;
+        Ok(AuthTokenFile { path })
+    }
+}
+impl Drop for AuthTokenFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Parses the arguments following `nrepl` on the command line,  returning the port to
+/// listen on.  Port 0 lets the OS pick a free one
+pub fn parse_port(args: &[String]) -> Result<u16, String> {
+    match args {
+        [] => Ok(DEFAULT_PORT),
+        [flag, port] if flag == "--port" || flag == "-p" => port
+            .parse::<u16>()
+            .map_err(|_| format!("Invalid port: {}", port)),
+        _ => Err(String::from(
+            "Usage: rust_clojure nrepl [--port PORT] [--record FILE]",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod parse_port_tests {
+        use crate::nrepl::{parse_port, DEFAULT_PORT};
+
+        fn args(args: &[&str]) -> Vec<String> {
+            args.iter().map(|arg| String::from(*arg)).collect()
+        }
+
+        #[test]
+        fn parse_port_defaults() {
+            assert_eq!(Ok(DEFAULT_PORT), parse_port(&args(&[])));
+        }
+
+        #[test]
+        fn parse_port_flag() {
+            assert_eq!(Ok(0), parse_port(&args(&["--port", "0"])));
+            assert_eq!(Ok(5555), parse_port(&args(&["-p", "5555"])));
+        }
+
+        #[test]
+        fn parse_port_rejects_garbage() {
+            assert!(parse_port(&args(&["--port", "http"])).is_err());
+            assert!(parse_port(&args(&["--port", "70000"])).is_err());
+            assert!(parse_port(&args(&["--verbose"])).is_err());
+        }
+    }
+}
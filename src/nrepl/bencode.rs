@@ -0,0 +1,477 @@
+//! Bencode, the wire format nREPL speaks by default
+//!
+//! Only the four bencode types exist: integers, byte strings, lists and dictionaries.
+//! nREPL only ever sends text in its byte strings, so we decode them straight to Strings
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bencode {
+    Int(i64),
+    Str(String),
+    List(Vec<Bencode>),
+    // Bencode requires dictionary keys to be written in sorted order, which
+    // BTreeMap gives us for free
+    Dict(BTreeMap<String, Bencode>),
+}
+impl Bencode {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Bencode::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Bencode::Int(i) => out.extend_from_slice(format!("i{}e", i).as_bytes()),
+            Bencode::Str(s) => {
+                out.extend_from_slice(format!("{}:", s.len()).as_bytes());
+                out.extend_from_slice(s.as_bytes());
+            }
+            Bencode::List(items) => {
+                out.push(b'l');
+                for item in items.iter() {
+                    item.encode(out);
+                }
+                out.push(b'e');
+            }
+            Bencode::Dict(entries) => {
+                out.push(b'd');
+                for (key, val) in entries.iter() {
+                    Bencode::Str(key.clone()).encode(out);
+                    val.encode(out);
+                }
+                out.push(b'e');
+            }
+        }
+    }
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![];
+        self.encode(&mut out);
+        out
+    }
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_bytes())?;
+        writer.flush()
+    }
+}
+impl From<&str> for Bencode {
+    fn from(s: &str) -> Bencode {
+        Bencode::Str(String::from(s))
+    }
+}
+impl From<String> for Bencode {
+    fn from(s: String) -> Bencode {
+        Bencode::Str(s)
+    }
+}
+impl From<i64> for Bencode {
+    fn from(i: i64) -> Bencode {
+        Bencode::Int(i)
+    }
+}
+impl fmt::Display for Bencode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.to_bytes()))
+    }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn read_byte<R: BufRead>(reader: &mut R) -> io::Result<Option<u8>> {
+    let mut byte = [0u8; 1];
+    match reader.read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+fn expect_byte<R: BufRead>(reader: &mut R) -> io::Result<u8> {
+    read_byte(reader)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Connection closed in the middle of a bencode message",
+        )
+    })
+}
+
+// Reads digits (and a leading '-') up to and including the terminator
+fn read_number<R: BufRead>(reader: &mut R, first: u8, terminator: u8) -> io::Result<i64> {
+    let mut digits = vec![first];
+    loop {
+        let byte = expect_byte(reader)?;
+        if byte == terminator {
+            break;
+        }
+        digits.push(byte);
+    }
+    let digits = String::from_utf8_lossy(&digits).to_string();
+    digits
+        .parse::<i64>()
+        .map_err(|_| invalid_data(format!("Invalid bencode number: {}", digits)))
+}
+
+fn read_value<R: BufRead>(reader: &mut R, first: u8) -> io::Result<Bencode> {
+    match first {
+        b'i' => {
+            let first_digit = expect_byte(reader)?;
+            Ok(Bencode::Int(read_number(reader, first_digit, b'e')?))
+        }
+        b'l' => {
+            let mut items = vec![];
+            loop {
+                match expect_byte(reader)? {
+                    b'e' => return Ok(Bencode::List(items)),
+                    byte => items.push(read_value(reader, byte)?),
+                }
+            }
+        }
+        b'd' => {
+            let mut entries = BTreeMap::new();
+            loop {
+                let byte = expect_byte(reader)?;
+                if byte == b'e' {
+                    return Ok(Bencode::Dict(entries));
+                }
+                let key = match read_value(reader, byte)? {
+                    Bencode::Str(key) => key,
+                    other => {
+                        return Err(invalid_data(format!(
+                            "Bencode dictionary keys must be strings, got {}",
+                            other
+                        )))
+                    }
+                };
+                let val_first = expect_byte(reader)?;
+                entries.insert(key, read_value(reader, val_first)?);
+            }
+        }
+        b'0'..=b'9' => {
+            let len = read_number(reader, first, b':')?;
+            let mut bytes = vec![0u8; len as usize];
+            reader.read_exact(&mut bytes)?;
+            Ok(Bencode::Str(String::from_utf8_lossy(&bytes).to_string()))
+        }
+        other => Err(invalid_data(format!(
+            "Unexpected byte '{}' at start of bencode value",
+            other as char
+        ))),
+    }
+}
+
+/// Reads the next bencode value off of the stream,  returning None if the stream
+/// ends cleanly before a new value starts
+pub fn read_bencode<R: BufRead>(reader: &mut R) -> io::Result<Option<Bencode>> {
+    match read_byte(reader)? {
+        None => Ok(None),
+        Some(first) => read_value(reader, first).map(Some),
+    }
+}
+
+/// How deeply lists and dictionaries may nest in one message.  nREPL messages are only
+/// ever a few levels deep;  this just keeps a hostile one from blowing our stack
+const MAX_DEPTH: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScanState {
+    // Expecting the start of a value (or the 'e' closing a list or dict)
+    Value,
+    Int,
+    Len(usize),
+    // Bytes of string left to skip past
+    Str(usize),
+}
+
+/// An incremental bencode decoder:  bytes go in as they arrive off the socket, in
+/// whatever chunks they happen to come in, and whole messages come out
+///
+/// It scans new bytes as they are fed to it, so it only keeps its place in the message
+/// rather than re-reading it;  a string's body is skipped over in one step.  A message is
+/// only decoded once all of it is here, so nothing is allocated based on a length a
+/// client merely claims
+#[derive(Debug)]
+pub struct Decoder {
+    buf: Vec<u8>,
+    // How far into buf we've scanned
+    pos: usize,
+    depth: usize,
+    state: ScanState,
+}
+impl Default for Decoder {
+    fn default() -> Decoder {
+        Decoder {
+            buf: vec![],
+            pos: 0,
+            depth: 0,
+            state: ScanState::Value,
+        }
+    }
+}
+impl Decoder {
+    pub fn new() -> Decoder {
+        Decoder::default()
+    }
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+    /// Returns the next whole message, if one has arrived.  An error means the stream is
+    /// not bencode, and there is no telling where the next message would start
+    pub fn next_message(&mut self) -> io::Result<Option<Bencode>> {
+        match self.scan()? {
+            Some(end) => {
+                let msg = read_bencode(&mut &self.buf[..end])?;
+                self.buf.drain(..end);
+                self.pos = 0;
+                Ok(msg)
+            }
+            None => Ok(None),
+        }
+    }
+    // Advances through the unscanned bytes, returning the end of the first message
+    // if we reach it
+    fn scan(&mut self) -> io::Result<Option<usize>> {
+        while self.pos < self.buf.len() {
+            let byte = self.buf[self.pos];
+            let mut value_done = false;
+            match self.state {
+                ScanState::Value => match byte {
+                    b'i' => self.state = ScanState::Int,
+                    b'l' | b'd' => {
+                        self.depth += 1;
+                        if self.depth > MAX_DEPTH {
+                            return Err(invalid_data(String::from(
+                                "Bencode message is nested too deeply",
+                            )));
+                        }
+                    }
+                    b'e' if self.depth > 0 => {
+                        self.depth -= 1;
+                        value_done = true;
+                    }
+                    b'0'..=b'9' => self.state = ScanState::Len((byte - b'0') as usize),
+                    _ => {
+                        return Err(invalid_data(format!(
+                            "Unexpected byte '{}' at start of bencode value",
+                            byte as char
+                        )))
+                    }
+                },
+                ScanState::Int => match byte {
+                    b'e' => {
+                        self.state = ScanState::Value;
+                        value_done = true;
+                    }
+                    b'0'..=b'9' | b'-' => {}
+                    _ => {
+                        return Err(invalid_data(format!(
+                            "Unexpected byte '{}' in bencode integer",
+                            byte as char
+                        )))
+                    }
+                },
+                ScanState::Len(len) => match byte {
+                    b':' if len == 0 => {
+                        self.state = ScanState::Value;
+                        value_done = true;
+                    }
+                    b':' => self.state = ScanState::Str(len),
+                    b'0'..=b'9' => {
+                        let len = len
+                            .checked_mul(10)
+                            .and_then(|len| len.checked_add((byte - b'0') as usize))
+                            .ok_or_else(|| {
+                                invalid_data(String::from("Bencode string length overflows"))
+                            })?;
+                        self.state = ScanState::Len(len);
+                    }
+                    _ => {
+                        return Err(invalid_data(format!(
+                            "Unexpected byte '{}' in bencode string length",
+                            byte as char
+                        )))
+                    }
+                },
+                ScanState::Str(remaining) => {
+                    let available = self.buf.len() - self.pos;
+                    if available < remaining {
+                        self.pos = self.buf.len();
+                        self.state = ScanState::Str(remaining - available);
+                        continue;
+                    }
+                    // Land on the string's last byte;  the increment below steps past it
+                    self.pos += remaining - 1;
+                    self.state = ScanState::Value;
+                    value_done = true;
+                }
+            }
+            self.pos += 1;
+            if value_done && self.depth == 0 {
+                return Ok(Some(self.pos));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod read_bencode_tests {
+        use crate::nrepl::bencode::{read_bencode, Bencode};
+        use std::collections::BTreeMap;
+        use std::io::Cursor;
+
+        fn read(input: &str) -> Bencode {
+            read_bencode(&mut Cursor::new(input.as_bytes()))
+                .unwrap()
+                .unwrap()
+        }
+
+        #[test]
+        fn read_int() {
+            assert_eq!(Bencode::Int(-42), read("i-42e"));
+        }
+
+        #[test]
+        fn read_str() {
+            assert_eq!(Bencode::from("(+ 1 2)"), read("7:(+ 1 2)"));
+        }
+
+        #[test]
+        fn read_list() {
+            assert_eq!(
+                Bencode::List(vec![Bencode::Int(1), Bencode::from("done")]),
+                read("li1e4:donee")
+            );
+        }
+
+        #[test]
+        fn read_dict() {
+            let mut expected = BTreeMap::new();
+            expected.insert(String::from("op"), Bencode::from("eval"));
+            expected.insert(String::from("id"), Bencode::Int(3));
+            assert_eq!(Bencode::Dict(expected), read("d2:op4:eval2:idi3ee"));
+        }
+
+        #[test]
+        fn read_empty_stream() {
+            assert_eq!(None, read_bencode(&mut Cursor::new(b"")).unwrap());
+        }
+
+        #[test]
+        fn read_truncated_message() {
+            assert!(read_bencode(&mut Cursor::new(b"d2:op4:ev")).is_err());
+        }
+
+        #[test]
+        fn read_consecutive_messages() {
+            let mut input = Cursor::new(b"i1ei2e");
+            assert_eq!(Some(Bencode::Int(1)), read_bencode(&mut input).unwrap());
+            assert_eq!(Some(Bencode::Int(2)), read_bencode(&mut input).unwrap());
+            assert_eq!(None, read_bencode(&mut input).unwrap());
+        }
+    }
+
+    mod encode_tests {
+        use crate::nrepl::bencode::Bencode;
+        use std::collections::BTreeMap;
+
+        #[test]
+        fn encode_dict_sorts_keys() {
+            let mut dict = BTreeMap::new();
+            dict.insert(String::from("value"), Bencode::from("3"));
+            dict.insert(String::from("id"), Bencode::from("1"));
+            assert_eq!(
+                b"d2:id1:15:value1:3e".to_vec(),
+                Bencode::Dict(dict).to_bytes()
+            );
+        }
+
+        #[test]
+        fn encode_multibyte_str_uses_byte_length() {
+            assert_eq!("2:é".as_bytes().to_vec(), Bencode::from("é").to_bytes());
+        }
+    }
+
+    mod decoder_tests {
+        use crate::nrepl::bencode::{Bencode, Decoder};
+
+        const MSG: &[u8] = b"d4:code7:(+ 1 2)2:op4:evale";
+
+        #[test]
+        fn decode_message_fed_one_byte_at_a_time() {
+            let mut decoder = Decoder::new();
+            for (ind, byte) in MSG.iter().enumerate() {
+                assert_eq!(
+                    None,
+                    decoder.next_message().unwrap(),
+                    "early at byte {}",
+                    ind
+                );
+                decoder.feed(&[*byte]);
+            }
+            let msg = decoder.next_message().unwrap().unwrap();
+            assert_eq!(MSG.to_vec(), msg.to_bytes());
+            assert_eq!(None, decoder.next_message().unwrap());
+        }
+
+        #[test]
+        fn decode_several_messages_from_one_read() {
+            let mut decoder = Decoder::new();
+            decoder.feed(&[MSG, MSG, b"i4e", &MSG[..5]].concat());
+            assert_eq!(
+                MSG.to_vec(),
+                decoder.next_message().unwrap().unwrap().to_bytes()
+            );
+            assert_eq!(
+                MSG.to_vec(),
+                decoder.next_message().unwrap().unwrap().to_bytes()
+            );
+            assert_eq!(Some(Bencode::Int(4)), decoder.next_message().unwrap());
+            assert_eq!(None, decoder.next_message().unwrap());
+            decoder.feed(&MSG[5..]);
+            assert_eq!(
+                MSG.to_vec(),
+                decoder.next_message().unwrap().unwrap().to_bytes()
+            );
+        }
+
+        #[test]
+        fn decode_large_string_across_reads() {
+            let body = "x".repeat(1 << 20);
+            let encoded = Bencode::from(body.as_str()).to_bytes();
+            let mut decoder = Decoder::new();
+            for chunk in encoded.chunks(4096) {
+                decoder.feed(chunk);
+            }
+            assert_eq!(
+                Some(Bencode::from(body.as_str())),
+                decoder.next_message().unwrap()
+            );
+        }
+
+        #[test]
+        fn claimed_length_alone_allocates_nothing() {
+            let mut decoder = Decoder::new();
+            decoder.feed(b"99999999999999:abc");
+            assert_eq!(None, decoder.next_message().unwrap());
+        }
+
+        #[test]
+        fn decode_errors() {
+            for bad in [&b"x"[..], b"99999999999999999999999:", b"i1xe", b"e"].iter() {
+                let mut decoder = Decoder::new();
+                decoder.feed(bad);
+                assert!(decoder.next_message().is_err(), "accepted {:?}", bad);
+            }
+            let mut decoder = Decoder::new();
+            decoder.feed(&b"l".repeat(1000));
+            assert!(decoder.next_message().is_err());
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! What an nREPL server has been doing;  counted as it serves,  for the metrics op to give
+//! in Prometheus' text format,  and an access log of every request it's sent
+use crate::nrepl::server::Request;
+use crate::transcript;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// The counts kept of a server's requests and evaluations.  Clones all count together,  so
+/// each connection and session can have one
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    counts: Arc<Counts>,
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    // By op;  any op we don't answer counted as "unknown"
+    requests: Mutex<BTreeMap<&'static str, u64>>,
+    evals: AtomicU64,
+    eval_errors: AtomicU64,
+    eval_nanos: AtomicU64,
+}
+
+impl Metrics {
+    /// Counts a request for op,  one of ops if we answer it
+    pub fn request(&self, op: Option<&str>, ops: &[&'static str]) {
+        let op = ops
+            .iter()
+            .find(|known| Some(**known) == op)
+            .copied()
+            .unwrap_or("unknown");
+        *self.counts.requests.lock().unwrap().entry(op).or_insert(0) += 1;
+    }
+    /// Counts an evaluation that took elapsed;  and that failed,  or was interrupted,  if
+    /// it didn't go through
+    pub fn eval(&self, elapsed: Duration, went_through: bool) {
+        self.counts.evals.fetch_add(1, Ordering::Relaxed);
+        if !went_through {
+            self.counts.eval_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.counts.eval_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+    /// The counts in Prometheus' text format,  with active_sessions as the sessions open
+    pub fn prometheus(&self, active_sessions: usize) -> String {
+        let evals = self.counts.evals.load(Ordering::Relaxed);
+        let eval_errors = self.counts.eval_errors.load(Ordering::Relaxed);
+        let eval_seconds = self.counts.eval_nanos.load(Ordering::Relaxed) as f64 / 1e9;
+        // Of no evaluations,  none failed and none took any time
+        let per_eval = |total: f64| match evals {
+            0 => 0.0,
+            evals => total / evals as f64,
+        };
+        let mut text = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, String)]| {
+            let _ = writeln!(text, "# HELP {} {}", name, help);
+            let _ = writeln!(text, "# TYPE {} {}", name, kind);
+            for (labels, val) in samples.iter() {
+                let _ = writeln!(text, "{}{} {}", name, labels, val);
+            }
+        };
+        let requests = self
+            .counts
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(op, count)| (format!("{{op=\"{}\"}}", op), count.to_string()))
+            .collect::<Vec<(String, String)>>();
+        let sample = |val: String| vec![(String::new(), val)];
+        metric(
+            "nrepl_requests_total",
+            "counter",
+            "Requests received,  by op",
+            &requests,
+        );
+        metric(
+            "nrepl_active_sessions",
+            "gauge",
+            "Sessions open",
+            &sample(active_sessions.to_string()),
+        );
+        metric(
+            "nrepl_evals_total",
+            "counter",
+            "Evaluations run,  of eval and load-file requests",
+            &sample(evals.to_string()),
+        );
+        metric(
+            "nrepl_eval_errors_total",
+            "counter",
+            "Evaluations that failed or were interrupted",
+            &sample(eval_errors.to_string()),
+        );
+        metric(
+            "nrepl_eval_error_rate",
+            "gauge",
+            "The fraction of evaluations that failed or were interrupted",
+            &sample(per_eval(eval_errors as f64).to_string()),
+        );
+        metric(
+            "nrepl_eval_seconds_mean",
+            "gauge",
+            "How long an evaluation took,  on average",
+            &sample(per_eval(eval_seconds).to_string()),
+        );
+        text
+    }
+}
+
+/// A file each request a server's sent is logged to,  a line each:
+///
+///   2026-10-16T09:30:00.123-00:00 127.0.0.1:50123 eval id=7 session=0e9c..
+///
+/// Clones all append to the same file
+#[derive(Debug, Clone)]
+pub struct AccessLog {
+    file: Arc<Mutex<File>>,
+}
+impl AccessLog {
+    /// Opens the log at path,  to be added to;  it's made if it isn't there yet
+    pub fn open(path: &str) -> io::Result<AccessLog> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(AccessLog {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+    /// Logs request,  from the client at peer.  A log that can't be written to is only
+    /// complained about;  the request's still answered
+    pub fn request(&self, peer: &str, request: &Request) {
+        let line = log_line(&transcript::timestamp(SystemTime::now()), peer, request);
+        let mut file = self.file.lock().unwrap();
+        if let Err(e) = writeln!(file, "{}", line) {
+            eprintln!("Could not write to access log: {}", e);
+        }
+    }
+}
+
+fn log_line(time: &str, peer: &str, request: &Request) -> String {
+    let mut line = format!("{} {} {}", time, peer, request.op().unwrap_or("-"));
+    for key in ["id", "session"].iter() {
+        if let Some(val) = request.get(key) {
+            line.push_str(&format!(" {}={}", key, val));
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nrepl::bencode::Bencode;
+    use crate::nrepl::metrics::{log_line, Metrics};
+    use crate::nrepl::server::Request;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    #[test]
+    fn metrics_are_given_in_prometheus_text_format() {
+        let metrics = Metrics::default();
+        let ops = ["clone", "eval"];
+        for op in [
+            Some("eval"),
+            Some("eval"),
+            Some("clone"),
+            Some("nonsense"),
+            None,
+        ]
+        .iter()
+        {
+            metrics.request(*op, &ops);
+        }
+        metrics.eval(Duration::from_millis(300), true);
+        metrics.eval(Duration::from_millis(100), false);
+        let text = metrics.prometheus(2);
+        for line in [
+            "# TYPE nrepl_requests_total counter",
+            "nrepl_requests_total{op=\"clone\"} 1",
+            "nrepl_requests_total{op=\"eval\"} 2",
+            "nrepl_requests_total{op=\"unknown\"} 2",
+            "nrepl_active_sessions 2",
+            "nrepl_evals_total 2",
+            "nrepl_eval_errors_total 1",
+            "nrepl_eval_error_rate 0.5",
+            "nrepl_eval_seconds_mean 0.2",
+        ]
+        .iter()
+        {
+            assert!(
+                text.lines().any(|l| l == *line),
+                "{} not in\n{}",
+                line,
+                text
+            );
+        }
+        assert!(Metrics::default()
+            .prometheus(0)
+            .lines()
+            .any(|line| line == "nrepl_eval_seconds_mean 0"));
+    }
+
+    #[test]
+    fn each_request_is_a_line_of_the_access_log() {
+        let mut msg = BTreeMap::new();
+        msg.insert(String::from("op"), Bencode::from("eval"));
+        msg.insert(String::from("id"), Bencode::from("7"));
+        msg.insert(String::from("code"), Bencode::from("(+ 1 2)"));
+        let request = Request::from_bencode(Bencode::Dict(msg)).unwrap();
+        assert_eq!(
+            "1970-01-01T00:00:00.000-00:00 127.0.0.1:50123 eval id=7",
+            log_line("1970-01-01T00:00:00.000-00:00", "127.0.0.1:50123", &request)
+        );
+    }
+}
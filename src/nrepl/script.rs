@@ -0,0 +1,570 @@
+//! Scripted nREPL exchanges,  for testing the server the way clients see it;  over a real
+//! connection,  in bencode
+//!
+//! A script is a file of lines,  each one of
+//!
+//!   -> {"op" "eval" "id" "2" "code" "(+ 1 2)"}    a request to send
+//!   <- {"id" "2" "ns" "user" "value" "3"}         a response to expect
+//!   sleep 300                                    a pause,  in milliseconds
+//!   wait                                         a pause till every request sent is done
+//!   ; a comment
+//!
+//! Each request's responses are expected in order,  but those to different requests may come
+//! in any order;  so a request only waits on the responses to earlier ones when it needs
+//! something from them.  In an expected response,  _ is anything,  and so is ?name,  the
+//! first time;  but from then on,  in responses and requests alike,  it's what it was then.
+//! So after (<- {"id" "1" "new-session" ?session ..}),  requests with "session" ?session go
+//! to the new session.  Every response must be expected,  and every one expected must come
+//!
+//! With NREPL_RECORD set,  scripts are rewritten to expect what the server sent,  rather than
+//! checked against it;  keeping their _s and ?names where they were.  So a script can be
+//! written as its requests,  and whatever responses need a ?name,  and recorded
+use crate::nrepl::bencode::{read_bencode, Bencode};
+use crate::nrepl::server::Server;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fs;
+use std::io::{self, BufReader};
+use std::iter::Peekable;
+use std::net::TcpStream;
+use std::str::Chars;
+use std::thread;
+use std::time::Duration;
+
+// How long a response can take before the script fails waiting for it
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A message,  with maybe _ and ?names in it
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Any,
+    Bind(String),
+    Int(i64),
+    Str(String),
+    List(Vec<Pattern>),
+    Dict(BTreeMap<String, Pattern>),
+}
+impl Pattern {
+    pub fn parse(text: &str) -> Result<Pattern, String> {
+        let mut chars = text.chars().peekable();
+        let pattern = parse_pattern(&mut chars)?;
+        skip_blank(&mut chars);
+        match chars.next() {
+            None => Ok(pattern),
+            Some(c) => Err(format!("Unexpected {} after {}", c, pattern)),
+        }
+    }
+    /// Whether actual is what's expected;  binding the ?names not yet bound if so
+    pub fn matches(&self, actual: &Bencode, bindings: &mut HashMap<String, Bencode>) -> bool {
+        let mut bound = bindings.clone();
+        let matches = self.match_into(actual, &mut bound);
+        if matches {
+            *bindings = bound;
+        }
+        matches
+    }
+    fn match_into(&self, actual: &Bencode, bindings: &mut HashMap<String, Bencode>) -> bool {
+        match (self, actual) {
+            (Pattern::Any, _) => true,
+            (Pattern::Bind(name), _) => match bindings.get(name) {
+                Some(bound) => bound == actual,
+                None => {
+                    bindings.insert(name.clone(), actual.clone());
+                    true
+                }
+            },
+            (Pattern::Int(i), Bencode::Int(actual)) => i == actual,
+            (Pattern::Str(s), Bencode::Str(actual)) => s == actual,
+            (Pattern::List(items), Bencode::List(actual)) => {
+                items.len() == actual.len()
+                    && items
+                        .iter()
+                        .zip(actual.iter())
+                        .all(|(item, actual)| item.match_into(actual, bindings))
+            }
+            (Pattern::Dict(entries), Bencode::Dict(actual)) => {
+                entries.len() == actual.len()
+                    && entries.iter().all(|(key, val)| match actual.get(key) {
+                        Some(actual) => val.match_into(actual, bindings),
+                        None => false,
+                    })
+            }
+            _ => false,
+        }
+    }
+    /// The message to send;  None while it has a ?name not yet bound
+    pub fn resolve(&self, bindings: &HashMap<String, Bencode>) -> Option<Bencode> {
+        match self {
+            Pattern::Any => panic!("Can't send _;  only what's known"),
+            Pattern::Bind(name) => bindings.get(name).cloned(),
+            Pattern::Int(i) => Some(Bencode::Int(*i)),
+            Pattern::Str(s) => Some(Bencode::from(s.as_str())),
+            Pattern::List(items) => items
+                .iter()
+                .map(|item| item.resolve(bindings))
+                .collect::<Option<_>>()
+                .map(Bencode::List),
+            Pattern::Dict(entries) => entries
+                .iter()
+                .map(|(key, val)| val.resolve(bindings).map(|val| (key.clone(), val)))
+                .collect::<Option<_>>()
+                .map(Bencode::Dict),
+        }
+    }
+    /// actual,  as it's expected;  with what's a ?name's value written as the ?name
+    pub fn recorded(actual: &Bencode, bindings: &HashMap<String, Bencode>) -> Pattern {
+        let mut names = bindings
+            .iter()
+            .filter(|(_, bound)| *bound == actual && matches!(actual, Bencode::Str(_)));
+        if let Some((name, _)) = names.next() {
+            return Pattern::Bind(name.clone());
+        }
+        match actual {
+            Bencode::Int(i) => Pattern::Int(*i),
+            Bencode::Str(s) => Pattern::Str(s.clone()),
+            Bencode::List(items) => Pattern::List(
+                items
+                    .iter()
+                    .map(|item| Pattern::recorded(item, bindings))
+                    .collect(),
+            ),
+            Bencode::Dict(entries) => Pattern::Dict(
+                entries
+                    .iter()
+                    .map(|(key, val)| (key.clone(), Pattern::recorded(val, bindings)))
+                    .collect(),
+            ),
+        }
+    }
+    /// actual,  as it's expected;  but with this pattern's _s and ?names where they still fit
+    pub fn rerecorded(&self, actual: &Bencode, bindings: &mut HashMap<String, Bencode>) -> Pattern {
+        match (self, actual) {
+            (Pattern::Any, _) => Pattern::Any,
+            (Pattern::Bind(name), _) if self.matches(actual, bindings) => {
+                Pattern::Bind(name.clone())
+            }
+            (Pattern::List(items), Bencode::List(actual)) if items.len() == actual.len() => {
+                Pattern::List(
+                    items
+                        .iter()
+                        .zip(actual.iter())
+                        .map(|(item, actual)| item.rerecorded(actual, bindings))
+                        .collect(),
+                )
+            }
+            (Pattern::Dict(entries), Bencode::Dict(actual)) => Pattern::Dict(
+                actual
+                    .iter()
+                    .map(|(key, actual)| {
+                        let val = match entries.get(key) {
+                            Some(val) => val.rerecorded(actual, bindings),
+                            None => Pattern::recorded(actual, bindings),
+                        };
+                        (key.clone(), val)
+                    })
+                    .collect(),
+            ),
+            _ => Pattern::recorded(actual, bindings),
+        }
+    }
+    fn id(&self) -> Option<&str> {
+        match self {
+            Pattern::Dict(entries) => match entries.get("id") {
+                Some(Pattern::Str(id)) => Some(id),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pattern::Any => write!(f, "_"),
+            Pattern::Bind(name) => write!(f, "?{}", name),
+            Pattern::Int(i) => write!(f, "{}", i),
+            Pattern::Str(s) => {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Pattern::List(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| item.to_string())
+                    .collect::<Vec<_>>();
+                write!(f, "[{}]", items.join(" "))
+            }
+            Pattern::Dict(entries) => {
+                let entries = entries
+                    .iter()
+                    .map(|(key, val)| format!("{} {}", Pattern::Str(key.clone()), val))
+                    .collect::<Vec<_>>();
+                write!(f, "{{{}}}", entries.join(" "))
+            }
+        }
+    }
+}
+
+fn skip_blank(chars: &mut Peekable<Chars>) {
+    while let Some(c) = chars.peek() {
+        if !c.is_whitespace() && *c != ',' {
+            return;
+        }
+        chars.next();
+    }
+}
+
+// What's up to a space or a closing bracket
+fn read_word(chars: &mut Peekable<Chars>) -> String {
+    let mut word = String::new();
+    while let Some(c) = chars.peek() {
+        if c.is_whitespace() || ",]}".contains(*c) {
+            break;
+        }
+        word.push(*c);
+        chars.next();
+    }
+    word
+}
+
+fn parse_pattern(chars: &mut Peekable<Chars>) -> Result<Pattern, String> {
+    skip_blank(chars);
+    match chars.peek() {
+        None => Err(String::from("Unexpected end of script line")),
+        Some('"') => {
+            chars.next();
+            let mut s = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => return Ok(Pattern::Str(s)),
+                    Some('\\') => match chars.next() {
+                        Some('n') => s.push('\n'),
+                        Some('t') => s.push('\t'),
+                        Some(c) => s.push(c),
+                        None => return Err(String::from("Unterminated string")),
+                    },
+                    Some(c) => s.push(c),
+                    None => return Err(String::from("Unterminated string")),
+                }
+            }
+        }
+        Some('[') => {
+            chars.next();
+            let mut items = vec![];
+            loop {
+                skip_blank(chars);
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                    return Ok(Pattern::List(items));
+                }
+                items.push(parse_pattern(chars)?);
+            }
+        }
+        Some('{') => {
+            chars.next();
+            let mut entries = BTreeMap::new();
+            loop {
+                skip_blank(chars);
+                if chars.peek() == Some(&'}') {
+                    chars.next();
+                    return Ok(Pattern::Dict(entries));
+                }
+                let key = match parse_pattern(chars)? {
+                    Pattern::Str(key) => key,
+                    other => return Err(format!("A key should be a string,  not {}", other)),
+                };
+                entries.insert(key, parse_pattern(chars)?);
+            }
+        }
+        Some(_) => {
+            let word = read_word(chars);
+            match word.as_str() {
+                "_" => Ok(Pattern::Any),
+                _ if word.starts_with('?') && word.len() > 1 => {
+                    Ok(Pattern::Bind(String::from(&word[1..])))
+                }
+                _ => word
+                    .parse()
+                    .map(Pattern::Int)
+                    .map_err(|_| format!("Unexpected {}", word)),
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Step {
+    Send(Pattern),
+    Expect(Pattern),
+    Sleep(Duration),
+    Wait,
+}
+
+fn parse_step(line: &str) -> Result<Option<Step>, String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with(';') {
+        return Ok(None);
+    }
+    if let Some(msg) = line.strip_prefix("->") {
+        return Pattern::parse(msg).map(|msg| Some(Step::Send(msg)));
+    }
+    if let Some(msg) = line.strip_prefix("<-") {
+        return Pattern::parse(msg).map(|msg| Some(Step::Expect(msg)));
+    }
+    if line == "wait" {
+        return Ok(Some(Step::Wait));
+    }
+    if let Some(msecs) = line.strip_prefix("sleep ") {
+        return msecs
+            .trim()
+            .parse()
+            .map(|msecs| Some(Step::Sleep(Duration::from_millis(msecs))))
+            .map_err(|_| format!("Can't sleep for {}", msecs));
+    }
+    Err(format!(
+        "Expected ->,  <-,  sleep,  wait or a comment;  not {}",
+        line
+    ))
+}
+
+// Where a script's got to,  and what it's still waiting for
+struct Run<'a> {
+    path: &'a str,
+    lines: Vec<String>,
+    steps: Vec<Option<Step>>,
+    reader: BufReader<TcpStream>,
+    bindings: HashMap<String, Bencode>,
+    // The lines of responses expected but yet to come
+    pending: VecDeque<usize>,
+    // The ids of requests sent that aren't done yet
+    undone: HashSet<String>,
+    recording: bool,
+    // The last line each id was on;  where a response no line expected is recorded after
+    last_line: HashMap<String, usize>,
+    unexpected: BTreeMap<usize, Vec<String>>,
+    // The lines of responses expected that never came
+    unmet: Vec<usize>,
+}
+impl Run<'_> {
+    // Reads one response,  and checks it against the first line expecting one with its id
+    fn receive(&mut self) -> io::Result<()> {
+        let response = match read_bencode(&mut self.reader)? {
+            Some(response) => response,
+            None => return Err(io::ErrorKind::UnexpectedEof.into()),
+        };
+        let id = match &response {
+            Bencode::Dict(entries) => entries.get("id").and_then(|id| id.as_str()),
+            _ => None,
+        }
+        .unwrap_or("")
+        .to_string();
+        if let Bencode::Dict(entries) = &response {
+            if let Some(Bencode::List(statuses)) = entries.get("status") {
+                if statuses.contains(&Bencode::from("done")) {
+                    self.undone.remove(&id);
+                }
+            }
+        }
+        let expected = self
+            .pending
+            .iter()
+            .position(|line| match &self.steps[*line] {
+                Some(Step::Expect(expected)) => expected.id() == Some(id.as_str()),
+                _ => false,
+            });
+        let line = match expected {
+            Some(i) => self.pending.remove(i).unwrap(),
+            None if self.recording => {
+                let after = self
+                    .last_line
+                    .get(&id)
+                    .copied()
+                    .unwrap_or(self.lines.len() - 1);
+                let recorded = Pattern::recorded(&response, &self.bindings);
+                self.unexpected
+                    .entry(after)
+                    .or_default()
+                    .push(format!("<- {}", recorded));
+                return Ok(());
+            }
+            None => panic!("{}: unexpected response {}", self.path, response),
+        };
+        self.last_line.insert(id, line);
+        let expected = match &self.steps[line] {
+            Some(Step::Expect(expected)) => expected.clone(),
+            _ => unreachable!(),
+        };
+        if expected.matches(&response, &mut self.bindings) {
+            return Ok(());
+        }
+        if !self.recording {
+            panic!(
+                "{}:{}: expected\n  {}\nbut got\n  {}",
+                self.path,
+                line + 1,
+                expected,
+                Pattern::recorded(&response, &self.bindings)
+            );
+        }
+        let recorded = expected.rerecorded(&response, &mut self.bindings);
+        self.lines[line] = format!("<- {}", recorded);
+        Ok(())
+    }
+    fn wait_for(&mut self, what: &str) {
+        if let Err(e) = self.receive() {
+            let pending = self
+                .pending
+                .iter()
+                .map(|line| format!("\n  {}: {}", line + 1, self.lines[*line]))
+                .collect::<String>();
+            panic!(
+                "{}: {} waiting for {};  still expecting{}",
+                self.path, e, what, pending
+            );
+        }
+    }
+}
+
+/// Runs the script at path against a server of its own;  panicking at the first response
+/// that isn't as expected,  unless it's recording
+pub fn run(path: &str) {
+    let text = fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    let lines = text.lines().map(String::from).collect::<Vec<_>>();
+    let steps = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parse_step(line).unwrap_or_else(|e| panic!("{}:{}: {}", path, i + 1, e)))
+        .collect::<Vec<_>>();
+
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.set_read_timeout(Some(RESPONSE_TIMEOUT)).unwrap();
+    let mut run = Run {
+        path,
+        lines,
+        steps,
+        reader: BufReader::new(stream.try_clone().unwrap()),
+        bindings: HashMap::new(),
+        pending: VecDeque::new(),
+        undone: HashSet::new(),
+        recording: std::env::var_os("NREPL_RECORD").is_some(),
+        last_line: HashMap::new(),
+        unexpected: BTreeMap::new(),
+        unmet: vec![],
+    };
+
+    for line in 0..run.steps.len() {
+        match &run.steps[line] {
+            Some(Step::Send(msg)) => {
+                let msg = msg.clone();
+                let request = loop {
+                    match msg.resolve(&run.bindings) {
+                        Some(request) => break request,
+                        None => run.wait_for(&format!("what line {} needs", line + 1)),
+                    }
+                };
+                if let Some(id) = msg.id() {
+                    run.last_line.insert(String::from(id), line);
+                    run.undone.insert(String::from(id));
+                }
+                request.write_to(&mut stream).unwrap();
+            }
+            Some(Step::Expect(_)) => run.pending.push_back(line),
+            Some(Step::Sleep(duration)) => thread::sleep(*duration),
+            Some(Step::Wait) => {
+                while !run.undone.is_empty() {
+                    run.wait_for(&format!("what line {} waits for", line + 1));
+                }
+            }
+            None => (),
+        }
+    }
+    while !run.pending.is_empty() || !run.undone.is_empty() {
+        if !run.recording {
+            run.wait_for("the responses expected");
+        } else if run.receive().is_err() {
+            // Nothing more's coming;  so neither are the responses still expected
+            run.unmet.extend(run.pending.drain(..));
+            run.undone.clear();
+        }
+    }
+
+    if run.recording {
+        let mut recorded = String::new();
+        for (i, line) in run.lines.iter().enumerate() {
+            if !run.unmet.contains(&i) {
+                recorded.push_str(line);
+                recorded.push('\n');
+            }
+            for line in run.unexpected.get(&i).into_iter().flatten() {
+                recorded.push_str(line);
+                recorded.push('\n');
+            }
+        }
+        fs::write(path, recorded).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nrepl::bencode::Bencode;
+    use crate::nrepl::script::{self, Pattern};
+    use std::collections::{BTreeMap, HashMap};
+
+    fn path(script: &str) -> String {
+        format!(
+            "{}/src/nrepl/test_scripts/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            script
+        )
+    }
+
+    #[test]
+    fn patterns_bind_what_they_first_match() {
+        let pattern =
+            Pattern::parse(r#"{"id" "1", "new-session" ?s "status" [_ "done"]}"#).unwrap();
+        let mut bindings = HashMap::new();
+        let response = |session: &str, status: &str| {
+            let mut entries = BTreeMap::new();
+            entries.insert(String::from("id"), Bencode::from("1"));
+            entries.insert(String::from("new-session"), Bencode::from(session));
+            entries.insert(
+                String::from("status"),
+                Bencode::List(vec![Bencode::from(status), Bencode::from("done")]),
+            );
+            Bencode::Dict(entries)
+        };
+        assert!(pattern.matches(&response("abc", "x"), &mut bindings));
+        assert_eq!(Some(&Bencode::from("abc")), bindings.get("s"));
+        assert!(pattern.matches(&response("abc", "y"), &mut bindings));
+        assert!(!pattern.matches(&response("def", "x"), &mut bindings));
+        assert_eq!(
+            r#"{"id" "1" "new-session" ?s "status" [_ "done"]}"#,
+            pattern.to_string()
+        );
+        let quoted = Pattern::parse(r#""say \"hi\"\n""#).unwrap();
+        assert_eq!(Pattern::Str(String::from("say \"hi\"\n")), quoted);
+        assert_eq!(quoted, Pattern::parse(&quoted.to_string()).unwrap());
+    }
+
+    #[test]
+    fn sessions_are_cloned_evaluated_in_and_closed() {
+        script::run(&path("sessions.nrepl"));
+    }
+
+    #[test]
+    fn an_interrupted_eval_leaves_its_session_usable() {
+        script::run(&path("interrupt.nrepl"));
+    }
+}
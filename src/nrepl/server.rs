@@ -0,0 +1,1890 @@
+use crate::clojure_repl;
+use crate::completion;
+use crate::environment::Environment;
+use crate::interrupt;
+use crate::namespace::{self, Location};
+use crate::nrepl::bencode::{Bencode, Decoder};
+use crate::nrepl::metrics::{AccessLog, Metrics};
+use crate::nrepl::tls::Tls;
+use crate::output;
+use crate::reader::{self, Source};
+use crate::repl;
+use crate::symbol::Symbol;
+use crate::trace;
+use crate::transcript::Transcript;
+use crate::type_tag::TypeTag;
+use crate::value::{Evaluable, Value};
+use crate::var::Var;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::Instant;
+
+/// A single message from an nREPL client,  ie  {"op" "eval" "code" "(+ 1 2)" "id" "7"}
+#[derive(Debug, Clone)]
+pub struct Request {
+    msg: BTreeMap<String, Bencode>,
+}
+impl Request {
+    pub fn from_bencode(msg: Bencode) -> Option<Request> {
+        match msg {
+            Bencode::Dict(msg) => Some(Request { msg }),
+            _ => None,
+        }
+    }
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.msg.get(key).and_then(Bencode::as_str)
+    }
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        match self.msg.get(key) {
+            Some(Bencode::Int(i)) => Some(*i),
+            _ => None,
+        }
+    }
+    pub fn op(&self) -> Option<&str> {
+        self.get("op")
+    }
+    pub fn session(&self) -> Option<&str> {
+        self.get("session")
+    }
+}
+
+/// A single message back to an nREPL client.  A request may be answered by several
+/// responses;  the last one always carries a "done" status
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    msg: BTreeMap<String, Bencode>,
+}
+impl Response {
+    /// Starts a response that echoes the request's id and session back,  so the client
+    /// can route it to whoever asked
+    pub fn for_request(request: &Request) -> Response {
+        let mut msg = BTreeMap::new();
+        for key in ["id", "session"].iter() {
+            if let Some(val) = request.msg.get(*key) {
+                msg.insert(String::from(*key), val.clone());
+            }
+        }
+        Response { msg }
+    }
+    pub fn set<V: Into<Bencode>>(mut self, key: &str, val: V) -> Response {
+        self.msg.insert(String::from(key), val.into());
+        self
+    }
+    pub fn status(self, statuses: &[&str]) -> Response {
+        let statuses = statuses
+            .iter()
+            .map(|status| Bencode::from(*status))
+            .collect();
+        self.set("status", Bencode::List(statuses))
+    }
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        Bencode::Dict(self.msg.clone()).write_to(writer)
+    }
+}
+
+/// The writing half of a client connection.  Responses to an eval are written by the
+/// session's evaluator thread as they're produced, while the connection's own thread
+/// keeps reading requests (so an interrupt can get through during a long eval)
+#[derive(Clone)]
+struct Transport {
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+impl Transport {
+    fn send(&self, responses: &[Response]) {
+        let mut writer = self.writer.lock().unwrap();
+        for response in responses.iter() {
+            // If the client hung up there's nobody left to tell
+            if response.write_to(&mut *writer).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+struct EvalJob {
+    request: Request,
+    transport: Transport,
+    transcript: Option<Transcript>,
+}
+impl EvalJob {
+    fn send(&self, responses: &[Response]) {
+        self.transport.send(responses)
+    }
+}
+
+/// How many requests a session holds queued behind the one it's evaluating.  Past that,
+/// whoever hands it another waits for room,  and so stops reading from its client;  a
+/// client can't pile up more work than the session will get to
+const QUEUE_CAPACITY: usize = 64;
+
+/// An nREPL session.  What's in place while evaluating (bindings,  where output goes,
+/// the namespace being read in) is kept per thread;  so each session owns a thread that
+/// evaluates in its Environment,  and connections hand it code to evaluate over a
+/// channel.  So a session evaluates one request at a time,  in the order they came in,
+/// while other sessions evaluate theirs alongside it
+#[derive(Clone)]
+struct Session {
+    jobs: mpsc::SyncSender<EvalJob>,
+    interrupt_flag: Arc<AtomicBool>,
+    // The id of the message currently being evaluated, if any
+    running: Arc<Mutex<Option<String>>>,
+    // Where each form evaluated in the session is recorded,  if anywhere
+    transcript: Option<Transcript>,
+}
+impl Session {
+    fn spawn(transcript: Option<Transcript>, metrics: Metrics) -> Session {
+        let (jobs, incoming_jobs) = mpsc::sync_channel::<EvalJob>(QUEUE_CAPACITY);
+        let interrupt_flag = Arc::new(AtomicBool::new(false));
+        let running = Arc::new(Mutex::new(None));
+        let session = Session {
+            jobs,
+            interrupt_flag: Arc::clone(&interrupt_flag),
+            running: Arc::clone(&running),
+            transcript,
+        };
+        thread::spawn(move || {
+            interrupt::install(Arc::clone(&interrupt_flag));
+            // Whatever the code being evaluated prints goes to whoever asked for the evaluation.
+            // Read while the job runs,  by the sink as much as by us;  and by any future it
+            // starts,  printing on a thread of its own
+            let current_job: Arc<RwLock<Option<EvalJob>>> = Arc::new(RwLock::new(None));
+            let sink_job = Arc::clone(&current_job);
+            output::install(Some(Arc::new(move |stream, text: &str| {
+                if let Some(job) = &*sink_job.read().unwrap() {
+                    let key = match stream {
+                        output::Stream::Out => "out",
+                        output::Stream::Err => "err",
+                    };
+                    job.send(&[Response::for_request(&job.request).set(key, text)]);
+                }
+            })));
+            let environment = Environment::clojure_core_environment();
+            for job in incoming_jobs {
+                {
+                    let mut running = running.lock().unwrap();
+                    *running = Some(job.request.get("id").unwrap_or("").to_string());
+                    interrupt_flag.store(false, Ordering::SeqCst);
+                }
+                *current_job.write().unwrap() = Some(job);
+                let job_ref = current_job.read().unwrap();
+                let job = job_ref.as_ref().unwrap();
+                let started = Instant::now();
+                // For an eval or load-file,  whether it went through
+                let evaluated = match job.request.op() {
+                    Some("load-file") => Some(load_file_request(&environment, job)),
+                    Some("clear") => {
+                        clear_request(&environment, job);
+                        None
+                    }
+                    Some("workspace-symbols") => {
+                        workspace_symbols_request(&environment, job);
+                        None
+                    }
+                    Some("signatures") => {
+                        signatures_request(&environment, job);
+                        None
+                    }
+                    Some("lookup") => {
+                        lookup_request(&environment, job);
+                        None
+                    }
+                    Some("completions") => {
+                        completions_request(&environment, job);
+                        None
+                    }
+                    Some("info") => {
+                        info_request(&environment, job);
+                        None
+                    }
+                    Some("eldoc") => {
+                        eldoc_request(&environment, job);
+                        None
+                    }
+                    _ => Some(eval_request(&environment, job)),
+                };
+                if let Some(went_through) = evaluated {
+                    metrics.eval(started.elapsed(), went_through);
+                }
+                let interrupted = {
+                    let mut running = running.lock().unwrap();
+                    *running = None;
+                    interrupt_flag.swap(false, Ordering::SeqCst)
+                };
+                if interrupted {
+                    job.send(&[Response::for_request(&job.request).status(&["interrupted"])]);
+                }
+                job.send(&[Response::for_request(&job.request).status(&["done"])]);
+                drop(job_ref);
+                *current_job.write().unwrap() = None;
+            }
+        });
+        session
+    }
+    /// Queues the request up for this session's evaluator,  waiting for room if the queue's
+    /// full;  it answers on its own
+    fn eval(&self, request: &Request, transport: &Transport) {
+        let job = EvalJob {
+            request: request.clone(),
+            transport: transport.clone(),
+            transcript: self.transcript.clone(),
+        };
+        if self.jobs.send(job).is_err() {
+            // The evaluator thread is gone (most likely it panicked)
+            transport.send(&[Response::for_request(request)
+                .set("err", "Session evaluator has died\n")
+                .status(&["error", "session-closed", "done"])]);
+        }
+    }
+    /// Interrupts the running evaluation,  if it is the one with id `interrupt_id` (or
+    /// whichever one it is, if that's None). Returns the statuses to answer with
+    fn interrupt(&self, interrupt_id: Option<&str>) -> &'static [&'static str] {
+        let running = self.running.lock().unwrap();
+        match (&*running, interrupt_id) {
+            (None, _) => &["session-idle", "done"],
+            (Some(running_id), Some(interrupt_id)) if running_id != interrupt_id => {
+                &["interrupt-id-mismatch", "done"]
+            }
+            _ => {
+                self.interrupt_flag.store(true, Ordering::SeqCst);
+                &["done"]
+            }
+        }
+    }
+}
+
+type Sessions = Arc<Mutex<HashMap<String, Session>>>;
+
+fn new_session_id() -> String {
+    let id: u128 = rand::random();
+    let hex = format!("{:032x}", id);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// What a def is said to be in when the client doesn't tell us its file,  as in Clojure
+const NO_SOURCE_PATH: &str = "NO_SOURCE_PATH";
+
+// The length of the whitespace, commas and comments text starts with
+fn blank_len(text: &str) -> usize {
+    let mut rest = text;
+    loop {
+        let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        rest = match trimmed.strip_prefix(';') {
+            Some(comment) => comment.find('\n').map_or("", |end| &comment[end..]),
+            None => return text.len() - trimmed.len(),
+        };
+    }
+}
+
+/// Reads and evaluates every form in `code`, handing each value's response to emit as
+/// soon as it is ready.  Stops at the first error;  the final "done" is left to the caller
+///
+/// code is taken to start at first_line of file,  which is where the defs it makes are
+/// recorded as made
+fn eval_code(
+    environment: &Arc<Environment>,
+    job: &EvalJob,
+    code: &str,
+    (file, first_line): (&str, usize),
+    emit: &mut dyn FnMut(Response),
+) -> bool {
+    let request = &job.request;
+    let source = Source::new(file, first_line, code);
+    let code = source.text();
+    let mut remaining = code;
+    loop {
+        if remaining
+            .trim_matches(|c: char| c.is_whitespace() || c == ',')
+            .is_empty()
+        {
+            return true;
+        }
+        let start = code.len() - remaining.len() + blank_len(remaining);
+        let location = Location {
+            file: String::from(file),
+            line: first_line + code[..start].matches('\n').count(),
+        };
+        let form = match source.reading(|| reader::try_read(remaining)) {
+            Ok((rest, form)) => {
+                remaining = rest;
+                form
+            }
+            Err(err) => {
+                job.send(&[Response::for_request(request)
+                    .set(
+                        "err",
+                        format!("Reader Error: could not read next form; {:?}\n", err),
+                    )
+                    .status(&["eval-error"])]);
+                return false;
+            }
+        };
+        let ns = environment.get_current_namespace_name();
+        let value = namespace::defining_at(location, || form.eval(Arc::clone(environment)));
+        if let Some(transcript) = &job.transcript {
+            transcript.record(&ns, &form, &value);
+        }
+        match value {
+            // The session reports interruptions itself
+            Value::Condition(_) if interrupt::is_interrupted() => return false,
+            Value::Condition(ref cond) => {
+                job.send(&[Response::for_request(request)
+                    .set("err", format!("{}\n{}", cond, trace::stack_trace(&value)))
+                    .status(&["eval-error"])]);
+                return false;
+            }
+            value => emit(
+                Response::for_request(request)
+                    .set("value", value.to_string())
+                    .set("ns", environment.get_current_namespace_name()),
+            ),
+        }
+    }
+}
+
+/// Editors send the file and line the code was taken from,  when it was taken from one.
+/// Whether every form evaluated
+fn eval_request(environment: &Arc<Environment>, job: &EvalJob) -> bool {
+    let code = job.request.get("code").unwrap_or("");
+    let file = job.request.get("file").unwrap_or(NO_SOURCE_PATH);
+    let line = job.request.get_int("line").unwrap_or(1).max(1) as usize;
+    eval_code(environment, job, code, (file, line), &mut |response| {
+        job.send(&[response])
+    })
+}
+
+/// Starts the session over;  everything it defined is forgotten,  and clojure.core is
+/// loaded afresh.  Other sessions keep what they have
+fn clear_request(environment: &Arc<Environment>, job: &EvalJob) {
+    environment.reset();
+    job.send(&[
+        Response::for_request(&job.request).set("ns", environment.get_current_namespace_name())
+    ]);
+}
+
+/// Like eval, but for a whole file's contents, so only the last value is sent back
+fn load_file_request(environment: &Arc<Environment>, job: &EvalJob) -> bool {
+    let mut last_value = None;
+    let contents = job.request.get("file").unwrap_or("");
+    let file = job
+        .request
+        .get("file-path")
+        .or_else(|| job.request.get("file-name"))
+        .unwrap_or(NO_SOURCE_PATH);
+    let went_through = eval_code(environment, job, contents, (file, 1), &mut |response| {
+        last_value = Some(response)
+    });
+    if let Some(response) = last_value {
+        job.send(&[response]);
+    }
+    went_through
+}
+
+/// Every symbol interned in every namespace,  as ns-interns lists them;  with its kind,
+/// and the file and line it was defined at when we know them.  With a query,  only the
+/// symbols whose names contain it (ignoring case)
+fn workspace_symbols_request(environment: &Arc<Environment>, job: &EvalJob) {
+    let query = job.request.get("query").unwrap_or("").to_lowercase();
+    let mut symbols = vec![];
+    for namespace in environment.all_namespaces() {
+        for (sym, val) in namespace.mappings() {
+            if !sym.name.to_lowercase().contains(&query) {
+                continue;
+            }
+            let mut symbol = BTreeMap::new();
+            symbol.insert(String::from("name"), Bencode::from(sym.name.to_string()));
+            symbol.insert(
+                String::from("ns"),
+                Bencode::from(namespace.name.name.to_string()),
+            );
+            symbol.insert(String::from("kind"), Bencode::from(kind(&val)));
+            if let Some(location) = namespace.location(&sym) {
+                symbol.insert(String::from("file"), Bencode::from(location.file));
+                symbol.insert(String::from("line"), Bencode::from(location.line as i64));
+            }
+            symbols.push(((namespace.name.name.clone(), sym.name), symbol));
+        }
+    }
+    symbols.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let symbols = symbols
+        .into_iter()
+        .map(|(_, symbol)| Bencode::Dict(symbol))
+        .collect();
+    job.send(&[Response::for_request(&job.request).set("symbols", Bencode::List(symbols))]);
+}
+
+// The kind workspace-symbols and signatures report a value as
+fn kind(val: &Value) -> &'static str {
+    match val.type_tag() {
+        TypeTag::Macro => "macro",
+        TypeTag::IFn | TypeTag::MultiFn => "function",
+        _ => "variable",
+    }
+}
+
+/// The var sym resolves to,  looked up from the request's ns,  or the current namespace
+fn resolve(environment: &Arc<Environment>, request: &Request, sym: &Symbol) -> Option<Var> {
+    let ns = request
+        .get("ns")
+        .map(Symbol::intern)
+        .unwrap_or_else(|| environment.get_current_namespace());
+    environment.resolve_var_in(&ns, sym)
+}
+
+/// The symbol a request asks about;  its "sym",  as CIDER sends it,  or its "symbol"
+fn requested_symbol(request: &Request) -> Symbol {
+    Symbol::intern(
+        request
+            .get("sym")
+            .or_else(|| request.get("symbol"))
+            .unwrap_or(""),
+    )
+}
+
+// What var holds,  if it's a fn or macro;  and its arglists,  each a list of its parameters
+fn signature(var: &Var) -> Option<(Arc<Value>, Bencode)> {
+    let val = var.deref();
+    let arglists = match &*val {
+        Value::IFn(ifn) | Value::Macro(ifn) => ifn.arglists()?,
+        _ => return None,
+    };
+    let arglists = arglists
+        .iter()
+        .map(|arglist| {
+            Bencode::List(
+                arglist
+                    .iter()
+                    .map(|arg| Bencode::from(arg.to_string()))
+                    .collect(),
+            )
+        })
+        .collect();
+    Some((val, Bencode::List(arglists)))
+}
+
+fn signatures_request(environment: &Arc<Environment>, job: &EvalJob) {
+    let request = &job.request;
+    let sym = requested_symbol(request);
+    let resolved = resolve(environment, request, &sym)
+        .and_then(|var| signature(&var).map(|(val, arglists)| (var, val, arglists)));
+    let (var, val, arglists) = match resolved {
+        Some(resolved) => resolved,
+        None => {
+            job.send(&[Response::for_request(request).status(&["no-info"])]);
+            return;
+        }
+    };
+    job.send(&[Response::for_request(request)
+        .set("name", sym.name.to_string())
+        .set("ns", var.namespace.name.name.to_string())
+        .set("kind", kind(&val))
+        .set("arglists", arglists)]);
+}
+
+/// Answers CIDER's eldoc;  the arglists of the fn or macro sym names,  and its docstring
+fn eldoc_request(environment: &Arc<Environment>, job: &EvalJob) {
+    let request = &job.request;
+    let sym = requested_symbol(request);
+    let resolved = resolve(environment, request, &sym)
+        .and_then(|var| signature(&var).map(|(val, arglists)| (var, val, arglists)));
+    let (var, val, arglists) = match resolved {
+        Some(resolved) => resolved,
+        None => {
+            job.send(&[Response::for_request(request).status(&["no-eldoc"])]);
+            return;
+        }
+    };
+    let mut response = Response::for_request(request)
+        .set("name", var.sym.name.to_string())
+        .set("ns", var.namespace.name.name.to_string())
+        .set("type", kind(&val))
+        .set("eldoc", arglists);
+    if let Some(doc) = clojure_repl::docstring(&var) {
+        response = response.set("docstring", doc);
+    }
+    job.send(&[response]);
+}
+
+/// Answers completions;  every name "prefix" could be the start of in the request's ns,
+/// with the namespace it's from and what type of thing it is
+fn completions_request(environment: &Arc<Environment>, job: &EvalJob) {
+    let request = &job.request;
+    let ns = request
+        .get("ns")
+        .map(Symbol::intern)
+        .unwrap_or_else(|| environment.get_current_namespace());
+    let prefix = request.get("prefix").unwrap_or("");
+    let completions = completion::completions(environment, &ns, prefix)
+        .into_iter()
+        .map(|candidate| {
+            let mut completion = BTreeMap::new();
+            completion.insert(
+                String::from("candidate"),
+                Bencode::from(candidate.candidate),
+            );
+            if let Some(ns) = candidate.ns {
+                completion.insert(String::from("ns"), Bencode::from(ns));
+            }
+            completion.insert(String::from("type"), Bencode::from(candidate.kind));
+            Bencode::Dict(completion)
+        })
+        .collect();
+    job.send(&[Response::for_request(request).set("completions", Bencode::List(completions))]);
+}
+
+/// Answers a lookup;  what's known of the var symbol names,  as doc shows it,  in "info"
+fn lookup_request(environment: &Arc<Environment>, job: &EvalJob) {
+    let request = &job.request;
+    let sym = requested_symbol(request);
+    let var = match resolve(environment, request, &sym) {
+        Some(var) => var,
+        None => {
+            job.send(&[Response::for_request(request).status(&["no-info"])]);
+            return;
+        }
+    };
+    job.send(&[Response::for_request(request).set("info", Bencode::Dict(info(&var)))]);
+}
+
+/// Answers CIDER's info;  what lookup answers,  but in the response itself,  and whether
+/// it's a special form
+fn info_request(environment: &Arc<Environment>, job: &EvalJob) {
+    let request = &job.request;
+    let sym = requested_symbol(request);
+    let special_form = reader::SPECIAL_FORMS.contains(&sym.name.as_str());
+    let mut info = match resolve(environment, request, &sym) {
+        Some(var) => info(&var),
+        None if special_form => BTreeMap::new(),
+        None => {
+            job.send(&[Response::for_request(request).status(&["no-info"])]);
+            return;
+        }
+    };
+    if special_form {
+        info.insert(String::from("name"), Bencode::from(sym.name.to_string()));
+        info.insert(String::from("special-form"), Bencode::from("true"));
+    }
+    let response = info
+        .into_iter()
+        .fold(Response::for_request(request), |response, (key, val)| {
+            response.set(&key, val)
+        });
+    job.send(&[response]);
+}
+
+// What's known of var,  as doc shows it
+fn info(var: &Var) -> BTreeMap<String, Bencode> {
+    let mut info = BTreeMap::new();
+    let mut set = |key: &str, val: Bencode| {
+        info.insert(String::from(key), val);
+    };
+    set("name", Bencode::from(var.sym.name.to_string()));
+    set("ns", Bencode::from(var.namespace.name.name.to_string()));
+    if let Some(arglists) = clojure_repl::arglists(var) {
+        set("arglists-str", Bencode::from(arglists));
+    }
+    if let Some(doc) = clojure_repl::docstring(var) {
+        set("doc", Bencode::from(doc));
+    }
+    if let Some(location) = var.namespace.location(&var.sym) {
+        set("file", Bencode::from(location.file));
+        set("line", Bencode::from(location.line as i64));
+    }
+    if let Value::Macro(_) = &*var.root() {
+        set("macro", Bencode::from("true"));
+    }
+    info
+}
+
+fn describe_response(request: &Request) -> Response {
+    let ops = OPS
+        .iter()
+        .map(|op| (String::from(*op), Bencode::Dict(BTreeMap::new())))
+        .collect();
+    let mut clojure_rs_version = BTreeMap::new();
+    clojure_rs_version.insert(
+        String::from("version-string"),
+        Bencode::from(env!("CARGO_PKG_VERSION")),
+    );
+    let capabilities = CAPABILITIES
+        .iter()
+        .map(|(capability, supported)| {
+            (String::from(*capability), Bencode::from(*supported as i64))
+        })
+        .collect();
+    clojure_rs_version.insert(String::from("capabilities"), Bencode::Dict(capabilities));
+    let mut versions = BTreeMap::new();
+    versions.insert(
+        String::from("clojure-rs"),
+        Bencode::Dict(clojure_rs_version),
+    );
+    Response::for_request(request)
+        .set("ops", Bencode::Dict(ops))
+        .set("versions", Bencode::Dict(versions))
+        .status(&["done"])
+}
+
+/// What of Clojure we support,  and what not yet,  as advertised by describe;  1 if it's
+/// supported,  0 if not.  So clients and test suites can tell what they can send us without
+/// trying it.  Keep it up to date as the language grows
+const CAPABILITIES: &[(&str, bool)] = &[
+    ("agents?", true),
+    ("auto-resolved-keywords?", false),
+    ("bigdec?", true),
+    ("bigint?", true),
+    ("destructuring?", false),
+    ("host-interop?", true),
+    ("lazy-seqs?", true),
+    ("multimethods?", true),
+    ("protocols?", true),
+    ("ratios?", true),
+    ("reader-conditionals?", true),
+    ("records?", true),
+    ("refs?", true),
+    ("regex?", true),
+    ("set-literals?", true),
+    ("string-escapes?", false),
+    ("transducers?", true),
+    ("transients?", true),
+];
+
+/// Every op we answer, as advertised by describe
+const OPS: &[&str] = &[
+    "clear",
+    "clone",
+    "close",
+    "completions",
+    "describe",
+    "eldoc",
+    "eval",
+    "info",
+    "interrupt",
+    "load-file",
+    "lookup",
+    "metrics",
+    "signatures",
+    "workspace-symbols",
+];
+
+/// Answers a single request.  Evals are handed off to their session and answered from
+/// there;  everything else is answered right away
+fn run_request(
+    request: &Request,
+    sessions: &Sessions,
+    ephemeral_session: &mut Option<Session>,
+    transport: &Transport,
+    transcript: &Option<Transcript>,
+    metrics: &Metrics,
+) {
+    metrics.request(request.op(), OPS);
+    let unknown_session =
+        || Response::for_request(request).status(&["error", "unknown-session", "done"]);
+    let response = match request.op() {
+        Some("clone") => {
+            let id = new_session_id();
+            sessions.lock().unwrap().insert(
+                id.clone(),
+                Session::spawn(transcript.clone(), metrics.clone()),
+            );
+            Response::for_request(request)
+                .set("new-session", id)
+                .status(&["done"])
+        }
+        Some("describe") => describe_response(request),
+        Some("metrics") => Response::for_request(request)
+            .set(
+                "metrics",
+                metrics.prometheus(sessions.lock().unwrap().len()),
+            )
+            .status(&["done"]),
+        // Quitting's taken as closing the session it's sent in;  not as ending the server
+        Some("eval") if is_quit(request) => match request.session() {
+            Some(_) => close_session(request, sessions),
+            None => {
+                *ephemeral_session = None;
+                Response::for_request(request).status(&["session-closed", "done"])
+            }
+        },
+        Some("clear")
+        | Some("completions")
+        | Some("eldoc")
+        | Some("eval")
+        | Some("info")
+        | Some("load-file")
+        | Some("lookup")
+        | Some("signatures")
+        | Some("workspace-symbols") => {
+            let session = match request.session() {
+                // Don't hold the lock while evaluating,  other connections need it
+                Some(id) => sessions.lock().unwrap().get(id).cloned(),
+                None => Some(
+                    ephemeral_session
+                        .get_or_insert_with(|| Session::spawn(transcript.clone(), metrics.clone()))
+                        .clone(),
+                ),
+            };
+            match session {
+                Some(session) => {
+                    session.eval(request, transport);
+                    return;
+                }
+                None => unknown_session(),
+            }
+        }
+        Some("interrupt") => {
+            let session = request
+                .session()
+                .and_then(|id| sessions.lock().unwrap().get(id).cloned());
+            match session {
+                Some(session) => Response::for_request(request)
+                    .status(session.interrupt(request.get("interrupt-id"))),
+                None => unknown_session(),
+            }
+        }
+        Some("close") => close_session(request, sessions),
+        _ => Response::for_request(request).status(&["error", "unknown-op", "done"]),
+    };
+    transport.send(&[response]);
+}
+
+fn close_session(request: &Request, sessions: &Sessions) -> Response {
+    let session = request
+        .session()
+        .and_then(|id| sessions.lock().unwrap().remove(id));
+    match session {
+        // Its evaluator finishes whatever is queued and then exits, once the
+        // last handle to the session is gone
+        Some(session) => {
+            session.interrupt(None);
+            Response::for_request(request).status(&["session-closed", "done"])
+        }
+        None => Response::for_request(request).status(&["error", "unknown-session", "done"]),
+    }
+}
+
+// Whether request evaluates nothing but a quit,  :repl/quit or (exit)
+fn is_quit(request: &Request) -> bool {
+    let code = request.get("code").unwrap_or("");
+    match reader::try_read(code) {
+        Ok((rest, form)) => blank_len(rest) == rest.len() && repl::quit_code(&form).is_some(),
+        Err(_) => false,
+    }
+}
+
+type Halves = (Box<dyn Read + Send>, Box<dyn Write + Send>);
+
+/// What's read from a client connection,  and what's written to it;  over TLS if there's
+/// any,  which the bencode read and written needn't know about
+fn split(stream: TcpStream, tls: Option<&Tls>) -> io::Result<Halves> {
+    match tls {
+        Some(tls) => {
+            let (reader, writer) = tls.accept(stream)?;
+            Ok((Box::new(reader), Box::new(writer)))
+        }
+        None => Ok((Box::new(stream.try_clone()?), Box::new(stream))),
+    }
+}
+
+/// Whether request carries auth_token;  compared in full whatever it carries,  so how
+/// long that takes says nothing of how much of it was right
+fn authenticates(request: &Request, auth_token: &str) -> bool {
+    match request.get("auth-token") {
+        Some(token) if token.len() == auth_token.len() => {
+            token
+                .bytes()
+                .zip(auth_token.bytes())
+                .fold(0, |differ, (a, b)| differ | (a ^ b))
+                == 0
+        }
+        _ => false,
+    }
+}
+
+/// Handles one client connection until it hangs up.  With auth_token,  nothing but
+/// describe is answered until a request has carried it as its "auth-token"
+fn handle_connection(
+    stream: TcpStream,
+    sessions: Sessions,
+    transcript: Option<Transcript>,
+    metrics: Metrics,
+    access_log: Option<AccessLog>,
+    tls: Option<Tls>,
+    auth_token: Option<String>,
+) -> io::Result<()> {
+    let peer = stream
+        .peer_addr()
+        .map(|addr| addr.to_string())
+        .unwrap_or_else(|_| String::from("-"));
+    let (mut reader, writer) = split(stream, tls.as_ref())?;
+    let transport = Transport {
+        writer: Arc::new(Mutex::new(writer)),
+    };
+    // Evals sent without a session go to one private to this connection,  made on first use
+    let mut ephemeral_session: Option<Session> = None;
+    let mut authenticated = auth_token.is_none();
+    let mut decoder = Decoder::new();
+    let mut chunk = [0u8; 8192];
+
+    loop {
+        let read = reader.read(&mut chunk)?;
+        if read == 0 {
+            return Ok(());
+        }
+        decoder.feed(&chunk[..read]);
+        while let Some(msg) = decoder.next_message()? {
+            match Request::from_bencode(msg) {
+                Some(request) => {
+                    if let Some(access_log) = &access_log {
+                        access_log.request(&peer, &request);
+                    }
+                    if let Some(auth_token) = &auth_token {
+                        authenticated = authenticated || authenticates(&request, auth_token);
+                    }
+                    if !authenticated && request.op() != Some("describe") {
+                        transport.send(&[Response::for_request(&request).status(&[
+                            "error",
+                            "unauthorized",
+                            "done",
+                        ])]);
+                        continue;
+                    }
+                    run_request(
+                        &request,
+                        &sessions,
+                        &mut ephemeral_session,
+                        &transport,
+                        &transcript,
+                        &metrics,
+                    )
+                }
+                // Not a message we can even answer,  as there's no id to answer to
+                None => continue,
+            }
+        }
+    }
+}
+
+/// An nREPL server over TCP,  speaking bencode.  Every connection is served on its own
+/// thread, and sessions are shared between all of them
+pub struct Server {
+    listener: TcpListener,
+    sessions: Sessions,
+    transcript: Option<Transcript>,
+    metrics: Metrics,
+    access_log: Option<AccessLog>,
+    tls: Option<Tls>,
+    auth_token: Option<String>,
+}
+impl Server {
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            transcript: None,
+            metrics: Metrics::default(),
+            access_log: None,
+            tls: None,
+            auth_token: None,
+        })
+    }
+    /// This server,  recording every session's evaluations to transcript,  if there is one
+    pub fn recording(self, transcript: Option<Transcript>) -> Server {
+        Server { transcript, ..self }
+    }
+    /// This server,  logging every request it's sent to access_log,  if there is one
+    pub fn logging(self, access_log: Option<AccessLog>) -> Server {
+        Server { access_log, ..self }
+    }
+    /// This server,  accepting only TLS connections set up with tls,  if there is one
+    pub fn encrypting(self, tls: Option<Tls>) -> Server {
+        Server { tls, ..self }
+    }
+    /// This server,  answering only describe on connections that haven't sent auth_token,
+    /// if there is one
+    pub fn authenticating(self, auth_token: Option<String>) -> Server {
+        Server { auth_token, ..self }
+    }
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+    /// Serves connections forever;  a client disconnecting only ends its own thread
+    pub fn run(&self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("nREPL: failed to accept connection: {}", e);
+                    continue;
+                }
+            };
+            let sessions = Arc::clone(&self.sessions);
+            let transcript = self.transcript.clone();
+            let metrics = self.metrics.clone();
+            let access_log = self.access_log.clone();
+            let tls = self.tls.clone();
+            let auth_token = self.auth_token.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle_connection(
+                    stream, sessions, transcript, metrics, access_log, tls, auth_token,
+                ) {
+                    eprintln!("nREPL: connection closed with error: {}", e);
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod server_tests {
+        use crate::nrepl::bencode::{read_bencode, Bencode};
+        use crate::nrepl::server::Server;
+        use std::collections::BTreeMap;
+        use std::io::BufReader;
+        use std::net::{SocketAddr, TcpStream};
+        use std::thread;
+        use std::time::{Duration, Instant};
+
+        fn start_server() -> SocketAddr {
+            let server = Server::bind("127.0.0.1:0").unwrap();
+            let addr = server.local_addr().unwrap();
+            thread::spawn(move || server.run());
+            addr
+        }
+
+        fn send(stream: &mut TcpStream, msg: &[(&str, &str)]) {
+            let mut dict = BTreeMap::new();
+            for (key, val) in msg.iter() {
+                dict.insert(String::from(*key), Bencode::from(*val));
+            }
+            Bencode::Dict(dict).write_to(stream).unwrap();
+        }
+
+        // Reads responses up to and including the one marked done
+        fn recv_until_done(reader: &mut BufReader<TcpStream>) -> Vec<BTreeMap<String, Bencode>> {
+            let mut responses = vec![];
+            loop {
+                let response = match read_bencode(reader).unwrap().unwrap() {
+                    Bencode::Dict(dict) => dict,
+                    other => panic!("Expected a dict, got {}", other),
+                };
+                let done = match response.get("status") {
+                    Some(Bencode::List(statuses)) => statuses.contains(&Bencode::from("done")),
+                    _ => false,
+                };
+                responses.push(response);
+                if done {
+                    return responses;
+                }
+            }
+        }
+
+        fn statuses(response: &BTreeMap<String, Bencode>) -> Vec<Bencode> {
+            match response.get("status") {
+                Some(Bencode::List(statuses)) => statuses.clone(),
+                _ => vec![],
+            }
+        }
+
+        fn clone_session(stream: &mut TcpStream, reader: &mut BufReader<TcpStream>) -> String {
+            send(stream, &[("op", "clone"), ("id", "clone")]);
+            match recv_until_done(reader)[0].get("new-session") {
+                Some(Bencode::Str(session)) => session.clone(),
+                other => panic!("Expected a new session, got {:?}", other),
+            }
+        }
+
+        fn eval(addr: SocketAddr, code: &str) -> Vec<BTreeMap<String, Bencode>> {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            send(&mut stream, &[("op", "eval"), ("id", "1"), ("code", code)]);
+            recv_until_done(&mut reader)
+        }
+
+        #[test]
+        fn eval_returns_value_then_done() {
+            let addr = start_server();
+            let responses = eval(addr, "(+ 1 2)");
+            assert_eq!(Some(&Bencode::from("3")), responses[0].get("value"));
+            assert_eq!(Some(&Bencode::from("1")), responses[0].get("id"));
+            assert_eq!(2, responses.len());
+        }
+
+        #[test]
+        fn printed_output_is_streamed_before_value() {
+            let addr = start_server();
+            let responses = eval(addr, "(println \"hi\") 5");
+            let out = responses
+                .iter()
+                .take_while(|response| !response.contains_key("value"))
+                .filter_map(|response| response.get("out").and_then(Bencode::as_str))
+                .collect::<String>();
+            assert_eq!("hi\n", out);
+            let values = responses
+                .iter()
+                .filter_map(|response| response.get("value"))
+                .collect::<Vec<&Bencode>>();
+            assert_eq!(vec![&Bencode::from("nil"), &Bencode::from("5")], values);
+        }
+
+        #[test]
+        fn eval_large_message() {
+            let addr = start_server();
+            // Well past any single read from the socket
+            let code = format!("(+ 1 2){}", " ".repeat(1 << 20));
+            let responses = eval(addr, &code);
+            assert_eq!(Some(&Bencode::from("3")), responses[0].get("value"));
+        }
+
+        #[test]
+        fn serves_concurrent_clients() {
+            let addr = start_server();
+            // Hold one connection open and idle;  it must not block the others
+            let _idle = TcpStream::connect(addr).unwrap();
+            let handles: Vec<_> = (0..4)
+                .map(|i| thread::spawn(move || eval(addr, &format!("(+ {} 1)", i))))
+                .collect();
+            for (i, handle) in handles.into_iter().enumerate() {
+                let responses = handle.join().unwrap();
+                assert_eq!(
+                    Some(&Bencode::from((i + 1).to_string())),
+                    responses[0].get("value")
+                );
+            }
+        }
+
+        #[test]
+        fn keeps_serving_after_disconnect() {
+            let addr = start_server();
+            drop(TcpStream::connect(addr).unwrap());
+            eval(addr, "1");
+            let responses = eval(addr, "2");
+            assert_eq!(Some(&Bencode::from("2")), responses[0].get("value"));
+        }
+
+        #[test]
+        fn sessions_are_shared_across_connections() {
+            let addr = start_server();
+            let mut first = TcpStream::connect(addr).unwrap();
+            let mut first_reader = BufReader::new(first.try_clone().unwrap());
+            let session = clone_session(&mut first, &mut first_reader);
+            send(
+                &mut first,
+                &[
+                    ("op", "eval"),
+                    ("id", "2"),
+                    ("session", &session),
+                    ("code", "(def x 41)"),
+                ],
+            );
+            recv_until_done(&mut first_reader);
+
+            let mut second = TcpStream::connect(addr).unwrap();
+            let mut second_reader = BufReader::new(second.try_clone().unwrap());
+            send(
+                &mut second,
+                &[
+                    ("op", "eval"),
+                    ("id", "3"),
+                    ("session", &session),
+                    ("code", "(+ x 1)"),
+                ],
+            );
+            let responses = recv_until_done(&mut second_reader);
+            assert_eq!(Some(&Bencode::from("42")), responses[0].get("value"));
+        }
+
+        #[test]
+        fn clearing_a_session_forgets_only_what_it_defined() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let cleared = clone_session(&mut stream, &mut reader);
+            let kept = clone_session(&mut stream, &mut reader);
+            for (id, session) in [("1", &cleared), ("2", &kept)] {
+                send(
+                    &mut stream,
+                    &[
+                        ("op", "eval"),
+                        ("id", id),
+                        ("session", session),
+                        ("code", "(def x 41)"),
+                    ],
+                );
+                recv_until_done(&mut reader);
+            }
+            send(
+                &mut stream,
+                &[("op", "clear"), ("id", "3"), ("session", &cleared)],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert_eq!(Some(&Bencode::from("user")), responses[0].get("ns"));
+
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "4"),
+                    ("session", &cleared),
+                    ("code", "[(resolve 'x) (inc 1)]"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert_eq!(Some(&Bencode::from("[nil 2]")), responses[0].get("value"));
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "5"),
+                    ("session", &kept),
+                    ("code", "(+ x 1)"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert_eq!(Some(&Bencode::from("42")), responses[0].get("value"));
+        }
+
+        #[test]
+        fn a_sessions_evals_run_in_order_while_other_sessions_run_alongside() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "log"),
+                    ("session", &session),
+                    ("code", "(def log (atom []))"),
+                ],
+            );
+            recv_until_done(&mut reader);
+            let started = Instant::now();
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "0"),
+                    ("session", &session),
+                    ("code", "(do (Thread/sleep 500) (swap! log conj 0))"),
+                ],
+            );
+            for i in 1..=20 {
+                let (id, code) = (i.to_string(), format!("(swap! log conj {})", i));
+                send(
+                    &mut stream,
+                    &[
+                        ("op", "eval"),
+                        ("id", &id),
+                        ("session", &session),
+                        ("code", &code),
+                    ],
+                );
+            }
+
+            // Another session needn't wait for this one
+            let responses = eval(addr, "(+ 1 2)");
+            assert_eq!(Some(&Bencode::from("3")), responses[0].get("value"));
+            assert!(started.elapsed() < Duration::from_millis(500));
+
+            loop {
+                let responses = recv_until_done(&mut reader);
+                if responses.last().unwrap().get("id") == Some(&Bencode::from("20")) {
+                    break;
+                }
+            }
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "check"),
+                    ("session", &session),
+                    ("code", "@log"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            let logged = (0..=20).map(|i| i.to_string()).collect::<Vec<String>>();
+            assert_eq!(
+                Some(&Bencode::from(format!("[{}]", logged.join(" ")))),
+                responses[0].get("value")
+            );
+        }
+
+        #[test]
+        fn unknown_session_is_an_error() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "1"),
+                    ("session", "nope"),
+                    ("code", "1"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert_eq!(
+                Some(&Bencode::List(vec![
+                    Bencode::from("error"),
+                    Bencode::from("unknown-session"),
+                    Bencode::from("done")
+                ])),
+                responses[0].get("status")
+            );
+        }
+
+        #[test]
+        fn describe_lists_ops() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            send(&mut stream, &[("op", "describe"), ("id", "1")]);
+            let responses = recv_until_done(&mut reader);
+            let ops = match responses[0].get("ops") {
+                Some(Bencode::Dict(ops)) => ops.clone(),
+                other => panic!("Expected an ops dict, got {:?}", other),
+            };
+            for op in [
+                "clone",
+                "close",
+                "describe",
+                "eval",
+                "interrupt",
+                "load-file",
+            ]
+            .iter()
+            {
+                assert!(ops.contains_key(*op), "describe is missing {}", op);
+            }
+        }
+
+        #[test]
+        fn describe_says_what_of_clojure_is_supported() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            send(&mut stream, &[("op", "describe"), ("id", "1")]);
+            let responses = recv_until_done(&mut reader);
+            let capabilities = match responses[0].get("versions") {
+                Some(Bencode::Dict(versions)) => match versions.get("clojure-rs") {
+                    Some(Bencode::Dict(clojure_rs)) => clojure_rs.get("capabilities").cloned(),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let capabilities = match capabilities {
+                Some(Bencode::Dict(capabilities)) => capabilities,
+                other => panic!("Expected a capabilities dict, got {:?}", other),
+            };
+            assert_eq!(Some(&Bencode::Int(1)), capabilities.get("lazy-seqs?"));
+            assert_eq!(Some(&Bencode::Int(1)), capabilities.get("set-literals?"));
+            assert_eq!(Some(&Bencode::Int(0)), capabilities.get("destructuring?"));
+        }
+
+        #[test]
+        fn closed_session_is_gone() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            send(
+                &mut stream,
+                &[("op", "close"), ("id", "1"), ("session", &session)],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert!(statuses(&responses[0]).contains(&Bencode::from("session-closed")));
+
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "2"),
+                    ("session", &session),
+                    ("code", "1"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert!(statuses(&responses[0]).contains(&Bencode::from("unknown-session")));
+        }
+
+        #[test]
+        fn quitting_closes_the_session_not_the_server() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            for (id, code) in [("1", ":repl/quit"), ("2", " (exit) ; bye")] {
+                let session = clone_session(&mut stream, &mut reader);
+                send(
+                    &mut stream,
+                    &[
+                        ("op", "eval"),
+                        ("id", id),
+                        ("session", &session),
+                        ("code", code),
+                    ],
+                );
+                let responses = recv_until_done(&mut reader);
+                assert!(statuses(&responses[0]).contains(&Bencode::from("session-closed")));
+                send(
+                    &mut stream,
+                    &[
+                        ("op", "eval"),
+                        ("id", "3"),
+                        ("session", &session),
+                        ("code", "1"),
+                    ],
+                );
+                let responses = recv_until_done(&mut reader);
+                assert!(statuses(&responses[0]).contains(&Bencode::from("unknown-session")));
+            }
+            assert_eq!(
+                Some(&Bencode::from("2")),
+                eval(addr, "(inc 1)")[0].get("value")
+            );
+        }
+
+        #[test]
+        fn load_file_returns_last_value() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            send(
+                &mut stream,
+                &[
+                    ("op", "load-file"),
+                    ("id", "1"),
+                    ("file", "(def a 1)\n(def b 2)\n(+ a b)\n"),
+                    ("file-name", "three.clj"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert_eq!(2, responses.len());
+            assert_eq!(Some(&Bencode::from("3")), responses[0].get("value"));
+        }
+
+        #[test]
+        fn errors_come_with_a_trace() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "1"),
+                    ("code", "(defn half [x]\n  (+ x :a))\n(half 4)"),
+                    ("file", "src/half.clj"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            let err = responses
+                .iter()
+                .find_map(|response| response.get("err").and_then(Bencode::as_str));
+            assert_eq!(
+                Some("Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: clojure.lang.Keyword\n    at + (src/half.clj:2:3)\n    at half (src/half.clj:3:1)\n"),
+                err
+            );
+        }
+
+        #[test]
+        fn workspace_symbols_knows_where_defs_were_made() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            send(
+                &mut stream,
+                &[
+                    ("op", "load-file"),
+                    ("id", "1"),
+                    ("session", &session),
+                    (
+                        "file",
+                        "\n(def zz-count 1)\n\n(defn zz-inc [x]\n  (+ x 1))\n",
+                    ),
+                    ("file-path", "src/zz.clj"),
+                ],
+            );
+            recv_until_done(&mut reader);
+            send(
+                &mut stream,
+                &[
+                    ("op", "workspace-symbols"),
+                    ("id", "2"),
+                    ("session", &session),
+                    ("query", "ZZ-"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            let symbols = match responses[0].get("symbols") {
+                Some(Bencode::List(symbols)) => symbols.clone(),
+                other => panic!("Expected a list of symbols, got {:?}", other),
+            };
+            let described = symbols
+                .iter()
+                .map(|symbol| match symbol {
+                    Bencode::Dict(symbol) => ["name", "kind", "file", "line"]
+                        .iter()
+                        .map(|key| match symbol.get(*key) {
+                            Some(Bencode::Str(s)) => s.clone(),
+                            Some(Bencode::Int(i)) => i.to_string(),
+                            other => panic!("Expected {}, got {:?}", key, other),
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" "),
+                    other => panic!("Expected a dict, got {}", other),
+                })
+                .collect::<Vec<String>>();
+            assert_eq!(
+                vec![
+                    "zz-count variable src/zz.clj 2",
+                    "zz-inc function src/zz.clj 4"
+                ],
+                described
+            );
+        }
+
+        #[test]
+        fn signatures_gives_arglists() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "1"),
+                    ("session", &session),
+                    ("code", "(defn greet [greeting & names] names)"),
+                ],
+            );
+            recv_until_done(&mut reader);
+            send(
+                &mut stream,
+                &[
+                    ("op", "signatures"),
+                    ("id", "2"),
+                    ("session", &session),
+                    ("symbol", "greet"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            let arglist = vec![
+                Bencode::from("greeting"),
+                Bencode::from("&"),
+                Bencode::from("names"),
+            ];
+            assert_eq!(
+                Some(&Bencode::List(vec![Bencode::List(arglist)])),
+                responses[0].get("arglists")
+            );
+            assert_eq!(Some(&Bencode::from("function")), responses[0].get("kind"));
+
+            send(
+                &mut stream,
+                &[
+                    ("op", "signatures"),
+                    ("id", "3"),
+                    ("session", &session),
+                    ("symbol", "nope"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert!(statuses(&responses[0]).contains(&Bencode::from("no-info")));
+        }
+
+        #[test]
+        fn lookup_gives_what_doc_shows() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "1"),
+                    ("session", &session),
+                    (
+                        "code",
+                        "(defn greet \"Greets them\" [greeting & names] names)",
+                    ),
+                ],
+            );
+            recv_until_done(&mut reader);
+            send(
+                &mut stream,
+                &[
+                    ("op", "lookup"),
+                    ("id", "2"),
+                    ("session", &session),
+                    ("symbol", "greet"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            let info = match responses[0].get("info") {
+                Some(Bencode::Dict(info)) => info,
+                info => panic!("No info in {:?}", info),
+            };
+            assert_eq!(Some(&Bencode::from("greet")), info.get("name"));
+            assert_eq!(Some(&Bencode::from("user")), info.get("ns"));
+            assert_eq!(Some(&Bencode::from("Greets them")), info.get("doc"));
+            assert_eq!(
+                Some(&Bencode::from("([greeting & names])")),
+                info.get("arglists-str")
+            );
+
+            send(
+                &mut stream,
+                &[
+                    ("op", "lookup"),
+                    ("id", "3"),
+                    ("session", &session),
+                    ("symbol", "nope"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert!(statuses(&responses[0]).contains(&Bencode::from("no-info")));
+        }
+
+        #[test]
+        fn completions_info_and_eldoc_answer_cider() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "1"),
+                    ("session", &session),
+                    (
+                        "code",
+                        "(defn greet \"Greets them\" [greeting & names] names)",
+                    ),
+                ],
+            );
+            recv_until_done(&mut reader);
+            send(
+                &mut stream,
+                &[
+                    ("op", "completions"),
+                    ("id", "2"),
+                    ("session", &session),
+                    ("prefix", "gre"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            let mut greet = BTreeMap::new();
+            greet.insert(String::from("candidate"), Bencode::from("greet"));
+            greet.insert(String::from("ns"), Bencode::from("user"));
+            greet.insert(String::from("type"), Bencode::from("function"));
+            assert_eq!(
+                Some(&Bencode::List(vec![Bencode::Dict(greet)])),
+                responses[0].get("completions")
+            );
+
+            send(
+                &mut stream,
+                &[
+                    ("op", "info"),
+                    ("id", "3"),
+                    ("session", &session),
+                    ("sym", "greet"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert_eq!(Some(&Bencode::from("Greets them")), responses[0].get("doc"));
+            assert_eq!(
+                Some(&Bencode::from("([greeting & names])")),
+                responses[0].get("arglists-str")
+            );
+            send(
+                &mut stream,
+                &[
+                    ("op", "info"),
+                    ("id", "4"),
+                    ("session", &session),
+                    ("sym", "recur"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert_eq!(
+                Some(&Bencode::from("true")),
+                responses[0].get("special-form")
+            );
+
+            send(
+                &mut stream,
+                &[
+                    ("op", "eldoc"),
+                    ("id", "5"),
+                    ("session", &session),
+                    ("sym", "greet"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            let arglist = vec![
+                Bencode::from("greeting"),
+                Bencode::from("&"),
+                Bencode::from("names"),
+            ];
+            assert_eq!(
+                Some(&Bencode::List(vec![Bencode::List(arglist)])),
+                responses[0].get("eldoc")
+            );
+            assert_eq!(
+                Some(&Bencode::from("Greets them")),
+                responses[0].get("docstring")
+            );
+        }
+
+        #[test]
+        fn metrics_count_what_the_server_has_done() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            for (id, code) in [("1", "(+ 1 2)"), ("2", "(+ 1 :a)")].iter() {
+                send(
+                    &mut stream,
+                    &[
+                        ("op", "eval"),
+                        ("id", id),
+                        ("session", &session),
+                        ("code", code),
+                    ],
+                );
+                recv_until_done(&mut reader);
+            }
+            send(&mut stream, &[("op", "nonsense"), ("id", "3")]);
+            recv_until_done(&mut reader);
+            send(&mut stream, &[("op", "metrics"), ("id", "4")]);
+            let responses = recv_until_done(&mut reader);
+            let metrics = match responses[0].get("metrics") {
+                Some(Bencode::Str(metrics)) => metrics.clone(),
+                metrics => panic!("No metrics in {:?}", metrics),
+            };
+            for line in [
+                "nrepl_requests_total{op=\"clone\"} 1",
+                "nrepl_requests_total{op=\"eval\"} 2",
+                "nrepl_requests_total{op=\"metrics\"} 1",
+                "nrepl_requests_total{op=\"unknown\"} 1",
+                "nrepl_active_sessions 1",
+                "nrepl_evals_total 2",
+                "nrepl_eval_errors_total 1",
+                "nrepl_eval_error_rate 0.5",
+            ]
+            .iter()
+            {
+                assert!(
+                    metrics.lines().any(|l| l == *line),
+                    "{} not in\n{}",
+                    line,
+                    metrics
+                );
+            }
+        }
+
+        #[test]
+        fn only_describe_is_answered_until_the_auth_token_is_sent() {
+            let server = Server::bind("127.0.0.1:0")
+                .unwrap()
+                .authenticating(Some(String::from("s3cret")));
+            let addr = server.local_addr().unwrap();
+            thread::spawn(move || server.run());
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            send(&mut stream, &[("op", "describe"), ("id", "1")]);
+            assert_eq!(
+                vec![Bencode::from("done")],
+                statuses(&recv_until_done(&mut reader)[0])
+            );
+            for token in ["s3cre", "s3cret!", "S3CRET"].iter() {
+                send(
+                    &mut stream,
+                    &[
+                        ("op", "eval"),
+                        ("id", "2"),
+                        ("code", "(+ 1 2)"),
+                        ("auth-token", token),
+                    ],
+                );
+                assert!(statuses(&recv_until_done(&mut reader)[0])
+                    .contains(&Bencode::from("unauthorized")));
+            }
+            send(&mut stream, &[("op", "clone"), ("id", "3")]);
+            assert!(
+                statuses(&recv_until_done(&mut reader)[0]).contains(&Bencode::from("unauthorized"))
+            );
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "4"),
+                    ("code", "(+ 1 2)"),
+                    ("auth-token", "s3cret"),
+                ],
+            );
+            assert_eq!(
+                Some(&Bencode::from("3")),
+                recv_until_done(&mut reader)[0].get("value")
+            );
+            // From then on,  the connection's trusted
+            send(
+                &mut stream,
+                &[("op", "eval"), ("id", "5"), ("code", "(+ 3 4)")],
+            );
+            assert_eq!(
+                Some(&Bencode::from("7")),
+                recv_until_done(&mut reader)[0].get("value")
+            );
+            // But no other is
+            assert!(statuses(&eval(addr, "(+ 1 2)")[0]).contains(&Bencode::from("unauthorized")));
+        }
+
+        #[test]
+        fn interrupt_idle_session() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            send(
+                &mut stream,
+                &[("op", "interrupt"), ("id", "1"), ("session", &session)],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert!(statuses(&responses[0]).contains(&Bencode::from("session-idle")));
+        }
+
+        #[test]
+        fn interrupt_stops_running_eval() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            // A good 10 seconds, if it were allowed to finish
+            let slow = format!("(do {})", "(Thread/sleep 100) ".repeat(100));
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "slow"),
+                    ("session", &session),
+                    ("code", &slow),
+                ],
+            );
+            thread::sleep(Duration::from_millis(300));
+            let started = Instant::now();
+            send(
+                &mut stream,
+                &[
+                    ("op", "interrupt"),
+                    ("id", "stop"),
+                    ("session", &session),
+                    ("interrupt-id", "slow"),
+                ],
+            );
+            // Responses to both messages may arrive in either order
+            let mut slow_statuses = vec![];
+            let mut stop_statuses = vec![];
+            while !slow_statuses.contains(&Bencode::from("done"))
+                || !stop_statuses.contains(&Bencode::from("done"))
+            {
+                for response in recv_until_done(&mut reader).iter() {
+                    match response.get("id") {
+                        Some(Bencode::Str(id)) if id == "slow" => {
+                            slow_statuses.extend(statuses(response))
+                        }
+                        _ => stop_statuses.extend(statuses(response)),
+                    }
+                }
+            }
+            assert!(slow_statuses.contains(&Bencode::from("interrupted")));
+            assert!(started.elapsed() < Duration::from_secs(5));
+
+            // The session is still usable afterwards
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "after"),
+                    ("session", &session),
+                    ("code", "(+ 1 1)"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert_eq!(Some(&Bencode::from("2")), responses[0].get("value"));
+        }
+
+        #[test]
+        fn interrupted_eval_still_runs_finally() {
+            let addr = start_server();
+            let mut stream = TcpStream::connect(addr).unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let session = clone_session(&mut stream, &mut reader);
+            let slow = format!(
+                "(try {} (finally (Thread/sleep 10) (def cleaned-up :yes)))",
+                "(Thread/sleep 100) ".repeat(100)
+            );
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "slow"),
+                    ("session", &session),
+                    ("code", &slow),
+                ],
+            );
+            thread::sleep(Duration::from_millis(300));
+            send(
+                &mut stream,
+                &[("op", "interrupt"), ("id", "stop"), ("session", &session)],
+            );
+            let mut slow_done = false;
+            let mut stop_done = false;
+            while !slow_done || !stop_done {
+                for response in recv_until_done(&mut reader).iter() {
+                    let done = statuses(response).contains(&Bencode::from("done"));
+                    match response.get("id") {
+                        Some(Bencode::Str(id)) if id == "slow" => slow_done |= done,
+                        _ => stop_done |= done,
+                    }
+                }
+            }
+
+            send(
+                &mut stream,
+                &[
+                    ("op", "eval"),
+                    ("id", "after"),
+                    ("session", &session),
+                    ("code", "cleaned-up"),
+                ],
+            );
+            let responses = recv_until_done(&mut reader);
+            assert_eq!(Some(&Bencode::from(":yes")), responses[0].get("value"));
+        }
+    }
+}
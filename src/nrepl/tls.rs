@@ -0,0 +1,246 @@
+//! TLS for nREPL connections,  so what's evaluated isn't sent in the clear
+//!
+//! A connection is read on its own thread while responses are written from the session's;
+//! a TcpStream is cloned for that,  but TLS has one state for both directions.  So each
+//! half keeps its own clone of the TcpStream,  and they share the TLS state behind a lock
+//! held only to decrypt what's been read,  or encrypt what's to be written;  never while
+//! waiting on the client
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig, ServerConnection};
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+
+/// Where the server's certificate chain and private key are,  as PEM;  and with client_ca,
+/// the certificates clients must present one signed by to connect at all
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsFiles {
+    pub cert: String,
+    pub key: String,
+    pub client_ca: Option<String>,
+}
+
+/// What every connection's TLS is set up with
+#[derive(Clone)]
+pub struct Tls {
+    config: Arc<ServerConfig>,
+}
+impl Tls {
+    /// Reads files;  an error says which couldn't be used,  and why
+    pub fn load(files: &TlsFiles) -> Result<Tls, String> {
+        let certs = read_certs(&files.cert)?;
+        let key = PrivateKeyDer::from_pem_file(&files.key)
+            .map_err(|e| format!("Could not read key {}: {}", files.key, e))?;
+        let builder = ServerConfig::builder();
+        let builder = match &files.client_ca {
+            Some(client_ca) => {
+                let mut roots = RootCertStore::empty();
+                for cert in read_certs(client_ca)? {
+                    roots
+                        .add(cert)
+                        .map_err(|e| format!("Invalid certificate in {}: {}", client_ca, e))?;
+                }
+                let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                    .build()
+                    .map_err(|e| format!("Invalid client CA {}: {}", client_ca, e))?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let config = builder
+            .with_single_cert(certs, key)
+            .map_err(|e| format!("Invalid certificate or key: {}", e))?;
+        Ok(Tls {
+            config: Arc::new(config),
+        })
+    }
+    /// Completes the handshake with the client at the other end of stream;  then gives the
+    /// connection's reading and writing halves
+    pub fn accept(&self, mut stream: TcpStream) -> io::Result<(TlsReader, TlsWriter)> {
+        let mut connection = ServerConnection::new(Arc::clone(&self.config))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        while connection.is_handshaking() {
+            connection.complete_io(&mut stream)?;
+        }
+        let connection = Arc::new(Mutex::new(connection));
+        Ok((
+            TlsReader {
+                stream: stream.try_clone()?,
+                connection: Arc::clone(&connection),
+            },
+            TlsWriter { stream, connection },
+        ))
+    }
+}
+
+fn read_certs(path: &str) -> Result<Vec<CertificateDer<'static>>, String> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("Could not read certificates {}: {}", path, e))?;
+    if certs.is_empty() {
+        return Err(format!("No certificates in {}", path));
+    }
+    Ok(certs)
+}
+
+// Sends the client whatever TLS has queued for it;  records we've encrypted,  or an alert
+fn write_pending(connection: &mut ServerConnection, stream: &mut TcpStream) -> io::Result<()> {
+    while connection.wants_write() {
+        connection.write_tls(stream)?;
+    }
+    Ok(())
+}
+
+pub struct TlsReader {
+    stream: TcpStream,
+    connection: Arc<Mutex<ServerConnection>>,
+}
+impl Read for TlsReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut received = [0u8; 8192];
+        loop {
+            match self.connection.lock().unwrap().reader().read(buf) {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => (),
+                // Ok(0) once the client's said it's closing
+                read => return read,
+            }
+            let len = self.stream.read(&mut received)?;
+            if len == 0 {
+                return Ok(0);
+            }
+            let mut connection = self.connection.lock().unwrap();
+            let mut unread = &received[..len];
+            while !unread.is_empty() {
+                connection.read_tls(&mut unread)?;
+                if let Err(e) = connection.process_new_packets() {
+                    // Telling the client why,  if we can
+                    let _ = write_pending(&mut connection, &mut self.stream);
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+                }
+            }
+            write_pending(&mut connection, &mut self.stream)?;
+        }
+    }
+}
+
+pub struct TlsWriter {
+    stream: TcpStream,
+    connection: Arc<Mutex<ServerConnection>>,
+}
+impl Write for TlsWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut connection = self.connection.lock().unwrap();
+        let written = connection.writer().write(buf)?;
+        write_pending(&mut connection, &mut self.stream)?;
+        Ok(written)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nrepl::bencode::{read_bencode, Bencode};
+    use crate::nrepl::server::Server;
+    use crate::nrepl::tls::{read_certs, Tls, TlsFiles};
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{PrivateKeyDer, ServerName};
+    use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+    use std::collections::BTreeMap;
+    use std::convert::TryFrom;
+    use std::io::{self, BufReader};
+    use std::net::{SocketAddr, TcpStream};
+    use std::sync::Arc;
+    use std::thread;
+
+    type Client = BufReader<StreamOwned<ClientConnection, TcpStream>>;
+
+    fn path(file: &str) -> String {
+        format!(
+            "{}/src/nrepl/test_certs/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            file
+        )
+    }
+
+    fn start_server(client_ca: Option<String>) -> SocketAddr {
+        let tls = Tls::load(&TlsFiles {
+            cert: path("server.pem"),
+            key: path("server.key"),
+            client_ca,
+        })
+        .unwrap();
+        let server = Server::bind("127.0.0.1:0").unwrap().encrypting(Some(tls));
+        let addr = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        addr
+    }
+
+    fn connect(addr: SocketAddr, with_cert: bool) -> Client {
+        let mut roots = RootCertStore::empty();
+        for cert in read_certs(&path("ca.pem")).unwrap() {
+            roots.add(cert).unwrap();
+        }
+        let config = ClientConfig::builder().with_root_certificates(roots);
+        let config = if with_cert {
+            let key = PrivateKeyDer::from_pem_file(path("client.key")).unwrap();
+            config
+                .with_client_auth_cert(read_certs(&path("client.pem")).unwrap(), key)
+                .unwrap()
+        } else {
+            config.with_no_client_auth()
+        };
+        let name = ServerName::try_from("localhost").unwrap();
+        let connection = ClientConnection::new(Arc::new(config), name).unwrap();
+        BufReader::new(StreamOwned::new(
+            connection,
+            TcpStream::connect(addr).unwrap(),
+        ))
+    }
+
+    // The value the server gives code,  once it's done;  or why the connection failed
+    fn eval(client: &mut Client, code: &str) -> io::Result<Option<Bencode>> {
+        let mut msg = BTreeMap::new();
+        for (key, val) in [("op", "eval"), ("id", "1"), ("code", code)].iter() {
+            msg.insert(String::from(*key), Bencode::from(*val));
+        }
+        Bencode::Dict(msg).write_to(client.get_mut())?;
+        let mut value = None;
+        loop {
+            match read_bencode(client)? {
+                Some(Bencode::Dict(response)) if response.contains_key("status") => {
+                    return Ok(value)
+                }
+                Some(Bencode::Dict(response)) => value = response.get("value").cloned(),
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    #[test]
+    fn tls_connections_are_served_as_any_other() {
+        let addr = start_server(None);
+        let mut client = connect(addr, false);
+        assert_eq!(
+            Some(Bencode::from("3")),
+            eval(&mut client, "(+ 1 2)").unwrap()
+        );
+        // Many TLS records,  and many reads from the socket
+        let long = format!("(+ 3 4){}", " ".repeat(1 << 20));
+        assert_eq!(Some(Bencode::from("7")), eval(&mut client, &long).unwrap());
+    }
+
+    #[test]
+    fn with_a_client_ca_only_clients_it_signed_are_served() {
+        let addr = start_server(Some(path("ca.pem")));
+        let mut client = connect(addr, true);
+        assert_eq!(
+            Some(Bencode::from("3")),
+            eval(&mut client, "(+ 1 2)").unwrap()
+        );
+        assert!(eval(&mut connect(addr, false), "(+ 1 2)").is_err());
+    }
+}
@@ -0,0 +1,319 @@
+//! An nREPL client;  so `rust_clojure connect`,  tests and other Rust tools can talk to a
+//! running ClojureRS,  or any other nREPL server,  without writing bencode themselves
+//!
+//! Each request goes with an id of its own,  and its responses are read until one says it's
+//! done;  anything the server sends about other requests is skipped.  Plain TCP only
+use crate::keyword::Keyword;
+use crate::nrepl::bencode::{read_bencode, Bencode};
+use crate::output::Stream;
+use crate::repl;
+use crate::socket_repl::FormReader;
+use crate::value::{ToValue, Value};
+use std::collections::BTreeMap;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+/// One of the messages a server answers a request with
+pub type Response = BTreeMap<String, Bencode>;
+
+/// Connects to the nREPL server at addr
+pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Client> {
+    let stream = TcpStream::connect(addr)?;
+    let reader = BufReader::new(stream.try_clone()?);
+    Ok(Client {
+        stream,
+        reader,
+        next_id: 0,
+        session: None,
+        auth_token: None,
+    })
+}
+
+#[derive(Debug)]
+pub struct Client {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+    next_id: u64,
+    // What requests are sent in;  without one,  the server evaluates each in a session it
+    // makes just for it
+    session: Option<String>,
+    auth_token: Option<String>,
+}
+
+/// What evaluating some code gave:  the value of each form in it,  what it printed,  and
+/// the namespace it left the session in
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Evaluation {
+    pub values: Vec<String>,
+    pub out: String,
+    pub err: String,
+    pub ns: Option<String>,
+    pub status: Vec<String>,
+}
+impl Evaluation {
+    /// Whether one of the forms ended in a condition (an exception,  to other servers)
+    pub fn failed(&self) -> bool {
+        self.status.iter().any(|status| status == "eval-error")
+    }
+}
+
+impl Client {
+    /// This client,  sending auth_token with each request;  as a server started with
+    /// --auth-token or --auth wants
+    pub fn authenticating(self, auth_token: Option<String>) -> Client {
+        Client { auth_token, ..self }
+    }
+    /// Sends op,  with args,  in this client's session;  and gives every response to it,  up
+    /// to the one that says it's done
+    pub fn request(&mut self, op: &str, args: &[(&str, Bencode)]) -> io::Result<Vec<Response>> {
+        let mut responses = vec![];
+        self.request_each(op, args, |response| responses.push(response.clone()))?;
+        Ok(responses)
+    }
+    /// request,  handing each response to each as it comes;  so what's printed while a long
+    /// eval runs can be shown before it's done
+    pub fn request_each(
+        &mut self,
+        op: &str,
+        args: &[(&str, Bencode)],
+        mut each: impl FnMut(&Response),
+    ) -> io::Result<()> {
+        self.next_id += 1;
+        let id = self.next_id.to_string();
+        let mut request = BTreeMap::new();
+        request.insert(String::from("op"), Bencode::from(op));
+        request.insert(String::from("id"), Bencode::from(id.as_str()));
+        if let Some(session) = &self.session {
+            request.insert(String::from("session"), Bencode::from(session.as_str()));
+        }
+        if let Some(auth_token) = &self.auth_token {
+            request.insert(
+                String::from("auth-token"),
+                Bencode::from(auth_token.as_str()),
+            );
+        }
+        for (key, val) in args.iter() {
+            request.insert(String::from(*key), val.clone());
+        }
+        Bencode::Dict(request).write_to(&mut self.stream)?;
+        loop {
+            let response = match read_bencode(&mut self.reader)? {
+                Some(Bencode::Dict(response)) => response,
+                Some(other) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Expected a dict, got {}", other),
+                    ))
+                }
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "The server hung up",
+                    ))
+                }
+            };
+            if response.get("id").and_then(Bencode::as_str) != Some(id.as_str()) {
+                continue;
+            }
+            each(&response);
+            if statuses(&response).any(|status| status == "done") {
+                return Ok(());
+            }
+        }
+    }
+    /// Evaluates code,  every form in it
+    pub fn eval(&mut self, code: &str) -> io::Result<Evaluation> {
+        self.eval_printing(code, |_, _| ())
+    }
+    /// eval,  handing what code prints to printed as it's printed
+    pub fn eval_printing(
+        &mut self,
+        code: &str,
+        mut printed: impl FnMut(Stream, &str),
+    ) -> io::Result<Evaluation> {
+        let mut evaluation = Evaluation::default();
+        self.request_each("eval", &[("code", Bencode::from(code))], |response| {
+            let text = |key| response.get(key).and_then(Bencode::as_str);
+            if let Some(value) = text("value") {
+                evaluation.values.push(value.to_string());
+            }
+            if let Some(out) = text("out") {
+                printed(Stream::Out, out);
+                evaluation.out.push_str(out);
+            }
+            if let Some(err) = text("err") {
+                printed(Stream::Err, err);
+                evaluation.err.push_str(err);
+            }
+            if let Some(ns) = text("ns") {
+                evaluation.ns = Some(ns.to_string());
+            }
+            evaluation
+                .status
+                .extend(statuses(response).map(String::from));
+        })?;
+        Ok(evaluation)
+    }
+    /// Starts this client's session over,  with only clojure.core defined;  as :repl/clear
+    /// does at the REPL.  The namespace it's left in
+    pub fn clear(&mut self) -> io::Result<Option<String>> {
+        let responses = self.request("clear", &[])?;
+        Ok(responses
+            .iter()
+            .find_map(|response| response.get("ns").and_then(Bencode::as_str))
+            .map(String::from))
+    }
+    /// Clones this client's session (or a new one,  if it has none),  and sends requests in
+    /// the clone from then on;  the clone's id
+    pub fn clone_session(&mut self) -> io::Result<String> {
+        let responses = self.request("clone", &[])?;
+        match responses
+            .iter()
+            .find_map(|response| response.get("new-session").and_then(Bencode::as_str))
+        {
+            Some(session) => {
+                self.session = Some(session.to_string());
+                Ok(session.to_string())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The server made no new session",
+            )),
+        }
+    }
+    /// Closes this client's session,  if it has one;  requests go without one after
+    pub fn close_session(&mut self) -> io::Result<()> {
+        if self.session.is_some() {
+            self.request("close", &[])?;
+            self.session = None;
+        }
+        Ok(())
+    }
+}
+
+/// A REPL,  as Repl::run_on is,  but evaluating each form read from input in client's
+/// session;  what's printed goes to output,  or errors,  as it's printed.  Whether every form
+/// went through,  once input runs out
+pub fn run_on<R: BufRead, W: Write, E: Write>(
+    client: &mut Client,
+    input: R,
+    output: &mut W,
+    errors: &mut E,
+    interactive: bool,
+) -> io::Result<bool> {
+    let mut ns = String::from("user");
+    let mut went_through = true;
+    let mut forms = FormReader::new(input);
+    loop {
+        if interactive {
+            write!(output, "{}=> ", ns)?;
+            output.flush()?;
+        }
+        // One that can't be read is sent all the same;  the server says what's wrong with it
+        let (form, source) = match forms.next_form()? {
+            Some(next) => next,
+            None => break,
+        };
+        // Quitting ends the REPL here;  whoever connected closes the session
+        if repl::quit_code(&form).is_some() {
+            break;
+        }
+        if form == Keyword::intern_with_ns("repl", "clear").to_value() {
+            ns = client.clear()?.unwrap_or(ns);
+            writeln!(output, "{}", Value::Nil)?;
+            continue;
+        }
+        let evaluation = client.eval_printing(&source, |stream, text| {
+            let _ = match stream {
+                Stream::Out => write!(output, "{}", text).and_then(|()| output.flush()),
+                Stream::Err => write!(errors, "{}", text).and_then(|()| errors.flush()),
+            };
+        })?;
+        for value in evaluation.values.iter() {
+            writeln!(output, "{}", value)?;
+        }
+        output.flush()?;
+        went_through = went_through && !evaluation.failed();
+        ns = evaluation.ns.unwrap_or(ns);
+    }
+    if interactive {
+        writeln!(output)?;
+    }
+    Ok(went_through)
+}
+
+fn statuses(response: &Response) -> impl Iterator<Item = &str> {
+    let statuses = match response.get("status") {
+        Some(Bencode::List(statuses)) => &statuses[..],
+        _ => &[],
+    };
+    statuses.iter().filter_map(Bencode::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nrepl::Server;
+    use crate::nrepl_client;
+    use std::io::Cursor;
+    use std::net::SocketAddr;
+    use std::thread;
+
+    fn start_server(auth_token: Option<&str>) -> SocketAddr {
+        let server = Server::bind("127.0.0.1:0")
+            .unwrap()
+            .authenticating(auth_token.map(String::from));
+        let addr = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        addr
+    }
+
+    #[test]
+    fn eval_gives_values_output_and_errors() {
+        let mut client = nrepl_client::connect(start_server(None)).unwrap();
+        let evaluation = client.eval("(println \"hi\") (+ 1 2)").unwrap();
+        assert_eq!(vec!["nil", "3"], evaluation.values);
+        assert_eq!("hi\n", evaluation.out);
+        assert_eq!(Some(String::from("user")), evaluation.ns);
+        assert!(!evaluation.failed());
+        let evaluation = client.eval("(/ 1 0)").unwrap();
+        assert!(evaluation.failed());
+        assert!(!evaluation.err.is_empty());
+    }
+
+    #[test]
+    fn the_remote_repl_prints_as_the_local_one_does() {
+        let mut client = nrepl_client::connect(start_server(None)).unwrap();
+        client.clone_session().unwrap();
+        let (mut output, mut errors) = (vec![], vec![]);
+        let input = "(def x 1)\n(println x)\n(in-ns 'app)\n:repl/clear\n(/ x 0)\n";
+        let went_through = nrepl_client::run_on(
+            &mut client,
+            Cursor::new(input),
+            &mut output,
+            &mut errors,
+            true,
+        )
+        .unwrap();
+        assert!(!went_through);
+        assert_eq!(
+            "user=> #'user/x\nuser=> 1\nnil\nuser=> #namespace[app]\napp=> nil\nuser=> user=> \n",
+            String::from_utf8(output).unwrap()
+        );
+        assert!(String::from_utf8(errors)
+            .unwrap()
+            .starts_with("Undefined symbol x"));
+    }
+
+    #[test]
+    fn a_cloned_session_keeps_its_definitions() {
+        let mut client = nrepl_client::connect(start_server(Some("secret")))
+            .unwrap()
+            .authenticating(Some(String::from("secret")));
+        client.clone_session().unwrap();
+        client.eval("(def x 41)").unwrap();
+        assert_eq!(vec!["42"], client.eval("(inc x)").unwrap().values);
+        client.close_session().unwrap();
+        assert!(client.eval("(inc x)").unwrap().failed());
+    }
+}
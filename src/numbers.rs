@@ -0,0 +1,585 @@
+//! Numbers,  and arithmetic that works across all their types,  as Clojure's does
+//!
+//! When two numbers of different types meet,  both are taken as the later of the two in
+//!
+//!   i32 -> BigInt -> Ratio -> BigDecimal -> f64
+//!
+//! An i32 that overflows becomes a BigInt,  rather than wrapping round,  and a ratio that
+//! comes out whole becomes one too;  so (+ 1/2 1/2) is 1N.  As in Clojure,  = only finds
+//! numbers equal if they're of the same kind (integers,  ratios,  decimals or floats),  so
+//! (= 1 1N) but not (= 1 1.0);  == compares any two by value
+//!
+//! BigDecimals are exact;  dividing them is a condition if the quotient never ends.  Within
+//! with-precision,  though,  what each operation gives is rounded to so many digits
+use crate::value::Value;
+use bigdecimal::{BigDecimal, RoundingMode};
+use num_bigint::{BigInt, Sign};
+use num_rational::BigRational;
+use num_traits::{ToPrimitive, Zero};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+/// How BigDecimal results are rounded within with-precision;  to precision significant
+/// digits.  A rounding of None is UNNECESSARY,  which has a result that'd need rounding be
+/// a condition instead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MathContext {
+    pub precision: NonZeroU64,
+    pub rounding: Option<RoundingMode>,
+}
+
+thread_local! {
+    // The context BigDecimal results are rounded in;  none,  outside with-precision
+    static MATH_CONTEXT: RefCell<Option<MathContext>> = const { RefCell::new(None) };
+}
+
+/// What f gives,  with BigDecimal results rounded in context while it runs
+pub fn with_math_context<R>(context: MathContext, f: impl FnOnce() -> R) -> R {
+    let outer = MATH_CONTEXT.with(|math_context| math_context.replace(Some(context)));
+    let result = f();
+    MATH_CONTEXT.with(|math_context| math_context.replace(outer));
+    result
+}
+
+/// The context BigDecimal results are rounded in right now,  if any
+pub fn math_context() -> Option<MathContext> {
+    MATH_CONTEXT.with(|math_context| *math_context.borrow())
+}
+
+// Where a number is in the tower
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Rank {
+    I32,
+    BigInt,
+    Ratio,
+    BigDecimal,
+    F64,
+}
+
+fn rank(val: &Value) -> Option<Rank> {
+    match val {
+        Value::I32(_) => Some(Rank::I32),
+        Value::BigInt(_) => Some(Rank::BigInt),
+        Value::Ratio(_) => Some(Rank::Ratio),
+        Value::BigDecimal(_) => Some(Rank::BigDecimal),
+        Value::F64(_) => Some(Rank::F64),
+        _ => None,
+    }
+}
+
+pub fn is_number(val: &Value) -> bool {
+    rank(val).is_some()
+}
+
+fn not_a_number(val: &Value) -> Value {
+    Value::Condition(
+        format!(
+            "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
+            val.type_tag()
+        )
+        .into(),
+    )
+}
+
+fn divide_by_zero() -> Value {
+    Value::Condition("Divide by zero".into())
+}
+
+/// The integer n,  as an i32 if it fits;  as numbers are read
+pub fn integer(n: BigInt) -> Value {
+    match n.to_i32() {
+        Some(n) => Value::I32(n),
+        None => Value::BigInt(Arc::new(n)),
+    }
+}
+
+/// numer/denom,  as a ratio in its lowest terms;  or a BigInt,  if that's whole
+pub fn ratio(numer: BigInt, denom: BigInt) -> Value {
+    if denom.is_zero() {
+        return divide_by_zero();
+    }
+    ratio_value(BigRational::new(numer, denom))
+}
+
+fn ratio_value(ratio: BigRational) -> Value {
+    if ratio.is_integer() {
+        Value::BigInt(Arc::new(ratio.to_integer()))
+    } else {
+        Value::Ratio(Arc::new(ratio))
+    }
+}
+
+// a/b,  if it can be written out in full as a decimal
+fn exact_quotient(a: &BigDecimal, b: &BigDecimal) -> Result<BigDecimal, Value> {
+    if b.is_zero() {
+        return Err(divide_by_zero());
+    }
+    let quotient = a / b;
+    if &quotient * b == *a {
+        Ok(quotient)
+    } else {
+        Err(Value::Condition(
+            "Non-terminating decimal expansion; no exact representable decimal result.".into(),
+        ))
+    }
+}
+
+// d,  rounded in the math context if there is one
+fn rounded(d: BigDecimal) -> Value {
+    let rounded = match math_context() {
+        Some(context) => round(d, &context),
+        None => Ok(d),
+    };
+    match rounded {
+        Ok(d) => Value::BigDecimal(Arc::new(d)),
+        Err(condition) => condition,
+    }
+}
+
+// d,  to no more than context's precision
+fn round(d: BigDecimal, context: &MathContext) -> Result<BigDecimal, Value> {
+    if d.digits() <= context.precision.get() {
+        return Ok(d);
+    }
+    let rounded = d.with_precision_round(
+        context.precision,
+        context.rounding.unwrap_or(RoundingMode::Down),
+    );
+    match context.rounding {
+        None if rounded != d => Err(Value::Condition("Rounding necessary".into())),
+        _ => Ok(rounded),
+    }
+}
+
+// a/b,  rounded in context;  worked out to a couple more digits than its precision,  and
+// whether there'd be any more after them
+fn quotient_in(context: &MathContext, a: &BigDecimal, b: &BigDecimal) -> Result<BigDecimal, Value> {
+    if b.is_zero() {
+        return Err(divide_by_zero());
+    }
+    let (a_int, a_scale) = a.as_bigint_and_exponent();
+    let (b_int, b_scale) = b.as_bigint_and_exponent();
+    let shift = context.precision.get() + 2 + b.digits();
+    let numer = a_int * BigInt::from(10).pow(shift as u32);
+    let (quotient, remainder) = (&numer / &b_int, &numer % &b_int);
+    let scale = a_scale - b_scale + shift as i64;
+    if remainder.is_zero() {
+        // With no more zeros on the end than it'd have without a context
+        let preferred = a_scale - b_scale;
+        let exact = BigDecimal::new(quotient, scale).normalized();
+        let exact = match exact.fractional_digit_count() < preferred {
+            true => exact.with_scale(preferred),
+            false => exact,
+        };
+        return round(exact, context);
+    }
+    // A 1 on the end stands for what's left;  so it's never taken for exactly halfway
+    let quotient = match quotient.sign() {
+        Sign::Minus => quotient * 10 - 1,
+        _ => quotient * 10 + 1,
+    };
+    round(BigDecimal::new(quotient, scale + 1), context)
+}
+
+fn to_big_int(val: &Value) -> Result<BigInt, Value> {
+    match val {
+        Value::I32(i) => Ok(BigInt::from(*i)),
+        Value::BigInt(i) => Ok((**i).clone()),
+        _ => Err(not_a_number(val)),
+    }
+}
+
+fn to_ratio(val: &Value) -> Result<BigRational, Value> {
+    match val {
+        Value::Ratio(r) => Ok((**r).clone()),
+        _ => to_big_int(val).map(BigRational::from_integer),
+    }
+}
+
+fn to_big_decimal(val: &Value) -> Result<BigDecimal, Value> {
+    match val {
+        Value::BigDecimal(d) => Ok((**d).clone()),
+        Value::Ratio(r) => {
+            let (numer, denom) = (
+                BigDecimal::from(r.numer().clone()),
+                BigDecimal::from(r.denom().clone()),
+            );
+            match math_context() {
+                Some(context) => quotient_in(&context, &numer, &denom),
+                None => exact_quotient(&numer, &denom),
+            }
+        }
+        _ => to_big_int(val).map(BigDecimal::from),
+    }
+}
+
+/// val as an f64,  if it's a number
+pub fn to_f64(val: &Value) -> Option<f64> {
+    match val {
+        Value::I32(i) => Some(f64::from(*i)),
+        Value::BigInt(i) => i.to_f64(),
+        Value::Ratio(r) => r.to_f64(),
+        Value::BigDecimal(d) => d.to_f64(),
+        Value::F64(f) => Some(*f),
+        _ => None,
+    }
+}
+
+// Two numbers,  both taken as whichever type the later of them in the tower has
+enum Pair {
+    I32(i32, i32),
+    BigInt(BigInt, BigInt),
+    Ratio(BigRational, BigRational),
+    BigDecimal(BigDecimal, BigDecimal),
+    F64(f64, f64),
+}
+
+fn pair(a: &Value, b: &Value) -> Result<Pair, Value> {
+    let rank_a = rank(a).ok_or_else(|| not_a_number(a))?;
+    let rank_b = rank(b).ok_or_else(|| not_a_number(b))?;
+    Ok(match (a, b, rank_a.max(rank_b)) {
+        (Value::I32(a), Value::I32(b), _) => Pair::I32(*a, *b),
+        (_, _, Rank::I32) | (_, _, Rank::BigInt) => Pair::BigInt(to_big_int(a)?, to_big_int(b)?),
+        (_, _, Rank::Ratio) => Pair::Ratio(to_ratio(a)?, to_ratio(b)?),
+        (_, _, Rank::BigDecimal) => Pair::BigDecimal(to_big_decimal(a)?, to_big_decimal(b)?),
+        (_, _, Rank::F64) => Pair::F64(to_f64(a).unwrap(), to_f64(b).unwrap()),
+    })
+}
+
+// An operation,  as it's done on each type;  the i32 one gives None when it overflows,  and
+// is done again on BigInts
+struct Op {
+    i32: fn(i32, i32) -> Option<i32>,
+    big_int: fn(BigInt, BigInt) -> BigInt,
+    ratio: fn(BigRational, BigRational) -> BigRational,
+    big_decimal: fn(BigDecimal, BigDecimal) -> BigDecimal,
+    f64: fn(f64, f64) -> f64,
+}
+
+fn apply(op: &Op, a: &Value, b: &Value) -> Value {
+    match pair(a, b) {
+        Ok(Pair::I32(a, b)) => match (op.i32)(a, b) {
+            Some(n) => Value::I32(n),
+            None => Value::BigInt(Arc::new((op.big_int)(a.into(), b.into()))),
+        },
+        Ok(Pair::BigInt(a, b)) => Value::BigInt(Arc::new((op.big_int)(a, b))),
+        Ok(Pair::Ratio(a, b)) => ratio_value((op.ratio)(a, b)),
+        Ok(Pair::BigDecimal(a, b)) => rounded((op.big_decimal)(a, b)),
+        Ok(Pair::F64(a, b)) => Value::F64((op.f64)(a, b)),
+        Err(condition) => condition,
+    }
+}
+
+/// op over vals,  from init;  stopping at the first condition
+pub fn fold(init: Value, vals: &[Arc<Value>], op: fn(&Value, &Value) -> Value) -> Value {
+    let mut acc = init;
+    for val in vals.iter() {
+        acc = op(&acc, val);
+        if let Value::Condition(_) = acc {
+            break;
+        }
+    }
+    acc
+}
+
+pub fn add(a: &Value, b: &Value) -> Value {
+    let op = Op {
+        i32: i32::checked_add,
+        big_int: |a, b| a + b,
+        ratio: |a, b| a + b,
+        big_decimal: |a, b| a + b,
+        f64: |a, b| a + b,
+    };
+    apply(&op, a, b)
+}
+
+pub fn subtract(a: &Value, b: &Value) -> Value {
+    let op = Op {
+        i32: i32::checked_sub,
+        big_int: |a, b| a - b,
+        ratio: |a, b| a - b,
+        big_decimal: |a, b| a - b,
+        f64: |a, b| a - b,
+    };
+    apply(&op, a, b)
+}
+
+pub fn multiply(a: &Value, b: &Value) -> Value {
+    let op = Op {
+        i32: i32::checked_mul,
+        big_int: |a, b| a * b,
+        ratio: |a, b| a * b,
+        big_decimal: |a, b| a * b,
+        f64: |a, b| a * b,
+    };
+    apply(&op, a, b)
+}
+
+pub fn negate(a: &Value) -> Value {
+    subtract(&Value::I32(0), a)
+}
+
+// Whether the second of pair is an exact zero,  that nothing can be divided by
+fn is_exact_zero(pair: &Pair) -> bool {
+    match pair {
+        Pair::I32(_, b) => *b == 0,
+        Pair::BigInt(_, b) => b.is_zero(),
+        Pair::Ratio(_, b) => b.is_zero(),
+        Pair::BigDecimal(_, b) => b.is_zero(),
+        Pair::F64(..) => false,
+    }
+}
+
+/// a/b;  exactly,  unless either is an f64,  so dividing integers can give a ratio.  Or,
+/// with BigDecimals within with-precision,  rounded
+pub fn divide(a: &Value, b: &Value) -> Value {
+    match pair(a, b) {
+        Ok(pair) if is_exact_zero(&pair) => divide_by_zero(),
+        Ok(Pair::I32(a, b)) => match (a.checked_rem(b), a.checked_div(b)) {
+            (Some(0), Some(quotient)) => Value::I32(quotient),
+            _ => ratio(a.into(), b.into()),
+        },
+        Ok(Pair::BigInt(a, b)) => ratio(a, b),
+        Ok(Pair::Ratio(a, b)) => ratio_value(a / b),
+        Ok(Pair::BigDecimal(a, b)) => {
+            let quotient = match math_context() {
+                Some(context) => quotient_in(&context, &a, &b),
+                None => exact_quotient(&a, &b),
+            };
+            match quotient {
+                Ok(quotient) => Value::BigDecimal(Arc::new(quotient)),
+                Err(condition) => condition,
+            }
+        }
+        Ok(Pair::F64(a, b)) => Value::F64(a / b),
+        Err(condition) => condition,
+    }
+}
+
+/// a/b,  rounded towards zero
+pub fn quot(a: &Value, b: &Value) -> Value {
+    match pair(a, b) {
+        Ok(pair) if is_exact_zero(&pair) => divide_by_zero(),
+        Ok(Pair::I32(a, b)) => match a.checked_div(b) {
+            Some(quotient) => Value::I32(quotient),
+            None => Value::BigInt(Arc::new(BigInt::from(a) / b)),
+        },
+        Ok(Pair::BigInt(a, b)) => Value::BigInt(Arc::new(a / b)),
+        Ok(Pair::Ratio(a, b)) => Value::BigInt(Arc::new((a / b).to_integer())),
+        Ok(Pair::BigDecimal(a, b)) => Value::BigDecimal(Arc::new((a / b).with_scale(0))),
+        Ok(Pair::F64(_, 0.0)) => divide_by_zero(),
+        Ok(Pair::F64(a, b)) => Value::F64((a / b).trunc()),
+        Err(condition) => condition,
+    }
+}
+
+/// What's left of a after (quot a b);  with a's sign
+pub fn rem(a: &Value, b: &Value) -> Value {
+    match pair(a, b) {
+        Ok(pair) if is_exact_zero(&pair) => divide_by_zero(),
+        Ok(Pair::I32(a, b)) => Value::I32(a.checked_rem(b).unwrap_or(0)),
+        Ok(Pair::BigInt(a, b)) => Value::BigInt(Arc::new(a % b)),
+        Ok(Pair::Ratio(a, b)) => ratio_value(&a - &b * (&a / &b).trunc()),
+        Ok(Pair::BigDecimal(a, b)) => Value::BigDecimal(Arc::new(a % b)),
+        Ok(Pair::F64(_, 0.0)) => divide_by_zero(),
+        Ok(Pair::F64(a, b)) => Value::F64(a % b),
+        Err(condition) => condition,
+    }
+}
+
+/// a modulo b;  as rem,  but with b's sign
+pub fn modulo(a: &Value, b: &Value) -> Value {
+    let remainder = rem(a, b);
+    let sign = |val: &Value| compare(val, &Value::I32(0));
+    match (sign(&remainder), sign(a), sign(b)) {
+        (Ok(Some(Ordering::Equal)), _, _) => remainder,
+        (Ok(_), Ok(a_sign), Ok(b_sign)) if a_sign == b_sign => remainder,
+        (Ok(_), _, _) => add(&remainder, b),
+        (Err(_), _, _) => remainder,
+    }
+}
+
+/// |a|;  the same type of number as a,  unless it's an i32 whose negation doesn't fit
+pub fn abs(a: &Value) -> Value {
+    match (a, signum(a)) {
+        (Value::F64(f), _) => Value::F64(f.abs()),
+        (_, Value::I32(-1)) => negate(a),
+        (_, Value::Condition(message)) => Value::Condition(message),
+        _ => a.clone(),
+    }
+}
+
+/// -1,  0 or 1,  as a is negative,  zero or positive;  a float,  as Math/signum gives,  if
+/// a is one
+pub fn signum(a: &Value) -> Value {
+    match a {
+        // Keeps NaN,  and zero's sign
+        Value::F64(f) if f.is_nan() || *f == 0.0 => Value::F64(*f),
+        Value::F64(f) => Value::F64(f.signum()),
+        _ => match compare(a, &Value::I32(0)) {
+            Ok(Some(Ordering::Less)) => Value::I32(-1),
+            Ok(Some(Ordering::Greater)) => Value::I32(1),
+            Ok(_) => Value::I32(0),
+            Err(condition) => condition,
+        },
+    }
+}
+
+/// How a compares to b,  by value;  None if either is NaN
+pub fn compare(a: &Value, b: &Value) -> Result<Option<Ordering>, Value> {
+    Ok(match pair(a, b)? {
+        Pair::I32(a, b) => Some(a.cmp(&b)),
+        Pair::BigInt(a, b) => Some(a.cmp(&b)),
+        Pair::Ratio(a, b) => Some(a.cmp(&b)),
+        Pair::BigDecimal(a, b) => Some(a.cmp(&b)),
+        Pair::F64(a, b) => a.partial_cmp(&b),
+    })
+}
+
+// The kinds of number = tells apart
+#[derive(Debug, PartialEq)]
+enum Category {
+    Integer,
+    Ratio,
+    Decimal,
+    Float,
+}
+
+fn category(val: &Value) -> Option<Category> {
+    match rank(val)? {
+        Rank::I32 | Rank::BigInt => Some(Category::Integer),
+        Rank::Ratio => Some(Category::Ratio),
+        Rank::BigDecimal => Some(Category::Decimal),
+        Rank::F64 => Some(Category::Float),
+    }
+}
+
+/// Whether a and b are numbers of the same kind,  and equal;  as = sees them
+pub fn equal(a: &Value, b: &Value) -> bool {
+    match (category(a), category(b)) {
+        (Some(a_category), Some(b_category)) if a_category == b_category => equiv(a, b) == Ok(true),
+        _ => false,
+    }
+}
+
+/// Whether a and b are equal by value,  whatever their types;  as == sees them
+pub fn equiv(a: &Value, b: &Value) -> Result<bool, Value> {
+    compare(a, b).map(|ordering| ordering == Some(Ordering::Equal))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::numbers::*;
+    use crate::repl::Repl;
+
+    fn big(s: &str) -> Value {
+        Value::BigInt(Arc::new(s.parse().unwrap()))
+    }
+
+    #[test]
+    fn i32s_that_overflow_become_big_ints() {
+        assert_eq!(
+            big("10000000000"),
+            multiply(&Value::I32(100_000), &Value::I32(100_000))
+        );
+        assert_eq!(big("2147483648"), negate(&Value::I32(i32::MIN)));
+        assert_eq!(
+            Value::I32(i32::MAX),
+            subtract(&big("2147483648"), &Value::I32(1))
+        );
+    }
+
+    #[test]
+    fn dividing_integers_is_exact() {
+        assert_eq!(Value::I32(4), divide(&Value::I32(24), &Value::I32(6)));
+        assert_eq!(
+            "[1/3 1N 5/2 -2 0.5 \"Divide by zero\"]",
+            Repl::default()
+                .eval_all(&["[(/ 1 3) (+ 1/2 1/2) (* 5 1/2) (quot -7 3) (/ 1 2.0) (ex-message (try (/ 1 0) (catch Exception e e)))]"])
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn abs_and_signum_keep_the_kind_of_number() {
+        let result = Repl::default().eval_all(&[
+            "(def result [(abs -3) (abs -3N) (abs -3/4) (abs -1.50M) (abs -2.5) (abs 2) (abs -2147483648) (abs -0.0)])",
+            "[(signum -3) (signum 0N) (signum 3/4) (signum -1.50M) (signum -2.5) (signum 0.0) result]",
+        ]);
+        assert_eq!(
+            "[-1 0 1 -1 -1 0 [3 3N 3/4 1.50M 2.5 2 2147483648N 0]]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn equality_goes_by_kind_of_number() {
+        let result = Repl::default().eval_all(&[
+            "[(= 1 1N) (= 1 1.0) (== 1 1.0 1N) (= 1/2 0.5) (== 1/2 0.5 0.50M) (= 1.5M 1.50M) (contains? #{1N} 1)]",
+        ]);
+        assert_eq!("[true false true false true true true]", result.to_string());
+    }
+
+    mod tower_tests {
+        use crate::numbers::*;
+        use num_bigint::BigInt;
+        use proptest::prelude::*;
+
+        // Any exact number;  an i32,  most often one near where it'd overflow,  or a BigInt,
+        // ratio or BigDecimal
+        fn exact() -> impl Strategy<Value = Value> {
+            prop_oneof![
+                (-20..20).prop_map(Value::I32),
+                prop_oneof![Just(i32::MIN), Just(i32::MAX), Just(-1)].prop_map(Value::I32),
+                any::<i32>().prop_map(Value::I32),
+                any::<i64>().prop_map(|n| Value::BigInt(Arc::new(BigInt::from(n) * 3))),
+                (any::<i32>(), 1..1000i32).prop_map(|(n, d)| ratio(n.into(), d.into())),
+                (any::<i64>(), 0..6i64).prop_map(|(n, scale)| Value::BigDecimal(Arc::new(
+                    BigDecimal::new(n.into(), scale)
+                ))),
+            ]
+        }
+
+        fn is_zero(a: &Value) -> bool {
+            equiv(a, &Value::I32(0)) == Ok(true)
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(1024))]
+            #[test]
+            fn quot_times_div_plus_rem_is_num(a in exact(), b in exact()) {
+                prop_assume!(!is_zero(&b));
+                let (quotient, remainder) = (quot(&a, &b), rem(&a, &b));
+                // A ratio that never ends as a decimal can't meet a BigDecimal
+                if let Value::Condition(_) = quotient {
+                    prop_assert_eq!(&quotient, &remainder);
+                    return Ok(());
+                }
+                let back = add(&multiply(&quotient, &b), &remainder);
+                prop_assert_eq!(Ok(true), equiv(&a, &back), "{} {} gave {} {}", a, b, quotient, remainder);
+                // Numbers of the same kind come back as that kind
+                if category(&a) == category(&b) {
+                    prop_assert!(equal(&a, &back), "{} {} gave {}", a, b, back);
+                }
+                // rem has num's sign;  mod,  div's
+                let below = |a: &Value, b: &Value| compare(&abs(a), &abs(b)) == Ok(Some(Ordering::Less));
+                prop_assert!(is_zero(&remainder) || signum(&remainder) == signum(&a));
+                prop_assert!(below(&remainder, &b));
+                let modulus = modulo(&a, &b);
+                prop_assert!(is_zero(&modulus) || signum(&modulus) == signum(&b), "{} {} mod {}", a, b, modulus);
+                prop_assert!(below(&modulus, &b));
+                prop_assert_eq!(Ok(true), equiv(&rem(&subtract(&modulus, &remainder), &b), &Value::I32(0)));
+            }
+
+            #[test]
+            fn signum_times_abs_is_num(a in exact()) {
+                let abs = abs(&a);
+                prop_assert_eq!(category(&a), category(&abs));
+                prop_assert!(signum(&abs) != Value::I32(-1));
+                prop_assert_eq!(Ok(true), equiv(&a, &multiply(&signum(&a), &abs)));
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! The options builtins take;  :key val pairs after their other arguments,  or an opts map
+//! like {:limit 2}.  Each builtin names the keys it knows,  so any other,  often a
+//! misspelling,  is the same "Unknown option" condition whichever builtin it's given to;
+//! rather than being quietly ignored
+
+use crate::error_message;
+use crate::persistent_list_map::ToPersistentListMapIter;
+use crate::type_tag::TypeTag;
+use crate::value::Value;
+use std::sync::Arc;
+
+/// The options a builtin's been given;  each one of the keys it knows,  with its value
+#[derive(Debug, Default)]
+pub struct Opts {
+    given: Vec<(&'static str, Arc<Value>)>,
+}
+impl Opts {
+    /// The options in args,  :key val pairs,  given to name
+    pub fn from_pairs(
+        name: &str,
+        known: &[&'static str],
+        args: &[Arc<Value>],
+    ) -> Result<Opts, Value> {
+        if !args.len().is_multiple_of(2) {
+            return Err(Value::Condition(
+                format!("{} takes options;  keys each followed by a value", name).into(),
+            ));
+        }
+        let mut opts = Opts::default();
+        for pair in args.chunks(2) {
+            opts.push(name, known, &pair[0], &pair[1])?;
+        }
+        Ok(opts)
+    }
+    /// The options in opts,  a map given to name;  or none,  if it's nil
+    pub fn from_map(name: &str, known: &[&'static str], opts: &Arc<Value>) -> Result<Opts, Value> {
+        let entries: Vec<(Arc<Value>, Arc<Value>)> = match &**opts {
+            Value::Nil => vec![],
+            Value::PersistentListMap(map) => {
+                map.iter().map(|entry| (entry.key, entry.val)).collect()
+            }
+            Value::PersistentTreeMap(map) => {
+                map.iter().map(|entry| (entry.key, entry.val)).collect()
+            }
+            _ => return Err(error_message::type_mismatch(TypeTag::IPersistentMap, opts)),
+        };
+        let mut given = Opts::default();
+        for (key, val) in entries.iter() {
+            given.push(name, known, key, val)?;
+        }
+        Ok(given)
+    }
+    fn push(
+        &mut self,
+        name: &str,
+        known: &[&'static str],
+        key: &Arc<Value>,
+        val: &Arc<Value>,
+    ) -> Result<(), Value> {
+        let option = match &**key {
+            Value::Keyword(keyword) if !keyword.sym.has_ns() => known
+                .iter()
+                .find(|option| **option == keyword.sym.name.as_str()),
+            _ => None,
+        };
+        match option {
+            Some(option) => {
+                self.given.push((option, Arc::clone(val)));
+                Ok(())
+            }
+            None => Err(unknown(name, known, key)),
+        }
+    }
+    /// What key's given as,  if it's given;  the last of them,  if it's given more than once
+    pub fn get(&self, key: &str) -> Option<&Arc<Value>> {
+        self.given
+            .iter()
+            .rev()
+            .find(|(option, _)| *option == key)
+            .map(|(_, val)| val)
+    }
+    /// Each option given,  in the order it was
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &Arc<Value>)> {
+        self.given.iter().map(|(option, val)| (*option, val))
+    }
+}
+
+/// The condition for key given as an option to name,  which knows only known
+pub fn unknown(name: &str, known: &[&str], key: &Value) -> Value {
+    let expected = match known {
+        [] => "it takes none".to_string(),
+        [known] => format!("expected :{}", known),
+        [known @ .., last] => format!("expected :{} or :{}", known.join(",  :"), last),
+    };
+    Value::Condition(
+        format!(
+            "Unknown option {} to {};  {}",
+            key.to_string_explicit(),
+            name,
+            expected
+        )
+        .into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn unknown_options_are_the_same_condition_from_every_builtin() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.io :as io] '[clojure.string :as string] '[clojure.edn :as edn])",
+            "(defn message [f] (ex-message (try (f) (catch Exception e e))))",
+            "[(message (fn [] (slurp \"Cargo.toml\" :encodng \"UTF-8\"))) (message (fn [] (string/split \"a b\" #\" \" {:limt 1}))) (message (fn [] (sort-by - {:comparater >} [1 2]))) (message (fn [] (edn/read-string {:of 1} \"\"))) (message (fn [] (slurp \"Cargo.toml\" :append)))]",
+        ]);
+        assert_eq!(
+            "[\"Unknown option :encodng to slurp;  expected :append or :encoding\" \"Unknown option :limt to clojure.string/split;  expected :limit\" \"Unknown option :comparater to sort-by;  expected :comparator\" \"Unknown option :of to clojure.edn/read-string;  expected :readers,  :default,  :eof,  :max-depth or :max-elements\" \"slurp takes options;  keys each followed by a value\"]",
+            result.to_string()
+        );
+        let result = Repl::default().eval_all(&[
+            "[(clojure.string/split \"a b c\" #\" \" {:limit 2}) (sort-by - {:comparator >} [1 3 2]) (sort-by - {} [1 3 2]) (clojure.edn/read-string {:eof :done} \"\")]",
+        ]);
+        assert_eq!(
+            "[[\"a\" \"b c\"] (1 2 3) (3 2 1) :done]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Where printed output goes
+//!
+//! Printing builtins write through here rather than straight to the process' stdout, so
+//! that whoever drives evaluation on a thread can redirect it;  an nREPL session, for
+//! instance, sends it back to the client that asked for the evaluation.  With no sink
+//! installed, output goes to stdout and stderr as usual
+//!
+//! On a terminal,  stderr can also have a status line;  a progress bar,  say,  redrawn in
+//! place.  Other output clears it first,  and it's drawn again after the next full line
+use std::cell::RefCell;
+use std::io;
+use std::io::{IsTerminal, Write};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stream {
+    Out,
+    Err,
+}
+
+// Send,  so what a future prints can go where its maker's output does
+pub type Sink = Arc<dyn Fn(Stream, &str) + Send + Sync>;
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+    static STATUS: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Moves back to the start of the line,  and clears it
+const CLEAR_LINE: &str = "\r\u{1b}[K";
+
+/// Redirects this thread's output to sink (or back to stdout/stderr, with None),
+/// returning whatever was installed before so it can be restored
+pub fn install(sink: Option<Sink>) -> Option<Sink> {
+    SINK.with(|installed| installed.replace(sink))
+}
+
+/// The sink this thread's output goes to,  if it's been redirected
+pub fn installed() -> Option<Sink> {
+    SINK.with(|installed| installed.borrow().clone())
+}
+
+pub fn write(stream: Stream, text: &str) {
+    // Cloned out, so the sink itself is free to print
+    if let Some(sink) = installed() {
+        return sink(stream, text);
+    }
+    let status = STATUS.with(|status| status.borrow().clone());
+    if status.is_some() {
+        eprint!("{}", CLEAR_LINE);
+    }
+    match stream {
+        Stream::Out => print!("{}", text),
+        Stream::Err => eprint!("{}", text),
+    }
+    if let Some(status) = status.filter(|_| text.ends_with('\n')) {
+        let _ = io::stdout().flush();
+        eprint!("{}", status);
+    }
+}
+
+/// Shows text as stderr's status line,  in place of the one before;  with None,  clears it.
+/// Only for when stderr is a terminal
+pub fn set_status(text: Option<&str>) {
+    STATUS.with(|status| *status.borrow_mut() = text.map(String::from));
+    eprint!("{}{}", CLEAR_LINE, text.unwrap_or_default());
+    let _ = io::stderr().flush();
+}
+
+pub fn flush(stream: Stream) {
+    // Sinks don't buffer
+    let installed = SINK.with(|installed| installed.borrow().is_some());
+    if !installed {
+        let _ = match stream {
+            Stream::Out => io::stdout().flush(),
+            Stream::Err => io::stderr().flush(),
+        };
+    }
+}
+
+/// Whether stream goes to a terminal;  never so with a sink installed
+pub fn is_terminal(stream: Stream) -> bool {
+    let installed = SINK.with(|installed| installed.borrow().is_some());
+    !installed
+        && match stream {
+            Stream::Out => io::stdout().is_terminal(),
+            Stream::Err => io::stderr().is_terminal(),
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output;
+    use crate::output::Stream;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn installed_sink_receives_output() {
+        let captured = Arc::new(Mutex::new(vec![]));
+        let sink_captured = Arc::clone(&captured);
+        let previous = output::install(Some(Arc::new(move |stream, text: &str| {
+            sink_captured
+                .lock()
+                .unwrap()
+                .push((stream, text.to_string()))
+        })));
+        output::write(Stream::Out, "hello");
+        output::write(Stream::Err, "oops");
+        output::install(previous);
+
+        assert_eq!(
+            vec![
+                (Stream::Out, String::from("hello")),
+                (Stream::Err, String::from("oops"))
+            ],
+            *captured.lock().unwrap()
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Regexes;  as read from #"pattern",  or made by re-pattern,  and matched by re-find and
+//! the like with the regex crate
+//!
+//! A pattern is compiled as the regex crate reads it,  not as java.util.regex does;  close,
+//! but without backreferences or lookaround
+use crate::error_message;
+use crate::persistent_vector::PersistentVector;
+use crate::shared_str::SharedStr;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use regex::{Captures, Regex};
+use std::sync::Arc;
+
+/// The regex pattern compiles to;  or a condition saying what's wrong with it
+pub fn compile(pattern: &str) -> Value {
+    match Regex::new(pattern) {
+        Ok(regex) => Value::Regex(Arc::new(regex)),
+        Err(e) => Value::Condition(format!("Invalid regex #\"{}\": {}", pattern, e).into()),
+    }
+}
+
+/// The regex and string args of a regex fn,  ie (re-find re s)
+pub fn regex_and_string(re: &Value, s: &Value) -> Result<(Arc<Regex>, SharedStr), Value> {
+    match (re, s) {
+        (Value::Regex(regex), Value::String(s)) => Ok((Arc::clone(regex), SharedStr::clone(s))),
+        (Value::Regex(_), _) => Err(error_message::type_mismatch(TypeTag::String, s)),
+        _ => Err(error_message::type_mismatch(TypeTag::Regex, re)),
+    }
+}
+
+/// What a match is given back as;  the string matched,  if the regex has no groups,  and
+/// otherwise a vector of it and each group's match (nil for those that didn't take part)
+pub fn match_value(captures: &Captures) -> Value {
+    if captures.len() == 1 {
+        return Value::String(captures[0].into());
+    }
+    captures
+        .iter()
+        .map(|group| match group {
+            Some(group) => Value::String(group.as_str().into()).to_rc_value(),
+            None => Arc::new(Value::Nil),
+        })
+        .collect::<PersistentVector>()
+        .to_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn regexes_are_read_and_printed_as_literals() {
+        let result = Repl::default().eval_all(&[
+            "[#\"a\\d+\" #\"say \\\"hi\\\"\" (re-pattern \"x|y\") (= #\"a\" #\"a\") (class #\"a\")]",
+        ]);
+        assert_eq!(
+            "[#\"a\\d+\" #\"say \\\"hi\\\"\" #\"x|y\" false rust.regex.Regex]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn bad_regexes_are_conditions() {
+        let result =
+            Repl::default().eval_all(&["(ex-message (try #\"a(\" (catch Exception e e)))"]);
+        assert!(result.to_string().starts_with("Invalid regex #\"a(\""));
+    }
+}
@@ -0,0 +1,155 @@
+//! A persistent set, as read from #{..}
+//!
+//! Its values are the keys of a hash array mapped trie (see hamt.rs);  so a set conj'd or
+//! disj'd shares all but a path of it with the set it came from,  and building one up
+//! copies nothing.  Values are hashed with SipHash keyed with a seed picked once per run,
+//! so strings read off the network can't be chosen to all collide and make each lookup a
+//! scan.  So the order a set prints in changes from run to run;  with *print-sorted* it's
+//! sorted,  see print_order
+use crate::hamt::Hamt;
+use crate::print_order;
+use crate::seqable::Seqable;
+use crate::value::{self, ToValue, Value};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Default)]
+pub struct PersistentHashSet {
+    vals: Hamt<()>,
+}
+impl PersistentHashSet {
+    pub fn empty() -> PersistentHashSet {
+        PersistentHashSet::default()
+    }
+    /// Returns a new set, also containing val
+    pub fn conj(&self, val: Arc<Value>) -> PersistentHashSet {
+        let mut set = self.clone();
+        set.conj_in_place(val);
+        set
+    }
+    /// Adds val to this set,  rather than to a new one;  for building a set up,  as with a
+    /// transient
+    pub fn conj_in_place(&mut self, val: Arc<Value>) {
+        self.vals.insert(val, ());
+    }
+    /// Returns a new set, without val
+    pub fn disj(&self, val: &Value) -> PersistentHashSet {
+        let mut set = self.clone();
+        set.vals.remove(val);
+        set
+    }
+    pub fn contains(&self, val: &Value) -> bool {
+        self.vals.contains_key(val)
+    }
+    pub fn len(&self) -> usize {
+        self.vals.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.vals.is_empty()
+    }
+    pub fn is_subset(&self, other: &PersistentHashSet) -> bool {
+        self.len() <= other.len() && self.vals.iter().all(|(val, _)| other.contains(val))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = Arc<Value>> + '_ {
+        self.vals.iter().map(|(val, _)| Arc::clone(val))
+    }
+}
+impl Seqable for PersistentHashSet {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(self.iter())
+    }
+}
+impl PartialEq for PersistentHashSet {
+    fn eq(&self, other: &PersistentHashSet) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+impl Hash for PersistentHashSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        value::hash_unordered(self.vals.iter().map(|(val, _)| val), state)
+    }
+}
+impl FromIterator<Arc<Value>> for PersistentHashSet {
+    fn from_iter<I: IntoIterator<Item = Arc<Value>>>(iter: I) -> Self {
+        PersistentHashSet {
+            vals: iter.into_iter().map(|val| (val, ())).collect(),
+        }
+    }
+}
+impl ToValue for PersistentHashSet {
+    fn to_value(&self) -> Value {
+        Value::PersistentHashSet(Arc::new(self.clone()))
+    }
+}
+impl fmt::Display for PersistentHashSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut vals = self
+            .vals
+            .iter()
+            .map(|(val, _)| val)
+            .collect::<Vec<&Arc<Value>>>();
+        print_order::sort(&mut vals, |val| val);
+        let str = vals
+            .iter()
+            .map(|rc_arg| rc_arg.to_string_explicit())
+            .collect::<Vec<std::string::String>>()
+            .join(" ");
+        write!(f, "#{{{}}}", str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent_hash_set::PersistentHashSet;
+    use crate::value::{ToValue, Value};
+    use std::sync::Arc;
+
+    fn set(vals: &[i32]) -> PersistentHashSet {
+        vals.iter().map(|i| i.to_rc_value()).collect()
+    }
+
+    #[test]
+    fn conj_and_disj_leave_the_original_alone() {
+        let original = set(&[1, 2]);
+        let conjed = original.conj(Arc::new(Value::I32(3)));
+        let disjed = original.disj(&Value::I32(1));
+        assert_eq!(set(&[1, 2]), original);
+        assert_eq!(set(&[1, 2, 3]), conjed);
+        assert_eq!(set(&[2]), disjed);
+    }
+
+    #[test]
+    fn duplicates_are_dropped() {
+        assert_eq!(2, set(&[1, 2, 1, 2]).len());
+    }
+
+    #[test]
+    fn equal_sets_hash_alike() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        let hash = |set: &PersistentHashSet| {
+            let mut hasher = DefaultHasher::new();
+            set.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&set(&[1, 2, 3])), hash(&set(&[3, 2, 1])));
+    }
+
+    #[test]
+    fn displays_like_clojure() {
+        assert_eq!("#{}", PersistentHashSet::empty().to_string());
+        assert_eq!("#{\"a\"}", set(&[]).conj("a".to_rc_value()).to_string());
+    }
+
+    #[test]
+    fn big_sets_are_built_without_copying_them_at_each_step() {
+        let result = crate::repl::Repl::default().eval_all(&[
+            "(def big (into #{} (range 200000)))",
+            "(def bigger (reduce conj big (range 200000 300000)))",
+            "[(count big) (count bigger) (count (disj bigger 1)) (contains? big 250000) (count (set (range 100000)))]",
+        ]);
+        assert_eq!("[200000 300000 299999 false 100000]", result.to_string());
+    }
+}
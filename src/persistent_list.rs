@@ -1,15 +1,16 @@
 use std::fmt;
 use std::fmt::Debug;
 use std::iter::FromIterator;
-use std::rc::Rc;
+use std::sync::Arc;
 
-use crate::value::{ToValue, Value};
-use std::hash::Hash;
+use crate::seqable::Seqable;
+use crate::value::{self, ToValue, Value};
+use std::hash::{Hash, Hasher};
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PersistentList {
     // @TODO refactor i32 (our len) into a usize
-    Cons(Rc<Value>, Rc<PersistentList>, i32),
+    Cons(Arc<Value>, Arc<PersistentList>, i32),
     Empty,
 }
 // @TODO definitely don't do this
@@ -17,17 +18,27 @@ pub enum PersistentList {
 struct EmptyHash {}
 
 use crate::persistent_list::PersistentList::{Cons, Empty};
-pub fn cons_rc(head: Rc<Value>, tail: Rc<PersistentList>) -> PersistentList {
+pub fn cons_rc(head: Arc<Value>, tail: Arc<PersistentList>) -> PersistentList {
     if let Cons(_, _, old_count) = &*tail {
-        Cons(Rc::clone(&head), Rc::clone(&tail), old_count + 1)
+        Cons(Arc::clone(&head), Arc::clone(&tail), old_count + 1)
     } else {
-        Cons(Rc::clone(&head), Rc::clone(&tail), 1)
+        Cons(Arc::clone(&head), Arc::clone(&tail), 1)
     }
 }
 
 /// For building a 'top level' list, that is the first to reference (and own) all of its values
 pub fn cons(head: Value, tail: PersistentList) -> PersistentList {
-    cons_rc(Rc::new(head), Rc::new(tail))
+    cons_rc(Arc::new(head), Arc::new(tail))
+}
+impl Seqable for PersistentList {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(PersistentList::iter(&Arc::new(self.clone())))
+    }
+}
+impl Hash for PersistentList {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        value::hash_ordered(PersistentList::iter(&Arc::new(self.clone())), state)
+    }
 }
 impl PersistentList {
     pub fn len(&self) -> i32 {
@@ -61,7 +72,7 @@ impl ToPersistentList for Vec<&Value> {
             .collect::<PersistentList>()
     }
 }
-impl ToPersistentList for Vec<Rc<Value>> {
+impl ToPersistentList for Vec<Arc<Value>> {
     fn into_list(self) -> PersistentList {
         self.into_iter().collect::<PersistentList>()
     }
@@ -93,52 +104,52 @@ impl fmt::Display for PersistentList {
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 pub trait ToPersistentListIter {
     fn iter(&self) -> PersistentListIter;
-    fn nth(&self, ind: usize) -> Rc<Value> {
+    fn nth(&self, ind: usize) -> Arc<Value> {
         match self.iter().nth(ind) {
             Some(rc_val) => rc_val,
-            None => Rc::new(Value::Nil),
+            None => Arc::new(Value::Nil),
         }
     }
 }
-impl ToPersistentListIter for Rc<PersistentList> {
+impl ToPersistentListIter for Arc<PersistentList> {
     fn iter(&self) -> PersistentListIter {
         PersistentListIter {
-            list: Rc::clone(self),
+            list: Arc::clone(self),
         }
     }
 }
-impl ToPersistentListIter for &Rc<PersistentList> {
+impl ToPersistentListIter for &Arc<PersistentList> {
     fn iter(&self) -> PersistentListIter {
         PersistentListIter {
-            list: Rc::clone(self),
+            list: Arc::clone(self),
         }
     }
 }
 impl PersistentList {
-    /// Deprecated; use ToPersistentListIter trait to call iter on Rc<PersistentList> directly intead
-    pub fn iter(rc_self: &Rc<PersistentList>) -> PersistentListIter {
+    /// Deprecated; use ToPersistentListIter trait to call iter on Arc<PersistentList> directly intead
+    pub fn iter(rc_self: &Arc<PersistentList>) -> PersistentListIter {
         PersistentListIter {
-            list: Rc::clone(rc_self),
+            list: Arc::clone(rc_self),
         }
     }
 }
 pub struct PersistentListIter {
-    list: Rc<PersistentList>,
+    list: Arc<PersistentList>,
 }
 impl Iterator for PersistentListIter {
-    type Item = Rc<Value>;
+    type Item = Arc<Value>;
     fn next(&mut self) -> Option<Self::Item> {
         match &*(self.list.clone()) {
             Cons(first, rest, _) => {
-                self.list = Rc::clone(&rest);
-                Some(Rc::clone(&first))
+                self.list = Arc::clone(rest);
+                Some(Arc::clone(first))
             }
             _ => None,
         }
     }
 }
-impl FromIterator<Rc<Value>> for PersistentList {
-    fn from_iter<I: IntoIterator<Item = Rc<Value>>>(iter: I) -> Self {
+impl FromIterator<Arc<Value>> for PersistentList {
+    fn from_iter<I: IntoIterator<Item = Arc<Value>>>(iter: I) -> Self {
         let mut retval = PersistentList::Empty;
         // @TODO see if we can directly loop through our original iter backwards, and avoid
         // dumping into this vector just to loop through again backwards
@@ -150,7 +161,7 @@ impl FromIterator<Rc<Value>> for PersistentList {
         }
         for i in coll_as_vec.iter().rev() {
             count += 1;
-            retval = Cons(Rc::clone(i), Rc::new(retval), count);
+            retval = Cons(Arc::clone(i), Arc::new(retval), count);
         }
 
         retval
@@ -171,9 +182,9 @@ mod tests {
             cons(2_i32.to_value(), cons(3_i32.to_value(), Empty)),
         );
         let plist3 = Empty;
-        let plist4 = cons_rc(4_i32.to_rc_value(), Rc::new(plist2.clone()));
-        let rc_plist4 = Rc::new(plist4.clone());
-        let plist5 = cons_rc(5_i32.to_rc_value(), Rc::clone(&rc_plist4));
+        let plist4 = cons_rc(4_i32.to_rc_value(), Arc::new(plist2.clone()));
+        let rc_plist4 = Arc::new(plist4.clone());
+        let plist5 = cons_rc(5_i32.to_rc_value(), Arc::clone(&rc_plist4));
 
         let vec6 = vec![
             1_i32.to_rc_value(),
@@ -184,9 +195,9 @@ mod tests {
             6_i32.to_rc_value(),
         ];
         let plist6 = vec6.into_iter().collect::<PersistentList>();
-        let plist6_2 = Rc::new(plist6.clone())
+        let plist6_2 = Arc::new(plist6.clone())
             .iter()
-            .map(|rc_val| Rc::clone(&rc_val))
+            .map(|rc_val| Arc::clone(&rc_val))
             .collect::<PersistentList>();
 
         assert_eq!(plist.len(), 2);
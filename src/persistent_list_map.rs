@@ -1,91 +1,173 @@
-//! For very small Persistent maps;  this is a persistent map implemented upon
-//! persistent lists, which already can share structure.  In this case, each
-//! element of the list is a key-value pair (so this is very similar to implementing
-//! a persistent associative structure in Elisp with alists)
+//! Persistent maps;  as read from {..},  and made by assoc and into
 //!
-//!   (MapEntry :a 1)
-//!       /           \
-//!      /            \
-//!(MapEntry :b 2)    (MapEntry :b 3)
-//!   /               \
-//! a                 b
-//! -------------------
-//! a => {:a 1 :b 2}
-//! b => {:a 1 :b 3}
+//! Up to 8 entries,  a map is an array of them,  copied on assoc (as Clojure's array-maps
+//! are);  it iterates,  and so prints,  in the order its keys were first added,  one assoc'd
+//! again keeping its place,  with its new value.  So {:b 1 :a 2} prints as it's written,  and
+//! so do the small option maps and test fixtures whose printed form people rely on
+//!
+//! Past 8 entries it's a hash array mapped trie (see hamt.rs),  as in Clojure;  found in,
+//! and assoc'd to,  in the same time however big,  and iterated in an order that changes
+//! from run to run.  With *print-sorted*,  either prints sorted,  see print_order
 
+use crate::hamt::{self, Hamt};
 use crate::maps::MapEntry;
-use crate::value::Value;
+use crate::print_order;
+use crate::seqable::Seqable;
+use crate::value::{self, Value};
 
-use std::collections::HashMap;
 use std::convert::From;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
-use std::rc::Rc;
+use std::slice;
+use std::sync::Arc;
+
+// The most entries a map keeps in insertion order,  before it's made a hash map
+const ARRAY_MAP_MAX: usize = 8;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone, Default)]
 pub enum PersistentListMap {
-    Map(Rc<PersistentListMap>, MapEntry),
+    /// Up to 8 entries,  in the order their keys were first added
+    Array(Vec<MapEntry>),
+    /// More,  by their keys' hashes
+    Hashed(Hamt<Arc<Value>>),
+    #[default]
     Empty,
 }
 // Again, only using strange IBlah convention to reflect the Clojure base
 // @TODO really though .. just rethink this
 /// A PersistentListMap.
 pub trait IPersistentMap {
-    fn get(&self, key: &Rc<Value>) -> Rc<Value>;
-    fn assoc(&self, key: Rc<Value>, value: Rc<Value>) -> Self;
+    fn get(&self, key: &Arc<Value>) -> Arc<Value>;
+    fn assoc(&self, key: Arc<Value>, value: Arc<Value>) -> Self;
 }
 impl IPersistentMap for PersistentListMap {
     // @TODO make fn of ILookup
-    fn get(&self, key: &Rc<Value>) -> Rc<Value> {
-        match self {
-            PersistentListMap::Map(parent, entry) => {
-                if entry.key == *key {
-                    return Rc::clone(&entry.val);
-                }
-                parent.get(key)
-            }
-            PersistentListMap::Empty => Rc::new(Value::Nil),
+    fn get(&self, key: &Arc<Value>) -> Arc<Value> {
+        match self.get_entry(key) {
+            Some(entry) => entry.val,
+            None => Arc::new(Value::Nil),
         }
     }
-    fn assoc(&self, key: Rc<Value>, val: Rc<Value>) -> PersistentListMap {
-        PersistentListMap::Map(Rc::new(self.clone()), MapEntry { key, val })
+    fn assoc(&self, key: Arc<Value>, val: Arc<Value>) -> PersistentListMap {
+        let mut map = self.clone();
+        map.assoc_in_place(key, val);
+        map
     }
 }
 
-impl IPersistentMap for Rc<PersistentListMap> {
+impl IPersistentMap for Arc<PersistentListMap> {
     // @TODO make fn of ILookup
-    fn get(&self, key: &Rc<Value>) -> Rc<Value> {
-        match &**self {
-            PersistentListMap::Map(parent, entry) => {
-                if entry.key == *key {
-                    return Rc::clone(&entry.val);
+    fn get(&self, key: &Arc<Value>) -> Arc<Value> {
+        (**self).get(key)
+    }
+    fn assoc(&self, key: Arc<Value>, val: Arc<Value>) -> Arc<PersistentListMap> {
+        Arc::new((**self).assoc(key, val))
+    }
+}
+
+impl PersistentListMap {
+    /// The entry for key,  if it's there;  as it may be,  mapped to nil
+    pub fn get_entry(&self, key: &Value) -> Option<MapEntry> {
+        match self {
+            PersistentListMap::Array(entries) => {
+                entries.iter().find(|entry| *entry.key == *key).cloned()
+            }
+            PersistentListMap::Hashed(hamt) => hamt.get_entry(key).map(|(key, val)| MapEntry {
+                key: Arc::clone(key),
+                val: Arc::clone(val),
+            }),
+            PersistentListMap::Empty => None,
+        }
+    }
+    pub fn contains_key(&self, key: &Value) -> bool {
+        match self {
+            PersistentListMap::Hashed(hamt) => hamt.contains_key(key),
+            _ => self.get_entry(key).is_some(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        match self {
+            PersistentListMap::Array(entries) => entries.len(),
+            PersistentListMap::Hashed(hamt) => hamt.len(),
+            PersistentListMap::Empty => 0,
+        }
+    }
+    /// Has key map to val in this map,  rather than in a new one;  for building a map up,
+    /// as with a transient
+    pub fn assoc_in_place(&mut self, key: Arc<Value>, val: Arc<Value>) {
+        match self {
+            PersistentListMap::Array(entries) => {
+                match entries.iter().position(|entry| entry.key == key) {
+                    Some(at) => entries[at].val = val,
+                    None if entries.len() < ARRAY_MAP_MAX => entries.push(MapEntry { key, val }),
+                    None => {
+                        let mut hamt = entries
+                            .drain(..)
+                            .map(|entry| (entry.key, entry.val))
+                            .collect::<Hamt<Arc<Value>>>();
+                        hamt.insert(key, val);
+                        *self = PersistentListMap::Hashed(hamt);
+                    }
                 }
-                parent.get(key)
             }
-            PersistentListMap::Empty => Rc::new(Value::Nil),
+            PersistentListMap::Hashed(hamt) => {
+                hamt.insert(key, val);
+            }
+            PersistentListMap::Empty => {
+                *self = PersistentListMap::Array(vec![MapEntry { key, val }])
+            }
         }
     }
-    fn assoc(&self, key: Rc<Value>, val: Rc<Value>) -> Rc<PersistentListMap> {
-        Rc::new(PersistentListMap::Map(
-            Rc::clone(self),
-            MapEntry { key, val },
-        ))
+    /// Takes key out of this map,  rather than a new one;  a hashed map left with 8 entries
+    /// or fewer is made an array again,  in whatever order the trie gives them
+    pub fn dissoc_in_place(&mut self, key: &Value) {
+        match self {
+            PersistentListMap::Array(entries) => {
+                entries.retain(|entry| *entry.key != *key);
+                if entries.is_empty() {
+                    *self = PersistentListMap::Empty;
+                }
+            }
+            PersistentListMap::Hashed(hamt) => {
+                if hamt.remove(key) && hamt.len() <= ARRAY_MAP_MAX {
+                    *self = PersistentListMap::Array(
+                        hamt.iter()
+                            .map(|(key, val)| MapEntry {
+                                key: Arc::clone(key),
+                                val: Arc::clone(val),
+                            })
+                            .collect(),
+                    );
+                }
+            }
+            PersistentListMap::Empty => {}
+        }
     }
 }
 
-// The purpose of these functions are no longer to implement conversion,
-// but to give us a cleaner way to invoke it
-pub trait ToPersistentListMap {
-    fn into_list_map(self) -> PersistentListMap;
+impl Seqable for PersistentListMap {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(self.iter().map(|entry| entry.to_vector_value()))
+    }
+}
+// Nor does the order entries were added in count
+impl PartialEq for PersistentListMap {
+    fn eq(&self, other: &PersistentListMap) -> bool {
+        self.len() == other.len()
+            && self.iter().all(|entry| {
+                other
+                    .get_entry(&entry.key)
+                    .is_some_and(|other_entry| other_entry.val == entry.val)
+            })
+    }
 }
-impl<T> ToPersistentListMap for T
-where
-    T: Into<PersistentListMap>,
-{
-    fn into_list_map(self) -> PersistentListMap {
-        Into::<PersistentListMap>::into(self)
+impl Hash for PersistentListMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        value::hash_unordered(self.iter(), state)
     }
 }
+
 impl From<Vec<MapEntry>> for PersistentListMap {
     fn from(item: Vec<MapEntry>) -> Self {
         item.into_iter().collect::<PersistentListMap>()
@@ -95,41 +177,49 @@ impl From<Vec<MapEntry>> for PersistentListMap {
 // Iterating
 //
 ////////////////////////////////////////////////////////////////////////////////////////////////////
-pub struct PersistentListMapIter {
-    node: Rc<PersistentListMap>,
-    seen: HashMap<Rc<Value>, bool>,
+/// A map's entries;  gone through where they are,  without collecting them first
+pub struct PersistentListMapIter<'a> {
+    entries: Entries<'a>,
+}
+enum Entries<'a> {
+    Array(slice::Iter<'a, MapEntry>),
+    Hashed(hamt::Iter<'a, Arc<Value>>),
 }
 pub trait ToPersistentListMapIter {
-    fn iter(&self) -> PersistentListMapIter;
+    fn iter(&self) -> PersistentListMapIter<'_>;
 }
-impl Iterator for PersistentListMapIter {
+impl Iterator for PersistentListMapIter<'_> {
     type Item = MapEntry;
     fn next(&mut self) -> Option<Self::Item> {
-        match &*(Rc::clone(&self.node)) {
-            PersistentListMap::Map(parent, mapentry) => {
-                self.node = Rc::clone(parent);
-                if self.seen.contains_key(&mapentry.key) {
-                    return self.next();
-                }
-                self.seen.insert(mapentry.key.clone(), true);
-                Some(mapentry.clone())
-            }
-            PersistentListMap::Empty => None,
+        match &mut self.entries {
+            Entries::Array(entries) => entries.next().cloned(),
+            Entries::Hashed(entries) => entries.next().map(|(key, val)| MapEntry {
+                key: Arc::clone(key),
+                val: Arc::clone(val),
+            }),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.entries {
+            Entries::Array(entries) => entries.size_hint(),
+            Entries::Hashed(entries) => entries.size_hint(),
         }
     }
 }
 
-impl ToPersistentListMapIter for Rc<PersistentListMap> {
-    fn iter(&self) -> PersistentListMapIter {
-        PersistentListMapIter {
-            node: Rc::clone(self),
-            seen: HashMap::new(),
-        }
+impl ToPersistentListMapIter for Arc<PersistentListMap> {
+    fn iter(&self) -> PersistentListMapIter<'_> {
+        (**self).iter()
     }
 }
 impl ToPersistentListMapIter for PersistentListMap {
-    fn iter(&self) -> PersistentListMapIter {
-        Rc::new(self.clone()).iter()
+    fn iter(&self) -> PersistentListMapIter<'_> {
+        let entries = match self {
+            PersistentListMap::Array(entries) => Entries::Array(entries.iter()),
+            PersistentListMap::Hashed(hamt) => Entries::Hashed(hamt.iter()),
+            PersistentListMap::Empty => Entries::Array([].iter()),
+        };
+        PersistentListMapIter { entries }
     }
 }
 
@@ -138,7 +228,7 @@ impl FromIterator<MapEntry> for PersistentListMap {
         let mut map_so_far = PersistentListMap::Empty;
 
         for i in iter {
-            map_so_far = PersistentListMap::Map(Rc::new(map_so_far), i.clone());
+            map_so_far.assoc_in_place(i.key, i.val);
         }
         map_so_far
     }
@@ -152,7 +242,9 @@ impl fmt::Display for PersistentListMap {
         let mut as_str = String::from("{");
         let mut first_loop = true;
 
-        for mapentry in self.iter() {
+        let mut entries = self.iter().collect::<Vec<MapEntry>>();
+        print_order::sort(&mut entries, |entry| &entry.key);
+        for mapentry in entries {
             if !first_loop {
                 as_str.push_str(", ");
             }
@@ -202,4 +294,45 @@ mod tests {
         println!("{}", map3);
         println!("{}", map4);
     }
+
+    #[test]
+    fn maps_iterate_in_the_order_their_keys_were_first_added() {
+        let entry = |key: &str, val: i32| MapEntry {
+            key: Symbol::intern(key).to_rc_value(),
+            val: val.to_rc_value(),
+        };
+        let map = vec![entry("b", 1), entry("a", 2), entry("c", 3)]
+            .into_iter()
+            .collect::<PersistentListMap>()
+            .assoc(Symbol::intern("a").to_rc_value(), 4_i32.to_rc_value())
+            .assoc(Symbol::intern("d").to_rc_value(), 5_i32.to_rc_value());
+        assert_eq!("{b 1, a 4, c 3, d 5}", map.to_string());
+    }
+
+    #[test]
+    fn literal_maps_print_as_they_are_written() {
+        let result = crate::repl::Repl::default().eval_all(&[
+            "[{:z 1 :y 2 :x 3} (assoc {:b 1 :a 2} :b 3 :c 4) (map first {:h 1 :g 2 :f 3 :e 4 :d 5 :c 6 :b 7 :a 8})]",
+        ]);
+        assert_eq!(
+            "[{:z 1, :y 2, :x 3} {:b 3, :a 2, :c 4} (:h :g :f :e :d :c :b :a)]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn maps_past_eight_entries_are_hashed_and_still_find_everything() {
+        let map = (0..100_i32).fold(PersistentListMap::Empty, |map, n| {
+            map.assoc(n.to_rc_value(), (n * 2).to_rc_value())
+        });
+        assert!(matches!(map, PersistentListMap::Hashed(_)));
+        assert_eq!(100, map.len());
+        assert_eq!(100, map.iter().count());
+        assert_eq!(Value::I32(84), *map.get(&42_i32.to_rc_value()));
+        assert_eq!(Value::Nil, *map.get(&100_i32.to_rc_value()));
+        let result = crate::repl::Repl::default().eval_all(&[
+            "(let [m (into {} (map (fn [n] [n n]) (range 20)))] [(count m) (get m 15) (count (assoc m 3 :x 20 :y)) (= m (into {} m))])",
+        ]);
+        assert_eq!("[20 15 21 true]", result.to_string());
+    }
 }
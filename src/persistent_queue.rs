@@ -0,0 +1,135 @@
+//! A persistent first in, first out queue,  as clojure.lang.PersistentQueue/EMPTY or
+//! #queue [..] make
+//!
+//! As in Clojure,  a front list that's taken from,  and a rear vector that's added to;
+//! when the front runs out,  the rear becomes the next front.  So conj, peek and pop take
+//! no more than constant time,  on average
+use crate::persistent_list::{PersistentList, ToPersistentList, ToPersistentListIter};
+use crate::persistent_vector::PersistentVector;
+use crate::seqable::Seqable;
+use crate::value::{self, ToValue, Value};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct PersistentQueue {
+    front: Arc<PersistentList>,
+    rear: PersistentVector,
+}
+impl PersistentQueue {
+    pub fn empty() -> PersistentQueue {
+        PersistentQueue {
+            front: Arc::new(PersistentList::Empty),
+            rear: PersistentVector::empty(),
+        }
+    }
+    /// Returns a new queue,  with val at the back
+    pub fn conj(&self, val: Arc<Value>) -> PersistentQueue {
+        if self.is_empty() {
+            return PersistentQueue {
+                front: Arc::new(vec![val].into_list()),
+                rear: PersistentVector::empty(),
+            };
+        }
+        PersistentQueue {
+            front: Arc::clone(&self.front),
+            rear: self.rear.conj(val),
+        }
+    }
+    /// The element at the front,  if there is one
+    pub fn peek(&self) -> Option<Arc<Value>> {
+        match &*self.front {
+            PersistentList::Cons(head, _, _) => Some(Arc::clone(head)),
+            PersistentList::Empty => None,
+        }
+    }
+    /// Returns a new queue,  without the element at the front;  the same empty queue if
+    /// there isn't one
+    pub fn pop(&self) -> PersistentQueue {
+        match &*self.front {
+            PersistentList::Cons(_, tail, _) if tail.len() > 0 => PersistentQueue {
+                front: Arc::clone(tail),
+                rear: self.rear.clone(),
+            },
+            PersistentList::Cons(..) => PersistentQueue {
+                front: Arc::new(self.rear.iter().cloned().collect::<Vec<_>>().into_list()),
+                rear: PersistentVector::empty(),
+            },
+            PersistentList::Empty => self.clone(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.front.len() as usize + self.rear.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// The elements,  front first
+    pub fn iter(&self) -> impl Iterator<Item = Arc<Value>> + '_ {
+        self.front.iter().chain(self.rear.iter().cloned())
+    }
+}
+impl PartialEq for PersistentQueue {
+    fn eq(&self, other: &PersistentQueue) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+// Alike with the lists, vectors and seqs it's equal to
+impl Hash for PersistentQueue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        value::hash_ordered(self.iter(), state)
+    }
+}
+impl Seqable for PersistentQueue {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(self.iter())
+    }
+}
+impl ToValue for PersistentQueue {
+    fn to_value(&self) -> Value {
+        Value::PersistentQueue(Arc::new(self.clone()))
+    }
+}
+impl fmt::Display for PersistentQueue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let str = self
+            .iter()
+            .map(|rc_arg| rc_arg.to_string_explicit())
+            .collect::<Vec<std::string::String>>()
+            .join(" ");
+        write!(f, "#queue [{}]", str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent_queue::PersistentQueue;
+    use crate::repl::Repl;
+    use crate::value::ToValue;
+
+    #[test]
+    fn elements_come_out_in_the_order_they_went_in() {
+        let queue = (1..=3).fold(PersistentQueue::empty(), |queue, i| {
+            queue.conj(i.to_rc_value())
+        });
+        assert_eq!("#queue [1 2 3]", queue.to_string());
+        assert_eq!(Some(1.to_rc_value()), queue.peek());
+        let queue = queue.pop().conj(4.to_rc_value()).pop();
+        assert_eq!("#queue [3 4]", queue.to_string());
+        assert_eq!(None, queue.pop().pop().peek());
+        assert!(queue.pop().pop().pop().is_empty());
+    }
+
+    #[test]
+    fn queues_are_sequential() {
+        let result = Repl::default().eval_all(&[
+            "(def q (conj clojure.lang.PersistentQueue/EMPTY 1 2 3))",
+            "[(peek q) (pop q) (seq q) (= q [1 2 3]) (= (hash q) (hash '(1 2 3))) (into #queue [] (map inc q)) (class q) (pop #queue [])]",
+        ]);
+        assert_eq!(
+            "[1 #queue [2 3] (1 2 3) true true #queue [2 3 4] clojure.lang.PersistentQueue #queue []]",
+            result.to_string()
+        );
+    }
+}
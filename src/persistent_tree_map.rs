@@ -0,0 +1,464 @@
+//! A persistent sorted map,  as made by sorted-map and sorted-map-by
+//!
+//! This is an AVL tree;  "changing" it copies only the path from the root down to the
+//! changed node,  so every version shares everything else with the one it came from.
+//! Keys are ordered either by their natural order (see natural_order) or by a ClojureRS
+//! function,  just as in Clojure
+use crate::ifn::IFn;
+use crate::maps::MapEntry;
+use crate::numbers;
+use crate::persistent_list_map::IPersistentMap;
+use crate::seqable::Seqable;
+use crate::value::{self, ToValue, Value};
+use std::cmp::{max, Ordering};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// The natural order of two values,  as (compare a b) sees it;  numbers by value, strings
+/// lexically, keywords and symbols by namespace then name, vectors by length then element
+/// by element, and nil before anything.  None if the two can't be compared at all
+pub fn natural_order(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        // As in Clojure,  anything compares equal to itself,  comparable or not
+        (a, b) if std::ptr::eq(a, b) => Some(Ordering::Equal),
+        (Value::Nil, Value::Nil) => Some(Ordering::Equal),
+        (Value::Nil, _) => Some(Ordering::Less),
+        (_, Value::Nil) => Some(Ordering::Greater),
+        // Numbers by value,  whatever their types;  NaN is as good as anything else
+        (a, b) if numbers::is_number(a) && numbers::is_number(b) => numbers::compare(a, b)
+            .ok()
+            .map(|ordering| ordering.unwrap_or(Ordering::Equal)),
+        (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+        (Value::Char(a), Value::Char(b)) => Some(a.cmp(b)),
+        (Value::Inst(a), Value::Inst(b)) => Some(a.cmp(b)),
+        (Value::Uuid(a), Value::Uuid(b)) => Some(a.cmp(b)),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Keyword(a), Value::Keyword(b)) => {
+            Some((&a.sym.ns, &a.sym.name).cmp(&(&b.sym.ns, &b.sym.name)))
+        }
+        (Value::Symbol(a), Value::Symbol(b)) => Some((&a.ns, &a.name).cmp(&(&b.ns, &b.name))),
+        (Value::PersistentVector(a), Value::PersistentVector(b)) => match a.len().cmp(&b.len()) {
+            Ordering::Equal => {
+                for (a, b) in a.iter().zip(b.iter()) {
+                    match natural_order(a, b)? {
+                        Ordering::Equal => {}
+                        ordering => return Some(ordering),
+                    }
+                }
+                Some(Ordering::Equal)
+            }
+            ordering => Some(ordering),
+        },
+        _ => None,
+    }
+}
+
+/// How a sorted collection orders its keys
+#[derive(Debug, Clone)]
+pub enum Comparator {
+    Natural,
+    Fn(Arc<dyn IFn>),
+}
+impl Comparator {
+    pub fn compare(&self, a: &Arc<Value>, b: &Arc<Value>) -> Ordering {
+        match self {
+            // Clojure would throw on keys that don't compare;  we have no way to throw from
+            // here,  so those at least get a consistent order, grouped by type
+            Comparator::Natural => natural_order(a, b)
+                .unwrap_or_else(|| a.type_tag().to_string().cmp(&b.type_tag().to_string())),
+            // Like Clojure,  a comparator can return a number (compare-style), or a boolean
+            // (ie, < or >) saying whether a comes strictly before b.  An error can't abort the
+            // insertion in progress,  so it's taken to mean the keys are equal
+            Comparator::Fn(ifn) => match ifn.invoke(vec![Arc::clone(a), Arc::clone(b)]) {
+                Value::I32(n) => n.cmp(&0),
+                Value::F64(n) => n.partial_cmp(&0.0).unwrap_or(Ordering::Equal),
+                Value::Nil | Value::Boolean(false) => {
+                    match ifn.invoke(vec![Arc::clone(b), Arc::clone(a)]) {
+                        Value::Nil | Value::Boolean(false) | Value::Condition(_) => Ordering::Equal,
+                        _ => Ordering::Greater,
+                    }
+                }
+                Value::Condition(_) => Ordering::Equal,
+                _ => Ordering::Less,
+            },
+        }
+    }
+}
+
+type Tree = Option<Arc<Node>>;
+
+#[derive(Debug)]
+struct Node {
+    entry: MapEntry,
+    left: Tree,
+    right: Tree,
+    height: usize,
+}
+
+fn height(tree: &Tree) -> usize {
+    tree.as_ref().map_or(0, |node| node.height)
+}
+
+fn node(entry: MapEntry, left: Tree, right: Tree) -> Tree {
+    let height = 1 + max(height(&left), height(&right));
+    Some(Arc::new(Node {
+        entry,
+        left,
+        right,
+        height,
+    }))
+}
+
+/// Builds a node out of subtrees whose heights differ by at most 2,  rotating it back into
+/// balance if they differ by 2
+fn balance(entry: MapEntry, left: Tree, right: Tree) -> Tree {
+    let (left_height, right_height) = (height(&left), height(&right));
+    if left_height > right_height + 1 {
+        let left = left.unwrap();
+        if height(&left.left) >= height(&left.right) {
+            node(
+                left.entry.clone(),
+                left.left.clone(),
+                node(entry, left.right.clone(), right),
+            )
+        } else {
+            let left_right = left.right.as_ref().unwrap();
+            node(
+                left_right.entry.clone(),
+                node(
+                    left.entry.clone(),
+                    left.left.clone(),
+                    left_right.left.clone(),
+                ),
+                node(entry, left_right.right.clone(), right),
+            )
+        }
+    } else if right_height > left_height + 1 {
+        let right = right.unwrap();
+        if height(&right.right) >= height(&right.left) {
+            node(
+                right.entry.clone(),
+                node(entry, left, right.left.clone()),
+                right.right.clone(),
+            )
+        } else {
+            let right_left = right.left.as_ref().unwrap();
+            node(
+                right_left.entry.clone(),
+                node(entry, left, right_left.left.clone()),
+                node(
+                    right.entry.clone(),
+                    right_left.right.clone(),
+                    right.right.clone(),
+                ),
+            )
+        }
+    } else {
+        node(entry, left, right)
+    }
+}
+
+/// Returns the new tree, and whether entry's key is new to it
+fn insert(tree: &Tree, entry: MapEntry, comparator: &Comparator) -> (Tree, bool) {
+    match tree {
+        None => (node(entry, None, None), true),
+        Some(current) => match comparator.compare(&entry.key, &current.entry.key) {
+            Ordering::Less => {
+                let (left, added) = insert(&current.left, entry, comparator);
+                (
+                    balance(current.entry.clone(), left, current.right.clone()),
+                    added,
+                )
+            }
+            Ordering::Greater => {
+                let (right, added) = insert(&current.right, entry, comparator);
+                (
+                    balance(current.entry.clone(), current.left.clone(), right),
+                    added,
+                )
+            }
+            // As in Clojure, the key already there stays
+            Ordering::Equal => (
+                node(
+                    MapEntry {
+                        key: Arc::clone(&current.entry.key),
+                        val: entry.val,
+                    },
+                    current.left.clone(),
+                    current.right.clone(),
+                ),
+                false,
+            ),
+        },
+    }
+}
+
+/// Returns the tree without its smallest entry,  and that entry
+fn remove_min(current: &Arc<Node>) -> (Tree, MapEntry) {
+    match &current.left {
+        None => (current.right.clone(), current.entry.clone()),
+        Some(left) => {
+            let (left, min) = remove_min(left);
+            (
+                balance(current.entry.clone(), left, current.right.clone()),
+                min,
+            )
+        }
+    }
+}
+
+/// Returns the tree without key,  or None if key wasn't there to begin with
+fn remove(tree: &Tree, key: &Arc<Value>, comparator: &Comparator) -> Option<Tree> {
+    let current = tree.as_ref()?;
+    match comparator.compare(key, &current.entry.key) {
+        Ordering::Less => remove(&current.left, key, comparator)
+            .map(|left| balance(current.entry.clone(), left, current.right.clone())),
+        Ordering::Greater => remove(&current.right, key, comparator)
+            .map(|right| balance(current.entry.clone(), current.left.clone(), right)),
+        Ordering::Equal => Some(match (&current.left, &current.right) {
+            (None, right) => right.clone(),
+            (left, None) => left.clone(),
+            (left, Some(right)) => {
+                let (right, min) = remove_min(right);
+                balance(min, left.clone(), right)
+            }
+        }),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PersistentTreeMap {
+    root: Tree,
+    count: usize,
+    comparator: Comparator,
+}
+impl PersistentTreeMap {
+    pub fn new(comparator: Comparator) -> PersistentTreeMap {
+        PersistentTreeMap {
+            root: None,
+            count: 0,
+            comparator,
+        }
+    }
+    pub fn comparator(&self) -> &Comparator {
+        &self.comparator
+    }
+    pub fn get_entry(&self, key: &Arc<Value>) -> Option<&MapEntry> {
+        let mut tree = &self.root;
+        while let Some(current) = tree {
+            tree = match self.comparator.compare(key, &current.entry.key) {
+                Ordering::Less => &current.left,
+                Ordering::Greater => &current.right,
+                Ordering::Equal => return Some(&current.entry),
+            };
+        }
+        None
+    }
+    pub fn contains_key(&self, key: &Arc<Value>) -> bool {
+        self.get_entry(key).is_some()
+    }
+    /// Returns a new map, without key
+    pub fn dissoc(&self, key: &Arc<Value>) -> PersistentTreeMap {
+        match remove(&self.root, key, &self.comparator) {
+            Some(root) => PersistentTreeMap {
+                root,
+                count: self.count - 1,
+                comparator: self.comparator.clone(),
+            },
+            None => self.clone(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.count
+    }
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// The entries in key order
+    pub fn iter(&self) -> PersistentTreeMapIter {
+        PersistentTreeMapIter::new(&self.root, true)
+    }
+    /// The entries in reverse key order
+    pub fn rev_iter(&self) -> PersistentTreeMapIter {
+        PersistentTreeMapIter::new(&self.root, false)
+    }
+}
+impl IPersistentMap for PersistentTreeMap {
+    fn get(&self, key: &Arc<Value>) -> Arc<Value> {
+        match self.get_entry(key) {
+            Some(entry) => Arc::clone(&entry.val),
+            None => Arc::new(Value::Nil),
+        }
+    }
+    fn assoc(&self, key: Arc<Value>, val: Arc<Value>) -> PersistentTreeMap {
+        let (root, added) = insert(&self.root, MapEntry { key, val }, &self.comparator);
+        PersistentTreeMap {
+            root,
+            count: if added { self.count + 1 } else { self.count },
+            comparator: self.comparator.clone(),
+        }
+    }
+}
+impl Seqable for PersistentTreeMap {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(self.iter().map(|entry| entry.to_vector_value()))
+    }
+}
+impl PartialEq for PersistentTreeMap {
+    fn eq(&self, other: &PersistentTreeMap) -> bool {
+        self.len() == other.len()
+            && self.iter().all(|entry| {
+                other
+                    .get_entry(&entry.key)
+                    .is_some_and(|other_entry| other_entry.val == entry.val)
+            })
+    }
+}
+impl Hash for PersistentTreeMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        value::hash_unordered(self.iter(), state)
+    }
+}
+impl ToValue for PersistentTreeMap {
+    fn to_value(&self) -> Value {
+        Value::PersistentTreeMap(Arc::new(self.clone()))
+    }
+}
+impl fmt::Display for PersistentTreeMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let str = self
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{} {}",
+                    entry.key.to_string_explicit(),
+                    entry.val.to_string_explicit()
+                )
+            })
+            .collect::<Vec<std::string::String>>()
+            .join(", ");
+        write!(f, "{{{}}}", str)
+    }
+}
+
+/// Walks a tree in order (or reverse order),  keeping the path still to be visited
+pub struct PersistentTreeMapIter {
+    pending: Vec<Arc<Node>>,
+    ascending: bool,
+}
+impl PersistentTreeMapIter {
+    fn new(root: &Tree, ascending: bool) -> PersistentTreeMapIter {
+        let mut iter = PersistentTreeMapIter {
+            pending: vec![],
+            ascending,
+        };
+        iter.descend(root.clone());
+        iter
+    }
+    /// Queues up tree's first node,  and every node on the way to it
+    fn descend(&mut self, mut tree: Tree) {
+        while let Some(current) = tree {
+            tree = if self.ascending {
+                current.left.clone()
+            } else {
+                current.right.clone()
+            };
+            self.pending.push(current);
+        }
+    }
+}
+impl Iterator for PersistentTreeMapIter {
+    type Item = MapEntry;
+    fn next(&mut self) -> Option<MapEntry> {
+        let current = self.pending.pop()?;
+        self.descend(if self.ascending {
+            current.right.clone()
+        } else {
+            current.left.clone()
+        });
+        Some(current.entry.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::maps::MapEntry;
+    use crate::persistent_list_map::IPersistentMap;
+    use crate::persistent_tree_map::{height, Comparator, PersistentTreeMap};
+    use crate::rust_core::GtFn;
+    use crate::value::{ToValue, Value};
+    use std::sync::Arc;
+
+    fn keys(pmap: &PersistentTreeMap) -> Vec<i32> {
+        pmap.iter()
+            .map(|MapEntry { key, .. }| match *key {
+                Value::I32(i) => i,
+                _ => panic!("non i32 key"),
+            })
+            .collect()
+    }
+
+    fn map_of(comparator: Comparator, ks: &[i32]) -> PersistentTreeMap {
+        ks.iter()
+            .fold(PersistentTreeMap::new(comparator), |pmap, k| {
+                pmap.assoc(k.to_rc_value(), Value::Nil.to_rc_value())
+            })
+    }
+
+    #[test]
+    fn iterates_in_key_order() {
+        let pmap = map_of(Comparator::Natural, &[5, 3, 9, 1, 4, 8, 2]);
+        assert_eq!(vec![1, 2, 3, 4, 5, 8, 9], keys(&pmap));
+        assert_eq!(
+            vec![9, 8, 5, 4, 3, 2, 1],
+            pmap.rev_iter()
+                .map(|entry| match *entry.key {
+                    Value::I32(i) => i,
+                    _ => panic!("non i32 key"),
+                })
+                .collect::<Vec<i32>>()
+        );
+    }
+
+    #[test]
+    fn stays_balanced() {
+        let pmap = map_of(Comparator::Natural, &(0..1000).collect::<Vec<i32>>());
+        assert_eq!(1000, pmap.len());
+        // An AVL tree of n nodes is less than 1.45 log2(n) high
+        assert!(height(&pmap.root) <= 14);
+        let pmap = (0..1000)
+            .filter(|i| i % 3 != 0)
+            .fold(pmap, |pmap, i| pmap.dissoc(&i.to_rc_value()));
+        assert_eq!(334, pmap.len());
+        assert!(height(&pmap.root) <= 12);
+        assert_eq!((0..1000).step_by(3).collect::<Vec<i32>>(), keys(&pmap));
+    }
+
+    #[test]
+    fn assoc_and_dissoc_leave_the_original_alone() {
+        let original = map_of(Comparator::Natural, &[1, 2, 3]);
+        let assoced = original.assoc(2.to_rc_value(), "two".to_rc_value());
+        let dissoced = original.dissoc(&1.to_rc_value());
+        assert_eq!(Value::Nil, *original.get(&2.to_rc_value()));
+        assert_eq!("two".to_value(), *assoced.get(&2.to_rc_value()));
+        assert_eq!(3, assoced.len());
+        assert_eq!(vec![1, 2, 3], keys(&original));
+        assert_eq!(vec![2, 3], keys(&dissoced));
+    }
+
+    #[test]
+    fn boolean_comparator_orders_by_it() {
+        let pmap = map_of(Comparator::Fn(Arc::new(GtFn {})), &[2, 3, 1, 3]);
+        assert_eq!(vec![3, 2, 1], keys(&pmap));
+        assert!(pmap.contains_key(&1.to_rc_value()));
+    }
+
+    #[test]
+    fn displays_like_clojure() {
+        let pmap = PersistentTreeMap::new(Comparator::Natural)
+            .assoc(2.to_rc_value(), "b".to_rc_value())
+            .assoc(1.to_rc_value(), "a".to_rc_value());
+        assert_eq!("{1 \"a\", 2 \"b\"}", pmap.to_string());
+    }
+}
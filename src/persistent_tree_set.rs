@@ -0,0 +1,118 @@
+//! A persistent sorted set,  as made by sorted-set and sorted-set-by
+//!
+//! Just a PersistentTreeMap whose keys are the elements,  as in Clojure
+use crate::maps::MapEntry;
+use crate::persistent_list_map::IPersistentMap;
+use crate::persistent_tree_map::{Comparator, PersistentTreeMap, PersistentTreeMapIter};
+use crate::seqable::Seqable;
+use crate::value::{self, ToValue, Value};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct PersistentTreeSet {
+    map: PersistentTreeMap,
+}
+impl PersistentTreeSet {
+    pub fn new(comparator: Comparator) -> PersistentTreeSet {
+        PersistentTreeSet {
+            map: PersistentTreeMap::new(comparator),
+        }
+    }
+    pub fn comparator(&self) -> &Comparator {
+        self.map.comparator()
+    }
+    /// Returns a new set, also containing val
+    pub fn conj(&self, val: Arc<Value>) -> PersistentTreeSet {
+        if self.map.contains_key(&val) {
+            return self.clone();
+        }
+        PersistentTreeSet {
+            map: self.map.assoc(val, Arc::new(Value::Nil)),
+        }
+    }
+    /// Returns a new set, without val
+    pub fn disj(&self, val: &Arc<Value>) -> PersistentTreeSet {
+        PersistentTreeSet {
+            map: self.map.dissoc(val),
+        }
+    }
+    /// The element of this set equal to val,  if there is one
+    pub fn get(&self, val: &Arc<Value>) -> Option<Arc<Value>> {
+        self.map.get_entry(val).map(|entry| Arc::clone(&entry.key))
+    }
+    pub fn contains(&self, val: &Arc<Value>) -> bool {
+        self.map.contains_key(val)
+    }
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+    /// The elements in order
+    pub fn iter(&self) -> PersistentTreeSetIter {
+        PersistentTreeSetIter(self.map.iter())
+    }
+    /// The elements in reverse order
+    pub fn rev_iter(&self) -> PersistentTreeSetIter {
+        PersistentTreeSetIter(self.map.rev_iter())
+    }
+}
+pub struct PersistentTreeSetIter(PersistentTreeMapIter);
+impl Iterator for PersistentTreeSetIter {
+    type Item = Arc<Value>;
+    fn next(&mut self) -> Option<Arc<Value>> {
+        self.0.next().map(|MapEntry { key, .. }| key)
+    }
+}
+impl PartialEq for PersistentTreeSet {
+    fn eq(&self, other: &PersistentTreeSet) -> bool {
+        self.map == other.map
+    }
+}
+impl Hash for PersistentTreeSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        value::hash_unordered(self.iter(), state)
+    }
+}
+impl Seqable for PersistentTreeSet {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(self.iter())
+    }
+}
+impl ToValue for PersistentTreeSet {
+    fn to_value(&self) -> Value {
+        Value::PersistentTreeSet(Arc::new(self.clone()))
+    }
+}
+impl fmt::Display for PersistentTreeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let str = self
+            .iter()
+            .map(|rc_arg| rc_arg.to_string_explicit())
+            .collect::<Vec<std::string::String>>()
+            .join(" ");
+        write!(f, "#{{{}}}", str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent_tree_map::Comparator;
+    use crate::persistent_tree_set::PersistentTreeSet;
+    use crate::value::ToValue;
+
+    #[test]
+    fn conj_keeps_order_and_drops_duplicates() {
+        let set = ["b", "c", "a", "b"]
+            .iter()
+            .fold(PersistentTreeSet::new(Comparator::Natural), |set, s| {
+                set.conj(s.to_rc_value())
+            });
+        assert_eq!(3, set.len());
+        assert_eq!("#{\"a\" \"b\" \"c\"}", set.to_string());
+        assert_eq!("#{\"a\" \"c\"}", set.disj(&"b".to_rc_value()).to_string());
+    }
+}
@@ -1,20 +1,445 @@
+//! A persistent vector,  laid out like Clojure's own:  a trie of 32-wide nodes (so lookups,
+//! assoc and conj touch log32 n of them,  which is never more than a handful), plus a
+//! "tail" of up to 32 elements kept out of the trie,  so that conj usually only has to
+//! copy that
+//!
+//! ```text
+//!            root
+//!          /      \
+//!   [0 .. 31]   [32 .. 63]     tail: [64 .. 70]
+//! ```
+//!
+//! A subvec is a window onto a vector (a start and a length),  and so takes no time to
+//! make,  also as in Clojure
+//!
+//! Transients are built on the _in_place edits;  these change a vector through Arc::make_mut,
+//! so a node is copied the first time it's edited if another vector shares it,  and edited
+//! in place from then on
 use std::convert::From;
 use std::fmt;
 use std::fmt::Debug;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
-use std::rc::Rc;
+use std::mem;
+use std::sync::Arc;
 
-use crate::value::{ToValue, Value};
+use crate::seqable::Seqable;
+use crate::value::{self, ToValue, Value};
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Branch(Vec<Arc<Node>>),
+    Leaf(Vec<Arc<Value>>),
+}
+impl Node {
+    fn children(&self) -> &[Arc<Node>] {
+        match self {
+            Node::Branch(children) => children,
+            Node::Leaf(_) => &[],
+        }
+    }
+    fn vals(&self) -> &[Arc<Value>] {
+        match self {
+            Node::Leaf(vals) => vals,
+            Node::Branch(_) => &[],
+        }
+    }
+    fn children_mut(&mut self) -> &mut Vec<Arc<Node>> {
+        match self {
+            Node::Branch(children) => children,
+            Node::Leaf(_) => unreachable!("leaves have no children"),
+        }
+    }
+}
+
+/// A node at level,  with node at the bottom of a chain of single-child branches
+fn new_path(level: usize, node: Arc<Node>) -> Arc<Node> {
+    if level == 0 {
+        node
+    } else {
+        Arc::new(Node::Branch(vec![new_path(level - BITS, node)]))
+    }
+}
+
+fn do_assoc(level: usize, node: &Arc<Node>, ind: usize, val: Arc<Value>) -> Arc<Node> {
+    match &**node {
+        Node::Leaf(vals) => {
+            let mut vals = vals.clone();
+            vals[ind & MASK] = val;
+            Arc::new(Node::Leaf(vals))
+        }
+        Node::Branch(children) => {
+            let mut children = children.clone();
+            let subind = (ind >> level) & MASK;
+            children[subind] = do_assoc(level - BITS, &children[subind], ind, val);
+            Arc::new(Node::Branch(children))
+        }
+    }
+}
+
+fn do_assoc_in_place(level: usize, node: &mut Arc<Node>, ind: usize, val: Arc<Value>) {
+    match Arc::make_mut(node) {
+        Node::Leaf(vals) => vals[ind & MASK] = val,
+        Node::Branch(children) => {
+            let subind = (ind >> level) & MASK;
+            do_assoc_in_place(level - BITS, &mut children[subind], ind, val)
+        }
+    }
+}
+
+/// The trie and tail proper;  every vector is some window onto one of these
+#[derive(Debug, Clone)]
+struct Trie {
+    count: usize,
+    // The level of the root;  leaves are at level 0
+    shift: usize,
+    root: Arc<Node>,
+    tail: Arc<Vec<Arc<Value>>>,
+}
+impl Trie {
+    fn empty() -> Trie {
+        Trie {
+            count: 0,
+            shift: BITS,
+            root: Arc::new(Node::Branch(vec![])),
+            tail: Arc::new(vec![]),
+        }
+    }
+    /// The index of the first element in the tail
+    fn tail_offset(&self) -> usize {
+        if self.count < WIDTH {
+            0
+        } else {
+            ((self.count - 1) >> BITS) << BITS
+        }
+    }
+    /// The leaf (or tail) holding ind
+    fn leaf_for(&self, ind: usize) -> &[Arc<Value>] {
+        if ind >= self.tail_offset() {
+            return &self.tail;
+        }
+        let mut node = &self.root;
+        let mut level = self.shift;
+        while level > 0 {
+            node = &node.children()[(ind >> level) & MASK];
+            level -= BITS;
+        }
+        node.vals()
+    }
+    fn get(&self, ind: usize) -> Option<&Arc<Value>> {
+        if ind < self.count {
+            self.leaf_for(ind).get(ind & MASK)
+        } else {
+            None
+        }
+    }
+    fn conj(&self, val: Arc<Value>) -> Trie {
+        // Room in the tail
+        if self.count - self.tail_offset() < WIDTH {
+            let mut tail = (*self.tail).clone();
+            tail.push(val);
+            return Trie {
+                count: self.count + 1,
+                shift: self.shift,
+                root: Arc::clone(&self.root),
+                tail: Arc::new(tail),
+            };
+        }
+        // Otherwise the full tail moves into the trie,  which may need to grow a level
+        let tail_node = Arc::new(Node::Leaf((*self.tail).clone()));
+        let (root, shift) = if (self.count >> BITS) > (1 << self.shift) {
+            (
+                Arc::new(Node::Branch(vec![
+                    Arc::clone(&self.root),
+                    new_path(self.shift, tail_node),
+                ])),
+                self.shift + BITS,
+            )
+        } else {
+            (
+                self.push_tail(self.shift, &self.root, tail_node),
+                self.shift,
+            )
+        };
+        Trie {
+            count: self.count + 1,
+            shift,
+            root,
+            tail: Arc::new(vec![val]),
+        }
+    }
+    fn push_tail(&self, level: usize, parent: &Arc<Node>, tail_node: Arc<Node>) -> Arc<Node> {
+        let subind = ((self.count - 1) >> level) & MASK;
+        let mut children = parent.children().to_vec();
+        let child = if level == BITS {
+            tail_node
+        } else {
+            match children.get(subind) {
+                Some(child) => self.push_tail(level - BITS, child, tail_node),
+                None => new_path(level - BITS, tail_node),
+            }
+        };
+        if subind < children.len() {
+            children[subind] = child;
+        } else {
+            children.push(child);
+        }
+        Arc::new(Node::Branch(children))
+    }
+    fn conj_in_place(&mut self, val: Arc<Value>) {
+        if self.count - self.tail_offset() < WIDTH {
+            Arc::make_mut(&mut self.tail).push(val);
+            self.count += 1;
+            return;
+        }
+        let tail = mem::replace(&mut self.tail, Arc::new(vec![val]));
+        let tail_node = Arc::new(Node::Leaf(
+            Arc::try_unwrap(tail).unwrap_or_else(|shared| (*shared).clone()),
+        ));
+        if (self.count >> BITS) > (1 << self.shift) {
+            let root = mem::replace(&mut self.root, Arc::new(Node::Branch(vec![])));
+            self.root = Arc::new(Node::Branch(vec![root, new_path(self.shift, tail_node)]));
+            self.shift += BITS;
+        } else {
+            Trie::push_tail_in_place(self.count, self.shift, &mut self.root, tail_node);
+        }
+        self.count += 1;
+    }
+    fn push_tail_in_place(
+        count: usize,
+        level: usize,
+        parent: &mut Arc<Node>,
+        tail_node: Arc<Node>,
+    ) {
+        let subind = ((count - 1) >> level) & MASK;
+        let children = Arc::make_mut(parent).children_mut();
+        if level == BITS {
+            if subind < children.len() {
+                children[subind] = tail_node;
+            } else {
+                children.push(tail_node);
+            }
+        } else if subind < children.len() {
+            Trie::push_tail_in_place(count, level - BITS, &mut children[subind], tail_node);
+        } else {
+            children.push(new_path(level - BITS, tail_node));
+        }
+    }
+    /// Expects ind to be in range
+    fn assoc_in_place(&mut self, ind: usize, val: Arc<Value>) {
+        if ind >= self.tail_offset() {
+            Arc::make_mut(&mut self.tail)[ind & MASK] = val;
+        } else {
+            do_assoc_in_place(self.shift, &mut self.root, ind, val);
+        }
+    }
+    /// Expects ind to be in range
+    fn assoc(&self, ind: usize, val: Arc<Value>) -> Trie {
+        if ind >= self.tail_offset() {
+            let mut tail = (*self.tail).clone();
+            tail[ind & MASK] = val;
+            Trie {
+                tail: Arc::new(tail),
+                ..self.clone()
+            }
+        } else {
+            Trie {
+                root: do_assoc(self.shift, &self.root, ind, val),
+                ..self.clone()
+            }
+        }
+    }
+    /// Expects there to be something to pop
+    fn pop(&self) -> Trie {
+        if self.count == 1 {
+            return Trie::empty();
+        }
+        if self.count - self.tail_offset() > 1 {
+            let mut tail = (*self.tail).clone();
+            tail.pop();
+            return Trie {
+                count: self.count - 1,
+                tail: Arc::new(tail),
+                ..self.clone()
+            };
+        }
+        // The tail empties out,  so the last leaf of the trie becomes the new one
+        let tail = self.leaf_for(self.count - 2).to_vec();
+        let mut root = self
+            .pop_tail(self.shift, &self.root)
+            .unwrap_or_else(|| Arc::new(Node::Branch(vec![])));
+        let mut shift = self.shift;
+        if shift > BITS && root.children().len() == 1 {
+            root = Arc::clone(&root.children()[0]);
+            shift -= BITS;
+        }
+        Trie {
+            count: self.count - 1,
+            shift,
+            root,
+            tail: Arc::new(tail),
+        }
+    }
+    /// Returns node without its last leaf,  or None if nothing would be left of it
+    fn pop_tail(&self, level: usize, node: &Arc<Node>) -> Option<Arc<Node>> {
+        let subind = ((self.count - 2) >> level) & MASK;
+        let child = if level > BITS {
+            self.pop_tail(level - BITS, &node.children()[subind])
+        } else {
+            None
+        };
+        if child.is_none() && subind == 0 {
+            return None;
+        }
+        let mut children = node.children()[..subind].to_vec();
+        children.extend(child);
+        Some(Arc::new(Node::Branch(children)))
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct PersistentVector {
-    pub vals: Vec<Rc<Value>>,
+    trie: Trie,
+    // The window onto trie this vector is;  all of it,  unless this is a subvec
+    start: usize,
+    len: usize,
+}
+impl PersistentVector {
+    pub fn empty() -> PersistentVector {
+        PersistentVector {
+            trie: Trie::empty(),
+            start: 0,
+            len: 0,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    pub fn nth(&self, ind: usize) -> Option<&Arc<Value>> {
+        if ind < self.len {
+            self.trie.get(self.start + ind)
+        } else {
+            None
+        }
+    }
+    /// The last element,  if there is one
+    pub fn peek(&self) -> Option<&Arc<Value>> {
+        self.len.checked_sub(1).and_then(|last| self.nth(last))
+    }
+    /// Returns a new vector, with val on the end
+    pub fn conj(&self, val: Arc<Value>) -> PersistentVector {
+        let end = self.start + self.len;
+        // A subvec that ends before its trie does just overwrites what comes after it
+        let trie = if end == self.trie.count {
+            self.trie.conj(val)
+        } else {
+            self.trie.assoc(end, val)
+        };
+        PersistentVector {
+            trie,
+            start: self.start,
+            len: self.len + 1,
+        }
+    }
+    /// Returns a new vector, with val at ind;  ind can be one past the end,  as with conj
+    pub fn assoc(&self, ind: usize, val: Arc<Value>) -> Option<PersistentVector> {
+        if ind < self.len {
+            Some(PersistentVector {
+                trie: self.trie.assoc(self.start + ind, val),
+                ..self.clone()
+            })
+        } else if ind == self.len {
+            Some(self.conj(val))
+        } else {
+            None
+        }
+    }
+    /// As conj,  but changes this vector
+    pub fn conj_in_place(&mut self, val: Arc<Value>) {
+        let end = self.start + self.len;
+        if end == self.trie.count {
+            self.trie.conj_in_place(val);
+        } else {
+            self.trie.assoc_in_place(end, val);
+        }
+        self.len += 1;
+    }
+    /// As assoc,  but changes this vector;  false, and no change, if ind is out of range
+    pub fn assoc_in_place(&mut self, ind: usize, val: Arc<Value>) -> bool {
+        if ind < self.len {
+            self.trie.assoc_in_place(self.start + ind, val);
+        } else if ind == self.len {
+            self.conj_in_place(val);
+        } else {
+            return false;
+        }
+        true
+    }
+    /// Returns a new vector, without the last element;  None if there is none
+    pub fn pop(&self) -> Option<PersistentVector> {
+        if self.len <= 1 {
+            return self.len.checked_sub(1).map(|_| PersistentVector::empty());
+        }
+        let trie = if self.start + self.len == self.trie.count {
+            self.trie.pop()
+        } else {
+            self.trie.clone()
+        };
+        Some(PersistentVector {
+            trie,
+            start: self.start,
+            len: self.len - 1,
+        })
+    }
+    /// The elements from start (inclusive) to end (exclusive);  None if that's out of range
+    pub fn subvec(&self, start: usize, end: usize) -> Option<PersistentVector> {
+        if start > end || end > self.len {
+            return None;
+        }
+        Some(PersistentVector {
+            trie: self.trie.clone(),
+            start: self.start + start,
+            len: end - start,
+        })
+    }
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            vector: self,
+            front: 0,
+            back: self.len,
+        }
+    }
+}
+impl Default for PersistentVector {
+    fn default() -> PersistentVector {
+        PersistentVector::empty()
+    }
+}
+impl Seqable for PersistentVector {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(self.iter().cloned())
+    }
+}
+impl PartialEq for PersistentVector {
+    fn eq(&self, other: &PersistentVector) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+impl Hash for PersistentVector {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        value::hash_ordered(self.iter().cloned(), state)
+    }
 }
 impl fmt::Display for PersistentVector {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let str = self
-            .vals
             .iter()
             .map(|rc_arg| rc_arg.to_string_explicit())
             .collect::<Vec<std::string::String>>()
@@ -23,17 +448,41 @@ impl fmt::Display for PersistentVector {
     }
 }
 
-impl From<Vec<Rc<Value>>> for PersistentVector {
-    fn from(item: Vec<Rc<Value>>) -> Self {
+/// Walks a vector from either end
+pub struct Iter<'a> {
+    vector: &'a PersistentVector,
+    front: usize,
+    back: usize,
+}
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Arc<Value>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.vector.nth(self.front - 1)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.vector.nth(self.back)
+    }
+}
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+impl From<Vec<Arc<Value>>> for PersistentVector {
+    fn from(item: Vec<Arc<Value>>) -> Self {
         item.into_iter().collect::<PersistentVector>()
     }
 }
-// impl Hash for PersistentVector {
-//     fn hash<H: Hasher>(&self, state: &mut H) {
-// 	let as_vec = Rc::new(self.clone()).iter().collect::<Vec<Rc<Value>>>();
-// 	as_vec.hash(state)
-//     }
-// }
 //
 // Mostly to just make some code more concise
 // @TODO ~lookup proper rust conversion traits~
@@ -49,50 +498,138 @@ pub trait ToPersistentVector {
         self.into_vector().to_value()
     }
 }
-impl ToPersistentVector for Vec<Rc<Value>> {
+impl ToPersistentVector for Vec<Arc<Value>> {
     fn into_vector(self) -> PersistentVector {
         self.into_iter().collect::<PersistentVector>()
     }
 }
 
-pub trait ToPersistentVectorIter {
-    fn iter(&self) -> PersistentVectorIter;
-}
-impl ToPersistentVectorIter for Rc<PersistentVector> {
-    fn iter(&self) -> PersistentVectorIter {
-        PersistentVectorIter {
-            vector: Rc::clone(self),
-            ind: 0,
+impl FromIterator<Arc<Value>> for PersistentVector {
+    // Builds the trie a level at a time,  rather than conj'ing (and copying the tail) once
+    // per element
+    fn from_iter<I: IntoIterator<Item = Arc<Value>>>(iter: I) -> Self {
+        let vals = iter.into_iter().collect::<Vec<Arc<Value>>>();
+        let count = vals.len();
+        let mut trie = Trie {
+            count,
+            ..Trie::empty()
+        };
+        let tail_offset = trie.tail_offset();
+        trie.tail = Arc::new(vals[tail_offset..].to_vec());
+
+        let mut nodes = vals[..tail_offset]
+            .chunks(WIDTH)
+            .map(|leaf| Arc::new(Node::Leaf(leaf.to_vec())))
+            .collect::<Vec<Arc<Node>>>();
+        while nodes.len() > WIDTH {
+            nodes = nodes
+                .chunks(WIDTH)
+                .map(|children| Arc::new(Node::Branch(children.to_vec())))
+                .collect();
+            trie.shift += BITS;
+        }
+        trie.root = Arc::new(Node::Branch(nodes));
+
+        PersistentVector {
+            trie,
+            start: 0,
+            len: count,
         }
-        // self.vals.iter().map(|rc_ref| Rc::clone(rc_ref))
     }
 }
-pub struct PersistentVectorIter {
-    vector: Rc<PersistentVector>,
-    ind: usize,
-}
-impl Iterator for PersistentVectorIter {
-    type Item = Rc<Value>;
-    fn next(&mut self) -> Option<Self::Item> {
-        let retval = (&*self.vector.vals)
-            .get(self.ind)
-            .map(|rc_val| Rc::clone(rc_val));
-        self.ind += 1;
-        retval
-    }
-}
-impl FromIterator<Rc<Value>> for PersistentVector {
-    //
-    // @TODO Surely we can just forward Vec's from_iter and wrap it
-    //
-    fn from_iter<I: IntoIterator<Item = Rc<Value>>>(iter: I) -> Self {
-        // @TODO see if we can directly loop through our original iter backwards, and avoid
-        // dumping into this vector just to loop through again backwards
-        let mut coll_as_vec = vec![];
-
-        for i in iter {
-            coll_as_vec.push(i);
-        }
-        PersistentVector { vals: coll_as_vec }
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent_vector::PersistentVector;
+    use crate::value::{ToValue, Value};
+    use std::sync::Arc;
+
+    fn ints(pvector: &PersistentVector) -> Vec<i32> {
+        pvector
+            .iter()
+            .map(|val| match **val {
+                Value::I32(i) => i,
+                _ => panic!("non i32 element"),
+            })
+            .collect()
+    }
+
+    fn range(n: i32) -> PersistentVector {
+        (0..n).map(|i| i.to_rc_value()).collect()
+    }
+
+    #[test]
+    fn conj_matches_collect() {
+        // Big enough to need a third level
+        for n in &[0, 1, 31, 32, 33, 1024, 1056, 1057, 2000] {
+            let conjed = (0..*n).fold(PersistentVector::empty(), |pvector, i| {
+                pvector.conj(i.to_rc_value())
+            });
+            assert_eq!(range(*n), conjed);
+            assert_eq!((0..*n).collect::<Vec<i32>>(), ints(&conjed));
+        }
+    }
+
+    #[test]
+    fn pop_undoes_conj() {
+        let mut pvector = range(1100);
+        for n in (0..1100).rev() {
+            assert_eq!(Some(&Arc::new(Value::I32(n))), pvector.peek());
+            pvector = pvector.pop().unwrap();
+            assert_eq!(n as usize, pvector.len());
+        }
+        assert_eq!(None, pvector.pop());
+        // Popping all the way shouldn't leave anything behind that confuses conj
+        assert_eq!(
+            range(40),
+            (0..40).fold(pvector, |v, i| v.conj(i.to_rc_value()))
+        );
+    }
+
+    #[test]
+    fn assoc_leaves_the_original_alone() {
+        let original = range(100);
+        let assoced = original
+            .assoc(5, "five".to_rc_value())
+            .unwrap()
+            .assoc(99, "last".to_rc_value())
+            .unwrap();
+        assert_eq!(Some(&"five".to_rc_value()), assoced.nth(5));
+        assert_eq!(Some(&"last".to_rc_value()), assoced.nth(99));
+        assert_eq!(range(100), original);
+        assert_eq!(None, original.assoc(101, Value::Nil.to_rc_value()));
+        assert_eq!(
+            101,
+            original.assoc(100, Value::Nil.to_rc_value()).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn subvec_is_a_window() {
+        let original = range(100);
+        let sub = original.subvec(10, 20).unwrap();
+        assert_eq!((10..20).collect::<Vec<i32>>(), ints(&sub));
+        // conj onto a subvec doesn't show through to the vector it came from
+        let conjed = sub.conj(Value::Nil.to_rc_value());
+        assert_eq!(Some(&Value::Nil.to_rc_value()), conjed.nth(10));
+        assert_eq!(range(100), original);
+        assert_eq!((10..19).collect::<Vec<i32>>(), ints(&sub.pop().unwrap()));
+        assert_eq!(None, original.subvec(5, 101));
+        assert_eq!(None, original.subvec(6, 5));
+    }
+
+    #[test]
+    fn iterates_backwards() {
+        assert_eq!(
+            (0..70).rev().collect::<Vec<i32>>(),
+            range(70)
+                .iter()
+                .rev()
+                .map(|val| match **val {
+                    Value::I32(i) => i,
+                    _ => panic!("non i32 element"),
+                })
+                .collect::<Vec<i32>>()
+        );
     }
 }
@@ -0,0 +1,73 @@
+//! The order sets and maps are printed in
+//!
+//! They're printed in whatever order they iterate in,  which is up to how they're laid
+//! out;  unless *print-sorted* is true.  Then they're printed sorted,  numbers first,  by
+//! value,  and everything else by how it's printed;  so what a program prints doesn't
+//! change when the collections do,  as golden-file tests want.  *print-sorted* starts out
+//! true if CLOJURE_RS_PRINT_SORTED is set to anything but "" or 0 or false
+use crate::dynamic;
+use crate::numbers;
+use crate::symbol::Symbol;
+use crate::value::Value;
+use std::env;
+use std::sync::OnceLock;
+
+pub const PRINT_SORTED_VAR: &str = "CLOJURE_RS_PRINT_SORTED";
+
+/// What *print-sorted* starts out as;  whether CLOJURE_RS_PRINT_SORTED says to sort
+pub fn sorted_by_default() -> bool {
+    static SORTED: OnceLock<bool> = OnceLock::new();
+    *SORTED.get_or_init(|| match env::var(PRINT_SORTED_VAR) {
+        Ok(sorted) => !["", "0", "false"].contains(&sorted.to_lowercase().as_str()),
+        Err(_) => false,
+    })
+}
+
+/// Whether sets and maps are printed sorted on this thread now;  as *print-sorted* is
+/// bound,  if it is
+pub fn sorted() -> bool {
+    match dynamic::bound(
+        &Symbol::intern("clojure.core"),
+        &Symbol::intern("*print-sorted*"),
+    ) {
+        Some(sorted) => sorted.is_truthy(),
+        None => sorted_by_default(),
+    }
+}
+
+// What val's sorted by;  numbers before anything else,  then its value (as an f64,  ordered
+// as f64::total_cmp orders them),  then how it's printed
+fn sort_key(val: &Value) -> (bool, i64, String) {
+    let printed = val.to_string_explicit();
+    match numbers::to_f64(val) {
+        Some(n) => {
+            let bits = n.to_bits() as i64;
+            (false, bits ^ (((bits >> 63) as u64) >> 1) as i64, printed)
+        }
+        None => (true, 0, printed),
+    }
+}
+
+/// Sorts items,  as they're printed sorted,  by the value val gives of each;  if they're
+/// printed sorted now
+pub fn sort<T>(items: &mut [T], val: impl Fn(&T) -> &Value) {
+    if sorted() {
+        items.sort_by_cached_key(|item| sort_key(val(item)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn print_sorted_sorts_sets_and_maps() {
+        let result = Repl::default().eval_all(&[
+            "(binding [*print-sorted* true] (pr-str #{:b 10 \"a\" 9 2.5} {:z 1 :a {:c 2 :b 1}}))",
+        ]);
+        assert_eq!(
+            "#{2.5 9 10 \"a\" :b} {:a {:b 1, :c 2}, :z 1}",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! (promise), (deliver p val) and @p
+use crate::ideref::IBlockingDeref;
+use crate::interrupt;
+use crate::value::Value;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+// How long a wait goes before looking again at whether it's been interrupted
+const INTERRUPT_CHECK: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Default)]
+pub struct Promise {
+    val: Mutex<Option<Arc<Value>>>,
+    // Signalled when val's delivered,  for whoever's waiting on it
+    delivered: Condvar,
+}
+impl Promise {
+    pub fn new() -> Promise {
+        Promise::default()
+    }
+    /// Delivers val, if nothing has been delivered yet.  Returns whether it was
+    pub fn deliver(&self, val: Arc<Value>) -> bool {
+        let mut current = self.val.lock().unwrap();
+        if current.is_some() {
+            return false;
+        }
+        *current = Some(val);
+        self.delivered.notify_all();
+        true
+    }
+}
+impl IBlockingDeref for Promise {
+    fn deref_blocking(&self, timeout: Option<Duration>) -> Option<Arc<Value>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut val = self.val.lock().unwrap();
+        loop {
+            if let Some(val) = &*val {
+                return Some(Arc::clone(val));
+            }
+            if interrupt::is_interrupted() {
+                return None;
+            }
+            let wait = match deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(left) => left.min(INTERRUPT_CHECK),
+                    None => return None,
+                },
+                None => INTERRUPT_CHECK,
+            };
+            val = self.delivered.wait_timeout(val, wait).unwrap().0;
+        }
+    }
+    fn is_realized(&self) -> bool {
+        self.val.lock().unwrap().is_some()
+    }
+}
+impl fmt::Display for Promise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &*self.val.lock().unwrap() {
+            Some(val) => write!(
+                f,
+                "#promise[{{:status :ready, :val {}}}]",
+                val.to_string_explicit()
+            ),
+            None => write!(f, "#promise[{{:status :pending, :val nil}}]"),
+        }
+    }
+}
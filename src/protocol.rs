@@ -1,5 +1,5 @@
 use crate::value::Value;
-use std::rc::Rc;
+use std::sync::Arc;
 
 // @TODO should we just exclusively call them protocols as
 //       we've been doing (deciding that Clojure without the host
@@ -16,14 +16,14 @@ use std::rc::Rc;
 /// functions to the Value inside, and unwrap() it to get your Value back when
 /// you're done
 pub trait Protocol: Sized {
-    fn try_as_protocol(val: &Rc<Value>) -> Option<Self>; // where Self:Sized;
+    fn try_as_protocol(val: &Arc<Value>) -> Option<Self>; // where Self:Sized;
     /// Panics if your Value isn't an instance of Protocol
-    fn as_protocol(val: &Rc<Value>) -> Self {
+    fn as_protocol(val: &Arc<Value>) -> Self {
         Self::try_as_protocol(val).unwrap()
     }
-    fn try_unwrap(&self) -> Option<Rc<Value>>;
+    fn try_unwrap(&self) -> Option<Arc<Value>>;
     /// Panics if your Value isn't an instance of Protocol
-    fn unwrap(&self) -> Rc<Value> {
+    fn unwrap(&self) -> Arc<Value> {
         self.try_unwrap().unwrap()
     }
 }
@@ -31,7 +31,7 @@ pub trait ProtocolCastable {
     fn try_as_protocol<T: Protocol>(&self) -> Option<T>;
     fn as_protocol<T: Protocol>(&self) -> T;
 }
-impl ProtocolCastable for Rc<Value> {
+impl ProtocolCastable for Arc<Value> {
     fn try_as_protocol<T: Protocol>(&self) -> Option<T> {
         T::try_as_protocol(self)
     }
@@ -10,18 +10,31 @@
 
 use nom::combinator::verify;
 use nom::{
-    branch::alt, bytes::complete::tag, combinator::opt, map, sequence::preceded, take_until,
-    terminated, Err::Incomplete, IResult,
+    branch::alt, bytes::complete::tag, combinator::opt, map, sequence::preceded, Err::Incomplete,
+    IResult,
 };
 
+use crate::data_readers;
+use crate::edn;
 use crate::keyword::Keyword;
 use crate::maps::MapEntry;
-use crate::persistent_list::ToPersistentList;
-use crate::persistent_list_map::ToPersistentListMap;
-use crate::persistent_vector::ToPersistentVector;
+use crate::meta;
+use crate::numbers;
+use crate::pattern;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list::{PersistentList, ToPersistentList, ToPersistentListIter};
+use crate::persistent_list_map::{PersistentListMap, ToPersistentListMapIter};
+use crate::persistent_vector::PersistentVector;
 use crate::symbol::Symbol;
+use crate::trace::{self, SourcePosition};
+use crate::transient::MapEdits;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, OnceLock};
 
 use std::io::BufRead;
 //
@@ -102,8 +115,9 @@ fn cons_str(head: char, tail: &str) -> String {
 ///   - `$`,
 ///   - `*`,
 ///   - `!`,
+///   - `#`,  as in the x# syntax-quote makes a gensym of
 fn is_identifier_char(chr: char) -> bool {
-    chr.is_alphanumeric() || "|?<>+-_=^%&$*!.".contains(chr)
+    chr.is_alphanumeric() || "|?<>+-_=^%&$*!.#".contains(chr)
 }
 
 /// Returns whether if a character can be in the head of an identifier.
@@ -120,14 +134,15 @@ fn is_identifier_char(chr: char) -> bool {
 ///   - `-`,
 ///   - `_`,
 ///   - `=`,
-///   - `^`,
 ///   - `%`,
 ///   - `&`,
 ///   - `$`,
 ///   - `*`,
 ///   - `!`,
+///
+/// but not `^`,  which starts metadata;  ^x is never a symbol
 fn is_non_numeric_identifier_char(chr: char) -> bool {
-    chr.is_alphabetic() || "|?<>+-_=^%&$*!.".contains(chr)
+    chr.is_alphabetic() || "|?<>+-_=%&$*!.".contains(chr)
 }
 
 /// Returns true if given character is a minus character
@@ -159,12 +174,27 @@ fn is_clojure_whitespace(c: char) -> bool {
 //////////////////////////////////////////////////////////////////////////////////////////////////////
 
 /// Consumes any whitespace from input, if there is any.
-/// Always succeeds.
+/// Only fails if what's after it is a form to skip,  that can't be read.
 ///
-/// A whitespace is either an ASCII whitespace or a comma.
+/// A whitespace is either an ASCII whitespace or a comma.  Forms that read as nothing at
+/// all count too;  #_ form,  and reader conditionals with no branch for us
 fn consume_clojure_whitespaces_parser(input: &str) -> IResult<&str, ()> {
     named!(parser<&str, &str>, take_while!(is_clojure_whitespace));
-    parser(input).map(|(rest, _)| (rest, ()))
+    let (mut rest_input, _) = parser(input)?;
+    loop {
+        if let Some(discarded_input) = rest_input.strip_prefix("#_") {
+            let (after_discarded, _) = try_read(discarded_input)?;
+            rest_input = after_discarded.trim_start_matches(is_clojure_whitespace);
+            continue;
+        }
+        if rest_input.starts_with("#?(") && !reading_edn() {
+            if let (after_conditional, None) = reader_conditional(rest_input)? {
+                rest_input = after_conditional.trim_start_matches(is_clojure_whitespace);
+                continue;
+            }
+        }
+        return Ok((rest_input, ()));
+    }
 }
 
 // This parser is made with nom's function combinator, rather than macros,
@@ -212,8 +242,8 @@ pub fn identifier_parser(input: &str) -> IResult<&str, String> {
 }
 
 /// Parses valid Clojure symbol
-/// Example Successes: a , b , |ab123|
-///                    namespace.subnamespace/a    cat/b   a.b.c/|ab123|
+/// Example Successes: a , b , |ab123| , /
+///                    namespace.subnamespace/a    cat/b   a.b.c/|ab123|   clojure.core//
 pub fn symbol_parser(input: &str) -> IResult<&str, Symbol> {
     named!(namespace_parser <&str,String>,
 	   do_parse!(
@@ -222,7 +252,11 @@ pub fn symbol_parser(input: &str) -> IResult<&str, Symbol> {
 	       (ns)));
 
     let (rest_input, ns) = opt(namespace_parser)(input)?;
-    let (rest_input, name) = identifier_parser(rest_input)?;
+    // / on its own is a name too;  division's
+    let (rest_input, name) = match rest_input.strip_prefix('/') {
+        Some(after) if !after.starts_with(is_identifier_char) => (after, String::from("/")),
+        _ => identifier_parser(rest_input)?,
+    };
     match ns {
         Some(ns) => Ok((rest_input, Symbol::intern_with_ns(&ns, &name))),
         None => Ok((rest_input, Symbol::intern(&name))),
@@ -320,9 +354,12 @@ pub fn try_read_i32(input: &str) -> IResult<&str, Value> {
 /// Example success:
 ///     true => Value::Boolean(true)
 ///     false => Value::Boolean(false)
+/// Example Failures:
+///     true?  falsey
 pub fn try_read_bool(input: &str) -> IResult<&str, Value> {
-    named!(bool_parser<&str,&str>, alt!( tag!("true") | tag!("false")));
-    let (rest_input, bool) = bool_parser(input)?;
+    let (rest_input, bool) = verify(identifier_parser, |ident: &str| {
+        ident == "true" || ident == "false"
+    })(input)?;
     Ok((rest_input, Value::Boolean(bool.parse().unwrap())))
 }
 
@@ -332,6 +369,50 @@ pub fn try_read_f64(input: &str) -> IResult<&str, Value> {
     to_value_parser(double_parser)(input)
 }
 
+/// Tries to parse &str into a number;  the i32s and f64s above,  and those past them
+/// Example Successes:
+///    1/3        => Value::Ratio(1/3),  and 4/2 => Value::I32(2)
+///    12N        => Value::BigInt(12)
+///    9999999999 => Value::BigInt(9999999999)
+///    1.5M       => Value::BigDecimal(1.5)
+/// Example Failures:
+///    -,  .5
+pub fn try_read_number(input: &str) -> IResult<&str, Value> {
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let sign_len = if input.starts_with('-') { 1 } else { 0 };
+    let integer_len = sign_len + digits(&input[sign_len..]);
+    if integer_len == sign_len {
+        return Err(nom::Err::Error((input, nom::error::ErrorKind::Digit)));
+    }
+    let (integer, rest_input) = input.split_at(integer_len);
+    let fraction_len = rest_input.strip_prefix('.').map(digits).unwrap_or(0);
+    if fraction_len > 0 {
+        let (decimal, rest_input) = input.split_at(integer_len + 1 + fraction_len);
+        return match rest_input.strip_prefix('M') {
+            Some(rest_input) => Ok((
+                rest_input,
+                Value::BigDecimal(Arc::new(decimal.parse().unwrap())),
+            )),
+            None => try_read_f64(input),
+        };
+    }
+    let n: BigInt = integer.parse().unwrap();
+    if let Some(denom) = rest_input.strip_prefix('/') {
+        let denom_len = digits(denom);
+        if denom_len > 0 {
+            let (denom, rest_input) = denom.split_at(denom_len);
+            let denom = numbers::integer(denom.parse().unwrap());
+            return Ok((rest_input, numbers::divide(&numbers::integer(n), &denom)));
+        }
+    }
+    match rest_input.chars().next() {
+        Some('N') => Ok((&rest_input[1..], Value::BigInt(Arc::new(n)))),
+        Some('M') => Ok((&rest_input[1..], Value::BigDecimal(Arc::new(n.into())))),
+        _ if n.to_i32().is_some() => try_read_i32(input),
+        _ => Ok((rest_input, Value::BigInt(Arc::new(n)))),
+    }
+}
+
 // Perhaps generalize this into reader macros
 /// Tries to parse &str into Value::Keyword
 /// Example Successes:
@@ -364,28 +445,190 @@ pub fn try_read_symbol(input: &str) -> IResult<&str, Value> {
 /// Example Successes:
 ///    nil => Value::Nil
 pub fn try_read_nil(input: &str) -> IResult<&str, Value> {
-    let (rest_input, _) = verify(identifier_parser,|ident: &str| ident == "nil")(input)?;
-    Ok((rest_input,Value::Nil))
+    let (rest_input, _) = verify(identifier_parser, |ident: &str| ident == "nil")(input)?;
+    Ok((rest_input, Value::Nil))
 }
 
-// @TODO allow escaped strings
-/// Tries to parse &str into Value::String
+/// Tries to parse &str into Value::String,  with its escapes;  see STRING_ESCAPES
 /// Example Successes:
 ///    "this is pretty straightforward" => Value::String("this is pretty straightforward")
+///    "say \"hi\"\n"                  => Value::String("say \"hi\"" and a newline)
 pub fn try_read_string(input: &str) -> IResult<&str, Value> {
     named!(quotation<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("\"")));
 
     let (rest_input, _) = quotation(input)?;
+    let mut string = String::new();
+    // The first escape that isn't one;  the string's read to its end all the same,  so that
+    // reading carries on after it
+    let mut unsupported = None;
+    let mut chars = rest_input.char_indices();
+    while let Some((ind, ch)) = chars.next() {
+        match ch {
+            '"' => {
+                let string = match unsupported {
+                    Some(escape) => Value::Condition(
+                        format!("Unsupported escape character: \\{}", escape).into(),
+                    ),
+                    None => Value::String(string.into()),
+                };
+                return Ok((&rest_input[ind + 1..], string));
+            }
+            '\\' => {
+                let escape = match chars.next() {
+                    Some((_, escape)) => escape,
+                    None => break,
+                };
+                let unescaped = match STRING_ESCAPES.iter().find(|(name, _)| *name == escape) {
+                    Some((_, unescaped)) => Some(*unescaped),
+                    // \u and four hex digits
+                    None if escape == 'u' => {
+                        let digits = rest_input[ind + 2..].get(..4).unwrap_or("");
+                        let ch = Some(digits)
+                            .filter(|digits| digits.chars().all(|ch| ch.is_ascii_hexdigit()))
+                            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                            .and_then(std::char::from_u32);
+                        if ch.is_some() {
+                            chars.nth(3);
+                        }
+                        ch
+                    }
+                    None => None,
+                };
+                match unescaped {
+                    Some(unescaped) => string.push(unescaped),
+                    None => {
+                        unsupported.get_or_insert(escape);
+                    }
+                }
+            }
+            _ => string.push(ch),
+        }
+    }
+    Err(Incomplete(nom::Needed::Unknown))
+}
 
-    named!(
-        string_parser<&str, String>,
-        map!(
-            terminated!(take_until!("\""), tag("\"")),
-            |v| String::from(v)
-        )
-    );
+/// The characters a string can have escaped,  ie \n for a newline;  and printed that way by
+/// pr-str
+pub const STRING_ESCAPES: &[(char, char)] = &[
+    ('"', '"'),
+    ('\\', '\\'),
+    ('n', '\n'),
+    ('t', '\t'),
+    ('r', '\r'),
+    ('b', '\u{8}'),
+    ('f', '\u{c}'),
+];
+
+/// s as it's written,  between quotes,  escaped as try_read_string reads it back
+pub fn quote_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for ch in s.chars() {
+        match STRING_ESCAPES
+            .iter()
+            .find(|(_, unescaped)| *unescaped == ch)
+        {
+            Some((name, _)) => {
+                quoted.push('\\');
+                quoted.push(*name);
+            }
+            None => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
 
-    to_value_parser(string_parser)(rest_input)
+/// Tries to parse &str into Value::Regex;  the pattern is compiled as it's read,  and if it
+/// can't be,  what's read is a condition saying why
+/// Example Successes:
+///    #"a\d+" => Value::Regex(..)
+pub fn try_read_regex(input: &str) -> IResult<&str, Value> {
+    named!(regex_start<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("#\"")));
+    let (rest_input, _) = regex_start(input)?;
+    // Escapes are left for the regex to make sense of;  only \" has to be skipped over here
+    let mut escaped = false;
+    for (ind, ch) in rest_input.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Ok((&rest_input[ind + 1..], pattern::compile(&rest_input[..ind]))),
+            _ => {}
+        }
+    }
+    Err(Incomplete(nom::Needed::Unknown))
+}
+
+/// The characters that are written by name,  ie \newline;  and printed that way by pr-str
+pub const CHAR_NAMES: &[(&str, char)] = &[
+    ("newline", '\n'),
+    ("space", ' '),
+    ("tab", '\t'),
+    ("return", '\r'),
+    ("backspace", '\u{8}'),
+    ("formfeed", '\u{c}'),
+];
+
+/// Tries to parse &str into Value::Char;  a character,  a name from CHAR_NAMES,  \uXXXX (in
+/// hex) or \oNNN (in octal,  up to \o377).  Anything else that's a word,  ie \foo,  is read
+/// as a condition saying it's no character
+/// Example Successes:
+///    \a => Value::Char('a')
+///    \newline => Value::Char('\n')
+///    \u00e9 => Value::Char('é')
+pub fn try_read_char(input: &str) -> IResult<&str, Value> {
+    named!(backslash<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("\\")));
+    let (rest_input, _) = backslash(input)?;
+    let first = match rest_input.chars().next() {
+        Some(first) => first,
+        None => return Err(Incomplete(nom::Needed::Size(1))),
+    };
+    // A word is read whole,  but anything else is only the one character
+    let len = if first.is_alphanumeric() {
+        rest_input
+            .find(|ch: char| !ch.is_alphanumeric())
+            .unwrap_or(rest_input.len())
+    } else {
+        first.len_utf8()
+    };
+    let (token, rest_input) = rest_input.split_at(len);
+    if token.chars().count() == 1 {
+        return Ok((rest_input, Value::Char(first)));
+    }
+    let code = |digits: &str, radix: u32, max: u32| {
+        u32::from_str_radix(digits, radix)
+            .ok()
+            .filter(|code| *code <= max)
+            .and_then(std::char::from_u32)
+    };
+    let ch = match CHAR_NAMES.iter().find(|(name, _)| *name == token) {
+        Some((_, ch)) => Some(*ch),
+        None if token.starts_with('u') && token.len() == 5 => code(&token[1..], 16, 0xFFFF),
+        None if token.starts_with('o') && token.len() <= 4 => code(&token[1..], 8, 0o377),
+        None => None,
+    };
+    match ch {
+        Some(ch) => Ok((rest_input, Value::Char(ch))),
+        None => Ok((
+            rest_input,
+            Value::Condition(format!("Unsupported character: \\{}", token).into()),
+        )),
+    }
+}
+
+/// Reads #tag form as what tag's reader fn makes of form;  see data_readers.rs
+/// Example Successes:
+///    #inst "2026-10-16"   => Value::Inst(..)
+///    #my/point [1 2]      => whatever the fn *data-readers* has for my/point gives
+pub fn try_read_tagged(input: &str) -> IResult<&str, Value> {
+    named!(hash<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("#")));
+    let (tag_input, _) = hash(input)?;
+    let (form_input, tag) = symbol_parser(tag_input)?;
+    let (rest_input, form) = try_read(form_input)?;
+    match EDN_OPTIONS.with(|options| options.borrow().clone()) {
+        Some(options) => Ok((rest_input, options.read_tagged(&tag, form))),
+        None => Ok((rest_input, data_readers::read_tagged(&tag, form))),
+    }
 }
 
 // @TODO Perhaps generalize this, or even generalize it as a reader macro
@@ -396,19 +639,42 @@ pub fn try_read_map(input: &str) -> IResult<&str, Value> {
     named!(lbracep<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("{")));
     named!(rbracep<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("}")));
     let (map_inner_input, _) = lbracep(input)?;
-    let mut map_as_vec: Vec<MapEntry> = Vec::new();
+    // Built up in place,  as with a transient
+    let mut map_edits = MapEdits::default();
     let mut rest_input = map_inner_input;
     loop {
         let right_brace = rbracep(rest_input);
         if let Ok((after_map_input, _)) = right_brace {
-            return Ok((after_map_input, map_as_vec.into_list_map().to_value()));
+            return Ok((after_map_input, map_edits.into_map().to_value()));
         }
         let (_rest_input, next_key) = try_read(rest_input)?;
         let (_rest_input, next_val) = try_read(_rest_input)?;
-        map_as_vec.push(MapEntry {
-            key: Rc::new(next_key),
-            val: Rc::new(next_val),
-        });
+        map_edits.assoc(Arc::new(next_key), Arc::new(next_val));
+        rest_input = _rest_input;
+    }
+}
+
+/// Tries to parse &str into Value::PersistentHashSet
+/// Example Successes:
+///    #{1 2 3} => Value::PersistentHashSet(..)
+pub fn try_read_set(input: &str) -> IResult<&str, Value> {
+    named!(lsetp<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("#{")));
+    named!(rbracep<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("}")));
+    let (set_inner_input, _) = lsetp(input)?;
+    let mut set_as_vec = Vec::new();
+    let mut rest_input = set_inner_input;
+    loop {
+        if let Ok((after_set_input, _)) = rbracep(rest_input) {
+            return Ok((
+                after_set_input,
+                set_as_vec
+                    .into_iter()
+                    .collect::<PersistentHashSet>()
+                    .to_value(),
+            ));
+        }
+        let (_rest_input, form) = try_read(rest_input)?;
+        set_as_vec.push(form.to_rc_value());
         rest_input = _rest_input;
     }
 }
@@ -416,26 +682,27 @@ pub fn try_read_map(input: &str) -> IResult<&str, Value> {
 // @TODO use nom functions in place of macro
 /// Tries to parse &str into Value::PersistentVector
 /// Example Successes:
-///    [1 2 3] => Value::PersistentVector(PersistentVector { vals: [Rc(Value::I32(1) ... ]})
+///    [1 2 3] => Value::PersistentVector(PersistentVector { vals: [Arc(Value::I32(1) ... ]})
 ///    [1 2 [5 10 15] 3]
-///      => Value::PersistentVector(PersistentVector { vals: [Rc(Value::I32(1) .. Rc(Value::PersistentVector..)]})
+///      => Value::PersistentVector(PersistentVector { vals: [Arc(Value::I32(1) .. Arc(Value::PersistentVector..)]})
 pub fn try_read_vector(input: &str) -> IResult<&str, Value> {
     named!(lbracketp<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("[")));
     named!(rbracketp<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("]")));
     let (vector_inner_input, _) = lbracketp(input)?;
-    let mut vector_as_vec = Vec::new();
+    // Built up in place,  as with a transient
+    let mut vector = PersistentVector::empty();
     // What's left of our input as we read more of our PersistentVector
     let mut rest_input = vector_inner_input;
     loop {
         // Try parse end of vector
         // If we succeeded,  we can convert our vector of values into a PersistentVector and return our success
         if let Ok((after_vector_input, _)) = rbracketp(rest_input) {
-            return Ok((after_vector_input, vector_as_vec.into_vector().to_value()));
+            return Ok((after_vector_input, vector.to_value()));
         }
 
         // Otherwise, we need to keep reading until we get that closing bracket letting us know we're finished
         let (_rest_input, form) = try_read(rest_input)?;
-        vector_as_vec.push(form.to_rc_value());
+        vector.conj_in_place(form.to_rc_value());
         rest_input = _rest_input;
     }
 }
@@ -449,7 +716,9 @@ pub fn try_read_list(input: &str) -> IResult<&str, Value> {
     let mut rest_input = list_inner_input;
     loop {
         if let Ok((after_list_input, _)) = rparenp(rest_input) {
-            return Ok((after_list_input, list_as_vec.into_list().to_value()));
+            let list = Arc::new(list_as_vec.into_list());
+            record_position(&list, input.trim_start_matches(is_clojure_whitespace));
+            return Ok((after_list_input, Value::PersistentList(list)));
         }
         let (_rest_input, form) = try_read(rest_input)?;
         list_as_vec.push(form.to_rc_value());
@@ -476,16 +745,643 @@ pub fn try_read_quoted(input: &str) -> IResult<&str, Value> {
     ))
 }
 
+/// Reads @form as (deref form)
+pub fn try_read_deref(input: &str) -> IResult<&str, Value> {
+    named!(at_sign<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("@")));
+
+    let (form, _) = at_sign(input)?;
+
+    let (rest_input, derefed_form_value) = try_read(form)?;
+
+    Ok((
+        rest_input,
+        vec![
+            Symbol::intern("deref").to_rc_value(),
+            derefed_form_value.to_rc_value(),
+        ]
+        .into_list()
+        .to_value(),
+    ))
+}
+
+/// Reads #'sym as (var sym)
+pub fn try_read_var(input: &str) -> IResult<&str, Value> {
+    named!(hash_quote<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("#'")));
+
+    let (form, _) = hash_quote(input)?;
+
+    let (rest_input, var_form_value) = try_read(form)?;
+
+    Ok((
+        rest_input,
+        vec![
+            Symbol::intern("var").to_rc_value(),
+            var_form_value.to_rc_value(),
+        ]
+        .into_list()
+        .to_value(),
+    ))
+}
+
+/// Reads ~form as (clojure.core/unquote form),  and ~@form as
+/// (clojure.core/unquote-splicing form);  for a syntax-quote around them to fill in
+pub fn try_read_unquote(input: &str) -> IResult<&str, Value> {
+    named!(tilde<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("~")));
+
+    let (form, _) = tilde(input)?;
+    let (form, splicing) = opt(tag("@"))(form)?;
+    let (rest_input, unquoted_form_value) = try_read(form)?;
+
+    let name = if splicing.is_some() {
+        "unquote-splicing"
+    } else {
+        "unquote"
+    };
+    Ok((
+        rest_input,
+        vec![
+            Symbol::intern_with_ns("clojure.core", name).to_rc_value(),
+            unquoted_form_value.to_rc_value(),
+        ]
+        .into_list()
+        .to_value(),
+    ))
+}
+
+thread_local! {
+    // The namespace syntax-quote qualifies symbols with;  the current namespace,  kept up
+    // to date by the environment,  as *ns* is in Clojure
+    static CURRENT_NAMESPACE: RefCell<String> = RefCell::new(String::from("user"));
+}
+
+/// Makes name the namespace syntax-quote qualifies symbols with,  from now on
+pub fn set_current_namespace(name: &str) {
+    CURRENT_NAMESPACE.with(|current| *current.borrow_mut() = String::from(name));
+}
+
+/// The namespace syntax-quote qualifies symbols with
+pub fn current_namespace() -> String {
+    CURRENT_NAMESPACE.with(|current| current.borrow().clone())
+}
+
+/// Reads `form as the code that builds form;  with each symbol in it qualified with the
+/// current namespace (or for x#,  replaced with a gensym),  and each ~x and ~@xs in it
+/// replaced with the value of x,  and each value of xs
+/// Example Successes:
+///    `(a ~b ~@cs) => (clojure.core/seq (clojure.core/concat (clojure.core/list (quote user/a))
+///                                                            (clojure.core/list b)
+///                                                            cs))
+///    `[x# :k]     => (clojure.core/into [] (clojure.core/concat
+///                                              (clojure.core/list (quote x__1__auto__))
+///                                              (clojure.core/list :k)))
+pub fn try_read_syntax_quote(input: &str) -> IResult<&str, Value> {
+    named!(backtick<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("`")));
+
+    let (form, _) = backtick(input)?;
+    let (rest_input, form) = try_read(form)?;
+
+    // Past the `,  this can't be read as anything else;  it fails if there's an ~@ outside
+    // of a list or vector
+    let failure = || nom::Err::Failure((input, nom::error::ErrorKind::Verify));
+    let expansion = SyntaxQuote::default()
+        .expand(&form.to_rc_value())
+        .ok_or_else(failure)?;
+    Ok((rest_input, (*expansion).clone()))
+}
+
+/// Clojure's special forms;  which syntax-quote leaves as they are,  rather than qualifying
+/// them
+pub(crate) const SPECIAL_FORMS: &[&str] = &[
+    "def",
+    "loop*",
+    "recur",
+    "if",
+    "case*",
+    "let*",
+    "letfn*",
+    "do",
+    "fn*",
+    "quote",
+    "var",
+    "import*",
+    ".",
+    "set!",
+    "deftype*",
+    "reify*",
+    "try",
+    "throw",
+    "monitor-enter",
+    "monitor-exit",
+    "catch",
+    "finally",
+    "new",
+    "&",
+];
+
+// What syntax-quote makes of a form;  along with the gensym it's given each x# so far,  so
+// that they're the same symbol all through the form
+#[derive(Default)]
+struct SyntaxQuote {
+    gensyms: HashMap<String, Symbol>,
+}
+impl SyntaxQuote {
+    // The code that builds form;  None if it has an ~@ that isn't in a list or vector
+    fn expand(&mut self, form: &Arc<Value>) -> Option<Arc<Value>> {
+        let expansion = match &**form {
+            Value::Symbol(sym) => quoted(self.qualify(sym)).to_rc_value(),
+            Value::PersistentList(plist) => {
+                match unquoted(form) {
+                    Some((false, unquoted_form)) => return Some(unquoted_form),
+                    Some((true, _)) => return None,
+                    None => {}
+                }
+                // () builds itself
+                if plist.len() == 0 {
+                    return Some(Arc::clone(form));
+                }
+                let concatenated = self.concat(plist.iter())?;
+                // A seq can't have metadata;  but then,  a list read in doesn't either
+                return Some(core_call("seq", vec![concatenated]));
+            }
+            Value::PersistentVector(pvector) => core_call(
+                "into",
+                vec![
+                    PersistentVector::empty().to_rc_value(),
+                    self.concat(pvector.iter().cloned())?,
+                ],
+            ),
+            Value::PersistentHashSet(pset) => core_call(
+                "into",
+                vec![
+                    PersistentHashSet::empty().to_rc_value(),
+                    self.concat(pset.iter())?,
+                ],
+            ),
+            // Each entry as a vector of its key and value;  in the order they were read
+            Value::PersistentListMap(plistmap) => {
+                let entries = plistmap
+                    .iter()
+                    .map(|entry| {
+                        let key = self.expand(&entry.key)?;
+                        let val = self.expand(&entry.val)?;
+                        Some(core_call(
+                            "list",
+                            vec![PersistentVector::from(vec![key, val]).to_rc_value()],
+                        ))
+                    })
+                    .collect::<Option<Vec<Arc<Value>>>>()?;
+                core_call(
+                    "into",
+                    vec![
+                        PersistentListMap::Empty.to_rc_value(),
+                        core_call("concat", entries),
+                    ],
+                )
+            }
+            // Keywords,  strings,  numbers and the like build themselves
+            _ => return Some(Arc::clone(form)),
+        };
+        match meta::meta(form) {
+            Some(meta) => Some(core_call("with-meta", vec![expansion, self.expand(&meta)?])),
+            None => Some(expansion),
+        }
+    }
+
+    // The code that builds the concatenation of items;  each one that's ~@xs giving xs,  and
+    // each other one a list of what it builds
+    fn concat(&mut self, items: impl Iterator<Item = Arc<Value>>) -> Option<Arc<Value>> {
+        let parts = items
+            .map(|item| match unquoted(&item) {
+                Some((true, spliced)) => Some(spliced),
+                Some((false, unquoted_item)) => Some(core_call("list", vec![unquoted_item])),
+                None => Some(core_call("list", vec![self.expand(&item)?])),
+            })
+            .collect::<Option<Vec<Arc<Value>>>>()?;
+        Some(core_call("concat", parts))
+    }
+
+    fn qualify(&mut self, sym: &Symbol) -> Symbol {
+        if sym.has_ns() || SPECIAL_FORMS.contains(&sym.name.as_str()) {
+            return sym.clone();
+        }
+        if let Some(prefix) = sym.name.strip_suffix('#') {
+            return self
+                .gensyms
+                .entry(sym.name.to_string())
+                .or_insert_with(|| Symbol::gensym(&format!("{}__", prefix), "__auto__"))
+                .clone();
+        }
+        CURRENT_NAMESPACE.with(|current| Symbol::intern_with_ns(&current.borrow(), &sym.name))
+    }
+}
+
+// If form is ~x,  (false,  x);  if it's ~@x,  (true,  x)
+fn unquoted(form: &Arc<Value>) -> Option<(bool, Arc<Value>)> {
+    let plist = match &**form {
+        Value::PersistentList(plist) if plist.len() == 2 => plist,
+        _ => return None,
+    };
+    let mut items = plist.iter();
+    let splicing = match &*items.next()? {
+        Value::Symbol(sym) if sym.ns == "clojure.core" && sym.name == "unquote" => false,
+        Value::Symbol(sym) if sym.ns == "clojure.core" && sym.name == "unquote-splicing" => true,
+        _ => return None,
+    };
+    Some((splicing, items.next()?))
+}
+
+fn quoted(sym: Symbol) -> Value {
+    vec![Symbol::intern("quote").to_rc_value(), sym.to_rc_value()]
+        .into_list()
+        .to_value()
+}
+
+// (clojure.core/name args..)
+fn core_call(name: &str, args: Vec<Arc<Value>>) -> Arc<Value> {
+    std::iter::once(Symbol::intern_with_ns("clojure.core", name).to_rc_value())
+        .chain(args)
+        .collect::<Vec<Arc<Value>>>()
+        .into_list()
+        .to_rc_value()
+}
+
+/// Reads ^meta form as form,  with meta merged into its metadata
+/// Example Successes:
+///    ^:private x      => x,  with metadata {:private true}
+///    ^String s        => s,  with metadata {:tag String}
+///    ^{:doc "a"} [1]  => [1],  with metadata {:doc "a"}
+pub fn try_read_meta(input: &str) -> IResult<&str, Value> {
+    named!(caret<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("^")));
+
+    let (meta_input, _) = caret(input)?;
+    let (form_input, meta) = try_read(meta_input)?;
+    let (rest_input, form) = try_read(form_input)?;
+
+    // Past the ^,  this can't be read as anything else
+    let failure = || nom::Err::Failure((input, nom::error::ErrorKind::Verify));
+    let meta = meta::normalize(&meta.to_rc_value()).map_err(|_| failure())?;
+    if !meta::supports_meta(&form) {
+        return Err(failure());
+    }
+    meta::set_meta(&form, Some(meta::merged(&form, &meta)));
+    Ok((rest_input, form))
+}
+
+thread_local! {
+    // Whether a #(..) is being read;  as they can't be nested
+    static IN_FN_LITERAL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Reads #(f % %2 %&) as (fn [p1 p2 & rest] (f p1 p2 rest));  % is %1,  and the fn takes as
+/// many args as the highest %n in it says (and more,  if it has %&)
+/// Example Successes:
+///    #(+ % 1)     => (fn [p1__1#] (+ p1__1# 1))
+///    #(vector %&) => (fn [& rest__2#] (vector rest__2#))
+pub fn try_read_fn_literal(input: &str) -> IResult<&str, Value> {
+    named!(hash_paren<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("#(")));
+    let (after_hash_paren, _) = hash_paren(input)?;
+    // The ( is read again,  as the start of the body
+    let body_input = &input[input.len() - after_hash_paren.len() - 1..];
+
+    // Past the #(,  this can't be read as anything else
+    let failure = || nom::Err::Failure((input, nom::error::ErrorKind::Verify));
+    if IN_FN_LITERAL.with(Cell::get) {
+        return Err(failure());
+    }
+    IN_FN_LITERAL.with(|in_fn_literal| in_fn_literal.set(true));
+    let read = try_read_list(body_input);
+    IN_FN_LITERAL.with(|in_fn_literal| in_fn_literal.set(false));
+    let (rest_input, body) = read?;
+
+    let mut args = FnLiteralArgs::default();
+    let body = args.replace(&body.to_rc_value());
+    let mut params = args.positional;
+    if let Some(rest) = args.rest {
+        params.push(Symbol::intern("&"));
+        params.push(rest);
+    }
+    let params = params
+        .into_iter()
+        .map(|param| param.to_rc_value())
+        .collect::<PersistentVector>();
+    Ok((
+        rest_input,
+        vec![
+            Symbol::intern("fn").to_rc_value(),
+            params.to_rc_value(),
+            body,
+        ]
+        .into_list()
+        .to_value(),
+    ))
+}
+
+// The params a #(..) has;  made as its %s are come across
+#[derive(Default)]
+struct FnLiteralArgs {
+    positional: Vec<Symbol>,
+    rest: Option<Symbol>,
+}
+impl FnLiteralArgs {
+    // The param %n stands for;  along with those for each %m before it,  used or not
+    fn positional(&mut self, n: usize) -> Symbol {
+        while self.positional.len() < n {
+            let prefix = format!("p{}__", self.positional.len() + 1);
+            self.positional.push(Symbol::gensym(&prefix, "#"));
+        }
+        self.positional[n - 1].clone()
+    }
+
+    // form,  with each % in it replaced with its param
+    fn replace(&mut self, form: &Arc<Value>) -> Arc<Value> {
+        let replaced = match &**form {
+            Value::Symbol(sym) if !sym.has_ns() && sym.name.starts_with('%') => {
+                let replacement = match &sym.name[1..] {
+                    "" => self.positional(1),
+                    "&" => self
+                        .rest
+                        .get_or_insert_with(|| Symbol::gensym("rest__", "#"))
+                        .clone(),
+                    n => match n.parse::<usize>() {
+                        Ok(n) if n >= 1 => self.positional(n),
+                        _ => return Arc::clone(form),
+                    },
+                };
+                return replacement.to_rc_value();
+            }
+            Value::PersistentList(plist) => {
+                let list = Arc::new(
+                    plist
+                        .iter()
+                        .map(|item| self.replace(&item))
+                        .collect::<Vec<Arc<Value>>>()
+                        .into_list(),
+                );
+                if let Some(position) = trace::position(plist) {
+                    trace::set_position(&list, position);
+                }
+                Value::PersistentList(list)
+            }
+            Value::PersistentVector(pvector) => pvector
+                .iter()
+                .map(|item| self.replace(item))
+                .collect::<PersistentVector>()
+                .to_value(),
+            Value::PersistentListMap(plistmap) => plistmap
+                .iter()
+                .map(|entry| MapEntry {
+                    key: self.replace(&entry.key),
+                    val: self.replace(&entry.val),
+                })
+                .collect::<PersistentListMap>()
+                .to_value(),
+            Value::PersistentHashSet(pset) => pset
+                .iter()
+                .map(|item| self.replace(&item))
+                .collect::<PersistentHashSet>()
+                .to_value(),
+            _ => return Arc::clone(form),
+        };
+        if let Some(meta) = meta::meta(form) {
+            meta::set_meta(&replaced, Some(meta));
+        }
+        replaced.to_rc_value()
+    }
+}
+
+/// The platform reader conditionals pick their branches for;  besides :default
+pub const READER_FEATURE: &str = "cljrs";
+
+/// *features*;  the set of the features reader conditionals pick,  as keywords
+pub fn features() -> Value {
+    std::iter::once(Keyword::intern(READER_FEATURE).to_rc_value())
+        .collect::<PersistentHashSet>()
+        .to_value()
+}
+
+// Reads #?(:feature form ..) as the form for the first feature that's ours;  None if
+// there isn't one,  in which case it's read as nothing at all
+fn reader_conditional(input: &str) -> IResult<&str, Option<Value>> {
+    // Not preceded by consume_clojure_whitespaces_parser,  as that's what reads the ones we
+    // skip with this
+    let list_input = match input
+        .trim_start_matches(is_clojure_whitespace)
+        .strip_prefix("#?")
+    {
+        Some(list_input) => list_input,
+        None => return Err(nom::Err::Error((input, nom::error::ErrorKind::Tag))),
+    };
+    let (rest_input, branches) = try_read_list(list_input)?;
+
+    // Past the #?,  this can't be read as anything else
+    let failure = || nom::Err::Failure((input, nom::error::ErrorKind::Verify));
+    let branches = match branches {
+        Value::PersistentList(branches) => branches.iter().collect::<Vec<Arc<Value>>>(),
+        _ => return Err(failure()),
+    };
+    if branches.len() % 2 != 0 {
+        return Err(failure());
+    }
+    for branch in branches.chunks(2) {
+        match &*branch[0] {
+            Value::Keyword(feature)
+                if !feature.sym.has_ns()
+                    && (feature.sym.name == READER_FEATURE || feature.sym.name == "default") =>
+            {
+                return Ok((rest_input, Some((*branch[1]).clone())))
+            }
+            Value::Keyword(_) => {}
+            _ => return Err(failure()),
+        }
+    }
+    Ok((rest_input, None))
+}
+
+/// Reads #?(:cljrs form :default other-form ..) as the form for the first feature that's
+/// ours;  :cljrs,  or :default.  One without either is skipped over,  as whitespace is
+/// Example Successes:
+///    #?(:clj 1 :cljrs 2) => 2
+pub fn try_read_reader_conditional(input: &str) -> IResult<&str, Value> {
+    match reader_conditional(input)? {
+        (rest_input, Some(form)) => Ok((rest_input, form)),
+        (_, None) => Err(nom::Err::Error((input, nom::error::ErrorKind::Verify))),
+    }
+}
+
+thread_local! {
+    // How tagged literals are read,  if what's being read is EDN
+    static EDN_OPTIONS: RefCell<Option<Arc<edn::Options>>> = const { RefCell::new(None) };
+}
+
+/// Runs f,  reading EDN with options while it does (or if they're None,  code again)
+pub fn with_edn<T>(options: Option<Arc<edn::Options>>, f: impl FnOnce() -> T) -> T {
+    let outer = EDN_OPTIONS.with(|current| current.replace(options));
+    // What f reads is counted against the limits on its own,  even when it's a tag's fn
+    // reading in the middle of a form
+    let outer_counts = COUNTS.with(|counts| counts.replace(Counts::default()));
+    let result = f();
+    COUNTS.with(|counts| counts.set(outer_counts));
+    EDN_OPTIONS.with(|current| *current.borrow_mut() = outer);
+    result
+}
+
+pub const MAX_DEPTH_VAR: &str = "CLOJURE_RS_MAX_READ_DEPTH";
+pub const MAX_ELEMENTS_VAR: &str = "CLOJURE_RS_MAX_READ_ELEMENTS";
+
+/// How much one top level form may hold;  so that reading text from anyone,  over nREPL or
+/// from a file,  can't overflow the stack nesting deeper and deeper,  or run out of memory.
+/// A form past either limit is read as a condition saying so
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    /// How deeply forms may nest in it;  the form itself is at depth 1
+    pub max_depth: usize,
+    /// How many forms it may be made of in all,  counting itself
+    pub max_elements: usize,
+}
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            max_depth: 256,
+            max_elements: 1_000_000,
+        }
+    }
+}
+impl Limits {
+    /// The limits code is read with;  the defaults,  but for what CLOJURE_RS_MAX_READ_DEPTH
+    /// and CLOJURE_RS_MAX_READ_ELEMENTS set
+    pub fn configured() -> Limits {
+        static CONFIGURED: OnceLock<Limits> = OnceLock::new();
+        *CONFIGURED.get_or_init(|| {
+            let var = |name| env::var(name).ok().and_then(|val| val.trim().parse().ok());
+            let defaults = Limits::default();
+            Limits {
+                max_depth: var(MAX_DEPTH_VAR).unwrap_or(defaults.max_depth),
+                max_elements: var(MAX_ELEMENTS_VAR).unwrap_or(defaults.max_elements),
+            }
+        })
+    }
+    // The limits being read with now;  EDN's own,  if it's EDN
+    fn current() -> Limits {
+        EDN_OPTIONS.with(|options| match &*options.borrow() {
+            Some(options) => options.limits,
+            None => Limits::configured(),
+        })
+    }
+}
+
+// How deep into the top level form being read we are,  and how many forms have been read
+// of it so far
+#[derive(Debug, Clone, Copy, Default)]
+struct Counts {
+    depth: usize,
+    elements: usize,
+}
+
+thread_local! {
+    static COUNTS: Cell<Counts> = Cell::new(Counts::default());
+    // Which limit the form being read went past,  once it has
+    static EXCEEDED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Counts one more form,  one deeper than the one it's in;  failing past the limits,  so that
+// no more of the top level form is read
+fn enter(input: &str) -> Result<(), nom::Err<(&str, nom::error::ErrorKind)>> {
+    let limits = Limits::current();
+    let counts = COUNTS.with(|counts| {
+        let mut current = counts.get();
+        current.depth += 1;
+        current.elements += 1;
+        counts.set(current);
+        current
+    });
+    let exceeded = if counts.depth > limits.max_depth {
+        format!("forms nest more than {} deep", limits.max_depth)
+    } else if counts.elements > limits.max_elements {
+        format!("more than {} forms", limits.max_elements)
+    } else {
+        return Ok(());
+    };
+    EXCEEDED.with(|message| *message.borrow_mut() = Some(exceeded));
+    Err(nom::Err::Failure((input, nom::error::ErrorKind::TooLarge)))
+}
+
+fn reading_edn() -> bool {
+    EDN_OPTIONS.with(|options| options.borrow().is_some())
+}
+
+/// Reads EDN,  while with_edn says to;  only data,  so none of the forms that are
+/// only used in code,  like 'x,  @x,  ^meta x or #(..)
+fn try_read_edn(input: &str) -> IResult<&str, Value> {
+    preceded(
+        consume_clojure_whitespaces_parser,
+        alt((
+            try_read_nil,
+            try_read_map,
+            try_read_string,
+            try_read_char,
+            try_read_set,
+            try_read_tagged,
+            try_read_number,
+            try_read_bool,
+            try_read_symbol,
+            try_read_keyword,
+            try_read_list,
+            try_read_vector,
+        )),
+    )(input)
+}
+
+/// Reads one form;  past the limits,  a condition saying which was exceeded,  in place of
+/// the rest of input
 pub fn try_read(input: &str) -> IResult<&str, Value> {
+    let outermost = COUNTS.with(|counts| counts.get().depth == 0);
+    let read = enter(input).and_then(|()| try_read_form(input));
+    COUNTS.with(|counts| {
+        let current = counts.get();
+        counts.set(Counts {
+            depth: current.depth - 1,
+            elements: if outermost { 0 } else { current.elements },
+        })
+    });
+    match read {
+        Err(nom::Err::Failure((_, nom::error::ErrorKind::TooLarge))) if outermost => {
+            let exceeded = EXCEEDED.with(|message| message.borrow_mut().take());
+            let message = format!(
+                "Reader Error: limits exceeded;  {}",
+                exceeded.unwrap_or_default()
+            );
+            Ok((&input[input.len()..], Value::Condition(message.into())))
+        }
+        read => read,
+    }
+}
+
+fn try_read_form(input: &str) -> IResult<&str, Value> {
+    if reading_edn() {
+        return try_read_edn(input);
+    }
     preceded(
         consume_clojure_whitespaces_parser,
         alt((
             try_read_quoted,
+            try_read_syntax_quote,
+            try_read_unquote,
+            try_read_deref,
+            try_read_meta,
             try_read_nil,
             try_read_map,
             try_read_string,
-            try_read_f64,
-            try_read_i32,
+            try_read_char,
+            // The forms that start with a #
+            alt((
+                try_read_set,
+                try_read_regex,
+                try_read_var,
+                try_read_fn_literal,
+                try_read_reader_conditional,
+                try_read_tagged,
+            )),
+            try_read_number,
             try_read_bool,
             try_read_nil,
             try_read_symbol,
@@ -505,39 +1401,377 @@ pub fn try_read(input: &str) -> IResult<&str, Value> {
 //
 ///////////////////////////////////////////////////////////////////////////////////////////////////
 
-// This is the high level read function that Clojure RS wraps
+/// The next form from reader;  what's after it on the last line it's on is lost.  A
+/// StreamReader keeps that,  for reading form after form
 pub fn read<R: BufRead>(reader: &mut R) -> Value {
-    // This is a buffer that will accumulate if a read requires more
-    // text to make sense, such as trying to read (+ 1
-    let mut input_buffer = String::new();
+    StreamReader::default().read(reader)
+}
 
-    // Ask for a line from the reader, try to read, and if unable (because we need more text),
-    // loop over and ask for more lines, accumulating them in input_buffer until we can read
-    loop {
-        let maybe_line = reader.by_ref().lines().next();
-        match maybe_line {
-            Some(Err(e)) => return Value::Condition(format!("Reader error: {}", e)),
-            Some(Ok(line)) => input_buffer.push_str(&line),
-            None => {
-                return Value::Condition(String::from("Tried to read empty stream; unexpected EOF"))
+/// Reads form after form from a stream,  a line at a time as they're needed;  keeping what
+/// it's read past the end of a form for the next,  so (def a 1) (def b 2) on one line is two
+/// forms.  Comments and whitespace between forms are skipped,  and the stream ending in the
+/// middle of a form is a condition saying so
+#[derive(Debug)]
+pub struct StreamReader {
+    // The lines read from the stream from the one the last form read ended on;  and the
+    // offset in them of what's not been read as a form yet,  so positions in traces are
+    // right even for a form after another on its line
+    text: String,
+    ind: usize,
+    // The line of the stream text starts on
+    line: usize,
+}
+impl Default for StreamReader {
+    fn default() -> StreamReader {
+        StreamReader {
+            text: String::new(),
+            ind: 0,
+            line: 1,
+        }
+    }
+}
+impl StreamReader {
+    /// The next form,  reading more of reader if it needs to
+    pub fn read<R: BufRead>(&mut self, reader: &mut R) -> Value {
+        loop {
+            let source = Source::new(NO_SOURCE_FILE, self.line, &blank_comments(&self.text));
+            let input = skip_blanks(&source.text()[self.ind..]);
+            if !input.is_empty() {
+                match source.reading(|| try_read(input)) {
+                    Ok((rest, form)) => {
+                        self.consume(source.text().len() - rest.len());
+                        return form;
+                    }
+                    // Needs more lines
+                    Err(Incomplete(_)) => {}
+                    Err(_) => {
+                        let position = source.position_of(input).unwrap();
+                        self.forget();
+                        return Value::Condition(
+                            format!("Reader Error: could not read the form at {}", position).into(),
+                        );
+                    }
+                }
+            }
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Err(e) => {
+                    self.forget();
+                    return Value::Condition(format!("Reader error: {}", e).into());
+                }
+                Ok(0) if input.is_empty() => {
+                    self.forget();
+                    return Value::Condition("Tried to read empty stream; unexpected EOF".into());
+                }
+                Ok(0) => {
+                    let position = source.position_of(input).unwrap();
+                    self.forget();
+                    return Value::Condition(
+                        format!(
+                            "Reader Error: EOF while reading;  the form at {} is never closed",
+                            position
+                        )
+                        .into(),
+                    );
+                }
+                Ok(_) => {
+                    self.text.push_str(&line);
+                    // So a symbol or number at the very end is read as complete
+                    if !line.ends_with('\n') {
+                        self.text.push('\n');
+                    }
+                }
             }
         }
+    }
+    /// Whether there's nothing but whitespace and comments after the last form read
+    pub fn is_between_forms(&self) -> bool {
+        skip_blanks(&blank_comments(&self.text)[self.ind..]).is_empty()
+    }
+    /// Forgets what's been read past the last form;  as when what's typed is given up on
+    pub fn forget(&mut self) {
+        self.consume(self.text.len());
+    }
+    // Takes what's unread to start at ind,  dropping the lines before the one it's on
+    fn consume(&mut self, ind: usize) {
+        let line_start = self.text[..ind]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+        self.line += self.text[..line_start].matches('\n').count();
+        self.text.drain(..line_start);
+        self.ind = ind - line_start;
+    }
+}
+
+/// Where something starts in the text it was read from;  line and column both count from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub col: usize,
+}
+impl Position {
+    // The position of byte offset ind in source
+    fn of(source: &str, ind: usize) -> Position {
+        let before = &source[..ind];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Position {
+            line: before.matches('\n').count() + 1,
+            col: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// What the file of forms read from something other than a file is called in traces
+pub const NO_SOURCE_FILE: &str = "NO_SOURCE_FILE";
+
+/// Text being read,  and the file it's from;  while it's being read with Source::reading,
+/// where each list read from it starts is recorded,  for traces to point to
+pub struct Source {
+    file: Arc<str>,
+    text: Arc<str>,
+    // The line of file that text starts on
+    first_line: usize,
+    // The byte offset each line of text starts at
+    line_starts: Vec<usize>,
+}
 
-        let line_read = try_read(&input_buffer);
-        match line_read {
-            Ok((_, value)) => return value,
-            // Continue accumulating more input
-            Err(Incomplete(_)) => continue,
+thread_local! {
+    // The source being read with Source::reading,  if any
+    static READING: RefCell<Option<Arc<Source>>> = const { RefCell::new(None) };
+}
+
+impl Source {
+    pub fn new(file: &str, first_line: usize, text: &str) -> Arc<Source> {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(ind, _)| ind + 1))
+            .collect();
+        Arc::new(Source {
+            file: file.into(),
+            text: text.into(),
+            first_line,
+            line_starts,
+        })
+    }
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    /// Runs f,  recording where each list it reads from text() starts
+    pub fn reading<T>(self: &Arc<Self>, f: impl FnOnce() -> T) -> T {
+        let outer = READING.with(|reading| reading.replace(Some(Arc::clone(self))));
+        let result = f();
+        READING.with(|reading| *reading.borrow_mut() = outer);
+        result
+    }
+    // Where input starts in file;  None unless input is the rest of text from some point
+    fn position_of(&self, input: &str) -> Option<SourcePosition> {
+        let ind = (input.as_ptr() as usize).checked_sub(self.text.as_ptr() as usize)?;
+        if ind + input.len() != self.text.len() {
+            return None;
+        }
+        let line = self.line_starts.partition_point(|&start| start <= ind);
+        let line_start = self.line_starts[line - 1];
+        Some(SourcePosition {
+            file: Arc::clone(&self.file),
+            line: self.first_line + line - 1,
+            col: self.text[line_start..ind].chars().count() + 1,
+        })
+    }
+}
+
+// Records where list,  just read from input,  starts;  if input is part of a source being read
+fn record_position(list: &Arc<PersistentList>, input: &str) {
+    let position = READING.with(|reading| {
+        reading
+            .borrow()
+            .as_ref()
+            .and_then(|source| source.position_of(input))
+    });
+    if let Some(position) = position {
+        trace::set_position(list, position);
+    }
+}
+
+/// Reads the forms of a file's source in turn,  recording where each list starts;  a
+/// syntax error is given as a condition,  and is the last thing read
+pub struct SourceReader {
+    source: Arc<Source>,
+    ind: usize,
+    failed: bool,
+}
+impl SourceReader {
+    pub fn new(file: &str, source: &str) -> SourceReader {
+        // The trailing newline lets a symbol or number at the very end be read as complete
+        let text = blank_comments(source) + "\n";
+        SourceReader {
+            source: Source::new(file, 1, &text),
+            ind: 0,
+            failed: false,
+        }
+    }
+}
+impl Iterator for SourceReader {
+    type Item = Value;
+    fn next(&mut self) -> Option<Value> {
+        let source = Arc::clone(&self.source);
+        let input = skip_blanks(&source.text()[self.ind..]);
+        if self.failed || input.is_empty() {
+            return None;
+        }
+        let read = source.reading(|| try_read(input));
+        match read {
+            Ok((rest, form)) => {
+                self.ind = source.text().len() - rest.len();
+                Some(form)
+            }
             Err(err) => {
-                return Value::Condition(format!(
-                    "Reader Error: could not read next form; {:?}",
-                    err
-                ))
+                self.failed = true;
+                let position = source.position_of(input).unwrap();
+                let message = match err {
+                    Incomplete(_) => format!(
+                        "Reader Error: EOF while reading;  the form at {} is never closed",
+                        position
+                    ),
+                    _ => format!("Reader Error: could not read the form at {}", position),
+                };
+                Some(Value::Condition(message.into()))
             }
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadError {
+    pub position: Position,
+    pub message: String,
+}
+
+/// What read_lenient makes of a source;  every form it could read,  with where each one
+/// starts,  and every syntax error it had to skip past
+#[derive(Debug, Default)]
+pub struct LenientRead {
+    pub forms: Vec<(Position, Value)>,
+    pub errors: Vec<ReadError>,
+}
+
+/// The text of the form that starts on line (counting from 1) of source,  as it's written
+/// there;  None if none can be read from there
+pub fn form_at_line(source: &str, line: usize) -> Option<&str> {
+    let start = source
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>();
+    let text = blank_comments(source) + "\n";
+    let input = skip_blanks(text.get(start..)?);
+    let (rest, _) = try_read(input).ok()?;
+    let end = (text.len() - rest.len()).min(source.len());
+    source.get(text.len() - input.len()..end)
+}
+
+// input,  past the whitespace at its start;  and the forms that read as nothing,  ie #_ form
+fn skip_blanks(input: &str) -> &str {
+    let input = input.trim_start_matches(is_clojure_whitespace);
+    consume_clojure_whitespaces_parser(input).map_or(input, |(rest_input, _)| rest_input)
+}
+
+// source,  with each comment blanked out (keeping every other byte where it was),  as
+// try_read doesn't know about them
+fn blank_comments(source: &str) -> String {
+    let mut blanked = String::with_capacity(source.len());
+    let mut in_string = false;
+    let mut in_comment = false;
+    let mut escaped = false;
+    for ch in source.chars() {
+        match ch {
+            // As in a regex,  #"\"";  the quote doesn't end it
+            _ if escaped => escaped = false,
+            '\n' => in_comment = false,
+            _ if in_comment => {
+                blanked.extend(std::iter::repeat_n(' ', ch.len_utf8()));
+                continue;
+            }
+            // Or a character,  as in \;
+            '\\' => escaped = true,
+            '"' => in_string = !in_string,
+            ';' if !in_string => {
+                in_comment = true;
+                blanked.push(' ');
+                continue;
+            }
+            _ => {}
+        }
+        blanked.push(ch);
+    }
+    blanked
+}
+
+// Where to carry on reading after a form that couldn't be read,  that starts at ind;  the
+// start of the next line that starts with something other than a closing bracket,  as
+// that's most likely the next top level form
+fn next_top_level_form(source: &str, ind: usize) -> Option<usize> {
+    let mut line_start = ind + source[ind..].find('\n')? + 1;
+    loop {
+        match source[line_start..].chars().next() {
+            Some(ch) if !is_clojure_whitespace(ch) && !matches!(ch, ')' | ']' | '}') => {
+                return Some(line_start)
+            }
+            Some(_) => line_start += source[line_start..].find('\n')? + 1,
+            None => return None,
+        }
+    }
+}
+
+/// Reads every form in source,  without giving up at the first syntax error;  instead,  the
+/// error is recorded with where the form it's in starts,  and reading resumes at the next
+/// top level form.  For tools that have to work on code that's still being written
+pub fn read_lenient(source: &str) -> LenientRead {
+    // The trailing newline lets a symbol or number at the very end be read as complete
+    let text = blank_comments(source) + "\n";
+    let mut read = LenientRead::default();
+    let mut ind = 0;
+    loop {
+        ind = text.len() - skip_blanks(&text[ind..]).len();
+        if ind >= source.len() {
+            return read;
+        }
+        let position = Position::of(source, ind);
+        let resume_at = match try_read(&text[ind..]) {
+            Ok((rest, form)) => {
+                read.forms.push((position, form));
+                ind = text.len() - rest.len();
+                continue;
+            }
+            Err(Incomplete(_)) => {
+                read.errors.push(ReadError {
+                    position,
+                    message: String::from("EOF while reading;  this form is never closed"),
+                });
+                next_top_level_form(&text, ind)
+            }
+            Err(_) => match text[ind..].chars().next() {
+                Some(ch @ (')' | ']' | '}')) => {
+                    read.errors.push(ReadError {
+                        position,
+                        message: format!("Unmatched delimiter: {}", ch),
+                    });
+                    Some(ind + 1)
+                }
+                _ => {
+                    read.errors.push(ReadError {
+                        position,
+                        message: String::from("Could not read this form"),
+                    });
+                    next_top_level_form(&text, ind)
+                }
+            },
+        };
+        match resume_at {
+            Some(resume_at) => ind = resume_at,
+            None => return read,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -694,6 +1928,11 @@ mod tests {
                 try_read_bool("false ").ok().unwrap().1
             );
         }
+
+        #[test]
+        fn try_read_boolean_prefixed_symbol_test() {
+            assert!(try_read_bool("true? ").is_err());
+        }
     }
 
     mod try_read_nil_tests {
@@ -710,11 +1949,12 @@ mod tests {
         use crate::reader::try_read_symbol;
         use crate::symbol::Symbol;
         use crate::value::Value;
+        use std::sync::Arc;
 
         #[test]
         fn try_read_minus_as_valid_symbol_test() {
             assert_eq!(
-                Value::Symbol(Symbol::intern("-")),
+                Value::Symbol(Arc::new(Symbol::intern("-"))),
                 try_read_symbol("- ").unwrap().1
             );
         }
@@ -722,17 +1962,19 @@ mod tests {
 
     mod try_read_tests {
         use crate::persistent_list;
+        use crate::persistent_list::ToPersistentListIter;
         use crate::persistent_list_map;
         use crate::persistent_vector;
         use crate::reader::try_read;
         use crate::symbol::Symbol;
         use crate::value::Value;
         use crate::value::Value::{PersistentList, PersistentListMap, PersistentVector};
+        use std::sync::Arc;
 
         #[test]
         fn try_read_empty_map_test() {
             assert_eq!(
-                PersistentListMap(persistent_list_map::PersistentListMap::Empty),
+                PersistentListMap(Arc::new(persistent_list_map::PersistentListMap::Empty)),
                 try_read("{} ").ok().unwrap().1
             );
         }
@@ -740,11 +1982,104 @@ mod tests {
         #[test]
         fn try_read_string_test() {
             assert_eq!(
-                Value::String(String::from("a string")),
+                Value::String("a string".into()),
                 try_read("\"a string\" ").ok().unwrap().1
             );
         }
 
+        #[test]
+        fn try_read_regex_test() {
+            let regex = try_read("#\"a\\\"; \\d\" ").ok().unwrap().1;
+            assert_eq!("#\"a\\\"; \\d\"", regex.to_string());
+            assert!(matches!(regex, Value::Regex(_)));
+            assert!(try_read("#\"a\\\"").is_err());
+        }
+
+        #[test]
+        fn try_read_char_test() {
+            let chars = try_read("[\\a \\( \\\\ \\newline \\space \\u00e9 \\o101 \\u \\o] ")
+                .ok()
+                .unwrap()
+                .1;
+            assert_eq!(
+                "[\\a \\( \\\\ \\newline \\space \\é \\A \\u \\o]",
+                chars.to_string()
+            );
+            assert_eq!(
+                Value::Condition("Unsupported character: \\foo".into()),
+                try_read("\\foo ").ok().unwrap().1
+            );
+            assert_eq!(
+                Value::Condition("Unsupported character: \\o400".into()),
+                try_read("\\o400 ").ok().unwrap().1
+            );
+        }
+
+        #[test]
+        fn try_read_fn_literal_test() {
+            let f = try_read("#(+ % %3 %&) ").ok().unwrap().1;
+            let parts = match &f {
+                Value::PersistentList(plist) => plist.iter().collect::<Vec<_>>(),
+                _ => panic!("#(..) should be read as a list, is: {}", f),
+            };
+            let params = match &*parts[1] {
+                Value::PersistentVector(pvector) => pvector.iter().cloned().collect::<Vec<_>>(),
+                _ => panic!("#(..) should have a param vector, has: {}", parts[1]),
+            };
+            assert_eq!("fn", parts[0].to_string());
+            assert_eq!("&", params[3].to_string());
+            assert_eq!(
+                format!("(+ {} {} {})", params[0], params[2], params[4]),
+                parts[2].to_string()
+            );
+            assert!(try_read("#(+ #(%) 1) ").is_err());
+        }
+
+        #[test]
+        fn try_read_discard_test() {
+            assert_eq!(
+                "[1 5 (a)]",
+                try_read("[1 #_ 2 #_#_ 3 4 5 (a #_b) #_ 6] ")
+                    .ok()
+                    .unwrap()
+                    .1
+                    .to_string()
+            );
+        }
+
+        #[test]
+        fn try_read_reader_conditional_test() {
+            assert_eq!(
+                "[2 4 5]",
+                try_read("[#?(:clj 1 :cljrs 2) #?(:clj 3) #?(:cljs 4 :default 4) 5] ")
+                    .ok()
+                    .unwrap()
+                    .1
+                    .to_string()
+            );
+            assert!(try_read("#?(:clj) ").is_err());
+        }
+
+        #[test]
+        fn try_read_tagged_test() {
+            assert_eq!(
+                Value::Inst(86_400_000),
+                try_read("#inst \"1970-01-02\" ").ok().unwrap().1
+            );
+            assert_eq!(
+                Value::Condition("Unrecognized date/time syntax: 1970-01-32".into()),
+                try_read("#inst\"1970-01-32\" ").ok().unwrap().1
+            );
+            assert_eq!(
+                "[#uuid \"00000000-0000-0000-0000-0000000000ff\" :rest]",
+                try_read("[#uuid \"00000000-0000-0000-0000-0000000000FF\" :rest] ")
+                    .ok()
+                    .unwrap()
+                    .1
+                    .to_string()
+            );
+        }
+
         #[test]
         fn try_read_int_test() {
             assert_eq!(Value::I32(1), try_read("1 ").ok().unwrap().1);
@@ -760,10 +2095,34 @@ mod tests {
             assert_eq!(Value::I32(-1), try_read("-1-2 ").ok().unwrap().1);
         }
 
+        #[test]
+        fn try_read_number_test() {
+            assert_eq!(
+                "[1/3 2 -5N 1.50M 2M 9999999999N 0.5]",
+                try_read("[1/3 4/2 -5N 1.50M 2M 9999999999 0.5] ")
+                    .ok()
+                    .unwrap()
+                    .1
+                    .to_string()
+            );
+        }
+
+        #[test]
+        fn try_read_slash_as_valid_symbol_test() {
+            assert_eq!(
+                Value::Symbol(Arc::new(Symbol::intern_with_ns("clojure.core", "/"))),
+                try_read("clojure.core// ").ok().unwrap().1
+            );
+            assert_eq!(
+                Value::Symbol(Arc::new(Symbol::intern_with_ns("", "/"))),
+                try_read("/ ").ok().unwrap().1
+            );
+        }
+
         #[test]
         fn try_read_valid_symbol_test() {
             assert_eq!(
-                Value::Symbol(Symbol::intern("my-symbol")),
+                Value::Symbol(Arc::new(Symbol::intern("my-symbol"))),
                 try_read("my-symbol ").ok().unwrap().1
             );
         }
@@ -771,7 +2130,7 @@ mod tests {
         #[test]
         fn try_read_minus_as_valid_symbol_test() {
             assert_eq!(
-                Value::Symbol(Symbol::intern("-")),
+                Value::Symbol(Arc::new(Symbol::intern("-"))),
                 try_read("- ").ok().unwrap().1
             );
         }
@@ -779,7 +2138,7 @@ mod tests {
         #[test]
         fn try_read_minus_prefixed_as_valid_symbol_test() {
             assert_eq!(
-                Value::Symbol(Symbol::intern("-prefixed")),
+                Value::Symbol(Arc::new(Symbol::intern("-prefixed"))),
                 try_read("-prefixed ").ok().unwrap().1
             );
         }
@@ -787,7 +2146,7 @@ mod tests {
         #[test]
         fn try_read_empty_list_test() {
             assert_eq!(
-                PersistentList(persistent_list::PersistentList::Empty),
+                PersistentList(Arc::new(persistent_list::PersistentList::Empty)),
                 try_read("() ").ok().unwrap().1
             );
         }
@@ -795,7 +2154,7 @@ mod tests {
         #[test]
         fn try_read_empty_vector_test() {
             assert_eq!(
-                PersistentVector(persistent_vector::PersistentVector { vals: [].to_vec() }),
+                PersistentVector(Arc::new(persistent_vector::PersistentVector::empty())),
                 try_read("[] ").ok().unwrap().1
             );
         }
@@ -809,6 +2168,84 @@ mod tests {
         fn try_read_bool_false_test() {
             assert_eq!(Value::Boolean(false), try_read("false ").ok().unwrap().1)
         }
+
+        #[test]
+        fn try_read_set_test() {
+            assert_eq!("#{1}", try_read("#{1 1} ").ok().unwrap().1.to_string());
+            assert_eq!("#{}", try_read("#{} ").ok().unwrap().1.to_string());
+        }
+
+        #[test]
+        fn try_read_deref_test() {
+            assert_eq!("(deref p)", try_read("@p ").ok().unwrap().1.to_string());
+        }
+
+        #[test]
+        fn try_read_syntax_quote_test() {
+            crate::reader::set_current_namespace("user");
+            assert_eq!(
+                "(clojure.core/seq (clojure.core/concat (clojure.core/list (quote user/a)) (clojure.core/list b) cs (clojure.core/list (quote if))))",
+                try_read("`(a ~b ~@cs if) ").ok().unwrap().1.to_string()
+            );
+            assert_eq!(
+                "(clojure.core/into [] (clojure.core/concat (clojure.core/list :k) (clojure.core/list (quote clojure.core/map))))",
+                try_read("`[:k clojure.core/map] ").ok().unwrap().1.to_string()
+            );
+            assert!(try_read("`~@xs ").is_err());
+        }
+
+        #[test]
+        fn try_read_syntax_quote_gensym_test() {
+            let form = try_read("`[x# x# y#] ").ok().unwrap().1.to_string();
+            let gensyms = form
+                .split("(quote ")
+                .skip(1)
+                .map(|quoted| &quoted[..quoted.find(')').unwrap()])
+                .collect::<Vec<&str>>();
+            assert_eq!(3, gensyms.len());
+            assert_eq!(gensyms[0], gensyms[1]);
+            assert_ne!(gensyms[0], gensyms[2]);
+            assert!(gensyms[0].starts_with("x__") && gensyms[0].ends_with("__auto__"));
+        }
+
+        #[test]
+        fn try_read_meta_test() {
+            let form = try_read("^:private ^String x ").ok().unwrap().1;
+            assert_eq!("x", form.to_string());
+            assert_eq!(
+                "{:tag String, :private true}",
+                crate::meta::meta(&form).unwrap().to_string()
+            );
+            assert!(try_read("^:private 1 ").is_err());
+        }
+
+        #[test]
+        fn try_read_past_the_limits_test() {
+            let deep = format!("{}{} 1", "(".repeat(100_000), ")".repeat(100_000));
+            let (rest, form) = try_read(&deep).unwrap();
+            assert_eq!(
+                "#Condition[\"Reader Error: limits exceeded;  forms nest more than 256 deep\"]",
+                form.to_string()
+            );
+            assert_eq!("", rest);
+            let nested = format!("{}{}", "[".repeat(256), "]".repeat(256));
+            assert!(matches!(try_read(&nested), Ok((_, PersistentVector(_)))));
+        }
+    }
+
+    mod syntax_quote_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn macros_build_code_with_syntax_quote() {
+            let result = Repl::default().eval_all(&[
+                "(defmacro unless [test & body] `(if ~test nil (do ~@body)))",
+                "(defmacro or2 [a b] `(let [x# ~a] (if x# x# ~b)))",
+                "(def x 10)",
+                "[(unless false 1 2) (unless true 1) (or2 nil x) (let [x 1] (or2 false x)) `{:a ~(inc 1)} (= `#{~@[1 2]} #{1 2})]",
+            ]);
+            assert_eq!("[2 nil 10 1 {:a 2} true]", result.to_string());
+        }
     }
 
     mod consume_clojure_whitespaces_tests {
@@ -853,4 +2290,79 @@ mod tests {
             assert_eq!(false, is_clojure_whitespace('a'));
         }
     }
+
+    mod read_lenient_tests {
+        use crate::reader::{read_lenient, Position};
+
+        fn at(line: usize, col: usize) -> Position {
+            Position { line, col }
+        }
+
+        #[test]
+        fn reads_forms_around_comments() {
+            let read = read_lenient("; start\n(def a \"; kept\") ; end\n  b ; more\n");
+            assert!(read.errors.is_empty());
+            let forms = read
+                .forms
+                .iter()
+                .map(|(position, form)| (*position, form.to_string()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vec![
+                    (at(2, 1), String::from("(def a \"; kept\")")),
+                    (at(3, 3), String::from("b"))
+                ],
+                forms
+            );
+        }
+
+        #[test]
+        fn resumes_at_the_next_top_level_form() {
+            let read = read_lenient("(defn f [x]\n  (g x]\n(def a 1)\n(def b [2\n(def c 3))\n");
+            let positions = read
+                .forms
+                .iter()
+                .map(|(position, _)| *position)
+                .collect::<Vec<_>>();
+            assert_eq!(vec![at(3, 1), at(5, 1)], positions);
+            let errors = read
+                .errors
+                .iter()
+                .map(|error| error.position)
+                .collect::<Vec<_>>();
+            assert_eq!(vec![at(1, 1), at(4, 1), at(5, 10)], errors);
+            assert_eq!("Unmatched delimiter: )", read.errors[2].message);
+        }
+    }
+
+    mod round_trip_tests {
+        use crate::edn;
+        use crate::reader::try_read;
+        use crate::value::Value;
+        use proptest::prelude::*;
+
+        // Identifiers from the characters identifiers are made of,  in any order;  so plenty
+        // that the reader won't take,  and plenty of odd ones it will
+        const IDENTIFIER: &str =
+            "[:]?([a.|?<>+\\-_=^%&$*!#é1١]{1,4}/)?[a.|?<>+\\-_=^%&$*!#é1١/]{1,4}";
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(2048))]
+            #[test]
+            fn each_symbol_and_keyword_read_prints_as_itself(s in IDENTIFIER) {
+                let read = match try_read(&format!("{} ", s)) {
+                    Ok((rest, read)) if rest.trim().is_empty() => read,
+                    _ => return Ok(()),
+                };
+                if !matches!(read, Value::Symbol(_) | Value::Keyword(_)) {
+                    return Ok(());
+                }
+                let printed = format!("{} ", read.to_string_explicit());
+                let back = try_read(&printed).ok().map(|(_, back)| back);
+                prop_assert_eq!(Some(read.clone()), back);
+                let edn = edn::print_str(&read).unwrap();
+                prop_assert_eq!(Ok(read), edn::read_str(&edn));
+            }
+        }
+    }
 }
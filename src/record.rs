@@ -0,0 +1,234 @@
+//! Types made with deftype and defrecord,  and their instances
+//!
+//! A type is named after the namespace it was made in,  as in Clojure;  (deftype Point ..)
+//! in user makes the type user.Point,  which (class p) gives back as a symbol like any
+//! other type.  An instance of a type made with defrecord is also a record:  its fields
+//! can be looked up by keyword,  and it's equal to any record of the same type with equal
+//! fields.  An instance of a plain deftype is only ever equal to itself
+//!
+//! What reify makes is the one instance of a type of its own,  with no fields;  made each
+//! time the reify is,  with the fns implementing its protocols as they closed over then
+use crate::clojure_protocol::Impls;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_list_map::IPersistentMap;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub struct Type {
+    pub name: Symbol,
+    pub fields: Vec<Symbol>,
+    pub is_record: bool,
+    // For a type made by reify,  its implementations of each protocol;  by the protocol's
+    // name
+    pub impls: HashMap<Symbol, Impls>,
+}
+impl Type {
+    pub fn new(ns: &str, name: &Symbol, fields: Vec<Symbol>, is_record: bool) -> Type {
+        Type {
+            name: Symbol::intern(&format!("{}.{}", ns, name.name)),
+            fields,
+            is_record,
+            impls: HashMap::new(),
+        }
+    }
+    /// The type of what a reify in the namespace ns makes;  named like ns.reify__12
+    pub fn reified(ns: &str, impls: HashMap<Symbol, Impls>) -> Type {
+        Type {
+            impls,
+            ..Type::new(ns, &Symbol::gensym("reify__", ""), vec![], false)
+        }
+    }
+    /// Where an instance of this type holds what it gives for key;  only a record's fields
+    /// can be looked up by key,  by keyword
+    pub fn field_index(&self, key: &Value) -> Option<usize> {
+        match key {
+            Value::Keyword(kw) if self.is_record && !kw.sym.has_ns() => {
+                self.fields.iter().position(|field| *field == kw.sym)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Instance {
+    type_: Arc<Type>,
+    // One for each of the type's fields,  in the same order
+    vals: Vec<Arc<Value>>,
+}
+impl Instance {
+    pub fn new(type_: Arc<Type>, vals: Vec<Arc<Value>>) -> Instance {
+        Instance { type_, vals }
+    }
+    pub fn type_(&self) -> &Arc<Type> {
+        &self.type_
+    }
+    pub fn type_tag(&self) -> TypeTag {
+        if self.type_.is_record {
+            TypeTag::IRecord
+        } else {
+            TypeTag::IType
+        }
+    }
+    pub fn field(&self, name: &Symbol) -> Option<Arc<Value>> {
+        self.type_
+            .fields
+            .iter()
+            .position(|field| field == name)
+            .map(|ind| Arc::clone(&self.vals[ind]))
+    }
+    /// What a record holds under key;  only a record's fields can be looked up this way
+    pub fn get(&self, key: &Value) -> Option<Arc<Value>> {
+        self.type_.field_index(key).map(|ind| self.field_at(ind))
+    }
+    /// The field at ind,  of those the type declares;  as found by field_index
+    pub fn field_at(&self, ind: usize) -> Arc<Value> {
+        Arc::clone(&self.vals[ind])
+    }
+}
+impl PartialEq for Instance {
+    fn eq(&self, other: &Instance) -> bool {
+        if !self.type_.is_record {
+            return std::ptr::eq(self, other);
+        }
+        Arc::ptr_eq(&self.type_, &other.type_) && self.vals == other.vals
+    }
+}
+impl Hash for Instance {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.type_.is_record {
+            self.type_.name.hash(state);
+            self.vals.hash(state);
+        } else {
+            (self as *const Instance as usize).hash(state);
+        }
+    }
+}
+impl fmt::Display for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.type_.is_record {
+            return write!(f, "#object[{}]", self.type_.name);
+        }
+        let fields = self
+            .type_
+            .fields
+            .iter()
+            .zip(self.vals.iter())
+            .map(|(field, val)| format!(":{} {}", field, val.to_string_explicit()))
+            .collect::<Vec<String>>()
+            .join(", ");
+        write!(f, "#{}{{{}}}", self.type_.name, fields)
+    }
+}
+
+/// (->Point x y)
+///
+/// Makes an instance of a type from its fields,  in the order the type declares them
+#[derive(Debug, Clone)]
+pub struct PositionalConstructorFn {
+    pub type_: Arc<Type>,
+}
+impl ToValue for PositionalConstructorFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PositionalConstructorFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != self.type_.fields.len() {
+            return error_message::wrong_arg_count(self.type_.fields.len(), args.len());
+        }
+        Value::Instance(Arc::new(Instance {
+            type_: Arc::clone(&self.type_),
+            vals: args,
+        }))
+    }
+    fn arglists(&self) -> Option<Vec<Vec<Symbol>>> {
+        Some(vec![self.type_.fields.clone()])
+    }
+}
+
+/// (map->Point {:x 1 :y 2})
+///
+/// Makes a record from a map of its fields;  any field the map leaves out is nil
+#[derive(Debug, Clone)]
+pub struct MapConstructorFn {
+    pub type_: Arc<Type>,
+}
+impl ToValue for MapConstructorFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MapConstructorFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let map = match &*args[0] {
+            Value::PersistentListMap(map) => map,
+            _ => return error_message::type_mismatch(TypeTag::PersistentListMap, &args[0]),
+        };
+        let vals = self
+            .type_
+            .fields
+            .iter()
+            .map(|field| {
+                let key = Keyword { sym: field.clone() }.to_rc_value();
+                map.get(&key)
+            })
+            .collect();
+        Value::Instance(Arc::new(Instance {
+            type_: Arc::clone(&self.type_),
+            vals,
+        }))
+    }
+    fn arglists(&self) -> Option<Vec<Vec<Symbol>>> {
+        Some(vec![vec![Symbol::intern("m")]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    #[test]
+    fn records_are_equal_when_their_fields_are() {
+        let result = Repl::default().eval_all(&[
+            "(defrecord Point [x y])",
+            "[(= (->Point 1 2) (map->Point {:x 1 :y 2})) (= (->Point 1 2) (->Point 2 1))]",
+        ]);
+        assert_eq!("[true false]", result.to_string());
+    }
+
+    #[test]
+    fn record_fields_are_looked_up_by_keyword() {
+        let result = Repl::default().eval_all(&[
+            "(defrecord Point [x y])",
+            "(def p (map->Point {:x 1}))",
+            "[(:x p) (get p :y) (:z p)]",
+        ]);
+        assert_eq!("[1 nil nil]", result.to_string());
+    }
+
+    #[test]
+    fn a_deftype_is_only_equal_to_itself() {
+        let repl = Repl::default();
+        let result = repl.eval_all(&[
+            "(deftype Box [val])",
+            "(def b (->Box 1))",
+            "[(= b b) (= b (->Box 1)) (:val b)]",
+        ]);
+        assert_eq!("[true false nil]", result.to_string());
+        assert_eq!(Value::Boolean(true), repl.eval_all(&["(= Box (class b))"]));
+    }
+}
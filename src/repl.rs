@@ -1,91 +1,718 @@
-use std::fs::File;
+use std::fs;
 use std::io;
-use std::io::BufRead;
-use std::io::BufReader;
+use std::io::IsTerminal;
 use std::io::Write;
 
-use crate::environment::Environment;
-use crate::reader;
+use crate::allocations;
+use crate::clojure_protocol::Protocol;
+use crate::completion;
+use crate::environment::{Environment, Fork};
+use crate::host_object::HostType;
+use crate::host_trait::{self, HostTrait};
+use crate::interrupt;
+use crate::keyword::Keyword;
+use crate::line_editor::{self, LineEditor, Typed};
+use crate::native_fn::NativeFn;
+use crate::persistent_list::{ToPersistentList, ToPersistentListIter};
+use crate::reader::{SourceReader, StreamReader, NO_SOURCE_FILE};
+use crate::symbol::Symbol;
+use crate::trace;
+use crate::transcript::{self, Transcript};
 use crate::value::Evaluable;
-use crate::value::Value;
-use std::rc::Rc;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct Repl {
-    environment: Rc<Environment>,
+    environment: Arc<Environment>,
+    // Where each form read and evaluated by run is recorded,  if anywhere
+    transcript: Option<Transcript>,
+    // What stands in for environment while this evaluates,  if this is a fork;  see fork
+    fork: Option<Fork>,
+    // How long what run and run_forms evaluate may take before it's interrupted,  if it's
+    // limited
+    timeout: Option<Duration>,
 }
 impl Repl {
-    pub fn new(environment: Rc<Environment>) -> Repl {
-        Repl { environment }
+    pub fn new(environment: Arc<Environment>) -> Repl {
+        Repl {
+            environment,
+            transcript: None,
+            fork: None,
+            timeout: interrupt::timeout(),
+        }
+    }
+    /// What this repl evaluates in
+    pub(crate) fn environment(&self) -> &Arc<Environment> {
+        &self.environment
+    }
+    /// This repl,  recording each form it reads and evaluates to transcript
+    pub fn recording(self, transcript: Transcript) -> Repl {
+        Repl {
+            transcript: Some(transcript),
+            ..self
+        }
+    }
+    /// This repl,  interrupting each form it runs once it's taken longer than timeout
+    pub fn timing_out(self, timeout: Duration) -> Repl {
+        Repl {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+    /// This repl,  with *command-line-args* args;  or nil,  if there are none
+    pub fn with_command_line_args(self, args: &[String]) -> Repl {
+        let args = match args {
+            [] => Value::Nil,
+            args => args
+                .iter()
+                .map(|arg| arg.to_rc_value())
+                .collect::<Vec<Arc<Value>>>()
+                .into_list()
+                .to_value(),
+        };
+        self.environment
+            .define("clojure.core/*command-line-args*", Arc::new(args));
+        self
+    }
+    /// A repl of its own with all that's defined here,  as it is now;  for evaluating code
+    /// to see what it does,  as a linter expanding macros or a test wanting a clean slate
+    /// might,  without this repl seeing any of it.  Drop it to forget all that,  or merge
+    /// it to keep it.  It's cheap,  as what's defined is shared rather than copied;  but so
+    /// are atoms and the like,  whose changes this repl sees too
+    pub fn fork(&self) -> Repl {
+        Repl {
+            environment: Arc::clone(&self.environment),
+            transcript: None,
+            fork: Some(self.in_fork(|| self.environment.fork())),
+            timeout: self.timeout,
+        }
+    }
+    /// Keeps all that's been defined in fork,  one of this repl's forks,  as if it had
+    /// been defined here;  and whatever was defined here since the fork,  it isn't
+    pub fn merge(&self, fork: Repl) {
+        if let Some(fork) = &fork.fork {
+            self.in_fork(|| self.environment.merge(fork));
+        }
+    }
+    // Runs f in this repl's fork,  if it is one
+    fn in_fork<T>(&self, f: impl FnOnce() -> T) -> T {
+        match &self.fork {
+            Some(fork) => self.environment.in_fork(fork, f),
+            None => f(),
+        }
     }
 
     // @TODO reconsider eval's signature;  since Value wraps all evaluables,  it might make more sense
     // to frame eval as "environment.eval(value)", and then likewise define a
     // 'repl.eval(value)', rather than 'value.eval(environment)'
     pub fn eval(&self, value: &Value) -> Value {
-        value.eval(Rc::clone(&self.environment))
+        self.in_fork(|| value.eval(Arc::clone(&self.environment)))
     }
 
-    // Just wraps reader's read
-    pub fn read<R: BufRead>(reader: &mut R) -> Value {
-        reader::read(reader)
+    /// Defines name (in the current namespace,  unless it's qualified,  as my.app/f is) as an
+    /// fn that calls f with its arguments;  f gives a condition for an error.  For whatever
+    /// embeds us
+    pub fn register_fn(
+        &self,
+        name: &str,
+        f: impl Fn(Vec<Arc<Value>>) -> Value + Send + Sync + 'static,
+    ) {
+        self.in_fork(|| {
+            self.environment
+                .define(name, NativeFn::new(name, f).to_rc_value())
+        });
+    }
+    /// Defines name (in the current namespace,  unless it's qualified) as val;  a Rust
+    /// value,  made a Clojure one.  For whatever embeds us
+    pub fn register_value(&self, name: &str, val: impl Into<Value>) {
+        self.in_fork(|| self.environment.define(name, Arc::new(val.into())));
     }
-    pub fn run(&self) {
-        let stdin = io::stdin();
 
+    /// Evaluates form,  one whatever embeds us has read or built itself;  giving a
+    /// condition,  rather than printing it,  if evaluating it fails
+    pub fn eval_form(&self, form: &Value) -> Value {
+        self.eval(form)
+    }
+    /// Reads and evaluates each form of code in turn,  with each name in bindings a local
+    /// bound to its value,  as in a let;  giving the last form's value,  or the first
+    /// condition reading or evaluating one gives.  So whatever embeds us can run code
+    /// against its own data without printing it for the code to read back;  the bindings
+    /// gone once it's done
+    pub fn eval_with_bindings(&self, code: &str, bindings: &[(&str, Value)]) -> Value {
+        let locals = Arc::new(Environment::new_local_environment(Arc::clone(
+            &self.environment,
+        )));
+        for (name, val) in bindings {
+            locals.insert(Symbol::intern(name), Arc::new(val.clone()));
+        }
+        let mut last_val = Value::Nil;
+        for form in SourceReader::new(NO_SOURCE_FILE, code) {
+            if let Value::Condition(_) = form {
+                return form;
+            }
+            last_val = self.in_fork(|| form.eval(Arc::clone(&locals)));
+            if let Value::Condition(_) = last_val {
+                return last_val;
+            }
+        }
+        last_val
+    }
+
+    /// Defines each of host_type's static fns;  so (Type/f args) calls f.  For whatever
+    /// embeds us
+    pub fn register_type(&self, host_type: HostType) {
+        self.in_fork(|| {
+            for (name, static_fn) in host_type.static_fns {
+                self.environment.define(&name, static_fn.to_rc_value());
+            }
+        });
+    }
+    /// Defines the protocol of T,  a trait declared with host_trait!,  and its methods;  giving
+    /// it,  for host_trait::implement to make what satisfies it a Box<T>.  For whatever embeds us
+    pub fn register_trait<T: HostTrait + ?Sized>(&self) -> Arc<Protocol> {
+        self.in_fork(|| host_trait::register::<T>(&self.environment))
+    }
+
+    /// Reads, evaluates and prints forms from stdin until it runs out,  or is told to quit;
+    /// giving the code to exit with
+    ///
+    /// When stdout isn't a terminal (say, we're piped into another program) there's no one
+    /// to prompt,  so only the results are printed
+    pub fn run(&self) -> i32 {
+        let stdout = io::stdout();
+        let interactive = stdout.is_terminal();
+        // Typed at a terminal,  lines are edited as they're typed;  the editor prompts
+        if interactive && io::stdin().is_terminal() {
+            let environment = Arc::clone(&self.environment);
+            let editor = LineEditor::new(line_editor::history_file()).completing(move |name| {
+                let ns = environment.get_current_namespace();
+                let candidates = completion::completions(&environment, &ns, name);
+                candidates.into_iter().map(|c| c.candidate).collect()
+            });
+            let mut typed = Typed::new(editor);
+            let environment = Arc::clone(&self.environment);
+            return self.run_on(
+                || typed.read_form(&format!("{}=> ", environment.get_current_namespace_name())),
+                &mut stdout.lock(),
+                false,
+            );
+        }
+        // stdin's locked only while a form's read;  so what's evaluated can read the lines
+        // after it,  as read-line does
+        let mut forms = StreamReader::default();
+        self.run_on(
+            || forms.read(&mut io::stdin().lock()),
+            &mut stdout.lock(),
+            interactive,
+        )
+    }
+    fn run_on<W: Write>(
+        &self,
+        mut read: impl FnMut() -> Value,
+        output: &mut W,
+        interactive: bool,
+    ) -> i32 {
+        // Whether each evaluation's followed by what it made and how long it took;  as
+        // :repl/stats switches it
+        let mut stats = false;
         loop {
-            print!("{}=> ", self.environment.get_current_namespace_name());
-            let _ = io::stdout().flush();
-
-            let next = {
-                let mut stdin_reader = stdin.lock();
-                // Read
-                Repl::read(&mut stdin_reader)
-                // Release stdin.lock
+            if interactive {
+                let _ = write!(
+                    output,
+                    "{}=> ",
+                    self.environment.get_current_namespace_name()
+                );
+                let _ = output.flush();
+            }
+
+            // Read;  running out of input,  as at Ctrl-D,  is quitting too
+            let next = read();
+            let quit = match is_eof(&next) {
+                true => Some(0),
+                false => quit_code(&next),
             };
+            if let Some(code) = quit {
+                if interactive {
+                    let _ = writeln!(output);
+                }
+                let _ = output.flush();
+                if let Some(transcript) = &self.transcript {
+                    transcript.close();
+                }
+                return code;
+            }
+
+            // :repl/clear isn't evaluated;  it starts over,  with only clojure.core defined
+            if next == Keyword::intern_with_ns("repl", "clear").to_value() {
+                self.environment.reset();
+                let _ = writeln!(output, "{}", Value::Nil);
+                let _ = output.flush();
+                continue;
+            }
+
+            // Nor is :repl/stats;  it switches stats on or off,  giving whether they're on
+            if next == Keyword::intern_with_ns("repl", "stats").to_value() {
+                stats = !stats;
+                let _ = writeln!(output, "{}", Value::Boolean(stats));
+                let _ = output.flush();
+                continue;
+            }
 
             // Eval
-            let evaled_next = self.eval(&next);
-            // Print
-            println!("{}", evaled_next);
+            let ns = self.environment.get_current_namespace_name();
+            let (evaled_next, made) =
+                interrupt::evaluating(self.timeout, || allocations::counting(|| self.eval(&next)));
+            if let Some(transcript) = &self.transcript {
+                transcript.record(&ns, &next, &evaled_next);
+            }
+            if !matches!(evaled_next, Value::Condition(_)) {
+                self.remember(&evaled_next);
+            }
+            // Print;  a condition along with where it came from
+            let _ = writeln!(output, "{}", evaled_next);
+            let _ = write!(output, "{}", trace::stack_trace(&evaled_next));
+            if stats {
+                let _ = writeln!(output, "{}", made);
+            }
+            let _ = output.flush();
             // Loop
         }
     }
+    // Makes val *1,  as it's the last value printed;  and what was *1 *2,  and *2 *3
+    fn remember(&self, val: &Value) {
+        let last = |name| {
+            self.environment
+                .get(&Symbol::intern_with_ns("clojure.core", name))
+        };
+        self.environment.define("clojure.core/*3", last("*2"));
+        self.environment.define("clojure.core/*2", last("*1"));
+        self.environment
+            .define("clojure.core/*1", val.to_rc_value());
+    }
     //
     // Will possibly just add this to our environment, or turn this into a parallel of clojure.lang.RT
     //
     /// Reads the code in a file sequentially and evaluates the result
     pub fn try_eval_file(&self, filepath: &str) -> Result<Value, std::io::Error> {
-        let core = File::open(filepath)?;
-        let mut reader = BufReader::new(core);
+        let source = fs::read_to_string(filepath)?;
+        Ok(self.eval_text(filepath, &source))
+    }
+    /// Reads and evaluates each form of source,  the text of file,  in turn;  printing any
+    /// condition one gives,  and giving the last one's value
+    pub fn eval_text(&self, file: &str, source: &str) -> Value {
+        let mut last_val = Value::Nil;
+        for form in SourceReader::new(file, source) {
+            if let Value::Condition(cond) = &form {
+                println!("Error reading file: {}", cond);
+                return form;
+            }
+
+            last_val = self.eval(&form);
 
-        let mut last_val = Repl::read(&mut reader);
-        loop {
-            // @TODO this is hardcoded until we refactor Conditions to have keys, so that
-            //       we can properly identify them
-            // @FIXME
             if let Value::Condition(cond) = &last_val {
-                if cond != "Tried to read empty stream; unexpected EOF" {
-                    println!("Error reading file: {}", cond);
+                print!("{}\n{}", cond, trace::stack_trace(&last_val));
+            }
+        }
+        last_val
+    }
+    /// Runs the file at filepath as a script,  stopping at the first form that fails
+    ///
+    /// Unlike try_eval_file,  a condition that makes it all the way up to the top of the
+    /// script isn't just printed;  it ends the script,  and is given back as the trace to
+    /// print,  naming the calls and the form it came out of
+    pub fn run_script(&self, filepath: &str) -> Result<(), String> {
+        let script = fs::read_to_string(filepath)
+            .map_err(|e| format!("Could not open script {}: {}", filepath, e))?;
+        self.run_forms(filepath, SourceReader::new(filepath, &script), false)
+    }
+    /// Evaluates each form of expr,  as -e does;  printing what each gives,  unless it's nil,
+    /// and stopping at the first that fails as run_script does
+    pub fn run_expr(&self, expr: &str) -> Result<(), String> {
+        self.run_forms(
+            NO_SOURCE_FILE,
+            SourceReader::new(NO_SOURCE_FILE, expr),
+            true,
+        )
+    }
+    /// Requires the namespace ns and calls its -main with *command-line-args*,  as -m does
+    pub fn run_main(&self, ns: &str) -> Result<(), String> {
+        let core = |name| Symbol::intern_with_ns("clojure.core", name).to_rc_value();
+        let require = vec![
+            core("require"),
+            vec![
+                Symbol::intern("quote").to_rc_value(),
+                Symbol::intern(ns).to_rc_value(),
+            ]
+            .into_list()
+            .to_rc_value(),
+        ];
+        let main = vec![
+            core("apply"),
+            Symbol::intern_with_ns(ns, "-main").to_rc_value(),
+            core("*command-line-args*"),
+        ];
+        let forms = vec![require, main]
+            .into_iter()
+            .map(|form| form.into_list().to_value());
+        self.run_forms(ns, forms, false)
+    }
+    // Evaluates each of forms,  read from file,  stopping at the first that fails;  printing
+    // what each gives,  if print and it isn't nil
+    fn run_forms(
+        &self,
+        file: &str,
+        forms: impl Iterator<Item = Value>,
+        print: bool,
+    ) -> Result<(), String> {
+        for form in forms {
+            if let Value::Condition(cond) = &form {
+                return Err(format!("Uncaught condition: {}\n    at {}", cond, file));
+            }
+            let evaled = interrupt::evaluating(self.timeout, || self.eval(&form));
+            if let Value::Condition(cond) = &evaled {
+                return Err(format!(
+                    "Uncaught condition: {}\n{}    at {}\n    at {}",
+                    cond,
+                    trace::stack_trace(&evaled),
+                    abbreviate(&form.to_string_explicit()),
+                    file
+                ));
+            }
+            if print && evaled != Value::Nil {
+                println!("{}", evaled);
+            }
+        }
+        Ok(())
+    }
+    /// Evaluates each form recorded in the transcript at filepath again,  in order,
+    /// printing it as the repl would have;  along with what it gave when it was recorded,
+    /// when that's different now
+    pub fn replay(&self, filepath: &str) -> Result<(), String> {
+        let transcript = fs::read_to_string(filepath)
+            .map_err(|e| format!("Could not open transcript {}: {}", filepath, e))?;
+        let stdout = io::stdout();
+        self.replay_on(&transcript::entries(&transcript), &mut stdout.lock());
+        Ok(())
+    }
+    fn replay_on<W: Write>(&self, entries: &[transcript::Entry], output: &mut W) {
+        for entry in entries {
+            let _ = writeln!(
+                output,
+                "{}=> {}",
+                self.environment.get_current_namespace_name(),
+                entry.form
+            );
+            for form in SourceReader::new(NO_SOURCE_FILE, &entry.form) {
+                let evaled = match form {
+                    Value::Condition(_) => form,
+                    form => self.eval(&form),
+                };
+                let _ = writeln!(output, "{}", evaled);
+                let _ = write!(output, "{}", trace::stack_trace(&evaled));
+                let now = match &evaled {
+                    Value::Condition(message) => Err(message.to_string()),
+                    value => Ok(value.to_string()),
+                };
+                match &entry.result {
+                    Ok(then) if now != entry.result => {
+                        let _ = writeln!(output, ";; recorded as {}", then);
+                    }
+                    Err(then) if now != entry.result => {
+                        let _ = writeln!(output, ";; recorded as a condition: {}", then);
+                    }
+                    _ => {}
                 }
-
-                return Ok(last_val);
             }
+            let _ = output.flush();
+        }
+    }
+}
+
+// @TODO this is hardcoded until we refactor Conditions to have keys, so that
+//       we can properly identify them
+// @FIXME
+fn is_eof(read: &Value) -> bool {
+    match read {
+        Value::Condition(cond) => &**cond == "Tried to read empty stream; unexpected EOF",
+        _ => false,
+    }
+}
 
-            let evaled_last_val = self.eval(&last_val);
+/// The code to exit with,  if form says to quit the REPL rather than being evaluated;
+/// :repl/quit,  (exit) or (exit n).  So a REPL ends the same way whichever's typed,  its
+/// transcript closed,  rather than exit ending the process in the middle of an evaluation;
+/// and over nREPL or a socket,  it's the session that's ended,  not the server
+pub fn quit_code(form: &Value) -> Option<i32> {
+    if *form == Keyword::intern_with_ns("repl", "quit").to_value() {
+        return Some(0);
+    }
+    let call = match form {
+        Value::PersistentList(call) => call.iter().collect::<Vec<Arc<Value>>>(),
+        _ => return None,
+    };
+    match call.split_first() {
+        Some((exit, args)) if **exit == Symbol::intern("exit").to_value() => match args {
+            [] => Some(0),
+            [code] => match **code {
+                Value::I32(code) => Some(code),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
 
-            if let Value::Condition(cond) = evaled_last_val {
-                println!("{}", cond);
-            }
+// A form in a trace only needs to be recognizable;  a whole defn would bury the message
+fn abbreviate(form: &str) -> String {
+    const MAX_LEN: usize = 60;
+    match form.char_indices().nth(MAX_LEN) {
+        Some((end, _)) => format!("{} ...", &form[..end]),
+        None => form.to_string(),
+    }
+}
 
-            last_val = Repl::read(&mut reader);
+// For tests that want to run a little Clojure
+#[cfg(test)]
+impl Repl {
+    /// Reads and evaluates each of sources in turn,  returning the last value
+    pub fn eval_all(&self, sources: &[&str]) -> Value {
+        let mut result = Value::Nil;
+        for source in sources {
+            let (_, form) = crate::reader::try_read(source).unwrap();
+            result = self.eval(&form);
         }
+        result
     }
 }
 
 impl Default for Repl {
     fn default() -> Repl {
-        Repl {
-            environment: Environment::clojure_core_environment(),
+        Repl::new(Environment::clojure_core_environment())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interrupt;
+    use crate::persistent_list::ToPersistentList;
+    use crate::reader::StreamReader;
+    use crate::repl::Repl;
+    use crate::symbol::Symbol;
+    use crate::transcript;
+    use crate::value::{ToValue, Value};
+    use std::collections::HashMap;
+    use std::io::Cursor;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn run(input: &str, interactive: bool) -> String {
+        let mut output = vec![];
+        let mut input = Cursor::new(input);
+        let mut forms = StreamReader::default();
+        Repl::default().run_on(|| forms.read(&mut input), &mut output, interactive);
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn piped_output_has_only_results() {
+        assert_eq!("3\na\n", run("(+ 1 2)\n(str \"a\")\n", false));
+    }
+
+    #[test]
+    fn forms_on_one_line_are_all_read() {
+        assert_eq!(
+            "#'user/a\n#'user/b\n[1 2]\n",
+            run(
+                "(def a 1) (def b 2)\n; a comment\n[a b] ; and another\n",
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn eof_inside_a_form_is_reported() {
+        let output = run("(+ 1 2) (inc\n  3", false);
+        assert!(output.starts_with("3\n"), "{}", output);
+        assert!(
+            output.contains("EOF while reading;  the form at NO_SOURCE_FILE:1:9 is never closed"),
+            "{}",
+            output
+        );
+    }
+
+    #[test]
+    fn a_terminal_gets_a_prompt() {
+        assert_eq!("user=> 3\nuser=> \n", run("(+ 1 2)\n", true));
+    }
+
+    #[test]
+    fn scripts_get_the_args_after_them() {
+        let repl = Repl::default().with_command_line_args(&[String::from("a"), String::from("-b")]);
+        assert_eq!(
+            "(\"a\" \"-b\")",
+            repl.eval_all(&["(pr-str *command-line-args*)"]).to_string()
+        );
+        let repl = Repl::default().with_command_line_args(&[]);
+        assert_eq!(Value::Nil, repl.eval_all(&["*command-line-args*"]));
+        assert_eq!(Ok(()), repl.run_expr("(def x 1) (inc x)"));
+        assert!(repl
+            .run_expr("(inc x) (/ 1 :a)")
+            .unwrap_err()
+            .contains("at (/ 1 :a)"));
+    }
+
+    #[test]
+    fn stats_follow_each_evaluation_once_switched_on() {
+        let output = run("(+ 1 2)\n:repl/stats\n[(+ 1 2)]\n:repl/stats\n4\n", false);
+        let lines = output.lines().collect::<Vec<&str>>();
+        assert_eq!(vec!["3", "true", "[3]"], lines[..3]);
+        assert!(lines[3]
+            .starts_with(";; 2 values made (1 clojure.lang.PersistentVector, 1 rust.std.i32) in "));
+        assert_eq!(vec!["false", "4"], lines[4..]);
+    }
+
+    #[test]
+    fn quitting_stops_reading_with_the_code_to_exit_with() {
+        for (input, code) in [
+            ("1\n:repl/quit\n2\n", 0),
+            ("1\n(exit)\n2\n", 0),
+            ("1\n(exit 3)\n2\n", 3),
+            ("1\n", 0),
+        ] {
+            let mut output = vec![];
+            let mut input = Cursor::new(input);
+            let mut forms = StreamReader::default();
+            let quit = Repl::default().run_on(|| forms.read(&mut input), &mut output, false);
+            assert_eq!(
+                ("1\n".to_string(), code),
+                (String::from_utf8(output).unwrap(), quit)
+            );
         }
     }
+
+    #[test]
+    fn forks_go_their_own_way_until_merged() {
+        let repl = Repl::default();
+        repl.eval_all(&[
+            "(def x 1)",
+            "(defn helper [] :live)",
+            "(defn caller [] (helper))",
+        ]);
+        let fork = repl.fork();
+        fork.eval_all(&[
+            "(def x 2)",
+            "(defn helper [] :forked)",
+            "(ns other)",
+            "(def forked @(future (user/caller)))",
+            "(def y 3)",
+        ]);
+        assert_eq!(
+            "[1 :live nil]",
+            repl.eval_all(&["[x (caller) (find-ns 'other)]"])
+                .to_string()
+        );
+        assert_eq!("user", repl.environment.get_current_namespace_name());
+        assert_eq!(
+            "[2 :forked :forked]",
+            fork.eval_all(&["[user/x (user/caller) forked]"])
+                .to_string()
+        );
+        repl.fork().eval_all(&["(def x 3)"]);
+        repl.merge(fork);
+        assert_eq!(
+            "[2 :forked 3]",
+            repl.eval_all(&["[user/x (user/caller) y]"]).to_string()
+        );
+        assert_eq!("other", repl.environment.get_current_namespace_name());
+    }
+
+    #[test]
+    fn clearing_starts_over_with_only_core() {
+        assert_eq!(
+            "#'user/x\n#namespace[app]\n#'app/y\nnil\n#'user/z\n[nil nil 3]\n",
+            run(
+                "(def x 1)\n(in-ns 'app)\n(def y 2)\n:repl/clear\n(def z 3)\n[(resolve 'x) (find-ns 'app) (inc 2)]\n",
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn a_replay_points_out_what_came_out_different() {
+        let entries = transcript::entries(
+            "{:time #inst \"2026-10-16T09:30:00.000Z\" :ns \"user\" :form \"(def x 2)\" :value \"#'user/x\"}\n\
+             {:time #inst \"2026-10-16T09:30:01.000Z\" :ns \"user\" :form \"(str \\\"x\\\" (+ x 1))\" :value \"x4\"}",
+        );
+        let mut output = vec![];
+        Repl::default().replay_on(&entries, &mut output);
+        assert_eq!(
+            "user=> (def x 2)\n#'user/x\nuser=> (str \"x\" (+ x 1))\nx3\n;; recorded as x4\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn registered_fns_and_values_are_there_for_scripts() {
+        let repl = Repl::default();
+        repl.register_fn("my.app/shout", |args: Vec<Arc<Value>>| match &*args[0] {
+            Value::String(s) => Value::from(s.to_uppercase()),
+            other => Value::Condition(format!("Not a string: {}", other).into()),
+        });
+        repl.register_value("config", HashMap::from([("port", 7888_i64)]));
+        repl.register_value("names", vec!["a", "b"]);
+        assert_eq!(
+            "[\"HI\" 7888 [\"a\" \"b\"]]",
+            repl.eval_all(&["[(my.app/shout \"hi\") (get config \"port\") names]"])
+                .to_string_explicit()
+        );
+        assert_eq!(
+            Value::Condition("Not a string: 1".into()),
+            repl.eval_all(&["(my.app/shout 1)"])
+        );
+    }
+
+    #[test]
+    fn hosts_can_eval_their_own_forms_and_bind_their_own_values() {
+        let repl = Repl::default();
+        let form = vec![
+            Symbol::intern("+").to_rc_value(),
+            Arc::new(Value::from(1_i64)),
+            Arc::new(Value::from(2_i64)),
+        ]
+        .into_list()
+        .to_value();
+        assert_eq!(Value::from(3_i64), repl.eval_form(&form));
+        let bindings = [
+            ("x", Value::from(40_i64)),
+            ("names", Value::from(vec!["a", "b"])),
+        ];
+        assert_eq!(
+            "[\"a\" \"b\" 42]",
+            repl.eval_with_bindings("(conj names (+ x (count names)))", &bindings)
+                .to_string_explicit()
+        );
+        assert!(matches!(
+            repl.eval_with_bindings("x", &[]),
+            Value::Condition(_)
+        ));
+        assert!(matches!(
+            repl.eval_with_bindings("(+ x 1) :after", &[("x", Value::from("a"))]),
+            Value::Condition(_)
+        ));
+    }
+
+    #[test]
+    fn a_form_that_times_out_still_runs_its_finally() {
+        let repl = Repl::default().timing_out(Duration::from_millis(50));
+        let ran = repl.run_expr(
+            "(def cleaned (atom false)) (try (loop [] (recur)) (finally (reset! cleaned true)))",
+        );
+        assert!(ran.unwrap_err().contains(interrupt::INTERRUPTED_MSG));
+        assert_eq!(
+            "[true 3]",
+            repl.eval_all(&["[@cleaned (+ 1 2)]"]).to_string()
+        );
+    }
 }
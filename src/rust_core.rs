@@ -8,9 +8,42 @@ pub use self::eval::*;
 // macros
 pub(crate) mod do_macro;
 pub use self::do_macro::*;
+pub(crate) mod when_macro;
+pub use self::when_macro::*;
+pub(crate) mod assert_macro;
+pub use self::assert_macro::*;
+pub(crate) mod and_macro;
+pub use self::and_macro::*;
+pub(crate) mod or_macro;
+pub use self::or_macro::*;
+pub(crate) mod cond_macro;
+pub use self::cond_macro::*;
+pub(crate) mod while_macro;
+pub use self::while_macro::*;
+pub(crate) mod dotimes_macro;
+pub use self::dotimes_macro::*;
+pub(crate) mod doseq_macro;
+pub use self::doseq_macro::*;
+pub(crate) mod for_macro;
+pub use self::for_macro::*;
+pub(crate) mod doto_macro;
+pub use self::doto_macro::*;
+pub(crate) mod dot_macros;
+pub use self::dot_macros::*;
+pub(crate) mod if_let_macro;
+pub use self::if_let_macro::*;
+pub(crate) mod thread_macros;
+pub use self::thread_macros::*;
+
+pub(crate) mod recur;
+pub use self::recur::*;
 
 pub(crate) mod ns;
 pub use self::ns::*;
+pub(crate) mod in_ns;
+pub use self::in_ns::*;
+pub(crate) mod refer_clojure_macro;
+pub use self::refer_clojure_macro::*;
 
 // arithmetics
 pub(crate) mod _plus_;
@@ -25,27 +58,456 @@ pub use self::_divide_::*;
 pub(crate) mod _multiply_;
 pub use self::_multiply_::*;
 
+pub(crate) mod quot;
+pub use self::quot::*;
+pub(crate) mod rem;
+pub use self::rem::*;
+pub(crate) mod mod_fn;
+pub use self::mod_fn::*;
+pub(crate) mod abs;
+pub use self::abs::*;
+pub(crate) mod signum;
+pub use self::signum::*;
+
+// comparisons
+pub(crate) mod compare;
+pub use self::compare::*;
+pub(crate) mod _lt_;
+pub use self::_lt_::*;
+pub(crate) mod _lte_;
+pub use self::_lte_::*;
+pub(crate) mod _gt_;
+pub use self::_gt_::*;
+pub(crate) mod _gte_;
+pub use self::_gte_::*;
+pub(crate) mod _eq_;
+pub use self::_eq_::*;
+pub(crate) mod not_eq_;
+pub use self::not_eq_::*;
+pub(crate) mod _equiv_;
+pub use self::_equiv_::*;
+pub(crate) mod hash;
+pub use self::hash::*;
+pub(crate) mod min;
+pub use self::min::*;
+pub(crate) mod max;
+pub use self::max::*;
+
 pub(crate) mod rand;
 pub use self::rand::*;
 
 pub(crate) mod rand_int;
 pub use self::rand_int::*;
 
+pub(crate) mod set_random_seed;
+pub use self::set_random_seed::*;
+
+pub(crate) mod random_uuid;
+pub use self::random_uuid::*;
+
 // string
 pub(crate) mod str;
 pub use self::str::*;
+pub(crate) mod sb;
+pub use self::sb::*;
+pub(crate) mod sb_append_bang_;
+pub use self::sb_append_bang_::*;
+pub(crate) mod sb_str;
+pub use self::sb_str::*;
+pub(crate) mod keyword;
+pub use self::keyword::*;
+pub(crate) mod symbol;
+pub use self::symbol::*;
+pub(crate) mod name;
+pub use self::name::*;
+pub(crate) mod namespace;
+pub use self::namespace::*;
+pub(crate) mod pr_str;
+pub use self::pr_str::*;
+pub(crate) mod format;
+pub use self::format::*;
+pub(crate) mod subs;
+pub use self::subs::*;
+
+// characters
+pub(crate) mod char;
+pub use self::char::*;
+pub(crate) mod int;
+pub use self::int::*;
+
+// regexes
+pub(crate) mod re_pattern;
+pub use self::re_pattern::*;
+pub(crate) mod re_find;
+pub use self::re_find::*;
+pub(crate) mod re_matches;
+pub use self::re_matches::*;
+pub(crate) mod re_seq;
+pub use self::re_seq::*;
 
 // operations on collections
 pub(crate) mod nth;
 pub use self::nth::*;
 pub(crate) mod concat;
 pub use self::concat::*;
+pub(crate) mod subvec;
+pub use self::subvec::*;
+pub(crate) mod peek;
+pub use self::peek::*;
+pub(crate) mod pop;
+pub use self::pop::*;
+pub(crate) mod rseq;
+pub use self::rseq::*;
+pub(crate) mod count;
+pub use self::count::*;
+pub(crate) mod reverse;
+pub use self::reverse::*;
 pub(crate) mod assoc;
 pub use self::assoc::*;
 pub(crate) mod get;
 pub use self::get::*;
+pub(crate) mod key;
+pub use self::key::*;
+pub(crate) mod val;
+pub use self::val::*;
 pub(crate) mod map;
 pub use self::map::*;
+pub(crate) mod pmap;
+pub use self::pmap::*;
+pub(crate) mod shuffle;
+pub use self::shuffle::*;
+pub(crate) mod random_sample;
+pub use self::random_sample::*;
+
+// reference types
+pub(crate) mod promise;
+pub use self::promise::*;
+pub(crate) mod deliver;
+pub use self::deliver::*;
+pub(crate) mod future_call;
+pub use self::future_call::*;
+pub(crate) mod deref;
+pub use self::deref::*;
+pub(crate) mod atom;
+pub use self::atom::*;
+pub(crate) mod durable_atom;
+pub use self::durable_atom::*;
+pub(crate) mod swap_bang_;
+pub use self::swap_bang_::*;
+pub(crate) mod reset_bang_;
+pub use self::reset_bang_::*;
+pub(crate) mod compare_and_set_bang_;
+pub use self::compare_and_set_bang_::*;
+pub(crate) mod add_watch;
+pub use self::add_watch::*;
+pub(crate) mod remove_watch;
+pub use self::remove_watch::*;
+pub(crate) mod ref_fn;
+pub use self::ref_fn::*;
+pub(crate) mod dosync_macro;
+pub use self::dosync_macro::*;
+pub(crate) mod with_precision_macro;
+pub use self::with_precision_macro::*;
+pub(crate) mod binding_macro;
+pub use self::binding_macro::*;
+pub(crate) mod set_bang_macro;
+pub use self::set_bang_macro::*;
+pub(crate) mod resolve;
+pub use self::resolve::*;
+pub(crate) mod alter_var_root;
+pub use self::alter_var_root::*;
+pub(crate) mod alter;
+pub use self::alter::*;
+pub(crate) mod ref_set;
+pub use self::ref_set::*;
+pub(crate) mod commute;
+pub use self::commute::*;
+pub(crate) mod agent;
+pub use self::agent::*;
+pub(crate) mod send;
+pub use self::send::*;
+pub(crate) mod await_fn;
+pub use self::await_fn::*;
+pub(crate) mod agent_error;
+pub use self::agent_error::*;
+pub(crate) mod restart_agent;
+pub use self::restart_agent::*;
+pub(crate) mod realized_qmark_;
+pub use self::realized_qmark_::*;
+
+// collections
+pub(crate) mod conj;
+pub use self::conj::*;
+pub(crate) mod disj;
+pub use self::disj::*;
+pub(crate) mod contains_qmark_;
+pub use self::contains_qmark_::*;
+pub(crate) mod byte_array;
+pub use self::byte_array::*;
+
+pub(crate) mod set;
+pub use self::set::*;
+// sorted collections
+pub(crate) mod sorted_map;
+pub use self::sorted_map::*;
+pub(crate) mod sorted_map_by;
+pub use self::sorted_map_by::*;
+pub(crate) mod sorted_set;
+pub use self::sorted_set::*;
+pub(crate) mod sorted_set_by;
+pub use self::sorted_set_by::*;
+pub(crate) mod subseq;
+pub use self::subseq::*;
+pub(crate) mod rsubseq;
+pub use self::rsubseq::*;
+
+// namespaces
+pub(crate) mod find_ns;
+pub use self::find_ns::*;
+pub(crate) mod create_ns;
+pub use self::create_ns::*;
+pub(crate) mod the_ns;
+pub use self::the_ns::*;
+pub(crate) mod ns_name;
+pub use self::ns_name::*;
+pub(crate) mod ns_publics;
+pub use self::ns_publics::*;
+pub(crate) mod ns_interns;
+pub use self::ns_interns::*;
+pub(crate) mod all_ns;
+pub use self::all_ns::*;
+pub(crate) mod ns_map;
+pub use self::ns_map::*;
+pub(crate) mod ns_refers;
+pub use self::ns_refers::*;
+pub(crate) mod ns_aliases;
+pub use self::ns_aliases::*;
+pub(crate) mod alias;
+pub use self::alias::*;
+pub(crate) mod refer;
+pub use self::refer::*;
+
+// hierarchies
+pub(crate) mod make_hierarchy;
+pub use self::make_hierarchy::*;
+pub(crate) mod derive;
+pub use self::derive::*;
+pub(crate) mod underive;
+pub use self::underive::*;
+pub(crate) mod parents;
+pub use self::parents::*;
+pub(crate) mod ancestors;
+pub use self::ancestors::*;
+pub(crate) mod descendants;
+pub use self::descendants::*;
+pub(crate) mod isa_qmark_;
+pub use self::isa_qmark_::*;
+
+// sequences
+pub(crate) mod lazy_seq_macro;
+pub use self::lazy_seq_macro::*;
+pub(crate) mod first;
+pub use self::first::*;
+pub(crate) mod rest;
+pub use self::rest::*;
+pub(crate) mod seq;
+pub use self::seq::*;
+pub(crate) mod cons;
+pub use self::cons::*;
+pub(crate) mod filter;
+pub use self::filter::*;
+pub(crate) mod take;
+pub use self::take::*;
+pub(crate) mod doall;
+pub use self::doall::*;
+pub(crate) mod dorun;
+pub use self::dorun::*;
+pub(crate) mod iterate;
+pub use self::iterate::*;
+pub(crate) mod repeat;
+pub use self::repeat::*;
+pub(crate) mod repeatedly;
+pub use self::repeatedly::*;
+pub(crate) mod range;
+pub use self::range::*;
+pub(crate) mod partition;
+pub use self::partition::*;
+pub(crate) mod drop;
+pub use self::drop::*;
+pub(crate) mod remove;
+pub use self::remove::*;
+pub(crate) mod keep;
+pub use self::keep::*;
+pub(crate) mod some;
+pub use self::some::*;
+pub(crate) mod run_bang_;
+pub use self::run_bang_::*;
+pub(crate) mod take_while;
+pub use self::take_while::*;
+pub(crate) mod drop_while;
+pub use self::drop_while::*;
+pub(crate) mod partition_all;
+pub use self::partition_all::*;
+pub(crate) mod partition_by;
+pub use self::partition_by::*;
+pub(crate) mod dedupe;
+pub use self::dedupe::*;
+pub(crate) mod halt_when;
+pub use self::halt_when::*;
+pub(crate) mod split_with;
+pub use self::split_with::*;
+pub(crate) mod split_at;
+pub use self::split_at::*;
+pub(crate) mod once;
+pub use self::once::*;
+pub(crate) mod cat;
+pub use self::cat::*;
+pub(crate) mod mapcat;
+pub use self::mapcat::*;
+
+// reducing,  and transducers;  see transducer.rs
+pub(crate) mod reduce;
+pub use self::reduce::*;
+pub(crate) mod reduced;
+pub use self::reduced::*;
+pub(crate) mod reduced_qmark_;
+pub use self::reduced_qmark_::*;
+pub(crate) mod ensure_reduced;
+pub use self::ensure_reduced::*;
+pub(crate) mod unreduced;
+pub use self::unreduced::*;
+pub(crate) mod comp;
+pub use self::comp::*;
+pub(crate) mod completing;
+pub use self::completing::*;
+pub(crate) mod transduce;
+pub use self::transduce::*;
+pub(crate) mod sequence;
+pub use self::sequence::*;
+pub(crate) mod eduction;
+pub use self::eduction::*;
+
+// transients
+pub(crate) mod transient;
+pub use self::transient::*;
+pub(crate) mod conj_bang_;
+pub use self::conj_bang_::*;
+pub(crate) mod assoc_bang_;
+pub use self::assoc_bang_::*;
+pub(crate) mod dissoc_bang_;
+pub use self::dissoc_bang_::*;
+pub(crate) mod persistent_bang_;
+pub use self::persistent_bang_::*;
+pub(crate) mod into;
+pub use self::into::*;
+pub(crate) mod mapv;
+pub use self::mapv::*;
+
+// truthiness
+pub(crate) mod boolean;
+pub use self::boolean::*;
+pub(crate) mod not;
+pub use self::not::*;
+pub(crate) mod true_qmark_;
+pub use self::true_qmark_::*;
+pub(crate) mod false_qmark_;
+pub use self::false_qmark_::*;
+pub(crate) mod string_qmark_;
+pub use self::string_qmark_::*;
+pub(crate) mod bytes_qmark_;
+pub use self::bytes_qmark_::*;
+pub(crate) mod var_qmark_;
+pub use self::var_qmark_::*;
+
+// types
+pub(crate) mod class;
+pub use self::class::*;
+pub(crate) mod bases;
+pub use self::bases::*;
+pub(crate) mod supers;
+pub use self::supers::*;
+pub(crate) mod predicates;
+pub use self::predicates::*;
+
+// protocols and the types that implement them
+pub(crate) mod defprotocol_macro;
+pub use self::defprotocol_macro::*;
+pub(crate) mod deftype_macro;
+pub use self::deftype_macro::*;
+pub(crate) mod extend;
+pub use self::extend::*;
+pub(crate) mod extend_type_macro;
+pub use self::extend_type_macro::*;
+pub(crate) mod reify_macro;
+pub use self::reify_macro::*;
+pub(crate) mod satisfies_qmark_;
+pub use self::satisfies_qmark_::*;
+
+// multimethods
+pub(crate) mod defmulti_macro;
+pub use self::defmulti_macro::*;
+pub(crate) mod defmethod_macro;
+pub use self::defmethod_macro::*;
+pub(crate) mod remove_method;
+pub use self::remove_method::*;
+pub(crate) mod remove_all_methods;
+pub use self::remove_all_methods::*;
+pub(crate) mod prefer_method;
+pub use self::prefer_method::*;
+pub(crate) mod methods;
+pub use self::methods::*;
+pub(crate) mod get_method;
+pub use self::get_method::*;
+pub(crate) mod prefers;
+pub use self::prefers::*;
+
+// sorting
+pub(crate) mod sort;
+pub use self::sort::*;
+pub(crate) mod sort_by;
+pub use self::sort_by::*;
+pub(crate) mod juxt;
+pub use self::juxt::*;
+
+// higher order fns
+pub(crate) mod apply;
+pub use self::apply::*;
+pub(crate) mod partial;
+pub use self::partial::*;
+pub(crate) mod complement;
+pub use self::complement::*;
+pub(crate) mod fnil;
+pub use self::fnil::*;
+pub(crate) mod memoize;
+pub use self::memoize::*;
+pub(crate) mod trampoline;
+pub use self::trampoline::*;
+
+// exceptions
+pub(crate) mod throw;
+pub use self::throw::*;
+pub(crate) mod ex_info;
+pub use self::ex_info::*;
+pub(crate) mod ex_data;
+pub use self::ex_data::*;
+pub(crate) mod ex_message;
+pub use self::ex_message::*;
+pub(crate) mod ex_cause;
+pub use self::ex_cause::*;
+
+// metadata
+pub(crate) mod meta;
+pub use self::meta::*;
+pub(crate) mod with_meta;
+pub use self::with_meta::*;
+pub(crate) mod vary_meta;
+pub use self::vary_meta::*;
+
+// resources
+pub(crate) mod close;
+pub use self::close::*;
+pub(crate) mod with_open_macro;
+pub use self::with_open_macro::*;
 
 // input and output
 pub(crate) mod system_newline;
@@ -58,11 +520,50 @@ pub(crate) mod string_print;
 pub use self::string_print::*;
 pub(crate) mod read_line;
 pub use self::read_line::*;
-
+pub(crate) mod with_out_str;
+pub use self::with_out_str::*;
+pub(crate) mod with_in_str;
+pub use self::with_in_str::*;
+pub(crate) mod line_seq;
+pub use self::line_seq::*;
+pub(crate) mod file_seq;
+pub use self::file_seq::*;
+pub(crate) mod spit;
+pub use self::spit::*;
+pub(crate) mod spit_bytes;
+pub use self::spit_bytes::*;
+pub(crate) mod printf;
+pub use self::printf::*;
 
 // other
 pub(crate) mod slurp;
 pub use self::slurp::*;
+pub(crate) mod slurp_bytes;
+pub use self::slurp_bytes::*;
 
 pub(crate) mod load_file;
 pub use self::load_file::*;
+pub(crate) mod load;
+pub use self::load::*;
+pub(crate) mod require;
+pub use self::require::*;
+pub(crate) mod use_fn;
+pub use self::use_fn::*;
+
+// process
+pub(crate) mod exit;
+pub use self::exit::*;
+pub(crate) mod add_shutdown_hook;
+pub use self::add_shutdown_hook::*;
+pub(crate) mod on_signal;
+pub use self::on_signal::*;
+
+// timers;  see timer.rs
+pub(crate) mod schedule_bang_;
+pub use self::schedule_bang_::*;
+pub(crate) mod every_bang_;
+pub use self::every_bang_::*;
+pub(crate) mod cancel_bang_;
+pub use self::cancel_bang_::*;
+pub(crate) mod sleep;
+pub use self::sleep::*;
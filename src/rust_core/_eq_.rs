@@ -0,0 +1,60 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (= x & more)
+///
+/// Whether all the arguments are equal
+#[derive(Debug, Clone)]
+pub struct EqualsFn {}
+impl ToValue for EqualsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for EqualsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        Value::Boolean(args.windows(2).all(|pair| pair[0] == pair[1]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod eq_tests {
+        use crate::ifn::IFn;
+        use crate::rust_core::{EqualsFn, NotEqualsFn};
+        use crate::value::{ToValue, Value};
+
+        fn args(vals: &[Value]) -> Vec<std::sync::Arc<Value>> {
+            vals.iter().map(|val| val.to_rc_value()).collect()
+        }
+
+        #[test]
+        fn equals_checks_every_argument() {
+            let eq = |vals: &[Value]| EqualsFn {}.invoke(args(vals));
+            assert_eq!(Value::Boolean(true), eq(&[Value::I32(1)]));
+            assert_eq!(
+                Value::Boolean(true),
+                eq(&[Value::I32(1), Value::I32(1), Value::I32(1)])
+            );
+            assert_eq!(
+                Value::Boolean(false),
+                eq(&[Value::I32(1), Value::I32(1), Value::I32(2)])
+            );
+        }
+
+        #[test]
+        fn not_equals_is_the_opposite() {
+            let not_eq = |vals: &[Value]| NotEqualsFn {}.invoke(args(vals));
+            assert_eq!(Value::Boolean(false), not_eq(&[Value::Nil]));
+            assert_eq!(
+                Value::Boolean(true),
+                not_eq(&[Value::I32(1), Value::I32(1), Value::I32(2)])
+            );
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (== x & more)
+///
+/// Whether all the numbers are equal by value;  unlike =,  whatever their types,  so
+/// (== 1 1.0 1N) but not (= 1 1.0)
+#[derive(Debug, Clone)]
+pub struct EquivFn {}
+impl ToValue for EquivFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for EquivFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        if let Some(arg) = args.iter().find(|arg| !numbers::is_number(arg)) {
+            return error_message::type_mismatch(TypeTag::Number, arg);
+        }
+        let mut all_equal = true;
+        for pair in args.windows(2) {
+            match numbers::equiv(&pair[0], &pair[1]) {
+                Ok(equal) => all_equal &= equal,
+                Err(condition) => return condition,
+            }
+        }
+        Value::Boolean(all_equal)
+    }
+}
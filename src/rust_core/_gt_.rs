@@ -0,0 +1,21 @@
+use crate::ifn::IFn;
+use crate::rust_core::compare::numbers_in_order;
+use crate::value::{ToValue, Value};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// (> x & more)
+///
+/// Whether the numbers are strictly decreasing
+#[derive(Debug, Clone)]
+pub struct GtFn {}
+impl ToValue for GtFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for GtFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        numbers_in_order(&args, |ordering| ordering == Ordering::Greater)
+    }
+}
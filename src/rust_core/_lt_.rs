@@ -0,0 +1,21 @@
+use crate::ifn::IFn;
+use crate::rust_core::compare::numbers_in_order;
+use crate::value::{ToValue, Value};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// (< x & more)
+///
+/// Whether the numbers are strictly increasing
+#[derive(Debug, Clone)]
+pub struct LtFn {}
+impl ToValue for LtFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for LtFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        numbers_in_order(&args, |ordering| ordering == Ordering::Less)
+    }
+}
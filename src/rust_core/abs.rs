@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (abs a)
+///
+/// The absolute value of a;  the same type of number
+#[derive(Debug, Clone)]
+pub struct AbsFn {}
+impl ToValue for AbsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AbsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        numbers::abs(&args[0])
+    }
+}
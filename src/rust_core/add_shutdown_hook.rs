@@ -0,0 +1,32 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::shutdown;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (add-shutdown-hook f)
+///
+/// Calls f, with no arguments, as the process ends;  through exit, or by reaching the end
+/// of a script
+#[derive(Debug, Clone)]
+pub struct AddShutdownHookFn {}
+impl ToValue for AddShutdownHookFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AddShutdownHookFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match ifn::as_ifn(&args[0]) {
+            Some(f) => {
+                shutdown::add_hook(f);
+                Value::Nil
+            }
+            None => error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (add-watch atom key f)
+///
+/// Calls (f key atom old new) after each change to atom.  Adding another watch under
+/// the same key replaces this one.  Returns atom
+#[derive(Debug, Clone)]
+pub struct AddWatchFn {}
+impl ToValue for AddWatchFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AddWatchFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let atom = match &*args[0] {
+            Value::Atom(atom) => atom,
+            _ => return error_message::type_mismatch(TypeTag::Atom, &args[0]),
+        };
+        match ifn::as_ifn(&args[2]) {
+            Some(f) => {
+                atom.add_watch(Arc::clone(&args[1]), f);
+                args[0].to_value()
+            }
+            None => error_message::type_mismatch(TypeTag::IFn, &args[2]),
+        }
+    }
+}
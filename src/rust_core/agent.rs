@@ -0,0 +1,33 @@
+use crate::agent::Agent;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::atom::validator_option;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (agent x & options)
+///
+/// Returns an agent holding x,  to be changed by the actions sent to it.  As with atom,
+/// the one option is :validator f
+#[derive(Debug, Clone)]
+pub struct AgentFn {}
+impl ToValue for AgentFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AgentFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let validator = match validator_option("agent", &args[1..]) {
+            Ok(validator) => validator,
+            Err(condition) => return condition,
+        };
+        match Agent::new(Arc::clone(&args[0]), validator) {
+            Ok(agent) => Value::Agent(Arc::new(agent)),
+            Err(condition) => condition,
+        }
+    }
+}
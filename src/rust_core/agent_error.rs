@@ -0,0 +1,35 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::shared_str::SharedStr;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (agent-error agent)
+///
+/// What failed agent,  as catch would have caught it;  an exception,  given rather than
+/// thrown,  so (when-let [e (agent-error a)] ..) can look at it.  nil if it hasn't failed
+#[derive(Debug, Clone)]
+pub struct AgentErrorFn {}
+impl ToValue for AgentErrorFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AgentErrorFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Agent(agent) => match agent.error() {
+                Some(error) => match &*error {
+                    Value::Condition(message) => Value::Exception(SharedStr::clone(message)),
+                    error => error.clone(),
+                },
+                None => Value::Nil,
+            },
+            _ => error_message::type_mismatch(TypeTag::Agent, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::the_ns::the_ns;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (alias alias ns)
+///
+/// Makes alias another name for the namespace named ns,  in the current namespace;  so\n/// alias/x resolves to ns/x there
+#[derive(Debug, Clone)]
+pub struct AliasFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl AliasFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> AliasFn {
+        AliasFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for AliasFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AliasFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let environment = &self.enclosing_environment;
+        let alias = match &*args[0] {
+            Value::Symbol(alias) => alias,
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        match the_ns(environment, &args[1]) {
+            Ok(namespace) => {
+                environment
+                    .find_or_create_namespace(&environment.get_current_namespace())
+                    .add_alias(alias, &namespace.name);
+                Value::Nil
+            }
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn aliases_name_namespaces_where_they_are_made() {
+        let result = Repl::default().eval_all(&[
+            "(ns lib.long-name)",
+            "(def x 1)",
+            "(ns app.core)",
+            "(alias 'short 'lib.long-name)",
+            "(def via-alias short/x)",
+            "(ns user)",
+            "[app.core/via-alias (get (ns-aliases 'app.core) 'short) (ns-aliases 'user) (try (alias 'missing 'no.such-ns) (catch Exception e :no-ns))]",
+        ]);
+        assert_eq!(
+            "[1 #namespace[lib.long-name] {} :no-ns]",
+            result.to_string()
+        );
+    }
+}
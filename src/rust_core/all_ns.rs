@@ -0,0 +1,40 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (all-ns)
+///
+/// Returns a list of every namespace
+#[derive(Debug, Clone)]
+pub struct AllNsFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl AllNsFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> AllNsFn {
+        AllNsFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for AllNsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AllNsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.is_empty() {
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        self.enclosing_environment
+            .all_namespaces()
+            .into_iter()
+            .map(|namespace| Arc::new(Value::Namespace(namespace)))
+            .collect::<Vec<Arc<Value>>>()
+            .into_list()
+            .to_value()
+    }
+}
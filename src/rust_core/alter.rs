@@ -0,0 +1,37 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::stm;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (alter ref f & args)
+///
+/// Sets ref to (apply f its-value args) within the current transaction;  returns what it
+/// was set to
+#[derive(Debug, Clone)]
+pub struct AlterFn {}
+impl ToValue for AlterFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AlterFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let reference = match &*args[0] {
+            Value::Ref(reference) => reference,
+            _ => return error_message::type_mismatch(TypeTag::Ref, &args[0]),
+        };
+        let f = match ifn::as_ifn(&args[1]) {
+            Some(ifn) => ifn,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        };
+        match stm::alter(reference, &f, &args[2..]) {
+            Ok(val) => val.to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (alter-var-root var f & args)
+///
+/// Gives var (f root args..) as its root,  where root is what it held outside of any
+/// binding;  and returns it
+#[derive(Debug, Clone)]
+pub struct AlterVarRootFn {}
+impl ToValue for AlterVarRootFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AlterVarRootFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let var = match &*args[0] {
+            Value::Var(var) => var,
+            _ => return error_message::type_mismatch(TypeTag::Var, &args[0]),
+        };
+        let root = var.root();
+        if let Value::Condition(_) = &*root {
+            return (*root).clone();
+        }
+        let mut f_args = vec![root];
+        f_args.extend_from_slice(&args[2..]);
+        match ifn::call(&args[1], f_args) {
+            Value::Condition(message) => Value::Condition(message),
+            root => {
+                let root = root.to_rc_value();
+                var.set_root(Arc::clone(&root));
+                (*root).clone()
+            }
+        }
+    }
+}
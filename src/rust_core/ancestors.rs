@@ -0,0 +1,33 @@
+use crate::hierarchy;
+use crate::ifn::IFn;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ancestors tag) or (ancestors h tag)
+///
+/// Returns every tag tag derives from, directly or not, or nil
+#[derive(Debug, Clone)]
+pub struct AncestorsFn {}
+impl ToValue for AncestorsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AncestorsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (hierarchy, args) = match hierarchy::hierarchy_and_args(&args, 1) {
+            Ok(split) => split,
+            Err(condition) => return condition,
+        };
+        let related = hierarchy.ancestors(&args[0]);
+        if related.is_empty() {
+            Value::Nil
+        } else {
+            related
+                .into_iter()
+                .collect::<PersistentHashSet>()
+                .to_value()
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (and x y ..)
+///
+/// Evaluates each form in turn,  stopping at the first falsey one.  Returns the value of
+/// the form it stopped at,  or of the last form,  or true if there are none
+///
+/// (and x y z) expands to
+///
+/// (let [and# x] (if and# (and y z) and#))
+#[derive(Debug, Clone)]
+pub struct AndMacro {}
+impl ToValue for AndMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for AndMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match args.split_first() {
+            Some((first, rest)) => short_circuit("and", first, rest, true),
+            None => Value::Boolean(true),
+        }
+    }
+}
+
+/// Shared by and and or;  the expansion that evaluates first once,  returning it if its
+/// truthiness is not continue_when,  and moving on to the rest of the forms if it is
+pub(crate) fn short_circuit(
+    name: &str,
+    first: &Arc<Value>,
+    rest: &[Arc<Value>],
+    continue_when: bool,
+) -> Value {
+    match rest {
+        [] => (**first).clone(),
+        _ => {
+            let local = Symbol::intern(&format!("{}__auto__", name)).to_rc_value();
+            let mut carry_on = vec![Symbol::intern(name).to_rc_value()];
+            carry_on.extend_from_slice(rest);
+            let carry_on = carry_on.into_list().to_rc_value();
+            let (then, otherwise) = if continue_when {
+                (carry_on, Arc::clone(&local))
+            } else {
+                (Arc::clone(&local), carry_on)
+            };
+            let if_expr = vec![
+                Symbol::intern("if").to_rc_value(),
+                Arc::clone(&local),
+                then,
+                otherwise,
+            ]
+            .into_list();
+            let bindings = vec![local, Arc::clone(first)]
+                .into_iter()
+                .collect::<PersistentVector>();
+            vec![
+                Symbol::intern("let").to_rc_value(),
+                bindings.to_rc_value(),
+                if_expr.to_rc_value(),
+            ]
+            .into_list()
+            .to_value()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod and_macro_tests {
+        use crate::ifn::IFn;
+        use crate::rust_core::{AndMacro, OrMacro};
+        use crate::symbol::Symbol;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn sym(name: &str) -> Arc<Value> {
+            Symbol::intern(name).to_rc_value()
+        }
+
+        #[test]
+        fn and_stops_at_the_first_falsey_form() {
+            let expansion = AndMacro {}.invoke(vec![sym("x"), sym("y"), sym("z")]);
+            assert_eq!(
+                "(let [and__auto__ x] (if and__auto__ (and y z) and__auto__))",
+                expansion.to_string()
+            );
+        }
+
+        #[test]
+        fn or_stops_at_the_first_truthy_form() {
+            let expansion = OrMacro {}.invoke(vec![sym("x"), sym("y")]);
+            assert_eq!(
+                "(let [or__auto__ x] (if or__auto__ or__auto__ (or y)))",
+                expansion.to_string()
+            );
+        }
+
+        #[test]
+        fn no_forms_is_the_identity() {
+            assert_eq!(Value::Boolean(true), AndMacro {}.invoke(vec![]));
+            assert_eq!(Value::Nil, OrMacro {}.invoke(vec![]));
+            assert_eq!(*sym("x"), AndMacro {}.invoke(vec![sym("x")]));
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (apply f args) (apply f x & args)
+///
+/// f called on the elements of args;  after any xs,  so (apply + 1 2 [3 4]) is
+/// (+ 1 2 3 4).  f can be a fn,  a keyword or a var
+#[derive(Debug, Clone)]
+pub struct ApplyFn {}
+impl ToValue for ApplyFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ApplyFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (spread, args) = args.split_last().unwrap();
+        let mut f_args = args[1..].to_vec();
+        for val in lazy_seq::iter(Arc::clone(spread)) {
+            if let Value::Condition(_) = &*val {
+                return val.to_value();
+            }
+            f_args.push(val);
+        }
+        ifn::call(&args[0], f_args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod apply_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn apply_spreads_its_last_arg() {
+            let result = Repl::default().eval_all(&[
+                "[(apply + [1 2]) (apply + 1 2 '(3 4)) (apply str nil) (apply :a [{:a 1}]) (apply list '[(+ 1 2)])]",
+            ]);
+            assert_eq!("[3 10 \"\" 1 ((+ 1 2))]", result.to_string());
+        }
+
+        #[test]
+        fn maps_sets_and_keywords_can_be_called_wherever_a_fn_can() {
+            let result = Repl::default().eval_all(&[
+                "[({:a 1} :a) ({:a 1} :b 2) (#{1 2} 2) (:b {:a 1} 3) (apply {:a 1} [:a]) (map {1 :x 2 :y} [1 2]) (filter #{1 3} [1 2 3]) ((comp :a first) [{:a 4}])]",
+            ]);
+            assert_eq!("[1 2 2 3 1 (:x :y) (1 3) 4]", result.to_string());
+        }
+
+        #[test]
+        fn apply_needs_a_seqable_last_arg() {
+            let result = Repl::default().eval_all(&["(apply + 1 2)"]);
+            assert_eq!(
+                "#Condition[\"Don't know how to create ISeq from: rust.std.i32\"]",
+                result.to_string()
+            );
+        }
+    }
+}
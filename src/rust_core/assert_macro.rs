@@ -0,0 +1,85 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (assert x) (assert x message)
+///
+/// Evaluates x,  and if it isn't truthy,  fails with "Assert failed: " and x's form (after
+/// message,  if there is one);  caught as an AssertionError.  Returns nil
+///
+/// (assert (pos? n) "n must be positive") expands to
+///
+/// (if (pos? n) nil (clojure.core/assert-failed* (quote (pos? n)) "n must be positive"))
+#[derive(Debug, Clone)]
+pub struct AssertMacro {}
+impl ToValue for AssertMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for AssertMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let mut failed = vec![
+            Symbol::intern_with_ns("clojure.core", "assert-failed*").to_rc_value(),
+            vec![Symbol::intern("quote").to_rc_value(), Arc::clone(&args[0])]
+                .into_list()
+                .to_rc_value(),
+        ];
+        failed.extend(args.get(1).cloned());
+        vec![
+            Symbol::intern("if").to_rc_value(),
+            Arc::clone(&args[0]),
+            Value::Nil.to_rc_value(),
+            failed.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+/// (clojure.core/assert-failed* form) (clojure.core/assert-failed* form message)
+///
+/// The condition for an assert of form that failed
+#[derive(Debug, Clone)]
+pub struct AssertFailedFn {}
+impl ToValue for AssertFailedFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AssertFailedFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match &args[..] {
+            [form] => Value::Condition(format!("Assert failed: {}", form).into()),
+            [form, message] => {
+                Value::Condition(format!("Assert failed: {}\n{}", message, form).into())
+            }
+            _ => error_message::wrong_varg_count(&[1, 2], args.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod assert_macro_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn assert_fails_unless_x_is_truthy() {
+            let result = Repl::default().eval_all(&[
+                "(defn message [f] (try (f) (catch AssertionError e (ex-message e))))",
+                "[(assert (= 1 1)) (message #(assert (= 1 2))) (message #(assert (nil? 1) (str \"not \" \"nil\")))]",
+            ]);
+            assert_eq!(
+                "[nil \"Assert failed: (= 1 2)\" \"Assert failed: not nil\\n(nil? 1)\"]",
+                result.to_string()
+            );
+        }
+    }
+}
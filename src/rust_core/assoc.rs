@@ -1,44 +1,76 @@
+use crate::error_message;
 use crate::ifn::IFn;
-use crate::persistent_list_map::IPersistentMap;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::type_tag::TypeTag;
 use crate::util::IsEven;
 use crate::value::{ToValue, Value};
 use itertools::Itertools;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// (assoc map key val & kvs)
 ///
 // General assoc fn; however,  currently just implemented
-// for our one map type, PersistentListMap
+// for PersistentListMap, PersistentTreeMap, PersistentVector and nil
 #[derive(Debug, Clone)]
 pub struct AssocFn {}
 impl ToValue for AssocFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for AssocFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         // We don't want even args, because assoc works like
         // (assoc {} :a 1) ;; 3 args
         // (assoc {} :a 1 :b 2) ;; 5 args
         // (assoc {} :a 1 :b 2 :c 3) ;; 7 args ...
         if args.len() < 3 || args.len().is_even() {
-            return Value::Condition(format!(
+            return Value::Condition(
+                format!(
                 "Wrong number of arguments given to function (Given: {}, Expected: 3 | 5 | 7 | ..)",
                 args.len()
-            ));
+            )
+                .into(),
+            );
         }
 
-        if let Value::PersistentListMap(pmap) = &*(args.get(0).unwrap().clone()) {
-            let mut retval = pmap.clone();
+        // nil's taken as an empty map
+        let pmap = match &*args[0] {
+            Value::PersistentListMap(pmap) => Some(Arc::clone(pmap)),
+            Value::Nil => Some(Arc::new(PersistentListMap::Empty)),
+            _ => None,
+        };
+        if let Some(pmap) = pmap {
+            let mut retval = pmap;
             for (key_value, val_value) in args.into_iter().skip(1).tuples() {
                 let key = key_value.to_rc_value();
                 let val = val_value.to_rc_value();
-                println!("key: {:?}, val: {:?}", key, val);
-                retval = pmap.assoc(key, val);
+                retval = retval.assoc(key, val);
             }
             return Value::PersistentListMap(retval);
         }
+        if let Value::PersistentTreeMap(ptmap) = &*args[0] {
+            return args[1..]
+                .chunks(2)
+                .fold((**ptmap).clone(), |ptmap, keyval| {
+                    ptmap.assoc(Arc::clone(&keyval[0]), Arc::clone(&keyval[1]))
+                })
+                .to_value();
+        }
+        if let Value::PersistentVector(pvector) = &*args[0] {
+            let mut pvector = (**pvector).clone();
+            for keyval in args[1..].chunks(2) {
+                let ind = match &*keyval[0] {
+                    Value::I32(ind) if *ind >= 0 => *ind as usize,
+                    _ => return error_message::type_mismatch(TypeTag::Integer, &keyval[0]),
+                };
+                pvector = match pvector.assoc(ind, Arc::clone(&keyval[1])) {
+                    Some(pvector) => pvector,
+                    None => return error_message::index_out_of_bounds(ind, pvector.len()),
+                };
+            }
+            return pvector.to_value();
+        }
 
         Value::Nil
     }
@@ -0,0 +1,51 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::util::IsEven;
+use crate::value::{ToValue, Value};
+use itertools::Itertools;
+use std::sync::Arc;
+
+/// (assoc! tcoll key val & kvs)
+///
+/// Sets each key to its val in a transient map or vector,  in place,  and returns it
+#[derive(Debug, Clone)]
+pub struct AssocBangFn {}
+impl ToValue for AssocBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AssocBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 3 || args.len().is_even() {
+            return Value::Condition(
+                format!(
+                "Wrong number of arguments given to function (Given: {}, Expected: 3 | 5 | 7 | ..)",
+                args.len()
+            )
+                .into(),
+            );
+        }
+        let tcoll = &args[0];
+        for (key, val) in args[1..].iter().tuples() {
+            let assoced = match (&**tcoll, &**key) {
+                (Value::TransientMap(tmap), _) => tmap.assoc(Arc::clone(key), Arc::clone(val)),
+                (Value::TransientVector(tvector), Value::I32(ind)) if *ind >= 0 => {
+                    tvector.assoc(*ind as usize, Arc::clone(val))
+                }
+                (Value::TransientVector(_), Value::I32(ind)) => {
+                    return error_message::index_cannot_be_negative(*ind as usize)
+                }
+                (Value::TransientVector(_), _) => {
+                    return error_message::type_mismatch(TypeTag::Integer, key)
+                }
+                _ => return error_message::type_mismatch(TypeTag::TransientMap, tcoll),
+            };
+            if let Err(condition) = assoced {
+                return condition;
+            }
+        }
+        tcoll.to_value()
+    }
+}
@@ -0,0 +1,63 @@
+use crate::atom::Atom;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::keyword::Keyword;
+use crate::type_tag::TypeTag;
+use crate::util::IsEven;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (atom x & options)
+///
+/// Returns an atom holding x.  The one option is :validator f ;  f is called on every
+/// value the atom would take,  x included,  and the atom only takes it if f returns truthy
+#[derive(Debug, Clone)]
+pub struct AtomFn {}
+impl ToValue for AtomFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AtomFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let validator = match validator_option("atom", &args[1..]) {
+            Ok(validator) => validator,
+            Err(condition) => return condition,
+        };
+        match Atom::new(Arc::clone(&args[0]), validator) {
+            Ok(atom) => Value::Atom(Arc::new(atom)),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// The :validator in options,  the option pairs given to atom or ref (named by name)
+pub(crate) fn validator_option(
+    name: &str,
+    options: &[Arc<Value>],
+) -> Result<Option<Arc<dyn IFn>>, Value> {
+    if !options.len().is_even() {
+        return Err(Value::Condition(
+            format!("Options to {} should come in pairs", name).into(),
+        ));
+    }
+    let mut validator = None;
+    for option in options.chunks(2) {
+        if *option[0] != Keyword::intern("validator").to_value() {
+            return Err(Value::Condition(
+                format!("Unknown {} option {}", name, option[0]).into(),
+            ));
+        }
+        validator = match &*option[1] {
+            Value::Nil => None,
+            _ => match ifn::as_ifn(&option[1]) {
+                Some(f) => Some(f),
+                None => return Err(error_message::type_mismatch(TypeTag::IFn, &option[1])),
+            },
+        };
+    }
+    Ok(validator)
+}
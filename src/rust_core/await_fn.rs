@@ -0,0 +1,37 @@
+use crate::agent::{self, Agent};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (await & agents)
+///
+/// Waits for every action sent so far to the agents to have run;  returns nil
+#[derive(Debug, Clone)]
+pub struct AwaitFn {}
+impl ToValue for AwaitFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for AwaitFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        // The actions we'd wait for only run once the current one is done
+        if agent::is_running() {
+            return Value::Condition("Can't await in agent action".into());
+        }
+        agent::run_pending();
+        for arg in args.iter() {
+            match &**arg {
+                Value::Agent(agent) => {
+                    if agent.error().is_some() {
+                        return Agent::failed();
+                    }
+                }
+                _ => return error_message::type_mismatch(TypeTag::Agent, arg),
+            }
+        }
+        Value::Nil
+    }
+}
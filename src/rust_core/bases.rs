@@ -0,0 +1,46 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (bases type)
+///
+/// The types type directly belongs to,  or nil
+#[derive(Debug, Clone)]
+pub struct BasesFn {}
+impl ToValue for BasesFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for BasesFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match type_named(&args[0]) {
+            Ok(type_tag) => type_list(type_tag.bases()),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// Shared with supers
+pub(crate) fn type_named(val: &Value) -> Result<TypeTag, Value> {
+    TypeTag::from_value(val)
+        .ok_or_else(|| Value::Condition(format!("No such type: {}", val).into()))
+}
+
+pub(crate) fn type_list(type_tags: Vec<TypeTag>) -> Value {
+    if type_tags.is_empty() {
+        return Value::Nil;
+    }
+    type_tags
+        .into_iter()
+        .map(|type_tag| type_tag.to_symbol().to_rc_value())
+        .collect::<Vec<Arc<Value>>>()
+        .into_list()
+        .to_value()
+}
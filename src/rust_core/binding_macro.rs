@@ -0,0 +1,153 @@
+use crate::dynamic::{self, Frame};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::{PersistentVector, ToPersistentVector};
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (binding* [var val ..] f)
+///
+/// Calls f with each var,  ^:dynamic,  bound to its val
+#[derive(Debug, Clone)]
+pub struct BindingFn {}
+impl ToValue for BindingFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for BindingFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let bindings = match &*args[0] {
+            Value::PersistentVector(bindings) => bindings.iter().cloned().collect::<Vec<_>>(),
+            _ => return error_message::type_mismatch(TypeTag::PersistentVector, &args[0]),
+        };
+        let mut frame = Frame::new();
+        for pair in bindings.chunks(2) {
+            let var = match &*pair[0] {
+                Value::Var(var) => var,
+                _ => return error_message::type_mismatch(TypeTag::Var, &pair[0]),
+            };
+            if !var.is_dynamic() {
+                return Value::Condition(
+                    format!(
+                        "Can't dynamically bind non-dynamic var: {}",
+                        var.qualified()
+                    )
+                    .into(),
+                );
+            }
+            let val = pair.get(1).cloned().unwrap_or_else(|| Arc::new(Value::Nil));
+            frame.insert(var.qualified(), val);
+        }
+        match &*args[1] {
+            Value::IFn(f) => dynamic::with_bindings(frame, || f.invoke(vec![])),
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        }
+    }
+}
+
+/// (binding [name val ..] body)
+///
+/// (binding [a 1 b 2] x y) expands to
+///
+/// (binding* [(var a) 1 (var b) 2] (fn [] x y))
+///
+/// so each val is evaluated before any name is bound
+#[derive(Debug, Clone)]
+pub struct BindingMacro {}
+impl ToValue for BindingMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for BindingMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (bindings, body) = match args.split_first() {
+            Some((bindings, body)) => (bindings, body),
+            None => return error_message::wrong_varg_count(&[1], 0),
+        };
+        let bindings = match &**bindings {
+            Value::PersistentVector(bindings) => bindings.iter().cloned().collect::<Vec<_>>(),
+            _ => return error_message::type_mismatch(TypeTag::PersistentVector, bindings),
+        };
+        if bindings.len() % 2 != 0 {
+            return Value::Condition(
+                "binding requires an even number of forms in binding vector".into(),
+            );
+        }
+        let mut vars = vec![];
+        for pair in bindings.chunks(2) {
+            vars.push(
+                vec![Symbol::intern("var").to_rc_value(), Arc::clone(&pair[0])]
+                    .into_list()
+                    .to_rc_value(),
+            );
+            vars.push(Arc::clone(&pair[1]));
+        }
+        let mut f = vec![
+            Symbol::intern("fn").to_rc_value(),
+            PersistentVector::empty().to_rc_value(),
+        ];
+        f.extend_from_slice(body);
+        vec![
+            Symbol::intern("binding*").to_rc_value(),
+            vars.into_vector().to_rc_value(),
+            f.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn binding_rebinds_dynamic_vars_for_as_long_as_its_body_runs() {
+        let result = Repl::default().eval_all(&[
+            "(ns app.config)",
+            "(def ^:dynamic *level* :info)",
+            "(defn level [] *level*)",
+            "(ns user (:require [app.config :as config]))",
+            "(def seen (binding [config/*level* :debug] [(config/level) (set! config/*level* :trace) (config/level) (binding [config/*level* :warn] (config/level)) (config/level)]))",
+            "[(var config/*level*) (var map) seen (config/level)]",
+        ]);
+        assert_eq!(
+            "[#'app.config/*level* #'clojure.core/map [:debug :trace :trace :warn :trace] :info]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn only_dynamic_vars_are_bound_and_only_bound_ones_set() {
+        let result = Repl::default().eval_all(&[
+            "(def ^:dynamic *x* 1)",
+            "(def y 2)",
+            "(defn message [f] (try (f) (catch Exception e (ex-message e))))",
+            "[(message (fn [] (binding [y 3] y))) (message (fn [] (set! *x* 3))) *x*]",
+        ]);
+        assert_eq!(
+            "[\"Can't dynamically bind non-dynamic var: user/y\" \"Can't change/establish root binding of: *x* with set\" 1]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn bindings_are_conveyed_to_futures_and_agents() {
+        let result = Repl::default().eval_all(&[
+            "(def ^:dynamic *x* 1)",
+            "(def a (agent nil))",
+            "(def f (binding [*x* 2] (send a (fn [_] *x*)) (future (set! *x* 3) *x*)))",
+            "(def bound (binding [*x* 4] (future (binding [*x* 5] nil) *x*)))",
+            "[@a @f @bound *x*]",
+        ]);
+        assert_eq!("[2 3 4 1]", result.to_string());
+    }
+}
@@ -0,0 +1,23 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (boolean x)
+///
+/// Whether x is truthy;  false if it is nil or false, and true otherwise
+#[derive(Debug, Clone)]
+pub struct BooleanFn {}
+impl ToValue for BooleanFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for BooleanFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Boolean(args[0].is_truthy())
+    }
+}
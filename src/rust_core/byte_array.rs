@@ -0,0 +1,106 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (byte-array size-or-seq)
+/// (byte-array size init-val-or-seq)
+///
+/// A byte array;  of size zeroes,  or of the numbers in seq.  Given both,  size bytes,  from
+/// the start of seq and then zeroes;  or each init-val.  Numbers from -128 to 255 will do,
+/// 255 being the byte -1 is
+#[derive(Debug, Clone)]
+pub struct ByteArrayFn {}
+impl ToValue for ByteArrayFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ByteArrayFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let bytes = match (args.len(), args.first().map(|arg| &**arg)) {
+            (1, Some(Value::I32(size))) => zeroes(*size),
+            (1, Some(_)) => bytes_of(&args[0]),
+            (2, Some(Value::I32(size))) => zeroes(*size).and_then(|mut bytes| {
+                match &*args[1] {
+                    Value::I32(_) => bytes.fill(byte(&args[1])?),
+                    _ => {
+                        let init = bytes_of(&args[1])?;
+                        let len = init.len().min(bytes.len());
+                        bytes[..len].copy_from_slice(&init[..len]);
+                    }
+                }
+                Ok(bytes)
+            }),
+            (2, Some(size)) => Err(error_message::type_mismatch(TypeTag::I32, size)),
+            (argc, _) => Err(error_message::wrong_varg_count(&[1, 2], argc)),
+        };
+        match bytes {
+            Ok(bytes) => Value::Bytes(bytes.into()),
+            Err(condition) => condition,
+        }
+    }
+}
+
+fn zeroes(size: i32) -> Result<Vec<u8>, Value> {
+    if size < 0 {
+        return Err(Value::Condition(
+            format!("Negative array size: {}", size).into(),
+        ));
+    }
+    Ok(vec![0; size as usize])
+}
+
+// The byte val is;  a number from -128 to 255
+fn byte(val: &Value) -> Result<u8, Value> {
+    match val {
+        Value::I32(i) if (-128..=255).contains(i) => Ok(*i as u8),
+        Value::I32(i) => Err(Value::Condition(
+            format!("Value out of range for byte: {}", i).into(),
+        )),
+        _ => Err(error_message::type_mismatch(TypeTag::I32, val)),
+    }
+}
+
+/// The bytes of coll;  a byte array,  or a seqable of numbers from -128 to 255
+pub(crate) fn bytes_of(coll: &Arc<Value>) -> Result<Vec<u8>, Value> {
+    if let Value::Bytes(bytes) = &**coll {
+        return Ok(bytes.to_vec());
+    }
+    let mut bytes = vec![];
+    lazy_seq::try_for_each(coll, |val| {
+        bytes.push(byte(&val)?);
+        Ok(())
+    })?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    mod byte_array_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn byte_arrays_hold_signed_bytes() {
+            let result = Repl::default().eval_all(&[
+                "(def bs (byte-array [1 -1 255 128]))",
+                "[bs (seq bs) (count bs) (byte-array 2) (byte-array 3 [7]) (byte-array 2 9) (bytes? bs) (bytes? [1]) (= bs bs) (= bs (byte-array [1]))]",
+            ]);
+            assert_eq!(
+                "[#bytes [1 -1 -1 -128] (1 -1 -1 -128) 4 #bytes [0 0] #bytes [7 0 0] #bytes [9 9] true false true false]",
+                result.to_string()
+            );
+        }
+
+        #[test]
+        fn byte_arrays_only_take_bytes() {
+            let result = Repl::default().eval_all(&["(byte-array [256])"]);
+            assert_eq!(
+                "#Condition[\"Value out of range for byte: 256\"]",
+                result.to_string()
+            );
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (bytes? x)
+///
+/// Whether x is a byte array
+#[derive(Debug, Clone)]
+pub struct BytesQmarkFn {}
+impl ToValue for BytesQmarkFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for BytesQmarkFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Boolean(matches!(&*args[0], Value::Bytes(_)))
+    }
+}
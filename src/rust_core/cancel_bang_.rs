@@ -0,0 +1,35 @@
+use crate::clojure_java_io;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::timer::Task;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (cancel! task)
+///
+/// Stops the fn that schedule! or every! gave task for being called again;  true,  unless
+/// it had already been cancelled,  or called the once it was to be
+#[derive(Debug, Clone)]
+pub struct CancelBangFn {}
+impl ToValue for CancelBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CancelBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match clojure_java_io::downcast::<Task>(&args[0]) {
+            Some(task) => Value::Boolean(task.cancel()),
+            None => Value::Condition(
+                format!(
+                    "Cannot cancel {};  give what schedule! or every! did",
+                    args[0].to_string_explicit()
+                )
+                .into(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::ifn::IFn;
+use crate::lazy_seq::{self, Step};
+use crate::transducer::{Stage, Transducer};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// cat
+///
+/// A transducer passing on each element of each input,  rather than the input
+pub fn cat() -> Transducer {
+    Transducer::new(|| Box::new(CatStage {}))
+}
+
+#[derive(Debug)]
+struct CatStage {}
+impl Stage for CatStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        step_each(rf, result, &input)
+    }
+}
+
+/// Steps rf with each element of coll in turn;  stopping at a (reduced ..) result,  and
+/// giving it as it is,  so whatever's reducing stops too
+pub(crate) fn step_each(rf: &Arc<dyn IFn>, result: Arc<Value>, coll: &Arc<Value>) -> Value {
+    let mut result = result;
+    let mut coll = Arc::clone(coll);
+    loop {
+        let (first, rest) = match lazy_seq::step(&coll) {
+            Ok(Step::Next(first, rest)) => (first, rest),
+            Ok(Step::Done) => return result.to_value(),
+            Err(condition) => return condition,
+        };
+        match rf.invoke(vec![result, first]) {
+            stepped @ Value::Reduced(_) | stepped @ Value::Condition(_) => return stepped,
+            stepped => result = Arc::new(stepped),
+        }
+        coll = rest;
+    }
+}
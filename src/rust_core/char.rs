@@ -0,0 +1,54 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+/// (char x)
+///
+/// The character whose code point is x;  a character is given back as is
+#[derive(Debug, Clone)]
+pub struct CharFn {}
+impl ToValue for CharFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CharFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Char(c) => Value::Char(*c),
+            Value::I32(i) => match u32::try_from(*i).ok().and_then(std::char::from_u32) {
+                Some(c) => Value::Char(c),
+                None => Value::Condition(format!("Value out of range for char: {}", i).into()),
+            },
+            _ => error_message::type_mismatch(TypeTag::Integer, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn chars_are_strs_ints_and_what_strings_are_made_of() {
+        let result = Repl::default().eval_all(&[
+            "[(str \\a \\space \\b) (int \\A) (char 233) (= \\a (char 97)) (nth \"héllo\" 1) (get \"abc\" 2) (get \"abc\" 3) (class \\a) (int 2.7)]",
+        ]);
+        assert_eq!(
+            "[\"a b\" 65 \\é true \\é \\c nil rust.std.char 2]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn pr_str_writes_chars_as_they_are_read() {
+        let result = Repl::default().eval_all(&["(pr-str \\a \"b\" \\newline [\\tab \\u0041])"]);
+        assert_eq!("\\a \"b\" \\newline [\\tab \\A]", result.to_string());
+    }
+}
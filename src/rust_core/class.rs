@@ -0,0 +1,31 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (class x) , also known as (type x)
+///
+/// The type of x,  as a symbol naming it;  ie (class 1) is rust.std.i32 .  See
+/// TypeTag::bases for how types relate,  and record.rs for the types deftype makes
+#[derive(Debug, Clone)]
+pub struct ClassFn {}
+impl ToValue for ClassFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ClassFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            // As in Clojure,  nil has no type
+            Value::Nil => Value::Nil,
+            Value::Instance(instance) => instance.type_().name.to_value(),
+            Value::HostObject(object) => Symbol::intern(object.type_name()).to_value(),
+            val => val.type_tag().to_symbol().to_value(),
+        }
+    }
+}
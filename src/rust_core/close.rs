@@ -0,0 +1,31 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (close resource)
+///
+/// Releases whatever a resource holds open;  this is what with-open calls on the way out.
+/// Closing nil does nothing;  a host object (a reader,  say) is closed with its close method
+#[derive(Debug, Clone)]
+pub struct CloseFn {}
+impl ToValue for CloseFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CloseFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Nil => Value::Nil,
+            Value::HostObject(object) => object
+                .call("close", vec![])
+                .unwrap_or_else(|| error_message::type_mismatch(TypeTag::Closeable, &args[0])),
+            _ => error_message::type_mismatch(TypeTag::Closeable, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::stm;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (commute ref f & args)
+///
+/// Like alter,  except that f is applied again at commit to whatever ref holds by then;
+/// so f should be commutative (+, conj ..),  but the transaction never has to retry
+/// because of this ref
+#[derive(Debug, Clone)]
+pub struct CommuteFn {}
+impl ToValue for CommuteFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CommuteFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let reference = match &*args[0] {
+            Value::Ref(reference) => reference,
+            _ => return error_message::type_mismatch(TypeTag::Ref, &args[0]),
+        };
+        let f = match ifn::as_ifn(&args[1]) {
+            Some(ifn) => ifn,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        };
+        match stm::commute(reference, &f, &args[2..]) {
+            Ok(val) => val.to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (comp & fns)
+///
+/// An fn applying the last of fns to its arguments,  then the one before it to that,  and so
+/// on;  ((comp f g) x) is (f (g x)).  As transducers wrap the reducing fn they're given,  a
+/// (comp (map f) (filter p)) maps first,  then filters.  (comp) gives back its argument.  Keywords and vars compose as the fns they call
+#[derive(Debug, Clone)]
+pub struct CompFn {}
+impl ToValue for CompFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CompFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if let Some(arg) = args.iter().find(|arg| !ifn::is_callable(arg)) {
+            return error_message::type_mismatch(TypeTag::IFn, arg);
+        }
+        match args.len() {
+            1 => args[0].to_value(),
+            _ => Value::ifn(Composed { fns: args }),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Composed {
+    fns: Vec<Arc<Value>>,
+}
+impl IFn for Composed {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (last, rest) = match self.fns.split_last() {
+            Some(fns) => fns,
+            None if args.len() == 1 => return args[0].to_value(),
+            None => return error_message::wrong_arg_count(1, args.len()),
+        };
+        let mut val = ifn::call(last, args);
+        for f in rest.iter().rev() {
+            if let Value::Condition(_) = val {
+                return val;
+            }
+            val = ifn::call(f, vec![Arc::new(val)]);
+        }
+        val
+    }
+}
@@ -0,0 +1,135 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers;
+use crate::persistent_tree_map::natural_order;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// (compare x y)
+///
+/// Returns a negative number, zero, or a positive number when x is less than, equal to, or
+/// greater than y
+#[derive(Debug, Clone)]
+pub struct CompareFn {}
+impl ToValue for CompareFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CompareFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match natural_order(&args[0], &args[1]) {
+            Some(ordering) => Value::I32(ordering as i32),
+            None => Value::Condition(
+                format!(
+                    "Cannot compare {} with {}",
+                    args[0].type_tag(),
+                    args[1].type_tag()
+                )
+                .into(),
+            ),
+        }
+    }
+}
+
+fn number(arg: &Value) -> Result<(), Value> {
+    if numbers::is_number(arg) {
+        Ok(())
+    } else {
+        Err(error_message::type_mismatch(TypeTag::Number, arg))
+    }
+}
+
+/// Shared by <, <=, > and >= ;  whether each number relates to the next as in_order says
+pub(crate) fn numbers_in_order(args: &[Arc<Value>], in_order: fn(Ordering) -> bool) -> Value {
+    if args.is_empty() {
+        return error_message::zero_arg_count(args.len());
+    }
+    if let Err(condition) = args.iter().try_for_each(|arg| number(arg)) {
+        return condition;
+    }
+    let mut all_in_order = true;
+    for pair in args.windows(2) {
+        match numbers::compare(&pair[0], &pair[1]) {
+            // Nothing is in order with NaN
+            Ok(ordering) => all_in_order &= ordering.is_some_and(in_order),
+            Err(condition) => return condition,
+        }
+    }
+    Value::Boolean(all_in_order)
+}
+
+/// Shared by min and max;  the number furthest towards wanted.  As in Clojure,  one f64
+/// makes the result an f64,  and one NaN makes it NaN
+pub(crate) fn extreme_number(args: &[Arc<Value>], wanted: Ordering) -> Value {
+    if args.is_empty() {
+        return error_message::zero_arg_count(args.len());
+    }
+    if let Err(condition) = args.iter().try_for_each(|arg| number(arg)) {
+        return condition;
+    }
+    let mut extreme = &args[0];
+    for arg in args[1..].iter() {
+        match numbers::compare(arg, extreme) {
+            Ok(Some(ordering)) if ordering == wanted => extreme = arg,
+            Ok(Some(_)) => {}
+            Ok(None) => return Value::F64(f64::NAN),
+            Err(condition) => return condition,
+        }
+    }
+    match numbers::to_f64(extreme) {
+        Some(f) if args.iter().any(|arg| matches!(**arg, Value::F64(_))) => Value::F64(f),
+        _ => (**extreme).clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod compare_tests {
+        use crate::ifn::IFn;
+        use crate::rust_core::{CompareFn, LtFn};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn compare(a: Value, b: Value) -> Value {
+            CompareFn {}.invoke(vec![Arc::new(a), Arc::new(b)])
+        }
+
+        #[test]
+        fn compares_naturally() {
+            assert_eq!(Value::I32(-1), compare(Value::I32(1), Value::F64(1.5)));
+            assert_eq!(Value::I32(1), compare("b".to_value(), "a".to_value()));
+            assert_eq!(Value::I32(0), compare(Value::Nil, Value::Nil));
+            assert_eq!(Value::I32(-1), compare(Value::Nil, Value::I32(0)));
+        }
+
+        #[test]
+        fn incomparable_values_are_a_condition() {
+            match compare(Value::I32(1), "a".to_value()) {
+                Value::Condition(_) => {}
+                other => panic!("expected a condition, got {}", other),
+            }
+        }
+
+        #[test]
+        fn less_than_chains() {
+            let lt =
+                |args: &[Value]| LtFn {}.invoke(args.iter().map(|a| a.to_rc_value()).collect());
+            assert_eq!(
+                Value::Boolean(true),
+                lt(&[Value::I32(1), Value::F64(1.5), Value::I32(2)])
+            );
+            assert_eq!(Value::Boolean(false), lt(&[Value::I32(1), Value::I32(1)]));
+            assert_eq!(Value::Boolean(true), lt(&[Value::I32(1)]));
+            assert_eq!(
+                Value::Boolean(false),
+                lt(&[Value::F64(f64::NAN), Value::I32(1)])
+            );
+        }
+    }
+}
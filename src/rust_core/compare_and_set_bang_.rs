@@ -0,0 +1,38 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (compare-and-set! atom old new)
+///
+/// Sets atom to new only if it's currently old,  and returns whether it did.  Unlike
+/// Clojure,  where old must be the very same object,  old only has to be equal;  our
+/// numbers and strings aren't interned,  so (compare-and-set! a 1 2) could otherwise never
+/// succeed
+#[derive(Debug, Clone)]
+pub struct CompareAndSetBangFn {}
+impl ToValue for CompareAndSetBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CompareAndSetBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let atom = match &*args[0] {
+            Value::Atom(atom) => atom,
+            _ => return error_message::type_mismatch(TypeTag::Atom, &args[0]),
+        };
+        let current = atom.deref();
+        if *current != *args[1] {
+            return Value::Boolean(false);
+        }
+        match atom.compare_and_set(&args[0], &current, Arc::clone(&args[2])) {
+            Ok(set) => Value::Boolean(set),
+            Err(condition) => condition,
+        }
+    }
+}
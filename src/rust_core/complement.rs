@@ -0,0 +1,42 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (complement f)
+///
+/// A fn that's true where f would be falsy,  and false where it would be truthy
+#[derive(Debug, Clone)]
+pub struct ComplementFn {}
+impl ToValue for ComplementFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ComplementFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        if !ifn::is_callable(&args[0]) {
+            return error_message::type_mismatch(TypeTag::IFn, &args[0]);
+        }
+        Value::ifn(Complement {
+            f: Arc::clone(&args[0]),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Complement {
+    f: Arc<Value>,
+}
+impl IFn for Complement {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match ifn::call(&self.f, args) {
+            Value::Condition(condition) => Value::Condition(condition),
+            val => Value::Boolean(!val.is_truthy()),
+        }
+    }
+}
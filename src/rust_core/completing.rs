@@ -0,0 +1,50 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (completing f) (completing f cf)
+///
+/// f as a reducing fn;  completed with cf,  or as it is without one.  What makes an fn of
+/// two arguments fit to give transduce
+#[derive(Debug, Clone)]
+pub struct CompletingFn {}
+impl ToValue for CompletingFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CompletingFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let mut fns = Vec::with_capacity(2);
+        for arg in args.iter() {
+            match ifn::as_ifn(arg) {
+                Some(f) => fns.push(f),
+                None => return error_message::type_mismatch(TypeTag::IFn, arg),
+            }
+        }
+        Value::ifn(Completing {
+            f: Arc::clone(&fns[0]),
+            cf: fns.get(1).cloned(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Completing {
+    f: Arc<dyn IFn>,
+    cf: Option<Arc<dyn IFn>>,
+}
+impl IFn for Completing {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match (&args[..], &self.cf) {
+            ([result], Some(cf)) => cf.invoke(vec![Arc::clone(result)]),
+            ([result], None) => result.to_value(),
+            _ => self.f.invoke(args),
+        }
+    }
+}
@@ -1,35 +1,33 @@
 use crate::ifn::IFn;
+use crate::lazy_seq::{self, LazySeq, Step};
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
-
-use crate::persistent_list::{PersistentList, ToPersistentListIter};
-use crate::persistent_vector::ToPersistentVectorIter;
+use std::sync::Arc;
 
 /// (concat x y & zs)
 ///
+/// A lazy seq of the elements of each coll in turn
 #[derive(Debug, Clone)]
 pub struct ConcatFn {}
 impl ToValue for ConcatFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for ConcatFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        let concatted_vec = args.iter().fold(Vec::new(), |mut sum, coll| {
-            let coll_vec = match &**coll {
-                Value::PersistentList(plist) => {
-                    Rc::new(plist.clone()).iter().collect::<Vec<Rc<Value>>>()
-                }
-                Value::PersistentVector(pvector) => {
-                    Rc::new(pvector.clone()).iter().collect::<Vec<Rc<Value>>>()
-                }
-                _ => vec![],
-            };
-
-            sum.extend(coll_vec);
-            sum
-        });
-        Value::PersistentList(concatted_vec.into_iter().collect::<PersistentList>())
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        lazy_concat(args).into_value()
     }
 }
+
+fn lazy_concat(colls: Vec<Arc<Value>>) -> LazySeq {
+    LazySeq::new(move || {
+        for (i, coll) in colls.iter().enumerate() {
+            if let Step::Next(first, rest) = lazy_seq::step(coll)? {
+                let mut rests = vec![rest];
+                rests.extend_from_slice(&colls[i + 1..]);
+                return Ok(Step::Next(first, Arc::new(lazy_concat(rests).into_value())));
+            }
+        }
+        Ok(Step::Done)
+    })
+}
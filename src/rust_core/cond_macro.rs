@@ -0,0 +1,74 @@
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::symbol::Symbol;
+use crate::util::IsEven;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (cond test expr ..)
+///
+/// Evaluates each test in turn,  and returns the value of the expr of the first truthy one,
+/// or nil if none are
+///
+/// (cond a x b y) expands to
+///
+/// (if a x (cond b y))
+#[derive(Debug, Clone)]
+pub struct CondMacro {}
+impl ToValue for CondMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for CondMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.len().is_even() {
+            return Value::Condition("cond requires an even number of forms".into());
+        }
+        match &args[..] {
+            [test, expr, rest @ ..] => {
+                let mut otherwise = vec![Symbol::intern("cond").to_rc_value()];
+                otherwise.extend_from_slice(rest);
+                vec![
+                    Symbol::intern("if").to_rc_value(),
+                    Arc::clone(test),
+                    Arc::clone(expr),
+                    otherwise.into_list().to_rc_value(),
+                ]
+                .into_list()
+                .to_value()
+            }
+            _ => Value::Nil,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod cond_macro_tests {
+        use crate::ifn::IFn;
+        use crate::rust_core::CondMacro;
+        use crate::symbol::Symbol;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn sym(name: &str) -> Arc<Value> {
+            Symbol::intern(name).to_rc_value()
+        }
+
+        #[test]
+        fn tests_each_clause_in_turn() {
+            let expansion = CondMacro {}.invoke(vec![sym("a"), sym("x"), sym("b"), sym("y")]);
+            assert_eq!("(if a x (cond b y))", expansion.to_string());
+            assert_eq!(Value::Nil, CondMacro {}.invoke(vec![]));
+        }
+
+        #[test]
+        fn odd_forms_is_an_error() {
+            assert!(matches!(
+                CondMacro {}.invoke(vec![sym("a"), sym("x"), sym("b")]),
+                Value::Condition(_)
+            ));
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::{cons_rc, PersistentList};
+use crate::persistent_list_map::IPersistentMap;
+use crate::persistent_vector::PersistentVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (conj) (conj coll) (conj coll x & xs)
+///
+/// Adds each x to coll, wherever is natural for coll;  the front of a list, the end of a
+/// vector.  Maps take [key val] pairs.  (conj nil x) is a list.  (conj) is [],  and
+/// (conj coll) is coll;  so conj can be a reducing fn
+#[derive(Debug, Clone)]
+pub struct ConjFn {}
+impl ToValue for ConjFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ConjFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match args.len() {
+            0 => return PersistentVector::empty().to_value(),
+            1 => return args[0].to_value(),
+            _ => (),
+        }
+        let xs = args[1..].iter().cloned();
+        match &*args[0] {
+            Value::PersistentHashSet(pset) => {
+                let mut pset = (**pset).clone();
+                for x in xs {
+                    pset = pset.conj(x);
+                }
+                pset.to_value()
+            }
+            Value::PersistentTreeSet(ptset) => xs
+                .fold((**ptset).clone(), |ptset, x| ptset.conj(x))
+                .to_value(),
+            Value::PersistentVector(pvector) => xs
+                .fold((**pvector).clone(), |pvector, x| pvector.conj(x))
+                .to_value(),
+            Value::PersistentQueue(pqueue) => xs
+                .fold((**pqueue).clone(), |pqueue, x| pqueue.conj(x))
+                .to_value(),
+            Value::PersistentList(plist) => conj_list(Arc::clone(plist), xs),
+            Value::Nil => conj_list(Arc::new(PersistentList::Empty), xs),
+            Value::PersistentListMap(pmap) => {
+                let mut pmap = Arc::clone(pmap);
+                for x in xs {
+                    match as_pair(&x) {
+                        Some((key, val)) => pmap = pmap.assoc(key, val),
+                        None => return error_message::type_mismatch(TypeTag::PersistentVector, &x),
+                    }
+                }
+                Value::PersistentListMap(pmap)
+            }
+            Value::PersistentTreeMap(ptmap) => {
+                let mut ptmap = (**ptmap).clone();
+                for x in xs {
+                    match as_pair(&x) {
+                        Some((key, val)) => ptmap = ptmap.assoc(key, val),
+                        None => return error_message::type_mismatch(TypeTag::PersistentVector, &x),
+                    }
+                }
+                ptmap.to_value()
+            }
+            _ => error_message::type_mismatch(TypeTag::ISeq, &args[0]),
+        }
+    }
+}
+
+/// A [key val] vector's key and val
+pub(crate) fn as_pair(x: &Value) -> Option<(Arc<Value>, Arc<Value>)> {
+    match x {
+        Value::PersistentVector(pair) if pair.len() == 2 => {
+            Some((Arc::clone(pair.nth(0)?), Arc::clone(pair.nth(1)?)))
+        }
+        _ => None,
+    }
+}
+
+fn conj_list(mut plist: Arc<PersistentList>, xs: impl Iterator<Item = Arc<Value>>) -> Value {
+    for x in xs {
+        plist = Arc::new(cons_rc(x, plist));
+    }
+    Value::PersistentList(plist)
+}
+
+#[cfg(test)]
+mod tests {
+    mod conj_tests {
+        use crate::ifn::IFn;
+        use crate::persistent_hash_set::PersistentHashSet;
+        use crate::persistent_list::ToPersistentList;
+        use crate::persistent_vector::PersistentVector;
+        use crate::rust_core::ConjFn;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn ints(vals: &[i32]) -> Vec<Arc<Value>> {
+            vals.iter().map(|i| i.to_rc_value()).collect()
+        }
+
+        #[test]
+        fn conj_onto_each_coll() {
+            let conj = |coll: Value| {
+                let mut args = vec![coll.to_rc_value()];
+                args.extend(ints(&[3, 4]));
+                ConjFn {}.invoke(args)
+            };
+            assert_eq!(
+                ints(&[1, 2, 3, 4])
+                    .into_iter()
+                    .collect::<PersistentVector>()
+                    .to_value(),
+                conj(
+                    ints(&[1, 2])
+                        .into_iter()
+                        .collect::<PersistentVector>()
+                        .to_value()
+                )
+            );
+            assert_eq!(
+                ints(&[4, 3, 1, 2]).into_list().to_value(),
+                conj(ints(&[1, 2]).into_list().to_value())
+            );
+            assert_eq!(ints(&[4, 3]).into_list().to_value(), conj(Value::Nil));
+            assert_eq!(
+                ints(&[1, 3, 4])
+                    .into_iter()
+                    .collect::<PersistentHashSet>()
+                    .to_value(),
+                conj(
+                    ints(&[1, 3])
+                        .into_iter()
+                        .collect::<PersistentHashSet>()
+                        .to_value()
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_vector::PersistentVector;
+use crate::rust_core::conj::as_pair;
+use crate::transient::TransientVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (conj! tcoll x)
+///
+/// Adds x to a transient vector or map,  in place,  and returns it.  As with conj,  maps
+/// take [key val] pairs
+#[derive(Debug, Clone)]
+pub struct ConjBangFn {}
+impl ToValue for ConjBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ConjBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (tcoll, x) = match &args[..] {
+            [] => {
+                return Value::TransientVector(Arc::new(TransientVector::new(
+                    PersistentVector::empty(),
+                )))
+            }
+            [tcoll] => return tcoll.to_value(),
+            [tcoll, x] => (tcoll, x),
+            _ => return error_message::wrong_varg_count(&[0, 1, 2], args.len()),
+        };
+        let conjed = match &**tcoll {
+            Value::TransientVector(tvector) => tvector.conj(Arc::clone(x)),
+            Value::TransientMap(tmap) => match as_pair(x) {
+                Some((key, val)) => tmap.assoc(key, val),
+                None => return error_message::type_mismatch(TypeTag::PersistentVector, x),
+            },
+            _ => return error_message::type_mismatch(TypeTag::TransientVector, tcoll),
+        };
+        match conjed {
+            Ok(()) => tcoll.to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
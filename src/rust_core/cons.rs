@@ -0,0 +1,34 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::LazySeq;
+use crate::persistent_list::{cons_rc, PersistentList};
+use crate::seqable;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (cons x coll)
+///
+/// A seq of x followed by the elements of coll.  Doesn't realize coll,  if it is lazy
+#[derive(Debug, Clone)]
+pub struct ConsFn {}
+impl ToValue for ConsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ConsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let x = Arc::clone(&args[0]);
+        match &*args[1] {
+            Value::PersistentList(plist) => cons_rc(x, Arc::clone(plist)).to_value(),
+            Value::Nil => cons_rc(x, Arc::new(PersistentList::Empty)).to_value(),
+            _ if seqable::seqable(&args[1]).is_some() => {
+                LazySeq::cons(x, Arc::clone(&args[1])).into_value()
+            }
+            _ => error_message::not_seqable(&args[1]),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (contains? coll key)
+///
+/// Whether key is in a set, a key of a map, or a valid index of a vector
+#[derive(Debug, Clone)]
+pub struct ContainsFn {}
+impl ToValue for ContainsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ContainsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let key = &args[1];
+        match &*args[0] {
+            Value::PersistentHashSet(pset) => Value::Boolean(pset.contains(key)),
+            Value::PersistentTreeSet(ptset) => Value::Boolean(ptset.contains(key)),
+            Value::PersistentTreeMap(ptmap) => Value::Boolean(ptmap.contains_key(key)),
+            // Not just a get, as a key can be there and map to nil
+            Value::PersistentListMap(pmap) => Value::Boolean(pmap.contains_key(key)),
+            Value::PersistentVector(pvector) => Value::Boolean(match &**key {
+                Value::I32(ind) => *ind >= 0 && (*ind as usize) < pvector.len(),
+                _ => false,
+            }),
+            Value::Nil => Value::Boolean(false),
+            _ => error_message::type_mismatch(TypeTag::PersistentHashSet, &args[0]),
+        }
+    }
+}
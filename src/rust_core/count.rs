@@ -0,0 +1,55 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (count coll)
+///
+/// How many elements coll has;  0 for nil.  A string's are its chars,  not its bytes;  see
+/// clojure.string/grapheme-count for what reads as one character
+#[derive(Debug, Clone)]
+pub struct CountFn {}
+impl ToValue for CountFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CountFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Nil => Value::I32(0),
+            Value::String(s) => Value::I32(s.chars().count() as i32),
+            Value::PersistentVector(pvector) => Value::I32(pvector.len() as i32),
+            Value::Bytes(bytes) => Value::I32(bytes.len() as i32),
+            _ => {
+                let mut count = 0;
+                match lazy_seq::try_for_each(&args[0], |_| {
+                    count += 1;
+                    Ok(())
+                }) {
+                    Ok(()) => Value::I32(count),
+                    Err(condition) => condition,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod count_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn count_counts_chars_and_elements() {
+            let result = Repl::default().eval_all(&[
+                r#"[(count nil) (count "héllo") (count "日本語") (count [1 2]) (count {:a 1}) (count (range 5))]"#,
+            ]);
+            assert_eq!("[0 5 3 2 1 5]", result.to_string());
+        }
+    }
+}
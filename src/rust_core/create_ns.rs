@@ -0,0 +1,39 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (create-ns sym)
+///
+/// Returns the namespace named by sym, creating it if it doesn't exist yet
+#[derive(Debug, Clone)]
+pub struct CreateNsFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl CreateNsFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> CreateNsFn {
+        CreateNsFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for CreateNsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for CreateNsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Symbol(sym) => {
+                Value::Namespace(self.enclosing_environment.find_or_create_namespace(sym))
+            }
+            _ => error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        }
+    }
+}
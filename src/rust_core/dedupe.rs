@@ -0,0 +1,66 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::transducer::{self, Stage, Transducer};
+use crate::value::{ToValue, Value};
+use std::sync::{Arc, Mutex};
+
+/// (dedupe) (dedupe coll)
+///
+/// A lazy seq of the elements of coll,  but for those equal to the one just before.
+/// Without coll,  a transducer passing on inputs that way
+#[derive(Debug, Clone)]
+pub struct DedupeFn {}
+impl ToValue for DedupeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DedupeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() > 1 {
+            return error_message::wrong_varg_count(&[0, 1], args.len());
+        }
+        let xform = Transducer::new(|| {
+            Box::new(DedupeStage {
+                last: Mutex::new(None),
+            })
+        });
+        match args.first() {
+            Some(coll) => transducer::sequence(&xform, Arc::clone(coll)),
+            None => xform.to_value(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DedupeStage {
+    // The input before this one,  if there's been one
+    last: Mutex<Option<Arc<Value>>>,
+}
+impl Stage for DedupeStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        let repeated = {
+            let mut last = self.last.lock().unwrap();
+            let repeated = last.as_ref().is_some_and(|last| *last == input);
+            *last = Some(Arc::clone(&input));
+            repeated
+        };
+        match repeated {
+            true => result.to_value(),
+            false => rf.invoke(vec![result, input]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn only_runs_of_equal_elements_are_deduped() {
+        let result = Repl::default().eval_all(&[
+            "[(dedupe [1 1 2 1 3 3]) (into [] (dedupe) [:a :a nil nil :b]) (dedupe [])]",
+        ]);
+        assert_eq!("[(1 2 1 3) [:a nil :b] ()]", result.to_string());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::multi_fn;
+use crate::persistent_list::ToPersistentList;
+use crate::rust_core::defprotocol_macro::quote;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (defmethod* multifn dispatch-val f)
+///
+/// Makes f the method multifn calls for dispatch-val,  and returns multifn
+#[derive(Debug, Clone)]
+pub struct DefmethodFn {}
+impl ToValue for DefmethodFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DefmethodFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let multi = match multi_fn::from_value(&args[0]) {
+            Ok(multi) => multi,
+            Err(condition) => return condition,
+        };
+        match &*args[2] {
+            Value::IFn(f) => {
+                multi.add_method(Arc::clone(&args[1]), Arc::clone(f));
+                args[0].to_value()
+            }
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[2]),
+        }
+    }
+}
+
+/// (defmethod multifn dispatch-val [args] body)
+///
+/// (defmethod area :square [s] (* (:side s) (:side s))) expands to
+///
+/// (defmethod* area :square (fn [s] (* (:side s) (:side s))))
+///
+/// A built-in type's name is taken as is,  so (defmethod describe clojure.lang.Number ..)
+/// is the method for numbers when dispatching on class
+#[derive(Debug, Clone)]
+pub struct DefmethodMacro {}
+impl ToValue for DefmethodMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DefmethodMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 3 {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to defmethod (Given: {}, Expected: >=3)",
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let dispatch_val = match TypeTag::from_value(&args[1]) {
+            Some(_) => quote(Arc::clone(&args[1])),
+            None => Arc::clone(&args[1]),
+        };
+        let mut f = vec![Symbol::intern("fn").to_rc_value()];
+        f.extend(args[2..].iter().cloned());
+        vec![
+            Symbol::intern("defmethod*").to_rc_value(),
+            Arc::clone(&args[0]),
+            dispatch_val,
+            f.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
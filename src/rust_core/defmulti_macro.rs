@@ -0,0 +1,122 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::multi_fn::{self, MultiFn};
+use crate::persistent_list::ToPersistentList;
+use crate::rust_core::defprotocol_macro::quote;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (defmulti* name dispatch-fn options)
+///
+/// Defines the multimethod name,  unless it's already defined;  so that reloading a file
+/// keeps the methods defined elsewhere.  The options are :default,  the dispatch value of
+/// the method to fall back on,  and :hierarchy,  a hierarchy (or an atom holding one) to
+/// dispatch with in place of the global one
+#[derive(Debug, Clone)]
+pub struct DefmultiFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl DefmultiFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> DefmultiFn {
+        DefmultiFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for DefmultiFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DefmultiFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 || !args.len().is_multiple_of(2) {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to defmulti (Given: {}, Expected: a name, a dispatch fn and option pairs)",
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let name = match &*args[0] {
+            Value::Symbol(sym) => sym,
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        let dispatch_fn = match &*args[1] {
+            Value::IFn(_) | Value::Keyword(_) => Arc::clone(&args[1]),
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        };
+        let mut default_dispatch_val = Keyword::intern("default").to_rc_value();
+        let mut hierarchy = None;
+        for option in args[2..].chunks(2) {
+            match &*option[0] {
+                Value::Keyword(kw) if kw.sym.name == "default" => {
+                    default_dispatch_val = Arc::clone(&option[1])
+                }
+                Value::Keyword(kw) if kw.sym.name == "hierarchy" => {
+                    hierarchy = Some(Arc::clone(&option[1]))
+                }
+                _ => {
+                    return Value::Condition(
+                        format!("Unknown defmulti option: {}", option[0]).into(),
+                    )
+                }
+            }
+        }
+        let environment = &self.enclosing_environment;
+        let existing = environment.get(name);
+        if multi_fn::from_value(&existing).is_ok() {
+            return existing.to_value();
+        }
+        let qualified_name =
+            Symbol::intern_with_ns(&environment.get_current_namespace_name(), &name.name);
+        let multi = Value::ifn(Arc::new(MultiFn::new(
+            qualified_name,
+            dispatch_fn,
+            default_dispatch_val,
+            hierarchy,
+        )));
+        environment.insert(Symbol::clone(name), Arc::new(multi.clone()));
+        multi
+    }
+}
+
+/// (defmulti name docstring? attr-map? dispatch-fn & options)
+///
+/// (defmulti area :shape :default :other) expands to
+///
+/// (defmulti* (quote area) :shape :default :other)
+#[derive(Debug, Clone)]
+pub struct DefmultiMacro {}
+impl ToValue for DefmultiMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DefmultiMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return Value::Condition(
+                "Wrong number of arguments given to defmulti (Given: 0, Expected: >=2)".into(),
+            );
+        }
+        let mut rest = &args[1..];
+        if let Some(Value::String(_)) = rest.first().map(|form| &**form) {
+            rest = &rest[1..];
+        }
+        if let Some(Value::PersistentListMap(_)) = rest.first().map(|form| &**form) {
+            rest = &rest[1..];
+        }
+        let mut expansion = vec![
+            Symbol::intern("defmulti*").to_rc_value(),
+            quote(Arc::clone(&args[0])),
+        ];
+        expansion.extend(rest.iter().cloned());
+        expansion.into_list().to_value()
+    }
+}
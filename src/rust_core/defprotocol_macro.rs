@@ -0,0 +1,156 @@
+use crate::clojure_protocol::{MethodFn, Protocol};
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::{ToPersistentList, ToPersistentListIter};
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (defprotocol* name method-specs)
+///
+/// Defines the protocol name,  and a fn for each of its methods.  Each spec is
+/// (method [this ..] [this ..] "doc"?);  the method's name, then each arglist it takes
+#[derive(Debug, Clone)]
+pub struct DefprotocolFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl DefprotocolFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> DefprotocolFn {
+        DefprotocolFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for DefprotocolFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DefprotocolFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let name = match &*args[0] {
+            Value::Symbol(sym) => sym,
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        let specs = match &*args[1] {
+            Value::PersistentList(specs) => specs.iter().collect::<Vec<Arc<Value>>>(),
+            _ => return error_message::type_mismatch(TypeTag::PersistentList, &args[1]),
+        };
+        let mut methods = vec![];
+        for spec in specs.iter() {
+            match method_spec(spec) {
+                Ok(method) => methods.push(method),
+                Err(condition) => return condition,
+            }
+        }
+        define(&self.enclosing_environment, name, methods);
+        name.to_value()
+    }
+}
+
+/// Defines the protocol name,  with methods,  in environment's current namespace;  and a
+/// fn for each of its methods
+pub fn define(
+    environment: &Environment,
+    name: &Symbol,
+    methods: Vec<(Symbol, Vec<Vec<Symbol>>)>,
+) -> Arc<Protocol> {
+    let qualified_name =
+        Symbol::intern_with_ns(&environment.get_current_namespace_name(), &name.name);
+    let protocol = Arc::new(Protocol::new(qualified_name, methods));
+    for method in protocol.method_names() {
+        let method_fn = MethodFn {
+            protocol: Arc::clone(&protocol),
+            method: method.clone(),
+        };
+        environment.insert(method, method_fn.to_rc_value());
+    }
+    environment.insert(
+        Symbol::clone(name),
+        Arc::new(Value::Protocol(Arc::clone(&protocol))),
+    );
+    protocol
+}
+
+// (method [this] [this x] "doc") => (method, [[this] [this x]])
+fn method_spec(spec: &Value) -> Result<(Symbol, Vec<Vec<Symbol>>), Value> {
+    let invalid = || Value::Condition(format!("Invalid protocol method spec: {}", spec).into());
+    let parts = match spec {
+        Value::PersistentList(parts) => parts.iter().collect::<Vec<Arc<Value>>>(),
+        _ => return Err(invalid()),
+    };
+    let name = match parts.first().map(|name| &**name) {
+        Some(Value::Symbol(name)) => Symbol::clone(name),
+        _ => return Err(invalid()),
+    };
+    let mut arglists = vec![];
+    for part in parts[1..].iter() {
+        match &**part {
+            Value::PersistentVector(arglist) => {
+                let arglist = arglist
+                    .iter()
+                    .map(|arg| match &**arg {
+                        Value::Symbol(arg) => Ok(Symbol::clone(arg)),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<Result<Vec<Symbol>, Value>>()?;
+                if arglist.is_empty() {
+                    return Err(Value::Condition(
+                        format!("Protocol method {} must take at least one arg", name).into(),
+                    ));
+                }
+                arglists.push(arglist);
+            }
+            // Docstrings
+            Value::String(_) => {}
+            _ => return Err(invalid()),
+        }
+    }
+    Ok((name, arglists))
+}
+
+/// (defprotocol name "doc"? method-specs)
+///
+/// (defprotocol Shape (area [this])) expands to
+///
+/// (defprotocol* (quote Shape) (quote ((area [this]))))
+#[derive(Debug, Clone)]
+pub struct DefprotocolMacro {}
+impl ToValue for DefprotocolMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DefprotocolMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return Value::Condition(
+                "Wrong number of arguments given to defprotocol (Given: 0, Expected: >=1)".into(),
+            );
+        }
+        let specs = args[1..]
+            .iter()
+            .filter(|spec| !matches!(&***spec, Value::String(_)))
+            .cloned()
+            .collect::<Vec<Arc<Value>>>();
+        vec![
+            Symbol::intern("defprotocol*").to_rc_value(),
+            quote(Arc::clone(&args[0])),
+            quote(specs.into_list().to_rc_value()),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+/// (quote form)
+pub(crate) fn quote(form: Arc<Value>) -> Arc<Value> {
+    vec![Symbol::intern("quote").to_rc_value(), form]
+        .into_list()
+        .to_rc_value()
+}
@@ -0,0 +1,217 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::record::{MapConstructorFn, PositionalConstructorFn, Type};
+use crate::rust_core::defprotocol_macro::quote;
+use crate::rust_core::extend_type_macro::{
+    arglist_syms, method_impl_form, protocol_impls, symbol_vector,
+};
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (deftype* name fields record?)
+///
+/// Defines the type name,  and ->name to make one from its fields;  and for a record,
+/// map->name to make one from a map of them
+#[derive(Debug, Clone)]
+pub struct DeftypeFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl DeftypeFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> DeftypeFn {
+        DeftypeFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for DeftypeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DeftypeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let name = match &*args[0] {
+            Value::Symbol(sym) => sym,
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        let fields = arglist_syms(&args[1]);
+        let environment = &self.enclosing_environment;
+        let type_ = Arc::new(Type::new(
+            &environment.get_current_namespace_name(),
+            name,
+            fields,
+            args[2].is_truthy(),
+        ));
+        let type_name = type_.name.to_rc_value();
+        let constructor = PositionalConstructorFn {
+            type_: Arc::clone(&type_),
+        };
+        environment.insert(
+            Symbol::intern(&format!("->{}", name.name)),
+            constructor.to_rc_value(),
+        );
+        if type_.is_record {
+            let constructor = MapConstructorFn { type_ };
+            environment.insert(
+                Symbol::intern(&format!("map->{}", name.name)),
+                constructor.to_rc_value(),
+            );
+        }
+        environment.insert(Symbol::clone(name), Arc::clone(&type_name));
+        type_name.to_value()
+    }
+}
+
+/// (field* x name)
+///
+/// The field of x called name;  how a deftype's methods see its fields
+#[derive(Debug, Clone)]
+pub struct FieldFn {}
+impl ToValue for FieldFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FieldFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let instance = match &*args[0] {
+            Value::Instance(instance) => instance,
+            _ => return error_message::type_mismatch(TypeTag::IType, &args[0]),
+        };
+        match &*args[1] {
+            Value::Symbol(field) => match instance.field(field) {
+                Some(val) => val.to_value(),
+                None => Value::Condition(
+                    format!("No field {} in {}", field, instance.type_().name).into(),
+                ),
+            },
+            _ => error_message::type_mismatch(TypeTag::Symbol, &args[1]),
+        }
+    }
+}
+
+// (deftype name [fields] protocol method-impls ..) or the same for defrecord
+fn expand_deftype(macro_name: &str, args: &[Arc<Value>], is_record: bool) -> Value {
+    if args.len() < 2 {
+        return Value::Condition(
+            format!(
+                "Wrong number of arguments given to {} (Given: {}, Expected: >=2)",
+                macro_name,
+                args.len()
+            )
+            .into(),
+        );
+    }
+    let fields = arglist_syms(&args[1]);
+    let groups = match protocol_impls(&args[2..]) {
+        Ok(groups) => groups,
+        Err(condition) => return condition,
+    };
+    let mut expansion = vec![
+        Symbol::intern("do").to_rc_value(),
+        vec![
+            Symbol::intern("deftype*").to_rc_value(),
+            quote(Arc::clone(&args[0])),
+            quote(symbol_vector(&fields)),
+            Arc::new(Value::Boolean(is_record)),
+        ]
+        .into_list()
+        .to_rc_value(),
+    ];
+    for (protocol, impls) in groups {
+        let mut extend_type = vec![
+            Symbol::intern("extend-type").to_rc_value(),
+            Arc::clone(&args[0]),
+            protocol,
+        ];
+        for (name, arglist, body) in impls {
+            let body = with_fields(&fields, &arglist, body);
+            extend_type.push(method_impl_form((name, arglist, body)));
+        }
+        expansion.push(extend_type.into_list().to_rc_value());
+    }
+    expansion.push(Arc::clone(&args[0]));
+    expansion.into_list().to_value()
+}
+
+// Wraps a method's body so it sees the fields of its first arg,  as in
+//
+// (let [x (field* this (quote x)) ..] body)
+//
+// leaving out any field one of its args is named after,  as the arg should win
+fn with_fields(fields: &[Symbol], arglist: &Value, body: Vec<Arc<Value>>) -> Vec<Arc<Value>> {
+    let args = arglist_syms(arglist);
+    let this = match args.first() {
+        Some(this) => this,
+        None => return body,
+    };
+    let mut bindings = vec![];
+    for field in fields.iter().filter(|field| !args.contains(field)) {
+        bindings.push(field.to_rc_value());
+        bindings.push(
+            vec![
+                Symbol::intern("field*").to_rc_value(),
+                this.to_rc_value(),
+                quote(field.to_rc_value()),
+            ]
+            .into_list()
+            .to_rc_value(),
+        );
+    }
+    let mut let_form = vec![
+        Symbol::intern("let").to_rc_value(),
+        bindings
+            .into_iter()
+            .collect::<PersistentVector>()
+            .to_rc_value(),
+    ];
+    let_form.extend(body);
+    vec![let_form.into_list().to_rc_value()]
+}
+
+/// (deftype name [fields] protocol method-impls ..)
+///
+/// (deftype Circle [r] Shape (area [c] (* 3 r r))) expands to
+///
+/// (do (deftype* (quote Circle) (quote [r]) false)
+///     (extend-type Circle Shape (area [c] (let [r (field* c (quote r))] (* 3 r r))))
+///     Circle)
+#[derive(Debug, Clone)]
+pub struct DeftypeMacro {}
+impl ToValue for DeftypeMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DeftypeMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        expand_deftype("deftype", &args, false)
+    }
+}
+
+/// (defrecord name [fields] protocol method-impls ..)
+///
+/// As deftype,  except that what it makes is a record
+#[derive(Debug, Clone)]
+pub struct DefrecordMacro {}
+impl ToValue for DefrecordMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DefrecordMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        expand_deftype("defrecord", &args, true)
+    }
+}
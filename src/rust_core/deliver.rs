@@ -0,0 +1,33 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (deliver promise val)
+///
+/// Returns the promise, or nil if it had already been delivered
+#[derive(Debug, Clone)]
+pub struct DeliverFn {}
+impl ToValue for DeliverFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DeliverFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match &*args[0] {
+            Value::Promise(promise) => {
+                if promise.deliver(Arc::clone(&args[1])) {
+                    args[0].to_value()
+                } else {
+                    Value::Nil
+                }
+            }
+            _ => error_message::type_mismatch(TypeTag::Promise, &args[0]),
+        }
+    }
+}
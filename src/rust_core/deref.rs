@@ -0,0 +1,158 @@
+use crate::error_message;
+use crate::ideref::IBlockingDeref;
+use crate::ifn::IFn;
+use crate::interrupt;
+use crate::stm;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// (deref ref) or (deref ref timeout-ms timeout-val) ; also written @ref
+///
+/// With a timeout, waits at most timeout-ms for a promise, future (or any other
+/// blocking reference) to get its value, and returns timeout-val if it doesn't.  An atom,
+/// ref,  agent,  var or reduced has no timeout,  as it always has a value
+#[derive(Debug, Clone)]
+pub struct DerefFn {}
+impl ToValue for DerefFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+
+pub(crate) fn as_blocking_deref(value: &Value) -> Option<&dyn IBlockingDeref> {
+    match value {
+        Value::Promise(promise) => Some(&**promise),
+        Value::Future(future) => Some(&**future),
+        _ => None,
+    }
+}
+
+impl IFn for DerefFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[1, 3], args.len());
+        }
+        // An atom, ref or agent always has its value;  there's nothing to wait for
+        let val = match &*args[0] {
+            Value::Atom(atom) => Some(atom.deref()),
+            Value::Ref(reference) => Some(stm::deref(reference)),
+            Value::Agent(agent) => Some(agent.deref()),
+            Value::Var(var) => Some(var.deref()),
+            Value::Reduced(val) => Some(Arc::clone(val)),
+            _ => None,
+        };
+        if let Some(val) = val {
+            return match args.len() {
+                1 => val.to_value(),
+                _ => error_message::wrong_arg_count(1, args.len()),
+            };
+        }
+        let blocking_ref = match as_blocking_deref(&args[0]) {
+            Some(blocking_ref) => blocking_ref,
+            None => return error_message::type_mismatch(TypeTag::IBlockingDeref, &args[0]),
+        };
+        if args.len() == 1 {
+            return match blocking_ref.deref_blocking(None) {
+                Some(val) => val.to_value(),
+                None => Value::Condition(interrupt::INTERRUPTED_MSG.into()),
+            };
+        }
+        let timeout_ms = match &*args[1] {
+            Value::I32(ms) if *ms >= 0 => *ms as u64,
+            _ => return error_message::type_mismatch(TypeTag::I32, &args[1]),
+        };
+        match blocking_ref.deref_blocking(Some(Duration::from_millis(timeout_ms))) {
+            Some(val) => val.to_value(),
+            None => args[2].to_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod deref_tests {
+        use crate::ifn::IFn;
+        use crate::repl::Repl;
+        use crate::rust_core::AddFn;
+        use crate::rust_core::{DeliverFn, DerefFn, FutureCallFn, PromiseFn};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn timeout_args(reference: &Arc<Value>) -> Vec<Arc<Value>> {
+            vec![
+                Arc::clone(reference),
+                Arc::new(Value::I32(10)),
+                Arc::new(Value::Keyword(Arc::new(crate::keyword::Keyword::intern(
+                    "timeout",
+                )))),
+            ]
+        }
+
+        #[test]
+        fn deref_delivered_promise() {
+            let promise = PromiseFn {}.invoke(vec![]).to_rc_value();
+            DeliverFn {}.invoke(vec![Arc::clone(&promise), Arc::new(Value::I32(5))]);
+            assert_eq!(Value::I32(5), DerefFn {}.invoke(vec![Arc::clone(&promise)]));
+            assert_eq!(Value::I32(5), DerefFn {}.invoke(timeout_args(&promise)));
+        }
+
+        #[test]
+        fn deliver_only_once() {
+            let promise = PromiseFn {}.invoke(vec![]).to_rc_value();
+            DeliverFn {}.invoke(vec![Arc::clone(&promise), Arc::new(Value::I32(1))]);
+            let second = DeliverFn {}.invoke(vec![Arc::clone(&promise), Arc::new(Value::I32(2))]);
+            assert_eq!(Value::Nil, second);
+            assert_eq!(Value::I32(1), DerefFn {}.invoke(vec![promise]));
+        }
+
+        #[test]
+        fn deref_pending_promise_times_out() {
+            let promise = PromiseFn {}.invoke(vec![]).to_rc_value();
+            assert_eq!(
+                *timeout_args(&promise)[2],
+                DerefFn {}.invoke(timeout_args(&promise))
+            );
+        }
+
+        #[test]
+        fn deref_waits_for_a_promise_delivered_on_another_thread() {
+            let result = Repl::default().eval_all(&[
+                "(def p (promise))",
+                "(def f (future [@p (realized? p)]))",
+                "(def before (realized? f))",
+                "(deliver p 1)",
+                "[before @f (realized? f)]",
+            ]);
+            assert_eq!("[false [1 true] true]", result.to_string());
+        }
+
+        #[test]
+        fn what_a_future_gives_keeps_its_metadata_and_ex_data() {
+            let result = Repl::default().eval_all(&[
+                "(def v @(future (with-meta [1] {:m 2})))",
+                "(def e (try @(future (throw (ex-info \"no\" {:a 1}))) (catch Exception e e)))",
+                "[v (meta v) (ex-message e) (ex-data e)]",
+            ]);
+            assert_eq!("[[1] {:m 2} \"no\" {:a 1}]", result.to_string());
+        }
+
+        #[test]
+        fn deref_future() {
+            let future = FutureCallFn {}
+                .invoke(vec![AddFn {}.to_rc_value()])
+                .to_rc_value();
+            assert_eq!(Value::I32(0), DerefFn {}.invoke(vec![Arc::clone(&future)]));
+            assert_eq!(Value::I32(0), DerefFn {}.invoke(timeout_args(&future)));
+        }
+
+        #[test]
+        fn deref_non_reference_is_an_error() {
+            assert!(matches!(
+                DerefFn {}.invoke(vec![Arc::new(Value::I32(1))]),
+                Value::Condition(_)
+            ));
+        }
+    }
+}
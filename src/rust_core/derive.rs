@@ -0,0 +1,33 @@
+use crate::hierarchy;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (derive tag parent) or (derive h tag parent)
+///
+/// Makes tag derive from parent, in the global hierarchy (returning nil),
+/// or in a new version of h (returning that)
+#[derive(Debug, Clone)]
+pub struct DeriveFn {}
+impl ToValue for DeriveFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DeriveFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let explicit = args.len() == 3;
+        let (hierarchy, args) = match hierarchy::hierarchy_and_args(&args, 2) {
+            Ok(split) => split,
+            Err(condition) => return condition,
+        };
+        match hierarchy.derive(Arc::clone(&args[0]), Arc::clone(&args[1])) {
+            Ok(derived) if explicit => derived.to_value(),
+            Ok(derived) => {
+                hierarchy::set_global(derived);
+                Value::Nil
+            }
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (disj set & ks)
+///
+/// Returns set without any of ks
+#[derive(Debug, Clone)]
+pub struct DisjFn {}
+impl ToValue for DisjFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DisjFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        match &*args[0] {
+            Value::PersistentHashSet(pset) => {
+                let mut pset = (**pset).clone();
+                for k in args[1..].iter() {
+                    pset = pset.disj(k);
+                }
+                pset.to_value()
+            }
+            Value::PersistentTreeSet(ptset) => args[1..]
+                .iter()
+                .fold((**ptset).clone(), |ptset, k| ptset.disj(k))
+                .to_value(),
+            Value::Nil => Value::Nil,
+            _ => error_message::type_mismatch(TypeTag::PersistentHashSet, &args[0]),
+        }
+    }
+}
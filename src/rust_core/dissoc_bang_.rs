@@ -0,0 +1,33 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (dissoc! tmap key & ks)
+///
+/// Removes each key from a transient map,  in place,  and returns it
+#[derive(Debug, Clone)]
+pub struct DissocBangFn {}
+impl ToValue for DissocBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DissocBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let tmap = match &*args[0] {
+            Value::TransientMap(tmap) => tmap,
+            _ => return error_message::type_mismatch(TypeTag::TransientMap, &args[0]),
+        };
+        for key in args[1..].iter() {
+            if let Err(condition) = tmap.dissoc(key) {
+                return condition;
+            }
+        }
+        args[0].to_value()
+    }
+}
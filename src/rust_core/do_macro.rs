@@ -1,7 +1,7 @@
 use crate::ifn::IFn;
 use crate::symbol::Symbol;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::persistent_list::ToPersistentList;
 
@@ -11,11 +11,11 @@ use crate::persistent_list::ToPersistentList;
 pub struct DoFn {}
 impl ToValue for DoFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for DoFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         // @TODO generalize arity exceptions, and other exceptions
         if args.is_empty() {
             return Value::Nil;
@@ -34,14 +34,14 @@ impl IFn for DoFn {
 pub struct DoMacro {}
 impl ToValue for DoMacro {
     fn to_value(&self) -> Value {
-        Value::Macro(Rc::new(self.clone()))
+        Value::macro_(self.clone())
     }
 }
 impl IFn for DoMacro {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         // @TODO generalize arity exceptions, and other exceptions
         if args.is_empty() {
-            return vec![Symbol::intern("do").to_rc_value(), Rc::new(Value::Nil)]
+            return vec![Symbol::intern("do").to_rc_value(), Arc::new(Value::Nil)]
                 .into_list()
                 .to_value();
         }
@@ -49,7 +49,7 @@ impl IFn for DoMacro {
         let args_for_ret_expr = args
             .iter()
             .map(|arg| arg.to_rc_value())
-            .collect::<Vec<Rc<Value>>>();
+            .collect::<Vec<Arc<Value>>>();
 
         let mut do_body = vec![Symbol::intern("do-fn*").to_rc_value()];
         do_body.extend_from_slice(args_for_ret_expr.get(0..).unwrap());
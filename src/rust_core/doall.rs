@@ -0,0 +1,84 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{self, Step};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (doall coll) (doall n coll)
+///
+/// Realizes all of coll,  or its first n elements;  running whatever side effects doing so
+/// has,  as a lazy map's f.  Gives back coll itself,  realized
+#[derive(Debug, Clone)]
+pub struct DoallFn {}
+impl ToValue for DoallFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DoallFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match realize(&args) {
+            Ok(()) => args.last().unwrap().to_value(),
+            Err(err) => err,
+        }
+    }
+}
+
+/// Steps through the coll last in args,  as many elements in as the n before it says if
+/// there is one;  what doall and dorun share
+pub(crate) fn realize(args: &[Arc<Value>]) -> Result<(), Value> {
+    let mut left = match args {
+        [_] => None,
+        [n, _] => match &**n {
+            Value::I32(n) => Some(*n),
+            _ => return Err(error_message::type_mismatch(TypeTag::Integer, n)),
+        },
+        _ => return Err(error_message::wrong_varg_count(&[1, 2], args.len())),
+    };
+    let mut coll = Arc::clone(args.last().unwrap());
+    while left.is_none_or(|left| left > 0) {
+        match lazy_seq::step(&coll)? {
+            Step::Next(_, rest) => coll = rest,
+            Step::Done => break,
+        }
+        left = left.map(|left| left - 1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn side_effects_run_when_forced_and_not_before() {
+        let result = Repl::default().eval_all(&[
+            "(def calls (atom []))",
+            "(defn noting [x] (swap! calls conj x) x)",
+            "(def xs (map noting (filter odd? (concat (list 1 2) (lazy-seq (list 3 4 5))))))",
+            "(def before @calls)",
+            "(def forced (doall xs))",
+            "(def after-doall @calls)",
+            "(dorun (map noting (list :a :b)))",
+            "[before after-doall forced (= xs forced) @calls]",
+        ]);
+        assert_eq!(
+            "[[] [1 3 5] (1 3 5) true [1 3 5 :a :b]]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn only_the_first_n_are_forced() {
+        let result = Repl::default().eval_all(&[
+            "(def calls (atom 0))",
+            "(def xs (map (fn [x] (swap! calls inc) x) (iterate inc 0)))",
+            "(def run (dorun 3 xs))",
+            "(def after-dorun @calls)",
+            "(doall 2 (map (fn [x] (swap! calls inc) x) (list 1 2 3 4)))",
+            "[run after-dorun @calls (dorun (list))]",
+        ]);
+        assert_eq!("[nil 3 5 nil]", result.to_string());
+    }
+}
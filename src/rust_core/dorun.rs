@@ -0,0 +1,24 @@
+use crate::ifn::IFn;
+use crate::rust_core::doall;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (dorun coll) (dorun n coll)
+///
+/// Realizes all of coll,  or its first n elements,  for the side effects of doing so;
+/// giving nil,  so none of it need be kept
+#[derive(Debug, Clone)]
+pub struct DorunFn {}
+impl ToValue for DorunFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DorunFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match doall::realize(&args) {
+            Ok(()) => Value::Nil,
+            Err(err) => err,
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (doseq [x xs] body)
+///
+/// Evaluates body once for each element of xs,  with x bound to it;  returns nil.  More
+/// bindings nest,  the last varying fastest,  and each binding can be followed by
+/// :let [bindings],  :when test (skipping the element unless test is truthy) and :while test
+/// (stopping that binding's walk once test isn't).  Expands onto reduce,  so vectors and
+/// ranges are walked by index
+///
+/// (doseq [x xs :when (p x)] a b) expands to
+///
+/// (reduce (fn [doseq__acc__ x] (when (p x) (do a b nil))) nil xs)
+#[derive(Debug, Clone)]
+pub struct DoseqMacro {}
+impl ToValue for DoseqMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DoseqMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let bindings = match args.first().map(|bindings| &**bindings) {
+            Some(Value::PersistentVector(bindings)) if bindings.len() % 2 == 0 => bindings,
+            _ => {
+                return Value::Condition(
+                    "doseq requires a vector of pairs of a symbol and a coll, ie [x xs]".into(),
+                )
+            }
+        };
+        let bindings = bindings.iter().cloned().collect::<Vec<Arc<Value>>>();
+        match expand(&bindings, &args[1..]) {
+            Ok(expansion) => expansion,
+            Err(condition) => condition,
+        }
+    }
+}
+
+fn list(forms: Vec<Arc<Value>>) -> Arc<Value> {
+    forms.into_list().to_rc_value()
+}
+
+fn sym(name: &str) -> Arc<Value> {
+    Symbol::intern(name).to_rc_value()
+}
+
+// The walk for the first binding of bindings,  and its modifiers;  around the walks for
+// the rest,  and at the last,  body
+fn expand(bindings: &[Arc<Value>], body: &[Arc<Value>]) -> Result<Value, Value> {
+    let (binding, coll) = match bindings {
+        [binding, coll, ..] if matches!(**binding, Value::Symbol(_)) => (binding, coll),
+        _ => {
+            return Err(Value::Condition(
+                "doseq binding should be a symbol and a coll".into(),
+            ))
+        }
+    };
+    let mut rest = &bindings[2..];
+    let mut modifiers = vec![];
+    while let [modifier, form, ..] = rest {
+        if !matches!(**modifier, Value::Keyword(_)) {
+            break;
+        }
+        modifiers.push((modifier, form));
+        rest = &rest[2..];
+    }
+    let mut step = vec![sym("do")];
+    if rest.is_empty() {
+        step.extend_from_slice(body);
+    } else {
+        step.push(expand(rest, body)?.to_rc_value());
+    }
+    step.push(Value::Nil.to_rc_value());
+    let mut step = list(step);
+    // Applied innermost first,  so the first modifier is the outermost test
+    for (modifier, form) in modifiers.into_iter().rev() {
+        step = match &*modifier.to_string() {
+            ":let" => list(vec![sym("let"), Arc::clone(form), step]),
+            ":when" => list(vec![sym("when"), Arc::clone(form), step]),
+            ":while" => list(vec![
+                sym("if"),
+                Arc::clone(form),
+                step,
+                list(vec![sym("reduced"), Value::Nil.to_rc_value()]),
+            ]),
+            other => {
+                return Err(Value::Condition(
+                    format!("Invalid doseq modifier: {}", other).into(),
+                ))
+            }
+        };
+    }
+    let step_fn = list(vec![
+        sym("fn"),
+        PersistentVector::empty()
+            .conj(sym("doseq__acc__"))
+            .conj(Arc::clone(binding))
+            .to_rc_value(),
+        step,
+    ]);
+    Ok(vec![
+        sym("reduce"),
+        step_fn,
+        Value::Nil.to_rc_value(),
+        Arc::clone(coll),
+    ]
+    .into_list()
+    .to_value())
+}
+
+#[cfg(test)]
+mod tests {
+    mod doseq_macro_tests {
+        use crate::ifn::IFn;
+        use crate::reader;
+        use crate::repl::Repl;
+        use crate::rust_core::DoseqMacro;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn read(source: &str) -> Arc<Value> {
+            reader::try_read(source).unwrap().1.to_rc_value()
+        }
+
+        #[test]
+        fn doseq_reduces_over_the_coll() {
+            let expansion = DoseqMacro {}.invoke(vec![read("[x xs :when (p x)]"), read("(f x)")]);
+            assert_eq!(
+                "(reduce (fn [doseq__acc__ x] (when (p x) (do (f x) nil))) nil xs)",
+                expansion.to_string()
+            );
+        }
+
+        #[test]
+        fn doseq_nests_its_bindings() {
+            let result = Repl::default().eval_all(&[
+                "(def seen (atom []))",
+                "(doseq [x [1 2 3] :while (< x 3) y (range 3) :let [z (* x y)] :when (= 1 (rem z 2))] (swap! seen conj [x y z]))",
+                "@seen",
+            ]);
+            assert_eq!("[[1 1 1]]", result.to_string());
+        }
+
+        #[test]
+        fn doseq_needs_symbols_and_colls() {
+            let expansion = DoseqMacro {}.invoke(vec![read("[1 xs]")]);
+            assert!(matches!(expansion, Value::Condition(_)));
+            let expansion = DoseqMacro {}.invoke(vec![read("[x]")]);
+            assert!(matches!(expansion, Value::Condition(_)));
+            let expansion = DoseqMacro {}.invoke(vec![read("[x xs :unless y]")]);
+            assert!(matches!(expansion, Value::Condition(_)));
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::stm;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (dosync* f)
+///
+/// Calls f in a transaction,  calling it again if the transaction has to retry
+#[derive(Debug, Clone)]
+pub struct DosyncFn {}
+impl ToValue for DosyncFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DosyncFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::IFn(f) => stm::run_in_transaction(|| f.invoke(vec![])),
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        }
+    }
+}
+
+/// (dosync body)
+///
+/// (dosync a b) expands to
+///
+/// (dosync* (fn [] a b))
+#[derive(Debug, Clone)]
+pub struct DosyncMacro {}
+impl ToValue for DosyncMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DosyncMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let mut body = vec![
+            Symbol::intern("fn").to_rc_value(),
+            PersistentVector::empty().to_rc_value(),
+        ];
+        body.extend_from_slice(&args);
+        vec![
+            Symbol::intern("dosync*").to_rc_value(),
+            body.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
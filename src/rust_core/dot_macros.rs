@@ -0,0 +1,153 @@
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentListIter;
+use crate::rust_core::thread_macros::{list, sym, thread};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (. obj method args) (. obj (method args)) (. obj -field)
+///
+/// Clojure's other way of writing a host object's method call or field;  see
+/// host_object.rs
+///
+/// (. obj (method a)) expands to
+///
+/// (.method obj a)
+#[derive(Debug, Clone)]
+pub struct DotMacro {}
+impl ToValue for DotMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DotMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let call = match &args[..] {
+            [_, member] => member_call(member),
+            [_, member, args @ ..] if matches!(**member, Value::Symbol(_)) => {
+                let mut forms = vec![Arc::clone(member)];
+                forms.extend_from_slice(args);
+                member_call(&list(forms))
+            }
+            _ => None,
+        };
+        match call {
+            Some(call) => thread(Arc::clone(&args[0]), &call, false).to_value(),
+            None => Value::Condition(
+                "Malformed member expression, expecting (. target member ...)".into(),
+            ),
+        }
+    }
+}
+
+/// (.. x form ..)
+///
+/// Calls each form's method on what the one before gave,  starting from x;  a form that
+/// isn't a list is a method of no args
+///
+/// (.. x (a 1) b) expands to
+///
+/// (.b (.a x 1))
+#[derive(Debug, Clone)]
+pub struct DotDotMacro {}
+impl ToValue for DotDotMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DotDotMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to .. (Given: {}, Expected: >=2)",
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let mut threaded = Arc::clone(&args[0]);
+        for form in &args[1..] {
+            match member_call(form) {
+                Some(call) => threaded = thread(threaded, &call, false),
+                None => {
+                    return Value::Condition(
+                        format!("Malformed member expression in .., got {}", form).into(),
+                    )
+                }
+            }
+        }
+        threaded.to_value()
+    }
+}
+
+// form,  method or (method args) or -field,  as a call of .method or .-field;  None if it
+// doesn't name a member
+fn member_call(form: &Arc<Value>) -> Option<Arc<Value>> {
+    let mut forms = match &**form {
+        Value::Symbol(_) => vec![Arc::clone(form)],
+        Value::PersistentList(plist) => plist.iter().collect::<Vec<Arc<Value>>>(),
+        _ => return None,
+    };
+    let member = forms.first()?;
+    forms[0] = dotted(member)?;
+    Some(list(forms))
+}
+
+// .member,  for member;  None if it's qualified or already has its dot
+fn dotted(member: &Arc<Value>) -> Option<Arc<Value>> {
+    match &**member {
+        Value::Symbol(member) if !member.has_ns() && !member.name.starts_with('.') => {
+            Some(sym(&format!(".{}", member.name)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod dot_macros_tests {
+        use crate::ifn::IFn;
+        use crate::reader;
+        use crate::repl::Repl;
+        use crate::rust_core::{DotDotMacro, DotMacro};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn read(source: &str) -> Arc<Value> {
+            reader::try_read(source).unwrap().1.to_rc_value()
+        }
+
+        #[test]
+        fn dot_forms_expand_to_member_calls() {
+            let expansion = DotMacro {}.invoke(vec![read("b"), read("(append x)")]);
+            assert_eq!("(.append b x)", expansion.to_string());
+            let expansion = DotMacro {}.invoke(vec![read("b"), read("append"), read("x")]);
+            assert_eq!("(.append b x)", expansion.to_string());
+            let expansion = DotMacro {}.invoke(vec![read("c"), read("-count")]);
+            assert_eq!("(.-count c)", expansion.to_string());
+            let expansion = DotDotMacro {}.invoke(vec![read("b"), read("(a 1)"), read("c")]);
+            assert_eq!("(.c (.a b 1))", expansion.to_string());
+        }
+
+        #[test]
+        fn dot_forms_call_host_objects() {
+            let result = Repl::default().eval_all(&[
+                "(def b (sb))",
+                "(. b append \"a\")",
+                "(. b (append \"b\"))",
+                "[(. b length) (.. b toString) (.. (doto (sb) (.append 1)) toString)]",
+            ]);
+            assert_eq!("[2 \"ab\" \"1\"]", result.to_string());
+        }
+
+        #[test]
+        fn a_malformed_dot_form_is_a_condition() {
+            let expansion = DotMacro {}.invoke(vec![read("b")]);
+            assert!(matches!(expansion, Value::Condition(_)));
+            let expansion = DotMacro {}.invoke(vec![read("b"), read("1")]);
+            assert!(matches!(expansion, Value::Condition(_)));
+            let expansion = DotDotMacro {}.invoke(vec![read("b"), read("a/b")]);
+            assert!(matches!(expansion, Value::Condition(_)));
+        }
+    }
+}
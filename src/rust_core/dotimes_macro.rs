@@ -0,0 +1,131 @@
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (dotimes [i n] body)
+///
+/// Evaluates body n times,  with i bound to 0 up to n - 1;  returns nil.  n is only
+/// evaluated once
+///
+/// (dotimes [i n] a b) expands to
+///
+/// (let [dotimes__auto__ n]
+///   (loop [i 0]
+///     (when (< i dotimes__auto__) a b (recur (+ i 1)))))
+#[derive(Debug, Clone)]
+pub struct DotimesMacro {}
+impl ToValue for DotimesMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DotimesMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (sym, count) = match args.first().map(|bindings| &**bindings) {
+            Some(Value::PersistentVector(bindings)) if bindings.len() == 2 => {
+                match bindings.nth(0).map(|sym| &**sym) {
+                    Some(Value::Symbol(_)) => (bindings.nth(0).unwrap(), bindings.nth(1).unwrap()),
+                    _ => {
+                        return Value::Condition(
+                            "dotimes binding should be a symbol and a count".into(),
+                        )
+                    }
+                }
+            }
+            _ => {
+                return Value::Condition(
+                    "dotimes requires a vector of a symbol and a count, ie [i 10]".into(),
+                )
+            }
+        };
+        let limit = Symbol::intern("dotimes__auto__").to_rc_value();
+
+        let mut when_body = vec![
+            Symbol::intern("when").to_rc_value(),
+            vec![
+                Symbol::intern("<").to_rc_value(),
+                Arc::clone(sym),
+                Arc::clone(&limit),
+            ]
+            .into_list()
+            .to_rc_value(),
+        ];
+        when_body.extend_from_slice(&args[1..]);
+        when_body.push(
+            vec![
+                Symbol::intern("recur").to_rc_value(),
+                vec![
+                    Symbol::intern("+").to_rc_value(),
+                    Arc::clone(sym),
+                    Value::I32(1).to_rc_value(),
+                ]
+                .into_list()
+                .to_rc_value(),
+            ]
+            .into_list()
+            .to_rc_value(),
+        );
+        let loop_form = vec![
+            Symbol::intern("loop").to_rc_value(),
+            PersistentVector::empty()
+                .conj(Arc::clone(sym))
+                .conj(Value::I32(0).to_rc_value())
+                .to_rc_value(),
+            when_body.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_rc_value();
+        vec![
+            Symbol::intern("let").to_rc_value(),
+            PersistentVector::empty()
+                .conj(limit)
+                .conj(Arc::clone(count))
+                .to_rc_value(),
+            loop_form,
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod dotimes_macro_tests {
+        use crate::ifn::IFn;
+        use crate::reader;
+        use crate::rust_core::{DotimesMacro, WhileMacro};
+        use crate::symbol::Symbol;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn read(source: &str) -> Arc<Value> {
+            reader::try_read(source).unwrap().1.to_rc_value()
+        }
+
+        #[test]
+        fn dotimes_counts_up_to_n() {
+            let expansion = DotimesMacro {}.invoke(vec![read("[i n]"), read("(f i)")]);
+            assert_eq!(
+                "(let [dotimes__auto__ n] (loop [i 0] (when (< i dotimes__auto__) (f i) (recur (+ i 1)))))",
+                expansion.to_string()
+            );
+        }
+
+        #[test]
+        fn dotimes_needs_a_symbol_and_a_count() {
+            let expansion = DotimesMacro {}.invoke(vec![read("[1 n]")]);
+            assert!(matches!(expansion, Value::Condition(_)));
+            let expansion = DotimesMacro {}.invoke(vec![Symbol::intern("i").to_rc_value()]);
+            assert!(matches!(expansion, Value::Condition(_)));
+        }
+
+        #[test]
+        fn while_recurs_after_its_body() {
+            let expansion = WhileMacro {}.invoke(vec![read("(f)"), read("(g)")]);
+            assert_eq!("(loop [] (when (f) (g) (recur)))", expansion.to_string());
+        }
+    }
+}
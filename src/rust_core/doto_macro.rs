@@ -0,0 +1,64 @@
+use crate::ifn::IFn;
+use crate::rust_core::thread_macros::{let_form, list, sym, thread};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (doto x form ..)
+///
+/// Evaluates x,  then calls each form with it as the first argument;  for its effects,  as
+/// it returns x
+///
+/// (doto x (f a) g) expands to
+///
+/// (let [doto__val__ x] (do (f doto__val__ a) (g doto__val__) doto__val__))
+#[derive(Debug, Clone)]
+pub struct DotoMacro {}
+impl ToValue for DotoMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for DotoMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return Value::Condition(
+                "Wrong number of arguments given to doto (Given: 0, Expected: >=1)".into(),
+            );
+        }
+        let val = sym("doto__val__");
+        let mut body = vec![sym("do")];
+        body.extend(
+            args[1..]
+                .iter()
+                .map(|form| thread(Arc::clone(&val), form, false)),
+        );
+        body.push(Arc::clone(&val));
+        let_form(vec![val, Arc::clone(&args[0])], list(body)).to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod doto_macro_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn doto_returns_x() {
+            let result = Repl::default().eval_all(&[
+                "(def seen (atom []))",
+                "[(doto 1 (->> (swap! seen conj)) (->> (* 10) (swap! seen conj))) @seen]",
+            ]);
+            assert_eq!("[1 [1 10]]", result.to_string());
+        }
+
+        #[test]
+        fn doto_sets_up_host_objects() {
+            let result = Repl::default().eval_all(&[
+                "(def b (doto (sb) (.append \"a\") (.append \"b\")))",
+                "(doseq [x [1 2]] (.append b x))",
+                "[(str b) (.length b)]",
+            ]);
+            assert_eq!("[\"ab12\" 4]", result.to_string());
+        }
+    }
+}
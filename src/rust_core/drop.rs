@@ -0,0 +1,56 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::transducer::{self, Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::{Arc, Mutex};
+
+/// (drop n) (drop n coll)
+///
+/// A lazy seq of all but the first n elements of coll.  Without coll,  a transducer passing
+/// on all but the first n inputs
+#[derive(Debug, Clone)]
+pub struct DropFn {}
+impl ToValue for DropFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DropFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let n = match &*args[0] {
+            Value::I32(n) => *n,
+            _ => return error_message::type_mismatch(TypeTag::Integer, &args[0]),
+        };
+        let xform = Transducer::new(move || {
+            Box::new(DropStage {
+                left: Mutex::new(n),
+            })
+        });
+        match args.get(1) {
+            Some(coll) => transducer::sequence(&xform, Arc::clone(coll)),
+            None => xform.to_value(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DropStage {
+    left: Mutex<i32>,
+}
+impl Stage for DropStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        let dropping = {
+            let mut left = self.left.lock().unwrap();
+            *left -= 1;
+            *left >= 0
+        };
+        match dropping {
+            true => result.to_value(),
+            false => rf.invoke(vec![result, input]),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::transducer::{self, Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// (drop-while pred) (drop-while pred coll)
+///
+/// A lazy seq of the elements of coll from the first one pred is falsy for.  Without coll,
+/// a transducer passing on inputs from that one on
+#[derive(Debug, Clone)]
+pub struct DropWhileFn {}
+impl ToValue for DropWhileFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DropWhileFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let pred = match ifn::as_ifn(&args[0]) {
+            Some(pred) => pred,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let xform = Transducer::new(move || {
+            Box::new(DropWhileStage {
+                pred: Arc::clone(&pred),
+                dropping: AtomicBool::new(true),
+            })
+        });
+        match args.get(1) {
+            Some(coll) => transducer::sequence(&xform, Arc::clone(coll)),
+            None => xform.to_value(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DropWhileStage {
+    pred: Arc<dyn IFn>,
+    dropping: AtomicBool,
+}
+impl Stage for DropWhileStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        if self.dropping.load(Ordering::SeqCst) {
+            match self.pred.invoke(vec![Arc::clone(&input)]) {
+                Value::Condition(condition) => return Value::Condition(condition),
+                passed if passed.is_truthy() => return result.to_value(),
+                _ => self.dropping.store(false, Ordering::SeqCst),
+            }
+        }
+        rf.invoke(vec![result, input])
+    }
+}
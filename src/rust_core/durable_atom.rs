@@ -0,0 +1,137 @@
+use crate::atom::Atom;
+use crate::clojure_java_io;
+use crate::edn;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::rust_core::validator_option;
+use crate::value::{ToValue, Value};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// (durable-atom path init & options)
+///
+/// An atom whose value is kept in the file at path,  as EDN;  so it's still there the next
+/// time the script's run.  If the file's there,  the atom starts with what it holds,  and
+/// init isn't used;  otherwise it starts with init,  written to the file.  Each change is
+/// written before the swap! or reset! that made it returns;  to a file beside it,  then
+/// moved over it,  so the file's never left half written.  A value that can't be written
+/// as EDN is refused,  as a validator would refuse it.  Takes :validator,  as atom does
+#[derive(Debug, Clone)]
+pub struct DurableAtomFn {}
+impl ToValue for DurableAtomFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for DurableAtomFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match durable_atom(&args[0], &args[1], &args[2..]) {
+            Ok(atom) => atom,
+            Err(condition) => condition,
+        }
+    }
+}
+
+fn durable_atom(path: &Value, init: &Arc<Value>, options: &[Arc<Value>]) -> Result<Value, Value> {
+    let path = clojure_java_io::as_path(path)?;
+    let validator = Arc::new(Storable {
+        validator: validator_option("durable-atom", options)?,
+    });
+    let stored = match fs::read_to_string(&path) {
+        Ok(text) => Some(edn::read_str(&text)?.to_rc_value()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(clojure_java_io::io_error("read", path.display(), e)),
+    };
+    let atom = Atom::new(
+        stored.clone().unwrap_or_else(|| Arc::clone(init)),
+        Some(validator),
+    )?;
+    let write = Write {
+        path,
+        lock: Arc::new(Mutex::new(())),
+    };
+    if stored.is_none() {
+        write.store(&atom.deref())?;
+    }
+    atom.add_watch(
+        Keyword::intern("durable-atom").to_rc_value(),
+        Arc::new(write),
+    );
+    Ok(Value::Atom(Arc::new(atom)))
+}
+
+// The atom's own validator,  if it has one;  and whether the value can be written at all
+#[derive(Debug, Clone)]
+struct Storable {
+    validator: Option<Arc<dyn IFn>>,
+}
+impl IFn for Storable {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if let Some(validator) = &self.validator {
+            match validator.invoke(args.clone()) {
+                valid if valid.is_truthy() => {}
+                refused => return refused,
+            }
+        }
+        match edn::print_str(&args[0]) {
+            Ok(_) => Value::Boolean(true),
+            Err(condition) => condition,
+        }
+    }
+}
+
+// The watch that writes each change
+#[derive(Debug, Clone)]
+struct Write {
+    path: PathBuf,
+    // Held while writing;  so two changes made at once don't write over each other
+    lock: Arc<Mutex<()>>,
+}
+impl Write {
+    fn store(&self, val: &Value) -> Result<(), Value> {
+        let text = edn::print_str(val)?;
+        let mut beside = self.path.clone().into_os_string();
+        beside.push(".tmp");
+        fs::write(&beside, text)
+            .and_then(|()| fs::rename(&beside, &self.path))
+            .map_err(|e| clojure_java_io::io_error("write", self.path.display(), e))
+    }
+}
+impl IFn for Write {
+    // (watch key atom old new);  the atom's value as it is once the lock's held is what's
+    // written,  not new,  so whichever change is written last is the latest
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let _lock = self.lock.lock().unwrap();
+        let latest = match &*args[1] {
+            Value::Atom(atom) => atom.deref(),
+            _ => Arc::clone(&args[3]),
+        };
+        match self.store(&latest) {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn the_atom_starts_with_what_was_written_before() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.io :as io])",
+            "(io/with-temp-dir [dir] (let [path (str dir \"/state.edn\") counts (durable-atom path {:runs 0})] (do (swap! counts (fn [m] (assoc m :runs (inc (:runs m))))) (swap! counts assoc :last \"today\") [(= @(durable-atom path {:runs 0}) @counts) (slurp path) (ex-message (try (reset! counts (atom 1)) (catch Exception e e))) @counts])))",
+        ]);
+        assert_eq!(
+            "[true \"{:runs 1, :last \\\"today\\\"}\" \"Can't print clojure.lang.Atom as EDN\" {:runs 1, :last \"today\"}]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::comp::CompFn;
+use crate::transducer;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (eduction xform* coll)
+///
+/// A lazy seq of what the xforms,  composed,  make of coll.  Unlike Clojure's,  it's a seq
+/// like any other,  and so keeps what it's made once walked
+#[derive(Debug, Clone)]
+pub struct EductionFn {}
+impl ToValue for EductionFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for EductionFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (coll, xforms) = match args.split_last() {
+            Some(split) => split,
+            None => return error_message::wrong_arg_count(1, 0),
+        };
+        let xform = CompFn {}.invoke(xforms.to_vec());
+        match xform {
+            Value::IFn(xform) => transducer::sequence(&**xform, Arc::clone(coll)),
+            _ => xform,
+        }
+    }
+}
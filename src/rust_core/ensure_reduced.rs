@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::transducer;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ensure-reduced x)
+///
+/// (reduced x),  unless x already is
+#[derive(Debug, Clone)]
+pub struct EnsureReducedFn {}
+impl ToValue for EnsureReducedFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for EnsureReducedFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        transducer::ensure_reduced(args[0].to_value())
+    }
+}
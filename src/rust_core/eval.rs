@@ -1,7 +1,7 @@
 use crate::environment::Environment;
 use crate::ifn::IFn;
 use crate::value::{Evaluable, ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error_message;
 
@@ -9,10 +9,10 @@ use crate::error_message;
 ///
 #[derive(Debug, Clone)]
 pub struct EvalFn {
-    enclosing_environment: Rc<Environment>,
+    enclosing_environment: Arc<Environment>,
 }
 impl EvalFn {
-    pub fn new(enclosing_environment: Rc<Environment>) -> EvalFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> EvalFn {
         EvalFn {
             enclosing_environment,
         }
@@ -21,16 +21,16 @@ impl EvalFn {
 
 impl ToValue for EvalFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for EvalFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         // @TODO generalize arity exceptions, and other exceptions
         if args.len() != 1 {
             return error_message::wrong_arg_count(1, args.len());
         }
         let arg = args.get(0).unwrap();
-        arg.eval(Rc::clone(&self.enclosing_environment))
+        arg.eval(Arc::clone(&self.enclosing_environment))
     }
 }
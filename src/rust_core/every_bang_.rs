@@ -0,0 +1,60 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::timer;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (every! ms f)
+///
+/// Calls f,  with no arguments,  every ms milliseconds,  the first time ms from now;  on
+/// the timer thread (see timer.rs).  A task,  that cancel! stops
+#[derive(Debug, Clone)]
+pub struct EveryBangFn {}
+impl ToValue for EveryBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for EveryBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let period = match timer::millis(&args[0]) {
+            Ok(period) if !period.is_zero() => period,
+            Ok(_) => return Value::Condition("every! needs a period longer than 0 ms".into()),
+            Err(condition) => return condition,
+        };
+        if !ifn::is_callable(&args[1]) {
+            return error_message::type_mismatch(TypeTag::IFn, &args[1]);
+        }
+        Value::HostObject(Arc::new(timer::schedule(
+            Arc::clone(&args[1]),
+            period,
+            Some(period),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn the_fn_is_called_again_and_again_until_cancelled() {
+        let result = Repl::default().eval_all(&[
+            "(def ticks (atom 0))",
+            "(def ticking (every! 10 (fn [] (swap! ticks inc))))",
+            "(sleep 200)",
+            "(def cancelled (cancel! ticking))",
+            "(def after @ticks)",
+            "(sleep 50)",
+            "[(> after 3) (= after @ticks) cancelled (str ticking)]",
+        ]);
+        assert_eq!(
+            "[true true true \"#task[{:status :cancelled}]\"]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use crate::condition;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ex-cause ex)
+///
+/// The condition that caused ex,  if ex-info was given one;  nil otherwise
+#[derive(Debug, Clone)]
+pub struct ExCauseFn {}
+impl ToValue for ExCauseFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExCauseFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        condition::ex_cause(&args[0]).map_or(Value::Nil, |cause| cause.to_value())
+    }
+}
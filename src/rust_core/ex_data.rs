@@ -0,0 +1,24 @@
+use crate::condition;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ex-data ex)
+///
+/// The map ex was made with by ex-info;  nil for anything else
+#[derive(Debug, Clone)]
+pub struct ExDataFn {}
+impl ToValue for ExDataFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExDataFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        condition::ex_data(&args[0]).map_or(Value::Nil, |data| data.to_value())
+    }
+}
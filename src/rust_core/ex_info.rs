@@ -0,0 +1,33 @@
+use crate::condition;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ex-info msg map) (ex-info msg map cause)
+///
+/// An exception with message msg,  carrying map as its ex-data,  and maybe the exception
+/// that caused it;  for throw to throw
+#[derive(Debug, Clone)]
+pub struct ExInfoFn {}
+impl ToValue for ExInfoFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExInfoFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
+        }
+        let message = match &*args[0] {
+            Value::String(message) => message,
+            _ => return error_message::type_mismatch(TypeTag::String, &args[0]),
+        };
+        if !matches!(&*args[1], Value::PersistentListMap(_)) {
+            return error_message::type_mismatch(TypeTag::IPersistentMap, &args[1]);
+        }
+        condition::ex_info(message, Arc::clone(&args[1]), args.get(2).cloned())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::shared_str::SharedStr;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ex-message ex)
+///
+/// The message of ex,  as a string,  if it's an exception;  nil otherwise
+#[derive(Debug, Clone)]
+pub struct ExMessageFn {}
+impl ToValue for ExMessageFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExMessageFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Exception(message) => Value::String(SharedStr::clone(message)),
+            _ => Value::Nil,
+        }
+    }
+}
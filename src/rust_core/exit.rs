@@ -0,0 +1,29 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::shutdown;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (exit) or (exit n)
+///
+/// Runs the shutdown hooks,  then ends the process with exit code n (0 by default)
+#[derive(Debug, Clone)]
+pub struct ExitFn {}
+impl ToValue for ExitFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExitFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match args.as_slice() {
+            [] => shutdown::exit(0),
+            [code] => match &**code {
+                Value::I32(code) => shutdown::exit(*code),
+                _ => error_message::type_mismatch(TypeTag::I32, code),
+            },
+            _ => error_message::wrong_varg_count(&[0, 1], args.len()),
+        }
+    }
+}
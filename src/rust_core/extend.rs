@@ -0,0 +1,118 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list_map::ToPersistentListMapIter;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::util::IsEven;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (extend type protocol method-map & more)
+///
+/// Implements each protocol's methods for type with the fns in the method map after it;
+/// the methods' names,  as keywords,  to their fns,  ie {:area (fn [c] (* 3 (:r c)))}.  As
+/// extend-type,  but a fn;  so the types,  protocols and fns can all be worked out as the
+/// program runs.  type is a symbol naming the type,  as (class x) gives it,  or nil.  Nothing
+/// is extended unless all of it can be
+#[derive(Debug, Clone)]
+pub struct ExtendFn {}
+impl ToValue for ExtendFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExtendFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 3 || args.len().is_even() {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to function (Given: {}, Expected: 3 | 5 | 7 | ..)",
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let type_name = match &*args[0] {
+            Value::Symbol(sym) => sym.to_string(),
+            Value::Nil => TypeTag::Nil.to_string(),
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        let mut extensions = vec![];
+        for protocol_and_methods in args[1..].chunks(2) {
+            let (protocol, method_map) = (&protocol_and_methods[0], &protocol_and_methods[1]);
+            let protocol = match &**protocol {
+                Value::Protocol(protocol) => protocol,
+                _ => return error_message::type_mismatch(TypeTag::Protocol, protocol),
+            };
+            let entries: Vec<(Arc<Value>, Arc<Value>)> = match &**method_map {
+                Value::PersistentListMap(map) => {
+                    map.iter().map(|entry| (entry.key, entry.val)).collect()
+                }
+                Value::PersistentTreeMap(map) => {
+                    map.iter().map(|entry| (entry.key, entry.val)).collect()
+                }
+                _ => return error_message::type_mismatch(TypeTag::IPersistentMap, method_map),
+            };
+            for (name, f) in entries {
+                let method = match &*name {
+                    Value::Keyword(kw) => Symbol::intern(&kw.sym.name),
+                    _ => return error_message::type_mismatch(TypeTag::Keyword, &name),
+                };
+                if protocol.arglists(&method).is_none() {
+                    return Value::Condition(
+                        format!("No method {} in protocol {}", method, protocol.name).into(),
+                    );
+                }
+                match &*f {
+                    Value::IFn(f) => {
+                        extensions.push((Arc::clone(protocol), method, Arc::clone(&**f)))
+                    }
+                    _ => return error_message::type_mismatch(TypeTag::IFn, &f),
+                }
+            }
+        }
+        for (protocol, method, f) in extensions {
+            if let Err(condition) = protocol.extend(&type_name, &method, f) {
+                return condition;
+            }
+        }
+        Value::Nil
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn types_are_extended_with_maps_of_fns() {
+        let result = Repl::default().eval_all(&[
+            "(defprotocol Shape (area [this]) (describe [this]))",
+            "(defprotocol Named (label [this]))",
+            "(defrecord Circle [r])",
+            "(deftype Square [side])",
+            "(def describe-impl {:describe (fn [this] [(class this) :shape])})",
+            "(extend Circle Shape (assoc describe-impl :area (fn [c] (* 3 (:r c)))) Named {:label (fn [_] \"circle\")})",
+            "(mapv (fn [t] (extend t Shape describe-impl)) [Square (class 1)])",
+            "[(area (->Circle 2)) (describe (->Square 1)) (describe 1) (label (->Circle 1)) (satisfies? Named (->Square 1))]",
+        ]);
+        assert_eq!(
+            "[6 [user.Square :shape] [rust.std.i32 :shape] \"circle\" false]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn nothing_is_extended_unless_everything_can_be() {
+        let result = Repl::default().eval_all(&[
+            "(defprotocol Shape (area [this]) (perimeter [this]))",
+            "(deftype Circle [r])",
+            "(def failed (ex-message (try (extend Circle Shape {:area (fn [_] 1) :volume (fn [_] 2)}) (catch Exception e e))))",
+            "[failed (satisfies? Shape (->Circle 1))]",
+        ]);
+        assert_eq!(
+            "[\"No method volume in protocol user/Shape\" false]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use crate::clojure_protocol::OBJECT;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::{ToPersistentList, ToPersistentListIter};
+use crate::persistent_vector::PersistentVector;
+use crate::rust_core::defprotocol_macro::quote;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (extend* type protocol method f)
+///
+/// Implements protocol's method for type with f,  where type is a symbol naming the type
+#[derive(Debug, Clone)]
+pub struct ExtendMethodFn {}
+impl ToValue for ExtendMethodFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ExtendMethodFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 4 {
+            return error_message::wrong_arg_count(4, args.len());
+        }
+        let type_name = match &*args[0] {
+            Value::Symbol(sym) => sym.to_string(),
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        let protocol = match &*args[1] {
+            Value::Protocol(protocol) => protocol,
+            _ => return error_message::type_mismatch(TypeTag::Protocol, &args[1]),
+        };
+        let method = match &*args[2] {
+            Value::Symbol(sym) => sym,
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[2]),
+        };
+        let f = match &*args[3] {
+            Value::IFn(f) => f,
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[3]),
+        };
+        match protocol.extend(&type_name, method, Arc::clone(f)) {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// One arity of a method's implementation;  its name,  arglist,  and body
+pub(crate) type MethodImpl = (Arc<Value>, Arc<Value>, Vec<Arc<Value>>);
+
+/// A protocol (or type),  and the method implementations given for it
+pub(crate) type ImplGroup = (Arc<Value>, Vec<MethodImpl>);
+
+/// Splits forms like Proto1 (m [this] ..) Proto2 (n ([this] ..) ([this x] ..)) into each
+/// protocol,  and the implementations given for it;  one per arity
+pub(crate) fn protocol_impls(forms: &[Arc<Value>]) -> Result<Vec<ImplGroup>, Value> {
+    let mut groups: Vec<ImplGroup> = vec![];
+    for form in forms.iter() {
+        match &**form {
+            Value::PersistentList(spec) => {
+                let invalid =
+                    || Value::Condition(format!("Invalid method implementation: {}", form).into());
+                let group = groups.last_mut().ok_or_else(invalid)?;
+                let parts = spec.iter().collect::<Vec<Arc<Value>>>();
+                let name = match parts.first() {
+                    Some(name) => Arc::clone(name),
+                    None => return Err(invalid()),
+                };
+                match parts.get(1).map(|part| &**part) {
+                    // (m [this] body)
+                    Some(Value::PersistentVector(_)) => {
+                        group
+                            .1
+                            .push((name, Arc::clone(&parts[1]), parts[2..].to_vec()))
+                    }
+                    // (m ([this] body) ([this x] body))
+                    Some(Value::PersistentList(_)) => {
+                        for arity in parts[1..].iter() {
+                            let arity = match &**arity {
+                                Value::PersistentList(arity) => {
+                                    arity.iter().collect::<Vec<Arc<Value>>>()
+                                }
+                                _ => return Err(invalid()),
+                            };
+                            match arity.first() {
+                                Some(arglist) => group.1.push((
+                                    Arc::clone(&name),
+                                    Arc::clone(arglist),
+                                    arity[1..].to_vec(),
+                                )),
+                                None => return Err(invalid()),
+                            }
+                        }
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            _ => groups.push((Arc::clone(form), vec![])),
+        }
+    }
+    Ok(groups)
+}
+
+/// What extend-type expects its type to be given as;  a built-in type's name (or Object,
+/// or nil) is taken as is,  anything else is evaluated,  as the name of a deftype is
+fn type_expr(type_: &Arc<Value>) -> Arc<Value> {
+    match &**type_ {
+        Value::Nil => quote(TypeTag::Nil.to_symbol().to_rc_value()),
+        Value::Symbol(sym)
+            if TypeTag::from_value(type_).is_some() || (!sym.has_ns() && sym.name == OBJECT) =>
+        {
+            quote(Arc::clone(type_))
+        }
+        _ => Arc::clone(type_),
+    }
+}
+
+/// (extend-type type protocol method-impls protocol method-impls ..)
+///
+/// (extend-type Circle Shape (area [c] 3)) expands to
+///
+/// (do (extend* Circle Shape (quote area) (fn [c] 3)) nil)
+#[derive(Debug, Clone)]
+pub struct ExtendTypeMacro {}
+impl ToValue for ExtendTypeMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for ExtendTypeMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return Value::Condition(
+                "Wrong number of arguments given to extend-type (Given: 0, Expected: >=1)".into(),
+            );
+        }
+        let groups = match protocol_impls(&args[1..]) {
+            Ok(groups) => groups,
+            Err(condition) => return condition,
+        };
+        let type_ = type_expr(&args[0]);
+        let mut expansion = vec![Symbol::intern("do").to_rc_value()];
+        for (protocol, impls) in groups {
+            for (name, arglist, body) in impls {
+                let mut f = vec![Symbol::intern("fn").to_rc_value(), arglist];
+                f.extend(body);
+                expansion.push(
+                    vec![
+                        Symbol::intern("extend*").to_rc_value(),
+                        Arc::clone(&type_),
+                        Arc::clone(&protocol),
+                        quote(name),
+                        f.into_list().to_rc_value(),
+                    ]
+                    .into_list()
+                    .to_rc_value(),
+                );
+            }
+        }
+        expansion.push(Arc::new(Value::Nil));
+        expansion.into_list().to_value()
+    }
+}
+
+/// (extend-protocol protocol type method-impls type method-impls ..)
+///
+/// (extend-protocol Shape Circle (area [c] 3) Square (area [s] 4)) expands to
+///
+/// (do (extend-type Circle Shape (area [c] 3)) (extend-type Square Shape (area [s] 4)))
+#[derive(Debug, Clone)]
+pub struct ExtendProtocolMacro {}
+impl ToValue for ExtendProtocolMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for ExtendProtocolMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return Value::Condition(
+                "Wrong number of arguments given to extend-protocol (Given: 0, Expected: >=1)"
+                    .into(),
+            );
+        }
+        // The same split as extend-type's,  only with types where the protocols were
+        let groups = match protocol_impls(&args[1..]) {
+            Ok(groups) => groups,
+            Err(condition) => return condition,
+        };
+        let mut expansion = vec![Symbol::intern("do").to_rc_value()];
+        for (type_, impls) in groups {
+            let mut extend_type = vec![
+                Symbol::intern("extend-type").to_rc_value(),
+                type_,
+                Arc::clone(&args[0]),
+            ];
+            extend_type.extend(impls.into_iter().map(method_impl_form));
+            expansion.push(extend_type.into_list().to_rc_value());
+        }
+        expansion.push(Arc::new(Value::Nil));
+        expansion.into_list().to_value()
+    }
+}
+
+/// (m [this] body) , as extend-type takes it
+pub(crate) fn method_impl_form((name, arglist, body): MethodImpl) -> Arc<Value> {
+    let mut form = vec![name, arglist];
+    form.extend(body);
+    form.into_list().to_rc_value()
+}
+
+/// The symbols an arglist binds
+pub(crate) fn arglist_syms(arglist: &Value) -> Vec<Symbol> {
+    match arglist {
+        Value::PersistentVector(arglist) => arglist
+            .iter()
+            .filter_map(|arg| match &**arg {
+                Value::Symbol(sym) => Some(Symbol::clone(sym)),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// [a b] , from a list of symbols
+pub(crate) fn symbol_vector(syms: &[Symbol]) -> Arc<Value> {
+    syms.iter()
+        .map(|sym| sym.to_rc_value())
+        .collect::<PersistentVector>()
+        .to_rc_value()
+}
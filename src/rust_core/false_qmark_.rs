@@ -0,0 +1,23 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (false? x)
+///
+/// Whether x is the value false
+#[derive(Debug, Clone)]
+pub struct FalseFn {}
+impl ToValue for FalseFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FalseFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Boolean(*args[0] == Value::Boolean(false))
+    }
+}
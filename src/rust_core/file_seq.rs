@@ -0,0 +1,48 @@
+use crate::clojure_java_io::{self, walk, File};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (file-seq dir)
+///
+/// A lazy seq of dir (a file,  or a path) and the files and directories under it,  however
+/// deep;  as files.  A directory comes before what's in it
+#[derive(Debug, Clone)]
+pub struct FileSeqFn {}
+impl ToValue for FileSeqFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FileSeqFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let dir = match clojure_java_io::as_path(&args[0]) {
+            Ok(dir) => dir,
+            Err(condition) => return condition,
+        };
+        let keep: walk::Keep = Arc::new(|path| {
+            let path = path.to_path_buf();
+            Ok(Some(Value::host_object(File { path })))
+        });
+        walk::tree(vec![dir], Arc::new(|_| true), keep).into_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn a_dir_comes_before_what_is_in_it() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.io :as io])",
+            "(io/with-temp-dir [d] (.mkdirs (io/file d \"b\")) (spit (io/file d \"b\" \"c.txt\") \"\") (spit (io/file d \"a.txt\") \"\")
+               (mapv #(subs (.getPath %) (count (.getPath d))) (file-seq d)))",
+        ]);
+        assert_eq!("[\"\" \"/a.txt\" \"/b\" \"/b/c.txt\"]", result.to_string());
+    }
+}
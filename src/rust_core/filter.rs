@@ -0,0 +1,116 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::interrupt;
+use crate::lazy_seq::{self, Chunks, LazySeq, Step};
+use crate::transducer::{Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (filter pred) (filter pred coll)
+///
+/// A lazy seq of the elements of coll for which pred is truthy.  Without coll,  a
+/// transducer passing on only those inputs.  Over a vector or range,  pred is applied a
+/// chunk at a time
+#[derive(Debug, Clone)]
+pub struct FilterFn {}
+impl ToValue for FilterFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FilterFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let pred = match ifn::as_ifn(&args[0]) {
+            Some(pred) => pred,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        match args.len() {
+            1 => Transducer::new(move || {
+                Box::new(FilterStage {
+                    pred: Arc::clone(&pred),
+                    keep: true,
+                })
+            })
+            .to_value(),
+            _ => match Chunks::of(&args[1]) {
+                Some(chunks) => chunked_filter(pred, chunks).into_value(),
+                None => lazy_filter(pred, Arc::clone(&args[1])).into_value(),
+            },
+        }
+    }
+}
+
+fn lazy_filter(pred: Arc<dyn IFn>, coll: Arc<Value>) -> LazySeq {
+    LazySeq::new(move || {
+        let mut coll = coll;
+        // Realizing one element may mean skipping any number of others
+        loop {
+            if interrupt::is_interrupted() {
+                return Err(Value::Condition(interrupt::INTERRUPTED_MSG.into()));
+            }
+            let (first, rest) = match lazy_seq::step(&coll)? {
+                Step::Next(first, rest) => (first, rest),
+                Step::Done => return Ok(Step::Done),
+            };
+            match pred.invoke(vec![Arc::clone(&first)]) {
+                Value::Condition(condition) => return Err(Value::Condition(condition)),
+                passed if passed.is_truthy() => {
+                    return Ok(Step::Next(
+                        first,
+                        Arc::new(lazy_filter(pred, rest).into_value()),
+                    ))
+                }
+                _ => coll = rest,
+            }
+        }
+    })
+}
+
+fn chunked_filter(pred: Arc<dyn IFn>, chunks: Chunks) -> LazySeq {
+    LazySeq::new(move || {
+        let mut chunks = chunks;
+        // As many chunks as it takes to find one that isn't filtered out entirely
+        loop {
+            let (chunk, rest) = match chunks.next_chunk() {
+                Some(next) => next,
+                None => return Ok(Step::Done),
+            };
+            let mut passed = Vec::with_capacity(chunk.len());
+            for val in chunk {
+                if interrupt::is_interrupted() {
+                    return Err(Value::Condition(interrupt::INTERRUPTED_MSG.into()));
+                }
+                match pred.invoke(vec![Arc::clone(&val)]) {
+                    Value::Condition(condition) => return Err(Value::Condition(condition)),
+                    truthy if truthy.is_truthy() => passed.push(val),
+                    _ => {}
+                }
+            }
+            if !passed.is_empty() {
+                let rest = Arc::new(chunked_filter(pred, rest).into_value());
+                return lazy_seq::chunk_step(passed, rest);
+            }
+            chunks = rest;
+        }
+    })
+}
+
+// What filter and remove pass on;  the inputs pred is truthy for if keep,  else the rest
+#[derive(Debug)]
+pub(crate) struct FilterStage {
+    pub(crate) pred: Arc<dyn IFn>,
+    pub(crate) keep: bool,
+}
+impl Stage for FilterStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        match self.pred.invoke(vec![Arc::clone(&input)]) {
+            Value::Condition(condition) => Value::Condition(condition),
+            passed if passed.is_truthy() == self.keep => rf.invoke(vec![result, input]),
+            _ => result.to_value(),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (find-ns sym)
+///
+/// Returns the namespace named by sym, or nil if there isn't one
+#[derive(Debug, Clone)]
+pub struct FindNsFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl FindNsFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> FindNsFn {
+        FindNsFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for FindNsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FindNsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Symbol(sym) => match self.enclosing_environment.find_namespace(sym) {
+                Some(namespace) => Value::Namespace(namespace),
+                None => Value::Nil,
+            },
+            _ => error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        }
+    }
+}
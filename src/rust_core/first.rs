@@ -0,0 +1,28 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{self, Step};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (first coll)
+///
+/// The first element of coll,  or nil if it is empty
+#[derive(Debug, Clone)]
+pub struct FirstFn {}
+impl ToValue for FirstFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FirstFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match lazy_seq::step(&args[0]) {
+            Ok(Step::Next(first, _)) => first.to_value(),
+            Ok(Step::Done) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
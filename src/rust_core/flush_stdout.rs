@@ -1,28 +1,27 @@
 use crate::ifn::IFn;
-use crate::value::{Value, ToValue, Evaluable};
-use std::rc::Rc;
-
-use std::io;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
 
+use crate::clojure_java_io;
 use crate::error_message;
-use nom::lib::std::convert::TryFrom;
-use std::io::{Read, Write};
 
-/// Read a line from stdin TODO: should be aware of *in*
-/// (defn read-line [])
+/// Flushes *out*
+/// (defn flush-stdout [])
 #[derive(Debug, Clone)]
 pub struct FlushStdoutFn {}
 impl ToValue for FlushStdoutFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for FlushStdoutFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 0 {
-            return error_message::wrong_arg_count(0, args.len())
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        match clojure_java_io::flush_out() {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
         }
-        io::stdout().flush();
-        Value::Nil
     }
-}
\ No newline at end of file
+}
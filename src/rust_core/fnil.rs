@@ -0,0 +1,62 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (fnil f x) (fnil f x y) (fnil f x y z)
+///
+/// A fn calling f on its args,  but with x in place of its first if that's nil,  y of its
+/// second,  and z of its third.  So ((fnil conj []) nil x) is [x]
+#[derive(Debug, Clone)]
+pub struct FnilFn {}
+impl ToValue for FnilFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FnilFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 || args.len() > 4 {
+            return error_message::wrong_varg_count(&[2, 3, 4], args.len());
+        }
+        if !ifn::is_callable(&args[0]) {
+            return error_message::type_mismatch(TypeTag::IFn, &args[0]);
+        }
+        Value::ifn(Fnil {
+            f: Arc::clone(&args[0]),
+            defaults: args[1..].to_vec(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Fnil {
+    f: Arc<Value>,
+    defaults: Vec<Arc<Value>>,
+}
+impl IFn for Fnil {
+    fn invoke(&self, mut args: Vec<Arc<Value>>) -> Value {
+        for (arg, default) in args.iter_mut().zip(self.defaults.iter()) {
+            if let Value::Nil = **arg {
+                *arg = Arc::clone(default);
+            }
+        }
+        ifn::call(&self.f, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod fnil_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn higher_order_fns_wrap_what_they_are_given() {
+            let result = Repl::default().eval_all(&[
+                "[((partial + 1 2) 3) ((partial :a) {:a 1}) (filter (complement :a) [{:a 1} {}]) ((fnil + 0 10) nil nil 5) ((fnil conj []) nil 1)]",
+            ]);
+            assert_eq!("[6 1 ({}) 15 [1]]", result.to_string());
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::ifn::IFn;
+use crate::persistent_vector::PersistentVector;
+use crate::rust_core::thread_macros::{list, sym};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (for [x xs] body)
+///
+/// A lazy seq of body for each element of xs,  with x bound to it.  More bindings nest,
+/// the last varying fastest,  and each binding can be followed by :let [bindings],  :when
+/// test (skipping the element unless test is truthy) and :while test (ending that binding's
+/// walk once test isn't);  as with doseq
+///
+/// (for [x xs :when (p x)] a) expands to
+///
+/// (mapcat (fn [x] (if (p x) (list a) (list))) xs)
+///
+/// and with a :while,  the walk is cut short where its step returns a reduced
+///
+/// (for [x xs :while (p x)] a) expands to
+///
+/// (mapcat (fn [for__step__] for__step__)
+///   (take-while (complement reduced?)
+///     (map (fn [x] (if (p x) (list a) (reduced nil))) xs)))
+#[derive(Debug, Clone)]
+pub struct ForMacro {}
+impl ToValue for ForMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for ForMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let bindings = match args.first().map(|bindings| &**bindings) {
+            Some(Value::PersistentVector(bindings)) if bindings.len() % 2 == 0 => bindings,
+            _ => {
+                return Value::Condition(
+                    "for requires a vector of pairs of a symbol and a coll, ie [x xs]".into(),
+                )
+            }
+        };
+        if args.len() != 2 {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to for (Given: {}, Expected: 2)",
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let bindings = bindings.iter().cloned().collect::<Vec<Arc<Value>>>();
+        match expand(&bindings, &args[1]) {
+            Ok(expansion) => expansion.to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
+
+// The walk for the first binding of bindings,  and its modifiers;  around the walks for
+// the rest,  and at the last,  body
+fn expand(bindings: &[Arc<Value>], body: &Arc<Value>) -> Result<Arc<Value>, Value> {
+    let (binding, coll) = match bindings {
+        [binding, coll, ..] if matches!(**binding, Value::Symbol(_)) => (binding, coll),
+        _ => {
+            return Err(Value::Condition(
+                "for binding should be a symbol and a coll".into(),
+            ))
+        }
+    };
+    let mut rest = &bindings[2..];
+    let mut modifiers = vec![];
+    while let [modifier, form, ..] = rest {
+        if !matches!(**modifier, Value::Keyword(_)) {
+            break;
+        }
+        modifiers.push((modifier, form));
+        rest = &rest[2..];
+    }
+    let mut step = if rest.is_empty() {
+        list(vec![sym("list"), Arc::clone(body)])
+    } else {
+        expand(rest, body)?
+    };
+    let mut cut_short = false;
+    // Applied innermost first,  so the first modifier is the outermost test
+    for (modifier, form) in modifiers.into_iter().rev() {
+        step = match &*modifier.to_string() {
+            ":let" => list(vec![sym("let"), Arc::clone(form), step]),
+            ":when" => list(vec![
+                sym("if"),
+                Arc::clone(form),
+                step,
+                list(vec![sym("list")]),
+            ]),
+            ":while" => {
+                cut_short = true;
+                list(vec![
+                    sym("if"),
+                    Arc::clone(form),
+                    step,
+                    list(vec![sym("reduced"), Value::Nil.to_rc_value()]),
+                ])
+            }
+            other => {
+                return Err(Value::Condition(
+                    format!("Invalid for modifier: {}", other).into(),
+                ))
+            }
+        };
+    }
+    let step_fn = list(vec![
+        sym("fn"),
+        PersistentVector::empty()
+            .conj(Arc::clone(binding))
+            .to_rc_value(),
+        step,
+    ]);
+    if !cut_short {
+        return Ok(list(vec![sym("mapcat"), step_fn, Arc::clone(coll)]));
+    }
+    let steps = list(vec![
+        sym("take-while"),
+        list(vec![sym("complement"), sym("reduced?")]),
+        list(vec![sym("map"), step_fn, Arc::clone(coll)]),
+    ]);
+    let each = list(vec![
+        sym("fn"),
+        PersistentVector::empty()
+            .conj(sym("for__step__"))
+            .to_rc_value(),
+        sym("for__step__"),
+    ]);
+    Ok(list(vec![sym("mapcat"), each, steps]))
+}
+
+#[cfg(test)]
+mod tests {
+    mod for_macro_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn for_nests_its_bindings() {
+            let result = Repl::default().eval_all(&[
+                "(for [x [1 2 3] :while (< x 3) y (range 3) :let [z (* x y)] :when (= 1 (rem z 2))] [x y z])",
+            ]);
+            assert_eq!("([1 1 1])", result.to_string());
+            let result = Repl::default().eval_all(&["(for [x [1 2] y [:a :b]] [x y])"]);
+            assert_eq!("([1 :a] [1 :b] [2 :a] [2 :b])", result.to_string());
+        }
+
+        #[test]
+        fn for_is_lazy() {
+            let result = Repl::default()
+                .eval_all(&["(take 3 (for [x (range) :when (= 0 (rem x 2))] (* x x)))"]);
+            assert_eq!("(0 4 16)", result.to_string());
+            let result = Repl::default().eval_all(&["(for [x [] y (range)] y)"]);
+            assert_eq!("()", result.to_string());
+        }
+
+        #[test]
+        fn for_needs_symbols_and_colls() {
+            let result = Repl::default().eval_all(&["(for [x [1] :unless true] x)"]);
+            assert_eq!(
+                "#Condition[\"Invalid for modifier: :unless\"]",
+                result.to_string()
+            );
+        }
+    }
+}
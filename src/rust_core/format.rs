@@ -0,0 +1,342 @@
+use crate::clojure_protocol::type_names;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use bigdecimal::{BigDecimal, RoundingMode};
+use num_bigint::BigInt;
+use num_traits::{Signed, Zero};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// (format fmt & args)
+///
+/// fmt with each of its directives replaced by the next of args,  as Java's String.format
+/// does it;  %[index$][flags][width][.precision]conversion,  where conversion is one of
+///
+///   s S  str of the arg,  or null for nil
+///   d    an integer;  x X and o in hex and octal
+///   f    a decimal,  to precision places (6 unless it's given);  e E in scientific notation
+///   c    a character;  b whether the arg is truthy
+///   % n  a % and a newline,  which take no arg
+///
+/// and flags are any of -#+ 0,( as Java has them
+#[derive(Debug, Clone)]
+pub struct FormatFn {}
+impl ToValue for FormatFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FormatFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_arg_count(1, 0);
+        }
+        match &*args[0] {
+            Value::String(fmt) => match format(fmt, &args[1..]) {
+                Ok(formatted) => Value::String(formatted.into()),
+                Err(condition) => condition,
+            },
+            _ => error_message::type_mismatch(TypeTag::String, &args[0]),
+        }
+    }
+}
+
+// One directive;  all but its conversion as fmt has it
+struct Spec<'a> {
+    directive: &'a str,
+    flags: &'a str,
+    width: Option<usize>,
+    precision: Option<usize>,
+}
+impl Spec<'_> {
+    fn has(&self, flag: char) -> bool {
+        self.flags.contains(flag)
+    }
+    // text,  padded out to the width with spaces;  on the right if it's left justified
+    fn pad(&self, text: String) -> String {
+        let len = text.chars().count();
+        match self.width {
+            Some(width) if width > len && self.has('-') => {
+                format!("{}{}", text, " ".repeat(width - len))
+            }
+            Some(width) if width > len => format!("{}{}", " ".repeat(width - len), text),
+            _ => text,
+        }
+    }
+    // A number,  from its digits;  with its sign,  prefix (0x,  say),  and zeros to the width
+    // if it's zero padded
+    fn number(&self, negative: bool, prefix: &str, digits: String) -> String {
+        let (sign, suffix) = match negative {
+            true if self.has('(') => ("(", ")"),
+            true => ("-", ""),
+            false if self.has('+') => ("+", ""),
+            false if self.has(' ') => (" ", ""),
+            false => ("", ""),
+        };
+        let len = sign.len() + prefix.len() + digits.chars().count() + suffix.len();
+        let zeros = match self.width {
+            Some(width) if width > len && self.has('0') => "0".repeat(width - len),
+            _ => String::new(),
+        };
+        self.pad(format!("{}{}{}{}{}", sign, prefix, zeros, digits, suffix))
+    }
+    fn mismatch(&self, arg: &Value) -> Value {
+        Value::Condition(format!("{} can't format {}", self.directive, type_names(arg)[0]).into())
+    }
+}
+
+// digits,  with a comma between each group of three
+fn grouped(digits: &str) -> String {
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+// A decimal arg,  and whether it's negative;  None if it's NaN or infinite,  as its str
+fn decimal(arg: &Value) -> Option<Result<(bool, BigDecimal), String>> {
+    match arg {
+        Value::F64(f) if !f.is_finite() => Some(Err(match *f {
+            f if f.is_nan() => "NaN".to_string(),
+            f if f > 0.0 => "Infinity".to_string(),
+            _ => "-Infinity".to_string(),
+        })),
+        // Its shortest repr,  as Java rounds it;  so 0.15 is 0.2 to one place
+        Value::F64(f) => BigDecimal::from_str(&f.abs().to_string())
+            .ok()
+            .map(|d| Ok((f.is_sign_negative(), d))),
+        Value::BigDecimal(d) => Some(Ok((d.is_negative(), d.abs()))),
+        _ => None,
+    }
+}
+
+// d,  which isn't negative,  as d.ddde+dd to precision places
+fn scientific(d: &BigDecimal, precision: usize, upper: bool) -> String {
+    let (mut mantissa, mut exponent) = match d.is_zero() {
+        true => (BigDecimal::zero(), 0),
+        false => {
+            let (digits, scale) = d.as_bigint_and_exponent();
+            let places = d.digits() as i64 - 1;
+            (BigDecimal::new(digits, places), places - scale)
+        }
+    };
+    let ten = BigDecimal::from(10);
+    mantissa = mantissa.with_scale_round(precision as i64, RoundingMode::HalfUp);
+    // 9.99 to one place is 10.0;  1.0,  and one more power of ten
+    if mantissa >= ten {
+        mantissa = (mantissa / ten).with_scale_round(precision as i64, RoundingMode::HalfUp);
+        exponent += 1;
+    }
+    let sign = if exponent < 0 { '-' } else { '+' };
+    let e = if upper { 'E' } else { 'e' };
+    format!(
+        "{}{}{}{:02}",
+        mantissa.to_plain_string(),
+        e,
+        sign,
+        exponent.abs()
+    )
+}
+
+// arg as conversion and spec have it
+fn convert(spec: &Spec, conversion: char, arg: &Value) -> Result<String, Value> {
+    Ok(match conversion {
+        's' | 'S' => {
+            let text = match arg {
+                Value::Nil => "null".to_string(),
+                _ => arg.to_string(),
+            };
+            let text = match spec.precision {
+                Some(precision) => text.chars().take(precision).collect(),
+                None => text,
+            };
+            spec.pad(match conversion {
+                'S' => text.to_uppercase(),
+                _ => text,
+            })
+        }
+        'd' => {
+            let (negative, digits) = match arg {
+                Value::I32(i) => (*i < 0, i.unsigned_abs().to_string()),
+                Value::BigInt(i) => (i.is_negative(), i.abs().to_string()),
+                _ => return Err(spec.mismatch(arg)),
+            };
+            let digits = match spec.has(',') {
+                true => grouped(&digits),
+                false => digits,
+            };
+            spec.number(negative, "", digits)
+        }
+        'x' | 'X' | 'o' => {
+            // Ours are longs,  as Java has them;  so a negative one is its two's complement
+            let (negative, i) = match arg {
+                Value::I32(i) => (false, BigInt::from(*i as i64 as u64)),
+                Value::BigInt(i) => (i.is_negative(), i.abs()),
+                _ => return Err(spec.mismatch(arg)),
+            };
+            let (prefix, digits) = match conversion {
+                'o' => ("0", i.to_str_radix(8)),
+                'X' => ("0X", i.to_str_radix(16).to_uppercase()),
+                _ => ("0x", i.to_str_radix(16)),
+            };
+            spec.number(negative, if spec.has('#') { prefix } else { "" }, digits)
+        }
+        'f' | 'e' | 'E' => {
+            let precision = spec.precision.unwrap_or(6);
+            let (negative, d) = match decimal(arg) {
+                Some(Ok(decimal)) => decimal,
+                Some(Err(special)) => return Ok(spec.pad(special)),
+                None => return Err(spec.mismatch(arg)),
+            };
+            let digits = match conversion {
+                'f' => {
+                    let rounded = d
+                        .with_scale_round(precision as i64, RoundingMode::HalfUp)
+                        .to_plain_string();
+                    match (spec.has(','), rounded.split_once('.')) {
+                        (true, Some((whole, fraction))) => {
+                            format!("{}.{}", grouped(whole), fraction)
+                        }
+                        (true, None) => grouped(&rounded),
+                        (false, _) => rounded,
+                    }
+                }
+                _ => scientific(&d, precision, conversion == 'E'),
+            };
+            spec.number(negative, "", digits)
+        }
+        'c' => match arg {
+            Value::Char(c) => spec.pad(c.to_string()),
+            Value::I32(i) => match std::char::from_u32(*i as u32) {
+                Some(c) => spec.pad(c.to_string()),
+                None => return Err(Value::Condition(format!("{} is not a character", i).into())),
+            },
+            Value::Nil => spec.pad("null".to_string()),
+            _ => return Err(spec.mismatch(arg)),
+        },
+        'b' | 'B' => {
+            let truthy = !matches!(arg, Value::Nil | Value::Boolean(false));
+            spec.pad(match conversion {
+                'B' => truthy.to_string().to_uppercase(),
+                _ => truthy.to_string(),
+            })
+        }
+        _ => {
+            return Err(Value::Condition(
+                format!("Unknown format conversion '{}'", conversion).into(),
+            ))
+        }
+    })
+}
+
+// The number at the start of s,  and the rest of s
+fn leading_number(s: &str) -> (Option<usize>, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    (s[..end].parse().ok(), &s[end..])
+}
+
+/// fmt,  with each directive replaced by the next of args (or the one it says)
+pub fn format(fmt: &str, args: &[Arc<Value>]) -> Result<String, Value> {
+    let mut formatted = String::new();
+    let mut next = 0;
+    let mut rest = fmt;
+    while let Some(start) = rest.find('%') {
+        formatted.push_str(&rest[..start]);
+        let directive = &rest[start..];
+        let mut remaining = &directive[1..];
+        // An index,  if there's a $ after it;  otherwise that number's the width
+        let index = match leading_number(remaining) {
+            (Some(index), after) if after.starts_with('$') => {
+                remaining = &after[1..];
+                Some(index)
+            }
+            _ => None,
+        };
+        let flags_end = remaining
+            .find(|c: char| !"-#+ 0,(".contains(c))
+            .unwrap_or(remaining.len());
+        let flags = &remaining[..flags_end];
+        let (width, after) = leading_number(&remaining[flags_end..]);
+        remaining = after;
+        let precision = match remaining.strip_prefix('.') {
+            Some(after) => {
+                let (precision, after) = leading_number(after);
+                remaining = after;
+                precision
+            }
+            None => None,
+        };
+        let conversion = match remaining.chars().next() {
+            Some(conversion) => conversion,
+            None => {
+                return Err(Value::Condition(
+                    format!("Unfinished format directive '{}'", directive).into(),
+                ))
+            }
+        };
+        rest = &remaining[conversion.len_utf8()..];
+        let spec = Spec {
+            directive: &directive[..directive.len() - rest.len()],
+            flags,
+            width,
+            precision,
+        };
+        match conversion {
+            '%' => formatted.push_str(&spec.pad("%".to_string())),
+            'n' => formatted.push('\n'),
+            _ => {
+                let i = match index {
+                    Some(index) => index.saturating_sub(1),
+                    None => {
+                        next += 1;
+                        next - 1
+                    }
+                };
+                let arg = args.get(i).ok_or_else(|| {
+                    Value::Condition(
+                        format!("Format specifier '{}' has no argument", spec.directive).into(),
+                    )
+                })?;
+                formatted.push_str(&convert(&spec, conversion, arg)?);
+            }
+        }
+    }
+    formatted.push_str(rest);
+    Ok(formatted)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    #[test]
+    fn format_fills_in_each_directive() {
+        let result = Repl::default().eval_all(&[
+            "[(format \"%s and %s\" \"a\" [1 2]) (format \"%5d|%-5d|%05d\" 42 42 -42) (format \"%,d %+d\" 1234567 3) (format \"%x %X %#x %o\" 255 255 255 8) (format \"%x\" -1) (format \"%.2f %f %8.3f\" 3.14159 1.5 2.0) (format \"%.1f %.3e\" 0.15 12345.678) (format \"%s%% %c %b\" nil \\a nil) (format \"%2$s %1$s\" \"a\" \"b\")]",
+        ]);
+        assert_eq!(
+            "[\"a and [1 2]\" \"   42|42   |-0042\" \"1,234,567 +3\" \"ff FF 0xff 10\" \"ffffffffffffffff\" \"3.14 1.500000    2.000\" \"0.2 1.235e+04\" \"null% a false\" \"b a\"]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn format_says_what_it_cant_format() {
+        let repl = Repl::default();
+        assert_eq!(
+            Value::Condition("Format specifier '%s' has no argument".into()),
+            repl.eval_all(&["(format \"%s %s\" 1)"])
+        );
+        assert_eq!(
+            Value::Condition("Unknown format conversion 'q'".into()),
+            repl.eval_all(&["(format \"%q\" 1)"])
+        );
+    }
+}
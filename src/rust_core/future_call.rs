@@ -0,0 +1,31 @@
+use crate::error_message;
+use crate::future::Future;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (future-call f)
+///
+/// Calls f (with no arguments) as a future;  (future body) wraps this
+#[derive(Debug, Clone)]
+pub struct FutureCallFn {}
+impl ToValue for FutureCallFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for FutureCallFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match ifn::as_ifn(&args[0]) {
+            Some(f) => match Future::run(move || f.invoke(vec![])) {
+                Ok(future) => Value::Future(future),
+                Err(condition) => condition,
+            },
+            None => error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        }
+    }
+}
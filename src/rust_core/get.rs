@@ -1,32 +1,56 @@
+use crate::error_message;
 use crate::ifn::IFn;
-use crate::persistent_list_map::IPersistentMap;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
-// General assoc fn; however,  currently just implemented
-// for our one map type, PersistentListMap
+/// (get coll key) (get coll key not-found)
+///
+// General get fn; works on maps, including sorted and transient ones, sets, records,
+// vectors and strings (whose elements and characters are got by index).  Anything else,
+// nil included, gives not-found, or nil
 #[derive(Debug, Clone)]
 pub struct GetFn {}
 impl ToValue for GetFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for GetFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        if args.len() != 2 {
-            return Value::Condition(format!(
-                "Wrong number of arguments given to function (Given: {}, Expected: 2)",
-                args.len()
-            ));
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
         }
-
-        if let Value::PersistentListMap(pmap) = &*(args.get(0).unwrap().clone()) {
-            let key = args.get(1).unwrap();
-            return pmap.get(key).to_value();
+        match lookup(&args[0], &args[1]) {
+            Ok(Some(val)) => val.to_value(),
+            // Anything that can't hold key,  nil included,  just doesn't have it
+            Ok(None) => args
+                .get(2)
+                .map_or(Value::Nil, |not_found| not_found.to_value()),
+            Err(condition) => condition,
         }
-        // @TODO add error in here with erkk's new error tools
-
-        Value::Nil
     }
 }
+
+// What key maps to in coll,  if it's there;  a key mapped to nil is there
+fn lookup(coll: &Arc<Value>, key: &Arc<Value>) -> Result<Option<Arc<Value>>, Value> {
+    Ok(match &**coll {
+        Value::PersistentListMap(pmap) => pmap.get_entry(key).map(|entry| entry.val),
+        Value::PersistentTreeMap(ptmap) => ptmap.get_entry(key).map(|entry| Arc::clone(&entry.val)),
+        Value::PersistentTreeSet(ptset) => ptset.get(key),
+        Value::PersistentHashSet(pset) if pset.contains(key) => Some(Arc::clone(key)),
+        Value::Instance(instance) => instance.get(key),
+        Value::TransientMap(tmap) => tmap.get(key)?,
+        Value::PersistentVector(pvector) => match &**key {
+            Value::I32(ind) if *ind >= 0 => pvector.nth(*ind as usize).cloned(),
+            _ => None,
+        },
+        Value::String(s) => match &**key {
+            Value::I32(ind) if *ind >= 0 => s
+                .chars()
+                .nth(*ind as usize)
+                .map(|c| Arc::new(Value::Char(c))),
+            _ => None,
+        },
+        _ => None,
+    })
+}
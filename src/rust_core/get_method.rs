@@ -0,0 +1,32 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::multi_fn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (get-method multifn dispatch-val)
+///
+/// The method multifn would call for dispatch-val,  or nil if there's none
+#[derive(Debug, Clone)]
+pub struct GetMethodFn {}
+impl ToValue for GetMethodFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for GetMethodFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let multi = match multi_fn::from_value(&args[0]) {
+            Ok(multi) => multi,
+            Err(condition) => return condition,
+        };
+        match multi.get_method(&args[1]) {
+            Ok(Some(method)) => Value::IFn(Arc::new(method)),
+            Ok(None) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::transducer::{Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::{Arc, Mutex};
+
+/// (halt-when pred) (halt-when pred retf)
+///
+/// A transducer that stops at the first input pred is truthy for;  and has transduce or
+/// into give that input,  rather than what's been made so far.  With retf,  they give
+/// (retf result input) instead,  result being what's been made,  completed
+#[derive(Debug, Clone)]
+pub struct HaltWhenFn {}
+impl ToValue for HaltWhenFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for HaltWhenFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        if let Some(f) = args.iter().find(|f| !ifn::is_callable(f)) {
+            return error_message::type_mismatch(TypeTag::IFn, f);
+        }
+        let (pred, retf) = (Arc::clone(&args[0]), args.get(1).cloned());
+        Transducer::new(move || {
+            Box::new(HaltWhenStage {
+                pred: Arc::clone(&pred),
+                retf: retf.clone(),
+                halted: Mutex::new(None),
+            })
+        })
+        .to_value()
+    }
+}
+
+#[derive(Debug)]
+struct HaltWhenStage {
+    pred: Arc<Value>,
+    retf: Option<Arc<Value>>,
+    // What's given in place of the result,  once an input's stopped the reduce
+    halted: Mutex<Option<Value>>,
+}
+impl Stage for HaltWhenStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        match ifn::call(&self.pred, vec![Arc::clone(&input)]) {
+            Value::Condition(condition) => return Value::Condition(condition),
+            halts if !halts.is_truthy() => return rf.invoke(vec![result, input]),
+            _ => {}
+        }
+        let halted = match &self.retf {
+            Some(retf) => match rf.invoke(vec![Arc::clone(&result)]) {
+                Value::Condition(condition) => return Value::Condition(condition),
+                completed => ifn::call(retf, vec![completed.to_rc_value(), input]),
+            },
+            None => input.to_value(),
+        };
+        if let Value::Condition(condition) = halted {
+            return Value::Condition(condition);
+        }
+        *self.halted.lock().unwrap() = Some(halted);
+        Value::Reduced(result)
+    }
+    fn complete(&self, rf: &Arc<dyn IFn>, result: Arc<Value>) -> Value {
+        let halted = self.halted.lock().unwrap().take();
+        match halted {
+            Some(halted) => halted,
+            None => rf.invoke(vec![result]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn the_input_that_halts_is_given_in_place_of_the_result() {
+        let result = Repl::default().eval_all(&[
+            "[(into [] (halt-when neg?) [1 2 -3 4]) (into [] (halt-when neg?) [1 2]) (transduce (halt-when neg? (fn [acc x] [acc x])) conj [] [1 -2 3]) (transduce (comp (map inc) (halt-when :stop)) conj [] [])]",
+        ]);
+        assert_eq!("[-3 [1 2] [[1] -2] []]", result.to_string());
+    }
+}
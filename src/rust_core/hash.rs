@@ -0,0 +1,62 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// (hash x)
+///
+/// The hash code of x;  values that are = have the same one
+#[derive(Debug, Clone)]
+pub struct HashFn {}
+impl ToValue for HashFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for HashFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let mut hasher = DefaultHasher::new();
+        args[0].hash(&mut hasher);
+        Value::I32(hasher.finish() as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod hash_tests {
+        use crate::ifn::IFn;
+        use crate::repl::Repl;
+        use crate::rust_core::HashFn;
+        use crate::value::{ToValue, Value};
+
+        fn hash(form: &str) -> Value {
+            let val = Repl::default().eval_all(&[form]);
+            HashFn {}.invoke(vec![val.to_rc_value()])
+        }
+
+        #[test]
+        fn equal_values_hash_alike() {
+            assert_eq!(hash("[1 2]"), hash("'(1 2)"));
+            assert_eq!(hash("[1 2]"), hash("(map inc [0 1])"));
+            assert_eq!(hash("{:a 1 :b 2}"), hash("(sorted-map :b 2 :a 1)"));
+            assert_eq!(hash("(assoc {:a 1} :a 2)"), hash("{:a 2}"));
+            assert_eq!(hash("#{1 2}"), hash("(sorted-set 2 1)"));
+            assert_eq!(hash("1"), hash("1N"));
+            assert_eq!(hash("0.0"), hash("-0.0"));
+            assert_eq!(hash("1.0M"), hash("1.00M"));
+        }
+
+        #[test]
+        fn takes_one_argument() {
+            match (HashFn {}).invoke(vec![]) {
+                Value::Condition(_) => {}
+                other => panic!("expected a condition, got {}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::ifn::IFn;
+use crate::rust_core::thread_macros::{let_form, list, sym};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (if-let [name test] then else) and (if-some [name test] then else)
+///
+/// Evaluates then with name bound to test's value if it's truthy (or with if-some,  if it
+/// isn't nil);  and else,  which defaults to nil,  otherwise
+///
+/// (if-let [x (f)] a b) expands to
+///
+/// (let [temp__val__ (f)] (if temp__val__ (let [x temp__val__] a) b))
+#[derive(Debug, Clone)]
+pub struct IfLetMacro {
+    pub some: bool,
+}
+impl ToValue for IfLetMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for IfLetMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let name = if self.some { "if-some" } else { "if-let" };
+        if args.len() != 2 && args.len() != 3 {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to {} (Given: {}, Expected: 2 or 3)",
+                    name,
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let otherwise = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| Value::Nil.to_rc_value());
+        match expand(name, self.some, &args[0], Arc::clone(&args[1]), otherwise) {
+            Ok(expansion) => expansion,
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// (when-let [name test] body) and (when-some [name test] body)
+///
+/// Evaluates body with name bound to test's value if it's truthy (or with when-some,  if
+/// it isn't nil);  and returns nil otherwise
+///
+/// (when-let [x (f)] a b) expands to
+///
+/// (let [temp__val__ (f)] (if temp__val__ (let [x temp__val__] (do a b)) nil))
+#[derive(Debug, Clone)]
+pub struct WhenLetMacro {
+    pub some: bool,
+}
+impl ToValue for WhenLetMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for WhenLetMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let name = if self.some { "when-some" } else { "when-let" };
+        if args.is_empty() {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to {} (Given: 0, Expected: >=1)",
+                    name
+                )
+                .into(),
+            );
+        }
+        let mut body = vec![sym("do")];
+        body.extend_from_slice(&args[1..]);
+        match expand(
+            name,
+            self.some,
+            &args[0],
+            list(body),
+            Value::Nil.to_rc_value(),
+        ) {
+            Ok(expansion) => expansion,
+            Err(condition) => condition,
+        }
+    }
+}
+
+// The let around the if that the if-let family expand to
+fn expand(
+    name: &str,
+    some: bool,
+    binding: &Value,
+    then: Arc<Value>,
+    otherwise: Arc<Value>,
+) -> Result<Value, Value> {
+    let (binding, test) = match binding {
+        Value::PersistentVector(binding) if binding.len() == 2 => {
+            let mut binding = binding.iter().cloned();
+            (binding.next().unwrap(), binding.next().unwrap())
+        }
+        Value::PersistentVector(_) => {
+            return Err(Value::Condition(
+                format!("{} requires exactly 2 forms in binding vector", name).into(),
+            ))
+        }
+        _ => {
+            return Err(Value::Condition(
+                format!("{} requires a vector for its binding", name).into(),
+            ))
+        }
+    };
+    let temp = sym("temp__val__");
+    let then = let_form(vec![binding, Arc::clone(&temp)], then);
+    let branch = if some {
+        list(vec![
+            sym("if"),
+            list(vec![sym("="), Value::Nil.to_rc_value(), Arc::clone(&temp)]),
+            otherwise,
+            then,
+        ])
+    } else {
+        list(vec![sym("if"), Arc::clone(&temp), then, otherwise])
+    };
+    Ok(let_form(vec![temp, test], branch).to_value())
+}
+
+#[cfg(test)]
+mod tests {
+    mod if_let_macro_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn if_let_binds_truthy_values() {
+            let result = Repl::default().eval_all(&[
+                "[(if-let [x (:a {:a 1})] (inc x) :none) (if-let [x false] x :none) (if-let [x nil] x) (when-let [x 2] (+ 1 1) (* x 10)) (when-let [x false] x)]",
+            ]);
+            assert_eq!("[2 :none nil 20 nil]", result.to_string());
+        }
+
+        #[test]
+        fn if_some_binds_non_nil_values() {
+            let result = Repl::default().eval_all(&[
+                "[(if-some [x false] (not x) :none) (if-some [x nil] x :none) (when-some [x false] [x]) (when-some [x nil] x)]",
+            ]);
+            assert_eq!("[true :none [false] nil]", result.to_string());
+        }
+
+        #[test]
+        fn if_let_needs_a_binding_vector() {
+            let result = Repl::default().eval_all(&["(if-let [x 1 y 2] x)"]);
+            assert_eq!(
+                "#Condition[\"if-let requires exactly 2 forms in binding vector\"]",
+                result.to_string()
+            );
+            let result = Repl::default().eval_all(&["(when-some x x)"]);
+            assert_eq!(
+                "#Condition[\"when-some requires a vector for its binding\"]",
+                result.to_string()
+            );
+        }
+    }
+}
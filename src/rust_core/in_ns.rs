@@ -0,0 +1,58 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (in-ns name)
+///
+/// Makes the namespace named name (creating it,  if there isn't one) the current one,  and returns it
+#[derive(Debug, Clone)]
+pub struct InNsFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl InNsFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> InNsFn {
+        InNsFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for InNsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for InNsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Symbol(name) => {
+                let environment = &self.enclosing_environment;
+                environment.change_namespace(Symbol::clone(name));
+                Value::Namespace(environment.find_or_create_namespace(name))
+            }
+            _ => error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn in_ns_switches_namespace_keeping_clojure_core() {
+        let result = Repl::default().eval_all(&[
+            "(in-ns 'app.core)",
+            "(def x (inc 1))",
+            "(in-ns 'user)",
+            "[app.core/x (ns-name (in-ns 'app.core))]",
+        ]);
+        assert_eq!("[2 app.core]", result.to_string());
+    }
+}
@@ -0,0 +1,32 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (int x)
+///
+/// x as an i32;  a number's fractional part is dropped,  and a character is its code point
+#[derive(Debug, Clone)]
+pub struct IntFn {}
+impl ToValue for IntFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for IntFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::I32(i) => Value::I32(*i),
+            Value::F64(f) if f.is_finite() && *f >= i32::MIN as f64 && *f <= i32::MAX as f64 => {
+                Value::I32(*f as i32)
+            }
+            Value::F64(f) => Value::Condition(format!("Value out of range for int: {}", f).into()),
+            Value::Char(c) => Value::I32(*c as i32),
+            _ => error_message::type_mismatch(TypeTag::Number, &args[0]),
+        }
+    }
+}
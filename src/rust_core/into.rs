@@ -0,0 +1,142 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq;
+use crate::persistent_vector::PersistentVector;
+use crate::rust_core::conj::as_pair;
+use crate::rust_core::{ConjFn, TransduceFn};
+use crate::transient::MapEdits;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (into to from) (into to xform from)
+///
+/// Conjs each element of from onto to;  or with xform,  transduces from with conj,  so a
+/// halt-when can give something else.  Without xform,  vectors,  sets and maps are built up in
+/// place,  as with transients,  rather than through a new collection per element
+#[derive(Debug, Clone)]
+pub struct IntoFn {}
+impl ToValue for IntoFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for IntoFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match &args[..] {
+            [] => PersistentVector::empty().to_value(),
+            [to] => to.to_value(),
+            [to, from] => into(to, from),
+            [to, xform, from] => TransduceFn {}.invoke(vec![
+                Arc::clone(xform),
+                ConjFn {}.to_rc_value(),
+                Arc::clone(to),
+                Arc::clone(from),
+            ]),
+            _ => error_message::wrong_varg_count(&[0, 1, 2, 3], args.len()),
+        }
+    }
+}
+
+pub(crate) fn into(to: &Arc<Value>, from: &Arc<Value>) -> Value {
+    let into = match &**to {
+        Value::PersistentVector(pvector) => {
+            let mut vector = (**pvector).clone();
+            lazy_seq::try_for_each(from, |x| {
+                vector.conj_in_place(x);
+                Ok(())
+            })
+            .map(|()| vector.to_value())
+        }
+        Value::PersistentHashSet(pset) => {
+            let mut set = (**pset).clone();
+            lazy_seq::try_for_each(from, |x| {
+                set.conj_in_place(x);
+                Ok(())
+            })
+            .map(|()| set.to_value())
+        }
+        Value::PersistentListMap(pmap) => {
+            let mut edits = MapEdits::new(pmap);
+            lazy_seq::try_for_each(from, |x| match as_pair(&x) {
+                Some((key, val)) => {
+                    edits.assoc(key, val);
+                    Ok(())
+                }
+                None => Err(error_message::type_mismatch(TypeTag::PersistentVector, &x)),
+            })
+            .map(|()| edits.into_map().to_value())
+        }
+        _ => {
+            let mut coll = Arc::clone(to);
+            lazy_seq::try_for_each(from, |x| {
+                let conjed = ConjFn {}.invoke(vec![Arc::clone(&coll), x]);
+                if let Value::Condition(_) = conjed {
+                    return Err(conjed);
+                }
+                coll = Arc::new(conjed);
+                Ok(())
+            })
+            .map(|()| coll.to_value())
+        }
+    };
+    into.unwrap_or_else(|condition| condition)
+}
+
+#[cfg(test)]
+mod tests {
+    mod into_tests {
+        use crate::ifn::IFn;
+        use crate::persistent_list::PersistentList;
+        use crate::persistent_list_map::PersistentListMap;
+        use crate::persistent_vector::PersistentVector;
+        use crate::rust_core::{IntoFn, RangeFn};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn into(to: Value, from: Value) -> Value {
+            IntoFn {}.invoke(vec![Arc::new(to), Arc::new(from)])
+        }
+        fn range(n: i32) -> Value {
+            RangeFn {}.invoke(vec![Arc::new(Value::I32(n))])
+        }
+
+        #[test]
+        fn into_a_vector_appends() {
+            let start = PersistentVector::empty().conj(Arc::new(Value::I32(9)));
+            assert_eq!("[9 0 1 2]", into(start.to_value(), range(3)).to_string());
+        }
+
+        #[test]
+        fn into_a_list_prepends() {
+            assert_eq!(
+                "(2 1 0)",
+                into(PersistentList::Empty.to_value(), range(3)).to_string()
+            );
+        }
+
+        #[test]
+        fn into_a_map_takes_pairs() {
+            let pairs = vec![
+                vec![Arc::new(Value::I32(1)), Arc::new(Value::I32(2))]
+                    .into_iter()
+                    .collect::<PersistentVector>()
+                    .to_rc_value(),
+                vec![Arc::new(Value::I32(1)), Arc::new(Value::I32(3))]
+                    .into_iter()
+                    .collect::<PersistentVector>()
+                    .to_rc_value(),
+            ]
+            .into_iter()
+            .collect::<PersistentVector>();
+            assert_eq!(
+                "{1 3}",
+                into(PersistentListMap::Empty.to_value(), pairs.to_value()).to_string()
+            );
+            assert!(matches!(
+                into(PersistentListMap::Empty.to_value(), range(1)),
+                Value::Condition(_)
+            ));
+        }
+    }
+}
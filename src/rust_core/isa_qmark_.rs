@@ -0,0 +1,24 @@
+use crate::hierarchy;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (isa? child parent) or (isa? h child parent)
+///
+/// Whether child is parent, or derives from it.  Vectors are compared element by element
+#[derive(Debug, Clone)]
+pub struct IsaFn {}
+impl ToValue for IsaFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for IsaFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (hierarchy, args) = match hierarchy::hierarchy_and_args(&args, 2) {
+            Ok(split) => split,
+            Err(condition) => return condition,
+        };
+        Value::Boolean(hierarchy.isa(&args[0], &args[1]))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq::LazySeq;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (iterate f x)
+///
+/// An infinite lazy seq of x, (f x), (f (f x)) and so on
+#[derive(Debug, Clone)]
+pub struct IterateFn {}
+impl ToValue for IterateFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for IterateFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match ifn::as_ifn(&args[0]) {
+            Some(f) => lazy_iterate(f, Arc::clone(&args[1])).into_value(),
+            None => error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        }
+    }
+}
+
+fn lazy_iterate(f: Arc<dyn IFn>, x: Arc<Value>) -> LazySeq {
+    let prev = Arc::clone(&x);
+    let rest = LazySeq::new(move || match f.invoke(vec![prev]) {
+        Value::Condition(condition) => Err(Value::Condition(condition)),
+        next => lazy_iterate(f, Arc::new(next)).step(),
+    });
+    LazySeq::cons(x, Arc::new(rest.into_value()))
+}
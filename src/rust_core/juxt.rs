@@ -0,0 +1,58 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::persistent_vector::PersistentVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (juxt f & fs)
+///
+/// A fn that calls each of f and fs on its args,  and gives back a vector of what they
+/// gave.  ((juxt :a :b) {:a 1 :b 2}) is [1 2];  so (sort-by (juxt :a :b) maps) sorts by :a,
+/// and then by :b
+#[derive(Debug, Clone)]
+pub struct JuxtFn {}
+impl ToValue for JuxtFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for JuxtFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        match Juxt::new(args) {
+            Ok(juxt) => Value::ifn(juxt),
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Juxt {
+    // Each a fn,  or anything else that can be called
+    fns: Vec<Arc<Value>>,
+}
+impl Juxt {
+    pub fn new(fns: Vec<Arc<Value>>) -> Result<Juxt, Value> {
+        match fns.iter().find(|f| !ifn::is_callable(f)) {
+            Some(f) => Err(error_message::type_mismatch(TypeTag::IFn, f)),
+            None => Ok(Juxt { fns }),
+        }
+    }
+}
+
+impl IFn for Juxt {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let mut results = Vec::with_capacity(self.fns.len());
+        for f in self.fns.iter() {
+            let result = ifn::call(f, args.clone());
+            if let Value::Condition(_) = result {
+                return result;
+            }
+            results.push(Arc::new(result));
+        }
+        PersistentVector::from(results).to_value()
+    }
+}
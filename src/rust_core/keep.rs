@@ -0,0 +1,48 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::transducer::{self, Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (keep f) (keep f coll)
+///
+/// A lazy seq of f of each element of coll,  leaving out the nils;  but not false.
+/// Without coll,  a transducer passing on those of each input
+#[derive(Debug, Clone)]
+pub struct KeepFn {}
+impl ToValue for KeepFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for KeepFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let f = match ifn::as_ifn(&args[0]) {
+            Some(f) => f,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let xform = Transducer::new(move || Box::new(KeepStage { f: Arc::clone(&f) }));
+        match args.get(1) {
+            Some(coll) => transducer::sequence(&xform, Arc::clone(coll)),
+            None => xform.to_value(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct KeepStage {
+    f: Arc<dyn IFn>,
+}
+impl Stage for KeepStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        match self.f.invoke(vec![input]) {
+            Value::Nil => result.to_value(),
+            Value::Condition(condition) => Value::Condition(condition),
+            kept => rf.invoke(vec![result, Arc::new(kept)]),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (key e)
+///
+/// The key of the map entry e;  as seq gives a map's entries
+#[derive(Debug, Clone)]
+pub struct KeyFn {}
+impl ToValue for KeyFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for KeyFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match map_entry(&args[0]) {
+            Ok((key, _)) => key.to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// The key and val of e,  if it's a map entry;  a vector of the two
+pub(crate) fn map_entry(e: &Value) -> Result<(&Arc<Value>, &Arc<Value>), Value> {
+    match e {
+        Value::PersistentVector(pvector) if pvector.len() == 2 => {
+            Ok((pvector.nth(0).unwrap(), pvector.nth(1).unwrap()))
+        }
+        _ => Err(Value::Condition(
+            format!(
+                "Expected a map entry, ie [key val];  got {}",
+                e.to_string_explicit()
+            )
+            .into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn key_and_val_take_apart_the_entries_of_a_map() {
+        let result = Repl::default().eval_all(&[
+            "[(map key (sorted-map :a 1 :b 2)) (map val (sorted-map :a 1 :b 2)) (key (first {:c 3}))]",
+        ]);
+        assert_eq!("[(:a :b) (1 2) :c]", result.to_string());
+        let result = Repl::default().eval_all(&["(key [1 2 3])"]);
+        assert!(matches!(result, crate::value::Value::Condition(_)));
+    }
+}
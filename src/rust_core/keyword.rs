@@ -0,0 +1,65 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (keyword name) or (keyword ns name)
+///
+/// The keyword with name,  in ns if it's given;  given one string,  as (keyword "a/b"),  a
+/// namespace may come before a /.  Given a symbol,  or keyword,  the keyword with the same
+/// name and namespace.  nil for nil
+#[derive(Debug, Clone)]
+pub struct KeywordFn {}
+impl ToValue for KeywordFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for KeywordFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match args.as_slice() {
+            [name] => match &**name {
+                Value::String(name) => Keyword {
+                    sym: Symbol::intern(name),
+                }
+                .to_value(),
+                Value::Symbol(sym) => Keyword {
+                    sym: Symbol::clone(sym),
+                }
+                .to_value(),
+                Value::Keyword(_) | Value::Nil => (**name).clone(),
+                _ => error_message::type_mismatch(TypeTag::String, name),
+            },
+            [ns, name] => match (&**ns, &**name) {
+                (Value::String(ns), Value::String(name)) => {
+                    Keyword::intern_with_ns(ns, name).to_value()
+                }
+                (Value::Nil, Value::String(name)) => Keyword::intern_with_ns("", name).to_value(),
+                (Value::String(_), _) | (Value::Nil, _) => {
+                    error_message::type_mismatch(TypeTag::String, name)
+                }
+                _ => error_message::type_mismatch(TypeTag::String, ns),
+            },
+            _ => error_message::wrong_varg_count(&[1, 2], args.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn keywords_and_symbols_are_made_and_taken_apart() {
+        let result = Repl::default().eval_all(&[
+            "[(keyword \"a\") (keyword \"app\" \"b\") (keyword \"app/c\") (keyword 'd/e) (keyword nil) (= (keyword \"k\") :k) (symbol \"x\") (symbol \"app\" \"y\") (symbol :k/z) (symbol #'inc) (= (symbol \"s\") 's) (name :a/b) (name 'c) (name \"s\") (namespace :a/b) (namespace 'c) (get {:a/b 1} (keyword \"a\" \"b\"))]",
+        ]);
+        assert_eq!(
+            "[:a :app/b :app/c :d/e nil true x app/y k/z clojure.core/inc true \"b\" \"c\" \"s\" \"a\" nil 1]",
+            result.to_string()
+        );
+    }
+}
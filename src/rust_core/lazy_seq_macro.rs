@@ -0,0 +1,61 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{self, LazySeq};
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (lazy-seq* f)
+///
+/// A seq whose contents are those of whatever f returns,  calling f only once something
+/// first looks into the seq
+#[derive(Debug, Clone)]
+pub struct LazySeqFn {}
+impl ToValue for LazySeqFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for LazySeqFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let f = match &*args[0] {
+            Value::IFn(ifn) => Arc::clone(ifn),
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        LazySeq::new(move || lazy_seq::step(&Arc::new(f.invoke(vec![])))).into_value()
+    }
+}
+
+/// (lazy-seq body)
+///
+/// (lazy-seq a b) expands to
+///
+/// (lazy-seq* (fn [] a b))
+#[derive(Debug, Clone)]
+pub struct LazySeqMacro {}
+impl ToValue for LazySeqMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for LazySeqMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let mut thunk = vec![
+            Symbol::intern("fn").to_rc_value(),
+            PersistentVector::empty().to_rc_value(),
+        ];
+        thunk.extend_from_slice(&args);
+        vec![
+            Symbol::intern("lazy-seq*").to_rc_value(),
+            thunk.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
@@ -0,0 +1,51 @@
+use crate::clojure_java_io::{self, Reader};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{LazySeq, Step};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (line-seq rdr)
+///
+/// A lazy seq of the lines rdr has left to read,  without their line endings;  each read
+/// only as the seq's walked to it,  so rdr must be open till then
+#[derive(Debug, Clone)]
+pub struct LineSeqFn {}
+impl ToValue for LineSeqFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for LineSeqFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        if clojure_java_io::downcast::<Reader>(&args[0]).is_none() {
+            return Value::Condition(
+                format!(
+                    "Cannot read lines from {};  give a reader",
+                    args[0].to_string_explicit()
+                )
+                .into(),
+            );
+        }
+        lazy_lines(Arc::clone(&args[0])).into_value()
+    }
+}
+
+fn lazy_lines(rdr: Arc<Value>) -> LazySeq {
+    LazySeq::new(move || {
+        let line = match clojure_java_io::downcast::<Reader>(&rdr) {
+            Some(reader) => reader.read_line()?,
+            None => None,
+        };
+        Ok(match line {
+            Some(line) => Step::Next(
+                Arc::new(Value::from(line)),
+                Arc::new(lazy_lines(rdr).into_value()),
+            ),
+            None => Step::Done,
+        })
+    })
+}
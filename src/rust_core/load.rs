@@ -0,0 +1,197 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_vector::ToPersistentVector;
+use crate::reader::SourceReader;
+use crate::repl::Repl;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+/// The environment variable *load-path* starts as the directories of;  separated as they
+/// are in PATH
+pub const LOAD_PATH_VAR: &str = "CLOJURE_RS_PATH";
+
+// The directories *load-path* starts as,  if they've been given in place of LOAD_PATH_VAR's
+static LOAD_PATH: OnceLock<Vec<String>> = OnceLock::new();
+
+thread_local! {
+    // The paths being loaded,  outermost first;  so a file that ends up loading itself
+    // again is caught,  rather than loading forever
+    static PENDING: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// (load & paths)
+///
+/// Evaluates the forms of each path's .clj file,  in one of the directories of *load-path*.
+/// A path starting with / is from the top of those,  and any other from the current
+/// namespace's directory;  so in my.app,  (load "util") loads my/util.clj
+#[derive(Debug, Clone)]
+pub struct LoadFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl LoadFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> LoadFn {
+        LoadFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for LoadFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for LoadFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        for path in args.iter() {
+            let path = match &**path {
+                Value::String(path) => path,
+                _ => return error_message::type_mismatch(TypeTag::String, path),
+            };
+            if let condition @ Value::Condition(_) = load(&self.enclosing_environment, path) {
+                return condition;
+            }
+        }
+        Value::Nil
+    }
+}
+
+/// The path load takes to namespace's file;  my.app-util's is /my/app_util
+pub fn root_resource(namespace: &Symbol) -> String {
+    format!("/{}", namespace.name.replace('-', "_").replace('.', "/"))
+}
+
+/// Makes path,  directories separated as they are in PATH,  what *load-path* starts as
+/// from now on;  rather than CLOJURE_RS_PATH.  Only the first path set is kept
+pub fn set_load_path(path: &str) {
+    let _ = LOAD_PATH.set(directories(path));
+}
+
+/// *load-path*,  as it starts;  the directories set,  or CLOJURE_RS_PATH's,  or failing
+/// those,  src and the current directory
+pub fn load_path() -> Value {
+    let directories = match (LOAD_PATH.get(), env::var(LOAD_PATH_VAR)) {
+        (Some(directories), _) => directories.clone(),
+        (None, Ok(path)) => directories(&path),
+        (None, Err(_)) => vec![String::from("src"), String::from(".")],
+    };
+    directories
+        .into_iter()
+        .map(|directory| Value::String(directory.into()).to_rc_value())
+        .collect::<Vec<Arc<Value>>>()
+        .into_vector()
+        .to_value()
+}
+
+fn directories(path: &str) -> Vec<String> {
+    env::split_paths(path)
+        .filter(|directory| !directory.as_os_str().is_empty())
+        .map(|directory| directory.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Shared with require;  evaluates the forms of the file path (as load takes it) names,  in
+/// the namespace we're in now,  which is the one we're in again afterwards.  Gives nil,  or
+/// the first condition a form gives
+pub(crate) fn load(environment: &Arc<Environment>, path: &str) -> Value {
+    let namespace = environment.get_current_namespace();
+    let path = match path.strip_prefix('/') {
+        Some(_) => path.to_string(),
+        None => {
+            let resource = root_resource(&namespace);
+            let directory = &resource[..resource.rfind('/').unwrap_or(0)];
+            format!("{}/{}", directory, path)
+        }
+    };
+    if let Some(cycle) = PENDING.with(|pending| cycle(&pending.borrow(), &path)) {
+        return Value::Condition(format!("Cyclic load dependency: {}", cycle).into());
+    }
+    let file = match find(environment, &path) {
+        Ok(file) => file,
+        Err(condition) => return condition,
+    };
+    let source = match fs::read_to_string(&file) {
+        Ok(source) => source,
+        Err(e) => return Value::Condition(format!("Could not read {}: {}", file, e).into()),
+    };
+    PENDING.with(|pending| pending.borrow_mut().push(path));
+    let repl = Repl::new(Arc::clone(environment));
+    let mut result = Value::Nil;
+    for form in SourceReader::new(&file, &source) {
+        let evaled = match form {
+            Value::Condition(_) => form,
+            form => repl.eval(&form),
+        };
+        if let Value::Condition(_) = evaled {
+            result = evaled;
+            break;
+        }
+    }
+    PENDING.with(|pending| pending.borrow_mut().pop());
+    environment.change_namespace(namespace);
+    result
+}
+
+/// Whether the file path (as load takes it from the top of *load-path*) is being loaded
+/// now;  so its namespace may be there without all it defines yet
+pub(crate) fn is_loading(path: &str) -> bool {
+    PENDING.with(|pending| pending.borrow().iter().any(|pending| pending == path))
+}
+
+// [ /a ]->/b->[ /a ],  if path is already being loaded
+fn cycle(pending: &[String], path: &str) -> Option<String> {
+    let start = pending.iter().position(|pending| pending == path)?;
+    let mut cycle = format!("[ {} ]", path);
+    for pending in pending[start + 1..].iter() {
+        cycle.push_str("->");
+        cycle.push_str(pending);
+    }
+    cycle.push_str(&format!("->[ {} ]", path));
+    Some(cycle)
+}
+
+// The file path names,  in the first directory of *load-path* that has it
+fn find(environment: &Environment, path: &str) -> Result<String, Value> {
+    let load_path = environment.get(&Symbol::intern_with_ns("clojure.core", "*load-path*"));
+    let directories = match &*load_path {
+        Value::PersistentVector(directories) => directories,
+        _ => {
+            return Err(error_message::type_mismatch(
+                TypeTag::PersistentVector,
+                &load_path,
+            ))
+        }
+    };
+    let relative = format!("{}.clj", &path[1..]);
+    for directory in directories.iter() {
+        let directory = match &**directory {
+            Value::String(directory) => directory,
+            _ => return Err(error_message::type_mismatch(TypeTag::String, directory)),
+        };
+        let file = Path::new(&**directory).join(&relative);
+        if file.is_file() {
+            return Ok(file.to_string_lossy().into_owned());
+        }
+    }
+    Err(Value::Condition(
+        format!("Could not locate {} on *load-path*", relative).into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rust_core::load::directories;
+
+    #[test]
+    fn a_load_path_is_split_as_path_is() {
+        assert_eq!(vec!["lib", "src"], directories("lib::src"));
+        assert!(directories("").is_empty());
+    }
+}
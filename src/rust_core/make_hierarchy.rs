@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::hierarchy;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (make-hierarchy)
+///
+/// Returns a new, empty hierarchy, for the versions of derive, isa? etc that take one
+#[derive(Debug, Clone)]
+pub struct MakeHierarchyFn {}
+impl ToValue for MakeHierarchyFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MakeHierarchyFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.is_empty() {
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        hierarchy::Hierarchy::default().to_value()
+    }
+}
@@ -1,40 +1,98 @@
 use crate::error_message;
-use crate::ifn::IFn;
-use crate::iterable::Iterable;
-use crate::persistent_list::PersistentList;
-use crate::protocol::ProtocolCastable;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq::{self, Chunks, LazySeq, Step};
+use crate::transducer::{Stage, Transducer};
 use crate::type_tag::TypeTag;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
-// This is a tide me over rust wrapper, as map is implemented in lower level primitives
-// in pure Clojure
-// // That being said, I have not decided as to whether or not there is value to having both
+/// (map f) (map f coll & colls)
+///
+/// A lazy seq of f applied to the first element of each coll,  then to the second of
+/// each,  and so on until any one coll runs out.  Without a coll,  a transducer passing on
+/// f of each input.  Over a vector or range alone,  f is applied a chunk at a time
 #[derive(Debug, Clone)]
 pub struct MapFn {}
 impl ToValue for MapFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for MapFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.is_empty() {
-            return error_message::wrong_arg_count(1, args.len());
+            return error_message::wrong_arg_count(2, args.len());
         }
-        if let Value::IFn(ifn) = &**args.get(0).unwrap() {
-            if let Some(iterable) = args.get(1).unwrap().try_as_protocol::<Iterable>() {
-                return iterable
-                    .iter()
-                    .map(|rc_val| Rc::new(ifn.invoke(vec![rc_val])))
-                    .collect::<PersistentList>()
-                    .to_value();
+        let f = match ifn::as_ifn(&args[0]) {
+            Some(f) => f,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        match args.len() {
+            1 => Transducer::new(move || Box::new(MapStage { f: Arc::clone(&f) })).to_value(),
+            2 => match Chunks::of(&args[1]) {
+                Some(chunks) => chunked_map(f, chunks).into_value(),
+                None => lazy_map(f, args[1..].to_vec()).into_value(),
+            },
+            _ => lazy_map(f, args[1..].to_vec()).into_value(),
+        }
+    }
+}
+
+fn lazy_map(f: Arc<dyn IFn>, colls: Vec<Arc<Value>>) -> LazySeq {
+    LazySeq::new(move || {
+        let mut firsts = Vec::with_capacity(colls.len());
+        let mut rests = Vec::with_capacity(colls.len());
+        for coll in colls.iter() {
+            match lazy_seq::step(coll)? {
+                Step::Next(first, rest) => {
+                    firsts.push(first);
+                    rests.push(rest);
+                }
+                Step::Done => return Ok(Step::Done),
             }
         }
-        Value::Condition(format!(
-            "Type mismatch; Expected instance of {}, Recieved type {}",
-            TypeTag::IFn,
-            args.len()
-        ))
+        match f.invoke(firsts) {
+            Value::Condition(condition) => Err(Value::Condition(condition)),
+            mapped => Ok(Step::Next(
+                Arc::new(mapped),
+                Arc::new(lazy_map(f, rests).into_value()),
+            )),
+        }
+    })
+}
+
+fn chunked_map(f: Arc<dyn IFn>, chunks: Chunks) -> LazySeq {
+    LazySeq::new(move || {
+        let (chunk, chunks) = match chunks.next_chunk() {
+            Some(next) => next,
+            None => return Ok(Step::Done),
+        };
+        let mut mapped = Vec::with_capacity(chunk.len());
+        for val in chunk {
+            match f.invoke(vec![val]) {
+                Value::Condition(condition) => return Err(Value::Condition(condition)),
+                val => mapped.push(Arc::new(val)),
+            }
+        }
+        lazy_seq::chunk_step(mapped, Arc::new(chunked_map(f, chunks).into_value()))
+    })
+}
+
+#[derive(Debug)]
+struct MapStage {
+    f: Arc<dyn IFn>,
+}
+impl Stage for MapStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        match self.f.invoke(vec![input]) {
+            Value::Condition(condition) => Value::Condition(condition),
+            mapped => rf.invoke(vec![result, Arc::new(mapped)]),
+        }
+    }
+    fn step_all(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, inputs: Vec<Arc<Value>>) -> Value {
+        match self.f.invoke(inputs) {
+            Value::Condition(condition) => Value::Condition(condition),
+            mapped => rf.invoke(vec![result, Arc::new(mapped)]),
+        }
     }
 }
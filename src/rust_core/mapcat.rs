@@ -0,0 +1,48 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::rust_core::cat::{self, step_each};
+use crate::rust_core::MapFn;
+use crate::transducer::{self, Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (mapcat f) (mapcat f coll & colls)
+///
+/// A lazy seq of the elements of each of what (map f coll & colls) gives,  one after
+/// another.  Without a coll,  a transducer passing on the elements of f of each input
+#[derive(Debug, Clone)]
+pub struct MapcatFn {}
+impl ToValue for MapcatFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MapcatFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let f = match ifn::as_ifn(&args[0]) {
+            Some(f) => f,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        if args.len() == 1 {
+            return Transducer::new(move || Box::new(MapcatStage { f: Arc::clone(&f) })).to_value();
+        }
+        transducer::sequence(&cat::cat(), MapFn {}.invoke(args).to_rc_value())
+    }
+}
+
+#[derive(Debug)]
+struct MapcatStage {
+    f: Arc<dyn IFn>,
+}
+impl Stage for MapcatStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        match self.f.invoke(vec![input]) {
+            Value::Condition(condition) => Value::Condition(condition),
+            mapped => step_each(rf, result, &Arc::new(mapped)),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::ifn::IFn;
+use crate::persistent_vector::PersistentVector;
+use crate::rust_core::into::into;
+use crate::rust_core::MapFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (mapv f coll & colls)
+///
+/// As map,  but into a vector,  and so not lazy
+#[derive(Debug, Clone)]
+pub struct MapvFn {}
+impl ToValue for MapvFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MapvFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        into(
+            &PersistentVector::empty().to_rc_value(),
+            &Arc::new(MapFn {}.invoke(args)),
+        )
+    }
+}
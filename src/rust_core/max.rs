@@ -0,0 +1,67 @@
+use crate::ifn::IFn;
+use crate::rust_core::compare::extreme_number;
+use crate::value::{ToValue, Value};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// (max x & more)
+///
+/// The greatest of the numbers;  an f64 if any of them is one
+#[derive(Debug, Clone)]
+pub struct MaxFn {}
+impl ToValue for MaxFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MaxFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        extreme_number(&args, Ordering::Greater)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod max_tests {
+        use crate::ifn::IFn;
+        use crate::rust_core::{MaxFn, MinFn};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn args(vals: &[Value]) -> Vec<Arc<Value>> {
+            vals.iter().map(|val| val.to_rc_value()).collect()
+        }
+
+        #[test]
+        fn max_and_min_of_ints_stay_ints() {
+            let ints = args(&[Value::I32(3), Value::I32(-1), Value::I32(2)]);
+            assert_eq!(Value::I32(3), MaxFn {}.invoke(ints.clone()));
+            assert_eq!(Value::I32(-1), MinFn {}.invoke(ints));
+        }
+
+        #[test]
+        fn any_f64_makes_an_f64() {
+            let mixed = args(&[Value::I32(3), Value::F64(1.5)]);
+            assert_eq!(Value::F64(3.0), MaxFn {}.invoke(mixed.clone()));
+            assert_eq!(Value::F64(1.5), MinFn {}.invoke(mixed));
+        }
+
+        #[test]
+        fn nan_wins() {
+            let max = MaxFn {}.invoke(args(&[Value::I32(3), Value::F64(f64::NAN)]));
+            match max {
+                Value::F64(f) => assert!(f.is_nan()),
+                other => panic!("expected NaN, got {}", other),
+            }
+        }
+
+        #[test]
+        fn non_numbers_are_a_condition() {
+            let min = MinFn {}.invoke(args(&[Value::I32(3), Value::Nil]));
+            match min {
+                Value::Condition(_) => {}
+                other => panic!("expected a condition, got {}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// (memoize f)
+///
+/// A fn calling f,  but only the first time it's given each list of args;  after that,
+/// returning what f returned then.  Errors aren't remembered,  so the next call tries again
+#[derive(Debug, Clone)]
+pub struct MemoizeFn {}
+impl ToValue for MemoizeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MemoizeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        if !ifn::is_callable(&args[0]) {
+            return error_message::type_mismatch(TypeTag::IFn, &args[0]);
+        }
+        Value::ifn(Memoized {
+            f: Arc::clone(&args[0]),
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+}
+
+// What f returned,  by the args it was given
+type Cache = HashMap<Vec<Arc<Value>>, Arc<Value>>;
+
+#[derive(Debug, Clone)]
+struct Memoized {
+    f: Arc<Value>,
+    cache: Arc<Mutex<Cache>>,
+}
+impl IFn for Memoized {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if let Some(val) = self.cache.lock().unwrap().get(&args) {
+            return val.to_value();
+        }
+        // Not holding the lock while f runs,  as f may well call itself through its var
+        match ifn::call(&self.f, args.clone()) {
+            condition @ Value::Condition(_) => condition,
+            val => {
+                self.cache.lock().unwrap().insert(args, val.to_rc_value());
+                val
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod memoize_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn f_is_called_once_per_args() {
+            let result = Repl::default().eval_all(&[
+                "(def calls (atom 0))",
+                "(def slow-inc (memoize (fn [x] (swap! calls + 1) (inc x))))",
+                "[(slow-inc 1) (slow-inc 1) (slow-inc 2) @calls]",
+            ]);
+            assert_eq!("[2 2 3 2]", result.to_string());
+        }
+
+        #[test]
+        fn memoized_fns_can_recur_through_their_var() {
+            let result = Repl::default().eval_all(&[
+                "(def fib (memoize (fn [n] (if (< n 2) n (+ (fib (- n 1)) (fib (- n 2)))))))",
+                "(fib 60)",
+            ]);
+            assert_eq!("1548008755920", result.to_string());
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::meta;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (meta obj)
+///
+/// Returns the metadata of obj,  or nil if it has none
+#[derive(Debug, Clone)]
+pub struct MetaFn {}
+impl ToValue for MetaFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MetaFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        if let Value::Var(var) = &*args[0] {
+            return var.meta();
+        }
+        match meta::meta(&args[0]) {
+            Some(meta) => meta.to_value(),
+            None => Value::Nil,
+        }
+    }
+}
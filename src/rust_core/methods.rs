@@ -0,0 +1,27 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::multi_fn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (methods multifn)
+///
+/// A map of each dispatch value multifn has a method for,  to that method
+#[derive(Debug, Clone)]
+pub struct MethodsFn {}
+impl ToValue for MethodsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MethodsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match multi_fn::from_value(&args[0]) {
+            Ok(multi) => multi.methods_value(),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::ifn::IFn;
+use crate::rust_core::compare::extreme_number;
+use crate::value::{ToValue, Value};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// (min x & more)
+///
+/// The least of the numbers;  an f64 if any of them is one
+#[derive(Debug, Clone)]
+pub struct MinFn {}
+impl ToValue for MinFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for MinFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        extreme_number(&args, Ordering::Less)
+    }
+}
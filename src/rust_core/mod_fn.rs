@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (mod num div)
+///
+/// num modulo div;  which,  unlike rem,  has div's sign
+#[derive(Debug, Clone)]
+pub struct ModFn {}
+impl ToValue for ModFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ModFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        numbers::modulo(&args[0], &args[1])
+    }
+}
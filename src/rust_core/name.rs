@@ -0,0 +1,29 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (name x)
+///
+/// The name of x,  a keyword or symbol,  without its namespace;  a string's its own
+#[derive(Debug, Clone)]
+pub struct NameFn {}
+impl ToValue for NameFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NameFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::String(_) => (*args[0]).clone(),
+            Value::Symbol(sym) => Value::String(sym.name.as_str().into()),
+            Value::Keyword(keyword) => Value::String(keyword.sym.name.as_str().into()),
+            _ => error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (namespace x)
+///
+/// The namespace of x,  a keyword or symbol,  as a string;  nil if it hasn't one
+#[derive(Debug, Clone)]
+pub struct NamespaceFn {}
+impl ToValue for NamespaceFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NamespaceFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Symbol(sym) => namespace(sym),
+            Value::Keyword(keyword) => namespace(&keyword.sym),
+            _ => error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        }
+    }
+}
+
+fn namespace(sym: &Symbol) -> Value {
+    match sym.has_ns() {
+        true => Value::String(sym.ns.as_str().into()),
+        false => Value::Nil,
+    }
+}
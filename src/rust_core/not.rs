@@ -0,0 +1,57 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (not x)
+///
+/// Whether x is falsey;  true if it is nil or false, and false otherwise
+#[derive(Debug, Clone)]
+pub struct NotFn {}
+impl ToValue for NotFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NotFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Boolean(!args[0].is_truthy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod not_tests {
+        use crate::ifn::IFn;
+        use crate::rust_core::{BooleanFn, FalseFn, NotFn, TrueFn};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        #[test]
+        fn only_nil_and_false_are_falsey() {
+            for falsey in [Value::Nil, Value::Boolean(false)] {
+                let args = vec![Arc::new(falsey)];
+                assert_eq!(Value::Boolean(true), NotFn {}.invoke(args.clone()));
+                assert_eq!(Value::Boolean(false), BooleanFn {}.invoke(args));
+            }
+            for truthy in [Value::I32(0), "".to_value(), Value::Boolean(true)] {
+                let args = vec![Arc::new(truthy)];
+                assert_eq!(Value::Boolean(false), NotFn {}.invoke(args.clone()));
+                assert_eq!(Value::Boolean(true), BooleanFn {}.invoke(args));
+            }
+        }
+
+        #[test]
+        fn true_and_false_are_only_the_booleans() {
+            let is_true = |val: Value| TrueFn {}.invoke(vec![Arc::new(val)]);
+            let is_false = |val: Value| FalseFn {}.invoke(vec![Arc::new(val)]);
+            assert_eq!(Value::Boolean(true), is_true(Value::Boolean(true)));
+            assert_eq!(Value::Boolean(false), is_true(Value::I32(1)));
+            assert_eq!(Value::Boolean(true), is_false(Value::Boolean(false)));
+            assert_eq!(Value::Boolean(false), is_false(Value::Nil));
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (not= x & more)
+///
+/// (not (= x & more))
+#[derive(Debug, Clone)]
+pub struct NotEqualsFn {}
+impl ToValue for NotEqualsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NotEqualsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        Value::Boolean(!args.windows(2).all(|pair| pair[0] == pair[1]))
+    }
+}
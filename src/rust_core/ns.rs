@@ -1,40 +1,112 @@
-use crate::environment::Environment;
+use crate::error_message;
 use crate::ifn::IFn;
+use crate::persistent_list::{PersistentList, ToPersistentList};
+use crate::rust_core::defprotocol_macro::quote;
+use crate::symbol::Symbol;
 use crate::type_tag::TypeTag;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
-
-use crate::error_message;
+use std::sync::Arc;
 
+/// (ns name docstring? attr-map? references*)
+///
+/// Makes the namespace named name the current one,  creating it if there isn't one,  with
+/// what each reference asks for;  (:require libs),  (:use libs) and (:load paths) as require,
+/// use and load,  and (:refer-clojure filters) to refer only some of clojure.core.  The
+/// JVM's (:import classes) and (:gen-class) do nothing here
+///
+/// (ns app.core (:require [app.util :as util])) expands to
+///
+/// (do (clojure.core/in-ns 'app.core) (clojure.core/require '[app.util :as util]) nil)
 #[derive(Debug, Clone)]
-pub struct NsMacro {
-    enclosing_environment: Rc<Environment>,
-}
-impl NsMacro {
-    pub fn new(enclosing_environment: Rc<Environment>) -> NsMacro {
-        NsMacro {
-            enclosing_environment,
-        }
-    }
-}
+pub struct NsMacro {}
 impl ToValue for NsMacro {
     fn to_value(&self) -> Value {
-        Value::Macro(Rc::new(self.clone()))
+        Value::macro_(self.clone())
     }
 }
 impl IFn for NsMacro {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        if args.len() != 1 {
-            return error_message::wrong_arg_count(1, args.len());
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (name, references) = match args.split_first() {
+            Some((name, references)) => (name, references),
+            None => return error_message::zero_arg_count(0),
+        };
+        if !matches!(**name, Value::Symbol(_)) {
+            return error_message::type_mismatch(TypeTag::Symbol, name);
         }
-
-        let namespace = args.get(0).unwrap();
-        match &**namespace {
-            Value::Symbol(sym) => {
-                self.enclosing_environment.change_namespace(sym.clone());
-                Value::Nil
-            }
-            _ => error_message::type_mismatch(TypeTag::Symbol, &**namespace),
+        let core = |name: &str| Symbol::intern_with_ns("clojure.core", name).to_rc_value();
+        let mut expansion = vec![
+            Symbol::intern("do").to_rc_value(),
+            vec![core("in-ns"), quote(Arc::clone(name))]
+                .into_list()
+                .to_rc_value(),
+        ];
+        for reference in references {
+            let items = match &**reference {
+                // The docstring and attr-map
+                Value::String(_) | Value::PersistentListMap(_) => continue,
+                Value::PersistentList(plist) => PersistentList::iter(plist).collect::<Vec<_>>(),
+                _ => return error_message::type_mismatch(TypeTag::PersistentList, reference),
+            };
+            let (kind, args) = match items.split_first() {
+                Some((kind, args)) => (kind, args),
+                None => continue,
+            };
+            let kind_name = match &**kind {
+                Value::Keyword(kind) => kind.sym.name.as_str(),
+                _ => return error_message::type_mismatch(TypeTag::Keyword, kind),
+            };
+            let call = match kind_name {
+                "require" | "use" => {
+                    let mut call = vec![core(kind_name)];
+                    call.extend(args.iter().map(|arg| quote(Arc::clone(arg))));
+                    call
+                }
+                "refer-clojure" | "load" => {
+                    let mut call = vec![core(kind_name)];
+                    call.extend_from_slice(args);
+                    call
+                }
+                "import" | "gen-class" => continue,
+                _ => return Value::Condition(format!("Unsupported ns reference {}", kind).into()),
+            };
+            expansion.push(call.into_list().to_rc_value());
         }
+        expansion.push(Arc::new(Value::Nil));
+        expansion.into_list().to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn ns_sets_up_what_its_namespace_refers_to() {
+        let result = Repl::default().eval_all(&[
+            "(ns lib.shapes \"Shapes\" {:author :me})",
+            "(def circle :circle)",
+            "(def square :square)",
+            "(ns app.core (:require [lib.shapes :as shapes :refer [circle]] [clojure.string :as string]) (:refer-clojure :exclude [dec]) (:import java.util.Date) (:gen-class))",
+            "(def dec :shadowed)",
+            "[circle shapes/square (string/upper-case \"a\") (inc 1) dec (ns-name (the-ns 'app.core))]",
+        ]);
+        assert_eq!(
+            "[:circle :square \"A\" 2 :shadowed app.core]",
+            result.to_string_explicit()
+        );
+    }
+
+    #[test]
+    fn ns_prefix_lists_and_use() {
+        let result = Repl::default().eval_all(&[
+            "(ns lib.a)",
+            "(def a 1)",
+            "(ns lib.b)",
+            "(def b 2)",
+            "(def c 3)",
+            "(ns app.core (:require (lib [a :as a])) (:use [lib.b :only [b]]))",
+            "[a/a b (try c (catch Exception e :unresolved))]",
+        ]);
+        assert_eq!("[1 2 :unresolved]", result.to_string());
     }
 }
@@ -0,0 +1,50 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::maps::MapEntry;
+use crate::persistent_list_map::PersistentListMap;
+use crate::rust_core::the_ns::the_ns;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ns-aliases ns)
+///
+/// Returns a map of the aliases in the namespace to the namespaces they're other names for
+#[derive(Debug, Clone)]
+pub struct NsAliasesFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl NsAliasesFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> NsAliasesFn {
+        NsAliasesFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for NsAliasesFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NsAliasesFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let environment = &self.enclosing_environment;
+        match the_ns(environment, &args[0]) {
+            Ok(namespace) => namespace
+                .aliases()
+                .into_iter()
+                .filter_map(|(alias, name)| {
+                    Some(MapEntry {
+                        key: alias.to_rc_value(),
+                        val: Arc::new(Value::Namespace(environment.find_namespace(&name)?)),
+                    })
+                })
+                .collect::<PersistentListMap>()
+                .to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::ns_publics::ns_map;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ns-interns ns)
+///
+/// Returns a map of the symbols interned in the namespace to their values;  private or not
+#[derive(Debug, Clone)]
+pub struct NsInternsFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl NsInternsFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> NsInternsFn {
+        NsInternsFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for NsInternsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NsInternsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        ns_map(&self.enclosing_environment, &args[0], true)
+    }
+}
@@ -0,0 +1,73 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::maps::MapEntry;
+use crate::persistent_list_map::PersistentListMap;
+use crate::rust_core::ns_refers::refers;
+use crate::rust_core::the_ns::the_ns;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ns-map ns)
+///
+/// Returns a map of every symbol that resolves in the namespace to its value;  those\n/// interned there,  and those referred from other namespaces
+#[derive(Debug, Clone)]
+pub struct NsMapFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl NsMapFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> NsMapFn {
+        NsMapFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for NsMapFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NsMapFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let environment = &self.enclosing_environment;
+        match the_ns(environment, &args[0]) {
+            Ok(namespace) => {
+                let mut mappings = refers(environment, &namespace);
+                mappings.extend(namespace.mappings());
+                mappings
+                    .into_iter()
+                    .map(|(key, val)| MapEntry {
+                        key: key.to_rc_value(),
+                        val,
+                    })
+                    .collect::<PersistentListMap>()
+                    .to_value()
+            }
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn ns_map_is_what_resolves_in_a_namespace() {
+        let result = Repl::default().eval_all(&[
+            "(ns lib.util)",
+            "(def helper 1)",
+            "(ns app.core)",
+            "(refer-clojure :exclude [dec])",
+            "(refer 'lib.util)",
+            "(def own 2)",
+            "(def mappings (ns-map 'app.core))",
+            "(def refers (ns-refers 'app.core))",
+            "[(get mappings 'own) (get mappings 'helper) (contains? mappings 'inc) (contains? mappings 'dec) (contains? refers 'own) (get refers 'helper)]",
+        ]);
+        assert_eq!("[2 1 true false false 1]", result.to_string());
+    }
+}
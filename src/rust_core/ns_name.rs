@@ -0,0 +1,37 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::the_ns::the_ns;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ns-name ns)
+///
+/// Returns the name of the namespace, as a symbol
+#[derive(Debug, Clone)]
+pub struct NsNameFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl NsNameFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> NsNameFn {
+        NsNameFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for NsNameFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NsNameFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match the_ns(&self.enclosing_environment, &args[0]) {
+            Ok(namespace) => namespace.name.to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
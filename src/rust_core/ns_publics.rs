@@ -0,0 +1,54 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::maps::MapEntry;
+use crate::persistent_list_map::PersistentListMap;
+use crate::rust_core::the_ns::the_ns;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ns-publics ns)
+///
+/// Returns a map of the symbols defined in the namespace to their values;  save for those
+/// defined ^:private
+#[derive(Debug, Clone)]
+pub struct NsPublicsFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl NsPublicsFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> NsPublicsFn {
+        NsPublicsFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for NsPublicsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NsPublicsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        ns_map(&self.enclosing_environment, &args[0], false)
+    }
+}
+
+/// The symbols defined in ns,  mapped to their values;  shared with ns-interns
+pub(crate) fn ns_map(environment: &Arc<Environment>, ns: &Arc<Value>, private: bool) -> Value {
+    match the_ns(environment, ns) {
+        Ok(namespace) => namespace
+            .mappings()
+            .into_iter()
+            .filter(|(sym, _)| private || !namespace.is_private(sym))
+            .map(|(sym, val)| MapEntry {
+                key: sym.to_rc_value(),
+                val,
+            })
+            .collect::<PersistentListMap>()
+            .to_value(),
+        Err(condition) => condition,
+    }
+}
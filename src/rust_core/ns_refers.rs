@@ -0,0 +1,74 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::maps::MapEntry;
+use crate::namespace::Namespace;
+use crate::persistent_list_map::PersistentListMap;
+use crate::rust_core::the_ns::the_ns;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ns-refers ns)
+///
+/// Returns a map of the symbols referred into the namespace from others to their values;\n/// those of clojure.core too
+#[derive(Debug, Clone)]
+pub struct NsRefersFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl NsRefersFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> NsRefersFn {
+        NsRefersFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for NsRefersFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NsRefersFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let environment = &self.enclosing_environment;
+        match the_ns(environment, &args[0]) {
+            Ok(namespace) => refers(environment, &namespace)
+                .into_iter()
+                .map(|(key, val)| MapEntry {
+                    key: key.to_rc_value(),
+                    val,
+                })
+                .collect::<PersistentListMap>()
+                .to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// The names namespace resolves to another namespace's,  with what they resolve to;
+/// shared with ns-map
+pub(crate) fn refers(
+    environment: &Environment,
+    namespace: &Namespace,
+) -> Vec<(Symbol, Arc<Value>)> {
+    let mut refers = namespace
+        .refers()
+        .into_iter()
+        .map(|(sym, var)| (sym, environment.get(&var)))
+        .collect::<Vec<(Symbol, Arc<Value>)>>();
+    if let Some(core) = environment.find_namespace(&Symbol::intern("clojure.core")) {
+        for (sym, val) in core.mappings() {
+            let referred = refers.iter().any(|(referred, _)| *referred == sym);
+            if namespace.refers_core(&sym) && !core.is_private(&sym) && !referred {
+                refers.push((sym, val));
+            }
+        }
+    }
+    refers
+        .into_iter()
+        .filter(|(sym, _)| matches!(*namespace.get(sym), Value::Condition(_)))
+        .collect()
+}
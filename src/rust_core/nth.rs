@@ -1,55 +1,81 @@
 use crate::ifn::IFn;
+use crate::lazy_seq;
 use crate::type_tag::TypeTag;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error_message;
 use crate::persistent_list::PersistentList::{Cons, Empty};
 use crate::persistent_list::ToPersistentListIter;
-use crate::persistent_vector::PersistentVector;
 
-/// (nth coll index)
+/// (nth coll index) (nth coll index not-found)
 ///
+/// Past the end of coll,  not-found if it's given;  nil's taken as empty
 #[derive(Debug, Clone)]
 pub struct NthFn {}
 impl ToValue for NthFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for NthFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         // @TODO generalize arity exceptions, and other exceptions
-        if args.len() != 2 {
+        if args.len() != 2 && args.len() != 3 {
             return error_message::wrong_varg_count(&[2, 3], args.len());
         }
-        // @TODO change iteration to work with Value references, or even change invoke to work on Rc<..>
+        let out_of_bounds = |ind, count| match args.get(2) {
+            Some(not_found) => not_found.to_value(),
+            None => error_message::index_out_of_bounds(ind, count),
+        };
+        // @TODO change iteration to work with Value references, or even change invoke to work on Arc<..>
         //       as we do everything else; surely we don't want to clone just to read from a collection
         if let Value::I32(ind) = **args.get(1).unwrap() {
             if ind < 0 {
-                return error_message::index_cannot_be_negative(ind as usize);
+                return match args.get(2) {
+                    Some(not_found) => not_found.to_value(),
+                    None => error_message::index_cannot_be_negative(ind as usize),
+                };
             }
             let ind = ind as usize;
 
             match &**args.get(0).unwrap() {
-                Value::PersistentList(Cons(head, tail, count)) => {
-                    let count = *count as usize;
-                    if ind >= count {
-                        error_message::index_out_of_bounds(ind, count)
-                    } else if ind == 0 {
-                        head.to_value()
-                    } else {
-                        tail.iter().nth(ind - 1).unwrap().to_value()
+                Value::PersistentList(plist) => match &**plist {
+                    Cons(head, tail, count) => {
+                        let count = *count as usize;
+                        if ind >= count {
+                            out_of_bounds(ind, count)
+                        } else if ind == 0 {
+                            head.to_value()
+                        } else {
+                            tail.iter().nth(ind - 1).unwrap().to_value()
+                        }
                     }
-                }
-                Value::PersistentList(Empty) => error_message::index_out_of_bounds(ind, 0),
-                Value::PersistentVector(PersistentVector { vals }) => {
-                    if ind >= vals.len() {
-                        error_message::index_out_of_bounds(ind, vals.len())
-                    } else {
-                        vals.get(ind).unwrap().to_value()
+                    Empty => out_of_bounds(ind, 0),
+                },
+                Value::PersistentVector(pvector) => match pvector.nth(ind) {
+                    Some(val) => val.to_value(),
+                    None => out_of_bounds(ind, pvector.len()),
+                },
+                // Realized only as far as the element asked for
+                Value::LazySeq(_) => {
+                    let mut count = 0;
+                    for val in lazy_seq::iter(Arc::clone(&args[0])) {
+                        if count == ind {
+                            return val.to_value();
+                        }
+                        count += 1;
                     }
+                    out_of_bounds(ind, count)
                 }
+                // Indexed by character,  not byte
+                Value::String(s) => match s.chars().nth(ind) {
+                    Some(c) => Value::Char(c),
+                    None => out_of_bounds(ind, s.chars().count()),
+                },
+                Value::Nil => args
+                    .get(2)
+                    .map_or(Value::Nil, |not_found| not_found.to_value()),
                 _ => error_message::type_mismatch(TypeTag::ISeq, &**args.get(0).unwrap()),
             }
         } else {
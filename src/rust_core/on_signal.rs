@@ -0,0 +1,57 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::signal;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (on-signal sig f)
+///
+/// Calls f,  with no arguments,  whenever the process is sent sig;  one of :int,  :term,
+/// :hup,  :usr1 and :usr2.  It's called on this thread,  between calls,  so a script can
+/// shut down gracefully,  or save where it's got to,  rather than being ended.  With f
+/// nil,  sig does what it did before any f was given.  Returns the f given before,  or nil
+#[derive(Debug, Clone)]
+pub struct OnSignalFn {}
+impl ToValue for OnSignalFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for OnSignalFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let signum = match &*args[0] {
+            Value::Keyword(sig) if !sig.sym.has_ns() => signal::number(&sig.sym.name),
+            _ => None,
+        };
+        let signum = match signum {
+            Some(signum) => signum,
+            None => {
+                let names = signal::SIGNALS
+                    .iter()
+                    .map(|(name, _)| format!(":{}", name))
+                    .collect::<Vec<String>>();
+                return Value::Condition(
+                    format!(
+                        "Cannot handle signal {};  {} can be",
+                        args[0].to_string_explicit(),
+                        names.join(",  ")
+                    )
+                    .into(),
+                );
+            }
+        };
+        let f = match &*args[1] {
+            Value::Nil => None,
+            f if ifn::is_callable(f) => Some(Arc::clone(&args[1])),
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        };
+        match signal::set_handler(signum, f) {
+            Some(before) => (*before).clone(),
+            None => Value::Nil,
+        }
+    }
+}
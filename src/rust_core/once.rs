@@ -0,0 +1,77 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{self, LazySeq, Step};
+use crate::value::{ToValue, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// (once coll)
+///
+/// coll,  as a seq that can be walked just the once;  going back to any of it more than an
+/// element behind the furthest it's been walked fails,  rather than giving it again.  For
+/// making sure a pipeline over a line-seq,  say,  reads it in a single pass,  and holds on to
+/// none of it behind it:
+///
+///   (partition-by count (once (line-seq rdr)))
+#[derive(Debug, Clone)]
+pub struct OnceFn {}
+impl ToValue for OnceFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for OnceFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        lazy_once(Arc::clone(&args[0]), 0, Arc::new(AtomicUsize::new(0))).into_value()
+    }
+}
+
+// coll from its index'th element on;  walked,  the index of the furthest element stepped to
+fn lazy_once(coll: Arc<Value>, index: usize, walked: Arc<AtomicUsize>) -> LazySeq {
+    let furthest = Arc::clone(&walked);
+    LazySeq::new(move || {
+        Ok(match lazy_seq::step(&coll)? {
+            Step::Next(first, rest) => Step::Next(
+                first,
+                Arc::new(lazy_once(rest, index + 1, walked).into_value()),
+            ),
+            Step::Done => Step::Done,
+        })
+    })
+    // An element behind is still in reach;  next steps to what's after the element it's
+    // given,  before the walk moves on to it
+    .with_guard(move || {
+        let furthest = furthest.fetch_max(index, Ordering::SeqCst).max(index);
+        if index + 1 < furthest {
+            return Err(Value::Condition(
+                format!(
+                    "Seq walked twice;  element {} was gone back to after element {}",
+                    index, furthest
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn a_once_seq_cannot_be_walked_again() {
+        let result = Repl::default().eval_all(&[
+            "(def xs (once (map inc [1 2 3 4])))",
+            "(def walked (reduce + xs))",
+            "[walked (ex-message (try (first xs) (catch Exception e e)))]",
+        ]);
+        assert_eq!(
+            "[14 \"Seq walked twice;  element 0 was gone back to after element 4\"]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use crate::ifn::IFn;
+use crate::rust_core::and_macro::short_circuit;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (or x y ..)
+///
+/// Evaluates each form in turn,  stopping at the first truthy one.  Returns the value of
+/// the form it stopped at,  or of the last form,  or nil if there are none
+///
+/// (or x y z) expands to
+///
+/// (let [or# x] (if or# or# (or y z)))
+#[derive(Debug, Clone)]
+pub struct OrMacro {}
+impl ToValue for OrMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for OrMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match args.split_first() {
+            Some((first, rest)) => short_circuit("or", first, rest, false),
+            None => Value::Nil,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::hierarchy;
+use crate::ifn::IFn;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (parents tag) or (parents h tag)
+///
+/// Returns the tags tag directly derives from, or nil
+#[derive(Debug, Clone)]
+pub struct ParentsFn {}
+impl ToValue for ParentsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ParentsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (hierarchy, args) = match hierarchy::hierarchy_and_args(&args, 1) {
+            Ok(split) => split,
+            Err(condition) => return condition,
+        };
+        let related = hierarchy.parents(&args[0]);
+        if related.is_empty() {
+            Value::Nil
+        } else {
+            related
+                .into_iter()
+                .collect::<PersistentHashSet>()
+                .to_value()
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (partial f & args)
+///
+/// A fn calling f on args,  followed by whatever args it's called on itself;
+/// ((partial + 1 2) 3) is (+ 1 2 3)
+#[derive(Debug, Clone)]
+pub struct PartialFn {}
+impl ToValue for PartialFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PartialFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match args.split_first() {
+            Some((f, _)) if !ifn::is_callable(f) => error_message::type_mismatch(TypeTag::IFn, f),
+            Some((f, args)) => Value::ifn(Partial {
+                f: Arc::clone(f),
+                args: args.to_vec(),
+            }),
+            None => error_message::zero_arg_count(args.len()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Partial {
+    f: Arc<Value>,
+    args: Vec<Arc<Value>>,
+}
+impl IFn for Partial {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let mut f_args = self.args.clone();
+        f_args.extend(args);
+        ifn::call(&self.f, f_args)
+    }
+}
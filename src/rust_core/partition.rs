@@ -0,0 +1,117 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{self, LazySeq, Step};
+use crate::persistent_list::ToPersistentList;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (partition n coll) (partition n step coll) (partition n step pad coll)
+///
+/// A lazy seq of lists of n elements of coll each,  starting step (default n) elements
+/// apart;  so a step less than n makes overlapping windows.  What's left at the end that
+/// isn't a whole partition is dropped;  unless pad is given,  in which case its elements
+/// fill it out to n,  as far as there are enough of them
+#[derive(Debug, Clone)]
+pub struct PartitionFn {}
+impl ToValue for PartitionFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PartitionFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (n, step, pad, coll) = match &args[..] {
+            [n, coll] => (n, n, None, coll),
+            [n, step, coll] => (n, step, None, coll),
+            [n, step, pad, coll] => (n, step, Some(Arc::clone(pad)), coll),
+            _ => return error_message::wrong_varg_count(&[2, 3, 4], args.len()),
+        };
+        match (&**n, &**step) {
+            (Value::I32(n), Value::I32(step)) => {
+                lazy_partition(*n, *step, pad, Arc::clone(coll), 0).into_value()
+            }
+            (Value::I32(_), _) => error_message::type_mismatch(TypeTag::Integer, step),
+            _ => error_message::type_mismatch(TypeTag::Integer, n),
+        }
+    }
+}
+
+// The partitions of coll,  once its first skip elements are dropped;  they're dropped when
+// it's realized,  so the step past one partition to the next is lazy too
+fn lazy_partition(
+    n: i32,
+    step: i32,
+    pad: Option<Arc<Value>>,
+    coll: Arc<Value>,
+    skip: i32,
+) -> LazySeq {
+    LazySeq::new(move || {
+        let mut coll = coll;
+        for _ in 0..skip {
+            coll = match lazy_seq::step(&coll)? {
+                Step::Next(_, rest) => rest,
+                Step::Done => return Ok(Step::Done),
+            };
+        }
+        if let Step::Done = lazy_seq::step(&coll)? {
+            return Ok(Step::Done);
+        }
+        let mut partition = take(n, Arc::clone(&coll), vec![])?;
+        if partition.len() as i32 == n {
+            let rest = lazy_partition(n, step, pad, coll, step.max(0)).into_value();
+            return Ok(Step::Next(
+                partition.into_list().to_rc_value(),
+                Arc::new(rest),
+            ));
+        }
+        // The last of them;  padded out,  if we can
+        match pad {
+            Some(pad) => {
+                partition = take(n, pad, partition)?;
+                Ok(Step::Next(
+                    partition.into_list().to_rc_value(),
+                    Arc::new(Value::Nil),
+                ))
+            }
+            None => Ok(Step::Done),
+        }
+    })
+}
+
+// taken,  followed by as many of coll's elements as there are,  up to n in all
+fn take(n: i32, coll: Arc<Value>, mut taken: Vec<Arc<Value>>) -> Result<Vec<Arc<Value>>, Value> {
+    let mut coll = coll;
+    while (taken.len() as i32) < n {
+        match lazy_seq::step(&coll)? {
+            Step::Next(first, rest) => {
+                taken.push(first);
+                coll = rest;
+            }
+            Step::Done => break,
+        }
+    }
+    Ok(taken)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn partitions_are_step_apart_and_padded_at_the_end() {
+        let result = Repl::default().eval_all(&[
+            "[(partition 2 (range 7)) (partition 3 1 [1 2 3 4]) (partition 2 3 (range 8)) (partition 3 3 [:a] (range 5)) (partition 4 4 [] [1 2 3]) (partition 2 [])]",
+        ]);
+        assert_eq!(
+            "[((0 1) (2 3) (4 5)) ((1 2 3) (2 3 4)) ((0 1) (3 4) (6 7)) ((0 1 2) (3 4 :a)) ((1 2 3)) ()]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn partitions_of_an_infinite_seq_are_lazy() {
+        let result = Repl::default().eval_all(&["(take 3 (partition 2 1 (iterate inc 0)))"]);
+        assert_eq!("((0 1) (1 2) (2 3))", result.to_string());
+    }
+}
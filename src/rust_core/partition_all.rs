@@ -0,0 +1,79 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_vector::PersistentVector;
+use crate::transducer::{self, Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+/// (partition-all n) (partition-all n coll)
+///
+/// A lazy seq of vectors of n elements of coll at a time;  the last with fewer,  if that's
+/// all that's left,  unlike partition's.  Without coll,  a transducer passing on such vectors
+/// of its inputs
+#[derive(Debug, Clone)]
+pub struct PartitionAllFn {}
+impl ToValue for PartitionAllFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PartitionAllFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let n = match &*args[0] {
+            Value::I32(n) if *n > 0 => *n as usize,
+            Value::I32(n) => {
+                return Value::Condition(format!("Can't partition into {}s", n).into())
+            }
+            _ => return error_message::type_mismatch(TypeTag::Integer, &args[0]),
+        };
+        let xform = Transducer::new(move || {
+            Box::new(PartitionAllStage {
+                n,
+                partition: Mutex::new(PersistentVector::empty()),
+            })
+        });
+        match args.get(1) {
+            Some(coll) => transducer::sequence(&xform, Arc::clone(coll)),
+            None => xform.to_value(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PartitionAllStage {
+    n: usize,
+    // The inputs so far that haven't been passed on in a full partition
+    partition: Mutex<PersistentVector>,
+}
+impl Stage for PartitionAllStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        let full = {
+            let mut partition = self.partition.lock().unwrap();
+            partition.conj_in_place(input);
+            match partition.len() == self.n {
+                true => Some(mem::take(&mut *partition)),
+                false => None,
+            }
+        };
+        match full {
+            Some(full) => rf.invoke(vec![result, full.to_rc_value()]),
+            None => result.to_value(),
+        }
+    }
+    fn complete(&self, rf: &Arc<dyn IFn>, result: Arc<Value>) -> Value {
+        let rest = mem::take(&mut *self.partition.lock().unwrap());
+        let result = match rest.is_empty() {
+            true => result,
+            false => match transducer::unreduced(rf.invoke(vec![result, rest.to_rc_value()])) {
+                Value::Condition(condition) => return Value::Condition(condition),
+                result => Arc::new(result),
+            },
+        };
+        rf.invoke(vec![result])
+    }
+}
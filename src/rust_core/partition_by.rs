@@ -0,0 +1,135 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq::{self, LazySeq, Step};
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::transducer::{self, Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+/// (partition-by f) (partition-by f coll)
+///
+/// A lazy seq of the runs in coll;  each as many elements in a row as f gives the same value
+/// for.  A run's read when it's reached,  all of it,  and the element after it too;  and
+/// coll is walked the once,  so it can be a line-seq of more than can be held at once.
+/// Without coll,  a transducer passing on such runs of its inputs,  as vectors
+#[derive(Debug, Clone)]
+pub struct PartitionByFn {}
+impl ToValue for PartitionByFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PartitionByFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        if !ifn::is_callable(&args[0]) {
+            return error_message::type_mismatch(TypeTag::IFn, &args[0]);
+        }
+        let f = Arc::clone(&args[0]);
+        match args.get(1) {
+            Some(coll) => lazy_runs(f, Arc::clone(coll)).into_value(),
+            None => Transducer::new(move || {
+                Box::new(PartitionByStage {
+                    f: Arc::clone(&f),
+                    run: Mutex::new((Value::Nil, PersistentVector::empty())),
+                })
+            })
+            .to_value(),
+        }
+    }
+}
+
+fn lazy_runs(f: Arc<Value>, coll: Arc<Value>) -> LazySeq {
+    LazySeq::new(move || {
+        let (first, mut rest) = match lazy_seq::step(&coll)? {
+            Step::Next(first, rest) => (first, rest),
+            Step::Done => return Ok(Step::Done),
+        };
+        let key = value_of(&f, &first)?;
+        let mut run = vec![first];
+        // rest is left at the first element of the next run
+        while let Step::Next(next, after) = lazy_seq::step(&rest)? {
+            if value_of(&f, &next)? != key {
+                break;
+            }
+            run.push(next);
+            rest = after;
+        }
+        Ok(Step::Next(
+            run.into_list().to_rc_value(),
+            Arc::new(lazy_runs(f, rest).into_value()),
+        ))
+    })
+}
+
+fn value_of(f: &Arc<Value>, x: &Arc<Value>) -> Result<Value, Value> {
+    match ifn::call(f, vec![Arc::clone(x)]) {
+        Value::Condition(condition) => Err(Value::Condition(condition)),
+        val => Ok(val),
+    }
+}
+
+#[derive(Debug)]
+struct PartitionByStage {
+    f: Arc<Value>,
+    // What f gave for the run so far,  and the run
+    run: Mutex<(Value, PersistentVector)>,
+}
+impl Stage for PartitionByStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        let key = match value_of(&self.f, &input) {
+            Ok(key) => key,
+            Err(condition) => return condition,
+        };
+        let ended = {
+            let mut run = self.run.lock().unwrap();
+            let ended = match !run.1.is_empty() && run.0 != key {
+                true => Some(mem::take(&mut run.1)),
+                false => None,
+            };
+            run.0 = key;
+            run.1.conj_in_place(input);
+            ended
+        };
+        match ended {
+            Some(ended) => rf.invoke(vec![result, ended.to_rc_value()]),
+            None => result.to_value(),
+        }
+    }
+    fn complete(&self, rf: &Arc<dyn IFn>, result: Arc<Value>) -> Value {
+        let rest = mem::take(&mut self.run.lock().unwrap().1);
+        let result = match rest.is_empty() {
+            true => result,
+            false => match transducer::unreduced(rf.invoke(vec![result, rest.to_rc_value()])) {
+                Value::Condition(condition) => return Value::Condition(condition),
+                result => Arc::new(result),
+            },
+        };
+        rf.invoke(vec![result])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn a_file_is_read_just_the_once_and_only_as_far_as_needed() {
+        let result = Repl::default().eval_all(&[
+            "(require '[clojure.java.io :as io])",
+            "(def reads (atom 0))",
+            "(defn counted [rdr] (map (fn [line] (swap! reads inc) line) (line-seq rdr)))",
+            "(def from-file (io/with-temp-dir [dir] (let [path (str dir \"/log.txt\")] (do (spit path \"a\na\nbb\ncc\nd\n\") (with-open [rdr (io/reader path)] (let [runs (partition-by count (once (counted rdr))) first-run (first runs) read-for-first @reads] [first-run read-for-first (into [] runs) @reads]))))))",
+            "[from-file (partition-by :k [{:k 1} {:k 1} {:k 2}]) (partition-by odd? []) (ex-message (try (partition-by 1 [1]) (catch Exception e e)))]",
+        ]);
+        assert_eq!(
+            "[[(\"a\" \"a\") 3 [(\"a\" \"a\") (\"bb\" \"cc\") (\"d\")] 5] (({:k 1} {:k 1}) ({:k 2})) () \"Type mismatch; Expected instance of clojure.lang.Function,  Recieved type 1\"]",
+            result.to_string()
+        );
+    }
+}
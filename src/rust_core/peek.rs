@@ -0,0 +1,39 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::PersistentList;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (peek coll)
+///
+/// The element pop would take away;  the last of a vector, the first of a list or queue.
+/// nil when coll is empty
+#[derive(Debug, Clone)]
+pub struct PeekFn {}
+impl ToValue for PeekFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PeekFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::PersistentVector(pvector) => {
+                pvector.peek().map_or(Value::Nil, |val| val.to_value())
+            }
+            Value::PersistentList(plist) => match &**plist {
+                PersistentList::Cons(head, _, _) => head.to_value(),
+                PersistentList::Empty => Value::Nil,
+            },
+            Value::PersistentQueue(pqueue) => {
+                pqueue.peek().map_or(Value::Nil, |val| val.to_value())
+            }
+            Value::Nil => Value::Nil,
+            _ => error_message::type_mismatch(TypeTag::Sequential, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (persistent! tcoll)
+///
+/// The vector or map a transient has built.  The transient can't be used after this
+#[derive(Debug, Clone)]
+pub struct PersistentBangFn {}
+impl ToValue for PersistentBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PersistentBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let persistent = match &*args[0] {
+            Value::TransientVector(tvector) => tvector.persistent().map(|v| v.to_value()),
+            Value::TransientMap(tmap) => tmap.persistent().map(|m| m.to_value()),
+            _ => return error_message::type_mismatch(TypeTag::TransientVector, &args[0]),
+        };
+        persistent.unwrap_or_else(|condition| condition)
+    }
+}
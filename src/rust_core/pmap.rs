@@ -0,0 +1,114 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq::{self, LazySeq, Step};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use crate::worker_pool::{self, Task};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// (pmap f coll & colls)
+///
+/// Like map,  but f is applied on the worker pool;  a few elements ahead of what's been
+/// realized,  so each core has one to work on.  Only worth it when f does enough to
+/// make up for handing it over
+#[derive(Debug, Clone)]
+pub struct PmapFn {}
+impl ToValue for PmapFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PmapFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match ifn::as_ifn(&args[0]) {
+            Some(f) => lazy_pmap(f, args[1..].to_vec(), VecDeque::new()).into_value(),
+            None => error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        }
+    }
+}
+
+// colls is empty once any one of them has run out;  ahead,  f applied to the elements
+// taken from them so far that haven't been realized yet
+fn lazy_pmap(f: Arc<dyn IFn>, colls: Vec<Arc<Value>>, ahead: VecDeque<Arc<Task>>) -> LazySeq {
+    LazySeq::new(move || {
+        let (mut colls, mut ahead) = (colls, ahead);
+        while !colls.is_empty() && ahead.len() < worker_pool::size() + 2 {
+            match step_all(&colls) {
+                Ok(Some((firsts, rests))) => {
+                    let f = Arc::clone(&f);
+                    ahead.push_back(worker_pool::submit(move || f.invoke(firsts)));
+                    colls = rests;
+                }
+                Ok(None) => colls = vec![],
+                Err(condition) if ahead.is_empty() => return Err(condition),
+                // Given once what's ahead of it has been,  as stepping colls again fails again
+                Err(_) => break,
+            }
+        }
+        match ahead.pop_front() {
+            Some(task) => match task.result() {
+                Value::Condition(condition) => Err(Value::Condition(condition)),
+                mapped => Ok(Step::Next(
+                    Arc::new(mapped),
+                    Arc::new(lazy_pmap(f, colls, ahead).into_value()),
+                )),
+            },
+            None => Ok(Step::Done),
+        }
+    })
+}
+
+// The first element of each coll,  and the rest of each
+type Stepped = (Vec<Arc<Value>>, Vec<Arc<Value>>);
+
+// None if any one coll's run out
+fn step_all(colls: &[Arc<Value>]) -> Result<Option<Stepped>, Value> {
+    let mut firsts = Vec::with_capacity(colls.len());
+    let mut rests = Vec::with_capacity(colls.len());
+    for coll in colls.iter() {
+        match lazy_seq::step(coll)? {
+            Step::Next(first, rest) => {
+                firsts.push(first);
+                rests.push(rest);
+            }
+            Step::Done => return Ok(None),
+        }
+    }
+    Ok(Some((firsts, rests)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn pmap_pcalls_and_pvalues_give_what_map_would() {
+        let result = Repl::default().eval_all(&[
+            "(def ^:dynamic *n* 1)",
+            "(def nested (pmap (fn [x] (apply + (pmap inc (range x)))) (range 40)))",
+            "[(pmap + [1 2 3] [10 20]) (take 3 (pmap inc (range))) (pcalls (fn [] 1) (fn [] 2)) (pvalues (+ 1 2) (* 2 3)) (binding [*n* 5] (into [] (pmap (fn [x] (+ x *n*)) [1 2]))) (nth nested 39)]",
+        ]);
+        assert_eq!(
+            "[(11 22) (1 2 3) (1 2) (3 6) [6 7] 780]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn a_failing_element_fails_only_once_those_before_it_are_realized() {
+        let result = Repl::default().eval_all(&[
+            "(def mapped (pmap (fn [x] (if (= x 5) (+ x :a) x)) (range 10)))",
+            "(take 5 mapped)",
+        ]);
+        assert_eq!("(0 1 2 3 4)", result.to_string());
+        let result = Repl::default().eval_all(&[
+            "(def mapped (pmap (fn [x] (if (= x 5) (+ x :a) x)) (range 10)))",
+            "(nth mapped 5)",
+        ]);
+        assert!(result.to_string().starts_with("#Condition[\"Type mismatch"));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::PersistentList;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (pop coll)
+///
+/// coll without the element peek gives;  for a vector, all but the last,  for a list or
+/// queue, all but the first.  An empty queue pops to itself
+#[derive(Debug, Clone)]
+pub struct PopFn {}
+impl ToValue for PopFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PopFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::PersistentVector(pvector) => match pvector.pop() {
+                Some(popped) => popped.to_value(),
+                None => Value::Condition("Can't pop empty vector".into()),
+            },
+            Value::PersistentList(plist) => match &**plist {
+                PersistentList::Cons(_, tail, _) => Value::PersistentList(Arc::clone(tail)),
+                PersistentList::Empty => Value::Condition("Can't pop empty list".into()),
+            },
+            Value::PersistentQueue(pqueue) => pqueue.pop().to_value(),
+            Value::Nil => Value::Nil,
+            _ => error_message::type_mismatch(TypeTag::Sequential, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod pop_tests {
+        use crate::ifn::IFn;
+        use crate::persistent_list::ToPersistentList;
+        use crate::persistent_vector::PersistentVector;
+        use crate::rust_core::{PeekFn, PopFn};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn ints(vals: &[i32]) -> Vec<Arc<Value>> {
+            vals.iter().map(|i| i.to_rc_value()).collect()
+        }
+
+        #[test]
+        fn vectors_pop_from_the_end_and_lists_from_the_front() {
+            let pvector = PersistentVector::from(ints(&[1, 2, 3])).to_rc_value();
+            let plist = ints(&[1, 2, 3]).into_list().to_rc_value();
+            assert_eq!(
+                PersistentVector::from(ints(&[1, 2])).to_value(),
+                PopFn {}.invoke(vec![Arc::clone(&pvector)])
+            );
+            assert_eq!(Value::I32(3), PeekFn {}.invoke(vec![pvector]));
+            assert_eq!(
+                ints(&[2, 3]).into_list().to_value(),
+                PopFn {}.invoke(vec![Arc::clone(&plist)])
+            );
+            assert_eq!(Value::I32(1), PeekFn {}.invoke(vec![plist]));
+        }
+
+        #[test]
+        fn popping_empty_is_a_condition() {
+            let popped = PopFn {}.invoke(vec![PersistentVector::empty().to_rc_value()]);
+            match popped {
+                Value::Condition(_) => {}
+                other => panic!("expected a condition, got {}", other),
+            }
+        }
+    }
+}
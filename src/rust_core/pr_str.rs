@@ -0,0 +1,26 @@
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (pr-str & xs)
+///
+/// xs as the reader would read them back,  separated by spaces;  so strings are quoted and
+/// characters are written \a,  \newline and the like
+#[derive(Debug, Clone)]
+pub struct PrStrFn {}
+impl ToValue for PrStrFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PrStrFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        Value::String(
+            args.iter()
+                .map(|arg| arg.to_string_explicit())
+                .collect::<Vec<String>>()
+                .join(" ")
+                .into(),
+        )
+    }
+}
@@ -0,0 +1,121 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+type Test = fn(&Value) -> Result<bool, Value>;
+
+/// Each predicate on one value that clojure.core has here,  and what it tests;  the ones
+/// specs are most often made of
+pub const PREDICATES: &[(&str, Test)] = &[
+    ("nil?", |x| Ok(matches!(x, Value::Nil))),
+    ("some?", |x| Ok(!matches!(x, Value::Nil))),
+    ("boolean?", |x| Ok(matches!(x, Value::Boolean(_)))),
+    ("number?", |x| Ok(numbers::is_number(x))),
+    ("int?", |x| Ok(matches!(x, Value::I32(_)))),
+    ("integer?", |x| Ok(is_integer(x))),
+    ("float?", |x| Ok(matches!(x, Value::F64(_)))),
+    ("ratio?", |x| Ok(matches!(x, Value::Ratio(_)))),
+    ("decimal?", |x| Ok(matches!(x, Value::BigDecimal(_)))),
+    ("char?", |x| Ok(matches!(x, Value::Char(_)))),
+    ("keyword?", |x| Ok(matches!(x, Value::Keyword(_)))),
+    ("symbol?", |x| Ok(matches!(x, Value::Symbol(_)))),
+    ("map?", |x| Ok(x.is_map())),
+    ("set?", |x| Ok(x.is_set())),
+    ("vector?", |x| Ok(matches!(x, Value::PersistentVector(_)))),
+    ("list?", |x| Ok(matches!(x, Value::PersistentList(_)))),
+    ("seq?", |x| {
+        Ok(matches!(x, Value::PersistentList(_) | Value::LazySeq(_)))
+    }),
+    ("sequential?", |x| Ok(x.is_sequential())),
+    ("coll?", |x| {
+        Ok(x.is_sequential() || x.is_map() || x.is_set())
+    }),
+    ("fn?", |x| Ok(matches!(x, Value::IFn(_)))),
+    ("pos?", |x| Ok(sign(x)? == Some(Ordering::Greater))),
+    ("neg?", |x| Ok(sign(x)? == Some(Ordering::Less))),
+    ("zero?", |x| Ok(sign(x)? == Some(Ordering::Equal))),
+    ("pos-int?", |x| {
+        Ok(is_integer(x) && sign(x)? == Some(Ordering::Greater))
+    }),
+    ("neg-int?", |x| {
+        Ok(is_integer(x) && sign(x)? == Some(Ordering::Less))
+    }),
+    ("nat-int?", |x| {
+        Ok(is_integer(x) && sign(x)? != Some(Ordering::Less))
+    }),
+    ("even?", |x| even(x)),
+    ("odd?", |x| even(x).map(|even| !even)),
+];
+
+fn is_integer(x: &Value) -> bool {
+    matches!(x, Value::I32(_) | Value::BigInt(_))
+}
+
+// How x compares to zero;  None if it's NaN
+fn sign(x: &Value) -> Result<Option<Ordering>, Value> {
+    numbers::compare(x, &Value::I32(0))
+}
+
+fn even(x: &Value) -> Result<bool, Value> {
+    if !is_integer(x) {
+        return Err(error_message::type_mismatch(TypeTag::Integer, x));
+    }
+    Ok(numbers::rem(x, &Value::I32(2)) == Value::I32(0))
+}
+
+/// nil?,  int?,  map?,  pos? and the rest of PREDICATES ; (pred x)
+///
+/// Whether x is what the predicate's named for
+#[derive(Debug, Clone)]
+pub struct PredicateFn {
+    test: Test,
+}
+impl PredicateFn {
+    pub fn new(test: Test) -> PredicateFn {
+        PredicateFn { test }
+    }
+}
+impl ToValue for PredicateFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PredicateFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match (self.test)(&args[0]) {
+            Ok(is) => Value::Boolean(is),
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod predicates_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn predicates_test_what_they_are_named_for() {
+            let result = Repl::default().eval_all(&[
+                "[(nil? nil) (some? false) (int? 1) (int? 1.0) (number? 1/2) (map? {}) (coll? #{}) (seq? (map inc [1])) (pos? 1/2) (neg? -1.5) (zero? 0) (nat-int? 0) (pos-int? 0) (even? 4) (odd? 4) (keyword? :a)]",
+            ]);
+            assert_eq!(
+                "[true true true false true true true true true true true true false true false true]",
+                result.to_string()
+            );
+        }
+
+        #[test]
+        fn number_predicates_only_take_numbers() {
+            let result = Repl::default().eval_all(&["(pos? \"1\")"]);
+            assert!(result.to_string().starts_with("#Condition[\"Type mismatch"));
+        }
+    }
+}
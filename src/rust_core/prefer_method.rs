@@ -0,0 +1,32 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::multi_fn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (prefer-method multifn x y)
+///
+/// Makes multifn call the method for x over the one for y,  when a dispatch value isa?
+/// both and neither derives from the other.  Returns multifn
+#[derive(Debug, Clone)]
+pub struct PreferMethodFn {}
+impl ToValue for PreferMethodFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PreferMethodFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let multi = match multi_fn::from_value(&args[0]) {
+            Ok(multi) => multi,
+            Err(condition) => return condition,
+        };
+        match multi.prefer_method(Arc::clone(&args[1]), Arc::clone(&args[2])) {
+            Ok(()) => args[0].to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
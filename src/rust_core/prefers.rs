@@ -0,0 +1,28 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::multi_fn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (prefers multifn)
+///
+/// A map of each dispatch value given to prefer-method,  to the set of those it's
+/// preferred over
+#[derive(Debug, Clone)]
+pub struct PrefersFn {}
+impl ToValue for PrefersFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PrefersFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match multi_fn::from_value(&args[0]) {
+            Ok(multi) => multi.prefers_value(),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -1,24 +1,28 @@
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
+use crate::clojure_java_io;
 use crate::error_message;
 
-/// Primitive printing function; TODO: should be aware of *out*
+/// Primitive printing function;  writes to *out* (stdout,  or the current output,  see
+/// crate::output,  unless it's bound)
 /// (defn print-string [string] .. prints single string without linebreak.. )
 #[derive(Debug, Clone)]
 pub struct PrintStringFn {}
 impl ToValue for PrintStringFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for PrintStringFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 1 {
             return error_message::wrong_arg_count(1, args.len());
         }
-        print!("{}", args.get(0).unwrap().to_string());
-        Value::Nil
+        match clojure_java_io::print(&args.first().unwrap().to_string()) {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
     }
 }
@@ -0,0 +1,33 @@
+use crate::clojure_java_io;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::format::format;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (printf fmt & args)
+///
+/// Prints (format fmt ..args) to *out*,  with no newline unless fmt has one
+#[derive(Debug, Clone)]
+pub struct PrintfFn {}
+impl ToValue for PrintfFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PrintfFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_arg_count(1, 0);
+        }
+        let fmt = match &*args[0] {
+            Value::String(fmt) => fmt,
+            _ => return error_message::type_mismatch(TypeTag::String, &args[0]),
+        };
+        match format(fmt, &args[1..]).and_then(|formatted| clojure_java_io::print(&formatted)) {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::promise::Promise;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (promise)
+///
+/// Returns a promise, which can be delivered a value exactly once with deliver
+#[derive(Debug, Clone)]
+pub struct PromiseFn {}
+impl ToValue for PromiseFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for PromiseFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.is_empty() {
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        Value::Promise(Arc::new(Promise::new()))
+    }
+}
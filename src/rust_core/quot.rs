@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (quot num div)
+///
+/// num divided by div,  rounded towards zero
+#[derive(Debug, Clone)]
+pub struct QuotFn {}
+impl ToValue for QuotFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for QuotFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        numbers::quot(&args[0], &args[1])
+    }
+}
@@ -1,8 +1,31 @@
 use crate::error_message;
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use rand::{thread_rng, Rng};
-use std::rc::Rc;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+thread_local! {
+    // The one source of randomness for every random builtin, so that seeding it makes
+    // a whole program reproducible.  Each thread gets its own; seeded from the OS until
+    // someone calls (set-random-seed! n)
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Runs f with the interpreter's shared random number generator
+pub fn with_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> T {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+/// Reseeds the shared generator;  None goes back to seeding from the OS
+pub fn set_seed(seed: Option<u64>) {
+    let rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    RNG.with(|shared| *shared.borrow_mut() = rng);
+}
 
 /// (rand) or (rand n)
 ///
@@ -10,23 +33,26 @@ use std::rc::Rc;
 pub struct RandFn {}
 impl ToValue for RandFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for RandFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         match args.len() {
-            0 => Value::F64(thread_rng().gen()),
+            0 => Value::F64(with_rng(|rng| rng.gen())),
             1 => {
                 let arg = args.get(0).unwrap().to_value();
                 match arg {
-                    Value::I32(i_) => Value::F64(thread_rng().gen_range(0.0, i_ as f64)),
-                    Value::F64(f_) => Value::F64(thread_rng().gen_range(0.0, f_)),
-                    _ => Value::Condition(format!(
-                        // TODO: what error message should be returned regarding using typetags?
-                        "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
-                        arg.type_tag()
-                    )),
+                    Value::I32(i_) => Value::F64(with_rng(|rng| rng.gen_range(0.0, i_ as f64))),
+                    Value::F64(f_) => Value::F64(with_rng(|rng| rng.gen_range(0.0, f_))),
+                    _ => Value::Condition(
+                        format!(
+                            // TODO: what error message should be returned regarding using typetags?
+                            "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
+                            arg.type_tag()
+                        )
+                        .into(),
+                    ),
                 }
             }
             _ => error_message::wrong_varg_count(&[0, 1], args.len()),
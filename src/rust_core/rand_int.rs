@@ -1,34 +1,64 @@
 use crate::error_message;
 use crate::ifn::IFn;
+use crate::rust_core::rand::with_rng;
 use crate::value::{ToValue, Value};
-use rand::{thread_rng, Rng};
-use std::rc::Rc;
+use rand::Rng;
+use std::sync::Arc;
 
-/// (rand) or (rand n)
+/// (rand-int n)
 ///
+/// A random int between 0 (inclusive) and n (exclusive);  as (int (rand n)),  so 0 for 0,
+/// and between n and 0 for a negative n
 #[derive(Debug, Clone)]
 pub struct RandIntFn {}
 impl ToValue for RandIntFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for RandIntFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         match args.len() {
             1 => {
                 let arg = args.get(0).unwrap().to_value();
                 match arg {
-                    Value::I32(i_) => Value::I32(thread_rng().gen_range(0, i_)),
-                    Value::F64(f_) => Value::I32(thread_rng().gen_range(0, f_ as i32)),
-                    _ => Value::Condition(format!(
-                        // TODO: what error message should be returned regarding using typetags?
-                        "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
-                        arg.type_tag()
-                    )),
+                    Value::I32(i_) => Value::I32(rand_int(i_)),
+                    Value::F64(f_) => Value::I32(rand_int(f_ as i32)),
+                    _ => Value::Condition(
+                        format!(
+                            // TODO: what error message should be returned regarding using typetags?
+                            "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
+                            arg.type_tag()
+                        )
+                        .into(),
+                    ),
                 }
             }
             _ => error_message::wrong_arg_count(1, args.len()),
         }
     }
 }
+
+// gen_range panics on an empty range,  so 0 is its own case
+fn rand_int(n: i32) -> i32 {
+    match n {
+        0 => 0,
+        n if n > 0 => with_rng(|rng| rng.gen_range(0, n)),
+        n => -(with_rng(|rng| rng.gen_range(0, -(n as i64))) as i32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod rand_int_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn rand_int_of_zero_or_less_does_not_panic() {
+            let result = Repl::default().eval_all(&[
+                "[(rand-int 0) (sort (set (map (fn [_] (rand-int -2)) (range 50)))) (sort (set (map (fn [_] (rand-int 2)) (range 50))))]",
+            ]);
+            assert_eq!("[0 (-1 0) (0 1)]", result.to_string());
+        }
+    }
+}
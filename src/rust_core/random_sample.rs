@@ -0,0 +1,80 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers;
+use crate::rust_core::rand::with_rng;
+use crate::transducer::{self, Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use rand::Rng;
+use std::sync::Arc;
+
+/// (random-sample prob) (random-sample prob coll)
+///
+/// A lazy seq of the items of coll,  each kept with probability prob.  Without coll,  a
+/// transducer passing on each input with that probability
+#[derive(Debug, Clone)]
+pub struct RandomSampleFn {}
+impl ToValue for RandomSampleFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RandomSampleFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let prob = match numbers::to_f64(&args[0]) {
+            Some(prob) => prob,
+            None => return error_message::type_mismatch(TypeTag::F64, &args[0]),
+        };
+        let xform = Transducer::new(move || Box::new(RandomSampleStage { prob }));
+        match args.get(1) {
+            Some(coll) => transducer::sequence(&xform, Arc::clone(coll)),
+            None => xform.to_value(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RandomSampleStage {
+    prob: f64,
+}
+impl Stage for RandomSampleStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        match with_rng(|rng| rng.gen::<f64>() < self.prob) {
+            true => rf.invoke(vec![result, input]),
+            false => result.to_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod random_sample_tests {
+        use crate::ifn::IFn;
+        use crate::persistent_list::PersistentList;
+        use crate::rust_core::RandomSampleFn;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn one_to_ten() -> Arc<Value> {
+            (1..=10)
+                .map(|i| Arc::new(Value::I32(i)))
+                .collect::<PersistentList>()
+                .to_rc_value()
+        }
+
+        #[test]
+        fn random_sample_with_prob_one_keeps_everything() {
+            let sample = RandomSampleFn {}.invoke(vec![Arc::new(Value::F64(1.0)), one_to_ten()]);
+            assert_eq!(*one_to_ten(), sample);
+        }
+
+        #[test]
+        fn random_sample_with_prob_zero_keeps_nothing() {
+            let sample = RandomSampleFn {}.invoke(vec![Arc::new(Value::F64(0.0)), one_to_ten()]);
+            assert_eq!(PersistentList::Empty.to_value(), sample);
+        }
+    }
+}
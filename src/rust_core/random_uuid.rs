@@ -0,0 +1,29 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::rand::with_rng;
+use crate::value::{ToValue, Value};
+use rand::Rng;
+use std::sync::Arc;
+
+/// (random-uuid)
+///
+/// Returns a random (version 4) UUID
+#[derive(Debug, Clone)]
+pub struct RandomUuidFn {}
+impl ToValue for RandomUuidFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RandomUuidFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if !args.is_empty() {
+            return error_message::wrong_arg_count(0, args.len());
+        }
+        let mut bytes: [u8; 16] = with_rng(|rng| rng.gen());
+        // Version 4,  variant 1
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        Value::Uuid(Arc::new(bytes))
+    }
+}
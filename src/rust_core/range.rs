@@ -0,0 +1,197 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{Counted, LazySeq, Step};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+/// (range) (range end) (range start end) (range start end step)
+///
+/// A lazy seq of the numbers from start (default 0) up to but not including end (default
+/// forever),  step (default 1) apart.  The numbers are i32s if every argument is,  and f64s
+/// otherwise;  the nth being start + n * step,  so (range 0.0 1.0 0.1) doesn't pile up
+/// rounding errors by adding step over and over
+#[derive(Debug, Clone)]
+pub struct RangeFn {}
+impl ToValue for RangeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RangeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (start, end, step) = match &args[..] {
+            [] => (Value::I32(0), None, Value::I32(1)),
+            [end] => (Value::I32(0), Some(end.to_value()), Value::I32(1)),
+            [start, end] => (start.to_value(), Some(end.to_value()), Value::I32(1)),
+            [start, end, step] => (start.to_value(), Some(end.to_value()), step.to_value()),
+            _ => return error_message::wrong_varg_count(&[0, 1, 2, 3], args.len()),
+        };
+        for arg in args.iter() {
+            if !matches!(**arg, Value::I32(_) | Value::F64(_)) {
+                return error_message::type_mismatch(TypeTag::Number, arg);
+            }
+        }
+        match (start, end, step) {
+            (Value::I32(start), Some(Value::I32(end)), Value::I32(step)) => {
+                let range = lazy_range(start, Some(end), step, 0, nth_i32);
+                // A step of zero might go on forever,  so that's left to be stepped through
+                match step {
+                    0 => range.into_value(),
+                    _ => range
+                        .with_counted(Counted { start, end, step })
+                        .into_value(),
+                }
+            }
+            (Value::I32(start), None, Value::I32(step)) => {
+                lazy_range(start, None, step, 0, nth_i32).into_value()
+            }
+            (start, end, step) => lazy_range(
+                as_f64(&start),
+                end.as_ref().map(as_f64),
+                as_f64(&step),
+                0,
+                nth_f64,
+            )
+            .into_value(),
+        }
+    }
+}
+
+fn as_f64(number: &Value) -> f64 {
+    match number {
+        Value::I32(i) => f64::from(*i),
+        Value::F64(f) => *f,
+        _ => unreachable!(),
+    }
+}
+
+fn nth_i32(start: i32, step: i32, n: u32) -> Option<i32> {
+    i32::try_from(i64::from(start) + i64::from(step) * i64::from(n)).ok()
+}
+
+fn nth_f64(start: f64, step: f64, n: u32) -> Option<f64> {
+    Some(start + step * f64::from(n))
+}
+
+// nth gives the nth number of the range,  or None once it can't be represented,  which
+// ends the range there
+fn lazy_range<T>(
+    start: T,
+    end: Option<T>,
+    step: T,
+    n: u32,
+    nth: fn(T, T, u32) -> Option<T>,
+) -> LazySeq
+where
+    T: Copy + PartialOrd + Default + ToValue + Send + 'static,
+{
+    LazySeq::new(move || {
+        let current = match nth(start, step, n) {
+            Some(current) => current,
+            None => return Ok(Step::Done),
+        };
+        let zero = T::default();
+        let in_range = match end {
+            None => true,
+            Some(end) if step > zero => current < end,
+            Some(end) if step < zero => current > end,
+            // A step of zero repeats start forever,  unless the range is empty
+            Some(end) => current != end,
+        };
+        if !in_range {
+            return Ok(Step::Done);
+        }
+        let rest = match n.checked_add(1) {
+            Some(n) => lazy_range(start, end, step, n, nth).into_value(),
+            None => Value::Nil,
+        };
+        Ok(Step::Next(current.to_rc_value(), Arc::new(rest)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    mod range_tests {
+        use crate::ifn::IFn;
+        use crate::persistent_vector::PersistentVector;
+        use crate::repl::Repl;
+        use crate::rust_core::{AddFn, BooleanFn, ConcatFn, FilterFn, MapFn, RangeFn, TakeFn};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn range(args: &[Value]) -> Value {
+            RangeFn {}.invoke(args.iter().map(|arg| arg.to_rc_value()).collect())
+        }
+
+        #[test]
+        fn counts_up_to_end() {
+            assert_eq!("(0 1 2)", range(&[Value::I32(3)]).to_string());
+            assert_eq!(
+                "(5 3 1)",
+                range(&[Value::I32(5), Value::I32(0), Value::I32(-2)]).to_string()
+            );
+            assert_eq!("()", range(&[Value::I32(3), Value::I32(3)]).to_string());
+            assert_eq!(
+                "(0 0.5)",
+                range(&[Value::I32(0), Value::I32(1), Value::F64(0.5)]).to_string()
+            );
+        }
+
+        #[test]
+        fn float_steps_do_not_drift() {
+            let tenths = range(&[Value::F64(0.0), Value::F64(1.0), Value::F64(0.1)]);
+            assert_eq!(
+                "(0 0.1 0.2 0.30000000000000004 0.4 0.5 0.6000000000000001 0.7000000000000001 0.8 0.9)",
+                tenths.to_string()
+            );
+            assert!(matches!(
+                range(&[Value::String("a".into()), Value::String("z".into())]),
+                Value::Condition(_)
+            ));
+        }
+
+        #[test]
+        fn reduce_counts_through_a_range_as_its_seq_would() {
+            let result = Repl::default().eval_all(&[
+                "(def ranges [(range 5) (range 10 0 -3) (range 3 3) (range 2147483640 2147483647 3)])",
+                "(map (fn [r] (= (reduce conj [] r) (reduce conj [] (map (fn [x] x) r)))) ranges)",
+            ]);
+            assert_eq!("(true true true true)", result.to_string());
+            let result = Repl::default()
+                .eval_all(&["(reduce (fn [acc x] (if (< x 3) (+ acc x) (reduced acc))) (range))"]);
+            assert_eq!("3", result.to_string());
+        }
+
+        #[test]
+        fn an_infinite_range_is_only_realized_as_far_as_it_is_taken() {
+            let taken = TakeFn {}.invoke(vec![Arc::new(Value::I32(3)), Arc::new(range(&[]))]);
+            assert_eq!("(0 1 2)", taken.to_string());
+        }
+
+        #[test]
+        fn map_filter_and_concat_are_lazy_too() {
+            let take = |n: i32, coll: Value| {
+                TakeFn {}.invoke(vec![Arc::new(Value::I32(n)), Arc::new(coll)])
+            };
+            let sums = MapFn {}.invoke(vec![
+                AddFn {}.to_rc_value(),
+                Arc::new(range(&[])),
+                Arc::new(range(&[Value::I32(10), Value::I32(20)])),
+            ]);
+            assert_eq!("(10 12 14)", take(3, sums).to_string());
+
+            let falsey_then_all = ConcatFn {}.invoke(vec![
+                vec![Arc::new(Value::Nil), Arc::new(Value::Boolean(false))]
+                    .into_iter()
+                    .collect::<PersistentVector>()
+                    .to_rc_value(),
+                Arc::new(range(&[])),
+            ]);
+            let truthy =
+                FilterFn {}.invoke(vec![BooleanFn {}.to_rc_value(), Arc::new(falsey_then_all)]);
+            assert_eq!("(0 1)", take(2, truthy).to_string());
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::pattern;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (re-find re s)
+///
+/// The first match of re in s;  the string matched,  or if re has groups,  a vector of it
+/// and what each group matched.  nil if there's none
+#[derive(Debug, Clone)]
+pub struct ReFindFn {}
+impl ToValue for ReFindFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReFindFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (regex, s) = match pattern::regex_and_string(&args[0], &args[1]) {
+            Ok(regex_and_string) => regex_and_string,
+            Err(condition) => return condition,
+        };
+        match regex.captures(&s) {
+            Some(captures) => pattern::match_value(&captures),
+            None => Value::Nil,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::pattern;
+use crate::value::{ToValue, Value};
+use regex::Regex;
+use std::sync::Arc;
+
+/// (re-matches re s)
+///
+/// As re-find,  but re has to match the whole of s
+#[derive(Debug, Clone)]
+pub struct ReMatchesFn {}
+impl ToValue for ReMatchesFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReMatchesFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (regex, s) = match pattern::regex_and_string(&args[0], &args[1]) {
+            Ok(regex_and_string) => regex_and_string,
+            Err(condition) => return condition,
+        };
+        // Anchored,  rather than checking the first match spans s;  with a|ab,  the first
+        // match of "ab" is only "a".  The group around re doesn't capture,  so re's groups
+        // keep their numbers
+        let whole = Regex::new(&format!(r"\A(?:{})\z", regex.as_str()))
+            .expect("a regex that compiled still compiles once anchored");
+        match whole.captures(&s) {
+            Some(captures) => pattern::match_value(&captures),
+            None => Value::Nil,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::pattern;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (re-pattern s)
+///
+/// The regex s is the pattern of,  as #"s" would read;  a regex is given back as is
+#[derive(Debug, Clone)]
+pub struct RePatternFn {}
+impl ToValue for RePatternFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RePatternFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::String(s) => pattern::compile(s),
+            Value::Regex(_) => (*args[0]).clone(),
+            _ => error_message::type_mismatch(TypeTag::String, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{LazySeq, Step};
+use crate::pattern;
+use crate::shared_str::SharedStr;
+use crate::value::{ToValue, Value};
+use regex::Regex;
+use std::sync::Arc;
+
+/// (re-seq re s)
+///
+/// A lazy seq of each match of re in s,  in turn,  as re-find gives them
+#[derive(Debug, Clone)]
+pub struct ReSeqFn {}
+impl ToValue for ReSeqFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReSeqFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match pattern::regex_and_string(&args[0], &args[1]) {
+            Ok((regex, s)) => lazy_matches(regex, s, 0).into_value(),
+            Err(condition) => condition,
+        }
+    }
+}
+
+// The matches of regex in s from at on
+fn lazy_matches(regex: Arc<Regex>, s: SharedStr, at: usize) -> LazySeq {
+    LazySeq::new(move || {
+        if at > s.len() {
+            return Ok(Step::Done);
+        }
+        let captures = match regex.captures_at(&s, at) {
+            Some(captures) => captures,
+            None => return Ok(Step::Done),
+        };
+        let whole = captures.get(0).unwrap();
+        // An empty match would only be found again;  the next one starts past it
+        let next = match s[whole.end()..].chars().next() {
+            Some(ch) if whole.is_empty() => whole.end() + ch.len_utf8(),
+            None if whole.is_empty() => s.len() + 1,
+            _ => whole.end(),
+        };
+        let first = pattern::match_value(&captures).to_rc_value();
+        let rest = lazy_matches(Arc::clone(&regex), SharedStr::clone(&s), next).into_value();
+        Ok(Step::Next(first, Arc::new(rest)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn regex_fns_give_groups_as_vectors() {
+        let result = Repl::default().eval_all(&[
+            "[(re-find #\"\\d+\" \"ab12cd345\") (re-find #\"(\\w)(\\d)?\" \"x\") (re-find #\"z\" \"abc\") (re-matches #\"a|ab\" \"ab\") (re-matches #\"(a)b\" \"abc\") (re-matches #\"(a)b\" \"ab\")]",
+        ]);
+        assert_eq!(
+            "[\"12\" [\"x\" \"x\" nil] nil \"ab\" nil [\"ab\" \"a\"]]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn re_seq_steps_past_empty_matches() {
+        let result = Repl::default().eval_all(&[
+            "[(re-seq #\"\\d+\" \"a1b22c333\") (re-seq #\"x*\" \"ab\") (re-seq #\"(\\w)=(\\d)\" \"a=1 b=2\") (re-seq #\"z\" \"abc\")]",
+        ]);
+        assert_eq!(
+            "[(\"1\" \"22\" \"333\") (\"\" \"\" \"\") ([\"a=1\" \"a\" \"1\"] [\"b=2\" \"b\" \"2\"]) ()]",
+            result.to_string()
+        );
+    }
+}
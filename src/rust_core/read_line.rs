@@ -1,34 +1,27 @@
 use crate::ifn::IFn;
-use crate::value::{Value, ToValue, Evaluable};
-use std::rc::Rc;
-
-use std::io;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
 
+use crate::clojure_java_io;
 use crate::error_message;
-use nom::lib::std::convert::TryFrom;
-use std::io::{Read, Write};
 
-/// Read a line from stdin TODO: should be aware of *in*
+/// Read a line from *in* (stdin,  unless it's bound);  nil once there are no more
 /// (defn read-line [])
 #[derive(Debug, Clone)]
 pub struct ReadLineFn {}
 impl ToValue for ReadLineFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for ReadLineFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 0 {
-            return error_message::wrong_arg_count(0, args.len())
+            return error_message::wrong_arg_count(0, args.len());
         }
-        let mut input = String::new();
-        match io::stdin().read_line(&mut input) {
-            Ok(_) => {
-                input.pop();
-                Value::String(input)
-            },
-            Err(error) => error_message::generic_err(Box::try_from(error).unwrap())
+        match clojure_java_io::read_line() {
+            Ok(line) => line.map_or(Value::Nil, Value::from),
+            Err(condition) => condition,
         }
     }
-}
\ No newline at end of file
+}
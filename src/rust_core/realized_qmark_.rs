@@ -0,0 +1,31 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::deref::as_blocking_deref;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (realized? x)
+///
+/// Whether a promise or future has its value yet,  or a lazy seq its first element
+#[derive(Debug, Clone)]
+pub struct RealizedFn {}
+impl ToValue for RealizedFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RealizedFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        if let Value::LazySeq(lseq) = &*args[0] {
+            return Value::Boolean(lseq.is_realized());
+        }
+        match as_blocking_deref(&args[0]) {
+            Some(blocking_ref) => Value::Boolean(blocking_ref.is_realized()),
+            None => error_message::type_mismatch(TypeTag::IBlockingDeref, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (recur & vals)
+///
+/// Goes round the enclosing loop or fn again,  with its bindings set to vals.  Only means
+/// anything as the last thing the loop or fn evaluates;  anywhere else, its value ends up
+/// passed around like any other
+#[derive(Debug, Clone)]
+pub struct RecurFn {}
+impl ToValue for RecurFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RecurFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        Value::Recur(Arc::new(args))
+    }
+}
@@ -0,0 +1,35 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::transducer;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (reduce f coll) (reduce f init coll)
+///
+/// f of init and the first element of coll,  then f of that and the second,  and so on;  or
+/// with no init,  starting from the first element.  (f) if there's nothing to start from.
+/// Stops early if f gives (reduced val),  giving val
+#[derive(Debug, Clone)]
+pub struct ReduceFn {}
+impl ToValue for ReduceFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReduceFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
+        }
+        let f = match ifn::as_ifn(&args[0]) {
+            Some(f) => f,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let init = match args.len() {
+            3 => Some(Arc::clone(&args[1])),
+            _ => None,
+        };
+        transducer::reduce(&f, init, args.last().unwrap())
+    }
+}
@@ -0,0 +1,23 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (reduced x)
+///
+/// x,  wrapped so that a reduce given it by its fn stops there,  with x
+#[derive(Debug, Clone)]
+pub struct ReducedFn {}
+impl ToValue for ReducedFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReducedFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Reduced(Arc::clone(&args[0]))
+    }
+}
@@ -0,0 +1,21 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (reduced? x)
+#[derive(Debug, Clone)]
+pub struct ReducedQmarkFn {}
+impl ToValue for ReducedQmarkFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReducedQmarkFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Boolean(matches!(&*args[0], Value::Reduced(_)))
+    }
+}
@@ -0,0 +1,33 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::atom::validator_option;
+use crate::stm::Ref;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ref x & options)
+///
+/// Returns a ref holding x,  to be changed in a dosync.  As with atom,  the one option is
+/// :validator f
+#[derive(Debug, Clone)]
+pub struct RefFn {}
+impl ToValue for RefFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RefFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let validator = match validator_option("ref", &args[1..]) {
+            Ok(validator) => validator,
+            Err(condition) => return condition,
+        };
+        match Ref::new(Arc::clone(&args[0]), validator) {
+            Ok(reference) => Value::Ref(Arc::new(reference)),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::stm;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (ref-set ref x)
+///
+/// Sets ref to x within the current transaction;  returns x
+#[derive(Debug, Clone)]
+pub struct RefSetFn {}
+impl ToValue for RefSetFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RefSetFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match &*args[0] {
+            Value::Ref(reference) => match stm::ref_set(reference, Arc::clone(&args[1])) {
+                Ok(val) => val.to_value(),
+                Err(condition) => condition,
+            },
+            _ => error_message::type_mismatch(TypeTag::Ref, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::namespace::Namespace;
+use crate::persistent_list::PersistentList;
+use crate::persistent_list_map::ToPersistentListMapIter;
+use crate::rust_core::the_ns::the_ns;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// (refer ns & filters)
+///
+/// Makes each public name of ns resolve to what it is there,  in the current namespace;
+/// those in :only [names],  if it's given,  save for those in :exclude [names],  and each
+/// in :rename {name new-name} by its new name.  Referring clojure.core,  as each namespace
+/// does,  again only refers what these leave of it
+#[derive(Debug, Clone)]
+pub struct ReferFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl ReferFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> ReferFn {
+        ReferFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for ReferFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReferFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (ns, filters) = match args.split_first() {
+            Some(split) => split,
+            None => return error_message::zero_arg_count(0),
+        };
+        let environment = &self.enclosing_environment;
+        match the_ns(environment, ns).and_then(|ns| refer(environment, &ns, filters)) {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// Refers from's public names into the current namespace,  as refer does with filters;
+/// shared with require,  use and ns
+pub(crate) fn refer(
+    environment: &Environment,
+    from: &Namespace,
+    filters: &[Arc<Value>],
+) -> Result<(), Value> {
+    let mut only = None;
+    let mut exclude = HashSet::new();
+    let mut rename = HashMap::new();
+    for filter in filters.chunks(2) {
+        let (key, val) = match filter {
+            [key, val] => (key, val),
+            _ => {
+                return Err(Value::Condition(
+                    format!("{} needs a value", filter[0]).into(),
+                ))
+            }
+        };
+        match &**key {
+            Value::Keyword(key) if key.sym.name == "only" => only = Some(symbols(val)?),
+            Value::Keyword(key) if key.sym.name == "exclude" => {
+                exclude = symbols(val)?.into_iter().collect()
+            }
+            Value::Keyword(key) if key.sym.name == "rename" => rename = renames(val)?,
+            _ => {
+                return Err(Value::Condition(
+                    format!("Unsupported option {}", key).into(),
+                ))
+            }
+        }
+    }
+    let publics = from
+        .mappings()
+        .into_iter()
+        .map(|(sym, _)| sym)
+        .filter(|sym| !from.is_private(sym))
+        .collect::<HashSet<Symbol>>();
+    if let Some(missing) = only.iter().flatten().find(|sym| !publics.contains(sym)) {
+        return Err(Value::Condition(
+            format!("{} does not exist in {}", missing, from.name).into(),
+        ));
+    }
+    let to = environment.find_or_create_namespace(&environment.get_current_namespace());
+    if from.name.name == "clojure.core" {
+        to.exclude_core(publics.iter().cloned());
+    }
+    for sym in publics {
+        if only.as_ref().is_some_and(|only| !only.contains(&sym)) || exclude.contains(&sym) {
+            continue;
+        }
+        let var = Symbol::intern_with_ns(&from.name.name, &sym.name);
+        to.refer(rename.get(&sym).unwrap_or(&sym), var);
+    }
+    Ok(())
+}
+
+// The symbols in a vector or list of them;  as :only and :exclude take
+fn symbols(val: &Arc<Value>) -> Result<Vec<Symbol>, Value> {
+    let vals: Vec<Arc<Value>> = match &**val {
+        Value::PersistentVector(pvector) => pvector.iter().cloned().collect(),
+        Value::PersistentList(plist) => PersistentList::iter(plist).collect(),
+        _ => return Err(error_message::type_mismatch(TypeTag::PersistentVector, val)),
+    };
+    vals.into_iter()
+        .map(|val| match &*val {
+            Value::Symbol(sym) => Ok((**sym).clone()),
+            _ => Err(error_message::type_mismatch(TypeTag::Symbol, &val)),
+        })
+        .collect()
+}
+
+// The new name of each symbol in a map of them;  as :rename takes
+fn renames(val: &Arc<Value>) -> Result<HashMap<Symbol, Symbol>, Value> {
+    let entries = match &**val {
+        Value::PersistentListMap(map) => map.iter().collect::<Vec<_>>(),
+        _ => {
+            return Err(error_message::type_mismatch(
+                TypeTag::PersistentListMap,
+                val,
+            ))
+        }
+    };
+    entries
+        .into_iter()
+        .map(|entry| match (&*entry.key, &*entry.val) {
+            (Value::Symbol(from), Value::Symbol(to)) => Ok(((**from).clone(), (**to).clone())),
+            _ => Err(error_message::type_mismatch(TypeTag::Symbol, &entry.key)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn refer_filters_what_it_refers() {
+        let result = Repl::default().eval_all(&[
+            "(ns lib.shapes)",
+            "(def circle :circle)",
+            "(def square :square)",
+            "(def ^:private secret :secret)",
+            "(ns app.core)",
+            "(refer 'lib.shapes :exclude '[square] :rename '{circle round})",
+            "(def refs (ns-refers 'app.core))",
+            "[round (contains? refs 'square) (contains? refs 'secret) (ex-message (try (refer 'lib.shapes :only '[triangle]) (catch Exception e e)))]",
+        ]);
+        assert_eq!(
+            "[:circle false false \"triangle does not exist in lib.shapes\"]",
+            result.to_string_explicit()
+        );
+    }
+
+    #[test]
+    fn clojure_core_can_be_referred_in_part() {
+        let result = Repl::default().eval_all(&[
+            "(ns app.core)",
+            "(refer 'clojure.core :exclude '[inc] :rename '{dec minus-one})",
+            "(def inc (fn [x] (+ x 10)))",
+            "[(inc 1) (minus-one 1) (try dec (catch Exception e :unresolved))]",
+        ]);
+        assert_eq!("[11 0 :unresolved]", result.to_string());
+    }
+}
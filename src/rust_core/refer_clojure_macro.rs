@@ -0,0 +1,32 @@
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::rust_core::defprotocol_macro::quote;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (refer-clojure & filters)
+///
+/// Refers only what filters leave of clojure.core into the current namespace;  as refer
+/// does,  with filters unevaluated
+///
+/// (refer-clojure :exclude [map]) expands to
+///
+/// (clojure.core/refer 'clojure.core ':exclude '[map])
+#[derive(Debug, Clone)]
+pub struct ReferClojureMacro {}
+impl ToValue for ReferClojureMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for ReferClojureMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let mut call = vec![
+            Symbol::intern_with_ns("clojure.core", "refer").to_rc_value(),
+            quote(Symbol::intern("clojure.core").to_rc_value()),
+        ];
+        call.extend(args.into_iter().map(quote));
+        call.into_list().to_value()
+    }
+}
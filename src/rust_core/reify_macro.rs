@@ -0,0 +1,143 @@
+use crate::clojure_protocol;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::record::{Instance, Type};
+use crate::rust_core::defprotocol_macro::quote;
+use crate::rust_core::extend_type_macro::protocol_impls;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// (reify* protocol method f protocol method f ..)
+///
+/// Makes the one instance of a new type,  which implements each protocol's method with
+/// the f after it
+#[derive(Debug, Clone)]
+pub struct ReifyFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl ReifyFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> ReifyFn {
+        ReifyFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for ReifyFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReifyFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let triples = args.chunks_exact(3);
+        if !triples.remainder().is_empty() {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to function (Given: {}, Expected: 0 | 3 | 6 | ..)",
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let mut impls = HashMap::new();
+        for protocol_method_f in triples {
+            let protocol = match &*protocol_method_f[0] {
+                Value::Protocol(protocol) => protocol,
+                _ => return error_message::type_mismatch(TypeTag::Protocol, &protocol_method_f[0]),
+            };
+            let method = match &*protocol_method_f[1] {
+                Value::Symbol(sym) => sym,
+                _ => return error_message::type_mismatch(TypeTag::Symbol, &protocol_method_f[1]),
+            };
+            let f = match &*protocol_method_f[2] {
+                Value::IFn(f) => f,
+                _ => return error_message::type_mismatch(TypeTag::IFn, &protocol_method_f[2]),
+            };
+            if protocol.arglists(method).is_none() {
+                return Value::Condition(
+                    format!("No method {} in protocol {}", method, protocol.name).into(),
+                );
+            }
+            clojure_protocol::add_impl(
+                impls.entry(protocol.name.clone()).or_default(),
+                method,
+                Arc::clone(f),
+            );
+        }
+        let ns = self.enclosing_environment.get_current_namespace_name();
+        let type_ = Type::reified(&ns, impls);
+        Value::Instance(Arc::new(Instance::new(Arc::new(type_), vec![])))
+    }
+}
+
+/// (reify protocol method-impls protocol method-impls ..)
+///
+/// (reify Shape (area [this] (* 3 r r))) expands to
+///
+/// (reify* Shape (quote area) (fn [this] (* 3 r r)))
+///
+/// so each method closes over the locals around it,  as they are each time it's evaluated
+#[derive(Debug, Clone)]
+pub struct ReifyMacro {}
+impl ToValue for ReifyMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for ReifyMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let groups = match protocol_impls(&args) {
+            Ok(groups) => groups,
+            Err(condition) => return condition,
+        };
+        let mut expansion = vec![Symbol::intern("reify*").to_rc_value()];
+        for (protocol, impls) in groups {
+            for (name, arglist, body) in impls {
+                let mut f = vec![Symbol::intern("fn").to_rc_value(), arglist];
+                f.extend(body);
+                expansion.push(Arc::clone(&protocol));
+                expansion.push(quote(name));
+                expansion.push(f.into_list().to_rc_value());
+            }
+        }
+        expansion.into_list().to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn each_reify_closes_over_its_own_locals() {
+        let result = Repl::default().eval_all(&[
+            "(defprotocol Shape (area [this]) (scale [this n] [this n m]))",
+            "(defprotocol Named (label [this]))",
+            "(defn circle [r] (reify Shape (area [_] (* 3 r r)) (scale [_ n] (* n r)) (scale [_ n m] (* n m r)) Named (label [_] \"circle\")))",
+            "(def small (circle 1))",
+            "(def big (circle 2))",
+            "[(area small) (area big) (scale big 5) (scale small 2 3) (label big) (satisfies? Named small) (= (class small) (class big))]",
+        ]);
+        assert_eq!("[3 12 10 6 \"circle\" true false]", result.to_string());
+    }
+
+    #[test]
+    fn a_reify_only_implements_what_it_says() {
+        let result = Repl::default().eval_all(&[
+            "(defprotocol Shape (area [this]))",
+            "(defprotocol Named (label [this]))",
+            "(def unit (reify Shape (area [_] 1)))",
+            "(def missing (ex-message (try (reify Shape (volume [_] 1)) (catch Exception e e))))",
+            "[(area unit) (satisfies? Named unit) missing]",
+        ]);
+        assert_eq!(
+            "[1 false \"No method volume in protocol user/Shape\"]",
+            result.to_string()
+        );
+    }
+}
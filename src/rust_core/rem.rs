@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (rem num div)
+///
+/// The remainder of (quot num div);  which has num's sign
+#[derive(Debug, Clone)]
+pub struct RemFn {}
+impl ToValue for RemFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RemFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        numbers::rem(&args[0], &args[1])
+    }
+}
@@ -0,0 +1,40 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::rust_core::filter::FilterStage;
+use crate::transducer::{self, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (remove pred) (remove pred coll)
+///
+/// A lazy seq of the elements of coll for which pred is falsy;  filter's opposite.  Without
+/// coll,  a transducer passing on only those inputs
+#[derive(Debug, Clone)]
+pub struct RemoveFn {}
+impl ToValue for RemoveFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RemoveFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let pred = match ifn::as_ifn(&args[0]) {
+            Some(pred) => pred,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let xform = Transducer::new(move || {
+            Box::new(FilterStage {
+                pred: Arc::clone(&pred),
+                keep: false,
+            })
+        });
+        match args.get(1) {
+            Some(coll) => transducer::sequence(&xform, Arc::clone(coll)),
+            None => xform.to_value(),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::multi_fn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (remove-all-methods multifn)
+///
+/// Removes every method multifn has,  and returns multifn
+#[derive(Debug, Clone)]
+pub struct RemoveAllMethodsFn {}
+impl ToValue for RemoveAllMethodsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RemoveAllMethodsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match multi_fn::from_value(&args[0]) {
+            Ok(multi) => {
+                multi.remove_all_methods();
+                args[0].to_value()
+            }
+            Err(condition) => condition,
+        }
+    }
+}
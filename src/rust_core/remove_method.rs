@@ -0,0 +1,30 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::multi_fn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (remove-method multifn dispatch-val)
+///
+/// Removes the method multifn has for dispatch-val,  and returns multifn
+#[derive(Debug, Clone)]
+pub struct RemoveMethodFn {}
+impl ToValue for RemoveMethodFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RemoveMethodFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match multi_fn::from_value(&args[0]) {
+            Ok(multi) => {
+                multi.remove_method(&args[1]);
+                args[0].to_value()
+            }
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (remove-watch atom key)
+///
+/// Removes the watch added under key,  if there is one.  Returns atom
+#[derive(Debug, Clone)]
+pub struct RemoveWatchFn {}
+impl ToValue for RemoveWatchFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RemoveWatchFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match &*args[0] {
+            Value::Atom(atom) => {
+                atom.remove_watch(&args[1]);
+                args[0].to_value()
+            }
+            _ => error_message::type_mismatch(TypeTag::Atom, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{LazySeq, Step};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (repeat x) (repeat n x)
+///
+/// A lazy seq of x,  n times or forever
+#[derive(Debug, Clone)]
+pub struct RepeatFn {}
+impl ToValue for RepeatFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RepeatFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match &args[..] {
+            [x] => lazy_repeat(Arc::clone(x), None).into_value(),
+            [n, x] => match &**n {
+                Value::I32(n) => lazy_repeat(Arc::clone(x), Some(*n)).into_value(),
+                _ => error_message::type_mismatch(TypeTag::Integer, n),
+            },
+            _ => error_message::wrong_varg_count(&[1, 2], args.len()),
+        }
+    }
+}
+
+fn lazy_repeat(x: Arc<Value>, times: Option<i32>) -> LazySeq {
+    LazySeq::new(move || match times {
+        Some(times) if times <= 0 => Ok(Step::Done),
+        _ => Ok(Step::Next(
+            Arc::clone(&x),
+            Arc::new(lazy_repeat(x, times.map(|times| times - 1)).into_value()),
+        )),
+    })
+}
@@ -0,0 +1,63 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq::{LazySeq, Step};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (repeatedly f) (repeatedly n f)
+///
+/// A lazy seq of what calling f with no args returns,  n times or forever;  f being called
+/// as each element is realized
+#[derive(Debug, Clone)]
+pub struct RepeatedlyFn {}
+impl ToValue for RepeatedlyFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RepeatedlyFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (times, f) = match &args[..] {
+            [f] => (None, f),
+            [n, f] => match &**n {
+                Value::I32(n) => (Some(*n), f),
+                _ => return error_message::type_mismatch(TypeTag::Integer, n),
+            },
+            _ => return error_message::wrong_varg_count(&[1, 2], args.len()),
+        };
+        if !ifn::is_callable(f) {
+            return error_message::type_mismatch(TypeTag::IFn, f);
+        }
+        lazy_repeatedly(Arc::clone(f), times).into_value()
+    }
+}
+
+fn lazy_repeatedly(f: Arc<Value>, times: Option<i32>) -> LazySeq {
+    LazySeq::new(move || match times {
+        Some(times) if times <= 0 => Ok(Step::Done),
+        _ => match ifn::call(&f, vec![]) {
+            Value::Condition(condition) => Err(Value::Condition(condition)),
+            val => Ok(Step::Next(
+                Arc::new(val),
+                Arc::new(lazy_repeatedly(f, times.map(|times| times - 1)).into_value()),
+            )),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    mod repeatedly_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn f_is_called_for_each_element() {
+            let result = Repl::default().eval_all(&[
+                "(def n (atom 0))",
+                "[(repeatedly 3 (fn [] (swap! n inc))) (take 2 (repeatedly (fn [] (swap! n inc)))) (repeatedly 0 rand)]",
+            ]);
+            assert_eq!("[(1 2 3) (4 5) ()]", result.to_string());
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_list::PersistentList;
+use crate::rust_core::load::{is_loading, load, root_resource};
+use crate::rust_core::refer::refer;
+use crate::rust_core::the_ns::the_ns;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+/// (require & libs)
+///
+/// Loads each lib,  a namespace's symbol,  from its file;  my.app-util from my/app_util.clj,
+/// found as load finds it.  A lib is only loaded the first time it's required,  or not at
+/// all if its namespace is already there,  unless :reload is given too.  Libs that require
+/// each other,  however far down,  are a condition naming the files that do
+///
+/// A lib can also be [lib :as alias :refer [names]],  to make alias another name for it in
+/// the current namespace,  and have names (or with :refer :all,  all of its public ones)
+/// resolve to its vars there too;  or (prefix lib..),  for prefix.lib..
+#[derive(Debug, Clone)]
+pub struct RequireFn {
+    enclosing_environment: Arc<Environment>,
+    // The libs required so far
+    loaded: Arc<RwLock<HashSet<Symbol>>>,
+}
+impl RequireFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> RequireFn {
+        RequireFn {
+            enclosing_environment,
+            loaded: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+    /// Requires each of libs;  referring all of each,  as use does,  if using
+    pub(crate) fn require(&self, args: &[Arc<Value>], using: bool) -> Value {
+        let mut libs = vec![];
+        let mut reload = false;
+        for arg in args.iter() {
+            match &**arg {
+                Value::Keyword(flag)
+                    if flag.sym.name == "reload" || flag.sym.name == "reload-all" =>
+                {
+                    reload = true
+                }
+                Value::Keyword(flag) => {
+                    return Value::Condition(format!("Unsupported option {}", flag).into())
+                }
+                _ => match libspecs(arg) {
+                    Ok(specs) => libs.extend(specs),
+                    Err(condition) => return condition,
+                },
+            }
+        }
+        for (lib, options) in libs {
+            let required = self
+                .load(&lib, reload)
+                .and_then(|()| self.refer(&lib, &options, using));
+            if let Err(condition) = required {
+                return condition;
+            }
+        }
+        Value::Nil
+    }
+    fn load(&self, lib: &Symbol, reload: bool) -> Result<(), Value> {
+        // A namespace made some other way than by loading its file,  as at the REPL,  is
+        // there already;  but one whose file is still being loaded is a cycle,  to load
+        // again and be told so
+        let path = root_resource(lib);
+        let loaded = self.loaded.read().unwrap().contains(lib)
+            || (self.enclosing_environment.find_namespace(lib).is_some() && !is_loading(&path));
+        if loaded && !reload {
+            return Ok(());
+        }
+        if let condition @ Value::Condition(_) = load(&self.enclosing_environment, &path) {
+            return Err(condition);
+        }
+        self.loaded.write().unwrap().insert(Symbol::clone(lib));
+        Ok(())
+    }
+    // Makes lib's alias,  and refers what options say to,  in the current namespace
+    fn refer(&self, lib: &Symbol, options: &[Arc<Value>], using: bool) -> Result<(), Value> {
+        let environment = &self.enclosing_environment;
+        let mut referring = using;
+        let mut filters = vec![];
+        for option in options.chunks(2) {
+            let (key, val) = match option {
+                [key, val] => (key, val),
+                _ => {
+                    return Err(Value::Condition(
+                        format!("{} needs a value", option[0]).into(),
+                    ))
+                }
+            };
+            let key_name = match &**key {
+                Value::Keyword(key) => key.sym.name.as_str(),
+                _ => return Err(error_message::type_mismatch(TypeTag::Keyword, key)),
+            };
+            match (key_name, &**val) {
+                ("as", Value::Symbol(alias)) => {
+                    let namespace = the_ns(environment, &lib.to_value())?;
+                    environment
+                        .find_or_create_namespace(&environment.get_current_namespace())
+                        .add_alias(alias, &namespace.name);
+                }
+                ("as", _) => return Err(error_message::type_mismatch(TypeTag::Symbol, val)),
+                ("refer", Value::Keyword(all)) if !using && all.sym.name == "all" => {
+                    referring = true
+                }
+                ("refer", _) if !using => {
+                    referring = true;
+                    filters.push(Keyword::intern("only").to_rc_value());
+                    filters.push(Arc::clone(val));
+                }
+                ("only", _) | ("exclude", _) if using => {
+                    filters.extend([Arc::clone(key), Arc::clone(val)])
+                }
+                ("rename", _) => filters.extend([Arc::clone(key), Arc::clone(val)]),
+                _ => {
+                    return Err(Value::Condition(
+                        format!("Unsupported option {}", key).into(),
+                    ))
+                }
+            }
+        }
+        if !referring {
+            return Ok(());
+        }
+        let namespace = the_ns(environment, &lib.to_value())?;
+        refer(environment, &namespace, &filters)
+    }
+}
+impl ToValue for RequireFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RequireFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        self.require(&args, false)
+    }
+}
+
+// A lib,  with the options given for it
+type Libspec = (Symbol, Vec<Arc<Value>>);
+
+// Each lib arg names,  with its options;  it's a lib,  [lib & options] or (prefix & libs),
+// each of which is prefix.lib
+fn libspecs(arg: &Arc<Value>) -> Result<Vec<Libspec>, Value> {
+    let items = match &**arg {
+        Value::Symbol(lib) => return Ok(vec![(Symbol::clone(lib), vec![])]),
+        Value::PersistentVector(spec) => spec.iter().cloned().collect::<Vec<Arc<Value>>>(),
+        Value::PersistentList(prefix_list) => PersistentList::iter(prefix_list).collect(),
+        _ => return Err(error_message::type_mismatch(TypeTag::Symbol, arg)),
+    };
+    let (lib, rest) = match items.split_first() {
+        Some((lib, rest)) => (lib_symbol(lib)?, rest),
+        None => return Err(error_message::type_mismatch(TypeTag::Symbol, arg)),
+    };
+    if let Value::PersistentVector(_) = &**arg {
+        return Ok(vec![(lib, rest.to_vec())]);
+    }
+    let mut specs = vec![];
+    for spec in rest {
+        for (suffix, options) in libspecs(spec)? {
+            specs.push((Symbol::intern(&format!("{}.{}", lib, suffix)), options));
+        }
+    }
+    Ok(specs)
+}
+
+fn lib_symbol(lib: &Arc<Value>) -> Result<Symbol, Value> {
+    match &**lib {
+        Value::Symbol(lib) => Ok(Symbol::clone(lib)),
+        _ => Err(error_message::type_mismatch(TypeTag::Symbol, lib)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use std::fs;
+
+    #[test]
+    fn libs_are_loaded_once_unless_reloaded() {
+        let dir = std::env::temp_dir().join("clojure_rs_require_test");
+        fs::create_dir_all(dir.join("app")).unwrap();
+        fs::write(
+            dir.join("app/main_lib.clj"),
+            "(swap! loads conj :main) (require 'app.util)",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("app/util.clj"),
+            "(swap! loads conj :util) (load \"/app/more\")",
+        )
+        .unwrap();
+        fs::write(dir.join("app/more.clj"), "(swap! loads conj :more)").unwrap();
+        let result = Repl::default().eval_all(&[
+            &format!(
+                "(def clojure.core/*load-path* [\"{}\"])",
+                dir.to_string_lossy()
+            ),
+            "(def loads (atom []))",
+            "(require 'app.main-lib)",
+            "(require 'app.main-lib 'app.util)",
+            "(require 'app.util :reload)",
+            "(load \"/app/more\")",
+            "[@loads (ex-message (try (require 'app.missing) (catch Exception e e)))]",
+        ]);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            "[[:main :util :more :util :more :more] \"Could not locate app/missing.clj on *load-path*\"]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn circular_requires_name_the_cycle() {
+        let dir = std::env::temp_dir().join("clojure_rs_circular_require_test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.clj"), "(require 'b)").unwrap();
+        fs::write(dir.join("b.clj"), "(require 'c)").unwrap();
+        fs::write(dir.join("c.clj"), "(require 'a)").unwrap();
+        fs::write(dir.join("d.clj"), "(def d-loaded true)").unwrap();
+        let result = Repl::default().eval_all(&[
+            &format!(
+                "(def clojure.core/*load-path* [\"{}\"])",
+                dir.to_string_lossy()
+            ),
+            "(def cycle (ex-message (try (require 'b) (catch Exception e e))))",
+            "(require 'd)",
+            "[cycle d-loaded]",
+        ]);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            "[\"Cyclic load dependency: [ /b ]->/c->/a->[ /b ]\" true]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn circular_requires_between_ns_forms_name_the_cycle() {
+        let dir = std::env::temp_dir().join("clojure_rs_circular_ns_require_test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.clj"), "(ns a (:require b))").unwrap();
+        fs::write(dir.join("b.clj"), "(ns b (:require c))").unwrap();
+        fs::write(dir.join("c.clj"), "(ns c) (require 'a)").unwrap();
+        fs::write(dir.join("d.clj"), "(def d-loaded true)").unwrap();
+        let result = Repl::default().eval_all(&[
+            &format!(
+                "(def clojure.core/*load-path* [\"{}\"])",
+                dir.to_string_lossy()
+            ),
+            "(def cycle (ex-message (try (require 'b) (catch Exception e e))))",
+            "(require 'd)",
+            "[cycle d-loaded]",
+        ]);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            "[\"Cyclic load dependency: [ /b ]->/c->/a->[ /b ]\" true]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn required_namespaces_can_be_aliased_and_referred() {
+        let dir = std::env::temp_dir().join("clojure_rs_require_refer_test");
+        fs::create_dir_all(dir.join("app")).unwrap();
+        fs::write(
+            dir.join("app/util.clj"),
+            "(ns app.util) (defn twice [x] (* 2 x)) (defn thrice [x] (* 3 x))",
+        )
+        .unwrap();
+        fs::write(dir.join("app/db.clj"), "(ns app.db) (def table :users)").unwrap();
+        let result = Repl::default().eval_all(&[
+            &format!(
+                "(def clojure.core/*load-path* [\"{}\"])",
+                dir.to_string_lossy()
+            ),
+            "(require '[app.util :as u :refer [twice]] '(app [db :refer :all]))",
+            "(require '[app.util :refer [thrice] :rename {thrice three-times}])",
+            "[(twice 1) (u/thrice 1) (three-times 2) table]",
+        ]);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!("[2 3 6 :users]", result.to_string());
+    }
+}
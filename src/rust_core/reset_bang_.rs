@@ -0,0 +1,30 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (reset! atom x)
+///
+/// Sets atom to x,  whatever it was;  returns x
+#[derive(Debug, Clone)]
+pub struct ResetBangFn {}
+impl ToValue for ResetBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ResetBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match &*args[0] {
+            Value::Atom(atom) => match atom.reset(&args[0], Arc::clone(&args[1])) {
+                Ok(_) => args[1].to_value(),
+                Err(condition) => condition,
+            },
+            _ => error_message::type_mismatch(TypeTag::Atom, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::the_ns::the_ns;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (resolve sym)
+///
+/// The var sym names in the current namespace,  or nil if it names none
+#[derive(Debug, Clone)]
+pub struct ResolveFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl ResolveFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> ResolveFn {
+        ResolveFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for ResolveFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ResolveFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Symbol(sym) => self
+                .enclosing_environment
+                .resolve_var(sym)
+                .map_or(Value::Nil, |var| Value::Var(Arc::new(var))),
+            _ => error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        }
+    }
+}
+
+/// (ns-resolve ns sym)
+///
+/// The var sym names in ns,  a namespace or the symbol naming one;  or nil if it names none
+#[derive(Debug, Clone)]
+pub struct NsResolveFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl NsResolveFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> NsResolveFn {
+        NsResolveFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for NsResolveFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for NsResolveFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let namespace = match the_ns(&self.enclosing_environment, &args[0]) {
+            Ok(namespace) => namespace,
+            Err(condition) => return condition,
+        };
+        match &*args[1] {
+            Value::Symbol(sym) => self
+                .enclosing_environment
+                .resolve_var_in(&namespace.name, sym)
+                .map_or(Value::Nil, |var| Value::Var(Arc::new(var))),
+            _ => error_message::type_mismatch(TypeTag::Symbol, &args[1]),
+        }
+    }
+}
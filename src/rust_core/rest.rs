@@ -0,0 +1,116 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{self, Step};
+use crate::persistent_list::PersistentList;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (rest coll)
+///
+/// The elements of coll after the first,  or () if there are none.  Realizes only the first
+/// element of a lazy seq
+#[derive(Debug, Clone)]
+pub struct RestFn {}
+impl ToValue for RestFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RestFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match lazy_seq::step(&args[0]) {
+            Ok(Step::Next(_, rest)) => match &*rest {
+                Value::LazySeq(_) | Value::PersistentList(_) => rest.to_value(),
+                // The rest of a cons onto some other collection
+                _ => match seq_of(&rest) {
+                    Value::Nil => PersistentList::Empty.to_value(),
+                    seq => seq,
+                },
+            },
+            Ok(Step::Done) => PersistentList::Empty.to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// The seq of coll;  nil if coll is empty,  or else a list or lazy seq of its elements
+pub(crate) fn seq_of(coll: &Arc<Value>) -> Value {
+    match lazy_seq::step(coll) {
+        Ok(Step::Done) => Value::Nil,
+        Ok(Step::Next(..)) => match &**coll {
+            Value::LazySeq(_) | Value::PersistentList(_) => coll.to_value(),
+            _ => lazy_seq::iter(Arc::clone(coll))
+                .collect::<PersistentList>()
+                .to_value(),
+        },
+        Err(condition) => condition,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod rest_tests {
+        use crate::ifn::IFn;
+        use crate::persistent_list::PersistentList;
+        use crate::persistent_vector::PersistentVector;
+        use crate::repl::Repl;
+        use crate::rust_core::{ConsFn, FirstFn, RestFn, SeqFn};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn vector(vals: &[i32]) -> Arc<Value> {
+            vals.iter()
+                .map(|i| i.to_rc_value())
+                .collect::<PersistentVector>()
+                .to_rc_value()
+        }
+
+        #[test]
+        fn first_and_rest_of_a_vector() {
+            assert_eq!(Value::I32(1), FirstFn {}.invoke(vec![vector(&[1, 2, 3])]));
+            assert_eq!(
+                "(2 3)",
+                RestFn {}.invoke(vec![vector(&[1, 2, 3])]).to_string()
+            );
+        }
+
+        #[test]
+        fn nothing_left_is_nil_or_empty() {
+            assert_eq!(Value::Nil, FirstFn {}.invoke(vec![vector(&[])]));
+            assert_eq!(
+                PersistentList::Empty.to_value(),
+                RestFn {}.invoke(vec![Arc::new(Value::Nil)])
+            );
+            assert_eq!(Value::Nil, SeqFn {}.invoke(vec![vector(&[])]));
+        }
+
+        #[test]
+        fn cons_onto_a_vector() {
+            let consed = ConsFn {}.invoke(vec![Arc::new(Value::I32(0)), vector(&[1, 2])]);
+            assert_eq!("(0 1 2)", consed.to_string());
+            assert_eq!(
+                "(1 2)",
+                RestFn {}.invoke(vec![Arc::new(consed)]).to_string()
+            );
+        }
+
+        #[test]
+        fn strings_maps_and_sets_are_seqable_too() {
+            let result = Repl::default().eval_all(&[
+                "[(seq \"ab\") (rest \"ab\") (seq \"\") (first (sorted-map :a 1)) (seq (sorted-set 2 1)) (cons 0 \"a\") (first nil)]",
+            ]);
+            assert_eq!(
+                "[(\\a \\b) (\\b) nil [:a 1] (1 2) (0 \\a) nil]",
+                result.to_string_explicit()
+            );
+            let result = SeqFn {}.invoke(vec![Arc::new(Value::I32(5))]);
+            assert_eq!(
+                Value::Condition("Don't know how to create ISeq from: rust.std.i32".into()),
+                result
+            );
+        }
+    }
+}
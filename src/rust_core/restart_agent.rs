@@ -0,0 +1,50 @@
+use crate::agent;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::type_tag::TypeTag;
+use crate::util::IsEven;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (restart-agent agent x & options)
+///
+/// Clears a failed agent's error and sets its value to x;  then runs the actions it held
+/// on to while failed,  unless given :clear-actions true.  Returns x
+#[derive(Debug, Clone)]
+pub struct RestartAgentFn {}
+impl ToValue for RestartAgentFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RestartAgentFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 || !args.len().is_even() {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to restart-agent (Given: {}, Expected: an agent, a value, then option pairs)",
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let agent = match &*args[0] {
+            Value::Agent(agent) => agent,
+            _ => return error_message::type_mismatch(TypeTag::Agent, &args[0]),
+        };
+        let mut clear_actions = false;
+        for option in args[2..].chunks(2) {
+            if *option[0] != Keyword::intern("clear-actions").to_value() {
+                return Value::Condition(
+                    format!("Unknown restart-agent option {}", option[0]).into(),
+                );
+            }
+            clear_actions = option[1].is_truthy();
+        }
+        match agent::restart(agent, Arc::clone(&args[1]), clear_actions) {
+            Ok(()) => args[1].to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq;
+use crate::persistent_list::ToPersistentList;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (reverse coll)
+///
+/// The elements of coll,  last first,  as a list;  a string's are its chars
+#[derive(Debug, Clone)]
+pub struct ReverseFn {}
+impl ToValue for ReverseFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ReverseFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let mut reversed = vec![];
+        if let Err(condition) = lazy_seq::try_for_each(&args[0], |val| {
+            reversed.push(val);
+            Ok(())
+        }) {
+            return condition;
+        }
+        reversed
+            .into_iter()
+            .rev()
+            .collect::<Vec<_>>()
+            .into_list()
+            .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod reverse_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn reverse_reverses_chars_and_elements() {
+            let result = Repl::default()
+                .eval_all(&[r#"[(reverse "héllo") (reverse [1 2 3]) (reverse nil)]"#]);
+            assert_eq!(r#"[(\o \l \l \é \h) (3 2 1) ()]"#, result.to_string());
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::ToPersistentVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (rseq coll)
+///
+/// The elements of a vector or sorted collection, last first;  nil if there are none.
+/// Takes no more time than walking coll forwards would
+#[derive(Debug, Clone)]
+pub struct RseqFn {}
+impl ToValue for RseqFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RseqFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let reversed: Vec<Arc<Value>> = match &*args[0] {
+            Value::PersistentVector(pvector) => pvector.iter().rev().cloned().collect(),
+            Value::PersistentTreeSet(ptset) => ptset.rev_iter().collect(),
+            Value::PersistentTreeMap(ptmap) => ptmap
+                .rev_iter()
+                .map(|entry| vec![entry.key, entry.val].into_vector().to_rc_value())
+                .collect(),
+            _ => return error_message::type_mismatch(TypeTag::Sequential, &args[0]),
+        };
+        if reversed.is_empty() {
+            Value::Nil
+        } else {
+            reversed.into_list().to_value()
+        }
+    }
+}
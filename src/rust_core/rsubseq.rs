@@ -0,0 +1,21 @@
+use crate::ifn::IFn;
+use crate::rust_core::subseq::subseq;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (rsubseq sc test key)
+/// (rsubseq sc start-test start-key end-test end-key)
+///
+/// Like subseq,  but in reverse order
+#[derive(Debug, Clone)]
+pub struct RsubseqFn {}
+impl ToValue for RsubseqFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RsubseqFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        subseq(&args, false)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (run! proc coll)
+///
+/// Calls proc with each element of coll,  for its effects;  returns nil.  Stops early if
+/// proc gives (reduced val),  as reduce would.  A condition from proc is raised
+///
+/// (run! #(.close %) conns)
+#[derive(Debug, Clone)]
+pub struct RunBangFn {}
+impl ToValue for RunBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for RunBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let proc = &args[0];
+        if !ifn::is_callable(proc) {
+            return error_message::type_mismatch(TypeTag::IFn, proc);
+        }
+        // Stopped early by (reduced val),  passed on as an Err,  as a condition is
+        let walked = lazy_seq::try_for_each(&args[1], |x| match ifn::call(proc, vec![x]) {
+            Value::Reduced(val) => Err(Value::Reduced(val)),
+            Value::Condition(condition) => Err(Value::Condition(condition)),
+            _ => Ok(()),
+        });
+        match walked {
+            Err(Value::Condition(condition)) => Value::Condition(condition),
+            _ => Value::Nil,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    #[test]
+    fn run_bang_calls_proc_with_each_element() {
+        let result = Repl::default().eval_all(&[
+            "(def b (sb))",
+            "[(run! #(.append b %) (range 4)) (run! #(when (= % 2) (reduced %)) (range)) (str b)]",
+        ]);
+        assert_eq!("[nil nil \"0123\"]", result.to_string());
+    }
+
+    #[test]
+    fn a_condition_from_proc_is_raised() {
+        let result = Repl::default().eval_all(&["(run! #(/ 1 %) [1 0])"]);
+        assert!(matches!(result, Value::Condition(_)));
+    }
+}
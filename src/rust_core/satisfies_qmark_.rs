@@ -0,0 +1,27 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (satisfies? protocol x)
+///
+/// Whether x's type,  or one it belongs to,  implements protocol
+#[derive(Debug, Clone)]
+pub struct SatisfiesFn {}
+impl ToValue for SatisfiesFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SatisfiesFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match &*args[0] {
+            Value::Protocol(protocol) => Value::Boolean(protocol.is_satisfied_by(&args[1])),
+            _ => error_message::type_mismatch(TypeTag::Protocol, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::string_builder::StringBuilder;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (sb) (sb s)
+///
+/// A new string builder,  holding s to start with if it's given
+#[derive(Debug, Clone)]
+pub struct SbFn {}
+impl ToValue for SbFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SbFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() > 1 {
+            return error_message::wrong_varg_count(&[0, 1], args.len());
+        }
+        let builder = StringBuilder::default();
+        if let Some(s) = args.first() {
+            builder.append(s);
+        }
+        Value::host_object(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn a_builder_holds_what_was_appended() {
+        let result = Repl::default().eval_all(&[
+            "(def b (sb \"a\"))",
+            "(dotimes [i 3] (sb-append! b i \"-\"))",
+            "(.append b nil)",
+            "(.append b :x)",
+            "[(sb-str b) (.length b) (str b) (= (sb-str (sb)) \"\")]",
+        ]);
+        assert_eq!(
+            "[\"a0-1-2-nil:x\" 12 \"a0-1-2-nil:x\" true]",
+            result.to_string_explicit()
+        );
+    }
+
+    #[test]
+    fn only_builders_can_be_appended_to() {
+        let result = Repl::default().eval_all(&["(sb-append! \"a\" \"b\")"]);
+        assert!(matches!(result, crate::value::Value::Condition(_)));
+    }
+}
@@ -0,0 +1,31 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::string_builder;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (sb-append! b & xs)
+///
+/// Appends what str makes of each of xs to the string builder b;  returns b
+#[derive(Debug, Clone)]
+pub struct SbAppendBangFn {}
+impl ToValue for SbAppendBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SbAppendBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let builder = match string_builder::builder(&args[0]) {
+            Ok(builder) => builder,
+            Err(condition) => return condition,
+        };
+        for x in args[1..].iter() {
+            builder.append(x);
+        }
+        args[0].to_value()
+    }
+}
@@ -0,0 +1,27 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::string_builder;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (sb-str b)
+///
+/// The string the string builder b holds so far
+#[derive(Debug, Clone)]
+pub struct SbStrFn {}
+impl ToValue for SbStrFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SbStrFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match string_builder::builder(&args[0]) {
+            Ok(builder) => Value::String(builder.text().into()),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::timer;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (schedule! ms f)
+///
+/// Calls f,  with no arguments,  ms milliseconds from now,  on the timer thread (see
+/// timer.rs);  a task,  that cancel! stops f being called if it hasn't been yet
+#[derive(Debug, Clone)]
+pub struct ScheduleBangFn {}
+impl ToValue for ScheduleBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ScheduleBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let delay = match timer::millis(&args[0]) {
+            Ok(delay) => delay,
+            Err(condition) => return condition,
+        };
+        if !ifn::is_callable(&args[1]) {
+            return error_message::type_mismatch(TypeTag::IFn, &args[1]);
+        }
+        Value::HostObject(Arc::new(timer::schedule(Arc::clone(&args[1]), delay, None)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn the_fn_is_called_once_its_time_comes_unless_cancelled() {
+        let result = Repl::default().eval_all(&[
+            "(def calls (atom []))",
+            "(def later (schedule! 20 (fn [] (swap! calls conj :later))))",
+            "(def never (schedule! 20 (fn [] (swap! calls conj :never))))",
+            "(def cancelled (cancel! never))",
+            "(schedule! 0 (fn [] (swap! calls conj :now)))",
+            "(sleep 200)",
+            "[@calls cancelled (cancel! never) (cancel! later) (str later)]",
+        ]);
+        assert_eq!(
+            "[[:now :later] true false false \"#task[{:status :done}]\"]",
+            result.to_string()
+        );
+    }
+}
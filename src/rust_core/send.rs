@@ -0,0 +1,58 @@
+use crate::agent;
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (send agent f & args)
+///
+/// Has the agent set itself to (apply f its-value args),  after any actions already sent
+/// to it;  returns the agent
+#[derive(Debug, Clone)]
+pub struct SendFn {}
+impl ToValue for SendFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SendFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        send(args)
+    }
+}
+
+/// (send-off agent f & args)
+///
+/// In Clojure,  send-off is for actions that block (on io, say),  and runs them on a pool of
+/// its own.  Our actions all run on the thread that sent them,  so it's the same as send
+#[derive(Debug, Clone)]
+pub struct SendOffFn {}
+impl ToValue for SendOffFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SendOffFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        send(args)
+    }
+}
+
+fn send(args: Vec<Arc<Value>>) -> Value {
+    if args.len() < 2 {
+        return error_message::wrong_arg_count(2, args.len());
+    }
+    let agent = match &*args[0] {
+        Value::Agent(agent) => agent,
+        _ => return error_message::type_mismatch(TypeTag::Agent, &args[0]),
+    };
+    let f = match ifn::as_ifn(&args[1]) {
+        Some(ifn) => ifn,
+        None => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+    };
+    match agent::send(agent, f, args[2..].to_vec()) {
+        Ok(()) => args[0].to_value(),
+        Err(condition) => condition,
+    }
+}
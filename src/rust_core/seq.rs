@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::rest::seq_of;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (seq coll)
+///
+/// The elements of coll as a seq,  or nil if there are none
+#[derive(Debug, Clone)]
+pub struct SeqFn {}
+impl ToValue for SeqFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SeqFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        seq_of(&args[0])
+    }
+}
@@ -0,0 +1,40 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{self, LazySeq};
+use crate::persistent_list::PersistentList;
+use crate::transducer;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (sequence coll) (sequence xform coll & colls)
+///
+/// coll as a seq;  () rather than nil if it's empty.  With xform,  a lazy seq of what it
+/// makes of coll's elements;  or given more colls,  of the first element of each,  then the
+/// second of each and so on,  all at once,  as (map f) takes them
+#[derive(Debug, Clone)]
+pub struct SequenceFn {}
+impl ToValue for SequenceFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SequenceFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match &args[..] {
+            [coll] => match &**coll {
+                Value::Nil => PersistentList::Empty.to_value(),
+                Value::PersistentList(_) | Value::LazySeq(_) => coll.to_value(),
+                _ => {
+                    let coll = Arc::clone(coll);
+                    LazySeq::new(move || lazy_seq::step(&coll)).into_value()
+                }
+            },
+            [xform, colls @ ..] if !colls.is_empty() => match &**xform {
+                Value::IFn(xform) => transducer::sequence_of(&***xform, colls.to_vec()),
+                _ => error_message::type_mismatch(TypeTag::IFn, xform),
+            },
+            _ => error_message::wrong_varg_count(&[1, 2], args.len()),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (set coll)
+///
+/// A set of the distinct elements of coll;  built up in place,  as into does
+#[derive(Debug, Clone)]
+pub struct SetFn {}
+impl ToValue for SetFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SetFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let mut set = PersistentHashSet::empty();
+        let built = lazy_seq::try_for_each(&args[0], |val| {
+            set.conj_in_place(val);
+            Ok(())
+        });
+        match built {
+            Ok(()) => set.to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::dynamic;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (set!* var val)
+///
+/// Sets var to val in the innermost binding of it;  giving val
+#[derive(Debug, Clone)]
+pub struct SetBangFn {}
+impl ToValue for SetBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SetBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match &*args[0] {
+            Value::Var(var) => match dynamic::set(&var.qualified(), Arc::clone(&args[1])) {
+                Ok(()) => (*args[1]).clone(),
+                Err(condition) => condition,
+            },
+            _ => error_message::type_mismatch(TypeTag::Var, &args[0]),
+        }
+    }
+}
+
+/// (set! name val)
+///
+/// (set! a 1) expands to
+///
+/// (set!* (var a) 1)
+#[derive(Debug, Clone)]
+pub struct SetBangMacro {}
+impl ToValue for SetBangMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for SetBangMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        vec![
+            Symbol::intern("set!*").to_rc_value(),
+            vec![Symbol::intern("var").to_rc_value(), Arc::clone(&args[0])]
+                .into_list()
+                .to_rc_value(),
+            Arc::clone(&args[1]),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
@@ -0,0 +1,70 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::rand::set_seed;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (set-random-seed! n) or (set-random-seed! nil)
+///
+/// Seeds the generator behind rand, rand-int, shuffle, random-sample and random-uuid,
+/// so the same seed replays the same sequence.  nil goes back to an unpredictable seed
+#[derive(Debug, Clone)]
+pub struct SetRandomSeedFn {}
+impl ToValue for SetRandomSeedFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SetRandomSeedFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::I32(seed) => set_seed(Some(*seed as u64)),
+            Value::Nil => set_seed(None),
+            _ => return error_message::type_mismatch(TypeTag::I32, &args[0]),
+        }
+        Value::Nil
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod set_random_seed_tests {
+        use crate::ifn::IFn;
+        use crate::rust_core::{RandFn, RandIntFn, SetRandomSeedFn};
+        use crate::value::Value;
+        use std::sync::Arc;
+
+        fn draws() -> Vec<Value> {
+            (0..5)
+                .map(|_| RandIntFn {}.invoke(vec![Arc::new(Value::I32(1000))]))
+                .chain((0..5).map(|_| RandFn {}.invoke(vec![])))
+                .collect()
+        }
+
+        #[test]
+        fn same_seed_same_draws() {
+            SetRandomSeedFn {}.invoke(vec![Arc::new(Value::I32(42))]);
+            let first = draws();
+            SetRandomSeedFn {}.invoke(vec![Arc::new(Value::I32(42))]);
+            assert_eq!(first, draws());
+        }
+
+        #[test]
+        fn different_seed_different_draws() {
+            SetRandomSeedFn {}.invoke(vec![Arc::new(Value::I32(1))]);
+            let first = draws();
+            SetRandomSeedFn {}.invoke(vec![Arc::new(Value::I32(2))]);
+            assert_ne!(first, draws());
+        }
+
+        #[test]
+        fn seed_must_be_an_int() {
+            let result = SetRandomSeedFn {}.invoke(vec![Arc::new(Value::String("42".into()))]);
+            assert!(matches!(result, Value::Condition(_)));
+        }
+    }
+}
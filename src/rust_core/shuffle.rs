@@ -0,0 +1,36 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::iterable::Iterable;
+use crate::persistent_vector::PersistentVector;
+use crate::protocol::ProtocolCastable;
+use crate::rust_core::rand::with_rng;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use rand::seq::SliceRandom;
+use std::sync::Arc;
+
+/// (shuffle coll)
+///
+/// Returns a random permutation of coll as a vector
+#[derive(Debug, Clone)]
+pub struct ShuffleFn {}
+impl ToValue for ShuffleFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ShuffleFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match args[0].try_as_protocol::<Iterable>() {
+            Some(iterable) => {
+                let mut vals = iterable.iter().collect::<Vec<Arc<Value>>>();
+                with_rng(|rng| vals.shuffle(rng));
+                PersistentVector::from(vals).to_value()
+            }
+            None => error_message::type_mismatch(TypeTag::ISeq, &args[0]),
+        }
+    }
+}
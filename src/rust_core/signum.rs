@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (signum a)
+///
+/// -1,  0 or 1,  by a's sign;  -1.0,  0.0 or 1.0 if a's a float
+#[derive(Debug, Clone)]
+pub struct SignumFn {}
+impl ToValue for SignumFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SignumFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        numbers::signum(&args[0])
+    }
+}
@@ -0,0 +1,44 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::interrupt;
+use crate::timer;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How long a sleep goes without checking whether it's been interrupted
+const SLICE: Duration = Duration::from_millis(10);
+
+/// (sleep ms)
+///
+/// Waits ms milliseconds (any number,  not negative);  nil.  Unlike Thread/sleep,  it can
+/// be interrupted,  and the fns on-signal was given are called while it waits
+#[derive(Debug, Clone)]
+pub struct SleepFn {}
+impl ToValue for SleepFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SleepFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let until = match timer::millis(&args[0]) {
+            Ok(ms) => Instant::now() + ms,
+            Err(condition) => return condition,
+        };
+        loop {
+            if interrupt::is_interrupted() {
+                return Value::Condition(interrupt::INTERRUPTED_MSG.into());
+            }
+            let now = Instant::now();
+            if now >= until {
+                return Value::Nil;
+            }
+            thread::sleep(SLICE.min(until - now));
+        }
+    }
+}
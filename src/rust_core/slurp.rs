@@ -1,10 +1,7 @@
+use crate::clojure_java_io;
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use nom::lib::std::convert::TryFrom;
-use std::rc::Rc;
-
-use std::fs::File;
-use std::io::Read;
+use std::sync::Arc;
 
 use reqwest;
 use url::Url;
@@ -13,54 +10,51 @@ use crate::error_message;
 
 /// (slurp f & opts)
 ///
-/// * Read a file provided (filename string) into a string (slurp "text.txt")
+/// * Read a file provided (filename string,  or file) into a string (slurp "text.txt")
+/// * Read what's left of a reader into a string (slurp rdr)
 /// * GET an URL into a string (slurp "http://www.example.com")
-/// TODO: clojure.java.io works with following types: Reader, BufferedReader,
-/// TODO: InputStream, File, URI, URL, Socket, byte arrays, character arrays,
-/// TODO and String
+///
+/// A file's read as UTF-8,  or as :encoding says;  ie (slurp "text.txt" :encoding "latin1").
+/// See slurp-bytes,  for what isn't text
+/// TODO: clojure.java.io works with following types: InputStream, URI, URL, Socket,
+/// TODO: byte arrays and character arrays
 /// TODO local file URIs
 ///
 #[derive(Debug, Clone)]
 pub struct SlurpFn {}
 impl ToValue for SlurpFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 
 impl IFn for SlurpFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        if !args.is_empty() {
-            let first_arg = &args.into_iter().next().unwrap().to_string();
-
-            let possible_url = Url::parse(first_arg);
-
-            match possible_url {
-                Ok(url) => {
-                    let rslt = reqwest::blocking::get(url.as_str());
-                    match rslt {
-                        Ok(res) => Value::String(res.text().unwrap()),
-                        Err(e) => error_message::generic_err(Box::try_from(e).unwrap()),
-                    }
-                }
-                Err(_) => {
-                    // try to find a file, if url parsing fails
-                    let filename = first_arg;
-                    let file_descriptor = File::open(filename);
-
-                    let mut f = match file_descriptor {
-                        Ok(file) => file,
-                        Err(e) => {
-                            return error_message::generic_err(Box::try_from(e).unwrap());
-                        }
-                    };
-                    let mut s = String::new();
-                    f.read_to_string(&mut s).unwrap();
-                    Value::String(s)
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], 0);
+        }
+        let options = match clojure_java_io::options("slurp", &args[1..]) {
+            Ok(options) => options,
+            Err(condition) => return condition,
+        };
+        let possible_url = match &*args[0] {
+            Value::String(s) => Url::parse(s).ok(),
+            _ => None,
+        };
+        match possible_url {
+            Some(url) => {
+                // A failed request,  or a body that isn't text,  is a condition rather than
+                // a panic
+                match reqwest::blocking::get(url.as_str()).and_then(|res| res.text()) {
+                    Ok(text) => Value::String(text.into()),
+                    Err(e) => error_message::generic_err(Box::new(e)),
                 }
             }
-        } else {
-            Value::Nil
+            // try to find a file, if url parsing fails
+            None => match clojure_java_io::read_all(&args[0], options.encoding) {
+                Ok(s) => Value::String(s.into()),
+                Err(condition) => condition,
+            },
         }
     }
 }
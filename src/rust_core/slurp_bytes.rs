@@ -0,0 +1,40 @@
+use crate::clojure_java_io;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+use url::Url;
+
+/// (slurp-bytes f)
+///
+/// The bytes of a file (a filename string,  or a file),  or of what GETting an URL gives;  as
+/// a byte array,  with nothing decoded
+#[derive(Debug, Clone)]
+pub struct SlurpBytesFn {}
+impl ToValue for SlurpBytesFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SlurpBytesFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let possible_url = match &*args[0] {
+            Value::String(s) => Url::parse(s).ok(),
+            _ => None,
+        };
+        let bytes = match possible_url {
+            Some(url) => match reqwest::blocking::get(url.as_str()).and_then(|res| res.bytes()) {
+                Ok(bytes) => Ok(bytes.to_vec()),
+                Err(e) => return error_message::generic_err(Box::from(e)),
+            },
+            None => clojure_java_io::read_bytes(&args[0]),
+        };
+        match bytes {
+            Ok(bytes) => Value::Bytes(bytes.into()),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (some pred coll)
+///
+/// The first truthy value pred gives for an element of coll,  going no further;  else nil.
+/// A condition from pred is raised,  not taken for a value
+#[derive(Debug, Clone)]
+pub struct SomeFn {}
+impl ToValue for SomeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SomeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let pred = &args[0];
+        if !ifn::is_callable(pred) {
+            return error_message::type_mismatch(TypeTag::IFn, pred);
+        }
+        // Stopped early by the value found,  passed on as an Err,  as a condition is
+        let walked = lazy_seq::try_for_each(&args[1], |x| match ifn::call(pred, vec![x]) {
+            val if val.is_truthy() => Err(val),
+            Value::Condition(condition) => Err(Value::Condition(condition)),
+            _ => Ok(()),
+        });
+        match walked {
+            Ok(()) => Value::Nil,
+            Err(val) => val,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn nil_and_conditions_are_as_in_clojure() {
+        let result = Repl::default().eval_all(&[
+            "(def calls (atom 0))",
+            "(def found (some (fn [x] (do (swap! calls inc) (when (even? x) (* x 10)))) (range 1 100)))",
+            "[found @calls (some :a [{:b 1} {:a false} {:a 2}]) (some even? nil) (ex-message (try (some (fn [x] (/ 1 x)) [0]) (catch Exception e e)))]",
+        ]);
+        assert_eq!("[20 2 2 nil \"Divide by zero\"]", result.to_string());
+        let result = Repl::default().eval_all(&[
+            "[(first nil) (rest nil) (conj nil 1) (get nil :k) (get nil :k 3) (assoc nil :k 1) (count nil) (nth nil 0) (str nil \"a\" nil)]",
+        ]);
+        assert_eq!("[nil () (1) nil 3 {:k 1} 0 nil \"a\"]", result.to_string());
+        let result = Repl::default().eval_all(&[
+            "[(get {:a nil} :a 1) (get [1 2] 1) (get [1 2] 2 :d) (get #{:x} :x) (get 5 :k :d) (nth [1] 3 :d) (= (assoc {} :a 1 :b 2) {:a 1 :b 2})]",
+        ]);
+        assert_eq!("[nil 2 :d :x :d :d true]", result.to_string());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq;
+use crate::persistent_list::PersistentList;
+use crate::persistent_tree_map::natural_order;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// (sort coll) (sort comparator coll)
+///
+/// The elements of coll as a seq,  sorted by comparator (default compare);  either a
+/// compare-style fn, or a predicate like < saying whether its first argument comes first.
+/// The sort is stable,  so equal elements keep their order
+#[derive(Debug, Clone)]
+pub struct SortFn {}
+impl ToValue for SortFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SortFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (comparator, coll) = match &args[..] {
+            [coll] => (None, coll),
+            [comparator, coll] => match as_comparator(comparator) {
+                Ok(comparator) => (Some(comparator), coll),
+                Err(condition) => return condition,
+            },
+            _ => return error_message::wrong_varg_count(&[1, 2], args.len()),
+        };
+        let mut keyed = vec![];
+        let elements = lazy_seq::try_for_each(coll, |val| {
+            keyed.push((Arc::clone(&val), val));
+            Ok(())
+        });
+        if let Err(condition) = elements {
+            return condition;
+        }
+        match sort_keyed(keyed, comparator.as_ref()) {
+            Ok(sorted) => sorted,
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// Shared with sort-by
+pub(crate) fn as_comparator(val: &Value) -> Result<Arc<dyn IFn>, Value> {
+    match val {
+        Value::IFn(ifn) => Ok(Arc::clone(ifn)),
+        _ => Err(error_message::type_mismatch(TypeTag::IFn, val)),
+    }
+}
+
+// How a compares to b;  by comparator as sorted-map-by would,  or else naturally
+fn compare(
+    comparator: Option<&Arc<dyn IFn>>,
+    a: &Arc<Value>,
+    b: &Arc<Value>,
+) -> Result<Ordering, Value> {
+    let comparator = match comparator {
+        Some(comparator) => comparator,
+        None => {
+            return natural_order(a, b).ok_or_else(|| {
+                Value::Condition(
+                    format!("Cannot compare {} with {}", a.type_tag(), b.type_tag()).into(),
+                )
+            })
+        }
+    };
+    match comparator.invoke(vec![Arc::clone(a), Arc::clone(b)]) {
+        Value::I32(n) => Ok(n.cmp(&0)),
+        Value::F64(n) => Ok(n.partial_cmp(&0.0).unwrap_or(Ordering::Equal)),
+        Value::Nil | Value::Boolean(false) => {
+            match comparator.invoke(vec![Arc::clone(b), Arc::clone(a)]) {
+                Value::Condition(condition) => Err(Value::Condition(condition)),
+                Value::Nil | Value::Boolean(false) => Ok(Ordering::Equal),
+                _ => Ok(Ordering::Greater),
+            }
+        }
+        Value::Condition(condition) => Err(Value::Condition(condition)),
+        _ => Ok(Ordering::Less),
+    }
+}
+
+/// Shared with sort-by;  each (key, val) pair's val,  as a seq in the order of their keys.
+/// Each key is worked out before sorting,  so comparing never has to again
+pub(crate) fn sort_keyed(
+    mut keyed: Vec<(Arc<Value>, Arc<Value>)>,
+    comparator: Option<&Arc<dyn IFn>>,
+) -> Result<Value, Value> {
+    // sort_by can't be stopped part way;  once a comparison fails,  the rest are skipped,
+    // and the first failure is what we give back
+    let failure = RefCell::new(None);
+    keyed.sort_by(|(a, _), (b, _)| {
+        if failure.borrow().is_some() {
+            return Ordering::Equal;
+        }
+        compare(comparator, a, b).unwrap_or_else(|condition| {
+            *failure.borrow_mut() = Some(condition);
+            Ordering::Equal
+        })
+    });
+    match failure.into_inner() {
+        Some(condition) => Err(condition),
+        None => Ok(keyed
+            .into_iter()
+            .map(|(_, val)| val)
+            .collect::<PersistentList>()
+            .to_value()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    #[test]
+    fn sorts_naturally_or_by_comparator() {
+        let result = Repl::default().eval_all(&[
+            "[(sort [3 1 2]) (sort > [3 1 2]) (sort (fn [a b] (compare b a)) [\"b\" \"c\" \"a\"]) (sort [])]",
+        ]);
+        assert_eq!(
+            "[(1 2 3) (3 2 1) (\"c\" \"b\" \"a\") ()]",
+            result.to_string()
+        );
+        assert!(matches!(
+            Repl::default().eval_all(&["(sort [1 :a])"]),
+            Value::Condition(_)
+        ));
+    }
+}
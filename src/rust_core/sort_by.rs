@@ -0,0 +1,93 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq;
+use crate::options::Opts;
+use crate::rust_core::juxt::Juxt;
+use crate::rust_core::sort::{as_comparator, sort_keyed};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (sort-by keyfn coll) (sort-by keyfn comparator coll) (sort-by keyfn opts coll)
+///
+/// The elements of coll as a seq,  sorted as sort would sort (keyfn element).  keyfn may be
+/// a fn,  a keyword,  or a vector of them as short for (juxt ..);  so (sort-by [:a :b] maps)
+/// sorts by :a,  and then by :b.  keyfn is called once per element,  not per comparison.
+/// The comparator can also be given as opts' :comparator
+#[derive(Debug, Clone)]
+pub struct SortByFn {}
+impl ToValue for SortByFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SortByFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (keyfn, comparator, coll) = match &args[..] {
+            [keyfn, coll] => (keyfn, None, coll),
+            [keyfn, comparator, coll] => match comparator_of(comparator) {
+                Ok(comparator) => (keyfn, comparator, coll),
+                Err(condition) => return condition,
+            },
+            _ => return error_message::wrong_varg_count(&[2, 3], args.len()),
+        };
+        let keyfn = match &**keyfn {
+            Value::PersistentVector(keyfns) => match Juxt::new(keyfns.iter().cloned().collect()) {
+                Ok(juxt) => Arc::new(Value::ifn(juxt)),
+                Err(condition) => return condition,
+            },
+            _ if ifn::is_callable(keyfn) => Arc::clone(keyfn),
+            _ => return error_message::type_mismatch(TypeTag::IFn, keyfn),
+        };
+        let mut keyed = vec![];
+        let elements = lazy_seq::try_for_each(coll, |val| {
+            let key = match ifn::call(&keyfn, vec![Arc::clone(&val)]) {
+                Value::Condition(condition) => return Err(Value::Condition(condition)),
+                key => Arc::new(key),
+            };
+            keyed.push((key, val));
+            Ok(())
+        });
+        if let Err(condition) = elements {
+            return condition;
+        }
+        match sort_keyed(keyed, comparator.as_ref()) {
+            Ok(sorted) => sorted,
+            Err(condition) => condition,
+        }
+    }
+}
+
+// The comparator given in place of one,  or as an opts map's :comparator
+fn comparator_of(given: &Arc<Value>) -> Result<Option<Arc<dyn IFn>>, Value> {
+    let comparator = match &**given {
+        Value::PersistentListMap(_) | Value::PersistentTreeMap(_) => {
+            match Opts::from_map("sort-by", &["comparator"], given)?.get("comparator") {
+                Some(comparator) => Arc::clone(comparator),
+                None => return Ok(None),
+            }
+        }
+        _ => Arc::clone(given),
+    };
+    as_comparator(&comparator).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn sorts_by_one_key_or_several() {
+        let result = Repl::default().eval_all(&[
+            "(def people [{:name \"b\" :age 30} {:name \"a\" :age 40} {:name \"c\" :age 30}])",
+            "(def calls (atom 0))",
+            "(defn age [p] (do (swap! calls inc) (:age p)))",
+            "(defn names [ps] (map (fn [p] (:name p)) ps))",
+            "[(names (sort-by :age people)) (names (sort-by age > people)) (names (sort-by [:age :name] people)) (names (sort-by (juxt :age :name) (fn [a b] (compare b a)) people)) @calls]",
+        ]);
+        assert_eq!(
+            "[(\"b\" \"c\" \"a\") (\"a\" \"b\" \"c\") (\"b\" \"c\" \"a\") (\"a\" \"c\" \"b\") 3]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use crate::ifn::IFn;
+use crate::persistent_list_map::IPersistentMap;
+use crate::persistent_tree_map::{Comparator, PersistentTreeMap};
+use crate::util::IsEven;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (sorted-map & keyvals)
+///
+/// A map that keeps its keys in their natural order
+#[derive(Debug, Clone)]
+pub struct SortedMapFn {}
+impl ToValue for SortedMapFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SortedMapFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        sorted_map(Comparator::Natural, &args)
+    }
+}
+
+/// Shared with sorted-map-by
+pub(crate) fn sorted_map(comparator: Comparator, keyvals: &[Arc<Value>]) -> Value {
+    if !keyvals.len().is_even() {
+        return Value::Condition(
+            format!(
+                "No value supplied for key: {}",
+                keyvals[keyvals.len() - 1].to_string_explicit()
+            )
+            .into(),
+        );
+    }
+    keyvals
+        .chunks(2)
+        .fold(PersistentTreeMap::new(comparator), |ptmap, keyval| {
+            ptmap.assoc(Arc::clone(&keyval[0]), Arc::clone(&keyval[1]))
+        })
+        .to_value()
+}
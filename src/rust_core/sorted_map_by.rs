@@ -0,0 +1,38 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_tree_map::Comparator;
+use crate::rust_core::sorted_map::sorted_map;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (sorted-map-by comparator & keyvals)
+///
+/// A map that keeps its keys in the order given by comparator;  either a compare-style
+/// fn, or a predicate like < saying whether its first argument comes first
+#[derive(Debug, Clone)]
+pub struct SortedMapByFn {}
+impl ToValue for SortedMapByFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SortedMapByFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        match as_comparator(&args[0]) {
+            Ok(comparator) => sorted_map(comparator, &args[1..]),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// Shared with sorted-set-by
+pub(crate) fn as_comparator(val: &Value) -> Result<Comparator, Value> {
+    match val {
+        Value::IFn(ifn) => Ok(Comparator::Fn(Arc::clone(ifn))),
+        _ => Err(error_message::type_mismatch(TypeTag::IFn, val)),
+    }
+}
@@ -0,0 +1,30 @@
+use crate::ifn::IFn;
+use crate::persistent_tree_map::Comparator;
+use crate::persistent_tree_set::PersistentTreeSet;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (sorted-set & keys)
+///
+/// A set that keeps its elements in their natural order
+#[derive(Debug, Clone)]
+pub struct SortedSetFn {}
+impl ToValue for SortedSetFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SortedSetFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        sorted_set(Comparator::Natural, &args)
+    }
+}
+
+/// Shared with sorted-set-by
+pub(crate) fn sorted_set(comparator: Comparator, keys: &[Arc<Value>]) -> Value {
+    keys.iter()
+        .fold(PersistentTreeSet::new(comparator), |ptset, key| {
+            ptset.conj(Arc::clone(key))
+        })
+        .to_value()
+}
@@ -0,0 +1,28 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::sorted_map_by::as_comparator;
+use crate::rust_core::sorted_set::sorted_set;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (sorted-set-by comparator & keys)
+///
+/// A set that keeps its elements in the order given by comparator
+#[derive(Debug, Clone)]
+pub struct SortedSetByFn {}
+impl ToValue for SortedSetByFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SortedSetByFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        match as_comparator(&args[0]) {
+            Ok(comparator) => sorted_set(comparator, &args[1..]),
+            Err(condition) => condition,
+        }
+    }
+}
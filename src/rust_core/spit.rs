@@ -0,0 +1,72 @@
+use crate::clojure_java_io::{self, Writer};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (spit f content & opts)
+///
+/// Writes (str content) to the file f names (a string,  or a file),  replacing what it holds;
+/// or adding to it,  with :append true.  Written as UTF-8,  or as :encoding says;  see
+/// spit-bytes,  for what isn't text
+#[derive(Debug, Clone)]
+pub struct SpitFn {}
+impl ToValue for SpitFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SpitFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let spat = clojure_java_io::options("spit", &args[2..]).and_then(|options| {
+            let path = clojure_java_io::as_path(&args[0])?;
+            let writer = Writer::open(&path, options.append, options.encoding)?;
+            writer.write(&args[1].to_string())?;
+            writer.close()
+        });
+        match spat {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn what_is_spat_is_slurped() {
+        let path = env::temp_dir().join(format!("spit_test_{}.txt", std::process::id()));
+        let path = path.display().to_string();
+        let repl = Repl::default();
+        let result = repl.eval_all(&[
+            &format!("(def path \"{}\")", path),
+            "(spit path \"one\")",
+            "(spit path [2] :append true :encoding \"UTF-8\")",
+            "(slurp path :encoding \"utf-8\")",
+        ]);
+        let latin1 = repl.eval_all(&[
+            "(spit path \"café\" :encoding \"latin1\")",
+            "[(seq (slurp-bytes path)) (slurp path :encoding \"ISO-8859-1\")]",
+        ]);
+        let condition = repl.eval_all(&["(spit path 3 :encoding \"EBCDIC\")"]);
+        let _ = fs::remove_file(&path);
+        assert_eq!("one[2]", result.to_string());
+        assert_eq!("[(99 97 102 -23) \"café\"]", latin1.to_string());
+        assert_eq!(
+            Value::Condition(
+                "Unsupported encoding EBCDIC;  UTF-8, UTF-16, UTF-16BE, UTF-16LE, ISO-8859-1 \
+                 and US-ASCII are"
+                    .into()
+            ),
+            condition
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use crate::clojure_java_io;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::byte_array;
+use crate::value::{ToValue, Value};
+use std::fs;
+use std::io::Write;
+use std::sync::Arc;
+
+/// (spit-bytes f content & opts)
+///
+/// Writes content,  a byte array or a seq of numbers from -128 to 255,  to the file f names (a
+/// string,  or a file) as it is;  replacing what the file holds,  or adding to it,  with
+/// :append true
+#[derive(Debug, Clone)]
+pub struct SpitBytesFn {}
+impl ToValue for SpitBytesFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SpitBytesFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let spat = clojure_java_io::options("spit-bytes", &args[2..]).and_then(|options| {
+            let path = clojure_java_io::as_path(&args[0])?;
+            let bytes = byte_array::bytes_of(&args[1])?;
+            fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .append(options.append)
+                .truncate(!options.append)
+                .open(&path)
+                .and_then(|mut file| file.write_all(&bytes))
+                .map_err(|e| clojure_java_io::io_error("write", path.display(), e))
+        });
+        match spat {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod spit_bytes_tests {
+        use crate::repl::Repl;
+        use std::env;
+        use std::fs;
+
+        #[test]
+        fn bytes_are_written_and_read_as_they_are() {
+            let path = env::temp_dir().join(format!("spit_bytes_test_{}", std::process::id()));
+            let path = path.display().to_string();
+            let result = Repl::default().eval_all(&[
+                &format!("(def path \"{}\")", path),
+                "(spit-bytes path (byte-array [0 255 128]))",
+                "(spit-bytes path [10] :append true)",
+                "[(seq (slurp-bytes path)) (count (slurp path))]",
+            ]);
+            let bytes = fs::read(&path);
+            let _ = fs::remove_file(&path);
+            assert_eq!(vec![0, 255, 128, 10], bytes.unwrap());
+            assert_eq!("[(0 -1 -128 10) 4]", result.to_string());
+        }
+    }
+}
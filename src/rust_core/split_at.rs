@@ -0,0 +1,29 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::split_with;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (split-at n coll)
+///
+/// [(take n coll) (drop n coll)];  but with coll walked the once,  as split-with does
+#[derive(Debug, Clone)]
+pub struct SplitAtFn {}
+impl ToValue for SplitAtFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SplitAtFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let n = match &*args[0] {
+            Value::I32(n) => (*n).max(0) as usize,
+            _ => return error_message::type_mismatch(TypeTag::Integer, &args[0]),
+        };
+        split_with::split(Arc::clone(&args[1]), move |index, _| Ok(index < n))
+    }
+}
@@ -0,0 +1,99 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::lazy_seq::{self, LazySeq, Step};
+use crate::persistent_vector::ToPersistentVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::{Arc, Mutex};
+
+/// (split-with pred coll)
+///
+/// [(take-while pred coll) (drop-while pred coll)];  but with coll walked the once,  the
+/// second seq going on from where the first stopped
+#[derive(Debug, Clone)]
+pub struct SplitWithFn {}
+impl ToValue for SplitWithFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SplitWithFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let pred = Arc::clone(&args[0]);
+        if !ifn::is_callable(&pred) {
+            return error_message::type_mismatch(TypeTag::IFn, &pred);
+        }
+        split(Arc::clone(&args[1]), move |_, x| {
+            match ifn::call(&pred, vec![Arc::clone(x)]) {
+                Value::Condition(condition) => Err(Value::Condition(condition)),
+                kept => Ok(kept.is_truthy()),
+            }
+        })
+    }
+}
+
+/// Whether the index'th element of a coll,  x,  is in the first part of the coll
+pub type Keep = Arc<dyn Fn(usize, &Arc<Value>) -> Result<bool, Value> + Send + Sync>;
+
+/// [the elements of coll up to the first keep says no to,  the rest];  two lazy seqs,
+/// with coll walked just the once
+pub fn split(
+    coll: Arc<Value>,
+    keep: impl Fn(usize, &Arc<Value>) -> Result<bool, Value> + Send + Sync + 'static,
+) -> Value {
+    // Where the first seq stopped,  once it has
+    let stopped = Arc::new(Mutex::new(None));
+    let kept = Arc::new(lazy_kept(coll, 0, Arc::new(keep), Arc::clone(&stopped)).into_value());
+    let first = Arc::clone(&kept);
+    let rest = LazySeq::new(move || {
+        lazy_seq::try_for_each(&kept, |_| Ok(()))?;
+        let rest = stopped.lock().unwrap().take();
+        match rest {
+            Some(rest) => lazy_seq::step(&rest),
+            None => Ok(Step::Done),
+        }
+    });
+    vec![first, rest.into_value().to_rc_value()]
+        .into_vector()
+        .to_value()
+}
+
+fn lazy_kept(
+    coll: Arc<Value>,
+    index: usize,
+    keep: Keep,
+    stopped: Arc<Mutex<Option<Arc<Value>>>>,
+) -> LazySeq {
+    LazySeq::new(move || match lazy_seq::step(&coll)? {
+        Step::Next(first, rest) if keep(index, &first)? => Ok(Step::Next(
+            first,
+            Arc::new(lazy_kept(rest, index + 1, keep, stopped).into_value()),
+        )),
+        _ => {
+            *stopped.lock().unwrap() = Some(coll);
+            Ok(Step::Done)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn the_second_part_goes_on_from_where_the_first_stopped() {
+        let result = Repl::default().eval_all(&[
+            "(def reads (atom 0))",
+            "(def xs (once (map (fn [x] (swap! reads inc) x) [1 2 3 10 4])))",
+            "(def parts (split-with (fn [x] (< x 5)) xs))",
+            "[(into [] (nth parts 1)) (first parts) @reads (split-at 2 [1 2 3]) (split-at 5 '(1 2)) (split-with odd? nil)]",
+        ]);
+        assert_eq!(
+            "[[10 4] (1 2 3) 5 [(1 2) (3)] [(1 2) ()] [() ()]]",
+            result.to_string()
+        );
+    }
+}
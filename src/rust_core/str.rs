@@ -1,23 +1,28 @@
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// (str x y & zs)
 ///
+/// nil's taken as the empty string
 #[derive(Debug, Clone)]
 pub struct StrFn {}
 impl ToValue for StrFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for StrFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         Value::String(
             args.into_iter()
-                .map(|arg| arg.to_string())
+                .map(|arg| match &*arg {
+                    Value::Nil => String::new(),
+                    arg => arg.to_string(),
+                })
                 .collect::<Vec<String>>()
-                .join(""),
+                .join("")
+                .into(),
         )
     }
 }
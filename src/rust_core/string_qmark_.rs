@@ -0,0 +1,23 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (string? x)
+///
+/// Whether x is a string
+#[derive(Debug, Clone)]
+pub struct StringQmarkFn {}
+impl ToValue for StringQmarkFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for StringQmarkFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Boolean(matches!(&*args[0], Value::String(_)))
+    }
+}
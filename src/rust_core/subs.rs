@@ -0,0 +1,93 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::shared_str::SharedStr;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (subs s start) or (subs s start end)
+///
+/// The chars of s from start (inclusive) to end (exclusive, defaulting to the end of s).
+/// Counts chars,  not bytes,  so never splits one;  see clojure.string/grapheme-subs for
+/// what reads as one character
+#[derive(Debug, Clone)]
+pub struct SubsFn {}
+impl ToValue for SubsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SubsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (s, start, end) = match subs_args(&args) {
+            Ok(subs_args) => subs_args,
+            Err(condition) => return condition,
+        };
+        let chars = s.chars().collect::<Vec<char>>();
+        match bounds(start, end, chars.len()) {
+            Ok((start, end)) => Value::String(chars[start..end].iter().collect::<String>().into()),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// Shared with grapheme-subs;  s,  start,  and end if it's given
+pub(crate) fn subs_args(args: &[Arc<Value>]) -> Result<(SharedStr, i32, Option<i32>), Value> {
+    if args.len() != 2 && args.len() != 3 {
+        return Err(error_message::wrong_varg_count(&[2, 3], args.len()));
+    }
+    let s = match &*args[0] {
+        Value::String(s) => SharedStr::clone(s),
+        _ => return Err(error_message::type_mismatch(TypeTag::String, &args[0])),
+    };
+    let mut bounds = vec![];
+    for bound in args[1..].iter() {
+        match **bound {
+            Value::I32(ind) => bounds.push(ind),
+            _ => return Err(error_message::type_mismatch(TypeTag::Integer, bound)),
+        }
+    }
+    Ok((s, bounds[0], bounds.get(1).copied()))
+}
+
+/// Shared with grapheme-subs;  start and end as indices into len things,  or the first
+/// that's out of range
+pub(crate) fn bounds(start: i32, end: Option<i32>, len: usize) -> Result<(usize, usize), Value> {
+    let end = end.unwrap_or(len as i32);
+    let out_of_range =
+        |ind: i32| Value::Condition(format!("String index out of range: {}", ind).into());
+    if start < 0 || start as usize > len {
+        return Err(out_of_range(start));
+    }
+    if end < start || end as usize > len {
+        return Err(out_of_range(end));
+    }
+    Ok((start as usize, end as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    mod subs_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn subs_counts_chars() {
+            let result = Repl::default().eval_all(&[r#"[(subs "héllo" 1) (subs "日本語" 1 2)]"#]);
+            assert_eq!(r#"["éllo" "本"]"#, result.to_string());
+        }
+
+        #[test]
+        fn subs_checks_its_bounds() {
+            let result = Repl::default().eval_all(&[r#"(subs "héllo" 2 6)"#]);
+            assert_eq!(
+                "#Condition[\"String index out of range: 6\"]",
+                result.to_string()
+            );
+            let result = Repl::default().eval_all(&[r#"(subs "héllo" 3 2)"#]);
+            assert_eq!(
+                "#Condition[\"String index out of range: 2\"]",
+                result.to_string()
+            );
+        }
+    }
+}
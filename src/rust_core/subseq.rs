@@ -0,0 +1,144 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::maps::MapEntry;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_tree_map::Comparator;
+use crate::persistent_vector::ToPersistentVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (subseq sc test key)
+/// (subseq sc start-test start-key end-test end-key)
+///
+/// The entries of sorted collection sc whose keys k pass (test (compare k key) 0) ,  in
+/// order;  ie, (subseq sc >= 2 < 5) .  sc's own comparator does the comparing
+#[derive(Debug, Clone)]
+pub struct SubseqFn {}
+impl ToValue for SubseqFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SubseqFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        subseq(&args, true)
+    }
+}
+
+/// Shared with rsubseq,  which is the same walk in reverse
+pub(crate) fn subseq(args: &[Arc<Value>], ascending: bool) -> Value {
+    if args.len() != 3 && args.len() != 5 {
+        return error_message::wrong_varg_count(&[3, 5], args.len());
+    }
+    let mut bounds = vec![];
+    for bound in args[1..].chunks(2) {
+        match &*bound[0] {
+            Value::IFn(test) => bounds.push((Arc::clone(test), Arc::clone(&bound[1]))),
+            _ => return error_message::type_mismatch(TypeTag::IFn, &bound[0]),
+        }
+    }
+    // Each entry, as (key, what to return for it)
+    let (comparator, entries): (&Comparator, Box<dyn Iterator<Item = _>>) = match &*args[0] {
+        Value::PersistentTreeMap(ptmap) => {
+            let to_key_and_pair = |MapEntry { key, val }| {
+                (Arc::clone(&key), vec![key, val].into_vector().to_rc_value())
+            };
+            (
+                ptmap.comparator(),
+                if ascending {
+                    Box::new(ptmap.iter().map(to_key_and_pair))
+                } else {
+                    Box::new(ptmap.rev_iter().map(to_key_and_pair))
+                },
+            )
+        }
+        Value::PersistentTreeSet(ptset) => {
+            let to_key_and_key = |key: Arc<Value>| (Arc::clone(&key), key);
+            (
+                ptset.comparator(),
+                if ascending {
+                    Box::new(ptset.iter().map(to_key_and_key))
+                } else {
+                    Box::new(ptset.rev_iter().map(to_key_and_key))
+                },
+            )
+        }
+        _ => return error_message::type_mismatch(TypeTag::PersistentTreeMap, &args[0]),
+    };
+
+    let mut included = vec![];
+    for (key, entry) in entries {
+        let mut passes = true;
+        for (test, bound) in bounds.iter() {
+            let ordering = Value::I32(comparator.compare(&key, bound) as i32);
+            match test.invoke(vec![Arc::new(ordering), Arc::new(Value::I32(0))]) {
+                Value::Condition(condition) => return Value::Condition(condition),
+                result => passes &= result.is_truthy(),
+            }
+        }
+        // The keys that pass form one run;  once it's over, nothing further along passes
+        if passes {
+            included.push(entry);
+        } else if !included.is_empty() {
+            break;
+        }
+    }
+    if included.is_empty() {
+        Value::Nil
+    } else {
+        included.into_list().to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod subseq_tests {
+        use crate::ifn::IFn;
+        use crate::persistent_list::ToPersistentList;
+        use crate::rust_core::{GtFn, GteFn, LtFn, RsubseqFn, SortedSetFn, SubseqFn};
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn ints(vals: &[i32]) -> Vec<Arc<Value>> {
+            vals.iter().map(|i| i.to_rc_value()).collect()
+        }
+
+        fn sorted_set() -> Arc<Value> {
+            SortedSetFn {}.invoke(ints(&[5, 1, 4, 2, 3])).to_rc_value()
+        }
+
+        #[test]
+        fn subseq_with_one_bound() {
+            assert_eq!(
+                ints(&[4, 5]).into_list().to_value(),
+                SubseqFn {}.invoke(vec![sorted_set(), GtFn {}.to_rc_value(), 3.to_rc_value()])
+            );
+            assert_eq!(
+                Value::Nil,
+                SubseqFn {}.invoke(vec![sorted_set(), GtFn {}.to_rc_value(), 9.to_rc_value()])
+            );
+        }
+
+        #[test]
+        fn subseq_and_rsubseq_with_two_bounds() {
+            let bounds = |sc: Arc<Value>| {
+                vec![
+                    sc,
+                    GteFn {}.to_rc_value(),
+                    2.to_rc_value(),
+                    LtFn {}.to_rc_value(),
+                    5.to_rc_value(),
+                ]
+            };
+            assert_eq!(
+                ints(&[2, 3, 4]).into_list().to_value(),
+                SubseqFn {}.invoke(bounds(sorted_set()))
+            );
+            assert_eq!(
+                ints(&[4, 3, 2]).into_list().to_value(),
+                RsubseqFn {}.invoke(bounds(sorted_set()))
+            );
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (subvec v start) or (subvec v start end)
+///
+/// The elements of v from start (inclusive) to end (exclusive, defaulting to the end of v).
+/// Takes no time, as the result shares v's structure
+#[derive(Debug, Clone)]
+pub struct SubvecFn {}
+impl ToValue for SubvecFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SubvecFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
+        }
+        let pvector = match &*args[0] {
+            Value::PersistentVector(pvector) => pvector,
+            _ => return error_message::type_mismatch(TypeTag::PersistentVector, &args[0]),
+        };
+        let mut bounds = vec![];
+        for bound in args[1..].iter() {
+            match **bound {
+                Value::I32(ind) if ind >= 0 => bounds.push(ind as usize),
+                Value::I32(ind) => return error_message::index_cannot_be_negative(ind as usize),
+                _ => return error_message::type_mismatch(TypeTag::Integer, bound),
+            }
+        }
+        let (start, end) = (bounds[0], *bounds.get(1).unwrap_or(&pvector.len()));
+        match pvector.subvec(start, end) {
+            Some(subvec) => subvec.to_value(),
+            None => error_message::index_out_of_bounds(end.max(start), pvector.len()),
+        }
+    }
+}
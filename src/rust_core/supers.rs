@@ -0,0 +1,27 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::bases::{type_list, type_named};
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (supers type)
+///
+/// Every type type belongs to,  directly or not,  or nil
+#[derive(Debug, Clone)]
+pub struct SupersFn {}
+impl ToValue for SupersFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SupersFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match type_named(&args[0]) {
+            Ok(type_tag) => type_list(type_tag.supers()),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (swap! atom f & args)
+///
+/// Sets atom to (apply f current-value args),  and returns what it was set to.  If f itself
+/// changes the atom,  f is called again on the atom's new value
+#[derive(Debug, Clone)]
+pub struct SwapBangFn {}
+impl ToValue for SwapBangFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SwapBangFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let atom = match &*args[0] {
+            Value::Atom(atom) => atom,
+            _ => return error_message::type_mismatch(TypeTag::Atom, &args[0]),
+        };
+        let f = match ifn::as_ifn(&args[1]) {
+            Some(ifn) => ifn,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        };
+        loop {
+            let old = atom.deref();
+            let mut f_args = vec![Arc::clone(&old)];
+            f_args.extend_from_slice(&args[2..]);
+            let new = match f.invoke(f_args) {
+                Value::Condition(condition) => return Value::Condition(condition),
+                new => Arc::new(new),
+            };
+            match atom.compare_and_set(&args[0], &old, Arc::clone(&new)) {
+                Ok(true) => return new.to_value(),
+                Ok(false) => continue,
+                Err(condition) => return condition,
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (symbol name) or (symbol ns name)
+///
+/// The symbol with name,  in ns if it's given;  given one string,  as (symbol "a/b"),  a
+/// namespace may come before a /.  Given a keyword,  the symbol with the same name and
+/// namespace;  given a var,  its qualified name
+#[derive(Debug, Clone)]
+pub struct SymbolFn {}
+impl ToValue for SymbolFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for SymbolFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match args.as_slice() {
+            [name] => match &**name {
+                Value::String(name) => Symbol::intern(name).to_value(),
+                Value::Symbol(_) => (**name).clone(),
+                Value::Keyword(keyword) => keyword.sym.to_value(),
+                Value::Var(var) => var.qualified().to_value(),
+                _ => error_message::type_mismatch(TypeTag::String, name),
+            },
+            [ns, name] => match (&**ns, &**name) {
+                (Value::String(ns), Value::String(name)) => {
+                    Symbol::intern_with_ns(ns, name).to_value()
+                }
+                (Value::Nil, Value::String(name)) => Symbol::intern_with_ns("", name).to_value(),
+                (Value::String(_), _) | (Value::Nil, _) => {
+                    error_message::type_mismatch(TypeTag::String, name)
+                }
+                _ => error_message::type_mismatch(TypeTag::String, ns),
+            },
+            _ => error_message::wrong_varg_count(&[1, 2], args.len()),
+        }
+    }
+}
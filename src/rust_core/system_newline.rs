@@ -1,25 +1,27 @@
 use crate::ifn::IFn;
 use crate::value::{ToValue, Value};
-use std::rc::Rc;
+use std::sync::Arc;
 
+use crate::clojure_java_io;
 use crate::error_message;
 
-/// Prints system newline, `\n` in rust on all platforms to stdout
-/// TODO: should be aware of *out*
-/// (defn print-string [string] .. prints single string without linebreak.. )
+/// Prints system newline, `\n` in rust on all platforms, to *out*
+/// (defn system-newline [] .. )
 #[derive(Debug, Clone)]
 pub struct SystemNewlineFn {}
 impl ToValue for SystemNewlineFn {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+        Value::ifn(self.clone())
     }
 }
 impl IFn for SystemNewlineFn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
         if args.len() != 0 {
             return error_message::wrong_arg_count(0, args.len());
         }
-        print!("\n");
-        Value::Nil
+        match clojure_java_io::print("\n") {
+            Ok(()) => Value::Nil,
+            Err(condition) => condition,
+        }
     }
-}
\ No newline at end of file
+}
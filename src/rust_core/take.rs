@@ -0,0 +1,75 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::{self, LazySeq, Step};
+use crate::transducer::{self, Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::{Arc, Mutex};
+
+/// (take n) (take n coll)
+///
+/// A lazy seq of the first n elements of coll,  or all of them if there are fewer.  Without
+/// coll,  a transducer passing on the first n inputs,  and then stopping
+#[derive(Debug, Clone)]
+pub struct TakeFn {}
+impl ToValue for TakeFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TakeFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        match &*args[0] {
+            Value::I32(n) if args.len() == 1 => {
+                let n = *n;
+                Transducer::new(move || {
+                    Box::new(TakeStage {
+                        left: Mutex::new(n),
+                    })
+                })
+                .to_value()
+            }
+            Value::I32(n) => lazy_take(*n, Arc::clone(&args[1])).into_value(),
+            _ => error_message::type_mismatch(TypeTag::Integer, &args[0]),
+        }
+    }
+}
+
+fn lazy_take(n: i32, coll: Arc<Value>) -> LazySeq {
+    LazySeq::new(move || {
+        if n <= 0 {
+            return Ok(Step::Done);
+        }
+        Ok(match lazy_seq::step(&coll)? {
+            Step::Next(first, rest) => {
+                Step::Next(first, Arc::new(lazy_take(n - 1, rest).into_value()))
+            }
+            Step::Done => Step::Done,
+        })
+    })
+}
+
+#[derive(Debug)]
+struct TakeStage {
+    left: Mutex<i32>,
+}
+impl Stage for TakeStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        let left = {
+            let mut left = self.left.lock().unwrap();
+            *left -= 1;
+            *left
+        };
+        let result = match left >= 0 {
+            true => rf.invoke(vec![result, input]),
+            false => result.to_value(),
+        };
+        match left > 0 {
+            true => result,
+            false => transducer::ensure_reduced(result),
+        }
+    }
+}
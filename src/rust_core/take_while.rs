@@ -0,0 +1,52 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::transducer::{self, Stage, Transducer};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (take-while pred) (take-while pred coll)
+///
+/// A lazy seq of the elements of coll up to the first one pred is falsy for.  Without coll,
+/// a transducer passing on inputs up to that one,  and then stopping
+#[derive(Debug, Clone)]
+pub struct TakeWhileFn {}
+impl ToValue for TakeWhileFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TakeWhileFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() || args.len() > 2 {
+            return error_message::wrong_varg_count(&[1, 2], args.len());
+        }
+        let pred = match ifn::as_ifn(&args[0]) {
+            Some(pred) => pred,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let xform = Transducer::new(move || {
+            Box::new(TakeWhileStage {
+                pred: Arc::clone(&pred),
+            })
+        });
+        match args.get(1) {
+            Some(coll) => transducer::sequence(&xform, Arc::clone(coll)),
+            None => xform.to_value(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TakeWhileStage {
+    pred: Arc<dyn IFn>,
+}
+impl Stage for TakeWhileStage {
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value {
+        match self.pred.invoke(vec![Arc::clone(&input)]) {
+            Value::Condition(condition) => Value::Condition(condition),
+            passed if passed.is_truthy() => rf.invoke(vec![result, input]),
+            _ => Value::Reduced(result),
+        }
+    }
+}
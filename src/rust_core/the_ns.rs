@@ -0,0 +1,90 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::namespace::Namespace;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (the-ns x)
+///
+/// If x is a namespace, returns it;  if it's a symbol, returns the namespace it names,
+/// erroring if there isn't one
+#[derive(Debug, Clone)]
+pub struct TheNsFn {
+    enclosing_environment: Arc<Environment>,
+}
+impl TheNsFn {
+    pub fn new(enclosing_environment: Arc<Environment>) -> TheNsFn {
+        TheNsFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for TheNsFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TheNsFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match the_ns(&self.enclosing_environment, &args[0]) {
+            Ok(namespace) => Value::Namespace(namespace),
+            Err(condition) => condition,
+        }
+    }
+}
+
+/// Resolves a namespace or the symbol naming one,  for the fns that take either
+pub(crate) fn the_ns(environment: &Environment, val: &Value) -> Result<Arc<Namespace>, Value> {
+    match val {
+        Value::Namespace(namespace) => Ok(Arc::clone(namespace)),
+        Value::Symbol(sym) => environment
+            .find_namespace(sym)
+            .ok_or_else(|| Value::Condition(format!("No namespace: {} found", sym).into())),
+        _ => Err(error_message::type_mismatch(TypeTag::Namespace, val)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod the_ns_tests {
+        use crate::environment::Environment;
+        use crate::rust_core::the_ns::the_ns;
+        use crate::symbol::Symbol;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        #[test]
+        fn the_ns_resolves_symbols_and_namespaces() {
+            let environment = Environment::new_main_environment();
+            let user = the_ns(&environment, &Symbol::intern("user").to_value()).unwrap();
+            assert_eq!(Symbol::intern("user"), user.name);
+            let again = the_ns(&environment, &Value::Namespace(Arc::clone(&user))).unwrap();
+            assert!(Arc::ptr_eq(&user, &again));
+        }
+
+        #[test]
+        fn the_ns_of_missing_namespace_is_an_error() {
+            let environment = Environment::new_main_environment();
+            assert!(the_ns(&environment, &Symbol::intern("nope").to_value()).is_err());
+            assert!(the_ns(&environment, &Value::I32(1)).is_err());
+        }
+
+        #[test]
+        fn namespace_values_share_their_mappings() {
+            let environment = Environment::new_main_environment();
+            let created = environment.find_or_create_namespace(&Symbol::intern("my.ns"));
+            created.insert(&Symbol::intern("a"), Arc::new(Value::I32(1)));
+            let found = the_ns(&environment, &Symbol::intern("my.ns").to_value()).unwrap();
+            assert_eq!(
+                Value::Namespace(created),
+                Value::Namespace(Arc::clone(&found))
+            );
+            assert_eq!(Value::I32(1), *found.get(&Symbol::intern("a")));
+        }
+    }
+}
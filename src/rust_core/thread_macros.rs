@@ -0,0 +1,254 @@
+use crate::ifn::IFn;
+use crate::persistent_list::{ToPersistentList, ToPersistentListIter};
+use crate::persistent_vector::ToPersistentVector;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (-> x form ..) and (->> x form ..)
+///
+/// Threads x through each form in turn;  as its first argument with ->,  or its last with
+/// ->>.  A form that isn't a list is called with x
+///
+/// (-> x (f a) g) expands to
+///
+/// (g (f x a))
+#[derive(Debug, Clone)]
+pub struct ThreadMacro {
+    pub last: bool,
+}
+impl ToValue for ThreadMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for ThreadMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return at_least_one(self.name());
+        }
+        let threaded = args[1..]
+            .iter()
+            .fold(Arc::clone(&args[0]), |x, form| thread(x, form, self.last));
+        threaded.to_value()
+    }
+}
+impl ThreadMacro {
+    fn name(&self) -> &'static str {
+        if self.last {
+            "->>"
+        } else {
+            "->"
+        }
+    }
+}
+
+/// (some-> x form ..) and (some->> x form ..)
+///
+/// Like -> and ->>,  but stopping at the first nil;  and returning it
+///
+/// (some-> x f g) expands to
+///
+/// (let [some__val__ x]
+///   (if (= nil some__val__) nil
+///     (let [some__val__ (f some__val__)]
+///       (if (= nil some__val__) nil (let [some__val__ (g some__val__)] some__val__)))))
+#[derive(Debug, Clone)]
+pub struct SomeThreadMacro {
+    pub last: bool,
+}
+impl ToValue for SomeThreadMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for SomeThreadMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return at_least_one(if self.last { "some->>" } else { "some->" });
+        }
+        let val = sym("some__val__");
+        let threaded = args[1..]
+            .iter()
+            .rev()
+            .fold(Arc::clone(&val), |inner, form| {
+                list(vec![
+                    sym("if"),
+                    list(vec![sym("="), Value::Nil.to_rc_value(), Arc::clone(&val)]),
+                    Value::Nil.to_rc_value(),
+                    let_form(
+                        vec![Arc::clone(&val), thread(Arc::clone(&val), form, self.last)],
+                        inner,
+                    ),
+                ])
+            });
+        let_form(vec![val, Arc::clone(&args[0])], threaded).to_value()
+    }
+}
+
+/// (cond-> x test form ..) and (cond->> x test form ..)
+///
+/// Like -> and ->>,  but only threading x through each form whose test is truthy.  The
+/// tests don't see x
+///
+/// (cond-> x a f b g) expands to
+///
+/// (let [cond__val__ x
+///       cond__val__ (if a (f cond__val__) cond__val__)
+///       cond__val__ (if b (g cond__val__) cond__val__)]
+///   cond__val__)
+#[derive(Debug, Clone)]
+pub struct CondThreadMacro {
+    pub last: bool,
+}
+impl ToValue for CondThreadMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for CondThreadMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let name = if self.last { "cond->>" } else { "cond->" };
+        if args.is_empty() {
+            return at_least_one(name);
+        }
+        if args.len().is_multiple_of(2) {
+            return Value::Condition(
+                format!("{} requires an even number of forms after x", name).into(),
+            );
+        }
+        let val = sym("cond__val__");
+        let mut bindings = vec![Arc::clone(&val), Arc::clone(&args[0])];
+        for clause in args[1..].chunks(2) {
+            bindings.push(Arc::clone(&val));
+            bindings.push(list(vec![
+                sym("if"),
+                Arc::clone(&clause[0]),
+                thread(Arc::clone(&val), &clause[1], self.last),
+                Arc::clone(&val),
+            ]));
+        }
+        let_form(bindings, Arc::clone(&val)).to_value()
+    }
+}
+
+/// (as-> x name form ..)
+///
+/// Binds name to x,  then to each form in turn;  so each form can put the last wherever
+/// it needs it.  Returns the last
+///
+/// (as-> x v (f v) (g 1 v)) expands to
+///
+/// (let [v x v (f v) v (g 1 v)] v)
+#[derive(Debug, Clone)]
+pub struct AsThreadMacro {}
+impl ToValue for AsThreadMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for AsThreadMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return Value::Condition(
+                format!(
+                    "Wrong number of arguments given to as-> (Given: {}, Expected: >=2)",
+                    args.len()
+                )
+                .into(),
+            );
+        }
+        let name = &args[1];
+        if !matches!(**name, Value::Symbol(_)) {
+            return Value::Condition("as-> binding should be a symbol".into());
+        }
+        let mut bindings = vec![Arc::clone(name), Arc::clone(&args[0])];
+        for form in &args[2..] {
+            bindings.push(Arc::clone(name));
+            bindings.push(Arc::clone(form));
+        }
+        let_form(bindings, Arc::clone(name)).to_value()
+    }
+}
+
+fn at_least_one(name: &str) -> Value {
+    Value::Condition(
+        format!(
+            "Wrong number of arguments given to {} (Given: 0, Expected: >=1)",
+            name
+        )
+        .into(),
+    )
+}
+
+pub(crate) fn list(forms: Vec<Arc<Value>>) -> Arc<Value> {
+    forms.into_list().to_rc_value()
+}
+
+pub(crate) fn sym(name: &str) -> Arc<Value> {
+    Symbol::intern(name).to_rc_value()
+}
+
+/// Shared with doto and the if-let family;  (let [bindings] body)
+pub(crate) fn let_form(bindings: Vec<Arc<Value>>, body: Arc<Value>) -> Arc<Value> {
+    list(vec![sym("let"), bindings.into_vector().to_rc_value(), body])
+}
+
+/// Shared with doto;  form called with x,  as its first argument or last
+pub(crate) fn thread(x: Arc<Value>, form: &Arc<Value>, last: bool) -> Arc<Value> {
+    let mut forms = match &**form {
+        Value::PersistentList(plist) => plist.iter().collect::<Vec<Arc<Value>>>(),
+        _ => vec![],
+    };
+    if forms.is_empty() {
+        return list(vec![Arc::clone(form), x]);
+    }
+    if last {
+        forms.push(x);
+    } else {
+        forms.insert(1, x);
+    }
+    list(forms)
+}
+
+#[cfg(test)]
+mod tests {
+    mod thread_macros_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn x_is_threaded_first_or_last() {
+            let result = Repl::default().eval_all(&[
+                "[(-> 1 inc (- 10) str) (->> 1 inc (- 10) str) (-> {:a {:b 2}} :a :b) (-> 3)]",
+            ]);
+            assert_eq!("[\"-8\" \"8\" 2 3]", result.to_string());
+        }
+
+        #[test]
+        fn some_threading_stops_at_nil() {
+            let result = Repl::default().eval_all(&[
+                "[(some-> {:a {:b 2}} :a :b inc) (some-> {:a 1} :b inc) (some->> [1 2] (map inc) (into [])) (some-> false not)]",
+            ]);
+            assert_eq!("[3 nil [2 3] true]", result.to_string());
+        }
+
+        #[test]
+        fn cond_threading_skips_falsey_tests() {
+            let result = Repl::default().eval_all(&[
+                "[(cond-> 1 true inc false (* 10) (= 1 1) (- 1)) (cond->> 1 true (- 10) nil inc)]",
+            ]);
+            assert_eq!("[1 9]", result.to_string());
+            let result = Repl::default().eval_all(&["(cond-> 1 true)"]);
+            assert_eq!(
+                "#Condition[\"cond-> requires an even number of forms after x\"]",
+                result.to_string()
+            );
+        }
+
+        #[test]
+        fn as_threading_binds_a_name() {
+            let result = Repl::default().eval_all(&["(as-> 2 v (* v 10) (- 100 v) [v v])"]);
+            assert_eq!("[80 80]", result.to_string());
+        }
+    }
+}
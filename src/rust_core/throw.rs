@@ -0,0 +1,29 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::shared_str::SharedStr;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (throw ex)
+///
+/// Throws ex,  an exception as made by ex-info or caught by catch;  to be caught by the
+/// nearest try with a catch clause for it
+#[derive(Debug, Clone)]
+pub struct ThrowFn {}
+impl ToValue for ThrowFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ThrowFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Exception(message) => Value::Condition(SharedStr::clone(message)),
+            _ => error_message::type_mismatch(TypeTag::Exception, &args[0]),
+        }
+    }
+}
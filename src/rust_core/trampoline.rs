@@ -0,0 +1,54 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::interrupt;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (trampoline f & args)
+///
+/// Calls f with args,  then calls what it returns with no args for as long as that's a fn;
+/// returning the first thing that isn't.  So mutually recursive fns can return a fn to call
+/// next rather than calling it,  and not grow the stack
+#[derive(Debug, Clone)]
+pub struct TrampolineFn {}
+impl ToValue for TrampolineFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TrampolineFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::zero_arg_count(args.len());
+        }
+        if !ifn::is_callable(&args[0]) {
+            return error_message::type_mismatch(TypeTag::IFn, &args[0]);
+        }
+        let mut bounced = ifn::call(&args[0], args[1..].to_vec());
+        while let Value::IFn(f) = bounced {
+            if interrupt::is_interrupted() {
+                return Value::Condition(interrupt::INTERRUPTED_MSG.into());
+            }
+            bounced = f.invoke(vec![]);
+        }
+        bounced
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod trampoline_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn fns_are_called_until_they_return_something_else() {
+            let result = Repl::default().eval_all(&[
+                "(defn my-even? [n] (if (= n 0) true (fn [] (my-odd? (- n 1)))))",
+                "(defn my-odd? [n] (if (= n 0) false (fn [] (my-even? (- n 1)))))",
+                "[(trampoline my-even? 100001) (trampoline + 1 2)]",
+            ]);
+            assert_eq!("[false 3]", result.to_string());
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::transducer;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (transduce xform f coll) (transduce xform f init coll)
+///
+/// Reduces coll with what xform makes of f,  starting from init,  or (f);  then completes
+/// it.  f is called with just the result to complete it,  so an fn of two arguments
+/// wants wrapping in completing
+#[derive(Debug, Clone)]
+pub struct TransduceFn {}
+impl ToValue for TransduceFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TransduceFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 && args.len() != 4 {
+            return error_message::wrong_varg_count(&[3, 4], args.len());
+        }
+        let (xform, f) = match (&*args[0], &*args[1]) {
+            (Value::IFn(xform), Value::IFn(_)) => (xform, Arc::clone(&args[1])),
+            (Value::IFn(_), _) => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let rf = match xform.invoke(vec![f]) {
+            Value::IFn(rf) => rf,
+            Value::Condition(condition) => return Value::Condition(condition),
+            other => return error_message::type_mismatch(TypeTag::IFn, &other),
+        };
+        let init = match args.len() {
+            4 => Arc::clone(&args[2]),
+            _ => match rf.invoke(vec![]) {
+                Value::Condition(condition) => return Value::Condition(condition),
+                init => Arc::new(init),
+            },
+        };
+        match transducer::reduce(&rf, Some(init), args.last().unwrap()) {
+            Value::Condition(condition) => Value::Condition(condition),
+            result => rf.invoke(vec![Arc::new(result)]),
+        }
+    }
+}
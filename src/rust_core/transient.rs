@@ -0,0 +1,33 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::transient::{TransientMap, TransientVector};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (transient coll)
+///
+/// A transient copy of a vector or map,  for conj!, assoc! and dissoc! to change in place
+#[derive(Debug, Clone)]
+pub struct TransientFn {}
+impl ToValue for TransientFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for TransientFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::PersistentVector(pvector) => {
+                Value::TransientVector(Arc::new(TransientVector::new((**pvector).clone())))
+            }
+            Value::PersistentListMap(pmap) => {
+                Value::TransientMap(Arc::new(TransientMap::new(pmap)))
+            }
+            _ => error_message::type_mismatch(TypeTag::Collection, &args[0]),
+        }
+    }
+}
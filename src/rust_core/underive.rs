@@ -0,0 +1,32 @@
+use crate::hierarchy;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (underive tag parent) or (underive h tag parent)
+///
+/// Undoes a derive, in the global hierarchy (returning nil), or in a new version
+/// of h (returning that)
+#[derive(Debug, Clone)]
+pub struct UnderiveFn {}
+impl ToValue for UnderiveFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for UnderiveFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let explicit = args.len() == 3;
+        let (hierarchy, args) = match hierarchy::hierarchy_and_args(&args, 2) {
+            Ok(split) => split,
+            Err(condition) => return condition,
+        };
+        let underived = hierarchy.underive(&args[0], &args[1]);
+        if explicit {
+            underived.to_value()
+        } else {
+            hierarchy::set_global(underived);
+            Value::Nil
+        }
+    }
+}
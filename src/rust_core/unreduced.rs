@@ -0,0 +1,24 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::transducer;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (unreduced x)
+///
+/// What x wraps if it's (reduced ..);  otherwise x
+#[derive(Debug, Clone)]
+pub struct UnreducedFn {}
+impl ToValue for UnreducedFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for UnreducedFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        transducer::unreduced(args[0].to_value())
+    }
+}
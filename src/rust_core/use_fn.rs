@@ -0,0 +1,29 @@
+use crate::ifn::IFn;
+use crate::rust_core::require::RequireFn;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (use & libs)
+///
+/// Requires each lib,  and refers all its public names into the current namespace;  or
+/// for [lib :only [names]],  only those,  as refer filters them
+#[derive(Debug, Clone)]
+pub struct UseFn {
+    require: RequireFn,
+}
+impl UseFn {
+    /// use,  sharing with require the libs loaded so far
+    pub fn new(require: RequireFn) -> UseFn {
+        UseFn { require }
+    }
+}
+impl ToValue for UseFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for UseFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        self.require.require(&args, true)
+    }
+}
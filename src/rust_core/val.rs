@@ -0,0 +1,27 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::rust_core::key::map_entry;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (val e)
+///
+/// The val of the map entry e;  as seq gives a map's entries
+#[derive(Debug, Clone)]
+pub struct ValFn {}
+impl ToValue for ValFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for ValFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match map_entry(&args[0]) {
+            Ok((_, val)) => val.to_value(),
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::error_message;
+use crate::ifn::{self, IFn};
+use crate::meta;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (vary-meta obj f & args)
+///
+/// Returns an object of the same type and value as obj,  with (apply f (meta obj) args) as
+/// its metadata
+#[derive(Debug, Clone)]
+pub struct VaryMetaFn {}
+impl ToValue for VaryMetaFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for VaryMetaFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let f = match ifn::as_ifn(&args[1]) {
+            Some(ifn) => ifn,
+            None => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        };
+        let old = meta::meta(&args[0]).unwrap_or_else(|| Arc::new(Value::Nil));
+        let mut f_args = vec![old];
+        f_args.extend_from_slice(&args[2..]);
+        let new = match f.invoke(f_args) {
+            Value::Condition(condition) => return Value::Condition(condition),
+            new => Arc::new(new),
+        };
+        match meta::with_meta(&args[0], &new) {
+            Ok(obj) => obj,
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (when test body)
+///
+/// Evaluates body when test is truthy,  and returns nil otherwise
+///
+/// (when test a b) expands to
+///
+/// (if test (do a b))
+#[derive(Debug, Clone)]
+pub struct WhenMacro {}
+impl ToValue for WhenMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for WhenMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return Value::Condition(
+                "Wrong number of arguments given to when (Given: 0, Expected: >=1)".into(),
+            );
+        }
+        let mut do_body = vec![Symbol::intern("do").to_rc_value()];
+        do_body.extend_from_slice(&args[1..]);
+        vec![
+            Symbol::intern("if").to_rc_value(),
+            Arc::clone(&args[0]),
+            do_body.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
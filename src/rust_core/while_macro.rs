@@ -0,0 +1,44 @@
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (while test body)
+///
+/// Evaluates body again and again, for as long as test is truthy;  returns nil
+///
+/// (while test a b) expands to
+///
+/// (loop [] (when test a b (recur)))
+#[derive(Debug, Clone)]
+pub struct WhileMacro {}
+impl ToValue for WhileMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for WhileMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return Value::Condition(
+                "Wrong number of arguments given to while (Given: 0, Expected: >=1)".into(),
+            );
+        }
+        let mut when_body = vec![Symbol::intern("when").to_rc_value()];
+        when_body.extend_from_slice(&args);
+        when_body.push(
+            vec![Symbol::intern("recur").to_rc_value()]
+                .into_list()
+                .to_rc_value(),
+        );
+        vec![
+            Symbol::intern("loop").to_rc_value(),
+            PersistentVector::empty().to_rc_value(),
+            when_body.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
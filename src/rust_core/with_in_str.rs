@@ -0,0 +1,40 @@
+use crate::clojure_java_io::Reader;
+use crate::dynamic::{self, Frame};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (with-in-str* s f)
+///
+/// Calls f with *in* bound to a fresh reader on s
+#[derive(Debug, Clone)]
+pub struct WithInStrFn {}
+impl ToValue for WithInStrFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for WithInStrFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let text = match &*args[0] {
+            Value::String(text) => text,
+            _ => return error_message::type_mismatch(TypeTag::String, &args[0]),
+        };
+        let f = match &*args[1] {
+            Value::IFn(f) => f,
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        };
+        let mut frame = Frame::new();
+        frame.insert(
+            Symbol::intern_with_ns("clojure.core", "*in*"),
+            Arc::new(Value::host_object(Reader::on_string(text))),
+        );
+        dynamic::with_bindings(frame, || f.invoke(vec![]))
+    }
+}
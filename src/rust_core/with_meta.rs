@@ -0,0 +1,27 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::meta;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (with-meta obj m)
+///
+/// Returns an object of the same type and value as obj,  with the map m as its metadata
+#[derive(Debug, Clone)]
+pub struct WithMetaFn {}
+impl ToValue for WithMetaFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for WithMetaFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match meta::with_meta(&args[0], &args[1]) {
+            Ok(obj) => obj,
+            Err(condition) => condition,
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (with-open [name init ..] body)
+///
+/// Binds each name to its init, evaluates body, and then closes each name, in reverse order.
+/// Each close is in a finally, so it still happens if body is interrupted
+///
+/// (with-open [a x b y] body) expands to
+///
+/// (let [a x] (try (with-open [b y] body) (finally (close a))))
+#[derive(Debug, Clone)]
+pub struct WithOpenMacro {}
+impl ToValue for WithOpenMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for WithOpenMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.is_empty() {
+            return Value::Condition(
+                "Wrong number of arguments given to with-open (Given: 0, Expected: >=1)".into(),
+            );
+        }
+        let bindings = match &*args[0] {
+            Value::PersistentVector(pvector) if pvector.len() % 2 == 0 => {
+                pvector.iter().cloned().collect::<Vec<Arc<Value>>>()
+            }
+            _ => {
+                return Value::Condition(
+                    "Bindings to with-open should be a vector with an even number of forms".into(),
+                )
+            }
+        };
+        let body = &args[1..];
+        if bindings.is_empty() {
+            let mut do_body = vec![Symbol::intern("do").to_rc_value()];
+            do_body.extend_from_slice(body);
+            return do_body.into_list().to_value();
+        }
+
+        let name = Arc::clone(&bindings[0]);
+        let inner_bindings = bindings[2..].iter().cloned().collect::<PersistentVector>();
+        let mut inner = vec![
+            Symbol::intern("with-open").to_rc_value(),
+            inner_bindings.to_rc_value(),
+        ];
+        inner.extend_from_slice(body);
+
+        let close = vec![Symbol::intern("close").to_rc_value(), Arc::clone(&name)].into_list();
+        let finally =
+            vec![Symbol::intern("finally").to_rc_value(), close.to_rc_value()].into_list();
+        let try_expr = vec![
+            Symbol::intern("try").to_rc_value(),
+            inner.into_list().to_rc_value(),
+            finally.to_rc_value(),
+        ]
+        .into_list();
+        let let_bindings = vec![name, Arc::clone(&bindings[1])]
+            .into_iter()
+            .collect::<PersistentVector>();
+        vec![
+            Symbol::intern("let").to_rc_value(),
+            let_bindings.to_rc_value(),
+            try_expr.to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod with_open_macro_tests {
+        use crate::ifn::IFn;
+        use crate::persistent_vector::PersistentVector;
+        use crate::rust_core::WithOpenMacro;
+        use crate::symbol::Symbol;
+        use crate::value::{ToValue, Value};
+        use std::sync::Arc;
+
+        fn sym(name: &str) -> Arc<Value> {
+            Symbol::intern(name).to_rc_value()
+        }
+
+        #[test]
+        fn closes_each_binding_in_a_finally() {
+            let bindings = vec![sym("a"), sym("x"), sym("b"), sym("y")]
+                .into_iter()
+                .collect::<PersistentVector>();
+            let expansion = WithOpenMacro {}.invoke(vec![bindings.to_rc_value(), sym("body")]);
+            assert_eq!(
+                "(let [a x] (try (with-open [b y] body) (finally (close a))))",
+                expansion.to_string()
+            );
+        }
+
+        #[test]
+        fn no_bindings_is_just_the_body() {
+            let bindings = PersistentVector::empty();
+            let expansion = WithOpenMacro {}.invoke(vec![bindings.to_rc_value(), sym("body")]);
+            assert_eq!("(do body)", expansion.to_string());
+        }
+
+        #[test]
+        fn odd_bindings_is_an_error() {
+            let bindings = vec![sym("a")].into_iter().collect::<PersistentVector>();
+            assert!(matches!(
+                WithOpenMacro {}.invoke(vec![bindings.to_rc_value()]),
+                Value::Condition(_)
+            ));
+        }
+    }
+}
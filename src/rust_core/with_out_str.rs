@@ -0,0 +1,58 @@
+use crate::clojure_java_io::Writer;
+use crate::dynamic::{self, Frame};
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::sync::Arc;
+
+/// (with-out-str* f)
+///
+/// Calls f with *out* bound to a fresh writer on a string;  what f printed
+#[derive(Debug, Clone)]
+pub struct WithOutStrFn {}
+impl ToValue for WithOutStrFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for WithOutStrFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let f = match &*args[0] {
+            Value::IFn(f) => f,
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let out = Arc::new(Value::host_object(Writer::on_string()));
+        let mut frame = Frame::new();
+        frame.insert(
+            Symbol::intern_with_ns("clojure.core", "*out*"),
+            Arc::clone(&out),
+        );
+        match dynamic::with_bindings(frame, || f.invoke(vec![])) {
+            Value::Condition(condition) => Value::Condition(condition),
+            _ => Value::String(out.to_string().into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn what_is_printed_is_the_string_and_what_is_read_the_string_given() {
+        assert_eq!(
+            "[\"a1\\nb\\n\" \"nil\" [\"one\" \"two\" nil]]",
+            Repl::default()
+                .eval_all(&[
+                    "(defn shout [] (.write *out* \"b\") (newline))",
+                    "[(with-out-str (print \"a\") (println 1) (shout)) (with-out-str (binding [*out* *err*] (print 2)) (pr nil)) (with-in-str (str \"one\" (with-out-str (newline)) \"two\") [(read-line) (read-line) (read-line)])]",
+                ])
+                .to_string_explicit()
+        );
+    }
+}
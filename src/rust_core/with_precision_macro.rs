@@ -0,0 +1,136 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::numbers::{self, MathContext};
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::PersistentVector;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use bigdecimal::RoundingMode;
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+/// (with-precision* precision rounding f)
+///
+/// Calls f with BigDecimal results rounded to precision digits,  by rounding;  a symbol
+/// naming one of Java's RoundingModes,  as HALF_UP
+#[derive(Debug, Clone)]
+pub struct WithPrecisionFn {}
+impl ToValue for WithPrecisionFn {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for WithPrecisionFn {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let precision = match &*args[0] {
+            Value::I32(precision) => match NonZeroU64::new((*precision).max(0) as u64) {
+                Some(precision) => precision,
+                None => {
+                    return Value::Condition(
+                        format!("Precision must be positive,  not {}", precision).into(),
+                    )
+                }
+            },
+            _ => return error_message::type_mismatch(TypeTag::I32, &args[0]),
+        };
+        let rounding = match &*args[1] {
+            Value::Symbol(mode) => match mode.name.as_str() {
+                "UP" => Some(RoundingMode::Up),
+                "DOWN" => Some(RoundingMode::Down),
+                "CEILING" => Some(RoundingMode::Ceiling),
+                "FLOOR" => Some(RoundingMode::Floor),
+                "HALF_UP" => Some(RoundingMode::HalfUp),
+                "HALF_DOWN" => Some(RoundingMode::HalfDown),
+                "HALF_EVEN" => Some(RoundingMode::HalfEven),
+                "UNNECESSARY" => None,
+                _ => return Value::Condition(format!("No rounding mode {}", mode).into()),
+            },
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[1]),
+        };
+        match &*args[2] {
+            Value::IFn(f) => numbers::with_math_context(
+                MathContext {
+                    precision,
+                    rounding,
+                },
+                || f.invoke(vec![]),
+            ),
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[2]),
+        }
+    }
+}
+
+/// (with-precision precision & body),  or (with-precision precision :rounding mode & body)
+///
+/// (with-precision 10 :rounding HALF_EVEN a b) expands to
+///
+/// (with-precision* 10 (quote HALF_EVEN) (fn [] a b))
+///
+/// rounding HALF_UP,  unless it's given
+#[derive(Debug, Clone)]
+pub struct WithPrecisionMacro {}
+impl ToValue for WithPrecisionMacro {
+    fn to_value(&self) -> Value {
+        Value::macro_(self.clone())
+    }
+}
+impl IFn for WithPrecisionMacro {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        let (precision, rest) = match args.split_first() {
+            Some(split) => split,
+            None => return error_message::wrong_varg_count(&[1], 0),
+        };
+        let (rounding, body) = match rest {
+            [key, mode, body @ ..] if **key == Keyword::intern("rounding").to_value() => {
+                (Arc::clone(mode), body)
+            }
+            _ => (Symbol::intern("HALF_UP").to_rc_value(), rest),
+        };
+        let mut f = vec![
+            Symbol::intern("fn").to_rc_value(),
+            PersistentVector::empty().to_rc_value(),
+        ];
+        f.extend_from_slice(body);
+        vec![
+            Symbol::intern("with-precision*").to_rc_value(),
+            Arc::clone(precision),
+            vec![Symbol::intern("quote").to_rc_value(), rounding]
+                .into_list()
+                .to_rc_value(),
+            f.into_list().to_rc_value(),
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn decimals_are_rounded_within_with_precision() {
+        let result = Repl::default().eval_all(&[
+            "(def third (ex-message (try (/ 1M 3) (catch Exception e e))))",
+            "[third (with-precision 5 (/ 1M 3)) (with-precision 2 (+ 1.25M 1)) (with-precision 10 (/ 1M 4)) (with-precision 2 :rounding HALF_EVEN (* 1.25M 1)) (with-precision 3 :rounding DOWN (/ -2M 3)) (with-precision 2 (+ 1/3 0M)) (/ 1M 8)]",
+        ]);
+        assert_eq!(
+            "[\"Non-terminating decimal expansion; no exact representable decimal result.\" 0.33333M 2.3M 0.25M 1.2M -0.666M 0.33M 0.125M]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn rounding_unnecessary_is_a_condition_when_it_is() {
+        let result = Repl::default().eval_all(&[
+            "(def rounded (ex-message (try (with-precision 2 :rounding UNNECESSARY (/ 1M 3)) (catch Exception e e))))",
+            "[rounded (with-precision 2 :rounding UNNECESSARY (* 1.50M 1))]",
+        ]);
+        assert_eq!("[\"Rounding necessary\" 1.5M]", result.to_string());
+    }
+}
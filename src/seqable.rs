@@ -0,0 +1,43 @@
+//! What seq walks,  and so first, rest and the rest of the sequence library;  any
+//! collection,  and besides those,  strings as their chars and byte arrays as their bytes.
+//! Lists and seqs are walked as they are,  and nil as an empty seq;  everything else is
+//! walked through Seqable
+use crate::shared_str::SharedStr;
+use crate::value::Value;
+use std::sync::Arc;
+
+pub trait Seqable {
+    /// Its elements,  in the order seq gives them;  a map's as map entries,  a vector of key
+    /// and val each
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_>;
+}
+
+/// val,  as something seq can walk;  if it is one
+pub fn seqable(val: &Value) -> Option<&dyn Seqable> {
+    Some(match val {
+        Value::PersistentList(plist) => &**plist,
+        Value::PersistentVector(pvector) => &**pvector,
+        Value::PersistentListMap(plistmap) => &**plistmap,
+        Value::PersistentHashSet(pset) => &**pset,
+        Value::PersistentTreeMap(ptmap) => &**ptmap,
+        Value::PersistentTreeSet(ptset) => &**ptset,
+        Value::PersistentQueue(pqueue) => &**pqueue,
+        Value::LazySeq(lseq) => &**lseq,
+        Value::String(string) => string,
+        Value::Bytes(bytes) => bytes,
+        _ => return None,
+    })
+}
+
+impl Seqable for SharedStr {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(self.chars().map(|c| Arc::new(Value::Char(c))))
+    }
+}
+
+// Signed,  as Java's bytes are
+impl Seqable for Arc<Vec<u8>> {
+    fn seq_iter(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        Box::new(self.iter().map(|&b| Arc::new(Value::I32(b as i8 as i32))))
+    }
+}
@@ -0,0 +1,71 @@
+//! A string as a Value holds it;  shared,  so cloning one is a refcount bump
+//!
+//! It's an Arc<String> rather than an Arc<str>,  whose pointer is two words wide,  so Value
+//! stays two words (see the layout note on Value).  It derefs to str,  and is made from a
+//! &str or String with into,  much as an Arc<str> would be
+use std::borrow::{Borrow, Cow};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Weak};
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SharedStr(Arc<String>);
+impl SharedStr {
+    /// Where this string is kept;  the same for each of its clones,  and no other
+    pub fn as_ptr(this: &SharedStr) -> usize {
+        Arc::as_ptr(&this.0) as usize
+    }
+    pub fn downgrade(this: &SharedStr) -> Weak<String> {
+        Arc::downgrade(&this.0)
+    }
+}
+
+impl Deref for SharedStr {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+impl AsRef<str> for SharedStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+impl Borrow<str> for SharedStr {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SharedStr {
+    fn from(s: String) -> Self {
+        SharedStr(Arc::new(s))
+    }
+}
+impl From<&str> for SharedStr {
+    fn from(s: &str) -> Self {
+        SharedStr(Arc::new(s.to_string()))
+    }
+}
+impl From<&String> for SharedStr {
+    fn from(s: &String) -> Self {
+        SharedStr(Arc::new(s.clone()))
+    }
+}
+impl From<Cow<'_, str>> for SharedStr {
+    fn from(s: Cow<'_, str>) -> Self {
+        SharedStr(Arc::new(s.into_owned()))
+    }
+}
+
+impl fmt::Display for SharedStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&**self.0, f)
+    }
+}
+// As a str's;  so a Value debug prints as it did when it held an Arc<str>
+impl fmt::Debug for SharedStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self.0, f)
+    }
+}
@@ -0,0 +1,64 @@
+//! How the process ends;  (exit n), and the hooks added by (add-shutdown-hook f)
+//!
+//! The hooks run once,  in the order they were added,  whether we end through exit or by
+//! reaching the end of a script.  A hook that fails doesn't stop the others;  its condition
+//! is printed to stderr, and we carry on ending
+use crate::clojure_java_io;
+use crate::ifn::IFn;
+use crate::output;
+use crate::output::Stream;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::process;
+use std::sync::Arc;
+
+thread_local! {
+    static HOOKS: RefCell<Vec<Arc<dyn IFn>>> = const { RefCell::new(vec![]) };
+}
+
+pub fn add_hook(hook: Arc<dyn IFn>) {
+    HOOKS.with(|hooks| hooks.borrow_mut().push(hook));
+}
+
+/// Runs each hook added so far,  and forgets them;  so a hook that calls exit itself
+/// doesn't run everything twice
+pub fn run_hooks() {
+    let hooks = HOOKS.with(|hooks| hooks.replace(vec![]));
+    for hook in hooks {
+        if let Value::Condition(condition) = hook.invoke(vec![]) {
+            output::write(
+                Stream::Err,
+                &format!("Shutdown hook failed: {}\n", condition),
+            );
+        }
+    }
+}
+
+/// Runs the hooks,  deletes the temp files made,  then ends the process with code
+pub fn exit(code: i32) -> ! {
+    run_hooks();
+    clojure_java_io::temp_file::delete_all();
+    output::flush(Stream::Out);
+    output::flush(Stream::Err);
+    process::exit(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::shutdown;
+    use crate::value::Value;
+
+    #[test]
+    fn hooks_run_once_even_after_one_fails() {
+        let repl = Repl::default();
+        repl.eval_all(&[
+            "(def calls (atom 0))",
+            "(add-shutdown-hook (fn [] (reset!)))",
+            "(add-shutdown-hook (fn [] (swap! calls inc)))",
+        ]);
+        shutdown::run_hooks();
+        shutdown::run_hooks();
+        assert_eq!(Value::I32(1), repl.eval_all(&["@calls"]));
+    }
+}
@@ -0,0 +1,203 @@
+//! Signals sent to the process;  what (on-signal :int f) reacts to
+//!
+//! The handler installed with the OS only notes that the signal came.  The fn given for it
+//! is called later,  on the thread that gave it,  the next time evaluation there checks
+//! whether it's been interrupted (see interrupt.rs);  so it runs between calls,  as the
+//! thread's own code does,  never in the middle of a finally,  and it can call exit with
+//! that thread's shutdown hooks still there to run.  A fn that fails doesn't stop the
+//! thread;  its condition is printed to stderr.  Signals no fn's been given for do what
+//! they always did;  SIGINT and SIGTERM end us.  Except that at the REPL,  and running a
+//! script,  -e or -m,  SIGINT (Ctrl-C) interrupts what's being evaluated instead
+use crate::ifn;
+use crate::output::{self, Stream};
+use crate::value::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::{self, ThreadId};
+
+/// The signals fns can be given for,  by the names on-signal knows them by
+#[cfg(unix)]
+pub const SIGNALS: &[(&str, i32)] = &[
+    ("int", libc::SIGINT),
+    ("term", libc::SIGTERM),
+    ("hup", libc::SIGHUP),
+    ("usr1", libc::SIGUSR1),
+    ("usr2", libc::SIGUSR2),
+];
+#[cfg(not(unix))]
+pub const SIGNALS: &[(&str, i32)] = &[];
+
+struct Handler {
+    f: Arc<Value>,
+    thread: ThreadId,
+}
+
+// A bit for each signal that's come,  by its number,  and not yet been handled
+static PENDING: AtomicU64 = AtomicU64::new(0);
+
+// The flag SIGINT sets,  once it's been made to interrupt rather than end us;  and whether
+// a fn's been given for it meanwhile,  which it's called for instead.  Kept where the
+// handler can see them without taking a lock
+static INTERRUPTS: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+static INT_HAS_FN: AtomicBool = AtomicBool::new(false);
+
+fn handlers() -> &'static Mutex<HashMap<i32, Handler>> {
+    static HANDLERS: OnceLock<Mutex<HashMap<i32, Handler>>> = OnceLock::new();
+    HANDLERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The number of the signal named name
+pub fn number(name: &str) -> Option<i32> {
+    SIGNALS
+        .iter()
+        .find(|(signal, _)| *signal == name)
+        .map(|(_, signum)| *signum)
+}
+
+/// Has f called when signum comes,  on this thread;  or,  if f is None,  has the signal do
+/// what it did to begin with.  The fn given for it before,  if there was one
+pub fn set_handler(signum: i32, f: Option<Arc<Value>>) -> Option<Arc<Value>> {
+    let mut handlers = handlers().lock().unwrap();
+    let interrupting = Some(signum) == number("int") && INTERRUPTS.get().is_some();
+    if Some(signum) == number("int") {
+        INT_HAS_FN.store(f.is_some(), Ordering::SeqCst);
+    }
+    let before = match f {
+        Some(f) => {
+            let thread = thread::current().id();
+            os::catch(signum);
+            handlers.insert(signum, Handler { f, thread })
+        }
+        // Back to interrupting,  if that's what it did
+        None if interrupting => handlers.remove(&signum),
+        None => {
+            os::release(signum);
+            handlers.remove(&signum)
+        }
+    };
+    before.map(|handler| handler.f)
+}
+
+/// Has SIGINT,  as Ctrl-C sends,  set the flag given back rather than end us;  unless a fn's
+/// given for it.  The flag's the same each time,  for the one terminal there is
+pub fn interrupting_on_int() -> Arc<AtomicBool> {
+    let flag = INTERRUPTS.get_or_init(|| Arc::new(AtomicBool::new(false)));
+    if let Some(signum) = number("int") {
+        os::catch(signum);
+    }
+    Arc::clone(flag)
+}
+
+/// Whether a signal's come that hasn't been handled;  as cheap to ask as an atomic load
+pub fn is_pending() -> bool {
+    PENDING.load(Ordering::Relaxed) != 0
+}
+
+/// Calls the fns given on this thread for each signal that's come since
+pub fn deliver() {
+    let current = thread::current().id();
+    let due = {
+        let handlers = handlers().lock().unwrap();
+        let mut due = vec![];
+        for (_, signum) in SIGNALS {
+            let bit = 1 << signum;
+            if PENDING.load(Ordering::SeqCst) & bit == 0 {
+                continue;
+            }
+            match handlers.get(signum) {
+                Some(handler) if handler.thread != current => continue,
+                Some(handler) => due.push(Arc::clone(&handler.f)),
+                // Caught just before its fn was taken away
+                None => {}
+            }
+            PENDING.fetch_and(!bit, Ordering::SeqCst);
+        }
+        due
+    };
+    for f in due {
+        if let Value::Condition(condition) = ifn::call(&f, vec![]) {
+            output::write(
+                Stream::Err,
+                &format!("Signal handler failed: {}\n", condition),
+            );
+        }
+    }
+}
+
+#[cfg(unix)]
+mod os {
+    use super::{INTERRUPTS, INT_HAS_FN, PENDING};
+    use std::sync::atomic::Ordering;
+
+    // All a handler can safely do is little more than this
+    extern "C" fn note(signum: libc::c_int) {
+        if signum == libc::SIGINT && !INT_HAS_FN.load(Ordering::SeqCst) {
+            if let Some(flag) = INTERRUPTS.get() {
+                flag.store(true, Ordering::SeqCst);
+                return;
+            }
+        }
+        PENDING.fetch_or(1 << signum, Ordering::SeqCst);
+    }
+
+    pub fn catch(signum: i32) {
+        let note = note as extern "C" fn(libc::c_int);
+        unsafe { libc::signal(signum, note as libc::sighandler_t) };
+    }
+
+    pub fn release(signum: i32) {
+        unsafe { libc::signal(signum, libc::SIG_DFL) };
+    }
+}
+
+#[cfg(not(unix))]
+mod os {
+    pub fn catch(_signum: i32) {}
+    pub fn release(_signum: i32) {}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::interrupt;
+    use crate::repl::Repl;
+    use crate::value::Value;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn the_fn_is_called_on_the_thread_that_gave_it_at_its_next_call() {
+        let repl = Repl::default();
+        repl.eval_all(&[
+            "(def caught (atom []))",
+            "(on-signal :usr2 (fn [] (swap! caught conj :usr2)))",
+        ]);
+        unsafe { libc::raise(libc::SIGUSR2) };
+        let result = repl.eval_all(&[
+            "(def seen @caught)",
+            "(on-signal :usr2 nil)",
+            "[seen @caught]",
+        ]);
+        assert_eq!("[[:usr2] [:usr2]]", result.to_string());
+        assert!(matches!(
+            repl.eval_all(&["(on-signal :kill (fn []))"]),
+            Value::Condition(_)
+        ));
+    }
+
+    #[test]
+    fn ctrl_c_interrupts_what_is_running_and_its_finally_still_runs() {
+        interrupt::on_ctrl_c();
+        let repl = Repl::default();
+        let ctrl_c = thread::spawn(|| {
+            thread::sleep(Duration::from_millis(50));
+            unsafe { libc::raise(libc::SIGINT) };
+        });
+        let ran = repl.run_expr(
+            "(def cleaned (atom false)) (try (loop [] (recur)) (finally (reset! cleaned true)))",
+        );
+        ctrl_c.join().unwrap();
+        assert!(ran.unwrap_err().contains(interrupt::INTERRUPTED_MSG));
+        assert_eq!("true", repl.eval_all(&["@cleaned"]).to_string());
+    }
+}
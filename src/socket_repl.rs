@@ -0,0 +1,311 @@
+//! A plain socket REPL,  for clients that don't speak nREPL;  `nc localhost 5555` is enough
+//!
+//! Comes in two flavors, like Clojure's own:
+//!
+//! - the socket REPL,  which behaves exactly like the terminal REPL
+//! - prepl (the "program REPL"),  which answers with one EDN map per line instead,  ie
+//!   {:tag :ret, :val "3", :ns "clojure.core", :ms 0, :form "(+ 1 2)"}
+//!   so tooling can tell results,  printed output and errors apart
+//!
+//! Each connection gets its own Environment,  evaluated on the connection's own thread
+use crate::environment::Environment;
+use crate::output;
+use crate::reader;
+use crate::repl;
+use crate::value::{Evaluable, Value};
+
+use nom::Err::Incomplete;
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Repl,
+    Prepl,
+}
+
+/// Quotes text as an EDN string
+fn edn_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Reads whole forms off a connection,  however they happen to be split across lines
+pub(crate) struct FormReader<R: BufRead> {
+    reader: R,
+    buffer: String,
+}
+impl<R: BufRead> FormReader<R> {
+    pub(crate) fn new(reader: R) -> FormReader<R> {
+        FormReader {
+            reader,
+            buffer: String::new(),
+        }
+    }
+    /// Returns the next form along with its source text,  or None once the client hangs up
+    pub(crate) fn next_form(&mut self) -> io::Result<Option<(Value, String)>> {
+        loop {
+            if !self
+                .buffer
+                .trim_matches(|c: char| c.is_whitespace() || c == ',')
+                .is_empty()
+            {
+                let read = match reader::try_read(&self.buffer) {
+                    Ok((rest, form)) => Some(Ok((form, self.buffer.len() - rest.len()))),
+                    // Wait for more input
+                    Err(Incomplete(_)) => None,
+                    Err(err) => Some(Err(format!(
+                        "Reader Error: could not read next form; {:?}",
+                        err
+                    ))),
+                };
+                match read {
+                    Some(Ok((form, consumed))) => {
+                        let source = self.buffer[..consumed].trim().to_string();
+                        self.buffer.drain(..consumed);
+                        return Ok(Some((form, source)));
+                    }
+                    // Skip the rest of the line;  there's no telling where the bad form ends
+                    Some(Err(err)) => {
+                        let source = std::mem::take(&mut self.buffer).trim().to_string();
+                        return Ok(Some((Value::Condition(err.into()), source)));
+                    }
+                    None => {}
+                }
+            }
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+/// Writes the answers for one connection,  in whichever format its mode calls for
+#[derive(Clone)]
+struct Printer {
+    mode: Mode,
+    writer: Arc<TcpStream>,
+}
+impl Printer {
+    fn write(&self, text: &str) {
+        // If the client hung up, the next read notices
+        let _ = (&*self.writer).write_all(text.as_bytes());
+    }
+    fn prompt(&self, environment: &Environment) {
+        if self.mode == Mode::Repl {
+            self.write(&format!("{}=> ", environment.get_current_namespace_name()));
+        }
+    }
+    fn out(&self, stream: output::Stream, text: &str) {
+        match self.mode {
+            Mode::Repl => self.write(text),
+            Mode::Prepl => {
+                let tag = match stream {
+                    output::Stream::Out => ":out",
+                    output::Stream::Err => ":err",
+                };
+                self.write(&format!("{{:tag {}, :val {}}}\n", tag, edn_string(text)));
+            }
+        }
+    }
+    fn ret(&self, environment: &Environment, value: &Value, source: &str, started: Instant) {
+        match self.mode {
+            Mode::Repl => self.write(&format!("{}\n", value)),
+            Mode::Prepl => {
+                let exception = match value {
+                    Value::Condition(_) => ", :exception true",
+                    _ => "",
+                };
+                self.write(&format!(
+                    "{{:tag :ret, :val {}, :ns {}, :ms {}, :form {}{}}}\n",
+                    edn_string(&value.to_string()),
+                    edn_string(&environment.get_current_namespace_name()),
+                    started.elapsed().as_millis(),
+                    edn_string(source),
+                    exception
+                ));
+            }
+        }
+    }
+}
+
+/// Handles one client connection until it hangs up
+fn handle_connection(stream: TcpStream, mode: Mode) -> io::Result<()> {
+    let printer = Printer {
+        mode,
+        writer: Arc::new(stream.try_clone()?),
+    };
+    let sink_printer = printer.clone();
+    output::install(Some(Arc::new(move |stream, text: &str| {
+        sink_printer.out(stream, text)
+    })));
+
+    let environment = Environment::clojure_core_environment();
+    let mut forms = FormReader::new(BufReader::new(stream));
+    loop {
+        printer.prompt(&environment);
+        let (form, source) = match forms.next_form()? {
+            Some((form, source)) if repl::quit_code(&form).is_none() => (form, source),
+            // Quitting hangs up on the client;  the server goes on
+            _ => return Ok(()),
+        };
+        let started = Instant::now();
+        let value = match form {
+            Value::Condition(_) => form,
+            _ => form.eval(Arc::clone(&environment)),
+        };
+        printer.ret(&environment, &value, &source, started);
+    }
+}
+
+/// A socket REPL (or prepl) server.  Every connection is served on its own thread
+pub struct SocketServer {
+    listener: TcpListener,
+    mode: Mode,
+}
+impl SocketServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A, mode: Mode) -> io::Result<SocketServer> {
+        Ok(SocketServer {
+            listener: TcpListener::bind(addr)?,
+            mode,
+        })
+    }
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+    /// Serves connections forever;  a client disconnecting only ends its own thread
+    pub fn run(&self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("Socket REPL: failed to accept connection: {}", e);
+                    continue;
+                }
+            };
+            let mode = self.mode;
+            thread::spawn(move || {
+                // Most likely just the client going away
+                let _ = handle_connection(stream, mode);
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod edn_string_tests {
+        use crate::socket_repl::edn_string;
+
+        #[test]
+        fn edn_string_escapes() {
+            assert_eq!("\"a \\\"b\\\"\\n\"", edn_string("a \"b\"\n"));
+        }
+    }
+
+    mod form_reader_tests {
+        use crate::socket_repl::FormReader;
+        use crate::value::Value;
+        use std::io::Cursor;
+
+        fn sources(input: &str) -> Vec<String> {
+            let mut forms = FormReader::new(Cursor::new(input.as_bytes().to_vec()));
+            let mut sources = vec![];
+            while let Some((_, source)) = forms.next_form().unwrap() {
+                sources.push(source);
+            }
+            sources
+        }
+
+        #[test]
+        fn several_forms_on_one_line() {
+            assert_eq!(vec!["(+ 1 2)", "5", ":a"], sources("(+ 1 2) 5 :a\n"));
+        }
+
+        #[test]
+        fn form_across_lines() {
+            assert_eq!(vec!["(+ 1\n2)"], sources("(+ 1\n2)\n"));
+        }
+
+        #[test]
+        fn unreadable_form_is_a_condition() {
+            let mut forms = FormReader::new(Cursor::new(b")\n1\n".to_vec()));
+            assert!(matches!(
+                forms.next_form().unwrap(),
+                Some((Value::Condition(_), _))
+            ));
+            assert_eq!(Value::I32(1), forms.next_form().unwrap().unwrap().0);
+        }
+    }
+
+    mod socket_server_tests {
+        use crate::socket_repl::{Mode, SocketServer};
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpStream;
+        use std::thread;
+
+        fn connect(mode: Mode) -> (TcpStream, BufReader<TcpStream>) {
+            let server = SocketServer::bind("127.0.0.1:0", mode).unwrap();
+            let addr = server.local_addr().unwrap();
+            thread::spawn(move || server.run());
+            let stream = TcpStream::connect(addr).unwrap();
+            let reader = BufReader::new(stream.try_clone().unwrap());
+            (stream, reader)
+        }
+
+        fn read_line(reader: &mut BufReader<TcpStream>) -> String {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            line
+        }
+
+        #[test]
+        fn socket_repl_prompts_and_prints() {
+            let (mut stream, mut reader) = connect(Mode::Repl);
+            stream.write_all(b"(+ 1 2)\n").unwrap();
+            assert_eq!("user=> 3\n", read_line(&mut reader));
+        }
+
+        #[test]
+        fn prepl_tags_output_and_results() {
+            let (mut stream, mut reader) = connect(Mode::Prepl);
+            stream.write_all(b"(println \"hi\")\n").unwrap();
+            let mut lines = vec![];
+            loop {
+                let line = read_line(&mut reader);
+                let is_ret = line.starts_with("{:tag :ret");
+                lines.push(line);
+                if is_ret {
+                    break;
+                }
+            }
+            assert!(lines.contains(&String::from("{:tag :out, :val \"hi\"}\n")));
+            let ret = lines.last().unwrap();
+            assert!(ret.contains(":val \"nil\""), "{}", ret);
+            assert!(ret.contains(":form \"(println \\\"hi\\\")\""), "{}", ret);
+        }
+
+        #[test]
+        fn prepl_flags_errors() {
+            let (mut stream, mut reader) = connect(Mode::Prepl);
+            stream.write_all(b"(nth [] 5)\n").unwrap();
+            assert!(read_line(&mut reader).contains(":exception true"));
+        }
+    }
+}
@@ -0,0 +1,350 @@
+//! Refs,  and the transactions (dosync) they're changed in
+//!
+//! A ref can only be changed within a transaction.  Within one,  alter, ref-set and commute
+//! only change the transaction's own view of each ref;  nothing else sees the changes
+//! until the whole transaction commits, and if it fails part way, nothing ever does.
+//!
+//! Each ref has a version,  bumped every time a transaction commits a change to it.  A
+//! transaction remembers the version of every ref it reads or sets,  and if any of them has
+//! moved on by the time it commits, some other transaction got there first;  ours is
+//! thrown away and its body run again from scratch.  Commutes are the exception:  they're
+//! just redone at commit on whatever the ref holds by then.
+//!
+//! Transactions on different threads run alongside each other,  but commit one at a time;
+//! so each can tell whether another got there first.  A dosync inside another joins it,
+//! as in Clojure
+use crate::atom;
+use crate::ifn::IFn;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// How many times a transaction is run before we give up on it
+const RETRY_LIMIT: usize = 10000;
+
+// Held by the transaction committing
+static COMMITTING: Mutex<()> = Mutex::new(());
+
+#[derive(Debug)]
+pub struct Ref {
+    val: RwLock<Arc<Value>>,
+    version: AtomicU64,
+    validator: Option<Arc<dyn IFn>>,
+}
+impl Ref {
+    /// Fails if validator rejects val
+    pub fn new(val: Arc<Value>, validator: Option<Arc<dyn IFn>>) -> Result<Ref, Value> {
+        atom::validate(&validator, &val)?;
+        Ok(Ref {
+            val: RwLock::new(val),
+            version: AtomicU64::new(0),
+            validator,
+        })
+    }
+    fn committed(&self) -> Arc<Value> {
+        Arc::clone(&self.val.read().unwrap())
+    }
+    fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+}
+impl fmt::Display for Ref {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "#ref[{{:status :ready, :val {}}}]",
+            self.val.read().unwrap().to_string_explicit()
+        )
+    }
+}
+
+type Commute = (Arc<Ref>, Arc<dyn IFn>, Vec<Arc<Value>>);
+
+#[derive(Default)]
+struct Transaction {
+    // Each ref read or set,  and the version it was at when we first did
+    seen: Vec<(Arc<Ref>, u64)>,
+    // Each ref set with alter or ref-set
+    sets: Vec<Arc<Ref>>,
+    // Our view of each ref we've changed
+    vals: Vec<(Arc<Ref>, Arc<Value>)>,
+    commutes: Vec<Commute>,
+}
+impl Transaction {
+    fn val(&self, reference: &Arc<Ref>) -> Option<Arc<Value>> {
+        self.vals
+            .iter()
+            .find(|(changed, _)| Arc::ptr_eq(changed, reference))
+            .map(|(_, val)| Arc::clone(val))
+    }
+    fn set_val(&mut self, reference: &Arc<Ref>, val: Arc<Value>) {
+        match self
+            .vals
+            .iter_mut()
+            .find(|(changed, _)| Arc::ptr_eq(changed, reference))
+        {
+            Some(entry) => entry.1 = val,
+            None => self.vals.push((Arc::clone(reference), val)),
+        }
+    }
+    fn see(&mut self, reference: &Arc<Ref>) {
+        if !self
+            .seen
+            .iter()
+            .any(|(seen, _)| Arc::ptr_eq(seen, reference))
+        {
+            self.seen.push((Arc::clone(reference), reference.version()));
+        }
+    }
+    fn is_set(&self, reference: &Arc<Ref>) -> bool {
+        self.sets.iter().any(|set| Arc::ptr_eq(set, reference))
+    }
+    fn is_commuted(&self, reference: &Arc<Ref>) -> bool {
+        self.commutes
+            .iter()
+            .any(|(commuted, _, _)| Arc::ptr_eq(commuted, reference))
+    }
+    /// Ok(false) if some ref we've seen has changed since,  and we need to run again
+    fn commit(mut self) -> Result<bool, Value> {
+        let _committing = COMMITTING.lock().unwrap();
+        if self
+            .seen
+            .iter()
+            .any(|(reference, version)| reference.version() != *version)
+        {
+            return Ok(false);
+        }
+        // Commutes are redone on the latest value,  which is what lets them never conflict
+        let mut commuted: Vec<(Arc<Ref>, Arc<Value>)> = vec![];
+        for (reference, f, args) in self.commutes.iter() {
+            if self.is_set(reference) {
+                continue;
+            }
+            let current = match commuted
+                .iter()
+                .position(|(done, _)| Arc::ptr_eq(done, reference))
+            {
+                Some(ind) => commuted.remove(ind).1,
+                None => reference.committed(),
+            };
+            let new = apply(f, current, args)?;
+            commuted.push((Arc::clone(reference), new));
+        }
+        for (reference, val) in commuted {
+            self.set_val(&reference, val);
+        }
+        for (reference, val) in self.vals.iter() {
+            atom::validate(&reference.validator, val)?;
+        }
+        for (reference, val) in self.vals {
+            *reference.val.write().unwrap() = val;
+            reference.version.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(true)
+    }
+}
+
+thread_local! {
+    static TRANSACTION: RefCell<Option<Transaction>> = const { RefCell::new(None) };
+}
+
+fn in_transaction() -> bool {
+    TRANSACTION.with(|transaction| transaction.borrow().is_some())
+}
+
+// Never called while TRANSACTION is borrowed;  f may well look at refs itself
+fn with_transaction<T>(f: impl FnOnce(&mut Transaction) -> T) -> Result<T, Value> {
+    TRANSACTION.with(|transaction| match &mut *transaction.borrow_mut() {
+        Some(transaction) => Ok(f(transaction)),
+        None => Err(Value::Condition("No transaction running".into())),
+    })
+}
+
+fn apply(f: &Arc<dyn IFn>, val: Arc<Value>, args: &[Arc<Value>]) -> Result<Arc<Value>, Value> {
+    let mut f_args = vec![val];
+    f_args.extend_from_slice(args);
+    match f.invoke(f_args) {
+        Value::Condition(condition) => Err(Value::Condition(condition)),
+        new => Ok(Arc::new(new)),
+    }
+}
+
+/// Runs body in a transaction,  again and again until it commits;  or within the current
+/// transaction, if there already is one.  A body that fails changes nothing
+pub fn run_in_transaction(body: impl Fn() -> Value) -> Value {
+    if in_transaction() {
+        return body();
+    }
+    for _ in 0..RETRY_LIMIT {
+        TRANSACTION.with(|transaction| *transaction.borrow_mut() = Some(Transaction::default()));
+        let result = body();
+        let transaction = TRANSACTION
+            .with(|transaction| transaction.borrow_mut().take())
+            .unwrap();
+        if let Value::Condition(_) = result {
+            return result;
+        }
+        match transaction.commit() {
+            Ok(true) => return result,
+            Ok(false) => continue,
+            Err(condition) => return condition,
+        }
+    }
+    Value::Condition("Transaction failed after reaching retry limit".into())
+}
+
+/// The ref's value;  within a transaction,  as the transaction sees it
+pub fn deref(reference: &Arc<Ref>) -> Arc<Value> {
+    with_transaction(|transaction| match transaction.val(reference) {
+        Some(val) => val,
+        None => {
+            transaction.see(reference);
+            reference.committed()
+        }
+    })
+    .unwrap_or_else(|_| reference.committed())
+}
+
+pub fn ref_set(reference: &Arc<Ref>, val: Arc<Value>) -> Result<Arc<Value>, Value> {
+    with_transaction(|transaction| {
+        if transaction.is_commuted(reference) {
+            return Err(Value::Condition("Can't set after commute".into()));
+        }
+        transaction.see(reference);
+        if !transaction.is_set(reference) {
+            transaction.sets.push(Arc::clone(reference));
+        }
+        transaction.set_val(reference, Arc::clone(&val));
+        Ok(val)
+    })?
+}
+
+pub fn alter(
+    reference: &Arc<Ref>,
+    f: &Arc<dyn IFn>,
+    args: &[Arc<Value>],
+) -> Result<Arc<Value>, Value> {
+    // Checked before f runs,  so f is never called for nothing
+    with_transaction(|_| ())?;
+    let new = apply(f, deref(reference), args)?;
+    ref_set(reference, new)
+}
+
+/// Like alter,  except that at commit f is run again on whatever the ref holds then;  so
+/// f should be commutative,  but the transaction never has to retry because of it
+pub fn commute(
+    reference: &Arc<Ref>,
+    f: &Arc<dyn IFn>,
+    args: &[Arc<Value>],
+) -> Result<Arc<Value>, Value> {
+    let current = with_transaction(|transaction| {
+        transaction
+            .val(reference)
+            .unwrap_or_else(|| reference.committed())
+    })?;
+    let new = apply(f, current, args)?;
+    with_transaction(|transaction| {
+        transaction.set_val(reference, Arc::clone(&new));
+        transaction
+            .commutes
+            .push((Arc::clone(reference), Arc::clone(f), args.to_vec()));
+    })?;
+    Ok(new)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ifn::IFn;
+    use crate::repl::Repl;
+    use crate::rust_core::AddFn;
+    use crate::stm::{self, Ref, TRANSACTION};
+    use crate::value::Value;
+    use std::cell::Cell;
+    use std::sync::Arc;
+
+    // Commits a change to reference as some other transaction would,  from the middle of
+    // the one currently running
+    fn commit_elsewhere(reference: &Arc<Ref>, val: i32) {
+        let ours = TRANSACTION.with(|transaction| transaction.borrow_mut().take());
+        stm::run_in_transaction(|| {
+            stm::ref_set(reference, Arc::new(Value::I32(val))).unwrap();
+            Value::Nil
+        });
+        TRANSACTION.with(|transaction| *transaction.borrow_mut() = ours);
+    }
+
+    fn add() -> Arc<dyn IFn> {
+        Arc::new(AddFn {})
+    }
+
+    #[test]
+    fn a_conflicting_commit_makes_us_run_again() {
+        let reference = Arc::new(Ref::new(Arc::new(Value::I32(0)), None).unwrap());
+        let runs = Cell::new(0);
+        stm::run_in_transaction(|| {
+            runs.set(runs.get() + 1);
+            stm::deref(&reference);
+            if runs.get() == 1 {
+                commit_elsewhere(&reference, 100);
+            }
+            stm::alter(&reference, &add(), &[Arc::new(Value::I32(1))]).unwrap();
+            Value::Nil
+        });
+        assert_eq!(2, runs.get());
+        assert_eq!(Value::I32(101), *stm::deref(&reference));
+    }
+
+    #[test]
+    fn commute_never_conflicts() {
+        let reference = Arc::new(Ref::new(Arc::new(Value::I32(0)), None).unwrap());
+        let runs = Cell::new(0);
+        stm::run_in_transaction(|| {
+            runs.set(runs.get() + 1);
+            stm::commute(&reference, &add(), &[Arc::new(Value::I32(1))]).unwrap();
+            if runs.get() == 1 {
+                commit_elsewhere(&reference, 100);
+            }
+            Value::Nil
+        });
+        assert_eq!(1, runs.get());
+        assert_eq!(Value::I32(101), *stm::deref(&reference));
+    }
+
+    #[test]
+    fn changes_are_all_or_nothing() {
+        let repl = Repl::default();
+        let result = repl.eval_all(&[
+            "(def from (ref 10))",
+            "(def to (ref 0 :validator (fn [x] (< x 5))))",
+            "(dosync (alter from - 3) (alter to + 3))",
+            "[@from @to]",
+        ]);
+        assert_eq!("[7 3]", result.to_string());
+        assert!(matches!(
+            repl.eval_all(&["(dosync (alter from - 3) (alter to + 3))"]),
+            Value::Condition(_)
+        ));
+        assert!(matches!(
+            repl.eval_all(&["(dosync (ref-set from 0) (+ 1 \"x\"))"]),
+            Value::Condition(_)
+        ));
+        assert_eq!("[7 3]", repl.eval_all(&["[@from @to]"]).to_string());
+    }
+
+    #[test]
+    fn refs_only_change_in_a_transaction() {
+        let repl = Repl::default();
+        assert!(matches!(
+            repl.eval_all(&["(def r (ref 1))", "(alter r inc)"]),
+            Value::Condition(_)
+        ));
+        assert!(matches!(
+            repl.eval_all(&["(dosync (commute r inc) (ref-set r 5))"]),
+            Value::Condition(_)
+        ));
+        let result = repl.eval_all(&["(dosync (dosync (alter r inc)) (alter r inc))", "@r"]);
+        assert_eq!(Value::I32(3), result);
+    }
+}
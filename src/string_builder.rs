@@ -0,0 +1,59 @@
+//! (sb), (sb-append! b x) and (sb-str b)
+//!
+//! A string builder is a string that's added to in place;  so building a string up a piece
+//! at a time takes time in proportion to its length,  not (as with (str s x) over and over)
+//! to its length squared.  Interop works on it too,  as on a java.lang.StringBuilder:
+//! (.append b x), (.length b) and (.toString b)
+use crate::clojure_java_io;
+use crate::error_message;
+use crate::host_object::HostObject;
+use crate::value::Value;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Default)]
+pub struct StringBuilder {
+    text: Mutex<String>,
+}
+impl StringBuilder {
+    /// Adds what str makes of x
+    pub fn append(&self, x: &Value) {
+        self.text.lock().unwrap().push_str(&x.to_string());
+    }
+    pub fn text(&self) -> String {
+        self.text.lock().unwrap().clone()
+    }
+}
+/// x as a string builder,  if it is one
+pub fn builder(x: &Value) -> Result<&StringBuilder, Value> {
+    clojure_java_io::downcast::<StringBuilder>(x).ok_or_else(|| {
+        Value::Condition(
+            format!(
+                "Expected a string builder, as (sb) makes;  got {}",
+                x.to_string_explicit()
+            )
+            .into(),
+        )
+    })
+}
+
+impl HostObject for StringBuilder {
+    fn type_name(&self) -> &str {
+        "java.lang.StringBuilder"
+    }
+    fn call(&self, method: &str, args: Vec<Arc<Value>>) -> Option<Value> {
+        Some(match (method, &args[..]) {
+            ("append", [x]) => {
+                self.append(x);
+                Value::Nil
+            }
+            ("length", []) => Value::I32(self.text.lock().unwrap().chars().count() as i32),
+            ("toString", []) => Value::String(self.text().into()),
+            ("append", _) => error_message::wrong_arg_count(2, args.len() + 1),
+            ("length" | "toString", _) => error_message::wrong_arg_count(1, args.len() + 1),
+            _ => return None,
+        })
+    }
+    fn display(&self) -> String {
+        self.text()
+    }
+}
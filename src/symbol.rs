@@ -1,16 +1,121 @@
+use std::cmp::Ordering as Order;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fmt;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
 
+/// A name,  interned;  there's only ever one of each at a time,  made the first time it's
+/// asked for,  so names compare and hash by which one they are rather than by what they
+/// say.  Once nothing has a name anymore,  it's forgotten
+#[derive(Clone)]
+pub struct Name(Arc<Interned>);
+
+struct Interned {
+    text: Box<str>,
+    // Of the text,  worked out once
+    hash: u64,
+}
+
+// Every name in use,  by what it says
+#[derive(Default)]
+struct Names {
+    interned: HashMap<Box<str>, Weak<Interned>>,
+    // How many there can be before we clear out the ones no longer in use
+    prune_at: usize,
+}
+
+impl Name {
+    pub fn intern(text: &str) -> Name {
+        static NAMES: OnceLock<Mutex<Names>> = OnceLock::new();
+        let mut names = NAMES.get_or_init(Default::default).lock().unwrap();
+        if let Some(interned) = names.interned.get(text).and_then(Weak::upgrade) {
+            return Name(interned);
+        }
+        if names.interned.len() >= names.prune_at {
+            names
+                .interned
+                .retain(|_, interned| interned.strong_count() > 0);
+            names.prune_at = (names.interned.len() * 2).max(1024);
+        }
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let interned = Arc::new(Interned {
+            text: text.into(),
+            hash: hasher.finish(),
+        });
+        names
+            .interned
+            .insert(text.into(), Arc::downgrade(&interned));
+        Name(interned)
+    }
+    pub fn as_str(&self) -> &str {
+        &self.0.text
+    }
+}
+impl Deref for Name {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+impl PartialEq for Name {
+    fn eq(&self, other: &Name) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl Eq for Name {}
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl PartialEq<&str> for Name {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+impl Hash for Name {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.0.hash);
+    }
+}
+// By what they say,  so what's sorted by name reads in order
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Name) -> Option<Order> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Name {
+    fn cmp(&self, other: &Name) -> Order {
+        self.as_str().cmp(other.as_str())
+    }
+}
+impl fmt::Debug for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A symbol;  its name and namespace are interned,  so symbols compare and hash in
+/// constant time
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
 pub struct Symbol {
-    pub name: String,
+    pub name: Name,
     // @TODO Should this be an optional string?
     //       on one hand, playing with this is closer to the original,
     //       and slightly easier to read and understand (for me).
     //       But you might say it doesn't force you to cover the None
     //       route, the sort of invariants ADTs are good at.
     //       Most likely, we will reimplement this as Option<String>
-    pub ns: String,
+    pub ns: Name,
 }
 impl Symbol {
     pub fn intern(name: &str) -> Symbol {
@@ -19,7 +124,8 @@ impl Symbol {
         // @TODO See if we will have any problems with manipulating
         //       text in other languages
         //       I think we will be ok here though
-        if let Some(ind) = name.chars().position(|c| c == '/') {
+        // As in Clojure,  / on its own is a name,  not a separator
+        if let Some(ind) = name.chars().position(|c| c == '/').filter(|_| name != "/") {
             // @TODO Make sure that the index given by ^
             //       has the same meaning as the index
             //       we are giving to this range
@@ -35,15 +141,25 @@ impl Symbol {
     }
     pub fn intern_with_ns(ns: &str, name: &str) -> Symbol {
         Symbol {
-            name: String::from(name),
-            ns: String::from(ns),
+            name: Name::intern(name),
+            ns: Name::intern(ns),
         }
     }
     pub fn unqualified(&self) -> Symbol {
-        Symbol::intern(&self.name)
+        Symbol {
+            name: self.name.clone(),
+            ns: Name::intern(""),
+        }
     }
     pub fn has_ns(&self) -> bool {
-        self.ns != ""
+        !self.ns.is_empty()
+    }
+    /// A symbol no other gensym is;  prefix followed by a number,  and maybe suffix,  as in
+    /// the x__12__auto__ that syntax-quote makes of x#
+    pub fn gensym(prefix: &str, suffix: &str) -> Symbol {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        Symbol::intern_with_ns("", &format!("{}{}{}", prefix, id, suffix))
     }
 }
 impl fmt::Display for Symbol {
@@ -58,7 +174,7 @@ impl fmt::Display for Symbol {
 mod tests {
 
     mod symbol_tests {
-        use crate::symbol::Symbol;
+        use crate::symbol::{Name, Symbol};
         use std::collections::HashMap;
 
         #[test]
@@ -66,8 +182,8 @@ mod tests {
             assert_eq!(
                 Symbol::intern("a"),
                 Symbol {
-                    ns: String::from(""),
-                    name: String::from("a")
+                    ns: Name::intern(""),
+                    name: Name::intern("a")
                 }
             );
         }
@@ -77,47 +193,56 @@ mod tests {
             assert_eq!(
                 Symbol::intern_with_ns("clojure.core", "a"),
                 Symbol {
-                    ns: String::from("clojure.core"),
-                    name: String::from("a")
+                    ns: Name::intern("clojure.core"),
+                    name: Name::intern("a")
                 }
             );
             assert_eq!(
                 Symbol::intern_with_ns("", "a"),
                 Symbol {
-                    ns: String::from(""),
-                    name: String::from("a")
+                    ns: Name::intern(""),
+                    name: Name::intern("a")
                 }
             );
             assert_eq!(
                 Symbol::intern("a"),
                 Symbol {
-                    ns: String::from(""),
-                    name: String::from("a")
+                    ns: Name::intern(""),
+                    name: Name::intern("a")
                 }
             );
             assert_eq!(
                 Symbol::intern("clojure.core/a"),
                 Symbol {
-                    ns: String::from("clojure.core"),
-                    name: String::from("a")
+                    ns: Name::intern("clojure.core"),
+                    name: Name::intern("a")
                 }
             );
             assert_eq!(
                 Symbol::intern("clojure/a"),
                 Symbol {
-                    ns: String::from("clojure"),
-                    name: String::from("a")
+                    ns: Name::intern("clojure"),
+                    name: Name::intern("a")
                 }
             );
             assert_eq!(
                 Symbol::intern("/a"),
                 Symbol {
-                    ns: String::from(""),
-                    name: String::from("a")
+                    ns: Name::intern(""),
+                    name: Name::intern("a")
                 }
             );
         }
         #[test]
+        fn names_are_one_and_the_same_however_they_are_made() {
+            let made = format!("{}/{}", "clojure.core", "a");
+            assert_eq!(Symbol::intern("clojure.core/a"), Symbol::intern(&made));
+            assert_eq!(Name::intern("a"), Symbol::intern(&made).name);
+            assert_ne!(Name::intern("a"), Name::intern("b"));
+            assert!(Name::intern("a") < Name::intern("b"));
+            assert_eq!("a", Name::intern("a").as_str());
+        }
+        #[test]
         fn test_work_with_hashmap() {
             let mut hashmap = HashMap::new();
             hashmap.insert(Symbol::intern("+"), 1_i32);
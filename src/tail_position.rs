@@ -0,0 +1,287 @@
+//! Checks,  once for each fn or loop form,  that each recur in it is in tail position and
+//! given as many args as it has bindings
+//!
+//! At runtime,  loop and fns go round again each time their body evaluates to a recur,
+//! rather than calling themselves;  so a recur anywhere but in tail position would be
+//! taken for a value.  Forms are expanded with clojure.walk/macroexpand-all,  to see where
+//! their tail is;  nested fns have their own recur target,  and are checked when made.
+//! What's found is kept for the form,  keyed by its list's allocation as trace.rs keeps
+//! positions,  so making a fn again,  or evaluating a loop again,  neither expands its
+//! macros again nor walks it
+use crate::clojure_walk::macroexpand_all::macroexpand_all_in;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::persistent_list::{self, PersistentList, ToPersistentListIter};
+use crate::persistent_list_map::ToPersistentListMapIter;
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, Weak};
+
+/// Checks (fn params & body),  given as arity,  its params and body;  Err is the condition
+/// to give in place of making it
+pub fn check_fn(
+    environment: &Arc<Environment>,
+    arity: &Arc<PersistentList>,
+    arg_syms: &[Symbol],
+) -> Result<(), Value> {
+    once(arity, || match expanded(environment, "fn", arity) {
+        Some(items) => Analysis {
+            argc: recur_count(arg_syms),
+        }
+        .body(items.get(2..).unwrap_or_default(), true),
+        None => Ok(()),
+    })
+}
+
+/// Checks (loop bindings & body),  given as args,  its bindings and body
+pub fn check_loop(environment: &Arc<Environment>, args: &Arc<PersistentList>) -> Result<(), Value> {
+    once(args, || match expanded(environment, "loop", args) {
+        // The loop is the target of the recurs in its own body
+        Some(items) => Analysis { argc: 0 }.list(&items, false),
+        None => Ok(()),
+    })
+}
+
+/// Checks body,  already macroexpanded,  the body of a fn or loop taking argc args;  as the
+/// compiler has it
+pub fn check_expanded(body: &[Arc<Value>], argc: usize) -> Result<(), Value> {
+    Analysis { argc }.body(body, true)
+}
+
+/// How many args recurring to a fn with arg_syms takes;  its rest args being one
+pub fn recur_count(arg_syms: &[Symbol]) -> usize {
+    arg_syms.iter().filter(|sym| sym.name != "&").count()
+}
+
+// (special & args),  expanded;  None if a macro in it gave a condition,  which is left for
+// evaluating it to point out
+fn expanded(
+    environment: &Arc<Environment>,
+    special: &str,
+    args: &Arc<PersistentList>,
+) -> Option<Vec<Arc<Value>>> {
+    let form = persistent_list::cons_rc(Symbol::intern(special).to_rc_value(), Arc::clone(args));
+    match macroexpand_all_in(environment, &[], &form.to_rc_value()) {
+        Value::PersistentList(list) => Some(list.iter().collect()),
+        _ => None,
+    }
+}
+
+struct Checked {
+    // Keeps the allocation from being reused while what was found for it is here
+    owner: Weak<PersistentList>,
+    result: Result<(), Value>,
+}
+
+// Shared by every thread,  as the code read is
+#[derive(Default)]
+struct Cache {
+    checked: HashMap<usize, Checked>,
+    // How many there can be before we next drop those of forms that are gone
+    prune_at: usize,
+}
+
+fn cache() -> MutexGuard<'static, Cache> {
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+    CACHE.get_or_init(Default::default).lock().unwrap()
+}
+
+// What check finds for form;  found the first time only.  The cache isn't held while
+// checking,  as expanding a macro can make fns of its own
+fn once(
+    form: &Arc<PersistentList>,
+    check: impl FnOnce() -> Result<(), Value>,
+) -> Result<(), Value> {
+    let key = Arc::as_ptr(form) as usize;
+    if let Some(checked) = cache().checked.get(&key) {
+        return checked.result.clone();
+    }
+    let result = check();
+    let mut cache = cache();
+    cache.checked.insert(
+        key,
+        Checked {
+            owner: Arc::downgrade(form),
+            result: result.clone(),
+        },
+    );
+    if cache.checked.len() >= cache.prune_at {
+        cache
+            .checked
+            .retain(|_, checked| checked.owner.strong_count() > 0);
+        cache.prune_at = 64.max(cache.checked.len() * 2);
+    }
+    result
+}
+
+struct Analysis {
+    argc: usize,
+}
+impl Analysis {
+    fn form(&self, form: &Arc<Value>, tail: bool) -> Result<(), Value> {
+        match &**form {
+            Value::PersistentList(list) => {
+                let items = list.iter().collect::<Vec<Arc<Value>>>();
+                self.list(&items, tail)
+            }
+            Value::PersistentVector(vector) => {
+                self.forms(&vector.iter().cloned().collect::<Vec<Arc<Value>>>())
+            }
+            Value::PersistentListMap(map) => map.iter().try_for_each(|entry| {
+                self.form(&entry.key, false)?;
+                self.form(&entry.val, false)
+            }),
+            Value::PersistentHashSet(set) => set.iter().try_for_each(|val| self.form(&val, false)),
+            _ => Ok(()),
+        }
+    }
+
+    // Forms none of which are in tail position
+    fn forms(&self, forms: &[Arc<Value>]) -> Result<(), Value> {
+        forms.iter().try_for_each(|form| self.form(form, false))
+    }
+
+    // Forms evaluated in turn for the last one's value;  which is in tail position if
+    // they are
+    fn body(&self, forms: &[Arc<Value>], tail: bool) -> Result<(), Value> {
+        match forms.split_last() {
+            Some((last, init)) => {
+                self.forms(init)?;
+                self.form(last, tail)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn list(&self, items: &[Arc<Value>], tail: bool) -> Result<(), Value> {
+        let head = match items.first() {
+            Some(head) => head,
+            None => return Ok(()),
+        };
+        let sym = match &**head {
+            Value::Symbol(sym) => sym,
+            _ => return self.forms(items),
+        };
+        let args = &items[1..];
+        if !sym.has_ns() || sym.ns == "clojure.core" {
+            match sym.name.as_str() {
+                "recur" => {
+                    if !tail {
+                        return Err(Value::Condition("Can only recur from tail position".into()));
+                    }
+                    if args.len() != self.argc {
+                        return Err(error_message::mismatched_recur_count(self.argc, args.len()));
+                    }
+                    return self.forms(args);
+                }
+                "quote" | "fn" | "defmacro" => return Ok(()),
+                "do" | "do-fn*" => return self.body(args, tail),
+                "if" => {
+                    return match args.split_first() {
+                        Some((test, branches)) => {
+                            self.form(test, false)?;
+                            branches
+                                .iter()
+                                .try_for_each(|branch| self.form(branch, tail))
+                        }
+                        None => Ok(()),
+                    };
+                }
+                "let" => {
+                    return match args.split_first() {
+                        Some((bindings, body)) => {
+                            self.form(bindings, false)?;
+                            self.body(body, tail)
+                        }
+                        None => Ok(()),
+                    };
+                }
+                // A loop is the target of the recurs in its own body
+                "loop" => {
+                    return match args.split_first() {
+                        Some((bindings, body)) => {
+                            self.form(bindings, false)?;
+                            let argc = match &**bindings {
+                                Value::PersistentVector(vector) => vector.len() / 2,
+                                _ => return Ok(()),
+                            };
+                            Analysis { argc }.body(body, true)
+                        }
+                        None => Ok(()),
+                    };
+                }
+                // Nothing in a try is in tail position;  its value is only known once
+                // finally has run
+                "try" => return self.forms(args),
+                _ => {}
+            }
+        }
+        self.forms(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    fn condition(source: &str) -> String {
+        match Repl::default().eval_all(&[source]) {
+            Value::Condition(message) => message.to_string(),
+            val => panic!("Expected a condition, got {}", val),
+        }
+    }
+
+    #[test]
+    fn recur_must_be_in_tail_position() {
+        assert_eq!(
+            "Can only recur from tail position",
+            condition("(fn [x] (+ 1 (recur x)))")
+        );
+        assert_eq!(
+            "Can only recur from tail position",
+            condition("(loop [i 0] (when (recur i) 1))")
+        );
+        assert_eq!(
+            "Can only recur from tail position",
+            condition("(defn f [x] (try (recur x) (finally 1)))")
+        );
+    }
+
+    #[test]
+    fn recur_args_are_counted_when_made() {
+        assert_eq!(
+            "Mismatched argument count to recur (Given: 2, Expected: 1)",
+            condition("(defn g [x] (if x (recur x 1) 0))")
+        );
+        assert_eq!(
+            "Mismatched argument count to recur (Given: 1, Expected: 2)",
+            condition("(fn [x] (loop [a 1 b 2] (cond a (recur 1) :else b)))")
+        );
+    }
+
+    #[test]
+    fn recur_goes_round_without_growing_the_stack() {
+        let result = Repl::default().eval_all(&[
+            "(defn count-down [n & more] (if (> n 0) (recur (dec n) more) (nth more 1)))",
+            "(defn twice [n] (loop [i 0 acc 0] (if (> i n) acc (let [j (inc i)] (do (recur j (+ acc 2)))))))",
+            "[(count-down 100000 1 2) (twice 100000)]",
+        ]);
+        assert_eq!("[2 200002]", result.to_string());
+    }
+
+    #[test]
+    fn a_form_evaluated_again_is_not_checked_again() {
+        // Each is expanded twice;  in checking the defn around it,  and then for itself
+        let result = Repl::default().eval_all(&[
+            "(def expansions (atom 0))",
+            "(defmacro counted [x] (do (swap! expansions inc) x))",
+            "(defn f [] (fn [] (counted 1)))",
+            "(defn g [] (loop [i 0] (if i i (counted i))))",
+            "(dotimes [_ 3] (f) (g))",
+            "@expansions",
+        ]);
+        assert_eq!("4", result.to_string());
+    }
+}
@@ -0,0 +1,193 @@
+//! schedule! and every!;  fns called later,  by a timer thread
+//!
+//! There's one timer thread,  started the first time something's scheduled.  It calls each
+//! fn when it's due,  one after another,  with what the thread that scheduled it had in
+//! place when it did (see future.rs's Conveyed);  so a fn that takes long holds up the rest,
+//! and had better hand its work to a future.  A fn that fails is reported on stderr,  and
+//! if it repeats,  it's called again when it's next due.  Like a daemon thread's,  what's
+//! still scheduled as the process ends never runs
+use crate::error_message;
+use crate::future::Conveyed;
+use crate::host_object::HostObject;
+use crate::ifn;
+use crate::numbers;
+use crate::output::{self, Stream};
+use crate::type_tag::TypeTag;
+use crate::value::Value;
+use std::cmp::Ordering as Order;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// As much as a future gets
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// The time ms (a number,  not negative) says,  in milliseconds
+pub fn millis(ms: &Value) -> Result<Duration, Value> {
+    match numbers::to_f64(ms) {
+        Some(ms) if ms >= 0.0 && ms.is_finite() => Ok(Duration::from_secs_f64(ms / 1000.0)),
+        Some(_) => Err(Value::Condition(
+            format!("Expected a time in milliseconds,  not {}", ms).into(),
+        )),
+        None => Err(error_message::type_mismatch(TypeTag::I32, ms)),
+    }
+}
+
+/// A fn scheduled to be called;  once,  or every period.  What schedule! and every! give
+pub struct Task {
+    f: Arc<Value>,
+    period: Option<Duration>,
+    conveyed: Conveyed,
+    cancelled: AtomicBool,
+    // Set once a fn called just the once has been
+    done: AtomicBool,
+}
+impl Task {
+    /// Whether it's still to be called;  so a fn that's been called just the once,  or
+    /// that's already been cancelled,  isn't
+    pub fn cancel(&self) -> bool {
+        !self.done.load(Ordering::SeqCst) && !self.cancelled.swap(true, Ordering::SeqCst)
+    }
+    fn status(&self) -> &'static str {
+        if self.cancelled.load(Ordering::SeqCst) {
+            "cancelled"
+        } else if self.done.load(Ordering::SeqCst) {
+            "done"
+        } else {
+            "pending"
+        }
+    }
+}
+impl fmt::Debug for Task {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display())
+    }
+}
+impl HostObject for Task {
+    fn type_name(&self) -> &str {
+        "clojure.lang.ScheduledTask"
+    }
+    fn call(&self, method: &str, args: Vec<Arc<Value>>) -> Option<Value> {
+        if !args.is_empty() {
+            return None;
+        }
+        Some(Value::Boolean(match method {
+            "cancel" => self.cancel(),
+            "isCancelled" => self.cancelled.load(Ordering::SeqCst),
+            "isDone" => self.status() != "pending",
+            _ => return None,
+        }))
+    }
+    fn display(&self) -> String {
+        format!("#task[{{:status :{}}}]", self.status())
+    }
+}
+
+/// Has f called after delay,  and then every period,  if there is one;  until it's cancelled
+pub fn schedule(f: Arc<Value>, delay: Duration, period: Option<Duration>) -> Arc<Task> {
+    let task = Arc::new(Task {
+        f,
+        period,
+        conveyed: Conveyed::capture(),
+        cancelled: AtomicBool::new(false),
+        done: AtomicBool::new(false),
+    });
+    timer().add(Arc::clone(&task), Instant::now() + delay);
+    task
+}
+
+// A task,  and when it's next due;  those scheduled for the same time run in the order they
+// were scheduled
+struct Due {
+    at: Instant,
+    order: u64,
+    task: Arc<Task>,
+}
+impl PartialEq for Due {
+    fn eq(&self, other: &Due) -> bool {
+        self.cmp(other) == Order::Equal
+    }
+}
+impl Eq for Due {}
+impl PartialOrd for Due {
+    fn partial_cmp(&self, other: &Due) -> Option<Order> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Due {
+    // Reversed,  so the heap gives the soonest first
+    fn cmp(&self, other: &Due) -> Order {
+        (other.at, other.order).cmp(&(self.at, self.order))
+    }
+}
+
+struct Timer {
+    queue: Mutex<BinaryHeap<Due>>,
+    // Signalled when something's added;  it may be due sooner than what's waited for
+    added: Condvar,
+    order: AtomicU64,
+}
+impl Timer {
+    fn add(&self, task: Arc<Task>, at: Instant) {
+        let order = self.order.fetch_add(1, Ordering::SeqCst);
+        self.queue.lock().unwrap().push(Due { at, order, task });
+        self.added.notify_one();
+    }
+    // The next task due,  once it is
+    fn next(&self) -> Due {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            queue = match queue.peek() {
+                Some(due) if due.at <= now => return queue.pop().unwrap(),
+                Some(due) => {
+                    let wait = due.at - now;
+                    self.added.wait_timeout(queue, wait).unwrap().0
+                }
+                None => self.added.wait(queue).unwrap(),
+            };
+        }
+    }
+    fn run(&self) {
+        loop {
+            let due = self.next();
+            let task = &due.task;
+            if task.cancelled.load(Ordering::SeqCst) {
+                continue;
+            }
+            let result = task.conveyed.clone().run(|| ifn::call(&task.f, vec![]));
+            if let Value::Condition(condition) = result {
+                output::write(
+                    Stream::Err,
+                    &format!("Scheduled fn failed: {}\n", condition),
+                );
+            }
+            match task.period {
+                // Not again and again to catch up,  if it took longer than its period
+                Some(period) => self.add(Arc::clone(task), (due.at + period).max(Instant::now())),
+                None => task.done.store(true, Ordering::SeqCst),
+            }
+        }
+    }
+}
+
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Arc<Timer>> = OnceLock::new();
+    TIMER.get_or_init(|| {
+        let timer = Arc::new(Timer {
+            queue: Mutex::new(BinaryHeap::new()),
+            added: Condvar::new(),
+            order: AtomicU64::new(0),
+        });
+        let running = Arc::clone(&timer);
+        thread::Builder::new()
+            .name(String::from("timer"))
+            .stack_size(STACK_SIZE)
+            .spawn(move || running.run())
+            .expect("a thread for the timer");
+        timer
+    })
+}
@@ -0,0 +1,141 @@
+//! Where a condition came from;  the calls it was given back through on its way out,  each
+//! with the position in its file of the list that made it
+//!
+//! The reader records where each list it reads from a file starts,  keyed by the list's
+//! allocation,  as metadata is in meta.rs;  evaluating it shares that allocation,  so when
+//! a call gives a condition,  the call's frame can say where it's written
+use crate::condition;
+use crate::persistent_list::PersistentList;
+use crate::value::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, Weak};
+
+/// Where a list starts in the file it was read from;  line and column both count from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcePosition {
+    pub file: Arc<str>,
+    pub line: usize,
+    pub col: usize,
+}
+impl fmt::Display for SourcePosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.col)
+    }
+}
+
+/// A call a condition was given back through;  named for what was called,  as written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub name: String,
+    pub position: Option<SourcePosition>,
+}
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.position {
+            Some(position) => write!(f, "{} ({})", self.name, position),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+struct Slot {
+    // Keeps the allocation from being reused while its position is here
+    owner: Weak<PersistentList>,
+    position: SourcePosition,
+}
+
+// Shared by every thread,  as the code read is
+#[derive(Default)]
+struct Positions {
+    slots: HashMap<usize, Slot>,
+    // How many slots there can be before we next drop those of lists that are gone
+    prune_at: usize,
+}
+
+fn positions() -> MutexGuard<'static, Positions> {
+    static POSITIONS: OnceLock<Mutex<Positions>> = OnceLock::new();
+    POSITIONS.get_or_init(Default::default).lock().unwrap()
+}
+
+fn key(list: &Arc<PersistentList>) -> usize {
+    Arc::as_ptr(list) as usize
+}
+
+/// Records that list,  just read,  starts at position
+pub fn set_position(list: &Arc<PersistentList>, position: SourcePosition) {
+    let mut positions = positions();
+    let owner = Arc::downgrade(list);
+    positions.slots.insert(key(list), Slot { owner, position });
+    if positions.slots.len() >= positions.prune_at {
+        positions
+            .slots
+            .retain(|_, slot| slot.owner.strong_count() > 0);
+        positions.prune_at = 64.max(positions.slots.len() * 2);
+    }
+}
+
+/// Where list was read from,  if it was read from a file
+pub fn position(list: &Arc<PersistentList>) -> Option<SourcePosition> {
+    positions()
+        .slots
+        .get(&key(list))
+        .map(|slot| slot.position.clone())
+}
+
+/// Adds the frame of call,  a list that was just evaluated,  to the trace of condition,  the
+/// condition it gave
+pub fn called(condition: &Value, call: &Arc<PersistentList>) {
+    let name = match &**call {
+        PersistentList::Cons(head, _, _) => match &**head {
+            Value::Symbol(sym) => sym.to_string(),
+            _ => String::from("fn"),
+        },
+        PersistentList::Empty => String::from("fn"),
+    };
+    let position = position(call);
+    condition::push_frame(condition, Frame { name, position });
+}
+
+/// The trace of condition,  a line for each call it came out of,  innermost first;  empty if
+/// it hasn't come out of any
+pub fn stack_trace(condition: &Value) -> String {
+    condition::frames(condition)
+        .iter()
+        .map(|frame| format!("    at {}\n", frame))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::trace;
+    use crate::value::Value;
+
+    #[test]
+    fn conditions_name_each_call_they_came_out_of() {
+        let repl = Repl::default();
+        let condition = repl.eval_text(
+            "app.clj",
+            "(defn inner [x] (+ x :a))\n; the middle one\n(defn outer [x]\n  (inc (inner x)))\n(outer 1)",
+        );
+        assert!(matches!(condition, Value::Condition(_)));
+        assert_eq!(
+            "    at + (app.clj:1:17)\n    at inner (app.clj:4:8)\n    at outer (app.clj:5:1)\n",
+            trace::stack_trace(&condition)
+        );
+    }
+
+    #[test]
+    fn caught_conditions_keep_their_trace_when_thrown_again() {
+        let repl = Repl::default();
+        let condition = repl.eval_text(
+            "t.clj",
+            "(defn f [] (throw (ex-info \"boom\" {})))\n(try (f) (catch Exception e (throw e)))",
+        );
+        assert_eq!(
+            "    at throw (t.clj:1:12)\n    at f (t.clj:2:6)\n    at throw (t.clj:2:29)\n",
+            trace::stack_trace(&condition)
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! A session's transcript;  every form evaluated at a REPL or over nREPL,  with what it
+//! printed,  so the session can be replayed later
+//!
+//! A transcript is EDN,  a map per line,  each only ever appended to the file:
+//!
+//!   {:time #inst "2026-10-16T09:30:00.123-00:00" :ns "user" :form "(+ 1 2)" :value "3"}
+//!
+//! with :error (the condition's message) in place of :value when the form failed.  The
+//! form is as it was read,  printed back out;  which reads back in as the same form
+use crate::inst;
+use crate::value::Value;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A transcript file being recorded to.  Clones all append to the same file,  so each of a
+/// server's sessions can have one
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    file: Arc<Mutex<File>>,
+}
+impl Transcript {
+    /// Opens the transcript at path,  to be added to;  it's made if it isn't there yet
+    pub fn open(path: &str) -> io::Result<Transcript> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Transcript {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+    /// Adds form,  evaluated in namespace ns,  and what it gave to the transcript.  A
+    /// transcript that can't be written to is only complained about;  the session goes on
+    pub fn record(&self, ns: &str, form: &Value, result: &Value) {
+        let line = entry_line(&timestamp(SystemTime::now()), ns, form, result);
+        let mut file = self.file.lock().unwrap();
+        if let Err(e) = writeln!(file, "{}", line) {
+            eprintln!("Could not write to transcript: {}", e);
+        }
+    }
+    /// Makes sure what's been recorded is in the file for good;  as the session ends
+    pub fn close(&self) {
+        let file = self.file.lock().unwrap();
+        if let Err(e) = file.sync_all() {
+            eprintln!("Could not write to transcript: {}", e);
+        }
+    }
+}
+
+/// A form recorded in a transcript,  and what it gave then
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub time: String,
+    pub ns: String,
+    pub form: String,
+    /// The value as it was printed;  or the message of the condition it gave
+    pub result: Result<String, String>,
+}
+
+/// The entries of a transcript,  in the order they were recorded;  lines that aren't one
+/// (say,  the last,  cut short by a crash) are skipped
+pub fn entries(transcript: &str) -> Vec<Entry> {
+    transcript.lines().filter_map(parse_entry).collect()
+}
+
+fn entry_line(time: &str, ns: &str, form: &Value, result: &Value) -> String {
+    let result = match result {
+        Value::Condition(message) => format!(":error {}", edn_string(message)),
+        value => format!(":value {}", edn_string(&value.to_string())),
+    };
+    format!(
+        "{{:time #inst {} :ns {} :form {} {}}}",
+        edn_string(time),
+        edn_string(ns),
+        edn_string(&form.to_string_explicit()),
+        result
+    )
+}
+
+fn edn_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for ch in s.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Entries only ever have keywords for keys and (maybe tagged) strings for values;  so
+// that's all this reads,  escapes and all,  which the reader can't
+fn parse_entry(line: &str) -> Option<Entry> {
+    let mut chars = line.trim().chars().peekable();
+    let skip_blanks = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+    };
+    let mut fields = HashMap::new();
+    if chars.next()? != '{' {
+        return None;
+    }
+    loop {
+        skip_blanks(&mut chars);
+        match chars.next()? {
+            '}' => break,
+            ':' => {}
+            _ => return None,
+        }
+        let key: String = std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace())).collect();
+        skip_blanks(&mut chars);
+        // A tag,  ie #inst,  only says what the string is
+        if chars.next_if_eq(&'#').is_some() {
+            while chars.next_if(|c| !c.is_whitespace()).is_some() {}
+            skip_blanks(&mut chars);
+        }
+        if chars.next()? != '"' {
+            return None;
+        }
+        let mut value = String::new();
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => value.push(match chars.next()? {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    ch => ch,
+                }),
+                ch => value.push(ch),
+            }
+        }
+        fields.insert(key, value);
+    }
+    let result = match (fields.remove("value"), fields.remove("error")) {
+        (Some(value), _) => Ok(value),
+        (None, Some(error)) => Err(error),
+        (None, None) => return None,
+    };
+    Some(Entry {
+        time: fields.remove("time").unwrap_or_default(),
+        ns: fields.remove("ns")?,
+        form: fields.remove("form")?,
+        result,
+    })
+}
+
+// time as an instant,  ie 2026-10-16T09:30:00.123-00:00
+pub(crate) fn timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    inst::format(since_epoch.as_millis() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reader;
+    use crate::transcript::{entries, entry_line, timestamp, Entry};
+    use crate::value::Value;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn entries_read_back_as_they_were_recorded() {
+        let (_, form) = reader::try_read("(str \"a\" 1) ").unwrap();
+        let transcript = format!(
+            "{}\n{}\n{{:time #inst \"cut short",
+            entry_line(
+                "2026-10-16T09:30:00.000-00:00",
+                "user",
+                &form,
+                &Value::String("a\"b\n".into())
+            ),
+            entry_line(
+                "2026-10-16T09:30:01.000-00:00",
+                "user",
+                &form,
+                &Value::Condition("bad \\ news".into())
+            ),
+        );
+        assert_eq!(
+            vec![
+                Entry {
+                    time: String::from("2026-10-16T09:30:00.000-00:00"),
+                    ns: String::from("user"),
+                    form: form.to_string_explicit(),
+                    result: Ok(String::from("a\"b\n")),
+                },
+                Entry {
+                    time: String::from("2026-10-16T09:30:01.000-00:00"),
+                    ns: String::from("user"),
+                    form: form.to_string_explicit(),
+                    result: Err(String::from("bad \\ news")),
+                },
+            ],
+            entries(&transcript)
+        );
+    }
+
+    #[test]
+    fn timestamps_are_utc_instants() {
+        assert_eq!("1970-01-01T00:00:00.000-00:00", timestamp(UNIX_EPOCH));
+        assert_eq!(
+            "2024-02-29T23:59:59.250-00:00",
+            timestamp(UNIX_EPOCH + Duration::from_millis(1_709_251_199_250))
+        );
+    }
+}
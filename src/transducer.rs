@@ -0,0 +1,289 @@
+//! Transducers;  transformations of reducing fns,  so a pipeline of maps,  filters,  takes
+//! and the like runs over a coll in one pass,  with no seq made between one step and the next
+//!
+//! A reducing fn is called with no arguments for an initial result,  with a result and an
+//! input for the next result,  and with just a result to complete it.  A transducer is an fn
+//! of one reducing fn,  giving another that does something more before passing inputs on;
+//! (map f) passes on f of each.  Those written in Rust give a Stage,  which Staged turns
+//! into the reducing fn.  A step that wants the reduce to stop gives (reduced result)
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::interrupt;
+use crate::lazy_seq::{self, LazySeq, Step};
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// What a transducer does with each input before the reducing fn it wraps gets it;  made
+/// fresh each time the transducer's applied,  so what it keeps count of starts over
+pub trait Stage: fmt::Debug + Send + Sync {
+    /// Passes what input becomes on to rf;  as many times as that's inputs,  maybe none
+    fn step(&self, rf: &Arc<dyn IFn>, result: Arc<Value>, input: Arc<Value>) -> Value;
+    /// Passes on what inputs,  one from each of several colls,  become;  only map's can
+    fn step_all(&self, _rf: &Arc<dyn IFn>, _result: Arc<Value>, inputs: Vec<Arc<Value>>) -> Value {
+        error_message::wrong_arg_count(2, inputs.len() + 1)
+    }
+    /// Passes on whatever's still held back,  then completes rf
+    fn complete(&self, rf: &Arc<dyn IFn>, result: Arc<Value>) -> Value {
+        rf.invoke(vec![result])
+    }
+}
+
+type MakeStage = dyn Fn() -> Box<dyn Stage> + Send + Sync;
+
+/// A transducer written in Rust;  what (map f) and the like give
+#[derive(Clone)]
+pub struct Transducer {
+    make_stage: Arc<MakeStage>,
+}
+impl Transducer {
+    pub fn new(make_stage: impl Fn() -> Box<dyn Stage> + Send + Sync + 'static) -> Transducer {
+        Transducer {
+            make_stage: Arc::new(make_stage),
+        }
+    }
+}
+impl fmt::Debug for Transducer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Transducer")
+    }
+}
+impl ToValue for Transducer {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for Transducer {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::IFn(rf) => Value::ifn(Staged {
+                rf: Arc::clone(rf),
+                stage: Arc::from((self.make_stage)()),
+            }),
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        }
+    }
+}
+
+/// The reducing fn a transducer makes of rf
+#[derive(Debug, Clone)]
+struct Staged {
+    rf: Arc<dyn IFn>,
+    stage: Arc<dyn Stage>,
+}
+impl IFn for Staged {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match &args[..] {
+            [] => self.rf.invoke(vec![]),
+            [result] => self.stage.complete(&self.rf, Arc::clone(result)),
+            [result, input] => self
+                .stage
+                .step(&self.rf, Arc::clone(result), Arc::clone(input)),
+            [result, inputs @ ..] => {
+                self.stage
+                    .step_all(&self.rf, Arc::clone(result), inputs.to_vec())
+            }
+        }
+    }
+}
+
+/// (reduced val),  unless it already is
+pub fn ensure_reduced(val: Value) -> Value {
+    match val {
+        Value::Reduced(_) | Value::Condition(_) => val,
+        val => Value::Reduced(Arc::new(val)),
+    }
+}
+
+/// What val wraps,  if it's (reduced ..);  otherwise val
+pub fn unreduced(val: Value) -> Value {
+    match val {
+        Value::Reduced(val) => (*val).clone(),
+        val => val,
+    }
+}
+
+/// Reduces coll with rf,  starting from init,  or with none from coll's first element;  (rf)
+/// if there's nothing to start from.  Stops early at a (reduced ..) result,  or a condition
+pub fn reduce(rf: &Arc<dyn IFn>, init: Option<Arc<Value>>, coll: &Arc<Value>) -> Value {
+    let mut result = init;
+    let walked = lazy_seq::try_for_each(coll, |input| {
+        let acc = match result.take() {
+            Some(acc) => acc,
+            None => {
+                result = Some(input);
+                return Ok(());
+            }
+        };
+        match rf.invoke(vec![acc, input]) {
+            // Stops the walk;  told apart from the walk's own conditions below
+            Value::Reduced(val) => Err(Value::Reduced(val)),
+            Value::Condition(condition) => Err(Value::Condition(condition)),
+            stepped => {
+                result = Some(Arc::new(stepped));
+                Ok(())
+            }
+        }
+    });
+    match (walked, result) {
+        (Err(Value::Reduced(val)), _) => (*val).clone(),
+        (Err(condition), _) => condition,
+        (Ok(()), Some(result)) => result.to_value(),
+        (Ok(()), None) => rf.invoke(vec![]),
+    }
+}
+
+/// A lazy seq of what xform makes of coll;  each step of coll taken only once what it's
+/// made so far has been walked past
+pub fn sequence(xform: &dyn IFn, coll: Arc<Value>) -> Value {
+    sequence_of(xform, vec![coll])
+}
+
+/// A lazy seq of what xform makes of the first element of each of colls,  then of the
+/// second of each,  and so on until any one runs out;  each step given all of them at once
+pub fn sequence_of(xform: &dyn IFn, colls: Vec<Arc<Value>>) -> Value {
+    let made = Arc::new(Mutex::new(VecDeque::new()));
+    match xform.invoke(vec![Collect {
+        made: Arc::clone(&made),
+    }
+    .to_rc_value()])
+    {
+        Value::IFn(rf) => lazy_sequence(Arc::clone(&*rf), made, Some(colls)).into_value(),
+        Value::Condition(condition) => Value::Condition(condition),
+        other => error_message::type_mismatch(TypeTag::IFn, &other),
+    }
+}
+
+// colls is None once one's run out,  or rf's asked to stop;  and rf's been completed
+fn lazy_sequence(
+    rf: Arc<dyn IFn>,
+    made: Arc<Mutex<VecDeque<Arc<Value>>>>,
+    colls: Option<Vec<Arc<Value>>>,
+) -> LazySeq {
+    LazySeq::new(move || {
+        let mut colls = colls;
+        loop {
+            if interrupt::is_interrupted() {
+                return Err(Value::Condition(interrupt::INTERRUPTED_MSG.into()));
+            }
+            let next = made.lock().unwrap().pop_front();
+            if let Some(next) = next {
+                let rest = lazy_sequence(rf, made, colls);
+                return Ok(Step::Next(next, Arc::new(rest.into_value())));
+            }
+            let some_colls = match &colls {
+                Some(some_colls) => some_colls,
+                None => return Ok(Step::Done),
+            };
+            let mut args = vec![Arc::new(Value::Nil)];
+            let mut rests = Vec::with_capacity(some_colls.len());
+            for coll in some_colls {
+                match lazy_seq::step(coll)? {
+                    Step::Next(first, rest) => {
+                        args.push(first);
+                        rests.push(rest);
+                    }
+                    Step::Done => break,
+                }
+            }
+            let (result, done) = match rests.len() == some_colls.len() {
+                true => match rf.invoke(args) {
+                    Value::Reduced(_) => (rf.invoke(vec![Arc::new(Value::Nil)]), true),
+                    stepped => {
+                        colls = Some(rests);
+                        (stepped, false)
+                    }
+                },
+                false => (rf.invoke(vec![Arc::new(Value::Nil)]), true),
+            };
+            if let Value::Condition(condition) = result {
+                return Err(Value::Condition(condition));
+            }
+            if done {
+                colls = None;
+            }
+        }
+    })
+}
+
+// The reducing fn sequence gives xform;  it only keeps what it's given,  for the seq
+#[derive(Debug, Clone)]
+struct Collect {
+    made: Arc<Mutex<VecDeque<Arc<Value>>>>,
+}
+impl ToValue for Collect {
+    fn to_value(&self) -> Value {
+        Value::ifn(self.clone())
+    }
+}
+impl IFn for Collect {
+    fn invoke(&self, args: Vec<Arc<Value>>) -> Value {
+        match &args[..] {
+            [] => Value::Nil,
+            [result] => result.to_value(),
+            [result, input] => {
+                self.made.lock().unwrap().push_back(Arc::clone(input));
+                result.to_value()
+            }
+            _ => error_message::wrong_varg_count(&[0, 1, 2], args.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    fn eval(exprs: &[&str]) -> String {
+        Repl::default().eval_all(exprs).to_string()
+    }
+
+    #[test]
+    fn a_pipeline_runs_the_same_through_transduce_into_and_sequence() {
+        let xform = "(def xf (comp (filter (fn [x] (= 1 (rem x 2)))) (map inc) (partition-all 2)))";
+        assert_eq!(
+            "[[2 4] [6]]",
+            eval(&[xform, "(transduce xf conj [] [1 2 3 4 5])"])
+        );
+        assert_eq!("[[2 4] [6]]", eval(&[xform, "(into [] xf [1 2 3 4 5])"]));
+        assert_eq!(
+            "[[2 4] [6]]",
+            eval(&[xform, "(into [] (sequence xf [1 2 3 4 5]))"])
+        );
+    }
+
+    #[test]
+    fn a_reduced_result_stops_the_reduce() {
+        assert_eq!(
+            "[1 2 3]",
+            eval(&["(into [] (comp (map inc) (take 3)) (range))"])
+        );
+        assert_eq!(
+            "6",
+            eval(&["(reduce (fn [acc x] (if (< x 4) (+ acc x) (reduced acc))) 0 (range))"])
+        );
+        assert_eq!(
+            "[[0 1] [2 3]]",
+            eval(&["(into [] (comp (partition-all 2) (take 2)) (range))"])
+        );
+    }
+
+    #[test]
+    fn sequence_steps_through_several_colls_at_once() {
+        assert_eq!(
+            "[(11 22) ([1 1] [2] [3]) [1 2 3 4] (1 2 1)]",
+            eval(&["[(sequence (map +) [1 2 3] [10 20]) (sequence (partition-by odd?) [1 1 2 3]) (into [] (mapcat (fn [x] x)) [[1 2] [3 4]]) (sequence (comp cat (random-sample 1)) [[1 2] [1]])]"])
+        );
+        assert_eq!(
+            "Wrong number of arguments given to function (Given: 3, Expected: 2)",
+            eval(&[
+                "(ex-message (try (count (sequence (filter odd?) [1] [2])) (catch Exception e e)))"
+            ])
+        );
+    }
+}
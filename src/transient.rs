@@ -0,0 +1,190 @@
+//! Transient vectors and maps,  as made by transient
+//!
+//! A transient is a collection that conj! and friends change in place,  for building up a
+//! big collection without making a new one at every step.  persistent! hands back the
+//! collection it built,  after which the transient can't be used again;  so nothing can
+//! change a collection once someone else can see it
+use crate::error_message;
+use crate::persistent_list_map::PersistentListMap;
+use crate::persistent_vector::PersistentVector;
+use crate::value::Value;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+const USED_AFTER_PERSISTENT: &str = "Transient used after persistent! call";
+
+fn used_after_persistent() -> Value {
+    Value::Condition(USED_AFTER_PERSISTENT.into())
+}
+
+#[derive(Debug)]
+pub struct TransientVector {
+    // None once persistent! has been called
+    vector: RwLock<Option<PersistentVector>>,
+}
+impl TransientVector {
+    /// Takes no time;  vector's nodes are only copied as they're first changed
+    pub fn new(vector: PersistentVector) -> TransientVector {
+        TransientVector {
+            vector: RwLock::new(Some(vector)),
+        }
+    }
+    pub fn conj(&self, val: Arc<Value>) -> Result<(), Value> {
+        match &mut *self.vector.write().unwrap() {
+            Some(vector) => {
+                vector.conj_in_place(val);
+                Ok(())
+            }
+            None => Err(used_after_persistent()),
+        }
+    }
+    /// ind can be one past the end,  as with conj
+    pub fn assoc(&self, ind: usize, val: Arc<Value>) -> Result<(), Value> {
+        match &mut *self.vector.write().unwrap() {
+            Some(vector) => {
+                let len = vector.len();
+                if vector.assoc_in_place(ind, val) {
+                    Ok(())
+                } else {
+                    Err(error_message::index_out_of_bounds(ind, len))
+                }
+            }
+            None => Err(used_after_persistent()),
+        }
+    }
+    pub fn persistent(&self) -> Result<PersistentVector, Value> {
+        self.vector
+            .write()
+            .unwrap()
+            .take()
+            .ok_or_else(used_after_persistent)
+    }
+}
+
+/// A map being built;  changed in place,  so each change takes the same time however big
+/// the map gets,  and only the parts of the map it started from that change are copied
+#[derive(Debug, Default)]
+pub struct MapEdits {
+    map: PersistentListMap,
+}
+impl MapEdits {
+    pub fn new(map: &PersistentListMap) -> MapEdits {
+        MapEdits { map: map.clone() }
+    }
+    pub fn assoc(&mut self, key: Arc<Value>, val: Arc<Value>) {
+        self.map.assoc_in_place(key, val);
+    }
+    pub fn dissoc(&mut self, key: &Arc<Value>) {
+        self.map.dissoc_in_place(key);
+    }
+    pub fn get(&self, key: &Arc<Value>) -> Option<Arc<Value>> {
+        self.map.get_entry(key).map(|entry| entry.val)
+    }
+    pub fn into_map(self) -> PersistentListMap {
+        self.map
+    }
+}
+
+#[derive(Debug)]
+pub struct TransientMap {
+    // None once persistent! has been called
+    edits: RwLock<Option<MapEdits>>,
+}
+impl TransientMap {
+    pub fn new(map: &PersistentListMap) -> TransientMap {
+        TransientMap {
+            edits: RwLock::new(Some(MapEdits::new(map))),
+        }
+    }
+    pub fn assoc(&self, key: Arc<Value>, val: Arc<Value>) -> Result<(), Value> {
+        match &mut *self.edits.write().unwrap() {
+            Some(edits) => {
+                edits.assoc(key, val);
+                Ok(())
+            }
+            None => Err(used_after_persistent()),
+        }
+    }
+    pub fn dissoc(&self, key: &Arc<Value>) -> Result<(), Value> {
+        match &mut *self.edits.write().unwrap() {
+            Some(edits) => {
+                edits.dissoc(key);
+                Ok(())
+            }
+            None => Err(used_after_persistent()),
+        }
+    }
+    pub fn get(&self, key: &Arc<Value>) -> Result<Option<Arc<Value>>, Value> {
+        match &*self.edits.read().unwrap() {
+            Some(edits) => Ok(edits.get(key)),
+            None => Err(used_after_persistent()),
+        }
+    }
+    pub fn persistent(&self) -> Result<PersistentListMap, Value> {
+        self.edits
+            .write()
+            .unwrap()
+            .take()
+            .map(MapEdits::into_map)
+            .ok_or_else(used_after_persistent)
+    }
+}
+
+impl fmt::Display for TransientVector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#transient[vector]")
+    }
+}
+impl fmt::Display for TransientMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#transient[map]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+    use crate::persistent_vector::PersistentVector;
+    use crate::transient::{TransientMap, TransientVector};
+    use crate::value::{ToValue, Value};
+
+    #[test]
+    fn building_a_vector_leaves_the_original_alone() {
+        let original = PersistentVector::empty().conj(1.to_rc_value());
+        let transient = TransientVector::new(original.clone());
+        for i in 2..100 {
+            transient.conj(i.to_rc_value()).unwrap();
+        }
+        transient.assoc(0, 0.to_rc_value()).unwrap();
+        let built = transient.persistent().unwrap();
+        assert_eq!(99, built.len());
+        assert_eq!(Some(&0.to_rc_value()), built.nth(0));
+        assert_eq!("[1]", original.to_string());
+    }
+
+    #[test]
+    fn a_map_keeps_one_entry_per_key() {
+        let original = PersistentListMap::Empty.assoc(1.to_rc_value(), 1.to_rc_value());
+        let transient = TransientMap::new(&original);
+        transient.assoc(1.to_rc_value(), 2.to_rc_value()).unwrap();
+        transient.assoc(3.to_rc_value(), 4.to_rc_value()).unwrap();
+        transient.dissoc(&3.to_rc_value()).unwrap();
+        assert_eq!(
+            Some(2.to_rc_value()),
+            transient.get(&1.to_rc_value()).unwrap()
+        );
+        assert_eq!("{1 2}", transient.persistent().unwrap().to_string());
+        assert_eq!("{1 1}", original.to_string());
+    }
+
+    #[test]
+    fn nothing_works_after_persistent() {
+        let transient = TransientVector::new(PersistentVector::empty());
+        transient.persistent().unwrap();
+        assert!(matches!(
+            transient.conj(Value::Nil.to_rc_value()),
+            Err(Value::Condition(_))
+        ));
+        assert!(matches!(transient.persistent(), Err(Value::Condition(_))));
+    }
+}
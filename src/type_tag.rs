@@ -1,23 +1,68 @@
+use crate::symbol;
+use crate::value;
 use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TypeTag {
     I32,
     F64,
+    BigInt,
+    Ratio,
+    BigDecimal,
     Boolean,
+    Char,
     Symbol,
     Keyword,
     IFn,
     Condition,
+    Exception,
+    Recur,
     PersistentList,
     PersistentVector,
     PersistentListMap,
+    PersistentHashSet,
+    PersistentTreeMap,
+    PersistentTreeSet,
+    PersistentQueue,
+    LazySeq,
+    TransientVector,
+    TransientMap,
     // Experimental; may make no sense at runtime, as we will likely be unable to take the value of a macro
     Macro,
     String,
     Integer,
     ISeq,
     Nil,
+    Promise,
+    Future,
+    Atom,
+    Ref,
+    Agent,
+    Channel,
+    Reduced,
+    HostObject,
+    IBlockingDeref,
+    Closeable,
+    Namespace,
+    Var,
+    Protocol,
+    Regex,
+    Inst,
+    Uuid,
+    Bytes,
+    // What every instance of a type made with deftype,  or defrecord,  is;  besides its own
+    // type
+    IType,
+    IRecord,
+    MultiFn,
+    // Abstract types;  nothing is an instance of these directly, but see bases
+    Number,
+    Collection,
+    Sequential,
+    IPersistentMap,
+    IPersistentSet,
+    Sorted,
+    IObj,
 }
 
 use TypeTag::*;
@@ -27,20 +72,200 @@ impl fmt::Display for TypeTag {
         let str = match self {
             I32 => std::string::String::from("rust.std.i32"),
             Boolean => std::string::String::from("rust.std.bool"),
+            Char => std::string::String::from("rust.std.char"),
             F64 => std::string::String::from("rust.std.f64"),
+            BigInt => std::string::String::from("clojure.lang.BigInt"),
+            Ratio => std::string::String::from("clojure.lang.Ratio"),
+            BigDecimal => std::string::String::from("clojure.lang.BigDecimal"),
             Symbol => std::string::String::from("clojure.lang.Symbol"),
             Keyword => std::string::String::from("clojure.lang.Keyword"),
             IFn => std::string::String::from("clojure.lang.Function"),
             Condition => std::string::String::from("clojure.lang.Condition"),
+            Exception => std::string::String::from("clojure.lang.Exception"),
+            Recur => std::string::String::from("clojure.lang.Recur"),
             PersistentList => std::string::String::from("clojure.lang.PersistentList"),
             PersistentVector => std::string::String::from("clojure.lang.PersistentVector"),
             PersistentListMap => std::string::String::from("clojure.lang.PersistentListMap"),
+            PersistentHashSet => std::string::String::from("clojure.lang.PersistentHashSet"),
+            PersistentTreeMap => std::string::String::from("clojure.lang.PersistentTreeMap"),
+            PersistentTreeSet => std::string::String::from("clojure.lang.PersistentTreeSet"),
+            PersistentQueue => std::string::String::from("clojure.lang.PersistentQueue"),
+            LazySeq => std::string::String::from("clojure.lang.LazySeq"),
+            TransientVector => std::string::String::from("clojure.lang.TransientVector"),
+            TransientMap => std::string::String::from("clojure.lang.TransientListMap"),
             Macro => std::string::String::from("clojure.lang.Macro"),
             TypeTag::String => std::string::String::from("rust.std.string.String"),
             TypeTag::Integer => std::string::String::from("clojure.lang.Integer"),
             ISeq => std::string::String::from("clojure.lang.ISeq"),
             Nil => std::string::String::from("clojure.lang.Nil"),
+            Promise => std::string::String::from("clojure.lang.Promise"),
+            Future => std::string::String::from("clojure.lang.Future"),
+            Atom => std::string::String::from("clojure.lang.Atom"),
+            Ref => std::string::String::from("clojure.lang.Ref"),
+            Agent => std::string::String::from("clojure.lang.Agent"),
+            Channel => std::string::String::from("clojure.core.async.Channel"),
+            Reduced => std::string::String::from("clojure.lang.Reduced"),
+            HostObject => std::string::String::from("rust.HostObject"),
+            IBlockingDeref => std::string::String::from("clojure.lang.IBlockingDeref"),
+            Closeable => std::string::String::from("clojure.lang.Closeable"),
+            Namespace => std::string::String::from("clojure.lang.Namespace"),
+            Var => std::string::String::from("clojure.lang.Var"),
+            Protocol => std::string::String::from("clojure.lang.Protocol"),
+            Regex => std::string::String::from("rust.regex.Regex"),
+            Inst => std::string::String::from("clojure.lang.Inst"),
+            Uuid => std::string::String::from("clojure.lang.UUID"),
+            Bytes => std::string::String::from("rust.std.bytes"),
+            IType => std::string::String::from("clojure.lang.IType"),
+            IRecord => std::string::String::from("clojure.lang.IRecord"),
+            MultiFn => std::string::String::from("clojure.lang.MultiFn"),
+            Number => std::string::String::from("clojure.lang.Number"),
+            Collection => std::string::String::from("clojure.lang.IPersistentCollection"),
+            Sequential => std::string::String::from("clojure.lang.Sequential"),
+            IPersistentMap => std::string::String::from("clojure.lang.IPersistentMap"),
+            IPersistentSet => std::string::String::from("clojure.lang.IPersistentSet"),
+            Sorted => std::string::String::from("clojure.lang.Sorted"),
+            IObj => std::string::String::from("clojure.lang.IObj"),
         };
         write!(f, "{}", str)
     }
 }
+
+const ALL: &[TypeTag] = &[
+    I32,
+    F64,
+    BigInt,
+    Ratio,
+    BigDecimal,
+    Boolean,
+    Char,
+    Symbol,
+    Keyword,
+    IFn,
+    Condition,
+    Exception,
+    Recur,
+    PersistentList,
+    PersistentVector,
+    PersistentListMap,
+    PersistentHashSet,
+    PersistentTreeMap,
+    PersistentTreeSet,
+    PersistentQueue,
+    LazySeq,
+    TransientVector,
+    TransientMap,
+    Macro,
+    TypeTag::String,
+    TypeTag::Integer,
+    ISeq,
+    Nil,
+    Promise,
+    Future,
+    Atom,
+    Ref,
+    Agent,
+    Channel,
+    Reduced,
+    HostObject,
+    IBlockingDeref,
+    Closeable,
+    Namespace,
+    Var,
+    Protocol,
+    Regex,
+    Inst,
+    Uuid,
+    Bytes,
+    IType,
+    IRecord,
+    MultiFn,
+    Number,
+    Collection,
+    Sequential,
+    IPersistentMap,
+    IPersistentSet,
+    Sorted,
+    IObj,
+];
+
+/// The built-in type hierarchy;  a small stand-in for the class hierarchy Clojure gets from
+/// the JVM, so that (isa? (class 1) 'clojure.lang.Number) and friends work.  In ClojureRS a
+/// type is represented by a symbol holding its name,  ie 'rust.std.i32
+impl TypeTag {
+    /// The type named by name,  if there is one
+    pub fn from_name(name: &str) -> Option<TypeTag> {
+        ALL.iter().find(|tag| tag.to_string() == name).copied()
+    }
+    /// The type val names,  if it's a type's symbol
+    pub fn from_value(val: &value::Value) -> Option<TypeTag> {
+        match val {
+            value::Value::Symbol(sym) if !sym.has_ns() => TypeTag::from_name(&sym.name),
+            _ => None,
+        }
+    }
+    pub fn to_symbol(self) -> symbol::Symbol {
+        symbol::Symbol::intern(&self.to_string())
+    }
+    /// The types this type directly belongs to
+    pub fn bases(&self) -> Vec<TypeTag> {
+        match self {
+            I32 | BigInt => vec![TypeTag::Integer],
+            TypeTag::Integer | Ratio | BigDecimal | F64 => vec![Number],
+            Symbol => vec![IObj],
+            PersistentList => vec![ISeq, Sequential, IObj],
+            LazySeq => vec![ISeq, Sequential],
+            PersistentVector => vec![Sequential, Collection, IObj],
+            ISeq => vec![Collection],
+            PersistentListMap => vec![IPersistentMap, IObj],
+            PersistentTreeMap => vec![IPersistentMap, Sorted, IObj],
+            PersistentHashSet => vec![IPersistentSet, IObj],
+            PersistentTreeSet => vec![IPersistentSet, Sorted, IObj],
+            PersistentQueue => vec![Sequential, Collection],
+            IPersistentMap | IPersistentSet => vec![Collection],
+            Promise | Future => vec![IBlockingDeref],
+            MultiFn => vec![IFn],
+            _ => vec![],
+        }
+    }
+    /// Every type this type belongs to,  directly or not;  nearest first, each only once
+    pub fn supers(&self) -> Vec<TypeTag> {
+        let mut supers: Vec<TypeTag> = vec![];
+        let mut pending = self.bases();
+        while !pending.is_empty() {
+            let base = pending.remove(0);
+            if !supers.contains(&base) {
+                pending.extend(base.bases());
+                supers.push(base);
+            }
+        }
+        supers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::type_tag::{TypeTag, ALL};
+
+    #[test]
+    fn names_round_trip() {
+        for tag in ALL {
+            assert_eq!(Some(*tag), TypeTag::from_name(&tag.to_string()));
+        }
+        assert_eq!(None, TypeTag::from_name("clojure.lang.Nothing"));
+    }
+
+    #[test]
+    fn supers_are_transitive() {
+        assert_eq!(
+            vec![TypeTag::Integer, TypeTag::Number],
+            TypeTag::I32.supers()
+        );
+        assert!(TypeTag::PersistentList
+            .supers()
+            .contains(&TypeTag::Collection));
+        assert!(TypeTag::PersistentVector
+            .supers()
+            .contains(&TypeTag::Collection));
+        assert!(TypeTag::Keyword.supers().is_empty());
+    }
+}
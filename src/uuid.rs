@@ -0,0 +1,33 @@
+//! UUIDs;  as read from #uuid "..",  or made by random-uuid,  kept as their 16 bytes
+
+/// The bytes of the UUID s,  ie 5d1e5a3c-8f6e-4b4f-9d5e-0a6f3c2b1a09;  or what's wrong
+/// with it
+pub fn parse(s: &str) -> Result<[u8; 16], String> {
+    let invalid = || format!("Invalid UUID string: {}", s);
+    let groups = s.split('-').map(str::len).collect::<Vec<_>>();
+    let hex = s.replace('-', "");
+    if groups != [8, 4, 4, 4, 12] || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let mut bytes = [0; 16];
+    for (ind, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[ind * 2..ind * 2 + 2], 16).unwrap();
+    }
+    Ok(bytes)
+}
+
+/// The UUID of bytes,  as it's printed;  in lower case hex
+pub fn format(bytes: &[u8; 16]) -> String {
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
@@ -1,23 +1,55 @@
+use crate::agent::Agent;
+use crate::allocations;
+use crate::atom::Atom;
+use crate::channel::Channel;
+use crate::clojure_profile;
+use crate::clojure_protocol::Protocol;
+use crate::condition;
 use crate::environment::Environment;
-use crate::ifn::IFn;
+use crate::error_message;
+use crate::future::Future;
+use crate::host_object::{self, HostObject};
+use crate::ifn::{self, IFn};
+use crate::inst;
+use crate::interrupt;
 use crate::keyword::Keyword;
 use crate::lambda;
+use crate::lazy_seq::LazySeq;
 use crate::maps::MapEntry;
+use crate::meta;
+use crate::namespace::{self, Location, Namespace};
+use crate::numbers;
+use crate::persistent_hash_set::PersistentHashSet;
 use crate::persistent_list::PersistentList::Cons;
 use crate::persistent_list::{PersistentList, ToPersistentList, ToPersistentListIter};
-use crate::persistent_list_map::{PersistentListMap, ToPersistentListMapIter};
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap, ToPersistentListMapIter};
+use crate::persistent_queue::PersistentQueue;
+use crate::persistent_tree_map::PersistentTreeMap;
+use crate::persistent_tree_set::PersistentTreeSet;
 use crate::persistent_vector::PersistentVector;
+use crate::promise::Promise;
+use crate::reader;
+use crate::record::Instance;
+use crate::shared_str::SharedStr;
+use crate::stm::Ref;
 use crate::symbol::Symbol;
+use crate::tail_position;
+use crate::trace;
+use crate::transient::{TransientMap, TransientVector};
 use crate::type_tag::TypeTag;
+use crate::uuid;
+use crate::var;
 
 extern crate rand;
-use rand::Rng;
 
+use num_traits::ToPrimitive;
 use std::cmp::{Ord, Ordering};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
-use std::rc::Rc;
+use std::sync::Arc;
 
 // @TODO Change IFn's name -- IFn is a function, not an IFn.
 //       The body it executes just happens to be an the IFn.
@@ -25,14 +57,24 @@ use std::rc::Rc;
 /// an int, a symbol, a fn, and so on.  Some Values here are more specific than others;
 /// I32 wraps any I32, but QuoteMacro specifically wraps the value for the quote macro, which
 /// is a special case macro that has hardcoded behavior.
+//
+// Layout note: anything that doesn't fit in a word lives behind an Arc, so a Value stays
+// 16 bytes (see the size test at the bottom of this file) and cloning one is at most a refcount
+// bump.  A pointer to a dyn IFn or str is two words,  so fns and host objects are behind a
+// second Arc (made by Value::ifn and the like),  and strings are SharedStrs
 #[derive(Debug, Clone)]
 pub enum Value {
     I32(i32),
     F64(f64),
+    // Past i32s;  see numbers.rs
+    BigInt(Arc<num_bigint::BigInt>),
+    Ratio(Arc<num_rational::BigRational>),
+    BigDecimal(Arc<bigdecimal::BigDecimal>),
     Boolean(bool),
-    Symbol(Symbol),
-    Keyword(Keyword),
-    IFn(Rc<dyn IFn>),
+    Char(char),
+    Symbol(Arc<Symbol>),
+    Keyword(Arc<Keyword>),
+    IFn(Arc<Arc<dyn IFn>>),
     //
     // Special case functions
     //
@@ -41,13 +83,25 @@ pub enum Value {
     // but it allows me to reach into our local environment through an invoke
     LexicalEvalFn,
 
-    PersistentList(PersistentList),
-    PersistentVector(PersistentVector),
-    PersistentListMap(PersistentListMap),
+    PersistentList(Arc<PersistentList>),
+    PersistentVector(Arc<PersistentVector>),
+    PersistentListMap(Arc<PersistentListMap>),
+    PersistentHashSet(Arc<PersistentHashSet>),
+    PersistentTreeMap(Arc<PersistentTreeMap>),
+    PersistentTreeSet(Arc<PersistentTreeSet>),
+    PersistentQueue(Arc<PersistentQueue>),
+    LazySeq(Arc<LazySeq>),
+    TransientVector(Arc<TransientVector>),
+    TransientMap(Arc<TransientMap>),
 
-    Condition(std::string::String),
+    Condition(SharedStr),
+    // A condition as a value,  not thrown;  as made by ex-info,  or caught by catch.  It
+    // shares the condition's message,  and so what condition.rs keeps beside it
+    Exception(SharedStr),
+    // What (recur a b) evaluates to;  the loop or fn it's in catches it and goes round again
+    Recur(Arc<Vec<Arc<Value>>>),
     // Macro body is still a function, that will be applied to our unevaled arguments
-    Macro(Rc<dyn IFn>),
+    Macro(Arc<Arc<dyn IFn>>),
     //
     // Special case macros
     //
@@ -56,11 +110,41 @@ pub enum Value {
     DefMacro,
     FnMacro,
     LetMacro,
+    LoopMacro,
     IfMacro,
+    TryMacro,
+    VarMacro,
 
-    String(std::string::String),
+    String(SharedStr),
     Nil,
+
+    Promise(Arc<Promise>),
+    Future(Arc<Future>),
+    Atom(Arc<Atom>),
+    Ref(Arc<Ref>),
+    Agent(Arc<Agent>),
+    Channel(Arc<Channel>),
+    // What (reduced val) wraps val in,  to stop a reduce early;  see transducer.rs
+    Reduced(Arc<Value>),
+    // One of whatever embeds us's own;  see host_object.rs
+    HostObject(Arc<Arc<dyn HostObject>>),
+    Namespace(Arc<Namespace>),
+    Var(Arc<var::Var>),
+    Instance(Arc<Instance>),
+    Protocol(Arc<Protocol>),
+    Regex(Arc<regex::Regex>),
+    // Milliseconds since the epoch;  see inst.rs
+    Inst(i64),
+    Uuid(Arc<[u8; 16]>),
+    // A byte array;  as slurp-bytes and byte-array make
+    Bytes(Arc<Vec<u8>>),
 }
+// A value may be handed to another thread,  as a future's body is;  so everything it holds
+// must be safe to share
+const _: fn() = || {
+    fn shared<T: Send + Sync>() {}
+    shared::<Value>();
+};
 use crate::value::Value::*;
 
 impl PartialEq for Value {
@@ -71,29 +155,120 @@ impl PartialEq for Value {
         match (self, other) {
             (I32(i), I32(i2)) => i == i2,
             (F64(d), F64(d2)) => d == d2,
+            (a, b) if numbers::is_number(a) && numbers::is_number(b) => numbers::equal(a, b),
             (Boolean(b), Boolean(b2)) => b == b2,
+            (Char(c), Char(c2)) => c == c2,
             (Symbol(sym), Symbol(sym2)) => sym == sym2,
             (Keyword(kw), Keyword(kw2)) => kw == kw2,
-            // Equality not defined on functions, similar to Clojure
-            // Change this perhaps? Diverge?
-            (IFn(_), IFn(_)) => false,
+            // As in Clojure,  a function is only equal to itself
+            (IFn(f), IFn(f2)) | (Macro(f), Macro(f2)) => {
+                std::ptr::addr_eq(Arc::as_ptr(&**f), Arc::as_ptr(&**f2))
+            }
             // Is it misleading for equality to sometimes work?
             (LexicalEvalFn, LexicalEvalFn) => true,
-            (PersistentList(plist), PersistentList(plist2)) => plist == plist2,
-            (PersistentVector(pvector), PersistentVector(pvector2)) => *pvector == *pvector2,
-            (PersistentListMap(plistmap), PersistentListMap(plistmap2)) => *plistmap == *plistmap2,
+            // Lists, vectors and seqs are equal to each other element by element,  maps
+            // to maps with the same entries,  and sets to sets with the same members,
+            // whatever kind of list, map or set each is
+            (a, b) if a.is_sequential() && b.is_sequential() => a.sequential().eq(b.sequential()),
+            (a, b) if a.is_map() && b.is_map() => equal_maps(a, b),
+            (a, b) if a.is_set() && b.is_set() => equal_sets(a, b),
             (Condition(msg), Condition(msg2)) => msg == msg2,
+            (Exception(msg), Exception(msg2)) => SharedStr::as_ptr(msg) == SharedStr::as_ptr(msg2),
+            (Recur(vals), Recur(vals2)) => vals == vals2,
             (QuoteMacro, QuoteMacro) => true,
             (DefmacroMacro, DefmacroMacro) => true,
             (DefMacro, DefMacro) => true,
             (LetMacro, LetMacro) => true,
+            (LoopMacro, LoopMacro) => true,
+            (TryMacro, TryMacro) => true,
+            (VarMacro, VarMacro) => true,
             (String(string), String(string2)) => string == string2,
             (Nil, Nil) => true,
+            // Reference types are only ever equal to themselves
+            (Promise(promise), Promise(promise2)) => Arc::ptr_eq(promise, promise2),
+            (Future(future), Future(future2)) => Arc::ptr_eq(future, future2),
+            (Atom(atom), Atom(atom2)) => Arc::ptr_eq(atom, atom2),
+            (Ref(reference), Ref(reference2)) => Arc::ptr_eq(reference, reference2),
+            (Agent(agent), Agent(agent2)) => Arc::ptr_eq(agent, agent2),
+            (Channel(channel), Channel(channel2)) => Arc::ptr_eq(channel, channel2),
+            (Reduced(val), Reduced(val2)) => Arc::ptr_eq(val, val2),
+            (HostObject(object), HostObject(object2)) => {
+                std::ptr::addr_eq(Arc::as_ptr(&**object), Arc::as_ptr(&**object2))
+            }
+            (Namespace(ns), Namespace(ns2)) => Arc::ptr_eq(ns, ns2),
+            (Var(var), Var(var2)) => var == var2,
+            (Instance(instance), Instance(instance2)) => instance == instance2,
+            (Protocol(protocol), Protocol(protocol2)) => Arc::ptr_eq(protocol, protocol2),
+            // As in Clojure,  two regexes with the same pattern aren't equal
+            (Regex(regex), Regex(regex2)) => Arc::ptr_eq(regex, regex2),
+            (Inst(millis), Inst(millis2)) => millis == millis2,
+            (Uuid(bytes), Uuid(bytes2)) => bytes == bytes2,
+            // As in Clojure,  an array is only equal to itself
+            (Bytes(bytes), Bytes(bytes2)) => Arc::ptr_eq(bytes, bytes2),
+            (TransientVector(tvector), TransientVector(tvector2)) => Arc::ptr_eq(tvector, tvector2),
+            (TransientMap(tmap), TransientMap(tmap2)) => Arc::ptr_eq(tmap, tmap2),
             _ => false,
         }
     }
 }
 
+/// Hashes vals in order;  shared by lists, vectors and seqs,  as they're equal to each
+/// other when their elements are
+pub(crate) fn hash_ordered<H: Hasher>(vals: impl Iterator<Item = Arc<Value>>, state: &mut H) {
+    let mut count: usize = 0;
+    for val in vals {
+        val.hash(state);
+        count += 1;
+    }
+    count.hash(state)
+}
+
+/// Hashes items whatever order they come in;  shared by maps and sets,  as two of
+/// different kinds (or with different comparators) can still be equal
+pub(crate) fn hash_unordered<T: Hash, H: Hasher>(items: impl Iterator<Item = T>, state: &mut H) {
+    let mut combined: u64 = 0;
+    for item in items {
+        let mut hasher = DefaultHasher::new();
+        item.hash(&mut hasher);
+        combined = combined.wrapping_add(hasher.finish());
+    }
+    combined.hash(state)
+}
+
+fn equal_maps(a: &Value, b: &Value) -> bool {
+    let entries = |map: &Value| match map {
+        PersistentListMap(plistmap) => plistmap.iter().collect::<Vec<MapEntry>>(),
+        PersistentTreeMap(ptmap) => ptmap.iter().collect(),
+        _ => vec![],
+    };
+    let (a_entries, b_entries) = (entries(a), entries(b));
+    a_entries.len() == b_entries.len()
+        && a_entries.iter().all(|entry| match b {
+            PersistentTreeMap(ptmap) => ptmap
+                .get_entry(&entry.key)
+                .is_some_and(|b_entry| b_entry.val == entry.val),
+            PersistentListMap(plistmap) => plistmap
+                .get_entry(&entry.key)
+                .is_some_and(|b_entry| b_entry.val == entry.val),
+            _ => false,
+        })
+}
+
+fn equal_sets(a: &Value, b: &Value) -> bool {
+    let members = |set: &Value| match set {
+        PersistentHashSet(pset) => pset.iter().collect::<Vec<Arc<Value>>>(),
+        PersistentTreeSet(ptset) => ptset.iter().collect(),
+        _ => vec![],
+    };
+    let a_members = members(a);
+    a_members.len() == members(b).len()
+        && a_members.iter().all(|val| match b {
+            PersistentHashSet(pset) => pset.contains(val),
+            PersistentTreeSet(ptset) => ptset.contains(val),
+            _ => false,
+        })
+}
+
 // Again, this is certainly not the right away to do this
 // @FIXME remove this entire monstrocity
 #[derive(Debug, Clone, Hash)]
@@ -105,6 +280,9 @@ enum ValueHash {
     FnMacro,
     IfMacro,
     LetMacro,
+    LoopMacro,
+    TryMacro,
+    VarMacro,
     Nil,
 }
 impl Eq for Value {}
@@ -112,36 +290,65 @@ impl Hash for Value {
     fn hash<H: Hasher>(&self, state: &mut H) {
         match self {
             I32(i) => i.hash(state),
-            F64(d) => d.to_value().hash(state),
+            // -0.0 = 0.0,  so they have to hash alike
+            F64(d) if *d == 0.0 => 0.0f64.to_bits().hash(state),
+            F64(d) => d.to_bits().hash(state),
+            // As the i32 it's equal to,  if there is one
+            BigInt(i) => match i.to_i32() {
+                Some(i) => i.hash(state),
+                None => i.hash(state),
+            },
+            Ratio(r) => r.hash(state),
+            BigDecimal(d) => d.hash(state),
             Boolean(b) => b.hash(state),
+            Char(c) => c.hash(state),
             Symbol(sym) => sym.hash(state),
             Keyword(kw) => kw.hash(state),
-            IFn(_) => {
-                let mut rng = rand::thread_rng();
-                let n2: u16 = rng.gen();
-                n2.hash(state)
-            }
+            IFn(f) => (Arc::as_ptr(&**f) as *const () as usize).hash(state),
             LexicalEvalFn => (ValueHash::LexicalEvalFn).hash(state),
+            // Each kind of collection hashes alike with the kinds it can be equal to
             PersistentList(plist) => plist.hash(state),
             PersistentVector(pvector) => pvector.hash(state),
             PersistentListMap(plistmap) => plistmap.hash(state),
+            PersistentHashSet(pset) => pset.hash(state),
+            PersistentTreeMap(ptmap) => ptmap.hash(state),
+            PersistentTreeSet(ptset) => ptset.hash(state),
+            PersistentQueue(pqueue) => pqueue.hash(state),
+            LazySeq(lseq) => lseq.hash(state),
             Condition(msg) => msg.hash(state),
-            // Random hash is temporary;
-            // @TODO implement hashing for functions / macros
-            Macro(_) => {
-                let mut rng = rand::thread_rng();
-                let n2: u16 = rng.gen();
-                n2.hash(state)
-            }
+            Exception(msg) => SharedStr::as_ptr(msg).hash(state),
+            Recur(vals) => vals.hash(state),
+            Macro(f) => (Arc::as_ptr(&**f) as *const () as usize).hash(state),
             QuoteMacro => ValueHash::QuoteMacro.hash(state),
             DefmacroMacro => ValueHash::DefmacroMacro.hash(state),
             DefMacro => ValueHash::DefMacro.hash(state),
             FnMacro => ValueHash::FnMacro.hash(state),
             LetMacro => ValueHash::LetMacro.hash(state),
+            LoopMacro => ValueHash::LoopMacro.hash(state),
             IfMacro => ValueHash::IfMacro.hash(state),
+            TryMacro => ValueHash::TryMacro.hash(state),
+            VarMacro => ValueHash::VarMacro.hash(state),
 
             String(string) => string.hash(state),
             Nil => ValueHash::Nil.hash(state),
+            Promise(promise) => (Arc::as_ptr(promise) as usize).hash(state),
+            Future(future) => (Arc::as_ptr(future) as usize).hash(state),
+            Atom(atom) => (Arc::as_ptr(atom) as usize).hash(state),
+            Ref(reference) => (Arc::as_ptr(reference) as usize).hash(state),
+            Agent(agent) => (Arc::as_ptr(agent) as usize).hash(state),
+            Channel(channel) => (Arc::as_ptr(channel) as usize).hash(state),
+            Reduced(val) => (Arc::as_ptr(val) as usize).hash(state),
+            HostObject(object) => (Arc::as_ptr(&**object) as *const () as usize).hash(state),
+            Namespace(ns) => ns.name.hash(state),
+            Var(var) => var.qualified().hash(state),
+            Instance(instance) => instance.hash(state),
+            Protocol(protocol) => (Arc::as_ptr(protocol) as usize).hash(state),
+            Regex(regex) => (Arc::as_ptr(regex) as usize).hash(state),
+            Inst(millis) => millis.hash(state),
+            Uuid(bytes) => bytes.hash(state),
+            Bytes(bytes) => (Arc::as_ptr(bytes) as usize).hash(state),
+            TransientVector(tvector) => (Arc::as_ptr(tvector) as usize).hash(state),
+            TransientMap(tmap) => (Arc::as_ptr(tmap) as usize).hash(state),
         }
         // self.id.hash(state);
         // self.phone.hash(state);
@@ -152,15 +359,38 @@ impl fmt::Display for Value {
         let str = match self {
             I32(val) => val.to_string(),
             F64(val) => val.to_string(),
+            BigInt(val) => val.to_string(),
+            Ratio(val) => val.to_string(),
+            BigDecimal(val) => val.to_string(),
             Boolean(val) => val.to_string(),
+            Char(c) => c.to_string(),
             Symbol(sym) => sym.to_string(),
             Keyword(kw) => kw.to_string(),
-            IFn(_) => std::string::String::from("#function[]"),
+            IFn(ifn) => match ifn.name() {
+                Some(name) => format!("#function[{}]", name),
+                None => std::string::String::from("#function[]"),
+            },
             LexicalEvalFn => std::string::String::from("#function[lexical-eval*]"),
             PersistentList(plist) => plist.to_string(),
             PersistentVector(pvector) => pvector.to_string(),
             PersistentListMap(plistmap) => plistmap.to_string(),
+            PersistentHashSet(pset) => pset.to_string(),
+            PersistentTreeMap(ptmap) => ptmap.to_string(),
+            PersistentTreeSet(ptset) => ptset.to_string(),
+            PersistentQueue(pqueue) => pqueue.to_string(),
+            LazySeq(lseq) => lseq.to_string(),
             Condition(msg) => format!("#Condition[\"{}\"]", msg),
+            Exception(msg) => match condition::ex_data(self) {
+                Some(data) => format!("#error[\"{}\" {}]", msg, data),
+                None => format!("#error[\"{}\"]", msg),
+            },
+            Recur(vals) => format!(
+                "#recur[{}]",
+                vals.iter()
+                    .map(|val| val.to_string_explicit())
+                    .collect::<Vec<std::string::String>>()
+                    .join(" ")
+            ),
             Macro(_) => std::string::String::from("#macro[]"),
             QuoteMacro => std::string::String::from("#macro[quote*]"),
             DefMacro => std::string::String::from("#macro[def*]"),
@@ -168,13 +398,56 @@ impl fmt::Display for Value {
             FnMacro => std::string::String::from("#macro[fn*]"),
             IfMacro => std::string::String::from("#macro[if*]"),
             LetMacro => std::string::String::from("#macro[let*]"),
-            Value::String(string) => string.clone(),
+            LoopMacro => std::string::String::from("#macro[loop*]"),
+            TryMacro => std::string::String::from("#macro[try*]"),
+            VarMacro => std::string::String::from("#macro[var*]"),
+            Value::String(string) => string.to_string(),
             Nil => std::string::String::from("nil"),
+            Promise(promise) => promise.to_string(),
+            Future(future) => future.to_string(),
+            Atom(atom) => atom.to_string(),
+            Ref(reference) => reference.to_string(),
+            Agent(agent) => agent.to_string(),
+            Channel(channel) => channel.to_string(),
+            Reduced(val) => format!(
+                "#reduced[{{:status :ready, :val {}}}]",
+                val.to_string_explicit()
+            ),
+            HostObject(object) => object.display(),
+            Namespace(ns) => ns.to_string(),
+            Var(var) => var.to_string(),
+            Instance(instance) => instance.to_string(),
+            Protocol(protocol) => protocol.to_string(),
+            Regex(regex) => format!("#\"{}\"", regex.as_str()),
+            Inst(millis) => format!("#inst \"{}\"", inst::format(*millis)),
+            Uuid(bytes) => format!("#uuid \"{}\"", uuid::format(bytes)),
+            // Signed,  as Java's bytes are
+            Bytes(bytes) => format!(
+                "#bytes [{}]",
+                bytes
+                    .iter()
+                    .map(|&b| (b as i8).to_string())
+                    .collect::<Vec<std::string::String>>()
+                    .join(" ")
+            ),
+            TransientVector(tvector) => tvector.to_string(),
+            TransientMap(tmap) => tmap.to_string(),
         };
         write!(f, "{}", str)
     }
 }
 impl Value {
+    /// f as a Value;  behind the second Arc the layout note on Value mentions
+    pub fn ifn(f: impl IFn + 'static) -> Value {
+        Value::IFn(Arc::new(Arc::new(f)))
+    }
+    /// A macro,  whose expansion is f called on its unevaluated args
+    pub fn macro_(f: impl IFn + 'static) -> Value {
+        Value::Macro(Arc::new(Arc::new(f)))
+    }
+    pub fn host_object(object: impl HostObject) -> Value {
+        Value::HostObject(Arc::new(Arc::new(object)))
+    }
     //
     // Likely temporary
     // I cannot remember for the life of me whether or not there's a function like this normally
@@ -185,7 +458,13 @@ impl Value {
     //
     pub fn to_string_explicit(&self) -> std::string::String {
         match self {
-            Value::String(string) => format!("\"{}\"", string),
+            Value::String(string) => reader::quote_string(string),
+            Value::Char(c) => match reader::CHAR_NAMES.iter().find(|(_, named)| named == c) {
+                Some((name, _)) => format!("\\{}", name),
+                None => format!("\\{}", c),
+            },
+            Value::BigInt(i) => format!("{}N", i),
+            Value::BigDecimal(d) => format!("{}M", d),
             _ => self.to_string(),
         }
     }
@@ -193,15 +472,27 @@ impl Value {
         match self {
             Value::I32(_) => TypeTag::I32,
             Value::F64(_) => TypeTag::F64,
+            Value::BigInt(_) => TypeTag::BigInt,
+            Value::Ratio(_) => TypeTag::Ratio,
+            Value::BigDecimal(_) => TypeTag::BigDecimal,
             Value::Boolean(_) => TypeTag::Boolean,
+            Value::Char(_) => TypeTag::Char,
             Value::Symbol(_) => TypeTag::Symbol,
             Value::Keyword(_) => TypeTag::Keyword,
+            Value::IFn(ifn) if ifn.as_multi_fn().is_some() => TypeTag::MultiFn,
             Value::IFn(_) => TypeTag::IFn,
             Value::LexicalEvalFn => TypeTag::IFn,
             Value::PersistentList(_) => TypeTag::PersistentList,
             Value::PersistentVector(_) => TypeTag::PersistentVector,
             Value::PersistentListMap(_) => TypeTag::PersistentListMap,
+            Value::PersistentHashSet(_) => TypeTag::PersistentHashSet,
+            Value::PersistentTreeMap(_) => TypeTag::PersistentTreeMap,
+            Value::PersistentTreeSet(_) => TypeTag::PersistentTreeSet,
+            Value::PersistentQueue(_) => TypeTag::PersistentQueue,
+            Value::LazySeq(_) => TypeTag::LazySeq,
             Value::Condition(_) => TypeTag::Condition,
+            Value::Exception(_) => TypeTag::Exception,
+            Value::Recur(_) => TypeTag::Recur,
             // Note; normal Clojure cannot take the value of a macro, so I don't imagine this
             // having significance in the long run, but we will see
             Value::Macro(_) => TypeTag::Macro,
@@ -209,10 +500,31 @@ impl Value {
             Value::DefMacro => TypeTag::Macro,
             Value::DefmacroMacro => TypeTag::Macro,
             Value::LetMacro => TypeTag::Macro,
+            Value::LoopMacro => TypeTag::Macro,
             Value::FnMacro => TypeTag::Macro,
             Value::IfMacro => TypeTag::Macro,
+            Value::TryMacro => TypeTag::Macro,
+            Value::VarMacro => TypeTag::Macro,
             Value::String(_) => TypeTag::String,
             Value::Nil => TypeTag::Nil,
+            Value::Promise(_) => TypeTag::Promise,
+            Value::Future(_) => TypeTag::Future,
+            Value::Atom(_) => TypeTag::Atom,
+            Value::Ref(_) => TypeTag::Ref,
+            Value::Agent(_) => TypeTag::Agent,
+            Value::Channel(_) => TypeTag::Channel,
+            Value::Reduced(_) => TypeTag::Reduced,
+            Value::HostObject(_) => TypeTag::HostObject,
+            Value::Namespace(_) => TypeTag::Namespace,
+            Value::Var(_) => TypeTag::Var,
+            Value::Instance(instance) => instance.type_tag(),
+            Value::Protocol(_) => TypeTag::Protocol,
+            Value::Regex(_) => TypeTag::Regex,
+            Value::Inst(_) => TypeTag::Inst,
+            Value::Uuid(_) => TypeTag::Uuid,
+            Value::Bytes(_) => TypeTag::Bytes,
+            Value::TransientVector(_) => TypeTag::TransientVector,
+            Value::TransientMap(_) => TypeTag::TransientMap,
         }
     }
 
@@ -237,36 +549,80 @@ impl Value {
     // hunt around for each individual implementation.
     //
     /// Applies any valid function-like Value to a PersistentList, or returns None if our Value can't be applied
+    ///
+    /// call is the whole list args is the tail of;  a condition a function gives is traced
+    /// back to it
     fn apply_to_persistent_list(
-        &self,
-        environment: &Rc<Environment>,
-        args: &Rc<PersistentList>,
-    ) -> Option<Rc<Value>> {
-        match self {
+        self: &Arc<Value>,
+        environment: &Arc<Environment>,
+        args: &Arc<PersistentList>,
+        call: &Arc<PersistentList>,
+    ) -> Option<Arc<Value>> {
+        match &**self {
             Value::IFn(ifn) => {
-                // Eval arguments
-                let evaled_arg_refs = PersistentList::iter(args)
-                    .map(|rc_arg| rc_arg.eval_to_rc(Rc::clone(environment)))
-                    .collect::<Vec<Rc<Value>>>();
+                // Eval arguments;  the first to throw a condition is what the call gives,
+                // without ifn being called at all
+                let evaled_arg_refs = match PersistentList::iter(args)
+                    .map(|rc_arg| eval_or_throw(&rc_arg, environment))
+                    .collect::<Result<Vec<Arc<Value>>, Arc<Value>>>()
+                {
+                    Ok(evaled_arg_refs) => evaled_arg_refs,
+                    Err(condition) => return Some(condition),
+                };
 
-                // Invoke fn on arguments
-                Some(Rc::new(ifn.invoke(evaled_arg_refs)))
+                // Invoke fn on arguments;  timed as a call of the var it's from,  if we're
+                // profiling
+                let result = clojure_profile::call(
+                    || clojure_profile::called_var(environment, call),
+                    || ifn.invoke(evaled_arg_refs),
+                );
+                allocations::made(&result);
+                if let Value::Condition(_) = result {
+                    trace::called(&result, call);
+                }
+                Some(Arc::new(result))
+            }
+            // (#'f a b) calls what f is now
+            Value::Var(var) => var
+                .deref()
+                .apply_to_persistent_list(environment, args, call),
+            //
+            // (:k coll) looks k up in coll,  as (get coll k) does,  and ({:k 1} :k) and
+            // (#{1} 1) look their arg up in themselves;  with the keyword or collection the
+            // call was read with,  rather than a copy made each time it's evaluated
+            //
+            _ if ifn::is_callable(self) => {
+                let evaled_arg_refs = match PersistentList::iter(args)
+                    .map(|rc_arg| eval_or_throw(&rc_arg, environment))
+                    .collect::<Result<Vec<Arc<Value>>, Arc<Value>>>()
+                {
+                    Ok(evaled_arg_refs) => evaled_arg_refs,
+                    Err(condition) => return Some(condition),
+                };
+                let result = ifn::call(self, evaled_arg_refs);
+                if let Value::Condition(_) = result {
+                    trace::called(&result, call);
+                }
+                Some(Arc::new(result))
             }
             LexicalEvalFn => {
                 if args.len() != 1 {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: 1)",
-                        args.len()
-                    ))));
+                    return Some(Arc::new(Value::Condition(
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: 1)",
+                            args.len()
+                        )
+                        .into(),
+                    )));
                 }
                 // This should only be one value
                 let evaled_arg_values = PersistentList::iter(args)
-                    .map(|rc_arg| rc_arg.eval_to_rc(Rc::clone(environment)))
-                    .collect::<Vec<Rc<Value>>>();
+                    .map(|rc_arg| rc_arg.eval_to_rc(Arc::clone(environment)))
+                    .collect::<Vec<Arc<Value>>>();
 
                 let evaled_arg = evaled_arg_values.get(0).unwrap();
 
-                Some(evaled_arg.eval_to_rc(Rc::clone(environment)))
+                Some(evaled_arg.eval_to_rc(Arc::clone(environment)))
             }
             //
             // Unless I'm mistaken, this is incorrect; instead of having a phase where
@@ -277,11 +633,19 @@ impl Value {
             // that's never interested me all that much
             //
             Value::Macro(ifn) => {
-                let arg_refs = PersistentList::iter(args).collect::<Vec<Rc<Value>>>();
+                let arg_refs = PersistentList::iter(args).collect::<Vec<Arc<Value>>>();
 
-                let macroexpansion = Rc::new(ifn.invoke(arg_refs));
+                let macroexpansion = Arc::new(ifn.invoke(arg_refs));
+                // So what it expands to is defined,  and reported,  where the call was written
+                if let (Value::PersistentList(expansion), Some(position)) =
+                    (&*macroexpansion, trace::position(call))
+                {
+                    if trace::position(expansion).is_none() {
+                        trace::set_position(expansion, position);
+                    }
+                }
 
-                Some(macroexpansion.eval_to_rc(Rc::clone(environment)))
+                Some(macroexpansion.eval_to_rc(Arc::clone(environment)))
             }
             //
             // Special case macros
@@ -300,48 +664,96 @@ impl Value {
             //   around :D. Originally needed for our lambdas,  we can probably now,
             //   should we choose,  define def without a 'special case macro', an extra
             //   value type -- although we still need to hardcode its definition in Rust,
-            //   as an implementation of the generic Value::Macro(Rc<IFn>)
+            //   as an implementation of the generic Value::Macro(Arc<IFn>)
             //
             DefMacro => {
                 let arg_rc_values = PersistentList::iter(args)
                     .map(|rc_arg| rc_arg)
-                    .collect::<Vec<Rc<Value>>>();
+                    .collect::<Vec<Arc<Value>>>();
 
-                if arg_rc_values.len() > 2 || arg_rc_values.is_empty() {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: 1-2)",
-                        arg_rc_values.len()
-                    ))));
+                if arg_rc_values.len() > 3 || arg_rc_values.len() < 2 {
+                    return Some(Arc::new(Value::Condition(
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: 2-3)",
+                            arg_rc_values.len()
+                        )
+                        .into(),
+                    )));
                 }
                 let defname = arg_rc_values.get(0).unwrap();
+                // (def name "doc" val)
+                let docstring = match arg_rc_values.len() {
+                    3 => match &*arg_rc_values[1] {
+                        Value::String(_) => Some(Arc::clone(&arg_rc_values[1])),
+                        _ => {
+                            return Some(Arc::new(error_message::type_mismatch(
+                                TypeTag::String,
+                                &arg_rc_values[1],
+                            )))
+                        }
+                    },
+                    _ => None,
+                };
                 let defval = arg_rc_values
-                    .get(1)
+                    .last()
                     .unwrap()
-                    .eval_to_rc(Rc::clone(&environment));
-                // Let's not do docstrings yet
-                // let docstring = ...
+                    .eval_to_rc(Arc::clone(environment));
+                // As in (def f (fn [x] (+ 1 (recur x)))) ;  nothing is defined
+                if let Value::Condition(_) = &*defval {
+                    return Some(defval);
+                }
                 match &**defname {
                     Value::Symbol(sym) => {
-                        environment.insert(sym.clone(), defval);
-                        // @TODO return var. For now, however, we only have symbols
-                        // @TODO intern from environment, don't make new sym ?
-                        Some(sym.to_rc_value())
+                        let defval = named_for(defval, sym, environment);
+                        // (def ^:private a 1) ;  the metadata is evaluated,  as a map
+                        // literal would be
+                        let mut meta = meta::meta(defname)
+                            .map(|meta| meta.eval_to_rc(Arc::clone(environment)));
+                        if let Some(docstring) = docstring {
+                            let defined = match meta.as_deref() {
+                                Some(Value::PersistentListMap(meta)) => (**meta).clone(),
+                                _ => PersistentListMap::Empty,
+                            };
+                            let doc = Keyword::intern("doc").to_rc_value();
+                            meta = Some(defined.assoc(doc, docstring).to_rc_value());
+                        }
+                        let define =
+                            || environment.insert_with_meta(Symbol::clone(sym), defval, meta);
+                        // Read from a file,  it's defined where it was written there
+                        match trace::position(call) {
+                            Some(position) => namespace::defining_at(
+                                Location {
+                                    file: position.file.to_string(),
+                                    line: position.line,
+                                },
+                                define,
+                            ),
+                            None => define(),
+                        }
+                        let namespace = environment
+                            .find_or_create_namespace(&environment.get_current_namespace());
+                        Some(Arc::new(Value::Var(Arc::new(var::Var::new(
+                            namespace, sym,
+                        )))))
                     }
-                    _ => Some(Rc::new(Value::Condition(std::string::String::from(
-                        "First argument to def must be a symbol",
-                    )))),
+                    _ => Some(Arc::new(Value::Condition(
+                        "First argument to def must be a symbol".into(),
+                    ))),
                 }
             }
             DefmacroMacro => {
                 let arg_rc_values = PersistentList::iter(args)
                     .map(|rc_arg| rc_arg)
-                    .collect::<Vec<Rc<Value>>>();
+                    .collect::<Vec<Arc<Value>>>();
 
                 if arg_rc_values.len() < 2 || arg_rc_values.is_empty() {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: >=2)",
-                        args.len()
-                    ))));
+                    return Some(Arc::new(Value::Condition(
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: >=2)",
+                            args.len()
+                        )
+                        .into(),
+                    )));
                 }
                 let macro_name = arg_rc_values.get(0).unwrap();
                 let macro_args = arg_rc_values.get(1).unwrap();
@@ -352,119 +764,101 @@ impl Value {
                     arg_rc_values.get(2..).unwrap()
                 };
                 let mut macro_invokable_body_vec =
-                    vec![Symbol::intern("fn").to_rc_value(), Rc::clone(macro_args)];
+                    vec![Symbol::intern("fn").to_rc_value(), Arc::clone(macro_args)];
                 // vec![do expr1 expr2 expr3]
                 macro_invokable_body_vec.extend_from_slice(macro_body_exprs);
                 let macro_invokable_body = macro_invokable_body_vec
                     .into_list()
-                    .eval(Rc::clone(&environment));
+                    .eval(Arc::clone(environment));
                 let macro_value = match &macro_invokable_body {
-		    Value::IFn(ifn) => Rc::new(Value::Macro(Rc::clone(&ifn))),
-		    _ => Rc::new(Value::Condition(std::string::String::from("Compiler Error: your macro_value somehow compiled into something else entirely.  I don't even know how that happened,  this behavior is hardcoded, that's impressive")))
+		    Value::IFn(ifn) => Arc::new(Value::Macro(Arc::clone(ifn))),
+		    _ => Arc::new(Value::Condition("Compiler Error: your macro_value somehow compiled into something else entirely.  I don't even know how that happened,  this behavior is hardcoded, that's impressive".into()))
 		};
                 Some(
                     vec![
                         Symbol::intern("def").to_rc_value(),
-                        Rc::clone(macro_name),
+                        Arc::clone(macro_name),
                         macro_value,
                     ]
                     .into_list()
-                    .eval_to_rc(Rc::clone(&environment)),
+                    .eval_to_rc(Arc::clone(environment)),
                 )
             }
             //
             // (fn [x y z] (+ x y z))
+            // (fn ([x] (+ x 1)) ([x y] (+ x y)))
             //
             // @TODO Rename for* everywhere, define for in terms of for* in
             //       ClojureRS
             FnMacro => {
-                let arg_rc_values = PersistentList::iter(args)
-                    .map(|rc_arg| rc_arg)
-                    .collect::<Vec<Rc<Value>>>();
+                let arg_rc_values = PersistentList::iter(args).collect::<Vec<Arc<Value>>>();
 
                 if arg_rc_values.is_empty() {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expect: >=1",
-                        arg_rc_values.len()
-                    ))));
+                    return Some(Arc::new(Value::Condition(
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expect: >=1",
+                            arg_rc_values.len()
+                        )
+                        .into(),
+                    )));
                 }
                 // Let's not do fn names yet
-                // let fnname = arg_rc_values.get(0).unwrap();
-                let fn_args = arg_rc_values.get(0).unwrap();
                 // Let's not do docstrings yet
-                // let docstring = ...
-                match &**fn_args {
-                    Value::PersistentVector(PersistentVector { vals }) => {
-                        let mut arg_syms_vec = vec![];
-                        let enclosing_environment =
-                            Rc::new(Environment::new_local_environment(Rc::clone(&environment)));
-                        for val in vals.iter() {
-                            if let Value::Symbol(sym) = &**val {
-                                arg_syms_vec.push(sym.clone());
-                            }
-                        }
-
-                        let fn_body =
-			// (fn [x y] ) -> nil 
-			    if arg_rc_values.len() <= 1 {
-				Rc::new(Value::Nil)
-				// (fn [x y] expr) -> expr 
-			    } else if arg_rc_values.len() == 2 {
-				Rc::clone(arg_rc_values.get(1).unwrap())
-				// (fn [x y] expr1 expr2 expr3) -> (do expr1 expr2 expr3) 
-			    } else {
-				// (&[expr1 expr2 expr3] 
-				let body_exprs = arg_rc_values.get(1..).unwrap();
-				// vec![do]
-				let mut do_body = vec![Symbol::intern("do").to_rc_value()];
-				// vec![do expr1 expr2 expr3]
-				do_body.extend_from_slice(body_exprs);
-				// (do expr1 expr2 expr3) 
-				do_body.into_list().to_rc_value()
-			    };
-
-                        Some(Rc::new(
-                            lambda::Fn {
-                                body: fn_body,
-                                enclosing_environment,
-                                arg_syms: arg_syms_vec,
-                            }
-                            .to_value(),
-                        ))
+                let lambda = match &*arg_rc_values[0] {
+                    Value::PersistentVector(_) => {
+                        lambda::Fn::new(environment, args).map(|lambda| lambda.to_value())
                     }
-                    _ => Some(Rc::new(Value::Condition(std::string::String::from(
-                        "First argument to def must be a symbol",
-                    )))),
+                    // Each arity a list of its params and its body
+                    Value::PersistentList(_) => arg_rc_values
+                        .iter()
+                        .map(|arity| match &**arity {
+                            Value::PersistentList(arity) => lambda::Fn::new(environment, arity),
+                            _ => Err(Value::Condition(
+                                format!("Invalid fn arity, expected a list: {}", arity).into(),
+                            )),
+                        })
+                        .collect::<Result<Vec<lambda::Fn>, Value>>()
+                        .and_then(lambda::MultiArityFn::new)
+                        .map(|lambda| lambda.to_value()),
+                    _ => Err(Value::Condition("Parameter declaration missing".into())),
+                };
+                let lambda = Arc::new(lambda.unwrap_or_else(|condition| condition));
+                if !matches!(*lambda, Value::Condition(_)) {
+                    allocations::made(&lambda);
                 }
+                Some(lambda)
             }
             LetMacro => {
                 let arg_rc_values = PersistentList::iter(args)
                     .map(|rc_arg| rc_arg)
-                    .collect::<Vec<Rc<Value>>>();
+                    .collect::<Vec<Arc<Value>>>();
                 if arg_rc_values.is_empty() || arg_rc_values.len() > 2 {
                     // @TODO: we give 0 but it may be 3, 4, 5...
-                    return Some(Rc::new(Value::Condition(std::string::String::from(
-                        "Wrong number of arguments given to let (Given: 0, Expecting: 1 or 2)",
-                    ))));
+                    return Some(Arc::new(Value::Condition(
+                        "Wrong number of arguments given to let (Given: 0, Expecting: 1 or 2)"
+                            .into(),
+                    )));
                 }
                 // Already guaranteed to exist by earlier checks
                 let local_bindings = arg_rc_values.get(0).unwrap();
                 match &**local_bindings {
                     Value::PersistentVector(vector) => {
-                        //let mut local_environment_map : HashMap<Symbol,Rc<Value>> = HashMap::new();
+                        //let mut local_environment_map : HashMap<Symbol,Arc<Value>> = HashMap::new();
                         let local_environment =
-                            Rc::new(Environment::new_local_environment(Rc::clone(environment)));
+                            Arc::new(Environment::new_local_environment(Arc::clone(environment)));
                         // let chunk_test2 =
-                        for pair in vector.vals.chunks(2) {
-                            if let Some(rc_sym) = (&*pair).get(0)
+                        let bindings = vector.iter().cloned().collect::<Vec<Arc<Value>>>();
+                        for pair in bindings.chunks(2) {
+                            if let Some(rc_sym) = pair.first()
                             //(*pair[0]).clone()
                             {
-                                let val = (&*pair)
-                                    .get(1)
-                                    .unwrap()
-                                    .eval_to_rc(Rc::clone(&local_environment));
+                                let val =
+                                    match eval_or_throw(pair.get(1).unwrap(), &local_environment) {
+                                        Ok(val) => val,
+                                        Err(condition) => return Some(condition),
+                                    };
                                 if let Value::Symbol(sym) = &(**rc_sym) {
-                                    local_environment.insert(sym.clone(), val);
+                                    local_environment.insert(Symbol::clone(sym), val);
                                     //println!("Sym found: {:?}: {:?}",sym,val)
                                 }
                             } else {
@@ -475,12 +869,79 @@ impl Value {
                         if let Some(body_) = body {
                             Some(body_.eval_to_rc(local_environment))
                         } else {
-                            Some(Rc::new(Value::Nil))
+                            Some(Arc::new(Value::Nil))
+                        }
+                    }
+                    _ => Some(Arc::new(Value::Condition(
+                        "Bindings to let should be a vector".into(),
+                    ))),
+                }
+            }
+            //
+            // (loop [a 1 b 2] body)
+            //
+            // Binds like let;  then, each time body evaluates to (recur x y), goes round again
+            // with a bound to x and b to y.  Each time round gets a fresh environment,  so a fn
+            // made in body keeps the bindings it was made with, not whatever they last were
+            //
+            LoopMacro => {
+                let arg_rc_values = PersistentList::iter(args).collect::<Vec<Arc<Value>>>();
+                let bindings = match arg_rc_values.first().map(|bindings| &**bindings) {
+                    Some(Value::PersistentVector(vector)) => {
+                        vector.iter().cloned().collect::<Vec<Arc<Value>>>()
+                    }
+                    Some(_) => {
+                        return Some(Arc::new(Value::Condition(
+                            "Bindings to loop should be a vector".into(),
+                        )))
+                    }
+                    None => {
+                        return Some(Arc::new(Value::Condition(
+                            "Wrong number of arguments given to loop (Given: 0, Expected: >=1)"
+                                .into(),
+                        )))
+                    }
+                };
+                if let Err(condition) = tail_position::check_loop(environment, args) {
+                    return Some(Arc::new(condition));
+                }
+                let mut syms = vec![];
+                let mut local_environment =
+                    Arc::new(Environment::new_local_environment(Arc::clone(environment)));
+                for pair in bindings.chunks(2) {
+                    match (&*pair[0], pair.get(1)) {
+                        (Value::Symbol(sym), Some(val)) => {
+                            let val = match eval_or_throw(val, &local_environment) {
+                                Ok(val) => val,
+                                Err(condition) => return Some(condition),
+                            };
+                            local_environment.insert(Symbol::clone(sym), val);
+                            syms.push(Symbol::clone(sym));
+                        }
+                        _ => {
+                            return Some(Arc::new(Value::Condition(
+                                "Bindings to loop should be pairs of a symbol and a value".into(),
+                            )))
                         }
                     }
-                    _ => Some(Rc::new(Value::Condition(std::string::String::from(
-                        "Bindings to let should be a vector",
-                    )))),
+                }
+                loop {
+                    let result = eval_body(&arg_rc_values[1..], &local_environment);
+                    let vals = match &*result {
+                        Value::Recur(vals) => vals,
+                        _ => return Some(result),
+                    };
+                    if vals.len() != syms.len() {
+                        return Some(Arc::new(error_message::mismatched_recur_count(
+                            syms.len(),
+                            vals.len(),
+                        )));
+                    }
+                    local_environment =
+                        Arc::new(Environment::new_local_environment(Arc::clone(environment)));
+                    for (sym, val) in syms.iter().zip(vals.iter()) {
+                        local_environment.insert(Symbol::clone(sym), Arc::clone(val));
+                    }
                 }
             }
             //
@@ -489,58 +950,241 @@ impl Value {
             //
             QuoteMacro => {
                 match args.len().cmp(&1) {
-                    Ordering::Greater => Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: 1)",
-                        args.len()
-                    )))),
+                    Ordering::Greater => Some(Arc::new(Value::Condition(
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: 1)",
+                            args.len()
+                        )
+                        .into(),
+                    ))),
                     // @TODO define is_empty()
-                    Ordering::Less => Some(Rc::new(Value::Condition(std::string::String::from(
-                        "Wrong number of arguments (Given: 0, Expected: 1)",
-                    )))),
+                    Ordering::Less => Some(Arc::new(Value::Condition(
+                        "Wrong number of arguments (Given: 0, Expected: 1)".into(),
+                    ))),
                     Ordering::Equal => Some(args.nth(0)),
                 }
             }
+            //
+            // (var x),  or #'x,  is x's var;  resolved where it's written,  as x would be,  but
+            // not looked up
+            //
+            VarMacro => {
+                if args.len() != 1 {
+                    return Some(Arc::new(error_message::wrong_arg_count(
+                        1,
+                        args.len() as usize,
+                    )));
+                }
+                match &*args.nth(0) {
+                    Value::Symbol(sym) => match environment.resolve_var(sym) {
+                        Some(var) => Some(Arc::new(Value::Var(Arc::new(var)))),
+                        None => Some(Arc::new(Value::Condition(
+                            format!("Unable to resolve var: {} in this context", sym).into(),
+                        ))),
+                    },
+                    arg => Some(Arc::new(error_message::type_mismatch(TypeTag::Symbol, arg))),
+                }
+            }
             IfMacro => {
                 if args.len() != 2 && args.len() != 3 {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: 2 or 3)",
-                        args.len()
-                    ))));
+                    return Some(Arc::new(Value::Condition(
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: 2 or 3)",
+                            args.len()
+                        )
+                        .into(),
+                    )));
+                }
+                let arg_refs = PersistentList::iter(args).collect::<Vec<Arc<Value>>>();
+                let condition = arg_refs.get(0).unwrap().eval(Arc::clone(environment));
+                // A test that failed doesn't get to pick a branch;  otherwise (while (f) ..)
+                // would go round forever once f started failing
+                if let Value::Condition(_) = condition {
+                    return Some(Arc::new(condition));
                 }
-                let arg_refs = PersistentList::iter(args).collect::<Vec<Rc<Value>>>();
-                let condition = arg_refs.get(0).unwrap().eval(Rc::clone(environment));
 
                 if condition.is_truthy() {
-                    Some(arg_refs.get(1).unwrap().eval_to_rc(Rc::clone(environment)))
+                    Some(arg_refs.get(1).unwrap().eval_to_rc(Arc::clone(environment)))
                 } else {
                     Some(
                         arg_refs
                             .get(2)
-                            .unwrap_or(&Rc::new(Value::Nil))
-                            .eval_to_rc(Rc::clone(environment)),
+                            .unwrap_or(&Arc::new(Value::Nil))
+                            .eval_to_rc(Arc::clone(environment)),
                     )
                 }
             }
             //
+            // (try expr1 expr2 (catch Exception e handler1 handler2) (finally cleanup1 cleanup2))
+            //
+            // Should the body throw a condition,  the first catch clause that catches it (see
+            // condition::catches) is evaluated in its place,  with e bound to it;  if none
+            // does,  it keeps going out.  The finally forms always run, even when the body was
+            // interrupted;  they are evaluated with interrupts masked, so an interrupted
+            // evaluation still gets to close what it opened before the interruption propagates
+            //
+            TryMacro => {
+                let arg_refs = PersistentList::iter(args).collect::<Vec<Arc<Value>>>();
+                let (clauses, finally) = match arg_refs.split_last() {
+                    Some((last, clauses)) if Value::is_finally_clause(last) => {
+                        (clauses, Some(last))
+                    }
+                    _ => (&arg_refs[..], None),
+                };
+                let body_len = clauses
+                    .iter()
+                    .position(|clause| clause.is_catch_clause())
+                    .unwrap_or(clauses.len());
+                let (body, catches) = clauses.split_at(body_len);
+
+                let mut result = eval_body(body, environment);
+                if let Value::Condition(message) = &*result {
+                    let message = SharedStr::clone(message);
+                    for clause in catches {
+                        let clause = match &**clause {
+                            Value::PersistentList(clause) => {
+                                PersistentList::iter(clause).collect::<Vec<Arc<Value>>>()
+                            }
+                            _ => continue,
+                        };
+                        let (selector, sym, handler) = match catch_clause(&clause) {
+                            Some(parts) => parts,
+                            None => {
+                                return Some(Arc::new(Value::Condition(
+                                    "catch takes what to catch and a symbol to bind it to, as in (catch Exception e ..)".into(),
+                                )))
+                            }
+                        };
+                        match condition::catches(selector, &result) {
+                            Ok(false) => continue,
+                            Ok(true) => {
+                                let local_environment = Arc::new(
+                                    Environment::new_local_environment(Arc::clone(environment)),
+                                );
+                                local_environment.insert(
+                                    Symbol::clone(sym),
+                                    Arc::new(Value::Exception(SharedStr::clone(&message))),
+                                );
+                                result = eval_body(handler, &local_environment);
+                            }
+                            Err(condition) => result = Arc::new(condition),
+                        }
+                        break;
+                    }
+                }
+                if let Some(Value::PersistentList(finally)) = finally.map(|clause| &**clause) {
+                    let cleanup = interrupt::uninterruptibly(|| {
+                        eval_body(
+                            &PersistentList::iter(finally)
+                                .skip(1)
+                                .collect::<Vec<Arc<Value>>>(),
+                            environment,
+                        )
+                    });
+                    // A condition thrown while cleaning up takes the place of the result
+                    if let Value::Condition(_) = &*cleanup {
+                        result = cleanup;
+                    }
+                }
+                Some(result)
+            }
+            //
             // If we're not a valid IFn
             //
             _ => None,
         }
     }
+    /// Applies this,  what the head of call evaluated to,  to call's args;  or gives the
+    /// condition that it can't be,  if it's not function-like
+    pub(crate) fn apply_to_call(
+        self: &Arc<Value>,
+        environment: &Arc<Environment>,
+        call: &Arc<PersistentList>,
+    ) -> Arc<Value> {
+        let args = match &**call {
+            Cons(_, args, _) => args,
+            PersistentList::Empty => return Arc::clone(self),
+        };
+        let try_apply_ifn = self.apply_to_persistent_list(environment, args, call);
+
+        // Right now we're using the normal error message, however maybe later we will try
+        //
+        // You tried to call value of type {} like a function, but only types of the
+        // interface clojure.lang.IFn can be called this way
+        //
+        // Sounds less correct but also seems clearer; the current error message relies on
+        // you pretty much already knowing when this error message is called
+        try_apply_ifn.unwrap_or_else(|| {
+            Arc::new(Value::Condition(
+                format!(
+                    "Execution Error: {} cannot be cast to clojure.lang.IFn",
+                    self.type_tag()
+                )
+                .into(),
+            ))
+        })
+    }
     ////////////////////////////////////////////////////////////////////////////////////////////////////
     // Eval Helper
     ////////////////////////////////////////////////////////////////////////////////////////////////////
-    fn is_truthy(&self) -> bool {
-        if let Value::Nil = self {
-            return false;
+    pub fn is_finally_clause(&self) -> bool {
+        self.is_clause("finally")
+    }
+    pub fn is_catch_clause(&self) -> bool {
+        self.is_clause("catch")
+    }
+    // Whether this is a list headed by the symbol name,  as (finally ..) is
+    fn is_clause(&self, name: &str) -> bool {
+        match self {
+            Value::PersistentList(plist) => match &**plist {
+                Cons(head, _, _) => **head == Symbol::intern(name).to_value(),
+                PersistentList::Empty => false,
+            },
+            _ => false,
+        }
+    }
+    /// As in Clojure,  nil and false are falsey,  and everything else is truthy
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Nil | Value::Boolean(false))
+    }
+    /// Whether this is a list, vector, queue or seq;  the collections = compares element by
+    /// element
+    pub fn is_sequential(&self) -> bool {
+        matches!(
+            self,
+            Value::PersistentList(_)
+                | Value::PersistentVector(_)
+                | Value::PersistentQueue(_)
+                | Value::LazySeq(_)
+        )
+    }
+    pub fn is_map(&self) -> bool {
+        matches!(
+            self,
+            Value::PersistentListMap(_) | Value::PersistentTreeMap(_)
+        )
+    }
+    pub fn is_set(&self) -> bool {
+        matches!(
+            self,
+            Value::PersistentHashSet(_) | Value::PersistentTreeSet(_)
+        )
+    }
+    // The elements of a list, vector, queue or seq,  in order
+    fn sequential(&self) -> Box<dyn Iterator<Item = Arc<Value>> + '_> {
+        match self {
+            Value::PersistentList(plist) => Box::new(PersistentList::iter(plist)),
+            Value::PersistentVector(pvector) => Box::new(pvector.iter().cloned()),
+            Value::PersistentQueue(pqueue) => Box::new(pqueue.iter()),
+            Value::LazySeq(lseq) => Box::new(lseq.iter()),
+            _ => Box::new(std::iter::empty()),
         }
-        true
     }
 }
 pub trait ToValue {
     fn to_value(&self) -> Value;
-    fn to_rc_value(&self) -> Rc<Value> {
-        Rc::new(self.to_value())
+    fn to_rc_value(&self) -> Arc<Value> {
+        Arc::new(self.to_value())
     }
 }
 impl ToValue for Value {
@@ -548,7 +1192,7 @@ impl ToValue for Value {
         self.clone()
     }
 }
-impl ToValue for Rc<Value> {
+impl ToValue for Arc<Value> {
     fn to_value(&self) -> Value {
         (**self).clone()
     }
@@ -563,6 +1207,11 @@ impl ToValue for f64 {
         Value::F64(*self)
     }
 }
+impl ToValue for char {
+    fn to_value(&self) -> Value {
+        Value::Char(*self)
+    }
+}
 impl ToValue for bool {
     fn to_value(&self) -> Value {
         Value::Boolean(*self)
@@ -570,42 +1219,118 @@ impl ToValue for bool {
 }
 impl ToValue for std::string::String {
     fn to_value(&self) -> Value {
-        Value::String(self.clone())
+        Value::String(self.clone().into())
     }
 }
 impl ToValue for str {
     fn to_value(&self) -> Value {
-        Value::String(std::string::String::from(self))
+        Value::String(self.into())
     }
 }
 impl ToValue for Symbol {
     fn to_value(&self) -> Value {
-        Value::Symbol(self.clone())
+        Value::Symbol(Arc::new(self.clone()))
     }
 }
 impl ToValue for Keyword {
     fn to_value(&self) -> Value {
-        Value::Keyword(self.clone())
+        Value::Keyword(Arc::new(self.clone()))
     }
 }
-impl ToValue for Rc<dyn IFn> {
+impl ToValue for Arc<dyn IFn> {
     fn to_value(&self) -> Value {
-        Value::IFn(Rc::clone(self))
+        Value::IFn(Arc::new(Arc::clone(self)))
     }
 }
 impl ToValue for PersistentList {
     fn to_value(&self) -> Value {
-        Value::PersistentList(self.clone())
+        Value::PersistentList(Arc::new(self.clone()))
     }
 }
 impl ToValue for PersistentVector {
     fn to_value(&self) -> Value {
-        Value::PersistentVector(self.clone())
+        Value::PersistentVector(Arc::new(self.clone()))
     }
 }
 impl ToValue for PersistentListMap {
     fn to_value(&self) -> Value {
-        Value::PersistentListMap(self.clone())
+        Value::PersistentListMap(Arc::new(self.clone()))
+    }
+}
+
+// Rust values as Clojure ones;  for whatever embeds us,  to give scripts its data (see
+// Repl::register_value) or return from its fns
+impl From<i32> for Value {
+    fn from(i: i32) -> Value {
+        Value::I32(i)
+    }
+}
+impl From<i64> for Value {
+    fn from(i: i64) -> Value {
+        numbers::integer(num_bigint::BigInt::from(i))
+    }
+}
+impl From<usize> for Value {
+    fn from(i: usize) -> Value {
+        numbers::integer(num_bigint::BigInt::from(i))
+    }
+}
+impl From<f64> for Value {
+    fn from(f: f64) -> Value {
+        Value::F64(f)
+    }
+}
+impl From<bool> for Value {
+    fn from(b: bool) -> Value {
+        Value::Boolean(b)
+    }
+}
+impl From<char> for Value {
+    fn from(c: char) -> Value {
+        Value::Char(c)
+    }
+}
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::String(s.into())
+    }
+}
+impl From<std::string::String> for Value {
+    fn from(s: std::string::String) -> Value {
+        Value::String(s.into())
+    }
+}
+impl From<()> for Value {
+    fn from(_: ()) -> Value {
+        Value::Nil
+    }
+}
+/// None is nil
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(val: Option<T>) -> Value {
+        val.map_or(Value::Nil, Into::into)
+    }
+}
+/// A vector
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(vals: Vec<T>) -> Value {
+        vals.into_iter()
+            .map(|val| Arc::new(val.into()))
+            .collect::<PersistentVector>()
+            .to_value()
+    }
+}
+/// A map;  in no particular order,  as a HashMap's in none
+impl<K: Into<Value>, V: Into<Value>> From<HashMap<K, V>> for Value {
+    fn from(entries: HashMap<K, V>) -> Value {
+        entries
+            .into_iter()
+            .map(|(key, val)| MapEntry {
+                key: Arc::new(key.into()),
+                val: Arc::new(val.into()),
+            })
+            .collect::<PersistentListMap>()
+            .to_value()
     }
 }
 
@@ -616,85 +1341,285 @@ impl ToValue for PersistentListMap {
 /// this mostly acts as a shortcut for evaluating outside types not yet converted into a Value,
 /// so you can write something like "1.eval(env)" instead of "1.to_value().eval(env)"
 pub trait Evaluable {
-    /// Evaluates a value and returns a Rc pointer to the evaluated ClojureRS Value
+    /// Evaluates a value and returns a Arc pointer to the evaluated ClojureRS Value
     /// The program primarily
-    fn eval_to_rc(&self, environment: Rc<Environment>) -> Rc<Value>;
+    fn eval_to_rc(&self, environment: Arc<Environment>) -> Arc<Value>;
     /// Evaluates a value and returns a new ClojureRS Value altogether, by cloning what
     /// eval_to_rc points to
-    fn eval(&self, environment: Rc<Environment>) -> Value {
+    fn eval(&self, environment: Arc<Environment>) -> Value {
         self.eval_to_rc(environment).to_value()
     }
 }
 
-impl Evaluable for Rc<Value> {
-    fn eval_to_rc(&self, environment: Rc<Environment>) -> Rc<Value> {
+/// form evaluated;  or Err,  if what it gives is a condition,  which is thrown to whatever
+/// was evaluating it
+pub fn eval_or_throw(
+    form: &Arc<Value>,
+    environment: &Arc<Environment>,
+) -> Result<Arc<Value>, Arc<Value>> {
+    let evaled = form.eval_to_rc(Arc::clone(environment));
+    match &*evaled {
+        Value::Condition(_) => Err(evaled),
+        _ => Ok(evaled),
+    }
+}
+
+// What to catch,  the symbol to bind it to,  and the handler's forms
+type CatchClause<'a> = (&'a Arc<Value>, &'a Symbol, &'a [Arc<Value>]);
+
+// (catch selector sym handler..) as its parts
+fn catch_clause(clause: &[Arc<Value>]) -> Option<CatchClause<'_>> {
+    match clause {
+        [_, selector, sym, handler @ ..] => match &**sym {
+            Value::Symbol(sym) => Some((selector, sym, handler)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// forms evaluated in turn,  for the last one's value;  or the first condition one throws
+fn eval_body(forms: &[Arc<Value>], environment: &Arc<Environment>) -> Arc<Value> {
+    let mut result = Arc::new(Value::Nil);
+    for form in forms {
+        result = match eval_or_throw(form, environment) {
+            Ok(val) => val,
+            Err(condition) => return condition,
+        };
+    }
+    result
+}
+
+// defval as (def sym defval) defines it;  a fn that isn't yet named,  named for the var,
+// as it then prints.  Kept out of eval,  whose every frame would otherwise be the bigger
+#[inline(never)]
+fn named_for(defval: Arc<Value>, sym: &Symbol, environment: &Arc<Environment>) -> Arc<Value> {
+    let ifn = match &*defval {
+        Value::IFn(ifn) if ifn.name().is_none() => ifn,
+        _ => return defval,
+    };
+    let name = match sym.has_ns() {
+        true => Symbol::clone(sym),
+        false => Symbol::intern_with_ns(&environment.get_current_namespace_name(), &sym.name),
+    };
+    match ifn.named(name) {
+        Some(named) => Arc::new(Value::IFn(Arc::new(named))),
+        None => defval,
+    }
+}
+
+// A collection literal form evaluated to evaled;  which gets form's metadata,  evaluated in
+// turn,  if it has any.  So ^{:a 1} [x] evaluates to [(eval x)],  with metadata {:a 1}
+fn with_evaled_meta(
+    form: &Arc<Value>,
+    evaled: Arc<Value>,
+    environment: Arc<Environment>,
+) -> Arc<Value> {
+    allocations::made(&evaled);
+    match meta::meta(form) {
+        Some(meta) => match meta::with_meta(&evaled, &meta.eval_to_rc(environment)) {
+            Ok(evaled) => Arc::new(evaled),
+            Err(condition) => Arc::new(condition),
+        },
+        None => evaled,
+    }
+}
+
+// {a b} => {(eval a) (eval b)};  apart from eval_to_rc,  so its iterator isn't kept in
+// the frame of every call evaluated
+fn eval_map_entries(
+    plistmap: &PersistentListMap,
+    environment: &Arc<Environment>,
+) -> Result<PersistentListMap, Arc<Value>> {
+    plistmap
+        .iter()
+        .map(|map_entry| {
+            Ok(MapEntry {
+                key: eval_or_throw(&map_entry.key, environment)?,
+                val: eval_or_throw(&map_entry.val, environment)?,
+            })
+        })
+        .collect()
+}
+
+impl Evaluable for Arc<Value> {
+    fn eval_to_rc(&self, environment: Arc<Environment>) -> Arc<Value> {
         match &**self {
             // Evaluating a symbol means grabbing the value its been bound to in our environment
             Value::Symbol(symbol) => environment.get(symbol),
             // Evaluating a vector [a b c] just means [(eval a) (eval b) (eval c)]
             Value::PersistentVector(pvector) => {
-                // Evaluate each Rc<Value> our PersistentVector wraps
+                // Evaluate each Arc<Value> our PersistentVector wraps
                 // and return a new PersistentVector wrapping the new evaluated Values
-                let evaled_vals = pvector
-                    .vals
+                let evaled_vals = match pvector
                     .iter()
-                    .map(|rc_val| rc_val.eval_to_rc(Rc::clone(&environment)))
-                    .collect::<PersistentVector>();
-                Rc::new(Value::PersistentVector(evaled_vals))
+                    .map(|rc_val| eval_or_throw(rc_val, &environment))
+                    .collect::<Result<PersistentVector, Arc<Value>>>()
+                {
+                    Ok(evaled_vals) => evaled_vals,
+                    Err(condition) => return condition,
+                };
+                with_evaled_meta(self, evaled_vals.to_rc_value(), environment)
             }
             Value::PersistentListMap(plistmap) => {
-                // Evaluate each Rc<Value> our PersistentVector wraps
-                // and return a new PersistentVector wrapping the new evaluated Values
-                let evaled_vals = plistmap
+                let evaled_vals = match eval_map_entries(plistmap, &environment) {
+                    Ok(evaled_vals) => evaled_vals,
+                    Err(condition) => return condition,
+                };
+                with_evaled_meta(self, evaled_vals.to_rc_value(), environment)
+            }
+            // #{a b c} => #{(eval a) (eval b) (eval c)}
+            Value::PersistentHashSet(pset) => {
+                let evaled_vals = match pset
                     .iter()
-                    .map(|map_entry| MapEntry {
-                        key: map_entry.key.eval_to_rc(Rc::clone(&environment)),
-                        val: map_entry.val.eval_to_rc(Rc::clone(&environment)),
-                    })
-                    .collect::<PersistentListMap>();
-                Rc::new(Value::PersistentListMap(evaled_vals))
+                    .map(|rc_val| eval_or_throw(&rc_val, &environment))
+                    .collect::<Result<PersistentHashSet, Arc<Value>>>()
+                {
+                    Ok(evaled_vals) => evaled_vals,
+                    Err(condition) => return condition,
+                };
+                with_evaled_meta(self, evaled_vals.to_rc_value(), environment)
             }
             // Evaluating a list (a b c) means calling a as a function or macro on arguments b and c
-            Value::PersistentList(plist) => match plist {
+            Value::PersistentList(plist) => match &**plist {
                 Cons(head, tail, __count) => {
+                    // Checked once per call, so that even a runaway loop can be stopped
+                    if interrupt::is_interrupted() {
+                        return Arc::new(Value::Condition(interrupt::INTERRUPTED_MSG.into()));
+                    }
                     // First we have to evaluate the head of our list and make sure it is function-like
                     // and can be invoked on our arguments
                     // (ie, a fn, a macro, a keyword ..)
                     // @TODO remove clone if possible
-                    let ifn = Rc::clone(head).eval_to_rc(Rc::clone(&environment));
-
-                    let try_apply_ifn =
-                        ifn.apply_to_persistent_list(&Rc::clone(&environment), tail);
-
-                    // Right now we're using the normal error message, however maybe later we will try
-                    //
-                    // You tried to call value of type {} like a function, but only types of the
-                    // interface clojure.lang.IFn can be called this way
-                    //
-                    // Sounds less correct but also seems clearer; the current error message relies on
-                    // you pretty much already knowing when this error message is called
-                    try_apply_ifn.unwrap_or_else(|| {
-                        Rc::new(Value::Condition(format!(
-                            "Execution Error: {} cannot be cast to clojure.lang.IFn",
-                            ifn.type_tag()
-                        )))
-                    })
+                    // (.method obj ..) and (.-field obj) aren't calls of what they name
+                    if let Value::Symbol(sym) = &**head {
+                        if let Some(val) = host_object::eval_member(sym, tail, &environment) {
+                            return Arc::new(val);
+                        }
+                    }
+                    let ifn = Arc::clone(head).eval_to_rc(Arc::clone(&environment));
+                    // Thrown while finding what to call,  as by ((throw e) 1);  given as it
+                    // is,  as an argument's would be
+                    if let Value::Condition(_) = &*ifn {
+                        return ifn;
+                    }
+                    ifn.apply_to_call(&environment, plist)
                 }
                 // () evals to ()
-                PersistentList::Empty => Rc::new(Value::PersistentList(PersistentList::Empty)),
+                PersistentList::Empty => Arc::clone(self),
             },
+            // A seq evaluates like the list it would print as;  so code built by a macro
+            // with concat and friends runs as expected
+            Value::LazySeq(lseq) => lseq
+                .iter()
+                .collect::<PersistentList>()
+                .eval_to_rc(environment),
             // Other types eval to self; (5 => 5,  "cat" => "cat",  #function[+] => #function[+]
-            _ => Rc::clone(&self),
+            _ => Arc::clone(self),
         }
     }
 }
 impl Evaluable for PersistentList {
-    fn eval_to_rc(&self, environment: Rc<Environment>) -> Rc<Value> {
+    fn eval_to_rc(&self, environment: Arc<Environment>) -> Arc<Value> {
         self.to_rc_value().eval_to_rc(environment)
     }
 }
 impl Evaluable for Value {
-    fn eval_to_rc(&self, environment: Rc<Environment>) -> Rc<Value> {
+    fn eval_to_rc(&self, environment: Arc<Environment>) -> Arc<Value> {
         self.to_rc_value().eval_to_rc(environment)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+    use std::mem::size_of;
+
+    fn eval_all(sources: &[&str]) -> Value {
+        Repl::default().eval_all(sources)
+    }
+
+    // Guards the layout note on Value; if this grows, box the new variant rather
+    // than bumping the number
+    #[test]
+    fn value_is_two_words() {
+        assert_eq!(size_of::<Value>(), 2 * size_of::<usize>());
+    }
+
+    #[test]
+    fn collections_are_equal_across_kinds() {
+        let result = eval_all(&["[(= [1 2] '(1 2) (map inc [0 1]))
+              (= {:a 1 :b 2} (assoc {:b 2 :a 0} :a 1) (sorted-map :b 2 :a 1))
+              (= #{1 [2]} #{'(2) 1}) (= (sorted-set 2 1) #{1 2})
+              (= [] {}) (= #{} {}) (= 1 1.0) (= 1 1N)]"]);
+        assert_eq!(
+            "[true true true true false false false true]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn equal_values_find_each_other_as_keys() {
+        let result = eval_all(&[
+            "(def f (fn [] 1))",
+            "[(get {[1 2] :x} '(1 2)) (contains? #{{:a 1}} (sorted-map :a 1)) (contains? #{f} f)]",
+        ]);
+        assert_eq!("[:x true true]", result.to_string());
+    }
+
+    #[test]
+    fn loop_goes_round_until_no_recur() {
+        assert_eq!(
+            "[0 1 2 3 4]",
+            eval_all(&["(loop [i 0 acc []] (if (< i 5) (recur (+ i 1) (conj acc i)) acc))"])
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn each_time_round_has_its_own_bindings() {
+        let result = eval_all(&[
+            "(def t (transient []))",
+            "(dotimes [i 3] (conj! t (fn [] i)))",
+            "(map (fn [f] (f)) (persistent! t))",
+        ]);
+        assert_eq!("(0 1 2)", result.to_string());
+    }
+
+    #[test]
+    fn recur_in_a_fn_does_not_grow_the_stack() {
+        let result = eval_all(&[
+            "(def countdown (fn [n] (if (= n 0) :done (recur (- n 1)))))",
+            "(countdown 100000)",
+        ]);
+        assert_eq!(":done", result.to_string());
+    }
+
+    #[test]
+    fn recur_takes_one_value_per_binding() {
+        assert!(matches!(
+            eval_all(&["(loop [i 0] (if (< i 3) (recur)))"]),
+            Value::Condition(_)
+        ));
+    }
+
+    #[test]
+    fn a_failing_test_stops_while() {
+        assert!(matches!(
+            eval_all(&["(while (< 1 \"x\") 1)"]),
+            Value::Condition(_)
+        ));
+    }
+
+    #[test]
+    fn what_finding_the_fn_to_call_throws_is_what_the_call_throws() {
+        let result = eval_all(&[
+            "[(ex-message (try ((throw (ex-info \"boom\" {})) 1) (catch Exception e e))) (try (no-such-fn 1) (catch Exception e (ex-message e)))]",
+        ]);
+        assert_eq!(
+            "[\"boom\" \"Undefined symbol no-such-fn\"]",
+            result.to_string()
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Vars;  what def makes,  what (var x) and #'x give,  and what resolve finds
+//!
+//! A var is a name in a namespace.  What it holds is kept in the namespace,  so a var
+//! always has what its name is defined as now,  or bound to;  calling one calls that,
+//! whatever it's been redefined as since
+use crate::keyword::Keyword;
+use crate::namespace::Namespace;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::symbol::Symbol;
+use crate::value::{ToValue, Value};
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct Var {
+    pub namespace: Arc<Namespace>,
+    // Unqualified
+    pub sym: Symbol,
+}
+impl Var {
+    pub fn new(namespace: Arc<Namespace>, sym: &Symbol) -> Var {
+        Var {
+            namespace,
+            sym: sym.unqualified(),
+        }
+    }
+    /// ns/name
+    pub fn qualified(&self) -> Symbol {
+        Symbol::intern_with_ns(&self.namespace.name.name, &self.sym.name)
+    }
+    /// What the var holds;  its binding,  if it's bound
+    pub fn deref(&self) -> Arc<Value> {
+        self.namespace.get(&self.sym)
+    }
+    /// What the var holds outside of any binding
+    pub fn root(&self) -> Arc<Value> {
+        self.namespace.root(&self.sym)
+    }
+    /// Gives the var val outside of any binding;  keeping its metadata
+    pub fn set_root(&self, val: Arc<Value>) {
+        self.namespace.set_root(&self.sym, val)
+    }
+    pub fn is_dynamic(&self) -> bool {
+        self.namespace.is_dynamic(&self.sym)
+    }
+    /// The metadata the var was defined with,  and its :ns and :name;  and the :file and
+    /// :line it was defined at,  if we know them
+    pub fn meta(&self) -> Value {
+        let mut meta = match self.namespace.meta(&self.sym).as_deref() {
+            Some(Value::PersistentListMap(meta)) => (**meta).clone(),
+            _ => PersistentListMap::Empty,
+        };
+        let mut assoc = |key: &str, val: Value| {
+            meta = meta.assoc(Keyword::intern(key).to_rc_value(), val.to_rc_value());
+        };
+        assoc("ns", Value::Namespace(Arc::clone(&self.namespace)));
+        assoc("name", self.sym.to_value());
+        if let Some(location) = self.namespace.location(&self.sym) {
+            assoc("file", Value::String(location.file.into()));
+            assoc("line", Value::I32(location.line as i32));
+        }
+        meta.to_value()
+    }
+}
+impl PartialEq for Var {
+    fn eq(&self, other: &Var) -> bool {
+        Arc::ptr_eq(&self.namespace, &other.namespace) && self.sym == other.sym
+    }
+}
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#'{}", self.qualified())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+
+    #[test]
+    fn a_var_calls_what_its_name_is_defined_as_now() {
+        let result = Repl::default().eval_all(&[
+            "(def v (defn twice [x] (* 2 x)))",
+            "(def f #'twice)",
+            "(defn twice [x] (* 3 x))",
+            "[v (var twice) (= v #'twice) (f 2) (@f 2) (var? f) (var? twice) (var-get #'v)]",
+        ]);
+        assert_eq!(
+            "[#'user/twice #'user/twice true 6 6 true false #'user/twice]",
+            result.to_string()
+        );
+    }
+
+    #[test]
+    fn vars_are_resolved_altered_and_carry_metadata() {
+        let result = Repl::default().eval_all(&[
+            "(def ^:dynamic *n* 1)",
+            "(def counter \"How many\" 1)",
+            "(def altered (alter-var-root #'counter + 10))",
+            "(def m (meta #'counter))",
+            "[(resolve (quote counter)) (resolve (quote nope)) (resolve (quote map)) (ns-resolve (quote clojure.core) (quote inc)) altered counter (:doc m) (:name m) (ns-name (:ns m)) (:dynamic (meta #'*n*))]",
+        ]);
+        assert_eq!(
+            "[#'user/counter nil #'clojure.core/map #'clojure.core/inc 11 11 \"How many\" counter user true]",
+            result.to_string()
+        );
+    }
+}
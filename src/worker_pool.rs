@@ -0,0 +1,86 @@
+//! A pool of worker threads,  one per core,  that pmap hands its work to
+//!
+//! Work still waiting for a worker when its result is wanted is run right there,  by
+//! whoever wants it;  so work that waits on other work (a pmap inside a pmap's fn) can't
+//! leave every worker waiting on work none of them will get to
+use crate::future::Conveyed;
+use crate::ideref::IBlockingDeref;
+use crate::interrupt;
+use crate::promise::Promise;
+use crate::value::Value;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+// As much as a future gets
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+type Job = Box<dyn FnOnce() -> Value + Send>;
+
+/// Work handed to the pool,  and what it gives once it's done
+pub struct Task {
+    job: Mutex<Option<(Conveyed, Job)>>,
+    result: Promise,
+}
+impl Task {
+    // Runs the job,  unless something's already taken it to run
+    fn run(&self, on_worker: bool) {
+        let taken = self.job.lock().unwrap().take();
+        if let Some((conveyed, job)) = taken {
+            // Whoever runs it off the pool is the one that made it,  or is at least
+            // realizing the seq it was made for;  what it has in place will do
+            let result = if on_worker { conveyed.run(job) } else { job() };
+            self.result.deliver(Arc::new(result));
+        }
+    }
+    /// What the job gave;  waiting for it if a worker's still running it
+    pub fn result(&self) -> Value {
+        self.run(false);
+        match self.result.deref_blocking(None) {
+            Some(val) => (*val).clone(),
+            None => Value::Condition(interrupt::INTERRUPTED_MSG.into()),
+        }
+    }
+}
+
+/// How many workers there are;  as many as there are cores
+pub fn size() -> usize {
+    thread::available_parallelism().map_or(1, |cores| cores.get())
+}
+
+/// Queues job to be run by the next free worker,  with what's in place here now
+pub fn submit(job: impl FnOnce() -> Value + Send + 'static) -> Arc<Task> {
+    let task = Arc::new(Task {
+        job: Mutex::new(Some((Conveyed::capture(), Box::new(job)))),
+        result: Promise::new(),
+    });
+    // If no worker could be started,  whoever wants the result runs it themselves
+    let _ = queue().lock().unwrap().send(Arc::clone(&task));
+    task
+}
+
+fn queue() -> &'static Mutex<Sender<Arc<Task>>> {
+    static QUEUE: OnceLock<Mutex<Sender<Arc<Task>>>> = OnceLock::new();
+    QUEUE.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..size() {
+            let receiver = Arc::clone(&receiver);
+            let _ = thread::Builder::new()
+                .name(String::from("worker"))
+                .stack_size(STACK_SIZE)
+                .spawn(move || work(&receiver));
+        }
+        Mutex::new(sender)
+    })
+}
+
+fn work(receiver: &Mutex<Receiver<Arc<Task>>>) {
+    loop {
+        let task = match receiver.lock().unwrap().recv() {
+            Ok(task) => task,
+            Err(_) => return,
+        };
+        task.run(true);
+    }
+}
@@ -0,0 +1,125 @@
+//! Embedding ClojureRS,  as a Rust program depending on the library would;  only through
+//! what it exports
+use rust_clojure::host_object::{HostObject, HostType};
+use rust_clojure::repl::Repl;
+use rust_clojure::value::{ToValue, Value};
+use rust_clojure::{host_trait, keyword_enum};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+keyword_enum! {
+    pub enum Status {
+        Ok,
+        NotFound,
+    }
+}
+
+host_trait! {
+    pub trait Greeter {
+        fn greet(&self, name: Arc<Value>) -> Value;
+    }
+}
+
+#[derive(Debug)]
+struct Counter {
+    count: i32,
+}
+impl HostObject for Counter {
+    fn type_name(&self) -> &str {
+        "my.app.Counter"
+    }
+    fn call(&self, method: &str, args: Vec<Arc<Value>>) -> Option<Value> {
+        match (method, &args[..]) {
+            ("plus", [n]) => match **n {
+                Value::I32(n) => Some(Value::I32(self.count + n)),
+                _ => Some(Value::Condition("Not an int".into())),
+            },
+            _ => None,
+        }
+    }
+}
+
+fn eval(repl: &Repl, code: &str) -> String {
+    repl.eval_with_bindings(code, &[]).to_string()
+}
+
+#[test]
+fn scripts_use_the_fns_values_and_types_registered_with_them() {
+    let repl = Repl::default();
+    repl.register_fn("my.app/lookup", |args| match args[0].to_string().as_str() {
+        "home" => Status::Ok.into(),
+        _ => Status::NotFound.into(),
+    });
+    repl.register_fn("my.app/found?", |args| match Status::try_from(&*args[0]) {
+        Ok(status) => (status == Status::Ok).into(),
+        Err(condition) => condition,
+    });
+    repl.register_value("port", 7888_i64);
+    repl.register_type(
+        HostType::new("my.app.Counter").static_fn("of", |args| match *args[0] {
+            Value::I32(count) => Value::host_object(Counter { count }),
+            _ => Value::Condition("Not an int".into()),
+        }),
+    );
+    assert_eq!(
+        "[:ok :not-found false 7888 5]",
+        eval(
+            &repl,
+            "[(my.app/lookup \"home\") (my.app/lookup \"away\") (my.app/found? :not-found) \
+             port (.plus (my.app.Counter/of 2) 3)]"
+        )
+    );
+}
+
+#[test]
+fn host_traits_are_implemented_from_clojure() {
+    let repl = Repl::default();
+    let protocol = repl.register_trait::<dyn Greeter>();
+    let greeter = repl.eval_with_bindings(
+        "(reify Greeter (greet [_ name] (str \"Hello, \" name)))",
+        &[],
+    );
+    let greeter = host_trait::implement::<dyn Greeter>(&protocol, greeter.to_rc_value()).unwrap();
+    assert_eq!(
+        "Hello, Rust",
+        greeter.greet("Rust".to_rc_value()).to_string()
+    );
+}
+
+#[test]
+fn hosts_evaluate_their_own_forms_against_their_own_values() {
+    let repl = Repl::default();
+    let form = repl.eval_with_bindings("'(+ 1 2)", &[]);
+    assert_eq!("3", repl.eval_form(&form).to_string());
+    let bindings = [
+        ("names", Value::from(vec!["a", "b"])),
+        ("x", Value::from(1_i64)),
+    ];
+    assert_eq!(
+        "[\"a\" \"b\" 3]",
+        repl.eval_with_bindings("(conj names (+ x (count names)))", &bindings)
+            .to_string()
+    );
+    // The bindings are gone once it's done
+    assert!(matches!(
+        repl.eval_with_bindings("x", &[]),
+        Value::Condition(_)
+    ));
+}
+
+#[test]
+fn what_a_fork_defines_is_only_kept_if_merged() {
+    let repl = Repl::default();
+    let dropped = repl.fork();
+    dropped.register_value("x", 1_i64);
+    drop(dropped);
+    assert!(matches!(
+        repl.eval_with_bindings("x", &[]),
+        Value::Condition(_)
+    ));
+    let merged = repl.fork();
+    merged.register_value("x", 2_i64);
+    assert_eq!("2", eval(&merged, "x"));
+    repl.merge(merged);
+    assert_eq!("2", eval(&repl, "x"));
+}